@@ -0,0 +1,185 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Continent {
+    Africa,
+    Antarctica,
+    Asia,
+    Europe,
+    NorthAmerica,
+    Oceania,
+    SouthAmerica,
+}
+
+impl Continent {
+    pub const ALL: [Continent; 7] = [
+        Continent::Africa,
+        Continent::Antarctica,
+        Continent::Asia,
+        Continent::Europe,
+        Continent::NorthAmerica,
+        Continent::Oceania,
+        Continent::SouthAmerica,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Continent::Africa => "AF",
+            Continent::Antarctica => "AN",
+            Continent::Asia => "AS",
+            Continent::Europe => "EU",
+            Continent::NorthAmerica => "NA",
+            Continent::Oceania => "OC",
+            Continent::SouthAmerica => "SA",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Result<Continent, String> {
+        let code = code.to_ascii_uppercase();
+        Continent::ALL
+            .into_iter()
+            .find(|continent| continent.code() == code)
+            .ok_or_else(|| format!("'{}' is not a valid continent code", code))
+    }
+}
+
+impl Display for Continent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+// The reference tables below are what the code generator enriches the countries found in the
+// database with. Continents follow the GeoNames codes that the IP-location databases use.
+const AFRICA: &[&str] = &[
+    "AO", "BF", "BI", "BJ", "BW", "CD", "CF", "CG", "CI", "CM", "CV", "DJ", "DZ", "EG", "EH", "ER",
+    "ET", "GA", "GH", "GM", "GN", "GQ", "GW", "KE", "KM", "LR", "LS", "LY", "MA", "MG", "ML", "MR",
+    "MU", "MW", "MZ", "NA", "NE", "NG", "RE", "RW", "SC", "SD", "SH", "SL", "SN", "SO", "SS", "ST",
+    "SZ", "TD", "TG", "TN", "TZ", "UG", "YT", "ZA", "ZM", "ZW",
+];
+const ANTARCTICA: &[&str] = &["AQ", "BV", "GS", "HM", "TF"];
+const ASIA: &[&str] = &[
+    "AE", "AF", "AM", "AZ", "BD", "BH", "BN", "BT", "CC", "CN", "CX", "GE", "HK", "ID", "IL", "IN",
+    "IO", "IQ", "IR", "JO", "JP", "KG", "KH", "KP", "KR", "KW", "KZ", "LA", "LB", "LK", "MM", "MN",
+    "MO", "MV", "MY", "NP", "OM", "PH", "PK", "PS", "QA", "SA", "SG", "SY", "TH", "TJ", "TM", "TR",
+    "TW", "UZ", "VN", "YE",
+];
+const EUROPE: &[&str] = &[
+    "AD", "AL", "AT", "AX", "BA", "BE", "BG", "BY", "CH", "CY", "CZ", "DE", "DK", "EE", "ES", "FI",
+    "FO", "FR", "GB", "GG", "GI", "GR", "HR", "HU", "IE", "IM", "IS", "IT", "JE", "LI", "LT", "LU",
+    "LV", "MC", "MD", "ME", "MK", "MT", "NL", "NO", "PL", "PT", "RO", "RS", "RU", "SE", "SI", "SJ",
+    "SK", "SM", "UA", "VA", "XK",
+];
+const NORTH_AMERICA: &[&str] = &[
+    "AG", "AI", "AW", "BB", "BL", "BM", "BQ", "BS", "BZ", "CA", "CR", "CU", "CW", "DM", "DO", "GD",
+    "GL", "GP", "GT", "HN", "HT", "JM", "KN", "KY", "LC", "MF", "MQ", "MS", "MX", "NI", "PA", "PM",
+    "PR", "SV", "SX", "TC", "TT", "US", "VC", "VG", "VI",
+];
+const OCEANIA: &[&str] = &[
+    "AS", "AU", "CK", "FJ", "FM", "GU", "KI", "MH", "MP", "NC", "NF", "NR", "NU", "NZ", "PF", "PG",
+    "PN", "PW", "SB", "TK", "TL", "TO", "TV", "UM", "VU", "WF", "WS",
+];
+const SOUTH_AMERICA: &[&str] = &[
+    "AR", "BO", "BR", "CL", "CO", "EC", "FK", "GF", "GY", "PE", "PY", "SR", "UY", "VE",
+];
+const EU_MEMBERS: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+lazy_static! {
+    static ref CONTINENT_BY_ISO3166: HashMap<&'static str, Continent> = [
+        (Continent::Africa, AFRICA),
+        (Continent::Antarctica, ANTARCTICA),
+        (Continent::Asia, ASIA),
+        (Continent::Europe, EUROPE),
+        (Continent::NorthAmerica, NORTH_AMERICA),
+        (Continent::Oceania, OCEANIA),
+        (Continent::SouthAmerica, SOUTH_AMERICA),
+    ]
+    .into_iter()
+    .flat_map(|(continent, codes)| codes.iter().map(move |code| (*code, continent)))
+    .collect();
+}
+
+pub fn reference_continent(iso3166: &str) -> Option<Continent> {
+    CONTINENT_BY_ISO3166
+        .get(iso3166.to_ascii_uppercase().as_str())
+        .copied()
+}
+
+pub fn is_reference_eu_member(iso3166: &str) -> bool {
+    EU_MEMBERS.contains(&iso3166.to_ascii_uppercase().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dbip_country::COUNTRIES;
+
+    #[test]
+    fn continent_codes_round_trip() {
+        Continent::ALL.into_iter().for_each(|continent| {
+            assert_eq!(Continent::from_code(continent.code()), Ok(continent));
+            assert_eq!(continent.to_string(), continent.code());
+        });
+        assert_eq!(Continent::from_code("eu"), Ok(Continent::Europe));
+        assert_eq!(
+            Continent::from_code("XX"),
+            Err("'XX' is not a valid continent code".to_string())
+        );
+    }
+
+    #[test]
+    fn every_known_country_has_a_reference_continent() {
+        let homeless = COUNTRIES
+            .iter()
+            .filter(|country| country.iso3166 != "ZZ")
+            .filter(|country| reference_continent(&country.iso3166).is_none())
+            .map(|country| country.iso3166.clone())
+            .collect::<Vec<String>>();
+
+        assert_eq!(homeless, Vec::<String>::new());
+    }
+
+    #[test]
+    fn no_country_is_on_two_continents() {
+        let all_codes = [
+            AFRICA,
+            ANTARCTICA,
+            ASIA,
+            EUROPE,
+            NORTH_AMERICA,
+            OCEANIA,
+            SOUTH_AMERICA,
+        ]
+        .concat();
+
+        assert_eq!(all_codes.len(), CONTINENT_BY_ISO3166.len());
+    }
+
+    #[test]
+    fn reference_continents_are_found_regardless_of_case() {
+        assert_eq!(reference_continent("FR"), Some(Continent::Europe));
+        assert_eq!(reference_continent("br"), Some(Continent::SouthAmerica));
+        assert_eq!(reference_continent("AQ"), Some(Continent::Antarctica));
+        assert_eq!(reference_continent("ZZ"), None);
+    }
+
+    #[test]
+    fn the_eu_has_twenty_seven_members_all_in_europe() {
+        assert_eq!(EU_MEMBERS.len(), 27);
+        EU_MEMBERS.iter().for_each(|iso3166| {
+            assert!(is_reference_eu_member(iso3166));
+            assert_eq!(reference_continent(iso3166), Some(Continent::Europe));
+        });
+        assert!(is_reference_eu_member("de"));
+        assert!(!is_reference_eu_member("GB"));
+        assert!(!is_reference_eu_member("CH"));
+        assert!(!is_reference_eu_member("ZZ"));
+    }
+}
@@ -6,7 +6,7 @@ use crate::country_block_serde::semi_private_items::{
     DeserializerPrivate, Difference, IPIntoOctets, IPIntoSegments, PlusMinusOneIP,
 };
 use crate::country_block_stream::{CountryBlock, IpRange};
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::ops::{BitOrAssign, ShlAssign};
@@ -179,6 +179,16 @@ impl CountryBlockSerializer {
         }
     }
 
+    // Like add(), but refuses a block that would corrupt the serialized data rather than panicking
+    pub fn try_add(&mut self, country_block: CountryBlock) -> Result<(), String> {
+        match country_block.ip_range {
+            IpRange::V4(start, end) => self.ipv4.check_block(start, end)?,
+            IpRange::V6(start, end) => self.ipv6.check_block(start, end)?,
+        }
+        self.add(country_block);
+        Ok(())
+    }
+
     pub fn finish(mut self) -> (FinalBitQueue, FinalBitQueue) {
         let last_ipv4 = Ipv4Addr::new(0xFF, 0xFF, 0xFF, 0xFF);
         let last_ipv6 = Ipv6Addr::new(
@@ -280,6 +290,29 @@ where
     }
 }
 
+impl<IPType, SegmentNumRep, const SEGMENTS_COUNT: usize>
+    VersionedIPSerializer<IPType, SegmentNumRep, SEGMENTS_COUNT>
+where
+    IPType: Copy + Ord + Display + Debug,
+    SegmentNumRep: Debug,
+{
+    fn check_block(&self, start: IPType, end: IPType) -> Result<(), String> {
+        if end < start {
+            Err(format!(
+                "Ending address {} is less than starting address {}",
+                end, start
+            ))
+        } else if self.block_count > 0 && start <= self.prev_end.ip {
+            Err(format!(
+                "Block starting at {} is out of order: the block before it ends at {}",
+                start, self.prev_end.ip
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl<IPType, SegmentNumRep, const SEGMENTS_COUNT: usize>
     VersionedIPSerializer<IPType, SegmentNumRep, SEGMENTS_COUNT>
 where
@@ -853,6 +886,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_add_accepts_blocks_in_order() {
+        let mut subject = CountryBlockSerializer::new();
+
+        let results = ipv4_country_blocks()
+            .into_iter()
+            .chain(ipv6_country_blocks())
+            .map(|country_block| subject.try_add(country_block))
+            .collect::<Vec<Result<(), String>>>();
+
+        assert!(results.iter().all(|result| result.is_ok()));
+        let (final_ipv4, final_ipv6) = subject.finish();
+        let mut expected = CountryBlockSerializer::new();
+        ipv4_country_blocks()
+            .into_iter()
+            .chain(ipv6_country_blocks())
+            .for_each(|country_block| expected.add(country_block));
+        let (expected_ipv4, expected_ipv6) = expected.finish();
+        assert_eq!(final_ipv4.block_count, expected_ipv4.block_count);
+        assert_eq!(final_ipv6.block_count, expected_ipv6.block_count);
+    }
+
+    #[test]
+    fn try_add_refuses_overlapping_and_backwards_blocks_instead_of_panicking() {
+        let mut subject = CountryBlockSerializer::new();
+        let block = |start: &str, end: &str| CountryBlock {
+            ip_range: IpRange::V4(
+                Ipv4Addr::from_str(start).unwrap(),
+                Ipv4Addr::from_str(end).unwrap(),
+            ),
+            country: SENTINEL.clone(),
+        };
+        subject.try_add(block("1.2.3.4", "1.2.3.10")).unwrap();
+
+        let overlapping = subject.try_add(block("1.2.3.10", "1.2.3.20"));
+        let preceding = subject.try_add(block("1.2.3.4", "1.2.3.10"));
+        let backwards = subject.try_add(block("1.2.3.30", "1.2.3.20"));
+        let following = subject.try_add(block("1.2.3.11", "255.255.255.255"));
+        let beyond_the_end = subject.try_add(block("255.255.255.255", "255.255.255.255"));
+
+        assert_eq!(
+            overlapping,
+            Err(
+                "Block starting at 1.2.3.10 is out of order: the block before it ends at 1.2.3.10"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            preceding,
+            Err(
+                "Block starting at 1.2.3.4 is out of order: the block before it ends at 1.2.3.10"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            backwards,
+            Err("Ending address 1.2.3.20 is less than starting address 1.2.3.30".to_string())
+        );
+        assert_eq!(following, Ok(()));
+        assert_eq!(
+            beyond_the_end,
+            Err(
+                "Block starting at 255.255.255.255 is out of order: the block before it ends at \
+                 255.255.255.255"
+                    .to_string()
+            )
+        );
+        let (final_ipv4, _) = subject.finish();
+        assert_eq!(final_ipv4.block_count, 3);
+    }
+
     #[test]
     fn add_works_for_ipv4() {
         let mut country_blocks = ipv4_country_blocks();
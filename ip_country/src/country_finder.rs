@@ -2,7 +2,7 @@
 
 use crate::countries::Countries;
 use crate::country_block_serde::CountryBlockDeserializer;
-use crate::country_block_stream::{Country, CountryBlock};
+use crate::country_block_stream::{Country, CountryBlock, IpRange};
 use crate::dbip_country;
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -59,6 +59,28 @@ impl<'a> CountryCodeFinder<'a> {
         }
     }
 
+    // All the IPv4 ranges the data assigns to the country, followed by all its IPv6 ranges
+    pub fn ranges_of_country(
+        &'a self,
+        iso3166: &str,
+    ) -> Result<impl Iterator<Item = &'a IpRange> + 'a, String> {
+        let country = self.countries.country_from_code(iso3166)?;
+        Ok(self
+            .ipv4
+            .iter()
+            .chain(self.ipv6.iter())
+            .filter(move |block| block.country == *country)
+            .map(|block| &block.ip_range))
+    }
+
+    // Whether the data puts the address in the country; an invalid country code matches nothing
+    pub fn contains(&'a self, ip_addr: IpAddr, iso3166: &str) -> bool {
+        match self.find_country(ip_addr) {
+            Some(country) => country.iso3166.eq_ignore_ascii_case(iso3166),
+            None => false,
+        }
+    }
+
     pub fn ensure_init(&self) {
         //This should provoke lazy_static to perform the value initialization
     }
@@ -160,6 +182,94 @@ mod tests {
         assert_eq!(result, None)
     }
 
+    #[test]
+    fn ranges_of_country_yields_every_ipv4_and_then_every_ipv6_range_of_the_country() {
+        COUNTRY_CODE_FINDER.ensure_init();
+        let country = select_country(&COUNTRIES, 50);
+        let expected_ranges = COUNTRY_CODE_FINDER
+            .ipv4
+            .iter()
+            .chain(COUNTRY_CODE_FINDER.ipv6.iter())
+            .filter(|block| block.country == *country)
+            .map(|block| block.ip_range.clone())
+            .collect_vec();
+
+        let result = COUNTRY_CODE_FINDER
+            .ranges_of_country(&country.iso3166.to_ascii_lowercase())
+            .unwrap()
+            .cloned()
+            .collect_vec();
+
+        assert_eq!(result, expected_ranges);
+        assert!(result
+            .iter()
+            .all(|range| COUNTRY_CODE_FINDER.find_country(range.start()) == Some(country)));
+    }
+
+    #[test]
+    fn ranges_of_country_rejects_an_invalid_country_code() {
+        let result = COUNTRY_CODE_FINDER.ranges_of_country("Q1").map(|_| ());
+
+        assert_eq!(
+            result,
+            Err("'Q1' is not a valid ISO3166 country code".to_string())
+        );
+    }
+
+    #[test]
+    fn contains_recognizes_an_address_of_the_country() {
+        COUNTRY_CODE_FINDER.ensure_init();
+        let country = select_country(&COUNTRIES, 50);
+        let ip_range = COUNTRY_CODE_FINDER
+            .ranges_of_country(&country.iso3166)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let result = COUNTRY_CODE_FINDER.contains(ip_range.end(), &country.iso3166);
+
+        assert_eq!(result, true);
+    }
+
+    #[test]
+    fn contains_refuses_an_address_of_another_country() {
+        COUNTRY_CODE_FINDER.ensure_init();
+        let country = select_country(&COUNTRIES, 50);
+        let other_country = select_country(&COUNTRIES, 25);
+        let ip_range = COUNTRY_CODE_FINDER
+            .ranges_of_country(&country.iso3166)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let result = COUNTRY_CODE_FINDER.contains(ip_range.start(), &other_country.iso3166);
+
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn contains_refuses_an_address_in_zz_block_and_an_invalid_country_code() {
+        COUNTRY_CODE_FINDER.ensure_init();
+        let zz_ip_range = &COUNTRY_CODE_FINDER
+            .ipv4
+            .iter()
+            .find(|block| &block.country.iso3166 == "ZZ")
+            .unwrap()
+            .ip_range;
+        let country = select_country(&COUNTRIES, 50);
+        let ip_range = COUNTRY_CODE_FINDER
+            .ranges_of_country(&country.iso3166)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let zz_result = COUNTRY_CODE_FINDER.contains(zz_ip_range.start(), "ZZ");
+        let invalid_code_result = COUNTRY_CODE_FINDER.contains(ip_range.start(), "Q1");
+
+        assert_eq!(zz_result, false);
+        assert_eq!(invalid_code_result, false);
+    }
+
     #[test]
     fn real_test_ipv4_with_google() {
         if dbip_country::COUNTRIES.country_from_code("US").is_err() {
@@ -0,0 +1,152 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::continents::Continent;
+use crate::countries::Countries;
+use crate::country_block_stream::Country;
+use crate::dbip_country;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref COUNTRY_GROUPS: CountryGroups<'static> = CountryGroups::new(
+        &dbip_country::COUNTRIES,
+        dbip_country::country_continent_codes(),
+        dbip_country::eu_membership_data()
+    );
+}
+
+// Lets selections like "any EU country" or "anywhere in South America" be expressed without
+// listing the countries by hand
+pub struct CountryGroups<'a> {
+    countries: &'a Countries,
+    continents: Vec<Option<Continent>>,
+    eu_membership: Vec<u64>,
+}
+
+impl<'a> CountryGroups<'a> {
+    pub fn new(
+        countries: &'a Countries,
+        continent_codes: Vec<&str>,
+        eu_membership: Vec<u64>,
+    ) -> Self {
+        Self {
+            countries,
+            continents: continent_codes
+                .into_iter()
+                .map(|code| Continent::from_code(code).ok())
+                .collect(),
+            eu_membership,
+        }
+    }
+
+    pub fn continent_of(&self, iso3166: &str) -> Option<Continent> {
+        let country = self.countries.country_from_code(iso3166).ok()?;
+        self.continent_at(country.index)
+    }
+
+    pub fn is_eu_member(&self, iso3166: &str) -> bool {
+        match self.countries.country_from_code(iso3166) {
+            Ok(country) => self.is_eu_member_at(country.index),
+            Err(_) => false,
+        }
+    }
+
+    pub fn countries_in(&self, continent: Continent) -> Vec<&'a Country> {
+        self.countries
+            .iter()
+            .filter(|country| self.continent_at(country.index) == Some(continent))
+            .collect()
+    }
+
+    pub fn eu_members(&self) -> Vec<&'a Country> {
+        self.countries
+            .iter()
+            .filter(|country| self.is_eu_member_at(country.index))
+            .collect()
+    }
+
+    pub fn ensure_init(&self) {
+        //This should provoke lazy_static to perform the value initialization
+    }
+
+    fn continent_at(&self, index: usize) -> Option<Continent> {
+        self.continents.get(index).copied().flatten()
+    }
+
+    fn is_eu_member_at(&self, index: usize) -> bool {
+        match self.eu_membership.get(index / 64) {
+            Some(bits) => (bits >> (index % 64)) & 1 == 1,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso3166_codes(countries: Vec<&Country>) -> Vec<&str> {
+        countries
+            .into_iter()
+            .map(|country| country.iso3166.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn finds_continents_of_countries_in_the_database() {
+        COUNTRY_GROUPS.ensure_init();
+
+        assert_eq!(COUNTRY_GROUPS.continent_of("FR"), Some(Continent::Europe));
+        assert_eq!(COUNTRY_GROUPS.continent_of("jp"), Some(Continent::Asia));
+        assert_eq!(
+            COUNTRY_GROUPS.continent_of("US"),
+            Some(Continent::NorthAmerica)
+        );
+        assert_eq!(COUNTRY_GROUPS.continent_of("ZZ"), None);
+        assert_eq!(COUNTRY_GROUPS.continent_of("QQ"), None);
+    }
+
+    #[test]
+    fn lists_countries_of_a_continent() {
+        let result = COUNTRY_GROUPS.countries_in(Continent::Antarctica);
+
+        assert_eq!(iso3166_codes(result), vec!["AQ", "BV", "GS", "HM", "TF"]);
+    }
+
+    #[test]
+    fn knows_eu_members_in_the_database() {
+        assert!(COUNTRY_GROUPS.is_eu_member("DE"));
+        assert!(COUNTRY_GROUPS.is_eu_member("se"));
+        assert!(!COUNTRY_GROUPS.is_eu_member("NO"));
+        assert!(!COUNTRY_GROUPS.is_eu_member("ZZ"));
+        assert!(!COUNTRY_GROUPS.is_eu_member("QQ"));
+        let eu_members = COUNTRY_GROUPS.eu_members();
+        assert_eq!(eu_members.len(), 27);
+        eu_members.into_iter().for_each(|country| {
+            assert_eq!(
+                COUNTRY_GROUPS.continent_of(&country.iso3166),
+                Some(Continent::Europe)
+            )
+        });
+    }
+
+    #[test]
+    fn reads_groups_from_generated_tables() {
+        let countries = Countries::new(vec![
+            ("AR".to_string(), "Argentina".to_string()),
+            ("EE".to_string(), "Estonia".to_string()),
+            ("XX".to_string(), "Nowhere".to_string()),
+        ]);
+
+        let subject = CountryGroups::new(&countries, vec!["--", "SA", "EU", "--"], vec![0b0100]);
+
+        assert_eq!(subject.continent_of("AR"), Some(Continent::SouthAmerica));
+        assert_eq!(subject.continent_of("EE"), Some(Continent::Europe));
+        assert_eq!(subject.continent_of("XX"), None);
+        assert_eq!(iso3166_codes(subject.eu_members()), vec!["EE"]);
+        assert_eq!(
+            iso3166_codes(subject.countries_in(Continent::SouthAmerica)),
+            vec!["AR"]
+        );
+        assert!(subject.countries_in(Continent::Oceania).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
-
-use lazy_static::lazy_static;
 use crate::countries::Countries;
+use crate::metadata::DBMetadata;
+use lazy_static::lazy_static;
 
 lazy_static! {
     pub static ref COUNTRIES: Countries = Countries::new(
@@ -263,12 +263,56 @@ lazy_static! {
     );
 }
 
+pub fn db_metadata() -> DBMetadata {
+    DBMetadata {
+        dataset: "DB-IP IP to Country Lite",
+        dataset_version: "unknown",
+        dataset_license: "CC BY 4.0, https://db-ip.com",
+        generated_at: "unknown",
+        generator_version: "0.1.0",
+    }
+}
+
+pub fn country_continent_codes() -> Vec<&'static str> {
+    vec![
+        "--", "EU", "AS", "AS", "NA", "NA", "EU", "AS", "AF", "AN", "SA", "OC", "EU", "OC", "NA",
+        "EU", "AS", "EU", "NA", "AS", "EU", "AF", "EU", "AS", "AF", "AF", "NA", "NA", "AS", "SA",
+        "NA", "SA", "NA", "AS", "AN", "AF", "EU", "NA", "NA", "AS", "AF", "AF", "AF", "EU", "AF",
+        "OC", "SA", "AF", "AS", "SA", "NA", "NA", "AF", "NA", "AS", "EU", "EU", "EU", "AF", "EU",
+        "NA", "NA", "AF", "SA", "EU", "AF", "AF", "AF", "EU", "AF", "EU", "OC", "SA", "OC", "EU",
+        "EU", "AF", "EU", "NA", "AS", "SA", "EU", "AF", "EU", "NA", "AF", "AF", "NA", "AF", "EU",
+        "AN", "NA", "OC", "AF", "SA", "AS", "AN", "NA", "EU", "NA", "EU", "AS", "EU", "AS", "EU",
+        "AS", "AS", "AS", "AS", "EU", "EU", "EU", "NA", "AS", "AS", "AF", "AS", "AS", "OC", "AF",
+        "NA", "AS", "AS", "AS", "NA", "AS", "AS", "AS", "NA", "EU", "AS", "AF", "AF", "EU", "EU",
+        "EU", "AF", "AF", "EU", "EU", "EU", "NA", "AF", "OC", "EU", "AF", "AS", "AS", "AS", "OC",
+        "NA", "AF", "NA", "EU", "AF", "AS", "AF", "NA", "AS", "AF", "AF", "OC", "AF", "OC", "AF",
+        "NA", "EU", "EU", "AS", "OC", "OC", "OC", "AS", "NA", "SA", "OC", "OC", "AS", "AS", "EU",
+        "NA", "OC", "NA", "AS", "EU", "OC", "SA", "AS", "AF", "EU", "EU", "EU", "AF", "AS", "OC",
+        "AF", "AF", "EU", "AS", "AF", "EU", "EU", "EU", "AF", "EU", "AF", "AF", "SA", "AF", "AF",
+        "NA", "NA", "AS", "AF", "NA", "AF", "AN", "AF", "AS", "AS", "OC", "OC", "AS", "AF", "OC",
+        "AS", "NA", "OC", "AS", "AF", "EU", "AF", "OC", "NA", "SA", "AS", "EU", "NA", "SA", "NA",
+        "NA", "AS", "OC", "OC", "OC", "EU", "AS", "AF", "AF", "AF", "AF",
+    ]
+}
+
+pub fn eu_membership_data() -> Vec<u64> {
+    vec![
+        0x0B80000000501000,
+        0x0000405402000851,
+        0x21080040020000E0,
+        0x0000000000000520,
+    ]
+}
+
 pub fn ipv4_country_data() -> (Vec<u64>, usize) {
     (
         vec![
-            0x8098000300801003, 0x18081B0020981C04, 0xB428C00158440E83, 0x00076162030DC320,
+            0x8098000300801003,
+            0x18081B0020981C04,
+            0xB428C00158440E83,
+            0x00076162030DC320,
         ],
-        256
+        256,
     )
 }
 
@@ -279,11 +323,20 @@ pub fn ipv4_country_block_count() -> usize {
 pub fn ipv6_country_data() -> (Vec<u64>, usize) {
     (
         vec![
-            0x3000040000400007, 0x00C0001400020000, 0x4400047000000700, 0x0160002300034000,
-            0x800470007C000D40, 0x200163808002B800, 0x1F000398006A000C, 0x004F8008E0010A00,
-            0x0AA0014200263800, 0x0018A002F0005980, 0x028080C006B800CE, 0x0000000000001BE0,
+            0x3000040000400007,
+            0x00C0001400020000,
+            0x4400047000000700,
+            0x0160002300034000,
+            0x800470007C000D40,
+            0x200163808002B800,
+            0x1F000398006A000C,
+            0x004F8008E0010A00,
+            0x0AA0014200263800,
+            0x0018A002F0005980,
+            0x028080C006B800CE,
+            0x0000000000001BE0,
         ],
-        737
+        737,
     )
 }
 
@@ -0,0 +1,134 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use flate2::read::MultiGzDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use std::io;
+use std::io::Read;
+
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const SNIFF_SIZE: usize = 4;
+
+// Lets the official gzip- or zstd-compressed database be piped straight into ip_country. The first
+// bytes of the stream decide whether (and how) it's decompressed; decompression streams, so memory
+// stays flat no matter how big the database is. Sniffing is put off until the first read so that
+// errors reading stdin get reported by the parsers the same way whether it's compressed or not.
+pub struct DecompressingReader<'a> {
+    source_opt: Option<&'a mut dyn Read>,
+    delegate_opt: Option<Box<dyn Read + 'a>>,
+}
+
+impl Read for DecompressingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.delegate_opt.is_none() {
+            let source = self
+                .source_opt
+                .take()
+                .expect("DecompressingReader lost its source");
+            self.delegate_opt = Some(Self::sniff(source)?);
+        }
+        self.delegate_opt
+            .as_mut()
+            .expect("DecompressingReader has no delegate")
+            .read(buf)
+    }
+}
+
+impl<'a> DecompressingReader<'a> {
+    pub fn new(source: &'a mut dyn Read) -> Self {
+        Self {
+            source_opt: Some(source),
+            delegate_opt: None,
+        }
+    }
+
+    fn sniff(source: &'a mut dyn Read) -> io::Result<Box<dyn Read + 'a>> {
+        let mut prefix = vec![];
+        Read::take(&mut *source, SNIFF_SIZE as u64).read_to_end(&mut prefix)?;
+        let restored = io::Cursor::new(prefix.clone()).chain(source);
+        if prefix.starts_with(&ZSTD_MAGIC) {
+            let decoder = StreamingDecoder::new(restored)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            Ok(Box::new(decoder))
+        } else if prefix.starts_with(&GZIP_MAGIC) {
+            Ok(Box::new(MultiGzDecoder::new(restored)))
+        } else {
+            Ok(Box::new(restored))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+    use std::io::{Error, ErrorKind, Write};
+    use test_utilities::byte_array_reader_writer::ByteArrayReader;
+
+    const DATA: &str = "1.0.0.0,1.0.0.255,AU\n1.0.1.0,1.0.3.255,CN\n";
+
+    fn read_all(compressed: Vec<u8>) -> io::Result<String> {
+        let mut stdin = ByteArrayReader::new(&compressed);
+        let mut subject = DecompressingReader::new(&mut stdin);
+        let mut result = String::new();
+        subject.read_to_string(&mut result)?;
+        Ok(result)
+    }
+
+    #[test]
+    fn uncompressed_input_passes_through_untouched() {
+        let result = read_all(DATA.as_bytes().to_vec()).unwrap();
+
+        assert_eq!(result, DATA);
+    }
+
+    #[test]
+    fn input_shorter_than_any_magic_passes_through_untouched() {
+        assert_eq!(read_all(b"1,".to_vec()).unwrap(), "1,");
+        assert_eq!(read_all(vec![]).unwrap(), "");
+    }
+
+    #[test]
+    fn gzipped_input_is_decompressed() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(DATA.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let result = read_all(compressed).unwrap();
+
+        assert_eq!(result, DATA);
+    }
+
+    #[test]
+    fn zstd_compressed_input_is_decompressed() {
+        let compressed = compress_to_vec(DATA.as_bytes(), CompressionLevel::Fastest);
+
+        let result = read_all(compressed).unwrap();
+
+        assert_eq!(result, DATA);
+    }
+
+    #[test]
+    fn corrupt_compressed_input_is_an_error() {
+        let mut corrupt = GZIP_MAGIC.to_vec();
+        corrupt.extend_from_slice(b"not really gzip");
+
+        let result = read_all(corrupt);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn errors_reading_the_source_are_passed_on() {
+        let mut stdin = ByteArrayReader::new(DATA.as_bytes())
+            .reject_next_read(Error::new(ErrorKind::BrokenPipe, "broken pipe"));
+        let mut subject = DecompressingReader::new(&mut stdin);
+        let mut buf = [0u8; 16];
+
+        let result = subject.read(&mut buf);
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::BrokenPipe);
+    }
+}
@@ -0,0 +1,544 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::error_report::{
+    EXIT_CODE_IO_FAILURE, EXIT_CODE_USAGE_ERROR, EXIT_CODE_VALIDATION_FAILURE,
+};
+use crate::ip_country::{ip_country, DBIPParserFactory};
+use crate::metadata::civil_date;
+use sha2::{Digest, Sha256};
+use std::io;
+use std::io::Read;
+use std::sync::Arc;
+use std::time::SystemTime;
+use ureq::native_tls::TlsConnector;
+use ureq::AgentBuilder;
+
+// Fetches the DB-IP country lite CSV and converts it in the same run:
+//   ip_country download (--sha256 <digest> | --checksum-url <url>) [--url <url>] [--max-errors <n>]
+// Without --url, the database of the current month is fetched. Nothing is converted unless the
+// SHA-256 of what was downloaded matches the expected one.
+pub const DOWNLOAD_SUBCOMMAND: &str = "download";
+pub const DBIP_CSV_URL_PREFIX: &str = "https://download.db-ip.com/free/dbip-country-lite-";
+const SHA256_HEX_LENGTH: usize = 64;
+
+pub trait Downloader {
+    fn download(&self, url: &str) -> Result<Vec<u8>, String>;
+}
+
+pub struct DownloaderReal {}
+
+impl Downloader for DownloaderReal {
+    // ureq's built-in TLS is rustls, which can't be resolved together with the Node's dependencies
+    fn download(&self, url: &str) -> Result<Vec<u8>, String> {
+        let tls_connector =
+            TlsConnector::new().map_err(|e| format!("Download of {} failed: {}", url, e))?;
+        let agent = AgentBuilder::new()
+            .tls_connector(Arc::new(tls_connector))
+            .build();
+        let response = agent
+            .get(url)
+            .call()
+            .map_err(|e| format!("Download of {} failed: {}", url, e))?;
+        let mut body = vec![];
+        response
+            .into_reader()
+            .read_to_end(&mut body)
+            .map_err(|e| format!("Download of {} broke off: {}", url, e))?;
+        Ok(body)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ExpectedChecksum {
+    Given(String),
+    Published(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct DownloadArgs {
+    url: String,
+    expected_checksum: ExpectedChecksum,
+    max_errors_opt: Option<String>,
+    dataset_version_opt: Option<String>,
+}
+
+pub fn download_and_convert(
+    args: Vec<String>,
+    stdout: &mut dyn io::Write,
+    stderr: &mut dyn io::Write,
+    downloader: &dyn Downloader,
+    parser_factory: &dyn DBIPParserFactory,
+    now: SystemTime,
+) -> i32 {
+    let download_args = match download_args(&args, now) {
+        Ok(download_args) => download_args,
+        Err(msg) => {
+            let _ = writeln!(stderr, "{}", msg);
+            return EXIT_CODE_USAGE_ERROR;
+        }
+    };
+    let expected_checksum = match expected_checksum(&download_args.expected_checksum, downloader) {
+        Ok(checksum) => checksum,
+        Err((msg, exit_code)) => {
+            let _ = writeln!(stderr, "{}", msg);
+            return exit_code;
+        }
+    };
+    let data = match downloader.download(&download_args.url) {
+        Ok(data) => data,
+        Err(msg) => {
+            let _ = writeln!(stderr, "{}", msg);
+            return EXIT_CODE_IO_FAILURE;
+        }
+    };
+    let actual_checksum = format!("{:x}", Sha256::digest(&data));
+    if actual_checksum != expected_checksum {
+        let _ = writeln!(
+            stderr,
+            "SHA-256 of {} is {}, but {} was expected; the download isn't converted",
+            download_args.url, actual_checksum, expected_checksum
+        );
+        return EXIT_CODE_VALIDATION_FAILURE;
+    }
+    let _ = writeln!(
+        stderr,
+        "Downloaded {} bytes from {} with the expected SHA-256; converting",
+        data.len(),
+        download_args.url
+    );
+    let mut converter_args = vec![args[0].clone(), "--csv".to_string()];
+    if let Some(max_errors) = download_args.max_errors_opt {
+        converter_args.extend(["--max-errors".to_string(), max_errors]);
+    }
+    if let Some(dataset_version) = download_args.dataset_version_opt {
+        converter_args.extend(["--dataset-version".to_string(), dataset_version]);
+    }
+    ip_country(
+        converter_args,
+        &mut data.as_slice(),
+        stdout,
+        stderr,
+        parser_factory,
+        now,
+    )
+}
+
+fn download_args(args: &[String], now: SystemTime) -> Result<DownloadArgs, String> {
+    let mut url_opt = None;
+    let mut sha256_opt = None;
+    let mut checksum_url_opt = None;
+    let mut max_errors_opt = None;
+    let mut dataset_version_opt = None;
+    let mut remaining = args.iter().skip(2);
+    while let Some(arg) = remaining.next() {
+        let slot = match arg.as_str() {
+            "--url" => &mut url_opt,
+            "--sha256" => &mut sha256_opt,
+            "--checksum-url" => &mut checksum_url_opt,
+            "--max-errors" => &mut max_errors_opt,
+            "--dataset-version" => &mut dataset_version_opt,
+            _ => {
+                return Err(format!(
+                    "Unknown argument of {}: {}",
+                    DOWNLOAD_SUBCOMMAND, arg
+                ))
+            }
+        };
+        match remaining.next() {
+            Some(value) => *slot = Some(value.clone()),
+            None => return Err(format!("{} must be followed by a value", arg)),
+        }
+    }
+    let url = url_opt.unwrap_or_else(|| current_csv_url(now));
+    let dataset_version_opt = dataset_version_opt.or_else(|| dataset_version_of(&url));
+    let expected_checksum = match (sha256_opt, checksum_url_opt) {
+        (Some(sha256), None) => ExpectedChecksum::Given(checked_sha256(&sha256)?),
+        (None, Some(checksum_url)) => ExpectedChecksum::Published(checksum_url),
+        _ => {
+            return Err(
+                "Exactly one of --sha256 and --checksum-url must say what to verify the download against"
+                    .to_string(),
+            )
+        }
+    };
+    for checked_url in [Some(&url), checksum_url_of(&expected_checksum)]
+        .into_iter()
+        .flatten()
+    {
+        if !checked_url.starts_with("https://") {
+            return Err(format!(
+                "Only HTTPS downloads are allowed, not {}",
+                checked_url
+            ));
+        }
+    }
+    Ok(DownloadArgs {
+        url,
+        expected_checksum,
+        max_errors_opt,
+        dataset_version_opt,
+    })
+}
+
+fn checksum_url_of(expected_checksum: &ExpectedChecksum) -> Option<&String> {
+    match expected_checksum {
+        ExpectedChecksum::Given(_) => None,
+        ExpectedChecksum::Published(url) => Some(url),
+    }
+}
+
+fn expected_checksum(
+    expected_checksum: &ExpectedChecksum,
+    downloader: &dyn Downloader,
+) -> Result<String, (String, i32)> {
+    match expected_checksum {
+        ExpectedChecksum::Given(sha256) => Ok(sha256.clone()),
+        ExpectedChecksum::Published(url) => {
+            let published = downloader
+                .download(url)
+                .map_err(|msg| (msg, EXIT_CODE_IO_FAILURE))?;
+            // Checksum files read "<digest>  <file name>", as written by sha256sum
+            let digest = String::from_utf8_lossy(&published)
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            checked_sha256(&digest).map_err(|_| {
+                (
+                    format!("{} doesn't publish a SHA-256 checksum", url),
+                    EXIT_CODE_VALIDATION_FAILURE,
+                )
+            })
+        }
+    }
+}
+
+fn checked_sha256(digest: &str) -> Result<String, String> {
+    if digest.len() == SHA256_HEX_LENGTH && digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(digest.to_ascii_lowercase())
+    } else {
+        Err(format!(
+            "A SHA-256 checksum is {} hexadecimal digits, not '{}'",
+            SHA256_HEX_LENGTH, digest
+        ))
+    }
+}
+
+fn current_csv_url(now: SystemTime) -> String {
+    let (year, month, _) = civil_date(now);
+    format!("{}{:04}-{:02}.csv.gz", DBIP_CSV_URL_PREFIX, year, month)
+}
+
+// DB-IP names its files after the month they were published, as in dbip-country-lite-2024-02.csv.gz
+fn dataset_version_of(url: &str) -> Option<String> {
+    let file_name = url.rsplit('/').next()?;
+    let version = file_name.strip_prefix("dbip-country-lite-")?.get(..7)?;
+    let is_year_month = version.char_indices().all(|(index, c)| match index {
+        4 => c == '-',
+        _ => c.is_ascii_digit(),
+    });
+    is_year_month.then(|| version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_report::EXIT_CODE_SUCCESS;
+    use crate::ip_country::DBIPParserFactoryReal;
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use test_utilities::byte_array_reader_writer::ByteArrayWriter;
+
+    static TEST_DATA: &str = "0.0.0.0,0.255.255.255,ZZ
+1.0.0.0,1.0.0.255,AU
+1.0.1.0,1.0.3.255,CN
+0:0:0:0:0:0:0:0,0:255:255:255:0:0:0:0,ZZ
+1:0:0:0:0:0:0:0,1:0:0:255:0:0:0:0,AU
+";
+
+    struct DownloaderMock {
+        download_params: Arc<Mutex<Vec<String>>>,
+        download_results: RefCell<Vec<Result<Vec<u8>, String>>>,
+    }
+
+    impl Downloader for DownloaderMock {
+        fn download(&self, url: &str) -> Result<Vec<u8>, String> {
+            self.download_params.lock().unwrap().push(url.to_string());
+            self.download_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl DownloaderMock {
+        fn new() -> Self {
+            Self {
+                download_params: Arc::new(Mutex::new(vec![])),
+                download_results: RefCell::new(vec![]),
+            }
+        }
+
+        fn download_params(mut self, params: &Arc<Mutex<Vec<String>>>) -> Self {
+            self.download_params = params.clone();
+            self
+        }
+
+        fn download_result(self, result: Result<Vec<u8>, String>) -> Self {
+            self.download_results.borrow_mut().push(result);
+            self
+        }
+    }
+
+    fn test_data_sha256() -> String {
+        format!("{:x}", Sha256::digest(TEST_DATA.as_bytes()))
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn run(args: Vec<String>, downloader: &DownloaderMock) -> (i32, String, String) {
+        let mut stdout = ByteArrayWriter::new();
+        let mut stderr = ByteArrayWriter::new();
+        let exit_code = download_and_convert(
+            args,
+            &mut stdout,
+            &mut stderr,
+            downloader,
+            &DBIPParserFactoryReal {},
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_709_164_800), // 2024-02-29
+        );
+        (exit_code, stdout.get_string(), stderr.get_string())
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DOWNLOAD_SUBCOMMAND, "download");
+        assert_eq!(
+            DBIP_CSV_URL_PREFIX,
+            "https://download.db-ip.com/free/dbip-country-lite-"
+        );
+        assert_eq!(SHA256_HEX_LENGTH, 64);
+    }
+
+    #[test]
+    fn dataset_version_is_taken_from_the_dbip_file_name() {
+        assert_eq!(
+            dataset_version_of("https://download.db-ip.com/free/dbip-country-lite-2024-02.csv.gz"),
+            Some("2024-02".to_string())
+        );
+        assert_eq!(
+            dataset_version_of("https://mirror.example.com/dbip-country-lite-2024-02.csv"),
+            Some("2024-02".to_string())
+        );
+        assert_eq!(
+            dataset_version_of("https://mirror.example.com/dbip-country-lite-latest.csv.gz"),
+            None
+        );
+        assert_eq!(
+            dataset_version_of("https://mirror.example.com/dbip-country-lite-2024"),
+            None
+        );
+        assert_eq!(
+            dataset_version_of("https://mirror.example.com/dbip.csv.gz"),
+            None
+        );
+    }
+
+    #[test]
+    fn download_with_the_given_checksum_is_converted() {
+        let download_params_arc = Arc::new(Mutex::new(vec![]));
+        let downloader = DownloaderMock::new()
+            .download_params(&download_params_arc)
+            .download_result(Ok(TEST_DATA.as_bytes().to_vec()));
+        let sha256 = test_data_sha256().to_uppercase();
+
+        let (exit_code, stdout, stderr) = run(
+            args(&["ip_country", "download", "--sha256", &sha256]),
+            &downloader,
+        );
+
+        assert_eq!(exit_code, EXIT_CODE_SUCCESS);
+        assert!(stdout.starts_with("\n// GENERATED CODE: REGENERATE, DO NOT MODIFY!\n"));
+        assert!(stdout.contains("        dataset_version: \"2024-02\",\n"));
+        assert!(stdout.contains("        generated_at: \"2024-02-29T00:00:00Z\",\n"));
+        assert_eq!(
+            stderr,
+            format!(
+                "Downloaded {} bytes from https://download.db-ip.com/free/dbip-country-lite-2024-02.csv.gz with the expected SHA-256; converting\n",
+                TEST_DATA.len()
+            )
+        );
+        assert_eq!(
+            *download_params_arc.lock().unwrap(),
+            vec!["https://download.db-ip.com/free/dbip-country-lite-2024-02.csv.gz".to_string()]
+        );
+    }
+
+    #[test]
+    fn download_is_verified_against_the_published_checksum() {
+        let download_params_arc = Arc::new(Mutex::new(vec![]));
+        let published = format!("{}  dbip.csv.gz\n", test_data_sha256());
+        let downloader = DownloaderMock::new()
+            .download_params(&download_params_arc)
+            .download_result(Ok(published.into_bytes()))
+            .download_result(Ok(TEST_DATA.as_bytes().to_vec()));
+
+        let (exit_code, stdout, _) = run(
+            args(&[
+                "ip_country",
+                "download",
+                "--url",
+                "https://mirror.example.com/dbip.csv.gz",
+                "--checksum-url",
+                "https://mirror.example.com/dbip.csv.gz.sha256",
+            ]),
+            &downloader,
+        );
+
+        assert_eq!(exit_code, EXIT_CODE_SUCCESS);
+        assert!(stdout.starts_with("\n// GENERATED CODE: REGENERATE, DO NOT MODIFY!\n"));
+        assert!(stdout.contains("        dataset_version: \"unknown\",\n"));
+        assert_eq!(
+            *download_params_arc.lock().unwrap(),
+            vec![
+                "https://mirror.example.com/dbip.csv.gz.sha256".to_string(),
+                "https://mirror.example.com/dbip.csv.gz".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn download_with_a_mismatched_checksum_is_not_converted() {
+        let downloader = DownloaderMock::new().download_result(Ok(b"tampered".to_vec()));
+        let sha256 = test_data_sha256();
+
+        let (exit_code, stdout, stderr) = run(
+            args(&["ip_country", "download", "--sha256", &sha256]),
+            &downloader,
+        );
+
+        assert_eq!(exit_code, EXIT_CODE_VALIDATION_FAILURE);
+        assert_eq!(stdout, "");
+        assert_eq!(
+            stderr,
+            format!(
+                "SHA-256 of https://download.db-ip.com/free/dbip-country-lite-2024-02.csv.gz is {:x}, but {} was expected; the download isn't converted\n",
+                Sha256::digest(b"tampered"),
+                sha256
+            )
+        );
+    }
+
+    #[test]
+    fn published_checksum_must_be_a_sha256_digest() {
+        let downloader = DownloaderMock::new().download_result(Ok(b"<html></html>".to_vec()));
+
+        let (exit_code, _, stderr) = run(
+            args(&[
+                "ip_country",
+                "download",
+                "--checksum-url",
+                "https://example.com/sums",
+            ]),
+            &downloader,
+        );
+
+        assert_eq!(exit_code, EXIT_CODE_VALIDATION_FAILURE);
+        assert_eq!(
+            stderr,
+            "https://example.com/sums doesn't publish a SHA-256 checksum\n"
+        );
+    }
+
+    #[test]
+    fn failed_download_is_reported() {
+        let downloader = DownloaderMock::new().download_result(Err("Download failed".to_string()));
+        let sha256 = test_data_sha256();
+
+        let (exit_code, stdout, stderr) = run(
+            args(&["ip_country", "download", "--sha256", &sha256]),
+            &downloader,
+        );
+
+        assert_eq!(exit_code, EXIT_CODE_IO_FAILURE);
+        assert_eq!(stdout, "");
+        assert_eq!(stderr, "Download failed\n");
+    }
+
+    #[test]
+    fn max_errors_is_passed_to_the_converter() {
+        let bad_data = "Ooga booga\n";
+        let downloader = DownloaderMock::new().download_result(Ok(bad_data.as_bytes().to_vec()));
+        let sha256 = format!("{:x}", Sha256::digest(bad_data.as_bytes()));
+
+        let (exit_code, _, stderr) = run(
+            args(&[
+                "ip_country",
+                "download",
+                "--sha256",
+                &sha256,
+                "--max-errors",
+                "0",
+            ]),
+            &downloader,
+        );
+
+        assert_eq!(exit_code, EXIT_CODE_USAGE_ERROR);
+        assert!(stderr.ends_with("--max-errors must be followed by a positive number\n"));
+    }
+
+    #[test]
+    fn download_args_are_checked() {
+        let sha256 = test_data_sha256();
+        let now = SystemTime::now();
+        let check = |args_slice: &[&str]| download_args(&args(args_slice), now).unwrap_err();
+
+        assert_eq!(
+            check(&["ip_country", "download"]),
+            "Exactly one of --sha256 and --checksum-url must say what to verify the download against"
+        );
+        assert_eq!(
+            check(&[
+                "ip_country",
+                "download",
+                "--sha256",
+                &sha256,
+                "--checksum-url",
+                "https://example.com/sums"
+            ]),
+            "Exactly one of --sha256 and --checksum-url must say what to verify the download against"
+        );
+        assert_eq!(
+            check(&["ip_country", "download", "--sha256", "abc"]),
+            "A SHA-256 checksum is 64 hexadecimal digits, not 'abc'"
+        );
+        assert_eq!(
+            check(&["ip_country", "download", "--sha256"]),
+            "--sha256 must be followed by a value"
+        );
+        assert_eq!(
+            check(&["ip_country", "download", "--mmdb"]),
+            "Unknown argument of download: --mmdb"
+        );
+        assert_eq!(
+            check(&[
+                "ip_country",
+                "download",
+                "--sha256",
+                &sha256,
+                "--url",
+                "http://example.com/dbip.csv.gz"
+            ]),
+            "Only HTTPS downloads are allowed, not http://example.com/dbip.csv.gz"
+        );
+        assert_eq!(
+            check(&[
+                "ip_country",
+                "download",
+                "--checksum-url",
+                "ftp://example.com/sums"
+            ]),
+            "Only HTTPS downloads are allowed, not ftp://example.com/sums"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use std::fmt::{Display, Formatter};
+
+pub const EXIT_CODE_SUCCESS: i32 = 0;
+pub const EXIT_CODE_IO_FAILURE: i32 = 1;
+pub const EXIT_CODE_PARSE_FAILURE: i32 = 2;
+pub const EXIT_CODE_VALIDATION_FAILURE: i32 = 3;
+pub const EXIT_CODE_USAGE_ERROR: i32 = 4;
+
+// Ordered by severity: when errors of several categories occur, the exit code is that of the most
+// severe one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ErrorCategory {
+    // The input is well-formed but describes something impossible
+    Validation,
+    // The input is malformed
+    Parse,
+    // The input couldn't be read or the output couldn't be written
+    Io,
+}
+
+impl ErrorCategory {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Validation => EXIT_CODE_VALIDATION_FAILURE,
+            ErrorCategory::Parse => EXIT_CODE_PARSE_FAILURE,
+            ErrorCategory::Io => EXIT_CODE_IO_FAILURE,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DBIPError {
+    pub category: ErrorCategory,
+    pub message: String,
+}
+
+impl Display for DBIPError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl DBIPError {
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorCategory::Io, message)
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(ErrorCategory::Parse, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCategory::Validation, message)
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            message: format!("Line {}: {}", line, self.message),
+            ..self
+        }
+    }
+
+    fn new(category: ErrorCategory, message: impl Into<String>) -> Self {
+        Self {
+            category,
+            message: message.into(),
+        }
+    }
+}
+
+// Collects the errors found while converting the database. The parsers carry on past bad rows,
+// but give up once the optional maximum error count is reached.
+#[derive(Debug, Default)]
+pub struct ErrorReport {
+    errors: Vec<DBIPError>,
+    max_errors_opt: Option<usize>,
+}
+
+impl ErrorReport {
+    pub fn new(max_errors_opt: Option<usize>) -> Self {
+        Self {
+            errors: vec![],
+            max_errors_opt,
+        }
+    }
+
+    pub fn add(&mut self, error: DBIPError) {
+        self.errors.push(error)
+    }
+
+    pub fn limit_reached(&self) -> bool {
+        match self.max_errors_opt {
+            Some(max_errors) => self.errors.len() >= max_errors,
+            None => false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[DBIPError] {
+        &self.errors
+    }
+
+    pub fn messages(&self) -> Vec<String> {
+        self.errors
+            .iter()
+            .map(|error| error.message.clone())
+            .collect()
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.errors
+            .iter()
+            .map(|error| error.category)
+            .max()
+            .map(|category| category.exit_code())
+            .unwrap_or(EXIT_CODE_SUCCESS)
+    }
+
+    pub fn summary(&self) -> String {
+        let count_of = |category: ErrorCategory| {
+            self.errors
+                .iter()
+                .filter(|error| error.category == category)
+                .count()
+        };
+        let summary = format!(
+            "Errors: {} ({} I/O, {} parse, {} validation)",
+            self.errors.len(),
+            count_of(ErrorCategory::Io),
+            count_of(ErrorCategory::Parse),
+            count_of(ErrorCategory::Validation)
+        );
+        match self.max_errors_opt {
+            Some(max_errors) if self.limit_reached() => format!(
+                "{}; gave up after reaching the maximum of {}",
+                summary, max_errors
+            ),
+            _ => summary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let mut codes = vec![
+            EXIT_CODE_SUCCESS,
+            EXIT_CODE_IO_FAILURE,
+            EXIT_CODE_PARSE_FAILURE,
+            EXIT_CODE_VALIDATION_FAILURE,
+            EXIT_CODE_USAGE_ERROR,
+        ];
+        codes.sort();
+        codes.dedup();
+
+        assert_eq!(codes.len(), 5);
+    }
+
+    #[test]
+    fn empty_report_means_success() {
+        let subject = ErrorReport::new(None);
+
+        assert!(subject.is_empty());
+        assert_eq!(subject.exit_code(), EXIT_CODE_SUCCESS);
+        assert_eq!(
+            subject.summary(),
+            "Errors: 0 (0 I/O, 0 parse, 0 validation)"
+        );
+    }
+
+    #[test]
+    fn exit_code_is_that_of_the_most_severe_error() {
+        let mut subject = ErrorReport::new(None);
+
+        subject.add(DBIPError::validation("Backwards"));
+        let validation_code = subject.exit_code();
+        subject.add(DBIPError::parse("Gibberish"));
+        let parse_code = subject.exit_code();
+        subject.add(DBIPError::io("Broken pipe"));
+        subject.add(DBIPError::validation("Backwards again"));
+        let io_code = subject.exit_code();
+
+        assert_eq!(validation_code, EXIT_CODE_VALIDATION_FAILURE);
+        assert_eq!(parse_code, EXIT_CODE_PARSE_FAILURE);
+        assert_eq!(io_code, EXIT_CODE_IO_FAILURE);
+        assert_eq!(
+            subject.summary(),
+            "Errors: 4 (1 I/O, 1 parse, 2 validation)"
+        );
+    }
+
+    #[test]
+    fn limit_is_reached_at_the_maximum_error_count() {
+        let mut subject = ErrorReport::new(Some(2));
+
+        subject.add(DBIPError::parse("First").on_line(3));
+        let after_one = subject.limit_reached();
+        subject.add(DBIPError::parse("Second").on_line(7));
+        let after_two = subject.limit_reached();
+
+        assert!(!after_one);
+        assert!(after_two);
+        assert_eq!(
+            subject.messages(),
+            vec!["Line 3: First".to_string(), "Line 7: Second".to_string()]
+        );
+        assert_eq!(
+            subject.summary(),
+            "Errors: 2 (0 I/O, 2 parse, 0 validation); gave up after reaching the maximum of 2"
+        );
+    }
+
+    #[test]
+    fn unlimited_report_never_reaches_its_limit() {
+        let mut subject = ErrorReport::new(None);
+
+        (0..1000).for_each(|_| subject.add(DBIPError::parse("Again")));
+
+        assert!(!subject.limit_reached());
+    }
+}
@@ -1,14 +1,23 @@
 // Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::bit_queue::BitQueue;
+use crate::continents::{is_reference_eu_member, reference_continent};
 use crate::countries::Countries;
 use crate::country_block_serde::FinalBitQueue;
+use crate::decompression::DecompressingReader;
+use crate::error_report::{DBIPError, ErrorReport, EXIT_CODE_SUCCESS, EXIT_CODE_USAGE_ERROR};
 use crate::ip_country_csv::CSVParser;
 use crate::ip_country_mmdb::MMDBParser;
+use crate::metadata::{
+    utc_timestamp, DATASET_LICENSE, DATASET_NAME, GENERATOR_VERSION, UNKNOWN_DATASET_VERSION,
+};
 use std::any::Any;
 use std::io;
+use std::time::SystemTime;
 
 const COUNTRY_BLOCK_BIT_SIZE: usize = 64;
+const CONTINENT_CODES_PER_LINE: usize = 16;
+const UNKNOWN_CONTINENT_CODE: &str = "--";
 
 pub fn ip_country(
     args: Vec<String>,
@@ -16,20 +25,39 @@ pub fn ip_country(
     stdout: &mut dyn io::Write,
     stderr: &mut dyn io::Write,
     parser_factory: &dyn DBIPParserFactory,
+    now: SystemTime,
 ) -> i32 {
+    let args_result = max_errors_from_args(&args)
+        .and_then(|max_errors_opt| Ok((max_errors_opt, dataset_version_from_args(&args)?)));
+    let (max_errors_opt, dataset_version) = match args_result {
+        Ok(values) => values,
+        Err(msg) => {
+            let _ = writeln!(stderr, "{}", msg);
+            return EXIT_CODE_USAGE_ERROR;
+        }
+    };
     let parser = parser_factory.make(&args);
-    let mut errors: Vec<String> = vec![];
-    let (final_ipv4, final_ipv6, countries) = parser.parse(stdin, &mut errors);
-    if let Err(error) = generate_rust_code(final_ipv4, final_ipv6, countries, stdout) {
-        errors.push(format!("Error generating Rust code: {:?}", error))
+    let mut errors = ErrorReport::new(max_errors_opt);
+    let mut input = DecompressingReader::new(stdin);
+    let (final_ipv4, final_ipv6, countries) = parser.parse(&mut input, &mut errors);
+    let provenance = Provenance {
+        dataset_version,
+        generated_at: utc_timestamp(now),
+    };
+    if let Err(error) = generate_rust_code(final_ipv4, final_ipv6, countries, &provenance, stdout) {
+        errors.add(DBIPError::io(format!(
+            "Error generating Rust code: {:?}",
+            error
+        )))
     }
     if errors.is_empty() {
-        0
-    } else {
-        let error_list = errors.join("\n");
-        write!(
-            stdout,
-            r#"
+        return EXIT_CODE_SUCCESS;
+    }
+    let error_list = errors.messages().join("\n");
+    // If the output can't be written, there's nobody left to tell; the exit code still says why
+    let _ = write!(
+        stdout,
+        r#"
             *** DO NOT USE THIS CODE ***
             It will produce incorrect results.
             The process that generated it found these errors:
@@ -39,14 +67,41 @@ pub fn ip_country(
             Fix the errors and regenerate the code.
             *** DO NOT USE THIS CODE ***
 "#,
-            error_list
-        )
-        .expect("expected WANRNING output");
-        write!(stderr, "{}", error_list).expect("expected error list output");
-        1
+        error_list
+    );
+    let _ = write!(stderr, "{}\n\n{}", error_list, errors.summary());
+    errors.exit_code()
+}
+
+fn max_errors_from_args(args: &[String]) -> Result<Option<usize>, String> {
+    let position = match args.iter().position(|arg| arg == "--max-errors") {
+        None => return Ok(None),
+        Some(position) => position,
+    };
+    match args.get(position + 1).map(|value| value.parse::<usize>()) {
+        Some(Ok(max_errors)) if max_errors > 0 => Ok(Some(max_errors)),
+        _ => Err("--max-errors must be followed by a positive number".to_string()),
+    }
+}
+
+// Which revision of the dataset is converted, as in 2024-02; DB-IP doesn't say so in the CSV
+fn dataset_version_from_args(args: &[String]) -> Result<String, String> {
+    let position = match args.iter().position(|arg| arg == "--dataset-version") {
+        None => return Ok(UNKNOWN_DATASET_VERSION.to_string()),
+        Some(position) => position,
+    };
+    match args.get(position + 1) {
+        Some(version) if !version.is_empty() && !version.starts_with("--") => Ok(version.clone()),
+        _ => Err("--dataset-version must be followed by the version of the dataset".to_string()),
     }
 }
 
+// What the generated code tells about itself, besides the constants in crate::metadata
+pub struct Provenance {
+    pub dataset_version: String,
+    pub generated_at: String,
+}
+
 pub trait DBIPParserFactory {
     fn make(&self, args: &[String]) -> Box<dyn DBIPParser>;
 }
@@ -69,7 +124,7 @@ pub trait DBIPParser: Any {
     fn parse(
         &self,
         stdin: &mut dyn io::Read,
-        errors: &mut Vec<String>,
+        errors: &mut ErrorReport,
     ) -> (FinalBitQueue, FinalBitQueue, Countries);
 }
 
@@ -77,10 +132,13 @@ pub fn generate_rust_code(
     final_ipv4: FinalBitQueue,
     final_ipv6: FinalBitQueue,
     countries: Countries,
+    provenance: &Provenance,
     output: &mut dyn io::Write,
 ) -> Result<(), io::Error> {
     write!(output, "\n// GENERATED CODE: REGENERATE, DO NOT MODIFY!\n")?;
-    generate_country_list(countries, output)?;
+    generate_country_list(&countries, output)?;
+    generate_metadata_code(provenance, output)?;
+    generate_country_group_code(&countries, output)?;
     generate_country_block_code(
         "ipv4_country",
         final_ipv4.bit_queue,
@@ -97,12 +155,13 @@ pub fn generate_rust_code(
 }
 
 fn generate_country_list(
-    countries: Countries,
+    countries: &Countries,
     output: &mut dyn io::Write,
 ) -> Result<(), io::Error> {
     writeln!(output)?;
     writeln!(output, "use lazy_static::lazy_static;")?;
     writeln!(output, "use crate::countries::Countries;")?;
+    writeln!(output, "use crate::metadata::DBMetadata;")?;
     writeln!(output)?;
     writeln!(output, "lazy_static! {{")?;
     writeln!(
@@ -129,6 +188,81 @@ fn generate_country_list(
     Ok(())
 }
 
+fn generate_metadata_code(
+    provenance: &Provenance,
+    output: &mut dyn io::Write,
+) -> Result<(), io::Error> {
+    writeln!(output)?;
+    writeln!(output, "pub fn db_metadata() -> DBMetadata {{")?;
+    writeln!(output, "    DBMetadata {{")?;
+    writeln!(output, "        dataset: {:?},", DATASET_NAME)?;
+    writeln!(
+        output,
+        "        dataset_version: {:?},",
+        provenance.dataset_version
+    )?;
+    writeln!(output, "        dataset_license: {:?},", DATASET_LICENSE)?;
+    writeln!(
+        output,
+        "        generated_at: {:?},",
+        provenance.generated_at
+    )?;
+    writeln!(
+        output,
+        "        generator_version: {:?},",
+        GENERATOR_VERSION
+    )?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}")?;
+    Ok(())
+}
+
+// Continents are listed by country index; EU membership is a bitset over the same indexes
+fn generate_country_group_code(
+    countries: &Countries,
+    output: &mut dyn io::Write,
+) -> Result<(), io::Error> {
+    writeln!(output)?;
+    writeln!(
+        output,
+        "pub fn country_continent_codes() -> Vec<&'static str> {{"
+    )?;
+    write!(output, "    vec![")?;
+    for (position, country) in countries.iter().enumerate() {
+        if position % CONTINENT_CODES_PER_LINE == 0 {
+            write!(output, "\n        ")?;
+        } else {
+            write!(output, " ")?;
+        }
+        let code = match reference_continent(&country.iso3166) {
+            Some(continent) => continent.code(),
+            None => UNKNOWN_CONTINENT_CODE,
+        };
+        write!(output, "\"{}\",", code)?;
+    }
+    write!(output, "\n    ]\n")?;
+    writeln!(output, "}}")?;
+    writeln!(output)?;
+    writeln!(output, "pub fn eu_membership_data() -> Vec<u64> {{")?;
+    write!(output, "    vec![")?;
+    let mut eu_membership = vec![0u64; countries.len().div_ceil(64)];
+    countries
+        .iter()
+        .filter(|country| is_reference_eu_member(&country.iso3166))
+        .for_each(|country| eu_membership[country.index / 64] |= 1 << (country.index % 64));
+    for (position, value) in eu_membership.iter().enumerate() {
+        if (position & 0b11) == 0 {
+            write!(output, "\n        ")?;
+        } else {
+            write!(output, " ")?;
+        }
+        write!(output, "0x{:016X},", value)?;
+    }
+    write!(output, "\n    ]\n")?;
+    writeln!(output, "}}")?;
+    Ok(())
+}
+
 fn generate_country_block_code(
     name: &str,
     mut bit_queue: BitQueue,
@@ -185,16 +319,21 @@ fn write_value(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error_report::{EXIT_CODE_IO_FAILURE, EXIT_CODE_PARSE_FAILURE};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
     use lazy_static::lazy_static;
     use std::any::TypeId;
     use std::cell::RefCell;
-    use std::io::{Error, ErrorKind};
+    use std::io::{Error, ErrorKind, Write};
     use std::sync::{Arc, Mutex};
+    use std::time::{Duration, UNIX_EPOCH};
     use test_utilities::byte_array_reader_writer::{ByteArrayReader, ByteArrayWriter};
 
     struct DBIPParserMock {
         parse_params: Arc<Mutex<Vec<Vec<String>>>>,
-        parse_errors: RefCell<Vec<Vec<String>>>,
+        parse_errors: RefCell<Vec<Vec<DBIPError>>>,
         parse_results: RefCell<Vec<(FinalBitQueue, FinalBitQueue, Countries)>>,
     }
 
@@ -206,10 +345,14 @@ mod tests {
         fn parse(
             &self,
             _stdin: &mut dyn io::Read,
-            errors: &mut Vec<String>,
+            errors: &mut ErrorReport,
         ) -> (FinalBitQueue, FinalBitQueue, Countries) {
-            self.parse_params.lock().unwrap().push(errors.clone());
-            errors.extend(self.parse_errors.borrow_mut().remove(0));
+            self.parse_params.lock().unwrap().push(errors.messages());
+            self.parse_errors
+                .borrow_mut()
+                .remove(0)
+                .into_iter()
+                .for_each(|error| errors.add(error));
             self.parse_results.borrow_mut().remove(0)
         }
     }
@@ -228,10 +371,8 @@ mod tests {
             self
         }
 
-        pub fn parse_errors(self, errors: Vec<&str>) -> Self {
-            self.parse_errors
-                .borrow_mut()
-                .push(errors.into_iter().map(|s| s.to_string()).collect());
+        pub fn parse_errors(self, errors: Vec<DBIPError>) -> Self {
+            self.parse_errors.borrow_mut().push(errors);
             self
         }
 
@@ -275,6 +416,8 @@ mod tests {
     }
 
     static TEST_DATA: &str = "I represent test data arriving on standard input.";
+    // 2024-02-29T13:05:00Z
+    const GENERATION_SECONDS_SINCE_EPOCH: u64 = 1_709_211_900;
     lazy_static! {
         static ref TEST_COUNTRIES: Countries = Countries::new(vec![
             ("FR".to_string(), "France".to_string()),
@@ -325,7 +468,7 @@ mod tests {
         let parser_factory = DBIPParserFactoryMock::new()
             .make_params(&make_params_arc)
             .make_result(parser);
-        let args = vec![];
+        let args = vec!["--dataset-version".to_string(), "2024-02".to_string()];
 
         let result = ip_country(
             args.clone(),
@@ -333,6 +476,7 @@ mod tests {
             &mut stdout,
             &mut stderr,
             &parser_factory,
+            generation_time(),
         );
 
         assert_eq!(result, 0);
@@ -350,6 +494,7 @@ mod tests {
 
 use lazy_static::lazy_static;
 use crate::countries::Countries;
+use crate::metadata::DBMetadata;
 
 lazy_static! {
     pub static ref COUNTRIES: Countries = Countries::new(
@@ -364,6 +509,28 @@ lazy_static! {
     );
 }
 
+pub fn db_metadata() -> DBMetadata {
+    DBMetadata {
+        dataset: "DB-IP IP to Country Lite",
+        dataset_version: "2024-02",
+        dataset_license: "CC BY 4.0, https://db-ip.com",
+        generated_at: "2024-02-29T13:05:00Z",
+        generator_version: "{generator_version}",
+    }
+}
+
+pub fn country_continent_codes() -> Vec<&'static str> {
+    vec![
+        "--", "NA", "EU",
+    ]
+}
+
+pub fn eu_membership_data() -> Vec<u64> {
+    vec![
+        0x0000000000000004,
+    ]
+}
+
 pub fn ipv4_country_data() -> (Vec<u64>, usize) {
     (
         vec![
@@ -390,7 +557,7 @@ pub fn ipv6_country_block_count() -> usize {
         21
 }
 "#
-            .to_string()
+            .replace("{generator_version}", GENERATOR_VERSION)
         );
         assert_eq!(stderr_string, "".to_string());
     }
@@ -405,7 +572,10 @@ pub fn ipv6_country_block_count() -> usize {
         let ipv6_result = final_bit_queue(0x8877665544332211, 21);
         let parser = DBIPParserMock::new()
             .parse_params(&parse_params_arc)
-            .parse_errors(vec!["First error", "Second error"])
+            .parse_errors(vec![
+                DBIPError::validation("First error"),
+                DBIPError::parse("Second error"),
+            ])
             .parse_result((ipv4_result, ipv6_result, &TEST_COUNTRIES));
         let make_params_arc = Arc::new(Mutex::new(vec![]));
         let parser_factory = DBIPParserFactoryMock::new()
@@ -419,9 +589,10 @@ pub fn ipv6_country_block_count() -> usize {
             &mut stdout,
             &mut stderr,
             &parser_factory,
+            generation_time(),
         );
 
-        assert_eq!(result, 1);
+        assert_eq!(result, EXIT_CODE_PARSE_FAILURE);
         let make_params = make_params_arc.lock().unwrap();
         assert_eq!(*make_params, vec![args.clone()]);
         let parse_params = parse_params_arc.lock().unwrap();
@@ -436,6 +607,7 @@ pub fn ipv6_country_block_count() -> usize {
 
 use lazy_static::lazy_static;
 use crate::countries::Countries;
+use crate::metadata::DBMetadata;
 
 lazy_static! {
     pub static ref COUNTRIES: Countries = Countries::new(
@@ -450,6 +622,28 @@ lazy_static! {
     );
 }
 
+pub fn db_metadata() -> DBMetadata {
+    DBMetadata {
+        dataset: "DB-IP IP to Country Lite",
+        dataset_version: "unknown",
+        dataset_license: "CC BY 4.0, https://db-ip.com",
+        generated_at: "2024-02-29T13:05:00Z",
+        generator_version: "{generator_version}",
+    }
+}
+
+pub fn country_continent_codes() -> Vec<&'static str> {
+    vec![
+        "--", "NA", "EU",
+    ]
+}
+
+pub fn eu_membership_data() -> Vec<u64> {
+    vec![
+        0x0000000000000004,
+    ]
+}
+
 pub fn ipv4_country_data() -> (Vec<u64>, usize) {
     (
         vec![
@@ -486,12 +680,14 @@ Second error
             Fix the errors and regenerate the code.
             *** DO NOT USE THIS CODE ***
 "#
-            .to_string()
+            .replace("{generator_version}", GENERATOR_VERSION)
         );
         assert_eq!(
             stderr_string,
             r#"First error
-Second error"#
+Second error
+
+Errors: 2 (0 I/O, 1 parse, 1 validation)"#
                 .to_string()
         );
     }
@@ -504,15 +700,161 @@ Second error"#
         stdout.reject_next_write(Error::new(ErrorKind::WriteZero, "Bad file Descriptor"));
         let factory = DBIPParserFactoryReal {};
 
-        let result = ip_country(vec!["--csv".to_string()], stdin, stdout, stderr, &factory);
+        let result = ip_country(
+            vec!["--csv".to_string()],
+            stdin,
+            stdout,
+            stderr,
+            &factory,
+            generation_time(),
+        );
 
-        assert_eq!(result, 1);
+        assert_eq!(result, EXIT_CODE_IO_FAILURE);
         let stdout_string = String::from_utf8(stdout.get_bytes()).unwrap();
         let stderr_string = String::from_utf8(stderr.get_bytes()).unwrap();
-        assert_eq!(stderr_string, "Error generating Rust code: Custom { kind: WriteZero, error: \"Bad file Descriptor\" }");
+        assert_eq!(stderr_string, "Error generating Rust code: Custom { kind: WriteZero, error: \"Bad file Descriptor\" }\n\nErrors: 1 (1 I/O, 0 parse, 0 validation)");
         assert_eq!(stdout_string, "\n            *** DO NOT USE THIS CODE ***\n            It will produce incorrect results.\n            The process that generated it found these errors:\n\nError generating Rust code: Custom { kind: WriteZero, error: \"Bad file Descriptor\" }\n\n            Fix the errors and regenerate the code.\n            *** DO NOT USE THIS CODE ***\n");
     }
 
+    #[test]
+    fn conversion_gives_up_at_the_maximum_error_count() {
+        let data = "0.0.0.0,0.255.255.255,ZZ\nBOOGA\n1.0.0.0,1.0.0.255,XX\nBOOGA,BOOGA,BOOGA\n";
+        let mut stdin = ByteArrayReader::new(data.as_bytes());
+        let mut stdout = ByteArrayWriter::new();
+        let mut stderr = ByteArrayWriter::new();
+        let factory = DBIPParserFactoryReal {};
+        let args = vec![
+            "ip_country".to_string(),
+            "--csv".to_string(),
+            "--max-errors".to_string(),
+            "2".to_string(),
+        ];
+
+        let result = ip_country(
+            args,
+            &mut stdin,
+            &mut stdout,
+            &mut stderr,
+            &factory,
+            generation_time(),
+        );
+
+        assert_eq!(result, EXIT_CODE_PARSE_FAILURE);
+        assert_eq!(
+            stderr.get_string(),
+            "Line 1: CSV format error: Error(UnequalLengths { pos: Some(Position { byte: 25, line: 2, record: 1 }), expected_len: 3, len: 1 })\n\
+             Line 2: 'XX' is not a valid ISO3166 country code\n\
+             \n\
+             Errors: 2 (0 I/O, 1 parse, 1 validation); gave up after reaching the maximum of 2"
+        );
+        assert!(stdout.get_string().contains("*** DO NOT USE THIS CODE ***"));
+    }
+
+    #[test]
+    fn bad_maximum_error_count_is_a_usage_error() {
+        let bad_values: Vec<Vec<String>> = vec![
+            vec!["--max-errors".to_string()],
+            vec!["--max-errors".to_string(), "0".to_string()],
+            vec!["--max-errors".to_string(), "many".to_string()],
+        ];
+
+        bad_values.into_iter().for_each(|args| {
+            let mut stdin = ByteArrayReader::new(TEST_DATA.as_bytes());
+            let mut stdout = ByteArrayWriter::new();
+            let mut stderr = ByteArrayWriter::new();
+            // Holds no parsers, so it would panic if ip_country tried to make one
+            let parser_factory = DBIPParserFactoryMock::new();
+
+            let result = ip_country(
+                args,
+                &mut stdin,
+                &mut stdout,
+                &mut stderr,
+                &parser_factory,
+                generation_time(),
+            );
+
+            assert_eq!(result, EXIT_CODE_USAGE_ERROR);
+            assert_eq!(stdout.get_string(), "");
+            assert_eq!(
+                stderr.get_string(),
+                "--max-errors must be followed by a positive number\n"
+            );
+        });
+    }
+
+    #[test]
+    fn missing_dataset_version_is_a_usage_error() {
+        let bad_values: Vec<Vec<String>> = vec![
+            vec!["--dataset-version".to_string()],
+            vec!["--dataset-version".to_string(), "".to_string()],
+            vec!["--dataset-version".to_string(), "--csv".to_string()],
+        ];
+
+        bad_values.into_iter().for_each(|args| {
+            let mut stdin = ByteArrayReader::new(TEST_DATA.as_bytes());
+            let mut stdout = ByteArrayWriter::new();
+            let mut stderr = ByteArrayWriter::new();
+            let parser_factory = DBIPParserFactoryMock::new();
+
+            let result = ip_country(
+                args,
+                &mut stdin,
+                &mut stdout,
+                &mut stderr,
+                &parser_factory,
+                generation_time(),
+            );
+
+            assert_eq!(result, EXIT_CODE_USAGE_ERROR);
+            assert_eq!(stdout.get_string(), "");
+            assert_eq!(
+                stderr.get_string(),
+                "--dataset-version must be followed by the version of the dataset\n"
+            );
+        });
+    }
+
+    #[test]
+    fn compressed_input_generates_the_same_code_as_uncompressed_input() {
+        let csv = "0.0.0.0,0.255.255.255,ZZ\n1.0.0.0,1.0.0.255,AU\n1.0.1.0,1.0.3.255,CN\n1:0:0:0:0:0:0:0,1:0:0:255:0:0:0:0,AU\n";
+        let mut gzip_encoder = GzEncoder::new(vec![], Compression::default());
+        gzip_encoder.write_all(csv.as_bytes()).unwrap();
+        let inputs = vec![
+            csv.as_bytes().to_vec(),
+            gzip_encoder.finish().unwrap(),
+            compress_to_vec(csv.as_bytes(), CompressionLevel::Fastest),
+        ];
+        let factory = DBIPParserFactoryReal {};
+
+        let outputs = inputs
+            .into_iter()
+            .map(|input| {
+                let mut stdin = ByteArrayReader::new(&input);
+                let mut stdout = ByteArrayWriter::new();
+                let mut stderr = ByteArrayWriter::new();
+                let result = ip_country(
+                    vec!["--csv".to_string()],
+                    &mut stdin,
+                    &mut stdout,
+                    &mut stderr,
+                    &factory,
+                    generation_time(),
+                );
+                assert_eq!(result, 0, "{}", stderr.get_string());
+                stdout.get_string()
+            })
+            .collect::<Vec<String>>();
+
+        assert!(outputs[0].contains("(\"AU\", \"Australia\")"));
+        assert_eq!(outputs[1], outputs[0]);
+        assert_eq!(outputs[2], outputs[0]);
+    }
+
+    fn generation_time() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(GENERATION_SECONDS_SINCE_EPOCH)
+    }
+
     fn final_bit_queue(contents: u64, block_count: usize) -> FinalBitQueue {
         let mut bit_queue = BitQueue::new();
         bit_queue.add_bits(contents, 64);
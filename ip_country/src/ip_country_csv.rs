@@ -1,6 +1,7 @@
 use crate::countries::Countries;
 use crate::country_block_serde::{CountryBlockSerializer, FinalBitQueue};
 use crate::country_block_stream::{CountryBlock, IpRange};
+use crate::error_report::{DBIPError, ErrorReport};
 use crate::ip_country::DBIPParser;
 use csv::{StringRecord, StringRecordIter};
 use lazy_static::lazy_static;
@@ -281,84 +282,84 @@ impl DBIPParser for CSVParser {
     fn parse(
         &self,
         stdin: &mut dyn io::Read,
-        errors: &mut Vec<String>,
+        errors: &mut ErrorReport,
     ) -> (FinalBitQueue, FinalBitQueue, Countries) {
         let mut csv_rdr = csv::Reader::from_reader(stdin);
         let mut serializer = CountryBlockSerializer::new();
-        let local_errors = csv_rdr
-            .records()
-            .map(|string_record_result| match string_record_result {
+        let countries: &Countries = &HARD_CODED_COUNTRIES;
+        for (idx, string_record_result) in csv_rdr.records().enumerate() {
+            if errors.limit_reached() {
+                break;
+            }
+            let result = match string_record_result {
                 Ok(string_record) => {
-                    let countries: &Countries = &HARD_CODED_COUNTRIES;
-                    CountryBlock::try_from((countries, string_record))
+                    CountryBlock::try_from((countries, string_record)).and_then(|country_block| {
+                        serializer
+                            .try_add(country_block)
+                            .map_err(DBIPError::validation)
+                    })
                 }
-                Err(e) => Err(format!("CSV format error: {:?}", e)),
-            })
-            .enumerate()
-            .flat_map(|(idx, country_block_result)| match country_block_result {
-                Ok(country_block) => {
-                    serializer.add(country_block);
-                    None
+                Err(e) if e.is_io_error() => {
+                    // The stream is broken; there's no point in trying to read past it
+                    errors.add(DBIPError::io(format!("CSV read error: {:?}", e)).on_line(idx + 1));
+                    break;
                 }
-                Err(e) => Some(format!("Line {}: {}", idx + 1, e)),
-            })
-            .collect::<Vec<String>>();
+                Err(e) => Err(DBIPError::parse(format!("CSV format error: {:?}", e))),
+            };
+            if let Err(e) = result {
+                errors.add(e.on_line(idx + 1))
+            }
+        }
         let (final_ipv4, final_ipv6) = serializer.finish();
-        errors.extend(local_errors);
         (final_ipv4, final_ipv6, HARD_CODED_COUNTRIES.clone())
     }
 }
 
 impl TryFrom<(&Countries, StringRecord)> for CountryBlock {
-    type Error = String;
+    type Error = DBIPError;
 
     fn try_from(
         (countries, string_record): (&Countries, StringRecord),
-    ) -> Result<CountryBlock, String> {
+    ) -> Result<CountryBlock, DBIPError> {
         let mut iter = string_record.iter();
         let start_ip = ip_addr_from_iter(&mut iter)?;
         let end_ip = ip_addr_from_iter(&mut iter)?;
         let iso3166 = match iter.next() {
-            None => return Err("CSV line contains no ISO 3166 country code".to_string()),
+            None => {
+                return Err(DBIPError::parse(
+                    "CSV line contains no ISO 3166 country code",
+                ))
+            }
             Some(s) => s,
         };
         if iter.next().is_some() {
-            return Err(format!(
+            return Err(DBIPError::parse(format!(
                 "CSV line should contain 3 elements, but contains {}",
                 string_record.len()
-            ));
-        };
-        validate_ip_range(start_ip, end_ip)?;
-        let country = countries.country_from_code(iso3166)?;
-        let country_block = match (start_ip, end_ip) {
-            (IpAddr::V4(start), IpAddr::V4(end)) => CountryBlock {
-                ip_range: IpRange::V4(start, end),
-                country: country.clone(),
-            },
-            (IpAddr::V6(start), IpAddr::V6(end)) => CountryBlock {
-                ip_range: IpRange::V6(start, end),
-                country: country.clone(),
-            },
-            (start, end) => panic!(
-                "Start and end addresses must be of the same type, not {} and {}",
-                start, end
-            ),
+            )));
         };
-        Ok(country_block)
+        let ip_range = validate_ip_range(start_ip, end_ip).map_err(DBIPError::validation)?;
+        let country = countries
+            .country_from_code(iso3166)
+            .map_err(DBIPError::validation)?;
+        Ok(CountryBlock {
+            ip_range,
+            country: country.clone(),
+        })
     }
 }
 
-fn ip_addr_from_iter(iter: &mut StringRecordIter) -> Result<IpAddr, String> {
+fn ip_addr_from_iter(iter: &mut StringRecordIter) -> Result<IpAddr, DBIPError> {
     let ip_string = match iter.next() {
-        None => return Err("Missing IP address in CSV record".to_string()),
+        None => return Err(DBIPError::parse("Missing IP address in CSV record")),
         Some(s) => s,
     };
     let ip_addr = match IpAddr::from_str(ip_string) {
         Err(e) => {
-            return Err(format!(
+            return Err(DBIPError::parse(format!(
                 "Invalid ({:?}) IP address in CSV record: '{}'",
                 e, ip_string
-            ))
+            )))
         }
         Ok(ip) => ip,
     };
@@ -380,13 +381,15 @@ where
     }
 }
 
-fn validate_ip_range(start_ip: IpAddr, end_ip: IpAddr) -> Result<(), String> {
+fn validate_ip_range(start_ip: IpAddr, end_ip: IpAddr) -> Result<IpRange, String> {
     match (start_ip, end_ip) {
         (IpAddr::V4(start_v4), IpAddr::V4(end_v4)) => {
-            validate_ips_are_sequential::<u32, Ipv4Addr>(start_v4, end_v4)
+            validate_ips_are_sequential::<u32, Ipv4Addr>(start_v4, end_v4)?;
+            Ok(IpRange::V4(start_v4, end_v4))
         }
         (IpAddr::V6(start_v6), IpAddr::V6(end_v6)) => {
-            validate_ips_are_sequential::<u128, Ipv6Addr>(start_v6, end_v6)
+            validate_ips_are_sequential::<u128, Ipv6Addr>(start_v6, end_v6)?;
+            Ok(IpRange::V6(start_v6, end_v6))
         }
         (s, e) => Err(format!(
             "Beginning address {} and ending address {} must be the same IP address version",
@@ -399,6 +402,10 @@ fn validate_ip_range(start_ip: IpAddr, end_ip: IpAddr) -> Result<(), String> {
 mod tests {
     use super::*;
     use crate::country_block_stream::Country;
+    use crate::error_report::{
+        ErrorCategory, EXIT_CODE_IO_FAILURE, EXIT_CODE_PARSE_FAILURE, EXIT_CODE_SUCCESS,
+        EXIT_CODE_VALIDATION_FAILURE,
+    };
     use std::cmp::min;
     use test_utilities::byte_array_reader_writer::ByteArrayReader;
 
@@ -451,13 +458,14 @@ BOOGA,BOOGA,BOOGA
     #[test]
     fn happy_path_test() {
         let mut stdin = ByteArrayReader::new(PROPER_TEST_DATA.as_bytes());
-        let mut errors = vec![];
+        let mut errors = ErrorReport::new(None);
         let subject = CSVParser {};
 
         let (ipv4_bit_queue, ipv6_bit_queue, countries) = subject.parse(&mut stdin, &mut errors);
 
         let expected_errors: Vec<String> = vec![];
-        assert_eq!(errors, expected_errors);
+        assert_eq!(errors.messages(), expected_errors);
+        assert_eq!(errors.exit_code(), EXIT_CODE_SUCCESS);
         assert_eq!(countries, HARD_CODED_COUNTRIES.clone());
         assert_eq!(ipv4_bit_queue.bit_queue.len(), 271);
         assert_eq!(ipv4_bit_queue.block_count, 11);
@@ -509,7 +517,7 @@ BOOGA,BOOGA,BOOGA
     #[test]
     fn sad_path_test() {
         let mut stdin = ByteArrayReader::new(BAD_TEST_DATA.as_bytes());
-        let mut errors = vec![];
+        let mut errors = ErrorReport::new(None);
         let subject = CSVParser {};
 
         let (ipv4_bit_queue, ipv6_bit_queue, countries) = subject.parse(&mut stdin, &mut errors);
@@ -559,7 +567,7 @@ BOOGA,BOOGA,BOOGA
                 122954
             ]
         );
-        assert_eq!(errors, vec![
+        assert_eq!(errors.messages(), vec![
             "Line 3: CSV format error: Error(UnequalLengths { pos: Some(Position { byte: 67, line: 4, record: 3 }), expected_len: 3, len: 2 })",
             "Line 4: CSV format error: Error(UnequalLengths { pos: Some(Position { byte: 80, line: 5, record: 4 }), expected_len: 3, len: 2 })",
             "Line 5: CSV format error: Error(UnequalLengths { pos: Some(Position { byte: 99, line: 6, record: 5 }), expected_len: 3, len: 4 })",
@@ -567,6 +575,85 @@ BOOGA,BOOGA,BOOGA
             "Line 7: Ending address 1.0.32.0 is less than starting address 1.0.63.255",
             "Line 17: Invalid (AddrParseError(Ip)) IP address in CSV record: 'BOOGA'",
         ]);
+        assert_eq!(
+            errors
+                .errors()
+                .iter()
+                .map(|error| error.category)
+                .collect::<Vec<ErrorCategory>>(),
+            vec![
+                ErrorCategory::Parse,
+                ErrorCategory::Parse,
+                ErrorCategory::Parse,
+                ErrorCategory::Parse,
+                ErrorCategory::Validation,
+                ErrorCategory::Parse,
+            ]
+        );
+        assert_eq!(errors.exit_code(), EXIT_CODE_PARSE_FAILURE);
+    }
+
+    #[test]
+    fn parsing_gives_up_at_the_maximum_error_count() {
+        let mut stdin = ByteArrayReader::new(BAD_TEST_DATA.as_bytes());
+        let mut errors = ErrorReport::new(Some(2));
+        let subject = CSVParser {};
+
+        let (ipv4_bit_queue, ipv6_bit_queue, _) = subject.parse(&mut stdin, &mut errors);
+
+        assert_eq!(errors.messages(), vec![
+            "Line 3: CSV format error: Error(UnequalLengths { pos: Some(Position { byte: 67, line: 4, record: 3 }), expected_len: 3, len: 2 })",
+            "Line 4: CSV format error: Error(UnequalLengths { pos: Some(Position { byte: 80, line: 5, record: 4 }), expected_len: 3, len: 2 })",
+        ]);
+        assert!(errors.limit_reached());
+        // Only the two good lines before the errors made it in, padded out with ZZ blocks
+        assert_eq!(ipv4_bit_queue.block_count, 4);
+        assert_eq!(ipv6_bit_queue.block_count, 0);
+    }
+
+    #[test]
+    fn out_of_order_lines_are_validation_errors_rather_than_panics() {
+        let data = "0.0.0.0,0.255.255.255,ZZ
+1.0.0.0,1.0.0.255,AU
+1.0.0.128,1.0.1.255,CN
+0.0.0.0,0.0.0.255,JP
+1.0.2.0,1.0.2.255,TH
+";
+        let mut stdin = ByteArrayReader::new(data.as_bytes());
+        let mut errors = ErrorReport::new(None);
+        let subject = CSVParser {};
+
+        let (ipv4_bit_queue, _, _) = subject.parse(&mut stdin, &mut errors);
+
+        assert_eq!(
+            errors.errors(),
+            &[
+                DBIPError::validation(
+                    "Line 2: Block starting at 1.0.0.128 is out of order: the block before it \
+                     ends at 1.0.0.255"
+                ),
+                DBIPError::validation(
+                    "Line 3: Block starting at 0.0.0.0 is out of order: the block before it \
+                     ends at 1.0.0.255"
+                ),
+            ]
+        );
+        assert_eq!(errors.exit_code(), EXIT_CODE_VALIDATION_FAILURE);
+        assert_eq!(ipv4_bit_queue.block_count, 5);
+    }
+
+    #[test]
+    fn broken_stream_is_an_io_error_that_stops_parsing() {
+        let mut stdin = ByteArrayReader::new(PROPER_TEST_DATA.as_bytes())
+            .reject_next_read(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"));
+        let mut errors = ErrorReport::new(None);
+        let subject = CSVParser {};
+
+        let _ = subject.parse(&mut stdin, &mut errors);
+
+        assert_eq!(errors.errors().len(), 1);
+        assert_eq!(errors.errors()[0].category, ErrorCategory::Io);
+        assert_eq!(errors.exit_code(), EXIT_CODE_IO_FAILURE);
     }
 
     fn test_countries() -> Countries {
@@ -585,7 +672,9 @@ BOOGA,BOOGA,BOOGA
 
         assert_eq!(
             result,
-            Err("CSV line contains no ISO 3166 country code".to_string())
+            Err(DBIPError::parse(
+                "CSV line contains no ISO 3166 country code"
+            ))
         );
     }
 
@@ -597,7 +686,9 @@ BOOGA,BOOGA,BOOGA
 
         assert_eq!(
             result,
-            Err("CSV line should contain 3 elements, but contains 4".to_string())
+            Err(DBIPError::parse(
+                "CSV line should contain 3 elements, but contains 4"
+            ))
         );
     }
 
@@ -649,7 +740,9 @@ BOOGA,BOOGA,BOOGA
 
         assert_eq!(
             result,
-            Err("Invalid (AddrParseError(Ip)) IP address in CSV record: 'Ooga'".to_string())
+            Err(DBIPError::parse(
+                "Invalid (AddrParseError(Ip)) IP address in CSV record: 'Ooga'"
+            ))
         );
     }
 
@@ -660,7 +753,10 @@ BOOGA,BOOGA,BOOGA
 
         let result = CountryBlock::try_from((&test_countries(), string_record));
 
-        assert_eq!(result, Err("Missing IP address in CSV record".to_string()));
+        assert_eq!(
+            result,
+            Err(DBIPError::parse("Missing IP address in CSV record"))
+        );
     }
 
     #[test]
@@ -671,7 +767,7 @@ BOOGA,BOOGA,BOOGA
             .err()
             .unwrap();
 
-        assert_eq!(result, "Missing IP address in CSV record".to_string());
+        assert_eq!(result, DBIPError::parse("Missing IP address in CSV record"));
     }
 
     #[test]
@@ -682,7 +778,9 @@ BOOGA,BOOGA,BOOGA
 
         assert_eq!(
             result,
-            Err("Ending address 1.2.3.3 is less than starting address 1.2.3.4".to_string())
+            Err(DBIPError::validation(
+                "Ending address 1.2.3.3 is less than starting address 1.2.3.4"
+            ))
         );
     }
 
@@ -694,10 +792,9 @@ BOOGA,BOOGA,BOOGA
 
         assert_eq!(
             result,
-            Err(
+            Err(DBIPError::validation(
                 "Ending address 1:2:3:4:5:6:7:7 is less than starting address 1:2:3:4:5:6:7:8"
-                    .to_string()
-            )
+            ))
         );
     }
 
@@ -709,8 +806,8 @@ BOOGA,BOOGA,BOOGA
         let result_46 = CountryBlock::try_from((&test_countries(), string_record_46));
         let result_64 = CountryBlock::try_from((&test_countries(), string_record_64));
 
-        assert_eq!(result_46, Err("Beginning address 4.3.2.1 and ending address 1:2:3:4:5:6:7:8 must be the same IP address version".to_string()));
-        assert_eq!(result_64, Err("Beginning address 1:2:3:4:5:6:7:8 and ending address 4.3.2.1 must be the same IP address version".to_string()));
+        assert_eq!(result_46, Err(DBIPError::validation("Beginning address 4.3.2.1 and ending address 1:2:3:4:5:6:7:8 must be the same IP address version")));
+        assert_eq!(result_64, Err(DBIPError::validation("Beginning address 1:2:3:4:5:6:7:8 and ending address 4.3.2.1 must be the same IP address version")));
     }
 
     #[test]
@@ -721,7 +818,9 @@ BOOGA,BOOGA,BOOGA
 
         assert_eq!(
             result,
-            Err("'XY' is not a valid ISO3166 country code".to_string())
+            Err(DBIPError::validation(
+                "'XY' is not a valid ISO3166 country code"
+            ))
         );
     }
 
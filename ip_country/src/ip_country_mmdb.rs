@@ -1,6 +1,7 @@
 use crate::countries::Countries;
 use crate::country_block_serde::{CountryBlockSerializer, FinalBitQueue};
 use crate::country_block_stream::{are_consecutive, Country, CountryBlock, IpRange};
+use crate::error_report::{DBIPError, ErrorReport};
 use crate::ip_country::DBIPParser;
 use ipnetwork::{IpNetwork, Ipv6Network};
 use itertools::Itertools;
@@ -27,19 +28,19 @@ impl DBIPParser for MMDBParser {
     fn parse(
         &self,
         stdin: &mut dyn io::Read,
-        errors: &mut Vec<String>,
+        errors: &mut ErrorReport,
     ) -> (FinalBitQueue, FinalBitQueue, Countries) {
         let mut bytes: Vec<u8> = vec![];
         match stdin.read_to_end(&mut bytes) {
             Ok(_) => {}
             Err(e) => {
-                errors.push(format!("Error reading from stdin: {}", e));
+                errors.add(DBIPError::io(format!("Error reading from stdin: {}", e)));
             }
         };
         let reader = match Reader::from_source(bytes) {
             Ok(r) => r,
             Err(e) => {
-                errors.push(format!("Error opening MaxMind DB: {}", e));
+                errors.add(DBIPError::parse(format!("Error opening MaxMind DB: {}", e)));
                 return (
                     FinalBitQueue::default(),
                     FinalBitQueue::default(),
@@ -53,7 +54,10 @@ impl DBIPParser for MMDBParser {
         let ip_ranges = match reader.within::<City>(IpNetwork::V6(ip_network)) {
             Ok(w) => Self::extract_data(w, &mut country_pairs, errors),
             Err(e) => {
-                errors.push(format!("Error creating within iterator: {}", e));
+                errors.add(DBIPError::parse(format!(
+                    "Error creating within iterator: {}",
+                    e
+                )));
                 vec![]
             }
         };
@@ -71,10 +75,10 @@ impl DBIPParser for MMDBParser {
                             country: country.clone(),
                         },
                         Err(e) => {
-                            errors.push(format!(
+                            errors.add(DBIPError::validation(format!(
                                 "Error finding country from code {} for IP range {:?}: {}",
                                 code, ip_range, e
-                            ));
+                            )));
                             CountryBlock {
                                 ip_range,
                                 country: Country::new(0, "ZZ", "Unknown"),
@@ -86,9 +90,11 @@ impl DBIPParser for MMDBParser {
         };
         let mut serializer = CountryBlockSerializer::new();
         let country_blocks = make_country_blocks(ip_ranges);
-        country_blocks
-            .into_iter()
-            .for_each(|block| serializer.add(block));
+        country_blocks.into_iter().for_each(|block| {
+            if let Err(e) = serializer.try_add(block) {
+                errors.add(DBIPError::validation(e))
+            }
+        });
         let (ipv4_bit_queue, ipv6_bit_queue) = serializer.finish();
 
         (ipv4_bit_queue, ipv6_bit_queue, countries)
@@ -103,7 +109,7 @@ impl MMDBParser {
     fn extract_data<'de>(
         within: Within<'de, City<'de>, Vec<u8>>,
         country_pairs: &mut HashSet<(String, String)>,
-        errors: &mut Vec<String>,
+        errors: &mut ErrorReport,
     ) -> Vec<(String, IpRange)> {
         let mut coded_ranges: Vec<(String, IpRange)> = vec![];
         let mut add_or_coalesce = |cur_code: &str, cur_range: IpRange| {
@@ -128,47 +134,64 @@ impl MMDBParser {
                 coded_ranges.push((cur_code.to_string(), cur_range));
             }
         };
-        within.for_each(|item_result| {
+        for item_result in within {
+            if errors.limit_reached() {
+                break;
+            }
             match item_result {
                 Ok(item) => {
                     let ip_range = Self::ipn_to_range(item.ip_net);
                     match item.info.country {
                         Some(country) => {
-                            match (country.iso_code, country.names.map(|ns| ns.get("en").map(|n| n.to_string()))) {
+                            match (
+                                country.iso_code,
+                                country.names.map(|ns| ns.get("en").map(|n| n.to_string())),
+                            ) {
                                 (Some(code), Some(Some(name))) => {
                                     country_pairs.insert((code.to_string(), name));
                                     add_or_coalesce(code, ip_range);
                                 }
                                 (Some(code), _) => {
-                                    errors.push(format!("Country code {:?} found but no name - using 'Unknown'", code));
+                                    errors.add(DBIPError::validation(format!(
+                                        "Country code {:?} found but no name - using 'Unknown'",
+                                        code
+                                    )));
                                     country_pairs.insert((code.to_string(), "Unknown".to_string()));
                                     add_or_coalesce(code, ip_range);
                                 }
                                 (None, Some(Some(name))) => {
-                                    errors.push(format!("Country code not found for country: {:?} - using Sentinel", name));
-                                    country_pairs.insert(("ZZ".to_string(), "Sentinel".to_string()));
+                                    errors.add(DBIPError::validation(format!(
+                                        "Country code not found for country: {:?} - using Sentinel",
+                                        name
+                                    )));
+                                    country_pairs
+                                        .insert(("ZZ".to_string(), "Sentinel".to_string()));
                                     add_or_coalesce("ZZ", ip_range);
                                 }
                                 (None, _) => {
-                                    errors.push(format!("Country code and name not found for range: {:?} - using Sentinel", item.ip_net));
-                                    country_pairs.insert(("ZZ".to_string(), "Sentinel".to_string()));
+                                    errors.add(DBIPError::validation(format!("Country code and name not found for range: {:?} - using Sentinel", item.ip_net)));
+                                    country_pairs
+                                        .insert(("ZZ".to_string(), "Sentinel".to_string()));
                                     add_or_coalesce("ZZ", ip_range);
                                 }
                             }
                         }
                         None => {
-                            errors.push(format!("No country information found for range: {:?} - using Sentinel", item.ip_net));
+                            errors.add(DBIPError::validation(format!(
+                                "No country information found for range: {:?} - using Sentinel",
+                                item.ip_net
+                            )));
                             country_pairs.insert(("ZZ".to_string(), "Sentinel".to_string()));
                             let ip_range = Self::ipn_to_range(item.ip_net);
                             add_or_coalesce("ZZ", ip_range);
-                        },
+                        }
                     }
-                },
+                }
                 Err(e) => {
-                    errors.push(format!("Error processing item: {}", e));
+                    errors.add(DBIPError::parse(format!("Error processing item: {}", e)));
                 }
             }
-        });
+        }
         coded_ranges
     }
 
@@ -185,6 +208,7 @@ mod tests {
     use super::*;
     use crate::country_block_serde::{Ipv4CountryBlockDeserializer, Ipv6CountryBlockDeserializer};
     use crate::country_finder::CountryCodeFinder;
+    use crate::error_report::{EXIT_CODE_IO_FAILURE, EXIT_CODE_PARSE_FAILURE};
     use std::cmp::min;
     use std::fs::File;
     use std::io::Read;
@@ -225,14 +249,15 @@ mod tests {
             delegate: Box::new(delegate),
         };
         let subject = MMDBParser::new();
-        let mut errors = vec![];
+        let mut errors = ErrorReport::new(None);
 
         let result = subject.parse(&mut stdin, &mut errors);
 
         assert_eq!(
-            errors,
+            errors.messages(),
             vec!["Error reading from stdin: broken pipe".to_string()]
         );
+        assert_eq!(errors.exit_code(), EXIT_CODE_IO_FAILURE);
         let country_code_finder = CountryCodeFinder::new(
             &result.2,
             country_data_from_bit_queue(result.0),
@@ -267,14 +292,15 @@ mod tests {
         let file = PathBuf::from("data/improperly-formatted.mmdb");
         let mut stdin = File::open(&file).unwrap();
         let subject = MMDBParser::new();
-        let mut errors = vec![];
+        let mut errors = ErrorReport::new(None);
 
         let result = subject.parse(&mut stdin, &mut errors);
 
         assert_eq!(
-            errors,
+            errors.messages(),
             vec!["Error opening MaxMind DB: Invalid database: Could not find MaxMind DB metadata in file.".to_string()]
         );
+        assert_eq!(errors.exit_code(), EXIT_CODE_PARSE_FAILURE);
         assert_eq!(result.0.block_count, 0);
         assert_eq!(result.1.block_count, 0);
         assert_eq!(result.2.len(), 1); // ZZ only
@@ -288,23 +314,42 @@ mod tests {
         let file = PathBuf::from("data/corrupted.mmdb");
         let mut stdin = File::open(&file).unwrap();
         let subject = MMDBParser::new();
-        let mut errors = vec![];
+        let mut errors = ErrorReport::new(None);
 
         let result = subject.parse(&mut stdin, &mut errors);
 
         assert_eq!(
-            errors,
+            errors.messages(),
             vec![
                 "Error processing item: Invalid database: the MaxMind DB file's data pointer resolves to an invalid location".to_string(),
                 "Error processing item: Invalid database: the MaxMind DB file's data pointer resolves to an invalid location".to_string(),
                 "Error processing item: Invalid database: the MaxMind DB file's data pointer resolves to an invalid location".to_string(),
             ]
         );
+        assert_eq!(errors.exit_code(), EXIT_CODE_PARSE_FAILURE);
         assert_eq!(result.0.block_count, 3);
         assert_eq!(result.1.block_count, 3);
         assert_eq!(result.2.len(), 3);
     }
 
+    #[test]
+    fn corrupted_with_maximum_error_count() {
+        let file = PathBuf::from("data/corrupted.mmdb");
+        let mut stdin = File::open(&file).unwrap();
+        let subject = MMDBParser::new();
+        let mut errors = ErrorReport::new(Some(1));
+
+        let _ = subject.parse(&mut stdin, &mut errors);
+
+        assert_eq!(
+            errors.messages(),
+            vec![
+                "Error processing item: Invalid database: the MaxMind DB file's data pointer resolves to an invalid location".to_string(),
+            ]
+        );
+        assert!(errors.limit_reached());
+    }
+
     #[test]
     fn happy_path() {
         /*
@@ -316,7 +361,7 @@ mod tests {
         let file = PathBuf::from("data/country-scratch-out.mmdb");
         let mut stdin = File::open(&file).unwrap();
         let subject = MMDBParser::new();
-        let mut errors = vec![];
+        let mut errors = ErrorReport::new(None);
 
         let (ipv4_bits, ipv6_bits, countries) = subject.parse(&mut stdin, &mut errors);
 
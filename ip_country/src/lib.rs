@@ -1,12 +1,25 @@
 // Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 pub mod bit_queue;
+pub mod continents;
 pub mod countries;
 pub mod country_block_serde;
 pub mod country_block_stream;
 pub mod country_finder;
+pub mod country_groups;
+pub mod decompression;
+pub mod download;
+pub mod error_report;
 pub mod ip_country;
 pub mod ip_country_csv;
 pub mod ip_country_mmdb;
+pub mod metadata;
 #[rustfmt::skip]
 pub mod dbip_country;
+
+use crate::metadata::DBMetadata;
+
+// Provenance of the country data compiled into this build
+pub fn db_metadata() -> DBMetadata {
+    dbip_country::db_metadata()
+}
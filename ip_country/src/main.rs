@@ -1,17 +1,31 @@
 // Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use ip_country_lib::download::{download_and_convert, DownloaderReal, DOWNLOAD_SUBCOMMAND};
 use ip_country_lib::ip_country::ip_country;
 use ip_country_lib::ip_country::DBIPParserFactoryReal;
 use std::env;
 use std::io;
 use std::process;
+use std::time::SystemTime;
 
 pub fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(|arg| arg.as_str()) == Some(DOWNLOAD_SUBCOMMAND) {
+        process::exit(download_and_convert(
+            args,
+            &mut io::stdout(),
+            &mut io::stderr(),
+            &DownloaderReal {},
+            &DBIPParserFactoryReal {},
+            SystemTime::now(),
+        ))
+    }
     process::exit(ip_country(
-        env::args().collect(),
+        args,
         &mut io::stdin(),
         &mut io::stdout(),
         &mut io::stderr(),
         &DBIPParserFactoryReal {},
+        SystemTime::now(),
     ))
 }
@@ -0,0 +1,127 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DATASET_NAME: &str = "DB-IP IP to Country Lite";
+// The dataset is licensed apart from the code it's embedded in, and its license demands attribution
+pub const DATASET_LICENSE: &str = "CC BY 4.0, https://db-ip.com";
+pub const UNKNOWN_DATASET_VERSION: &str = "unknown";
+pub const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Where the embedded country data came from, so that an exit-country decision can be traced back
+// to the revision of the dataset it was based on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DBMetadata {
+    pub dataset: &'static str,
+    pub dataset_version: &'static str,
+    pub dataset_license: &'static str,
+    pub generated_at: &'static str,
+    pub generator_version: &'static str,
+}
+
+impl Display for DBMetadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} ({}), generated {} by ip_country {}",
+            self.dataset,
+            self.dataset_version,
+            self.dataset_license,
+            self.generated_at,
+            self.generator_version
+        )
+    }
+}
+
+// As in 2024-02-29T13:05:00Z
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = seconds_since_epoch(time);
+    let (year, month, day) = civil_date(time);
+    let second_of_day = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        second_of_day / 3_600,
+        second_of_day % 3_600 / 60,
+        second_of_day % 60
+    )
+}
+
+// Year, month and day in UTC, after Howard Hinnant's algorithm
+pub fn civil_date(time: SystemTime) -> (i64, u32, u32) {
+    let shifted_days = (seconds_since_epoch(time) / 86_400) as i64 + 719_468;
+    let era = shifted_days / 146_097;
+    let day_of_era = shifted_days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32, day as u32)
+}
+
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .expect("Clock is set before the epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DATASET_NAME, "DB-IP IP to Country Lite");
+        assert_eq!(DATASET_LICENSE, "CC BY 4.0, https://db-ip.com");
+        assert_eq!(UNKNOWN_DATASET_VERSION, "unknown");
+        assert_eq!(GENERATOR_VERSION, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn metadata_is_displayed_in_one_line() {
+        let subject = DBMetadata {
+            dataset: DATASET_NAME,
+            dataset_version: "2024-02",
+            dataset_license: DATASET_LICENSE,
+            generated_at: "2024-02-29T13:05:00Z",
+            generator_version: "1.2.3",
+        };
+
+        let result = subject.to_string();
+
+        assert_eq!(
+            result,
+            "DB-IP IP to Country Lite 2024-02 (CC BY 4.0, https://db-ip.com), generated 2024-02-29T13:05:00Z by ip_country 1.2.3"
+        );
+    }
+
+    #[test]
+    fn civil_date_is_computed_from_the_time() {
+        let at = |seconds: u64| civil_date(UNIX_EPOCH + Duration::from_secs(seconds));
+
+        assert_eq!(at(0), (1970, 1, 1));
+        assert_eq!(at(951_782_400), (2000, 2, 29));
+        assert_eq!(at(1_704_067_199), (2023, 12, 31));
+        assert_eq!(at(1_704_067_200), (2024, 1, 1));
+        assert_eq!(at(1_709_164_800), (2024, 2, 29));
+        assert_eq!(at(1_709_251_200), (2024, 3, 1));
+    }
+
+    #[test]
+    fn utc_timestamp_includes_the_time_of_day() {
+        let result = utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_709_211_900));
+
+        assert_eq!(result, "2024-02-29T13:05:00Z");
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::command_factory::CommandFactoryError::{CommandSyntax, UnrecognizedSubcommand};
+use crate::commands::blockchain_self_test_command::BlockchainSelfTestCommand;
+use crate::commands::blockchain_stats_command::BlockchainStatsCommand;
 use crate::commands::change_password_command::ChangePasswordCommand;
 use crate::commands::check_password_command::CheckPasswordCommand;
 use crate::commands::commands_common::Command;
@@ -17,6 +19,8 @@ use crate::commands::set_configuration_command::SetConfigurationCommand;
 use crate::commands::setup_command::SetupCommand;
 use crate::commands::shutdown_command::ShutdownCommand;
 use crate::commands::start_command::StartCommand;
+use crate::commands::sweep_wallet_command::SweepWalletCommand;
+use crate::commands::unlock_wallet_command::UnlockWalletCommand;
 use crate::commands::wallet_addresses_command::WalletAddressesCommand;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -35,6 +39,8 @@ pub struct CommandFactoryReal;
 impl CommandFactory for CommandFactoryReal {
     fn make(&self, pieces: &[String]) -> Result<Box<dyn Command>, CommandFactoryError> {
         let boxed_command: Box<dyn Command> = match pieces[0].as_str() {
+            "blockchain-self-test" => Box::new(BlockchainSelfTestCommand::new()),
+            "blockchain-stats" => Box::new(BlockchainStatsCommand::new()),
             "change-password" => match ChangePasswordCommand::new_change(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
@@ -87,6 +93,14 @@ impl CommandFactory for CommandFactoryReal {
             },
             "shutdown" => Box::new(ShutdownCommand::new()),
             "start" => Box::new(StartCommand::new()),
+            "sweep-wallet" => match SweepWalletCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "unlock-wallet" => match UnlockWalletCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "wallet-addresses" => match WalletAddressesCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
@@ -0,0 +1,148 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{transaction, Command, CommandError};
+use clap::{App, SubCommand};
+use masq_lib::messages::{UiBlockchainSelfTestRequest, UiBlockchainSelfTestResponse};
+use masq_lib::{as_any_ref_in_trait_impl, short_writeln};
+use std::fmt::Debug;
+
+// Every query may take the whole of the Node's blockchain request timeout
+const BLOCKCHAIN_SELF_TEST_COMMAND_TIMEOUT_MILLIS: u64 = 60000;
+const BLOCKCHAIN_SELF_TEST_SUBCOMMAND_ABOUT: &str =
+    "Runs a battery of read-only queries against the blockchain service the Node is configured \
+     with and reports how each went and how long it took. Useful when payments aren't happening. \
+     Only valid if Node is already running.";
+
+#[derive(Debug)]
+pub struct BlockchainSelfTestCommand {}
+
+pub fn blockchain_self_test_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("blockchain-self-test").about(BLOCKCHAIN_SELF_TEST_SUBCOMMAND_ABOUT)
+}
+
+impl Command for BlockchainSelfTestCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiBlockchainSelfTestRequest {};
+        let output: Result<UiBlockchainSelfTestResponse, CommandError> =
+            transaction(input, context, BLOCKCHAIN_SELF_TEST_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                response.steps.iter().for_each(|step| {
+                    short_writeln!(
+                        context.stdout(),
+                        "{}: {} ({} ms) {}",
+                        step.name,
+                        if step.passed { "PASS" } else { "FAIL" },
+                        step.latency_ms,
+                        step.detail
+                    )
+                });
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Blockchain self-test failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+    as_any_ref_in_trait_impl!();
+}
+
+impl Default for BlockchainSelfTestCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockchainSelfTestCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError::ConnectionDropped;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::{ToMessageBody, UiSelfTestStep};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(BLOCKCHAIN_SELF_TEST_COMMAND_TIMEOUT_MILLIS, 60000);
+        assert_eq!(
+            BLOCKCHAIN_SELF_TEST_SUBCOMMAND_ABOUT,
+            "Runs a battery of read-only queries against the blockchain service the Node is \
+             configured with and reports how each went and how long it took. Useful when \
+             payments aren't happening. Only valid if Node is already running."
+        );
+    }
+
+    #[test]
+    fn blockchain_self_test_command_reports_every_step() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBlockchainSelfTestResponse {
+                steps: vec![
+                    UiSelfTestStep {
+                        name: "Chain id".to_string(),
+                        passed: true,
+                        latency_ms: 45,
+                        detail: "Chain id 137".to_string(),
+                    },
+                    UiSelfTestStep {
+                        name: "Token transfer logs".to_string(),
+                        passed: false,
+                        latency_ms: 5001,
+                        detail: "Blockchain error: No response within 5000 ms".to_string(),
+                    },
+                ],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let factory = CommandFactoryReal::new();
+        let subject = factory.make(&["blockchain-self-test".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Chain id: PASS (45 ms) Chain id 137\n\
+             Token transfer logs: FAIL (5001 ms) Blockchain error: No response within 5000 ms\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBlockchainSelfTestRequest {}.tmb(0),
+                BLOCKCHAIN_SELF_TEST_COMMAND_TIMEOUT_MILLIS
+            )]
+        )
+    }
+
+    #[test]
+    fn blockchain_self_test_command_handles_send_failure() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BlockchainSelfTestCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Blockchain self-test failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
+}
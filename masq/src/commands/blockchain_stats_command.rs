@@ -0,0 +1,193 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{UiBlockchainStatsRequest, UiBlockchainStatsResponse};
+use masq_lib::{as_any_ref_in_trait_impl, short_writeln};
+use std::fmt::Debug;
+
+const BLOCKCHAIN_STATS_SUBCOMMAND_ABOUT: &str =
+    "Displays how many requests the Node has sent to its blockchain service each day (UTC) of \
+     the last month, next to the daily quota configured with --rpc-daily-quota. Only valid if \
+     Node is already running.";
+
+#[derive(Debug)]
+pub struct BlockchainStatsCommand {}
+
+pub fn blockchain_stats_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("blockchain-stats").about(BLOCKCHAIN_STATS_SUBCOMMAND_ABOUT)
+}
+
+impl Command for BlockchainStatsCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiBlockchainStatsRequest {};
+        let output: Result<UiBlockchainStatsResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                match response.daily_quota {
+                    0 => short_writeln!(context.stdout(), "Daily quota: none"),
+                    quota => short_writeln!(context.stdout(), "Daily quota: {} requests", quota),
+                }
+                if response.usage.is_empty() {
+                    short_writeln!(context.stdout(), "No requests have been recorded.");
+                    return Ok(());
+                }
+                short_writeln!(
+                    context.stdout(),
+                    "{:<10}   {:>10}   {:>12}   {}",
+                    "Day",
+                    "Requests",
+                    "Weight",
+                    "Endpoint"
+                );
+                response.usage.iter().for_each(|usage| {
+                    short_writeln!(
+                        context.stdout(),
+                        "{:<10}   {:>10}   {:>12}   {}",
+                        usage.day,
+                        usage.calls,
+                        usage.weight,
+                        usage.endpoint
+                    )
+                });
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(
+                    context.stderr(),
+                    "Blockchain stats retrieval failed: {:?}",
+                    e
+                );
+                Err(e)
+            }
+        }
+    }
+    as_any_ref_in_trait_impl!();
+}
+
+impl Default for BlockchainStatsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockchainStatsCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError::ConnectionDropped;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::{ToMessageBody, UiRpcUsage};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            BLOCKCHAIN_STATS_SUBCOMMAND_ABOUT,
+            "Displays how many requests the Node has sent to its blockchain service each day \
+             (UTC) of the last month, next to the daily quota configured with --rpc-daily-quota. \
+             Only valid if Node is already running."
+        );
+    }
+
+    #[test]
+    fn blockchain_stats_command_reports_the_quota_and_the_usage_by_day() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBlockchainStatsResponse {
+                daily_quota: 100_000,
+                usage: vec![
+                    UiRpcUsage {
+                        endpoint: "https://rpc.example.com/*****".to_string(),
+                        day: "2024-03-02".to_string(),
+                        calls: 81_234,
+                        weight: 1_702_115,
+                    },
+                    UiRpcUsage {
+                        endpoint: "https://rpc.example.com/*****".to_string(),
+                        day: "2024-03-01".to_string(),
+                        calls: 456,
+                        weight: 9_870,
+                    },
+                ],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let factory = CommandFactoryReal::new();
+        let subject = factory.make(&["blockchain-stats".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Daily quota: 100000 requests\n\
+             Day            Requests         Weight   Endpoint\n\
+             2024-03-02        81234        1702115   https://rpc.example.com/*****\n\
+             2024-03-01          456           9870   https://rpc.example.com/*****\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBlockchainStatsRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        )
+    }
+
+    #[test]
+    fn blockchain_stats_command_reports_no_quota_and_no_usage() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiBlockchainStatsResponse {
+                daily_quota: 0,
+                usage: vec![],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BlockchainStatsCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Daily quota: none\n\
+             No requests have been recorded.\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn blockchain_stats_command_handles_send_failure() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BlockchainStatsCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Blockchain stats retrieval failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
+}
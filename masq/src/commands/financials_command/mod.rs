@@ -20,14 +20,15 @@ use crate::commands::financials_command::parsing_and_value_dressing::restricted:
 };
 use crate::commands::financials_command::pretty_print_utils::restricted::process_gwei_into_requested_format;
 use crate::commands::financials_command::pretty_print_utils::restricted::{
-    financial_status_totals_title, main_title_for_tops_opt, no_records_found, prepare_metadata,
+    approximate_fiat_totals_title, debt_aging_title, financial_status_totals_title,
+    main_title_for_tops_opt, no_records_found, observe_only_title, prepare_metadata,
     render_accounts_generic, subtitle_for_tops, title_for_custom_query,
     triple_or_single_blank_line, StringValuesFormattableAccount,
 };
 use clap::ArgMatches;
 use masq_lib::messages::{
-    CustomQueries, QueryResults, RangeQuery, TopRecordsConfig, UiFinancialStatistics,
-    UiFinancialsRequest, UiFinancialsResponse,
+    CustomQueries, QueryResults, RangeQuery, TopRecordsConfig, UiAgingBuckets, UiDebtAging,
+    UiFinancialStatistics, UiFinancialsRequest, UiFinancialsResponse,
 };
 use masq_lib::short_writeln;
 use masq_lib::utils::ExpectValue;
@@ -135,6 +136,62 @@ impl FinancialsCommand {
             gwei_flag,
             stdout
         );
+        if let Some(fiat) = stats.approximate_fiat_opt.as_ref() {
+            approximate_fiat_totals_title(stdout, &fiat.currency);
+            dump_parameter_line(
+                stdout,
+                "Unpaid and pending payable:",
+                &fiat.total_unpaid_and_pending_payable,
+            );
+            dump_parameter_line(stdout, "Paid payable:", &fiat.total_paid_payable);
+            dump_parameter_line(stdout, "Unpaid receivable:", &fiat.total_unpaid_receivable);
+            dump_parameter_line(stdout, "Paid receivable:", &fiat.total_paid_receivable);
+        }
+        if let Some(observed) = stats.observe_only_opt.as_ref() {
+            observe_only_title(stdout);
+            dump_parameter_line(
+                stdout,
+                "Creditors:",
+                &observed.would_be_paid_accounts.to_string(),
+            );
+            dump_parameter_line(
+                stdout,
+                "Total:",
+                &process_gwei_into_requested_format(observed.would_be_paid_gwei, gwei_flag),
+            );
+        }
+        // Without debts, there is nothing to chart
+        if stats.aging != UiDebtAging::default() {
+            debt_aging_title(stdout, gwei_flag);
+            Self::dump_aging_lines(stdout, "Payable", &stats.aging.payable, gwei_flag);
+            Self::dump_aging_lines(stdout, "Receivable", &stats.aging.receivable, gwei_flag);
+        }
+    }
+
+    fn dump_aging_lines(
+        stdout: &mut dyn Write,
+        side: &str,
+        buckets: &UiAgingBuckets,
+        gwei_flag: bool,
+    ) {
+        [
+            ("up to 1 day", &buckets.up_to_one_day),
+            ("1 to 3 days", &buckets.one_to_three_days),
+            ("3 to 7 days", &buckets.three_to_seven_days),
+            ("over 7 days", &buckets.over_seven_days),
+        ]
+        .iter()
+        .for_each(|(period, bucket)| {
+            dump_parameter_line(
+                stdout,
+                &format!("{} {}:", side, period),
+                &format!(
+                    "{} (accounts: {})",
+                    process_gwei_into_requested_format(bucket.balance_gwei, gwei_flag),
+                    bucket.accounts
+                ),
+            )
+        })
     }
 
     fn process_queried_records(
@@ -324,8 +381,9 @@ mod tests {
     use crate::test_utils::mocks::CommandContextMock;
     use atty::Stream;
     use masq_lib::messages::{
-        ToMessageBody, TopRecordsOrdering, UiFinancialStatistics, UiFinancialsResponse,
-        UiPayableAccount, UiReceivableAccount,
+        ToMessageBody, TopRecordsOrdering, UiAgingBucket, UiAgingBuckets, UiApproximateFiatTotals,
+        UiFinancialStatistics, UiFinancialsResponse, UiObserveOnlyPayables, UiPayableAccount,
+        UiReceivableAccount,
     };
     use masq_lib::ui_gateway::MessageBody;
     use masq_lib::utils::slice_of_strs_to_vec_of_strings;
@@ -911,6 +969,9 @@ mod tests {
                 total_paid_payable_gwei: 78_455_555,
                 total_unpaid_receivable_gwei: -55_000_400,
                 total_paid_receivable_gwei: 1_278_766_555_456,
+                approximate_fiat_opt: None,
+                observe_only_opt: None,
+                aging: UiDebtAging::default(),
             }),
             query_results_opt: None,
         };
@@ -952,6 +1013,168 @@ mod tests {
         assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
     }
 
+    #[test]
+    fn financials_command_shows_approximate_fiat_totals_if_node_supplies_them() {
+        let expected_response = UiFinancialsResponse {
+            stats_opt: Some(UiFinancialStatistics {
+                total_unpaid_and_pending_payable_gwei: 1_166_880_215,
+                total_paid_payable_gwei: 78_455_555,
+                total_unpaid_receivable_gwei: -55_000_400,
+                total_paid_receivable_gwei: 1_278_766_555_456,
+                approximate_fiat_opt: Some(UiApproximateFiatTotals {
+                    currency: "USD".to_string(),
+                    total_unpaid_and_pending_payable: "0.29".to_string(),
+                    total_paid_payable: "0.02".to_string(),
+                    total_unpaid_receivable: "-0.01".to_string(),
+                    total_paid_receivable: "319.69".to_string(),
+                }),
+                observe_only_opt: None,
+                aging: UiDebtAging::default(),
+            }),
+            query_results_opt: None,
+        };
+        let mut context = CommandContextMock::new().transact_result(Ok(expected_response.tmb(31)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let args = &["financials".to_string()];
+        let subject = FinancialsCommand::new(args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "\
+                \n\
+                Financial status totals in MASQ\n\
+                \n\
+                Unpaid and pending payable:       1.16\n\
+                Paid payable:                     0.07\n\
+                Unpaid receivable:                -0.05\n\
+                Paid receivable:                  1,278.76\n\
+                \n\
+                Approximately in USD (at price feed rates, not for accounting)\n\
+                \n\
+                Unpaid and pending payable:       0.29\n\
+                Paid payable:                     0.02\n\
+                Unpaid receivable:                -0.01\n\
+                Paid receivable:                  319.69\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn financials_command_shows_what_would_be_paid_if_node_only_observes() {
+        let expected_response = UiFinancialsResponse {
+            stats_opt: Some(UiFinancialStatistics {
+                total_unpaid_and_pending_payable_gwei: 1_166_880_215,
+                total_paid_payable_gwei: 78_455_555,
+                total_unpaid_receivable_gwei: 55_000_400,
+                total_paid_receivable_gwei: 1_278_766_555_456,
+                approximate_fiat_opt: None,
+                observe_only_opt: Some(UiObserveOnlyPayables {
+                    would_be_paid_accounts: 2,
+                    would_be_paid_gwei: 1_100_000_000,
+                }),
+                aging: UiDebtAging::default(),
+            }),
+            query_results_opt: None,
+        };
+        let mut context = CommandContextMock::new().transact_result(Ok(expected_response.tmb(31)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let args = &["financials".to_string()];
+        let subject = FinancialsCommand::new(args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "\
+                \n\
+                Financial status totals in MASQ\n\
+                \n\
+                Unpaid and pending payable:       1.16\n\
+                Paid payable:                     0.07\n\
+                Unpaid receivable:                0.05\n\
+                Paid receivable:                  1,278.76\n\
+                \n\
+                Observe-only mode (no consuming wallet), latest payable scan would have paid\n\
+                \n\
+                Creditors:                        2\n\
+                Total:                            1.10\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn financials_command_charts_debt_aging() {
+        let expected_response = UiFinancialsResponse {
+            stats_opt: Some(UiFinancialStatistics {
+                total_unpaid_and_pending_payable_gwei: 1_245_335_770,
+                total_paid_payable_gwei: 0,
+                total_unpaid_receivable_gwei: 55_000_400,
+                total_paid_receivable_gwei: 0,
+                approximate_fiat_opt: None,
+                observe_only_opt: None,
+                aging: UiDebtAging {
+                    payable: UiAgingBuckets {
+                        up_to_one_day: UiAgingBucket {
+                            accounts: 2,
+                            balance_gwei: 1_166_880_215,
+                        },
+                        over_seven_days: UiAgingBucket {
+                            accounts: 1,
+                            balance_gwei: 78_455_555,
+                        },
+                        ..UiAgingBuckets::default()
+                    },
+                    receivable: UiAgingBuckets {
+                        three_to_seven_days: UiAgingBucket {
+                            accounts: 1,
+                            balance_gwei: 55_000_400,
+                        },
+                        ..UiAgingBuckets::default()
+                    },
+                },
+            }),
+            query_results_opt: None,
+        };
+        let mut context = CommandContextMock::new().transact_result(Ok(expected_response.tmb(31)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let args = &["financials".to_string(), "--gwei".to_string()];
+        let subject = FinancialsCommand::new(args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "\
+                \n\
+                Financial status totals in gwei\n\
+                \n\
+                Unpaid and pending payable:       1,245,335,770\n\
+                Paid payable:                     0\n\
+                Unpaid receivable:                55,000,400\n\
+                Paid receivable:                  0\n\
+                \n\
+                Unpaid balances in gwei by time since last paid\n\
+                \n\
+                Payable up to 1 day:              1,166,880,215 (accounts: 2)\n\
+                Payable 1 to 3 days:              0 (accounts: 0)\n\
+                Payable 3 to 7 days:              0 (accounts: 0)\n\
+                Payable over 7 days:              78,455,555 (accounts: 1)\n\
+                Receivable up to 1 day:           0 (accounts: 0)\n\
+                Receivable 1 to 3 days:           0 (accounts: 0)\n\
+                Receivable 3 to 7 days:           55,000,400 (accounts: 1)\n\
+                Receivable over 7 days:           0 (accounts: 0)\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
     #[test]
     fn are_both_sets_to_be_displayed_works_for_top_records() {
         //top records always print as a pair so it always consists of both sets
@@ -1020,6 +1243,9 @@ mod tests {
                 total_paid_payable_gwei: 235555554578,
                 total_unpaid_receivable_gwei: 0,
                 total_paid_receivable_gwei: 665557,
+                approximate_fiat_opt: None,
+                observe_only_opt: None,
+                aging: UiDebtAging::default(),
             }),
             query_results_opt: Some(if for_top_records {
                 QueryResults {
@@ -1029,6 +1255,7 @@ mod tests {
                             age_s: 5645405400,
                             balance_gwei: 68843325667,
                             pending_payable_hash_opt: None,
+                            pending_payment_opt: None,
                         },
                         UiPayableAccount {
                             wallet: "0x6DbcCaC5596b7ac986ff8F7ca06F212aEB444440".to_string(),
@@ -1038,6 +1265,7 @@ mod tests {
                                 "0x0290db1d56121112f4d45c1c3f36348644f6afd20b759b762f1dba9c4949066e"
                                     .to_string(),
                             ),
+                            pending_payment_opt: None,
                         },
                     ]),
                     receivable_opt: Some(vec![
@@ -1063,6 +1291,7 @@ mod tests {
                             "0x0290db1d56121112f4d45c1c3f36348644f6afd20b759b762f1dba9c4949066e"
                                 .to_string(),
                         ),
+                        pending_payment_opt: None,
                     }]),
                     receivable_opt: None,
                 }
@@ -1356,6 +1585,7 @@ mod tests {
                         "0x0290db1d56121112f4d45c1c3f36348644f6afd20b759b762f1dba9c4949066e"
                             .to_string(),
                     ),
+                    pending_payment_opt: None,
                 }]),
                 receivable_opt: Some(vec![UiReceivableAccount {
                     wallet: "0x8bA50675e590b545D2128905b89039256Eaa24F6".to_string(),
@@ -1433,6 +1663,9 @@ mod tests {
                 total_paid_payable_gwei: 55555,
                 total_unpaid_receivable_gwei: 221144,
                 total_paid_receivable_gwei: 66555,
+                approximate_fiat_opt: None,
+                observe_only_opt: None,
+                aging: UiDebtAging::default(),
             }),
             query_results_opt: Some(QueryResults {
                 payable_opt: Some(vec![]),
@@ -1510,6 +1743,9 @@ mod tests {
                 total_paid_payable_gwei: 55555,
                 total_unpaid_receivable_gwei: 221144,
                 total_paid_receivable_gwei: 66555,
+                approximate_fiat_opt: None,
+                observe_only_opt: None,
+                aging: UiDebtAging::default(),
             }),
             query_results_opt: Some(QueryResults {
                 payable_opt: None,
@@ -1607,12 +1843,14 @@ mod tests {
                             "0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638"
                                 .to_string(),
                         ),
+                        pending_payment_opt: None,
                     },
                     UiPayableAccount {
                         wallet: "0xEA674fdac714fd979de3EdF0F56AA9716B198ec8".to_string(),
                         age_s: 28120444,
                         balance_gwei: 97524120,
                         pending_payable_hash_opt: None,
+                        pending_payment_opt: None,
                     },
                 ]),
                 receivable_opt: Some(vec![
@@ -1693,18 +1931,21 @@ mod tests {
                             "0x5fe272ed1e941cc05fbd624ec4b1546cd03c25d53e24ba2c18b11feb83cd4581"
                                 .to_string(),
                         ),
+                        pending_payment_opt: None,
                     },
                     UiPayableAccount {
                         wallet: "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888".to_string(),
                         age_s: 70000,
                         balance_gwei: 708090,
                         pending_payable_hash_opt: None,
+                        pending_payment_opt: None,
                     },
                     UiPayableAccount {
                         wallet: "0x6DbcCaC5596b7ac986ff8F7ca06F212aEB444440".to_string(),
                         age_s: 6089909,
                         balance_gwei: 66658,
                         pending_payable_hash_opt: None,
+                        pending_payment_opt: None,
                     },
                 ]),
                 receivable_opt: None,
@@ -9,7 +9,7 @@ pub(in crate::commands::financials_command) mod restricted {
     };
     use crate::commands::financials_command::FinancialsCommand;
     use masq_lib::constants::WALLET_ADDRESS_LENGTH;
-    use masq_lib::messages::{UiPayableAccount, UiReceivableAccount};
+    use masq_lib::messages::{UiPayableAccount, UiReceivableAccount, UiUnconfirmedPayment};
     use masq_lib::short_writeln;
     use masq_lib::utils::to_string;
     use std::fmt::{Debug, Display};
@@ -27,15 +27,28 @@ pub(in crate::commands::financials_command) mod restricted {
                 self.wallet.to_string(),
                 self.age_s.separate_with_commas(),
                 process_gwei_into_requested_format(self.balance_gwei, is_gwei),
-                if let Some(hash) = &self.pending_payable_hash_opt {
-                    hash.to_string()
-                } else {
-                    "None".to_string()
+                match (&self.pending_payable_hash_opt, &self.pending_payment_opt) {
+                    (Some(hash), Some(payment)) => {
+                        format!("{} ({})", hash, describe_pending_payment(payment))
+                    }
+                    (Some(hash), None) => hash.to_string(),
+                    (None, _) => "None".to_string(),
                 },
             ]
         }
     }
 
+    fn describe_pending_payment(payment: &UiUnconfirmedPayment) -> String {
+        match &payment.failure_opt {
+            Some(_) => "failed".to_string(),
+            None => format!(
+                "sent {} s ago, {} receipt checks",
+                payment.age_s.separate_with_commas(),
+                payment.attempt
+            ),
+        }
+    }
+
     impl StringValuesFormattableAccount for UiReceivableAccount {
         fn convert_to_strings(&self, ordinal_num: usize, is_gwei: bool) -> Vec<String> {
             vec![
@@ -55,6 +68,29 @@ pub(in crate::commands::financials_command) mod restricted {
         );
     }
 
+    pub fn approximate_fiat_totals_title(stdout: &mut dyn Write, currency: &str) {
+        short_writeln!(
+            stdout,
+            "\nApproximately in {} (at price feed rates, not for accounting)\n",
+            currency
+        );
+    }
+
+    pub fn observe_only_title(stdout: &mut dyn Write) {
+        short_writeln!(
+            stdout,
+            "\nObserve-only mode (no consuming wallet), latest payable scan would have paid\n"
+        );
+    }
+
+    pub fn debt_aging_title(stdout: &mut dyn Write, is_gwei: bool) {
+        short_writeln!(
+            stdout,
+            "\nUnpaid balances in {} by time since last paid\n",
+            &gwei_or_masq_balance(is_gwei)[9..13]
+        );
+    }
+
     pub fn main_title_for_tops_opt(fin_com: &FinancialsCommand, stdout: &mut dyn Write) {
         if let Some(tr_config) = fin_com.top_records_opt.as_ref() {
             short_writeln!(stdout, "Up to {} top accounts\n", tr_config.count)
@@ -252,6 +288,7 @@ mod tests {
     use crate::commands::financials_command::pretty_print_utils::restricted::{
         figure_out_max_widths, StringValuesFormattableAccount,
     };
+    use masq_lib::messages::{UiPayableAccount, UiUnconfirmedPayment};
 
     #[derive(Clone)]
     struct TestAccount {
@@ -271,6 +308,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn payable_account_shows_the_state_of_its_pending_payment() {
+        let make_account = |failure_opt: Option<String>| UiPayableAccount {
+            wallet: "0x6DbcCaC5596b7ac986ff8F7ca06F212aEB444440".to_string(),
+            age_s: 150,
+            balance_gwei: 8_000,
+            pending_payable_hash_opt: Some("0x0290…066e".to_string()),
+            pending_payment_opt: Some(UiUnconfirmedPayment {
+                transaction_hash:
+                    "0x0290db1d56121112f4d45c1c3f36348644f6afd20b759b762f1dba9c4949066e".to_string(),
+                amount_gwei: 8_000,
+                age_s: 4_500,
+                attempt: 7,
+                failure_opt,
+            }),
+        };
+
+        let pending = make_account(None).convert_to_strings(1, true);
+        let failed = make_account(Some("ERROR".to_string())).convert_to_strings(2, true);
+
+        assert_eq!(
+            pending[4],
+            "0x0290…066e (sent 4,500 s ago, 7 receipt checks)"
+        );
+        assert_eq!(failed[4], "0x0290…066e (failed)");
+    }
+
     #[test]
     fn figure_out_max_widths_works() {
         let mut vec_of_accounts = vec![
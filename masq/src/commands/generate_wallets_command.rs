@@ -7,6 +7,7 @@ use crate::commands::commands_common::{
 use clap::{App, Arg, SubCommand};
 use lazy_static::lazy_static;
 use masq_lib::as_any_ref_in_trait_impl;
+use masq_lib::constants::MAX_EARNING_WALLET_COUNT;
 use masq_lib::messages::{UiGenerateSeedSpec, UiGenerateWalletsRequest, UiGenerateWalletsResponse};
 use masq_lib::short_writeln;
 use masq_lib::utils::DEFAULT_EARNING_DERIVATION_PATH;
@@ -28,6 +29,12 @@ lazy_static! {
             earning address instead.",
         DEFAULT_EARNING_DERIVATION_PATH.as_str()
     );
+    static ref EARNING_WALLET_COUNT_ARG_HELP: String = format!(
+        "How many earning wallets to rotate among, counting up from the last index of the \
+            earning path; the Node picks a different one each day it starts. (1 to {}; leave \
+            this out to keep a single earning wallet.)",
+        MAX_EARNING_WALLET_COUNT
+    );
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -43,6 +50,7 @@ pub struct GenerateWalletsCommand {
     seed_spec_opt: Option<SeedSpec>,
     consuming_path_opt: Option<String>,
     earning_path_opt: Option<String>,
+    earning_wallet_count_opt: Option<u32>,
 }
 
 const GENERATE_WALLET_SUBCOMMAND_ABOUT: &str =
@@ -84,6 +92,11 @@ impl GenerateWalletsCommand {
 
         let consuming_path_opt = matches.value_of("consuming-path").map(to_string);
         let earning_path_opt = matches.value_of("earning-path").map(to_string);
+        let earning_wallet_count_opt = matches.value_of("earning-wallet-count").map(|count| {
+            count
+                .parse::<u32>()
+                .expect("earning-wallet-count validator is broken")
+        });
         let seed_spec_opt = if consuming_path_opt.is_some() || earning_path_opt.is_some() {
             Some(SeedSpec {
                 word_count: matches
@@ -110,6 +123,7 @@ impl GenerateWalletsCommand {
             seed_spec_opt,
             consuming_path_opt,
             earning_path_opt,
+            earning_wallet_count_opt,
         })
     }
 
@@ -158,6 +172,7 @@ impl Command for GenerateWalletsCommand {
                 }),
             consuming_derivation_path_opt: self.consuming_path_opt.as_ref().cloned(),
             earning_derivation_path_opt: self.earning_path_opt.as_ref().cloned(),
+            earning_wallet_count_opt: self.earning_wallet_count_opt,
         };
         let response: UiGenerateWalletsResponse =
             transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
@@ -224,6 +239,25 @@ pub fn generate_wallets_subcommand() -> App<'static, 'static> {
                 .required(false)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("earning-wallet-count")
+                .help(EARNING_WALLET_COUNT_ARG_HELP.as_str())
+                .long("earning-wallet-count")
+                .value_name("EARNING-WALLET-COUNT")
+                .required(false)
+                .takes_value(true)
+                .validator(validate_earning_wallet_count),
+        )
+}
+
+pub fn validate_earning_wallet_count(count: String) -> Result<(), String> {
+    match count.parse::<u32>() {
+        Ok(value) if value > 0 && value <= MAX_EARNING_WALLET_COUNT => Ok(()),
+        _ => Err(format!(
+            "Earning wallet count must be an integer between 1 and {}, not '{}'",
+            MAX_EARNING_WALLET_COUNT, count
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -308,6 +342,68 @@ mod tests {
         );
         assert_eq!(WORD_COUNT_ARG_DEFAULT_VALUE, "12");
         assert_eq!(LANGUAGE_ARG_DEFAULT_VALUE, "English");
+        assert_eq!(
+            EARNING_WALLET_COUNT_ARG_HELP.to_string(),
+            format!(
+                "How many earning wallets to rotate among, counting up from the last index of the \
+                 earning path; the Node picks a different one each day it starts. (1 to {}; leave \
+                 this out to keep a single earning wallet.)",
+                MAX_EARNING_WALLET_COUNT
+            )
+        );
+    }
+
+    #[test]
+    fn command_factory_works_with_earning_wallet_count() {
+        let subject = CommandFactoryReal::new();
+
+        let result = subject
+            .make(&[
+                "generate-wallets".to_string(),
+                "--db-password".to_string(),
+                "password".to_string(),
+                "--earning-path".to_string(),
+                "m/44'/60'/0'/100/0/201".to_string(),
+                "--earning-wallet-count".to_string(),
+                "3".to_string(),
+            ])
+            .unwrap();
+
+        let generate_wallets_command: &GenerateWalletsCommand =
+            result.as_any().downcast_ref().unwrap();
+        assert_eq!(generate_wallets_command.earning_wallet_count_opt, Some(3));
+    }
+
+    #[test]
+    fn validate_earning_wallet_count_accepts_counts_in_range() {
+        assert_eq!(validate_earning_wallet_count("1".to_string()), Ok(()));
+        assert_eq!(
+            validate_earning_wallet_count(MAX_EARNING_WALLET_COUNT.to_string()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_earning_wallet_count_rejects_counts_out_of_range() {
+        let expected_error = |count: &str| {
+            Err(format!(
+                "Earning wallet count must be an integer between 1 and {}, not '{}'",
+                MAX_EARNING_WALLET_COUNT, count
+            ))
+        };
+
+        assert_eq!(
+            validate_earning_wallet_count("0".to_string()),
+            expected_error("0")
+        );
+        assert_eq!(
+            validate_earning_wallet_count("101".to_string()),
+            expected_error("101")
+        );
+        assert_eq!(
+            validate_earning_wallet_count("booga".to_string()),
+            expected_error("booga")
+        );
     }
 
     #[test]
@@ -344,7 +440,8 @@ mod tests {
                     passphrase_opt: Some("booga".to_string()),
                 }),
                 consuming_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
-                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string())
+                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -377,7 +474,8 @@ mod tests {
                     passphrase_opt: None,
                 }),
                 consuming_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
-                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string())
+                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -414,7 +512,8 @@ mod tests {
                     passphrase_opt: Some("booga".to_string()),
                 }),
                 consuming_path_opt: None,
-                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string())
+                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -445,7 +544,8 @@ mod tests {
                     passphrase_opt: None,
                 }),
                 consuming_path_opt: None,
-                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string())
+                earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -482,7 +582,8 @@ mod tests {
                     passphrase_opt: Some("booga".to_string()),
                 }),
                 consuming_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
-                earning_path_opt: None
+                earning_path_opt: None,
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -513,7 +614,8 @@ mod tests {
                     passphrase_opt: None,
                 }),
                 consuming_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
-                earning_path_opt: None
+                earning_path_opt: None,
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -538,7 +640,8 @@ mod tests {
                 db_password: "password".to_string(),
                 seed_spec_opt: None,
                 consuming_path_opt: None,
-                earning_path_opt: None
+                earning_path_opt: None,
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -575,6 +678,7 @@ mod tests {
             }),
             consuming_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
             earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+            earning_wallet_count_opt: Some(4),
         };
 
         subject.execute(&mut context).err().unwrap(); // don't need success, just request translation
@@ -591,7 +695,8 @@ mod tests {
                         mnemonic_passphrase_opt: Some("booga".to_string()),
                     }),
                     consuming_derivation_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
-                    earning_derivation_path_opt: Some("m/44'/60'/0'/100/0/201".to_string())
+                    earning_derivation_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+                    earning_wallet_count_opt: Some(4),
                 }
                 .tmb(0),
                 1000
@@ -610,6 +715,7 @@ mod tests {
             seed_spec_opt: None,
             consuming_path_opt: None,
             earning_path_opt: None,
+            earning_wallet_count_opt: None,
         };
 
         subject.execute(&mut context).err().unwrap(); // don't need success, just request translation
@@ -622,7 +728,8 @@ mod tests {
                     db_password: "password".to_string(),
                     seed_spec_opt: None,
                     consuming_derivation_path_opt: None,
-                    earning_derivation_path_opt: None
+                    earning_derivation_path_opt: None,
+                    earning_wallet_count_opt: None,
                 }
                 .tmb(0),
                 1000
@@ -722,6 +829,7 @@ Private key of   earning wallet: BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB\n\
             }),
             consuming_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
             earning_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+            earning_wallet_count_opt: None,
         };
 
         let result = subject.execute(&mut context);
@@ -739,7 +847,8 @@ Private key of   earning wallet: BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB\n\
                         mnemonic_passphrase_opt: Some("booga".to_string()),
                     }),
                     consuming_derivation_path_opt: Some("m/44'/60'/0'/100/0/200".to_string()),
-                    earning_derivation_path_opt: Some("m/44'/60'/0'/100/0/201".to_string())
+                    earning_derivation_path_opt: Some("m/44'/60'/0'/100/0/201".to_string()),
+                    earning_wallet_count_opt: None,
                 }
                 .tmb(0),
                 1000
@@ -1,5 +1,7 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod blockchain_self_test_command;
+pub mod blockchain_stats_command;
 pub mod change_password_command;
 pub mod check_password_command;
 pub mod commands_common;
@@ -16,4 +18,6 @@ pub mod set_configuration_command;
 pub mod setup_command;
 pub mod shutdown_command;
 pub mod start_command;
+pub mod sweep_wallet_command;
+pub mod unlock_wallet_command;
 pub mod wallet_addresses_command;
@@ -4,6 +4,7 @@ use crate::command_context::CommandContext;
 use crate::commands::commands_common::{
     transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
 };
+use crate::commands::generate_wallets_command::validate_earning_wallet_count;
 use clap::{App, Arg, ArgGroup, SubCommand};
 use itertools::{Either, Itertools};
 use masq_lib::as_any_ref_in_trait_impl;
@@ -24,6 +25,7 @@ pub struct RecoverWalletsCommand {
     seed_spec_opt: Option<SeedSpec>,
     consuming: Either<String, String>,
     earning: Either<String, String>,
+    earning_wallet_count_opt: Option<u32>,
 }
 
 impl RecoverWalletsCommand {
@@ -59,6 +61,11 @@ impl RecoverWalletsCommand {
                 x
             ),
         };
+        let earning_wallet_count_opt = matches.value_of("earning-wallet-count").map(|count| {
+            count
+                .parse::<u32>()
+                .expect("earning-wallet-count validator is broken")
+        });
         let consuming_wallet_derivation_path_opt = matches.value_of("consuming-path");
         let consuming_wallet_key_opt = matches.value_of("consuming-key");
         let consuming = match (
@@ -81,6 +88,7 @@ impl RecoverWalletsCommand {
             seed_spec_opt,
             consuming,
             earning,
+            earning_wallet_count_opt,
         })
     }
 }
@@ -113,6 +121,7 @@ impl Command for RecoverWalletsCommand {
                 Either::Left(address) => Some(address.clone()),
                 Either::Right(_) => None,
             },
+            earning_wallet_count_opt: self.earning_wallet_count_opt,
         };
         let _: UiRecoverWalletsResponse =
             transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
@@ -144,6 +153,9 @@ const EARNING_PATH_ARG_HELP: &str =
      quotes will cause problems.";
 const EARNING_ADDRESS_ARG_HELP: &str =
     "The address of the earning wallet. Represent it as '0x' followed by 40 hexadecimal digits.";
+const EARNING_WALLET_COUNT_ARG_HELP: &str =
+    "How many earning wallets were generated to rotate among, counting up from the last index of \
+     the earning path. Leave this out if there is only one.";
 const LANGUAGE_ARG_POSSIBLE_VALUES: [&str; 8] = [
     "English",
     "Chinese",
@@ -226,6 +238,16 @@ pub fn recover_wallets_subcommand() -> App<'static, 'static> {
                 .required(false)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("earning-wallet-count")
+                .help(EARNING_WALLET_COUNT_ARG_HELP)
+                .long("earning-wallet-count")
+                .value_name("EARNING-WALLET-COUNT")
+                .required(false)
+                .takes_value(true)
+                .requires("earning-path")
+                .validator(validate_earning_wallet_count),
+        )
         .group(
             ArgGroup::with_name("consuming")
                 .arg("consuming-path")
@@ -305,6 +327,11 @@ mod tests {
                 "Spanish",
             ]
         );
+        assert_eq!(
+            EARNING_WALLET_COUNT_ARG_HELP,
+            "How many earning wallets were generated to rotate among, counting up from the last \
+             index of the earning path. Leave this out if there is only one."
+        );
         assert_eq!(LANGUAGE_ARG_DEFAULT_VALUE, "English")
     }
 
@@ -344,7 +371,8 @@ mod tests {
                     language: "English".to_string(),
                 }),
                 consuming: Either::Right ("m/60'/44'/0'/100/0/200".to_string()),
-                earning: Either::Right ("m/60'/44'/0'/100/0/201".to_string())
+                earning: Either::Right ("m/60'/44'/0'/100/0/201".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -376,6 +404,7 @@ mod tests {
                     "0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF".to_string()
                 ),
                 earning: Either::Left("0x0123456789012345678901234567890123456789".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
@@ -428,10 +457,67 @@ mod tests {
                 }),
                 consuming: Either::Right("ooga".to_string()),
                 earning: Either::Right("booga".to_string()),
+                earning_wallet_count_opt: None,
             }
         )
     }
 
+    #[test]
+    fn earning_wallet_count_is_accepted_with_earning_path() {
+        let subject = CommandFactoryReal::new();
+
+        let result = subject
+            .make(&[
+                "recover-wallets".to_string(),
+                "--db-password".to_string(),
+                "password".to_string(),
+                "--mnemonic-phrase".to_string(),
+                "word".to_string(),
+                "--consuming-path".to_string(),
+                "ooga".to_string(),
+                "--earning-path".to_string(),
+                "booga".to_string(),
+                "--earning-wallet-count".to_string(),
+                "5".to_string(),
+            ])
+            .unwrap();
+
+        let recover_wallets_command: &RecoverWalletsCommand =
+            result.as_any().downcast_ref().unwrap();
+        assert_eq!(recover_wallets_command.earning_wallet_count_opt, Some(5));
+    }
+
+    #[test]
+    fn earning_wallet_count_requires_earning_path() {
+        let subject = CommandFactoryReal::new();
+
+        let result = subject
+            .make(&[
+                "recover-wallets".to_string(),
+                "--db-password".to_string(),
+                "password".to_string(),
+                "--consuming-key".to_string(),
+                "0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF".to_string(),
+                "--earning-address".to_string(),
+                "0x0123456789012345678901234567890123456789".to_string(),
+                "--earning-wallet-count".to_string(),
+                "5".to_string(),
+            ])
+            .err()
+            .unwrap();
+
+        let msg = match result {
+            CommandFactoryError::CommandSyntax(msg) => msg,
+            x => panic!("Expected CommandSyntax, but got {:?}", x),
+        };
+        assert_eq!(
+            msg.contains("The following required arguments were not provided:"),
+            true,
+            "{}",
+            msg
+        );
+    }
+
     #[test]
     fn earning_wallet_must_be_specified_somehow() {
         let subject = CommandFactoryReal::new();
@@ -511,6 +597,7 @@ mod tests {
             }),
             consuming: Either::Right("consuming path".to_string()),
             earning: Either::Right("earning path".to_string()),
+            earning_wallet_count_opt: Some(2),
         };
 
         let result = subject.execute(&mut context);
@@ -531,6 +618,7 @@ mod tests {
                     consuming_private_key_opt: None,
                     earning_derivation_path_opt: Some("earning path".to_string()),
                     earning_address_opt: None,
+                    earning_wallet_count_opt: Some(2),
                 }
                 .tmb(0),
                 1000
@@ -558,6 +646,7 @@ mod tests {
             seed_spec_opt: None,
             consuming: Either::Left("consuming private key".to_string()),
             earning: Either::Left("earning address".to_string()),
+            earning_wallet_count_opt: None,
         };
 
         let result = subject.execute(&mut context);
@@ -574,6 +663,7 @@ mod tests {
                     consuming_private_key_opt: Some("consuming private key".to_string()),
                     earning_derivation_path_opt: None,
                     earning_address_opt: Some("earning address".to_string()),
+                    earning_wallet_count_opt: None,
                 }
                 .tmb(0),
                 1000
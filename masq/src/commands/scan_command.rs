@@ -4,6 +4,7 @@ use crate::command_context::CommandContext;
 use crate::commands::commands_common::{transaction, Command, CommandError};
 use clap::{App, Arg, SubCommand};
 use masq_lib::messages::{ScanType, UiScanRequest, UiScanResponse};
+use masq_lib::short_writeln;
 use std::fmt::Debug;
 use std::str::FromStr;
 
@@ -45,7 +46,16 @@ impl Command for ScanCommand {
             SCAN_COMMAND_TIMEOUT_MILLIS,
         );
         match result {
-            Ok(_response) => Ok(()),
+            Ok(response) => {
+                if response.queued {
+                    short_writeln!(
+                        context.stdout(),
+                        "A {} scan was running already; this one will follow it",
+                        self.name.to_lowercase()
+                    );
+                }
+                Ok(())
+            }
             Err(e) => Err(e),
         }
     }
@@ -90,7 +100,8 @@ mod tests {
     #[test]
     fn testing_command_factory_here() {
         let factory = CommandFactoryReal::new();
-        let mut context = CommandContextMock::new().transact_result(Ok(UiScanResponse {}.tmb(0)));
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiScanResponse { queued: false }.tmb(0)));
         let subject = factory
             .make(&["scan".to_string(), "payables".to_string()])
             .unwrap();
@@ -111,7 +122,7 @@ mod tests {
         let transact_params_arc = Arc::new(Mutex::new(vec![]));
         let mut context = CommandContextMock::new()
             .transact_params(&transact_params_arc)
-            .transact_result(Ok(UiScanResponse {}.tmb(0)));
+            .transact_result(Ok(UiScanResponse { queued: false }.tmb(0)));
         let stdout_arc = context.stdout_arc();
         let stderr_arc = context.stderr_arc();
         let factory = CommandFactoryReal::new();
@@ -134,6 +145,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn scan_command_tells_that_the_scan_was_queued_behind_a_running_one() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiScanResponse { queued: true }.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = ScanCommand::new(&["scan".to_string(), "Receivables".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "A receivables scan was running already; this one will follow it\n".to_string()
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
     #[test]
     fn scan_command_handles_send_failure() {
         let mut context = CommandContextMock::new()
@@ -0,0 +1,225 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiSweepWalletRequest, UiSweepWalletResponse};
+use masq_lib::{as_any_ref_in_trait_impl, short_writeln};
+
+const SWEEP_WALLET_SUBCOMMAND_ABOUT: &str =
+    "Sends the whole service fee balance of the consuming wallet, less an optional reserve, to \
+     another address in a single transaction. Useful when retiring the wallet.";
+const RECIPIENT_ARG_HELP: &str = "The address of the wallet that will receive the tokens.";
+const RESERVE_GWEI_ARG_HELP: &str =
+    "Amount of service fee in gwei to be left in the consuming wallet. Nothing is left by default.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SweepWalletCommand {
+    pub recipient_address: String,
+    pub reserve_gwei_opt: Option<u64>,
+}
+
+impl SweepWalletCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match sweep_wallet_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            recipient_address: matches
+                .value_of("recipient")
+                .expect("recipient is not properly required")
+                .to_string(),
+            reserve_gwei_opt: matches.value_of("reserve-gwei").map(|reserve| {
+                reserve
+                    .parse::<u64>()
+                    .expect("reserve-gwei is not properly validated")
+            }),
+        })
+    }
+}
+
+pub fn sweep_wallet_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("sweep-wallet")
+        .about(SWEEP_WALLET_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("recipient")
+                .help(RECIPIENT_ARG_HELP)
+                .value_name("RECIPIENT")
+                .required(true)
+                .case_insensitive(false),
+        )
+        .arg(
+            Arg::with_name("reserve-gwei")
+                .help(RESERVE_GWEI_ARG_HELP)
+                .long("reserve-gwei")
+                .value_name("RESERVE-GWEI")
+                .takes_value(true)
+                .validator(validate_reserve_gwei),
+        )
+}
+
+fn validate_reserve_gwei(reserve: String) -> Result<(), String> {
+    match reserve.parse::<u64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!(
+            "Reserve must be a nonnegative integer number of gwei, not '{}'",
+            reserve
+        )),
+    }
+}
+
+impl Command for SweepWalletCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiSweepWalletRequest {
+            recipient_address: self.recipient_address.clone(),
+            reserve_gwei_opt: self.reserve_gwei_opt,
+        };
+        let msg: UiSweepWalletResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        short_writeln!(
+            context.stdout(),
+            "Sweeping {} gwei to {} in transaction {}",
+            msg.swept_gwei,
+            self.recipient_address,
+            msg.transaction_hash
+        );
+        Ok(())
+    }
+    as_any_ref_in_trait_impl!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::{Command, CommandError};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            SWEEP_WALLET_SUBCOMMAND_ABOUT,
+            "Sends the whole service fee balance of the consuming wallet, less an optional \
+             reserve, to another address in a single transaction. Useful when retiring the wallet."
+        );
+        assert_eq!(
+            RECIPIENT_ARG_HELP,
+            "The address of the wallet that will receive the tokens."
+        );
+        assert_eq!(
+            RESERVE_GWEI_ARG_HELP,
+            "Amount of service fee in gwei to be left in the consuming wallet. Nothing is left by \
+             default."
+        );
+    }
+
+    #[test]
+    fn sweep_wallet_sends_request_and_reports_the_transaction() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiSweepWalletResponse {
+                transaction_hash: "0xabcd".to_string(),
+                swept_gwei: 1_234,
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let factory = CommandFactoryReal::new();
+        let subject = factory
+            .make(&[
+                "sweep-wallet".to_string(),
+                "0x00000000000000000000000000000000000000ab".to_string(),
+                "--reserve-gwei".to_string(),
+                "100".to_string(),
+            ])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Sweeping 1234 gwei to 0x00000000000000000000000000000000000000ab in transaction \
+             0xabcd\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiSweepWalletRequest {
+                    recipient_address: "0x00000000000000000000000000000000000000ab".to_string(),
+                    reserve_gwei_opt: Some(100),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        )
+    }
+
+    #[test]
+    fn sweep_wallet_leaves_no_reserve_by_default() {
+        let result = SweepWalletCommand::new(&[
+            "sweep-wallet".to_string(),
+            "0x00000000000000000000000000000000000000ab".to_string(),
+        ]);
+
+        assert_eq!(
+            result,
+            Ok(SweepWalletCommand {
+                recipient_address: "0x00000000000000000000000000000000000000ab".to_string(),
+                reserve_gwei_opt: None,
+            })
+        )
+    }
+
+    #[test]
+    fn sweep_wallet_handles_error_from_node() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(4644, "bad bad bad thing".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let subject =
+            SweepWalletCommand::new(&["sweep-wallet".to_string(), "0xabcd".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(4644, "bad bad bad thing".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn sweep_wallet_requires_recipient() {
+        let result = SweepWalletCommand::new(&["sweep-wallet".to_string()]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided:"),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn validate_reserve_gwei_rejects_what_is_not_a_nonnegative_integer() {
+        assert_eq!(validate_reserve_gwei("0".to_string()), Ok(()));
+        assert_eq!(
+            validate_reserve_gwei("-5".to_string()),
+            Err("Reserve must be a nonnegative integer number of gwei, not '-5'".to_string())
+        );
+        assert_eq!(
+            validate_reserve_gwei("booga".to_string()),
+            Err("Reserve must be a nonnegative integer number of gwei, not 'booga'".to_string())
+        );
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiUnlockWalletRequest, UiUnlockWalletResponse};
+use masq_lib::{as_any_ref_in_trait_impl, short_writeln};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnlockWalletCommand {
+    pub db_password: String,
+}
+
+impl UnlockWalletCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match unlock_wallet_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            db_password: matches
+                .value_of("db-password")
+                .expect("db-password is not properly required")
+                .to_string(),
+        })
+    }
+}
+
+const UNLOCK_WALLET_SUBCOMMAND_ABOUT: &str =
+    "Unlocks the consuming wallet stored encrypted in the database, so that the Node can \
+     sign payments with it. Useful when the Node was started without --db-password.";
+const DB_PASSWORD_ARG_HELP: &str =
+    "The current database password, which the consuming wallet is encrypted with.";
+
+pub fn unlock_wallet_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("unlock-wallet")
+        .about(UNLOCK_WALLET_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("db-password")
+                .help(DB_PASSWORD_ARG_HELP)
+                .value_name("DB-PASSWORD")
+                .required(true)
+                .case_insensitive(false),
+        )
+}
+
+impl Command for UnlockWalletCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiUnlockWalletRequest {
+            db_password: self.db_password.clone(),
+        };
+        let msg: UiUnlockWalletResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        short_writeln!(
+            context.stdout(),
+            "Consuming wallet {} is unlocked",
+            msg.consuming_wallet_address
+        );
+        Ok(())
+    }
+    as_any_ref_in_trait_impl!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::{Command, CommandError};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::{ToMessageBody, UiUnlockWalletRequest, UiUnlockWalletResponse};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            UNLOCK_WALLET_SUBCOMMAND_ABOUT,
+            "Unlocks the consuming wallet stored encrypted in the database, so that the Node can \
+             sign payments with it. Useful when the Node was started without --db-password."
+        );
+        assert_eq!(
+            DB_PASSWORD_ARG_HELP,
+            "The current database password, which the consuming wallet is encrypted with."
+        );
+    }
+
+    #[test]
+    fn unlock_wallet_with_password_right() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiUnlockWalletResponse {
+                consuming_wallet_address: "0x464654jhkjhk6".to_string(),
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let factory = CommandFactoryReal::new();
+        let subject = factory
+            .make(&["unlock-wallet".to_string(), "bonkers".to_string()])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Consuming wallet 0x464654jhkjhk6 is unlocked\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiUnlockWalletRequest {
+                    db_password: "bonkers".to_string(),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        )
+    }
+
+    #[test]
+    fn unlock_wallet_handles_error_from_node() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(4644, "bad bad bad thing".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let subject =
+            UnlockWalletCommand::new(&["unlock-wallet".to_string(), "bonkers".to_string()])
+                .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(4644, "bad bad bad thing".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn unlock_wallet_requires_password() {
+        let result = UnlockWalletCommand::new(&["unlock-wallet".to_string()]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided:"),
+            "{}",
+            msg
+        );
+    }
+}
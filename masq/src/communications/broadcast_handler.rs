@@ -7,7 +7,8 @@ use crate::terminal::terminal_interface::TerminalWrapper;
 use crossbeam_channel::{unbounded, RecvError, Sender};
 use masq_lib::messages::{
     FromMessageBody, UiConnectionChangeBroadcast, UiLogBroadcast, UiNewPasswordBroadcast,
-    UiNodeCrashedBroadcast, UiSetupBroadcast, UiUndeliveredFireAndForget,
+    UiNodeCrashedBroadcast, UiServiceFeeTopUpBroadcast, UiSetupBroadcast,
+    UiSpendRateLimitBroadcast, UiTransactionFeeRunwayBroadcast, UiUndeliveredFireAndForget,
 };
 use masq_lib::ui_gateway::MessageBody;
 use masq_lib::utils::ExpectValue;
@@ -17,6 +18,9 @@ use std::io::Write;
 use std::thread;
 
 use crate::notifications::connection_change_notification::ConnectionChangeNotification;
+use crate::notifications::service_fee_top_up_notification::ServiceFeeTopUpNotification;
+use crate::notifications::spend_rate_limit_notification::SpendRateLimitNotification;
+use crate::notifications::transaction_fee_runway_notification::TransactionFeeRunwayNotification;
 
 pub trait BroadcastHandle: Send {
     fn send(&self, message_body: MessageBody);
@@ -111,6 +115,19 @@ impl BroadcastHandlerReal {
                         stdout,
                         terminal_interface,
                     );
+                } else if let Ok((body, _)) =
+                    UiTransactionFeeRunwayBroadcast::fmb(message_body.clone())
+                {
+                    TransactionFeeRunwayNotification::handle_broadcast(
+                        body,
+                        stdout,
+                        terminal_interface,
+                    );
+                } else if let Ok((body, _)) = UiSpendRateLimitBroadcast::fmb(message_body.clone()) {
+                    SpendRateLimitNotification::handle_broadcast(body, stdout, terminal_interface);
+                } else if let Ok((body, _)) = UiServiceFeeTopUpBroadcast::fmb(message_body.clone())
+                {
+                    ServiceFeeTopUpNotification::handle_broadcast(body, stdout, terminal_interface);
                 } else {
                     handle_unrecognized_broadcast(message_body, stderr, terminal_interface)
                 }
@@ -194,7 +211,7 @@ mod tests {
     use masq_lib::messages::UiSetupResponseValueStatus::{Configured, Default};
     use masq_lib::messages::{
         CrashReason, SerializableLogLevel, ToMessageBody, UiConnectionChangeBroadcast,
-        UiConnectionStage, UiLogBroadcast, UiNodeCrashedBroadcast,
+        UiConnectionStage, UiLogBroadcast, UiNodeCrashedBroadcast, UiTransactionFeeRunwayBroadcast,
     };
     use masq_lib::messages::{UiSetupBroadcast, UiSetupResponseValue, UiSetupResponseValueStatus};
     use masq_lib::ui_gateway::MessagePath;
@@ -378,6 +395,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transaction_fee_runway_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiTransactionFeeRunwayBroadcast {
+            consuming_wallet_address: "0xabc".to_string(),
+            transaction_fee_balance_gwei: 12,
+            scans_left: 1,
+            runway_sec: 600,
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nConsuming wallet 0xabc is running low on gas: its 12 gwei will pay for only about 1 \
+             more payable scans (~600 sec). Consider topping it up.\n\n"
+                .to_string()
+        );
+        assert_eq!(
+            handle.stderr_so_far(),
+            "".to_string(),
+            "stderr: '{}'",
+            stdout
+        );
+    }
+
+    #[test]
+    fn spend_rate_limit_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiSpendRateLimitBroadcast {
+            deferred_payments: 2,
+            deferred_gwei: 300,
+            service_fee_allowance_gwei: 0,
+            transaction_fee_allowance_gwei: 15,
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\n2 payments totaling 300 gwei were deferred to later scans by the spend rate limits; \
+             only 0 gwei of service fee and 15 gwei of transaction fee could be spent now.\n\n"
+                .to_string()
+        );
+        assert_eq!(
+            handle.stderr_so_far(),
+            "".to_string(),
+            "stderr: '{}'",
+            stdout
+        );
+    }
+
+    #[test]
+    fn service_fee_top_up_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiServiceFeeTopUpBroadcast {
+            consuming_wallet_address: "0x0123456789abcdef0123456789abcdef01234567".to_string(),
+            service_fee_balance_gwei: 500,
+            outstanding_debt_gwei: 300,
+            projected_debt_growth_gwei: 700,
+            horizon_hours: 24,
+            top_up_gwei: 500,
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nConsuming wallet 0x0123456789abcdef0123456789abcdef01234567 holds 500 gwei of service \
+             fee, but the Node owes 300 gwei already and is projected to owe 700 gwei more within 24 \
+             hours. Consider topping it up with 500 gwei.\n\n"
+                .to_string()
+        );
+        assert_eq!(
+            handle.stderr_so_far(),
+            "".to_string(),
+            "stderr: '{}'",
+            stdout
+        );
+    }
+
     #[test]
     fn unexpected_broadcasts_are_ineffectual_but_dont_kill_the_handler() {
         let (factory, handle) = TestStreamFactory::new();
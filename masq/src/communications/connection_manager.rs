@@ -555,8 +555,8 @@ mod tests {
     use crate::test_utils::client_utils::make_client;
     use crossbeam_channel::TryRecvError;
     use masq_lib::messages::{
-        CrashReason, FromMessageBody, ToMessageBody, UiFinancialStatistics, UiNodeCrashedBroadcast,
-        UiSetupBroadcast,
+        CrashReason, FromMessageBody, ToMessageBody, UiDebtAging, UiFinancialStatistics,
+        UiNodeCrashedBroadcast, UiSetupBroadcast,
     };
     use masq_lib::messages::{
         UiFinancialsRequest, UiFinancialsResponse, UiRedirect, UiSetupRequest, UiSetupResponse,
@@ -1176,6 +1176,9 @@ mod tests {
                     total_paid_payable_gwei: 22,
                     total_unpaid_receivable_gwei: 29,
                     total_paid_receivable_gwei: 32,
+                    approximate_fiat_opt: None,
+                    observe_only_opt: None,
+                    aging: UiDebtAging::default(),
                 }),
                 query_results_opt: None,
             }
@@ -1218,6 +1221,9 @@ mod tests {
                     total_paid_payable_gwei: 22,
                     total_unpaid_receivable_gwei: 29,
                     total_paid_receivable_gwei: 32,
+                    approximate_fiat_opt: None,
+                    observe_only_opt: None,
+                    aging: UiDebtAging::default(),
                 }),
                 query_results_opt: None
             }
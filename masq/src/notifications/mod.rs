@@ -2,3 +2,6 @@
 
 pub mod connection_change_notification;
 pub mod crashed_notification;
+pub mod service_fee_top_up_notification;
+pub mod spend_rate_limit_notification;
+pub mod transaction_fee_runway_notification;
@@ -0,0 +1,63 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiServiceFeeTopUpBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct ServiceFeeTopUpNotification {}
+
+impl ServiceFeeTopUpNotification {
+    pub fn handle_broadcast(
+        response: UiServiceFeeTopUpBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nConsuming wallet {} holds {} gwei of service fee, but the Node owes {} gwei already \
+             and is projected to owe {} gwei more within {} hours. Consider topping it up with {} gwei.\n",
+            response.consuming_wallet_address,
+            response.service_fee_balance_gwei,
+            response.outstanding_debt_gwei,
+            response.projected_debt_growth_gwei,
+            response.horizon_hours,
+            response.top_up_gwei
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+    use test_utilities::byte_array_reader_writer::ByteArrayWriter;
+
+    #[test]
+    fn broadcasts_recommended_top_up() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiServiceFeeTopUpBroadcast {
+            consuming_wallet_address: "0x0123456789abcdef0123456789abcdef01234567".to_string(),
+            service_fee_balance_gwei: 10_000,
+            outstanding_debt_gwei: 4_000,
+            projected_debt_growth_gwei: 15_000,
+            horizon_hours: 72,
+            top_up_gwei: 9_000,
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        ServiceFeeTopUpNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nConsuming wallet 0x0123456789abcdef0123456789abcdef01234567 holds 10000 gwei of service \
+             fee, but the Node owes 4000 gwei already and is projected to owe 15000 gwei more within 72 \
+             hours. Consider topping it up with 9000 gwei.\n\n"
+        );
+    }
+}
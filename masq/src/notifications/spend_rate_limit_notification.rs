@@ -0,0 +1,58 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiSpendRateLimitBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct SpendRateLimitNotification {}
+
+impl SpendRateLimitNotification {
+    pub fn handle_broadcast(
+        response: UiSpendRateLimitBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\n{} payments totaling {} gwei were deferred to later scans by the spend rate limits; \
+             only {} gwei of service fee and {} gwei of transaction fee could be spent now.\n",
+            response.deferred_payments,
+            response.deferred_gwei,
+            response.service_fee_allowance_gwei,
+            response.transaction_fee_allowance_gwei
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+    use test_utilities::byte_array_reader_writer::ByteArrayWriter;
+
+    #[test]
+    fn broadcasts_deferred_payments() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiSpendRateLimitBroadcast {
+            deferred_payments: 3,
+            deferred_gwei: 4_500,
+            service_fee_allowance_gwei: 1_200,
+            transaction_fee_allowance_gwei: 80,
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        SpendRateLimitNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\n3 payments totaling 4500 gwei were deferred to later scans by the spend rate limits; \
+             only 1200 gwei of service fee and 80 gwei of transaction fee could be spent now.\n\n"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiTransactionFeeRunwayBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct TransactionFeeRunwayNotification {}
+
+impl TransactionFeeRunwayNotification {
+    pub fn handle_broadcast(
+        response: UiTransactionFeeRunwayBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nConsuming wallet {} is running low on gas: its {} gwei will pay for only about {} \
+             more payable scans (~{} sec). Consider topping it up.\n",
+            response.consuming_wallet_address,
+            response.transaction_fee_balance_gwei,
+            response.scans_left,
+            response.runway_sec
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+    use test_utilities::byte_array_reader_writer::ByteArrayWriter;
+
+    #[test]
+    fn broadcasts_short_transaction_fee_runway() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiTransactionFeeRunwayBroadcast {
+            consuming_wallet_address: "0x0123456789012345678901234567890123456789".to_string(),
+            transaction_fee_balance_gwei: 7,
+            scans_left: 3,
+            runway_sec: 1800,
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        TransactionFeeRunwayNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nConsuming wallet 0x0123456789012345678901234567890123456789 is running low on gas: \
+             its 7 gwei will pay for only about 3 more payable scans (~1800 sec). Consider topping \
+             it up.\n\n"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::commands::blockchain_self_test_command::blockchain_self_test_subcommand;
+use crate::commands::blockchain_stats_command::blockchain_stats_subcommand;
 use crate::commands::change_password_command::{
     change_password_subcommand, set_password_subcommand,
 };
@@ -17,6 +19,8 @@ use crate::commands::set_configuration_command::set_configuration_subcommand;
 use crate::commands::setup_command::setup_subcommand;
 use crate::commands::shutdown_command::shutdown_subcommand;
 use crate::commands::start_command::start_subcommand;
+use crate::commands::sweep_wallet_command::sweep_wallet_subcommand;
+use crate::commands::unlock_wallet_command::unlock_wallet_subcommand;
 use crate::commands::wallet_addresses_command::wallet_addresses_subcommand;
 use clap::{App, AppSettings, Arg};
 use lazy_static::lazy_static;
@@ -62,6 +66,8 @@ pub fn app() -> App<'static, 'static> {
                 .validator(validate_ui_port)
                 .help(UI_PORT_HELP.as_str()),
         )
+        .subcommand(blockchain_self_test_subcommand())
+        .subcommand(blockchain_stats_subcommand())
         .subcommand(change_password_subcommand())
         .subcommand(check_password_subcommand())
         .subcommand(crash_subcommand())
@@ -78,6 +84,8 @@ pub fn app() -> App<'static, 'static> {
         .subcommand(setup_subcommand())
         .subcommand(shutdown_subcommand())
         .subcommand(start_subcommand())
+        .subcommand(sweep_wallet_subcommand())
+        .subcommand(unlock_wallet_subcommand())
         .subcommand(wallet_addresses_subcommand())
 }
 
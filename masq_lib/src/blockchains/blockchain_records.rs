@@ -19,6 +19,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: POLYGON_MAINNET_FULL_IDENTIFIER,
         contract: POLYGON_MAINNET_CONTRACT_ADDRESS,
         contract_creation_block: POLYGON_MAINNET_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "MASQ (PoS)",
+        token_symbol: "MASQ",
+        token_decimals: 18,
     },
     BlockchainRecord {
         self_id: Chain::EthMainnet,
@@ -26,6 +31,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: ETH_MAINNET_FULL_IDENTIFIER,
         contract: ETH_MAINNET_CONTRACT_ADDRESS,
         contract_creation_block: ETH_MAINNET_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "MASQ",
+        token_symbol: "MASQ",
+        token_decimals: 18,
     },
     BlockchainRecord {
         self_id: Chain::BaseMainnet,
@@ -33,6 +43,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: BASE_MAINNET_FULL_IDENTIFIER,
         contract: BASE_MAINNET_CONTRACT_ADDRESS,
         contract_creation_block: BASE_MAINNET_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "MASQ",
+        token_symbol: "MASQ",
+        token_decimals: 18,
     },
     BlockchainRecord {
         self_id: Chain::BaseSepolia,
@@ -40,6 +55,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: BASE_SEPOLIA_FULL_IDENTIFIER,
         contract: BASE_SEPOLIA_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: BASE_SEPOLIA_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "tMASQ",
+        token_symbol: "tMASQ",
+        token_decimals: 18,
     },
     BlockchainRecord {
         self_id: Chain::PolyAmoy,
@@ -47,6 +67,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: POLYGON_AMOY_FULL_IDENTIFIER,
         contract: POLYGON_AMOY_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: POLYGON_AMOY_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "tMASQ",
+        token_symbol: "tMASQ",
+        token_decimals: 18,
     },
     BlockchainRecord {
         self_id: Chain::EthRopsten,
@@ -54,6 +79,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: ETH_ROPSTEN_FULL_IDENTIFIER,
         contract: ETH_ROPSTEN_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: ETH_ROPSTEN_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "SHRD",
+        token_symbol: "SHRD",
+        token_decimals: 18,
     },
     BlockchainRecord {
         self_id: Chain::Dev,
@@ -61,6 +91,11 @@ pub const CHAINS: [BlockchainRecord; 7] = [
         literal_identifier: DEV_CHAIN_FULL_IDENTIFIER,
         contract: MULTINODE_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: MULTINODE_TESTNET_CONTRACT_CREATION_BLOCK,
+        payment_router_opt: None,
+        legacy_contract_opt: None,
+        token_name: "MASQ",
+        token_symbol: "MASQ",
+        token_decimals: 18,
     },
 ];
 
@@ -71,6 +106,15 @@ pub struct BlockchainRecord {
     pub literal_identifier: &'static str,
     pub contract: Address,
     pub contract_creation_block: u64,
+    // A disperse-style contract able to pay several creditors within a single transaction
+    pub payment_router_opt: Option<Address>,
+    // The token contract being retired after a migration. Received payments are still looked for
+    // at it during the transition window, while payables are always paid at the primary one
+    pub legacy_contract_opt: Option<Address>,
+    // What the token contract is expected to say about itself when asked at startup
+    pub token_name: &'static str,
+    pub token_symbol: &'static str,
+    pub token_decimals: u8,
 }
 
 // $tMASQ (Amoy)
@@ -197,6 +241,11 @@ mod tests {
                 literal_identifier: "eth-mainnet",
                 contract: ETH_MAINNET_CONTRACT_ADDRESS,
                 contract_creation_block: ETH_MAINNET_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "MASQ",
+                token_symbol: "MASQ",
+                token_decimals: 18,
             }
         );
     }
@@ -213,6 +262,11 @@ mod tests {
                 literal_identifier: "eth-ropsten",
                 contract: ETH_ROPSTEN_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: ETH_ROPSTEN_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "SHRD",
+                token_symbol: "SHRD",
+                token_decimals: 18,
             }
         );
     }
@@ -229,6 +283,11 @@ mod tests {
                 literal_identifier: "polygon-mainnet",
                 contract: POLYGON_MAINNET_CONTRACT_ADDRESS,
                 contract_creation_block: POLYGON_MAINNET_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "MASQ (PoS)",
+                token_symbol: "MASQ",
+                token_decimals: 18,
             }
         );
     }
@@ -245,6 +304,11 @@ mod tests {
                 literal_identifier: "polygon-amoy",
                 contract: POLYGON_AMOY_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: POLYGON_AMOY_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "tMASQ",
+                token_symbol: "tMASQ",
+                token_decimals: 18,
             }
         );
     }
@@ -261,6 +325,11 @@ mod tests {
                 literal_identifier: "base-mainnet",
                 contract: BASE_MAINNET_CONTRACT_ADDRESS,
                 contract_creation_block: BASE_MAINNET_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "MASQ",
+                token_symbol: "MASQ",
+                token_decimals: 18,
             }
         );
     }
@@ -277,6 +346,11 @@ mod tests {
                 literal_identifier: "base-sepolia",
                 contract: BASE_SEPOLIA_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: BASE_SEPOLIA_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "tMASQ",
+                token_symbol: "tMASQ",
+                token_decimals: 18,
             }
         );
     }
@@ -293,10 +367,27 @@ mod tests {
                 literal_identifier: "dev",
                 contract: MULTINODE_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: MULTINODE_TESTNET_CONTRACT_CREATION_BLOCK,
+                payment_router_opt: None,
+                legacy_contract_opt: None,
+                token_name: "MASQ",
+                token_symbol: "MASQ",
+                token_decimals: 18,
             }
         );
     }
 
+    #[test]
+    fn legacy_contracts_differ_from_the_primary_ones() {
+        CHAINS.iter().for_each(|record| {
+            assert_ne!(
+                record.legacy_contract_opt,
+                Some(record.contract),
+                "{:?}",
+                record.self_id
+            )
+        })
+    }
+
     fn return_examined<'a>(chain: Chain) -> &'a BlockchainRecord {
         find_record_opt(&|blockchain_record| blockchain_record.self_id == chain).unwrap()
     }
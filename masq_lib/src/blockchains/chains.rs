@@ -65,6 +65,16 @@ impl Chain {
     fn mainnets() -> &'static [Chain] {
         &[Chain::PolyMainnet, Chain::BaseMainnet, Chain::EthMainnet]
     }
+
+    // Networks that have been shut down. They are still recognized so that a Node holding a
+    // database made for one of them can be told what has become of it
+    pub fn is_deprecated(&self) -> bool {
+        Self::deprecated().contains(self)
+    }
+
+    fn deprecated() -> &'static [Chain] {
+        &[Chain::EthRopsten]
+    }
 }
 
 pub fn chain_from_chain_identifier_opt(identifier: &str) -> Option<Chain> {
@@ -143,6 +153,11 @@ mod tests {
             literal_identifier: "",
             contract: Default::default(),
             contract_creation_block: 0,
+            payment_router_opt: None,
+            legacy_contract_opt: None,
+            token_name: "",
+            token_symbol: "",
+            token_decimals: 0,
         }
     }
 
@@ -158,6 +173,19 @@ mod tests {
         })
     }
 
+    #[test]
+    fn is_deprecated_knows_only_about_shut_down_networks() {
+        CHAINS.iter().for_each(|blockchain_record| {
+            let chain = blockchain_record.self_id;
+            assert_eq!(
+                chain.is_deprecated(),
+                chain == Chain::EthRopsten,
+                "{:?}",
+                chain
+            )
+        })
+    }
+
     fn assert_mainnet_exist() {
         assert!(CHAINS
             .iter()
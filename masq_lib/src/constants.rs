@@ -5,7 +5,7 @@ use crate::data_version::DataVersion;
 use const_format::concatcp;
 
 pub const DEFAULT_CHAIN: Chain = Chain::PolyMainnet;
-pub const CURRENT_SCHEMA_VERSION: usize = 10;
+pub const CURRENT_SCHEMA_VERSION: usize = 23;
 
 pub const HIGHEST_RANDOM_CLANDESTINE_PORT: u16 = 9999;
 pub const HTTP_PORT: u16 = 80;
@@ -19,8 +19,18 @@ pub const CURRENT_LOGFILE_NAME: &str = "MASQNode_rCURRENT.log";
 pub const MASQ_PROMPT: &str = "masq> ";
 
 pub const DEFAULT_GAS_PRICE: u64 = 1; //TODO ?? Really
+pub const DEFAULT_GAS_RUNWAY_ALERT_SCANS: u64 = 5;
+pub const DEFAULT_MAX_BATCH_SIZE: u16 = 0;
+pub const DEFAULT_MAX_PENDING_AGE_SEC: u64 = 21_600;
+pub const DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS: u64 = 12;
+pub const DEFAULT_RECEIVABLE_SCAN_ATTEMPTS: u16 = 3;
+pub const DEFAULT_RECEIVABLE_SCAN_RECORDS: u64 = 0;
+pub const DEFAULT_RPC_DAILY_QUOTA: u64 = 0;
+pub const DEFAULT_TOP_UP_HORIZON_HOURS: u64 = 0;
+pub const DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS: u64 = 5;
 
 pub const WALLET_ADDRESS_LENGTH: usize = 42;
+pub const MAX_EARNING_WALLET_COUNT: u32 = 100;
 pub const MASQ_TOTAL_SUPPLY: u64 = 37_500_000;
 pub const WEIS_IN_GWEI: i128 = 1_000_000_000;
 
@@ -47,6 +57,10 @@ pub const DNS_RESOLVER_FAILURE_CURRENT_VERSION: DataVersion = DataVersion { majo
 pub const GOSSIP_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 pub const GOSSIP_FAILURE_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 pub const NODE_RECORD_INNER_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
+pub const PAYMENT_NOTICE_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
+pub const EXIT_OPTIONS_QUERY_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
+pub const EXIT_OPTIONS_REPORT_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
+pub const USAGE_INVOICE_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 
 //error codes
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -67,6 +81,7 @@ pub const UNRECOGNIZED_PARAMETER: u64 = CONFIGURATOR_PREFIX | 11;
 pub const NON_PARSABLE_VALUE: u64 = CONFIGURATOR_PREFIX | 12;
 pub const MISSING_DATA: u64 = CONFIGURATOR_PREFIX | 13;
 pub const UNKNOWN_ERROR: u64 = CONFIGURATOR_PREFIX | 14;
+pub const ILLEGAL_VALUE: u64 = CONFIGURATOR_PREFIX | 15;
 
 //moved from masq_lib/messages
 pub const UI_NODE_COMMUNICATION_PREFIX: u64 = 0x8000_0000_0000_0000;
@@ -84,6 +99,17 @@ pub const ACCOUNTANT_PREFIX: u64 = 0x0040_0000_0000_0000;
 pub const REQUEST_WITH_NO_VALUES: u64 = ACCOUNTANT_PREFIX | 1;
 pub const REQUEST_WITH_MUTUALLY_EXCLUSIVE_PARAMS: u64 = ACCOUNTANT_PREFIX | 2;
 pub const VALUE_EXCEEDS_ALLOWED_LIMIT: u64 = ACCOUNTANT_PREFIX | 3;
+pub const PAYMENT_RETRY_ERROR: u64 = ACCOUNTANT_PREFIX | 4;
+pub const PAYABLE_WRITE_OFF_ERROR: u64 = ACCOUNTANT_PREFIX | 5;
+pub const ACCOUNTS_EXPORT_ERROR: u64 = ACCOUNTANT_PREFIX | 6;
+pub const ACCOUNTS_IMPORT_ERROR: u64 = ACCOUNTANT_PREFIX | 7;
+pub const ACCOUNT_DETAIL_ERROR: u64 = ACCOUNTANT_PREFIX | 8;
+pub const CONSISTENCY_REPAIR_ERROR: u64 = ACCOUNTANT_PREFIX | 9;
+pub const WHY_NOT_PAID_ERROR: u64 = ACCOUNTANT_PREFIX | 10;
+
+//blockchain bridge
+pub const BLOCKCHAIN_BRIDGE_PREFIX: u64 = 0x0020_0000_0000_0000;
+pub const SWEEP_WALLET_ERROR: u64 = BLOCKCHAIN_BRIDGE_PREFIX | 1;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -127,7 +153,17 @@ mod tests {
         assert_eq!(CURRENT_LOGFILE_NAME, "MASQNode_rCURRENT.log");
         assert_eq!(MASQ_PROMPT, "masq> ");
         assert_eq!(DEFAULT_GAS_PRICE, 1);
+        assert_eq!(DEFAULT_GAS_RUNWAY_ALERT_SCANS, 5);
+        assert_eq!(DEFAULT_MAX_BATCH_SIZE, 0);
+        assert_eq!(DEFAULT_MAX_PENDING_AGE_SEC, 21_600);
+        assert_eq!(DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS, 12);
+        assert_eq!(DEFAULT_RECEIVABLE_SCAN_ATTEMPTS, 3);
+        assert_eq!(DEFAULT_RECEIVABLE_SCAN_RECORDS, 0);
+        assert_eq!(DEFAULT_RPC_DAILY_QUOTA, 0);
+        assert_eq!(DEFAULT_TOP_UP_HORIZON_HOURS, 0);
+        assert_eq!(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS, 5);
         assert_eq!(WALLET_ADDRESS_LENGTH, 42);
+        assert_eq!(MAX_EARNING_WALLET_COUNT, 100);
         assert_eq!(MASQ_TOTAL_SUPPLY, 37_500_000);
         assert_eq!(WEIS_IN_GWEI, 1_000_000_000);
         assert_eq!(ETH_MAINNET_CONTRACT_CREATION_BLOCK, 11_170_708);
@@ -155,6 +191,7 @@ mod tests {
         assert_eq!(NON_PARSABLE_VALUE, CONFIGURATOR_PREFIX | 12);
         assert_eq!(MISSING_DATA, CONFIGURATOR_PREFIX | 13);
         assert_eq!(UNKNOWN_ERROR, CONFIGURATOR_PREFIX | 14);
+        assert_eq!(ILLEGAL_VALUE, CONFIGURATOR_PREFIX | 15);
         assert_eq!(UI_NODE_COMMUNICATION_PREFIX, 0x8000_0000_0000_0000);
         assert_eq!(NODE_LAUNCH_ERROR, UI_NODE_COMMUNICATION_PREFIX | 1);
         assert_eq!(NODE_NOT_RUNNING_ERROR, UI_NODE_COMMUNICATION_PREFIX | 2);
@@ -170,6 +207,15 @@ mod tests {
             ACCOUNTANT_PREFIX | 2
         );
         assert_eq!(VALUE_EXCEEDS_ALLOWED_LIMIT, ACCOUNTANT_PREFIX | 3);
+        assert_eq!(PAYMENT_RETRY_ERROR, ACCOUNTANT_PREFIX | 4);
+        assert_eq!(PAYABLE_WRITE_OFF_ERROR, ACCOUNTANT_PREFIX | 5);
+        assert_eq!(ACCOUNTS_EXPORT_ERROR, ACCOUNTANT_PREFIX | 6);
+        assert_eq!(ACCOUNTS_IMPORT_ERROR, ACCOUNTANT_PREFIX | 7);
+        assert_eq!(ACCOUNT_DETAIL_ERROR, ACCOUNTANT_PREFIX | 8);
+        assert_eq!(CONSISTENCY_REPAIR_ERROR, ACCOUNTANT_PREFIX | 9);
+        assert_eq!(WHY_NOT_PAID_ERROR, ACCOUNTANT_PREFIX | 10);
+        assert_eq!(BLOCKCHAIN_BRIDGE_PREFIX, 0x0020_0000_0000_0000);
+        assert_eq!(SWEEP_WALLET_ERROR, BLOCKCHAIN_BRIDGE_PREFIX | 1);
         assert_eq!(CENTRAL_DELIMITER, '@');
         assert_eq!(CHAIN_IDENTIFIER_DELIMITER, ':');
         assert_eq!(POLYGON_FAMILY, "polygon");
@@ -204,6 +250,22 @@ mod tests {
             NODE_RECORD_INNER_CURRENT_VERSION,
             DataVersion { major: 0, minor: 1 }
         );
+        assert_eq!(
+            PAYMENT_NOTICE_CURRENT_VERSION,
+            DataVersion { major: 0, minor: 1 }
+        );
+        assert_eq!(
+            EXIT_OPTIONS_QUERY_CURRENT_VERSION,
+            DataVersion { major: 0, minor: 1 }
+        );
+        assert_eq!(
+            EXIT_OPTIONS_REPORT_CURRENT_VERSION,
+            DataVersion { major: 0, minor: 1 }
+        );
+        assert_eq!(
+            USAGE_INVOICE_CURRENT_VERSION,
+            DataVersion { major: 0, minor: 1 }
+        );
         assert_eq!(PAYLOAD_ZERO_SIZE, 0usize);
     }
 
@@ -216,6 +278,10 @@ mod tests {
             GOSSIP_CURRENT_VERSION,
             GOSSIP_FAILURE_CURRENT_VERSION,
             NODE_RECORD_INNER_CURRENT_VERSION,
+            PAYMENT_NOTICE_CURRENT_VERSION,
+            EXIT_OPTIONS_QUERY_CURRENT_VERSION,
+            EXIT_OPTIONS_REPORT_CURRENT_VERSION,
+            USAGE_INVOICE_CURRENT_VERSION,
         ]
         .into_iter()
         .for_each(|item| {
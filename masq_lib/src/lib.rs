@@ -23,6 +23,7 @@ pub mod constants;
 pub mod crash_point;
 pub mod data_version;
 pub mod exit_locations;
+pub mod presets;
 pub mod shared_schema;
 pub mod test_utils;
 pub mod type_obfuscation;
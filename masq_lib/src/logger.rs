@@ -116,6 +116,15 @@ impl Logger {
         }
     }
 
+    // A clone whose lines all carry the span, like "Accountant[scan_id=7]", so that the lines of
+    // one operation passing through several actors can be picked out of the log together
+    pub fn in_span(&self, span: &str) -> Logger {
+        Logger {
+            name: format!("{}[{}]", self.name, span),
+            ..self.clone()
+        }
+    }
+
     pub fn trace<F>(&self, log_function: F)
     where
         F: FnOnce() -> String,
@@ -979,6 +988,25 @@ mod tests {
         tlh.exists_log_containing("error! 42");
     }
 
+    #[test]
+    fn logger_in_span_marks_its_lines_with_the_span() {
+        init_test_logging();
+        let _guard = prepare_test_environment();
+        let logger = make_logger_at_level(Level::Info);
+
+        let subject = logger.in_span("scan_id=7");
+
+        info!(subject, "inside the span");
+        debug!(subject, "below the level of the original logger");
+        assert_eq!(
+            format!("{:?}", subject),
+            "Logger{ name: \"test[scan_id=7]\" }"
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing("INFO: test[scan_id=7]: inside the span");
+        tlh.exists_no_log_containing("below the level of the original logger");
+    }
+
     #[test]
     fn debug_for_logger() {
         let logger = Logger::new("my new logger");
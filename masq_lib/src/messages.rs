@@ -436,6 +436,50 @@ fire_and_forget_message!(UiUnmarshalError, "unmarshalError");
 // These messages are sent to or by the Node only
 ///////////////////////////////////////////////////////////////////
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiBlockchainSelfTestRequest {}
+conversation_message!(UiBlockchainSelfTestRequest, "blockchainSelfTest");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiSelfTestStep {
+    pub name: String,
+    pub passed: bool,
+    #[serde(rename = "latencyMs")]
+    pub latency_ms: u64,
+    // What the query found if it passed, or why it failed
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiBlockchainSelfTestResponse {
+    pub steps: Vec<UiSelfTestStep>,
+}
+conversation_message!(UiBlockchainSelfTestResponse, "blockchainSelfTest");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiBlockchainStatsRequest {}
+conversation_message!(UiBlockchainStatsRequest, "blockchainStats");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiRpcUsage {
+    pub endpoint: String,
+    // UTC, as YYYY-MM-DD
+    pub day: String,
+    pub calls: u64,
+    // Estimated cost of the calls, in units in the spirit of those metered providers bill
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiBlockchainStatsResponse {
+    // Calls allowed per endpoint and day; zero means no quota
+    #[serde(rename = "dailyQuota")]
+    pub daily_quota: u64,
+    // The latest day first
+    pub usage: Vec<UiRpcUsage>,
+}
+conversation_message!(UiBlockchainStatsResponse, "blockchainStats");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UiChangePasswordRequest {
     #[serde(rename = "oldPasswordOpt")]
@@ -449,6 +493,23 @@ conversation_message!(UiChangePasswordRequest, "changePassword");
 pub struct UiChangePasswordResponse {}
 conversation_message!(UiChangePasswordResponse, "changePassword");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiChangeSettingsRequest {
+    #[serde(rename = "gasPriceOpt")]
+    pub gas_price_opt: Option<u64>,
+    #[serde(rename = "scanIntervalsOpt")]
+    pub scan_intervals_opt: Option<UiScanIntervals>,
+    #[serde(rename = "paymentThresholdsOpt")]
+    pub payment_thresholds_opt: Option<UiPaymentThresholds>,
+    #[serde(rename = "logLevelOpt")]
+    pub log_level_opt: Option<String>,
+}
+conversation_message!(UiChangeSettingsRequest, "changeSettings");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiChangeSettingsResponse {}
+conversation_message!(UiChangeSettingsResponse, "changeSettings");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UiCheckPasswordRequest {
     #[serde(rename = "dbPasswordOpt")]
@@ -587,6 +648,180 @@ pub struct UiDescriptorResponse {
 }
 conversation_message!(UiDescriptorResponse, "descriptor");
 
+// The messages the Accountant answers or broadcasts make up the finance protocol, which has a
+// version of its own so that a UI can find out what of it a Node offers. Version 1 is the finance
+// protocol as it was before it had a version.
+pub const FINANCE_PROTOCOL_VERSION: u32 = 4;
+
+// Each finance opcode with the version that introduced it
+pub const FINANCE_OPCODES: &[(&str, u32)] = &[
+    ("financials", 1),
+    ("scan", 1),
+    ("accountDetail", 2),
+    ("checkConsistency", 2),
+    ("disputes", 2),
+    ("exportAccounts", 2),
+    ("financeHandshake", 2),
+    ("importAccounts", 2),
+    ("repairConsistency", 2),
+    ("retryFailedPayments", 2),
+    ("serviceFeeTopUp", 3),
+    ("spendRateLimit", 2),
+    ("transactionFeeRunway", 2),
+    ("triggerPayableScan", 2),
+    ("whyNotPaid", 4),
+    ("writeOffPayable", 2),
+];
+
+// A field added to a finance message that existed before it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FinanceField {
+    pub opcode: &'static str,
+    // Names of the objects leading to the field from the payload; arrays on the way are crossed
+    // element by element
+    pub path: &'static [&'static str],
+    pub name: &'static str,
+    pub version: u32,
+}
+
+pub const FINANCE_FIELDS: &[FinanceField] = &[
+    FinanceField {
+        opcode: "financials",
+        path: &["statsOpt"],
+        name: "approximateFiatOpt",
+        version: 2,
+    },
+    FinanceField {
+        opcode: "financials",
+        path: &["statsOpt"],
+        name: "observeOnlyOpt",
+        version: 2,
+    },
+    FinanceField {
+        opcode: "financials",
+        path: &["statsOpt"],
+        name: "aging",
+        version: 2,
+    },
+    FinanceField {
+        opcode: "financials",
+        path: &["queryResultsOpt", "payableOpt"],
+        name: "pendingPaymentOpt",
+        version: 2,
+    },
+];
+
+// The opcodes, and the fields as "opcode.fieldName", that a given version of the finance protocol
+// offers
+pub fn finance_capabilities(version: u32) -> Vec<String> {
+    let opcodes = FINANCE_OPCODES
+        .iter()
+        .filter(|(_, introduced)| *introduced <= version)
+        .map(|(opcode, _)| opcode.to_string());
+    let fields = FINANCE_FIELDS
+        .iter()
+        .filter(|field| field.version <= version)
+        .map(|field| format!("{}.{}", field.opcode, field.name));
+    opcodes.chain(fields).collect()
+}
+
+// A finance message may name the version of the finance protocol it is written in by an extra
+// member of its payload. Parties that don't know about versions ignore it like any unknown field.
+pub struct FinanceEnvelope {}
+
+impl FinanceEnvelope {
+    pub const VERSION_FIELD: &'static str = "financeVersion";
+
+    pub fn version_of(body: &MessageBody) -> Option<u32> {
+        let json = body.payload.as_ref().ok()?;
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        value
+            .get(Self::VERSION_FIELD)?
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+    }
+
+    pub fn stamp(body: MessageBody, version: u32) -> MessageBody {
+        Self::rewrite(body, |payload| {
+            payload.insert(Self::VERSION_FIELD.to_string(), version.into());
+        })
+    }
+
+    // Leaves out the fields the finance protocol acquired after the given version
+    pub fn downgrade(body: MessageBody, version: u32) -> MessageBody {
+        let fields = FINANCE_FIELDS
+            .iter()
+            .filter(|field| field.opcode == body.opcode && field.version > version)
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            return body;
+        }
+        Self::rewrite(body, |payload| {
+            fields
+                .iter()
+                .for_each(|field| Self::remove_field(payload, field.path, field.name))
+        })
+    }
+
+    fn rewrite<F>(body: MessageBody, change: F) -> MessageBody
+    where
+        F: FnOnce(&mut serde_json::Map<String, serde_json::Value>),
+    {
+        let parsed_opt = match &body.payload {
+            Ok(json) => serde_json::from_str::<serde_json::Value>(json).ok(),
+            Err(_) => None,
+        };
+        let mut value = match parsed_opt {
+            Some(value) => value,
+            None => return body,
+        };
+        match value.as_object_mut() {
+            Some(payload) => change(payload),
+            None => return body,
+        }
+        MessageBody {
+            payload: Ok(value.to_string()),
+            ..body
+        }
+    }
+
+    fn remove_field(
+        object: &mut serde_json::Map<String, serde_json::Value>,
+        path: &[&str],
+        name: &str,
+    ) {
+        match path.split_first() {
+            None => {
+                object.remove(name);
+            }
+            Some((first, rest)) => match object.get_mut(*first) {
+                Some(serde_json::Value::Object(inner)) => Self::remove_field(inner, rest, name),
+                Some(serde_json::Value::Array(elements)) => elements
+                    .iter_mut()
+                    .filter_map(|element| element.as_object_mut())
+                    .for_each(|inner| Self::remove_field(inner, rest, name)),
+                _ => (),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiFinanceHandshakeRequest {
+    // The latest version of the finance protocol the UI understands
+    #[serde(rename = "maxVersion")]
+    pub max_version: u32,
+}
+conversation_message!(UiFinanceHandshakeRequest, "financeHandshake");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiFinanceHandshakeResponse {
+    // The version the Node will speak to this UI from now on
+    pub version: u32,
+    pub capabilities: Vec<String>,
+}
+conversation_message!(UiFinanceHandshakeResponse, "financeHandshake");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiFinancialsRequest {
     #[serde(rename = "statsRequired")]
@@ -662,6 +897,65 @@ pub struct UiFinancialStatistics {
     pub total_unpaid_receivable_gwei: i64,
     #[serde(rename = "totalPaidReceivableGwei")]
     pub total_paid_receivable_gwei: u64,
+    #[serde(rename = "approximateFiatOpt")]
+    pub approximate_fiat_opt: Option<UiApproximateFiatTotals>,
+    #[serde(rename = "observeOnlyOpt")]
+    pub observe_only_opt: Option<UiObserveOnlyPayables>,
+    // Missing from responses downgraded to version 1 of the finance protocol
+    #[serde(default)]
+    pub aging: UiDebtAging,
+}
+
+// Unpaid balances grouped by how long ago the account was last paid. Receivables paid off or
+// overpaid don't count.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct UiDebtAging {
+    pub payable: UiAgingBuckets,
+    pub receivable: UiAgingBuckets,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct UiAgingBuckets {
+    #[serde(rename = "upToOneDay")]
+    pub up_to_one_day: UiAgingBucket,
+    #[serde(rename = "oneToThreeDays")]
+    pub one_to_three_days: UiAgingBucket,
+    #[serde(rename = "threeToSevenDays")]
+    pub three_to_seven_days: UiAgingBucket,
+    #[serde(rename = "overSevenDays")]
+    pub over_seven_days: UiAgingBucket,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct UiAgingBucket {
+    pub accounts: u64,
+    #[serde(rename = "balanceGwei")]
+    pub balance_gwei: u64,
+}
+
+// Only present while the Node has no consuming wallet to pay from: its payable scans then merely
+// observe. Both values are zero until the first such scan has finished.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct UiObserveOnlyPayables {
+    #[serde(rename = "wouldBePaidAccounts")]
+    pub would_be_paid_accounts: u64,
+    #[serde(rename = "wouldBePaidGwei")]
+    pub would_be_paid_gwei: u64,
+}
+
+// Only present if a price feed is configured and has answered recently. Values are decimal strings
+// rounded to hundredths of the currency unit; they are approximate and must not be used for accounting.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct UiApproximateFiatTotals {
+    pub currency: String,
+    #[serde(rename = "totalUnpaidAndPendingPayable")]
+    pub total_unpaid_and_pending_payable: String,
+    #[serde(rename = "totalPaidPayable")]
+    pub total_paid_payable: String,
+    #[serde(rename = "totalUnpaidReceivable")]
+    pub total_unpaid_receivable: String,
+    #[serde(rename = "totalPaidReceivable")]
+    pub total_paid_receivable: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -681,6 +975,9 @@ pub struct UiPayableAccount {
     pub balance_gwei: u64,
     #[serde(rename = "pendingPayableHashOpt")]
     pub pending_payable_hash_opt: Option<String>,
+    // Details of the transaction identified by the hash above, as far as the Node has tracked it
+    #[serde(rename = "pendingPaymentOpt")]
+    pub pending_payment_opt: Option<UiUnconfirmedPayment>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -692,6 +989,258 @@ pub struct UiReceivableAccount {
     pub balance_gwei: i64,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRetryFailedPaymentsRequest {
+    // Failed transactions whose payments are to be sent again; if empty, nothing is resubmitted
+    #[serde(rename = "transactionHashes")]
+    pub transaction_hashes: Vec<String>,
+    #[serde(rename = "gasPriceBumpPercentOpt")]
+    pub gas_price_bump_percent_opt: Option<u8>,
+}
+conversation_message!(UiRetryFailedPaymentsRequest, "retryFailedPayments");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRetryFailedPaymentsResponse {
+    pub payments: Vec<UiUnconfirmedPayment>,
+    #[serde(rename = "resubmittedHashes")]
+    pub resubmitted_hashes: Vec<String>,
+}
+conversation_message!(UiRetryFailedPaymentsResponse, "retryFailedPayments");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWriteOffPayableRequest {
+    #[serde(rename = "creditorWallet")]
+    pub creditor_wallet: String,
+    pub reason: String,
+    // If true, services the creditor goes on providing aren't recorded as new debts
+    #[serde(rename = "banCreditor")]
+    pub ban_creditor: bool,
+}
+conversation_message!(UiWriteOffPayableRequest, "writeOffPayable");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWriteOffPayableResponse {
+    #[serde(rename = "writtenOffGwei")]
+    pub written_off_gwei: u64,
+}
+conversation_message!(UiWriteOffPayableResponse, "writeOffPayable");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiExportAccountsRequest {
+    // Where the Node writes the file; an existing file is never overwritten
+    pub path: String,
+}
+conversation_message!(UiExportAccountsRequest, "exportAccounts");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiExportAccountsResponse {
+    pub payables: usize,
+    pub receivables: usize,
+    #[serde(rename = "pendingPayables")]
+    pub pending_payables: usize,
+}
+conversation_message!(UiExportAccountsResponse, "exportAccounts");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiImportAccountsRequest {
+    // A file written by exportAccounts
+    pub path: String,
+}
+conversation_message!(UiImportAccountsRequest, "importAccounts");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiImportAccountsResponse {
+    pub payables: usize,
+    pub receivables: usize,
+    #[serde(rename = "pendingPayables")]
+    pub pending_payables: usize,
+}
+conversation_message!(UiImportAccountsResponse, "importAccounts");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiAccountDetailRequest {
+    pub wallet: String,
+}
+conversation_message!(UiAccountDetailRequest, "accountDetail");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiAccountDetailResponse {
+    pub wallet: String,
+    pub banned: bool,
+    // What we owe the wallet
+    #[serde(rename = "payableOpt")]
+    pub payable_opt: Option<UiPayableDetail>,
+    // What the wallet owes us
+    #[serde(rename = "receivableOpt")]
+    pub receivable_opt: Option<UiReceivableDetail>,
+    // Only as far back as the Node keeps them to detect vanished payments
+    #[serde(rename = "receivedPayments")]
+    pub received_payments: Vec<UiReceivedPayment>,
+    #[serde(rename = "writeOffs")]
+    pub write_offs: Vec<UiPayableWriteOff>,
+}
+conversation_message!(UiAccountDetailResponse, "accountDetail");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiPayableDetail {
+    #[serde(rename = "balanceGwei")]
+    pub balance_gwei: u64,
+    #[serde(rename = "lastPaidAgeS")]
+    pub last_paid_age_s: u64,
+    #[serde(rename = "pendingPaymentOpt")]
+    pub pending_payment_opt: Option<UiUnconfirmedPayment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiReceivableDetail {
+    #[serde(rename = "balanceGwei")]
+    pub balance_gwei: i64,
+    #[serde(rename = "lastReceivedAgeS")]
+    pub last_received_age_s: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiReceivedPayment {
+    #[serde(rename = "blockNumber")]
+    pub block_number: u64,
+    #[serde(rename = "amountGwei")]
+    pub amount_gwei: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiPayableWriteOff {
+    #[serde(rename = "amountGwei")]
+    pub amount_gwei: u64,
+    pub reason: String,
+    pub banned: bool,
+    #[serde(rename = "ageS")]
+    pub age_s: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWhyNotPaidRequest {
+    pub wallet: String,
+}
+conversation_message!(UiWhyNotPaidRequest, "whyNotPaid");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWhyNotPaidResponse {
+    pub wallet: String,
+    // What we owe the wallet now
+    #[serde(rename = "owedGwei")]
+    pub owed_gwei: u64,
+    // Too young or too small a debt isn't even considered for payment
+    #[serde(rename = "belowThreshold")]
+    pub below_threshold: bool,
+    // Forgotten when the Node restarts
+    #[serde(rename = "latestScanOpt")]
+    pub latest_scan_opt: Option<UiPaymentDecisionChain>,
+}
+conversation_message!(UiWhyNotPaidResponse, "whyNotPaid");
+
+// What the latest payable scan that considered the wallet decided about it, step by step
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiPaymentDecisionChain {
+    #[serde(rename = "ageS")]
+    pub age_s: u64,
+    #[serde(rename = "owedGwei")]
+    pub owed_gwei: u64,
+    #[serde(rename = "serviceFeeBalanceGwei")]
+    pub service_fee_balance_gwei: u64,
+    #[serde(rename = "transactionFeeBalanceGwei")]
+    pub transaction_fee_balance_gwei: u64,
+    pub decisions: Vec<UiPaymentDecision>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiPaymentDecision {
+    // One of "qualified", "adjusted", "heldBack", "sent" and "failed"
+    pub decision: String,
+    // What was left of the debt to pay after the decision
+    #[serde(rename = "payableGwei")]
+    pub payable_gwei: u64,
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiCheckConsistencyRequest {}
+conversation_message!(UiCheckConsistencyRequest, "checkConsistency");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiCheckConsistencyResponse {
+    // None if the accounts are consistent; otherwise the plan to confirm with repairConsistency
+    #[serde(rename = "planIdOpt")]
+    pub plan_id_opt: Option<u64>,
+    pub anomalies: Vec<UiAnomaly>,
+}
+conversation_message!(UiCheckConsistencyResponse, "checkConsistency");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiAnomaly {
+    pub description: String,
+    pub repair: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRepairConsistencyRequest {
+    #[serde(rename = "planId")]
+    pub plan_id: u64,
+}
+conversation_message!(UiRepairConsistencyRequest, "repairConsistency");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRepairConsistencyResponse {
+    pub repaired: usize,
+}
+conversation_message!(UiRepairConsistencyResponse, "repairConsistency");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiDisputesRequest {}
+conversation_message!(UiDisputesRequest, "disputes");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiDisputesResponse {
+    // The oldest first
+    pub disputes: Vec<UiInvoiceDispute>,
+}
+conversation_message!(UiDisputesResponse, "disputes");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiInvoiceDispute {
+    #[serde(rename = "creditorWallet")]
+    pub creditor_wallet: String,
+    #[serde(rename = "periodStartSec")]
+    pub period_start_sec: u64,
+    #[serde(rename = "periodEndSec")]
+    pub period_end_sec: u64,
+    // What the creditor claims it did for us
+    #[serde(rename = "invoicedServices")]
+    pub invoiced_services: u64,
+    // What our own books say it did
+    #[serde(rename = "bookedServices")]
+    pub booked_services: u64,
+    #[serde(rename = "invoicedGwei")]
+    pub invoiced_gwei: u64,
+    #[serde(rename = "bookedGwei")]
+    pub booked_gwei: u64,
+    #[serde(rename = "receivedAtSec")]
+    pub received_at_sec: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiUnconfirmedPayment {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    #[serde(rename = "amountGwei")]
+    pub amount_gwei: u64,
+    #[serde(rename = "ageS")]
+    pub age_s: u64,
+    pub attempt: u16,
+    // None while the transaction is still waiting for its confirmation
+    #[serde(rename = "failureOpt")]
+    pub failure_opt: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiGenerateSeedSpec {
     #[serde(rename = "mnemonicPhraseSizeOpt")]
@@ -712,6 +1261,10 @@ pub struct UiGenerateWalletsRequest {
     pub consuming_derivation_path_opt: Option<String>,
     #[serde(rename = "earningDerivationPathOpt")]
     pub earning_derivation_path_opt: Option<String>,
+    // Derives that many earning wallets, at consecutive indexes of the earning derivation path,
+    // for the Node to rotate among
+    #[serde(rename = "earningWalletCountOpt")]
+    pub earning_wallet_count_opt: Option<u32>,
 }
 conversation_message!(UiGenerateWalletsRequest, "generateWallets");
 
@@ -773,6 +1326,8 @@ pub struct UiRecoverWalletsRequest {
     pub earning_derivation_path_opt: Option<String>,
     #[serde(rename = "earningAddressOpt")]
     pub earning_address_opt: Option<String>,
+    #[serde(rename = "earningWalletCountOpt")]
+    pub earning_wallet_count_opt: Option<u32>,
 }
 conversation_message!(UiRecoverWalletsRequest, "recoverWallets");
 
@@ -808,9 +1363,55 @@ pub struct UiScanRequest {
 conversation_message!(UiScanRequest, "scan");
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
-pub struct UiScanResponse {}
+pub struct UiScanResponse {
+    // A scan of the type was running already. The requested one is queued to follow it, and this
+    // response comes before it has begun
+    #[serde(default)]
+    pub queued: bool,
+}
 conversation_message!(UiScanResponse, "scan");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiTriggerPayableScanRequest {}
+conversation_message!(UiTriggerPayableScanRequest, "triggerPayableScan");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiTriggerPayableScanResponse {
+    // How many debts were found due for payment
+    pub qualified: usize,
+    pub paid: Vec<UiPaidPayable>,
+    // Paid, but less than owed
+    pub adjusted: Vec<UiAdjustedPayable>,
+    pub skipped: Vec<UiSkippedPayable>,
+}
+conversation_message!(UiTriggerPayableScanResponse, "triggerPayableScan");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiPaidPayable {
+    pub wallet: String,
+    #[serde(rename = "amountGwei")]
+    pub amount_gwei: u64,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiAdjustedPayable {
+    pub wallet: String,
+    #[serde(rename = "owedGwei")]
+    pub owed_gwei: u64,
+    #[serde(rename = "adjustedGwei")]
+    pub adjusted_gwei: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSkippedPayable {
+    pub wallet: String,
+    #[serde(rename = "owedGwei")]
+    pub owed_gwei: u64,
+    pub reason: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiSetConfigurationRequest {
     pub name: String,
@@ -831,6 +1432,86 @@ conversation_message!(UiShutdownRequest, "shutdown");
 pub struct UiShutdownResponse {}
 conversation_message!(UiShutdownResponse, "shutdown");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiTransactionFeeRunwayBroadcast {
+    #[serde(rename = "consumingWalletAddress")]
+    pub consuming_wallet_address: String,
+    #[serde(rename = "transactionFeeBalanceGwei")]
+    pub transaction_fee_balance_gwei: u64,
+    #[serde(rename = "scansLeft")]
+    pub scans_left: u64,
+    #[serde(rename = "runwaySec")]
+    pub runway_sec: u64,
+}
+fire_and_forget_message!(UiTransactionFeeRunwayBroadcast, "transactionFeeRunway");
+
+// Payments due but left unpaid by a payable scan because they would have gone over a spend rate
+// limit; the allowances are what was left under the tighter of the hourly and daily caps
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiSpendRateLimitBroadcast {
+    #[serde(rename = "deferredPayments")]
+    pub deferred_payments: u64,
+    #[serde(rename = "deferredGwei")]
+    pub deferred_gwei: u64,
+    #[serde(rename = "serviceFeeAllowanceGwei")]
+    pub service_fee_allowance_gwei: u64,
+    #[serde(rename = "transactionFeeAllowanceGwei")]
+    pub transaction_fee_allowance_gwei: u64,
+}
+fire_and_forget_message!(UiSpendRateLimitBroadcast, "spendRateLimit");
+
+// The service fee balance won't pay off what we owe our creditors now together with what we're
+// projected to owe them by the end of the top-up horizon
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiServiceFeeTopUpBroadcast {
+    #[serde(rename = "consumingWalletAddress")]
+    pub consuming_wallet_address: String,
+    #[serde(rename = "serviceFeeBalanceGwei")]
+    pub service_fee_balance_gwei: u64,
+    #[serde(rename = "outstandingDebtGwei")]
+    pub outstanding_debt_gwei: u64,
+    #[serde(rename = "projectedDebtGrowthGwei")]
+    pub projected_debt_growth_gwei: u64,
+    #[serde(rename = "horizonHours")]
+    pub horizon_hours: u64,
+    #[serde(rename = "topUpGwei")]
+    pub top_up_gwei: u64,
+}
+fire_and_forget_message!(UiServiceFeeTopUpBroadcast, "serviceFeeTopUp");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiSweepWalletRequest {
+    #[serde(rename = "recipientAddress")]
+    pub recipient_address: String,
+    // Service fee left behind in the consuming wallet; nothing if None
+    #[serde(rename = "reserveGweiOpt")]
+    pub reserve_gwei_opt: Option<u64>,
+}
+conversation_message!(UiSweepWalletRequest, "sweepWallet");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiSweepWalletResponse {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    #[serde(rename = "sweptGwei")]
+    pub swept_gwei: u64,
+}
+conversation_message!(UiSweepWalletResponse, "sweepWallet");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiUnlockWalletRequest {
+    #[serde(rename = "dbPassword")]
+    pub db_password: String,
+}
+conversation_message!(UiUnlockWalletRequest, "unlockWallet");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiUnlockWalletResponse {
+    #[serde(rename = "consumingWalletAddress")]
+    pub consuming_wallet_address: String,
+}
+conversation_message!(UiUnlockWalletResponse, "unlockWallet");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UiWalletAddressesRequest {
     #[serde(rename = "dbPassword")]
@@ -911,6 +1592,36 @@ pub struct UiSetExitLocationResponse {
 }
 conversation_message!(UiSetExitLocationResponse, "exitLocation");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiExitOptionsRequest {}
+conversation_message!(UiExitOptionsRequest, "exitOptions");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiExitNeighbor {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    #[serde(rename = "latencyMs")]
+    pub latency_ms: u64,
+    #[serde(rename = "ageSeconds")]
+    pub age_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiExitCountryOption {
+    #[serde(rename = "countryCode")]
+    pub country_code: String,
+    // The neighbors reporting exit in the country, the most responsive first
+    pub neighbors: Vec<UiExitNeighbor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiExitOptionsResponse {
+    // Ordered by the most responsive neighbor of each country
+    #[serde(rename = "exitCountries")]
+    pub exit_countries: Vec<UiExitCountryOption>,
+}
+conversation_message!(UiExitOptionsResponse, "exitOptions");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -920,6 +1631,8 @@ mod tests {
     #[test]
     fn constants_have_correct_values() {
         assert_eq!(NODE_UI_PROTOCOL, "MASQNode-UIv2");
+        assert_eq!(FINANCE_PROTOCOL_VERSION, 4);
+        assert_eq!(FinanceEnvelope::VERSION_FIELD, "financeVersion");
     }
 
     #[test]
@@ -1276,4 +1989,152 @@ mod tests {
             Err("Unrecognized ordering: 'upside-down'".to_string())
         );
     }
+
+    #[test]
+    fn finance_capabilities_grow_with_the_version() {
+        let version_1 = finance_capabilities(1);
+        let version_2 = finance_capabilities(2);
+        let version_3 = finance_capabilities(3);
+        let version_4 = finance_capabilities(4);
+
+        assert_eq!(
+            version_1,
+            vec!["financials".to_string(), "scan".to_string()]
+        );
+        assert_eq!(
+            version_2.len(),
+            FINANCE_OPCODES.len() + FINANCE_FIELDS.len() - 2
+        );
+        assert!(version_2.contains(&"financeHandshake".to_string()));
+        assert!(version_2.contains(&"financials.aging".to_string()));
+        assert!(version_2.contains(&"financials.pendingPaymentOpt".to_string()));
+        assert!(!version_2.contains(&"serviceFeeTopUp".to_string()));
+        assert_eq!(
+            version_3.len(),
+            FINANCE_OPCODES.len() + FINANCE_FIELDS.len() - 1
+        );
+        assert!(version_3.contains(&"serviceFeeTopUp".to_string()));
+        assert!(!version_3.contains(&"whyNotPaid".to_string()));
+        assert_eq!(
+            version_4.len(),
+            FINANCE_OPCODES.len() + FINANCE_FIELDS.len()
+        );
+        assert!(version_4.contains(&"whyNotPaid".to_string()));
+        assert_eq!(finance_capabilities(FINANCE_PROTOCOL_VERSION), version_4);
+    }
+
+    #[test]
+    fn finance_envelope_stamps_a_version_that_unaware_parties_ignore() {
+        let body = UiScanRequest {
+            scan_type: ScanType::Payables,
+        }
+        .tmb(1234);
+
+        let stamped = FinanceEnvelope::stamp(body.clone(), 2);
+
+        assert_eq!(FinanceEnvelope::version_of(&body), None);
+        assert_eq!(FinanceEnvelope::version_of(&stamped), Some(2));
+        assert_eq!(
+            UiScanRequest::fmb(stamped).unwrap(),
+            (
+                UiScanRequest {
+                    scan_type: ScanType::Payables
+                },
+                1234
+            )
+        );
+    }
+
+    #[test]
+    fn finance_envelope_leaves_error_payloads_alone() {
+        let body = MessageBody {
+            opcode: "financials".to_string(),
+            path: Conversation(1234),
+            payload: Err((4321, "Booga".to_string())),
+        };
+
+        let result = FinanceEnvelope::downgrade(FinanceEnvelope::stamp(body.clone(), 1), 1);
+
+        assert_eq!(result, body);
+        assert_eq!(FinanceEnvelope::version_of(&result), None);
+    }
+
+    #[test]
+    fn finance_envelope_downgrade_leaves_out_the_fields_of_later_versions() {
+        let response = || UiFinancialsResponse {
+            stats_opt: Some(UiFinancialStatistics {
+                total_unpaid_and_pending_payable_gwei: 1,
+                total_paid_payable_gwei: 2,
+                total_unpaid_receivable_gwei: 3,
+                total_paid_receivable_gwei: 4,
+                approximate_fiat_opt: None,
+                observe_only_opt: Some(UiObserveOnlyPayables {
+                    would_be_paid_accounts: 5,
+                    would_be_paid_gwei: 6,
+                }),
+                aging: UiDebtAging {
+                    payable: UiAgingBuckets {
+                        up_to_one_day: UiAgingBucket {
+                            accounts: 7,
+                            balance_gwei: 8,
+                        },
+                        ..Default::default()
+                    },
+                    receivable: UiAgingBuckets::default(),
+                },
+            }),
+            query_results_opt: Some(QueryResults {
+                payable_opt: Some(vec![UiPayableAccount {
+                    wallet: "0x01".to_string(),
+                    age_s: 9,
+                    balance_gwei: 10,
+                    pending_payable_hash_opt: Some("0x02".to_string()),
+                    pending_payment_opt: Some(UiUnconfirmedPayment {
+                        transaction_hash: "0x02".to_string(),
+                        amount_gwei: 10,
+                        age_s: 11,
+                        attempt: 1,
+                        failure_opt: None,
+                    }),
+                }]),
+                receivable_opt: None,
+            }),
+        };
+
+        let version_1 = FinanceEnvelope::downgrade(response().tmb(1234), 1);
+        let version_2 = FinanceEnvelope::downgrade(response().tmb(1234), 2);
+
+        let version_1_json: serde_json::Value =
+            serde_json::from_str(version_1.payload.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            version_1_json,
+            serde_json::json!({
+                "statsOpt": {
+                    "totalUnpaidAndPendingPayableGwei": 1,
+                    "totalPaidPayableGwei": 2,
+                    "totalUnpaidReceivableGwei": 3,
+                    "totalPaidReceivableGwei": 4
+                },
+                "queryResultsOpt": {
+                    "payableOpt": [{
+                        "wallet": "0x01",
+                        "ageS": 9,
+                        "balanceGwei": 10,
+                        "pendingPayableHashOpt": "0x02"
+                    }],
+                    "receivableOpt": null
+                }
+            })
+        );
+        let (parsed, context_id) = UiFinancialsResponse::fmb(version_1).unwrap();
+        assert_eq!(context_id, 1234);
+        let stats = parsed.stats_opt.unwrap();
+        assert_eq!(stats.observe_only_opt, None);
+        assert_eq!(stats.aging, UiDebtAging::default());
+        assert_eq!(
+            parsed.query_results_opt.unwrap().payable_opt.unwrap()[0].pending_payment_opt,
+            None
+        );
+        assert_eq!(version_2, response().tmb(1234));
+    }
 }
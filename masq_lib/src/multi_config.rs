@@ -156,14 +156,14 @@ pub trait VclArg: Debug {
     fn dup(&self) -> Box<dyn VclArg>;
 }
 
-fn vcl_args_to_args(vcl_args: &[Box<dyn VclArg>]) -> Vec<String> {
+pub(crate) fn vcl_args_to_args(vcl_args: &[Box<dyn VclArg>]) -> Vec<String> {
     vec![String::new()] // ersatz command
         .into_iter()
         .chain(vcl_args.iter().flat_map(|va| va.to_args()))
         .collect()
 }
 
-fn vcl_args_to_vcl_args(vcl_args: &[Box<dyn VclArg>]) -> Vec<&dyn VclArg> {
+pub(crate) fn vcl_args_to_vcl_args(vcl_args: &[Box<dyn VclArg>]) -> Vec<&dyn VclArg> {
     vcl_args.iter().map(|box_ref| box_ref.as_ref()).collect()
 }
 
@@ -382,6 +382,7 @@ pub enum ConfigFileVclError {
     Unreadable(PathBuf, std::io::Error),
     CorruptToml(PathBuf, String),
     InvalidConfig(PathBuf, String),
+    IncludeCycle(Vec<PathBuf>),
 }
 
 impl Display for ConfigFileVclError {
@@ -412,6 +413,15 @@ impl Display for ConfigFileVclError {
                 "Configuration file {:?} doesn't make sense: {}.",
                 path, error
             ),
+            ConfigFileVclError::IncludeCycle(paths) => write!(
+                fmt,
+                "Configuration files include each other in a cycle: {}.",
+                paths
+                    .iter()
+                    .map(|path| format!("{:?}", path))
+                    .collect::<Vec<String>>()
+                    .join(" -> ")
+            ),
         }
     }
 }
@@ -421,7 +431,7 @@ impl ConfigFileVcl {
         file_path: &Path,
         user_specified: bool,
     ) -> Result<ConfigFileVcl, ConfigFileVclError> {
-        let mut file: File = match File::open(file_path) {
+        let file: File = match File::open(file_path) {
             Err(e) => {
                 if user_specified {
                     return Err(ConfigFileVclError::OpenError(file_path.to_path_buf(), e));
@@ -431,23 +441,7 @@ impl ConfigFileVcl {
             }
             Ok(file) => file,
         };
-        let mut contents = String::new();
-        match file.read_to_string(&mut contents) {
-            Err(ref e) if e.kind() == ErrorKind::InvalidData => {
-                return Err(ConfigFileVclError::CorruptUtf8(file_path.to_path_buf()))
-            }
-            Err(e) => return Err(ConfigFileVclError::Unreadable(file_path.to_path_buf(), e)),
-            Ok(_) => (),
-        };
-        let table: Table = match toml::de::from_str(&contents) {
-            Err(e) => {
-                return Err(ConfigFileVclError::CorruptToml(
-                    file_path.to_path_buf(),
-                    e.to_string(),
-                ))
-            }
-            Ok(table) => table,
-        };
+        let table = Self::load_table(file_path, file, &mut vec![])?;
         let vcl_args_and_errs = table.keys().map(|key| {
             let name = format!("--{}", key);
             let value = match table.get(key).expect("value disappeared") {
@@ -495,6 +489,121 @@ impl ConfigFileVcl {
             Err(vcl_errs.remove(0))
         }
     }
+
+    fn load_table(
+        file_path: &Path,
+        mut file: File,
+        include_chain: &mut Vec<PathBuf>,
+    ) -> Result<Table, ConfigFileVclError> {
+        let mut contents = String::new();
+        match file.read_to_string(&mut contents) {
+            Err(ref e) if e.kind() == ErrorKind::InvalidData => {
+                return Err(ConfigFileVclError::CorruptUtf8(file_path.to_path_buf()))
+            }
+            Err(e) => return Err(ConfigFileVclError::Unreadable(file_path.to_path_buf(), e)),
+            Ok(_) => (),
+        };
+        let mut table: Table = match toml::de::from_str(&contents) {
+            Err(e) => {
+                return Err(ConfigFileVclError::CorruptToml(
+                    file_path.to_path_buf(),
+                    e.to_string(),
+                ))
+            }
+            Ok(table) => table,
+        };
+        let includes = Self::extract_includes(file_path, &mut table)?;
+        Self::expand_environment_variables(file_path, &mut table)?;
+        include_chain.push(canonical_path(file_path));
+        // included files are applied in order, and the including file overrides them all
+        let mut merged = Table::new();
+        for include in includes {
+            let include_path = file_path
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join(include);
+            let canonical_include_path = canonical_path(&include_path);
+            if include_chain.contains(&canonical_include_path) {
+                let mut cycle = include_chain.clone();
+                cycle.push(canonical_include_path);
+                return Err(ConfigFileVclError::IncludeCycle(cycle));
+            }
+            let include_file = match File::open(&include_path) {
+                Err(e) => return Err(ConfigFileVclError::OpenError(include_path, e)),
+                Ok(file) => file,
+            };
+            merged.extend(Self::load_table(
+                &include_path,
+                include_file,
+                include_chain,
+            )?);
+        }
+        include_chain.pop();
+        merged.extend(table);
+        Ok(merged)
+    }
+
+    fn extract_includes(
+        file_path: &Path,
+        table: &mut Table,
+    ) -> Result<Vec<String>, ConfigFileVclError> {
+        let invalid_include = || {
+            ConfigFileVclError::InvalidConfig(
+                file_path.to_path_buf(),
+                "'include' must be an array of file names".to_string(),
+            )
+        };
+        match table.remove("include") {
+            None => Ok(vec![]),
+            Some(Value::Array(values)) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::String(name) => Ok(name),
+                    _ => Err(invalid_include()),
+                })
+                .collect(),
+            Some(_) => Err(invalid_include()),
+        }
+    }
+
+    fn expand_environment_variables(
+        file_path: &Path,
+        table: &mut Table,
+    ) -> Result<(), ConfigFileVclError> {
+        let regex = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("Bad regex");
+        for (key, value) in table.iter_mut() {
+            let string = match value {
+                Value::String(string) => string,
+                _ => continue,
+            };
+            let mut expanded = String::new();
+            let mut copied_up_to = 0;
+            for captures in regex.captures_iter(string) {
+                let reference = captures.get(0).expect("Regex match disappeared");
+                let variable_name = &captures[1];
+                let variable_value = match std::env::var(variable_name) {
+                    Ok(v) => v,
+                    Err(_) => return Err(ConfigFileVclError::InvalidConfig(
+                        file_path.to_path_buf(),
+                        format!(
+                            "parameter '{}' refers to environment variable '{}', which is not set",
+                            key, variable_name
+                        ),
+                    )),
+                };
+                expanded.push_str(&string[copied_up_to..reference.start()]);
+                expanded.push_str(&variable_value);
+                copied_up_to = reference.end();
+            }
+            expanded.push_str(&string[copied_up_to..]);
+            *string = expanded;
+        }
+        Ok(())
+    }
+}
+
+fn canonical_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
 
 fn append<T>(ts: Vec<T>, t: T) -> Vec<T> {
@@ -1166,6 +1275,196 @@ pub mod tests {
         )
     }
 
+    #[test]
+    fn config_file_vcl_error_displays_include_cycle() {
+        let subject = ConfigFileVclError::IncludeCycle(vec![
+            PathBuf::from("booga.toml"),
+            PathBuf::from("wooga.toml"),
+            PathBuf::from("booga.toml"),
+        ]);
+
+        let result = subject.to_string();
+
+        assert_eq!(
+            result,
+            "Configuration files include each other in a cycle: \"booga.toml\" -> \"wooga.toml\" -> \"booga.toml\".".to_string()
+        );
+    }
+
+    #[test]
+    fn config_file_vcl_expands_environment_variables() {
+        let _guard = EnvironmentGuard::new();
+        let home_dir = ensure_node_home_directory_exists(
+            "multi_config",
+            "config_file_vcl_expands_environment_variables",
+        );
+        let file_path = home_dir.join("config.toml");
+        {
+            let mut toml_file = File::create(&file_path).unwrap();
+            toml_file
+                .write_all(b"string-arg = \"${BOOGA}/${WOOGA_2}/$BOOGA/${}\"\nnumeric-arg = 47\n")
+                .unwrap();
+        }
+        std::env::set_var("BOOGA", "booga");
+        std::env::set_var("WOOGA_2", "wooga");
+
+        let subject = ConfigFileVcl::new(&file_path, true).unwrap();
+
+        assert_eq!(
+            subject.args(),
+            vec![
+                "".to_string(),
+                "--numeric-arg".to_string(),
+                "47".to_string(),
+                "--string-arg".to_string(),
+                "booga/wooga/$BOOGA/${}".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn config_file_vcl_complains_about_undefined_environment_variable() {
+        let _guard = EnvironmentGuard::new();
+        let home_dir = ensure_node_home_directory_exists(
+            "multi_config",
+            "config_file_vcl_complains_about_undefined_environment_variable",
+        );
+        let file_path = home_dir.join("config.toml");
+        {
+            let mut toml_file = File::create(&file_path).unwrap();
+            toml_file
+                .write_all(b"string-arg = \"${UNDEFINED_BOOGA}\"\n")
+                .unwrap();
+        }
+        std::env::remove_var("UNDEFINED_BOOGA");
+
+        let result = ConfigFileVcl::new(&file_path, true).err().unwrap();
+
+        assert_contains(
+            &result.to_string(),
+            "doesn't make sense: parameter 'string-arg' refers to environment variable 'UNDEFINED_BOOGA', which is not set.",
+        )
+    }
+
+    #[test]
+    fn config_file_vcl_merges_included_files_relative_to_the_including_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "multi_config",
+            "config_file_vcl_merges_included_files_relative_to_the_including_file",
+        );
+        std::fs::create_dir_all(home_dir.join("common")).unwrap();
+        let file_path = home_dir.join("config.toml");
+        {
+            let mut toml_file = File::create(&file_path).unwrap();
+            toml_file
+                .write_all(b"include = [\"common/first.toml\", \"common/second.toml\"]\nstring-arg = \"own\"\n")
+                .unwrap();
+            let mut first_file = File::create(home_dir.join("common/first.toml")).unwrap();
+            first_file
+                .write_all(b"include = [\"third.toml\"]\nnumeric-arg = 1\nstring-arg = \"first\"\n")
+                .unwrap();
+            let mut second_file = File::create(home_dir.join("common/second.toml")).unwrap();
+            second_file.write_all(b"numeric-arg = 2\n").unwrap();
+            let mut third_file = File::create(home_dir.join("common/third.toml")).unwrap();
+            third_file
+                .write_all(b"boolean-arg = true\nnumeric-arg = 3\n")
+                .unwrap();
+        }
+
+        let subject = ConfigFileVcl::new(&file_path, true).unwrap();
+
+        assert_eq!(
+            subject.args(),
+            vec![
+                "".to_string(),
+                "--boolean-arg".to_string(),
+                "true".to_string(),
+                "--numeric-arg".to_string(),
+                "2".to_string(),
+                "--string-arg".to_string(),
+                "own".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn config_file_vcl_complains_about_missing_included_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "multi_config",
+            "config_file_vcl_complains_about_missing_included_file",
+        );
+        let file_path = home_dir.join("config.toml");
+        {
+            let mut toml_file = File::create(&file_path).unwrap();
+            toml_file
+                .write_all(b"include = [\"nonexistent.toml\"]\n")
+                .unwrap();
+        }
+
+        let result = ConfigFileVcl::new(&file_path, true).err().unwrap();
+
+        assert_contains(
+            &result.to_string(),
+            &format!(
+                "Couldn't open configuration file \"{}\"",
+                home_dir.join("nonexistent.toml").to_string_lossy()
+            ),
+        )
+    }
+
+    #[test]
+    fn config_file_vcl_complains_about_include_that_is_not_an_array_of_names() {
+        let home_dir = ensure_node_home_directory_exists(
+            "multi_config",
+            "config_file_vcl_complains_about_include_that_is_not_an_array_of_names",
+        );
+        let file_path = home_dir.join("config.toml");
+        {
+            let mut toml_file = File::create(&file_path).unwrap();
+            toml_file.write_all(b"include = [\"a.toml\", 3]\n").unwrap();
+        }
+
+        let result = ConfigFileVcl::new(&file_path, true).err().unwrap();
+
+        assert_contains(
+            &result.to_string(),
+            "doesn't make sense: 'include' must be an array of file names.",
+        )
+    }
+
+    #[test]
+    fn config_file_vcl_detects_include_cycle() {
+        let home_dir = ensure_node_home_directory_exists(
+            "multi_config",
+            "config_file_vcl_detects_include_cycle",
+        );
+        let file_path = home_dir.join("config.toml");
+        {
+            let mut toml_file = File::create(&file_path).unwrap();
+            toml_file
+                .write_all(b"include = [\"other.toml\"]\n")
+                .unwrap();
+            let mut other_file = File::create(home_dir.join("other.toml")).unwrap();
+            other_file
+                .write_all(b"include = [\"./config.toml\"]\n")
+                .unwrap();
+        }
+
+        let result = ConfigFileVcl::new(&file_path, true).err().unwrap();
+
+        match result {
+            ConfigFileVclError::IncludeCycle(paths) => assert_eq!(
+                paths,
+                vec![
+                    canonical_path(&file_path),
+                    canonical_path(&home_dir.join("other.toml")),
+                    canonical_path(&file_path),
+                ]
+            ),
+            x => panic!("Expected IncludeCycle, got {:?}", x),
+        }
+    }
+
     fn assert_contains(haystack: &str, needle: &str) {
         assert_eq!(
             haystack.contains(needle),
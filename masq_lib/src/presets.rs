@@ -0,0 +1,155 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::multi_config::{vcl_args_to_args, vcl_args_to_vcl_args, NameValueVclArg};
+use crate::multi_config::{VclArg, VirtualCommandLine};
+use toml::value::Table;
+use toml::Value;
+
+pub const CONSERVATIVE_SPENDER_PRESET: &str = "conservative-spender";
+pub const AGGRESSIVE_PAYER_PRESET: &str = "aggressive-payer";
+pub const PRESET_NAMES: &[&str] = &[CONSERVATIVE_SPENDER_PRESET, AGGRESSIVE_PAYER_PRESET];
+
+// Each preset is a TOML file shaped like a configuration file, with values for the parameters
+// it sets; it is compiled into the binary, so that it can't go missing or be tampered with
+const PRESETS: &[(&str, &str)] = &[
+    (
+        CONSERVATIVE_SPENDER_PRESET,
+        include_str!("presets/conservative-spender.toml"),
+    ),
+    (
+        AGGRESSIVE_PAYER_PRESET,
+        include_str!("presets/aggressive-payer.toml"),
+    ),
+];
+
+// Supplies the values of a preset as if they were written in a configuration file. It's meant to
+// go underneath all the other sources, so that any parameter given explicitly wins over the preset
+#[derive(Debug)]
+pub struct PresetVcl {
+    vcl_args: Vec<Box<dyn VclArg>>,
+}
+
+impl VirtualCommandLine for PresetVcl {
+    fn vcl_args(&self) -> Vec<&dyn VclArg> {
+        vcl_args_to_vcl_args(&self.vcl_args)
+    }
+
+    fn args(&self) -> Vec<String> {
+        vcl_args_to_args(&self.vcl_args)
+    }
+}
+
+impl PresetVcl {
+    pub fn new(name: &str) -> Result<PresetVcl, String> {
+        let contents = PRESETS
+            .iter()
+            .find(|(preset_name, _)| *preset_name == name)
+            .map(|(_, contents)| *contents)
+            .ok_or_else(|| format!("There is no preset named '{}'", name))?;
+        let table: Table = toml::de::from_str(contents)
+            .unwrap_or_else(|e| panic!("Preset '{}' isn't valid TOML: {}", name, e));
+        let vcl_args = table
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => value.to_string(),
+                    _ => panic!(
+                        "Preset '{}' has a value for '{}' that isn't a scalar",
+                        name, key
+                    ),
+                };
+                let arg: Box<dyn VclArg> =
+                    Box::new(NameValueVclArg::new(&format!("--{}", key), &value));
+                arg
+            })
+            .collect();
+        Ok(PresetVcl { vcl_args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multi_config::{CommandLineVcl, MultiConfig};
+    use crate::shared_schema::shared_app;
+    use clap::{value_t, App};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(CONSERVATIVE_SPENDER_PRESET, "conservative-spender");
+        assert_eq!(AGGRESSIVE_PAYER_PRESET, "aggressive-payer");
+    }
+
+    #[test]
+    fn preset_names_match_the_presets() {
+        let names = PRESETS.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+        assert_eq!(names, PRESET_NAMES.to_vec());
+    }
+
+    #[test]
+    fn every_preset_is_accepted_by_the_schema() {
+        let schema = shared_app(App::new("test"));
+        PRESET_NAMES.iter().for_each(|name| {
+            let vcls: Vec<Box<dyn VirtualCommandLine>> =
+                vec![Box::new(PresetVcl::new(name).unwrap())];
+
+            let result = MultiConfig::try_new(&schema, vcls);
+
+            assert!(result.is_ok(), "preset {}: {:?}", name, result.err());
+        })
+    }
+
+    #[test]
+    fn preset_supplies_its_values() {
+        let subject = PresetVcl::new(AGGRESSIVE_PAYER_PRESET).unwrap();
+
+        let result = subject.args();
+
+        assert_eq!(
+            result,
+            vec![
+                "".to_string(),
+                "--payment-thresholds".to_string(),
+                "500000000|600|1200|250000000|10800|250000000".to_string(),
+                "--scan-intervals".to_string(),
+                "120|120|300".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn explicit_parameters_win_over_the_preset() {
+        let schema = shared_app(App::new("test"));
+        let vcls: Vec<Box<dyn VirtualCommandLine>> = vec![
+            Box::new(PresetVcl::new(AGGRESSIVE_PAYER_PRESET).unwrap()),
+            Box::new(CommandLineVcl::new(vec![
+                "program".to_string(),
+                "--scan-intervals".to_string(),
+                "10|20|30".to_string(),
+            ])),
+        ];
+
+        let multi_config = MultiConfig::try_new(&schema, vcls).unwrap();
+
+        assert_eq!(
+            value_m!(multi_config, "scan-intervals", String),
+            Some("10|20|30".to_string())
+        );
+        assert_eq!(
+            value_m!(multi_config, "payment-thresholds", String),
+            Some("500000000|600|1200|250000000|10800|250000000".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_preset_is_refused() {
+        let result = PresetVcl::new("reckless-gambler");
+
+        assert_eq!(
+            result.err(),
+            Some("There is no preset named 'reckless-gambler'".to_string())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 // Copyright (c) 2022, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::blockchains::chains::Chain;
 use crate::test_utils::utils::UrlHolder;
 use crate::utils::localhost;
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -8,6 +9,8 @@ use itertools::Either::{Left, Right};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::io::{ErrorKind, Read, Write};
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener, TcpStream};
 use std::sync::{Arc, Mutex};
@@ -22,11 +25,32 @@ lazy_static! {
         Regex::new(r"HTTP/(\d\.\d)").expect("Bad regular expression");
 }
 
+// Selectors of the ERC-20 functions a token contract describes itself with
+pub const TOKEN_NAME_SELECTOR: &str = "0x06fdde03";
+pub const TOKEN_SYMBOL_SELECTOR: &str = "0x95d89b41";
+pub const TOKEN_DECIMALS_SELECTOR: &str = "0x313ce567";
+
+pub fn abi_encoded_string(string: &str) -> String {
+    let mut bytes = string.as_bytes().to_vec();
+    bytes.resize((bytes.len() + 31) / 32 * 32, 0);
+    let hex_bytes = bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    format!("0x{:064x}{:064x}{}", 32, string.len(), hex_bytes)
+}
+
+fn call_script_key(selector: &str) -> String {
+    format!("eth_call:{}", selector)
+}
+
 pub struct MBCSBuilder {
     port: u16,
     run_in_docker: bool,
     response_batch_opt: Option<Vec<String>>,
     responses: Vec<String>,
+    scripts: HashMap<String, Vec<Value>>,
+    unresponsive: bool,
     notifier: Sender<()>,
 }
 
@@ -37,6 +61,8 @@ impl MBCSBuilder {
             run_in_docker: false,
             response_batch_opt: None,
             responses: vec![],
+            scripts: HashMap::new(),
+            unresponsive: false,
             notifier: unbounded().0,
         }
     }
@@ -104,6 +130,64 @@ impl MBCSBuilder {
         self.store_response_string(body)
     }
 
+    // Scripted answers are matched to requests by their JSON-RPC method rather than by their order
+    // of arrival, and they echo the id of the request. The last answer scripted for a method keeps
+    // being repeated, which lets a test model a whole scenario, such as a payable scan followed by
+    // any number of pending payable scans, without counting the requests in advance. Requests for
+    // methods without a script are answered from the ordered responses as usual.
+    pub fn scripted_result<R>(self, method: &str, result: R) -> Self
+    where
+        R: Serialize,
+    {
+        let result = serde_json::to_value(&result).unwrap();
+        self.store_script(method, json!({ "result": result }))
+    }
+
+    pub fn scripted_error(self, method: &str, code: i64, message: &str) -> Self {
+        self.store_script(
+            method,
+            json!({ "error": { "code": code, "message": message } }),
+        )
+    }
+
+    // Scripts the eth_calls of a single contract function, told apart from other eth_calls by the
+    // selector their data starts with, such as "0x95d89b41" for symbol()
+    pub fn scripted_call_result<R>(self, selector: &str, result: R) -> Self
+    where
+        R: Serialize,
+    {
+        let result = serde_json::to_value(&result).unwrap();
+        self.store_script(&call_script_key(selector), json!({ "result": result }))
+    }
+
+    // Answers the handshake a BlockchainBridge performs once it's bound: the chain id, what the
+    // token contract tells about itself, and the probes of the capabilities of the service, whose
+    // fee history has base fees too low to make a difference to any gas price
+    pub fn serving_chain(self, chain: Chain) -> Self {
+        let record = chain.rec();
+        self.scripted_result("eth_chainId", format!("{:#x}", record.num_chain_id))
+            .scripted_call_result(TOKEN_NAME_SELECTOR, abi_encoded_string(record.token_name))
+            .scripted_call_result(
+                TOKEN_SYMBOL_SELECTOR,
+                abi_encoded_string(record.token_symbol),
+            )
+            .scripted_call_result(
+                TOKEN_DECIMALS_SELECTOR,
+                format!("{:#066x}", record.token_decimals),
+            )
+            .scripted_result(
+                "eth_feeHistory",
+                json!({"oldestBlock": "0x1", "baseFeePerGas": ["0x0", "0x0"], "gasUsedRatio": [0.0]}),
+            )
+    }
+
+    // Takes in the requests but never answers them, keeping the connection open like a blockchain
+    // service that hangs
+    pub fn unresponsive(mut self) -> Self {
+        self.unresponsive = true;
+        self
+    }
+
     pub fn notifier(mut self, notifier: Sender<()>) -> Self {
         self.notifier = notifier;
         self
@@ -123,6 +207,8 @@ impl MBCSBuilder {
             thread_info_opt: None,
             requests_arc: requests,
             responses: self.responses,
+            scripts: self.scripts,
+            unresponsive: self.unresponsive,
             notifier: self.notifier,
         };
         server.start();
@@ -136,6 +222,14 @@ impl MBCSBuilder {
         }
         self
     }
+
+    fn store_script(mut self, method: &str, answer: Value) -> Self {
+        self.scripts
+            .entry(method.to_string())
+            .or_insert_with(Vec::new)
+            .push(answer);
+        self
+    }
 }
 
 struct MBCSThreadInfo {
@@ -148,6 +242,8 @@ pub struct MockBlockchainClientServer {
     thread_info_opt: Option<MBCSThreadInfo>,
     requests_arc: Arc<Mutex<Vec<String>>>,
     responses: Vec<String>,
+    scripts: HashMap<String, Vec<Value>>,
+    unresponsive: bool,
     notifier: Sender<()>,
 }
 
@@ -207,6 +303,8 @@ impl MockBlockchainClientServer {
         self.port_or_local_addr = Right(listener.local_addr().unwrap());
         let requests_arc = self.requests_arc.clone();
         let mut responses: Vec<String> = self.responses.drain(..).collect();
+        let mut scripts: HashMap<String, Vec<Value>> = self.scripts.drain().collect();
+        let unresponsive = self.unresponsive;
         let (stopper_tx, stopper_rx) = unbounded();
         let notifier = self.notifier.clone();
         let join_handle = thread::spawn(move || {
@@ -235,6 +333,8 @@ impl MockBlockchainClientServer {
                 &mut conn_state,
                 &requests_arc,
                 &mut responses,
+                &mut scripts,
+                unresponsive,
                 &stopper_rx,
                 notifier,
             );
@@ -256,6 +356,8 @@ impl MockBlockchainClientServer {
         conn_state: &mut ConnectionState,
         requests_arc: &Arc<Mutex<Vec<String>>>,
         responses: &mut Vec<String>,
+        scripts: &mut HashMap<String, Vec<Value>>,
+        unresponsive: bool,
         stopper_rx: &Receiver<()>,
         notifier_tx: Sender<()>,
     ) {
@@ -268,14 +370,19 @@ impl MockBlockchainClientServer {
             match body_opt {
                 Some(body) if body.is_empty() => break,
                 Some(body) => {
+                    let scripted_response_opt = Self::make_scripted_response(scripts, &body);
                     {
                         let mut requests = requests_arc.lock().unwrap();
                         requests.push(body);
                     }
-                    if responses.is_empty() {
-                        break;
+                    if unresponsive {
+                        continue;
                     }
-                    let response = responses.remove(0);
+                    let response = match scripted_response_opt {
+                        Some(response) => response,
+                        None if responses.is_empty() => break,
+                        None => responses.remove(0),
+                    };
                     Self::send_body(conn_state, response);
                     let _ = notifier_tx.send(()); // receiver doesn't exist if test didn't set it up
                 }
@@ -285,6 +392,81 @@ impl MockBlockchainClientServer {
         }
     }
 
+    fn make_scripted_response(
+        scripts: &mut HashMap<String, Vec<Value>>,
+        request: &str,
+    ) -> Option<String> {
+        if scripts.is_empty() {
+            return None;
+        }
+        let content_offset = request.find("\r\n\r\n")? + 4;
+        let (single_requests, is_batch) =
+            match serde_json::from_str::<Value>(&request[content_offset..]).ok()? {
+                Value::Array(batch) => (batch, true),
+                single => (vec![single], false),
+            };
+        let methods = single_requests
+            .iter()
+            .map(|single| Self::script_key(scripts, single))
+            .collect::<Option<Vec<String>>>()?;
+        if !methods.iter().all(|method| scripts.contains_key(method)) {
+            return None;
+        }
+        let mut answers = single_requests
+            .iter()
+            .zip(methods)
+            .map(|(single, method)| Self::answer_from_script(scripts, &method, single.get("id")))
+            .collect::<Vec<Value>>();
+        let response = if is_batch {
+            Value::Array(answers)
+        } else {
+            answers.remove(0)
+        };
+        Some(response.to_string())
+    }
+
+    // An eth_call scripted by the selector of its function takes precedence over eth_calls
+    // scripted as a whole
+    fn script_key(scripts: &HashMap<String, Vec<Value>>, single_request: &Value) -> Option<String> {
+        let method = single_request.get("method")?.as_str()?;
+        let selector_opt = single_request
+            .get("params")
+            .and_then(|params| params.get(0))
+            .and_then(|call| call.get("data"))
+            .and_then(|data| data.as_str())
+            .and_then(|data| data.get(0..10));
+        match selector_opt {
+            Some(selector) if method == "eth_call" => {
+                let key = call_script_key(selector);
+                if scripts.contains_key(&key) {
+                    Some(key)
+                } else {
+                    Some(method.to_string())
+                }
+            }
+            _ => Some(method.to_string()),
+        }
+    }
+
+    fn answer_from_script(
+        scripts: &mut HashMap<String, Vec<Value>>,
+        method: &str,
+        id_opt: Option<&Value>,
+    ) -> Value {
+        let answers = scripts.get_mut(method).expect("script disappeared");
+        let mut answer = if answers.len() > 1 {
+            answers.remove(0)
+        } else {
+            answers[0].clone()
+        };
+        let fields = answer
+            .as_object_mut()
+            .expect("scripted answer is not an object");
+        fields.insert("jsonrpc".to_string(), json!("2.0"));
+        fields.insert("id".to_string(), id_opt.cloned().unwrap_or(Value::Null));
+        answer
+    }
+
     fn receive_body(conn_state: &mut ConnectionState) {
         let offset = conn_state.receive_buffer_occupied;
         let limit = conn_state.receive_buffer.len();
@@ -133,6 +133,7 @@ pub struct NodeStartupConfig {
     pub chain: Chain,
     pub db_password_opt: Option<String>,
     pub scans_opt: Option<bool>,
+    pub dev_faucet: bool,
     pub log_level_opt: Option<Level>,
     pub ui_port_opt: Option<u16>,
 }
@@ -165,6 +166,7 @@ impl NodeStartupConfig {
             chain: TEST_DEFAULT_MULTINODE_CHAIN,
             db_password_opt: Some("password".to_string()),
             scans_opt: None,
+            dev_faucet: false,
             log_level_opt: None,
             ui_port_opt: None,
         }
@@ -238,6 +240,11 @@ impl NodeStartupConfig {
             });
         }
 
+        if self.dev_faucet {
+            args.push("--dev-faucet".to_string());
+            args.push("on".to_string());
+        }
+
         if let Some(ref level) = self.log_level_opt {
             args.push("--log-level".to_string());
             args.push(
@@ -427,6 +434,7 @@ pub struct NodeStartupConfigBuilder {
     blockchain_service_url: Option<String>,
     chain: Chain,
     scans_opt: Option<bool>,
+    dev_faucet: bool,
     log_level_opt: Option<Level>,
     ui_port_opt: Option<u16>,
     db_password: Option<String>,
@@ -483,6 +491,7 @@ impl NodeStartupConfigBuilder {
             blockchain_service_url: Some("https://0.0.0.0".to_string()),
             chain: TEST_DEFAULT_MULTINODE_CHAIN,
             scans_opt: None,
+            dev_faucet: false,
             log_level_opt: None,
             ui_port_opt: None,
             db_password: Some("password".to_string()),
@@ -509,6 +518,7 @@ impl NodeStartupConfigBuilder {
             blockchain_service_url: config.blockchain_service_url_opt.clone(),
             chain: config.chain,
             scans_opt: config.scans_opt,
+            dev_faucet: config.dev_faucet,
             log_level_opt: config.log_level_opt,
             ui_port_opt: config.ui_port_opt,
             db_password: config.db_password_opt.clone(),
@@ -631,6 +641,11 @@ impl NodeStartupConfigBuilder {
         self
     }
 
+    pub fn dev_faucet(mut self, dev_faucet: bool) -> Self {
+        self.dev_faucet = dev_faucet;
+        self
+    }
+
     pub fn log_level(mut self, level: Level) -> Self {
         self.log_level_opt = Some(level);
         self
@@ -667,6 +682,7 @@ impl NodeStartupConfigBuilder {
             chain: self.chain,
             db_password_opt: self.db_password,
             scans_opt: self.scans_opt,
+            dev_faucet: self.dev_faucet,
             log_level_opt: self.log_level_opt,
             ui_port_opt: self.ui_port_opt,
         }
@@ -1397,6 +1413,7 @@ mod tests {
             chain: TEST_DEFAULT_MULTINODE_CHAIN,
             db_password_opt: Some("booga".to_string()),
             scans_opt: Some(false),
+            dev_faucet: true,
             log_level_opt: Some(Level::Info),
             ui_port_opt: Some(4321),
         };
@@ -1460,6 +1477,7 @@ mod tests {
         );
         assert_eq!(result.db_password_opt, Some("booga".to_string()));
         assert_eq!(result.scans_opt, Some(false));
+        assert!(result.dev_faucet);
         assert_eq!(result.log_level_opt, Some(Level::Info));
         assert_eq!(result.ui_port_opt, Some(4321));
         assert_eq!(
@@ -1513,6 +1531,7 @@ mod tests {
             .rate_pack(rate_pack)
             .payment_thresholds(payment_thresholds)
             .consuming_wallet_info(default_consuming_wallet_info())
+            .dev_faucet(true)
             .build();
 
         let result = subject.make_args();
@@ -1544,6 +1563,8 @@ mod tests {
                 TEST_DEFAULT_MULTINODE_CHAIN.rec().literal_identifier,
                 "--db-password",
                 "password",
+                "--dev-faucet",
+                "on",
             ))
         );
     }
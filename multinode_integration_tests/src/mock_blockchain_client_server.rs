@@ -12,6 +12,7 @@ mod tests {
     };
     use masq_lib::utils::find_free_port;
     use serde_derive::{Deserialize, Serialize};
+    use serde_json::{json, Value};
     use std::io::{ErrorKind, Read, Write};
     use std::net::TcpStream;
     use std::ops::Add;
@@ -238,6 +239,67 @@ mod tests {
         ])
     }
 
+    #[test]
+    fn mbcs_answers_scripted_methods_regardless_of_the_order_of_requests() {
+        let _cluster = MASQNodeCluster::start();
+        let port = find_free_port();
+        let subject = MockBlockchainClientServer::builder(port)
+            .scripted_result("eth_gasPrice", "0x3B9ACA00")
+            .scripted_result("eth_getTransactionCount", "0x1")
+            .scripted_result("eth_getTransactionCount", "0x2")
+            .scripted_error("eth_sendRawTransaction", -32000, "nonce too low")
+            .ok_response("unscripted", 7)
+            .run_in_docker()
+            .start();
+        let mut client = connect(port);
+        let mut exchange = |body: &str| {
+            client.write(make_post(body).as_slice()).unwrap();
+            let (response_header, response_body) = receive_response(&mut client);
+            verify_response_header(&response_header, &response_body);
+            serde_json::from_str::<Value>(&response_body).unwrap()
+        };
+
+        let batch_response = exchange(
+            r#"[{"jsonrpc": "2.0", "method": "eth_getTransactionCount", "id": 3}, {"jsonrpc": "2.0", "method": "eth_sendRawTransaction", "id": 4}]"#,
+        );
+        let gas_price_response =
+            exchange(r#"{"jsonrpc": "2.0", "method": "eth_gasPrice", "id": 5}"#);
+        let repeated_nonce_responses = (6..8)
+            .map(|id| {
+                exchange(&format!(
+                    r#"{{"jsonrpc": "2.0", "method": "eth_getTransactionCount", "id": {}}}"#,
+                    id
+                ))
+            })
+            .collect::<Vec<Value>>();
+        let unscripted_response =
+            exchange(r#"{"jsonrpc": "2.0", "method": "eth_blockNumber", "id": 7}"#);
+
+        assert_eq!(
+            batch_response,
+            json!([
+                {"jsonrpc": "2.0", "result": "0x1", "id": 3},
+                {"jsonrpc": "2.0", "error": {"code": -32000, "message": "nonce too low"}, "id": 4}
+            ])
+        );
+        assert_eq!(
+            gas_price_response,
+            json!({"jsonrpc": "2.0", "result": "0x3B9ACA00", "id": 5})
+        );
+        assert_eq!(
+            repeated_nonce_responses,
+            vec![
+                json!({"jsonrpc": "2.0", "result": "0x2", "id": 6}),
+                json!({"jsonrpc": "2.0", "result": "0x2", "id": 7})
+            ]
+        );
+        assert_eq!(
+            unscripted_response,
+            json!({"jsonrpc": "2.0", "result": "unscripted", "id": 7})
+        );
+        assert_eq!(subject.requests().len(), 5);
+    }
+
     fn connect(port: u16) -> TcpStream {
         let deadline = Instant::now().add(Duration::from_secs(1));
         let addr = DockerHostSocketAddr::new(port);
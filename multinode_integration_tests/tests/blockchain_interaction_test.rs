@@ -32,6 +32,7 @@ fn debtors_are_credited_once_but_not_twice() {
     // Create and initialize mock blockchain client: prepare a receivable at block 2000
     eprintln!("Setting up mock blockchain client");
     let blockchain_client_server = MBCSBuilder::new(mbcs_port)
+        .serving_chain(cluster.chain)
         .err_response(
             429,
             "The requests per second (RPS) of your requests are higher than your plan allows."
@@ -21,7 +21,7 @@ use node_lib::blockchain::bip32::Bip32EncryptionKeyProvider;
 use node_lib::blockchain::blockchain_interface::blockchain_interface_web3::{
     BlockchainInterfaceWeb3, REQUESTS_IN_PARALLEL,
 };
-use node_lib::blockchain::blockchain_interface::BlockchainInterface;
+use node_lib::blockchain::blockchain_interface::BlockchainReader;
 use node_lib::database::db_initializer::{
     DbInitializationConfig, DbInitializer, DbInitializerReal, ExternalData,
 };
@@ -59,7 +59,8 @@ fn verify_bill_payment() {
         "Ganache is not as predictable as we thought: Update blockchain_interface::MULTINODE_CONTRACT_ADDRESS with {:?}",
         contract_addr
     );
-    let blockchain_interface = BlockchainInterfaceWeb3::new(http, event_loop_handle, cluster.chain);
+    let blockchain_interface =
+        BlockchainInterfaceWeb3::new(http, event_loop_handle, cluster.chain, vec![], 0, 1);
     assert_balances(
         &contract_owner_wallet,
         &blockchain_interface,
@@ -329,7 +330,8 @@ fn verify_pending_payables() {
         "Ganache is not as predictable as we thought: Update blockchain_interface::MULTINODE_CONTRACT_ADDRESS with {:?}",
         contract_addr
     );
-    let blockchain_interface = BlockchainInterfaceWeb3::new(http, event_loop_handle, cluster.chain);
+    let blockchain_interface =
+        BlockchainInterfaceWeb3::new(http, event_loop_handle, cluster.chain, vec![], 0, 1);
     assert_balances(
         &contract_owner_wallet,
         &blockchain_interface,
@@ -0,0 +1,404 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::accounts_transfer_dao::AccountsSnapshot;
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::db_access_objects::receivable_dao::ReceivableAccount;
+use crate::accountant::db_access_objects::utils::{from_time_t, to_time_t};
+use crate::accountant::PendingPayableId;
+use crate::blockchain::blockchain_bridge::{PendingPayableFingerprint, ReplacementBasis};
+use crate::sub_lib::wallet::{Wallet, WalletError};
+use serde_derive::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use web3::types::H256;
+
+// To be raised with every change of the format that a Node reading the older one would get wrong
+pub const ACCOUNTS_FILE_SCHEMA_VERSION: u32 = 1;
+
+// The portable form of the accounts of a Node, for them to be moved to another Node. Amounts in wei
+// are decimal strings, because many JSON readers would lose precision on numbers this big;
+// timestamps are in seconds since the Unix epoch
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AccountsFile {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: u32,
+    pub chain: String,
+    pub payables: Vec<AccountsFilePayable>,
+    pub receivables: Vec<AccountsFileReceivable>,
+    #[serde(rename = "pendingPayables")]
+    pub pending_payables: Vec<AccountsFilePendingPayable>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AccountsFilePayable {
+    pub wallet: String,
+    #[serde(rename = "balanceWei")]
+    pub balance_wei: String,
+    #[serde(rename = "lastPaidTimestamp")]
+    pub last_paid_timestamp: i64,
+    // The transaction among the pending payables that is to pay this payable
+    #[serde(rename = "pendingPayableHashOpt")]
+    pub pending_payable_hash_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AccountsFileReceivable {
+    pub wallet: String,
+    #[serde(rename = "balanceWei")]
+    pub balance_wei: String,
+    #[serde(rename = "lastReceivedTimestamp")]
+    pub last_received_timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AccountsFilePendingPayable {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    #[serde(rename = "amountWei")]
+    pub amount_wei: String,
+    pub timestamp: i64,
+    pub attempt: u16,
+    #[serde(rename = "processErrorOpt")]
+    pub process_error_opt: Option<String>,
+    #[serde(rename = "nonceOpt")]
+    pub nonce_opt: Option<u64>,
+    #[serde(rename = "gasPriceWeiOpt")]
+    pub gas_price_wei_opt: Option<String>,
+}
+
+impl From<&AccountsSnapshot> for AccountsFile {
+    fn from(snapshot: &AccountsSnapshot) -> Self {
+        AccountsFile {
+            schema_version: ACCOUNTS_FILE_SCHEMA_VERSION,
+            chain: snapshot.chain_name.clone(),
+            payables: snapshot
+                .payables
+                .iter()
+                .map(|payable| AccountsFilePayable {
+                    wallet: payable.wallet.to_string(),
+                    balance_wei: payable.balance_wei.to_string(),
+                    last_paid_timestamp: to_time_t(payable.last_paid_timestamp),
+                    pending_payable_hash_opt: payable
+                        .pending_payable_opt
+                        .map(|id| format!("{:?}", id.hash)),
+                })
+                .collect(),
+            receivables: snapshot
+                .receivables
+                .iter()
+                .map(|receivable| AccountsFileReceivable {
+                    wallet: receivable.wallet.to_string(),
+                    balance_wei: receivable.balance_wei.to_string(),
+                    last_received_timestamp: to_time_t(receivable.last_received_timestamp),
+                })
+                .collect(),
+            pending_payables: snapshot
+                .fingerprints
+                .iter()
+                .map(|fingerprint| AccountsFilePendingPayable {
+                    transaction_hash: format!("{:?}", fingerprint.hash),
+                    amount_wei: fingerprint.amount.to_string(),
+                    timestamp: to_time_t(fingerprint.timestamp),
+                    attempt: fingerprint.attempt,
+                    process_error_opt: fingerprint.process_error.clone(),
+                    nonce_opt: fingerprint.replacement_basis_opt.map(|basis| basis.nonce),
+                    gas_price_wei_opt: fingerprint
+                        .replacement_basis_opt
+                        .map(|basis| basis.gas_price_wei.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+// Rowids aren't portable; they're left at zero for the importing database to assign
+impl TryFrom<AccountsFile> for AccountsSnapshot {
+    type Error = String;
+
+    fn try_from(file: AccountsFile) -> Result<Self, Self::Error> {
+        if file.schema_version != ACCOUNTS_FILE_SCHEMA_VERSION {
+            return Err(format!(
+                "Accounts file of schema version {} can't be read; version {} is expected",
+                file.schema_version, ACCOUNTS_FILE_SCHEMA_VERSION
+            ));
+        }
+        Ok(AccountsSnapshot {
+            chain_name: file.chain,
+            payables: file
+                .payables
+                .into_iter()
+                .map(|payable| -> Result<PayableAccount, String> {
+                    Ok(PayableAccount {
+                        wallet: parse_wallet(&payable.wallet)?,
+                        balance_wei: parse_number(&payable.balance_wei)?,
+                        last_paid_timestamp: from_time_t(payable.last_paid_timestamp),
+                        pending_payable_opt: match payable.pending_payable_hash_opt {
+                            Some(hash) => Some(PendingPayableId::new(0, parse_hash(&hash)?)),
+                            None => None,
+                        },
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            receivables: file
+                .receivables
+                .into_iter()
+                .map(|receivable| -> Result<ReceivableAccount, String> {
+                    Ok(ReceivableAccount {
+                        wallet: parse_wallet(&receivable.wallet)?,
+                        balance_wei: parse_number(&receivable.balance_wei)?,
+                        last_received_timestamp: from_time_t(receivable.last_received_timestamp),
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            fingerprints: file
+                .pending_payables
+                .into_iter()
+                .map(
+                    |pending_payable| -> Result<PendingPayableFingerprint, String> {
+                        Ok(PendingPayableFingerprint {
+                            rowid: 0,
+                            timestamp: from_time_t(pending_payable.timestamp),
+                            hash: parse_hash(&pending_payable.transaction_hash)?,
+                            attempt: pending_payable.attempt,
+                            amount: parse_number(&pending_payable.amount_wei)?,
+                            process_error: pending_payable.process_error_opt,
+                            replacement_basis_opt: match (
+                                pending_payable.nonce_opt,
+                                pending_payable.gas_price_wei_opt,
+                            ) {
+                                (Some(nonce), Some(gas_price_wei)) => Some(ReplacementBasis {
+                                    nonce,
+                                    gas_price_wei: parse_number(&gas_price_wei)?,
+                                }),
+                                _ => None,
+                            },
+                        })
+                    },
+                )
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+pub fn write_accounts_file(path: &Path, snapshot: &AccountsSnapshot) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(&AccountsFile::from(snapshot)).expect("serialization failed");
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| format!("Can't create {}: {}", path.display(), e))?;
+    file.write_all(json.as_bytes())
+        .map_err(|e| format!("Can't write {}: {}", path.display(), e))
+}
+
+pub fn read_accounts_file(path: &Path) -> Result<AccountsSnapshot, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    let file: AccountsFile = serde_json::from_str(&json)
+        .map_err(|e| format!("{} is no accounts file: {}", path.display(), e))?;
+    AccountsSnapshot::try_from(file)
+}
+
+fn parse_wallet(wallet: &str) -> Result<Wallet, String> {
+    Wallet::from_str(wallet).map_err(|e| match e {
+        WalletError::InvalidChecksum(_) => format!("Invalid wallet: {}", e),
+        _ => format!("Invalid wallet: {}", wallet),
+    })
+}
+
+fn parse_hash(hash: &str) -> Result<H256, String> {
+    hash.strip_prefix("0x")
+        .and_then(|hex| H256::from_str(hex).ok())
+        .ok_or_else(|| format!("Invalid transaction hash: {}", hash))
+}
+
+fn parse_number<T: FromStr>(number: &str) -> Result<T, String> {
+    number
+        .parse()
+        .map_err(|_| format!("Invalid amount of wei: {}", number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    fn make_snapshot() -> AccountsSnapshot {
+        AccountsSnapshot {
+            chain_name: "polygon-mainnet".to_string(),
+            payables: vec![
+                PayableAccount {
+                    wallet: make_wallet("creditor_1"),
+                    balance_wei: u128::MAX / 3,
+                    last_paid_timestamp: from_time_t(1_650_000_000),
+                    pending_payable_opt: None,
+                },
+                PayableAccount {
+                    wallet: make_wallet("creditor_2"),
+                    balance_wei: 4_567_890_000_000,
+                    last_paid_timestamp: from_time_t(1_660_000_000),
+                    pending_payable_opt: Some(PendingPayableId::new(0, make_tx_hash(0x123))),
+                },
+            ],
+            receivables: vec![ReceivableAccount {
+                wallet: make_wallet("debtor"),
+                balance_wei: i128::MIN / 3,
+                last_received_timestamp: from_time_t(1_655_000_000),
+            }],
+            fingerprints: vec![
+                PendingPayableFingerprint {
+                    rowid: 0,
+                    timestamp: from_time_t(1_670_000_000),
+                    hash: make_tx_hash(0x123),
+                    attempt: 3,
+                    amount: 4_000_000_000_000,
+                    process_error: None,
+                    replacement_basis_opt: Some(ReplacementBasis {
+                        nonce: 45,
+                        gas_price_wei: 60_000_000_000,
+                    }),
+                },
+                PendingPayableFingerprint {
+                    rowid: 0,
+                    timestamp: from_time_t(1_671_000_000),
+                    hash: make_tx_hash(0x456),
+                    attempt: 1,
+                    amount: 1_111_111,
+                    process_error: Some("Reverted".to_string()),
+                    replacement_basis_opt: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(ACCOUNTS_FILE_SCHEMA_VERSION, 1);
+    }
+
+    #[test]
+    fn written_accounts_file_reads_back_the_same() {
+        let home_dir = ensure_node_home_directory_exists(
+            "accounts_file",
+            "written_accounts_file_reads_back_the_same",
+        );
+        let path = home_dir.join("accounts.json");
+        let snapshot = make_snapshot();
+
+        write_accounts_file(&path, &snapshot).unwrap();
+        let result = read_accounts_file(&path);
+
+        assert_eq!(result, Ok(snapshot))
+    }
+
+    #[test]
+    fn amounts_are_written_as_strings() {
+        let result = AccountsFile::from(&make_snapshot());
+
+        assert_eq!(result.schema_version, ACCOUNTS_FILE_SCHEMA_VERSION);
+        assert_eq!(result.payables[0].balance_wei, (u128::MAX / 3).to_string());
+        assert_eq!(
+            result.receivables[0].balance_wei,
+            (i128::MIN / 3).to_string()
+        );
+        assert_eq!(
+            result.pending_payables[0].gas_price_wei_opt,
+            Some("60000000000".to_string())
+        );
+        assert_eq!(
+            result.payables[1].pending_payable_hash_opt,
+            Some(format!("{:?}", make_tx_hash(0x123)))
+        )
+    }
+
+    #[test]
+    fn existing_file_is_not_overwritten() {
+        let home_dir =
+            ensure_node_home_directory_exists("accounts_file", "existing_file_is_not_overwritten");
+        let path = home_dir.join("accounts.json");
+        std::fs::write(&path, "precious").unwrap();
+
+        let result = write_accounts_file(&path, &make_snapshot());
+
+        let error = result.unwrap_err();
+        assert!(
+            error.starts_with(&format!("Can't create {}: ", path.display())),
+            "{}",
+            error
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "precious")
+    }
+
+    #[test]
+    fn file_of_another_schema_version_is_refused() {
+        let mut file = AccountsFile::from(&make_snapshot());
+        file.schema_version = 2;
+
+        let result = AccountsSnapshot::try_from(file);
+
+        assert_eq!(
+            result,
+            Err(
+                "Accounts file of schema version 2 can't be read; version 1 is expected"
+                    .to_string()
+            )
+        )
+    }
+
+    #[test]
+    fn file_with_malformed_values_is_refused() {
+        let assert_refused = |tamper: fn(&mut AccountsFile), expected_error: &str| {
+            let mut file = AccountsFile::from(&make_snapshot());
+            tamper(&mut file);
+
+            let result = AccountsSnapshot::try_from(file);
+
+            assert_eq!(result, Err(expected_error.to_string()))
+        };
+
+        assert_refused(
+            |file| file.payables[0].wallet = "0xbadwallet".to_string(),
+            "Invalid wallet: 0xbadwallet",
+        );
+        assert_refused(
+            |file| {
+                file.receivables[0].wallet =
+                    "0xAbCdEf0123456789012345678901234567890123".to_string()
+            },
+            "Invalid wallet: Address 0xAbCdEf0123456789012345678901234567890123 fails its EIP-55 \
+             checksum; check it for typos, or give it all in lower case",
+        );
+        assert_refused(
+            |file| file.receivables[0].balance_wei = "12.5".to_string(),
+            "Invalid amount of wei: 12.5",
+        );
+        assert_refused(
+            |file| file.pending_payables[1].transaction_hash = "123abc".to_string(),
+            "Invalid transaction hash: 123abc",
+        );
+    }
+
+    #[test]
+    fn reading_something_else_than_accounts_file_is_refused() {
+        let home_dir = ensure_node_home_directory_exists(
+            "accounts_file",
+            "reading_something_else_than_accounts_file_is_refused",
+        );
+        let path = home_dir.join("accounts.json");
+        std::fs::write(&path, "{\"schemaVersion\": 1}").unwrap();
+
+        let result = read_accounts_file(&path);
+
+        let error = result.unwrap_err();
+        assert!(
+            error.starts_with(&format!("{} is no accounts file: ", path.display())),
+            "{}",
+            error
+        )
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::sub_lib::wallet::Wallet;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const FORECAST_HISTORY_HOURS: u64 = 24;
+const SECONDS_PER_HOUR: u64 = 3_600;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct HourlyUsage {
+    bytes: u64,
+    wei: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebtGrowthProjection {
+    pub creditor: Wallet,
+    // The rates expected for the coming hour
+    pub bytes_per_hour: u64,
+    pub wei_per_hour: u128,
+    pub projected_growth_wei: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopUpRecommendation {
+    pub projected_debt_growth_wei: u128,
+    // What the service fee balance lacks to pay off the debts we have and the ones we're likely
+    // to run into within the horizon; zero if it suffices
+    pub top_up_wei: u128,
+}
+
+// Projects how our debts to creditors will grow in the near future. The services consumed from
+// each creditor are summed up by the hour, and a straight line fitted through the hours completed
+// within the last FORECAST_HISTORY_HOURS is followed into the future. The line is followed no
+// further ahead than the history it was fitted through reaches back; from there on, the rate
+// stays where it got
+pub struct ConsumptionForecaster {
+    started_hour: u64,
+    hourly_usage: HashMap<Wallet, BTreeMap<u64, HourlyUsage>>,
+}
+
+impl ConsumptionForecaster {
+    pub fn new(started: SystemTime) -> Self {
+        Self {
+            started_hour: hour_of(started),
+            hourly_usage: HashMap::new(),
+        }
+    }
+
+    pub fn record_consumed(
+        &mut self,
+        creditor: &Wallet,
+        payload_size: usize,
+        charge_wei: u128,
+        timestamp: SystemTime,
+    ) {
+        let hour = hour_of(timestamp);
+        let hours = self
+            .hourly_usage
+            .entry(creditor.as_address_wallet())
+            .or_default();
+        let usage = hours.entry(hour).or_default();
+        usage.bytes = usage.bytes.saturating_add(payload_size as u64);
+        usage.wei = usage.wei.saturating_add(charge_wei);
+        let oldest_kept = hour.saturating_sub(FORECAST_HISTORY_HOURS);
+        *hours = hours.split_off(&oldest_kept);
+    }
+
+    // Biggest growth first; creditors whose debts aren't expected to grow are left out
+    pub fn project(&self, now: SystemTime, horizon: Duration) -> Vec<DebtGrowthProjection> {
+        let current_hour = hour_of(now);
+        let first_hour = self
+            .started_hour
+            .max(current_hour.saturating_sub(FORECAST_HISTORY_HOURS));
+        let completed_hours = current_hour.saturating_sub(first_hour);
+        if completed_hours == 0 {
+            return vec![];
+        }
+        let horizon_hours = (horizon.as_secs() + SECONDS_PER_HOUR - 1) / SECONDS_PER_HOUR;
+        let mut projections = self
+            .hourly_usage
+            .iter()
+            .map(|(creditor, hours)| {
+                let series = (first_hour..current_hour)
+                    .map(|hour| hours.get(&hour).copied().unwrap_or_default())
+                    .collect::<Vec<HourlyUsage>>();
+                let bytes = Trend::fit(series.iter().map(|usage| usage.bytes as f64));
+                let wei = Trend::fit(series.iter().map(|usage| usage.wei as f64));
+                DebtGrowthProjection {
+                    creditor: creditor.clone(),
+                    bytes_per_hour: bytes.rate_ahead(1) as u64,
+                    wei_per_hour: wei.rate_ahead(1) as u128,
+                    projected_growth_wei: wei.total_ahead(horizon_hours) as u128,
+                }
+            })
+            .filter(|projection| projection.projected_growth_wei > 0)
+            .collect::<Vec<DebtGrowthProjection>>();
+        projections.sort_by(|a, b| {
+            b.projected_growth_wei
+                .cmp(&a.projected_growth_wei)
+                .then_with(|| a.creditor.address().cmp(&b.creditor.address()))
+        });
+        projections
+    }
+
+    pub fn recommend_top_up(
+        &self,
+        now: SystemTime,
+        horizon: Duration,
+        outstanding_debt_wei: u128,
+        service_fee_balance_wei: u128,
+    ) -> TopUpRecommendation {
+        let projected_debt_growth_wei = self
+            .project(now, horizon)
+            .iter()
+            .fold(0_u128, |total, projection| {
+                total.saturating_add(projection.projected_growth_wei)
+            });
+        TopUpRecommendation {
+            projected_debt_growth_wei,
+            top_up_wei: outstanding_debt_wei
+                .saturating_add(projected_debt_growth_wei)
+                .saturating_sub(service_fee_balance_wei),
+        }
+    }
+}
+
+struct Trend {
+    hours: f64,
+    mean: f64,
+    slope: f64,
+}
+
+impl Trend {
+    fn fit(series: impl Iterator<Item = f64>) -> Self {
+        let values = series.collect::<Vec<f64>>();
+        let hours = values.len() as f64;
+        let mean_hour = (hours - 1.0) / 2.0;
+        let mean = values.iter().sum::<f64>() / hours;
+        let (covariance, variance) =
+            values
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(covariance, variance), (hour, value)| {
+                    let deviation = hour as f64 - mean_hour;
+                    (
+                        covariance + deviation * (value - mean),
+                        variance + deviation * deviation,
+                    )
+                });
+        let slope = if variance > 0.0 {
+            covariance / variance
+        } else {
+            0.0
+        };
+        Self { hours, mean, slope }
+    }
+
+    fn rate_ahead(&self, hours_ahead: u64) -> f64 {
+        let hours_ahead = (hours_ahead as f64).min(self.hours);
+        let hour = self.hours - 1.0 + hours_ahead;
+        (self.mean + self.slope * (hour - (self.hours - 1.0) / 2.0)).max(0.0)
+    }
+
+    fn total_ahead(&self, horizon_hours: u64) -> f64 {
+        (1..=horizon_hours)
+            .map(|ahead| self.rate_ahead(ahead))
+            .sum()
+    }
+}
+
+fn hour_of(timestamp: SystemTime) -> u64 {
+    timestamp
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock went back before the epoch")
+        .as_secs()
+        / SECONDS_PER_HOUR
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_wallet;
+
+    fn at_hour(hour: u64, minute: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(hour * SECONDS_PER_HOUR + minute * 60)
+    }
+
+    fn hours(count: u64) -> Duration {
+        Duration::from_secs(count * SECONDS_PER_HOUR)
+    }
+
+    fn record_hourly(
+        subject: &mut ConsumptionForecaster,
+        creditor: &Wallet,
+        first_hour: u64,
+        charges_wei: &[u128],
+    ) {
+        charges_wei
+            .iter()
+            .enumerate()
+            .for_each(|(offset, charge_wei)| {
+                subject.record_consumed(
+                    creditor,
+                    (*charge_wei / 10) as usize,
+                    *charge_wei,
+                    at_hour(first_hour + offset as u64, 30),
+                )
+            })
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(FORECAST_HISTORY_HOURS, 24);
+        assert_eq!(SECONDS_PER_HOUR, 3_600);
+    }
+
+    #[test]
+    fn steady_consumption_is_projected_at_the_same_rate() {
+        let creditor = make_wallet("creditor");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        record_hourly(&mut subject, &creditor, 100, &[1_000, 1_000, 1_000]);
+
+        let result = subject.project(at_hour(103, 10), hours(2));
+
+        assert_eq!(
+            result,
+            vec![DebtGrowthProjection {
+                creditor,
+                bytes_per_hour: 100,
+                wei_per_hour: 1_000,
+                projected_growth_wei: 2_000,
+            }]
+        )
+    }
+
+    #[test]
+    fn rising_consumption_is_projected_to_keep_rising() {
+        let creditor = make_wallet("creditor");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        record_hourly(&mut subject, &creditor, 100, &[1_000, 2_000, 3_000]);
+
+        let result = subject.project(at_hour(103, 0), Duration::from_secs(5_000));
+
+        assert_eq!(
+            result,
+            vec![DebtGrowthProjection {
+                creditor,
+                bytes_per_hour: 400,
+                wei_per_hour: 4_000,
+                projected_growth_wei: 9_000,
+            }]
+        )
+    }
+
+    #[test]
+    fn trend_is_followed_no_further_ahead_than_the_history_reaches_back() {
+        let creditor = make_wallet("creditor");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        record_hourly(&mut subject, &creditor, 100, &[1_000, 2_000]);
+
+        let result = subject.project(at_hour(102, 0), hours(4));
+
+        assert_eq!(
+            result[0].projected_growth_wei,
+            3_000 + 4_000 + 4_000 + 4_000
+        )
+    }
+
+    #[test]
+    fn creditors_whose_debts_are_not_expected_to_grow_are_left_out() {
+        let falling = make_wallet("falling");
+        let silent = make_wallet("silent");
+        let busy = make_wallet("busy");
+        let modest = make_wallet("modest");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        record_hourly(&mut subject, &falling, 100, &[3_000, 2_000, 1_000]);
+        record_hourly(&mut subject, &silent, 100, &[0, 0, 0]);
+        record_hourly(&mut subject, &busy, 100, &[5_000, 5_000, 5_000]);
+        record_hourly(&mut subject, &modest, 100, &[0, 100, 200]);
+
+        let result = subject.project(at_hour(103, 0), hours(1));
+
+        assert_eq!(
+            result
+                .into_iter()
+                .map(|projection| (projection.creditor, projection.projected_growth_wei))
+                .collect::<Vec<(Wallet, u128)>>(),
+            vec![(busy, 5_000), (modest, 300)]
+        )
+    }
+
+    #[test]
+    fn only_completed_hours_within_the_history_count() {
+        let creditor = make_wallet("creditor");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        subject.record_consumed(&creditor, 1_000_000, 1_000_000, at_hour(110, 0));
+        record_hourly(&mut subject, &creditor, 135, &[600; 24]);
+        subject.record_consumed(&creditor, 1_000_000, 1_000_000, at_hour(159, 59));
+
+        let result = subject.project(at_hour(159, 59), hours(1));
+
+        assert_eq!(result[0].wei_per_hour, 600);
+        assert_eq!(result[0].projected_growth_wei, 600)
+    }
+
+    #[test]
+    fn hours_before_the_node_started_are_not_taken_for_idle_ones() {
+        let creditor = make_wallet("creditor");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 45));
+        record_hourly(&mut subject, &creditor, 100, &[800, 800]);
+
+        let result = subject.project(at_hour(102, 0), hours(3));
+
+        assert_eq!(result[0].projected_growth_wei, 2_400)
+    }
+
+    #[test]
+    fn nothing_is_projected_before_the_first_hour_is_over() {
+        let creditor = make_wallet("creditor");
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        subject.record_consumed(&creditor, 100, 1_000, at_hour(100, 30));
+
+        let result = subject.project(at_hour(100, 59), hours(24));
+
+        assert_eq!(result, vec![])
+    }
+
+    #[test]
+    fn top_up_covers_the_outstanding_debt_and_the_projected_growth() {
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        record_hourly(&mut subject, &make_wallet("abc"), 100, &[1_000, 1_000]);
+        record_hourly(&mut subject, &make_wallet("def"), 100, &[500, 500]);
+
+        let result = subject.recommend_top_up(at_hour(102, 0), hours(10), 4_000, 10_000);
+
+        assert_eq!(
+            result,
+            TopUpRecommendation {
+                projected_debt_growth_wei: 15_000,
+                top_up_wei: 9_000,
+            }
+        )
+    }
+
+    #[test]
+    fn no_top_up_is_recommended_if_the_balance_suffices() {
+        let mut subject = ConsumptionForecaster::new(at_hour(100, 0));
+        record_hourly(&mut subject, &make_wallet("abc"), 100, &[1_000]);
+
+        let result = subject.recommend_top_up(at_hour(101, 0), hours(10), 4_000, 14_000);
+
+        assert_eq!(
+            result,
+            TopUpRecommendation {
+                projected_debt_growth_wei: 10_000,
+                top_up_wei: 0,
+            }
+        )
+    }
+}
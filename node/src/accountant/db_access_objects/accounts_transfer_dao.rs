@@ -0,0 +1,455 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::checked_conversion;
+use crate::accountant::db_access_objects::payable_dao::{PayableAccount, PayableDaoReal};
+use crate::accountant::db_access_objects::pending_payable_dao::PendingPayableDaoReal;
+use crate::accountant::db_access_objects::receivable_dao::{ReceivableAccount, ReceivableDaoReal};
+use crate::accountant::db_access_objects::utils::{
+    to_time_t, DaoFactoryReal, VigilantRusqliteFlatten, ACTIVE_CHAIN,
+};
+use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
+use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+use crate::database::rusqlite_wrappers::{ConnectionWrapper, TransactionSafeWrapper};
+use crate::sub_lib::wallet::Wallet;
+use rusqlite::{Row, ToSql};
+use std::collections::HashMap;
+use web3::types::H256;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AccountsTransferDaoError {
+    // Descriptions of the existing rows the imported records collide with
+    Conflicts(Vec<String>),
+    // The snapshot was taken from a database of another chain
+    ChainMismatch(String),
+    // A payable awaits a transaction whose fingerprint isn't among the imported ones
+    UnknownFingerprint(H256),
+    RusqliteError(String),
+}
+
+impl From<rusqlite::Error> for AccountsTransferDaoError {
+    fn from(input: rusqlite::Error) -> Self {
+        AccountsTransferDaoError::RusqliteError(format!("{:?}", input))
+    }
+}
+
+// Everything the Node keeps about its debts on the active chain. Rowids are those of the
+// database the snapshot was taken from; a payable finds its fingerprint by the transaction hash
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct AccountsSnapshot {
+    pub chain_name: String,
+    pub payables: Vec<PayableAccount>,
+    pub receivables: Vec<ReceivableAccount>,
+    pub fingerprints: Vec<PendingPayableFingerprint>,
+}
+
+pub trait AccountsTransferDao {
+    fn export(&self) -> AccountsSnapshot;
+    // All or nothing: if any record collides with an existing row, the database stays untouched
+    fn import(&mut self, snapshot: &AccountsSnapshot) -> Result<(), AccountsTransferDaoError>;
+}
+
+pub trait AccountsTransferDaoFactory {
+    fn make(&self) -> Box<dyn AccountsTransferDao>;
+}
+
+impl AccountsTransferDaoFactory for DaoFactoryReal {
+    fn make(&self) -> Box<dyn AccountsTransferDao> {
+        Box::new(AccountsTransferDaoReal::new(self.make_connection()))
+    }
+}
+
+pub struct AccountsTransferDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl AccountsTransferDao for AccountsTransferDaoReal {
+    fn export(&self) -> AccountsSnapshot {
+        AccountsSnapshot {
+            chain_name: self
+                .conn
+                .prepare(&format!("select {ACTIVE_CHAIN}"))
+                .expect("Internal error")
+                .query_row([], |row| row.get(0))
+                .expect("database is corrupt"),
+            payables: self.select_all(
+                &format!(
+                    "select wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, \
+                     pending_payable_rowid, pending_payable.transaction_hash from payable \
+                     left join pending_payable on \
+                     pending_payable.rowid = payable.pending_payable_rowid \
+                     where payable.chain = {ACTIVE_CHAIN} order by wallet_address"
+                ),
+                PayableDaoReal::create_payable_account,
+            ),
+            receivables: self.select_all(
+                &format!(
+                    "select wallet_address, balance_high_b, balance_low_b, \
+                     last_received_timestamp from receivable where chain = {ACTIVE_CHAIN} \
+                     order by wallet_address"
+                ),
+                ReceivableDaoReal::create_receivable_account,
+            ),
+            fingerprints: self.select_all(
+                &format!(
+                    "select rowid, transaction_hash, amount_high_b, amount_low_b, \
+                     payable_timestamp, attempt, process_error, nonce, gas_price_wei \
+                     from pending_payable where chain = {ACTIVE_CHAIN} order by rowid"
+                ),
+                PendingPayableDaoReal::fingerprint_from_row,
+            ),
+        }
+    }
+
+    fn import(&mut self, snapshot: &AccountsSnapshot) -> Result<(), AccountsTransferDaoError> {
+        let txn = self.conn.transaction()?;
+        let chain_name: String = txn
+            .prepare(&format!("select {ACTIVE_CHAIN}"))?
+            .query_row([], |row| row.get(0))?;
+        if snapshot.chain_name != chain_name {
+            return Err(AccountsTransferDaoError::ChainMismatch(
+                snapshot.chain_name.clone(),
+            ));
+        }
+        let conflicts = Self::conflicts(&txn, snapshot)?;
+        if !conflicts.is_empty() {
+            return Err(AccountsTransferDaoError::Conflicts(conflicts));
+        }
+        let rowids = snapshot
+            .fingerprints
+            .iter()
+            .map(|fingerprint| {
+                Self::insert_fingerprint(&txn, fingerprint).map(|rowid| (fingerprint.hash, rowid))
+            })
+            .collect::<Result<HashMap<H256, i64>, AccountsTransferDaoError>>()?;
+        for payable in &snapshot.payables {
+            let rowid_opt = payable
+                .pending_payable_opt
+                .as_ref()
+                .map(|id| {
+                    rowids
+                        .get(&id.hash)
+                        .copied()
+                        .ok_or(AccountsTransferDaoError::UnknownFingerprint(id.hash))
+                })
+                .transpose()?;
+            Self::insert_payable(&txn, payable, rowid_opt)?
+        }
+        for receivable in &snapshot.receivables {
+            Self::insert_receivable(&txn, receivable)?
+        }
+        txn.commit()?;
+        Ok(())
+    }
+}
+
+impl AccountsTransferDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    fn select_all<T>(&self, sql: &str, from_row: fn(&Row) -> rusqlite::Result<T>) -> Vec<T> {
+        self.conn
+            .prepare(sql)
+            .expect("Internal error")
+            .query_map([], from_row)
+            .expect("rusqlite failure")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    // Wallets and hashes are unique across all chains, so rows of other chains collide too
+    fn conflicts(
+        txn: &TransactionSafeWrapper,
+        snapshot: &AccountsSnapshot,
+    ) -> Result<Vec<String>, AccountsTransferDaoError> {
+        let wallet_exists = |table: &str, wallet: &Wallet| -> Result<bool, rusqlite::Error> {
+            txn.prepare(&format!("select 1 from {} where wallet_address = ?", table))?
+                .exists(&[wallet])
+        };
+        let mut conflicts = vec![];
+        for payable in &snapshot.payables {
+            if wallet_exists("payable", &payable.wallet)? {
+                conflicts.push(format!("payable of {}", payable.wallet))
+            }
+        }
+        for receivable in &snapshot.receivables {
+            if wallet_exists("receivable", &receivable.wallet)? {
+                conflicts.push(format!("receivable of {}", receivable.wallet))
+            }
+        }
+        for fingerprint in &snapshot.fingerprints {
+            if txn
+                .prepare("select 1 from pending_payable where transaction_hash = ?")?
+                .exists(&[&format!("{:?}", fingerprint.hash)])?
+            {
+                conflicts.push(format!("pending payable {:?}", fingerprint.hash))
+            }
+        }
+        Ok(conflicts)
+    }
+
+    fn insert_fingerprint(
+        txn: &TransactionSafeWrapper,
+        fingerprint: &PendingPayableFingerprint,
+    ) -> Result<i64, AccountsTransferDaoError> {
+        let hash = format!("{:?}", fingerprint.hash);
+        let (amount_high_b, amount_low_b) =
+            BigIntDivider::deconstruct(checked_conversion::<u128, i128>(fingerprint.amount));
+        let nonce_opt = fingerprint
+            .replacement_basis_opt
+            .map(|basis| checked_conversion::<u64, i64>(basis.nonce));
+        let gas_price_wei_opt = fingerprint
+            .replacement_basis_opt
+            .map(|basis| checked_conversion::<u128, i64>(basis.gas_price_wei));
+        let params: &[&dyn ToSql] = &[
+            &hash,
+            &amount_high_b,
+            &amount_low_b,
+            &to_time_t(fingerprint.timestamp),
+            &fingerprint.attempt,
+            &fingerprint.process_error,
+            &nonce_opt,
+            &gas_price_wei_opt,
+        ];
+        txn.execute(
+            &format!(
+                "insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, \
+                 payable_timestamp, attempt, process_error, nonce, gas_price_wei, chain) \
+                 values (?, ?, ?, ?, ?, ?, ?, ?, {ACTIVE_CHAIN})"
+            ),
+            params,
+        )?;
+        Ok(txn
+            .prepare("select rowid from pending_payable where transaction_hash = ?")?
+            .query_row(&[&hash], |row| row.get::<usize, i64>(0))?)
+    }
+
+    fn insert_payable(
+        txn: &TransactionSafeWrapper,
+        payable: &PayableAccount,
+        pending_payable_rowid_opt: Option<i64>,
+    ) -> Result<(), AccountsTransferDaoError> {
+        let (balance_high_b, balance_low_b) =
+            BigIntDivider::deconstruct(checked_conversion::<u128, i128>(payable.balance_wei));
+        let params: &[&dyn ToSql] = &[
+            &payable.wallet,
+            &balance_high_b,
+            &balance_low_b,
+            &to_time_t(payable.last_paid_timestamp),
+            &pending_payable_rowid_opt,
+        ];
+        txn.execute(
+            &format!(
+                "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+                 last_paid_timestamp, pending_payable_rowid, chain) \
+                 values (?, ?, ?, ?, ?, {ACTIVE_CHAIN})"
+            ),
+            params,
+        )?;
+        Ok(())
+    }
+
+    fn insert_receivable(
+        txn: &TransactionSafeWrapper,
+        receivable: &ReceivableAccount,
+    ) -> Result<(), AccountsTransferDaoError> {
+        let (balance_high_b, balance_low_b) = BigIntDivider::deconstruct(receivable.balance_wei);
+        let params: &[&dyn ToSql] = &[
+            &receivable.wallet,
+            &balance_high_b,
+            &balance_low_b,
+            &to_time_t(receivable.last_received_timestamp),
+        ];
+        txn.execute(
+            &format!(
+                "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+                 last_received_timestamp, chain) values (?, ?, ?, ?, {ACTIVE_CHAIN})"
+            ),
+            params,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::db_access_objects::utils::from_time_t;
+    use crate::accountant::PendingPayableId;
+    use crate::blockchain::blockchain_bridge::ReplacementBasis;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal,
+    };
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+
+    fn make_subject(test_name: &str) -> AccountsTransferDaoReal {
+        let home_dir = ensure_node_home_directory_exists("accounts_transfer_dao", test_name);
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        AccountsTransferDaoReal::new(conn)
+    }
+
+    fn make_snapshot() -> AccountsSnapshot {
+        let pending_hash = make_tx_hash(0x123);
+        AccountsSnapshot {
+            chain_name: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            payables: vec![
+                PayableAccount {
+                    wallet: make_wallet("creditor_1"),
+                    balance_wei: u128::MAX / 5,
+                    last_paid_timestamp: from_time_t(1_650_000_000),
+                    pending_payable_opt: None,
+                },
+                PayableAccount {
+                    wallet: make_wallet("creditor_2"),
+                    balance_wei: 4_567_890_000_000,
+                    last_paid_timestamp: from_time_t(1_660_000_000),
+                    pending_payable_opt: Some(PendingPayableId::new(7, pending_hash)),
+                },
+            ],
+            receivables: vec![ReceivableAccount {
+                wallet: make_wallet("debtor"),
+                balance_wei: -1_234_567_890,
+                last_received_timestamp: from_time_t(1_655_000_000),
+            }],
+            fingerprints: vec![
+                PendingPayableFingerprint {
+                    rowid: 7,
+                    timestamp: from_time_t(1_670_000_000),
+                    hash: pending_hash,
+                    attempt: 3,
+                    amount: 4_000_000_000_000,
+                    process_error: None,
+                    replacement_basis_opt: Some(ReplacementBasis {
+                        nonce: 45,
+                        gas_price_wei: 60_000_000_000,
+                    }),
+                },
+                PendingPayableFingerprint {
+                    rowid: 8,
+                    timestamp: from_time_t(1_671_000_000),
+                    hash: make_tx_hash(0x456),
+                    attempt: 1,
+                    amount: 1_111_111,
+                    process_error: Some("Reverted".to_string()),
+                    replacement_basis_opt: None,
+                },
+            ],
+        }
+    }
+
+    // Rowids are assigned by the importing database and the payables point to them
+    fn with_rowids(mut snapshot: AccountsSnapshot, first_rowid: u64) -> AccountsSnapshot {
+        let rowids: HashMap<H256, u64> = snapshot
+            .fingerprints
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, fingerprint)| {
+                fingerprint.rowid = first_rowid + idx as u64;
+                (fingerprint.hash, fingerprint.rowid)
+            })
+            .collect();
+        snapshot.payables.iter_mut().for_each(|payable| {
+            if let Some(id) = payable.pending_payable_opt.as_mut() {
+                id.rowid = rowids[&id.hash]
+            }
+        });
+        snapshot
+    }
+
+    fn sorted_by_wallet(mut snapshot: AccountsSnapshot) -> AccountsSnapshot {
+        snapshot
+            .payables
+            .sort_by_key(|payable| payable.wallet.to_string());
+        snapshot
+    }
+
+    fn empty_snapshot() -> AccountsSnapshot {
+        AccountsSnapshot {
+            chain_name: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            ..AccountsSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn export_of_an_empty_database_names_just_the_chain() {
+        let subject = make_subject("export_of_an_empty_database_names_just_the_chain");
+
+        let result = subject.export();
+
+        assert_eq!(result, empty_snapshot())
+    }
+
+    #[test]
+    fn imported_accounts_are_exported_again() {
+        let mut subject = make_subject("imported_accounts_are_exported_again");
+        let snapshot = make_snapshot();
+
+        let result = subject.import(&snapshot);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(subject.export(), sorted_by_wallet(with_rowids(snapshot, 1)))
+    }
+
+    #[test]
+    fn import_reports_all_conflicts_and_changes_nothing() {
+        let mut subject = make_subject("import_reports_all_conflicts_and_changes_nothing");
+        let existing = AccountsSnapshot {
+            chain_name: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            payables: vec![make_snapshot().payables.remove(0)],
+            receivables: make_snapshot().receivables,
+            fingerprints: vec![make_snapshot().fingerprints.remove(1)],
+        };
+        subject.import(&existing).unwrap();
+
+        let result = subject.import(&make_snapshot());
+
+        assert_eq!(
+            result,
+            Err(AccountsTransferDaoError::Conflicts(vec![
+                format!("payable of {}", make_wallet("creditor_1")),
+                format!("receivable of {}", make_wallet("debtor")),
+                format!("pending payable {:?}", make_tx_hash(0x456)),
+            ]))
+        );
+        assert_eq!(subject.export(), with_rowids(existing, 1))
+    }
+
+    #[test]
+    fn import_refuses_payable_awaiting_a_transaction_without_fingerprint() {
+        let mut subject =
+            make_subject("import_refuses_payable_awaiting_a_transaction_without_fingerprint");
+        let mut snapshot = make_snapshot();
+        snapshot.fingerprints.remove(0);
+
+        let result = subject.import(&snapshot);
+
+        assert_eq!(
+            result,
+            Err(AccountsTransferDaoError::UnknownFingerprint(make_tx_hash(
+                0x123
+            )))
+        );
+        assert_eq!(subject.export(), empty_snapshot())
+    }
+
+    #[test]
+    fn import_refuses_snapshot_of_another_chain() {
+        let mut subject = make_subject("import_refuses_snapshot_of_another_chain");
+        let mut snapshot = make_snapshot();
+        snapshot.chain_name = "eth-mainnet".to_string();
+
+        let result = subject.import(&snapshot);
+
+        assert_eq!(
+            result,
+            Err(AccountsTransferDaoError::ChainMismatch(
+                "eth-mainnet".to_string()
+            ))
+        );
+        assert_eq!(subject.export(), empty_snapshot())
+    }
+}
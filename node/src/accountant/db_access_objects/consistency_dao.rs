@@ -0,0 +1,490 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::checked_conversion;
+use crate::accountant::db_access_objects::utils::{
+    DaoFactoryReal, VigilantRusqliteFlatten, ACTIVE_CHAIN,
+};
+use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
+use crate::database::rusqlite_wrappers::ConnectionWrapper;
+use crate::sub_lib::wallet::Wallet;
+use rusqlite::{Row, ToSql};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use web3::types::H256;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConsistencyDaoError {
+    // The anomaly isn't in the database anymore; the description says which
+    AnomalyGone(String),
+    RusqliteError(String),
+}
+
+impl From<rusqlite::Error> for ConsistencyDaoError {
+    fn from(input: rusqlite::Error) -> Self {
+        ConsistencyDaoError::RusqliteError(format!("{:?}", input))
+    }
+}
+
+// Rows of the payable and pending_payable tables that don't fit together. Outside a scan, every
+// fingerprint belongs to a payable waiting for it, and every payable waiting for a transaction
+// has its fingerprint
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Anomaly {
+    // If the transaction was confirmed, there'd be no payable to settle. A failed one may have been
+    // taken for paid, and then the creditor was never paid
+    OrphanFingerprint {
+        rowid: u64,
+        hash: H256,
+        amount_wei: u128,
+        failed: bool,
+    },
+    // The payable would wait forever for a confirmation; it never qualifies for payment again
+    DanglingPayable {
+        wallet: Wallet,
+        balance_wei: u128,
+        pending_payable_rowid: u64,
+    },
+}
+
+impl Anomaly {
+    pub fn repair(&self) -> &'static str {
+        match self {
+            Anomaly::OrphanFingerprint { .. } => "Forget the transaction",
+            Anomaly::DanglingPayable { .. } => {
+                "Release the payable so that the next payable scan pays it again"
+            }
+        }
+    }
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Anomaly::OrphanFingerprint {
+                hash,
+                amount_wei,
+                failed: false,
+                ..
+            } => write!(
+                f,
+                "Transaction {:?} of {} wei is awaited by no payable",
+                hash, amount_wei
+            ),
+            Anomaly::OrphanFingerprint {
+                hash,
+                amount_wei,
+                failed: true,
+                ..
+            } => write!(
+                f,
+                "Failed transaction {:?} of {} wei is awaited by no payable; if the payable was \
+                 settled nonetheless, the creditor is still unpaid",
+                hash, amount_wei
+            ),
+            Anomaly::DanglingPayable {
+                wallet,
+                balance_wei,
+                pending_payable_rowid,
+            } => write!(
+                f,
+                "Payable of {} wei owed to {} awaits transaction #{}, which is not on record",
+                balance_wei, wallet, pending_payable_rowid
+            ),
+        }
+    }
+}
+
+pub trait ConsistencyDao {
+    fn anomalies(&self) -> Vec<Anomaly>;
+    // All or nothing: if any of the anomalies is gone, the database stays untouched
+    fn repair(&mut self, anomalies: &[Anomaly]) -> Result<(), ConsistencyDaoError>;
+}
+
+pub trait ConsistencyDaoFactory {
+    fn make(&self) -> Box<dyn ConsistencyDao>;
+}
+
+impl ConsistencyDaoFactory for DaoFactoryReal {
+    fn make(&self) -> Box<dyn ConsistencyDao> {
+        Box::new(ConsistencyDaoReal::new(self.make_connection()))
+    }
+}
+
+// Abandoned fingerprints are kept on purpose after their payables were released from them
+const ORPHAN_CONDITION: &str = "rowid not in (select pending_payable_rowid from payable \
+     where pending_payable_rowid is not null) \
+     and (process_error is null or process_error not like 'ABANDONED:%')";
+const DANGLING_CONDITION: &str = "pending_payable_rowid is not null \
+     and pending_payable_rowid not in (select rowid from pending_payable)";
+
+pub struct ConsistencyDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl ConsistencyDao for ConsistencyDaoReal {
+    fn anomalies(&self) -> Vec<Anomaly> {
+        let mut anomalies = self.select_all(
+            &format!(
+                "select rowid, transaction_hash, amount_high_b, amount_low_b, process_error \
+                 from pending_payable where chain = {ACTIVE_CHAIN} and {ORPHAN_CONDITION} \
+                 order by rowid"
+            ),
+            Self::orphan_fingerprint_from_row,
+        );
+        anomalies.extend(self.select_all(
+            &format!(
+                "select wallet_address, balance_high_b, balance_low_b, pending_payable_rowid \
+                 from payable where chain = {ACTIVE_CHAIN} and {DANGLING_CONDITION} \
+                 order by wallet_address"
+            ),
+            Self::dangling_payable_from_row,
+        ));
+        anomalies
+    }
+
+    fn repair(&mut self, anomalies: &[Anomaly]) -> Result<(), ConsistencyDaoError> {
+        let txn = self.conn.transaction()?;
+        for anomaly in anomalies {
+            let changed_rows = match anomaly {
+                Anomaly::OrphanFingerprint { rowid, .. } => {
+                    let params: &[&dyn ToSql] = &[&checked_conversion::<u64, i64>(*rowid)];
+                    let changed_rows = txn.execute(
+                        &format!(
+                            "delete from pending_payable where rowid = ? and {ORPHAN_CONDITION}"
+                        ),
+                        params,
+                    )?;
+                    txn.execute(
+                        "delete from pending_payable_share where pending_payable_rowid = ?",
+                        params,
+                    )?;
+                    changed_rows
+                }
+                Anomaly::DanglingPayable {
+                    wallet,
+                    pending_payable_rowid,
+                    ..
+                } => {
+                    let params: &[&dyn ToSql] = &[
+                        wallet,
+                        &checked_conversion::<u64, i64>(*pending_payable_rowid),
+                    ];
+                    txn.execute(
+                        &format!(
+                            "update payable set pending_payable_rowid = null \
+                             where wallet_address = ? and pending_payable_rowid = ? \
+                             and {DANGLING_CONDITION}"
+                        ),
+                        params,
+                    )?
+                }
+            };
+            if changed_rows != 1 {
+                return Err(ConsistencyDaoError::AnomalyGone(anomaly.to_string()));
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+}
+
+impl ConsistencyDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    fn select_all(
+        &self,
+        sql: &str,
+        from_row: fn(&Row) -> rusqlite::Result<Anomaly>,
+    ) -> Vec<Anomaly> {
+        self.conn
+            .prepare(sql)
+            .expect("Internal error")
+            .query_map([], from_row)
+            .expect("rusqlite failure")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    fn orphan_fingerprint_from_row(row: &Row) -> rusqlite::Result<Anomaly> {
+        let rowid: i64 = row.get(0)?;
+        let hash: String = row.get(1)?;
+        let process_error: Option<String> = row.get(4)?;
+        Ok(Anomaly::OrphanFingerprint {
+            rowid: checked_conversion::<i64, u64>(rowid),
+            hash: H256::from_str(&hash[2..]).expect("hash in the database is corrupt"),
+            amount_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                row.get(2)?,
+                row.get(3)?,
+            )),
+            failed: process_error.is_some(),
+        })
+    }
+
+    fn dangling_payable_from_row(row: &Row) -> rusqlite::Result<Anomaly> {
+        let pending_payable_rowid: i64 = row.get(3)?;
+        Ok(Anomaly::DanglingPayable {
+            wallet: row.get(0)?,
+            balance_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                row.get(1)?,
+                row.get(2)?,
+            )),
+            pending_payable_rowid: checked_conversion::<i64, u64>(pending_payable_rowid),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::db_access_objects::payable_dao::{PayableDao, PayableDaoReal};
+    use crate::accountant::db_access_objects::pending_payable_dao::{
+        PendingPayableDao, PendingPayableDaoReal,
+    };
+    use crate::accountant::db_access_objects::utils::from_time_t;
+    use crate::blockchain::blockchain_bridge::{CreditorShare, ReplacementBasis};
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal,
+    };
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::path::PathBuf;
+
+    struct Daos {
+        subject: ConsistencyDaoReal,
+        payable_dao: PayableDaoReal,
+        pending_payable_dao: PendingPayableDaoReal,
+    }
+
+    fn make_daos(test_name: &str) -> Daos {
+        let home_dir = ensure_node_home_directory_exists("consistency_dao", test_name);
+        let conn = |home_dir: &PathBuf| {
+            DbInitializerReal::default()
+                .initialize(home_dir, DbInitializationConfig::test_default())
+                .unwrap()
+        };
+        Daos {
+            subject: ConsistencyDaoReal::new(conn(&home_dir)),
+            payable_dao: PayableDaoReal::new(conn(&home_dir)),
+            pending_payable_dao: PendingPayableDaoReal::new(conn(&home_dir)),
+        }
+    }
+
+    // Rowids 1 and 2 are awaited by creditors 1 and 2; rowids 3 and 4, the latter failed, by
+    // nobody. Creditor 3 awaits rowid 5, which doesn't exist, and creditor 4 nothing
+    fn set_up_anomalies(daos: &Daos) {
+        let hashes_and_amounts = (1..=4)
+            .map(|n| HashAndAmount {
+                hash: make_tx_hash(n),
+                amount: n as u128 * 1_000,
+            })
+            .collect::<Vec<HashAndAmount>>();
+        daos.pending_payable_dao
+            .insert_new_fingerprints(
+                &hashes_and_amounts,
+                from_time_t(1_000_000),
+                ReplacementBasis {
+                    nonce: 1,
+                    gas_price_wei: 1_000_000_000,
+                },
+            )
+            .unwrap();
+        daos.pending_payable_dao.mark_failures(&[4]).unwrap();
+        let creditors = (1..=4)
+            .map(|n| make_wallet(&format!("creditor_{}", n)))
+            .collect::<Vec<Wallet>>();
+        creditors.iter().enumerate().for_each(|(idx, creditor)| {
+            daos.payable_dao
+                .more_money_payable(from_time_t(1_000_000), creditor, (idx as u128 + 1) * 10_000)
+                .unwrap()
+        });
+        daos.payable_dao
+            .mark_pending_payables_rowids(&[
+                (&creditors[0], 1),
+                (&creditors[1], 2),
+                (&creditors[2], 5),
+            ])
+            .unwrap();
+    }
+
+    fn expected_anomalies() -> Vec<Anomaly> {
+        vec![
+            Anomaly::OrphanFingerprint {
+                rowid: 3,
+                hash: make_tx_hash(3),
+                amount_wei: 3_000,
+                failed: false,
+            },
+            Anomaly::OrphanFingerprint {
+                rowid: 4,
+                hash: make_tx_hash(4),
+                amount_wei: 4_000,
+                failed: true,
+            },
+            Anomaly::DanglingPayable {
+                wallet: make_wallet("creditor_3"),
+                balance_wei: 30_000,
+                pending_payable_rowid: 5,
+            },
+        ]
+    }
+
+    #[test]
+    fn a_consistent_database_has_no_anomalies() {
+        let daos = make_daos("a_consistent_database_has_no_anomalies");
+
+        let result = daos.subject.anomalies();
+
+        assert_eq!(result, vec![]);
+    }
+
+    #[test]
+    fn anomalies_are_found_among_payables_and_fingerprints() {
+        let daos = make_daos("anomalies_are_found_among_payables_and_fingerprints");
+        set_up_anomalies(&daos);
+
+        let result = daos.subject.anomalies();
+
+        assert_eq!(result, expected_anomalies());
+    }
+
+    #[test]
+    fn repair_forgets_orphan_fingerprints_and_releases_dangling_payables() {
+        let mut daos =
+            make_daos("repair_forgets_orphan_fingerprints_and_releases_dangling_payables");
+        set_up_anomalies(&daos);
+
+        let result = daos.subject.repair(&expected_anomalies());
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(daos.subject.anomalies(), vec![]);
+        let remaining_rowids = daos
+            .pending_payable_dao
+            .return_all_errorless_fingerprints()
+            .into_iter()
+            .map(|fingerprint| fingerprint.rowid)
+            .collect::<Vec<u64>>();
+        assert_eq!(remaining_rowids, vec![1, 2]);
+        assert_eq!(
+            daos.pending_payable_dao.return_failed_fingerprints(),
+            vec![]
+        );
+        let released = daos
+            .payable_dao
+            .account_status(&make_wallet("creditor_3"))
+            .unwrap();
+        assert_eq!(released.pending_payable_opt, None);
+        assert_eq!(released.balance_wei, 30_000);
+    }
+
+    #[test]
+    fn repair_forgets_the_creditor_shares_of_orphan_fingerprints_too() {
+        let mut daos = make_daos("repair_forgets_the_creditor_shares_of_orphan_fingerprints_too");
+        set_up_anomalies(&daos);
+        daos.pending_payable_dao
+            .insert_creditor_shares(&[
+                CreditorShare {
+                    hash: make_tx_hash(1),
+                    wallet: make_wallet("creditor_1"),
+                    amount: 1_000,
+                },
+                CreditorShare {
+                    hash: make_tx_hash(3),
+                    wallet: make_wallet("creditor_4"),
+                    amount: 3_000,
+                },
+            ])
+            .unwrap();
+
+        let result = daos.subject.repair(&expected_anomalies());
+
+        assert_eq!(result, Ok(()));
+        let remaining_share_rowids = daos
+            .subject
+            .conn
+            .prepare("select pending_payable_rowid from pending_payable_share")
+            .unwrap()
+            .query_map([], |row| row.get::<usize, i64>(0))
+            .unwrap()
+            .vigilant_flatten()
+            .collect::<Vec<i64>>();
+        assert_eq!(remaining_share_rowids, vec![1]);
+    }
+
+    #[test]
+    fn repair_changes_nothing_if_an_anomaly_is_gone() {
+        let mut daos = make_daos("repair_changes_nothing_if_an_anomaly_is_gone");
+        set_up_anomalies(&daos);
+        let gone = Anomaly::OrphanFingerprint {
+            rowid: 1,
+            hash: make_tx_hash(1),
+            amount_wei: 1_000,
+            failed: false,
+        };
+
+        let result = daos
+            .subject
+            .repair(&[expected_anomalies()[0].clone(), gone.clone()]);
+
+        assert_eq!(
+            result,
+            Err(ConsistencyDaoError::AnomalyGone(gone.to_string()))
+        );
+        assert_eq!(daos.subject.anomalies(), expected_anomalies());
+    }
+
+    #[test]
+    fn abandoned_fingerprints_are_no_anomaly() {
+        let daos = make_daos("abandoned_fingerprints_are_no_anomaly");
+        set_up_anomalies(&daos);
+        daos.pending_payable_dao
+            .mark_abandoned(&[3], "pending for longer than 3600sec")
+            .unwrap();
+
+        let result = daos.subject.anomalies();
+
+        assert_eq!(result, expected_anomalies()[1..].to_vec());
+    }
+
+    #[test]
+    fn anomalies_describe_themselves_and_their_repairs() {
+        let anomalies = expected_anomalies();
+
+        let result = anomalies
+            .iter()
+            .map(|anomaly| (anomaly.to_string(), anomaly.repair()))
+            .collect::<Vec<(String, &str)>>();
+
+        assert_eq!(
+            result,
+            vec![
+                (
+                    format!(
+                        "Transaction {:?} of 3000 wei is awaited by no payable",
+                        make_tx_hash(3)
+                    ),
+                    "Forget the transaction"
+                ),
+                (
+                    format!(
+                        "Failed transaction {:?} of 4000 wei is awaited by no payable; if the \
+                         payable was settled nonetheless, the creditor is still unpaid",
+                        make_tx_hash(4)
+                    ),
+                    "Forget the transaction"
+                ),
+                (
+                    format!(
+                        "Payable of 30000 wei owed to {} awaits transaction #5, which is not on \
+                         record",
+                        make_wallet("creditor_3")
+                    ),
+                    "Release the payable so that the next payable scan pays it again"
+                ),
+            ]
+        );
+    }
+}
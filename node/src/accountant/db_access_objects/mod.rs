@@ -1,7 +1,11 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod accounts_transfer_dao;
 pub mod banned_dao;
+pub mod consistency_dao;
+pub mod outbound_payment_dao;
 pub mod payable_dao;
 pub mod pending_payable_dao;
 pub mod receivable_dao;
+pub mod rpc_usage_dao;
 pub mod utils;
@@ -0,0 +1,542 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::checked_conversion;
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::db_access_objects::pending_payable_dao::PendingPayable;
+use crate::accountant::db_access_objects::utils::{
+    from_time_t, to_time_t, DaoFactoryReal, VigilantRusqliteFlatten,
+};
+use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::BlockchainAgentSnapshot;
+use crate::database::rusqlite_wrappers::ConnectionWrapper;
+use itertools::Itertools;
+use rusqlite::{Row, ToSql};
+use std::str::FromStr;
+use std::time::SystemTime;
+use web3::types::H256;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OutboundPaymentDaoError {
+    InsertionFailed(String),
+    UpdateFailed(String),
+    RecordDeletion(String),
+}
+
+impl From<rusqlite::Error> for OutboundPaymentDaoError {
+    fn from(input: rusqlite::Error) -> Self {
+        OutboundPaymentDaoError::InsertionFailed(input.to_string())
+    }
+}
+
+// Queued payments are on their way to the BlockchainBridge; submitted ones have their transaction
+// sent, and confirmed ones stay only until the other payments of the same instructions are done
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OutboundPaymentStatus {
+    Queued,
+    Submitted,
+    Confirmed,
+}
+
+impl OutboundPaymentStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutboundPaymentStatus::Queued => "queued",
+            OutboundPaymentStatus::Submitted => "submitted",
+            OutboundPaymentStatus::Confirmed => "confirmed",
+        }
+    }
+
+    fn from_column(status: &str) -> Self {
+        match status {
+            "queued" => OutboundPaymentStatus::Queued,
+            "submitted" => OutboundPaymentStatus::Submitted,
+            "confirmed" => OutboundPaymentStatus::Confirmed,
+            x => panic!("Unknown outbound payment status '{}' - database corrupt", x),
+        }
+    }
+}
+
+// A payment of the instructions handed over to the BlockchainBridge, together with the timestamp
+// and agent snapshot shared by all payments of the instructions
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OutboundPayment {
+    pub rowid: u64,
+    pub instructions_rowid: u64,
+    pub timestamp: SystemTime,
+    pub agent_snapshot: BlockchainAgentSnapshot,
+    pub wallet_address: String,
+    pub amount_wei: u128,
+    pub hash_opt: Option<H256>,
+    pub status: OutboundPaymentStatus,
+}
+
+pub trait OutboundPaymentDao {
+    // Returns the rowid of the instructions; all their payments are queued
+    fn enqueue(
+        &mut self,
+        timestamp: SystemTime,
+        agent_snapshot: &BlockchainAgentSnapshot,
+        accounts: &[PayableAccount],
+    ) -> Result<u64, OutboundPaymentDaoError>;
+    // Payments of the instructions left queued weren't sent and are dropped
+    fn mark_submitted(
+        &self,
+        instructions_rowid: u64,
+        sent_payments: &[PendingPayable],
+    ) -> Result<(), OutboundPaymentDaoError>;
+    fn mark_confirmed(&self, hashes: &[H256]) -> Result<(), OutboundPaymentDaoError>;
+    fn unfinished(&self) -> Vec<OutboundPayment>;
+    fn remove(&self, rowids: &[u64]) -> Result<(), OutboundPaymentDaoError>;
+}
+
+pub trait OutboundPaymentDaoFactory {
+    fn make(&self) -> Box<dyn OutboundPaymentDao>;
+}
+
+impl OutboundPaymentDaoFactory for DaoFactoryReal {
+    fn make(&self) -> Box<dyn OutboundPaymentDao> {
+        Box::new(OutboundPaymentDaoReal::new(self.make_connection()))
+    }
+}
+
+pub struct OutboundPaymentDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl OutboundPaymentDao for OutboundPaymentDaoReal {
+    fn enqueue(
+        &mut self,
+        timestamp: SystemTime,
+        agent_snapshot: &BlockchainAgentSnapshot,
+        accounts: &[PayableAccount],
+    ) -> Result<u64, OutboundPaymentDaoError> {
+        let snapshot = serde_json::to_string(agent_snapshot).expect("serialization failed");
+        let txn = self.conn.transaction()?;
+        let params: &[&dyn ToSql] = &[&to_time_t(timestamp), &snapshot];
+        let instructions_rowid: i64 = txn
+            .prepare(
+                "insert into outbound_instructions (timestamp, agent_snapshot) values (?, ?) \
+                 returning rowid",
+            )?
+            .query_row(params, |row| row.get(0))?;
+        for account in accounts {
+            let (amount_high_b, amount_low_b) =
+                BigIntDivider::deconstruct(checked_conversion::<u128, i128>(account.balance_wei));
+            let params: &[&dyn ToSql] = &[
+                &instructions_rowid,
+                &account.wallet,
+                &amount_high_b,
+                &amount_low_b,
+                &OutboundPaymentStatus::Queued.as_str(),
+            ];
+            txn.execute(
+                "insert into outbound_payment (instructions_rowid, wallet_address, amount_high_b, \
+                 amount_low_b, status) values (?, ?, ?, ?, ?)",
+                params,
+            )?;
+        }
+        txn.commit()?;
+        Ok(checked_conversion::<i64, u64>(instructions_rowid))
+    }
+
+    fn mark_submitted(
+        &self,
+        instructions_rowid: u64,
+        sent_payments: &[PendingPayable],
+    ) -> Result<(), OutboundPaymentDaoError> {
+        let instructions_rowid = checked_conversion::<u64, i64>(instructions_rowid);
+        let mut stm = self
+            .conn
+            .prepare(
+                "update outbound_payment set status = ?, transaction_hash = ? \
+                 where instructions_rowid = ? and wallet_address = ? and status = ?",
+            )
+            .expect("Internal error");
+        for payment in sent_payments {
+            let params: &[&dyn ToSql] = &[
+                &OutboundPaymentStatus::Submitted.as_str(),
+                &format!("{:?}", payment.hash),
+                &instructions_rowid,
+                &payment.recipient_wallet,
+                &OutboundPaymentStatus::Queued.as_str(),
+            ];
+            if let Err(e) = stm.execute(params) {
+                return Err(OutboundPaymentDaoError::UpdateFailed(e.to_string()));
+            }
+        }
+        let params: &[&dyn ToSql] = &[&instructions_rowid, &OutboundPaymentStatus::Queued.as_str()];
+        match self
+            .conn
+            .prepare("delete from outbound_payment where instructions_rowid = ? and status = ?")
+            .expect("Internal error")
+            .execute(params)
+        {
+            Ok(_) => self.remove_finished_instructions(),
+            Err(e) => Err(OutboundPaymentDaoError::RecordDeletion(e.to_string())),
+        }
+    }
+
+    fn mark_confirmed(&self, hashes: &[H256]) -> Result<(), OutboundPaymentDaoError> {
+        let sql = format!(
+            "update outbound_payment set status = '{}' where transaction_hash in ({})",
+            OutboundPaymentStatus::Confirmed.as_str(),
+            hashes.iter().map(|hash| format!("'{:?}'", hash)).join(", ")
+        );
+        match self.conn.prepare(&sql).expect("Internal error").execute([]) {
+            Ok(_) => self.remove_finished_instructions(),
+            Err(e) => Err(OutboundPaymentDaoError::UpdateFailed(e.to_string())),
+        }
+    }
+
+    fn unfinished(&self) -> Vec<OutboundPayment> {
+        let mut stm = self
+            .conn
+            .prepare(
+                "select outbound_payment.rowid, instructions_rowid, timestamp, agent_snapshot, \
+                 wallet_address, amount_high_b, amount_low_b, transaction_hash, status \
+                 from outbound_payment inner join outbound_instructions \
+                 on outbound_instructions.rowid = outbound_payment.instructions_rowid \
+                 where status != ? order by outbound_payment.rowid",
+            )
+            .expect("Internal error");
+        stm.query_map(
+            [OutboundPaymentStatus::Confirmed.as_str()],
+            Self::payment_from_row,
+        )
+        .expect("rusqlite failure")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    fn remove(&self, rowids: &[u64]) -> Result<(), OutboundPaymentDaoError> {
+        let sql = format!(
+            "delete from outbound_payment where rowid in ({})",
+            rowids.iter().join(", ")
+        );
+        match self.conn.prepare(&sql).expect("Internal error").execute([]) {
+            Ok(_) => self.remove_finished_instructions(),
+            Err(e) => Err(OutboundPaymentDaoError::RecordDeletion(e.to_string())),
+        }
+    }
+}
+
+impl OutboundPaymentDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    // Instructions whose payments are all confirmed or gone are done with
+    fn remove_finished_instructions(&self) -> Result<(), OutboundPaymentDaoError> {
+        let payments_sql = format!(
+            "delete from outbound_payment where instructions_rowid not in \
+             (select instructions_rowid from outbound_payment where status != '{}')",
+            OutboundPaymentStatus::Confirmed.as_str()
+        );
+        let instructions_sql = "delete from outbound_instructions where rowid not in \
+             (select instructions_rowid from outbound_payment)";
+        [payments_sql.as_str(), instructions_sql]
+            .into_iter()
+            .try_for_each(
+                |sql| match self.conn.prepare(sql).expect("Internal error").execute([]) {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(OutboundPaymentDaoError::RecordDeletion(e.to_string())),
+                },
+            )
+    }
+
+    fn payment_from_row(row: &Row) -> rusqlite::Result<OutboundPayment> {
+        let rowid: i64 = Self::get_with_expect(row, 0);
+        let instructions_rowid: i64 = Self::get_with_expect(row, 1);
+        let timestamp: i64 = Self::get_with_expect(row, 2);
+        let snapshot: String = Self::get_with_expect(row, 3);
+        let hash_opt: Option<String> = Self::get_with_expect(row, 7);
+        let status: String = Self::get_with_expect(row, 8);
+        Ok(OutboundPayment {
+            rowid: checked_conversion::<i64, u64>(rowid),
+            instructions_rowid: checked_conversion::<i64, u64>(instructions_rowid),
+            timestamp: from_time_t(timestamp),
+            agent_snapshot: serde_json::from_str(&snapshot).expect("database is corrupt"),
+            wallet_address: Self::get_with_expect(row, 4),
+            amount_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                Self::get_with_expect(row, 5),
+                Self::get_with_expect(row, 6),
+            )),
+            hash_opt: hash_opt.map(|hash| H256::from_str(&hash[2..]).expect("database is corrupt")),
+            status: OutboundPaymentStatus::from_column(&status),
+        })
+    }
+
+    fn get_with_expect<T: rusqlite::types::FromSql>(row: &Row, index: usize) -> T {
+        row.get(index).expect("database is corrupt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::test_utils::make_payable_account;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::database::rusqlite_wrappers::ConnectionWrapperReal;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::{Connection, OpenFlags};
+
+    fn make_agent_snapshot() -> BlockchainAgentSnapshot {
+        BlockchainAgentSnapshot {
+            consuming_wallet_address: "0x0000000000000000000000000000000000123456".to_string(),
+            transaction_fee_balance_wei: 5_000_000_000_000,
+            service_fee_balance_wei: u128::MAX / 5,
+            agreed_fee_per_computation_unit: 60_000_000_000,
+            chain_identifier: "polygon-amoy".to_string(),
+        }
+    }
+
+    fn make_subject(test_name: &str) -> OutboundPaymentDaoReal {
+        let home_dir = ensure_node_home_directory_exists("outbound_payment_dao", test_name);
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        OutboundPaymentDaoReal::new(conn)
+    }
+
+    fn make_expected_payment(
+        rowid: u64,
+        instructions_rowid: u64,
+        account: &PayableAccount,
+        hash_opt: Option<H256>,
+        status: OutboundPaymentStatus,
+    ) -> OutboundPayment {
+        OutboundPayment {
+            rowid,
+            instructions_rowid,
+            timestamp: from_time_t(1_700_000_000),
+            agent_snapshot: make_agent_snapshot(),
+            wallet_address: account.wallet.to_string(),
+            amount_wei: account.balance_wei,
+            hash_opt,
+            status,
+        }
+    }
+
+    #[test]
+    fn enqueued_payments_are_unfinished_until_confirmed() {
+        let mut subject = make_subject("enqueued_payments_are_unfinished_until_confirmed");
+        let mut account_1 = make_payable_account(111);
+        account_1.balance_wei = u128::MAX / 3;
+        let account_2 = make_payable_account(222);
+        let account_3 = make_payable_account(333);
+        let timestamp = from_time_t(1_700_000_000);
+
+        let instructions_rowid = subject
+            .enqueue(
+                timestamp,
+                &make_agent_snapshot(),
+                &[account_1.clone(), account_2.clone(), account_3.clone()],
+            )
+            .unwrap();
+        let queued = subject.unfinished();
+        subject
+            .mark_submitted(
+                instructions_rowid,
+                &[
+                    PendingPayable::new(account_1.wallet.clone(), make_tx_hash(0x1)),
+                    PendingPayable::new(account_2.wallet.clone(), make_tx_hash(0x2)),
+                ],
+            )
+            .unwrap();
+        let submitted = subject.unfinished();
+        subject.mark_confirmed(&[make_tx_hash(0x1)]).unwrap();
+        let partially_confirmed = subject.unfinished();
+        subject.mark_confirmed(&[make_tx_hash(0x2)]).unwrap();
+        let all_confirmed = subject.unfinished();
+
+        assert_eq!(instructions_rowid, 1);
+        assert_eq!(
+            queued,
+            vec![
+                make_expected_payment(1, 1, &account_1, None, OutboundPaymentStatus::Queued),
+                make_expected_payment(2, 1, &account_2, None, OutboundPaymentStatus::Queued),
+                make_expected_payment(3, 1, &account_3, None, OutboundPaymentStatus::Queued),
+            ]
+        );
+        // The payment to account_3 was never sent
+        assert_eq!(
+            submitted,
+            vec![
+                make_expected_payment(
+                    1,
+                    1,
+                    &account_1,
+                    Some(make_tx_hash(0x1)),
+                    OutboundPaymentStatus::Submitted
+                ),
+                make_expected_payment(
+                    2,
+                    1,
+                    &account_2,
+                    Some(make_tx_hash(0x2)),
+                    OutboundPaymentStatus::Submitted
+                ),
+            ]
+        );
+        assert_eq!(
+            partially_confirmed,
+            vec![make_expected_payment(
+                2,
+                1,
+                &account_2,
+                Some(make_tx_hash(0x2)),
+                OutboundPaymentStatus::Submitted
+            )]
+        );
+        assert_eq!(all_confirmed, vec![]);
+        let row_count = |table: &str| -> i64 {
+            subject
+                .conn
+                .prepare(&format!("select count(*) from {}", table))
+                .unwrap()
+                .query_row([], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(row_count("outbound_payment"), 0);
+        assert_eq!(row_count("outbound_instructions"), 0)
+    }
+
+    #[test]
+    fn mark_submitted_concerns_only_the_given_instructions() {
+        let mut subject = make_subject("mark_submitted_concerns_only_the_given_instructions");
+        let account = make_payable_account(111);
+        let timestamp = from_time_t(1_700_000_000);
+        let instructions_rowid_1 = subject
+            .enqueue(timestamp, &make_agent_snapshot(), &[account.clone()])
+            .unwrap();
+        let instructions_rowid_2 = subject
+            .enqueue(timestamp, &make_agent_snapshot(), &[account.clone()])
+            .unwrap();
+
+        subject
+            .mark_submitted(
+                instructions_rowid_2,
+                &[PendingPayable::new(
+                    account.wallet.clone(),
+                    make_tx_hash(0x2),
+                )],
+            )
+            .unwrap();
+
+        assert_eq!(
+            subject.unfinished(),
+            vec![
+                make_expected_payment(
+                    1,
+                    instructions_rowid_1,
+                    &account,
+                    None,
+                    OutboundPaymentStatus::Queued
+                ),
+                make_expected_payment(
+                    2,
+                    instructions_rowid_2,
+                    &account,
+                    Some(make_tx_hash(0x2)),
+                    OutboundPaymentStatus::Submitted
+                ),
+            ]
+        )
+    }
+
+    #[test]
+    fn remove_works() {
+        let mut subject = make_subject("remove_works");
+        let account_1 = make_payable_account(111);
+        let account_2 = make_payable_account(222);
+        let timestamp = from_time_t(1_700_000_000);
+        subject
+            .enqueue(timestamp, &make_agent_snapshot(), &[account_1.clone()])
+            .unwrap();
+        subject
+            .enqueue(timestamp, &make_agent_snapshot(), &[account_2.clone()])
+            .unwrap();
+
+        subject.remove(&[1]).unwrap();
+
+        assert_eq!(
+            subject.unfinished(),
+            vec![make_expected_payment(
+                2,
+                2,
+                &account_2,
+                None,
+                OutboundPaymentStatus::Queued
+            )]
+        );
+        let instructions_count: i64 = subject
+            .conn
+            .prepare("select count(*) from outbound_instructions")
+            .unwrap()
+            .query_row([], |row| row.get(0))
+            .unwrap();
+        assert_eq!(instructions_count, 1)
+    }
+
+    #[test]
+    fn outbound_payment_dao_handles_errors() {
+        let home_dir = ensure_node_home_directory_exists(
+            "outbound_payment_dao",
+            "outbound_payment_dao_handles_errors",
+        );
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let mut subject =
+            OutboundPaymentDaoReal::new(Box::new(ConnectionWrapperReal::new(conn_read_only)));
+
+        let enqueue_result = subject.enqueue(
+            from_time_t(1_700_000_000),
+            &make_agent_snapshot(),
+            &[make_payable_account(111)],
+        );
+        let mark_submitted_result = subject.mark_submitted(
+            1,
+            &[PendingPayable::new(
+                make_payable_account(111).wallet,
+                make_tx_hash(0x1),
+            )],
+        );
+        let mark_confirmed_result = subject.mark_confirmed(&[make_tx_hash(0x1)]);
+        let remove_result = subject.remove(&[1]);
+
+        let readonly = "attempt to write a readonly database".to_string();
+        assert_eq!(
+            enqueue_result,
+            Err(OutboundPaymentDaoError::InsertionFailed(readonly.clone()))
+        );
+        assert_eq!(
+            mark_submitted_result,
+            Err(OutboundPaymentDaoError::UpdateFailed(readonly.clone()))
+        );
+        assert_eq!(
+            mark_confirmed_result,
+            Err(OutboundPaymentDaoError::UpdateFailed(readonly.clone()))
+        );
+        assert_eq!(
+            remove_result,
+            Err(OutboundPaymentDaoError::RecordDeletion(readonly))
+        )
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown outbound payment status 'lost' - database corrupt")]
+    fn unknown_status_is_database_corruption() {
+        OutboundPaymentStatus::from_column("lost");
+    }
+}
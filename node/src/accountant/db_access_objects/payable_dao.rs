@@ -7,22 +7,24 @@ use crate::accountant::db_big_integer::big_int_db_processor::{BigIntDbProcessor,
 use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
 use crate::accountant::db_access_objects::utils;
 use crate::accountant::db_access_objects::utils::{
-    sum_i128_values_from_table, to_time_t, AssemblerFeeder, CustomQuery, DaoFactoryReal,
-    RangeStmConfig, TopStmConfig, VigilantRusqliteFlatten,
+    debt_aging_from_table, sum_i128_values_from_table, to_time_t, AssemblerFeeder, CustomQuery,
+    DaoFactoryReal, DebtAging, RangeStmConfig, TopStmConfig, VigilantRusqliteFlatten,
+    ACTIVE_CHAIN,
 };
 use crate::accountant::db_access_objects::payable_dao::mark_pending_payable_associated_functions::{
     compose_case_expression, execute_command, serialize_wallets,
 };
-use crate::accountant::{checked_conversion, sign_conversion, PendingPayableId};
+use crate::accountant::{
+    checked_conversion, comma_joined_stringifiable, sign_conversion, PendingPayableId,
+};
 use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
 use crate::database::rusqlite_wrappers::ConnectionWrapper;
+use crate::sub_lib::accountant::PaymentThresholds;
 use crate::sub_lib::wallet::Wallet;
-#[cfg(test)]
 use ethereum_types::{BigEndianHash, U256};
 use masq_lib::utils::ExpectValue;
-#[cfg(test)]
-use rusqlite::OptionalExtension;
-use rusqlite::{Error, Row};
+use rusqlite::{Error, OptionalExtension, Row, ToSql};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::str::FromStr;
 use std::time::SystemTime;
@@ -33,6 +35,14 @@ use web3::types::H256;
 pub enum PayableDaoError {
     SignConversion(u128),
     RusqliteError(String),
+    NoSuchPayable,
+    PaymentPending(u64),
+}
+
+impl From<rusqlite::Error> for PayableDaoError {
+    fn from(input: Error) -> Self {
+        PayableDaoError::RusqliteError(format!("{:?}", input))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -43,6 +53,28 @@ pub struct PayableAccount {
     pub pending_payable_opt: Option<PendingPayableId>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayableWriteOff {
+    pub wallet: Wallet,
+    pub balance_wei: u128,
+    pub reason: String,
+    pub banned: bool,
+    pub timestamp: SystemTime,
+}
+
+// A creditor's invoice that our own books don't bear out
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvoiceDispute {
+    pub creditor_wallet: Wallet,
+    pub period_start: SystemTime,
+    pub period_end: SystemTime,
+    pub invoiced_services: u64,
+    pub booked_services: u64,
+    pub invoiced_wei: u128,
+    pub booked_wei: u128,
+    pub received_at: SystemTime,
+}
+
 pub trait PayableDao: Debug + Send {
     fn more_money_payable(
         &self,
@@ -63,11 +95,51 @@ pub trait PayableDao: Debug + Send {
 
     fn non_pending_payables(&self) -> Vec<PayableAccount>;
 
+    // Detaches the payables from their failed transactions so that they can be paid again
+    fn release_failed_payables(
+        &self,
+        pending_payable_rowids: &[u64],
+    ) -> Result<Vec<PayableAccount>, PayableDaoError>;
+
+    // The creditors awaiting the given transactions, keyed by the rowids of their fingerprints
+    fn creditors_of_pending_payables(&self, pending_payable_rowids: &[u64])
+        -> HashMap<u64, Wallet>;
+
+    fn upsert_creditor_thresholds(
+        &self,
+        creditor_wallet: &Wallet,
+        payment_thresholds: &PaymentThresholds,
+    ) -> Result<(), PayableDaoError>;
+
+    // Thresholds the creditors advertised about themselves, to be used instead of ours
+    fn creditor_thresholds(&self) -> HashMap<Wallet, PaymentThresholds>;
+
+    // Gives up on the creditor's payable, keeping its record in payable_write_off. A banned
+    // creditor is also put in the banned table. Returns the balance written off
+    fn write_off(
+        &mut self,
+        wallet: &Wallet,
+        reason: &str,
+        ban: bool,
+        timestamp: SystemTime,
+    ) -> Result<u128, PayableDaoError>;
+
+    // The payables given up on for the creditor, oldest first
+    fn write_offs(&self, wallet: &Wallet) -> Vec<PayableWriteOff>;
+
+    fn record_invoice_dispute(&self, dispute: &InvoiceDispute) -> Result<(), PayableDaoError>;
+
+    // All the disputes on record, oldest first
+    fn invoice_disputes(&self) -> Vec<InvoiceDispute>;
+
     fn custom_query(&self, custom_query: CustomQuery<u64>) -> Option<Vec<PayableAccount>>;
 
     fn total(&self) -> u128;
 
-    #[cfg(test)]
+    // Balances by time since they were last paid
+    fn aging(&self, now: SystemTime) -> DebtAging;
+
+    // The hash in the pending payable ID is a placeholder; only the rowid is known here
     fn account_status(&self, wallet: &Wallet) -> Option<PayableAccount>;
 }
 
@@ -94,11 +166,11 @@ impl PayableDao for PayableDaoReal {
         wallet: &Wallet,
         amount: u128,
     ) -> Result<(), PayableDaoError> {
-        let main_sql = "insert into payable (wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid) \
-                values (:wallet, :balance_high_b, :balance_low_b, :last_paid_timestamp, null) on conflict (wallet_address) do update set \
-                balance_high_b = balance_high_b + :balance_high_b, balance_low_b = balance_low_b + :balance_low_b where wallet_address = :wallet";
-        let update_clause_with_compensated_overflow = "update payable set \
-                balance_high_b = :balance_high_b, balance_low_b = :balance_low_b where wallet_address = :wallet";
+        let main_sql = format!("insert into payable (wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid, chain) \
+                values (:wallet, :balance_high_b, :balance_low_b, :last_paid_timestamp, null, {ACTIVE_CHAIN}) on conflict (wallet_address, chain) do update set \
+                balance_high_b = balance_high_b + :balance_high_b, balance_low_b = balance_low_b + :balance_low_b where wallet_address = :wallet");
+        let update_clause_with_compensated_overflow = format!("update payable set \
+                balance_high_b = :balance_high_b, balance_low_b = :balance_low_b where wallet_address = :wallet and chain = {ACTIVE_CHAIN}");
 
         let last_paid_timestamp = to_time_t(timestamp);
         let params = SQLParamsBuilder::default()
@@ -115,7 +187,7 @@ impl PayableDao for PayableDaoReal {
 
         self.big_int_db_processor.execute(
             Either::Left(self.conn.as_ref()),
-            BigIntSqlConfig::new(main_sql, update_clause_with_compensated_overflow, params),
+            BigIntSqlConfig::new(&main_sql, &update_clause_with_compensated_overflow, params),
         )?;
 
         Ok(())
@@ -136,10 +208,10 @@ impl PayableDao for PayableDaoReal {
             "update payable set \
                 pending_payable_rowid = {} \
              where
-                pending_payable_rowid is null and wallet_address in ({})
+                pending_payable_rowid is null and wallet_address in ({}) and chain = {}
              returning
                 pending_payable_rowid",
-            case_expr, wallets,
+            case_expr, wallets, ACTIVE_CHAIN,
         );
         execute_command(&*self.conn, wallets_and_rowids, &sql)
     }
@@ -149,13 +221,17 @@ impl PayableDao for PayableDaoReal {
         confirmed_payables: &[PendingPayableFingerprint],
     ) -> Result<(), PayableDaoError> {
         confirmed_payables.iter().try_for_each(|pending_payable_fingerprint| {
+            let creditor_shares = self.creditor_shares(pending_payable_fingerprint.rowid);
+            if !creditor_shares.is_empty() {
+                return self.creditor_shares_confirmed(pending_payable_fingerprint, &creditor_shares);
+            }
 
-            let main_sql = "update payable set \
+            let main_sql = format!("update payable set \
                     balance_high_b = balance_high_b + :balance_high_b, balance_low_b = balance_low_b + :balance_low_b, \
-                    last_paid_timestamp = :last_paid, pending_payable_rowid = null where pending_payable_rowid = :rowid";
-            let update_clause_with_compensated_overflow = "update payable set \
+                    last_paid_timestamp = :last_paid, pending_payable_rowid = null where pending_payable_rowid = :rowid and chain = {ACTIVE_CHAIN}");
+            let update_clause_with_compensated_overflow = format!("update payable set \
                     balance_high_b = :balance_high_b, balance_low_b = :balance_low_b, last_paid_timestamp = :last_paid, \
-                    pending_payable_rowid = null where pending_payable_rowid = :rowid";
+                    pending_payable_rowid = null where pending_payable_rowid = :rowid and chain = {ACTIVE_CHAIN}");
 
             let i64_rowid = checked_conversion::<u64, i64>(pending_payable_fingerprint.rowid);
             let last_paid = to_time_t(pending_payable_fingerprint.timestamp);
@@ -166,8 +242,8 @@ impl PayableDao for PayableDaoReal {
                 .build();
 
             self.big_int_db_processor.execute(Either::Left(self.conn.as_ref()), BigIntSqlConfig::new(
-                main_sql,
-                update_clause_with_compensated_overflow,
+                &main_sql,
+                &update_clause_with_compensated_overflow,
                 params))?;
 
             Ok(())
@@ -175,35 +251,279 @@ impl PayableDao for PayableDaoReal {
     }
 
     fn non_pending_payables(&self) -> Vec<PayableAccount> {
-        let sql = "\
-        select wallet_address, balance_high_b, balance_low_b, last_paid_timestamp from \
-        payable where pending_payable_rowid is null";
-        let mut stmt = self.conn.prepare(sql).expect("Internal error");
+        let sql = format!(
+            "select wallet_address, balance_high_b, balance_low_b, last_paid_timestamp from \
+             payable where pending_payable_rowid is null and chain = {ACTIVE_CHAIN}"
+        );
+        let mut stmt = self.conn.prepare(&sql).expect("Internal error");
+        stmt.query_map([], Self::create_non_pending_payable_account)
+            .expect("Database is corrupt")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    fn release_failed_payables(
+        &self,
+        pending_payable_rowids: &[u64],
+    ) -> Result<Vec<PayableAccount>, PayableDaoError> {
+        let sql = format!(
+            "update payable set pending_payable_rowid = null \
+             where pending_payable_rowid in ({}) and chain = {} \
+             returning wallet_address, balance_high_b, balance_low_b, last_paid_timestamp",
+            comma_joined_stringifiable(pending_payable_rowids, |rowid| rowid.to_string()),
+            ACTIVE_CHAIN
+        );
+        let mut stmt = self.conn.prepare(&sql).expect("Internal error");
+        let released = stmt
+            .query_map([], Self::create_non_pending_payable_account)
+            .map_err(|e| PayableDaoError::RusqliteError(e.to_string()))?
+            .collect::<Result<Vec<PayableAccount>, Error>>()
+            .map_err(|e| PayableDaoError::RusqliteError(e.to_string()))?;
+        Ok(released)
+    }
+
+    fn creditors_of_pending_payables(
+        &self,
+        pending_payable_rowids: &[u64],
+    ) -> HashMap<u64, Wallet> {
+        let sql = format!(
+            "select pending_payable_rowid, wallet_address from payable \
+             where pending_payable_rowid in ({}) and chain = {}",
+            comma_joined_stringifiable(pending_payable_rowids, |rowid| rowid.to_string()),
+            ACTIVE_CHAIN
+        );
+        let mut stmt = self.conn.prepare(&sql).expect("Internal error");
         stmt.query_map([], |row| {
-            let wallet_result: Result<Wallet, Error> = row.get(0);
-            let high_b_result: Result<i64, Error> = row.get(1);
-            let low_b_result: Result<i64, Error> = row.get(2);
-            let last_paid_timestamp_result = row.get(3);
-            match (
-                wallet_result,
-                high_b_result,
-                low_b_result,
-                last_paid_timestamp_result,
-            ) {
-                (Ok(wallet), Ok(high_b), Ok(low_b), Ok(last_paid_timestamp)) => {
-                    Ok(PayableAccount {
-                        wallet,
-                        balance_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
-                            high_b, low_b,
-                        )),
-                        last_paid_timestamp: utils::from_time_t(last_paid_timestamp),
-                        pending_payable_opt: None,
-                    })
-                }
-                _ => panic!("Database is corrupt: PAYABLE table columns and/or types"),
+            Ok((
+                checked_conversion::<i64, u64>(row.get::<usize, i64>(0)?),
+                row.get::<usize, Wallet>(1)?,
+            ))
+        })
+        .expect("rusqlite failure")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    fn upsert_creditor_thresholds(
+        &self,
+        creditor_wallet: &Wallet,
+        payment_thresholds: &PaymentThresholds,
+    ) -> Result<(), PayableDaoError> {
+        let values = [
+            payment_thresholds.debt_threshold_gwei,
+            payment_thresholds.maturity_threshold_sec,
+            payment_thresholds.payment_grace_period_sec,
+            payment_thresholds.permanent_debt_allowed_gwei,
+            payment_thresholds.threshold_interval_sec,
+            payment_thresholds.unban_below_gwei,
+        ]
+        .into_iter()
+        .map(|value| {
+            i64::try_from(value).map_err(|_| PayableDaoError::SignConversion(value as u128))
+        })
+        .collect::<Result<Vec<i64>, PayableDaoError>>()?;
+        let mut params: Vec<&dyn ToSql> = vec![creditor_wallet];
+        params.extend(values.iter().map(|value| value as &dyn ToSql));
+        match self
+            .conn
+            .prepare(
+                "insert or replace into creditor_thresholds (wallet_address, debt_threshold_gwei, \
+                 maturity_threshold_sec, payment_grace_period_sec, permanent_debt_allowed_gwei, \
+                 threshold_interval_sec, unban_below_gwei) values (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .expect("Internal error")
+            .execute(params.as_slice())
+        {
+            Ok(1) => Ok(()),
+            Ok(x) => panic!("expected 1 changed row but got {}", x),
+            Err(e) => Err(PayableDaoError::RusqliteError(e.to_string())),
+        }
+    }
+
+    fn creditor_thresholds(&self) -> HashMap<Wallet, PaymentThresholds> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "select wallet_address, debt_threshold_gwei, maturity_threshold_sec, \
+                 payment_grace_period_sec, permanent_debt_allowed_gwei, threshold_interval_sec, \
+                 unban_below_gwei from creditor_thresholds",
+            )
+            .expect("Internal error");
+        stmt.query_map([], |row| {
+            let value = |index: usize| {
+                row.get::<usize, i64>(index)
+                    .map(checked_conversion::<i64, u64>)
+            };
+            Ok((
+                row.get::<usize, Wallet>(0)?,
+                PaymentThresholds {
+                    debt_threshold_gwei: value(1)?,
+                    maturity_threshold_sec: value(2)?,
+                    payment_grace_period_sec: value(3)?,
+                    permanent_debt_allowed_gwei: value(4)?,
+                    threshold_interval_sec: value(5)?,
+                    unban_below_gwei: value(6)?,
+                },
+            ))
+        })
+        .expect("rusqlite failure")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    fn write_off(
+        &mut self,
+        wallet: &Wallet,
+        reason: &str,
+        ban: bool,
+        timestamp: SystemTime,
+    ) -> Result<u128, PayableDaoError> {
+        let txn = self.conn.transaction()?;
+        let payable_opt = txn
+            .prepare(&format!(
+                "select balance_high_b, balance_low_b, pending_payable_rowid from payable \
+                 where wallet_address = ? and chain = {ACTIVE_CHAIN}"
+            ))?
+            .query_row(&[wallet], |row| {
+                Ok((
+                    row.get::<usize, i64>(0)?,
+                    row.get::<usize, i64>(1)?,
+                    row.get::<usize, Option<i64>>(2)?,
+                ))
+            })
+            .optional()?;
+        let (balance_high_b, balance_low_b) = match payable_opt {
+            None => return Err(PayableDaoError::NoSuchPayable),
+            // Writing it off now would leave the confirmation of the payment without its payable
+            Some((_, _, Some(rowid))) => {
+                return Err(PayableDaoError::PaymentPending(checked_conversion(rowid)))
             }
+            Some((balance_high_b, balance_low_b, None)) => (balance_high_b, balance_low_b),
+        };
+        let timestamp = to_time_t(timestamp);
+        let write_off_params: &[&dyn ToSql] = &[
+            wallet,
+            &balance_high_b,
+            &balance_low_b,
+            &reason,
+            &ban,
+            &timestamp,
+        ];
+        txn.execute(
+            &format!(
+                "insert into payable_write_off (wallet_address, balance_high_b, balance_low_b, \
+                 reason, banned, timestamp, chain) values (?, ?, ?, ?, ?, ?, {ACTIVE_CHAIN})"
+            ),
+            write_off_params,
+        )?;
+        let wallet_params: &[&dyn ToSql] = &[wallet];
+        txn.execute(
+            &format!("delete from payable where wallet_address = ? and chain = {ACTIVE_CHAIN}"),
+            wallet_params,
+        )?;
+        if ban {
+            txn.execute(
+                "insert or ignore into banned (wallet_address) values (?)",
+                wallet_params,
+            )?;
+        }
+        txn.commit()?;
+        Ok(checked_conversion::<i128, u128>(
+            BigIntDivider::reconstitute(balance_high_b, balance_low_b),
+        ))
+    }
+
+    fn write_offs(&self, wallet: &Wallet) -> Vec<PayableWriteOff> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "select balance_high_b, balance_low_b, reason, banned, timestamp \
+                 from payable_write_off where wallet_address = ? and chain = {ACTIVE_CHAIN} \
+                 order by rowid"
+            ))
+            .expect("Couldn't prepare statement");
+        stmt.query_map(&[wallet], |row| {
+            Ok(PayableWriteOff {
+                wallet: wallet.clone(),
+                balance_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                    row.get(0)?,
+                    row.get(1)?,
+                )),
+                reason: row.get(2)?,
+                banned: row.get(3)?,
+                timestamp: utils::from_time_t(row.get(4)?),
+            })
         })
-        .expect("Database is corrupt")
+        .expect("Couldn't retrieve write-offs: database corruption")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    fn record_invoice_dispute(&self, dispute: &InvoiceDispute) -> Result<(), PayableDaoError> {
+        let service_count = |count: u64| {
+            sign_conversion::<u64, i64>(count)
+                .map_err(|count| PayableDaoError::SignConversion(count as u128))
+        };
+        let invoiced_services = service_count(dispute.invoiced_services)?;
+        let booked_services = service_count(dispute.booked_services)?;
+        let (invoiced_high_b, invoiced_low_b) = BigIntDivider::deconstruct(
+            sign_conversion::<u128, i128>(dispute.invoiced_wei)
+                .map_err(PayableDaoError::SignConversion)?,
+        );
+        let (booked_high_b, booked_low_b) = BigIntDivider::deconstruct(
+            sign_conversion::<u128, i128>(dispute.booked_wei)
+                .map_err(PayableDaoError::SignConversion)?,
+        );
+        let params: &[&dyn ToSql] = &[
+            &dispute.creditor_wallet,
+            &to_time_t(dispute.period_start),
+            &to_time_t(dispute.period_end),
+            &invoiced_services,
+            &booked_services,
+            &invoiced_high_b,
+            &invoiced_low_b,
+            &booked_high_b,
+            &booked_low_b,
+            &to_time_t(dispute.received_at),
+        ];
+        let sql = format!(
+            "insert into invoice_dispute (creditor_address, period_start, period_end, \
+             invoiced_services, booked_services, invoiced_high_b, invoiced_low_b, booked_high_b, \
+             booked_low_b, received_at, chain) \
+             values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, {ACTIVE_CHAIN})"
+        );
+        self.conn.prepare(&sql)?.execute(params)?;
+        Ok(())
+    }
+
+    fn invoice_disputes(&self) -> Vec<InvoiceDispute> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "select creditor_address, period_start, period_end, invoiced_services, \
+                 booked_services, invoiced_high_b, invoiced_low_b, booked_high_b, booked_low_b, \
+                 received_at from invoice_dispute where chain = {ACTIVE_CHAIN} order by rowid"
+            ))
+            .expect("Couldn't prepare statement");
+        stmt.query_map([], |row| {
+            Ok(InvoiceDispute {
+                creditor_wallet: row.get(0)?,
+                period_start: utils::from_time_t(row.get(1)?),
+                period_end: utils::from_time_t(row.get(2)?),
+                invoiced_services: checked_conversion::<i64, u64>(row.get(3)?),
+                booked_services: checked_conversion::<i64, u64>(row.get(4)?),
+                invoiced_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                    row.get(5)?,
+                    row.get(6)?,
+                )),
+                booked_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                    row.get(7)?,
+                    row.get(8)?,
+                )),
+                received_at: utils::from_time_t(row.get(9)?),
+            })
+        })
+        .expect("Couldn't retrieve invoice disputes: database corruption")
         .vigilant_flatten()
         .collect()
     }
@@ -211,7 +531,7 @@ impl PayableDao for PayableDaoReal {
     fn custom_query(&self, custom_query: CustomQuery<u64>) -> Option<Vec<PayableAccount>> {
         let variant_top = TopStmConfig{
             limit_clause: "limit :limit_count",
-            gwei_min_resolution_clause: "where (balance_high_b > 0) or ((balance_high_b = 0) and (balance_low_b >= 1000000000))",
+            gwei_min_resolution_clause: "where ((balance_high_b > 0) or ((balance_high_b = 0) and (balance_low_b >= 1000000000)))",
             age_ordering_clause: "last_paid_timestamp asc",
         };
         let variant_range = RangeStmConfig {
@@ -258,13 +578,22 @@ impl PayableDao for PayableDaoReal {
         })
     }
 
-    #[cfg(test)]
+    fn aging(&self, now: SystemTime) -> DebtAging {
+        debt_aging_from_table(
+            self.conn.as_ref(),
+            &Self::table_name(),
+            "last_paid_timestamp",
+            now,
+        )
+    }
+
     fn account_status(&self, wallet: &Wallet) -> Option<PayableAccount> {
-        let stm = "\
-            select balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid \
-            from payable \
-            where wallet_address = ?";
-        let mut stmt = self.conn.prepare(stm).unwrap();
+        let stm = format!(
+            "select balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid \
+             from payable \
+             where wallet_address = ? and chain = {ACTIVE_CHAIN}"
+        );
+        let mut stmt = self.conn.prepare(&stm).unwrap();
         stmt.query_row(&[&wallet], |row| {
             let high_bytes_result = row.get(0);
             let low_bytes_result = row.get(1);
@@ -286,7 +615,7 @@ impl PayableDao for PayableDaoReal {
                         pending_payable_opt: match rowid {
                             Some(rowid) => Some(PendingPayableId::new(
                                 u64::try_from(rowid).unwrap(),
-                                H256::from_uint(&U256::from(0)), //garbage
+                                H256::from_uint(&U256::from(0)),
                             )),
                             None => None,
                         },
@@ -311,7 +640,97 @@ impl PayableDaoReal {
         }
     }
 
-    fn create_payable_account(row: &Row) -> rusqlite::Result<PayableAccount> {
+    // Shares of the creditors paid together by the transaction of the fingerprint
+    fn creditor_shares(&self, pending_payable_rowid: u64) -> Vec<(Wallet, u128)> {
+        let sql = format!(
+            "select wallet_address, amount_high_b, amount_low_b from pending_payable_share \
+             where pending_payable_rowid = {}",
+            pending_payable_rowid
+        );
+        let mut stmt = self.conn.prepare(&sql).expect("Internal error");
+        stmt.query_map([], |row| {
+            let wallet: Wallet = row.get(0)?;
+            let high_b: i64 = row.get(1)?;
+            let low_b: i64 = row.get(2)?;
+            Ok((
+                wallet,
+                checked_conversion::<i128, u128>(BigIntDivider::reconstitute(high_b, low_b)),
+            ))
+        })
+        .expect("Database is corrupt")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    // The whole amount of the transaction would be far too much for each of its creditors
+    fn creditor_shares_confirmed(
+        &self,
+        pending_payable_fingerprint: &PendingPayableFingerprint,
+        creditor_shares: &[(Wallet, u128)],
+    ) -> Result<(), PayableDaoError> {
+        let main_sql = format!("update payable set \
+                balance_high_b = balance_high_b + :balance_high_b, balance_low_b = balance_low_b + :balance_low_b, \
+                last_paid_timestamp = :last_paid, pending_payable_rowid = null \
+                where wallet_address = :wallet and pending_payable_rowid = :rowid and chain = {ACTIVE_CHAIN}");
+        let update_clause_with_compensated_overflow = format!("update payable set \
+                balance_high_b = :balance_high_b, balance_low_b = :balance_low_b, last_paid_timestamp = :last_paid, \
+                pending_payable_rowid = null where wallet_address = :wallet and pending_payable_rowid = :rowid \
+                and chain = {ACTIVE_CHAIN}");
+
+        let i64_rowid = checked_conversion::<u64, i64>(pending_payable_fingerprint.rowid);
+        let last_paid = to_time_t(pending_payable_fingerprint.timestamp);
+        creditor_shares.iter().try_for_each(|(wallet, amount)| {
+            let params = SQLParamsBuilder::default()
+                .key(WalletAddress(wallet))
+                .wei_change(WeiChange::new(
+                    "balance",
+                    *amount,
+                    WeiChangeDirection::Subtraction,
+                ))
+                .other_params(vec![
+                    ParamByUse::BeforeAndAfterOverflow(DisplayableRusqliteParamPair::new(
+                        ":last_paid",
+                        &last_paid,
+                    )),
+                    ParamByUse::BeforeAndAfterOverflow(DisplayableRusqliteParamPair::new(
+                        ":rowid", &i64_rowid,
+                    )),
+                ])
+                .build();
+
+            self.big_int_db_processor.execute(
+                Either::Left(self.conn.as_ref()),
+                BigIntSqlConfig::new(&main_sql, &update_clause_with_compensated_overflow, params),
+            )?;
+
+            Ok(())
+        })
+    }
+
+    fn create_non_pending_payable_account(row: &Row) -> rusqlite::Result<PayableAccount> {
+        let wallet_result: Result<Wallet, Error> = row.get(0);
+        let high_b_result: Result<i64, Error> = row.get(1);
+        let low_b_result: Result<i64, Error> = row.get(2);
+        let last_paid_timestamp_result = row.get(3);
+        match (
+            wallet_result,
+            high_b_result,
+            low_b_result,
+            last_paid_timestamp_result,
+        ) {
+            (Ok(wallet), Ok(high_b), Ok(low_b), Ok(last_paid_timestamp)) => Ok(PayableAccount {
+                wallet,
+                balance_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                    high_b, low_b,
+                )),
+                last_paid_timestamp: utils::from_time_t(last_paid_timestamp),
+                pending_payable_opt: None,
+            }),
+            _ => panic!("Database is corrupt: PAYABLE table columns and/or types"),
+        }
+    }
+
+    pub(crate) fn create_payable_account(row: &Row) -> rusqlite::Result<PayableAccount> {
         let wallet_result: Result<Wallet, Error> = row.get(0);
         let balance_high_bytes_result = row.get(1);
         let balance_low_bytes_result = row.get(2);
@@ -369,13 +788,14 @@ impl PayableDaoReal {
                payable
            left join pending_payable on
                pending_payable.rowid = payable.pending_payable_rowid
-           {} {}
+           {} {} and payable.chain = {}
            order by
                {},
                {}
            {}",
             feeder.main_where_clause,
             feeder.where_clause_extension,
+            ACTIVE_CHAIN,
             feeder.order_by_first_param,
             feeder.order_by_second_param,
             feeder.limit_clause
@@ -387,13 +807,17 @@ impl TableNameDAO for PayableDaoReal {
     fn table_name() -> String {
         String::from("payable")
     }
+
+    fn row_scope_opt() -> Option<String> {
+        Some(format!("chain = {ACTIVE_CHAIN}"))
+    }
 }
 
 mod mark_pending_payable_associated_functions {
     use crate::accountant::comma_joined_stringifiable;
     use crate::accountant::db_access_objects::payable_dao::PayableDaoError;
     use crate::accountant::db_access_objects::utils::{
-        update_rows_and_return_valid_count, VigilantRusqliteFlatten,
+        update_rows_and_return_valid_count, VigilantRusqliteFlatten, ACTIVE_CHAIN,
     };
     use crate::database::rusqlite_wrappers::ConnectionWrapper;
     use crate::sub_lib::wallet::Wallet;
@@ -505,8 +929,10 @@ mod mark_pending_payable_associated_functions {
     ) -> Vec<(Wallet, Option<u64>)> {
         let select_dealt_accounts =
             format!(
-                "select wallet_address, pending_payable_rowid from payable where wallet_address in ({})",
-                serialize_wallets(wallets_and_rowids, Some('\''))
+                "select wallet_address, pending_payable_rowid from payable where wallet_address in ({}) \
+                 and chain = {}",
+                serialize_wallets(wallets_and_rowids, Some('\'')),
+                ACTIVE_CHAIN
             );
         let row_processor = |row: &Row| {
             Ok((
@@ -541,10 +967,17 @@ mod mark_pending_payable_associated_functions {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::accountant::db_access_objects::utils::{from_time_t, now_time_t, to_time_t};
+    use crate::accountant::db_access_objects::utils::{
+        from_time_t, now_time_t, to_time_t, AgingBucket,
+    };
     use crate::accountant::gwei_to_wei;
     use crate::accountant::db_access_objects::payable_dao::mark_pending_payable_associated_functions::explanatory_extension;
-    use crate::accountant::test_utils::{assert_account_creation_fn_fails_on_finding_wrong_columns_and_value_types, make_pending_payable_fingerprint, trick_rusqlite_with_read_only_conn};
+    use crate::accountant::db_access_objects::pending_payable_dao::{
+        PendingPayableDao, PendingPayableDaoReal,
+    };
+    use crate::blockchain::blockchain_bridge::{CreditorShare, ReplacementBasis};
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
+    use crate::accountant::test_utils::{assert_account_creation_fn_fails_on_finding_wrong_columns_and_value_types, make_custom_payment_thresholds, make_pending_payable_fingerprint, trick_rusqlite_with_read_only_conn};
     use crate::blockchain::test_utils::make_tx_hash;
     use crate::database::rusqlite_wrappers::ConnectionWrapperReal;
     use crate::database::db_initializer::{
@@ -580,6 +1013,51 @@ mod tests {
         assert_eq!(to_time_t(status.last_paid_timestamp), to_time_t(now));
     }
 
+    #[test]
+    fn more_money_payable_leaves_the_account_of_the_same_wallet_on_another_chain_alone() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "more_money_payable_leaves_the_account_of_the_same_wallet_on_another_chain_alone",
+        );
+        let now = SystemTime::now();
+        let wallet = make_wallet("booga");
+        let boxed_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        boxed_conn
+            .prepare(
+                "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+                 last_paid_timestamp, pending_payable_rowid, chain) values (?, 0, 5000, \
+                 1500000000, null, 'another-chain')",
+            )
+            .unwrap()
+            .execute([wallet.to_string()])
+            .unwrap();
+        let subject = PayableDaoReal::new(boxed_conn);
+
+        subject.more_money_payable(now, &wallet, 1234).unwrap();
+        subject.more_money_payable(now, &wallet, 1000).unwrap();
+
+        let status = subject.account_status(&wallet).unwrap();
+        assert_eq!(status.balance_wei, 2234);
+        let account_on_another_chain = subject
+            .conn
+            .prepare(
+                "select balance_high_b, balance_low_b, last_paid_timestamp from payable \
+                 where wallet_address = ? and chain = 'another-chain'",
+            )
+            .unwrap()
+            .query_row([wallet.to_string()], |row| {
+                Ok((
+                    row.get::<usize, i64>(0)?,
+                    row.get::<usize, i64>(1)?,
+                    row.get::<usize, i64>(2)?,
+                ))
+            })
+            .unwrap();
+        assert_eq!(account_on_another_chain, (0, 5000, 1500000000));
+    }
+
     #[test]
     fn more_money_payable_works_for_existing_address_without_overflow() {
         //asserting on correctness of the main sql clause
@@ -714,9 +1192,12 @@ mod tests {
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
         {
-            let insert = "insert into payable (wallet_address, balance_high_b, balance_low_b, \
-             last_paid_timestamp) values (?, ?, ?, ?), (?, ?, ?, ?), (?, ?, ?, ?)";
-            let mut stm = boxed_conn.prepare(insert).unwrap();
+            let insert = format!(
+                "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+                 last_paid_timestamp, chain) values (?, ?, ?, ?, {ACTIVE_CHAIN}), \
+                 (?, ?, ?, ?, {ACTIVE_CHAIN}), (?, ?, ?, ?, {ACTIVE_CHAIN})"
+            );
+            let mut stm = boxed_conn.prepare(&insert).unwrap();
             let params = [
                 [&wallet_0 as &dyn ToSql, &12345, &1, &45678],
                 [&wallet_1, &0, &i64::MAX, &150_000_000],
@@ -937,6 +1418,7 @@ mod tests {
             attempt: 1,
             amount: balance_change_1,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let fingerprint_2 = PendingPayableFingerprint {
             rowid: rowid_2,
@@ -945,6 +1427,7 @@ mod tests {
             attempt: 1,
             amount: balance_change_2,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let previous_timestamp_1 = from_time_t(previous_timestamp_1_s);
         let previous_timestamp_2 = from_time_t(previous_timestamp_2_s);
@@ -1052,6 +1535,89 @@ mod tests {
         assert_eq!(resulting_account_2_opt, Some(expected_resulting_status_2))
     }
 
+    #[test]
+    fn transactions_confirmed_debits_each_creditor_paid_by_one_transaction_with_its_own_share() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "transactions_confirmed_debits_each_creditor_paid_by_one_transaction_with_its_own_share",
+        );
+        let make_conn = || {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap()
+        };
+        let pending_payable_dao = PendingPayableDaoReal::new(make_conn());
+        let subject = PayableDaoReal::new(make_conn());
+        let hash = make_tx_hash(0x2d5);
+        let wallet_1 = make_wallet("creditor_1");
+        let wallet_2 = make_wallet("creditor_2");
+        let previous_timestamp = from_time_t(180_000_000);
+        subject
+            .more_money_payable(previous_timestamp, &wallet_1, 1_500_000)
+            .unwrap();
+        subject
+            .more_money_payable(previous_timestamp, &wallet_2, 2_500_000)
+            .unwrap();
+        pending_payable_dao
+            .insert_new_fingerprints(
+                &[HashAndAmount {
+                    hash,
+                    amount: 3_000_000,
+                }],
+                from_time_t(190_000_000),
+                ReplacementBasis {
+                    nonce: 4,
+                    gas_price_wei: 1_000_000_000,
+                },
+            )
+            .unwrap();
+        pending_payable_dao
+            .insert_creditor_shares(&[
+                CreditorShare {
+                    hash,
+                    wallet: wallet_1.clone(),
+                    amount: 1_000_000,
+                },
+                CreditorShare {
+                    hash,
+                    wallet: wallet_2.clone(),
+                    amount: 2_000_000,
+                },
+            ])
+            .unwrap();
+        let fingerprint = pending_payable_dao
+            .return_all_errorless_fingerprints()
+            .remove(0);
+        subject
+            .mark_pending_payables_rowids(&[
+                (&wallet_1, fingerprint.rowid),
+                (&wallet_2, fingerprint.rowid),
+            ])
+            .unwrap();
+
+        let result = subject.transactions_confirmed(&[fingerprint.clone()]);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            subject.account_status(&wallet_1),
+            Some(PayableAccount {
+                wallet: wallet_1,
+                balance_wei: 500_000,
+                last_paid_timestamp: fingerprint.timestamp,
+                pending_payable_opt: None,
+            })
+        );
+        assert_eq!(
+            subject.account_status(&wallet_2),
+            Some(PayableAccount {
+                wallet: wallet_2,
+                balance_wei: 500_000,
+                last_paid_timestamp: fingerprint.timestamp,
+                pending_payable_opt: None,
+            })
+        );
+    }
+
     #[test]
     fn transaction_confirmed_works_for_generic_sql_error() {
         let home_dir = ensure_node_home_directory_exists(
@@ -1217,28 +1783,688 @@ mod tests {
     }
 
     #[test]
-    fn custom_query_handles_empty_table_in_top_records_mode() {
-        let main_test_setup = |_conn: &dyn ConnectionWrapper, _insert: InsertPayableHelperFn| {};
-        let subject = custom_query_test_body_for_payable(
-            "custom_query_handles_empty_table_in_top_records_mode",
-            main_test_setup,
+    fn creditor_thresholds_can_be_upserted_and_read() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "creditor_thresholds_can_be_upserted_and_read",
+        );
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
         );
+        let wallet_1 = make_wallet("creditor_1");
+        let wallet_2 = make_wallet("creditor_2");
+        let first_thresholds = make_custom_payment_thresholds();
+        let second_thresholds = PaymentThresholds {
+            debt_threshold_gwei: i64::MAX as u64,
+            ..PaymentThresholds::default()
+        };
+        let nothing_at_start = subject.creditor_thresholds();
 
-        let result = subject.custom_query(CustomQuery::TopRecords {
-            count: 6,
-            ordered_by: Balance,
-        });
+        subject
+            .upsert_creditor_thresholds(&wallet_1, &first_thresholds)
+            .unwrap();
+        subject
+            .upsert_creditor_thresholds(&wallet_2, &first_thresholds)
+            .unwrap();
+        subject
+            .upsert_creditor_thresholds(&wallet_2, &second_thresholds)
+            .unwrap();
 
-        assert_eq!(result, None)
+        assert_eq!(nothing_at_start, HashMap::new());
+        assert_eq!(
+            subject.creditor_thresholds(),
+            HashMap::from_iter(vec![
+                (wallet_1, first_thresholds),
+                (wallet_2, second_thresholds)
+            ])
+        );
     }
 
-    type InsertPayableHelperFn<'b> =
-        &'b dyn for<'a> Fn(&'a dyn ConnectionWrapper, &'a str, i128, i64, Option<i64>);
-
-    fn insert_payable_record_fn(
-        conn: &dyn ConnectionWrapper,
-        wallet: &str,
-        balance: i128,
+    #[test]
+    fn write_off_moves_payables_to_the_write_off_table_and_bans_on_request() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "write_off_moves_payables_to_the_write_off_table_and_bans_on_request",
+        );
+        let mut subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let now = SystemTime::now();
+        let wallet_1 = make_wallet("creditor_1");
+        let wallet_2 = make_wallet("creditor_2");
+        let wallet_3 = make_wallet("creditor_3");
+        subject.more_money_payable(now, &wallet_1, 1_111).unwrap();
+        subject.more_money_payable(now, &wallet_2, 2_222).unwrap();
+        subject.more_money_payable(now, &wallet_3, 3_333).unwrap();
+
+        let result_1 = subject.write_off(&wallet_1, "Gone for good", true, now);
+        let result_2 = subject.write_off(&wallet_2, "Settled off-chain", false, now);
+
+        assert_eq!(result_1, Ok(1_111));
+        assert_eq!(result_2, Ok(2_222));
+        assert_eq!(subject.account_status(&wallet_1), None);
+        assert_eq!(subject.account_status(&wallet_2), None);
+        assert_eq!(
+            subject.account_status(&wallet_3).unwrap().balance_wei,
+            3_333
+        );
+        let mut stmt = subject
+            .conn
+            .prepare(
+                "select wallet_address, balance_high_b, balance_low_b, reason, banned, timestamp \
+                 from payable_write_off order by rowid",
+            )
+            .unwrap();
+        let write_offs = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<usize, Wallet>(0)?,
+                    row.get::<usize, i64>(1)?,
+                    row.get::<usize, i64>(2)?,
+                    row.get::<usize, String>(3)?,
+                    row.get::<usize, bool>(4)?,
+                    row.get::<usize, i64>(5)?,
+                ))
+            })
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            write_offs,
+            vec![
+                (
+                    wallet_1.clone(),
+                    0,
+                    1_111,
+                    "Gone for good".to_string(),
+                    true,
+                    to_time_t(now)
+                ),
+                (
+                    wallet_2,
+                    0,
+                    2_222,
+                    "Settled off-chain".to_string(),
+                    false,
+                    to_time_t(now)
+                ),
+            ]
+        );
+        let mut stmt = subject
+            .conn
+            .prepare("select wallet_address from banned")
+            .unwrap();
+        let banned = stmt
+            .query_map([], |row| row.get::<usize, Wallet>(0))
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect::<Vec<Wallet>>();
+        assert_eq!(banned, vec![wallet_1]);
+    }
+
+    #[test]
+    fn write_off_refuses_creditor_without_payable() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "write_off_refuses_creditor_without_payable",
+        );
+        let mut subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+
+        let result = subject.write_off(
+            &make_wallet("stranger"),
+            "Whatever",
+            true,
+            SystemTime::now(),
+        );
+
+        assert_eq!(result, Err(PayableDaoError::NoSuchPayable));
+        let write_off_count = subject
+            .conn
+            .prepare("select count(*) from payable_write_off")
+            .unwrap()
+            .query_row([], |row| row.get::<usize, i64>(0))
+            .unwrap();
+        assert_eq!(write_off_count, 0);
+    }
+
+    #[test]
+    fn write_off_refuses_payable_awaiting_payment_confirmation() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "write_off_refuses_payable_awaiting_payment_confirmation",
+        );
+        let mut subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let wallet = make_wallet("creditor");
+        subject
+            .more_money_payable(SystemTime::now(), &wallet, 1_234)
+            .unwrap();
+        subject
+            .mark_pending_payables_rowids(&[(&wallet, 45)])
+            .unwrap();
+
+        let result = subject.write_off(&wallet, "Whatever", false, SystemTime::now());
+
+        assert_eq!(result, Err(PayableDaoError::PaymentPending(45)));
+        assert_eq!(subject.account_status(&wallet).unwrap().balance_wei, 1_234);
+    }
+
+    #[test]
+    fn write_offs_returns_those_of_the_given_creditor_oldest_first() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "write_offs_returns_those_of_the_given_creditor_oldest_first",
+        );
+        let mut subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let earlier = from_time_t(1_000_000);
+        let later = from_time_t(2_000_000);
+        let wallet = make_wallet("creditor");
+        let other_wallet = make_wallet("other_creditor");
+        subject.more_money_payable(earlier, &wallet, 1_111).unwrap();
+        subject
+            .write_off(&wallet, "Vanished", false, earlier)
+            .unwrap();
+        subject
+            .more_money_payable(earlier, &other_wallet, 2_222)
+            .unwrap();
+        subject
+            .write_off(&other_wallet, "Irrelevant", false, earlier)
+            .unwrap();
+        subject.more_money_payable(later, &wallet, 3_333).unwrap();
+        subject
+            .write_off(&wallet, "Vanished again", true, later)
+            .unwrap();
+
+        let result = subject.write_offs(&wallet);
+
+        assert_eq!(
+            result,
+            vec![
+                PayableWriteOff {
+                    wallet: wallet.clone(),
+                    balance_wei: 1_111,
+                    reason: "Vanished".to_string(),
+                    banned: false,
+                    timestamp: earlier,
+                },
+                PayableWriteOff {
+                    wallet: wallet.clone(),
+                    balance_wei: 3_333,
+                    reason: "Vanished again".to_string(),
+                    banned: true,
+                    timestamp: later,
+                },
+            ]
+        );
+        assert_eq!(subject.write_offs(&make_wallet("stranger")), vec![]);
+    }
+
+    fn make_invoice_dispute(creditor: &str, received_at: i64) -> InvoiceDispute {
+        InvoiceDispute {
+            creditor_wallet: make_wallet(creditor),
+            period_start: from_time_t(received_at - 3_600),
+            period_end: from_time_t(received_at - 60),
+            invoiced_services: 15,
+            booked_services: 12,
+            invoiced_wei: u128::MAX >> 2,
+            booked_wei: 4_567_000_000_000,
+            received_at: from_time_t(received_at),
+        }
+    }
+
+    #[test]
+    fn invoice_disputes_are_recorded_and_returned_oldest_first() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "invoice_disputes_are_recorded_and_returned_oldest_first",
+        );
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let first = make_invoice_dispute("creditor", 1_000_000);
+        let second = make_invoice_dispute("other_creditor", 2_000_000);
+        let initial = subject.invoice_disputes();
+
+        subject.record_invoice_dispute(&first).unwrap();
+        subject.record_invoice_dispute(&second).unwrap();
+
+        assert_eq!(initial, vec![]);
+        assert_eq!(subject.invoice_disputes(), vec![first, second]);
+    }
+
+    #[test]
+    fn record_invoice_dispute_refuses_values_too_big_for_the_database() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "record_invoice_dispute_refuses_values_too_big_for_the_database",
+        );
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let too_many_services = InvoiceDispute {
+            invoiced_services: u64::MAX,
+            ..make_invoice_dispute("creditor", 1_000_000)
+        };
+        let too_much_wei = InvoiceDispute {
+            invoiced_wei: u128::MAX,
+            ..make_invoice_dispute("creditor", 1_000_000)
+        };
+
+        let services_result = subject.record_invoice_dispute(&too_many_services);
+        let wei_result = subject.record_invoice_dispute(&too_much_wei);
+
+        assert_eq!(
+            services_result,
+            Err(PayableDaoError::SignConversion(u64::MAX as u128))
+        );
+        assert_eq!(wei_result, Err(PayableDaoError::SignConversion(u128::MAX)));
+        assert_eq!(subject.invoice_disputes(), vec![]);
+    }
+
+    #[test]
+    fn upsert_creditor_thresholds_refuses_values_too_big_for_the_database() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "upsert_creditor_thresholds_refuses_values_too_big_for_the_database",
+        );
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let payment_thresholds = PaymentThresholds {
+            unban_below_gwei: u64::MAX,
+            ..PaymentThresholds::default()
+        };
+
+        let result =
+            subject.upsert_creditor_thresholds(&make_wallet("creditor"), &payment_thresholds);
+
+        assert_eq!(
+            result,
+            Err(PayableDaoError::SignConversion(u64::MAX as u128))
+        );
+        assert_eq!(subject.creditor_thresholds(), HashMap::new());
+    }
+
+    #[test]
+    fn upsert_creditor_thresholds_handles_error() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "upsert_creditor_thresholds_handles_error",
+        );
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let subject = PayableDaoReal::new(Box::new(ConnectionWrapperReal::new(conn_read_only)));
+
+        let result = subject.upsert_creditor_thresholds(
+            &make_wallet("creditor"),
+            &make_custom_payment_thresholds(),
+        );
+
+        assert_eq!(
+            result,
+            Err(PayableDaoError::RusqliteError(
+                "attempt to write a readonly database".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn release_failed_payables_detaches_payables_from_the_given_transactions() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "release_failed_payables_detaches_payables_from_the_given_transactions",
+        );
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let mut flags = OpenFlags::empty();
+        flags.insert(OpenFlags::SQLITE_OPEN_READ_WRITE);
+        let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
+        let conn = ConnectionWrapperReal::new(conn);
+        let insert = |wallet: &Wallet, balance: i128, pending_payable_rowid: Option<i64>| {
+            insert_payable_record_fn(
+                &conn,
+                &wallet.to_string(),
+                balance,
+                111_111_111,
+                pending_payable_rowid,
+            );
+        };
+        let wallet_1 = make_wallet("foobar");
+        let wallet_2 = make_wallet("barfoo");
+        let wallet_3 = make_wallet("bazbar");
+        insert(&wallet_1, 1_234_567_890, Some(15));
+        insert(&wallet_2, 9_876_543_210, Some(16));
+        insert(&wallet_3, 5_555_555_555, Some(17));
+
+        let mut result = subject.release_failed_payables(&[15, 17]).unwrap();
+
+        result.sort_by(|a, b| a.balance_wei.cmp(&b.balance_wei));
+        assert_eq!(
+            result,
+            vec![
+                PayableAccount {
+                    wallet: wallet_1.clone(),
+                    balance_wei: 1_234_567_890,
+                    last_paid_timestamp: from_time_t(111_111_111),
+                    pending_payable_opt: None
+                },
+                PayableAccount {
+                    wallet: wallet_3.clone(),
+                    balance_wei: 5_555_555_555,
+                    last_paid_timestamp: from_time_t(111_111_111),
+                    pending_payable_opt: None
+                },
+            ]
+        );
+        assert_eq!(
+            subject
+                .account_status(&wallet_1)
+                .unwrap()
+                .pending_payable_opt,
+            None
+        );
+        assert_eq!(
+            subject
+                .account_status(&wallet_2)
+                .unwrap()
+                .pending_payable_opt
+                .unwrap()
+                .rowid,
+            16
+        );
+        assert_eq!(
+            subject
+                .account_status(&wallet_3)
+                .unwrap()
+                .pending_payable_opt,
+            None
+        );
+    }
+
+    #[test]
+    fn creditors_of_pending_payables_finds_the_wallets_awaiting_the_given_transactions() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "creditors_of_pending_payables_finds_the_wallets_awaiting_the_given_transactions",
+        );
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let mut flags = OpenFlags::empty();
+        flags.insert(OpenFlags::SQLITE_OPEN_READ_WRITE);
+        let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
+        let conn = ConnectionWrapperReal::new(conn);
+        let wallet_1 = make_wallet("foobar");
+        let wallet_2 = make_wallet("barfoo");
+        let wallet_3 = make_wallet("bazbar");
+        let wallet_4 = make_wallet("foobaz");
+        insert_payable_record_fn(&conn, &wallet_1.to_string(), 1_234, 111_111_111, Some(15));
+        insert_payable_record_fn(&conn, &wallet_2.to_string(), 5_678, 111_111_111, Some(16));
+        insert_payable_record_fn(&conn, &wallet_3.to_string(), 9_012, 111_111_111, Some(17));
+        insert_payable_record_fn(&conn, &wallet_4.to_string(), 3_456, 111_111_111, None);
+
+        let result = subject.creditors_of_pending_payables(&[15, 17, 18]);
+
+        assert_eq!(result, HashMap::from([(15, wallet_1), (17, wallet_3)]));
+    }
+
+    fn insert_payable_record_on_another_chain(
+        conn: &dyn ConnectionWrapper,
+        wallet: &Wallet,
+        balance: i128,
+        pending_payable_rowid: Option<i64>,
+    ) {
+        let (high_bytes, low_bytes) = BigIntDivider::deconstruct(balance);
+        let params: &[&dyn ToSql] = &[wallet, &high_bytes, &low_bytes, &pending_payable_rowid];
+        conn.prepare(
+            "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+             last_paid_timestamp, pending_payable_rowid, chain) values (?, ?, ?, 111111111, ?, \
+             'another-chain')",
+        )
+        .unwrap()
+        .execute(params)
+        .unwrap();
+    }
+
+    fn payable_record_on_another_chain(
+        conn: &dyn ConnectionWrapper,
+        wallet: &Wallet,
+    ) -> (i128, i64, Option<i64>) {
+        conn.prepare(
+            "select balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid \
+             from payable where wallet_address = ? and chain = 'another-chain'",
+        )
+        .unwrap()
+        .query_row([wallet], |row| {
+            Ok((
+                BigIntDivider::reconstitute(row.get(0)?, row.get(1)?),
+                row.get(2)?,
+                row.get(3)?,
+            ))
+        })
+        .unwrap()
+    }
+
+    fn payable_dao_with_extra_connection(
+        test_name: &str,
+    ) -> (PayableDaoReal, Box<dyn ConnectionWrapper>) {
+        let home_dir = ensure_node_home_directory_exists("payable_dao", test_name);
+        let subject = PayableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let mut flags = OpenFlags::empty();
+        flags.insert(OpenFlags::SQLITE_OPEN_READ_WRITE);
+        let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
+        (subject, Box::new(ConnectionWrapperReal::new(conn)))
+    }
+
+    #[test]
+    fn mark_pending_payables_rowids_leaves_the_same_wallet_on_another_chain_alone() {
+        let (subject, conn) = payable_dao_with_extra_connection(
+            "mark_pending_payables_rowids_leaves_the_same_wallet_on_another_chain_alone",
+        );
+        let wallet = make_wallet("booga");
+        insert_payable_record_on_another_chain(conn.as_ref(), &wallet, 5_000, None);
+        insert_payable_record_fn(conn.as_ref(), &wallet.to_string(), 1_234, 111_111_111, None);
+
+        let result = subject.mark_pending_payables_rowids(&[(&wallet, 456)]);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            subject
+                .account_status(&wallet)
+                .unwrap()
+                .pending_payable_opt
+                .unwrap()
+                .rowid,
+            456
+        );
+        assert_eq!(
+            payable_record_on_another_chain(conn.as_ref(), &wallet),
+            (5_000, 111_111_111, None)
+        );
+    }
+
+    #[test]
+    fn account_status_ignores_the_same_wallet_on_another_chain() {
+        let (subject, conn) = payable_dao_with_extra_connection(
+            "account_status_ignores_the_same_wallet_on_another_chain",
+        );
+        let wallet = make_wallet("booga");
+        insert_payable_record_on_another_chain(conn.as_ref(), &wallet, 5_000, Some(15));
+
+        let status_before = subject.account_status(&wallet);
+        insert_payable_record_fn(conn.as_ref(), &wallet.to_string(), 1_234, 222_222_222, None);
+        let status_after = subject.account_status(&wallet);
+
+        assert_eq!(status_before, None);
+        assert_eq!(
+            status_after,
+            Some(PayableAccount {
+                wallet,
+                balance_wei: 1_234,
+                last_paid_timestamp: from_time_t(222_222_222),
+                pending_payable_opt: None
+            })
+        );
+    }
+
+    #[test]
+    fn creditors_of_pending_payables_ignores_transactions_on_another_chain() {
+        let (subject, conn) = payable_dao_with_extra_connection(
+            "creditors_of_pending_payables_ignores_transactions_on_another_chain",
+        );
+        let wallet_1 = make_wallet("foobar");
+        let wallet_2 = make_wallet("barfoo");
+        insert_payable_record_on_another_chain(conn.as_ref(), &wallet_1, 5_000, Some(15));
+        insert_payable_record_on_another_chain(conn.as_ref(), &wallet_2, 6_000, Some(16));
+        insert_payable_record_fn(
+            conn.as_ref(),
+            &wallet_2.to_string(),
+            1_234,
+            111_111_111,
+            Some(15),
+        );
+
+        let result = subject.creditors_of_pending_payables(&[15, 16]);
+
+        assert_eq!(result, HashMap::from([(15, wallet_2)]));
+    }
+
+    #[test]
+    fn release_failed_payables_leaves_transactions_on_another_chain_alone() {
+        let (subject, conn) = payable_dao_with_extra_connection(
+            "release_failed_payables_leaves_transactions_on_another_chain_alone",
+        );
+        let wallet = make_wallet("booga");
+        insert_payable_record_on_another_chain(conn.as_ref(), &wallet, 5_000, Some(15));
+        insert_payable_record_fn(
+            conn.as_ref(),
+            &wallet.to_string(),
+            1_234,
+            111_111_111,
+            Some(15),
+        );
+
+        let result = subject.release_failed_payables(&[15]).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PayableAccount {
+                wallet: wallet.clone(),
+                balance_wei: 1_234,
+                last_paid_timestamp: from_time_t(111_111_111),
+                pending_payable_opt: None
+            }]
+        );
+        assert_eq!(
+            payable_record_on_another_chain(conn.as_ref(), &wallet),
+            (5_000, 111_111_111, Some(15))
+        );
+    }
+
+    #[test]
+    fn transactions_confirmed_hitting_overflow_leaves_the_same_wallet_on_another_chain_alone() {
+        let (subject, conn) = payable_dao_with_extra_connection(
+            "transactions_confirmed_hitting_overflow_leaves_the_same_wallet_on_another_chain_alone",
+        );
+        let wallet = make_wallet("booga");
+        //seeded first so that a select unaware of the chain would read this row
+        insert_payable_record_on_another_chain(conn.as_ref(), &wallet, 5_000, Some(789));
+        insert_payable_record_fn(
+            conn.as_ref(),
+            &wallet.to_string(),
+            10_000,
+            111_111_111,
+            Some(789),
+        );
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 789,
+            timestamp: from_time_t(199_000_000),
+            hash: make_tx_hash(12345),
+            attempt: 1,
+            amount: 111,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+
+        let result = subject.transactions_confirmed(&[fingerprint]);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            subject.account_status(&wallet),
+            Some(PayableAccount {
+                wallet: wallet.clone(),
+                balance_wei: 10_000 - 111,
+                last_paid_timestamp: from_time_t(199_000_000),
+                pending_payable_opt: None
+            })
+        );
+        assert_eq!(
+            payable_record_on_another_chain(conn.as_ref(), &wallet),
+            (5_000, 111_111_111, Some(789))
+        );
+    }
+
+    #[test]
+    fn custom_query_handles_empty_table_in_top_records_mode() {
+        let main_test_setup = |_conn: &dyn ConnectionWrapper, _insert: InsertPayableHelperFn| {};
+        let subject = custom_query_test_body_for_payable(
+            "custom_query_handles_empty_table_in_top_records_mode",
+            main_test_setup,
+        );
+
+        let result = subject.custom_query(CustomQuery::TopRecords {
+            count: 6,
+            ordered_by: Balance,
+        });
+
+        assert_eq!(result, None)
+    }
+
+    type InsertPayableHelperFn<'b> =
+        &'b dyn for<'a> Fn(&'a dyn ConnectionWrapper, &'a str, i128, i64, Option<i64>);
+
+    fn insert_payable_record_fn(
+        conn: &dyn ConnectionWrapper,
+        wallet: &str,
+        balance: i128,
         timestamp: i64,
         pending_payable_rowid: Option<i64>,
     ) {
@@ -1251,7 +2477,7 @@ mod tests {
             &pending_payable_rowid,
         ];
         conn
-            .prepare("insert into payable (wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid) values (?, ?, ?, ?, ?)")
+            .prepare(&format!("insert into payable (wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid, chain) values (?, ?, ?, ?, ?, {ACTIVE_CHAIN})"))
             .unwrap()
             .execute(params)
             .unwrap();
@@ -1606,6 +2832,69 @@ mod tests {
         assert_eq!(total, 4_000_123_123)
     }
 
+    #[test]
+    fn aging_sorts_payables_by_time_since_last_paid() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "aging_sorts_payables_by_time_since_last_paid",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let now = utils::now_time_t();
+        let day = 86_400;
+        insert_payable_record_fn(
+            &*conn,
+            "0x1111111111111111111111111111111111111111",
+            1_000,
+            now - 1_000,
+            None,
+        );
+        insert_payable_record_fn(
+            &*conn,
+            "0x2222222222222222222222222222222222222222",
+            2_000,
+            now - day - 1,
+            Some(3),
+        );
+        insert_payable_record_fn(
+            &*conn,
+            "0x3333333333333333333333333333333333333333",
+            3_000,
+            now - 2 * day,
+            None,
+        );
+        insert_payable_record_fn(
+            &*conn,
+            "0x4444444444444444444444444444444444444444",
+            4_000,
+            now - 30 * day,
+            None,
+        );
+        let subject = PayableDaoReal::new(conn);
+
+        let result = subject.aging(from_time_t(now));
+
+        assert_eq!(
+            result.buckets,
+            [
+                AgingBucket {
+                    accounts: 1,
+                    balance_wei: 1_000
+                },
+                AgingBucket {
+                    accounts: 2,
+                    balance_wei: 5_000
+                },
+                AgingBucket::default(),
+                AgingBucket {
+                    accounts: 1,
+                    balance_wei: 4_000
+                },
+            ]
+        )
+    }
+
     #[test]
     #[should_panic(
         expected = "database corrupted: found negative value -999999 in payable table for row id 2"
@@ -1665,7 +2954,10 @@ mod tests {
     }
 
     fn payable_read_only_conn(path: &Path) -> Connection {
-        trick_rusqlite_with_read_only_conn(path, DbInitializerReal::create_payable_table)
+        trick_rusqlite_with_read_only_conn(path, |conn| {
+            DbInitializerReal::create_config_table(conn);
+            DbInitializerReal::create_payable_table(conn)
+        })
     }
 
     fn custom_query_test_body_for_payable<F>(test_name: &str, main_setup_fn: F) -> PayableDaoReal
@@ -1686,7 +2978,7 @@ mod tests {
             &1,
         ];
         conn
-            .prepare("insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt) values (?,?,?,?,?)")
+            .prepare(&format!("insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, chain) values (?,?,?,?,?,{ACTIVE_CHAIN})"))
             .unwrap()
             .execute(pending_payable_account)
             .unwrap();
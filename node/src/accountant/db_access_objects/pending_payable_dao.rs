@@ -1,22 +1,28 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::accountant::db_access_objects::utils::{
-    from_time_t, to_time_t, DaoFactoryReal, VigilantRusqliteFlatten,
+    from_time_t, to_time_t, DaoFactoryReal, VigilantRusqliteFlatten, ACTIVE_CHAIN,
 };
 use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
 use crate::accountant::{checked_conversion, comma_joined_stringifiable};
-use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+use crate::blockchain::blockchain_bridge::{
+    CreditorShare, PendingPayableFingerprint, ReplacementBasis,
+};
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
 use crate::database::rusqlite_wrappers::ConnectionWrapper;
 use crate::sub_lib::wallet::Wallet;
+use itertools::Itertools;
 use masq_lib::utils::ExpectValue;
-use rusqlite::Row;
+use rusqlite::{Row, ToSql};
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::str::FromStr;
 use std::time::SystemTime;
 use web3::types::H256;
 
+// Starts the process error of a fingerprint whose transaction was given up on while pending
+pub const ABANDONED_MARK: &str = "ABANDONED";
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum PendingPayableDaoError {
     InsertionFailed(String),
@@ -37,14 +43,36 @@ pub trait PendingPayableDao {
     // Note that the order of the returned results is not guaranteed
     fn fingerprints_rowids(&self, hashes: &[H256]) -> TransactionHashes;
     fn return_all_errorless_fingerprints(&self) -> Vec<PendingPayableFingerprint>;
+    fn return_failed_fingerprints(&self) -> Vec<PendingPayableFingerprint>;
+    // Told by the outbound payments and the creditor shares, since a payable may have let go of
+    // its fingerprint while the transaction is still out there
+    fn recipients_of_errorless_fingerprints(&self) -> HashSet<Wallet>;
     fn insert_new_fingerprints(
         &self,
         hashes_and_amounts: &[HashAndAmount],
         batch_wide_timestamp: SystemTime,
+        first_transaction_basis: ReplacementBasis,
+    ) -> Result<(), PendingPayableDaoError>;
+    // Attaches each share to the fingerprint of its transaction, which must be in already
+    fn insert_creditor_shares(
+        &self,
+        shares: &[CreditorShare],
     ) -> Result<(), PendingPayableDaoError>;
-    fn delete_fingerprints(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError>;
+    // The fingerprint is taken over by the replacement, so the payables stay attached to it
+    fn record_replacement(
+        &self,
+        rowid: u64,
+        hash: H256,
+        basis: ReplacementBasis,
+    ) -> Result<(), PendingPayableDaoError>;
+    fn delete_fingerprints(&mut self, ids: &[u64]) -> Result<(), PendingPayableDaoError>;
     fn increment_scan_attempts(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError>;
     fn mark_failures(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError>;
+    // Makes failed fingerprints pending again, so that their receipts are looked up once more
+    fn unmark_failures(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError>;
+    // The fingerprints stay as a record of the transactions given up on, though the payables
+    // are expected to have been released from them
+    fn mark_abandoned(&self, ids: &[u64], reason: &str) -> Result<(), PendingPayableDaoError>;
 }
 
 impl PendingPayableDao for PendingPayableDaoReal<'_> {
@@ -59,8 +87,10 @@ impl PendingPayableDao for PendingPayableDaoReal<'_> {
         }
 
         let sql = format!(
-            "select transaction_hash, rowid from pending_payable where transaction_hash in ({})",
-            comma_joined_stringifiable(hashes, |hash| format!("'{:?}'", hash))
+            "select transaction_hash, rowid from pending_payable where transaction_hash in ({}) \
+             and chain = {}",
+            comma_joined_stringifiable(hashes, |hash| format!("'{:?}'", hash)),
+            ACTIVE_CHAIN
         );
 
         let all_found_records = self
@@ -88,67 +118,75 @@ impl PendingPayableDao for PendingPayableDaoReal<'_> {
     }
 
     fn return_all_errorless_fingerprints(&self) -> Vec<PendingPayableFingerprint> {
-        let mut stm = self
-            .conn
-            .prepare(
-                "select rowid, transaction_hash, amount_high_b, amount_low_b, \
-                 payable_timestamp, attempt from pending_payable where process_error is null",
-            )
-            .expect("Internal error");
-        stm.query_map([], |row| {
-            let rowid: u64 = Self::get_with_expect(row, 0);
-            let transaction_hash: String = Self::get_with_expect(row, 1);
-            let amount_high_bytes: i64 = Self::get_with_expect(row, 2);
-            let amount_low_bytes: i64 = Self::get_with_expect(row, 3);
-            let timestamp: i64 = Self::get_with_expect(row, 4);
-            let attempt: u16 = Self::get_with_expect(row, 5);
-            Ok(PendingPayableFingerprint {
-                rowid,
-                timestamp: from_time_t(timestamp),
-                hash: H256::from_str(&transaction_hash[2..]).unwrap_or_else(|e| {
-                    panic!(
-                        "Invalid hash format (\"{}\": {:?}) - database corrupt",
-                        transaction_hash, e
-                    )
-                }),
-                attempt,
-                amount: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
-                    amount_high_bytes,
-                    amount_low_bytes,
-                )),
-                process_error: None,
-            })
-        })
-        .expect("rusqlite failure")
-        .vigilant_flatten()
-        .collect()
+        self.fingerprints_where("process_error is null")
+    }
+
+    fn return_failed_fingerprints(&self) -> Vec<PendingPayableFingerprint> {
+        self.fingerprints_where("process_error is not null")
+    }
+
+    fn recipients_of_errorless_fingerprints(&self) -> HashSet<Wallet> {
+        let errorless = format!("process_error is null and chain = {ACTIVE_CHAIN}");
+        let sql = format!(
+            "select wallet_address from outbound_payment where transaction_hash in \
+             (select transaction_hash from pending_payable where {errorless}) \
+             union select wallet_address from pending_payable_share where pending_payable_rowid in \
+             (select rowid from pending_payable where {errorless})"
+        );
+        self.conn
+            .prepare(&sql)
+            .expect("Internal error")
+            .query_map([], |row| row.get::<usize, Wallet>(0))
+            .expect("rusqlite failure")
+            .vigilant_flatten()
+            .collect()
     }
 
     fn insert_new_fingerprints(
         &self,
         hashes_and_amounts: &[HashAndAmount],
         batch_wide_timestamp: SystemTime,
+        first_transaction_basis: ReplacementBasis,
     ) -> Result<(), PendingPayableDaoError> {
         fn values_clause_for_fingerprints_to_insert(
             hashes_and_amounts: &[HashAndAmount],
             batch_wide_timestamp: SystemTime,
+            first_transaction_basis: ReplacementBasis,
         ) -> String {
             let time_t = to_time_t(batch_wide_timestamp);
-            comma_joined_stringifiable(hashes_and_amounts, |hash_and_amount| {
-                let amount_checked = checked_conversion::<u128, i128>(hash_and_amount.amount);
-                let (high_bytes, low_bytes) = BigIntDivider::deconstruct(amount_checked);
-                format!(
-                    "('{:?}', {}, {}, {}, 1, null)",
-                    hash_and_amount.hash, high_bytes, low_bytes, time_t
-                )
-            })
+            let gas_price_wei =
+                checked_conversion::<u128, i64>(first_transaction_basis.gas_price_wei);
+            hashes_and_amounts
+                .iter()
+                .enumerate()
+                .map(|(idx, hash_and_amount)| {
+                    let amount_checked = checked_conversion::<u128, i128>(hash_and_amount.amount);
+                    let (high_bytes, low_bytes) = BigIntDivider::deconstruct(amount_checked);
+                    let nonce = first_transaction_basis.nonce + idx as u64;
+                    format!(
+                        "('{:?}', {}, {}, {}, 1, null, {}, {}, {})",
+                        hash_and_amount.hash,
+                        high_bytes,
+                        low_bytes,
+                        time_t,
+                        checked_conversion::<u64, i64>(nonce),
+                        gas_price_wei,
+                        ACTIVE_CHAIN
+                    )
+                })
+                .join(", ")
         }
 
         let insert_sql = format!(
             "insert into pending_payable (\
-            transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error\
+            transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error, \
+            nonce, gas_price_wei, chain\
             ) values {}",
-            values_clause_for_fingerprints_to_insert(hashes_and_amounts, batch_wide_timestamp)
+            values_clause_for_fingerprints_to_insert(
+                hashes_and_amounts,
+                batch_wide_timestamp,
+                first_transaction_basis
+            )
         );
         match self
             .conn
@@ -166,25 +204,92 @@ impl PendingPayableDao for PendingPayableDaoReal<'_> {
         }
     }
 
-    fn delete_fingerprints(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
+    fn insert_creditor_shares(
+        &self,
+        shares: &[CreditorShare],
+    ) -> Result<(), PendingPayableDaoError> {
+        let selects = shares
+            .iter()
+            .map(|share| {
+                let amount_checked = checked_conversion::<u128, i128>(share.amount);
+                let (high_bytes, low_bytes) = BigIntDivider::deconstruct(amount_checked);
+                format!(
+                    "select rowid, '{}', {}, {} from pending_payable where transaction_hash = '{:?}'",
+                    share.wallet, high_bytes, low_bytes, share.hash
+                )
+            })
+            .join(" union all ");
         let sql = format!(
-            "delete from pending_payable where rowid in ({})",
-            Self::serialize_ids(ids)
+            "insert into pending_payable_share (pending_payable_rowid, wallet_address, \
+             amount_high_b, amount_low_b) {}",
+            selects
         );
-        match self
-            .conn
-            .prepare(&sql)
-            .expect("delete command wrong")
-            .execute([])
-        {
-            Ok(x) if x == ids.len() => Ok(()),
+        match self.conn.prepare(&sql).expect("Internal error").execute([]) {
+            Ok(x) if x == shares.len() => Ok(()),
+            Ok(x) => Err(PendingPayableDaoError::InsertionFailed(format!(
+                "Only {} of {} creditor shares found the fingerprints of their transactions",
+                x,
+                shares.len()
+            ))),
+            Err(e) => Err(PendingPayableDaoError::InsertionFailed(e.to_string())),
+        }
+    }
+
+    fn record_replacement(
+        &self,
+        rowid: u64,
+        hash: H256,
+        basis: ReplacementBasis,
+    ) -> Result<(), PendingPayableDaoError> {
+        let sql = format!(
+            "update pending_payable set transaction_hash = '{:?}', nonce = {}, gas_price_wei = {} \
+             where rowid = {}",
+            hash,
+            checked_conversion::<u64, i64>(basis.nonce),
+            checked_conversion::<u128, i64>(basis.gas_price_wei),
+            rowid
+        );
+        match self.conn.prepare(&sql).expect("Internal error").execute([]) {
+            Ok(1) => Ok(()),
+            Ok(num) => panic!(
+                "Database corrupt: replacing the transaction of fingerprint {}: expected to update \
+                 1 row but did {}",
+                rowid, num
+            ),
+            Err(e) => Err(PendingPayableDaoError::UpdateFailed(e.to_string())),
+        }
+    }
+
+    fn delete_fingerprints(&mut self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
+        let serialized_ids = Self::serialize_ids(ids);
+        let to_deletion_error =
+            |e: rusqlite::Error| PendingPayableDaoError::RecordDeletion(e.to_string());
+        // The shares must go along; SQLite hands a freed rowid out again to the next fingerprint
+        let txn = self.conn.transaction().map_err(to_deletion_error)?;
+        match txn.execute(
+            &format!(
+                "delete from pending_payable where rowid in ({})",
+                serialized_ids
+            ),
+            &[],
+        ) {
+            Ok(x) if x == ids.len() => (),
             Ok(num) => panic!(
                 "deleting fingerprint, expected {} rows to be changed, but the actual number is {}",
                 ids.len(),
                 num
             ),
-            Err(e) => Err(PendingPayableDaoError::RecordDeletion(e.to_string())),
+            Err(e) => return Err(to_deletion_error(e)),
         }
+        txn.execute(
+            &format!(
+                "delete from pending_payable_share where pending_payable_rowid in ({})",
+                serialized_ids
+            ),
+            &[],
+        )
+        .and_then(|_| txn.commit())
+        .map_err(to_deletion_error)
     }
 
     fn increment_scan_attempts(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
@@ -223,6 +328,50 @@ impl PendingPayableDao for PendingPayableDaoReal<'_> {
             Err(e) => Err(PendingPayableDaoError::ErrorMarkFailed(e.to_string())),
         }
     }
+
+    fn unmark_failures(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
+        let sql = format!(
+            "update pending_payable set process_error = null where rowid in ({})",
+            Self::serialize_ids(ids)
+        );
+        match self
+            .conn
+            .prepare(&sql)
+            .expect("Internal error")
+            .execute([])
+        {
+            Ok(num) if num == ids.len() => Ok(()),
+            Ok(num) => panic!(
+                "Database corrupt: unmarking failure at fingerprints: expected to change {} rows but did {}",
+                ids.len(),
+                num
+            ),
+            Err(e) => Err(PendingPayableDaoError::ErrorMarkFailed(e.to_string())),
+        }
+    }
+
+    fn mark_abandoned(&self, ids: &[u64], reason: &str) -> Result<(), PendingPayableDaoError> {
+        let sql = format!(
+            "update pending_payable set process_error = ? where rowid in ({})",
+            Self::serialize_ids(ids)
+        );
+        let process_error = format!("{}: {}", ABANDONED_MARK, reason);
+        let params: &[&dyn ToSql] = &[&process_error];
+        match self
+            .conn
+            .prepare(&sql)
+            .expect("Internal error")
+            .execute(params)
+        {
+            Ok(num) if num == ids.len() => Ok(()),
+            Ok(num) => panic!(
+                "Database corrupt: marking fingerprints abandoned: expected to change {} rows but did {}",
+                ids.len(),
+                num
+            ),
+            Err(e) => Err(PendingPayableDaoError::ErrorMarkFailed(e.to_string())),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -257,6 +406,57 @@ impl<'a> PendingPayableDaoReal<'a> {
     fn serialize_ids(ids: &[u64]) -> String {
         comma_joined_stringifiable(ids, |id| id.to_string())
     }
+
+    fn fingerprints_where(&self, condition: &str) -> Vec<PendingPayableFingerprint> {
+        let sql = format!(
+            "select rowid, transaction_hash, amount_high_b, amount_low_b, \
+             payable_timestamp, attempt, process_error, nonce, gas_price_wei from pending_payable \
+             where {} and chain = {}",
+            condition, ACTIVE_CHAIN
+        );
+        let mut stm = self.conn.prepare(&sql).expect("Internal error");
+        stm.query_map([], Self::fingerprint_from_row)
+            .expect("rusqlite failure")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    // Expects the columns rowid, transaction_hash, amount_high_b, amount_low_b,
+    // payable_timestamp, attempt, process_error, nonce and gas_price_wei in this order
+    pub(crate) fn fingerprint_from_row(row: &Row) -> rusqlite::Result<PendingPayableFingerprint> {
+        let rowid: u64 = Self::get_with_expect(row, 0);
+        let transaction_hash: String = Self::get_with_expect(row, 1);
+        let amount_high_bytes: i64 = Self::get_with_expect(row, 2);
+        let amount_low_bytes: i64 = Self::get_with_expect(row, 3);
+        let timestamp: i64 = Self::get_with_expect(row, 4);
+        let attempt: u16 = Self::get_with_expect(row, 5);
+        let process_error: Option<String> = Self::get_with_expect(row, 6);
+        let nonce_opt: Option<i64> = Self::get_with_expect(row, 7);
+        let gas_price_wei_opt: Option<i64> = Self::get_with_expect(row, 8);
+        Ok(PendingPayableFingerprint {
+            rowid,
+            timestamp: from_time_t(timestamp),
+            hash: H256::from_str(&transaction_hash[2..]).unwrap_or_else(|e| {
+                panic!(
+                    "Invalid hash format (\"{}\": {:?}) - database corrupt",
+                    transaction_hash, e
+                )
+            }),
+            attempt,
+            amount: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                amount_high_bytes,
+                amount_low_bytes,
+            )),
+            process_error,
+            replacement_basis_opt: match (nonce_opt, gas_price_wei_opt) {
+                (Some(nonce), Some(gas_price_wei)) => Some(ReplacementBasis {
+                    nonce: checked_conversion::<i64, u64>(nonce),
+                    gas_price_wei: checked_conversion::<i64, u128>(gas_price_wei),
+                }),
+                _ => None,
+            },
+        })
+    }
 }
 
 pub trait PendingPayableDaoFactory {
@@ -273,11 +473,13 @@ impl PendingPayableDaoFactory for DaoFactoryReal {
 mod tests {
     use crate::accountant::checked_conversion;
     use crate::accountant::db_access_objects::pending_payable_dao::{
-        PendingPayableDao, PendingPayableDaoError, PendingPayableDaoReal,
+        PendingPayableDao, PendingPayableDaoError, PendingPayableDaoReal, ABANDONED_MARK,
     };
     use crate::accountant::db_access_objects::utils::from_time_t;
     use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
-    use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+    use crate::blockchain::blockchain_bridge::{
+        CreditorShare, PendingPayableFingerprint, ReplacementBasis,
+    };
     use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
     use crate::blockchain::test_utils::make_tx_hash;
     use crate::database::db_initializer::{
@@ -285,12 +487,32 @@ mod tests {
     };
     use crate::database::rusqlite_wrappers::ConnectionWrapperReal;
     use crate::database::test_utils::ConnectionWrapperMock;
+    use crate::sub_lib::wallet::Wallet;
+    use crate::test_utils::make_wallet;
     use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
-    use rusqlite::{Connection, OpenFlags};
+    use rusqlite::{Connection, OpenFlags, ToSql};
+    use std::collections::HashSet;
     use std::str::FromStr;
     use std::time::SystemTime;
     use web3::types::H256;
 
+    const FIRST_TRANSACTION_BASIS: ReplacementBasis = ReplacementBasis {
+        nonce: 5,
+        gas_price_wei: 1_234_000_000,
+    };
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(ABANDONED_MARK, "ABANDONED");
+    }
+
+    fn basis_of_nth_transaction(n: u64) -> Option<ReplacementBasis> {
+        Some(ReplacementBasis {
+            nonce: FIRST_TRANSACTION_BASIS.nonce + n,
+            ..FIRST_TRANSACTION_BASIS
+        })
+    }
+
     #[test]
     fn insert_new_fingerprints_happy_path() {
         let home_dir = ensure_node_home_directory_exists(
@@ -319,6 +541,7 @@ mod tests {
             .insert_new_fingerprints(
                 &[hash_and_amount_1, hash_and_amount_2],
                 batch_wide_timestamp,
+                FIRST_TRANSACTION_BASIS,
             )
             .unwrap();
 
@@ -332,7 +555,8 @@ mod tests {
                     hash: hash_and_amount_1.hash,
                     attempt: 1,
                     amount: hash_and_amount_1.amount,
-                    process_error: None
+                    process_error: None,
+                    replacement_basis_opt: basis_of_nth_transaction(0),
                 },
                 PendingPayableFingerprint {
                     rowid: 2,
@@ -340,7 +564,8 @@ mod tests {
                     hash: hash_and_amount_2.hash,
                     attempt: 1,
                     amount: hash_and_amount_2.amount,
-                    process_error: None
+                    process_error: None,
+                    replacement_basis_opt: basis_of_nth_transaction(1),
                 }
             ]
         )
@@ -369,7 +594,8 @@ mod tests {
         let subject = PendingPayableDaoReal::new(Box::new(wrapped_conn));
         let hash_and_amount = HashAndAmount { hash, amount };
 
-        let result = subject.insert_new_fingerprints(&[hash_and_amount], timestamp);
+        let result =
+            subject.insert_new_fingerprints(&[hash_and_amount], timestamp, FIRST_TRANSACTION_BASIS);
 
         assert_eq!(
             result,
@@ -401,7 +627,11 @@ mod tests {
             amount: amount_1,
         };
 
-        let _ = subject.insert_new_fingerprints(&[hash_and_amount], batch_wide_timestamp);
+        let _ = subject.insert_new_fingerprints(
+            &[hash_and_amount],
+            batch_wide_timestamp,
+            FIRST_TRANSACTION_BASIS,
+        );
     }
 
     #[test]
@@ -434,7 +664,11 @@ mod tests {
         let fingerprints_init_input = vec![hash_and_amount_1, hash_and_amount_2];
         {
             subject
-                .insert_new_fingerprints(&fingerprints_init_input, timestamp)
+                .insert_new_fingerprints(
+                    &fingerprints_init_input,
+                    timestamp,
+                    FIRST_TRANSACTION_BASIS,
+                )
                 .unwrap();
         }
 
@@ -466,7 +700,7 @@ mod tests {
         let wrapped_conn = DbInitializerReal::default()
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
-        let subject = PendingPayableDaoReal::new(wrapped_conn);
+        let mut subject = PendingPayableDaoReal::new(wrapped_conn);
         let hash_1 = make_tx_hash(11119);
         let hash_2 = make_tx_hash(22229);
         let hash_3 = make_tx_hash(33339);
@@ -481,6 +715,7 @@ mod tests {
                     amount: 8901234,
                 }],
                 SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
             )
             .unwrap();
         subject
@@ -490,6 +725,7 @@ mod tests {
                     amount: 1234567,
                 }],
                 SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
             )
             .unwrap();
         subject.delete_fingerprints(&[1]).unwrap();
@@ -529,6 +765,7 @@ mod tests {
                 .insert_new_fingerprints(
                     &[hash_and_amount_1, hash_and_amount_2],
                     batch_wide_timestamp,
+                    FIRST_TRANSACTION_BASIS,
                 )
                 .unwrap();
         }
@@ -544,7 +781,8 @@ mod tests {
                     hash: hash_1,
                     attempt: 1,
                     amount: amount_1,
-                    process_error: None
+                    process_error: None,
+                    replacement_basis_opt: basis_of_nth_transaction(0),
                 },
                 PendingPayableFingerprint {
                     rowid: 2,
@@ -552,7 +790,8 @@ mod tests {
                     hash: hash_2,
                     attempt: 1,
                     amount: amount_2,
-                    process_error: None
+                    process_error: None,
+                    replacement_basis_opt: basis_of_nth_transaction(1),
                 }
             ]
         )
@@ -578,7 +817,11 @@ mod tests {
         let hash_and_amount_2 = HashAndAmount { hash, amount };
         {
             subject
-                .insert_new_fingerprints(&[hash_and_amount_1, hash_and_amount_2], timestamp)
+                .insert_new_fingerprints(
+                    &[hash_and_amount_1, hash_and_amount_2],
+                    timestamp,
+                    FIRST_TRANSACTION_BASIS,
+                )
                 .unwrap();
             subject.mark_failures(&[1]).unwrap();
         }
@@ -593,11 +836,130 @@ mod tests {
                 hash,
                 attempt: 1,
                 amount,
-                process_error: None
+                process_error: None,
+                replacement_basis_opt: basis_of_nth_transaction(1),
             }]
         )
     }
 
+    #[test]
+    fn return_failed_fingerprints_returns_only_records_with_error_marks() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "return_failed_fingerprints_returns_only_records_with_error_marks",
+        );
+        let wrapped_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(wrapped_conn);
+        let timestamp = from_time_t(198_000_000);
+        let hash = make_tx_hash(11119);
+        let amount = 2000;
+        let hash_and_amount_1 = HashAndAmount { hash, amount };
+        let hash_and_amount_2 = HashAndAmount {
+            hash: make_tx_hash(10000),
+            amount: 333,
+        };
+        {
+            subject
+                .insert_new_fingerprints(
+                    &[hash_and_amount_1, hash_and_amount_2],
+                    timestamp,
+                    FIRST_TRANSACTION_BASIS,
+                )
+                .unwrap();
+            subject.mark_failures(&[1]).unwrap();
+        }
+
+        let result = subject.return_failed_fingerprints();
+
+        assert_eq!(
+            result,
+            vec![PendingPayableFingerprint {
+                rowid: 1,
+                timestamp,
+                hash,
+                attempt: 1,
+                amount,
+                process_error: Some("ERROR".to_string()),
+                replacement_basis_opt: basis_of_nth_transaction(0),
+            }]
+        )
+    }
+
+    #[test]
+    fn recipients_of_errorless_fingerprints_come_from_outbound_payments_and_shares() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "recipients_of_errorless_fingerprints_come_from_outbound_payments_and_shares",
+        );
+        let wrapped_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(wrapped_conn);
+        let hash_1 = make_tx_hash(111);
+        let hash_2 = make_tx_hash(222);
+        let hash_3 = make_tx_hash(333);
+        subject
+            .insert_new_fingerprints(
+                &[
+                    HashAndAmount {
+                        hash: hash_1,
+                        amount: 1000,
+                    },
+                    HashAndAmount {
+                        hash: hash_2,
+                        amount: 2000,
+                    },
+                    HashAndAmount {
+                        hash: hash_3,
+                        amount: 3000,
+                    },
+                ],
+                SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+        subject.mark_failures(&[3]).unwrap();
+        subject
+            .insert_creditor_shares(&[
+                CreditorShare {
+                    hash: hash_2,
+                    wallet: make_wallet("sharer"),
+                    amount: 2000,
+                },
+                CreditorShare {
+                    hash: hash_3,
+                    wallet: make_wallet("failed_sharer"),
+                    amount: 3000,
+                },
+            ])
+            .unwrap();
+        let mut stm = subject
+            .conn
+            .prepare(
+                "insert into outbound_payment (instructions_rowid, wallet_address, amount_high_b, \
+                 amount_low_b, transaction_hash, status) values (1, ?, 0, 1000, ?, 'submitted')",
+            )
+            .unwrap();
+        [
+            (make_wallet("outbound"), hash_1),
+            (make_wallet("failed_outbound"), hash_3),
+        ]
+        .into_iter()
+        .for_each(|(wallet, hash)| {
+            let params: &[&dyn ToSql] = &[&wallet, &format!("{:?}", hash)];
+            stm.execute(params).unwrap();
+        });
+
+        let result = subject.recipients_of_errorless_fingerprints();
+
+        assert_eq!(
+            result,
+            HashSet::from([make_wallet("outbound"), make_wallet("sharer")])
+        )
+    }
+
     #[test]
     #[should_panic(
         expected = "Invalid hash format (\"silly_hash\": Invalid character 'l' at position 0) - database corrupt"
@@ -612,9 +974,9 @@ mod tests {
             .unwrap();
         {
             wrapped_conn
-                .prepare("insert into pending_payable \
-                (rowid, transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error) \
-                values (1, 'silly_hash', 4, 111, 10000000000, 1, null)")
+                .prepare(&format!("insert into pending_payable \
+                (rowid, transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error, chain) \
+                values (1, 'silly_hash', 4, 111, 10000000000, 1, null, {ACTIVE_CHAIN})"))
                 .unwrap()
                 .execute([])
                 .unwrap();
@@ -624,6 +986,226 @@ mod tests {
         let _ = subject.return_all_errorless_fingerprints();
     }
 
+    #[test]
+    fn insert_creditor_shares_attaches_them_to_the_fingerprint_of_their_transaction() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "insert_creditor_shares_attaches_them_to_the_fingerprint_of_their_transaction",
+        );
+        let wrapped_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(wrapped_conn);
+        let hash_1 = make_tx_hash(1234);
+        let hash_2 = make_tx_hash(2345);
+        subject
+            .insert_new_fingerprints(
+                &[
+                    HashAndAmount {
+                        hash: hash_1,
+                        amount: 1111,
+                    },
+                    HashAndAmount {
+                        hash: hash_2,
+                        amount: i64::MAX as u128 + 3,
+                    },
+                ],
+                SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+        let share_1 = CreditorShare {
+            hash: hash_2,
+            wallet: make_wallet("creditor_1"),
+            amount: i64::MAX as u128,
+        };
+        let share_2 = CreditorShare {
+            hash: hash_2,
+            wallet: make_wallet("creditor_2"),
+            amount: 3,
+        };
+
+        let result = subject.insert_creditor_shares(&[share_1.clone(), share_2.clone()]);
+
+        assert_eq!(result, Ok(()));
+        let mut stm = subject
+            .conn
+            .prepare(
+                "select pending_payable_rowid, wallet_address, amount_high_b, amount_low_b \
+                 from pending_payable_share order by wallet_address",
+            )
+            .unwrap();
+        let shares = stm
+            .query_map([], |row| {
+                Ok((
+                    row.get::<usize, i64>(0).unwrap(),
+                    row.get::<usize, Wallet>(1).unwrap(),
+                    BigIntDivider::reconstitute(row.get(2).unwrap(), row.get(3).unwrap()),
+                ))
+            })
+            .unwrap()
+            .flatten()
+            .collect::<Vec<_>>();
+        let mut expected_shares = vec![
+            (2, share_1.wallet, i64::MAX as i128),
+            (2, share_2.wallet, 3),
+        ];
+        expected_shares.sort_by_key(|(_, wallet, _)| wallet.to_string());
+        assert_eq!(shares, expected_shares)
+    }
+
+    #[test]
+    fn insert_creditor_shares_fails_on_a_share_without_a_fingerprint() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "insert_creditor_shares_fails_on_a_share_without_a_fingerprint",
+        );
+        let wrapped_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(wrapped_conn);
+        let hash = make_tx_hash(1234);
+        subject
+            .insert_new_fingerprints(
+                &[HashAndAmount { hash, amount: 1111 }],
+                SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+
+        let result = subject.insert_creditor_shares(&[
+            CreditorShare {
+                hash,
+                wallet: make_wallet("creditor_1"),
+                amount: 1000,
+            },
+            CreditorShare {
+                hash: make_tx_hash(9999),
+                wallet: make_wallet("creditor_2"),
+                amount: 111,
+            },
+        ]);
+
+        assert_eq!(
+            result,
+            Err(PendingPayableDaoError::InsertionFailed(
+                "Only 1 of 2 creditor shares found the fingerprints of their transactions"
+                    .to_string()
+            ))
+        )
+    }
+
+    #[test]
+    fn delete_fingerprints_deletes_the_creditor_shares_too() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "delete_fingerprints_deletes_the_creditor_shares_too",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let mut subject = PendingPayableDaoReal::new(conn);
+        let hash_1 = make_tx_hash(1234);
+        let hash_2 = make_tx_hash(2345);
+        subject
+            .insert_new_fingerprints(
+                &[
+                    HashAndAmount {
+                        hash: hash_1,
+                        amount: 1111,
+                    },
+                    HashAndAmount {
+                        hash: hash_2,
+                        amount: 2222,
+                    },
+                ],
+                SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+        subject
+            .insert_creditor_shares(&[
+                CreditorShare {
+                    hash: hash_1,
+                    wallet: make_wallet("creditor_1"),
+                    amount: 1000,
+                },
+                CreditorShare {
+                    hash: hash_1,
+                    wallet: make_wallet("creditor_2"),
+                    amount: 111,
+                },
+                CreditorShare {
+                    hash: hash_2,
+                    wallet: make_wallet("creditor_3"),
+                    amount: 2222,
+                },
+            ])
+            .unwrap();
+
+        let result = subject.delete_fingerprints(&[1]);
+
+        assert_eq!(result, Ok(()));
+        let remaining_share_rowids = subject
+            .conn
+            .prepare("select pending_payable_rowid from pending_payable_share")
+            .unwrap()
+            .query_map([], |row| row.get::<usize, i64>(0))
+            .unwrap()
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(remaining_share_rowids, vec![2])
+    }
+
+    #[test]
+    fn delete_fingerprints_keeps_the_fingerprints_if_their_creditor_shares_cannot_go() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "delete_fingerprints_keeps_the_fingerprints_if_their_creditor_shares_cannot_go",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        conn.prepare(
+            "create trigger share_guard before delete on pending_payable_share \
+             begin select raise(abort, 'shares are stuck'); end",
+        )
+        .unwrap()
+        .execute([])
+        .unwrap();
+        let mut subject = PendingPayableDaoReal::new(conn);
+        let hash = make_tx_hash(1234);
+        subject
+            .insert_new_fingerprints(
+                &[HashAndAmount { hash, amount: 1111 }],
+                SystemTime::now(),
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+        subject
+            .insert_creditor_shares(&[CreditorShare {
+                hash,
+                wallet: make_wallet("creditor"),
+                amount: 1111,
+            }])
+            .unwrap();
+
+        let result = subject.delete_fingerprints(&[1]);
+
+        assert_eq!(
+            result,
+            Err(PendingPayableDaoError::RecordDeletion(
+                "shares are stuck".to_string()
+            ))
+        );
+        let remaining_hashes = subject
+            .return_all_errorless_fingerprints()
+            .into_iter()
+            .map(|fingerprint| fingerprint.hash)
+            .collect::<Vec<_>>();
+        assert_eq!(remaining_hashes, vec![hash])
+    }
+
     #[test]
     fn delete_fingerprints_happy_path() {
         let home_dir = ensure_node_home_directory_exists(
@@ -633,7 +1215,7 @@ mod tests {
         let conn = DbInitializerReal::default()
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
-        let subject = PendingPayableDaoReal::new(conn);
+        let mut subject = PendingPayableDaoReal::new(conn);
         {
             subject
                 .insert_new_fingerprints(
@@ -652,6 +1234,7 @@ mod tests {
                         },
                     ],
                     SystemTime::now(),
+                    FIRST_TRANSACTION_BASIS,
                 )
                 .unwrap();
         }
@@ -684,7 +1267,7 @@ mod tests {
         .unwrap();
         let wrapped_conn = ConnectionWrapperReal::new(conn_read_only);
         let rowid = 45;
-        let subject = PendingPayableDaoReal::new(Box::new(wrapped_conn));
+        let mut subject = PendingPayableDaoReal::new(Box::new(wrapped_conn));
 
         let result = subject.delete_fingerprints(&[rowid]);
 
@@ -710,7 +1293,7 @@ mod tests {
             .unwrap();
         let rowid_1 = 1;
         let rowid_2 = 2;
-        let subject = PendingPayableDaoReal::new(conn);
+        let mut subject = PendingPayableDaoReal::new(conn);
         {
             subject
                 .insert_new_fingerprints(
@@ -719,6 +1302,7 @@ mod tests {
                         amount: 5555,
                     }],
                     SystemTime::now(),
+                    FIRST_TRANSACTION_BASIS,
                 )
                 .unwrap();
         }
@@ -726,6 +1310,111 @@ mod tests {
         let _ = subject.delete_fingerprints(&[rowid_1, rowid_2]);
     }
 
+    #[test]
+    fn record_replacement_works() {
+        let home_dir =
+            ensure_node_home_directory_exists("pending_payable_dao", "record_replacement_works");
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let timestamp = from_time_t(195_000_000);
+        let hash_and_amount_1 = HashAndAmount {
+            hash: make_tx_hash(1111),
+            amount: 4444,
+        };
+        let hash_and_amount_2 = HashAndAmount {
+            hash: make_tx_hash(2222),
+            amount: 5555,
+        };
+        let subject = PendingPayableDaoReal::new(conn);
+        {
+            subject
+                .insert_new_fingerprints(
+                    &[hash_and_amount_1, hash_and_amount_2],
+                    timestamp,
+                    FIRST_TRANSACTION_BASIS,
+                )
+                .unwrap();
+        }
+        let replacement_hash = make_tx_hash(3333);
+        let replacement_basis = ReplacementBasis {
+            nonce: 6,
+            gas_price_wei: 1_480_800_000,
+        };
+
+        let result = subject.record_replacement(2, replacement_hash, replacement_basis);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            subject.return_all_errorless_fingerprints(),
+            vec![
+                PendingPayableFingerprint {
+                    rowid: 1,
+                    timestamp,
+                    hash: hash_and_amount_1.hash,
+                    attempt: 1,
+                    amount: hash_and_amount_1.amount,
+                    process_error: None,
+                    replacement_basis_opt: basis_of_nth_transaction(0),
+                },
+                PendingPayableFingerprint {
+                    rowid: 2,
+                    timestamp,
+                    hash: replacement_hash,
+                    attempt: 1,
+                    amount: hash_and_amount_2.amount,
+                    process_error: None,
+                    replacement_basis_opt: Some(replacement_basis),
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn record_replacement_sad_path() {
+        let home_dir =
+            ensure_node_home_directory_exists("pending_payable_dao", "record_replacement_sad_path");
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let wrapped_conn = ConnectionWrapperReal::new(conn_read_only);
+        let subject = PendingPayableDaoReal::new(Box::new(wrapped_conn));
+
+        let result = subject.record_replacement(1, make_tx_hash(3333), FIRST_TRANSACTION_BASIS);
+
+        assert_eq!(
+            result,
+            Err(PendingPayableDaoError::UpdateFailed(
+                "attempt to write a readonly database".to_string()
+            ))
+        )
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Database corrupt: replacing the transaction of fingerprint 4: expected to \
+        update 1 row but did 0"
+    )]
+    fn record_replacement_panics_on_unexpected_row_change_count() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "record_replacement_panics_on_unexpected_row_change_count",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(conn);
+
+        let _ = subject.record_replacement(4, make_tx_hash(3333), FIRST_TRANSACTION_BASIS);
+    }
+
     #[test]
     fn increment_scan_attempts_works() {
         let home_dir = ensure_node_home_directory_exists(
@@ -757,6 +1446,7 @@ mod tests {
                 .insert_new_fingerprints(
                     &[hash_and_amount_1, hash_and_amount_2, hash_and_amount_3],
                     timestamp,
+                    FIRST_TRANSACTION_BASIS,
                 )
                 .unwrap();
         }
@@ -846,7 +1536,11 @@ mod tests {
         let subject = PendingPayableDaoReal::new(conn);
         {
             subject
-                .insert_new_fingerprints(&[hash_and_amount_1, hash_and_amount_2], timestamp)
+                .insert_new_fingerprints(
+                    &[hash_and_amount_1, hash_and_amount_2],
+                    timestamp,
+                    FIRST_TRANSACTION_BASIS,
+                )
                 .unwrap();
         }
 
@@ -876,6 +1570,7 @@ mod tests {
                         amount_low_b,
                     )),
                     process_error,
+                    replacement_basis_opt: None,
                 })
             })
             .unwrap()
@@ -890,7 +1585,8 @@ mod tests {
                     hash: hash_1,
                     attempt: 1,
                     amount: amount_1,
-                    process_error: None
+                    process_error: None,
+                    replacement_basis_opt: None,
                 },
                 PendingPayableFingerprint {
                     rowid: 2,
@@ -898,7 +1594,8 @@ mod tests {
                     hash: hash_2,
                     attempt: 1,
                     amount: amount_2,
-                    process_error: Some("ERROR".to_string())
+                    process_error: Some("ERROR".to_string()),
+                    replacement_basis_opt: None,
                 }
             ]
         )
@@ -947,4 +1644,187 @@ mod tests {
 
         let _ = subject.mark_failures(&[10, 20]);
     }
+
+    #[test]
+    fn unmark_failures_works() {
+        let home_dir =
+            ensure_node_home_directory_exists("pending_payable_dao", "unmark_failures_works");
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let timestamp = from_time_t(190_000_000);
+        let subject = PendingPayableDaoReal::new(conn);
+        subject
+            .insert_new_fingerprints(
+                &[
+                    HashAndAmount {
+                        hash: make_tx_hash(555),
+                        amount: 1234,
+                    },
+                    HashAndAmount {
+                        hash: make_tx_hash(666),
+                        amount: 2345,
+                    },
+                    HashAndAmount {
+                        hash: make_tx_hash(777),
+                        amount: 3456,
+                    },
+                ],
+                timestamp,
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+        subject.mark_failures(&[1, 2, 3]).unwrap();
+
+        let result = subject.unmark_failures(&[1, 3]);
+
+        assert_eq!(result, Ok(()));
+        let errorless_hashes = subject
+            .return_all_errorless_fingerprints()
+            .into_iter()
+            .map(|fingerprint| (fingerprint.rowid, fingerprint.hash))
+            .collect::<Vec<(u64, H256)>>();
+        assert_eq!(
+            errorless_hashes,
+            vec![(1, make_tx_hash(555)), (3, make_tx_hash(777))]
+        );
+        let failed_hashes = subject
+            .return_failed_fingerprints()
+            .into_iter()
+            .map(|fingerprint| (fingerprint.rowid, fingerprint.hash))
+            .collect::<Vec<(u64, H256)>>();
+        assert_eq!(failed_hashes, vec![(2, make_tx_hash(666))])
+    }
+
+    #[test]
+    fn unmark_failures_sad_path() {
+        let home_dir =
+            ensure_node_home_directory_exists("pending_payable_dao", "unmark_failures_sad_path");
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let wrapped_conn = ConnectionWrapperReal::new(conn_read_only);
+        let subject = PendingPayableDaoReal::new(Box::new(wrapped_conn));
+
+        let result = subject.unmark_failures(&[1]);
+
+        assert_eq!(
+            result,
+            Err(PendingPayableDaoError::ErrorMarkFailed(
+                "attempt to write a readonly database".to_string()
+            ))
+        )
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Database corrupt: unmarking failure at fingerprints: expected to change 2 rows but did 0"
+    )]
+    fn unmark_failures_panics_on_wrong_row_change_count() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "unmark_failures_panics_on_wrong_row_change_count",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(conn);
+
+        let _ = subject.unmark_failures(&[10, 20]);
+    }
+
+    #[test]
+    fn mark_abandoned_works() {
+        let home_dir =
+            ensure_node_home_directory_exists("pending_payable_dao", "mark_abandoned_works");
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let timestamp = from_time_t(190_000_000);
+        let subject = PendingPayableDaoReal::new(conn);
+        subject
+            .insert_new_fingerprints(
+                &[
+                    HashAndAmount {
+                        hash: make_tx_hash(555),
+                        amount: 1234,
+                    },
+                    HashAndAmount {
+                        hash: make_tx_hash(666),
+                        amount: 2345,
+                    },
+                ],
+                timestamp,
+                FIRST_TRANSACTION_BASIS,
+            )
+            .unwrap();
+
+        let result = subject.mark_abandoned(&[2], "pending for longer than 3600sec");
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            subject.return_failed_fingerprints(),
+            vec![PendingPayableFingerprint {
+                rowid: 2,
+                timestamp,
+                hash: make_tx_hash(666),
+                attempt: 1,
+                amount: 2345,
+                process_error: Some("ABANDONED: pending for longer than 3600sec".to_string()),
+                replacement_basis_opt: basis_of_nth_transaction(1),
+            }]
+        );
+        assert_eq!(subject.return_all_errorless_fingerprints().len(), 1)
+    }
+
+    #[test]
+    fn mark_abandoned_sad_path() {
+        let home_dir =
+            ensure_node_home_directory_exists("pending_payable_dao", "mark_abandoned_sad_path");
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let wrapped_conn = ConnectionWrapperReal::new(conn_read_only);
+        let subject = PendingPayableDaoReal::new(Box::new(wrapped_conn));
+
+        let result = subject.mark_abandoned(&[1], "whatever");
+
+        assert_eq!(
+            result,
+            Err(PendingPayableDaoError::ErrorMarkFailed(
+                "attempt to write a readonly database".to_string()
+            ))
+        )
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Database corrupt: marking fingerprints abandoned: expected to change 2 rows but did 0"
+    )]
+    fn mark_abandoned_panics_on_wrong_row_change_count() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "mark_abandoned_panics_on_wrong_row_change_count",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(conn);
+
+        let _ = subject.mark_abandoned(&[10, 20], "whatever");
+    }
 }
@@ -4,8 +4,9 @@ use crate::accountant::checked_conversion;
 use crate::accountant::db_access_objects::receivable_dao::ReceivableDaoError::RusqliteError;
 use crate::accountant::db_access_objects::utils;
 use crate::accountant::db_access_objects::utils::{
-    sum_i128_values_from_table, to_time_t, AssemblerFeeder, CustomQuery, DaoFactoryReal,
-    RangeStmConfig, ThresholdUtils, TopStmConfig, VigilantRusqliteFlatten,
+    debt_aging_from_table, sum_i128_values_from_table, to_time_t, AssemblerFeeder, CustomQuery,
+    DaoFactoryReal, DebtAging, RangeStmConfig, ThresholdUtils, TopStmConfig,
+    VigilantRusqliteFlatten, ACTIVE_CHAIN,
 };
 use crate::accountant::db_big_integer::big_int_db_processor::KeyVariants::WalletAddress;
 use crate::accountant::db_big_integer::big_int_db_processor::{
@@ -20,7 +21,7 @@ use crate::database::db_initializer::{connection_or_panic, DbInitializerReal};
 use crate::database::rusqlite_wrappers::{ConnectionWrapper, TransactionSafeWrapper};
 use crate::sub_lib::accountant::PaymentThresholds;
 use crate::sub_lib::wallet::Wallet;
-use indoc::indoc;
+use indoc::formatdoc;
 use itertools::Either;
 use masq_lib::constants::WEIS_IN_GWEI;
 use masq_lib::logger::Logger;
@@ -64,6 +65,21 @@ pub trait ReceivableDao {
         transactions: &[BlockchainTransaction],
     ) -> TransactionSafeWrapper;
 
+    fn received_payments_between(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Vec<BlockchainTransaction>;
+
+    fn received_payments_from(&self, wallet: &Wallet) -> Vec<BlockchainTransaction>;
+
+    fn reverse_received_payments(
+        &mut self,
+        vanished_payments: &[BlockchainTransaction],
+    ) -> Result<(), ReceivableDaoError>;
+
+    fn forget_received_payments_before(&self, block_number: u64) -> Result<(), ReceivableDaoError>;
+
     fn new_delinquencies(
         &self,
         now: SystemTime,
@@ -76,6 +92,9 @@ pub trait ReceivableDao {
 
     fn total(&self) -> i128;
 
+    // Balances by time since they were last paid; overpaid accounts are left out
+    fn aging(&self, now: SystemTime) -> DebtAging;
+
     // Test-only method but because of shares with multi-node tests #[cfg(test)] cannot be applied
     fn account_status(&self, wallet: &Wallet) -> Option<ReceivableAccount>;
 
@@ -114,11 +133,11 @@ impl ReceivableDao for ReceivableDaoReal {
         wallet: &Wallet,
         amount: u128,
     ) -> Result<(), ReceivableDaoError> {
-        let main_sql = "insert into receivable (wallet_address, balance_high_b, balance_low_b, last_received_timestamp) values \
-        (:wallet, :balance_high_b, :balance_low_b, :last_received_timestamp) on conflict (wallet_address) do update set \
-        balance_high_b = balance_high_b + :balance_high_b, balance_low_b = balance_low_b + :balance_low_b";
-        let update_clause_with_compensated_overflow = "update receivable set balance_high_b = :balance_high_b, balance_low_b = :balance_low_b \
-        where wallet_address = :wallet";
+        let main_sql = format!("insert into receivable (wallet_address, balance_high_b, balance_low_b, last_received_timestamp, chain) values \
+        (:wallet, :balance_high_b, :balance_low_b, :last_received_timestamp, {ACTIVE_CHAIN}) on conflict (wallet_address, chain) do update set \
+        balance_high_b = balance_high_b + :balance_high_b, balance_low_b = balance_low_b + :balance_low_b");
+        let update_clause_with_compensated_overflow = format!("update receivable set balance_high_b = :balance_high_b, balance_low_b = :balance_low_b \
+        where wallet_address = :wallet and chain = {ACTIVE_CHAIN}");
 
         let last_received_timestamp = to_time_t(timestamp);
         let params = SQLParamsBuilder::default()
@@ -138,7 +157,7 @@ impl ReceivableDao for ReceivableDaoReal {
 
         self.big_int_db_processor.execute(
             Either::Left(self.conn.as_ref()),
-            BigIntSqlConfig::new(main_sql, update_clause_with_compensated_overflow, params),
+            BigIntSqlConfig::new(&main_sql, &update_clause_with_compensated_overflow, params),
         )?;
 
         Ok(())
@@ -187,6 +206,99 @@ impl ReceivableDao for ReceivableDaoReal {
         }
     }
 
+    fn received_payments_between(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Vec<BlockchainTransaction> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "select block_number, wallet_address, amount_high_b, amount_low_b from received_payment \
+                 where block_number >= :from_block and block_number <= :to_block",
+            )
+            .expect("Couldn't prepare statement");
+        stmt.query_map(
+            named_params! {
+                ":from_block": checked_conversion::<u64, i64>(from_block),
+                ":to_block": checked_conversion::<u64, i64>(to_block),
+            },
+            Self::create_received_payment,
+        )
+        .expect("Couldn't retrieve received payments: database corruption")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    fn received_payments_from(&self, wallet: &Wallet) -> Vec<BlockchainTransaction> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "select block_number, wallet_address, amount_high_b, amount_low_b from received_payment \
+                 where wallet_address = ? order by block_number",
+            )
+            .expect("Couldn't prepare statement");
+        stmt.query_map(&[wallet], Self::create_received_payment)
+            .expect("Couldn't retrieve received payments: database corruption")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    fn reverse_received_payments(
+        &mut self,
+        vanished_payments: &[BlockchainTransaction],
+    ) -> Result<(), ReceivableDaoError> {
+        let main_sql = format!(
+            "update receivable set balance_high_b = balance_high_b + :balance_high_b, \
+                 balance_low_b = balance_low_b + :balance_low_b \
+                 where wallet_address = :wallet and chain = {ACTIVE_CHAIN}"
+        );
+        let update_clause_with_compensated_overflow = format!(
+            "update receivable set balance_high_b = :balance_high_b, \
+                 balance_low_b = :balance_low_b \
+                 where wallet_address = :wallet and chain = {ACTIVE_CHAIN}"
+        );
+        let delete_sql = "delete from received_payment where rowid = (select rowid from received_payment \
+                 where block_number = :block_number and wallet_address = :wallet and amount_high_b = :amount_high_b \
+                 and amount_low_b = :amount_low_b limit 1)";
+
+        let txn = self.conn.transaction()?;
+        vanished_payments.iter().try_for_each(|payment| {
+            let params = SQLParamsBuilder::default()
+                .key(WalletAddress(&payment.from))
+                .wei_change(WeiChange::new(
+                    "balance",
+                    payment.wei_amount,
+                    WeiChangeDirection::Addition,
+                ))
+                .build();
+            self.big_int_db_processor.execute(
+                Either::Right(&txn),
+                BigIntSqlConfig::new(&main_sql, &update_clause_with_compensated_overflow, params),
+            )?;
+            let (amount_high_b, amount_low_b) =
+                BigIntDivider::deconstruct(checked_conversion::<u128, i128>(payment.wei_amount));
+            txn.prepare(delete_sql)?.execute(named_params! {
+                ":block_number": checked_conversion::<u64, i64>(payment.block_number),
+                ":wallet": &payment.from,
+                ":amount_high_b": amount_high_b,
+                ":amount_low_b": amount_low_b,
+            })?;
+            Ok::<(), ReceivableDaoError>(())
+        })?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn forget_received_payments_before(&self, block_number: u64) -> Result<(), ReceivableDaoError> {
+        self.conn
+            .prepare("delete from received_payment where block_number < :block_number")?
+            .execute(named_params! {
+                ":block_number": checked_conversion::<u64, i64>(block_number),
+            })?;
+        Ok(())
+    }
+
     fn new_delinquencies(
         &self,
         now: SystemTime,
@@ -195,22 +307,24 @@ impl ReceivableDao for ReceivableDaoReal {
         let slope = ThresholdUtils::slope(payment_thresholds) as i64;
         let (permanent_debt_allowed_high_b, permanent_debt_allowed_low_b) =
             BigIntDivider::deconstruct(gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei));
-        let sql = indoc!(
+        let sql = formatdoc!(
             r"
                 select r.wallet_address, r.balance_high_b, r.balance_low_b, r.last_received_timestamp
                 from receivable r
                 left outer join banned b on r.wallet_address = b.wallet_address
                 where
-                    r.last_received_timestamp < :sugg_and_grace
+                    r.chain = {}
+                    and r.last_received_timestamp < :sugg_and_grace
                     and ((r.balance_high_b > slope_drop_high_bytes(:debt_threshold, :slope, :sugg_and_grace - r.last_received_timestamp))
                         or ((r.balance_high_b = slope_drop_high_bytes(:debt_threshold, :slope, :sugg_and_grace - r.last_received_timestamp))
                         and (r.balance_low_b > slope_drop_low_bytes(:debt_threshold, :slope, :sugg_and_grace - r.last_received_timestamp))))
                     and ((r.balance_high_b > :permanent_debt_allowed_high_b) or ((r.balance_high_b = 0) and (r.balance_low_b > :permanent_debt_allowed_low_b)))
                     and b.wallet_address is null
-            "
+            ",
+            ACTIVE_CHAIN
         );
         self.conn
-            .prepare(sql)
+            .prepare(&sql)
             .expect("Couldn't prepare statement")
             .query_map(
                 named_params! {
@@ -228,15 +342,17 @@ impl ReceivableDao for ReceivableDaoReal {
     }
 
     fn paid_delinquencies(&self, payment_thresholds: &PaymentThresholds) -> Vec<ReceivableAccount> {
-        let sql = indoc!(
+        let sql = formatdoc!(
             r"
             select r.wallet_address, r.balance_high_b, r.balance_low_b, r.last_received_timestamp
             from receivable r inner join banned b on r.wallet_address = b.wallet_address
             where
-                (r.balance_high_b < :unban_balance_high_b) or ((balance_high_b = :unban_balance_high_b) and (balance_low_b <= :unban_balance_low_b))
-        "
+                r.chain = {}
+                and ((r.balance_high_b < :unban_balance_high_b) or ((balance_high_b = :unban_balance_high_b) and (balance_low_b <= :unban_balance_low_b)))
+        ",
+            ACTIVE_CHAIN
         );
-        let mut stmt = self.conn.prepare(sql).expect("Couldn't prepare statement");
+        let mut stmt = self.conn.prepare(&sql).expect("Couldn't prepare statement");
         let (unban_balance_high_b, unban_balance_low_b) = BigIntDivider::deconstruct(
             (payment_thresholds.unban_below_gwei as i128) * WEIS_IN_GWEI,
         );
@@ -255,7 +371,7 @@ impl ReceivableDao for ReceivableDaoReal {
     fn custom_query(&self, custom_query: CustomQuery<i64>) -> Option<Vec<ReceivableAccount>> {
         let variant_top = TopStmConfig{
             limit_clause: "limit :limit_count",
-            gwei_min_resolution_clause: "where (balance_high_b > 0) or ((balance_high_b = 0) and (balance_low_b >= 1000000000))",
+            gwei_min_resolution_clause: "where ((balance_high_b > 0) or ((balance_high_b = 0) and (balance_low_b >= 1000000000)))",
             age_ordering_clause: "last_received_timestamp asc",
         };
         let variant_range = RangeStmConfig {
@@ -291,12 +407,21 @@ impl ReceivableDao for ReceivableDaoReal {
         )
     }
 
+    fn aging(&self, now: SystemTime) -> DebtAging {
+        debt_aging_from_table(
+            self.conn.as_ref(),
+            &Self::table_name(),
+            "last_received_timestamp",
+            now,
+        )
+    }
+
     fn account_status(&self, wallet: &Wallet) -> Option<ReceivableAccount> {
         let mut stmt = self
             .conn
-            .prepare(
-                "select wallet_address, balance_high_b, balance_low_b, last_received_timestamp from receivable where wallet_address = ?",
-            )
+            .prepare(&format!(
+                "select wallet_address, balance_high_b, balance_low_b, last_received_timestamp from receivable where wallet_address = ? and chain = {ACTIVE_CHAIN}",
+            ))
             .expect("Internal error");
         match stmt
             .query_row(&[&wallet], Self::create_receivable_account)
@@ -328,13 +453,16 @@ impl ReceivableDaoReal {
     ) -> Result<TransactionSafeWrapper<'txn>, ReceivableDaoError> {
         // The plus signs are intended. 'Subtraction' provided by the '.wei_change()' causes x of u128
         // to become -x of i128 which produces a negative i64 integer in the column for the high bytes
-        let main_sql = "update receivable set balance_high_b = balance_high_b + :balance_high_b, \
+        let main_sql = format!(
+            "update receivable set balance_high_b = balance_high_b + :balance_high_b, \
                  balance_low_b = balance_low_b + :balance_low_b, last_received_timestamp = :last_received \
-                 where wallet_address = :wallet";
-        let update_clause_with_compensated_overflow =
+                 where wallet_address = :wallet and chain = {ACTIVE_CHAIN}"
+        );
+        let update_clause_with_compensated_overflow = format!(
             "update receivable set balance_high_b = :balance_high_b, \
                  balance_low_b = :balance_low_b, last_received_timestamp = :last_received \
-                 where wallet_address = :wallet";
+                 where wallet_address = :wallet and chain = {ACTIVE_CHAIN}"
+        );
 
         match received_payments.iter().try_for_each(|received_payment| {
             let last_received_timestamp = to_time_t(timestamp);
@@ -352,11 +480,11 @@ impl ReceivableDaoReal {
 
             let result = big_int_db_processor.execute(
                 Either::Right(&txn),
-                BigIntSqlConfig::new(main_sql, update_clause_with_compensated_overflow, params),
+                BigIntSqlConfig::new(&main_sql, &update_clause_with_compensated_overflow, params),
             );
 
             match result {
-                Ok(_) => Ok(()),
+                Ok(_) => Self::record_received_payment(&txn, received_payment),
                 Err(BigIntDatabaseError::General(err_msg)) => {
                     Err(ReceivableDaoError::RusqliteError(err_msg))
                 }
@@ -370,6 +498,43 @@ impl ReceivableDaoReal {
         }
     }
 
+    // Credited payments are remembered for as long as a chain reorganization can still undo them
+    fn record_received_payment(
+        txn: &TransactionSafeWrapper,
+        received_payment: &BlockchainTransaction,
+    ) -> Result<(), ReceivableDaoError> {
+        let (amount_high_b, amount_low_b) =
+            BigIntDivider::deconstruct(checked_conversion::<u128, i128>(
+                received_payment.wei_amount,
+            ));
+        txn.prepare(
+            "insert into received_payment (block_number, wallet_address, amount_high_b, amount_low_b) \
+             values (:block_number, :wallet, :amount_high_b, :amount_low_b)",
+        )?
+        .execute(named_params! {
+            ":block_number": checked_conversion::<u64, i64>(received_payment.block_number),
+            ":wallet": &received_payment.from,
+            ":amount_high_b": amount_high_b,
+            ":amount_low_b": amount_low_b,
+        })?;
+        Ok(())
+    }
+
+    fn create_received_payment(row: &Row) -> rusqlite::Result<BlockchainTransaction> {
+        let block_number: i64 = row.get(0)?;
+        let from: Wallet = row.get(1)?;
+        let amount_high_b: i64 = row.get(2)?;
+        let amount_low_b: i64 = row.get(3)?;
+        Ok(BlockchainTransaction {
+            block_number: checked_conversion::<i64, u64>(block_number),
+            from,
+            wei_amount: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                amount_high_b,
+                amount_low_b,
+            )),
+        })
+    }
+
     fn verify_possibly_unknown_wallet(
         txn: &TransactionSafeWrapper,
         logger: &Logger,
@@ -399,7 +564,7 @@ impl ReceivableDaoReal {
             .expect("checking on a row failed")
     }
 
-    fn create_receivable_account(row: &Row) -> rusqlite::Result<ReceivableAccount> {
+    pub(crate) fn create_receivable_account(row: &Row) -> rusqlite::Result<ReceivableAccount> {
         let wallet: Result<Wallet, Error> = row.get(0);
         let balance_high_b_result = row.get(1);
         let balance_low_b_result = row.get(2);
@@ -433,13 +598,14 @@ impl ReceivableDaoReal {
                  last_received_timestamp
              from
                  receivable
-             {} {}
+             {} {} and chain = {}
              order by
                  {},
                  {}
              {}",
             feeder.main_where_clause,
             feeder.where_clause_extension,
+            ACTIVE_CHAIN,
             feeder.order_by_first_param,
             feeder.order_by_second_param,
             feeder.limit_clause
@@ -487,13 +653,17 @@ impl TableNameDAO for ReceivableDaoReal {
     fn table_name() -> String {
         String::from("receivable")
     }
+
+    fn row_scope_opt() -> Option<String> {
+        Some(format!("chain = {ACTIVE_CHAIN}"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::accountant::db_access_objects::utils::{
-        from_time_t, now_time_t, to_time_t, CustomQuery,
+        from_time_t, now_time_t, to_time_t, AgingBucket, CustomQuery,
     };
     use crate::accountant::gwei_to_wei;
     use crate::accountant::test_utils::{
@@ -628,6 +798,51 @@ mod tests {
         assert_eq!(to_time_t(status.last_received_timestamp), payment_time_t);
     }
 
+    #[test]
+    fn more_money_receivable_leaves_the_account_of_the_same_wallet_on_another_chain_alone() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "more_money_receivable_leaves_the_account_of_the_same_wallet_on_another_chain_alone",
+        );
+        let now = SystemTime::now();
+        let wallet = make_wallet("booga");
+        let boxed_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        boxed_conn
+            .prepare(
+                "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+                 last_received_timestamp, chain) values (?, 0, 5000, 1500000000, \
+                 'another-chain')",
+            )
+            .unwrap()
+            .execute([wallet.to_string()])
+            .unwrap();
+        let subject = ReceivableDaoReal::new(boxed_conn);
+
+        subject.more_money_receivable(now, &wallet, 1234).unwrap();
+        subject.more_money_receivable(now, &wallet, 1000).unwrap();
+
+        let status = subject.account_status(&wallet).unwrap();
+        assert_eq!(status.balance_wei, 2234);
+        let account_on_another_chain = subject
+            .conn
+            .prepare(
+                "select balance_high_b, balance_low_b, last_received_timestamp from receivable \
+                 where wallet_address = ? and chain = 'another-chain'",
+            )
+            .unwrap()
+            .query_row([wallet.to_string()], |row| {
+                Ok((
+                    row.get::<usize, i64>(0)?,
+                    row.get::<usize, i64>(1)?,
+                    row.get::<usize, i64>(2)?,
+                ))
+            })
+            .unwrap();
+        assert_eq!(account_on_another_chain, (0, 5000, 1500000000));
+    }
+
     #[test]
     fn more_money_receivable_works_for_existing_address_without_overflow() {
         //testing correctness of the main SQL
@@ -910,6 +1125,331 @@ mod tests {
         log_handler.exists_no_log_containing(&format!("ERROR: {test_name}: "));
     }
 
+    #[test]
+    fn more_money_received_remembers_credited_payments_but_not_those_from_unknown_payers() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "more_money_received_remembers_credited_payments_but_not_those_from_unknown_payers",
+        );
+        let debtor = make_wallet("debtor");
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor, 10_000)
+            .unwrap();
+        let credited_payment = BlockchainTransaction {
+            block_number: 1_000,
+            from: debtor.clone(),
+            wei_amount: 1_234,
+        };
+        let big_credited_payment = BlockchainTransaction {
+            block_number: 1_010,
+            from: debtor,
+            wei_amount: i64::MAX as u128 + 5,
+        };
+        let unknown_payment = BlockchainTransaction {
+            block_number: 1_005,
+            from: make_wallet("stranger"),
+            wei_amount: 4_321,
+        };
+        let transactions = vec![
+            credited_payment.clone(),
+            unknown_payment,
+            big_credited_payment.clone(),
+        ];
+
+        let txn = subject.more_money_received(SystemTime::now(), &transactions);
+        txn.commit().unwrap();
+
+        assert_eq!(
+            subject.received_payments_between(1_000, 1_010),
+            vec![credited_payment.clone(), big_credited_payment]
+        );
+        assert_eq!(
+            subject.received_payments_between(999, 1_009),
+            vec![credited_payment]
+        );
+        assert_eq!(subject.received_payments_between(1_011, 2_000), vec![]);
+    }
+
+    #[test]
+    fn received_payments_from_returns_those_of_the_given_debtor_by_block() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "received_payments_from_returns_those_of_the_given_debtor_by_block",
+        );
+        let debtor = make_wallet("debtor");
+        let other_debtor = make_wallet("other_debtor");
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor, 10_000)
+            .unwrap();
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &other_debtor, 10_000)
+            .unwrap();
+        let later_payment = BlockchainTransaction {
+            block_number: 1_010,
+            from: debtor.clone(),
+            wei_amount: 2_222,
+        };
+        let other_payment = BlockchainTransaction {
+            block_number: 1_005,
+            from: other_debtor,
+            wei_amount: 3_333,
+        };
+        let earlier_payment = BlockchainTransaction {
+            block_number: 1_000,
+            from: debtor.clone(),
+            wei_amount: 1_111,
+        };
+        let txn = subject.more_money_received(
+            SystemTime::now(),
+            &[
+                later_payment.clone(),
+                other_payment,
+                earlier_payment.clone(),
+            ],
+        );
+        txn.commit().unwrap();
+
+        let result = subject.received_payments_from(&debtor);
+
+        assert_eq!(result, vec![earlier_payment, later_payment]);
+        assert_eq!(
+            subject.received_payments_from(&make_wallet("stranger")),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn reverse_received_payments_gives_back_the_debt_and_forgets_one_payment_per_reversal() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "reverse_received_payments_gives_back_the_debt_and_forgets_one_payment_per_reversal",
+        );
+        let debtor = make_wallet("debtor");
+        let previous_timestamp = from_time_t(to_time_t(SystemTime::now()) - 5_000);
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(previous_timestamp, &debtor, 50_000)
+            .unwrap();
+        let repeated_payment = BlockchainTransaction {
+            block_number: 1_000,
+            from: debtor.clone(),
+            wei_amount: 7_000,
+        };
+        let other_payment = BlockchainTransaction {
+            block_number: 1_001,
+            from: debtor.clone(),
+            wei_amount: 3_000,
+        };
+        let transactions = vec![
+            repeated_payment.clone(),
+            repeated_payment.clone(),
+            other_payment.clone(),
+        ];
+        let payment_time = from_time_t(to_time_t(SystemTime::now()) - 1_000);
+        let txn = subject.more_money_received(payment_time, &transactions);
+        txn.commit().unwrap();
+
+        let result = subject.reverse_received_payments(&[repeated_payment.clone()]);
+
+        assert_eq!(result, Ok(()));
+        let status = subject.account_status(&debtor).unwrap();
+        assert_eq!(status.balance_wei, 50_000 - 7_000 - 7_000 - 3_000 + 7_000);
+        assert_eq!(status.last_received_timestamp, payment_time);
+        assert_eq!(
+            subject.received_payments_between(0, u64::MAX >> 1),
+            vec![repeated_payment, other_payment]
+        );
+    }
+
+    fn insert_receivable_record_on_another_chain(
+        conn: &dyn ConnectionWrapper,
+        wallet: &Wallet,
+        balance: i128,
+    ) {
+        let (high_bytes, low_bytes) = BigIntDivider::deconstruct(balance);
+        let params: &[&dyn ToSql] = &[wallet, &high_bytes, &low_bytes];
+        conn.prepare(
+            "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+             last_received_timestamp, chain) values (?, ?, ?, 1500000000, 'another-chain')",
+        )
+        .unwrap()
+        .execute(params)
+        .unwrap();
+    }
+
+    fn receivable_record_on_another_chain(
+        conn: &dyn ConnectionWrapper,
+        wallet: &Wallet,
+    ) -> (i128, i64) {
+        conn.prepare(
+            "select balance_high_b, balance_low_b, last_received_timestamp from receivable \
+             where wallet_address = ? and chain = 'another-chain'",
+        )
+        .unwrap()
+        .query_row([wallet], |row| {
+            Ok((
+                BigIntDivider::reconstitute(row.get(0)?, row.get(1)?),
+                row.get(2)?,
+            ))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn more_money_received_hitting_overflow_leaves_the_same_wallet_on_another_chain_alone() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "more_money_received_hitting_overflow_leaves_the_same_wallet_on_another_chain_alone",
+        );
+        let debtor = make_wallet("debtor");
+        let payment_time = from_time_t(to_time_t(SystemTime::now()) - 1_000);
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        //seeded first so that a select unaware of the chain would read this row
+        insert_receivable_record_on_another_chain(subject.conn.as_ref(), &debtor, 5_000);
+        let initial = i64::MAX as u128 - 123;
+        let received = i64::MAX as u128 - 200;
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor, initial)
+            .unwrap();
+        let transactions = vec![BlockchainTransaction {
+            from: debtor.clone(),
+            wei_amount: received,
+            block_number: 35_u64,
+        }];
+
+        let txn = subject.more_money_received(payment_time, &transactions);
+
+        txn.commit().unwrap();
+        let status = subject.account_status(&debtor).unwrap();
+        assert_eq!(status.balance_wei, initial as i128 - received as i128);
+        assert_eq!(status.last_received_timestamp, payment_time);
+        assert_eq!(
+            receivable_record_on_another_chain(subject.conn.as_ref(), &debtor),
+            (5_000, 1500000000)
+        );
+    }
+
+    #[test]
+    fn reverse_received_payments_leaves_the_same_wallet_on_another_chain_alone() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "reverse_received_payments_leaves_the_same_wallet_on_another_chain_alone",
+        );
+        let debtor = make_wallet("debtor");
+        let payment_time = from_time_t(to_time_t(SystemTime::now()) - 1_000);
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        insert_receivable_record_on_another_chain(subject.conn.as_ref(), &debtor, 5_000);
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor, 50_000)
+            .unwrap();
+        let payment = BlockchainTransaction {
+            block_number: 1_000,
+            from: debtor.clone(),
+            wei_amount: 7_000,
+        };
+        let txn = subject.more_money_received(payment_time, &[payment.clone()]);
+        txn.commit().unwrap();
+
+        let result = subject.reverse_received_payments(&[payment]);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(subject.account_status(&debtor).unwrap().balance_wei, 50_000);
+        assert_eq!(
+            receivable_record_on_another_chain(subject.conn.as_ref(), &debtor),
+            (5_000, 1500000000)
+        );
+    }
+
+    #[test]
+    fn reverse_received_payments_handles_error() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "reverse_received_payments_handles_error",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        {
+            let mut stmt = conn.prepare("drop table received_payment").unwrap();
+            stmt.execute([]).unwrap();
+        }
+        let debtor = make_wallet("debtor");
+        let mut subject = ReceivableDaoReal::new(conn);
+        subject
+            .more_money_receivable(SystemTime::now(), &debtor, 50_000)
+            .unwrap();
+
+        let result = subject.reverse_received_payments(&[BlockchainTransaction {
+            block_number: 1_000,
+            from: debtor.clone(),
+            wei_amount: 7_000,
+        }]);
+
+        assert_eq!(
+            result,
+            Err(ReceivableDaoError::RusqliteError(
+                "SqliteFailure(Error { code: Unknown, extended_code: 1 }, Some(\"no such table: received_payment\"))"
+                    .to_string()
+            ))
+        );
+        assert_eq!(subject.account_status(&debtor).unwrap().balance_wei, 50_000);
+    }
+
+    #[test]
+    fn forget_received_payments_before_drops_only_older_payments() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "forget_received_payments_before_drops_only_older_payments",
+        );
+        let debtor = make_wallet("debtor");
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(SystemTime::now(), &debtor, 50_000)
+            .unwrap();
+        let make_payment = |block_number| BlockchainTransaction {
+            block_number,
+            from: debtor.clone(),
+            wei_amount: 1_000,
+        };
+        let transactions = vec![make_payment(99), make_payment(100), make_payment(101)];
+        let txn = subject.more_money_received(SystemTime::now(), &transactions);
+        txn.commit().unwrap();
+
+        let result = subject.forget_received_payments_before(100);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            subject.received_payments_between(0, 1_000),
+            vec![make_payment(100), make_payment(101)]
+        );
+    }
+
     #[test]
     fn more_money_received_general_db_error() {
         init_test_logging();
@@ -1031,6 +1571,7 @@ mod tests {
                     StmtTypeDirective::ExecuteProdCode,
                     StmtTypeDirective::ExecuteProdCode,
                     StmtTypeDirective::ExecuteProdCode,
+                    StmtTypeDirective::ExecuteProdCode,
                     StmtTypeDirective::UseAlteredStmt(
                         AlteredStmtBySQLOrigin::SQLIdenticalWithProdCode,
                     ),
@@ -1070,8 +1611,8 @@ mod tests {
         assert_eq!(panic_msg, &expected_panic_msg);
         let prepare_params = prepare_params_arc.lock().unwrap();
         // Asserting that we did perform the first transaction completely which is a process
-        // composed of three SQL statements if it includes handling an overflow like here
-        assert_eq!(&prepare_params[0..3],
+        // composed of four SQL statements if it includes handling an overflow like here
+        assert_eq!(&prepare_params[0..4],
             &[
               "update receivable set balance_high_b = balance_high_b + :balance_high_b, balance_low_b \
               = balance_low_b + :balance_low_b, last_received_timestamp = :last_received where wallet_address \
@@ -1079,7 +1620,9 @@ mod tests {
               "select balance_high_b, balance_low_b from receivable where wallet_address = \
               '0x0000000000000000000000000000000000616263'",
               "update receivable set balance_high_b = :balance_high_b, balance_low_b = :balance_low_b, \
-              last_received_timestamp = :last_received where wallet_address = :wallet"
+              last_received_timestamp = :last_received where wallet_address = :wallet",
+              "insert into received_payment (block_number, wallet_address, amount_high_b, amount_low_b) \
+              values (:block_number, :wallet, :amount_high_b, :amount_low_b)"
             ]);
         // The first transaction did not affect the db, was rolled back
         let account_status = receivable_dao.account_status(&first_wallet);
@@ -1140,10 +1683,10 @@ mod tests {
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
         let wallet = make_wallet("blah");
-        conn.prepare(
+        conn.prepare(&format!(
             "insert into receivable ( wallet_address, balance_high_b, balance_low_b, \
-            last_received_timestamp ) values ( ?, 111, 222, 111222333 )",
-        )
+            last_received_timestamp, chain ) values ( ?, 111, 222, 111222333, {ACTIVE_CHAIN} )"
+        ))
         .unwrap()
         .execute(&[&wallet])
         .unwrap();
@@ -1819,6 +2362,55 @@ mod tests {
         assert_eq!(total, 3_000_000_000)
     }
 
+    #[test]
+    fn aging_sorts_receivables_by_time_since_last_received_and_leaves_out_overpaid_ones() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "aging_sorts_receivables_by_time_since_last_received_and_leaves_out_overpaid_ones",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let insert = insert_account_by_separate_values;
+        let now = utils::now_time_t();
+        let day = 86_400;
+        insert(
+            &*conn,
+            "0x1111111111111111111111111111111111111111",
+            1_000,
+            now - 4 * day,
+        );
+        insert(
+            &*conn,
+            "0x2222222222222222222222222222222222222222",
+            2_000,
+            now - 7 * day + 1,
+        );
+        insert(
+            &*conn,
+            "0x3333333333333333333333333333333333333333",
+            -3_000,
+            now - 5 * day,
+        );
+        insert(&*conn, "0x4444444444444444444444444444444444444444", 0, now);
+        let subject = ReceivableDaoReal::new(conn);
+
+        let result = subject.aging(from_time_t(now));
+
+        assert_eq!(
+            result.buckets,
+            [
+                AgingBucket::default(),
+                AgingBucket::default(),
+                AgingBucket {
+                    accounts: 2,
+                    balance_wei: 3_000
+                },
+                AgingBucket::default(),
+            ]
+        )
+    }
+
     #[test]
     fn correctly_totals_zero_records() {
         let home_dir =
@@ -1849,7 +2441,7 @@ mod tests {
     }
 
     fn add_receivable_account(conn: &Box<dyn ConnectionWrapper>, account: &ReceivableAccount) {
-        let mut stmt = conn.prepare ("insert into receivable (wallet_address, balance_high_b, balance_low_b, last_received_timestamp) values (?, ?, ?, ?)").unwrap();
+        let mut stmt = conn.prepare (&format!("insert into receivable (wallet_address, balance_high_b, balance_low_b, last_received_timestamp, chain) values (?, ?, ?, ?, {ACTIVE_CHAIN})")).unwrap();
         let (high_bytes, low_bytes) = BigIntDivider::deconstruct(account.balance_wei);
         let params: &[&dyn ToSql] = &[
             &account.wallet,
@@ -1869,7 +2461,7 @@ mod tests {
         let (high_bytes, low_bytes) = BigIntDivider::deconstruct(balance);
         let params: &[&dyn ToSql] = &[&wallet, &high_bytes, &low_bytes, &timestamp];
         conn
-        .prepare("insert into receivable (wallet_address, balance_high_b, balance_low_b, last_received_timestamp) values (?, ?, ?, ?)")
+        .prepare(&format!("insert into receivable (wallet_address, balance_high_b, balance_low_b, last_received_timestamp, chain) values (?, ?, ?, ?, {ACTIVE_CHAIN})"))
         .unwrap()
         .execute(params)
         .unwrap();
@@ -1883,7 +2475,10 @@ mod tests {
     }
 
     fn receivable_read_only_conn(path: &Path) -> Connection {
-        trick_rusqlite_with_read_only_conn(path, DbInitializerReal::create_receivable_table)
+        trick_rusqlite_with_read_only_conn(path, |conn| {
+            DbInitializerReal::create_config_table(conn);
+            DbInitializerReal::create_receivable_table(conn)
+        })
     }
 
     fn custom_query_test_body_for_receivable<F>(
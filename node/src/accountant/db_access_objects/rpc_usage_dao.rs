@@ -0,0 +1,228 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::checked_conversion;
+use crate::accountant::db_access_objects::utils::VigilantRusqliteFlatten;
+use crate::database::rusqlite_wrappers::ConnectionWrapper;
+use rusqlite::{Row, ToSql};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RpcUsageDaoError {
+    RecordingFailed(String),
+    RecordDeletion(String),
+}
+
+// The requests sent to one endpoint of the blockchain service during one day (UTC, as YYYY-MM-DD)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RpcUsage {
+    pub endpoint: String,
+    pub day: String,
+    pub calls: u64,
+    pub weight: u64,
+}
+
+pub trait RpcUsageDao {
+    // Adds to what has been booked for the same endpoint and day already
+    fn add(&self, usage: &RpcUsage) -> Result<(), RpcUsageDaoError>;
+    // The latest day comes first
+    fn usage_since(&self, day: &str) -> Vec<RpcUsage>;
+    fn delete_before(&self, day: &str) -> Result<(), RpcUsageDaoError>;
+}
+
+pub struct RpcUsageDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl RpcUsageDao for RpcUsageDaoReal {
+    fn add(&self, usage: &RpcUsage) -> Result<(), RpcUsageDaoError> {
+        let calls = checked_conversion::<u64, i64>(usage.calls);
+        let weight = checked_conversion::<u64, i64>(usage.weight);
+        let params: &[&dyn ToSql] = &[&usage.endpoint, &usage.day, &calls, &weight];
+        match self
+            .conn
+            .prepare(
+                "insert into rpc_usage (endpoint, day, calls, weight) values (?, ?, ?, ?) \
+                 on conflict (endpoint, day) do update \
+                 set calls = calls + excluded.calls, weight = weight + excluded.weight",
+            )
+            .expect("Internal error")
+            .execute(params)
+        {
+            Ok(1) => Ok(()),
+            Ok(x) => panic!("expected 1 changed row but got {}", x),
+            Err(e) => Err(RpcUsageDaoError::RecordingFailed(e.to_string())),
+        }
+    }
+
+    fn usage_since(&self, day: &str) -> Vec<RpcUsage> {
+        let mut stm = self
+            .conn
+            .prepare(
+                "select endpoint, day, calls, weight from rpc_usage where day >= ? \
+                 order by day desc, endpoint",
+            )
+            .expect("Internal error");
+        stm.query_map([day], Self::usage_from_row)
+            .expect("rusqlite failure")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    fn delete_before(&self, day: &str) -> Result<(), RpcUsageDaoError> {
+        match self
+            .conn
+            .prepare("delete from rpc_usage where day < ?")
+            .expect("Internal error")
+            .execute([day])
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(RpcUsageDaoError::RecordDeletion(e.to_string())),
+        }
+    }
+}
+
+impl RpcUsageDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    fn usage_from_row(row: &Row) -> rusqlite::Result<RpcUsage> {
+        let calls: i64 = Self::get_with_expect(row, 2);
+        let weight: i64 = Self::get_with_expect(row, 3);
+        Ok(RpcUsage {
+            endpoint: Self::get_with_expect(row, 0),
+            day: Self::get_with_expect(row, 1),
+            calls: checked_conversion::<i64, u64>(calls),
+            weight: checked_conversion::<i64, u64>(weight),
+        })
+    }
+
+    fn get_with_expect<T: rusqlite::types::FromSql>(row: &Row, index: usize) -> T {
+        row.get(index).expect("database is corrupt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::database::rusqlite_wrappers::ConnectionWrapperReal;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::{Connection, OpenFlags};
+
+    fn make_usage(endpoint: &str, day: &str, calls: u64, weight: u64) -> RpcUsage {
+        RpcUsage {
+            endpoint: endpoint.to_string(),
+            day: day.to_string(),
+            calls,
+            weight,
+        }
+    }
+
+    #[test]
+    fn add_sums_usage_by_endpoint_and_day() {
+        let home_dir = ensure_node_home_directory_exists(
+            "rpc_usage_dao",
+            "add_sums_usage_by_endpoint_and_day",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = RpcUsageDaoReal::new(conn);
+
+        subject
+            .add(&make_usage("https://one.com/*****", "2024-03-01", 3, 60))
+            .unwrap();
+        subject
+            .add(&make_usage("https://one.com/*****", "2024-03-02", 4, 80))
+            .unwrap();
+        subject
+            .add(&make_usage("https://two.com", "2024-03-02", 1, 75))
+            .unwrap();
+        subject
+            .add(&make_usage("https://one.com/*****", "2024-03-02", 5, 1_000))
+            .unwrap();
+
+        assert_eq!(
+            subject.usage_since("2024-03-01"),
+            vec![
+                make_usage("https://one.com/*****", "2024-03-02", 9, 1_080),
+                make_usage("https://two.com", "2024-03-02", 1, 75),
+                make_usage("https://one.com/*****", "2024-03-01", 3, 60),
+            ]
+        );
+        assert_eq!(
+            subject.usage_since("2024-03-02"),
+            vec![
+                make_usage("https://one.com/*****", "2024-03-02", 9, 1_080),
+                make_usage("https://two.com", "2024-03-02", 1, 75),
+            ]
+        );
+        assert_eq!(subject.usage_since("2024-03-03"), vec![]);
+    }
+
+    #[test]
+    fn delete_before_keeps_the_given_day_and_later() {
+        let home_dir = ensure_node_home_directory_exists(
+            "rpc_usage_dao",
+            "delete_before_keeps_the_given_day_and_later",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = RpcUsageDaoReal::new(conn);
+        ["2024-02-28", "2024-02-29", "2024-03-01"]
+            .iter()
+            .for_each(|day| {
+                subject
+                    .add(&make_usage("https://one.com", day, 1, 10))
+                    .unwrap()
+            });
+
+        subject.delete_before("2024-02-29").unwrap();
+
+        assert_eq!(
+            subject.usage_since("2000-01-01"),
+            vec![
+                make_usage("https://one.com", "2024-03-01", 1, 10),
+                make_usage("https://one.com", "2024-02-29", 1, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn add_and_delete_before_handle_errors() {
+        let home_dir = ensure_node_home_directory_exists(
+            "rpc_usage_dao",
+            "add_and_delete_before_handle_errors",
+        );
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let subject = RpcUsageDaoReal::new(Box::new(ConnectionWrapperReal::new(conn_read_only)));
+
+        let add_result = subject.add(&make_usage("https://one.com", "2024-03-01", 1, 10));
+        let delete_result = subject.delete_before("2024-03-01");
+
+        assert_eq!(
+            add_result,
+            Err(RpcUsageDaoError::RecordingFailed(
+                "attempt to write a readonly database".to_string()
+            ))
+        );
+        assert_eq!(
+            delete_result,
+            Err(RpcUsageDaoError::RecordDeletion(
+                "attempt to write a readonly database".to_string()
+            ))
+        )
+    }
+}
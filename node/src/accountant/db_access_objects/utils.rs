@@ -1,17 +1,25 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
-use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::db_access_objects::payable_dao::{
+    InvoiceDispute, PayableAccount, PayableWriteOff,
+};
 use crate::accountant::db_access_objects::receivable_dao::ReceivableAccount;
 use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
-use crate::accountant::{checked_conversion, gwei_to_wei, sign_conversion};
+use crate::accountant::{checked_conversion, gwei_to_wei, sign_conversion, wei_to_gwei};
+use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+use crate::blockchain::blockchain_interface::data_structures::BlockchainTransaction;
 use crate::database::db_initializer::{
     connection_or_panic, DbInitializationConfig, DbInitializerReal,
 };
 use crate::database::rusqlite_wrappers::ConnectionWrapper;
 use crate::sub_lib::accountant::PaymentThresholds;
+use itertools::Itertools;
 use masq_lib::constants::WEIS_IN_GWEI;
+use masq_lib::logger::Logger;
 use masq_lib::messages::{
-    RangeQuery, TopRecordsConfig, TopRecordsOrdering, UiPayableAccount, UiReceivableAccount,
+    RangeQuery, TopRecordsConfig, TopRecordsOrdering, UiAgingBucket, UiAgingBuckets, UiDebtAging,
+    UiInvoiceDispute, UiPayableAccount, UiPayableDetail, UiPayableWriteOff, UiReceivableAccount,
+    UiReceivableDetail, UiReceivedPayment, UiUnconfirmedPayment,
 };
 use rusqlite::{Row, Statement, ToSql};
 use std::fmt::{Debug, Display};
@@ -254,7 +262,10 @@ impl<T: Copy> From<&RangeQuery<T>> for CustomQuery<T> {
     }
 }
 
-pub fn remap_payable_accounts(accounts: Vec<PayableAccount>) -> Vec<UiPayableAccount> {
+pub fn remap_payable_accounts(
+    accounts: Vec<PayableAccount>,
+    fingerprints: &[PendingPayableFingerprint],
+) -> Vec<UiPayableAccount> {
     accounts
         .into_iter()
         .map(|account| UiPayableAccount {
@@ -274,7 +285,14 @@ pub fn remap_payable_accounts(accounts: Vec<PayableAccount>) -> Vec<UiPayableAcc
             },
             pending_payable_hash_opt: account
                 .pending_payable_opt
+                .as_ref()
                 .map(|full_id| full_id.hash.to_string()),
+            pending_payment_opt: account.pending_payable_opt.as_ref().and_then(|id| {
+                fingerprints
+                    .iter()
+                    .find(|fingerprint| fingerprint.rowid == id.rowid)
+                    .map(remap_unconfirmed_payment)
+            }),
         })
         .collect()
 }
@@ -298,6 +316,89 @@ pub fn remap_receivable_accounts(accounts: Vec<ReceivableAccount>) -> Vec<UiRece
         .collect()
 }
 
+// Unlike the records of the queries, a single account may be less than a gwei; it shows as zero
+pub fn remap_payable_detail(
+    account: PayableAccount,
+    fingerprints: &[PendingPayableFingerprint],
+) -> UiPayableDetail {
+    UiPayableDetail {
+        balance_gwei: wei_to_gwei(account.balance_wei),
+        last_paid_age_s: to_age(account.last_paid_timestamp),
+        pending_payment_opt: account.pending_payable_opt.as_ref().and_then(|id| {
+            fingerprints
+                .iter()
+                .find(|fingerprint| fingerprint.rowid == id.rowid)
+                .map(remap_unconfirmed_payment)
+        }),
+    }
+}
+
+pub fn remap_receivable_detail(account: ReceivableAccount) -> UiReceivableDetail {
+    UiReceivableDetail {
+        balance_gwei: wei_to_gwei(account.balance_wei),
+        last_received_age_s: to_age(account.last_received_timestamp),
+    }
+}
+
+pub fn remap_received_payments(payments: Vec<BlockchainTransaction>) -> Vec<UiReceivedPayment> {
+    payments
+        .into_iter()
+        .map(|payment| UiReceivedPayment {
+            block_number: payment.block_number,
+            amount_gwei: wei_to_gwei(payment.wei_amount),
+        })
+        .collect()
+}
+
+pub fn remap_write_offs(write_offs: Vec<PayableWriteOff>) -> Vec<UiPayableWriteOff> {
+    write_offs
+        .into_iter()
+        .map(|write_off| UiPayableWriteOff {
+            amount_gwei: wei_to_gwei(write_off.balance_wei),
+            reason: write_off.reason,
+            banned: write_off.banned,
+            age_s: to_age(write_off.timestamp),
+        })
+        .collect()
+}
+
+pub fn remap_invoice_disputes(disputes: Vec<InvoiceDispute>) -> Vec<UiInvoiceDispute> {
+    let to_sec = |timestamp: SystemTime| checked_conversion::<i64, u64>(to_time_t(timestamp));
+    disputes
+        .into_iter()
+        .map(|dispute| UiInvoiceDispute {
+            creditor_wallet: dispute.creditor_wallet.to_string(),
+            period_start_sec: to_sec(dispute.period_start),
+            period_end_sec: to_sec(dispute.period_end),
+            invoiced_services: dispute.invoiced_services,
+            booked_services: dispute.booked_services,
+            // The creditor may have invoiced more than a u64 of gwei can hold
+            invoiced_gwei: sign_conversion::<u128, u64>(
+                dispute.invoiced_wei / WEIS_IN_GWEI as u128,
+            )
+            .unwrap_or(u64::MAX),
+            booked_gwei: wei_to_gwei(dispute.booked_wei),
+            received_at_sec: to_sec(dispute.received_at),
+        })
+        .collect()
+}
+
+pub fn remap_unconfirmed_payments(
+    fingerprints: Vec<PendingPayableFingerprint>,
+) -> Vec<UiUnconfirmedPayment> {
+    fingerprints.iter().map(remap_unconfirmed_payment).collect()
+}
+
+fn remap_unconfirmed_payment(fingerprint: &PendingPayableFingerprint) -> UiUnconfirmedPayment {
+    UiUnconfirmedPayment {
+        transaction_hash: format!("{:?}", fingerprint.hash),
+        amount_gwei: wei_to_gwei(fingerprint.amount),
+        age_s: to_age(fingerprint.timestamp),
+        attempt: fingerprint.attempt,
+        failure_opt: fingerprint.process_error.clone(),
+    }
+}
+
 fn to_age(timestamp: SystemTime) -> u64 {
     (to_time_t(SystemTime::now()) - to_time_t(timestamp)) as u64
 }
@@ -324,6 +425,43 @@ pub trait VigilantRusqliteFlatten {
 
 impl<T: Iterator<Item = rusqlite::Result<R>>, R> VigilantRusqliteFlatten for T {}
 
+// The chain the database was created for, which must be the chain of the Node as checked on startup.
+// Monetary rows stamped with any other chain are invisible to the DAOs
+pub const ACTIVE_CHAIN: &str = "(select value from config where name = 'chain_name')";
+
+pub const TABLES_WITH_CHAIN: [&str; 3] = ["payable", "receivable", "pending_payable"];
+
+pub fn count_rows_of_other_chains(conn: &dyn ConnectionWrapper) -> Vec<(&'static str, usize)> {
+    TABLES_WITH_CHAIN
+        .into_iter()
+        .map(|table| {
+            let select_stm = format!("select count(*) from {table} where chain != {ACTIVE_CHAIN}");
+            let count = conn
+                .prepare(&select_stm)
+                .expect("select stm error")
+                .query_row([], |row| row.get::<usize, i64>(0))
+                .expect("select query failed");
+            (table, count as usize)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+pub fn warn_about_rows_of_other_chains(conn: &dyn ConnectionWrapper, logger: &Logger) {
+    let rows_of_other_chains = count_rows_of_other_chains(conn);
+    if !rows_of_other_chains.is_empty() {
+        warning!(
+            logger,
+            "The database holds rows of another chain, which will be ignored: {}. Was it restored \
+             from a backup made for a different chain?",
+            rows_of_other_chains
+                .into_iter()
+                .map(|(table, count)| format!("{} in {}", count, table))
+                .join(", ")
+        )
+    }
+}
+
 pub fn sum_i128_values_from_table(
     conn: &dyn ConnectionWrapper,
     table: &str,
@@ -331,7 +469,9 @@ pub fn sum_i128_values_from_table(
     value_completer: fn(usize, &Row) -> rusqlite::Result<i128>,
 ) -> i128 {
     let mut row_number = 0;
-    let select_stm = format!("select {param_name}_high_b, {param_name}_low_b from {table}");
+    let select_stm = format!(
+        "select {param_name}_high_b, {param_name}_low_b from {table} where chain = {ACTIVE_CHAIN}"
+    );
     conn.prepare(&select_stm)
         .expect("select stm error")
         .query_map([], |row| {
@@ -343,6 +483,80 @@ pub fn sum_i128_values_from_table(
         .sum()
 }
 
+// Lower limits of the aging buckets after the first one: a day, three days and a week
+pub const DEBT_AGING_LIMITS_SEC: [i64; 3] = [86_400, 259_200, 604_800];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AgingBucket {
+    pub accounts: u64,
+    pub balance_wei: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DebtAging {
+    pub buckets: [AgingBucket; 4],
+}
+
+impl DebtAging {
+    fn add(&mut self, age_sec: i64, balance_wei: u128) {
+        let index = DEBT_AGING_LIMITS_SEC
+            .iter()
+            .take_while(|limit| age_sec >= **limit)
+            .count();
+        let bucket = &mut self.buckets[index];
+        bucket.accounts += 1;
+        bucket.balance_wei = bucket.balance_wei.saturating_add(balance_wei);
+    }
+}
+
+// Sorts the positive balances of the table by the age of the timestamp column, all in one pass
+pub fn debt_aging_from_table(
+    conn: &dyn ConnectionWrapper,
+    table: &str,
+    timestamp_column: &str,
+    now: SystemTime,
+) -> DebtAging {
+    let now_sec = to_time_t(now);
+    let select_stm = format!(
+        "select {timestamp_column}, balance_high_b, balance_low_b from {table} \
+         where chain = {ACTIVE_CHAIN}"
+    );
+    conn.prepare(&select_stm)
+        .expect("select stm error")
+        .query_map([], |row| {
+            let timestamp = row.get::<usize, i64>(0)?;
+            let balance = BigIntDivider::reconstitute(row.get(1)?, row.get(2)?);
+            Ok((timestamp, balance))
+        })
+        .expect("select query failed")
+        .vigilant_flatten()
+        .filter(|(_, balance)| *balance > 0)
+        .fold(DebtAging::default(), |mut aging, (timestamp, balance)| {
+            aging.add(now_sec - timestamp, balance as u128);
+            aging
+        })
+}
+
+pub fn remap_debt_aging(payable: DebtAging, receivable: DebtAging) -> UiDebtAging {
+    let remap_buckets = |aging: DebtAging| {
+        let [up_to_one_day, one_to_three_days, three_to_seven_days, over_seven_days] =
+            aging.buckets.map(|bucket| UiAgingBucket {
+                accounts: bucket.accounts,
+                balance_gwei: wei_to_gwei(bucket.balance_wei),
+            });
+        UiAgingBuckets {
+            up_to_one_day,
+            one_to_three_days,
+            three_to_seven_days,
+            over_seven_days,
+        }
+    };
+    UiDebtAging {
+        payable: remap_buckets(payable),
+        receivable: remap_buckets(receivable),
+    }
+}
+
 pub fn update_rows_and_return_valid_count(
     update_returning_stm: &mut Statement,
     update_row_validator: fn(&Row) -> rusqlite::Result<bool>,
@@ -435,17 +649,20 @@ impl ThresholdUtils {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::accountant::PendingPayableId;
+    use crate::database::db_initializer::DbInitializer;
     use crate::database::rusqlite_wrappers::ConnectionWrapperReal;
     use crate::sub_lib::accountant::DEFAULT_PAYMENT_THRESHOLDS;
     use crate::test_utils::make_wallet;
-    use itertools::Itertools;
     use masq_lib::constants::MASQ_TOTAL_SUPPLY;
     use masq_lib::messages::TopRecordsOrdering::Balance;
-    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
     use rusqlite::types::{ToSqlOutput, Value};
     use rusqlite::{Connection, OpenFlags};
     use std::collections::HashMap;
     use std::time::UNIX_EPOCH;
+    use web3::types::H256;
 
     #[test]
     fn set_age_constraints_works() {
@@ -528,7 +745,7 @@ mod tests {
                 pending_payable_opt: None,
             },
         ];
-        remap_payable_accounts(accounts);
+        remap_payable_accounts(accounts, &[]);
     }
 
     #[test]
@@ -973,4 +1190,360 @@ mod tests {
             function_to_validate_row_value,
         );
     }
+
+    #[test]
+    fn remap_invoice_disputes_works() {
+        let disputes = vec![
+            InvoiceDispute {
+                creditor_wallet: make_wallet("creditor"),
+                period_start: from_time_t(1_000_000),
+                period_end: from_time_t(1_003_600),
+                invoiced_services: 15,
+                booked_services: 12,
+                invoiced_wei: 5_555_555_555_000_000_000,
+                booked_wei: 4_444_444_444_000_000_000,
+                received_at: from_time_t(1_003_660),
+            },
+            InvoiceDispute {
+                creditor_wallet: make_wallet("greedy"),
+                period_start: from_time_t(2_000_000),
+                period_end: from_time_t(2_003_600),
+                invoiced_services: 1,
+                booked_services: 1,
+                invoiced_wei: u128::MAX,
+                booked_wei: 1_000_000_000,
+                received_at: from_time_t(2_003_660),
+            },
+        ];
+
+        let result = remap_invoice_disputes(disputes);
+
+        assert_eq!(
+            result,
+            vec![
+                UiInvoiceDispute {
+                    creditor_wallet: make_wallet("creditor").to_string(),
+                    period_start_sec: 1_000_000,
+                    period_end_sec: 1_003_600,
+                    invoiced_services: 15,
+                    booked_services: 12,
+                    invoiced_gwei: 5_555_555_555,
+                    booked_gwei: 4_444_444_444,
+                    received_at_sec: 1_003_660,
+                },
+                UiInvoiceDispute {
+                    creditor_wallet: make_wallet("greedy").to_string(),
+                    period_start_sec: 2_000_000,
+                    period_end_sec: 2_003_600,
+                    invoiced_services: 1,
+                    booked_services: 1,
+                    invoiced_gwei: u64::MAX,
+                    booked_gwei: 1,
+                    received_at_sec: 2_003_660,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn debt_aging_sorts_balances_into_buckets_by_age() {
+        let mut subject = DebtAging::default();
+
+        subject.add(-10, 1);
+        subject.add(86_399, 10);
+        subject.add(86_400, 100);
+        subject.add(259_199, 1_000);
+        subject.add(259_200, 10_000);
+        subject.add(604_799, 100_000);
+        subject.add(604_800, 1_000_000);
+        subject.add(i64::MAX, 10_000_000);
+
+        assert_eq!(
+            subject.buckets,
+            [
+                AgingBucket {
+                    accounts: 2,
+                    balance_wei: 11
+                },
+                AgingBucket {
+                    accounts: 2,
+                    balance_wei: 1_100
+                },
+                AgingBucket {
+                    accounts: 2,
+                    balance_wei: 110_000
+                },
+                AgingBucket {
+                    accounts: 2,
+                    balance_wei: 11_000_000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn remap_debt_aging_works() {
+        let bucket = |accounts: u64, balance_wei: u128| AgingBucket {
+            accounts,
+            balance_wei,
+        };
+        let payable = DebtAging {
+            buckets: [
+                bucket(1, 1_000_000_000),
+                bucket(2, 2_000_000_000),
+                bucket(0, 0),
+                bucket(4, 4_000_000_000),
+            ],
+        };
+        let receivable = DebtAging {
+            buckets: [
+                bucket(0, 0),
+                bucket(0, 0),
+                bucket(3, 3_999_999_999),
+                bucket(0, 0),
+            ],
+        };
+
+        let result = remap_debt_aging(payable, receivable);
+
+        let ui_bucket = |accounts: u64, balance_gwei: u64| UiAgingBucket {
+            accounts,
+            balance_gwei,
+        };
+        assert_eq!(
+            result,
+            UiDebtAging {
+                payable: UiAgingBuckets {
+                    up_to_one_day: ui_bucket(1, 1),
+                    one_to_three_days: ui_bucket(2, 2),
+                    three_to_seven_days: ui_bucket(0, 0),
+                    over_seven_days: ui_bucket(4, 4),
+                },
+                receivable: UiAgingBuckets {
+                    up_to_one_day: ui_bucket(0, 0),
+                    one_to_three_days: ui_bucket(0, 0),
+                    three_to_seven_days: ui_bucket(3, 3),
+                    over_seven_days: ui_bucket(0, 0),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn remap_payable_accounts_attaches_the_fingerprint_of_the_pending_payment() {
+        let now = SystemTime::now();
+        let hash = H256::from_low_u64_be(0x3e7);
+        let accounts = vec![
+            PayableAccount {
+                wallet: make_wallet("abc123"),
+                balance_wei: 4_888_123_457,
+                last_paid_timestamp: now,
+                pending_payable_opt: Some(PendingPayableId::new(7, hash)),
+            },
+            PayableAccount {
+                wallet: make_wallet("def456"),
+                balance_wei: 5_000_000_000,
+                last_paid_timestamp: now,
+                pending_payable_opt: None,
+            },
+        ];
+        let fingerprints = vec![
+            PendingPayableFingerprint {
+                rowid: 6,
+                timestamp: now - Duration::from_secs(10),
+                hash: H256::from_low_u64_be(0x3e6),
+                attempt: 1,
+                amount: 1_000_000_000,
+                process_error: None,
+                replacement_basis_opt: None,
+            },
+            PendingPayableFingerprint {
+                rowid: 7,
+                timestamp: now - Duration::from_secs(300),
+                hash,
+                attempt: 4,
+                amount: 4_888_123_457,
+                process_error: None,
+                replacement_basis_opt: None,
+            },
+        ];
+
+        let result = remap_payable_accounts(accounts, &fingerprints);
+
+        let pending_payment = result[0].pending_payment_opt.as_ref().unwrap();
+        assert_eq!(pending_payment.transaction_hash, format!("{:?}", hash));
+        assert_eq!(pending_payment.amount_gwei, 4);
+        assert!(pending_payment.age_s >= 300 && pending_payment.age_s <= 301);
+        assert_eq!(pending_payment.attempt, 4);
+        assert_eq!(pending_payment.failure_opt, None);
+        assert_eq!(result[1].pending_payment_opt, None);
+    }
+
+    #[test]
+    fn remap_unconfirmed_payments_works() {
+        let now = SystemTime::now();
+        let fingerprints = vec![PendingPayableFingerprint {
+            rowid: 5,
+            timestamp: now - Duration::from_secs(600),
+            hash: H256::from_low_u64_be(0x1c8),
+            attempt: 3,
+            amount: 7_777_000_000_001,
+            process_error: Some("ERROR".to_string()),
+            replacement_basis_opt: None,
+        }];
+
+        let result = remap_unconfirmed_payments(fingerprints);
+
+        assert_eq!(result.len(), 1);
+        let payment = &result[0];
+        assert_eq!(
+            payment.transaction_hash,
+            "0x00000000000000000000000000000000000000000000000000000000000001c8"
+        );
+        assert_eq!(payment.amount_gwei, 7_777);
+        assert!(payment.age_s >= 600 && payment.age_s <= 601);
+        assert_eq!(payment.attempt, 3);
+        assert_eq!(payment.failure_opt, Some("ERROR".to_string()));
+    }
+
+    fn insert_rows_of_chain(conn: &dyn ConnectionWrapper, chain: &str, salt: u64) {
+        let statements = [
+            format!(
+                "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+                 last_paid_timestamp, pending_payable_rowid, chain) values ('0x{salt}1', 0, \
+                 5000, 1500000000, null, '{chain}')"
+            ),
+            format!(
+                "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+                 last_received_timestamp, chain) values ('0x{salt}2', 0, 6000, 1500000000, \
+                 '{chain}')"
+            ),
+            format!(
+                "insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, \
+                 payable_timestamp, attempt, process_error, chain) values ('0x{salt}3', 0, 7000, \
+                 1500000000, 1, null, '{chain}')"
+            ),
+        ];
+        statements.iter().for_each(|stm| {
+            conn.prepare(stm).unwrap().execute([]).unwrap();
+        })
+    }
+
+    #[test]
+    fn count_rows_of_other_chains_ignores_rows_of_the_active_chain() {
+        let home_dir = ensure_node_home_directory_exists(
+            "db_access_objects_utils",
+            "count_rows_of_other_chains_ignores_rows_of_the_active_chain",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        insert_rows_of_chain(
+            conn.as_ref(),
+            TEST_DEFAULT_CHAIN.rec().literal_identifier,
+            1,
+        );
+
+        let result = count_rows_of_other_chains(conn.as_ref());
+
+        assert_eq!(result, vec![])
+    }
+
+    #[test]
+    fn count_rows_of_other_chains_finds_rows_of_any_other_chain() {
+        let home_dir = ensure_node_home_directory_exists(
+            "db_access_objects_utils",
+            "count_rows_of_other_chains_finds_rows_of_any_other_chain",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        insert_rows_of_chain(
+            conn.as_ref(),
+            TEST_DEFAULT_CHAIN.rec().literal_identifier,
+            1,
+        );
+        insert_rows_of_chain(conn.as_ref(), "eth-mainnet", 2);
+        insert_rows_of_chain(conn.as_ref(), "polygon-mainnet", 3);
+        conn.prepare("delete from receivable where chain = 'eth-mainnet'")
+            .unwrap()
+            .execute([])
+            .unwrap();
+
+        let result = count_rows_of_other_chains(conn.as_ref());
+
+        assert_eq!(
+            result,
+            vec![("payable", 2), ("receivable", 1), ("pending_payable", 2)]
+        )
+    }
+
+    #[test]
+    fn warn_about_rows_of_other_chains_keeps_quiet_if_there_are_none() {
+        init_test_logging();
+        let test_name = "warn_about_rows_of_other_chains_keeps_quiet_if_there_are_none";
+        let home_dir = ensure_node_home_directory_exists("db_access_objects_utils", test_name);
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        insert_rows_of_chain(
+            conn.as_ref(),
+            TEST_DEFAULT_CHAIN.rec().literal_identifier,
+            1,
+        );
+
+        warn_about_rows_of_other_chains(conn.as_ref(), &Logger::new(test_name));
+
+        TestLogHandler::new().exists_no_log_containing(&format!("WARN: {test_name}"));
+    }
+
+    #[test]
+    fn warn_about_rows_of_other_chains_reports_where_they_were_found() {
+        init_test_logging();
+        let test_name = "warn_about_rows_of_other_chains_reports_where_they_were_found";
+        let home_dir = ensure_node_home_directory_exists("db_access_objects_utils", test_name);
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        insert_rows_of_chain(conn.as_ref(), "eth-mainnet", 1);
+        conn.prepare("delete from payable")
+            .unwrap()
+            .execute([])
+            .unwrap();
+
+        warn_about_rows_of_other_chains(conn.as_ref(), &Logger::new(test_name));
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: The database holds rows of another chain, which will be ignored: \
+             1 in receivable, 1 in pending_payable. Was it restored from a backup made for a \
+             different chain?"
+        ));
+    }
+
+    #[test]
+    fn sum_i128_values_from_table_sums_only_rows_of_the_active_chain() {
+        let home_dir = ensure_node_home_directory_exists(
+            "db_access_objects_utils",
+            "sum_i128_values_from_table_sums_only_rows_of_the_active_chain",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        insert_rows_of_chain(
+            conn.as_ref(),
+            TEST_DEFAULT_CHAIN.rec().literal_identifier,
+            1,
+        );
+        insert_rows_of_chain(conn.as_ref(), "eth-mainnet", 2);
+
+        let result =
+            sum_i128_values_from_table(conn.as_ref(), "receivable", "balance", |_, row| {
+                Ok(BigIntDivider::reconstitute(
+                    row.get::<usize, i64>(0).unwrap(),
+                    row.get::<usize, i64>(1).unwrap(),
+                ))
+            });
+
+        assert_eq!(result, 6000)
+    }
 }
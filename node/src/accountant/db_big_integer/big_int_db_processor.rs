@@ -240,12 +240,15 @@ impl<'params, T: TableNameDAO> BigIntSqlConfig<'params, T> {
 
     fn select_sql(&self) -> String {
         format!(
-            "select {}, {} from {} where {} = '{}'",
+            "select {}, {} from {} where {} = '{}'{}",
             &self.params.wei_change_params.high_bytes.name[1..],
             &self.params.wei_change_params.low_bytes.name[1..],
             T::table_name(),
             self.params.table_unique_key,
-            self.key_param_value()
+            self.key_param_value(),
+            T::row_scope_opt()
+                .map(|scope| format!(" and {}", scope))
+                .unwrap_or_default()
         )
     }
 
@@ -557,6 +560,11 @@ impl<'params> SQLParams<'params> {
 
 pub trait TableNameDAO: Debug + Send {
     fn table_name() -> String;
+
+    // Narrows down the rows sharing the unique key, for tables where the key alone isn't unique
+    fn row_scope_opt() -> Option<String> {
+        None
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -0,0 +1,207 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::{
+    BlockchainAgent, TransactionFeeShortage,
+};
+use crate::sub_lib::wei::Wei;
+use std::collections::VecDeque;
+
+pub const SCAN_HISTORY_LENGTH: usize = 10;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GasRunway {
+    pub scans_left: u64,
+    pub transaction_fee_balance: Wei,
+    pub shortage: TransactionFeeShortage,
+}
+
+// Guesses how many more payable scans the transaction fee balance can pay for,
+// assuming upcoming scans are about as big as the recent ones
+pub struct GasRunwayEstimator {
+    alert_threshold_scans: u64,
+    transaction_counts: VecDeque<usize>,
+}
+
+impl GasRunwayEstimator {
+    pub fn new(alert_threshold_scans: u64) -> Self {
+        Self {
+            alert_threshold_scans,
+            transaction_counts: VecDeque::with_capacity(SCAN_HISTORY_LENGTH),
+        }
+    }
+
+    pub fn record_scan(&mut self, transaction_count: usize) {
+        if self.transaction_counts.len() == SCAN_HISTORY_LENGTH {
+            self.transaction_counts.pop_front();
+        }
+        self.transaction_counts.push_back(transaction_count)
+    }
+
+    // Only yields the runway if it is shorter than the alert threshold
+    pub fn check(&self, agent: &dyn BlockchainAgent) -> Option<GasRunway> {
+        if self.alert_threshold_scans == 0 {
+            return None;
+        }
+        let typical_transaction_count = self.typical_transaction_count();
+        if typical_transaction_count == 0 {
+            return None;
+        }
+        let fee_per_scan = agent.estimated_transaction_fee_total(typical_transaction_count);
+        if fee_per_scan == Wei::ZERO {
+            return None;
+        }
+        let transaction_fee_balance = Wei::saturating_from_u256(
+            agent
+                .consuming_wallet_balances()
+                .transaction_fee_balance_in_minor_units,
+        );
+        let scans_left = u64::try_from(transaction_fee_balance.as_u128() / fee_per_scan.as_u128())
+            .unwrap_or(u64::MAX);
+        (scans_left < self.alert_threshold_scans).then(|| GasRunway {
+            scans_left,
+            transaction_fee_balance,
+            shortage: TransactionFeeShortage::diagnose(
+                agent,
+                transaction_fee_balance,
+                fee_per_scan.saturating_mul(self.alert_threshold_scans as u128),
+            ),
+        })
+    }
+
+    fn typical_transaction_count(&self) -> usize {
+        let scans = self.transaction_counts.len();
+        if scans == 0 {
+            return 0;
+        }
+        let total: usize = self.transaction_counts.iter().sum();
+        (total + scans - 1) / scans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
+    use std::sync::{Arc, Mutex};
+    use web3::types::U256;
+
+    fn make_agent(
+        fee_per_scan_wei: u128,
+        transaction_fee_balance_wei: u128,
+    ) -> BlockchainAgentMock {
+        BlockchainAgentMock::default()
+            .estimated_transaction_fee_total_result(Wei::new(fee_per_scan_wei))
+            .consuming_wallet_balances_result(ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(transaction_fee_balance_wei),
+                masq_token_balance_in_minor_units: U256::from(1_000_000_000_u64),
+            })
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(SCAN_HISTORY_LENGTH, 10)
+    }
+
+    #[test]
+    fn check_alerts_when_runway_is_shorter_than_threshold() {
+        let estimated_transaction_fee_total_params_arc = Arc::new(Mutex::new(vec![]));
+        let agent = make_agent(1_000, 4_999)
+            .estimated_transaction_fee_total_params(&estimated_transaction_fee_total_params_arc);
+        let mut subject = GasRunwayEstimator::new(5);
+        subject.record_scan(2);
+        subject.record_scan(3);
+
+        let result = subject.check(&agent);
+
+        assert_eq!(
+            result,
+            Some(GasRunway {
+                scans_left: 4,
+                transaction_fee_balance: Wei::new(4_999),
+                shortage: TransactionFeeShortage::NoBaseFee
+            })
+        );
+        let estimated_transaction_fee_total_params =
+            estimated_transaction_fee_total_params_arc.lock().unwrap();
+        assert_eq!(*estimated_transaction_fee_total_params, vec![3])
+    }
+
+    #[test]
+    fn check_tells_whether_the_runway_is_short_only_because_of_a_gas_price_spike() {
+        let agent = make_agent(1_000, 4_999)
+            .agreed_fee_per_computation_unit_result(Wei::new(400))
+            .base_fee_per_computation_unit_result(Some(Wei::new(100)));
+        let mut subject = GasRunwayEstimator::new(5);
+        subject.record_scan(3);
+
+        let result = subject.check(&agent);
+
+        assert_eq!(
+            result,
+            Some(GasRunway {
+                scans_left: 4,
+                transaction_fee_balance: Wei::new(4_999),
+                shortage: TransactionFeeShortage::GasPriceSpike {
+                    base_fee: Wei::new(100),
+                    fee_total_at_base_fee: Wei::new(1_250)
+                }
+            })
+        )
+    }
+
+    #[test]
+    fn check_stays_quiet_when_runway_reaches_threshold() {
+        let agent = make_agent(1_000, 5_000);
+        let mut subject = GasRunwayEstimator::new(5);
+        subject.record_scan(1);
+
+        let result = subject.check(&agent);
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn check_stays_quiet_when_alert_is_turned_off() {
+        let agent = BlockchainAgentMock::default();
+        let mut subject = GasRunwayEstimator::new(0);
+        subject.record_scan(5);
+
+        let result = subject.check(&agent);
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn check_stays_quiet_without_transactions_to_learn_from() {
+        let agent = BlockchainAgentMock::default();
+        let mut subject = GasRunwayEstimator::new(5);
+        subject.record_scan(0);
+
+        let result = subject.check(&agent);
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn check_stays_quiet_if_transactions_cost_nothing() {
+        let agent = make_agent(0, 0);
+        let mut subject = GasRunwayEstimator::new(5);
+        subject.record_scan(2);
+
+        let result = subject.check(&agent);
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn only_recent_scans_are_remembered() {
+        let mut subject = GasRunwayEstimator::new(5);
+        subject.record_scan(100);
+        (0..SCAN_HISTORY_LENGTH).for_each(|_| subject.record_scan(2));
+
+        let result = subject.typical_transaction_count();
+
+        assert_eq!(result, 2)
+    }
+}
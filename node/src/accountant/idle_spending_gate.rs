@@ -0,0 +1,259 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableDao;
+use crate::accountant::gwei_to_wei;
+use crate::accountant::scanners::payment_urgency::payment_deadline;
+use crate::sub_lib::accountant::{IdleSpendingFreeze, PaymentThresholds};
+use masq_lib::logger::Logger;
+use std::time::{Duration, SystemTime};
+
+// Holds back the scheduled payable scans while the Node is idle, judged by the reports of routing
+// and of services consumed that the proxies send the Accountant. The Node counts as busy since it
+// started up, so the first idle period runs from then.
+pub struct IdleSpendingGate {
+    freeze_opt: Option<IdleSpendingFreeze>,
+    last_traffic: SystemTime,
+    frozen: bool,
+}
+
+impl IdleSpendingGate {
+    pub fn new(freeze_opt: Option<IdleSpendingFreeze>, now: SystemTime) -> Self {
+        Self {
+            freeze_opt,
+            last_traffic: now,
+            frozen: false,
+        }
+    }
+
+    pub fn record_traffic(&mut self, now: SystemTime, logger: &Logger) {
+        self.last_traffic = self.last_traffic.max(now);
+        if self.frozen {
+            self.frozen = false;
+            info!(logger, "Traffic is back; payable scans resume")
+        }
+    }
+
+    // The nearest deadline is looked up only once the Node has been idle long enough to freeze
+    pub fn lets_scan_through(
+        &mut self,
+        now: SystemTime,
+        nearest_deadline_opt: &dyn Fn() -> Option<SystemTime>,
+        logger: &Logger,
+    ) -> bool {
+        let freeze = match self.freeze_opt {
+            Some(freeze) => freeze,
+            None => return true,
+        };
+        let idle_sec = now
+            .duration_since(self.last_traffic)
+            .unwrap_or(Duration::from_secs(0))
+            .as_secs();
+        if idle_sec < freeze.idle_sec {
+            return true;
+        }
+        if !self.frozen {
+            self.frozen = true;
+            info!(
+                logger,
+                "No traffic routed or consumed for {} sec; payable scans are paused until it comes \
+                 back",
+                idle_sec
+            )
+        }
+        if let Some(deadline) = nearest_deadline_opt() {
+            let sec_to_deadline = deadline
+                .duration_since(now)
+                .unwrap_or(Duration::from_secs(0))
+                .as_secs();
+            if sec_to_deadline <= freeze.deadline_margin_sec {
+                warning!(
+                    logger,
+                    "Scanning for payables although the Node is idle; a payment deadline is only \
+                     {} sec away",
+                    sec_to_deadline
+                );
+                return true;
+            }
+        }
+        debug!(logger, "Payable scan skipped; the Node is idle");
+        false
+    }
+}
+
+// The nearest of the deadlines of the debts big enough for their creditors to ban us for
+pub fn nearest_payment_deadline(
+    payable_dao: &dyn PayableDao,
+    our_payment_thresholds: &PaymentThresholds,
+) -> Option<SystemTime> {
+    let payables = payable_dao.non_pending_payables();
+    if payables.is_empty() {
+        return None;
+    }
+    let creditor_thresholds = payable_dao.creditor_thresholds();
+    payables
+        .iter()
+        .flat_map(|payable| {
+            let thresholds = creditor_thresholds
+                .get(&payable.wallet)
+                .unwrap_or(our_payment_thresholds);
+            (payable.balance_wei > gwei_to_wei(thresholds.permanent_debt_allowed_gwei))
+                .then(|| payment_deadline(payable, thresholds))
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::test_utils::PayableDaoMock;
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::time::UNIX_EPOCH;
+
+    const FREEZE: IdleSpendingFreeze = IdleSpendingFreeze {
+        idle_sec: 3_600,
+        deadline_margin_sec: 600,
+    };
+
+    fn no_deadline() -> Option<SystemTime> {
+        None
+    }
+
+    #[test]
+    fn without_a_freeze_every_scan_runs() {
+        let start = SystemTime::now();
+        let mut subject = IdleSpendingGate::new(None, start);
+
+        let result = subject.lets_scan_through(
+            start + Duration::from_secs(1_000_000),
+            &no_deadline,
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result, true);
+    }
+
+    #[test]
+    fn scans_pause_after_the_idle_period_and_resume_with_traffic() {
+        init_test_logging();
+        let test_name = "scans_pause_after_the_idle_period_and_resume_with_traffic";
+        let logger = Logger::new(test_name);
+        let start = SystemTime::now();
+        let at = |sec: u64| start + Duration::from_secs(sec);
+        let deadline_lookups = Cell::new(0);
+        let nearest_deadline_opt = || {
+            deadline_lookups.set(deadline_lookups.get() + 1);
+            None
+        };
+        let mut subject = IdleSpendingGate::new(Some(FREEZE), start);
+
+        let still_busy = subject.lets_scan_through(at(3_599), &nearest_deadline_opt, &logger);
+        let idle = subject.lets_scan_through(at(3_600), &nearest_deadline_opt, &logger);
+        let still_idle = subject.lets_scan_through(at(7_200), &nearest_deadline_opt, &logger);
+        subject.record_traffic(at(7_300), &logger);
+        let busy_again = subject.lets_scan_through(at(7_400), &nearest_deadline_opt, &logger);
+
+        assert_eq!(still_busy, true);
+        assert_eq!(idle, false);
+        assert_eq!(still_idle, false);
+        assert_eq!(busy_again, true);
+        assert_eq!(deadline_lookups.get(), 2);
+        let tlh = TestLogHandler::new();
+        tlh.assert_logs_contain_in_order(vec![
+            &format!(
+                "INFO: {test_name}: No traffic routed or consumed for 3600 sec; payable scans are \
+                 paused until it comes back"
+            ),
+            &format!("INFO: {test_name}: Traffic is back; payable scans resume"),
+        ]);
+        tlh.exists_no_log_containing(&format!(
+            "INFO: {test_name}: No traffic routed or consumed for 7200 sec"
+        ));
+    }
+
+    #[test]
+    fn a_deadline_within_the_margin_lets_the_scan_through_while_idle() {
+        init_test_logging();
+        let test_name = "a_deadline_within_the_margin_lets_the_scan_through_while_idle";
+        let logger = Logger::new(test_name);
+        let start = SystemTime::now();
+        let now = start + Duration::from_secs(5_000);
+        let mut subject = IdleSpendingGate::new(Some(FREEZE), start);
+
+        let distant =
+            subject.lets_scan_through(now, &|| Some(now + Duration::from_secs(601)), &logger);
+        let near =
+            subject.lets_scan_through(now, &|| Some(now + Duration::from_secs(600)), &logger);
+        let overdue =
+            subject.lets_scan_through(now, &|| Some(now - Duration::from_secs(1)), &logger);
+
+        assert_eq!(distant, false);
+        assert_eq!(near, true);
+        assert_eq!(overdue, true);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Scanning for payables although the Node is idle; a payment \
+             deadline is only 600 sec away"
+        ));
+    }
+
+    #[test]
+    fn traffic_reported_out_of_order_does_not_shorten_the_busy_period() {
+        let start = SystemTime::now();
+        let mut subject = IdleSpendingGate::new(Some(FREEZE), start);
+        let logger = Logger::new("test");
+        subject.record_traffic(start + Duration::from_secs(1_000), &logger);
+
+        subject.record_traffic(start + Duration::from_secs(10), &logger);
+
+        let result =
+            subject.lets_scan_through(start + Duration::from_secs(4_599), &no_deadline, &logger);
+        assert_eq!(result, true);
+    }
+
+    #[test]
+    fn nearest_payment_deadline_weighs_each_debt_by_the_thresholds_it_is_paid_by() {
+        let our_thresholds = PaymentThresholds {
+            maturity_threshold_sec: 1_000,
+            payment_grace_period_sec: 1_000,
+            permanent_debt_allowed_gwei: 100,
+            ..PaymentThresholds::default()
+        };
+        let creditor_thresholds = PaymentThresholds {
+            maturity_threshold_sec: 100,
+            payment_grace_period_sec: 100,
+            ..our_thresholds
+        };
+        let make_payable = |name: &str, balance_gwei: u64, last_paid_sec: u64| PayableAccount {
+            wallet: make_wallet(name),
+            balance_wei: gwei_to_wei(balance_gwei),
+            last_paid_timestamp: UNIX_EPOCH + Duration::from_secs(last_paid_sec),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(vec![
+                make_payable("ours", 101, 500),
+                make_payable("too_small_to_ban_for", 100, 0),
+                make_payable("strict_creditor", 101, 1_000),
+            ])
+            .creditor_thresholds_result(HashMap::from([(
+                make_wallet("strict_creditor"),
+                creditor_thresholds,
+            )]));
+
+        let result = nearest_payment_deadline(&payable_dao, &our_thresholds);
+
+        assert_eq!(result, Some(UNIX_EPOCH + Duration::from_secs(1_200)));
+    }
+
+    #[test]
+    fn nearest_payment_deadline_is_unknown_without_debts() {
+        let payable_dao = PayableDaoMock::new().non_pending_payables_result(vec![]);
+
+        let result = nearest_payment_deadline(&payable_dao, &PaymentThresholds::default());
+
+        assert_eq!(result, None);
+    }
+}
@@ -1,51 +1,126 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod accounts_file;
+pub mod consumption_forecast;
 pub mod db_access_objects;
 pub mod db_big_integer;
 pub mod financials;
+pub mod gas_runway;
+pub mod idle_spending_gate;
+pub mod nonce_reconciliation;
+#[cfg(test)]
+mod payable_pipeline_tests;
+pub mod payable_scan_report;
 pub mod payment_adjuster;
+pub mod payment_decisions;
+pub mod payment_notices;
+pub mod price_feed;
+pub mod repair_plan;
 pub mod scanners;
+pub mod stuck_batch_bumper;
+pub mod usage_invoices;
+pub mod wallet_selector;
 
 #[cfg(test)]
 pub mod test_utils;
 
 use core::fmt::Debug;
-use masq_lib::constants::{SCAN_ERROR, WEIS_IN_GWEI};
+use masq_lib::constants::{
+    ACCOUNTS_EXPORT_ERROR, ACCOUNTS_IMPORT_ERROR, ACCOUNT_DETAIL_ERROR, CONSISTENCY_REPAIR_ERROR,
+    DEFAULT_MAX_PENDING_AGE_SEC, PAYABLE_WRITE_OFF_ERROR, PAYMENT_RETRY_ERROR, SCAN_ERROR,
+    WEIS_IN_GWEI, WHY_NOT_PAID_ERROR,
+};
 use std::cell::{Ref, RefCell};
 
-use crate::accountant::db_access_objects::payable_dao::{PayableDao, PayableDaoError};
+use crate::accountant::accounts_file::{read_accounts_file, write_accounts_file};
+use crate::accountant::db_access_objects::accounts_transfer_dao::{
+    AccountsSnapshot, AccountsTransferDao, AccountsTransferDaoError,
+};
+use crate::accountant::db_access_objects::banned_dao::BAN_CACHE;
+use crate::accountant::db_access_objects::consistency_dao::{ConsistencyDao, ConsistencyDaoError};
+use crate::accountant::db_access_objects::outbound_payment_dao::{
+    OutboundPayment, OutboundPaymentDao, OutboundPaymentStatus,
+};
+use crate::accountant::db_access_objects::payable_dao::{
+    PayableAccount, PayableDao, PayableDaoError,
+};
 use crate::accountant::db_access_objects::pending_payable_dao::PendingPayableDao;
 use crate::accountant::db_access_objects::receivable_dao::{ReceivableDao, ReceivableDaoError};
 use crate::accountant::db_access_objects::utils::{
-    remap_payable_accounts, remap_receivable_accounts, CustomQuery, DaoFactoryReal,
+    remap_debt_aging, remap_invoice_disputes, remap_payable_accounts, remap_payable_detail,
+    remap_receivable_accounts, remap_receivable_detail, remap_received_payments,
+    remap_unconfirmed_payments, remap_write_offs, CustomQuery, DaoFactoryReal,
 };
+use crate::accountant::consumption_forecast::ConsumptionForecaster;
+use crate::accountant::gas_runway::{GasRunway, GasRunwayEstimator};
+use crate::accountant::idle_spending_gate::{nearest_payment_deadline, IdleSpendingGate};
+use crate::accountant::price_feed::{format_fiat, make_price_feed, PriceFeed};
+use crate::accountant::stuck_batch_bumper::StuckBatchBumper;
 use crate::accountant::financials::visibility_restricted_module::{
     check_query_is_within_tech_limits, financials_entry_check,
 };
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::{
     BlockchainAgentWithContextMessage, QualifiedPayablesMessage,
 };
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::{
+    BlockchainAgent, TransactionFeeShortage,
+};
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{
+    BlockchainAgentSnapshot, PayableScanMode,
+};
+use crate::accountant::payable_scan_report::{
+    PayableScanReport, SKIPPED_BY_ADJUSTMENT, SKIPPED_BY_ADJUSTMENT_INVARIANTS,
+    SKIPPED_BY_GAS_PRICE_CEILING, SKIPPED_BY_PAYMENT_WINDOW, SKIPPED_BY_SPEND_RATE_LIMIT,
+};
+use crate::accountant::payment_decisions::PaymentDecisions;
+use crate::accountant::payment_notices::PaymentNotices;
+use crate::accountant::nonce_reconciliation::{
+    write_nonce_anomaly_report, NonceAnomalyReport, NonceReconciliation,
+};
+use crate::accountant::repair_plan::RepairPlans;
+use crate::accountant::scanners::creditor_hold::CreditorReachability;
+use crate::accountant::scanners::spend_rate_guard::SpendRateDeferral;
+use crate::accountant::scanners::scan_queue::ScanState;
 use crate::accountant::scanners::{BeginScanError, ScanSchedulers, Scanners};
-use crate::blockchain::blockchain_bridge::{BlockMarker, PendingPayableFingerprint, PendingPayableFingerprintSeeds, RetrieveTransactions};
+use crate::accountant::usage_invoices::{ServiceKind, UsageInvoices};
+use crate::accountant::wallet_selector::{make_wallet_selector, WalletSelector};
+use crate::blockchain::blockchain_bridge::{BlockMarker, PendingPayableFingerprint, PendingPayableFingerprintSeeds, PendingPayableReplacement, ReplacementBasis, RetrieveTransactions};
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
 use crate::blockchain::blockchain_interface::data_structures::errors::PayableTransactionError;
 use crate::blockchain::blockchain_interface::data_structures::{
-    BlockchainTransaction, ProcessedPayableFallible,
+    BlockchainTransaction, ProcessedPayableFallible, RecheckedTransactions,
 };
 use crate::bootstrapper::BootstrapperConfig;
 use crate::database::db_initializer::DbInitializationConfig;
 use crate::sub_lib::accountant::AccountantSubs;
 use crate::sub_lib::accountant::DaoFactories;
 use crate::sub_lib::accountant::FinancialStatistics;
+use crate::sub_lib::accountant::PaymentNotice_0v1;
+use crate::sub_lib::accountant::ReportConfirmedNonce;
+use crate::sub_lib::accountant::ReportCreditorReachabilityMessage;
+use crate::sub_lib::accountant::ReportCreditorThresholdsMessage;
+use crate::sub_lib::accountant::ReportExitFailoverMessage;
 use crate::sub_lib::accountant::ReportExitServiceProvidedMessage;
+use crate::sub_lib::accountant::ReportPaymentNoticeMessage;
 use crate::sub_lib::accountant::ReportRoutingServiceProvidedMessage;
 use crate::sub_lib::accountant::ReportServicesConsumedMessage;
-use crate::sub_lib::accountant::{MessageIdGenerator, MessageIdGeneratorReal};
-use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
-use crate::sub_lib::neighborhood::{ConfigChange, ConfigChangeMsg};
+use crate::sub_lib::accountant::ReportUsageInvoiceMessage;
+use crate::sub_lib::accountant::{
+    MessageIdGenerator, MessageIdGeneratorReal, PaymentThresholds, ScanId,
+};
+use crate::sub_lib::blockchain_bridge::{
+    OutboundPaymentsInstructions, ReplacePendingPayable, VoidAbandonedTransaction,
+};
+use crate::sub_lib::neighborhood::{
+    ConfigChange, ConfigChangeMsg, SendPaymentNoticeMessage, SendUsageInvoiceMessage,
+    ShutdownBarrierMessage,
+};
 use crate::sub_lib::peer_actors::{BindMessage, StartMessage};
-use crate::sub_lib::utils::{handle_ui_crash_request, NODE_MAILBOX_CAPACITY};
-use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::utils::{
+    handle_ui_crash_request, NotifyLaterHandle, NotifyLaterHandleReal, NODE_MAILBOX_CAPACITY,
+};
+use crate::sub_lib::wallet::{Wallet, WalletError};
+use crate::sub_lib::wei::Wei;
 use actix::Actor;
 use actix::Addr;
 use actix::AsyncContext;
@@ -58,47 +133,108 @@ use itertools::Itertools;
 use masq_lib::crash_point::CrashPoint;
 use masq_lib::logger::Logger;
 use masq_lib::messages::UiFinancialsResponse;
-use masq_lib::messages::{FromMessageBody, ToMessageBody, UiFinancialsRequest};
 use masq_lib::messages::{
-    QueryResults, ScanType, UiFinancialStatistics, UiPayableAccount, UiReceivableAccount,
-    UiScanRequest,
+    finance_capabilities, FinanceEnvelope, FromMessageBody, ToMessageBody,
+    UiFinanceHandshakeRequest, UiFinanceHandshakeResponse, UiFinancialsRequest,
+    FINANCE_PROTOCOL_VERSION,
+};
+use masq_lib::messages::{
+    QueryResults, ScanType, UiAccountDetailRequest, UiAccountDetailResponse, UiAnomaly,
+    UiApproximateFiatTotals, UiCheckConsistencyRequest, UiCheckConsistencyResponse,
+    UiDisputesRequest, UiDisputesResponse, UiExportAccountsRequest, UiExportAccountsResponse,
+    UiFinancialStatistics, UiImportAccountsRequest, UiImportAccountsResponse,
+    UiObserveOnlyPayables, UiPayableAccount, UiReceivableAccount,
+    UiRepairConsistencyRequest, UiRepairConsistencyResponse, UiRetryFailedPaymentsRequest,
+    UiRetryFailedPaymentsResponse, UiScanRequest, UiScanResponse, UiServiceFeeTopUpBroadcast,
+    UiSpendRateLimitBroadcast, UiTransactionFeeRunwayBroadcast, UiTriggerPayableScanRequest,
+    UiTriggerPayableScanResponse, UiWhyNotPaidRequest, UiWhyNotPaidResponse,
+    UiWriteOffPayableRequest, UiWriteOffPayableResponse,
 };
 use masq_lib::ui_gateway::MessageTarget::ClientId;
+use masq_lib::ui_gateway::MessageTarget;
 use masq_lib::ui_gateway::{MessageBody, MessagePath};
 use masq_lib::ui_gateway::{NodeFromUiMessage, NodeToUiMessage};
+use masq_lib::type_obfuscation::Obfuscated;
 use masq_lib::utils::ExpectValue;
 use std::any::type_name;
+use std::collections::{HashMap, HashSet};
 #[cfg(test)]
 use std::default::Default;
 use std::fmt::Display;
 use std::ops::{Div, Mul};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::time::SystemTime;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 use web3::types::H256;
-use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::TransactionReceiptResult;
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionReceiptResult, TxReceipt, TxStatus};
 
 pub const CRASH_KEY: &str = "ACCOUNTANT";
-pub const DEFAULT_PENDING_TOO_LONG_SEC: u64 = 21_600; //6 hours
+pub const DEFAULT_PENDING_TOO_LONG_SEC: u64 = DEFAULT_MAX_PENDING_AGE_SEC; //6 hours
+pub const SCAN_RETRY_BASE_DELAY: Duration = Duration::from_secs(10);
+// By how much the void transaction outbids the abandoned one it takes the nonce of
+pub const VOID_GAS_PRICE_BUMP_PERCENT: u8 = 15;
+const PAYABLES_BEING_SCANNED: &str =
+    "Payables are being scanned right now; try again after the scan";
 
 pub struct Accountant {
     suppress_initial_scans: bool,
+    scan_pipelining: bool,
     consuming_wallet_opt: Option<Wallet>,
+    additional_consuming_wallets: Vec<Wallet>,
+    wallet_selector: Box<dyn WalletSelector>,
+    gas_runway_estimator: GasRunwayEstimator,
+    consumption_forecaster: ConsumptionForecaster,
+    // How long the service fee balance should last; zero if nobody cares
+    top_up_horizon_hours: u64,
+    price_feed: Box<dyn PriceFeed>,
     earning_wallet: Wallet,
+    earning_wallet_rotation: Vec<Wallet>,
     payable_dao: Box<dyn PayableDao>,
     receivable_dao: Box<dyn ReceivableDao>,
     pending_payable_dao: Box<dyn PendingPayableDao>,
+    accounts_transfer_dao: Box<dyn AccountsTransferDao>,
+    consistency_dao: Box<dyn ConsistencyDao>,
+    outbound_payment_dao: Box<dyn OutboundPaymentDao>,
+    // The instructions handed over to the BlockchainBridge last, if they made it into the queue
+    outbound_instructions_rowid_opt: Option<u64>,
+    // Transactions of queued payments still waiting for their confirmation
+    submitted_outbound_payments: HashSet<H256>,
+    repair_plans: RepairPlans,
+    // Kept while a payable scan demanded by an operator is running
+    payable_scan_report_opt: Option<PayableScanReport>,
     crashable: bool,
     scanners: Scanners,
+    // Only one payable scan runs at a time; this is the id of the latest one
+    payable_scan_id: ScanId,
     scan_schedulers: ScanSchedulers,
+    scan_retry_handle: Box<dyn NotifyLaterHandle<RetryScan, Accountant>>,
+    consecutive_retryable_scan_failures: HashMap<ScanType, u32>,
+    payment_thresholds: Rc<RefCell<PaymentThresholds>>,
     financial_statistics: Rc<RefCell<FinancialStatistics>>,
+    creditor_reachability: Rc<RefCell<CreditorReachability>>,
+    abandoned_transactions: Rc<RefCell<Vec<PendingPayableFingerprint>>>,
+    stuck_batch_bumper: StuckBatchBumper,
+    idle_spending_gate: IdleSpendingGate,
+    payment_notices: PaymentNotices,
+    payment_decisions: PaymentDecisions,
+    usage_invoices: UsageInvoices,
     outbound_payments_instructions_sub_opt: Option<Recipient<OutboundPaymentsInstructions>>,
     qualified_payables_sub_opt: Option<Recipient<QualifiedPayablesMessage>>,
     retrieve_transactions_sub_opt: Option<Recipient<RetrieveTransactions>>,
     request_transaction_receipts_subs_opt: Option<Recipient<RequestTransactionReceipts>>,
+    void_abandoned_transaction_sub_opt: Option<Recipient<VoidAbandonedTransaction>>,
+    replace_pending_payable_sub_opt: Option<Recipient<ReplacePendingPayable>>,
     report_inbound_payments_sub_opt: Option<Recipient<ReceivedPayments>>,
     report_sent_payables_sub_opt: Option<Recipient<SentPayables>>,
+    send_payment_notice_sub_opt: Option<Recipient<SendPaymentNoticeMessage>>,
+    send_usage_invoice_sub_opt: Option<Recipient<SendUsageInvoiceMessage>>,
+    shutdown_barrier_sub_opt: Option<Recipient<ShutdownBarrierMessage>>,
     ui_message_sub_opt: Option<Recipient<NodeToUiMessage>>,
+    // The version of the finance protocol each UI client settled on in a handshake
+    finance_versions: HashMap<u64, u32>,
+    // Where the report of transactions the Node has no record of is left
+    data_directory: PathBuf,
     message_id_generator: Box<dyn MessageIdGenerator>,
     logger: Logger,
 }
@@ -131,6 +267,8 @@ pub struct ReceivedPayments {
     pub timestamp: SystemTime,
     pub new_start_block: BlockMarker,
     pub transactions: Vec<BlockchainTransaction>,
+    pub rechecked_opt: Option<RecheckedTransactions>,
+    pub pending: Vec<BlockchainTransaction>,
     pub response_skeleton_opt: Option<ResponseSkeleton>,
 }
 
@@ -160,6 +298,13 @@ pub struct ScanError {
     pub scan_type: ScanType,
     pub response_skeleton_opt: Option<ResponseSkeleton>,
     pub msg: String,
+    pub retryable: bool,
+}
+
+// An extra, off-schedule attempt at a scheduled scan that failed for a transient reason
+#[derive(Debug, Clone, Copy, Message, PartialEq, Eq)]
+pub struct RetryScan {
+    pub scan_type: ScanType,
 }
 
 impl Handler<BindMessage> for Accountant {
@@ -183,11 +328,14 @@ impl Handler<StartMessage> for Accountant {
     type Result = ();
 
     fn handle(&mut self, _msg: StartMessage, ctx: &mut Self::Context) -> Self::Result {
+        self.reconcile_outbound_payments();
+        self.propose_repairs_of_anomalies();
         if self.suppress_initial_scans {
             info!(
                 &self.logger,
                 "Started with --scans off; declining to begin database and blockchain scans"
             );
+            self.check_receipts_left_in_flight();
         } else {
             debug!(
                 &self.logger,
@@ -211,6 +359,9 @@ impl Handler<ReceivedPayments> for Accountant {
     type Result = ();
 
     fn handle(&mut self, msg: ReceivedPayments, _ctx: &mut Self::Context) -> Self::Result {
+        self.consecutive_retryable_scan_failures
+            .remove(&ScanType::Receivables);
+        self.payment_notices.reconcile(&msg, &self.logger);
         if let Some(node_to_ui_msg) = self.scanners.receivable.finish_scan(msg, &self.logger) {
             self.ui_message_sub_opt
                 .as_ref()
@@ -218,6 +369,7 @@ impl Handler<ReceivedPayments> for Accountant {
                 .try_send(node_to_ui_msg)
                 .expect("UIGateway is dead");
         }
+        self.begin_queued_scans();
     }
 }
 
@@ -229,7 +381,8 @@ impl Handler<BlockchainAgentWithContextMessage> for Accountant {
         msg: BlockchainAgentWithContextMessage,
         _ctx: &mut Self::Context,
     ) -> Self::Result {
-        self.handle_payable_payment_setup(msg)
+        self.handle_payable_payment_setup(msg);
+        self.begin_queued_scans();
     }
 }
 
@@ -237,13 +390,23 @@ impl Handler<SentPayables> for Accountant {
     type Result = ();
 
     fn handle(&mut self, msg: SentPayables, _ctx: &mut Self::Context) -> Self::Result {
-        if let Some(node_to_ui_msg) = self.scanners.payable.finish_scan(msg, &self.logger) {
+        let logger = self.payable_scan_id.span(&self.logger);
+        let demanded_report_opt = self
+            .take_payable_scan_report(msg.response_skeleton_opt)
+            .map(|report| (report.response_skeleton, report.finish(&msg)));
+        self.mark_outbound_payments_submitted(&msg, &logger);
+        self.payment_decisions.finish(&msg);
+        let node_to_ui_msg_opt = self.scanners.payable.finish_scan(msg, &logger);
+        if let Some((response_skeleton, response)) = demanded_report_opt {
+            self.send_payable_scan_report(response_skeleton, Ok(response))
+        } else if let Some(node_to_ui_msg) = node_to_ui_msg_opt {
             self.ui_message_sub_opt
                 .as_ref()
                 .expect("UIGateway is not bound")
                 .try_send(node_to_ui_msg)
                 .expect("UIGateway is dead");
         }
+        self.begin_queued_scans();
     }
 }
 
@@ -251,6 +414,9 @@ impl Handler<ScanForPayables> for Accountant {
     type Result = ();
 
     fn handle(&mut self, msg: ScanForPayables, ctx: &mut Self::Context) -> Self::Result {
+        if self.scan_pipelining {
+            self.check_receipts_alongside_payable_scan();
+        }
         self.handle_request_of_scan_for_payable(msg.response_skeleton_opt);
         self.schedule_next_scan(ScanType::Payables, ctx);
     }
@@ -277,11 +443,17 @@ impl Handler<ScanForReceivables> for Accountant {
 impl Handler<ScanError> for Accountant {
     type Result = ();
 
-    fn handle(&mut self, scan_error: ScanError, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, scan_error: ScanError, ctx: &mut Self::Context) -> Self::Result {
         error!(self.logger, "Received ScanError: {:?}", scan_error);
+        let demanded_report_opt = match scan_error.scan_type {
+            ScanType::Payables => self.take_payable_scan_report(scan_error.response_skeleton_opt),
+            _ => None,
+        };
         match scan_error.scan_type {
             ScanType::Payables => {
-                self.scanners.payable.mark_as_ended(&self.logger);
+                let logger = self.payable_scan_id.span(&self.logger);
+                self.scanners.payable.mark_as_ended(&logger);
+                self.payment_decisions.fail_remaining(&scan_error.msg);
             }
             ScanType::PendingPayables => {
                 self.scanners.pending_payable.mark_as_ended(&self.logger);
@@ -290,19 +462,24 @@ impl Handler<ScanError> for Accountant {
                 self.scanners.receivable.mark_as_ended(&self.logger);
             }
         };
+        if scan_error.response_skeleton_opt.is_none() {
+            self.consider_retrying_scan(scan_error.scan_type, scan_error.retryable, ctx);
+        }
+        self.begin_queued_scans();
         if let Some(response_skeleton) = scan_error.response_skeleton_opt {
+            let error = format!(
+                "{:?} scan failed: '{}'",
+                scan_error.scan_type, scan_error.msg
+            );
+            if demanded_report_opt.is_some() {
+                return self.send_payable_scan_report(response_skeleton, Err(error));
+            }
             let error_msg = NodeToUiMessage {
                 target: ClientId(response_skeleton.client_id),
                 body: MessageBody {
                     opcode: "scan".to_string(),
                     path: MessagePath::Conversation(response_skeleton.context_id),
-                    payload: Err((
-                        SCAN_ERROR,
-                        format!(
-                            "{:?} scan failed: '{}'",
-                            scan_error.scan_type, scan_error.msg
-                        ),
-                    )),
+                    payload: Err((SCAN_ERROR, error)),
                 },
             };
             error!(self.logger, "Sending UiScanResponse: {:?}", error_msg);
@@ -315,6 +492,19 @@ impl Handler<ScanError> for Accountant {
     }
 }
 
+impl Handler<RetryScan> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, msg: RetryScan, _ctx: &mut Self::Context) -> Self::Result {
+        // the regular schedule keeps running on its own; nothing new is scheduled here
+        match msg.scan_type {
+            ScanType::Payables => self.handle_request_of_scan_for_payable(None),
+            ScanType::PendingPayables => self.handle_request_of_scan_for_pending_payable(None),
+            ScanType::Receivables => self.handle_request_of_scan_for_receivable(None),
+        }
+    }
+}
+
 impl Handler<ReportRoutingServiceProvidedMessage> for Accountant {
     type Result = ();
 
@@ -351,6 +541,80 @@ impl Handler<ReportServicesConsumedMessage> for Accountant {
     }
 }
 
+impl Handler<ReportCreditorThresholdsMessage> for Accountant {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ReportCreditorThresholdsMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_report_creditor_thresholds_message(msg);
+    }
+}
+
+impl Handler<ReportCreditorReachabilityMessage> for Accountant {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ReportCreditorReachabilityMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_report_creditor_reachability_message(msg);
+    }
+}
+
+impl Handler<ReportExitFailoverMessage> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReportExitFailoverMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_report_exit_failover_message(msg);
+    }
+}
+
+impl Handler<ReportPaymentNoticeMessage> for Accountant {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ReportPaymentNoticeMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_report_payment_notice_message(msg);
+    }
+}
+
+impl Handler<ReportUsageInvoiceMessage> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReportUsageInvoiceMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_report_usage_invoice_message(msg);
+    }
+}
+
+impl Handler<ReportConfirmedNonce> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReportConfirmedNonce, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_report_confirmed_nonce(msg);
+    }
+}
+
+// The BlockchainBridge reports fingerprints and sent payables to us before it passes the barrier,
+// so by the time it gets here they have all been recorded
+impl Handler<ShutdownBarrierMessage> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, msg: ShutdownBarrierMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.shutdown_barrier_sub_opt
+            .as_ref()
+            .expect("Neighborhood is unbound")
+            .try_send(msg)
+            .expect("Neighborhood is dead")
+    }
+}
+
 pub trait SkeletonOptHolder {
     fn skeleton_opt(&self) -> Option<ResponseSkeleton>;
 }
@@ -377,6 +641,11 @@ impl Handler<ReportTransactionReceipts> for Accountant {
     type Result = ();
 
     fn handle(&mut self, msg: ReportTransactionReceipts, _ctx: &mut Self::Context) -> Self::Result {
+        self.consecutive_retryable_scan_failures
+            .remove(&ScanType::PendingPayables);
+        let payment_notices = self.make_payment_notices(&msg);
+        let confirmed_outbound_payments = self.confirmed_outbound_payments(&msg);
+        let stuck_fingerprints = self.stuck_batch_bumper.climb(&msg, SystemTime::now());
         if let Some(node_to_ui_msg) = self.scanners.pending_payable.finish_scan(msg, &self.logger) {
             self.ui_message_sub_opt
                 .as_ref()
@@ -384,6 +653,11 @@ impl Handler<ReportTransactionReceipts> for Accountant {
                 .try_send(node_to_ui_msg)
                 .expect("UIGateway is dead");
         }
+        self.mark_outbound_payments_confirmed(confirmed_outbound_payments);
+        self.void_abandoned_transactions();
+        self.bump_stuck_transactions(stuck_fingerprints);
+        self.send_payment_notices(payment_notices);
+        self.begin_queued_scans();
     }
 }
 
@@ -398,13 +672,23 @@ impl Handler<PendingPayableFingerprintSeeds> for Accountant {
     }
 }
 
+impl Handler<PendingPayableReplacement> for Accountant {
+    type Result = ();
+    fn handle(&mut self, msg: PendingPayableReplacement, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_pending_payable_replacement(msg)
+    }
+}
+
 impl Handler<NodeFromUiMessage> for Accountant {
     type Result = ();
 
     fn handle(&mut self, msg: NodeFromUiMessage, ctx: &mut Self::Context) -> Self::Result {
         let client_id = msg.client_id;
-        if let Ok((request, context_id)) = UiFinancialsRequest::fmb(msg.body.clone()) {
-            self.handle_financials(&request, client_id, context_id)
+        if let Ok((body, context_id)) = UiFinanceHandshakeRequest::fmb(msg.body.clone()) {
+            self.handle_finance_handshake(&body, client_id, context_id)
+        } else if let Ok((request, context_id)) = UiFinancialsRequest::fmb(msg.body.clone()) {
+            let version = self.finance_version(client_id, &msg.body);
+            self.handle_financials(&request, client_id, context_id, version)
         } else if let Ok((body, context_id)) = UiScanRequest::fmb(msg.body.clone()) {
             self.handle_externally_triggered_scan(
                 ctx,
@@ -414,6 +698,26 @@ impl Handler<NodeFromUiMessage> for Accountant {
                     context_id,
                 },
             )
+        } else if let Ok((body, context_id)) = UiRetryFailedPaymentsRequest::fmb(msg.body.clone()) {
+            self.handle_retry_failed_payments(&body, client_id, context_id)
+        } else if let Ok((body, context_id)) = UiWriteOffPayableRequest::fmb(msg.body.clone()) {
+            self.handle_write_off_payable(&body, client_id, context_id)
+        } else if let Ok((body, context_id)) = UiExportAccountsRequest::fmb(msg.body.clone()) {
+            self.handle_export_accounts(&body, client_id, context_id)
+        } else if let Ok((body, context_id)) = UiImportAccountsRequest::fmb(msg.body.clone()) {
+            self.handle_import_accounts(&body, client_id, context_id)
+        } else if let Ok((body, context_id)) = UiAccountDetailRequest::fmb(msg.body.clone()) {
+            self.handle_account_detail(&body, client_id, context_id)
+        } else if let Ok((body, context_id)) = UiWhyNotPaidRequest::fmb(msg.body.clone()) {
+            self.handle_why_not_paid(&body, client_id, context_id)
+        } else if let Ok((_, context_id)) = UiCheckConsistencyRequest::fmb(msg.body.clone()) {
+            self.handle_check_consistency(client_id, context_id)
+        } else if let Ok((_, context_id)) = UiDisputesRequest::fmb(msg.body.clone()) {
+            self.handle_disputes(client_id, context_id)
+        } else if let Ok((body, context_id)) = UiRepairConsistencyRequest::fmb(msg.body.clone()) {
+            self.handle_repair_consistency(&body, client_id, context_id)
+        } else if let Ok((_, context_id)) = UiTriggerPayableScanRequest::fmb(msg.body.clone()) {
+            self.handle_trigger_payable_scan(client_id, context_id)
         } else {
             handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
         }
@@ -425,35 +729,90 @@ impl Accountant {
         let payment_thresholds = config.payment_thresholds_opt.expectv("Payment thresholds");
         let scan_intervals = config.scan_intervals_opt.expectv("Scan Intervals");
         let earning_wallet = config.earning_wallet.clone();
+        let payment_thresholds = Rc::new(RefCell::new(payment_thresholds));
         let financial_statistics = Rc::new(RefCell::new(FinancialStatistics::default()));
+        let creditor_reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        let abandoned_transactions = Rc::new(RefCell::new(vec![]));
         let payable_dao = dao_factories.payable_dao_factory.make();
         let pending_payable_dao = dao_factories.pending_payable_dao_factory.make();
         let receivable_dao = dao_factories.receivable_dao_factory.make();
+        let accounts_transfer_dao = dao_factories.accounts_transfer_dao_factory.make();
+        let consistency_dao = dao_factories.consistency_dao_factory.make();
+        let outbound_payment_dao = dao_factories.outbound_payment_dao_factory.make();
         let scanners = Scanners::new(
             dao_factories,
-            Rc::new(payment_thresholds),
+            Rc::clone(&payment_thresholds),
             config.when_pending_too_long_sec,
             Rc::clone(&financial_statistics),
+            config.smart_account_owners.clone(),
+            config.earning_wallet_rotation.clone(),
+            config.payment_batching_opt,
+            config.unreachable_creditor_hold_opt,
+            Rc::clone(&creditor_reachability),
+            config.spend_rate_limits_opt,
+            config.payment_window_opt,
+            config.gas_price_ceiling_opt,
+            config.check_adjustment_invariants,
+            Rc::clone(&abandoned_transactions),
         );
 
         Accountant {
             suppress_initial_scans: config.suppress_initial_scans,
+            scan_pipelining: config.scan_pipelining,
             consuming_wallet_opt: config.consuming_wallet_opt.clone(),
+            additional_consuming_wallets: config.additional_consuming_wallets.clone(),
+            wallet_selector: make_wallet_selector(config.consuming_wallet_selection),
+            gas_runway_estimator: GasRunwayEstimator::new(config.gas_runway_alert_scans),
+            consumption_forecaster: ConsumptionForecaster::new(SystemTime::now()),
+            top_up_horizon_hours: config.top_up_horizon_hours,
+            price_feed: make_price_feed(config.price_feed_url_opt.clone()),
             earning_wallet,
+            earning_wallet_rotation: config.earning_wallet_rotation.clone(),
             payable_dao,
             receivable_dao,
             pending_payable_dao,
+            accounts_transfer_dao,
+            consistency_dao,
+            outbound_payment_dao,
+            outbound_instructions_rowid_opt: None,
+            submitted_outbound_payments: HashSet::new(),
+            repair_plans: RepairPlans::default(),
+            payable_scan_report_opt: None,
             scanners,
+            payable_scan_id: ScanId::default(),
             crashable: config.crash_point == CrashPoint::Message,
             scan_schedulers: ScanSchedulers::new(scan_intervals),
+            scan_retry_handle: Box::new(NotifyLaterHandleReal::default()),
+            consecutive_retryable_scan_failures: HashMap::new(),
+            payment_thresholds,
             financial_statistics: Rc::clone(&financial_statistics),
+            creditor_reachability,
+            abandoned_transactions,
+            stuck_batch_bumper: StuckBatchBumper::new(
+                config.gas_bump_ladder_opt,
+                config.when_pending_too_long_sec,
+            ),
+            idle_spending_gate: IdleSpendingGate::new(
+                config.idle_spending_freeze_opt,
+                SystemTime::now(),
+            ),
+            payment_notices: PaymentNotices::default(),
+            payment_decisions: PaymentDecisions::default(),
+            usage_invoices: UsageInvoices::default(),
             outbound_payments_instructions_sub_opt: None,
             qualified_payables_sub_opt: None,
             report_sent_payables_sub_opt: None,
             retrieve_transactions_sub_opt: None,
             report_inbound_payments_sub_opt: None,
             request_transaction_receipts_subs_opt: None,
+            void_abandoned_transaction_sub_opt: None,
+            replace_pending_payable_sub_opt: None,
+            send_payment_notice_sub_opt: None,
+            send_usage_invoice_sub_opt: None,
+            shutdown_barrier_sub_opt: None,
             ui_message_sub_opt: None,
+            finance_versions: HashMap::new(),
+            data_directory: config.data_directory.clone(),
             message_id_generator: Box::new(MessageIdGeneratorReal::default()),
             logger: Logger::new("Accountant"),
         }
@@ -470,9 +829,17 @@ impl Accountant {
             report_payable_payments_setup: recipient!(addr, BlockchainAgentWithContextMessage),
             report_inbound_payments: recipient!(addr, ReceivedPayments),
             init_pending_payable_fingerprints: recipient!(addr, PendingPayableFingerprintSeeds),
+            report_pending_payable_replacement: recipient!(addr, PendingPayableReplacement),
             report_transaction_receipts: recipient!(addr, ReportTransactionReceipts),
             report_sent_payments: recipient!(addr, SentPayables),
             scan_errors: recipient!(addr, ScanError),
+            report_creditor_thresholds: recipient!(addr, ReportCreditorThresholdsMessage),
+            report_creditor_reachability: recipient!(addr, ReportCreditorReachabilityMessage),
+            report_exit_failover: recipient!(addr, ReportExitFailoverMessage),
+            report_payment_notice: recipient!(addr, ReportPaymentNoticeMessage),
+            report_usage_invoice: recipient!(addr, ReportUsageInvoiceMessage),
+            report_confirmed_nonce: recipient!(addr, ReportConfirmedNonce),
+            shutdown_barrier: recipient!(addr, ShutdownBarrierMessage),
             ui_message_sub: recipient!(addr, NodeFromUiMessage),
         }
     }
@@ -488,30 +855,39 @@ impl Accountant {
         timestamp: SystemTime,
         payload_size: usize,
         wallet: &Wallet,
-    ) {
+    ) -> Option<u128> {
         let byte_charge = byte_rate as u128 * (payload_size as u128);
         let total_charge = service_rate as u128 + byte_charge;
         if !self.our_wallet(wallet) {
-            match self.receivable_dao
-                .as_ref()
-                .more_money_receivable(timestamp, wallet, total_charge) {
-                Ok(_) => (),
-                Err(ReceivableDaoError::SignConversion(_)) => error!(
-                    self.logger,
-                    "Overflow error recording service provided for {}: service rate {}, byte rate {}, payload size {}. Skipping",
-                    wallet,
-                    service_rate,
-                    byte_rate,
-                    payload_size
+            match self.receivable_dao.as_ref().more_money_receivable(
+                timestamp,
+                wallet,
+                total_charge,
+            ) {
+                Ok(_) => Some(total_charge),
+                Err(ReceivableDaoError::SignConversion(_)) => {
+                    error!(
+                        self.logger,
+                        "Overflow error recording service provided for {}: service rate {}, byte rate {}, payload size {}. Skipping",
+                        wallet,
+                        service_rate,
+                        byte_rate,
+                        payload_size
+                    );
+                    None
+                }
+                Err(e) => panic!(
+                    "Recording services provided for {} but has hit fatal database error: {:?}",
+                    wallet, e
                 ),
-                Err(e) => panic!("Recording services provided for {} but has hit fatal database error: {:?}", wallet, e)
-            };
+            }
         } else {
             warning!(
                 self.logger,
                 "Declining to record a receivable against our wallet {} for service we provided",
                 wallet
             );
+            None
         }
     }
 
@@ -522,38 +898,68 @@ impl Accountant {
         timestamp: SystemTime,
         payload_size: usize,
         wallet: &Wallet,
-    ) {
+    ) -> Option<u128> {
         let byte_charge = byte_rate as u128 * (payload_size as u128);
         let total_charge = service_rate as u128 + byte_charge;
-        if !self.our_wallet(wallet) {
-            match self.payable_dao
+        if BAN_CACHE.is_banned(wallet) {
+            // We don't do business with banned wallets, so we don't run into debt to them either
+            debug!(
+                self.logger,
+                "Declining to record a payable against banned wallet {} for service it provided",
+                wallet
+            );
+            None
+        } else if !self.our_wallet(wallet) {
+            match self
+                .payable_dao
                 .as_ref()
-                .more_money_payable(timestamp, wallet, total_charge) {
-                Ok(_) => (),
-                Err(PayableDaoError::SignConversion(_)) => error!(
-                    self.logger,
-                    "Overflow error recording consumed services from {}: total charge {}, service rate {}, byte rate {}, payload size {}. Skipping",
-                    wallet,
-                    total_charge,
-                    service_rate,
-                    byte_rate,
-                    payload_size
+                .more_money_payable(timestamp, wallet, total_charge)
+            {
+                Ok(_) => Some(total_charge),
+                Err(PayableDaoError::SignConversion(_)) => {
+                    error!(
+                        self.logger,
+                        "Overflow error recording consumed services from {}: total charge {}, service rate {}, byte rate {}, payload size {}. Skipping",
+                        wallet,
+                        total_charge,
+                        service_rate,
+                        byte_rate,
+                        payload_size
+                    );
+                    None
+                }
+                Err(e) => panic!(
+                    "Recording services consumed from {} but has hit fatal database error: {:?}",
+                    wallet, e
                 ),
-                Err(e) => panic!("Recording services consumed from {} but has hit fatal database error: {:?}", wallet, e)
-            };
+            }
         } else {
             warning!(
                 self.logger,
                 "Declining to record a payable against our wallet {} for service we provided",
                 wallet
             );
+            None
         }
     }
 
     fn our_wallet(&self, wallet: &Wallet) -> bool {
+        self.consuming_wallets()
+            .iter()
+            .any(|consuming| consuming.address() == wallet.address())
+            || wallet.address() == self.earning_wallet.address()
+            || self
+                .earning_wallet_rotation
+                .iter()
+                .any(|earning| earning.address() == wallet.address())
+    }
+
+    fn consuming_wallets(&self) -> Vec<Wallet> {
         match &self.consuming_wallet_opt {
-            Some(ref consuming) if consuming.address() == wallet.address() => true,
-            _ => wallet.address() == self.earning_wallet.address(),
+            Some(primary) => std::iter::once(primary.clone())
+                .chain(self.additional_consuming_wallets.iter().cloned())
+                .collect(),
+            None => vec![],
         }
     }
 
@@ -570,33 +976,166 @@ impl Accountant {
         self.qualified_payables_sub_opt =
             Some(msg.peer_actors.blockchain_bridge.qualified_payables);
         self.report_sent_payables_sub_opt = Some(msg.peer_actors.accountant.report_sent_payments);
+        self.send_payment_notice_sub_opt = Some(msg.peer_actors.neighborhood.send_payment_notice);
+        self.send_usage_invoice_sub_opt = Some(msg.peer_actors.neighborhood.send_usage_invoice);
+        self.shutdown_barrier_sub_opt = Some(msg.peer_actors.neighborhood.shutdown_barrier);
         self.ui_message_sub_opt = Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
         self.request_transaction_receipts_subs_opt = Some(
             msg.peer_actors
                 .blockchain_bridge
                 .request_transaction_receipts,
         );
+        self.void_abandoned_transaction_sub_opt =
+            Some(msg.peer_actors.blockchain_bridge.void_abandoned_transaction);
+        self.replace_pending_payable_sub_opt =
+            Some(msg.peer_actors.blockchain_bridge.replace_pending_payable);
         info!(self.logger, "Accountant bound");
     }
 
+    // An adjustment left behind by a crash is not resumed: the balances it was based on are
+    // stale by now, while the debts it covered are still unpaid in the database and the next
+    // payable scan will weigh them against fresh balances anyway
+    // Transactions still pending when the Node went down can't wait for the first scan, and
+    // with --scans off there would be none
+    fn check_receipts_left_in_flight(&mut self) {
+        let in_flight_count = self
+            .pending_payable_dao
+            .return_all_errorless_fingerprints()
+            .len();
+        if in_flight_count > 0 {
+            info!(
+                self.logger,
+                "{} transactions were still pending when the Node went down; checking their \
+                 receipts now",
+                in_flight_count
+            );
+            self.handle_request_of_scan_for_pending_payable(None)
+        }
+    }
+
+    // Payments queued at a crash may have been sent or not; the debts stay on record either way.
+    // Submitted ones whose transactions are no longer pending were settled otherwise, e.g. failed
+    fn reconcile_outbound_payments(&mut self) {
+        let unfinished = self.outbound_payment_dao.unfinished();
+        if unfinished.is_empty() {
+            return;
+        }
+        let (queued, submitted): (Vec<OutboundPayment>, Vec<OutboundPayment>) = unfinished
+            .into_iter()
+            .partition(|payment| payment.status == OutboundPaymentStatus::Queued);
+        queued.iter().for_each(|payment| {
+            let age_sec = SystemTime::now()
+                .duration_since(payment.timestamp)
+                .unwrap_or_default()
+                .as_secs();
+            warning!(
+                self.logger,
+                "Payment of {} wei to {} from consuming wallet {}, handed over to the \
+                 BlockchainBridge {} sec ago, was never reported as sent before the Node went \
+                 down; the debt remains on record for the next payable scan",
+                payment.amount_wei,
+                payment.wallet_address,
+                payment.agent_snapshot.consuming_wallet_address,
+                age_sec
+            )
+        });
+        let still_pending: HashSet<H256> = if submitted.is_empty() {
+            HashSet::new()
+        } else {
+            self.pending_payable_dao
+                .return_all_errorless_fingerprints()
+                .into_iter()
+                .map(|fingerprint| fingerprint.hash)
+                .collect()
+        };
+        let (pending, settled): (Vec<OutboundPayment>, Vec<OutboundPayment>) =
+            submitted.into_iter().partition(|payment| {
+                payment
+                    .hash_opt
+                    .map(|hash| still_pending.contains(&hash))
+                    .unwrap_or(false)
+            });
+        if !settled.is_empty() {
+            debug!(
+                self.logger,
+                "Dropping {} outbound payments from the queue as their transactions are no \
+                 longer pending",
+                settled.len()
+            )
+        }
+        self.submitted_outbound_payments
+            .extend(pending.iter().flat_map(|payment| payment.hash_opt));
+        let rowids = queued
+            .iter()
+            .chain(settled.iter())
+            .map(|payment| payment.rowid)
+            .collect_vec();
+        if rowids.is_empty() {
+            return;
+        }
+        if let Err(e) = self.outbound_payment_dao.remove(&rowids) {
+            error!(
+                self.logger,
+                "Failed to remove outbound payments {:?} from the queue: {:?}", rowids, e
+            )
+        }
+    }
+
+    // Payables and fingerprints out of step, as a crash in the middle of a scan could leave them
+    fn propose_repairs_of_anomalies(&mut self) {
+        if let Some(plan) = self.repair_plans.propose(self.consistency_dao.anomalies()) {
+            plan.anomalies.iter().for_each(|anomaly| {
+                warning!(
+                    self.logger,
+                    "Inconsistent accounts: {}; proposed repair: {}",
+                    anomaly,
+                    anomaly.repair()
+                )
+            });
+            warning!(
+                self.logger,
+                "No repair is made until repair plan {} is confirmed with repairConsistency",
+                plan.id
+            )
+        }
+    }
+
     fn handle_config_change_msg(&mut self, msg: ConfigChangeMsg) {
-        if let ConfigChange::UpdateWallets(wallet_pair) = msg.change {
-            if self.earning_wallet != wallet_pair.earning_wallet {
+        match msg.change {
+            ConfigChange::UpdateWallets(wallet_pair) => {
+                if self.earning_wallet != wallet_pair.earning_wallet {
+                    info!(
+                        self.logger,
+                        "Earning Wallet has been updated: {}", wallet_pair.earning_wallet
+                    );
+                    self.earning_wallet = wallet_pair.earning_wallet;
+                }
+                if self.consuming_wallet_opt != Some(wallet_pair.consuming_wallet.clone()) {
+                    info!(
+                        self.logger,
+                        "Consuming Wallet has been updated: {}", wallet_pair.consuming_wallet
+                    );
+                    self.consuming_wallet_opt = Some(wallet_pair.consuming_wallet);
+                }
+            }
+            ConfigChange::UpdatePaymentThresholds(payment_thresholds) => {
                 info!(
                     self.logger,
-                    "Earning Wallet has been updated: {}", wallet_pair.earning_wallet
+                    "Payment thresholds have been updated: {}", payment_thresholds
                 );
-                self.earning_wallet = wallet_pair.earning_wallet;
+                *self.payment_thresholds.borrow_mut() = payment_thresholds;
             }
-            if self.consuming_wallet_opt != Some(wallet_pair.consuming_wallet.clone()) {
+            ConfigChange::UpdateScanIntervals(scan_intervals) => {
                 info!(
                     self.logger,
-                    "Consuming Wallet has been updated: {}", wallet_pair.consuming_wallet
+                    "Scan intervals have been updated: {}", scan_intervals
                 );
-                self.consuming_wallet_opt = Some(wallet_pair.consuming_wallet);
+                // scans already scheduled keep their original timing
+                self.scan_schedulers = ScanSchedulers::new(scan_intervals);
+            }
+            ConfigChange::UpdatePassword(_) | ConfigChange::UpdateMinHops(_) => {
+                trace!(self.logger, "Ignored irrelevant message: {:?}", msg);
             }
-        } else {
-            trace!(self.logger, "Ignored irrelevant message: {:?}", msg);
         }
     }
 
@@ -608,27 +1147,80 @@ impl Accountant {
             .schedule(ctx)
     }
 
+    fn consider_retrying_scan(
+        &mut self,
+        scan_type: ScanType,
+        retryable: bool,
+        ctx: &mut Context<Self>,
+    ) {
+        if !retryable {
+            self.consecutive_retryable_scan_failures.remove(&scan_type);
+            return;
+        }
+        let failures = self
+            .consecutive_retryable_scan_failures
+            .entry(scan_type)
+            .or_insert(0);
+        *failures += 1;
+        let delay = SCAN_RETRY_BASE_DELAY * 2_u32.saturating_pow((*failures - 1).min(16));
+        let interval = self
+            .scan_schedulers
+            .schedulers
+            .get(&scan_type)
+            .unwrap_or_else(|| panic!("Scan Scheduler {:?} not properly prepared", scan_type))
+            .interval();
+        if delay >= interval {
+            debug!(
+                self.logger,
+                "{:?} scan failed {} times in a row; leaving it to the regular schedule",
+                scan_type,
+                failures
+            );
+            return;
+        }
+        debug!(
+            self.logger,
+            "{:?} scan failed for a transient reason; retrying in {} s",
+            scan_type,
+            delay.as_secs()
+        );
+        let _ = self
+            .scan_retry_handle
+            .notify_later(RetryScan { scan_type }, delay, ctx);
+    }
+
     fn handle_report_routing_service_provided_message(
         &mut self,
         msg: ReportRoutingServiceProvidedMessage,
     ) {
+        self.idle_spending_gate
+            .record_traffic(msg.timestamp, &self.logger);
         debug!(
             self.logger,
             "Charging routing of {} bytes to wallet {}", msg.payload_size, msg.paying_wallet
         );
-        self.record_service_provided(
+        if let Some(charge) = self.record_service_provided(
             msg.service_rate,
             msg.byte_rate,
             msg.timestamp,
             msg.payload_size,
             &msg.paying_wallet,
-        );
+        ) {
+            self.usage_invoices.record_provided(
+                &msg.paying_wallet,
+                ServiceKind::Routing,
+                charge,
+                msg.timestamp,
+            )
+        }
     }
 
     fn handle_report_exit_service_provided_message(
         &mut self,
         msg: ReportExitServiceProvidedMessage,
     ) {
+        self.idle_spending_gate
+            .record_traffic(msg.timestamp, &self.logger);
         debug!(
             self.logger,
             "Charging exit service for {} bytes to wallet {} at {} per service and {} per byte",
@@ -637,13 +1229,20 @@ impl Accountant {
             msg.service_rate,
             msg.byte_rate
         );
-        self.record_service_provided(
+        if let Some(charge) = self.record_service_provided(
             msg.service_rate,
             msg.byte_rate,
             msg.timestamp,
             msg.payload_size,
             &msg.paying_wallet,
-        );
+        ) {
+            self.usage_invoices.record_provided(
+                &msg.paying_wallet,
+                ServiceKind::Exit,
+                charge,
+                msg.timestamp,
+            )
+        }
     }
 
     fn msg_id(&self) -> u32 {
@@ -655,6 +1254,8 @@ impl Accountant {
     }
 
     fn handle_report_services_consumed_message(&mut self, msg: ReportServicesConsumedMessage) {
+        self.idle_spending_gate
+            .record_traffic(msg.timestamp, &self.logger);
         let msg_id = self.msg_id();
         debug!(
             self.logger,
@@ -663,13 +1264,26 @@ impl Accountant {
             msg.exit.earning_wallet,
             msg.exit.payload_size
         );
-        self.record_service_consumed(
+        if let Some(charge) = self.record_service_consumed(
             msg.exit.service_rate,
             msg.exit.byte_rate,
             msg.timestamp,
             msg.exit.payload_size,
             &msg.exit.earning_wallet,
-        );
+        ) {
+            self.consumption_forecaster.record_consumed(
+                &msg.exit.earning_wallet,
+                msg.exit.payload_size,
+                charge,
+                msg.timestamp,
+            );
+            self.usage_invoices.record_consumed(
+                &msg.exit.earning_wallet,
+                ServiceKind::Exit,
+                charge,
+                msg.timestamp,
+            )
+        }
         msg.routing.iter().for_each(|routing_service| {
             debug!(
                 self.logger,
@@ -678,1537 +1292,4861 @@ impl Accountant {
                 routing_service.earning_wallet,
                 msg.routing_payload_size
             );
-            self.record_service_consumed(
+            if let Some(charge) = self.record_service_consumed(
                 routing_service.service_rate,
                 routing_service.byte_rate,
                 msg.timestamp,
                 msg.routing_payload_size,
                 &routing_service.earning_wallet,
-            );
-        })
-    }
-
-    fn handle_payable_payment_setup(&mut self, msg: BlockchainAgentWithContextMessage) {
-        let blockchain_bridge_instructions = match self
-            .scanners
-            .payable
-            .try_skipping_payment_adjustment(msg, &self.logger)
-        {
-            Ok(Either::Left(finalized_msg)) => finalized_msg,
-            Ok(Either::Right(unaccepted_msg)) => {
-                //TODO we will eventually query info from Neighborhood before the adjustment, according to GH-699
-                self.scanners
-                    .payable
-                    .perform_payment_adjustment(unaccepted_msg, &self.logger)
+            ) {
+                self.consumption_forecaster.record_consumed(
+                    &routing_service.earning_wallet,
+                    msg.routing_payload_size,
+                    charge,
+                    msg.timestamp,
+                );
+                self.usage_invoices.record_consumed(
+                    &routing_service.earning_wallet,
+                    ServiceKind::Routing,
+                    charge,
+                    msg.timestamp,
+                )
             }
-            Err(_e) => todo!("be completed by GH-711"),
-        };
-        self.outbound_payments_instructions_sub_opt
-            .as_ref()
-            .expect("BlockchainBridge is unbound")
-            .try_send(blockchain_bridge_instructions)
-            .expect("BlockchainBridge is dead")
-        //TODO implement send point for ScanError; be completed by GH-711
+        })
     }
 
-    fn handle_financials(&self, msg: &UiFinancialsRequest, client_id: u64, context_id: u64) {
-        let body: MessageBody = self.compute_financials(msg, context_id);
-        self.ui_message_sub_opt
-            .as_ref()
-            .expect("UiGateway not bound")
-            .try_send(NodeToUiMessage {
-                target: ClientId(client_id),
-                body,
+    fn handle_report_creditor_thresholds_message(&self, msg: ReportCreditorThresholdsMessage) {
+        msg.creditor_thresholds
+            .iter()
+            .for_each(|(creditor_wallet, payment_thresholds)| {
+                match self
+                    .payable_dao
+                    .upsert_creditor_thresholds(creditor_wallet, payment_thresholds)
+                {
+                    Ok(()) => debug!(
+                        self.logger,
+                        "Creditor {} is to be paid by its own payment thresholds {:?}",
+                        creditor_wallet,
+                        payment_thresholds
+                    ),
+                    Err(e) => warning!(
+                        self.logger,
+                        "Payment thresholds advertised by creditor {} not saved: {:?}",
+                        creditor_wallet,
+                        e
+                    ),
+                }
             })
-            .expect("UiGateway is dead");
     }
 
-    fn compute_financials(&self, msg: &UiFinancialsRequest, context_id: u64) -> MessageBody {
-        if let Err(message_body) = financials_entry_check(msg, context_id) {
-            return message_body;
-        };
-        let stats_opt = self.process_stats(msg);
-        let query_results_opt = match self.process_queries_of_records(msg, context_id) {
-            Ok(results_opt) => results_opt,
-            Err(message_body) => return message_body,
-        };
-        UiFinancialsResponse {
-            stats_opt,
-            query_results_opt,
-        }
-        .tmb(context_id)
+    fn handle_report_creditor_reachability_message(&self, msg: ReportCreditorReachabilityMessage) {
+        debug!(
+            self.logger,
+            "Neighborhood reports {} creditors reachable and {} unreachable",
+            msg.reachable.len(),
+            msg.unreachable.len()
+        );
+        self.creditor_reachability.borrow_mut().update(&msg);
     }
 
-    fn request_payable_accounts_by_specific_mode(
-        &self,
-        mode: CustomQuery<u64>,
-    ) -> Option<Vec<UiPayableAccount>> {
-        self.payable_dao
-            .custom_query(mode)
-            .map(remap_payable_accounts)
+    fn handle_report_exit_failover_message(&self, msg: ReportExitFailoverMessage) {
+        info!(
+            self.logger,
+            "Neighborhood has selected exit country {}; debts to its {} creditors will be favored \
+             in the next payment adjustment",
+            msg.exit_country,
+            msg.creditors.len()
+        );
+        self.creditor_reachability
+            .borrow_mut()
+            .favor_exit_creditors(&msg);
     }
 
-    fn request_receivable_accounts_by_specific_mode(
-        &self,
-        mode: CustomQuery<i64>,
-    ) -> Option<Vec<UiReceivableAccount>> {
-        self.receivable_dao
-            .custom_query(mode)
-            .map(remap_receivable_accounts)
+    fn handle_report_payment_notice_message(&mut self, msg: ReportPaymentNoticeMessage) {
+        let debtor_wallet = msg.notice.payer.wallet.clone();
+        if self.receivable_dao.account_status(&debtor_wallet).is_none() {
+            warning!(
+                self.logger,
+                "Ignoring notice of payment {:?} from {}, who owes us nothing",
+                msg.notice.tx_hash,
+                debtor_wallet
+            );
+            return;
+        }
+        if self
+            .payment_notices
+            .accept(msg.notice, &self.earning_wallet, &self.logger)
+        {
+            self.send_usage_invoice(&debtor_wallet);
+            if self.payment_notices.is_scan_due(SystemTime::now()) {
+                self.handle_request_of_scan_for_receivable(None)
+            }
+        }
     }
 
-    fn process_stats(&self, msg: &UiFinancialsRequest) -> Option<UiFinancialStatistics> {
-        if msg.stats_required {
-            let financial_statistics = self.financial_statistics();
-            Some(UiFinancialStatistics {
-                total_unpaid_and_pending_payable_gwei: wei_to_gwei(self.payable_dao.total()),
-                total_paid_payable_gwei: wei_to_gwei(financial_statistics.total_paid_payable_wei),
-                total_unpaid_receivable_gwei: wei_to_gwei(self.receivable_dao.total()),
-                total_paid_receivable_gwei: wei_to_gwei(
-                    financial_statistics.total_paid_receivable_wei,
-                ),
-            })
-        } else {
-            None
-        }
+    // A debtor who has just paid us learns what for, so that it can check our claims against its
+    // own books
+    fn send_usage_invoice(&mut self, debtor_wallet: &Wallet) {
+        let invoice = match self.usage_invoices.make_invoice(
+            &self.earning_wallet,
+            debtor_wallet,
+            SystemTime::now(),
+        ) {
+            Some(invoice) => invoice,
+            None => return,
+        };
+        debug!(
+            self.logger,
+            "Invoicing {} for {} routing and {} exit services worth {} wei",
+            debtor_wallet,
+            invoice.routing_services,
+            invoice.exit_services,
+            invoice.amount_wei
+        );
+        self.send_usage_invoice_sub_opt
+            .as_ref()
+            .expect("Neighborhood is unbound")
+            .try_send(SendUsageInvoiceMessage { invoice })
+            .expect("Neighborhood is dead")
     }
 
-    fn process_top_records_query(&self, msg: &UiFinancialsRequest) -> Option<QueryResults> {
-        msg.top_records_opt.map(|config| {
-            let payable = self
-                .request_payable_accounts_by_specific_mode(config.into())
-                .unwrap_or_default();
-            let receivable = self
-                .request_receivable_accounts_by_specific_mode(config.into())
-                .unwrap_or_default();
+    // Fingerprints don't tell the consuming wallets apart, so with more than one of them, the
+    // nonces of one wallet can't be checked against them
+    fn handle_report_confirmed_nonce(&mut self, msg: ReportConfirmedNonce) {
+        if !self.additional_consuming_wallets.is_empty() {
+            debug!(
+                self.logger,
+                "Pending payables not reconciled with the confirmed nonce {} of {}: there are \
+                 more consuming wallets",
+                msg.confirmed_nonce,
+                msg.wallet
+            );
+            return;
+        }
+        let reconciliation = NonceReconciliation::new(
+            msg.confirmed_nonce,
+            &self.pending_payable_dao.return_all_errorless_fingerprints(),
+            &self.pending_payable_dao.return_failed_fingerprints(),
+        );
+        if !reconciliation.due_for_receipts.is_empty() {
+            info!(
+                self.logger,
+                "Transactions {} were mined below the confirmed nonce {}; their receipts will be \
+                 looked up",
+                comma_joined_stringifiable(&reconciliation.due_for_receipts, |fingerprint| {
+                    format!("{:?}", fingerprint.hash)
+                }),
+                msg.confirmed_nonce
+            )
+        }
+        if !reconciliation.reopened.is_empty() {
+            let rowids = reconciliation
+                .reopened
+                .iter()
+                .map(|fingerprint| fingerprint.rowid)
+                .collect::<Vec<u64>>();
+            if let Err(e) = self.pending_payable_dao.unmark_failures(&rowids) {
+                panic!(
+                    "Unable to reopen failed transactions below the confirmed nonce {}: {:?}",
+                    msg.confirmed_nonce, e
+                )
+            }
+            info!(
+                self.logger,
+                "Failed transactions {} were mined below the confirmed nonce {}; their receipts \
+                 will be looked up again",
+                comma_joined_stringifiable(&reconciliation.reopened, |fingerprint| {
+                    format!("{:?}", fingerprint.hash)
+                }),
+                msg.confirmed_nonce
+            )
+        }
+        if reconciliation.unrecorded_nonces.is_empty() {
+            return;
+        }
+        let report = NonceAnomalyReport {
+            wallet: msg.wallet.to_string(),
+            confirmed_nonce: msg.confirmed_nonce,
+            unrecorded_nonces: reconciliation.unrecorded_nonces.clone(),
+            reopened_transactions: reconciliation
+                .reopened
+                .iter()
+                .map(|fingerprint| fingerprint.hash)
+                .collect(),
+        };
+        let whereabouts = match write_nonce_anomaly_report(&self.data_directory, &report) {
+            Ok(path) => format!("see {}", path.display()),
+            Err(e) => format!("the report could not be written: {}", e),
+        };
+        warning!(
+            self.logger,
+            "The chain confirmed transactions of {} at nonces {} that the Node holds no record \
+             of; {}",
+            msg.wallet,
+            comma_joined_stringifiable(&reconciliation.unrecorded_nonces, u64::to_string),
+            whereabouts
+        )
+    }
 
-            QueryResults {
-                payable_opt: Some(payable),
-                receivable_opt: Some(receivable),
+    fn handle_report_usage_invoice_message(&mut self, msg: ReportUsageInvoiceMessage) {
+        let invoice = msg.invoice;
+        if !self
+            .consuming_wallets()
+            .iter()
+            .any(|wallet| wallet.address() == invoice.debtor_wallet.address())
+        {
+            warning!(
+                self.logger,
+                "Ignoring invoice from {}: {} is not our consuming wallet",
+                invoice.creditor_wallet,
+                invoice.debtor_wallet
+            );
+            return;
+        }
+        match self.usage_invoices.reconcile(&invoice, SystemTime::now()) {
+            Ok(()) => info!(
+                self.logger,
+                "Invoice from {} for {} routing and {} exit services agrees with our books",
+                invoice.creditor_wallet,
+                invoice.routing_services,
+                invoice.exit_services
+            ),
+            Err(dispute) => {
+                warning!(
+                    self.logger,
+                    "Disputing invoice from {}: it claims {} services worth {} wei, but we've \
+                     booked {} worth {} wei",
+                    dispute.creditor_wallet,
+                    dispute.invoiced_services,
+                    dispute.invoiced_wei,
+                    dispute.booked_services,
+                    dispute.booked_wei
+                );
+                if let Err(e) = self.payable_dao.record_invoice_dispute(&dispute) {
+                    error!(
+                        self.logger,
+                        "Failed to record dispute of invoice from {}: {:?}",
+                        dispute.creditor_wallet,
+                        e
+                    )
+                }
             }
-        })
+        }
     }
 
-    fn process_custom_queries(
-        &self,
-        msg: &UiFinancialsRequest,
-        context_id: u64,
-    ) -> Result<Option<QueryResults>, MessageBody> {
-        Ok(match msg.custom_queries_opt.as_ref() {
-            Some(specs) => {
-                let payable_opt = if let Some(query_specs) = specs.payable_opt.as_ref() {
-                    let query = CustomQuery::from(query_specs);
-                    check_query_is_within_tech_limits(&query, "payable", context_id)?;
-                    self.request_payable_accounts_by_specific_mode(query)
-                } else {
-                    None
-                };
-                let receivable_opt = if let Some(query_specs) = specs.receivable_opt.as_ref() {
-                    let query = CustomQuery::from(query_specs);
-                    check_query_is_within_tech_limits(&query, "receivable", context_id)?;
-                    self.request_receivable_accounts_by_specific_mode(query)
-                } else {
-                    None
-                };
+    // Our creditors learn of confirmed payments from notices signed by the consuming wallet; the
+    // creditor of each transaction has to be looked up before its confirmation detaches it
+    fn make_payment_notices(&self, msg: &ReportTransactionReceipts) -> Vec<PaymentNotice_0v1> {
+        let paying_wallet = match self.consuming_wallet_opt.as_ref() {
+            Some(wallet) => wallet,
+            None => return vec![],
+        };
+        let confirmed = msg
+            .fingerprints_with_receipts
+            .iter()
+            .flat_map(|(receipt_result, fingerprint)| match receipt_result {
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    status: TxStatus::Succeeded(block),
+                    ..
+                }) => Some((fingerprint, block.block_number.as_u64())),
+                _ => None,
+            })
+            .collect_vec();
+        if confirmed.is_empty() {
+            return vec![];
+        }
+        let creditors = self.payable_dao.creditors_of_pending_payables(
+            &confirmed
+                .iter()
+                .map(|(fingerprint, _)| fingerprint.rowid)
+                .collect_vec(),
+        );
+        confirmed
+            .into_iter()
+            .flat_map(|(fingerprint, block_number)| {
+                let creditor_wallet = creditors.get(&fingerprint.rowid)?;
+                match PaymentNotice_0v1::new(
+                    paying_wallet,
+                    creditor_wallet,
+                    fingerprint.hash,
+                    fingerprint.amount,
+                    block_number,
+                ) {
+                    Ok(notice) => Some(notice),
+                    Err(e) => {
+                        warning!(
+                            self.logger,
+                            "Cannot sign notice of payment {:?} to {}: {:?}",
+                            fingerprint.hash,
+                            creditor_wallet,
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 
-                Some(QueryResults {
-                    payable_opt,
-                    receivable_opt,
-                })
-            }
-            None => None,
+    fn send_payment_notices(&self, payment_notices: Vec<PaymentNotice_0v1>) {
+        payment_notices.into_iter().for_each(|notice| {
+            debug!(
+                self.logger,
+                "Giving {} notice of payment {:?}", notice.creditor_wallet, notice.tx_hash
+            );
+            self.send_payment_notice_sub_opt
+                .as_ref()
+                .expect("Neighborhood is unbound")
+                .try_send(SendPaymentNoticeMessage { notice })
+                .expect("Neighborhood is dead")
         })
     }
 
-    fn process_queries_of_records(
-        &self,
-        msg: &UiFinancialsRequest,
-        context_id: u64,
-    ) -> Result<Option<QueryResults>, MessageBody> {
-        let top_records_opt = self.process_top_records_query(msg);
-        let custom_query_records_opt = match self.process_custom_queries(msg, context_id) {
-            Ok(query_results) => query_results,
-            Err(message_body) => return Err(message_body),
+    fn handle_payable_payment_setup(&mut self, msg: BlockchainAgentWithContextMessage) {
+        self.wallet_selector.record_agent(msg.agent.as_ref());
+        let logger = self.payable_scan_id.span(&self.logger);
+        let now = SystemTime::now();
+        let qualified_payables: Vec<PayableAccount> =
+            msg.protected_qualified_payables.clone().expose_vector();
+        let mut report_opt = self.take_payable_scan_report(msg.response_skeleton_opt);
+        if let Some(report) = report_opt.as_mut() {
+            report.start(qualified_payables.clone())
+        }
+        let wallet_balances = msg.agent.consuming_wallet_balances();
+        self.payment_decisions.start(qualified_payables.clone(), wallet_balances, now);
+        let mut narrow_report = |instructions: &OutboundPaymentsInstructions, reason: &str| {
+            if let Some(report) = report_opt.as_mut() {
+                report.narrow(&instructions.affordable_accounts, reason)
+            }
+            self.payment_decisions
+                .narrow(&instructions.affordable_accounts, reason)
         };
-        match vec![top_records_opt, custom_query_records_opt]
-            .into_iter()
-            .find(|results| results.is_some())
+        let blockchain_bridge_instructions = match self
+            .scanners
+            .payable
+            .try_skipping_payment_adjustment(msg, &logger)
         {
-            Some(results) => Ok(results),
-            None => Ok(None),
+            Ok(Either::Left(finalized_msg)) => finalized_msg,
+            Ok(Either::Right(unaccepted_msg)) => {
+                //TODO we will eventually query info from Neighborhood before the adjustment, according to GH-699
+                self.scanners
+                    .payable
+                    .perform_payment_adjustment(unaccepted_msg, &logger)
+            }
+            Err(_e) => todo!("be completed by GH-711"),
+        };
+        narrow_report(&blockchain_bridge_instructions, SKIPPED_BY_ADJUSTMENT);
+        let blockchain_bridge_instructions = self.scanners.payable.confine_to_payment_window(
+            blockchain_bridge_instructions,
+            now,
+            &logger,
+        );
+        narrow_report(&blockchain_bridge_instructions, SKIPPED_BY_PAYMENT_WINDOW);
+        let blockchain_bridge_instructions = self.scanners.payable.defer_while_gas_is_expensive(
+            blockchain_bridge_instructions,
+            now,
+            &logger,
+        );
+        narrow_report(
+            &blockchain_bridge_instructions,
+            SKIPPED_BY_GAS_PRICE_CEILING,
+        );
+        let (blockchain_bridge_instructions, deferral_opt) = self
+            .scanners
+            .payable
+            .limit_spend_rate(blockchain_bridge_instructions, now, &logger);
+        narrow_report(&blockchain_bridge_instructions, SKIPPED_BY_SPEND_RATE_LIMIT);
+        let blockchain_bridge_instructions = self.scanners.payable.enforce_adjustment_invariants(
+            &qualified_payables,
+            wallet_balances.masq_token_balance_in_minor_units.as_u128(),
+            blockchain_bridge_instructions,
+            &logger,
+        );
+        narrow_report(&blockchain_bridge_instructions, SKIPPED_BY_ADJUSTMENT_INVARIANTS);
+        if let Some(deferral) = deferral_opt {
+            self.announce_spend_rate_deferral(deferral)
+        }
+        if blockchain_bridge_instructions
+            .affordable_accounts
+            .is_empty()
+        {
+            self.scanners.payable.mark_as_ended(&logger);
+            if let Some(report) = report_opt {
+                let response_skeleton = report.response_skeleton;
+                self.send_payable_scan_report(response_skeleton, Ok(report.nothing_paid()))
+            } else if let Some(response_skeleton) =
+                blockchain_bridge_instructions.response_skeleton_opt
+            {
+                self.ui_message_sub_opt
+                    .as_ref()
+                    .expect("UiGateway is unbound")
+                    .try_send(NodeToUiMessage {
+                        target: ClientId(response_skeleton.client_id),
+                        body: UiScanResponse { queued: false }.tmb(response_skeleton.context_id),
+                    })
+                    .expect("UiGateway is dead")
+            }
+            return;
+        }
+        self.gas_runway_estimator
+            .record_scan(blockchain_bridge_instructions.affordable_accounts.len());
+        if let Some(runway) = self
+            .gas_runway_estimator
+            .check(blockchain_bridge_instructions.agent.as_ref())
+        {
+            self.alert_short_gas_runway(
+                runway,
+                blockchain_bridge_instructions.agent.consuming_wallet(),
+            )
         }
+        self.recommend_service_fee_top_up(blockchain_bridge_instructions.agent.as_ref(), now);
+        self.preview_payment_fees(&blockchain_bridge_instructions);
+        self.queue_outbound_payments(&blockchain_bridge_instructions, &logger);
+        self.payable_scan_report_opt = report_opt;
+        self.outbound_payments_instructions_sub_opt
+            .as_ref()
+            .expect("BlockchainBridge is unbound")
+            .try_send(OutboundPaymentsInstructions {
+                scan_id: self.payable_scan_id,
+                ..blockchain_bridge_instructions
+            })
+            .expect("BlockchainBridge is dead")
+        //TODO implement send point for ScanError; be completed by GH-711
     }
 
-    fn handle_request_of_scan_for_payable(
+    // Should the Node go down before the BlockchainBridge reports back, the queue tells what was
+    // on its way
+    fn queue_outbound_payments(
         &mut self,
-        response_skeleton_opt: Option<ResponseSkeleton>,
+        instructions: &OutboundPaymentsInstructions,
+        logger: &Logger,
     ) {
-        let result = match self.consuming_wallet_opt.clone() {
-            Some(consuming_wallet) => self.scanners.payable.begin_scan(
-                consuming_wallet,
-                SystemTime::now(),
-                response_skeleton_opt,
-                &self.logger,
-            ),
-            None => Err(BeginScanError::NoConsumingWalletFound),
-        };
-
-        match result {
-            Ok(scan_message) => {
-                self.qualified_payables_sub_opt
-                    .as_ref()
-                    .expect("BlockchainBridge is unbound")
-                    .try_send(scan_message)
-                    .expect("BlockchainBridge is dead");
+        self.outbound_instructions_rowid_opt = match self.outbound_payment_dao.enqueue(
+            SystemTime::now(),
+            &BlockchainAgentSnapshot::new(instructions.agent.as_ref()),
+            &instructions.affordable_accounts,
+        ) {
+            Ok(rowid) => Some(rowid),
+            Err(e) => {
+                warning!(
+                    logger,
+                    "Failed to queue the outbound payments; a crash before they are sent would \
+                     leave no trace of them: {:?}",
+                    e
+                );
+                None
             }
-            Err(e) => e.handle_error(
-                &self.logger,
-                ScanType::Payables,
-                response_skeleton_opt.is_some(),
-            ),
         }
     }
 
-    fn handle_request_of_scan_for_pending_payable(
-        &mut self,
-        response_skeleton_opt: Option<ResponseSkeleton>,
-    ) {
-        let result = match self.consuming_wallet_opt.clone() {
-            Some(consuming_wallet) => self.scanners.pending_payable.begin_scan(
-                consuming_wallet, // This argument is not used and is therefore irrelevant
-                SystemTime::now(),
-                response_skeleton_opt,
-                &self.logger,
-            ),
-            None => Err(BeginScanError::NoConsumingWalletFound),
+    fn mark_outbound_payments_submitted(&mut self, msg: &SentPayables, logger: &Logger) {
+        let instructions_rowid = match self.outbound_instructions_rowid_opt.take() {
+            Some(rowid) => rowid,
+            None => return,
         };
-
-        match result {
-            Ok(scan_message) => self
-                .request_transaction_receipts_subs_opt
-                .as_ref()
-                .expect("BlockchainBridge is unbound")
-                .try_send(scan_message)
-                .expect("BlockchainBridge is dead"),
-            Err(e) => e.handle_error(
-                &self.logger,
-                ScanType::PendingPayables,
-                response_skeleton_opt.is_some(),
+        let sent_payments = match msg.payment_procedure_result.as_ref() {
+            Ok(processed) => processed
+                .iter()
+                .flat_map(|payment| match payment {
+                    ProcessedPayableFallible::Correct(pending_payable) => {
+                        Some(pending_payable.clone())
+                    }
+                    ProcessedPayableFallible::Failed(_) => None,
+                })
+                .collect_vec(),
+            Err(_) => vec![],
+        };
+        match self
+            .outbound_payment_dao
+            .mark_submitted(instructions_rowid, &sent_payments)
+        {
+            Ok(()) => self
+                .submitted_outbound_payments
+                .extend(sent_payments.iter().map(|payment| payment.hash)),
+            Err(e) => warning!(
+                logger,
+                "Failed to mark the queued outbound payments as submitted: {:?}",
+                e
             ),
         }
     }
 
-    fn handle_request_of_scan_for_receivable(
-        &mut self,
-        response_skeleton_opt: Option<ResponseSkeleton>,
-    ) {
-        match self.scanners.receivable.begin_scan(
-            self.earning_wallet.clone(),
-            SystemTime::now(),
-            response_skeleton_opt,
-            &self.logger,
-        ) {
-            Ok(scan_message) => self
-                .retrieve_transactions_sub_opt
-                .as_ref()
-                .expect("BlockchainBridge is unbound")
-                .try_send(scan_message)
-                .expect("BlockchainBridge is dead"),
-            Err(e) => e.handle_error(
-                &self.logger,
-                ScanType::Receivables,
-                response_skeleton_opt.is_some(),
-            ),
-        };
+    fn confirmed_outbound_payments(&self, msg: &ReportTransactionReceipts) -> Vec<H256> {
+        msg.fingerprints_with_receipts
+            .iter()
+            .flat_map(|(receipt_result, fingerprint)| match receipt_result {
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    status: TxStatus::Succeeded(_),
+                    ..
+                }) if self.submitted_outbound_payments.contains(&fingerprint.hash) => {
+                    Some(fingerprint.hash)
+                }
+                _ => None,
+            })
+            .collect()
     }
 
-    fn handle_externally_triggered_scan(
-        &mut self,
-        _ctx: &mut Context<Accountant>,
-        scan_type: ScanType,
-        response_skeleton: ResponseSkeleton,
-    ) {
-        match scan_type {
-            ScanType::Payables => self.handle_request_of_scan_for_payable(Some(response_skeleton)),
-            ScanType::PendingPayables => {
-                self.handle_request_of_scan_for_pending_payable(Some(response_skeleton));
-            }
-            ScanType::Receivables => {
-                self.handle_request_of_scan_for_receivable(Some(response_skeleton))
-            }
+    fn mark_outbound_payments_confirmed(&mut self, hashes: Vec<H256>) {
+        if hashes.is_empty() {
+            return;
+        }
+        hashes.iter().for_each(|hash| {
+            self.submitted_outbound_payments.remove(hash);
+        });
+        if let Err(e) = self.outbound_payment_dao.mark_confirmed(&hashes) {
+            warning!(
+                self.logger,
+                "Failed to mark the outbound payments of transactions {} as confirmed: {:?}",
+                hashes.iter().map(|hash| format!("{:?}", hash)).join(", "),
+                e
+            )
         }
     }
 
-    fn handle_new_pending_payable_fingerprints(&self, msg: PendingPayableFingerprintSeeds) {
-        fn serialize_hashes(fingerprints_data: &[HashAndAmount]) -> String {
-            comma_joined_stringifiable(fingerprints_data, |hash_and_amount| {
-                format!("{:?}", hash_and_amount.hash)
-            })
+    fn preview_payment_fees(&self, instructions: &OutboundPaymentsInstructions) {
+        let accounts_count = instructions.affordable_accounts.len();
+        if accounts_count == 0 {
+            return;
         }
-        match self
-            .pending_payable_dao
-            .insert_new_fingerprints(&msg.hashes_and_balances, msg.batch_wide_timestamp)
-        {
-            Ok(_) => debug!(
-                self.logger,
-                "Saved new pending payable fingerprints for: {}",
-                serialize_hashes(&msg.hashes_and_balances)
-            ),
-            Err(e) => error!(
-                self.logger,
-                "Failed to process new pending payable fingerprints due to '{:?}', \
-                 disabling the automated confirmation for all these transactions: {}",
-                e,
-                serialize_hashes(&msg.hashes_and_balances)
+        let service_fee_wei: u128 = instructions
+            .affordable_accounts
+            .iter()
+            .map(|account| account.balance_wei)
+            .sum();
+        let transaction_fee_wei = instructions
+            .agent
+            .estimated_transaction_fee_total(accounts_count)
+            .as_u128();
+        let fiat_part = match self.price_feed.quote_opt() {
+            Some(quote) => format!(
+                " (approximately {} {} and {} {} at price feed rates)",
+                format_fiat(
+                    quote.service_fee_in_fiat(i128::try_from(service_fee_wei).unwrap_or(i128::MAX))
+                ),
+                quote.currency,
+                format_fiat(quote.transaction_fee_in_fiat(transaction_fee_wei)),
+                quote.currency
             ),
+            None => String::new(),
+        };
+        info!(
+            self.payable_scan_id.span(&self.logger),
+            "Paying {} wei of MASQ to {} creditors for an estimated {} wei in transaction fees{}",
+            service_fee_wei,
+            accounts_count,
+            transaction_fee_wei,
+            fiat_part
+        );
+    }
+
+    fn alert_short_gas_runway(&self, runway: GasRunway, consuming_wallet: &Wallet) {
+        let runway_sec = runway.scans_left.saturating_mul(
+            self.scan_schedulers
+                .schedulers
+                .get(&ScanType::Payables)
+                .expectv("payable scan scheduler")
+                .interval()
+                .as_secs(),
+        );
+        let advice = match runway.shortage {
+            TransactionFeeShortage::GasPriceSpike { .. } => {
+                format!("{}, so waiting for cheaper gas may do", runway.shortage)
+            }
+            TransactionFeeShortage::InsufficientBalance { .. } => {
+                format!("{}; consider topping it up", runway.shortage)
+            }
+            TransactionFeeShortage::NoBaseFee => "consider topping it up".to_string(),
+        };
+        warning!(
+            self.logger,
+            "Consuming wallet {} holds {} wei for transaction fees, which will pay for only about {} \
+            more payable scans (~{} sec); {}",
+            consuming_wallet,
+            runway.transaction_fee_balance.as_u128(),
+            runway.scans_left,
+            runway_sec,
+            advice
+        );
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway is unbound")
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiTransactionFeeRunwayBroadcast {
+                    consuming_wallet_address: consuming_wallet.to_string(),
+                    transaction_fee_balance_gwei: u64::try_from(
+                        runway.transaction_fee_balance.as_gwei(),
+                    )
+                    .unwrap_or(u64::MAX),
+                    scans_left: runway.scans_left,
+                    runway_sec,
+                }
+                .tmb(0),
+            })
+            .expect("UiGateway is dead");
+    }
+
+    // Speaks up only if the service fee balance won't pay off our debts, present and projected,
+    // to the end of the top-up horizon
+    fn recommend_service_fee_top_up(&self, agent: &dyn BlockchainAgent, now: SystemTime) {
+        if self.top_up_horizon_hours == 0 {
+            return;
+        }
+        let horizon = Duration::from_secs(self.top_up_horizon_hours.saturating_mul(3_600));
+        self.consumption_forecaster
+            .project(now, horizon)
+            .iter()
+            .for_each(|projection| {
+                debug!(
+                    self.logger,
+                    "Debt to {} is projected to grow by {} wei within {} hours ({} bytes, {} wei \
+                     an hour)",
+                    projection.creditor,
+                    projection.projected_growth_wei,
+                    self.top_up_horizon_hours,
+                    projection.bytes_per_hour,
+                    projection.wei_per_hour
+                )
+            });
+        let service_fee_balance_wei = Wei::saturating_from_u256(
+            agent
+                .consuming_wallet_balances()
+                .masq_token_balance_in_minor_units,
+        )
+        .as_u128();
+        let outstanding_debt_wei = self.payable_dao.total();
+        let recommendation = self.consumption_forecaster.recommend_top_up(
+            now,
+            horizon,
+            outstanding_debt_wei,
+            service_fee_balance_wei,
+        );
+        if recommendation.top_up_wei == 0 {
+            return;
         }
+        let consuming_wallet = agent.consuming_wallet();
+        warning!(
+            self.logger,
+            "Consuming wallet {} holds {} wei of service fee, while {} wei is owed already and {} wei \
+            more is projected to be owed within {} hours; consider topping it up with {} wei",
+            consuming_wallet,
+            service_fee_balance_wei,
+            outstanding_debt_wei,
+            recommendation.projected_debt_growth_wei,
+            self.top_up_horizon_hours,
+            recommendation.top_up_wei
+        );
+        let to_gwei = |wei: u128| u64::try_from(wei / WEIS_IN_GWEI as u128).unwrap_or(u64::MAX);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway is unbound")
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiServiceFeeTopUpBroadcast {
+                    consuming_wallet_address: consuming_wallet.to_string(),
+                    service_fee_balance_gwei: to_gwei(service_fee_balance_wei),
+                    outstanding_debt_gwei: to_gwei(outstanding_debt_wei),
+                    projected_debt_growth_gwei: to_gwei(recommendation.projected_debt_growth_wei),
+                    horizon_hours: self.top_up_horizon_hours,
+                    top_up_gwei: to_gwei(recommendation.top_up_wei),
+                }
+                .tmb(0),
+            })
+            .expect("UiGateway is dead");
     }
 
-    fn financial_statistics(&self) -> Ref<'_, FinancialStatistics> {
-        self.financial_statistics.borrow()
+    fn announce_spend_rate_deferral(&self, deferral: SpendRateDeferral) {
+        let to_gwei = |wei: u128| u64::try_from(wei / WEIS_IN_GWEI as u128).unwrap_or(u64::MAX);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway is unbound")
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiSpendRateLimitBroadcast {
+                    deferred_payments: deferral.deferred_payments as u64,
+                    deferred_gwei: to_gwei(deferral.deferred_wei),
+                    service_fee_allowance_gwei: to_gwei(deferral.service_fee_allowance_wei),
+                    transaction_fee_allowance_gwei: to_gwei(deferral.transaction_fee_allowance_wei),
+                }
+                .tmb(0),
+            })
+            .expect("UiGateway is dead");
     }
-}
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct PendingPayableId {
-    pub rowid: u64,
-    pub hash: H256,
-}
+    fn handle_finance_handshake(
+        &mut self,
+        msg: &UiFinanceHandshakeRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let version = msg.max_version.clamp(1, FINANCE_PROTOCOL_VERSION);
+        self.finance_versions.insert(client_id, version);
+        let body = UiFinanceHandshakeResponse {
+            version,
+            capabilities: finance_capabilities(version),
+        }
+        .tmb(context_id);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body: FinanceEnvelope::stamp(body, version),
+            })
+            .expect("UiGateway is dead");
+    }
 
-impl PendingPayableId {
-    pub fn new(rowid: u64, hash: H256) -> Self {
-        Self { rowid, hash }
+    // A version named in the request itself beats the one from the handshake; UIs that state
+    // neither get the latest
+    fn finance_version(&self, client_id: u64, request: &MessageBody) -> u32 {
+        FinanceEnvelope::version_of(request)
+            .or_else(|| self.finance_versions.get(&client_id).copied())
+            .unwrap_or(FINANCE_PROTOCOL_VERSION)
+            .clamp(1, FINANCE_PROTOCOL_VERSION)
     }
 
-    fn rowids(ids: &[Self]) -> Vec<u64> {
-        ids.iter().map(|id| id.rowid).collect()
+    fn handle_financials(
+        &self,
+        msg: &UiFinancialsRequest,
+        client_id: u64,
+        context_id: u64,
+        version: u32,
+    ) {
+        let body = FinanceEnvelope::stamp(
+            FinanceEnvelope::downgrade(self.compute_financials(msg, context_id), version),
+            version,
+        );
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
     }
 
-    fn serialize_hashes_to_string(ids: &[Self]) -> String {
-        comma_joined_stringifiable(ids, |id| format!("{:?}", id.hash))
+    fn handle_retry_failed_payments(
+        &mut self,
+        msg: &UiRetryFailedPaymentsRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let body = match self.resubmit_failed_payments(msg) {
+            Ok(resubmitted_hashes) => {
+                let mut fingerprints = self.pending_payable_dao.return_failed_fingerprints();
+                fingerprints.extend(self.pending_payable_dao.return_all_errorless_fingerprints());
+                UiRetryFailedPaymentsResponse {
+                    payments: remap_unconfirmed_payments(fingerprints),
+                    resubmitted_hashes,
+                }
+                .tmb(context_id)
+            }
+            Err(e) => {
+                warning!(self.logger, "Failed payments not resubmitted: {}", e);
+                MessageBody {
+                    opcode: "retryFailedPayments".to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((PAYMENT_RETRY_ERROR, e)),
+                }
+            }
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
     }
-}
 
-impl From<PendingPayableFingerprint> for PendingPayableId {
-    fn from(pending_payable_fingerprint: PendingPayableFingerprint) -> Self {
-        Self {
-            hash: pending_payable_fingerprint.hash,
-            rowid: pending_payable_fingerprint.rowid,
+    fn resubmit_failed_payments(
+        &mut self,
+        msg: &UiRetryFailedPaymentsRequest,
+    ) -> Result<Vec<String>, String> {
+        if msg.transaction_hashes.is_empty() {
+            return Ok(vec![]);
+        }
+        let failed_fingerprints = self.pending_payable_dao.return_failed_fingerprints();
+        let rowids_and_hashes = msg
+            .transaction_hashes
+            .iter()
+            .map(|hash_str| {
+                let hash = H256::from_str(hash_str.trim_start_matches("0x"))
+                    .map_err(|_| format!("Invalid transaction hash: {}", hash_str))?;
+                failed_fingerprints
+                    .iter()
+                    .find(|fingerprint| fingerprint.hash == hash)
+                    .map(|fingerprint| (fingerprint.rowid, hash))
+                    .ok_or_else(|| format!("No failed payment with transaction hash {:?}", hash))
+            })
+            .collect::<Result<Vec<(u64, H256)>, String>>()?;
+        if self.scanners.payable.scan_started_at().is_some() {
+            return Err("Payables are being paid right now; try again after the scan".to_string());
+        }
+        let consuming_wallet = self
+            .wallet_selector
+            .select(&self.consuming_wallets())
+            .ok_or_else(|| "No consuming wallet to pay from".to_string())?;
+        let (rowids, hashes): (Vec<u64>, Vec<H256>) = rowids_and_hashes.into_iter().unzip();
+        let serialized_hashes = hashes
+            .iter()
+            .map(|hash| format!("{:?}", hash))
+            .collect::<Vec<String>>();
+        // The payables must be released first; if they still pointed to deleted fingerprints,
+        // they would never be paid again
+        let payables = self
+            .payable_dao
+            .release_failed_payables(&rowids)
+            .map_err(|e| format!("Releasing payables failed: {:?}", e))?;
+        self.pending_payable_dao
+            .delete_fingerprints(&rowids)
+            .map_err(|e| format!("Deleting failed transactions failed: {:?}", e))?;
+        info!(
+            self.logger,
+            "Resubmitting {} payables of failed transactions {}",
+            payables.len(),
+            serialized_hashes.join(", ")
+        );
+        if !payables.is_empty() {
+            self.scanners.payable.mark_as_started(SystemTime::now());
+            self.payable_scan_id = self.payable_scan_id.next();
+            self.qualified_payables_sub_opt
+                .as_ref()
+                .expect("BlockchainBridge is unbound")
+                .try_send(QualifiedPayablesMessage {
+                    scan_id: self.payable_scan_id,
+                    ..QualifiedPayablesMessage::new(
+                        Obfuscated::obfuscate_vector(payables),
+                        consuming_wallet,
+                        msg.gas_price_bump_percent_opt,
+                        None,
+                    )
+                })
+                .expect("BlockchainBridge is dead");
         }
+        Ok(serialized_hashes)
     }
-}
 
-pub fn comma_joined_stringifiable<T, F>(collection: &[T], stringify: F) -> String
-where
-    F: FnMut(&T) -> String,
-{
-    collection.iter().map(stringify).join(", ")
-}
+    fn handle_write_off_payable(
+        &mut self,
+        msg: &UiWriteOffPayableRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let body = match self.write_off_payable(msg) {
+            Ok(written_off_wei) => UiWriteOffPayableResponse {
+                written_off_gwei: wei_to_gwei(written_off_wei),
+            }
+            .tmb(context_id),
+            Err(e) => {
+                warning!(self.logger, "Payable not written off: {}", e);
+                MessageBody {
+                    opcode: "writeOffPayable".to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((PAYABLE_WRITE_OFF_ERROR, e)),
+                }
+            }
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
 
-pub fn sign_conversion<T: Copy, S: TryFrom<T>>(num: T) -> Result<S, T> {
-    S::try_from(num).map_err(|_| num)
-}
+    fn write_off_payable(&mut self, msg: &UiWriteOffPayableRequest) -> Result<u128, String> {
+        let wallet = Wallet::from_str(&msg.creditor_wallet).map_err(|e| match e {
+            WalletError::InvalidChecksum(_) => format!("Invalid creditor wallet: {}", e),
+            _ => format!("Invalid creditor wallet: {}", msg.creditor_wallet),
+        })?;
+        // The payable could be on its way to the BlockchainBridge
+        if self.scanners.payable.scan_started_at().is_some() {
+            return Err("Payables are being paid right now; try again after the scan".to_string());
+        }
+        let written_off_wei = self
+            .payable_dao
+            .write_off(&wallet, &msg.reason, msg.ban_creditor, SystemTime::now())
+            .map_err(|e| match e {
+                PayableDaoError::NoSuchPayable => format!("No payable is owed to {}", wallet),
+                PayableDaoError::PaymentPending(_) => format!(
+                    "The payable owed to {} is being paid; try again once the payment is \
+                     confirmed or failed",
+                    wallet
+                ),
+                e => format!("Writing off the payable failed: {:?}", e),
+            })?;
+        if msg.ban_creditor {
+            BAN_CACHE.insert(wallet.clone())
+        }
+        info!(
+            self.logger,
+            "Wrote off the payable of {} wei owed to {}{}; reason: {}",
+            written_off_wei,
+            wallet,
+            if msg.ban_creditor {
+                " and banned the creditor"
+            } else {
+                ""
+            },
+            msg.reason
+        );
+        Ok(written_off_wei)
+    }
 
-pub fn politely_checked_conversion<T: Copy + Display, S: TryFrom<T>>(num: T) -> Result<S, String> {
-    sign_conversion(num).map_err(|num| {
-        format!(
-            "Overflow detected with {}: cannot be converted from {} to {}",
-            num,
-            type_name::<T>(),
-            type_name::<S>()
-        )
-    })
-}
+    fn handle_export_accounts(
+        &self,
+        msg: &UiExportAccountsRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let snapshot = self.accounts_transfer_dao.export();
+        let body = match write_accounts_file(Path::new(&msg.path), &snapshot) {
+            Ok(()) => {
+                info!(
+                    self.logger,
+                    "Exported {} payables, {} receivables and {} pending payables to {}",
+                    snapshot.payables.len(),
+                    snapshot.receivables.len(),
+                    snapshot.fingerprints.len(),
+                    msg.path
+                );
+                UiExportAccountsResponse {
+                    payables: snapshot.payables.len(),
+                    receivables: snapshot.receivables.len(),
+                    pending_payables: snapshot.fingerprints.len(),
+                }
+                .tmb(context_id)
+            }
+            Err(e) => {
+                warning!(self.logger, "Accounts not exported: {}", e);
+                MessageBody {
+                    opcode: "exportAccounts".to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((ACCOUNTS_EXPORT_ERROR, e)),
+                }
+            }
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
 
-#[track_caller]
-pub fn checked_conversion<T: Copy + Display, S: TryFrom<T>>(num: T) -> S {
-    politely_checked_conversion(num).unwrap_or_else(|msg| panic!("{}", msg))
-}
+    fn handle_import_accounts(
+        &mut self,
+        msg: &UiImportAccountsRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let body = match self.import_accounts(msg) {
+            Ok(snapshot) => UiImportAccountsResponse {
+                payables: snapshot.payables.len(),
+                receivables: snapshot.receivables.len(),
+                pending_payables: snapshot.fingerprints.len(),
+            }
+            .tmb(context_id),
+            Err(e) => {
+                warning!(self.logger, "Accounts not imported: {}", e);
+                MessageBody {
+                    opcode: "importAccounts".to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((ACCOUNTS_IMPORT_ERROR, e)),
+                }
+            }
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
 
-pub fn gwei_to_wei<T: Mul<Output = T> + From<u32> + From<S>, S>(gwei: S) -> T {
-    (T::from(gwei)).mul(T::from(WEIS_IN_GWEI as u32))
-}
+    fn import_accounts(
+        &mut self,
+        msg: &UiImportAccountsRequest,
+    ) -> Result<AccountsSnapshot, String> {
+        // The scans would work with the rows as they were when they began
+        if self.scanners.payable.scan_started_at().is_some()
+            || self.scanners.pending_payable.scan_started_at().is_some()
+        {
+            return Err(
+                "Payables are being scanned right now; try again after the scan".to_string(),
+            );
+        }
+        let snapshot = read_accounts_file(Path::new(&msg.path))?;
+        self.accounts_transfer_dao
+            .import(&snapshot)
+            .map_err(|e| match e {
+                AccountsTransferDaoError::Conflicts(conflicts) => format!(
+                    "Nothing imported; these records would overwrite existing ones: {}",
+                    conflicts.join(", ")
+                ),
+                AccountsTransferDaoError::ChainMismatch(chain_name) => format!(
+                    "Nothing imported; the accounts are on {}, not on the chain of this Node",
+                    chain_name
+                ),
+                AccountsTransferDaoError::UnknownFingerprint(hash) => format!(
+                    "Nothing imported; a payable awaits the transaction {:?}, which is missing \
+                     among the pending payables",
+                    hash
+                ),
+                e => format!("Importing the accounts failed: {:?}", e),
+            })?;
+        info!(
+            self.logger,
+            "Imported {} payables, {} receivables and {} pending payables from {}",
+            snapshot.payables.len(),
+            snapshot.receivables.len(),
+            snapshot.fingerprints.len(),
+            msg.path
+        );
+        Ok(snapshot)
+    }
 
-pub fn wei_to_gwei<T: TryFrom<S>, S: Display + Copy + Div<Output = S> + From<u32>>(wei: S) -> T {
-    checked_conversion::<S, T>(wei.div(S::from(WEIS_IN_GWEI as u32)))
-}
+    fn handle_account_detail(&self, msg: &UiAccountDetailRequest, client_id: u64, context_id: u64) {
+        let body = match self.account_detail(msg) {
+            Ok(response) => response.tmb(context_id),
+            Err(e) => MessageBody {
+                opcode: "accountDetail".to_string(),
+                path: MessagePath::Conversation(context_id),
+                payload: Err((ACCOUNT_DETAIL_ERROR, e)),
+            },
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::accountant::db_access_objects::payable_dao::{
-        PayableAccount, PayableDaoError, PayableDaoFactory,
-    };
-    use crate::accountant::db_access_objects::pending_payable_dao::{
-        PendingPayable, PendingPayableDaoError, TransactionHashes,
-    };
-    use crate::accountant::db_access_objects::receivable_dao::ReceivableAccount;
-    use crate::accountant::db_access_objects::utils::{from_time_t, to_time_t, CustomQuery};
-    use crate::accountant::payment_adjuster::Adjustment;
-    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
-    use crate::accountant::scanners::test_utils::protect_payables_in_test;
-    use crate::accountant::scanners::BeginScanError;
-    use crate::accountant::test_utils::DaoWithDestination::{
-        ForAccountantBody, ForPayableScanner, ForPendingPayableScanner, ForReceivableScanner,
-    };
-    use crate::accountant::test_utils::{
-        bc_from_earning_wallet, bc_from_wallets, make_payable_account, make_payables,
-        BannedDaoFactoryMock, ConfigDaoFactoryMock, MessageIdGeneratorMock, NullScanner,
-        PayableDaoFactoryMock, PayableDaoMock, PayableScannerBuilder, PaymentAdjusterMock,
-        PendingPayableDaoFactoryMock, PendingPayableDaoMock, ReceivableDaoFactoryMock,
-        ReceivableDaoMock, ScannerMock,
-    };
-    use crate::accountant::test_utils::{AccountantBuilder, BannedDaoMock};
-    use crate::accountant::Accountant;
-    use crate::blockchain::blockchain_bridge::BlockchainBridge;
-    use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
-    use crate::blockchain::test_utils::{
-        make_blockchain_interface_web3, make_tx_hash, ReceiptResponseBuilder,
-    };
-    use crate::database::rusqlite_wrappers::TransactionSafeWrapper;
-    use crate::database::test_utils::transaction_wrapper_mock::TransactionInnerWrapperMockBuilder;
-    use crate::db_config::config_dao::ConfigDaoRecord;
-    use crate::db_config::mocks::ConfigDaoMock;
-    use crate::match_every_type_id;
-    use crate::sub_lib::accountant::{
-        ExitServiceConsumed, PaymentThresholds, RoutingServiceConsumed, ScanIntervals,
-        DEFAULT_EARNING_WALLET, DEFAULT_PAYMENT_THRESHOLDS,
-    };
-    use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
-    use crate::sub_lib::neighborhood::ConfigChange;
-    use crate::sub_lib::neighborhood::{Hops, WalletPair};
-    use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
-    use crate::test_utils::recorder::make_recorder;
-    use crate::test_utils::recorder::peer_actors_builder;
-    use crate::test_utils::recorder::Recorder;
-    use crate::test_utils::recorder_stop_conditions::{StopCondition, StopConditions};
-    use crate::test_utils::unshared_test_utils::arbitrary_id_stamp::ArbitraryIdStamp;
-    use crate::test_utils::unshared_test_utils::notify_handlers::NotifyLaterHandleMock;
-    use crate::test_utils::unshared_test_utils::system_killer_actor::SystemKillerActor;
-    use crate::test_utils::unshared_test_utils::{
-        assert_on_initialization_with_panic_on_migration, make_bc_with_defaults,
-        prove_that_crash_request_handler_is_hooked_up, AssertionsMessage,
-    };
-    use crate::test_utils::{make_paying_wallet, make_wallet};
-    use actix::{Arbiter, System};
-    use ethereum_types::U64;
-    use ethsign_crypto::Keccak256;
-    use log::Level;
-    use masq_lib::constants::{
-        REQUEST_WITH_MUTUALLY_EXCLUSIVE_PARAMS, REQUEST_WITH_NO_VALUES, SCAN_ERROR,
-        VALUE_EXCEEDS_ALLOWED_LIMIT,
-    };
-    use masq_lib::messages::TopRecordsOrdering::{Age, Balance};
-    use masq_lib::messages::{
-        CustomQueries, RangeQuery, ScanType, TopRecordsConfig, UiFinancialStatistics,
-        UiMessageError, UiPayableAccount, UiReceivableAccount, UiScanRequest, UiScanResponse,
-    };
-    use masq_lib::test_utils::logging::init_test_logging;
-    use masq_lib::test_utils::logging::TestLogHandler;
-    use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
-    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
-    use masq_lib::ui_gateway::MessagePath::Conversation;
-    use masq_lib::ui_gateway::{MessageBody, MessagePath, NodeFromUiMessage, NodeToUiMessage};
-    use masq_lib::utils::find_free_port;
-    use std::any::TypeId;
-    use std::ops::{Add, Sub};
-    use std::str::FromStr;
-    use std::sync::Arc;
-    use std::sync::Mutex;
-    use std::time::Duration;
-    use std::vec;
-    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionBlock, TxReceipt, TxStatus};
-
-    impl Handler<AssertionsMessage<Accountant>> for Accountant {
-        type Result = ();
+    // Everything the database keeps about the counterpart. Payments we've sent are only tracked
+    // until confirmed, so no more than the one pending can be shown
+    fn account_detail(
+        &self,
+        msg: &UiAccountDetailRequest,
+    ) -> Result<UiAccountDetailResponse, String> {
+        let wallet = Wallet::from_str(&msg.wallet).map_err(|e| match e {
+            WalletError::InvalidChecksum(_) => format!("Invalid wallet: {}", e),
+            _ => format!("Invalid wallet: {}", msg.wallet),
+        })?;
+        let payable_opt = self.payable_dao.account_status(&wallet).map(|account| {
+            let fingerprints = if account.pending_payable_opt.is_some() {
+                let mut fingerprints = self.pending_payable_dao.return_failed_fingerprints();
+                fingerprints.extend(self.pending_payable_dao.return_all_errorless_fingerprints());
+                fingerprints
+            } else {
+                vec![]
+            };
+            remap_payable_detail(account, &fingerprints)
+        });
+        Ok(UiAccountDetailResponse {
+            wallet: wallet.to_string(),
+            banned: BAN_CACHE.is_banned(&wallet),
+            payable_opt,
+            receivable_opt: self
+                .receivable_dao
+                .account_status(&wallet)
+                .map(remap_receivable_detail),
+            received_payments: remap_received_payments(
+                self.receivable_dao.received_payments_from(&wallet),
+            ),
+            write_offs: remap_write_offs(self.payable_dao.write_offs(&wallet)),
+        })
+    }
 
-        fn handle(
-            &mut self,
-            msg: AssertionsMessage<Accountant>,
-            _ctx: &mut Self::Context,
-        ) -> Self::Result {
-            (msg.assertions)(self)
-        }
+    fn handle_why_not_paid(&self, msg: &UiWhyNotPaidRequest, client_id: u64, context_id: u64) {
+        let body = match self.why_not_paid(msg, SystemTime::now()) {
+            Ok(response) => response.tmb(context_id),
+            Err(e) => MessageBody {
+                opcode: "whyNotPaid".to_string(),
+                path: MessagePath::Conversation(context_id),
+                payload: Err((WHY_NOT_PAID_ERROR, e)),
+            },
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
     }
 
-    #[test]
-    fn constants_have_correct_values() {
-        assert_eq!(CRASH_KEY, "ACCOUNTANT");
-        assert_eq!(DEFAULT_PENDING_TOO_LONG_SEC, 21_600);
+    // A debt too young or too small never makes it into a payable scan; one that did is explained
+    // by what the latest scan considering it decided
+    fn why_not_paid(
+        &self,
+        msg: &UiWhyNotPaidRequest,
+        now: SystemTime,
+    ) -> Result<UiWhyNotPaidResponse, String> {
+        let wallet = Wallet::from_str(&msg.wallet).map_err(|e| match e {
+            WalletError::InvalidChecksum(_) => format!("Invalid wallet: {}", e),
+            _ => format!("Invalid wallet: {}", msg.wallet),
+        })?;
+        let payable_opt = self.payable_dao.account_status(&wallet);
+        let below_threshold = payable_opt
+            .as_ref()
+            .map(|payable| self.scanners.payable.is_below_threshold(payable, now))
+            .unwrap_or(false);
+        Ok(UiWhyNotPaidResponse {
+            wallet: wallet.to_string(),
+            owed_gwei: payable_opt
+                .map(|payable| wei_to_gwei(payable.balance_wei))
+                .unwrap_or(0),
+            below_threshold,
+            latest_scan_opt: self
+                .payment_decisions
+                .chain_opt(&wallet)
+                .map(|chain| chain.to_ui(now)),
+        })
     }
 
-    #[test]
-    fn new_calls_factories_properly() {
-        let config = make_bc_with_defaults();
-        let payable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
-        let pending_payable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
-        let receivable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
-        let banned_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
-        let config_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_factory = PayableDaoFactoryMock::new()
-            .make_params(&payable_dao_factory_params_arc)
-            .make_result(PayableDaoMock::new()) // For Accountant
-            .make_result(PayableDaoMock::new()) // For Payable Scanner
-            .make_result(PayableDaoMock::new()); // For PendingPayable Scanner
-        let pending_payable_dao_factory = PendingPayableDaoFactoryMock::new()
-            .make_params(&pending_payable_dao_factory_params_arc)
-            .make_result(PendingPayableDaoMock::new()) // For Accountant
-            .make_result(PendingPayableDaoMock::new()) // For Payable Scanner
-            .make_result(PendingPayableDaoMock::new()); // For PendingPayable Scanner
-        let receivable_dao_factory = ReceivableDaoFactoryMock::new()
-            .make_params(&receivable_dao_factory_params_arc)
-            .make_result(ReceivableDaoMock::new()) // For Accountant
-            .make_result(ReceivableDaoMock::new()); // For Receivable Scanner
-        let banned_dao_factory = BannedDaoFactoryMock::new()
-            .make_params(&banned_dao_factory_params_arc)
-            .make_result(BannedDaoMock::new()); // For Receivable Scanner
-        let config_dao_factory = ConfigDaoFactoryMock::new()
-            .make_params(&config_dao_factory_params_arc)
-            .make_result(ConfigDaoMock::new()); // For receivable scanner
+    fn handle_disputes(&self, client_id: u64, context_id: u64) {
+        let body = UiDisputesResponse {
+            disputes: remap_invoice_disputes(self.payable_dao.invoice_disputes()),
+        }
+        .tmb(context_id);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
 
-        let _ = Accountant::new(
-            config,
-            DaoFactories {
-                payable_dao_factory: Box::new(payable_dao_factory),
-                pending_payable_dao_factory: Box::new(pending_payable_dao_factory),
-                receivable_dao_factory: Box::new(receivable_dao_factory),
-                banned_dao_factory: Box::new(banned_dao_factory),
-                config_dao_factory: Box::new(config_dao_factory),
+    fn handle_check_consistency(&mut self, client_id: u64, context_id: u64) {
+        let body = match self.refuse_while_payables_are_scanned() {
+            Ok(()) => {
+                let plan_opt = self.repair_plans.propose(self.consistency_dao.anomalies());
+                UiCheckConsistencyResponse {
+                    plan_id_opt: plan_opt.as_ref().map(|plan| plan.id),
+                    anomalies: plan_opt
+                        .map(|plan| plan.anomalies)
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|anomaly| UiAnomaly {
+                            description: anomaly.to_string(),
+                            repair: anomaly.repair().to_string(),
+                        })
+                        .collect(),
+                }
+                .tmb(context_id)
+            }
+            Err(e) => MessageBody {
+                opcode: "checkConsistency".to_string(),
+                path: MessagePath::Conversation(context_id),
+                payload: Err((CONSISTENCY_REPAIR_ERROR, e)),
             },
-        );
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
 
-        assert_eq!(
-            *payable_dao_factory_params_arc.lock().unwrap(),
-            vec![(), (), ()]
-        );
-        assert_eq!(
-            *pending_payable_dao_factory_params_arc.lock().unwrap(),
-            vec![(), (), ()]
-        );
-        assert_eq!(
-            *receivable_dao_factory_params_arc.lock().unwrap(),
-            vec![(), ()]
-        );
-        assert_eq!(*banned_dao_factory_params_arc.lock().unwrap(), vec![()]);
-        assert_eq!(*config_dao_factory_params_arc.lock().unwrap(), vec![()]);
+    fn handle_repair_consistency(
+        &mut self,
+        msg: &UiRepairConsistencyRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let body = match self.repair_consistency(msg) {
+            Ok(repaired) => UiRepairConsistencyResponse { repaired }.tmb(context_id),
+            Err(e) => {
+                warning!(self.logger, "Accounts not repaired: {}", e);
+                MessageBody {
+                    opcode: "repairConsistency".to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((CONSISTENCY_REPAIR_ERROR, e)),
+                }
+            }
+        };
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
     }
 
-    #[test]
-    fn accountant_have_proper_defaulted_values() {
-        let bootstrapper_config = make_bc_with_defaults();
-        let payable_dao_factory = Box::new(
-            PayableDaoFactoryMock::new()
-                .make_result(PayableDaoMock::new()) // For Accountant
-                .make_result(PayableDaoMock::new()) // For Payable Scanner
-                .make_result(PayableDaoMock::new()), // For PendingPayable Scanner
-        );
-        let pending_payable_dao_factory = Box::new(
-            PendingPayableDaoFactoryMock::new()
-                .make_result(PendingPayableDaoMock::new()) // For Accountant
-                .make_result(PendingPayableDaoMock::new()) // For Payable Scanner
-                .make_result(PendingPayableDaoMock::new()), // For PendingPayable Scanner
-        );
-        let receivable_dao_factory = Box::new(
-            ReceivableDaoFactoryMock::new()
-                .make_result(ReceivableDaoMock::new()) // For Accountant
-                .make_result(ReceivableDaoMock::new()), // For Scanner
-        );
-        let banned_dao_factory =
-            Box::new(BannedDaoFactoryMock::new().make_result(BannedDaoMock::new()));
-        let config_dao_factory =
-            Box::new(ConfigDaoFactoryMock::new().make_result(ConfigDaoMock::new()));
+    fn repair_consistency(&mut self, msg: &UiRepairConsistencyRequest) -> Result<usize, String> {
+        self.refuse_while_payables_are_scanned()?;
+        let plan = self.repair_plans.take(msg.plan_id)?;
+        self.consistency_dao
+            .repair(&plan.anomalies)
+            .map_err(|e| match e {
+                ConsistencyDaoError::AnomalyGone(anomaly) => format!(
+                    "Nothing repaired; the accounts have changed since the check: {}; check \
+                     the consistency again",
+                    anomaly
+                ),
+                e => format!("Repairing the accounts failed: {:?}", e),
+            })?;
+        plan.anomalies.iter().for_each(|anomaly| {
+            info!(
+                self.logger,
+                "Repaired inconsistent accounts: {}; repair: {}",
+                anomaly,
+                anomaly.repair()
+            )
+        });
+        Ok(plan.anomalies.len())
+    }
 
-        let result = Accountant::new(
-            bootstrapper_config,
-            DaoFactories {
-                payable_dao_factory,
-                pending_payable_dao_factory,
-                receivable_dao_factory,
-                banned_dao_factory,
-                config_dao_factory,
-            },
-        );
+    // Mid-scan, a fingerprint is recorded before its payables are marked as waiting for it
+    fn refuse_while_payables_are_scanned(&self) -> Result<(), String> {
+        if self.scanners.payable.scan_started_at().is_some()
+            || self.scanners.pending_payable.scan_started_at().is_some()
+        {
+            Err(PAYABLES_BEING_SCANNED.to_string())
+        } else {
+            Ok(())
+        }
+    }
 
-        let financial_statistics = result.financial_statistics().clone();
-        let assert_scan_scheduler = |scan_type: ScanType, expected_scan_interval: Duration| {
-            assert_eq!(
-                result
-                    .scan_schedulers
-                    .schedulers
-                    .get(&scan_type)
-                    .unwrap()
-                    .interval(),
-                expected_scan_interval
-            )
+    fn compute_financials(&self, msg: &UiFinancialsRequest, context_id: u64) -> MessageBody {
+        if let Err(message_body) = financials_entry_check(msg, context_id) {
+            return message_body;
         };
-        let default_scan_intervals = ScanIntervals::default();
-        assert_scan_scheduler(
-            ScanType::Payables,
-            default_scan_intervals.payable_scan_interval,
-        );
-        assert_scan_scheduler(
-            ScanType::PendingPayables,
-            default_scan_intervals.pending_payable_scan_interval,
-        );
-        assert_scan_scheduler(
-            ScanType::Receivables,
-            default_scan_intervals.receivable_scan_interval,
-        );
-        assert_eq!(result.consuming_wallet_opt, None);
-        assert_eq!(result.earning_wallet, *DEFAULT_EARNING_WALLET);
-        assert_eq!(result.suppress_initial_scans, false);
-        result
-            .message_id_generator
-            .as_any()
-            .downcast_ref::<MessageIdGeneratorReal>()
-            .unwrap();
-        assert_eq!(result.crashable, false);
-        assert_eq!(financial_statistics.total_paid_receivable_wei, 0);
-        assert_eq!(financial_statistics.total_paid_payable_wei, 0);
+        let stats_opt = self.process_stats(msg);
+        let query_results_opt = match self.process_queries_of_records(msg, context_id) {
+            Ok(results_opt) => results_opt,
+            Err(message_body) => return message_body,
+        };
+        UiFinancialsResponse {
+            stats_opt,
+            query_results_opt,
+        }
+        .tmb(context_id)
     }
 
-    #[test]
-    fn accountant_handles_config_change_msg() {
-        assert_handling_of_config_change_msg(
-            ConfigChangeMsg {
-                change: ConfigChange::UpdateWallets(WalletPair {
-                    consuming_wallet: make_paying_wallet(b"new_consuming_wallet"),
-                    earning_wallet: make_wallet("new_earning_wallet"),
-                }),
-            },
-            |subject: &Accountant| {
-                assert_eq!(
-                    subject.consuming_wallet_opt,
-                    Some(make_paying_wallet(b"new_consuming_wallet"))
-                );
-                assert_eq!(subject.earning_wallet, make_wallet("new_earning_wallet"));
-                let _ = TestLogHandler::new().assert_logs_contain_in_order(
-                    vec![
-                        "INFO: ConfigChange: Earning Wallet has been updated: 0x00006e65775f6561726e696e675f77616c6c6574",
-                        "INFO: ConfigChange: Consuming Wallet has been updated: 0xfa133bbf90bce093fa2e7caa6da68054af66793e",
-                    ]
-                );
-            },
-        );
-        assert_handling_of_config_change_msg(
-            ConfigChangeMsg {
-                change: ConfigChange::UpdatePassword("new password".to_string()),
-            },
-            |_subject: &Accountant| {
-                let _ = TestLogHandler::new().exists_log_containing(
-                    "TRACE: ConfigChange: Ignored irrelevant message: \
-                    ConfigChangeMsg { change: UpdatePassword(\"new password\") }",
-                );
-            },
-        );
-        assert_handling_of_config_change_msg(
-            ConfigChangeMsg {
-                change: ConfigChange::UpdateMinHops(Hops::FourHops),
-            },
-            |_subject: &Accountant| {
-                let _ = TestLogHandler::new().exists_log_containing(
-                    "TRACE: ConfigChange: Ignored irrelevant message: \
-                    ConfigChangeMsg { change: UpdateMinHops(FourHops) }",
-                );
-            },
-        );
+    fn request_payable_accounts_by_specific_mode(
+        &self,
+        mode: CustomQuery<u64>,
+    ) -> Option<Vec<UiPayableAccount>> {
+        self.payable_dao.custom_query(mode).map(|accounts| {
+            let fingerprints = if accounts
+                .iter()
+                .any(|account| account.pending_payable_opt.is_some())
+            {
+                let mut fingerprints = self.pending_payable_dao.return_failed_fingerprints();
+                fingerprints.extend(self.pending_payable_dao.return_all_errorless_fingerprints());
+                fingerprints
+            } else {
+                vec![]
+            };
+            remap_payable_accounts(accounts, &fingerprints)
+        })
     }
 
-    fn assert_handling_of_config_change_msg<A>(msg: ConfigChangeMsg, assertions: A)
-    where
-        A: FnOnce(&Accountant),
-    {
-        init_test_logging();
-        let mut subject = AccountantBuilder::default()
-            .bootstrapper_config(make_bc_with_defaults())
-            .build();
-        subject.logger = Logger::new("ConfigChange");
-
-        subject.handle_config_change_msg(msg);
+    fn request_receivable_accounts_by_specific_mode(
+        &self,
+        mode: CustomQuery<i64>,
+    ) -> Option<Vec<UiReceivableAccount>> {
+        self.receivable_dao
+            .custom_query(mode)
+            .map(remap_receivable_accounts)
+    }
 
-        assertions(&subject);
+    fn process_stats(&self, msg: &UiFinancialsRequest) -> Option<UiFinancialStatistics> {
+        if msg.stats_required {
+            let now = SystemTime::now();
+            let financial_statistics = self.financial_statistics();
+            let total_unpaid_and_pending_payable_wei = self.payable_dao.total();
+            let total_unpaid_receivable_wei = self.receivable_dao.total();
+            let approximate_fiat_opt = self.price_feed.quote_opt().map(|quote| {
+                let in_fiat = |wei: u128| {
+                    format_fiat(quote.service_fee_in_fiat(i128::try_from(wei).unwrap_or(i128::MAX)))
+                };
+                UiApproximateFiatTotals {
+                    total_unpaid_and_pending_payable: in_fiat(total_unpaid_and_pending_payable_wei),
+                    total_paid_payable: in_fiat(financial_statistics.total_paid_payable_wei),
+                    total_unpaid_receivable: format_fiat(
+                        quote.service_fee_in_fiat(total_unpaid_receivable_wei),
+                    ),
+                    total_paid_receivable: in_fiat(financial_statistics.total_paid_receivable_wei),
+                    currency: quote.currency,
+                }
+            });
+            Some(UiFinancialStatistics {
+                total_unpaid_and_pending_payable_gwei: wei_to_gwei(
+                    total_unpaid_and_pending_payable_wei,
+                ),
+                total_paid_payable_gwei: wei_to_gwei(financial_statistics.total_paid_payable_wei),
+                total_unpaid_receivable_gwei: wei_to_gwei(total_unpaid_receivable_wei),
+                total_paid_receivable_gwei: wei_to_gwei(
+                    financial_statistics.total_paid_receivable_wei,
+                ),
+                approximate_fiat_opt,
+                observe_only_opt: self.observe_only_payables_opt(),
+                aging: remap_debt_aging(
+                    self.payable_dao.aging(now),
+                    self.receivable_dao.aging(now),
+                ),
+            })
+        } else {
+            None
+        }
     }
 
-    #[test]
-    fn scan_receivables_request() {
-        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(10_000),
-            receivable_scan_interval: Duration::from_millis(10_000),
-            pending_payable_scan_interval: Duration::from_secs(100),
-        });
-        let receivable_dao = ReceivableDaoMock::new()
-            .new_delinquencies_result(vec![])
-            .paid_delinquencies_result(vec![]);
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
-            .build();
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
-        let ui_message = NodeFromUiMessage {
-            client_id: 1234,
-            body: UiScanRequest {
-                scan_type: ScanType::Receivables,
-            }
-            .tmb(4321),
-        };
+    fn observe_only_payables_opt(&self) -> Option<UiObserveOnlyPayables> {
+        if self.consuming_wallet_opt.is_some() {
+            return None;
+        }
+        let observed_opt = self.scanners.payable.observed_payables_opt();
+        Some(UiObserveOnlyPayables {
+            would_be_paid_accounts: observed_opt.map_or(0, |observed| observed.accounts as u64),
+            would_be_paid_gwei: observed_opt.map_or(0, |observed| wei_to_gwei(observed.total_wei)),
+        })
+    }
 
-        subject_addr.try_send(ui_message).unwrap();
+    fn process_top_records_query(&self, msg: &UiFinancialsRequest) -> Option<QueryResults> {
+        msg.top_records_opt.map(|config| {
+            let payable = self
+                .request_payable_accounts_by_specific_mode(config.into())
+                .unwrap_or_default();
+            let receivable = self
+                .request_receivable_accounts_by_specific_mode(config.into())
+                .unwrap_or_default();
 
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        assert_eq!(
-            blockchain_bridge_recording.get_record::<RetrieveTransactions>(0),
-            &RetrieveTransactions {
-                recipient: make_wallet("earning_wallet"),
-                response_skeleton_opt: Some(ResponseSkeleton {
-                    client_id: 1234,
-                    context_id: 4321,
-                }),
+            QueryResults {
+                payable_opt: Some(payable),
+                receivable_opt: Some(receivable),
             }
-        );
+        })
     }
 
-    #[test]
-    fn received_payments_with_response_skeleton_sends_response_to_ui_gateway() {
-        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(10_000),
-            receivable_scan_interval: Duration::from_millis(10_000),
-            pending_payable_scan_interval: Duration::from_secs(100),
-        });
-        config.suppress_initial_scans = true;
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .config_dao(
-                ConfigDaoMock::new()
-                    .get_result(Ok(ConfigDaoRecord::new("start_block", None, false)))
-                    .set_result(Ok(())),
-            )
-            .build();
-        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
-        let received_payments = ReceivedPayments {
-            timestamp: SystemTime::now(),
-            new_start_block: BlockMarker::Value(0),
-            response_skeleton_opt: Some(ResponseSkeleton {
-                client_id: 1234,
-                context_id: 4321,
-            }),
-            transactions: vec![],
-        };
-
-        subject_addr.try_send(received_payments).unwrap();
+    fn process_custom_queries(
+        &self,
+        msg: &UiFinancialsRequest,
+        context_id: u64,
+    ) -> Result<Option<QueryResults>, MessageBody> {
+        Ok(match msg.custom_queries_opt.as_ref() {
+            Some(specs) => {
+                let payable_opt = if let Some(query_specs) = specs.payable_opt.as_ref() {
+                    let query = CustomQuery::from(query_specs);
+                    check_query_is_within_tech_limits(&query, "payable", context_id)?;
+                    self.request_payable_accounts_by_specific_mode(query)
+                } else {
+                    None
+                };
+                let receivable_opt = if let Some(query_specs) = specs.receivable_opt.as_ref() {
+                    let query = CustomQuery::from(query_specs);
+                    check_query_is_within_tech_limits(&query, "receivable", context_id)?;
+                    self.request_receivable_accounts_by_specific_mode(query)
+                } else {
+                    None
+                };
 
-        System::current().stop();
-        system.run();
-        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
-        assert_eq!(
-            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
-            &NodeToUiMessage {
-                target: ClientId(1234),
-                body: UiScanResponse {}.tmb(4321),
+                Some(QueryResults {
+                    payable_opt,
+                    receivable_opt,
+                })
             }
-        );
+            None => None,
+        })
     }
 
-    #[test]
-    fn scan_payables_request() {
-        let config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
-        let consuming_wallet = make_paying_wallet(b"consuming");
-        let payable_account = PayableAccount {
-            wallet: make_wallet("wallet"),
-            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
-            last_paid_timestamp: SystemTime::now().sub(Duration::from_secs(
-                (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 1) as u64,
-            )),
-            pending_payable_opt: None,
-        };
-        let payable_dao =
-            PayableDaoMock::new().non_pending_payables_result(vec![payable_account.clone()]);
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .consuming_wallet(consuming_wallet.clone())
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .build();
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
-        let ui_message = NodeFromUiMessage {
-            client_id: 1234,
-            body: UiScanRequest {
-                scan_type: ScanType::Payables,
-            }
-            .tmb(4321),
+    fn process_queries_of_records(
+        &self,
+        msg: &UiFinancialsRequest,
+        context_id: u64,
+    ) -> Result<Option<QueryResults>, MessageBody> {
+        let top_records_opt = self.process_top_records_query(msg);
+        let custom_query_records_opt = match self.process_custom_queries(msg, context_id) {
+            Ok(query_results) => query_results,
+            Err(message_body) => return Err(message_body),
         };
+        match vec![top_records_opt, custom_query_records_opt]
+            .into_iter()
+            .find(|results| results.is_some())
+        {
+            Some(results) => Ok(results),
+            None => Ok(None),
+        }
+    }
 
-        subject_addr.try_send(ui_message).unwrap();
+    fn handle_request_of_scan_for_payable(
+        &mut self,
+        response_skeleton_opt: Option<ResponseSkeleton>,
+    ) {
+        // Scans demanded by the operator run even while the Node is idle
+        if response_skeleton_opt.is_none() && !self.lets_payable_scan_through() {
+            return;
+        }
+        let scan_id = self.payable_scan_id.next();
+        let candidates = self.consuming_wallets();
+        let (mode, wallet) = match self.wallet_selector.select(&candidates) {
+            Some(consuming_wallet) => (PayableScanMode::Paying, consuming_wallet),
+            // This argument is irrelevant as nothing is sent to the BlockchainBridge then
+            None => (PayableScanMode::ObserveOnly, self.earning_wallet.clone()),
+        };
+        self.scanners.payable.set_mode(mode);
+        let result = self.scanners.payable.begin_scan(
+            wallet,
+            SystemTime::now(),
+            response_skeleton_opt,
+            &scan_id.span(&self.logger),
+        );
+        // The scan still running keeps the id it was given
+        if !matches!(result, Err(BeginScanError::ScanAlreadyRunning(_))) {
+            self.payable_scan_id = scan_id;
+        }
 
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        assert_eq!(
-            blockchain_bridge_recording.get_record::<QualifiedPayablesMessage>(0),
-            &QualifiedPayablesMessage {
-                protected_qualified_payables: protect_payables_in_test(vec![payable_account]),
-                consuming_wallet,
-                response_skeleton_opt: Some(ResponseSkeleton {
-                    client_id: 1234,
-                    context_id: 4321,
-                })
+        match result {
+            Ok(scan_message) => {
+                self.qualified_payables_sub_opt
+                    .as_ref()
+                    .expect("BlockchainBridge is unbound")
+                    .try_send(QualifiedPayablesMessage {
+                        scan_id,
+                        ..scan_message
+                    })
+                    .expect("BlockchainBridge is dead");
             }
-        );
+            Err(e) => {
+                e.handle_error(
+                    &self.logger,
+                    ScanType::Payables,
+                    response_skeleton_opt.is_some(),
+                );
+                if let Some(report) = self.take_payable_scan_report(response_skeleton_opt) {
+                    let response_skeleton = report.response_skeleton;
+                    let result = match e {
+                        BeginScanError::NothingToProcess
+                        | BeginScanError::CalledFromNullScanner => Ok(report.nothing_paid()),
+                        BeginScanError::ScanAlreadyRunning(_) => {
+                            Err(PAYABLES_BEING_SCANNED.to_string())
+                        }
+                        BeginScanError::NoConsumingWalletFound | BeginScanError::ObserveOnly => {
+                            Err("There is no consuming wallet to pay from".to_string())
+                        }
+                    };
+                    self.send_payable_scan_report(response_skeleton, result)
+                }
+            }
+        }
     }
 
-    #[test]
-    fn sent_payable_with_response_skeleton_sends_scan_response_to_ui_gateway() {
-        let config = bc_from_earning_wallet(make_wallet("earning_wallet"));
-        let pending_payable_dao =
-            PendingPayableDaoMock::default().fingerprints_rowids_result(TransactionHashes {
-                rowid_results: vec![(1, make_tx_hash(123))],
-                no_rowid_results: vec![],
-            });
-        let payable_dao = PayableDaoMock::default().mark_pending_payables_rowids_result(Ok(()));
-        let subject = AccountantBuilder::default()
-            .pending_payable_daos(vec![ForPayableScanner(pending_payable_dao)])
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .bootstrapper_config(config)
-            .build();
-        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+    fn lets_payable_scan_through(&mut self) -> bool {
+        let payable_dao = self.payable_dao.as_ref();
+        let payment_thresholds = self.payment_thresholds.borrow();
+        self.idle_spending_gate.lets_scan_through(
+            SystemTime::now(),
+            &|| nearest_payment_deadline(payable_dao, &payment_thresholds),
+            &self.logger,
+        )
+    }
 
-        let sent_payable = SentPayables {
-            payment_procedure_result: Ok(vec![ProcessedPayableFallible::Correct(PendingPayable {
-                recipient_wallet: make_wallet("blah"),
-                hash: make_tx_hash(123),
-            })]),
-            response_skeleton_opt: Some(ResponseSkeleton {
-                client_id: 1234,
-                context_id: 4321,
-            }),
+    fn handle_request_of_scan_for_pending_payable(
+        &mut self,
+        response_skeleton_opt: Option<ResponseSkeleton>,
+    ) {
+        let result = match self.consuming_wallet_opt.clone() {
+            Some(consuming_wallet) => self.scanners.pending_payable.begin_scan(
+                consuming_wallet, // This argument is not used and is therefore irrelevant
+                SystemTime::now(),
+                response_skeleton_opt,
+                &self.logger,
+            ),
+            None => Err(BeginScanError::NoConsumingWalletFound),
         };
 
-        subject_addr.try_send(sent_payable).unwrap();
+        match result {
+            Ok(scan_message) => self
+                .request_transaction_receipts_subs_opt
+                .as_ref()
+                .expect("BlockchainBridge is unbound")
+                .try_send(scan_message)
+                .expect("BlockchainBridge is dead"),
+            Err(e) => e.handle_error(
+                &self.logger,
+                ScanType::PendingPayables,
+                response_skeleton_opt.is_some(),
+            ),
+        }
+    }
 
-        System::current().stop();
-        system.run();
-        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
-        assert_eq!(
-            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
-            &NodeToUiMessage {
-                target: ClientId(1234),
-                body: UiScanResponse {}.tmb(4321),
-            }
-        );
+    // Receipts of the previous batches are requested before the next payables are analyzed, both
+    // conversations with the BlockchainBridge then run side by side. Creditors awaiting a payment
+    // can't be paid twice: their payables stay out of the analysis until the payment is settled
+    fn check_receipts_alongside_payable_scan(&mut self) {
+        // Fingerprints of a batch in flight get tied to their creditors only once SentPayables
+        // arrives; their receipts must not be processed before that
+        if self.scanners.payable.scan_started_at().is_some() {
+            debug!(
+                self.logger,
+                "Skipping the pipelined receipt check; the running payable scan still awaits its \
+                 sent payables"
+            );
+            return;
+        }
+        self.handle_request_of_scan_for_pending_payable(None)
     }
 
-    #[test]
-    fn received_balances_and_qualified_payables_under_our_money_limit_thus_all_forwarded_to_blockchain_bridge(
+    fn handle_request_of_scan_for_receivable(
+        &mut self,
+        response_skeleton_opt: Option<ResponseSkeleton>,
     ) {
-        // the numbers for balances don't do real math, they need not to match either the condition for
-        // the payment adjustment or the actual values that come from the payable size reducing algorithm;
-        // all that is mocked in this test
-        init_test_logging();
-        let test_name = "received_balances_and_qualified_payables_under_our_money_limit_thus_all_forwarded_to_blockchain_bridge";
-        let is_adjustment_required_params_arc = Arc::new(Mutex::new(vec![]));
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let instructions_recipient = blockchain_bridge
-            .system_stop_conditions(match_every_type_id!(OutboundPaymentsInstructions))
-            .start()
-            .recipient();
-        let mut subject = AccountantBuilder::default().build();
-        let payment_adjuster = PaymentAdjusterMock::default()
-            .is_adjustment_required_params(&is_adjustment_required_params_arc)
-            .is_adjustment_required_result(Ok(None));
-        let payable_scanner = PayableScannerBuilder::new()
-            .payment_adjuster(payment_adjuster)
-            .build();
-        subject.scanners.payable = Box::new(payable_scanner);
-        subject.outbound_payments_instructions_sub_opt = Some(instructions_recipient);
-        subject.logger = Logger::new(test_name);
-        let subject_addr = subject.start();
-        let account_1 = make_payable_account(44_444);
-        let account_2 = make_payable_account(333_333);
-        let system = System::new("test");
-        let agent_id_stamp = ArbitraryIdStamp::new();
-        let agent = BlockchainAgentMock::default().set_arbitrary_id_stamp(agent_id_stamp);
-        let accounts = vec![account_1, account_2];
-        let msg = BlockchainAgentWithContextMessage {
-            protected_qualified_payables: protect_payables_in_test(accounts.clone()),
-            agent: Box::new(agent),
-            response_skeleton_opt: Some(ResponseSkeleton {
-                client_id: 1234,
-                context_id: 4321,
-            }),
+        match self.scanners.receivable.begin_scan(
+            self.earning_wallet.clone(),
+            SystemTime::now(),
+            response_skeleton_opt,
+            &self.logger,
+        ) {
+            Ok(scan_message) => self
+                .retrieve_transactions_sub_opt
+                .as_ref()
+                .expect("BlockchainBridge is unbound")
+                .try_send(scan_message)
+                .expect("BlockchainBridge is dead"),
+            Err(e) => e.handle_error(
+                &self.logger,
+                ScanType::Receivables,
+                response_skeleton_opt.is_some(),
+            ),
         };
+    }
 
-        subject_addr.try_send(msg).unwrap();
-
-        system.run();
-        let mut is_adjustment_required_params = is_adjustment_required_params_arc.lock().unwrap();
-        let (blockchain_agent_with_context_msg_actual, logger_clone) =
-            is_adjustment_required_params.remove(0);
-        assert_eq!(
-            blockchain_agent_with_context_msg_actual.protected_qualified_payables,
-            protect_payables_in_test(accounts.clone())
-        );
-        assert_eq!(
-            blockchain_agent_with_context_msg_actual.response_skeleton_opt,
-            Some(ResponseSkeleton {
-                client_id: 1234,
-                context_id: 4321,
-            })
-        );
-        assert_eq!(
-            blockchain_agent_with_context_msg_actual
-                .agent
-                .arbitrary_id_stamp(),
-            agent_id_stamp
-        );
-        assert!(is_adjustment_required_params.is_empty());
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        let payments_instructions =
-            blockchain_bridge_recording.get_record::<OutboundPaymentsInstructions>(0);
-        assert_eq!(payments_instructions.affordable_accounts, accounts);
-        assert_eq!(
-            payments_instructions.response_skeleton_opt,
-            Some(ResponseSkeleton {
-                client_id: 1234,
-                context_id: 4321,
-            })
-        );
-        assert_eq!(
-            payments_instructions.agent.arbitrary_id_stamp(),
-            agent_id_stamp
-        );
-        assert_eq!(blockchain_bridge_recording.len(), 1);
-        test_use_of_the_same_logger(&logger_clone, test_name)
-        // adjust_payments() did not need a prepared result which means it wasn't reached
-        // because otherwise this test would've panicked
+    fn handle_trigger_payable_scan(&mut self, client_id: u64, context_id: u64) {
+        let response_skeleton = ResponseSkeleton {
+            client_id,
+            context_id,
+        };
+        // The report of the scan running must not be replaced
+        if self.scanners.payable.scan_started_at().is_some() {
+            return self.send_payable_scan_report(
+                response_skeleton,
+                Err(PAYABLES_BEING_SCANNED.to_string()),
+            );
+        }
+        self.payable_scan_report_opt = Some(PayableScanReport::new(response_skeleton));
+        self.handle_request_of_scan_for_payable(Some(response_skeleton))
     }
 
-    fn test_use_of_the_same_logger(logger_clone: &Logger, test_name: &str) {
-        let experiment_msg = format!("DEBUG: {test_name}: hello world");
-        let log_handler = TestLogHandler::default();
-        log_handler.exists_no_log_containing(&experiment_msg);
-        debug!(logger_clone, "hello world");
-        log_handler.exists_log_containing(&experiment_msg);
+    // Only the scan the report was made for may take it
+    fn take_payable_scan_report(
+        &mut self,
+        response_skeleton_opt: Option<ResponseSkeleton>,
+    ) -> Option<PayableScanReport> {
+        let demanded_opt = self
+            .payable_scan_report_opt
+            .as_ref()
+            .map(|report| report.response_skeleton);
+        if response_skeleton_opt.is_some() && demanded_opt == response_skeleton_opt {
+            self.payable_scan_report_opt.take()
+        } else {
+            None
+        }
     }
 
-    #[test]
-    fn received_qualified_payables_exceeding_our_masq_balance_are_adjusted_before_forwarded_to_blockchain_bridge(
+    fn send_payable_scan_report(
+        &self,
+        response_skeleton: ResponseSkeleton,
+        result: Result<UiTriggerPayableScanResponse, String>,
     ) {
-        // the numbers for balances don't do real math, they need not to match either the condition for
-        // the payment adjustment or the actual values that come from the payable size reducing algorithm;
-        // all that is mocked in this test
-        init_test_logging();
-        let test_name = "received_qualified_payables_exceeding_our_masq_balance_are_adjusted_before_forwarded_to_blockchain_bridge";
-        let adjust_payments_params_arc = Arc::new(Mutex::new(vec![]));
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let report_recipient = blockchain_bridge
-            .system_stop_conditions(match_every_type_id!(OutboundPaymentsInstructions))
-            .start()
-            .recipient();
-        let mut subject = AccountantBuilder::default().build();
-        let unadjusted_account_1 = make_payable_account(111_111);
-        let unadjusted_account_2 = make_payable_account(222_222);
-        let adjusted_account_1 = PayableAccount {
-            balance_wei: gwei_to_wei(55_550_u64),
-            ..unadjusted_account_1.clone()
-        };
-        let adjusted_account_2 = PayableAccount {
-            balance_wei: gwei_to_wei(100_000_u64),
-            ..unadjusted_account_2.clone()
-        };
-        let response_skeleton = ResponseSkeleton {
-            client_id: 12,
-            context_id: 55,
-        };
-        let agent_id_stamp_first_phase = ArbitraryIdStamp::new();
-        let agent =
-            BlockchainAgentMock::default().set_arbitrary_id_stamp(agent_id_stamp_first_phase);
-        let initial_unadjusted_accounts = protect_payables_in_test(vec![
-            unadjusted_account_1.clone(),
-            unadjusted_account_2.clone(),
-        ]);
-        let msg = BlockchainAgentWithContextMessage {
-            protected_qualified_payables: initial_unadjusted_accounts.clone(),
-            agent: Box::new(agent),
-            response_skeleton_opt: Some(response_skeleton),
-        };
-        // In the real world the agents are identical, here they bear different ids
-        // so that we can watch their journey better
-        let agent_id_stamp_second_phase = ArbitraryIdStamp::new();
-        let agent =
-            BlockchainAgentMock::default().set_arbitrary_id_stamp(agent_id_stamp_second_phase);
-        let affordable_accounts = vec![adjusted_account_1.clone(), adjusted_account_2.clone()];
-        let payments_instructions = OutboundPaymentsInstructions {
-            affordable_accounts: affordable_accounts.clone(),
-            agent: Box::new(agent),
-            response_skeleton_opt: Some(response_skeleton),
+        let body = match result {
+            Ok(response) => response.tmb(response_skeleton.context_id),
+            Err(e) => MessageBody {
+                opcode: "triggerPayableScan".to_string(),
+                path: MessagePath::Conversation(response_skeleton.context_id),
+                payload: Err((SCAN_ERROR, e)),
+            },
         };
-        let payment_adjuster = PaymentAdjusterMock::default()
-            .is_adjustment_required_result(Ok(Some(Adjustment::MasqToken)))
-            .adjust_payments_params(&adjust_payments_params_arc)
-            .adjust_payments_result(payments_instructions);
-        let payable_scanner = PayableScannerBuilder::new()
-            .payment_adjuster(payment_adjuster)
-            .build();
-        subject.scanners.payable = Box::new(payable_scanner);
-        subject.outbound_payments_instructions_sub_opt = Some(report_recipient);
-        subject.logger = Logger::new(test_name);
-        let subject_addr = subject.start();
-        let system = System::new("test");
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UIGateway is not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(response_skeleton.client_id),
+                body,
+            })
+            .expect("UIGateway is dead")
+    }
 
-        subject_addr.try_send(msg).unwrap();
+    fn handle_externally_triggered_scan(
+        &mut self,
+        _ctx: &mut Context<Accountant>,
+        scan_type: ScanType,
+        response_skeleton: ResponseSkeleton,
+    ) {
+        match self.scanners.scan_state(scan_type) {
+            ScanState::Idle => self.handle_request_of_scan(scan_type, Some(response_skeleton)),
+            ScanState::Running(_) | ScanState::Queued(_) => {
+                self.queue_externally_triggered_scan(scan_type, response_skeleton)
+            }
+        }
+    }
 
-        let before = SystemTime::now();
-        assert_eq!(system.run(), 0);
-        let after = SystemTime::now();
-        let mut adjust_payments_params = adjust_payments_params_arc.lock().unwrap();
-        let (actual_prepared_adjustment, captured_now, logger_clone) =
-            adjust_payments_params.remove(0);
-        assert_eq!(actual_prepared_adjustment.adjustment, Adjustment::MasqToken);
-        assert_eq!(
-            actual_prepared_adjustment
-                .original_setup_msg
-                .protected_qualified_payables,
-            initial_unadjusted_accounts
-        );
-        assert_eq!(
-            actual_prepared_adjustment
-                .original_setup_msg
-                .agent
-                .arbitrary_id_stamp(),
-            agent_id_stamp_first_phase
-        );
-        assert!(
-            before <= captured_now && captured_now <= after,
-            "captured timestamp should have been between {:?} and {:?} but was {:?}",
-            before,
-            after,
-            captured_now
-        );
-        assert!(adjust_payments_params.is_empty());
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        let payments_instructions =
-            blockchain_bridge_recording.get_record::<OutboundPaymentsInstructions>(0);
-        assert_eq!(
-            payments_instructions.agent.arbitrary_id_stamp(),
-            agent_id_stamp_second_phase
-        );
-        assert_eq!(
-            payments_instructions.affordable_accounts,
-            affordable_accounts
-        );
-        assert_eq!(
-            payments_instructions.response_skeleton_opt,
-            Some(response_skeleton)
-        );
-        assert_eq!(blockchain_bridge_recording.len(), 1);
-        test_use_of_the_same_logger(&logger_clone, test_name)
+    // Rather than running alongside the scan of its type, the requested scan follows it. The UI
+    // is told so right away; it gets no other response
+    fn queue_externally_triggered_scan(
+        &mut self,
+        scan_type: ScanType,
+        response_skeleton: ResponseSkeleton,
+    ) {
+        if self.scanners.queue.enqueue(scan_type) {
+            info!(
+                self.logger,
+                "{:?} scan is running; the requested one is queued to follow it", scan_type
+            )
+        } else {
+            info!(
+                self.logger,
+                "{:?} scan is running with another queued; the requested one is merged into it",
+                scan_type
+            )
+        }
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UIGateway is not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(response_skeleton.client_id),
+                body: UiScanResponse { queued: true }.tmb(response_skeleton.context_id),
+            })
+            .expect("UIGateway is dead")
     }
 
-    #[test]
-    fn scan_pending_payables_request() {
-        let mut config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
-        config.suppress_initial_scans = true;
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(10_000),
-            receivable_scan_interval: Duration::from_millis(10_000),
-            pending_payable_scan_interval: Duration::from_secs(100),
-        });
-        let fingerprint = PendingPayableFingerprint {
-            rowid: 1234,
-            timestamp: SystemTime::now(),
-            hash: Default::default(),
-            attempt: 1,
-            amount: 1_000_000,
-            process_error: None,
-        };
-        let pending_payable_dao = PendingPayableDaoMock::default()
-            .return_all_errorless_fingerprints_result(vec![fingerprint.clone()]);
-        let subject = AccountantBuilder::default()
-            .consuming_wallet(make_paying_wallet(b"consuming"))
-            .bootstrapper_config(config)
-            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
-            .build();
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
-        let ui_message = NodeFromUiMessage {
-            client_id: 1234,
-            body: UiScanRequest {
-                scan_type: ScanType::PendingPayables,
+    fn begin_queued_scans(&mut self) {
+        self.scanners
+            .take_queued_scans_ready_to_begin()
+            .into_iter()
+            .for_each(|scan_type| {
+                debug!(
+                    self.logger,
+                    "Beginning the {:?} scan queued behind the one just ended", scan_type
+                );
+                self.handle_request_of_scan(scan_type, None)
+            })
+    }
+
+    fn handle_request_of_scan(
+        &mut self,
+        scan_type: ScanType,
+        response_skeleton_opt: Option<ResponseSkeleton>,
+    ) {
+        match scan_type {
+            ScanType::Payables => self.handle_request_of_scan_for_payable(response_skeleton_opt),
+            ScanType::PendingPayables => {
+                self.handle_request_of_scan_for_pending_payable(response_skeleton_opt)
             }
-            .tmb(4321),
-        };
+            ScanType::Receivables => {
+                self.handle_request_of_scan_for_receivable(response_skeleton_opt)
+            }
+        }
+    }
 
-        subject_addr.try_send(ui_message).unwrap();
+    // The pending payable scanner leaves here the abandoned transactions whose nonces are known
+    fn void_abandoned_transactions(&self) {
+        let abandoned = self
+            .abandoned_transactions
+            .borrow_mut()
+            .drain(..)
+            .collect_vec();
+        if abandoned.is_empty() {
+            return;
+        }
+        let void_sub = self
+            .void_abandoned_transaction_sub_opt
+            .as_ref()
+            .expect("BlockchainBridge is unbound");
+        abandoned.into_iter().for_each(|fingerprint| {
+            void_sub
+                .try_send(VoidAbandonedTransaction {
+                    hash: fingerprint.hash,
+                    basis: fingerprint
+                        .replacement_basis_opt
+                        .expect("abandoned transaction without its nonce"),
+                    gas_price_bump_percent: VOID_GAS_PRICE_BUMP_PERCENT,
+                })
+                .expect("BlockchainBridge is dead")
+        })
+    }
 
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        assert_eq!(
-            blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(0),
-            &RequestTransactionReceipts {
-                pending_payable: vec![fingerprint],
-                response_skeleton_opt: Some(ResponseSkeleton {
-                    client_id: 1234,
-                    context_id: 4321,
-                }),
-            }
-        );
+    // Each replacement pays the same creditor as the transaction it outbids
+    fn bump_stuck_transactions(&self, stuck_fingerprints: Vec<PendingPayableFingerprint>) {
+        let gas_price_bump_percent = match self.stuck_batch_bumper.step_percent() {
+            Some(step_percent) if !stuck_fingerprints.is_empty() => step_percent,
+            _ => return,
+        };
+        let rowids = stuck_fingerprints
+            .iter()
+            .map(|fingerprint| fingerprint.rowid)
+            .collect_vec();
+        let creditors = self.payable_dao.creditors_of_pending_payables(&rowids);
+        let replace_sub = self
+            .replace_pending_payable_sub_opt
+            .as_ref()
+            .expect("BlockchainBridge is unbound");
+        stuck_fingerprints.into_iter().for_each(|fingerprint| {
+            let wallet = match creditors.get(&fingerprint.rowid) {
+                Some(wallet) => wallet.clone(),
+                None => {
+                    warning!(
+                        self.logger,
+                        "Transaction {:?} is stuck, but the creditor it pays is unknown; it will \
+                         not be outbid",
+                        fingerprint.hash
+                    );
+                    return;
+                }
+            };
+            debug!(
+                self.logger,
+                "Outbidding stuck transaction {:?} by {}%",
+                fingerprint.hash,
+                gas_price_bump_percent
+            );
+            let account = PayableAccount {
+                wallet,
+                balance_wei: fingerprint.amount,
+                last_paid_timestamp: fingerprint.timestamp,
+                pending_payable_opt: None,
+            };
+            replace_sub
+                .try_send(ReplacePendingPayable {
+                    fingerprint,
+                    accounts: vec![account],
+                    gas_price_bump_percent,
+                    response_skeleton_opt: None,
+                })
+                .expect("BlockchainBridge is dead")
+        })
     }
 
-    #[test]
-    fn scan_request_from_ui_is_handled_in_case_the_scan_is_already_running() {
-        init_test_logging();
-        let test_name = "scan_request_from_ui_is_handled_in_case_the_scan_is_already_running";
-        let mut config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
-        config.suppress_initial_scans = true;
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(10_000),
-            receivable_scan_interval: Duration::from_millis(10_000),
-            pending_payable_scan_interval: Duration::from_secs(100),
-        });
-        let fingerprint = PendingPayableFingerprint {
-            rowid: 1234,
-            timestamp: SystemTime::now(),
-            hash: Default::default(),
-            attempt: 1,
-            amount: 1_000_000,
-            process_error: None,
+    fn handle_new_pending_payable_fingerprints(&self, msg: PendingPayableFingerprintSeeds) {
+        fn serialize_hashes(fingerprints_data: &[HashAndAmount]) -> String {
+            comma_joined_stringifiable(fingerprints_data, |hash_and_amount| {
+                format!("{:?}", hash_and_amount.hash)
+            })
+        }
+        let first_transaction_basis = ReplacementBasis {
+            nonce: msg.first_nonce,
+            gas_price_wei: msg.batch_wide_gas_price_wei,
         };
-        let pending_payable_dao = PendingPayableDaoMock::default()
-            .return_all_errorless_fingerprints_result(vec![fingerprint]);
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .consuming_wallet(make_paying_wallet(b"consuming"))
-            .logger(Logger::new(test_name))
-            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
-            .build();
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let first_message = NodeFromUiMessage {
-            client_id: 1234,
-            body: UiScanRequest {
-                scan_type: ScanType::PendingPayables,
+        match self.pending_payable_dao.insert_new_fingerprints(
+            &msg.hashes_and_balances,
+            msg.batch_wide_timestamp,
+            first_transaction_basis,
+        ) {
+            Ok(_) => debug!(
+                self.logger,
+                "Saved new pending payable fingerprints for: {}",
+                serialize_hashes(&msg.hashes_and_balances)
+            ),
+            Err(e) => {
+                error!(
+                    self.logger,
+                    "Failed to process new pending payable fingerprints due to '{:?}', \
+                     disabling the automated confirmation for all these transactions: {}",
+                    e,
+                    serialize_hashes(&msg.hashes_and_balances)
+                );
+                return;
             }
-            .tmb(4321),
-        };
-        let second_message = first_message.clone();
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
-        subject_addr.try_send(first_message).unwrap();
-
-        subject_addr.try_send(second_message).unwrap();
+        }
+        if msg.creditor_shares.is_empty() {
+            return;
+        }
+        if let Err(e) = self
+            .pending_payable_dao
+            .insert_creditor_shares(&msg.creditor_shares)
+        {
+            error!(
+                self.logger,
+                "Failed to record the shares of creditors paid by a single transaction due to \
+                 '{:?}'; once confirmed, each of them would be debited the whole transaction: {}",
+                e,
+                comma_joined_stringifiable(&msg.creditor_shares, |share| format!(
+                    "{:?} to {}",
+                    share.hash, share.wallet
+                ))
+            )
+        }
+    }
 
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        TestLogHandler::new().exists_log_containing(&format!(
-            "INFO: {}: PendingPayables scan was already initiated",
-            test_name
-        ));
-        assert_eq!(blockchain_bridge_recording.len(), 1);
+    fn handle_pending_payable_replacement(&self, msg: PendingPayableReplacement) {
+        match self
+            .pending_payable_dao
+            .record_replacement(msg.rowid, msg.hash, msg.basis)
+        {
+            Ok(_) => debug!(
+                self.logger,
+                "Pending payable fingerprint {} now tracks transaction {:?} with nonce {} and \
+                 gas price {} wei",
+                msg.rowid,
+                msg.hash,
+                msg.basis.nonce,
+                msg.basis.gas_price_wei
+            ),
+            Err(e) => error!(
+                self.logger,
+                "Failed to record transaction {:?} at pending payable fingerprint {} due to \
+                 '{:?}'; its confirmation will not be recognized",
+                msg.hash,
+                msg.rowid,
+                e
+            ),
+        }
     }
 
-    #[test]
-    fn report_transaction_receipts_with_response_skeleton_sends_scan_response_to_ui_gateway() {
-        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(10_000),
-            receivable_scan_interval: Duration::from_millis(10_000),
-            pending_payable_scan_interval: Duration::from_secs(100),
-        });
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .build();
-        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
-        let subject_addr = subject.start();
-        let system = System::new("test");
-        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
-        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
-        let report_transaction_receipts = ReportTransactionReceipts {
-            fingerprints_with_receipts: vec![],
-            response_skeleton_opt: Some(ResponseSkeleton {
-                client_id: 1234,
-                context_id: 4321,
-            }),
-        };
+    fn financial_statistics(&self) -> Ref<'_, FinancialStatistics> {
+        self.financial_statistics.borrow()
+    }
+}
 
-        subject_addr.try_send(report_transaction_receipts).unwrap();
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PendingPayableId {
+    pub rowid: u64,
+    pub hash: H256,
+}
 
-        System::current().stop();
-        system.run();
-        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
-        assert_eq!(
-            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
-            &NodeToUiMessage {
-                target: ClientId(1234),
-                body: UiScanResponse {}.tmb(4321),
-            }
-        );
+impl PendingPayableId {
+    pub fn new(rowid: u64, hash: H256) -> Self {
+        Self { rowid, hash }
     }
 
-    #[test]
-    fn accountant_calls_payable_dao_to_mark_pending_payable() {
-        let fingerprints_rowids_params_arc = Arc::new(Mutex::new(vec![]));
-        let mark_pending_payables_rowids_params_arc = Arc::new(Mutex::new(vec![]));
-        let expected_wallet = make_wallet("paying_you");
-        let expected_hash = H256::from("transaction_hash".keccak256());
-        let expected_rowid = 45623;
-        let pending_payable_dao = PendingPayableDaoMock::default()
-            .fingerprints_rowids_params(&fingerprints_rowids_params_arc)
-            .fingerprints_rowids_result(TransactionHashes {
-                rowid_results: vec![(expected_rowid, expected_hash)],
-                no_rowid_results: vec![],
-            });
-        let payable_dao = PayableDaoMock::new()
-            .mark_pending_payables_rowids_params(&mark_pending_payables_rowids_params_arc)
-            .mark_pending_payables_rowids_result(Ok(()));
-        let system = System::new("accountant_calls_payable_dao_to_mark_pending_payable");
-        let accountant = AccountantBuilder::default()
-            .bootstrapper_config(bc_from_earning_wallet(make_wallet("some_wallet_address")))
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .pending_payable_daos(vec![ForPayableScanner(pending_payable_dao)])
-            .build();
-        let expected_payable = PendingPayable::new(expected_wallet.clone(), expected_hash.clone());
-        let sent_payable = SentPayables {
-            payment_procedure_result: Ok(vec![ProcessedPayableFallible::Correct(
-                expected_payable.clone(),
-            )]),
-            response_skeleton_opt: None,
-        };
-        let subject = accountant.start();
+    fn rowids(ids: &[Self]) -> Vec<u64> {
+        ids.iter().map(|id| id.rowid).collect()
+    }
 
-        subject
-            .try_send(sent_payable)
-            .expect("unexpected actix error");
+    fn serialize_hashes_to_string(ids: &[Self]) -> String {
+        comma_joined_stringifiable(ids, |id| format!("{:?}", id.hash))
+    }
+}
 
-        System::current().stop();
-        system.run();
-        let fingerprints_rowids_params = fingerprints_rowids_params_arc.lock().unwrap();
-        assert_eq!(*fingerprints_rowids_params, vec![vec![expected_hash]]);
-        let mark_pending_payables_rowids_params =
-            mark_pending_payables_rowids_params_arc.lock().unwrap();
-        assert_eq!(
-            *mark_pending_payables_rowids_params,
-            vec![vec![(expected_wallet, expected_rowid)]]
-        );
+impl From<PendingPayableFingerprint> for PendingPayableId {
+    fn from(pending_payable_fingerprint: PendingPayableFingerprint) -> Self {
+        Self {
+            hash: pending_payable_fingerprint.hash,
+            rowid: pending_payable_fingerprint.rowid,
+        }
     }
+}
 
-    #[test]
-    fn accountant_sends_initial_payable_payments_msg_when_qualified_payable_found() {
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let now = SystemTime::now();
-        let payment_thresholds = PaymentThresholds::default();
-        let (qualified_payables, _, all_non_pending_payables) =
-            make_payables(now, &payment_thresholds);
-        let payable_dao =
-            PayableDaoMock::new().non_pending_payables_result(all_non_pending_payables);
-        let system = System::new(
-            "accountant_sends_initial_payable_payments_msg_when_qualified_payable_found",
-        );
-        let consuming_wallet = make_paying_wallet(b"consuming");
-        let mut subject = AccountantBuilder::default()
-            .bootstrapper_config(bc_from_earning_wallet(make_wallet("some_wallet_address")))
-            .consuming_wallet(consuming_wallet.clone())
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .build();
-        subject.scanners.pending_payable = Box::new(NullScanner::new());
-        subject.scanners.receivable = Box::new(NullScanner::new());
-        let accountant_addr = subject.start();
-        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        send_bind_message!(accountant_subs, peer_actors);
+pub fn comma_joined_stringifiable<T, F>(collection: &[T], stringify: F) -> String
+where
+    F: FnMut(&T) -> String,
+{
+    collection.iter().map(stringify).join(", ")
+}
 
-        send_start_message!(accountant_subs);
+pub fn sign_conversion<T: Copy, S: TryFrom<T>>(num: T) -> Result<S, T> {
+    S::try_from(num).map_err(|_| num)
+}
 
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recorder = blockchain_bridge_recording_arc.lock().unwrap();
-        assert_eq!(blockchain_bridge_recorder.len(), 1);
-        let message = blockchain_bridge_recorder.get_record::<QualifiedPayablesMessage>(0);
-        assert_eq!(
-            message,
-            &QualifiedPayablesMessage {
-                protected_qualified_payables: protect_payables_in_test(qualified_payables),
-                consuming_wallet,
-                response_skeleton_opt: None,
-            }
-        );
-    }
+pub fn politely_checked_conversion<T: Copy + Display, S: TryFrom<T>>(num: T) -> Result<S, String> {
+    sign_conversion(num).map_err(|num| {
+        format!(
+            "Overflow detected with {}: cannot be converted from {} to {}",
+            num,
+            type_name::<T>(),
+            type_name::<S>()
+        )
+    })
+}
 
-    #[test]
-    fn accountant_requests_blockchain_bridge_to_scan_for_received_payments() {
-        init_test_logging();
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let earning_wallet = make_wallet("someearningwallet");
-        let system =
-            System::new("accountant_requests_blockchain_bridge_to_scan_for_received_payments");
-        let receivable_dao = ReceivableDaoMock::new()
-            .new_delinquencies_result(vec![])
-            .paid_delinquencies_result(vec![]);
-        let mut subject = AccountantBuilder::default()
-            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
-            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
-            .build();
-        subject.scanners.pending_payable = Box::new(NullScanner::new());
-        subject.scanners.payable = Box::new(NullScanner::new());
-        let accountant_addr = subject.start();
-        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        send_bind_message!(accountant_subs, peer_actors);
+#[track_caller]
+pub fn checked_conversion<T: Copy + Display, S: TryFrom<T>>(num: T) -> S {
+    politely_checked_conversion(num).unwrap_or_else(|msg| panic!("{}", msg))
+}
 
-        send_start_message!(accountant_subs);
+pub fn gwei_to_wei<T: Mul<Output = T> + From<u32> + From<S>, S>(gwei: S) -> T {
+    (T::from(gwei)).mul(T::from(WEIS_IN_GWEI as u32))
+}
 
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recorder = blockchain_bridge_recording_arc.lock().unwrap();
-        assert_eq!(blockchain_bridge_recorder.len(), 1);
-        let retrieve_transactions_msg =
-            blockchain_bridge_recorder.get_record::<RetrieveTransactions>(0);
-        assert_eq!(
-            retrieve_transactions_msg,
-            &RetrieveTransactions {
-                recipient: earning_wallet.clone(),
-                response_skeleton_opt: None,
-            }
-        );
+pub fn wei_to_gwei<T: TryFrom<S>, S: Display + Copy + Div<Output = S> + From<u32>>(wei: S) -> T {
+    checked_conversion::<S, T>(wei.div(S::from(WEIS_IN_GWEI as u32)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::consumption_forecast::DebtGrowthProjection;
+    use crate::accountant::db_access_objects::consistency_dao::Anomaly;
+    use crate::accountant::db_access_objects::outbound_payment_dao::OutboundPaymentDaoError;
+    use crate::accountant::db_access_objects::payable_dao::{
+        InvoiceDispute, PayableAccount, PayableDaoError, PayableDaoFactory, PayableWriteOff,
+    };
+    use crate::accountant::db_access_objects::pending_payable_dao::{
+        PendingPayable, PendingPayableDaoError, TransactionHashes,
+    };
+    use crate::accountant::db_access_objects::receivable_dao::ReceivableAccount;
+    use crate::accountant::db_access_objects::utils::{
+        from_time_t, to_time_t, AgingBucket, CustomQuery, DebtAging,
+    };
+    use crate::accountant::payment_adjuster::Adjustment;
+    use crate::accountant::payment_decisions::PaymentDecision;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{
+        BlockchainAgentSnapshot, ObservedPayables,
+    };
+    use crate::accountant::scanners::test_utils::protect_payables_in_test;
+    use crate::accountant::scanners::{BeginScanError, PayableScanner};
+    use crate::accountant::test_utils::DaoWithDestination::{
+        ForAccountantBody, ForPayableScanner, ForPendingPayableScanner, ForReceivableScanner,
+    };
+    use crate::accountant::test_utils::{
+        bc_from_earning_wallet, bc_from_wallets, make_custom_payment_thresholds,
+        make_payable_account, make_payable_account_with_wallet_and_balance_and_timestamp_opt,
+        make_payables, make_pending_payable_fingerprint, make_receivable_account, AccountsTransferDaoFactoryMock, AccountsTransferDaoMock, BannedDaoFactoryMock, ConfigDaoFactoryMock, ConsistencyDaoFactoryMock, ConsistencyDaoMock, MessageIdGeneratorMock, NullScanner,
+        OutboundPaymentDaoFactoryMock, OutboundPaymentDaoMock,
+        PayableDaoFactoryMock, PayableDaoMock, PayableScannerBuilder, PaymentAdjusterMock,
+        PendingPayableDaoFactoryMock, PendingPayableDaoMock, ReceivableDaoFactoryMock,
+        PriceFeedMock, ReceivableDaoMock, ScannerMock,
+    };
+    use crate::accountant::price_feed::FiatQuote;
+    use crate::accountant::test_utils::{AccountantBuilder, BannedDaoMock};
+    use crate::accountant::Accountant;
+    use crate::blockchain::blockchain_bridge::{BlockchainBridge, CreditorShare};
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
+    use crate::blockchain::test_utils::{
+        make_blockchain_interface_web3, make_tx_hash, ReceiptResponseBuilder,
+    };
+    use crate::database::rusqlite_wrappers::TransactionSafeWrapper;
+    use crate::database::test_utils::transaction_wrapper_mock::TransactionInnerWrapperMockBuilder;
+    use crate::db_config::config_dao::ConfigDaoRecord;
+    use crate::db_config::mocks::ConfigDaoMock;
+    use crate::match_every_type_id;
+    use crate::sub_lib::accountant::{
+        ExitServiceConsumed, GasBumpLadder, GasPriceCeiling, IdleSpendingFreeze, PaymentThresholds,
+        PaymentWindow, RoutingServiceConsumed, ScanIntervals, SpendRateLimits, UsageInvoice_0v1,
+        DEFAULT_EARNING_WALLET, DEFAULT_PAYMENT_THRESHOLDS,
+    };
+    use crate::sub_lib::blockchain_bridge::{
+        ConsumingWalletBalances, OutboundPaymentsInstructions,
+    };
+    use crate::sub_lib::neighborhood::ConfigChange;
+    use crate::sub_lib::neighborhood::{Hops, WalletPair};
+    use crate::sub_lib::wei::Wei;
+    use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
+    use crate::test_utils::recorder::make_recorder;
+    use crate::test_utils::recorder::peer_actors_builder;
+    use crate::test_utils::recorder::Recorder;
+    use crate::test_utils::recorder_stop_conditions::{StopCondition, StopConditions};
+    use crate::test_utils::unshared_test_utils::arbitrary_id_stamp::ArbitraryIdStamp;
+    use crate::test_utils::unshared_test_utils::notify_handlers::NotifyLaterHandleMock;
+    use crate::test_utils::unshared_test_utils::system_killer_actor::SystemKillerActor;
+    use crate::test_utils::unshared_test_utils::{
+        assert_on_initialization_with_panic_on_migration, make_bc_with_defaults,
+        prove_that_crash_request_handler_is_hooked_up, AssertionsMessage,
+    };
+    use crate::test_utils::{make_paying_wallet, make_wallet};
+    use actix::{Arbiter, System};
+    use ethereum_types::U64;
+    use ethsign_crypto::Keccak256;
+    use log::Level;
+    use masq_lib::blockchains::chains::Chain;
+    use masq_lib::constants::{
+        REQUEST_WITH_MUTUALLY_EXCLUSIVE_PARAMS, REQUEST_WITH_NO_VALUES, SCAN_ERROR,
+        VALUE_EXCEEDS_ALLOWED_LIMIT,
+    };
+    use masq_lib::messages::TopRecordsOrdering::{Age, Balance};
+    use masq_lib::messages::{
+        CustomQueries, RangeQuery, ScanType, TopRecordsConfig, UiAgingBucket,
+        UiAgingBuckets, UiDebtAging, UiFinancialStatistics, UiMessageError, UiPayableAccount,
+        UiPayableDetail,
+        UiPayableWriteOff, UiReceivableAccount, UiReceivableDetail, UiReceivedPayment,
+        UiPaidPayable, UiPaymentDecision, UiPaymentDecisionChain, UiScanRequest, UiScanResponse,
+        UiSkippedPayable, UiUnconfirmedPayment,
+    };
+    use masq_lib::test_utils::logging::init_test_logging;
+    use masq_lib::test_utils::logging::TestLogHandler;
+    use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+    use masq_lib::ui_gateway::MessagePath::Conversation;
+    use masq_lib::ui_gateway::{MessageBody, MessagePath, NodeFromUiMessage, NodeToUiMessage};
+    use masq_lib::utils::find_free_port;
+    use std::any::TypeId;
+    use std::fs;
+    use std::ops::{Add, Sub};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use std::vec;
+    use time::OffsetDateTime;
+    use web3::types::U256;
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionBlock, TxReceipt, TxStatus};
+
+    impl Handler<AssertionsMessage<Accountant>> for Accountant {
+        type Result = ();
+
+        fn handle(
+            &mut self,
+            msg: AssertionsMessage<Accountant>,
+            _ctx: &mut Self::Context,
+        ) -> Self::Result {
+            (msg.assertions)(self)
+        }
     }
 
     #[test]
-    fn accountant_processes_msg_with_received_payments_using_receivables_dao_and_then_updates_start_block(
-    ) {
-        let more_money_received_params_arc = Arc::new(Mutex::new(vec![]));
-        let commit_params_arc = Arc::new(Mutex::new(vec![]));
-        let get_params_arc = Arc::new(Mutex::new(vec![]));
-        let set_by_guest_transaction_params_arc = Arc::new(Mutex::new(vec![]));
-        let now = SystemTime::now();
-        let earning_wallet = make_wallet("earner3000");
-        let expected_receivable_1 = BlockchainTransaction {
-            block_number: 7,
-            from: make_wallet("wallet0"),
-            wei_amount: 456,
-        };
-        let expected_receivable_2 = BlockchainTransaction {
-            block_number: 13,
-            from: make_wallet("wallet1"),
-            wei_amount: 10000,
-        };
-        let transaction_id = ArbitraryIdStamp::new();
-        let txn_inner_builder = TransactionInnerWrapperMockBuilder::default()
-            .commit_params(&commit_params_arc)
-            .commit_result(Ok(()))
-            .set_arbitrary_id_stamp(transaction_id);
-        let wrapped_transaction = TransactionSafeWrapper::new_with_builder(txn_inner_builder);
-        let receivable_dao = ReceivableDaoMock::new()
-            .more_money_received_params(&more_money_received_params_arc)
-            .more_money_received_result(wrapped_transaction);
-        let config_dao = ConfigDaoMock::new()
-            .get_params(&get_params_arc)
-            .get_result(Ok(ConfigDaoRecord::new("start_block", None, false)))
-            .set_by_guest_transaction_params(&set_by_guest_transaction_params_arc)
-            .set_by_guest_transaction_result(Ok(()));
-        let accountant = AccountantBuilder::default()
-            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
-            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
-            .config_dao(config_dao)
-            .build();
-        let system = System::new("accountant_uses_receivables_dao_to_process_received_payments");
-        let subject = accountant.start();
-        subject
-            .try_send(ReceivedPayments {
-                timestamp: now,
-                new_start_block: BlockMarker::Value(123456789u64),
-                response_skeleton_opt: None,
-                transactions: vec![expected_receivable_1.clone(), expected_receivable_2.clone()],
-            })
-            .expect("unexpected actix error");
-
-        System::current().stop();
-        system.run();
-        let more_money_received_params = more_money_received_params_arc.lock().unwrap();
+    fn constants_have_correct_values() {
+        assert_eq!(CRASH_KEY, "ACCOUNTANT");
+        assert_eq!(DEFAULT_PENDING_TOO_LONG_SEC, 21_600);
+        assert_eq!(VOID_GAS_PRICE_BUMP_PERCENT, 15);
         assert_eq!(
-            *more_money_received_params,
-            vec![(now, vec![expected_receivable_1, expected_receivable_2])]
+            PAYABLES_BEING_SCANNED,
+            "Payables are being scanned right now; try again after the scan"
         );
-        let commit_params = commit_params_arc.lock().unwrap();
-        assert_eq!(*commit_params, vec![()]);
-        let set_by_guest_transaction_params = set_by_guest_transaction_params_arc.lock().unwrap();
-        assert_eq!(
-            *set_by_guest_transaction_params,
-            vec![(
-                transaction_id,
-                "start_block".to_string(),
-                Some("123456789".to_string())
-            )]
-        )
     }
 
     #[test]
-    fn accountant_scans_after_startup() {
-        init_test_logging();
-        let pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
-        let payable_params_arc = Arc::new(Mutex::new(vec![]));
-        let new_delinquencies_params_arc = Arc::new(Mutex::new(vec![]));
-        let paid_delinquencies_params_arc = Arc::new(Mutex::new(vec![]));
-        let (blockchain_bridge, _, _) = make_recorder();
-        let earning_wallet = make_wallet("earning");
-        let system = System::new("accountant_scans_after_startup");
-        let config = bc_from_wallets(make_wallet("buy"), earning_wallet.clone());
-        let payable_dao = PayableDaoMock::new()
-            .non_pending_payables_params(&payable_params_arc)
-            .non_pending_payables_result(vec![]);
-        let pending_payable_dao = PendingPayableDaoMock::default()
-            .return_all_errorless_fingerprints_params(&pending_payable_params_arc)
-            .return_all_errorless_fingerprints_result(vec![]);
-        let receivable_dao = ReceivableDaoMock::new()
-            .new_delinquencies_parameters(&new_delinquencies_params_arc)
-            .new_delinquencies_result(vec![])
-            .paid_delinquencies_parameters(&paid_delinquencies_params_arc)
-            .paid_delinquencies_result(vec![]);
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
-            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
-            .build();
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        let subject_addr: Addr<Accountant> = subject.start();
-        let subject_subs = Accountant::make_subs_from(&subject_addr);
-        send_bind_message!(subject_subs, peer_actors);
+    fn new_calls_factories_properly() {
+        let config = make_bc_with_defaults();
+        let payable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let pending_payable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let config_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let accounts_transfer_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let consistency_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let outbound_payment_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_factory = PayableDaoFactoryMock::new()
+            .make_params(&payable_dao_factory_params_arc)
+            .make_result(PayableDaoMock::new()) // For Accountant
+            .make_result(PayableDaoMock::new()) // For Payable Scanner
+            .make_result(PayableDaoMock::new()); // For PendingPayable Scanner
+        let pending_payable_dao_factory = PendingPayableDaoFactoryMock::new()
+            .make_params(&pending_payable_dao_factory_params_arc)
+            .make_result(PendingPayableDaoMock::new()) // For Accountant
+            .make_result(PendingPayableDaoMock::new()) // For Payable Scanner
+            .make_result(PendingPayableDaoMock::new()); // For PendingPayable Scanner
+        let receivable_dao_factory = ReceivableDaoFactoryMock::new()
+            .make_params(&receivable_dao_factory_params_arc)
+            .make_result(ReceivableDaoMock::new()) // For Accountant
+            .make_result(ReceivableDaoMock::new()); // For Receivable Scanner
+        let banned_dao_factory = BannedDaoFactoryMock::new()
+            .make_params(&banned_dao_factory_params_arc)
+            .make_result(BannedDaoMock::new()); // For Receivable Scanner
+        let config_dao_factory = ConfigDaoFactoryMock::new()
+            .make_params(&config_dao_factory_params_arc)
+            .make_result(ConfigDaoMock::new()); // For receivable scanner
+        let accounts_transfer_dao_factory = AccountsTransferDaoFactoryMock::new()
+            .make_params(&accounts_transfer_dao_factory_params_arc)
+            .make_result(AccountsTransferDaoMock::new()); // For Accountant
+        let consistency_dao_factory = ConsistencyDaoFactoryMock::new()
+            .make_params(&consistency_dao_factory_params_arc)
+            .make_result(ConsistencyDaoMock::new()); // For Accountant
+        let outbound_payment_dao_factory = OutboundPaymentDaoFactoryMock::new()
+            .make_params(&outbound_payment_dao_factory_params_arc)
+            .make_result(OutboundPaymentDaoMock::new()); // For Accountant
 
-        send_start_message!(subject_subs);
+        let _ = Accountant::new(
+            config,
+            DaoFactories {
+                payable_dao_factory: Box::new(payable_dao_factory),
+                pending_payable_dao_factory: Box::new(pending_payable_dao_factory),
+                receivable_dao_factory: Box::new(receivable_dao_factory),
+                banned_dao_factory: Box::new(banned_dao_factory),
+                config_dao_factory: Box::new(config_dao_factory),
+                accounts_transfer_dao_factory: Box::new(accounts_transfer_dao_factory),
+                consistency_dao_factory: Box::new(consistency_dao_factory),
+                outbound_payment_dao_factory: Box::new(outbound_payment_dao_factory),
+            },
+        );
 
-        System::current().stop();
-        system.run();
-        let payable_params = payable_params_arc.lock().unwrap();
-        let pending_payable_params = pending_payable_params_arc.lock().unwrap();
-        //proof of calling pieces of scan_for_delinquencies()
-        let mut new_delinquencies_params = new_delinquencies_params_arc.lock().unwrap();
-        let (captured_timestamp, captured_curves) = new_delinquencies_params.remove(0);
-        let paid_delinquencies_params = paid_delinquencies_params_arc.lock().unwrap();
-        assert_eq!(*payable_params, vec![()]);
-        assert_eq!(*pending_payable_params, vec![()]);
-        assert!(new_delinquencies_params.is_empty());
-        assert!(
-            captured_timestamp < SystemTime::now()
-                && captured_timestamp >= from_time_t(to_time_t(SystemTime::now()) - 5)
+        assert_eq!(
+            *payable_dao_factory_params_arc.lock().unwrap(),
+            vec![(), (), ()]
+        );
+        assert_eq!(
+            *pending_payable_dao_factory_params_arc.lock().unwrap(),
+            vec![(), (), ()]
+        );
+        assert_eq!(
+            *receivable_dao_factory_params_arc.lock().unwrap(),
+            vec![(), ()]
+        );
+        assert_eq!(*banned_dao_factory_params_arc.lock().unwrap(), vec![()]);
+        assert_eq!(*config_dao_factory_params_arc.lock().unwrap(), vec![()]);
+        assert_eq!(
+            *accounts_transfer_dao_factory_params_arc.lock().unwrap(),
+            vec![()]
+        );
+        assert_eq!(
+            *consistency_dao_factory_params_arc.lock().unwrap(),
+            vec![()]
+        );
+        assert_eq!(
+            *outbound_payment_dao_factory_params_arc.lock().unwrap(),
+            vec![()]
         );
-        assert_eq!(captured_curves, PaymentThresholds::default());
-        assert_eq!(paid_delinquencies_params.len(), 1);
-        assert_eq!(paid_delinquencies_params[0], PaymentThresholds::default());
-        let tlh = TestLogHandler::new();
-        tlh.exists_log_containing("INFO: Accountant: Scanning for payables");
-        tlh.exists_log_containing("INFO: Accountant: Scanning for pending payable");
-        tlh.exists_log_containing(&format!(
-            "INFO: Accountant: Scanning for receivables to {}",
-            earning_wallet
-        ));
-        tlh.exists_log_containing("INFO: Accountant: Scanning for delinquencies");
     }
 
     #[test]
-    fn periodical_scanning_for_receivables_and_delinquencies_works() {
+    fn accountant_have_proper_defaulted_values() {
+        let bootstrapper_config = make_bc_with_defaults();
+        let payable_dao_factory = Box::new(
+            PayableDaoFactoryMock::new()
+                .make_result(PayableDaoMock::new()) // For Accountant
+                .make_result(PayableDaoMock::new()) // For Payable Scanner
+                .make_result(PayableDaoMock::new()), // For PendingPayable Scanner
+        );
+        let pending_payable_dao_factory = Box::new(
+            PendingPayableDaoFactoryMock::new()
+                .make_result(PendingPayableDaoMock::new()) // For Accountant
+                .make_result(PendingPayableDaoMock::new()) // For Payable Scanner
+                .make_result(PendingPayableDaoMock::new()), // For PendingPayable Scanner
+        );
+        let receivable_dao_factory = Box::new(
+            ReceivableDaoFactoryMock::new()
+                .make_result(ReceivableDaoMock::new()) // For Accountant
+                .make_result(ReceivableDaoMock::new()), // For Scanner
+        );
+        let banned_dao_factory =
+            Box::new(BannedDaoFactoryMock::new().make_result(BannedDaoMock::new()));
+        let config_dao_factory =
+            Box::new(ConfigDaoFactoryMock::new().make_result(ConfigDaoMock::new()));
+        let accounts_transfer_dao_factory = Box::new(
+            AccountsTransferDaoFactoryMock::new().make_result(AccountsTransferDaoMock::new()),
+        );
+        let consistency_dao_factory =
+            Box::new(ConsistencyDaoFactoryMock::new().make_result(ConsistencyDaoMock::new()));
+        let outbound_payment_dao_factory = Box::new(
+            OutboundPaymentDaoFactoryMock::new().make_result(OutboundPaymentDaoMock::new()),
+        );
+
+        let result = Accountant::new(
+            bootstrapper_config,
+            DaoFactories {
+                payable_dao_factory,
+                pending_payable_dao_factory,
+                receivable_dao_factory,
+                banned_dao_factory,
+                config_dao_factory,
+                accounts_transfer_dao_factory,
+                consistency_dao_factory,
+                outbound_payment_dao_factory,
+            },
+        );
+
+        let financial_statistics = result.financial_statistics().clone();
+        let assert_scan_scheduler = |scan_type: ScanType, expected_scan_interval: Duration| {
+            assert_eq!(
+                result
+                    .scan_schedulers
+                    .schedulers
+                    .get(&scan_type)
+                    .unwrap()
+                    .interval(),
+                expected_scan_interval
+            )
+        };
+        let default_scan_intervals = ScanIntervals::default();
+        assert_scan_scheduler(
+            ScanType::Payables,
+            default_scan_intervals.payable_scan_interval,
+        );
+        assert_scan_scheduler(
+            ScanType::PendingPayables,
+            default_scan_intervals.pending_payable_scan_interval,
+        );
+        assert_scan_scheduler(
+            ScanType::Receivables,
+            default_scan_intervals.receivable_scan_interval,
+        );
+        assert_eq!(result.consuming_wallet_opt, None);
+        assert_eq!(result.earning_wallet, *DEFAULT_EARNING_WALLET);
+        assert_eq!(result.suppress_initial_scans, false);
+        assert_eq!(result.scan_pipelining, false);
+        result
+            .message_id_generator
+            .as_any()
+            .downcast_ref::<MessageIdGeneratorReal>()
+            .unwrap();
+        assert_eq!(result.crashable, false);
+        assert_eq!(financial_statistics.total_paid_receivable_wei, 0);
+        assert_eq!(financial_statistics.total_paid_payable_wei, 0);
+    }
+
+    #[test]
+    fn accountant_handles_config_change_msg() {
+        assert_handling_of_config_change_msg(
+            ConfigChangeMsg {
+                change: ConfigChange::UpdateWallets(WalletPair {
+                    consuming_wallet: make_paying_wallet(b"new_consuming_wallet"),
+                    earning_wallet: make_wallet("new_earning_wallet"),
+                }),
+            },
+            |subject: &Accountant| {
+                assert_eq!(
+                    subject.consuming_wallet_opt,
+                    Some(make_paying_wallet(b"new_consuming_wallet"))
+                );
+                assert_eq!(subject.earning_wallet, make_wallet("new_earning_wallet"));
+                let _ = TestLogHandler::new().assert_logs_contain_in_order(
+                    vec![
+                        "INFO: ConfigChange: Earning Wallet has been updated: 0x00006e65775f6561726e696e675f77616c6c6574",
+                        "INFO: ConfigChange: Consuming Wallet has been updated: 0xfa133bbf90bce093fa2e7caa6da68054af66793e",
+                    ]
+                );
+            },
+        );
+        assert_handling_of_config_change_msg(
+            ConfigChangeMsg {
+                change: ConfigChange::UpdatePassword("new password".to_string()),
+            },
+            |_subject: &Accountant| {
+                let _ = TestLogHandler::new().exists_log_containing(
+                    "TRACE: ConfigChange: Ignored irrelevant message: \
+                    ConfigChangeMsg { change: UpdatePassword(\"new password\") }",
+                );
+            },
+        );
+        assert_handling_of_config_change_msg(
+            ConfigChangeMsg {
+                change: ConfigChange::UpdateMinHops(Hops::FourHops),
+            },
+            |_subject: &Accountant| {
+                let _ = TestLogHandler::new().exists_log_containing(
+                    "TRACE: ConfigChange: Ignored irrelevant message: \
+                    ConfigChangeMsg { change: UpdateMinHops(FourHops) }",
+                );
+            },
+        );
+        let new_payment_thresholds = PaymentThresholds {
+            debt_threshold_gwei: 6_000_000,
+            maturity_threshold_sec: 1_400,
+            payment_grace_period_sec: 1_200,
+            permanent_debt_allowed_gwei: 500_000,
+            threshold_interval_sec: 10_000,
+            unban_below_gwei: 400_000,
+        };
+        assert_handling_of_config_change_msg(
+            ConfigChangeMsg {
+                change: ConfigChange::UpdatePaymentThresholds(new_payment_thresholds.clone()),
+            },
+            |subject: &Accountant| {
+                assert_eq!(*subject.payment_thresholds.borrow(), new_payment_thresholds);
+                let payable_scanner = subject
+                    .scanners
+                    .payable
+                    .as_any()
+                    .downcast_ref::<PayableScanner>()
+                    .unwrap();
+                assert_eq!(
+                    *payable_scanner.common.payment_thresholds.borrow(),
+                    new_payment_thresholds
+                );
+                TestLogHandler::new().exists_log_containing(
+                    "INFO: ConfigChange: Payment thresholds have been updated: \
+                    6000000|1400|1200|500000|10000|400000",
+                );
+            },
+        );
+        assert_handling_of_config_change_msg(
+            ConfigChangeMsg {
+                change: ConfigChange::UpdateScanIntervals(ScanIntervals {
+                    pending_payable_scan_interval: Duration::from_secs(150),
+                    payable_scan_interval: Duration::from_secs(250),
+                    receivable_scan_interval: Duration::from_secs(350),
+                }),
+            },
+            |subject: &Accountant| {
+                let interval_of = |scan_type: ScanType| {
+                    subject
+                        .scan_schedulers
+                        .schedulers
+                        .get(&scan_type)
+                        .unwrap()
+                        .interval()
+                };
+                assert_eq!(
+                    interval_of(ScanType::PendingPayables),
+                    Duration::from_secs(150)
+                );
+                assert_eq!(interval_of(ScanType::Payables), Duration::from_secs(250));
+                assert_eq!(interval_of(ScanType::Receivables), Duration::from_secs(350));
+                TestLogHandler::new().exists_log_containing(
+                    "INFO: ConfigChange: Scan intervals have been updated: 150|250|350",
+                );
+            },
+        );
+    }
+
+    fn assert_handling_of_config_change_msg<A>(msg: ConfigChangeMsg, assertions: A)
+    where
+        A: FnOnce(&Accountant),
+    {
         init_test_logging();
-        let test_name = "periodical_scanning_for_receivables_and_delinquencies_works";
-        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
-        let notify_later_receivable_params_arc = Arc::new(Mutex::new(vec![]));
-        let system = System::new(test_name);
-        SystemKillerActor::new(Duration::from_secs(10)).start(); // a safety net for GitHub Actions
-        let receivable_scanner = ScannerMock::new()
-            .begin_scan_params(&begin_scan_params_arc)
-            .begin_scan_result(Err(BeginScanError::NothingToProcess))
-            .begin_scan_result(Ok(RetrieveTransactions {
-                recipient: make_wallet("some_recipient"),
-                response_skeleton_opt: None,
-            }))
-            .stop_the_system_after_last_msg();
-        let earning_wallet = make_wallet("earning");
-        let mut config = bc_from_earning_wallet(earning_wallet.clone());
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .build();
+        subject.logger = Logger::new("ConfigChange");
+
+        subject.handle_config_change_msg(msg);
+
+        assertions(&subject);
+    }
+
+    #[test]
+    fn scan_receivables_request() {
+        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
         config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_secs(100),
-            receivable_scan_interval: Duration::from_millis(99),
+            payable_scan_interval: Duration::from_millis(10_000),
+            receivable_scan_interval: Duration::from_millis(10_000),
             pending_payable_scan_interval: Duration::from_secs(100),
         });
-        let mut subject = AccountantBuilder::default()
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let subject = AccountantBuilder::default()
             .bootstrapper_config(config)
-            .logger(Logger::new(test_name))
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
             .build();
-        subject.scanners.payable = Box::new(NullScanner::new()); // Skipping
-        subject.scanners.pending_payable = Box::new(NullScanner::new()); // Skipping
-        subject.scanners.receivable = Box::new(receivable_scanner);
-        subject.scan_schedulers.update_scheduler(
-            ScanType::Receivables,
-            Some(Box::new(
-                NotifyLaterHandleMock::default()
-                    .notify_later_params(&notify_later_receivable_params_arc)
-                    .capture_msg_and_let_it_fly_on(),
-            )),
-            None,
-        );
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
         let subject_addr = subject.start();
-        let subject_subs = Accountant::make_subs_from(&subject_addr);
-        let peer_actors = peer_actors_builder().build();
-        send_bind_message!(subject_subs, peer_actors);
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiScanRequest {
+                scan_type: ScanType::Receivables,
+            }
+            .tmb(4321),
+        };
 
-        send_start_message!(subject_subs);
+        subject_addr.try_send(ui_message).unwrap();
 
-        let time_before = SystemTime::now();
+        System::current().stop();
         system.run();
-        let time_after = SystemTime::now();
-        let notify_later_receivable_params = notify_later_receivable_params_arc.lock().unwrap();
-        TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: {test_name}: There was nothing to process during Receivables scan."
-        ));
-        let mut begin_scan_params = begin_scan_params_arc.lock().unwrap();
-        let (
-            first_attempt_wallet,
-            first_attempt_timestamp,
-            first_attempt_response_skeleton_opt,
-            first_attempt_logger,
-        ) = begin_scan_params.remove(0);
-        let (
-            second_attempt_wallet,
-            second_attempt_timestamp,
-            second_attempt_response_skeleton_opt,
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RetrieveTransactions>(0),
+            &RetrieveTransactions {
+                recipients: vec![make_wallet("earning_wallet")],
+                response_skeleton_opt: Some(ResponseSkeleton {
+                    client_id: 1234,
+                    context_id: 4321,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn received_payments_with_response_skeleton_sends_response_to_ui_gateway() {
+        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(10_000),
+            receivable_scan_interval: Duration::from_millis(10_000),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        config.suppress_initial_scans = true;
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .config_dao(
+                ConfigDaoMock::new()
+                    .get_result(Ok(ConfigDaoRecord::new("start_block", None, false)))
+                    .set_result(Ok(())),
+            )
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let received_payments = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block: BlockMarker::Value(0),
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            }),
+            transactions: vec![],
+            rechecked_opt: None,
+            pending: vec![],
+        };
+
+        subject_addr.try_send(received_payments).unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: false }.tmb(4321),
+            }
+        );
+    }
+
+    #[test]
+    fn scan_payables_request() {
+        let config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let payable_account = PayableAccount {
+            wallet: make_wallet("wallet"),
+            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
+            last_paid_timestamp: SystemTime::now().sub(Duration::from_secs(
+                (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 1) as u64,
+            )),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(vec![payable_account.clone()])
+            .creditor_thresholds_result(HashMap::new());
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiScanRequest {
+                scan_type: ScanType::Payables,
+            }
+            .tmb(4321),
+        };
+
+        subject_addr.try_send(ui_message).unwrap();
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<QualifiedPayablesMessage>(0),
+            &QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![payable_account]),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId(1),
+                response_skeleton_opt: Some(ResponseSkeleton {
+                    client_id: 1234,
+                    context_id: 4321,
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn scan_payables_request_pays_from_consuming_wallet_picked_by_selector() {
+        let mut config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        let primary_consuming_wallet = make_paying_wallet(b"primary");
+        let additional_consuming_wallet = make_paying_wallet(b"additional");
+        config.additional_consuming_wallets = vec![additional_consuming_wallet.clone()];
+        let payable_account = PayableAccount {
+            wallet: make_wallet("wallet"),
+            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
+            last_paid_timestamp: SystemTime::now().sub(Duration::from_secs(
+                (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 1) as u64,
+            )),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(vec![payable_account.clone()])
+            .creditor_thresholds_result(HashMap::new());
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(primary_consuming_wallet.clone())
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        // The primary wallet has had its turn already
+        let candidates = subject.consuming_wallets();
+        assert_eq!(
+            subject.wallet_selector.select(&candidates),
+            Some(primary_consuming_wallet)
+        );
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiScanRequest {
+                scan_type: ScanType::Payables,
+            }
+            .tmb(4321),
+        };
+
+        subject_addr.try_send(ui_message).unwrap();
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording
+                .get_record::<QualifiedPayablesMessage>(0)
+                .consuming_wallet,
+            additional_consuming_wallet
+        );
+    }
+
+    #[test]
+    fn additional_consuming_wallets_are_unavailable_without_primary_consuming_wallet() {
+        let mut config = bc_from_earning_wallet(make_wallet("earning"));
+        let additional_consuming_wallet = make_wallet("additional");
+        config.additional_consuming_wallets = vec![additional_consuming_wallet.clone()];
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .build();
+        subject.consuming_wallet_opt = None;
+
+        assert_eq!(subject.consuming_wallets(), vec![]);
+
+        let primary_consuming_wallet = make_wallet("primary");
+        subject.consuming_wallet_opt = Some(primary_consuming_wallet.clone());
+
+        assert_eq!(
+            subject.consuming_wallets(),
+            vec![
+                primary_consuming_wallet,
+                additional_consuming_wallet.clone()
+            ]
+        );
+        assert_eq!(subject.our_wallet(&additional_consuming_wallet), true);
+        assert_eq!(subject.our_wallet(&make_wallet("stranger")), false);
+    }
+
+    #[test]
+    fn sent_payable_with_response_skeleton_sends_scan_response_to_ui_gateway() {
+        let config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        let pending_payable_dao =
+            PendingPayableDaoMock::default().fingerprints_rowids_result(TransactionHashes {
+                rowid_results: vec![(1, make_tx_hash(123))],
+                no_rowid_results: vec![],
+            });
+        let payable_dao = PayableDaoMock::default().mark_pending_payables_rowids_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForPayableScanner(pending_payable_dao)])
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .bootstrapper_config(config)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        let sent_payable = SentPayables {
+            payment_procedure_result: Ok(vec![ProcessedPayableFallible::Correct(PendingPayable {
+                recipient_wallet: make_wallet("blah"),
+                hash: make_tx_hash(123),
+            })]),
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            }),
+        };
+
+        subject_addr.try_send(sent_payable).unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: false }.tmb(4321),
+            }
+        );
+    }
+
+    #[test]
+    fn received_balances_and_qualified_payables_under_our_money_limit_thus_all_forwarded_to_blockchain_bridge(
+    ) {
+        // the numbers for balances don't do real math, they need not to match either the condition for
+        // the payment adjustment or the actual values that come from the payable size reducing algorithm;
+        // all that is mocked in this test
+        init_test_logging();
+        let test_name = "received_balances_and_qualified_payables_under_our_money_limit_thus_all_forwarded_to_blockchain_bridge";
+        let is_adjustment_required_params_arc = Arc::new(Mutex::new(vec![]));
+        let enqueue_params_arc = Arc::new(Mutex::new(vec![]));
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let instructions_recipient = blockchain_bridge
+            .system_stop_conditions(match_every_type_id!(OutboundPaymentsInstructions))
+            .start()
+            .recipient();
+        let outbound_payment_dao = OutboundPaymentDaoMock::new()
+            .enqueue_params(&enqueue_params_arc)
+            .enqueue_result(Ok(1));
+        let mut subject = AccountantBuilder::default()
+            .outbound_payment_dao(outbound_payment_dao)
+            .build();
+        let payment_adjuster = PaymentAdjusterMock::default()
+            .is_adjustment_required_params(&is_adjustment_required_params_arc)
+            .is_adjustment_required_result(Ok(None));
+        let payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt = Some(instructions_recipient);
+        subject.logger = Logger::new(test_name);
+        let subject_addr = subject.start();
+        let account_1 = make_payable_account(44_444);
+        let account_2 = make_payable_account(333_333);
+        let system = System::new("test");
+        let agent_id_stamp = ArbitraryIdStamp::new();
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+            masq_token_balance_in_minor_units: U256::from(1_000_000),
+        };
+        let agent = BlockchainAgentMock::default()
+            .set_arbitrary_id_stamp(agent_id_stamp)
+            // For the gas runway check
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            // For the fee preview
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            // For the log of payment decisions
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            // For the snapshot kept with the queued payments
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::new(123))
+            .consuming_wallet_result(consuming_wallet.clone())
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let accounts = vec![account_1, account_2];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts.clone()),
+            agent: Box::new(agent),
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            }),
+        };
+
+        subject_addr.try_send(msg).unwrap();
+
+        system.run();
+        let mut is_adjustment_required_params = is_adjustment_required_params_arc.lock().unwrap();
+        let (blockchain_agent_with_context_msg_actual, logger_clone) =
+            is_adjustment_required_params.remove(0);
+        assert_eq!(
+            blockchain_agent_with_context_msg_actual.protected_qualified_payables,
+            protect_payables_in_test(accounts.clone())
+        );
+        assert_eq!(
+            blockchain_agent_with_context_msg_actual.response_skeleton_opt,
+            Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            })
+        );
+        assert_eq!(
+            blockchain_agent_with_context_msg_actual
+                .agent
+                .arbitrary_id_stamp(),
+            agent_id_stamp
+        );
+        assert!(is_adjustment_required_params.is_empty());
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        let payments_instructions =
+            blockchain_bridge_recording.get_record::<OutboundPaymentsInstructions>(0);
+        assert_eq!(payments_instructions.affordable_accounts, accounts);
+        assert_eq!(
+            payments_instructions.response_skeleton_opt,
+            Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            })
+        );
+        assert_eq!(
+            payments_instructions.agent.arbitrary_id_stamp(),
+            agent_id_stamp
+        );
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+        let mut enqueue_params = enqueue_params_arc.lock().unwrap();
+        let (_, agent_snapshot, enqueued_accounts) = enqueue_params.remove(0);
+        assert_eq!(
+            agent_snapshot,
+            BlockchainAgentSnapshot {
+                consuming_wallet_address: consuming_wallet.to_string(),
+                transaction_fee_balance_wei: 1_000_000,
+                service_fee_balance_wei: 1_000_000,
+                agreed_fee_per_computation_unit: 123,
+                chain_identifier: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            }
+        );
+        assert_eq!(enqueued_accounts, accounts);
+        assert!(enqueue_params.is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Paying 377777000000000 wei of MASQ to 2 creditors for an estimated \
+            1000 wei in transaction fees"
+        ));
+        test_use_of_the_same_logger(&logger_clone, test_name)
+        // adjust_payments() did not need a prepared result which means it wasn't reached
+        // because otherwise this test would've panicked
+    }
+
+    #[test]
+    fn short_gas_runway_is_announced_by_warning_and_ui_broadcast() {
+        init_test_logging();
+        let test_name = "short_gas_runway_is_announced_by_warning_and_ui_broadcast";
+        let estimated_transaction_fee_total_params_arc = Arc::new(Mutex::new(vec![]));
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut config = make_bc_with_defaults();
+        config.gas_runway_alert_scans = 10;
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_secs(600),
+            pending_payable_scan_interval: Duration::from_secs(60),
+            receivable_scan_interval: Duration::from_secs(60),
+        });
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .outbound_payment_dao(OutboundPaymentDaoMock::new().enqueue_result(Ok(1)))
+            .build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        subject.logger = Logger::new(test_name);
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(7_500_000_000_u64),
+            masq_token_balance_in_minor_units: U256::from(1_000_000),
+        };
+        let agent = BlockchainAgentMock::default()
+            .consuming_wallet_result(consuming_wallet.clone())
+            .estimated_transaction_fee_total_params(&estimated_transaction_fee_total_params_arc)
+            .estimated_transaction_fee_total_result(Wei::new(2_000_000_000))
+            .estimated_transaction_fee_total_result(Wei::new(2_000_000_000))
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts.clone()),
+            agent: Box::new(agent),
+            response_skeleton_opt: None,
+        };
+        let system = System::new(test_name);
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        let estimated_transaction_fee_total_params =
+            estimated_transaction_fee_total_params_arc.lock().unwrap();
+        assert_eq!(*estimated_transaction_fee_total_params, vec![2, 2]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiTransactionFeeRunwayBroadcast {
+                    consuming_wallet_address: consuming_wallet.to_string(),
+                    transaction_fee_balance_gwei: 7,
+                    scans_left: 3,
+                    runway_sec: 1800,
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording
+                .get_record::<OutboundPaymentsInstructions>(0)
+                .affordable_accounts,
+            accounts
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Consuming wallet {} holds 7500000000 wei for transaction fees, \
+            which will pay for only about 3 more payable scans (~1800 sec); consider topping it up",
+            consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn short_service_fee_balance_is_announced_with_the_recommended_top_up() {
+        init_test_logging();
+        let test_name = "short_service_fee_balance_is_announced_with_the_recommended_top_up";
+        let (blockchain_bridge, _, _) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut config = make_bc_with_defaults();
+        config.gas_runway_alert_scans = 0;
+        config.top_up_horizon_hours = 10;
+        let payable_dao = PayableDaoMock::new().total_result(4_000_000_000_000);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .outbound_payment_dao(OutboundPaymentDaoMock::new().enqueue_result(Ok(1)))
+            .logger(Logger::new(test_name))
+            .build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        let now = SystemTime::now();
+        let creditor = make_wallet("creditor");
+        let mut forecaster = ConsumptionForecaster::new(now - Duration::from_secs(30 * 3_600));
+        // Steady through the current hour too, so that the projection holds if the hour turns
+        // in the middle of the test
+        (0..=24).for_each(|hours_ago| {
+            forecaster.record_consumed(
+                &creditor,
+                1_000,
+                1_000_000_000_000,
+                now - Duration::from_secs(hours_ago * 3_600),
+            )
+        });
+        subject.consumption_forecaster = forecaster;
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(1_000_000_000_000_u64),
+            masq_token_balance_in_minor_units: U256::from(5_000_000_000_000_u64),
+        };
+        let agent = BlockchainAgentMock::default()
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .consuming_wallet_result(consuming_wallet.clone())
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(vec![make_payable_account(111)]),
+            agent: Box::new(agent),
+            response_skeleton_opt: None,
+        };
+        let system = System::new(test_name);
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiServiceFeeTopUpBroadcast {
+                    consuming_wallet_address: consuming_wallet.to_string(),
+                    service_fee_balance_gwei: 5_000,
+                    outstanding_debt_gwei: 4_000,
+                    projected_debt_growth_gwei: 10_000,
+                    horizon_hours: 10,
+                    top_up_gwei: 9_000,
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Consuming wallet {} holds 5000000000000 wei of service fee, while \
+            4000000000000 wei is owed already and 10000000000000 wei more is projected to be owed \
+            within 10 hours; consider topping it up with 9000000000000 wei",
+            consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn services_consumed_feed_the_consumption_forecaster() {
+        let payable_dao = PayableDaoMock::new()
+            .more_money_payable_result(Ok(()))
+            .more_money_payable_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        let now = SystemTime::now();
+        let an_hour_ago = now - Duration::from_secs(3_600);
+        subject.consumption_forecaster = ConsumptionForecaster::new(an_hour_ago);
+        let exit_wallet = make_wallet("exit");
+        let routing_wallet = make_wallet("routing");
+
+        subject.handle_report_services_consumed_message(ReportServicesConsumedMessage {
+            timestamp: an_hour_ago,
+            exit: ExitServiceConsumed {
+                earning_wallet: exit_wallet.clone(),
+                payload_size: 1200,
+                service_rate: 120,
+                byte_rate: 30,
+            },
+            routing_payload_size: 3456,
+            routing: vec![RoutingServiceConsumed {
+                earning_wallet: routing_wallet.clone(),
+                service_rate: 42,
+                byte_rate: 24,
+            }],
+        });
+
+        assert_eq!(
+            subject
+                .consumption_forecaster
+                .project(now, Duration::from_secs(3_600)),
+            vec![
+                DebtGrowthProjection {
+                    creditor: routing_wallet,
+                    bytes_per_hour: 3456,
+                    wei_per_hour: 42 + 3456 * 24,
+                    projected_growth_wei: 42 + 3456 * 24,
+                },
+                DebtGrowthProjection {
+                    creditor: exit_wallet,
+                    bytes_per_hour: 1200,
+                    wei_per_hour: 120 + 1200 * 30,
+                    projected_growth_wei: 120 + 1200 * 30,
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn spend_rate_deferral_is_announced_by_ui_broadcast_and_the_rest_is_paid() {
+        let estimated_transaction_fee_total_params_arc = Arc::new(Mutex::new(vec![]));
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut config = make_bc_with_defaults();
+        config.gas_runway_alert_scans = 0;
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .outbound_payment_dao(OutboundPaymentDaoMock::new().enqueue_result(Ok(1)))
+            .build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .spend_rate_limits(SpendRateLimits {
+                service_fee_per_hour_gwei: 200,
+                service_fee_per_day_gwei: 1_000,
+                transaction_fee_per_hour_gwei: 5,
+                transaction_fee_per_day_gwei: 50,
+            })
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(1_000_000_000_000_u64),
+            masq_token_balance_in_minor_units: U256::from(1_000_000),
+        };
+        let agent = BlockchainAgentMock::default()
+            .estimated_transaction_fee_total_params(&estimated_transaction_fee_total_params_arc)
+            .estimated_transaction_fee_total_result(Wei::new(1_000_000_000))
+            .estimated_transaction_fee_total_result(Wei::new(1_000_000_000))
+            .estimated_transaction_fee_total_result(Wei::new(1_000_000_000))
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .consuming_wallet_result(make_paying_wallet(b"consuming"))
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts.clone()),
+            agent: Box::new(agent),
+            response_skeleton_opt: None,
+        };
+        let system = System::new("spend_rate_deferral_is_announced_by_ui_broadcast");
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        let estimated_transaction_fee_total_params =
+            estimated_transaction_fee_total_params_arc.lock().unwrap();
+        assert_eq!(*estimated_transaction_fee_total_params, vec![1, 1, 1]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiSpendRateLimitBroadcast {
+                    deferred_payments: 1,
+                    deferred_gwei: 222,
+                    service_fee_allowance_gwei: 200,
+                    transaction_fee_allowance_gwei: 5,
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording
+                .get_record::<OutboundPaymentsInstructions>(0)
+                .affordable_accounts,
+            vec![accounts[0].clone()]
+        );
+    }
+
+    #[test]
+    fn payable_scan_ends_without_payments_if_spend_rate_limits_defer_them_all() {
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default().build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .spend_rate_limits(SpendRateLimits {
+                service_fee_per_hour_gwei: 0,
+                service_fee_per_day_gwei: 0,
+                transaction_fee_per_hour_gwei: 5,
+                transaction_fee_per_day_gwei: 50,
+            })
+            .build();
+        payable_scanner.mark_as_started(SystemTime::now());
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts),
+            agent: Box::new(
+                BlockchainAgentMock::default().consuming_wallet_balances_result(
+                    ConsumingWalletBalances {
+                        transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                        masq_token_balance_in_minor_units: U256::from(1_000_000),
+                    },
+                ),
+            ),
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            }),
+        };
+        let system =
+            System::new("payable_scan_ends_without_payments_if_spend_rate_limits_defer_them_all");
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.scanners.payable.scan_started_at(), None);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiSpendRateLimitBroadcast {
+                    deferred_payments: 2,
+                    deferred_gwei: 333,
+                    service_fee_allowance_gwei: 0,
+                    transaction_fee_allowance_gwei: 5,
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(1),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: false }.tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 2);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
+    }
+
+    #[test]
+    fn payable_scan_ends_without_payments_outside_the_payment_window() {
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default().build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let hour_now = OffsetDateTime::now_utc().hour();
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .payment_window(PaymentWindow {
+                start_hour: (hour_now + 2) % 24,
+                end_hour: (hour_now + 3) % 24,
+                low_gas_price_gwei: 0,
+                deadline_margin_sec: 3_600,
+            })
+            .build();
+        payable_scanner.mark_as_started(SystemTime::now());
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts),
+            agent: Box::new(
+                BlockchainAgentMock::default().consuming_wallet_balances_result(
+                    ConsumingWalletBalances {
+                        transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                        masq_token_balance_in_minor_units: U256::from(1_000_000),
+                    },
+                ),
+            ),
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            }),
+        };
+        let system = System::new("payable_scan_ends_without_payments_outside_the_payment_window");
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.scanners.payable.scan_started_at(), None);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: false }.tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
+    }
+
+    #[test]
+    fn payable_scan_pays_nothing_if_what_is_left_breaks_the_adjustment_invariants() {
+        init_test_logging();
+        let test_name =
+            "payable_scan_pays_nothing_if_what_is_left_breaks_the_adjustment_invariants";
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default()
+            .logger(Logger::new(test_name))
+            .build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .check_adjustment_invariants(true)
+            .build();
+        payable_scanner.mark_as_started(SystemTime::now());
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        // Left unadjusted, the debts add up to more than the service fee balance
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts),
+            agent: Box::new(
+                BlockchainAgentMock::default().consuming_wallet_balances_result(
+                    ConsumingWalletBalances {
+                        transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                        masq_token_balance_in_minor_units: U256::from(1_000_000),
+                    },
+                ),
+            ),
+            response_skeleton_opt: None,
+        };
+        let system = System::new(test_name);
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.scanners.payable.scan_started_at(), None);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: {test_name}: Payment adjustment broke its invariants; nothing is paid in this \
+             scan. Diagnostics: "
+        ));
+    }
+
+    #[test]
+    fn payable_scan_logs_the_decisions_taken_about_each_debt() {
+        let mut subject = AccountantBuilder::default().build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let hour_now = OffsetDateTime::now_utc().hour();
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .payment_window(PaymentWindow {
+                start_hour: (hour_now + 2) % 24,
+                end_hour: (hour_now + 3) % 24,
+                low_gas_price_gwei: 0,
+                deadline_margin_sec: 3_600,
+            })
+            .build();
+        payable_scanner.mark_as_started(SystemTime::now());
+        subject.scanners.payable = Box::new(payable_scanner);
+        let account = make_payable_account(111);
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(vec![account.clone()]),
+            agent: Box::new(
+                BlockchainAgentMock::default().consuming_wallet_balances_result(
+                    ConsumingWalletBalances {
+                        transaction_fee_balance_in_minor_units: U256::from(3_000_000),
+                        masq_token_balance_in_minor_units: U256::from(5_000_000),
+                    },
+                ),
+            ),
+            response_skeleton_opt: None,
+        };
+        let before = SystemTime::now();
+        let system = System::new("payable_scan_logs_the_decisions_taken_about_each_debt");
+
+        subject.handle_payable_payment_setup(msg);
+
+        let after = SystemTime::now();
+        System::current().stop();
+        system.run();
+        let chain = subject
+            .payment_decisions
+            .chain_opt(&account.wallet)
+            .unwrap();
+        assert!(before <= chain.timestamp && chain.timestamp <= after);
+        assert_eq!(chain.owed_wei, account.balance_wei);
+        assert_eq!(chain.service_fee_balance_wei, 5_000_000);
+        assert_eq!(chain.transaction_fee_balance_wei, 3_000_000);
+        assert_eq!(
+            chain.decisions,
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::HeldBack {
+                    reason: SKIPPED_BY_PAYMENT_WINDOW.to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn trigger_payable_scan_request_starts_a_payable_scan() {
+        let config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let payable_account = PayableAccount {
+            wallet: make_wallet("wallet"),
+            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
+            last_paid_timestamp: SystemTime::now().sub(Duration::from_secs(
+                (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 1) as u64,
+            )),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(vec![payable_account.clone()])
+            .creditor_thresholds_result(HashMap::new());
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiTriggerPayableScanRequest {}.tmb(4321),
+        };
+
+        subject_addr.try_send(ui_message).unwrap();
+
+        let response_skeleton = ResponseSkeleton {
+            client_id: 1234,
+            context_id: 4321,
+        };
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(move |actor: &mut Accountant| {
+                    assert_eq!(
+                        actor.payable_scan_report_opt,
+                        Some(PayableScanReport::new(response_skeleton))
+                    );
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<QualifiedPayablesMessage>(0),
+            &QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![payable_account]),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId(1),
+                response_skeleton_opt: Some(response_skeleton)
+            }
+        );
+    }
+
+    #[test]
+    fn trigger_payable_scan_request_is_refused_while_payables_are_scanned() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+        let running_report = PayableScanReport::new(ResponseSkeleton {
+            client_id: 1111,
+            context_id: 2222,
+        });
+        subject.payable_scan_report_opt = Some(running_report.clone());
+        let system =
+            System::new("trigger_payable_scan_request_is_refused_while_payables_are_scanned");
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        subject.qualified_payables_sub_opt = Some(blockchain_bridge.start().recipient());
+
+        subject.handle_trigger_payable_scan(1234, 4321);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.payable_scan_report_opt, Some(running_report));
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: MessageBody {
+                    opcode: "triggerPayableScan".to_string(),
+                    path: Conversation(4321),
+                    payload: Err((SCAN_ERROR, PAYABLES_BEING_SCANNED.to_string())),
+                },
+            }
+        );
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
+    }
+
+    #[test]
+    fn trigger_payable_scan_request_reports_at_once_if_nothing_qualifies() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let payable_dao = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        let system =
+            System::new("trigger_payable_scan_request_reports_at_once_if_nothing_qualifies");
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_trigger_payable_scan(1234, 4321);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.payable_scan_report_opt, None);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiTriggerPayableScanResponse {
+                    qualified: 0,
+                    paid: vec![],
+                    adjusted: vec![],
+                    skipped: vec![],
+                }
+                .tmb(4321),
+            }
+        );
+    }
+
+    #[test]
+    fn payable_scan_triggered_by_operator_reports_debts_deferred_by_the_payment_window() {
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default().build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let hour_now = OffsetDateTime::now_utc().hour();
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .payment_window(PaymentWindow {
+                start_hour: (hour_now + 2) % 24,
+                end_hour: (hour_now + 3) % 24,
+                low_gas_price_gwei: 0,
+                deadline_margin_sec: 3_600,
+            })
+            .build();
+        payable_scanner.mark_as_started(SystemTime::now());
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        subject.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+        let response_skeleton = ResponseSkeleton {
+            client_id: 1234,
+            context_id: 4321,
+        };
+        subject.payable_scan_report_opt = Some(PayableScanReport::new(response_skeleton));
+        let account_1 = make_payable_account(111);
+        let account_2 = make_payable_account(222);
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(vec![
+                account_1.clone(),
+                account_2.clone(),
+            ]),
+            agent: Box::new(
+                BlockchainAgentMock::default().consuming_wallet_balances_result(
+                    ConsumingWalletBalances {
+                        transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                        masq_token_balance_in_minor_units: U256::from(1_000_000),
+                    },
+                ),
+            ),
+            response_skeleton_opt: Some(response_skeleton),
+        };
+        let system = System::new(
+            "payable_scan_triggered_by_operator_reports_debts_deferred_by_the_payment_window",
+        );
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.scanners.payable.scan_started_at(), None);
+        assert_eq!(subject.payable_scan_report_opt, None);
+        let skipped = |account: &PayableAccount| UiSkippedPayable {
+            wallet: account.wallet.to_string(),
+            owed_gwei: wei_to_gwei(account.balance_wei),
+            reason: SKIPPED_BY_PAYMENT_WINDOW.to_string(),
+        };
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiTriggerPayableScanResponse {
+                    qualified: 2,
+                    paid: vec![],
+                    adjusted: vec![],
+                    skipped: vec![skipped(&account_1), skipped(&account_2)],
+                }
+                .tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
+    }
+
+    #[test]
+    fn payable_scan_triggered_by_operator_reports_what_was_paid_once_the_payments_are_sent() {
+        let mark_submitted_params_arc = Arc::new(Mutex::new(vec![]));
+        let account_1 = make_payable_account(111);
+        let account_2 = make_payable_account(222);
+        let pending_payable_dao =
+            PendingPayableDaoMock::default().fingerprints_rowids_result(TransactionHashes {
+                rowid_results: vec![(1, make_tx_hash(1)), (2, make_tx_hash(2))],
+                no_rowid_results: vec![],
+            });
+        let payable_dao = PayableDaoMock::default().mark_pending_payables_rowids_result(Ok(()));
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(pending_payable_dao)
+            .payment_adjuster(payment_adjuster)
+            .build();
+        payable_scanner.mark_as_started(SystemTime::now());
+        let outbound_payment_dao = OutboundPaymentDaoMock::new()
+            .enqueue_result(Ok(7))
+            .mark_submitted_params(&mark_submitted_params_arc)
+            .mark_submitted_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .outbound_payment_dao(outbound_payment_dao)
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        let agent = BlockchainAgentMock::default()
+            // For the gas runway check
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            // For the fee preview
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            // For the log of payment decisions
+            .consuming_wallet_balances_result(ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                masq_token_balance_in_minor_units: U256::from(1_000_000),
+            })
+            // For the snapshot kept with the queued payments
+            .consuming_wallet_balances_result(ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                masq_token_balance_in_minor_units: U256::from(1_000_000),
+            })
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .consuming_wallet_result(make_paying_wallet(b"consuming"))
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new(
+            "payable_scan_triggered_by_operator_reports_what_was_paid_once_the_payments_are_sent",
+        );
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        let response_skeleton = ResponseSkeleton {
+            client_id: 1234,
+            context_id: 4321,
+        };
+        subject.payable_scan_report_opt = Some(PayableScanReport::new(response_skeleton));
+        subject.handle_payable_payment_setup(BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(vec![
+                account_1.clone(),
+                account_2.clone(),
+            ]),
+            agent: Box::new(agent),
+            response_skeleton_opt: Some(response_skeleton),
+        });
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let sent_payables = SentPayables {
+            payment_procedure_result: Ok(vec![
+                ProcessedPayableFallible::Correct(PendingPayable {
+                    recipient_wallet: account_1.wallet.clone(),
+                    hash: make_tx_hash(1),
+                }),
+                ProcessedPayableFallible::Correct(PendingPayable {
+                    recipient_wallet: account_2.wallet.clone(),
+                    hash: make_tx_hash(2),
+                }),
+            ]),
+            response_skeleton_opt: Some(response_skeleton),
+        };
+
+        subject_addr.try_send(sent_payables).unwrap();
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording
+                .get_record::<OutboundPaymentsInstructions>(0)
+                .affordable_accounts,
+            vec![account_1.clone(), account_2.clone()]
+        );
+        let mark_submitted_params = mark_submitted_params_arc.lock().unwrap();
+        assert_eq!(
+            *mark_submitted_params,
+            vec![(
+                7,
+                vec![
+                    PendingPayable::new(account_1.wallet.clone(), make_tx_hash(1)),
+                    PendingPayable::new(account_2.wallet.clone(), make_tx_hash(2))
+                ]
+            )]
+        );
+        let paid = |account: &PayableAccount, hash| UiPaidPayable {
+            wallet: account.wallet.to_string(),
+            amount_gwei: wei_to_gwei(account.balance_wei),
+            transaction_hash: format!("{:?}", hash),
+        };
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiTriggerPayableScanResponse {
+                    qualified: 2,
+                    paid: vec![
+                        paid(&account_1, make_tx_hash(1)),
+                        paid(&account_2, make_tx_hash(2))
+                    ],
+                    adjusted: vec![],
+                    skipped: vec![],
+                }
+                .tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+    }
+
+    #[test]
+    fn scan_error_fails_the_debts_the_payable_scan_was_about_to_pay() {
+        let mut subject = AccountantBuilder::default().build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+        let account = make_payable_account(111);
+        subject.payment_decisions.start(
+            vec![account.clone()],
+            ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                masq_token_balance_in_minor_units: U256::from(1_000_000),
+            },
+            SystemTime::now(),
+        );
+        let subject_addr = subject.start();
+        let system = System::new("test");
+
+        subject_addr
+            .try_send(ScanError {
+                scan_type: ScanType::Payables,
+                response_skeleton_opt: None,
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(move |actor: &mut Accountant| {
+                    assert_eq!(
+                        actor
+                            .payment_decisions
+                            .chain_opt(&account.wallet)
+                            .unwrap()
+                            .decisions,
+                        vec![
+                            PaymentDecision::Qualified,
+                            PaymentDecision::Failed {
+                                reason: EXAMPLE_ERROR_MSG.to_string()
+                            }
+                        ]
+                    );
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        system.run();
+    }
+
+    #[test]
+    fn scan_error_ends_payable_scan_triggered_by_operator_with_an_error_response() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default().build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+        subject.payable_scan_report_opt = Some(PayableScanReport::new(EXAMPLE_RESPONSE_SKELETON));
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ScanError {
+                scan_type: ScanType::Payables,
+                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(|actor: &mut Accountant| {
+                    assert_eq!(actor.payable_scan_report_opt, None);
+                    assert_eq!(actor.scanners.payable.scan_started_at(), None);
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(EXAMPLE_RESPONSE_SKELETON.client_id),
+                body: MessageBody {
+                    opcode: "triggerPayableScan".to_string(),
+                    path: Conversation(EXAMPLE_RESPONSE_SKELETON.context_id),
+                    payload: Err((
+                        SCAN_ERROR,
+                        format!("Payables scan failed: '{}'", EXAMPLE_ERROR_MSG)
+                    )),
+                },
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+    }
+
+    #[test]
+    fn only_urgent_payments_are_sent_while_the_gas_price_is_above_the_ceiling() {
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut config = make_bc_with_defaults();
+        config.gas_runway_alert_scans = 0;
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .outbound_payment_dao(OutboundPaymentDaoMock::new().enqueue_result(Ok(1)))
+            .build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let mut payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .gas_price_ceiling(GasPriceCeiling {
+                ceiling_gwei: 30,
+                deadline_margin_sec: 3_600,
+            })
+            .build();
+        let now = SystemTime::now();
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let urgent_wallet = accounts[1].wallet.clone();
+        payable_scanner
+            .payment_urgency
+            .remember_deadlines(&accounts, &|account| {
+                if account.wallet == urgent_wallet {
+                    now + Duration::from_secs(600)
+                } else {
+                    now + Duration::from_secs(86_400)
+                }
+            });
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(1_000_000_000_000_u64),
+            masq_token_balance_in_minor_units: U256::from(1_000_000),
+        };
+        let agent = BlockchainAgentMock::default()
+            // For the log of payment decisions
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::from_gwei(50))
+            .estimated_transaction_fee_total_result(Wei::new(1_000_000_000))
+            // For the snapshot kept with the queued payments
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::from_gwei(50))
+            .consuming_wallet_result(make_paying_wallet(b"consuming"))
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts.clone()),
+            agent: Box::new(agent),
+            response_skeleton_opt: None,
+        };
+        let system =
+            System::new("only_urgent_payments_are_sent_while_the_gas_price_is_above_the_ceiling");
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording
+                .get_record::<OutboundPaymentsInstructions>(0)
+                .affordable_accounts,
+            vec![accounts[1].clone()]
+        );
+    }
+
+    #[test]
+    fn payments_are_sent_even_if_they_could_not_be_queued() {
+        init_test_logging();
+        let test_name = "payments_are_sent_even_if_they_could_not_be_queued";
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut config = make_bc_with_defaults();
+        config.gas_runway_alert_scans = 0;
+        let outbound_payment_dao = OutboundPaymentDaoMock::new().enqueue_result(Err(
+            OutboundPaymentDaoError::InsertionFailed("Booga".to_string()),
+        ));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .outbound_payment_dao(outbound_payment_dao)
+            .logger(Logger::new(test_name))
+            .build();
+        let payment_adjuster =
+            PaymentAdjusterMock::default().is_adjustment_required_result(Ok(None));
+        let payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt =
+            Some(blockchain_bridge.start().recipient());
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+            masq_token_balance_in_minor_units: U256::from(1_000_000),
+        };
+        let agent = BlockchainAgentMock::default()
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            .consuming_wallet_balances_result(wallet_balances)
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .consuming_wallet_result(make_paying_wallet(b"consuming"))
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let accounts = vec![make_payable_account(111), make_payable_account(222)];
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: protect_payables_in_test(accounts.clone()),
+            agent: Box::new(agent),
+            response_skeleton_opt: None,
+        };
+        let system = System::new(test_name);
+
+        subject.handle_payable_payment_setup(msg);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.outbound_instructions_rowid_opt, None);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording
+                .get_record::<OutboundPaymentsInstructions>(0)
+                .affordable_accounts,
+            accounts
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Failed to queue the outbound payments; a crash before they are \
+             sent would leave no trace of them: InsertionFailed(\"Booga\")"
+        ));
+    }
+
+    #[test]
+    fn payment_fees_preview_shows_approximate_fiat_values_if_price_feed_has_quote() {
+        init_test_logging();
+        let test_name =
+            "payment_fees_preview_shows_approximate_fiat_values_if_price_feed_has_quote";
+        let estimated_transaction_fee_total_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = AccountantBuilder::default().build();
+        subject.price_feed = Box::new(PriceFeedMock::default().quote_opt_result(Some(FiatQuote {
+            currency: "EUR".to_string(),
+            transaction_fee_unit_price: 3_000.0,
+            service_fee_unit_price: 0.5,
+        })));
+        subject.logger = Logger::new(test_name);
+        let agent = BlockchainAgentMock::default()
+            .estimated_transaction_fee_total_params(&estimated_transaction_fee_total_params_arc)
+            .estimated_transaction_fee_total_result(Wei::new(1_500_000_000_000_000));
+        let instructions = OutboundPaymentsInstructions {
+            affordable_accounts: vec![
+                make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                    make_wallet("abc"),
+                    10_000_000_000_000_000_000,
+                    None,
+                ),
+                make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                    make_wallet("def"),
+                    4_000_000_000_000_000_000,
+                    None,
+                ),
+                make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                    make_wallet("ghi"),
+                    6_000_000_000_000_000_000,
+                    None,
+                ),
+            ],
+            agent: Box::new(agent),
+            scan_id: ScanId::default(),
+            response_skeleton_opt: None,
+        };
+
+        subject.preview_payment_fees(&instructions);
+
+        let estimated_transaction_fee_total_params =
+            estimated_transaction_fee_total_params_arc.lock().unwrap();
+        assert_eq!(*estimated_transaction_fee_total_params, vec![3]);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Paying 20000000000000000000 wei of MASQ to 3 creditors for an \
+            estimated 1500000000000000 wei in transaction fees (approximately 10.00 EUR and 4.50 EUR \
+            at price feed rates)"
+        ));
+    }
+
+    #[test]
+    fn payment_fees_preview_is_skipped_if_nobody_is_to_be_paid() {
+        init_test_logging();
+        let test_name = "payment_fees_preview_is_skipped_if_nobody_is_to_be_paid";
+        let mut subject = AccountantBuilder::default().build();
+        subject.price_feed = Box::new(PriceFeedMock::default());
+        subject.logger = Logger::new(test_name);
+        let instructions = OutboundPaymentsInstructions {
+            affordable_accounts: vec![],
+            agent: Box::new(BlockchainAgentMock::default()),
+            scan_id: ScanId::default(),
+            response_skeleton_opt: None,
+        };
+
+        subject.preview_payment_fees(&instructions);
+
+        TestLogHandler::new().exists_no_log_containing(&format!("INFO: {test_name}: Paying"));
+    }
+
+    fn test_use_of_the_same_logger(logger_clone: &Logger, test_name: &str) {
+        let experiment_msg = format!("DEBUG: {test_name}: hello world");
+        let log_handler = TestLogHandler::default();
+        log_handler.exists_no_log_containing(&experiment_msg);
+        debug!(logger_clone, "hello world");
+        log_handler.exists_log_containing(&experiment_msg);
+    }
+
+    #[test]
+    fn received_qualified_payables_exceeding_our_masq_balance_are_adjusted_before_forwarded_to_blockchain_bridge(
+    ) {
+        // the numbers for balances don't do real math, they need not to match either the condition for
+        // the payment adjustment or the actual values that come from the payable size reducing algorithm;
+        // all that is mocked in this test
+        init_test_logging();
+        let test_name = "received_qualified_payables_exceeding_our_masq_balance_are_adjusted_before_forwarded_to_blockchain_bridge";
+        let adjust_payments_params_arc = Arc::new(Mutex::new(vec![]));
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let report_recipient = blockchain_bridge
+            .system_stop_conditions(match_every_type_id!(OutboundPaymentsInstructions))
+            .start()
+            .recipient();
+        let mut subject = AccountantBuilder::default()
+            .outbound_payment_dao(OutboundPaymentDaoMock::new().enqueue_result(Ok(1)))
+            .build();
+        let unadjusted_account_1 = make_payable_account(111_111);
+        let unadjusted_account_2 = make_payable_account(222_222);
+        let adjusted_account_1 = PayableAccount {
+            balance_wei: gwei_to_wei(55_550_u64),
+            ..unadjusted_account_1.clone()
+        };
+        let adjusted_account_2 = PayableAccount {
+            balance_wei: gwei_to_wei(100_000_u64),
+            ..unadjusted_account_2.clone()
+        };
+        let response_skeleton = ResponseSkeleton {
+            client_id: 12,
+            context_id: 55,
+        };
+        let agent_id_stamp_first_phase = ArbitraryIdStamp::new();
+        let wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(4_444_444_444_u64),
+            masq_token_balance_in_minor_units: U256::from(155_550_000_000_000_u64),
+        };
+        let agent = BlockchainAgentMock::default()
+            .set_arbitrary_id_stamp(agent_id_stamp_first_phase)
+            // For the log of payment decisions
+            .consuming_wallet_balances_result(wallet_balances);
+        let initial_unadjusted_accounts = protect_payables_in_test(vec![
+            unadjusted_account_1.clone(),
+            unadjusted_account_2.clone(),
+        ]);
+        let msg = BlockchainAgentWithContextMessage {
+            protected_qualified_payables: initial_unadjusted_accounts.clone(),
+            agent: Box::new(agent),
+            response_skeleton_opt: Some(response_skeleton),
+        };
+        // In the real world the agents are identical, here they bear different ids
+        // so that we can watch their journey better
+        let agent_id_stamp_second_phase = ArbitraryIdStamp::new();
+        let agent = BlockchainAgentMock::default()
+            .set_arbitrary_id_stamp(agent_id_stamp_second_phase)
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            .estimated_transaction_fee_total_result(Wei::new(1_000))
+            .consuming_wallet_balances_result(ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                masq_token_balance_in_minor_units: U256::from(1_000_000),
+            })
+            // For the snapshot kept with the queued payments
+            .consuming_wallet_balances_result(wallet_balances)
+            .agreed_fee_per_computation_unit_result(Wei::new(123))
+            .consuming_wallet_result(make_paying_wallet(b"consuming"))
+            .get_chain_result(TEST_DEFAULT_CHAIN);
+        let affordable_accounts = vec![adjusted_account_1.clone(), adjusted_account_2.clone()];
+        let payments_instructions = OutboundPaymentsInstructions {
+            affordable_accounts: affordable_accounts.clone(),
+            agent: Box::new(agent),
+            scan_id: ScanId::default(),
+            response_skeleton_opt: Some(response_skeleton),
+        };
+        let payment_adjuster = PaymentAdjusterMock::default()
+            .is_adjustment_required_result(Ok(Some(Adjustment::MasqToken)))
+            .adjust_payments_params(&adjust_payments_params_arc)
+            .adjust_payments_result(payments_instructions);
+        let payable_scanner = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.outbound_payments_instructions_sub_opt = Some(report_recipient);
+        subject.logger = Logger::new(test_name);
+        let subject_addr = subject.start();
+        let system = System::new("test");
+
+        subject_addr.try_send(msg).unwrap();
+
+        let before = SystemTime::now();
+        assert_eq!(system.run(), 0);
+        let after = SystemTime::now();
+        let mut adjust_payments_params = adjust_payments_params_arc.lock().unwrap();
+        let (actual_prepared_adjustment, captured_now, logger_clone) =
+            adjust_payments_params.remove(0);
+        assert_eq!(actual_prepared_adjustment.adjustment, Adjustment::MasqToken);
+        assert_eq!(
+            actual_prepared_adjustment
+                .original_setup_msg
+                .protected_qualified_payables,
+            initial_unadjusted_accounts
+        );
+        assert_eq!(
+            actual_prepared_adjustment
+                .original_setup_msg
+                .agent
+                .arbitrary_id_stamp(),
+            agent_id_stamp_first_phase
+        );
+        assert!(
+            before <= captured_now && captured_now <= after,
+            "captured timestamp should have been between {:?} and {:?} but was {:?}",
+            before,
+            after,
+            captured_now
+        );
+        assert!(adjust_payments_params.is_empty());
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        let payments_instructions =
+            blockchain_bridge_recording.get_record::<OutboundPaymentsInstructions>(0);
+        assert_eq!(
+            payments_instructions.agent.arbitrary_id_stamp(),
+            agent_id_stamp_second_phase
+        );
+        assert_eq!(
+            payments_instructions.affordable_accounts,
+            affordable_accounts
+        );
+        assert_eq!(
+            payments_instructions.response_skeleton_opt,
+            Some(response_skeleton)
+        );
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+        test_use_of_the_same_logger(&logger_clone, test_name)
+    }
+
+    #[test]
+    fn scan_pending_payables_request() {
+        let mut config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        config.suppress_initial_scans = true;
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(10_000),
+            receivable_scan_interval: Duration::from_millis(10_000),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 1234,
+            timestamp: SystemTime::now(),
+            hash: Default::default(),
+            attempt: 1,
+            amount: 1_000_000,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_all_errorless_fingerprints_result(vec![fingerprint.clone()]);
+        let subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .bootstrapper_config(config)
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .build();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiScanRequest {
+                scan_type: ScanType::PendingPayables,
+            }
+            .tmb(4321),
+        };
+
+        subject_addr.try_send(ui_message).unwrap();
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(0),
+            &RequestTransactionReceipts {
+                pending_payable: vec![fingerprint],
+                response_skeleton_opt: Some(ResponseSkeleton {
+                    client_id: 1234,
+                    context_id: 4321,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn scan_request_from_ui_is_queued_in_case_the_scan_is_already_running() {
+        init_test_logging();
+        let test_name = "scan_request_from_ui_is_queued_in_case_the_scan_is_already_running";
+        let mut config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        config.suppress_initial_scans = true;
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(10_000),
+            receivable_scan_interval: Duration::from_millis(10_000),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 1234,
+            timestamp: SystemTime::now(),
+            hash: Default::default(),
+            attempt: 1,
+            amount: 1_000_000,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_all_errorless_fingerprints_result(vec![fingerprint]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .logger(Logger::new(test_name))
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .build();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let ui_message = |context_id: u64| NodeFromUiMessage {
+            client_id: 1234,
+            body: UiScanRequest {
+                scan_type: ScanType::PendingPayables,
+            }
+            .tmb(context_id),
+        };
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        subject_addr.try_send(ui_message(4321)).unwrap();
+
+        subject_addr.try_send(ui_message(4322)).unwrap();
+        subject_addr.try_send(ui_message(4323)).unwrap();
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: true }.tmb(4322),
+            }
+        );
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(1),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: true }.tmb(4323),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 2);
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: PendingPayables scan is running; the requested one is queued to \
+            follow it"
+        ));
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: PendingPayables scan is running with another queued; the \
+            requested one is merged into it"
+        ));
+    }
+
+    #[test]
+    fn queued_scan_begins_once_the_running_one_has_ended() {
+        init_test_logging();
+        let test_name = "queued_scan_begins_once_the_running_one_has_ended";
+        let mut config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        config.suppress_initial_scans = true;
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(10_000),
+            receivable_scan_interval: Duration::from_millis(10_000),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 1234,
+            timestamp: SystemTime::now(),
+            hash: Default::default(),
+            attempt: 1,
+            amount: 1_000_000,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_all_errorless_fingerprints_result(vec![fingerprint.clone()])
+            .return_all_errorless_fingerprints_result(vec![fingerprint.clone()]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .logger(Logger::new(test_name))
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .build();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new(test_name);
+        let ui_message = |context_id: u64| NodeFromUiMessage {
+            client_id: 1234,
+            body: UiScanRequest {
+                scan_type: ScanType::PendingPayables,
+            }
+            .tmb(context_id),
+        };
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        subject_addr.try_send(ui_message(4321)).unwrap();
+        subject_addr.try_send(ui_message(4322)).unwrap();
+        subject_addr.try_send(ui_message(4323)).unwrap();
+
+        subject_addr
+            .try_send(ReportTransactionReceipts {
+                fingerprints_with_receipts: vec![],
+                response_skeleton_opt: Some(ResponseSkeleton {
+                    client_id: 1234,
+                    context_id: 4321,
+                }),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 2);
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(1),
+            &RequestTransactionReceipts {
+                pending_payable: vec![fingerprint],
+                response_skeleton_opt: None,
+            }
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(2),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: false }.tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 3);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Beginning the PendingPayables scan queued behind the one just \
+            ended"
+        ));
+    }
+
+    #[test]
+    fn report_transaction_receipts_with_response_skeleton_sends_scan_response_to_ui_gateway() {
+        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(10_000),
+            receivable_scan_interval: Duration::from_millis(10_000),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let report_transaction_receipts = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![],
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 1234,
+                context_id: 4321,
+            }),
+        };
+
+        subject_addr.try_send(report_transaction_receipts).unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiScanResponse { queued: false }.tmb(4321),
+            }
+        );
+    }
+
+    #[test]
+    fn accountant_calls_payable_dao_to_mark_pending_payable() {
+        let fingerprints_rowids_params_arc = Arc::new(Mutex::new(vec![]));
+        let mark_pending_payables_rowids_params_arc = Arc::new(Mutex::new(vec![]));
+        let expected_wallet = make_wallet("paying_you");
+        let expected_hash = H256::from("transaction_hash".keccak256());
+        let expected_rowid = 45623;
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .fingerprints_rowids_params(&fingerprints_rowids_params_arc)
+            .fingerprints_rowids_result(TransactionHashes {
+                rowid_results: vec![(expected_rowid, expected_hash)],
+                no_rowid_results: vec![],
+            });
+        let payable_dao = PayableDaoMock::new()
+            .mark_pending_payables_rowids_params(&mark_pending_payables_rowids_params_arc)
+            .mark_pending_payables_rowids_result(Ok(()));
+        let system = System::new("accountant_calls_payable_dao_to_mark_pending_payable");
+        let accountant = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("some_wallet_address")))
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(pending_payable_dao)])
+            .build();
+        let expected_payable = PendingPayable::new(expected_wallet.clone(), expected_hash.clone());
+        let sent_payable = SentPayables {
+            payment_procedure_result: Ok(vec![ProcessedPayableFallible::Correct(
+                expected_payable.clone(),
+            )]),
+            response_skeleton_opt: None,
+        };
+        let subject = accountant.start();
+
+        subject
+            .try_send(sent_payable)
+            .expect("unexpected actix error");
+
+        System::current().stop();
+        system.run();
+        let fingerprints_rowids_params = fingerprints_rowids_params_arc.lock().unwrap();
+        assert_eq!(*fingerprints_rowids_params, vec![vec![expected_hash]]);
+        let mark_pending_payables_rowids_params =
+            mark_pending_payables_rowids_params_arc.lock().unwrap();
+        assert_eq!(
+            *mark_pending_payables_rowids_params,
+            vec![vec![(expected_wallet, expected_rowid)]]
+        );
+    }
+
+    #[test]
+    fn accountant_sends_initial_payable_payments_msg_when_qualified_payable_found() {
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let now = SystemTime::now();
+        let payment_thresholds = PaymentThresholds::default();
+        let (qualified_payables, _, all_non_pending_payables) =
+            make_payables(now, &payment_thresholds);
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let system = System::new(
+            "accountant_sends_initial_payable_payments_msg_when_qualified_payable_found",
+        );
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("some_wallet_address")))
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        subject.scanners.pending_payable = Box::new(NullScanner::new());
+        subject.scanners.receivable = Box::new(NullScanner::new());
+        let accountant_addr = subject.start();
+        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        send_bind_message!(accountant_subs, peer_actors);
+
+        send_start_message!(accountant_subs);
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recorder = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recorder.len(), 1);
+        let message = blockchain_bridge_recorder.get_record::<QualifiedPayablesMessage>(0);
+        assert_eq!(
+            message,
+            &QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(qualified_payables),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId(1),
+                response_skeleton_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn accountant_requests_blockchain_bridge_to_scan_for_received_payments() {
+        init_test_logging();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let earning_wallet = make_wallet("someearningwallet");
+        let system =
+            System::new("accountant_requests_blockchain_bridge_to_scan_for_received_payments");
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        subject.scanners.pending_payable = Box::new(NullScanner::new());
+        subject.scanners.payable = Box::new(NullScanner::new());
+        let accountant_addr = subject.start();
+        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        send_bind_message!(accountant_subs, peer_actors);
+
+        send_start_message!(accountant_subs);
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recorder = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recorder.len(), 1);
+        let retrieve_transactions_msg =
+            blockchain_bridge_recorder.get_record::<RetrieveTransactions>(0);
+        assert_eq!(
+            retrieve_transactions_msg,
+            &RetrieveTransactions {
+                recipients: vec![earning_wallet.clone()],
+                response_skeleton_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn accountant_processes_msg_with_received_payments_using_receivables_dao_and_then_updates_start_block(
+    ) {
+        let more_money_received_params_arc = Arc::new(Mutex::new(vec![]));
+        let commit_params_arc = Arc::new(Mutex::new(vec![]));
+        let get_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_by_guest_transaction_params_arc = Arc::new(Mutex::new(vec![]));
+        let now = SystemTime::now();
+        let earning_wallet = make_wallet("earner3000");
+        let expected_receivable_1 = BlockchainTransaction {
+            block_number: 7,
+            from: make_wallet("wallet0"),
+            wei_amount: 456,
+        };
+        let expected_receivable_2 = BlockchainTransaction {
+            block_number: 13,
+            from: make_wallet("wallet1"),
+            wei_amount: 10000,
+        };
+        let transaction_id = ArbitraryIdStamp::new();
+        let txn_inner_builder = TransactionInnerWrapperMockBuilder::default()
+            .commit_params(&commit_params_arc)
+            .commit_result(Ok(()))
+            .set_arbitrary_id_stamp(transaction_id);
+        let wrapped_transaction = TransactionSafeWrapper::new_with_builder(txn_inner_builder);
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_received_params(&more_money_received_params_arc)
+            .more_money_received_result(wrapped_transaction);
+        let config_dao = ConfigDaoMock::new()
+            .get_params(&get_params_arc)
+            .get_result(Ok(ConfigDaoRecord::new("start_block", None, false)))
+            .set_by_guest_transaction_params(&set_by_guest_transaction_params_arc)
+            .set_by_guest_transaction_result(Ok(()));
+        let accountant = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .config_dao(config_dao)
+            .build();
+        let system = System::new("accountant_uses_receivables_dao_to_process_received_payments");
+        let subject = accountant.start();
+        subject
+            .try_send(ReceivedPayments {
+                timestamp: now,
+                new_start_block: BlockMarker::Value(123456789u64),
+                response_skeleton_opt: None,
+                transactions: vec![expected_receivable_1.clone(), expected_receivable_2.clone()],
+                rechecked_opt: None,
+                pending: vec![],
+            })
+            .expect("unexpected actix error");
+
+        System::current().stop();
+        system.run();
+        let more_money_received_params = more_money_received_params_arc.lock().unwrap();
+        assert_eq!(
+            *more_money_received_params,
+            vec![(now, vec![expected_receivable_1, expected_receivable_2])]
+        );
+        let commit_params = commit_params_arc.lock().unwrap();
+        assert_eq!(*commit_params, vec![()]);
+        let set_by_guest_transaction_params = set_by_guest_transaction_params_arc.lock().unwrap();
+        assert_eq!(
+            *set_by_guest_transaction_params,
+            vec![(
+                transaction_id,
+                "start_block".to_string(),
+                Some("123456789".to_string())
+            )]
+        )
+    }
+
+    #[test]
+    fn accountant_scans_after_startup() {
+        init_test_logging();
+        let pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let new_delinquencies_params_arc = Arc::new(Mutex::new(vec![]));
+        let paid_delinquencies_params_arc = Arc::new(Mutex::new(vec![]));
+        let (blockchain_bridge, _, _) = make_recorder();
+        let earning_wallet = make_wallet("earning");
+        let system = System::new("accountant_scans_after_startup");
+        let config = bc_from_wallets(make_wallet("buy"), earning_wallet.clone());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_params(&payable_params_arc)
+            .non_pending_payables_result(vec![]);
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_all_errorless_fingerprints_params(&pending_payable_params_arc)
+            .return_all_errorless_fingerprints_result(vec![]);
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_parameters(&new_delinquencies_params_arc)
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_parameters(&paid_delinquencies_params_arc)
+            .paid_delinquencies_result(vec![]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        let subject_addr: Addr<Accountant> = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        send_bind_message!(subject_subs, peer_actors);
+
+        send_start_message!(subject_subs);
+
+        System::current().stop();
+        system.run();
+        let payable_params = payable_params_arc.lock().unwrap();
+        let pending_payable_params = pending_payable_params_arc.lock().unwrap();
+        //proof of calling pieces of scan_for_delinquencies()
+        let mut new_delinquencies_params = new_delinquencies_params_arc.lock().unwrap();
+        let (captured_timestamp, captured_curves) = new_delinquencies_params.remove(0);
+        let paid_delinquencies_params = paid_delinquencies_params_arc.lock().unwrap();
+        assert_eq!(*payable_params, vec![()]);
+        assert_eq!(*pending_payable_params, vec![()]);
+        assert!(new_delinquencies_params.is_empty());
+        assert!(
+            captured_timestamp < SystemTime::now()
+                && captured_timestamp >= from_time_t(to_time_t(SystemTime::now()) - 5)
+        );
+        assert_eq!(captured_curves, PaymentThresholds::default());
+        assert_eq!(paid_delinquencies_params.len(), 1);
+        assert_eq!(paid_delinquencies_params[0], PaymentThresholds::default());
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing("INFO: Accountant[scan_id=1]: Scanning for payables");
+        tlh.exists_log_containing("INFO: Accountant: Scanning for pending payable");
+        tlh.exists_log_containing(&format!(
+            "INFO: Accountant: Scanning for receivables to {}",
+            earning_wallet
+        ));
+        tlh.exists_log_containing("INFO: Accountant: Scanning for delinquencies");
+    }
+
+    #[test]
+    fn periodical_scanning_for_receivables_and_delinquencies_works() {
+        init_test_logging();
+        let test_name = "periodical_scanning_for_receivables_and_delinquencies_works";
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let notify_later_receivable_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new(test_name);
+        SystemKillerActor::new(Duration::from_secs(10)).start(); // a safety net for GitHub Actions
+        let receivable_scanner = ScannerMock::new()
+            .begin_scan_params(&begin_scan_params_arc)
+            .begin_scan_result(Err(BeginScanError::NothingToProcess))
+            .begin_scan_result(Ok(RetrieveTransactions {
+                recipients: vec![make_wallet("some_recipient")],
+                response_skeleton_opt: None,
+            }))
+            .stop_the_system_after_last_msg();
+        let earning_wallet = make_wallet("earning");
+        let mut config = bc_from_earning_wallet(earning_wallet.clone());
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_secs(100),
+            receivable_scan_interval: Duration::from_millis(99),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.payable = Box::new(NullScanner::new()); // Skipping
+        subject.scanners.pending_payable = Box::new(NullScanner::new()); // Skipping
+        subject.scanners.receivable = Box::new(receivable_scanner);
+        subject.scan_schedulers.update_scheduler(
+            ScanType::Receivables,
+            Some(Box::new(
+                NotifyLaterHandleMock::default()
+                    .notify_later_params(&notify_later_receivable_params_arc)
+                    .capture_msg_and_let_it_fly_on(),
+            )),
+            None,
+        );
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        let peer_actors = peer_actors_builder().build();
+        send_bind_message!(subject_subs, peer_actors);
+
+        send_start_message!(subject_subs);
+
+        let time_before = SystemTime::now();
+        system.run();
+        let time_after = SystemTime::now();
+        let notify_later_receivable_params = notify_later_receivable_params_arc.lock().unwrap();
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: There was nothing to process during Receivables scan."
+        ));
+        let mut begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        let (
+            first_attempt_wallet,
+            first_attempt_timestamp,
+            first_attempt_response_skeleton_opt,
+            first_attempt_logger,
+        ) = begin_scan_params.remove(0);
+        let (
+            second_attempt_wallet,
+            second_attempt_timestamp,
+            second_attempt_response_skeleton_opt,
+            second_attempt_logger,
+        ) = begin_scan_params.remove(0);
+        assert_eq!(first_attempt_wallet, second_attempt_wallet);
+        assert_eq!(second_attempt_wallet, earning_wallet);
+        assert!(time_before <= first_attempt_timestamp);
+        assert!(first_attempt_timestamp <= second_attempt_timestamp);
+        assert!(second_attempt_timestamp <= time_after);
+        assert_eq!(first_attempt_response_skeleton_opt, None);
+        assert_eq!(second_attempt_response_skeleton_opt, None);
+        debug!(first_attempt_logger, "first attempt");
+        debug!(second_attempt_logger, "second attempt");
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!("DEBUG: {test_name}: first attempt"));
+        tlh.exists_log_containing(&format!("DEBUG: {test_name}: second attempt"));
+        assert_eq!(
+            *notify_later_receivable_params,
+            vec![
+                (
+                    ScanForReceivables {
+                        response_skeleton_opt: None
+                    },
+                    Duration::from_millis(99)
+                ),
+                (
+                    ScanForReceivables {
+                        response_skeleton_opt: None
+                    },
+                    Duration::from_millis(99)
+                ),
+            ]
+        )
+    }
+
+    #[test]
+    fn periodical_scanning_for_pending_payable_works() {
+        init_test_logging();
+        let test_name = "periodical_scanning_for_pending_payable_works";
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let notify_later_pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new(test_name);
+        SystemKillerActor::new(Duration::from_secs(10)).start(); // a safety net for GitHub Actions
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let pending_payable_scanner = ScannerMock::new()
+            .begin_scan_params(&begin_scan_params_arc)
+            .begin_scan_result(Err(BeginScanError::NothingToProcess))
+            .begin_scan_result(Ok(RequestTransactionReceipts {
+                pending_payable: vec![],
+                response_skeleton_opt: None,
+            }))
+            .stop_the_system_after_last_msg();
+        let mut config = make_bc_with_defaults();
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_secs(100),
+            receivable_scan_interval: Duration::from_secs(100),
+            pending_payable_scan_interval: Duration::from_millis(98),
+        });
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(consuming_wallet.clone())
+            .bootstrapper_config(config)
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.payable = Box::new(NullScanner::new()); //skipping
+        subject.scanners.pending_payable = Box::new(pending_payable_scanner);
+        subject.scanners.receivable = Box::new(NullScanner::new()); //skipping
+        subject.scan_schedulers.update_scheduler(
+            ScanType::PendingPayables,
+            Some(Box::new(
+                NotifyLaterHandleMock::default()
+                    .notify_later_params(&notify_later_pending_payable_params_arc)
+                    .capture_msg_and_let_it_fly_on(),
+            )),
+            None,
+        );
+        let subject_addr: Addr<Accountant> = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        let peer_actors = peer_actors_builder().build();
+        send_bind_message!(subject_subs, peer_actors);
+
+        send_start_message!(subject_subs);
+
+        let time_before = SystemTime::now();
+        system.run();
+        let time_after = SystemTime::now();
+        let notify_later_pending_payable_params =
+            notify_later_pending_payable_params_arc.lock().unwrap();
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: There was nothing to process during PendingPayables scan."
+        ));
+        let mut begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        let (
+            first_attempt_wallet,
+            first_attempt_timestamp,
+            first_attempt_response_skeleton_opt,
+            first_attempt_logger,
+        ) = begin_scan_params.remove(0);
+        let (
+            second_attempt_wallet,
+            second_attempt_timestamp,
+            second_attempt_response_skeleton_opt,
             second_attempt_logger,
         ) = begin_scan_params.remove(0);
         assert_eq!(first_attempt_wallet, second_attempt_wallet);
-        assert_eq!(second_attempt_wallet, earning_wallet);
+        assert_eq!(second_attempt_wallet, consuming_wallet);
+        assert!(time_before <= first_attempt_timestamp);
+        assert!(first_attempt_timestamp <= second_attempt_timestamp);
+        assert!(second_attempt_timestamp <= time_after);
+        assert_eq!(first_attempt_response_skeleton_opt, None);
+        assert_eq!(second_attempt_response_skeleton_opt, None);
+        debug!(first_attempt_logger, "first attempt");
+        debug!(second_attempt_logger, "second attempt");
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!("DEBUG: {test_name}: first attempt"));
+        tlh.exists_log_containing(&format!("DEBUG: {test_name}: second attempt"));
+        assert_eq!(
+            *notify_later_pending_payable_params,
+            vec![
+                (
+                    ScanForPendingPayables {
+                        response_skeleton_opt: None
+                    },
+                    Duration::from_millis(98)
+                ),
+                (
+                    ScanForPendingPayables {
+                        response_skeleton_opt: None
+                    },
+                    Duration::from_millis(98)
+                ),
+            ]
+        )
+    }
+
+    #[test]
+    fn periodical_scanning_for_payable_works() {
+        init_test_logging();
+        let test_name = "periodical_scanning_for_payable_works";
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let notify_later_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new(test_name);
+        SystemKillerActor::new(Duration::from_secs(10)).start(); // a safety net for GitHub Actions
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let payable_scanner = ScannerMock::new()
+            .begin_scan_params(&begin_scan_params_arc)
+            .begin_scan_result(Err(BeginScanError::NothingToProcess))
+            .begin_scan_result(Ok(QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![make_payable_account(
+                    123,
+                )]),
+                consuming_wallet: consuming_wallet.clone(),
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId::default(),
+                response_skeleton_opt: None,
+            }))
+            .stop_the_system_after_last_msg();
+        let mut config = bc_from_earning_wallet(make_wallet("hi"));
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(97),
+            receivable_scan_interval: Duration::from_secs(100), // We'll never run this scanner
+            pending_payable_scan_interval: Duration::from_secs(100), // We'll never run this scanner
+        });
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(consuming_wallet.clone())
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.payable = Box::new(payable_scanner);
+        subject.scanners.pending_payable = Box::new(NullScanner::new()); //skipping
+        subject.scanners.receivable = Box::new(NullScanner::new()); //skipping
+        subject.scan_schedulers.update_scheduler(
+            ScanType::Payables,
+            Some(Box::new(
+                NotifyLaterHandleMock::default()
+                    .notify_later_params(&notify_later_payables_params_arc)
+                    .capture_msg_and_let_it_fly_on(),
+            )),
+            None,
+        );
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        let peer_actors = peer_actors_builder().build();
+        send_bind_message!(subject_subs, peer_actors);
+
+        send_start_message!(subject_subs);
+
+        let time_before = SystemTime::now();
+        system.run();
+        let time_after = SystemTime::now();
+        //the second attempt is the one where the queue is empty and System::current.stop() ends the cycle
+        let notify_later_payables_params = notify_later_payables_params_arc.lock().unwrap();
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: There was nothing to process during Payables scan."
+        ));
+        let mut begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        let (
+            first_attempt_wallet,
+            first_attempt_timestamp,
+            first_attempt_response_skeleton_opt,
+            first_attempt_logger,
+        ) = begin_scan_params.remove(0);
+        let (
+            second_attempt_wallet,
+            second_attempt_timestamp,
+            second_attempt_response_skeleton_opt,
+            second_attempt_logger,
+        ) = begin_scan_params.remove(0);
+        assert_eq!(first_attempt_wallet, second_attempt_wallet);
+        assert_eq!(second_attempt_wallet, consuming_wallet);
         assert!(time_before <= first_attempt_timestamp);
         assert!(first_attempt_timestamp <= second_attempt_timestamp);
         assert!(second_attempt_timestamp <= time_after);
@@ -2217,1807 +6155,4945 @@ mod tests {
         debug!(first_attempt_logger, "first attempt");
         debug!(second_attempt_logger, "second attempt");
         let tlh = TestLogHandler::new();
-        tlh.exists_log_containing(&format!("DEBUG: {test_name}: first attempt"));
-        tlh.exists_log_containing(&format!("DEBUG: {test_name}: second attempt"));
+        tlh.exists_log_containing(&format!("DEBUG: {test_name}[scan_id=1]: first attempt"));
+        tlh.exists_log_containing(&format!("DEBUG: {test_name}[scan_id=2]: second attempt"));
+        assert_eq!(
+            *notify_later_payables_params,
+            vec![
+                (
+                    ScanForPayables {
+                        response_skeleton_opt: None
+                    },
+                    Duration::from_millis(97)
+                ),
+                (
+                    ScanForPayables {
+                        response_skeleton_opt: None
+                    },
+                    Duration::from_millis(97)
+                ),
+            ]
+        )
+    }
+
+    #[test]
+    fn payable_scan_only_observes_if_consuming_wallet_is_not_found() {
+        init_test_logging();
+        let test_name = "payable_scan_only_observes_if_consuming_wallet_is_not_found";
+        let now = SystemTime::now();
+        let (qualified_payables, _, all_non_pending_payables) =
+            make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .build();
+        subject.consuming_wallet_opt = None;
+        subject.logger = Logger::new(test_name);
+        subject.qualified_payables_sub_opt = Some(blockchain_bridge.start().recipient());
+        let system = System::new(test_name);
+
+        subject.handle_request_of_scan_for_payable(None);
+
+        System::current().stop();
+        system.run();
+        let has_scan_started = subject.scanners.payable.scan_started_at().is_some();
+        assert_eq!(has_scan_started, false);
+        assert_eq!(
+            subject.scanners.payable.observed_payables_opt(),
+            Some(ObservedPayables {
+                accounts: 2,
+                total_wei: qualified_payables
+                    .iter()
+                    .map(|account| account.balance_wei)
+                    .sum()
+            })
+        );
+        assert_eq!(blockchain_bridge_recording_arc.lock().unwrap().len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}[scan_id=1]: Observe-only mode, no consuming wallet to pay from: 2 \
+             qualified debts totaling"
+        ));
+    }
+
+    #[test]
+    fn payable_scan_leaves_observe_only_mode_once_consuming_wallet_is_found() {
+        let (qualified_payables, _, all_non_pending_payables) =
+            make_payables(SystemTime::now(), &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables.clone())
+            .creditor_thresholds_result(HashMap::new())
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        subject.consuming_wallet_opt = None;
+        subject.qualified_payables_sub_opt = Some(blockchain_bridge.start().recipient());
+        let system =
+            System::new("payable_scan_leaves_observe_only_mode_once_consuming_wallet_is_found");
+        subject.handle_request_of_scan_for_payable(None);
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        subject.consuming_wallet_opt = Some(consuming_wallet.clone());
+
+        subject.handle_request_of_scan_for_payable(None);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.scanners.payable.observed_payables_opt(), None);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        let message = blockchain_bridge_recording.get_record::<QualifiedPayablesMessage>(0);
+        assert_eq!(
+            message.protected_qualified_payables,
+            protect_payables_in_test(qualified_payables)
+        );
+        assert_eq!(message.consuming_wallet, consuming_wallet);
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+    }
+
+    #[test]
+    fn compute_financials_reveals_observe_only_mode_without_consuming_wallet() {
+        let payable_dao = PayableDaoMock::new()
+            .total_result(264_567_894_578)
+            .aging_result(DebtAging::default());
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result(987_654_328_996)
+            .aging_result(DebtAging::default());
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        subject.consuming_wallet_opt = None;
+        let mut payable_scanner = PayableScannerBuilder::new().build();
+        payable_scanner.mode = PayableScanMode::ObserveOnly;
+        payable_scanner.observed_payables_opt = Some(ObservedPayables {
+            accounts: 3,
+            total_wei: 4_567_000_000_123,
+        });
+        subject.scanners.payable = Box::new(payable_scanner);
+        let request = UiFinancialsRequest {
+            stats_required: true,
+            top_records_opt: None,
+            custom_queries_opt: None,
+        };
+
+        let result = subject.compute_financials(&request, 1234);
+
+        let (response, _) = UiFinancialsResponse::fmb(result).unwrap();
+        assert_eq!(
+            response.stats_opt.unwrap().observe_only_opt,
+            Some(UiObserveOnlyPayables {
+                would_be_paid_accounts: 3,
+                would_be_paid_gwei: 4_567
+            })
+        )
+    }
+
+    #[test]
+    fn compute_financials_reports_zeros_in_observe_only_mode_before_its_first_scan() {
+        let payable_dao = PayableDaoMock::new()
+            .total_result(0)
+            .aging_result(DebtAging::default());
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result(0)
+            .aging_result(DebtAging::default());
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        subject.consuming_wallet_opt = None;
+        let request = UiFinancialsRequest {
+            stats_required: true,
+            top_records_opt: None,
+            custom_queries_opt: None,
+        };
+
+        let result = subject.compute_financials(&request, 1234);
+
+        let (response, _) = UiFinancialsResponse::fmb(result).unwrap();
+        assert_eq!(
+            response.stats_opt.unwrap().observe_only_opt,
+            Some(UiObserveOnlyPayables {
+                would_be_paid_accounts: 0,
+                would_be_paid_gwei: 0
+            })
+        )
+    }
+
+    #[test]
+    fn pending_payable_scan_is_not_initiated_if_consuming_wallet_is_not_found() {
+        init_test_logging();
+        let test_name = "pending_payable_scan_is_not_initiated_if_consuming_wallet_is_not_found";
+        let mut subject = AccountantBuilder::default().build();
+        subject.consuming_wallet_opt = None;
+        subject.logger = Logger::new(test_name);
+
+        subject.handle_request_of_scan_for_pending_payable(None);
+
+        let has_scan_started = subject.scanners.pending_payable.scan_started_at().is_some();
+        assert_eq!(has_scan_started, false);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Cannot initiate PendingPayables scan because no consuming wallet was found."
+        ));
+    }
+
+    #[test]
+    fn start_message_reconciles_outbound_payments_left_unfinished_by_crash() {
+        init_test_logging();
+        let test_name = "start_message_reconciles_outbound_payments_left_unfinished_by_crash";
+        let remove_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new(test_name);
+        let mut config = bc_from_earning_wallet(make_wallet("hi"));
+        config.suppress_initial_scans = true;
+        let agent_snapshot = BlockchainAgentSnapshot {
+            consuming_wallet_address: "0x0000000000000000000000000000000000c0ffee".to_string(),
+            transaction_fee_balance_wei: 5_000,
+            service_fee_balance_wei: 2_500_000,
+            agreed_fee_per_computation_unit: 10,
+            chain_identifier: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+        };
+        let make_payment = |rowid: u64, amount_wei: u128, hash_opt, status| OutboundPayment {
+            rowid,
+            instructions_rowid: 1,
+            timestamp: SystemTime::now()
+                .checked_sub(Duration::from_secs(300))
+                .unwrap(),
+            agent_snapshot: agent_snapshot.clone(),
+            wallet_address: make_wallet(&format!("creditor{}", rowid)).to_string(),
+            amount_wei,
+            hash_opt,
+            status,
+        };
+        let outbound_payment_dao = OutboundPaymentDaoMock::new()
+            .unfinished_result(vec![
+                make_payment(1, 1_000, None, OutboundPaymentStatus::Queued),
+                make_payment(
+                    2,
+                    2_000,
+                    Some(make_tx_hash(2)),
+                    OutboundPaymentStatus::Submitted,
+                ),
+                make_payment(
+                    3,
+                    3_000,
+                    Some(make_tx_hash(3)),
+                    OutboundPaymentStatus::Submitted,
+                ),
+            ])
+            .remove_params(&remove_params_arc)
+            .remove_result(Ok(()));
+        let still_pending_fingerprint = PendingPayableFingerprint {
+            hash: make_tx_hash(3),
+            ..make_pending_payable_fingerprint()
+        };
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .return_all_errorless_fingerprints_result(vec![still_pending_fingerprint.clone()])
+            .return_all_errorless_fingerprints_result(vec![still_pending_fingerprint]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .outbound_payment_dao(outbound_payment_dao)
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        send_bind_message!(subject_subs, peer_actors_builder().build());
+
+        send_start_message!(subject_subs);
+
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(|actor: &mut Accountant| {
+                    assert_eq!(
+                        actor.submitted_outbound_payments,
+                        HashSet::from([make_tx_hash(3)])
+                    );
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+        let remove_params = remove_params_arc.lock().unwrap();
+        assert_eq!(*remove_params, vec![vec![1, 2]]);
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_matching(&format!(
+            "WARN: {test_name}: Payment of 1000 wei to {} from consuming wallet \
+             0x0000000000000000000000000000000000c0ffee, handed over to the BlockchainBridge 30\\d \
+             sec ago, was never reported as sent before the Node went down; the debt remains on \
+             record for the next payable scan",
+            make_wallet("creditor1")
+        ));
+        log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Dropping 1 outbound payments from the queue as their \
+             transactions are no longer pending"
+        ));
+    }
+
+    #[test]
+    fn start_message_proposes_repairs_of_anomalies_left_by_crash() {
+        init_test_logging();
+        let test_name = "start_message_proposes_repairs_of_anomalies_left_by_crash";
+        let system = System::new(test_name);
+        let mut config = bc_from_earning_wallet(make_wallet("hi"));
+        config.suppress_initial_scans = true;
+        let dangling_payable = Anomaly::DanglingPayable {
+            wallet: make_wallet("creditor"),
+            balance_wei: 1_000_000,
+            pending_payable_rowid: 7,
+        };
+        let consistency_dao =
+            ConsistencyDaoMock::new().anomalies_result(vec![dangling_payable.clone()]);
+        let pending_payable_dao =
+            PendingPayableDaoMock::new().return_all_errorless_fingerprints_result(vec![]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consistency_dao(consistency_dao)
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        send_bind_message!(subject_subs, peer_actors_builder().build());
+
+        send_start_message!(subject_subs);
+
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Inconsistent accounts: {}; proposed repair: Release the payable \
+             so that the next payable scan pays it again",
+            dangling_payable
+        ));
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: No repair is made until repair plan 1 is confirmed with \
+             repairConsistency"
+        ));
+    }
+
+    #[test]
+    fn start_message_triggers_no_scans_in_suppress_mode() {
+        init_test_logging();
+        let test_name = "start_message_triggers_no_scans_in_suppress_mode";
+        let system = System::new(test_name);
+        let mut config = bc_from_earning_wallet(make_wallet("hi"));
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_millis(100),
+            receivable_scan_interval: Duration::from_millis(100),
+            pending_payable_scan_interval: Duration::from_millis(100),
+        });
+        config.suppress_initial_scans = true;
+        let peer_actors = peer_actors_builder().build();
+        let pending_payable_dao =
+            PendingPayableDaoMock::new().return_all_errorless_fingerprints_result(vec![]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        send_bind_message!(subject_subs, peer_actors);
+
+        send_start_message!(subject_subs);
+
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+        // no panics because of recalcitrant DAOs; therefore other DAOs were not called; therefore test passes
+        TestLogHandler::new().exists_log_containing(
+            &format!("{test_name}: Started with --scans off; declining to begin database and blockchain scans"),
+        );
+    }
+
+    #[test]
+    fn start_message_checks_receipts_left_in_flight_even_in_suppress_mode() {
+        init_test_logging();
+        let test_name = "start_message_checks_receipts_left_in_flight_even_in_suppress_mode";
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let mut config = bc_from_earning_wallet(make_wallet("hi"));
+        config.suppress_initial_scans = true;
+        let fingerprint = make_pending_payable_fingerprint();
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .return_all_errorless_fingerprints_result(vec![fingerprint.clone()]);
+        let request_transaction_receipts = RequestTransactionReceipts {
+            pending_payable: vec![fingerprint],
+            response_skeleton_opt: None,
+        };
+        let pending_payable_scanner = ScannerMock::new()
+            .begin_scan_params(&begin_scan_params_arc)
+            .begin_scan_result(Ok(request_transaction_receipts.clone()));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.pending_payable = Box::new(pending_payable_scanner);
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        send_bind_message!(subject_subs, peer_actors);
+
+        send_start_message!(subject_subs);
+
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+        let begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        assert_eq!(begin_scan_params.len(), 1);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(0),
+            &request_transaction_receipts
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: 1 transactions were still pending when the Node went down; \
+             checking their receipts now"
+        ));
+    }
+
+    #[test]
+    fn shutdown_barrier_is_passed_on_to_the_neighborhood() {
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let system = System::new("shutdown_barrier_is_passed_on_to_the_neighborhood");
+        let subject = AccountantBuilder::default().build();
+        let subject_addr = subject.start();
+        let subject_subs = Accountant::make_subs_from(&subject_addr);
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        send_bind_message!(subject_subs, peer_actors);
+
+        subject_subs
+            .shutdown_barrier
+            .try_send(ShutdownBarrierMessage { client_id: 1234 })
+            .unwrap();
+
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 1);
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownBarrierMessage>(0),
+            &ShutdownBarrierMessage { client_id: 1234 }
+        );
+    }
+
+    #[test]
+    fn scan_for_payables_message_does_not_trigger_payment_for_balances_below_the_curve() {
+        init_test_logging();
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let payment_thresholds = PaymentThresholds {
+            threshold_interval_sec: 2_592_000,
+            debt_threshold_gwei: 1_000_000_000,
+            payment_grace_period_sec: 86_400,
+            maturity_threshold_sec: 86_400,
+            permanent_debt_allowed_gwei: 10_000_000,
+            unban_below_gwei: 10_000_000,
+        };
+        let config = bc_from_earning_wallet(make_wallet("mine"));
+        let now = to_time_t(SystemTime::now());
+        let accounts = vec![
+            // below minimum balance, to the right of time intersection (inside buffer zone)
+            PayableAccount {
+                wallet: make_wallet("wallet0"),
+                balance_wei: gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei - 1),
+                last_paid_timestamp: from_time_t(
+                    now - checked_conversion::<u64, i64>(
+                        payment_thresholds.threshold_interval_sec + 10,
+                    ),
+                ),
+                pending_payable_opt: None,
+            },
+            // above balance intersection, to the left of minimum time (outside buffer zone)
+            PayableAccount {
+                wallet: make_wallet("wallet1"),
+                balance_wei: gwei_to_wei(payment_thresholds.debt_threshold_gwei + 1),
+                last_paid_timestamp: from_time_t(
+                    now - checked_conversion::<u64, i64>(
+                        payment_thresholds.maturity_threshold_sec - 10,
+                    ),
+                ),
+                pending_payable_opt: None,
+            },
+            // above minimum balance, to the right of minimum time (not in buffer zone, below the curve)
+            PayableAccount {
+                wallet: make_wallet("wallet2"),
+                balance_wei: gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei + 55),
+                last_paid_timestamp: from_time_t(
+                    now - checked_conversion::<u64, i64>(
+                        payment_thresholds.maturity_threshold_sec + 15,
+                    ),
+                ),
+                pending_payable_opt: None,
+            },
+        ];
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(accounts.clone())
+            .creditor_thresholds_result(HashMap::new())
+            .non_pending_payables_result(vec![]);
+        let (blockchain_bridge, _, blockchain_bridge_recordings_arc) = make_recorder();
+        let system = System::new(
+            "scan_for_payable_message_does_not_trigger_payment_for_balances_below_the_curve",
+        );
+        let blockchain_bridge_addr: Addr<Recorder> = blockchain_bridge.start();
+        let outbound_payments_instructions_sub =
+            blockchain_bridge_addr.recipient::<OutboundPaymentsInstructions>();
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .build();
+        subject.outbound_payments_instructions_sub_opt = Some(outbound_payments_instructions_sub);
+
+        let _result = subject.scanners.payable.begin_scan(
+            consuming_wallet,
+            SystemTime::now(),
+            None,
+            &subject.logger,
+        );
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recordings = blockchain_bridge_recordings_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recordings.len(), 0);
+    }
+
+    #[test]
+    fn scan_for_payable_message_triggers_payment_for_balances_over_the_curve() {
+        init_test_logging();
+        let mut config = bc_from_earning_wallet(make_wallet("mine"));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        config.scan_intervals_opt = Some(ScanIntervals {
+            pending_payable_scan_interval: Duration::from_secs(50_000),
+            payable_scan_interval: Duration::from_secs(50_000),
+            receivable_scan_interval: Duration::from_secs(50_000),
+        });
+        let now = to_time_t(SystemTime::now());
+        let qualified_payables = vec![
+            // slightly above minimum balance, to the right of the curve (time intersection)
+            PayableAccount {
+                wallet: make_wallet("wallet0"),
+                balance_wei: gwei_to_wei(
+                    DEFAULT_PAYMENT_THRESHOLDS.permanent_debt_allowed_gwei + 1,
+                ),
+                last_paid_timestamp: from_time_t(
+                    now - checked_conversion::<u64, i64>(
+                        DEFAULT_PAYMENT_THRESHOLDS.threshold_interval_sec
+                            + DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec
+                            + 10,
+                    ),
+                ),
+                pending_payable_opt: None,
+            },
+            // slightly above the curve (balance intersection), to the right of minimum time
+            PayableAccount {
+                wallet: make_wallet("wallet1"),
+                balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
+                last_paid_timestamp: from_time_t(
+                    now - checked_conversion::<u64, i64>(
+                        DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 10,
+                    ),
+                ),
+                pending_payable_opt: None,
+            },
+        ];
+        let payable_dao = PayableDaoMock::default()
+            .non_pending_payables_result(qualified_payables.clone())
+            .creditor_thresholds_result(HashMap::new());
+        let (blockchain_bridge, _, blockchain_bridge_recordings_arc) = make_recorder();
+        let blockchain_bridge = blockchain_bridge
+            .system_stop_conditions(match_every_type_id!(QualifiedPayablesMessage));
+        let system =
+            System::new("scan_for_payable_message_triggers_payment_for_balances_over_the_curve");
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .build();
+        subject.scanners.pending_payable = Box::new(NullScanner::new());
+        subject.scanners.receivable = Box::new(NullScanner::new());
+        let subject_addr = subject.start();
+        let accountant_subs = Accountant::make_subs_from(&subject_addr);
+        send_bind_message!(accountant_subs, peer_actors);
+
+        send_start_message!(accountant_subs);
+
+        system.run();
+        let blockchain_bridge_recordings = blockchain_bridge_recordings_arc.lock().unwrap();
+        let message = blockchain_bridge_recordings.get_record::<QualifiedPayablesMessage>(0);
+        assert_eq!(
+            message,
+            &QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(qualified_payables),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId(1),
+                response_skeleton_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn scan_for_payables_with_scan_pipelining_checks_receipts_alongside_the_payable_scan() {
+        let mut config = bc_from_earning_wallet(make_wallet("mine"));
+        config.scan_pipelining = true;
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let last_paid_timestamp = to_time_t(SystemTime::now())
+            - DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec as i64
+            - 1;
+        let payable_account = PayableAccount {
+            wallet: make_wallet("creditor"),
+            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
+            last_paid_timestamp: from_time_t(last_paid_timestamp),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::default()
+            .non_pending_payables_result(vec![payable_account.clone()])
+            .creditor_thresholds_result(HashMap::new());
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 555,
+            timestamp: from_time_t(210_000_000),
+            hash: make_tx_hash(45678),
+            attempt: 1,
+            amount: 4444,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_all_errorless_fingerprints_result(vec![fingerprint.clone()]);
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let blockchain_bridge_addr = blockchain_bridge
+            .system_stop_conditions(match_every_type_id!(
+                RequestTransactionReceipts,
+                QualifiedPayablesMessage
+            ))
+            .start();
+        let system = System::new(
+            "scan_for_payables_with_scan_pipelining_checks_receipts_alongside_the_payable_scan",
+        );
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![
+                ForPayableScanner(
+                    PendingPayableDaoMock::new()
+                        .recipients_of_errorless_fingerprints_result(HashSet::new()),
+                ),
+                ForPendingPayableScanner(pending_payable_dao),
+            ])
+            .build();
+        subject.request_transaction_receipts_subs_opt =
+            Some(blockchain_bridge_addr.clone().recipient());
+        subject.qualified_payables_sub_opt = Some(blockchain_bridge_addr.recipient());
+        let subject_addr = subject.start();
+
+        subject_addr
+            .try_send(ScanForPayables {
+                response_skeleton_opt: None,
+            })
+            .unwrap();
+
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 2);
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(0),
+            &RequestTransactionReceipts {
+                pending_payable: vec![fingerprint],
+                response_skeleton_opt: None,
+            }
+        );
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<QualifiedPayablesMessage>(1),
+            &QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![payable_account]),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId(1),
+                response_skeleton_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn pipelined_receipt_check_waits_until_the_running_payable_scan_gets_its_sent_payables() {
+        init_test_logging();
+        let test_name =
+            "pipelined_receipt_check_waits_until_the_running_payable_scan_gets_its_sent_payables";
+        let mut config = bc_from_earning_wallet(make_wallet("mine"));
+        config.scan_pipelining = true;
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+
+        // Unbound BlockchainBridge; any receipt request would panic
+        subject.check_receipts_alongside_payable_scan();
+
+        assert_eq!(subject.scanners.pending_payable.scan_started_at(), None);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Skipping the pipelined receipt check; the running payable scan \
+             still awaits its sent payables"
+        ));
+    }
+
+    #[test]
+    fn accountant_does_not_initiate_another_scan_if_one_is_already_running() {
+        init_test_logging();
+        let test_name = "accountant_does_not_initiate_another_scan_if_one_is_already_running";
+        let payable_dao = PayableDaoMock::default();
+        let (blockchain_bridge, _, blockchain_bridge_recording) = make_recorder();
+        let blockchain_bridge_addr = blockchain_bridge
+            .system_stop_conditions(match_every_type_id!(
+                QualifiedPayablesMessage,
+                QualifiedPayablesMessage
+            ))
+            .start();
+        let pps_for_blockchain_bridge_sub = blockchain_bridge_addr.clone().recipient();
+        let last_paid_timestamp = to_time_t(SystemTime::now())
+            - DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec as i64
+            - 1;
+        let payable_account = PayableAccount {
+            wallet: make_wallet("scan_for_payables"),
+            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
+            last_paid_timestamp: from_time_t(last_paid_timestamp),
+            pending_payable_opt: None,
+        };
+        let payable_dao = payable_dao
+            .non_pending_payables_result(vec![payable_account.clone()])
+            .creditor_thresholds_result(HashMap::new())
+            .non_pending_payables_result(vec![payable_account])
+            .creditor_thresholds_result(HashMap::new());
+        let config = bc_from_earning_wallet(make_wallet("mine"));
+        let system = System::new(test_name);
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .logger(Logger::new(test_name))
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPayableScanner(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new())
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )])
+            .bootstrapper_config(config)
+            .build();
+        let message_before = ScanForPayables {
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 111,
+                context_id: 222,
+            }),
+        };
+        let message_after = ScanForPayables {
+            response_skeleton_opt: Some(ResponseSkeleton {
+                client_id: 333,
+                context_id: 444,
+            }),
+        };
+        subject.qualified_payables_sub_opt = Some(pps_for_blockchain_bridge_sub);
+        let addr = subject.start();
+        addr.try_send(message_before.clone()).unwrap();
+
+        addr.try_send(ScanForPayables {
+            response_skeleton_opt: None,
+        })
+        .unwrap();
+
+        // We ignored the second ScanForPayables message because the first message meant a scan
+        // was already in progress; now let's make it look like that scan has ended so that we
+        // can prove the next message will start another one.
+        addr.try_send(AssertionsMessage {
+            assertions: Box::new(|accountant: &mut Accountant| {
+                accountant
+                    .scanners
+                    .payable
+                    .mark_as_ended(&Logger::new("irrelevant"))
+            }),
+        })
+        .unwrap();
+        addr.try_send(message_after.clone()).unwrap();
+        system.run();
+        let recording = blockchain_bridge_recording.lock().unwrap();
+        let messages_received = recording.len();
+        assert_eq!(messages_received, 2);
+        let first_message: &QualifiedPayablesMessage = recording.get_record(0);
+        assert_eq!(
+            first_message.response_skeleton_opt,
+            message_before.response_skeleton_opt
+        );
+        let second_message: &QualifiedPayablesMessage = recording.get_record(1);
+        assert_eq!(
+            second_message.response_skeleton_opt,
+            message_after.response_skeleton_opt
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {}: Payables scan was already initiated",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn scan_for_pending_payables_finds_still_pending_payables() {
+        init_test_logging();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let blockchain_bridge_addr = blockchain_bridge
+            .system_stop_conditions(match_every_type_id!(RequestTransactionReceipts))
+            .start();
+        let payable_fingerprint_1 = PendingPayableFingerprint {
+            rowid: 555,
+            timestamp: from_time_t(210_000_000),
+            hash: make_tx_hash(45678),
+            attempt: 1,
+            amount: 4444,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let payable_fingerprint_2 = PendingPayableFingerprint {
+            rowid: 550,
+            timestamp: from_time_t(210_000_100),
+            hash: make_tx_hash(112233),
+            attempt: 2,
+            amount: 7999,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_all_errorless_fingerprints_result(vec![
+                payable_fingerprint_1.clone(),
+                payable_fingerprint_2.clone(),
+            ]);
+        let config = bc_from_earning_wallet(make_wallet("mine"));
+        let system = System::new("pending payable scan");
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .bootstrapper_config(config)
+            .build();
+
+        subject.request_transaction_receipts_subs_opt = Some(blockchain_bridge_addr.recipient());
+        let account_addr = subject.start();
+
+        let _ = account_addr
+            .try_send(ScanForPendingPayables {
+                response_skeleton_opt: None,
+            })
+            .unwrap();
+
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+        let received_msg = blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(0);
+        assert_eq!(
+            received_msg,
+            &RequestTransactionReceipts {
+                pending_payable: vec![payable_fingerprint_1, payable_fingerprint_2],
+                response_skeleton_opt: None,
+            }
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing("DEBUG: Accountant: Found 2 pending payables to process");
+    }
+
+    #[test]
+    fn report_routing_service_provided_message_is_received() {
+        init_test_logging();
+        let now = SystemTime::now();
+        let bootstrapper_config = bc_from_earning_wallet(make_wallet("hi"));
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc)
+            .more_money_receivable_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(bootstrapper_config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+        let system = System::new("report_routing_service_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        let paying_wallet = make_wallet("booga");
+        subject_addr
+            .try_send(ReportRoutingServiceProvidedMessage {
+                timestamp: now,
+                paying_wallet: paying_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            })
+            .unwrap();
+
+        System::current().stop_with_code(0);
+        system.run();
+        let more_money_receivable_parameters = more_money_receivable_parameters_arc.lock().unwrap();
+        assert_eq!(
+            more_money_receivable_parameters[0],
+            (now, make_wallet("booga"), (1 * 42) + (1234 * 24))
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: Accountant: Charging routing of 1234 bytes to wallet {}",
+            paying_wallet
+        ));
+    }
+
+    #[test]
+    fn report_routing_service_provided_message_is_received_from_our_consuming_wallet() {
+        init_test_logging();
+        let consuming_wallet = make_wallet("our consuming wallet");
+        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("our earning wallet"));
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+        let system = System::new("report_routing_service_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(ReportRoutingServiceProvidedMessage {
+                timestamp: SystemTime::now(),
+                paying_wallet: consuming_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            })
+            .unwrap();
+
+        System::current().stop_with_code(0);
+        system.run();
+        assert!(more_money_receivable_parameters_arc
+            .lock()
+            .unwrap()
+            .is_empty());
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
+            consuming_wallet,
+        ));
+    }
+
+    #[test]
+    fn report_routing_service_provided_message_is_received_from_our_earning_wallet() {
+        init_test_logging();
+        let earning_wallet = make_wallet("our earning wallet");
+        let config = bc_from_earning_wallet(earning_wallet.clone());
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+        let system = System::new("report_routing_service_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(ReportRoutingServiceProvidedMessage {
+                timestamp: SystemTime::now(),
+                paying_wallet: earning_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            })
+            .unwrap();
+
+        System::current().stop_with_code(0);
+        system.run();
+        assert!(more_money_receivable_parameters_arc
+            .lock()
+            .unwrap()
+            .is_empty());
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
+            earning_wallet,
+        ));
+    }
+
+    #[test]
+    fn report_routing_service_provided_message_is_received_from_our_other_earning_wallet() {
+        init_test_logging();
+        let earning_wallet = make_wallet("our earning wallet");
+        let other_earning_wallet = make_wallet("our other earning wallet");
+        let mut config = bc_from_earning_wallet(earning_wallet.clone());
+        config.earning_wallet_rotation = vec![earning_wallet, other_earning_wallet.clone()];
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+
+        subject.handle_report_routing_service_provided_message(
+            ReportRoutingServiceProvidedMessage {
+                timestamp: SystemTime::now(),
+                paying_wallet: other_earning_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            },
+        );
+
+        assert!(more_money_receivable_parameters_arc
+            .lock()
+            .unwrap()
+            .is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
+            other_earning_wallet,
+        ));
+    }
+
+    #[test]
+    fn report_exit_service_provided_message_is_received() {
+        init_test_logging();
+        let now = SystemTime::now();
+        let config = bc_from_earning_wallet(make_wallet("hi"));
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc)
+            .more_money_receivable_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+        let system = System::new("report_exit_service_provided_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        let paying_wallet = make_wallet("booga");
+        subject_addr
+            .try_send(ReportExitServiceProvidedMessage {
+                timestamp: now,
+                paying_wallet: paying_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let more_money_receivable_parameters = more_money_receivable_parameters_arc.lock().unwrap();
+        assert_eq!(
+            more_money_receivable_parameters[0],
+            (now, make_wallet("booga"), (1 * 42) + (1234 * 24))
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: Accountant: Charging exit service for 1234 bytes to wallet {}",
+            paying_wallet
+        ));
+    }
+
+    #[test]
+    fn report_exit_service_provided_message_is_received_from_our_consuming_wallet() {
+        init_test_logging();
+        let consuming_wallet = make_wallet("my consuming wallet");
+        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("my earning wallet"));
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+        let system = System::new("report_exit_service_provided_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(ReportExitServiceProvidedMessage {
+                timestamp: SystemTime::now(),
+                paying_wallet: consuming_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        assert!(more_money_receivable_parameters_arc
+            .lock()
+            .unwrap()
+            .is_empty());
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
+            consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn report_exit_service_provided_message_is_received_from_our_earning_wallet() {
+        init_test_logging();
+        let earning_wallet = make_wallet("my earning wallet");
+        let config = bc_from_earning_wallet(earning_wallet.clone());
+        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let receivable_dao_mock = ReceivableDaoMock::new()
+            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .build();
+        let system = System::new("report_exit_service_provided_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(ReportExitServiceProvidedMessage {
+                timestamp: SystemTime::now(),
+                paying_wallet: earning_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        assert!(more_money_receivable_parameters_arc
+            .lock()
+            .unwrap()
+            .is_empty());
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
+            earning_wallet,
+        ));
+    }
+
+    #[test]
+    fn report_creditor_thresholds_message_is_saved_per_creditor() {
+        init_test_logging();
+        let test_name = "report_creditor_thresholds_message_is_saved_per_creditor";
+        let upsert_creditor_thresholds_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .upsert_creditor_thresholds_params(&upsert_creditor_thresholds_params_arc)
+            .upsert_creditor_thresholds_result(Ok(()))
+            .upsert_creditor_thresholds_result(Err(PayableDaoError::SignConversion(
+                u64::MAX as u128,
+            )));
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        let system = System::new(test_name);
+        let subject_addr: Addr<Accountant> = subject.start();
+        let creditor_1 = make_wallet("creditor 1");
+        let creditor_2 = make_wallet("creditor 2");
+        let bad_payment_thresholds = PaymentThresholds {
+            unban_below_gwei: u64::MAX,
+            ..PaymentThresholds::default()
+        };
+
+        subject_addr
+            .try_send(ReportCreditorThresholdsMessage {
+                creditor_thresholds: vec![
+                    (creditor_1.clone(), make_custom_payment_thresholds()),
+                    (creditor_2.clone(), bad_payment_thresholds),
+                ],
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let upsert_creditor_thresholds_params =
+            upsert_creditor_thresholds_params_arc.lock().unwrap();
+        assert_eq!(
+            *upsert_creditor_thresholds_params,
+            vec![
+                (creditor_1.clone(), make_custom_payment_thresholds()),
+                (creditor_2.clone(), bad_payment_thresholds)
+            ]
+        );
+        let test_log_handler = TestLogHandler::new();
+        test_log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Creditor {} is to be paid by its own payment thresholds {:?}",
+            creditor_1,
+            make_custom_payment_thresholds()
+        ));
+        test_log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Payment thresholds advertised by creditor {} not saved: \
+            SignConversion(18446744073709551615)",
+            creditor_2
+        ));
+    }
+
+    #[test]
+    fn report_creditor_reachability_message_updates_what_the_payable_scanner_sees() {
+        init_test_logging();
+        let test_name =
+            "report_creditor_reachability_message_updates_what_the_payable_scanner_sees";
+        let subject = AccountantBuilder::default()
+            .logger(Logger::new(test_name))
+            .build();
+        let creditor_reachability = Rc::clone(&subject.creditor_reachability);
+        let system = System::new(test_name);
+        let subject_addr: Addr<Accountant> = subject.start();
+        let earlier = SystemTime::now().sub(Duration::from_secs(100));
+        let later = SystemTime::now();
+        let gone = make_wallet("gone");
+        let back = make_wallet("back");
+
+        subject_addr
+            .try_send(ReportCreditorReachabilityMessage {
+                timestamp: earlier,
+                reachable: vec![],
+                unreachable: vec![gone.clone(), back.clone()],
+            })
+            .unwrap();
+        subject_addr
+            .try_send(ReportCreditorReachabilityMessage {
+                timestamp: later,
+                reachable: vec![back.clone()],
+                unreachable: vec![gone.clone()],
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let creditor_reachability = creditor_reachability.borrow();
+        assert_eq!(
+            creditor_reachability.unreachable_since(&gone),
+            Some(earlier)
+        );
+        assert_eq!(creditor_reachability.unreachable_since(&back), None);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Neighborhood reports 1 creditors reachable and 1 unreachable"
+        ));
+    }
+
+    #[test]
+    fn report_exit_failover_message_tells_the_payable_scanner_whom_to_favor() {
+        init_test_logging();
+        let test_name = "report_exit_failover_message_tells_the_payable_scanner_whom_to_favor";
+        let subject = AccountantBuilder::default()
+            .logger(Logger::new(test_name))
+            .build();
+        let creditor_reachability = Rc::clone(&subject.creditor_reachability);
+        let system = System::new(test_name);
+        let subject_addr: Addr<Accountant> = subject.start();
+        let czech = make_wallet("czech");
+
+        subject_addr
+            .try_send(ReportExitFailoverMessage {
+                timestamp: SystemTime::now(),
+                exit_country: "CZ".to_string(),
+                creditors: vec![czech.clone()],
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            creditor_reachability.borrow_mut().take_exit_creditors(),
+            vec![czech].into_iter().collect::<HashSet<_>>()
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Neighborhood has selected exit country CZ; debts to its 1 \
+             creditors will be favored in the next payment adjustment"
+        ));
+    }
+
+    #[test]
+    fn accountant_gives_creditors_notice_of_confirmed_payments() {
+        let creditors_of_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let creditor_wallet = make_wallet("creditor");
+        let payable_dao = PayableDaoMock::new()
+            .creditors_of_pending_payables_params(&creditors_of_pending_payables_params_arc)
+            .creditors_of_pending_payables_result(HashMap::from([(5, creditor_wallet.clone())]));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_wallets(
+                consuming_wallet.clone(),
+                make_wallet("earning"),
+            ))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        subject.scanners.pending_payable = Box::new(ScannerMock::new().finish_scan_result(None));
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let system = System::new("accountant_gives_creditors_notice_of_confirmed_payments");
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let make_fingerprint = |rowid: u64, hash: H256, amount: u128| PendingPayableFingerprint {
+            rowid,
+            timestamp: from_time_t(200_000_000),
+            hash,
+            attempt: 1,
+            amount,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let succeeded = |hash: H256, block_number: u64| {
+            TransactionReceiptResult::RpcResponse(TxReceipt {
+                transaction_hash: hash,
+                status: TxStatus::Succeeded(TransactionBlock {
+                    block_hash: Default::default(),
+                    block_number: U64::from(block_number),
+                }),
+            })
+        };
+        let msg = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![
+                (
+                    succeeded(make_tx_hash(111), 1_234),
+                    make_fingerprint(5, make_tx_hash(111), 4_567_000_000),
+                ),
+                (
+                    TransactionReceiptResult::RpcResponse(TxReceipt {
+                        transaction_hash: make_tx_hash(222),
+                        status: TxStatus::Pending,
+                    }),
+                    make_fingerprint(6, make_tx_hash(222), 8_901_000_000),
+                ),
+                (
+                    succeeded(make_tx_hash(333), 1_235),
+                    make_fingerprint(7, make_tx_hash(333), 2_345_000_000),
+                ),
+            ],
+            response_skeleton_opt: None,
+        };
+
+        subject_addr.try_send(msg).unwrap();
+
+        System::current().stop();
+        system.run();
+        let creditors_of_pending_payables_params =
+            creditors_of_pending_payables_params_arc.lock().unwrap();
+        assert_eq!(*creditors_of_pending_payables_params, vec![vec![5, 7]]);
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        let notice = &neighborhood_recording
+            .get_record::<SendPaymentNoticeMessage>(0)
+            .notice;
+        assert_eq!(
+            notice,
+            &PaymentNotice_0v1::new(
+                &consuming_wallet,
+                &creditor_wallet,
+                make_tx_hash(111),
+                4_567_000_000,
+                1_234
+            )
+            .unwrap()
+        );
+        assert_eq!(notice.is_signed_by_payer(), true);
+        assert_eq!(neighborhood_recording.len(), 1);
+    }
+
+    #[test]
+    fn accountant_marks_outbound_payments_confirmed_by_receipts() {
+        let mark_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
+        let outbound_payment_dao = OutboundPaymentDaoMock::new()
+            .mark_confirmed_params(&mark_confirmed_params_arc)
+            .mark_confirmed_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earning")))
+            .outbound_payment_dao(outbound_payment_dao)
+            .build();
+        subject.scanners.pending_payable = Box::new(ScannerMock::new().finish_scan_result(None));
+        subject.submitted_outbound_payments = HashSet::from([make_tx_hash(111), make_tx_hash(333)]);
+        let system = System::new("accountant_marks_outbound_payments_confirmed_by_receipts");
+        let subject_addr = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+        let receipt = |hash: H256, status: TxStatus| {
+            (
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    transaction_hash: hash,
+                    status,
+                }),
+                PendingPayableFingerprint {
+                    hash,
+                    ..make_pending_payable_fingerprint()
+                },
+            )
+        };
+        let succeeded = TxStatus::Succeeded(TransactionBlock {
+            block_hash: Default::default(),
+            block_number: U64::from(1_234),
+        });
+        let msg = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![
+                receipt(make_tx_hash(111), succeeded.clone()),
+                receipt(make_tx_hash(222), succeeded),
+                receipt(make_tx_hash(333), TxStatus::Pending),
+            ],
+            response_skeleton_opt: None,
+        };
+
+        subject_addr.try_send(msg).unwrap();
+
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(|actor: &mut Accountant| {
+                    assert_eq!(
+                        actor.submitted_outbound_payments,
+                        HashSet::from([make_tx_hash(333)])
+                    );
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        system.run();
+        let mark_confirmed_params = mark_confirmed_params_arc.lock().unwrap();
+        assert_eq!(*mark_confirmed_params, vec![vec![make_tx_hash(111)]]);
+    }
+
+    #[test]
+    fn accountant_without_consuming_wallet_gives_no_notice_of_payments() {
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earning")))
+            .build();
+        subject.scanners.pending_payable = Box::new(ScannerMock::new().finish_scan_result(None));
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let system = System::new("accountant_without_consuming_wallet_gives_no_notice_of_payments");
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ReportTransactionReceipts {
+                fingerprints_with_receipts: vec![(
+                    TransactionReceiptResult::RpcResponse(TxReceipt {
+                        transaction_hash: make_tx_hash(111),
+                        status: TxStatus::Succeeded(TransactionBlock {
+                            block_hash: Default::default(),
+                            block_number: U64::from(1_234),
+                        }),
+                    }),
+                    make_pending_payable_fingerprint(),
+                )],
+                response_skeleton_opt: None,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+    }
+
+    #[test]
+    fn payment_notice_starts_receivable_scan_and_is_reconciled_with_its_results() {
+        init_test_logging();
+        let test_name = "payment_notice_starts_receivable_scan_and_is_reconciled_with_its_results";
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let earning_wallet = make_wallet("earning");
+        let debtor_wallet = make_paying_wallet(b"debtor");
+        let receivable_dao = ReceivableDaoMock::new()
+            .account_status_result(Some(make_receivable_account(1, false)))
+            .account_status_result(Some(make_receivable_account(1, false)));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.receivable = Box::new(
+            ScannerMock::new()
+                .begin_scan_params(&begin_scan_params_arc)
+                .begin_scan_result(Ok(RetrieveTransactions {
+                    recipients: vec![earning_wallet.clone()],
+                    response_skeleton_opt: None,
+                }))
+                .finish_scan_result(None),
+        );
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let notice = PaymentNotice_0v1::new(
+            &debtor_wallet,
+            &earning_wallet,
+            make_tx_hash(111),
+            4_567_000_000,
+            1_234,
+        )
+        .unwrap();
+        let forged_notice = PaymentNotice_0v1 {
+            tx_hash: make_tx_hash(222),
+            ..notice.clone()
+        };
+
+        subject_addr
+            .try_send(ReportPaymentNoticeMessage { notice })
+            .unwrap();
+        subject_addr
+            .try_send(ReportPaymentNoticeMessage {
+                notice: forged_notice,
+            })
+            .unwrap();
+        subject_addr
+            .try_send(ReceivedPayments {
+                timestamp: SystemTime::now(),
+                new_start_block: BlockMarker::Value(1_235),
+                transactions: vec![BlockchainTransaction {
+                    block_number: 1_234,
+                    from: debtor_wallet.as_address_wallet(),
+                    wei_amount: 4_567_000_000,
+                }],
+                rechecked_opt: None,
+                pending: vec![],
+                response_skeleton_opt: None,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        assert_eq!(begin_scan_params.len(), 1);
+        assert_eq!(begin_scan_params[0].0, earning_wallet);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RetrieveTransactions>(0),
+            &RetrieveTransactions {
+                recipients: vec![earning_wallet],
+                response_skeleton_opt: None,
+            }
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring notice of payment {:?} whose signature does not prove it \
+            comes from {}",
+            make_tx_hash(222),
+            debtor_wallet.as_address_wallet()
+        ));
+        tlh.exists_log_containing(&format!(
+            "INFO: {test_name}: Payment {:?} of 4,567,000,000 wei from {} announced by its payer \
+            has been received",
+            make_tx_hash(111),
+            debtor_wallet.as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn payment_notice_from_a_wallet_that_owes_us_nothing_is_ignored() {
+        init_test_logging();
+        let test_name = "payment_notice_from_a_wallet_that_owes_us_nothing_is_ignored";
+        let account_status_params_arc = Arc::new(Mutex::new(vec![]));
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let earning_wallet = make_wallet("earning");
+        let stranger_wallet = make_paying_wallet(b"stranger");
+        let receivable_dao = ReceivableDaoMock::new()
+            .account_status_params(&account_status_params_arc)
+            .account_status_result(None);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.scanners.receivable =
+            Box::new(ScannerMock::new().begin_scan_params(&begin_scan_params_arc));
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let notice = PaymentNotice_0v1::new(
+            &stranger_wallet,
+            &earning_wallet,
+            make_tx_hash(111),
+            4_567_000_000,
+            1_234,
+        )
+        .unwrap();
+
+        subject_addr
+            .try_send(ReportPaymentNoticeMessage { notice })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let account_status_params = account_status_params_arc.lock().unwrap();
+        assert_eq!(
+            *account_status_params,
+            vec![stranger_wallet.as_address_wallet()]
+        );
+        assert!(begin_scan_params_arc.lock().unwrap().is_empty());
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring notice of payment {:?} from {}, who owes us nothing",
+            make_tx_hash(111),
+            stranger_wallet.as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn payment_notices_arriving_together_share_one_receivable_scan() {
+        let test_name = "payment_notices_arriving_together_share_one_receivable_scan";
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let earning_wallet = make_wallet("earning");
+        let first_debtor_wallet = make_paying_wallet(b"first debtor");
+        let second_debtor_wallet = make_paying_wallet(b"second debtor");
+        let receivable_dao = ReceivableDaoMock::new()
+            .account_status_result(Some(make_receivable_account(1, false)))
+            .account_status_result(Some(make_receivable_account(2, false)));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        subject.scanners.receivable = Box::new(
+            ScannerMock::new()
+                .begin_scan_params(&begin_scan_params_arc)
+                .begin_scan_result(Ok(RetrieveTransactions {
+                    recipients: vec![earning_wallet.clone()],
+                    response_skeleton_opt: None,
+                })),
+        );
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let first_notice = PaymentNotice_0v1::new(
+            &first_debtor_wallet,
+            &earning_wallet,
+            make_tx_hash(111),
+            1_000_000_000,
+            1_234,
+        )
+        .unwrap();
+        let second_notice = PaymentNotice_0v1::new(
+            &second_debtor_wallet,
+            &earning_wallet,
+            make_tx_hash(222),
+            2_000_000_000,
+            1_235,
+        )
+        .unwrap();
+
+        subject_addr
+            .try_send(ReportPaymentNoticeMessage {
+                notice: first_notice,
+            })
+            .unwrap();
+        subject_addr
+            .try_send(ReportPaymentNoticeMessage {
+                notice: second_notice,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        assert_eq!(begin_scan_params.len(), 1);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 1);
+    }
+
+    #[test]
+    fn accepted_payment_notice_is_answered_with_invoice_for_the_services_we_provided() {
+        let test_name =
+            "accepted_payment_notice_is_answered_with_invoice_for_the_services_we_provided";
+        let earning_wallet = make_wallet("earning");
+        let debtor_wallet = make_paying_wallet(b"debtor");
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_receivable_result(Ok(()))
+            .more_money_receivable_result(Ok(()))
+            .account_status_result(Some(make_receivable_account(1, false)));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        subject.scanners.receivable = Box::new(
+            ScannerMock::new()
+                .begin_scan_result(Ok(RetrieveTransactions {
+                    recipients: vec![earning_wallet.clone()],
+                    response_skeleton_opt: None,
+                }))
+                .finish_scan_result(None),
+        );
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let since = SystemTime::now();
+        subject_addr
+            .try_send(ReportRoutingServiceProvidedMessage {
+                timestamp: since,
+                paying_wallet: debtor_wallet.clone(),
+                payload_size: 1_000,
+                service_rate: 100,
+                byte_rate: 2,
+            })
+            .unwrap();
+        subject_addr
+            .try_send(ReportExitServiceProvidedMessage {
+                timestamp: since,
+                paying_wallet: debtor_wallet.clone(),
+                payload_size: 2_000,
+                service_rate: 300,
+                byte_rate: 4,
+            })
+            .unwrap();
+        let notice = PaymentNotice_0v1::new(
+            &debtor_wallet,
+            &earning_wallet,
+            make_tx_hash(111),
+            10_400,
+            1_234,
+        )
+        .unwrap();
+
+        subject_addr
+            .try_send(ReportPaymentNoticeMessage { notice })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        let invoice = &neighborhood_recording
+            .get_record::<SendUsageInvoiceMessage>(0)
+            .invoice;
+        assert_eq!(invoice.creditor_wallet, earning_wallet.as_address_wallet());
+        assert_eq!(invoice.debtor_wallet, debtor_wallet.as_address_wallet());
+        assert_eq!(invoice.period_start_sec as i64, to_time_t(since));
+        assert_eq!(invoice.routing_services, 1);
+        assert_eq!(invoice.exit_services, 1);
+        assert_eq!(invoice.amount_wei, U256::from(10_400));
+        assert_eq!(neighborhood_recording.len(), 1);
+    }
+
+    fn make_fingerprint_with_nonce(
+        rowid: u64,
+        nonce: u64,
+        process_error_opt: Option<&str>,
+    ) -> PendingPayableFingerprint {
+        PendingPayableFingerprint {
+            rowid,
+            hash: make_tx_hash(rowid as u32),
+            process_error: process_error_opt.map(|error| error.to_string()),
+            replacement_basis_opt: Some(ReplacementBasis {
+                nonce,
+                gas_price_wei: 40_000_000_000,
+            }),
+            ..make_pending_payable_fingerprint()
+        }
+    }
+
+    #[test]
+    fn accountant_reconciles_fingerprints_with_the_confirmed_nonce() {
+        init_test_logging();
+        let test_name = "accountant_reconciles_fingerprints_with_the_confirmed_nonce";
+        let data_directory = ensure_node_home_directory_exists("accountant", test_name);
+        let unmark_failures_params_arc = Arc::new(Mutex::new(vec![]));
+        let mined = make_fingerprint_with_nonce(1, 20, None);
+        let not_mined_yet = make_fingerprint_with_nonce(2, 24, None);
+        let failed = make_fingerprint_with_nonce(3, 22, Some("ERROR"));
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .return_all_errorless_fingerprints_result(vec![mined.clone(), not_mined_yet])
+            .return_failed_fingerprints_result(vec![failed.clone()])
+            .unmark_failures_params(&unmark_failures_params_arc)
+            .unmark_failures_result(Ok(()));
+        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        config.data_directory = data_directory.clone();
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+
+        subject_addr
+            .try_send(ReportConfirmedNonce {
+                wallet: consuming_wallet.clone(),
+                confirmed_nonce: 23,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let unmark_failures_params = unmark_failures_params_arc.lock().unwrap();
+        assert_eq!(*unmark_failures_params, vec![vec![3]]);
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Transactions {:?} were mined below the confirmed nonce 23; their \
+             receipts will be looked up",
+            mined.hash
+        ));
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Failed transactions {:?} were mined below the confirmed nonce 23; \
+             their receipts will be looked up again",
+            failed.hash
+        ));
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: The chain confirmed transactions of {} at nonces 21 that the Node \
+             holds no record of; see {}",
+            consuming_wallet,
+            data_directory.join("nonce_anomalies_").display()
+        ));
+        let reports = fs::read_dir(&data_directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(reports.len(), 1);
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&reports[0]).unwrap()).unwrap();
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "wallet": consuming_wallet.to_string(),
+                "confirmedNonce": 23,
+                "unrecordedNonces": [21],
+                "reopenedTransactions": [format!("{:?}", failed.hash)]
+            })
+        );
+    }
+
+    #[test]
+    fn confirmed_nonce_agreeing_with_the_fingerprints_changes_nothing() {
+        init_test_logging();
+        let test_name = "confirmed_nonce_agreeing_with_the_fingerprints_changes_nothing";
+        let data_directory = ensure_node_home_directory_exists("accountant", test_name);
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .return_all_errorless_fingerprints_result(vec![make_fingerprint_with_nonce(
+                1, 20, None,
+            )])
+            .return_failed_fingerprints_result(vec![make_fingerprint_with_nonce(
+                2,
+                18,
+                Some("ABANDONED: pending for longer than 3600sec"),
+            )]);
+        let mut subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.data_directory = data_directory.clone();
+
+        subject.handle_report_confirmed_nonce(ReportConfirmedNonce {
+            wallet: make_paying_wallet(b"consuming"),
+            confirmed_nonce: 20,
+        });
+
+        assert_eq!(fs::read_dir(&data_directory).unwrap().count(), 0);
+        TestLogHandler::new().exists_no_log_containing(&format!("{test_name}:"));
+    }
+
+    #[test]
+    fn confirmed_nonce_is_not_reconciled_with_more_consuming_wallets() {
+        init_test_logging();
+        let test_name = "confirmed_nonce_is_not_reconciled_with_more_consuming_wallets";
+        let mut subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForAccountantBody(PendingPayableDaoMock::new())])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.additional_consuming_wallets = vec![make_paying_wallet(b"additional")];
+        let consuming_wallet = make_paying_wallet(b"consuming");
+
+        subject.handle_report_confirmed_nonce(ReportConfirmedNonce {
+            wallet: consuming_wallet.clone(),
+            confirmed_nonce: 20,
+        });
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Pending payables not reconciled with the confirmed nonce 20 of \
+             {}: there are more consuming wallets",
+            consuming_wallet
+        ));
+    }
+
+    fn make_usage_invoice_to(
+        debtor_wallet: &Wallet,
+        routing_services: u64,
+        amount_wei: u128,
+    ) -> ReportUsageInvoiceMessage {
+        ReportUsageInvoiceMessage {
+            invoice: UsageInvoice_0v1::new(
+                &make_wallet("creditor"),
+                debtor_wallet,
+                1_000_000,
+                1_003_600,
+                routing_services,
+                0,
+                amount_wei,
+            ),
+        }
+    }
+
+    #[test]
+    fn usage_invoice_agreeing_with_our_books_is_accepted() {
+        init_test_logging();
+        let test_name = "usage_invoice_agreeing_with_our_books_is_accepted";
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let record_invoice_dispute_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao =
+            PayableDaoMock::new().record_invoice_dispute_params(&record_invoice_dispute_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.usage_invoices.record_consumed(
+            &make_wallet("creditor"),
+            ServiceKind::Routing,
+            5_000,
+            SystemTime::now(),
+        );
+
+        subject.handle_report_usage_invoice_message(make_usage_invoice_to(
+            &consuming_wallet,
+            1,
+            5_000,
+        ));
+
+        assert!(record_invoice_dispute_params_arc.lock().unwrap().is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Invoice from {} for 1 routing and 0 exit services agrees with our \
+            books",
+            make_wallet("creditor").as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn usage_invoice_claiming_more_than_we_booked_is_disputed() {
+        init_test_logging();
+        let test_name = "usage_invoice_claiming_more_than_we_booked_is_disputed";
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let creditor_wallet = make_wallet("creditor");
+        let record_invoice_dispute_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .record_invoice_dispute_params(&record_invoice_dispute_params_arc)
+            .record_invoice_dispute_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.usage_invoices.record_consumed(
+            &creditor_wallet,
+            ServiceKind::Routing,
+            5_000,
+            SystemTime::now(),
+        );
+        let before = SystemTime::now();
+
+        subject.handle_report_usage_invoice_message(make_usage_invoice_to(
+            &consuming_wallet,
+            2,
+            7_000,
+        ));
+
+        let after = SystemTime::now();
+        let record_invoice_dispute_params = record_invoice_dispute_params_arc.lock().unwrap();
+        let dispute = &record_invoice_dispute_params[0];
+        assert_eq!(
+            dispute,
+            &InvoiceDispute {
+                creditor_wallet: creditor_wallet.as_address_wallet(),
+                period_start: from_time_t(1_000_000),
+                period_end: from_time_t(1_003_600),
+                invoiced_services: 2,
+                booked_services: 1,
+                invoiced_wei: 7_000,
+                booked_wei: 5_000,
+                received_at: dispute.received_at,
+            }
+        );
+        assert!(before <= dispute.received_at && dispute.received_at <= after);
+        assert_eq!(record_invoice_dispute_params.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Disputing invoice from {}: it claims 2 services worth 7000 wei, \
+            but we've booked 1 worth 5000 wei",
+            creditor_wallet.as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn usage_invoice_addressed_to_another_debtor_is_ignored() {
+        init_test_logging();
+        let test_name = "usage_invoice_addressed_to_another_debtor_is_ignored";
+        let record_invoice_dispute_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao =
+            PayableDaoMock::new().record_invoice_dispute_params(&record_invoice_dispute_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .logger(Logger::new(test_name))
+            .build();
+        let stranger_wallet = make_paying_wallet(b"stranger");
+
+        subject.handle_report_usage_invoice_message(make_usage_invoice_to(
+            &stranger_wallet,
+            1,
+            5_000,
+        ));
+
+        assert!(record_invoice_dispute_params_arc.lock().unwrap().is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring invoice from {}: {} is not our consuming wallet",
+            make_wallet("creditor").as_address_wallet(),
+            stranger_wallet.as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn report_services_consumed_message_is_received() {
+        init_test_logging();
+        let config = make_bc_with_defaults();
+        let more_money_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new()
+            .more_money_payable_params(more_money_payable_params_arc.clone())
+            .more_money_payable_result(Ok(()))
+            .more_money_payable_result(Ok(()))
+            .more_money_payable_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .build();
+        subject.message_id_generator = Box::new(MessageIdGeneratorMock::default().id_result(123));
+        let system = System::new("report_services_consumed_message_is_received");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+        let earning_wallet_exit = make_wallet("exit");
+        let earning_wallet_routing_1 = make_wallet("routing 1");
+        let earning_wallet_routing_2 = make_wallet("routing 2");
+        let timestamp = SystemTime::now();
+
+        subject_addr
+            .try_send(ReportServicesConsumedMessage {
+                timestamp,
+                exit: ExitServiceConsumed {
+                    earning_wallet: earning_wallet_exit.clone(),
+                    payload_size: 1200,
+                    service_rate: 120,
+                    byte_rate: 30,
+                },
+                routing_payload_size: 3456,
+                routing: vec![
+                    RoutingServiceConsumed {
+                        earning_wallet: earning_wallet_routing_1.clone(),
+                        service_rate: 42,
+                        byte_rate: 24,
+                    },
+                    RoutingServiceConsumed {
+                        earning_wallet: earning_wallet_routing_2.clone(),
+                        service_rate: 52,
+                        byte_rate: 33,
+                    },
+                ],
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let more_money_payable_params = more_money_payable_params_arc.lock().unwrap();
+        assert_eq!(
+            more_money_payable_params
+                .iter()
+                .map(|(timestamp, wallet, amount)| (timestamp, wallet, amount))
+                .collect::<Vec<_>>(),
+            vec![
+                (&timestamp, &earning_wallet_exit, &((1 * 120) + (1200 * 30))),
+                (
+                    &timestamp,
+                    &earning_wallet_routing_1,
+                    &((1 * 42) + (3456 * 24))
+                ),
+                (
+                    &timestamp,
+                    &earning_wallet_routing_2,
+                    &((1 * 52) + (3456 * 33))
+                )
+            ]
+        );
+        let test_log_handler = TestLogHandler::new();
+
+        test_log_handler.exists_log_containing(&format!(
+            "DEBUG: Accountant: MsgId 123: Accruing debt to {} for consuming 1200 exited bytes",
+            earning_wallet_exit
+        ));
+        test_log_handler.exists_log_containing(&format!(
+            "DEBUG: Accountant: MsgId 123: Accruing debt to {} for consuming 3456 routed bytes",
+            earning_wallet_routing_1
+        ));
+        test_log_handler.exists_log_containing(&format!(
+            "DEBUG: Accountant: MsgId 123: Accruing debt to {} for consuming 3456 routed bytes",
+            earning_wallet_routing_2
+        ));
+    }
+
+    fn assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
+        config: BootstrapperConfig,
+        message: ReportServicesConsumedMessage,
+    ) -> Arc<Mutex<Vec<(SystemTime, Wallet, u128)>>> {
+        let more_money_payable_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao_mock = PayableDaoMock::new()
+            .non_pending_payables_result(vec![])
+            .more_money_payable_result(Ok(()))
+            .more_money_payable_params(more_money_payable_parameters_arc.clone());
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .build();
+        let system = System::new("test");
+        let subject_addr: Addr<Accountant> = subject.start();
+        subject_addr
+            .try_send(BindMessage {
+                peer_actors: peer_actors_builder().build(),
+            })
+            .unwrap();
+
+        subject_addr.try_send(message).unwrap();
+
+        System::current().stop();
+        system.run();
+        more_money_payable_parameters_arc
+    }
+
+    #[test]
+    fn routing_service_consumed_is_reported_for_our_consuming_wallet() {
+        init_test_logging();
+        let consuming_wallet = make_wallet("the consuming wallet");
+        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("the earning wallet"));
+        let foreign_wallet = make_wallet("exit wallet");
+        let timestamp = SystemTime::now();
+        let report_message = ReportServicesConsumedMessage {
+            timestamp,
+            exit: ExitServiceConsumed {
+                earning_wallet: foreign_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 45,
+                byte_rate: 10,
+            },
+            routing_payload_size: 3333,
+            routing: vec![RoutingServiceConsumed {
+                earning_wallet: consuming_wallet.clone(),
+                service_rate: 42,
+                byte_rate: 6,
+            }],
+        };
+
+        let more_money_payable_params_arc =
+            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
+                config,
+                report_message,
+            );
+
+        let more_money_payable_params = more_money_payable_params_arc.lock().unwrap();
+        assert_eq!(
+            *more_money_payable_params,
+            //except processing the exit service there was no change in payables
+            vec![(timestamp, foreign_wallet, (45 + 10 * 1234))]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
+            consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn routing_service_consumed_is_reported_for_our_earning_wallet() {
+        init_test_logging();
+        let earning_wallet =
+            make_wallet("routing_service_consumed_is_reported_for_our_earning_wallet");
+        let foreign_wallet = make_wallet("exit wallet");
+        let config = bc_from_earning_wallet(earning_wallet.clone());
+        let timestamp = SystemTime::now();
+        let report_message = ReportServicesConsumedMessage {
+            timestamp,
+            exit: ExitServiceConsumed {
+                earning_wallet: foreign_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 45,
+                byte_rate: 10,
+            },
+            routing_payload_size: 3333,
+            routing: vec![RoutingServiceConsumed {
+                earning_wallet: earning_wallet.clone(),
+                service_rate: 42,
+                byte_rate: 6,
+            }],
+        };
+
+        let more_money_payable_params_arc =
+            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
+                config,
+                report_message,
+            );
+
+        let more_money_payable_params = more_money_payable_params_arc.lock().unwrap();
+        assert_eq!(
+            *more_money_payable_params,
+            //except processing the exit service there was no change in payables
+            vec![(timestamp, foreign_wallet, (45 + 10 * 1234))]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
+            earning_wallet
+        ));
+    }
+
+    #[test]
+    fn exit_service_consumed_is_reported_for_our_consuming_wallet() {
+        init_test_logging();
+        let consuming_wallet =
+            make_wallet("exit_service_consumed_is_reported_for_our_consuming_wallet");
+        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("own earning wallet"));
+        let report_message = ReportServicesConsumedMessage {
+            timestamp: SystemTime::now(),
+            exit: ExitServiceConsumed {
+                earning_wallet: consuming_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            },
+            routing_payload_size: 3333,
+            routing: vec![],
+        };
+
+        let more_money_payable_params_arc =
+            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
+                config,
+                report_message,
+            );
+
+        assert!(more_money_payable_params_arc.lock().unwrap().is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
+            consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn exit_service_consumed_is_reported_for_our_earning_wallet() {
+        init_test_logging();
+        let earning_wallet = make_wallet("own earning wallet");
+        let config = bc_from_earning_wallet(earning_wallet.clone());
+        let report_message = ReportServicesConsumedMessage {
+            timestamp: SystemTime::now(),
+            exit: ExitServiceConsumed {
+                earning_wallet: earning_wallet.clone(),
+                payload_size: 1234,
+                service_rate: 42,
+                byte_rate: 24,
+            },
+            routing_payload_size: 3333,
+            routing: vec![],
+        };
+
+        let more_money_payable_params_arc =
+            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
+                config,
+                report_message,
+            );
+
+        assert!(more_money_payable_params_arc.lock().unwrap().is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
+            earning_wallet
+        ));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Recording services provided for 0x000000000000000000000000000000626f6f6761 \
+    but has hit fatal database error: RusqliteError(\"we cannot help ourselves; this is baaad\")"
+    )]
+    fn record_service_provided_panics_on_fatal_errors() {
+        init_test_logging();
+        let wallet = make_wallet("booga");
+        let receivable_dao = ReceivableDaoMock::new().more_money_receivable_result(Err(
+            ReceivableDaoError::RusqliteError(
+                "we cannot help ourselves; this is baaad".to_string(),
+            ),
+        ));
+        let subject = AccountantBuilder::default()
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+
+        let _ = subject.record_service_provided(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+    }
+
+    #[test]
+    fn record_service_provided_handles_overflow() {
+        init_test_logging();
+        let wallet = make_wallet("booga");
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_receivable_result(Err(ReceivableDaoError::SignConversion(1234)));
+        let subject = AccountantBuilder::default()
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+
+        subject.record_service_provided(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: Accountant: Overflow error recording service provided for {}: service rate {}, byte rate 1, payload size 2. Skipping",
+            wallet,
+            i64::MAX as u64
+        ));
+    }
+
+    #[test]
+    fn record_service_consumed_handles_overflow() {
+        init_test_logging();
+        let wallet = make_wallet("booga");
+        let payable_dao = PayableDaoMock::new()
+            .more_money_payable_result(Err(PayableDaoError::SignConversion(1234)));
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        let service_rate = i64::MAX as u64;
+
+        subject.record_service_consumed(service_rate, 1, SystemTime::now(), 2, &wallet);
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: Accountant: Overflow error recording consumed services from {}: total charge {}, service rate {}, byte rate 1, payload size 2. Skipping",
+            wallet,
+            i64::MAX as u64 + 1 * 2,
+            i64::MAX as u64
+        ));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Recording services consumed from 0x000000000000000000000000000000626f6f6761 but \
+     has hit fatal database error: RusqliteError(\"we cannot help ourselves; this is baaad\")"
+    )]
+    fn record_service_consumed_panics_on_fatal_errors() {
+        init_test_logging();
+        let wallet = make_wallet("booga");
+        let payable_dao = PayableDaoMock::new().more_money_payable_result(Err(
+            PayableDaoError::RusqliteError("we cannot help ourselves; this is baaad".to_string()),
+        ));
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+
+        let _ = subject.record_service_consumed(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "panic message (processed with: node_lib::sub_lib::utils::crash_request_analyzer)"
+    )]
+    fn accountant_can_be_crashed_properly_but_not_improperly() {
+        let mut config = make_bc_with_defaults();
+        config.crash_point = CrashPoint::Message;
+        let accountant = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .build();
+
+        prove_that_crash_request_handler_is_hooked_up(accountant, CRASH_KEY);
+    }
+
+    #[test]
+    fn pending_transaction_is_registered_and_monitored_until_it_gets_confirmed_or_canceled() {
+        init_test_logging();
+        let port = find_free_port();
+        let pending_tx_hash_1 =
+            H256::from_str("e66814b2812a80d619813f51aa999c0df84eb79d10f4923b2b7667b30d6b33d3")
+                .unwrap();
+        let pending_tx_hash_2 =
+            H256::from_str("0288ef000581b3bca8a2017eac9aea696366f8f1b7437f18d1aad57bccb7032c")
+                .unwrap();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .serving_chain(Chain::PolyMainnet)
+            // Blockchain Agent Gas Price
+            .ok_response("0x3B9ACA00".to_string(), 0) // 1000000000
+            // Blockchain Agent transaction fee balance
+            .ok_response("0xFFF0".to_string(), 0) // 65520
+            // Blockchain Agent masq balance
+            .ok_response(
+                "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(),
+                0,
+            )
+            // Blockchain Agent latest block
+            .raw_response(
+                r#"{"jsonrpc": "2.0", "result": {"baseFeePerGas": "0x1DCD6500", "gasLimit": "0x1C9C380"}, "id": 0}"#
+                    .to_string(),
+            )
+            // Submit payments to blockchain
+            .ok_response("0xFFF0".to_string(), 1)
+            .begin_batch()
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_1)
+                    .build(),
+            )
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_2)
+                    .build(),
+            )
+            .end_batch()
+            // Round 1 - handle_request_transaction_receipts
+            .begin_batch()
+            .raw_response(r#"{ "jsonrpc": "2.0", "id": 1, "result": null }"#.to_string()) // Null response
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_2)
+                    .build(),
+            )
+            .end_batch()
+            // Round 2 - handle_request_transaction_receipts
+            .begin_batch()
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_1)
+                    .build(),
+            )
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_2)
+                    .build(),
+            )
+            .end_batch()
+            // Round 3 - handle_request_transaction_receipts
+            .begin_batch()
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_1)
+                    .status(U64::from(0))
+                    .build(),
+            )
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_2)
+                    .build(),
+            )
+            .end_batch()
+            // Round 4 - handle_request_transaction_receipts
+            .begin_batch()
+            .raw_response(
+                ReceiptResponseBuilder::default()
+                    .transaction_hash(pending_tx_hash_2)
+                    .status(U64::from(1))
+                    .block_number(U64::from(1234))
+                    .block_hash(Default::default())
+                    .build(),
+            )
+            .end_batch()
+            .start();
+        let non_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let mark_pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let return_all_errorless_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
+        let update_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
+        let mark_failure_params_arc = Arc::new(Mutex::new(vec![]));
+        let transactions_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
+        let delete_record_params_arc = Arc::new(Mutex::new(vec![]));
+        let notify_later_scan_for_pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let notify_later_scan_for_pending_payable_arc_cloned =
+            notify_later_scan_for_pending_payable_params_arc.clone(); // because it moves into a closure
+        let rowid_for_account_1 = 3;
+        let rowid_for_account_2 = 5;
+        let now = SystemTime::now();
+        let past_payable_timestamp_1 = now.sub(Duration::from_secs(
+            (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 555) as u64,
+        ));
+        let past_payable_timestamp_2 = now.sub(Duration::from_secs(
+            (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 50) as u64,
+        ));
+        let this_payable_timestamp_1 = now;
+        let this_payable_timestamp_2 = now.add(Duration::from_millis(50));
+        let payable_account_balance_1 =
+            gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 10);
+        let payable_account_balance_2 =
+            gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 666);
+        let wallet_account_1 = make_wallet("creditor1");
+        let wallet_account_2 = make_wallet("creditor2");
+        let blockchain_interface = make_blockchain_interface_web3(port);
+        let consuming_wallet = make_paying_wallet(b"wallet");
+        let system = System::new("pending_transaction");
+        let persistent_config_id_stamp = ArbitraryIdStamp::new();
+        let persistent_config = PersistentConfigurationMock::default()
+            .set_arbitrary_id_stamp(persistent_config_id_stamp);
+        let blockchain_bridge = BlockchainBridge::new(
+            Box::new(blockchain_interface),
+            Arc::new(Mutex::new(persistent_config)),
+            false,
+            Some(consuming_wallet.clone()),
+            None,
+            None,
+        );
+        let account_1 = PayableAccount {
+            wallet: wallet_account_1.clone(),
+            balance_wei: payable_account_balance_1,
+            last_paid_timestamp: past_payable_timestamp_1,
+            pending_payable_opt: None,
+        };
+        let account_2 = PayableAccount {
+            wallet: wallet_account_2.clone(),
+            balance_wei: payable_account_balance_2,
+            last_paid_timestamp: past_payable_timestamp_2,
+            pending_payable_opt: None,
+        };
+        let pending_payable_scan_interval = 1000; // should be slightly less than 1/5 of the time until shutting the system
+        let payable_dao_for_payable_scanner = PayableDaoMock::new()
+            .non_pending_payables_params(&non_pending_payables_params_arc)
+            .non_pending_payables_result(vec![account_1, account_2])
+            .creditor_thresholds_result(HashMap::new())
+            .mark_pending_payables_rowids_params(&mark_pending_payable_params_arc)
+            .mark_pending_payables_rowids_result(Ok(()));
+        let payable_dao_for_pending_payable_scanner = PayableDaoMock::new()
+            .transactions_confirmed_params(&transactions_confirmed_params_arc)
+            .transactions_confirmed_result(Ok(()));
+        let mut bootstrapper_config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
+        bootstrapper_config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_secs(1_000_000), // we don't care about this scan
+            receivable_scan_interval: Duration::from_secs(1_000_000), // we don't care about this scan
+            pending_payable_scan_interval: Duration::from_millis(pending_payable_scan_interval),
+        });
+        let fingerprint_1_first_round = PendingPayableFingerprint {
+            rowid: rowid_for_account_1,
+            timestamp: this_payable_timestamp_1,
+            hash: pending_tx_hash_1,
+            attempt: 1,
+            amount: payable_account_balance_1,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let fingerprint_2_first_round = PendingPayableFingerprint {
+            rowid: rowid_for_account_2,
+            timestamp: this_payable_timestamp_2,
+            hash: pending_tx_hash_2,
+            attempt: 1,
+            amount: payable_account_balance_2,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let fingerprint_1_second_round = PendingPayableFingerprint {
+            attempt: 2,
+            ..fingerprint_1_first_round.clone()
+        };
+        let fingerprint_2_second_round = PendingPayableFingerprint {
+            attempt: 2,
+            ..fingerprint_2_first_round.clone()
+        };
+        let fingerprint_1_third_round = PendingPayableFingerprint {
+            attempt: 3,
+            ..fingerprint_1_first_round.clone()
+        };
+        let fingerprint_2_third_round = PendingPayableFingerprint {
+            attempt: 3,
+            ..fingerprint_2_first_round.clone()
+        };
+        let fingerprint_2_fourth_round = PendingPayableFingerprint {
+            attempt: 4,
+            ..fingerprint_2_first_round.clone()
+        };
+        let pending_payable_dao_for_payable_scanner = PendingPayableDaoMock::default()
+            .recipients_of_errorless_fingerprints_result(HashSet::new())
+            .fingerprints_rowids_result(TransactionHashes {
+                rowid_results: vec![
+                    (rowid_for_account_1, pending_tx_hash_1),
+                    (rowid_for_account_2, pending_tx_hash_2),
+                ],
+                no_rowid_results: vec![],
+            })
+            .fingerprints_rowids_result(TransactionHashes {
+                rowid_results: vec![
+                    (rowid_for_account_1, pending_tx_hash_1),
+                    (rowid_for_account_2, pending_tx_hash_2),
+                ],
+                no_rowid_results: vec![],
+            });
+        let mut pending_payable_dao_for_pending_payable_scanner = PendingPayableDaoMock::new()
+            .return_all_errorless_fingerprints_params(&return_all_errorless_fingerprints_params_arc)
+            .return_all_errorless_fingerprints_result(vec![])
+            .return_all_errorless_fingerprints_result(vec![
+                fingerprint_1_first_round,
+                fingerprint_2_first_round,
+            ])
+            .return_all_errorless_fingerprints_result(vec![
+                fingerprint_1_second_round,
+                fingerprint_2_second_round,
+            ])
+            .return_all_errorless_fingerprints_result(vec![
+                fingerprint_1_third_round,
+                fingerprint_2_third_round,
+            ])
+            .return_all_errorless_fingerprints_result(vec![fingerprint_2_fourth_round.clone()])
+            .fingerprints_rowids_result(TransactionHashes {
+                rowid_results: vec![
+                    (rowid_for_account_1, pending_tx_hash_1),
+                    (rowid_for_account_2, pending_tx_hash_2),
+                ],
+                no_rowid_results: vec![],
+            })
+            .increment_scan_attempts_params(&update_fingerprint_params_arc)
+            .increment_scan_attempts_result(Ok(()))
+            .increment_scan_attempts_result(Ok(()))
+            .increment_scan_attempts_result(Ok(()))
+            .mark_failures_params(&mark_failure_params_arc)
+            // we don't have a better solution yet, so we mark this down
+            .mark_failures_result(Ok(()))
+            .delete_fingerprints_params(&delete_record_params_arc)
+            // this is used during confirmation of the successful one
+            .delete_fingerprints_result(Ok(()));
+        pending_payable_dao_for_pending_payable_scanner
+            .have_return_all_errorless_fingerprints_shut_down_the_system = true;
+        let pending_payable_dao_for_accountant =
+            PendingPayableDaoMock::new().insert_fingerprints_result(Ok(()));
+        let accountant_addr = Arbiter::builder()
+            .stop_system_on_panic(true)
+            .start(move |_| {
+                let mut subject = AccountantBuilder::default()
+                    .consuming_wallet(consuming_wallet)
+                    .bootstrapper_config(bootstrapper_config)
+                    .payable_daos(vec![
+                        ForPayableScanner(payable_dao_for_payable_scanner),
+                        ForPendingPayableScanner(payable_dao_for_pending_payable_scanner),
+                    ])
+                    .pending_payable_daos(vec![
+                        ForAccountantBody(pending_payable_dao_for_accountant),
+                        ForPayableScanner(pending_payable_dao_for_payable_scanner),
+                        ForPendingPayableScanner(pending_payable_dao_for_pending_payable_scanner),
+                    ])
+                    .build();
+                subject.scanners.receivable = Box::new(NullScanner::new());
+                let notify_later_half_mock = NotifyLaterHandleMock::default()
+                    .notify_later_params(&notify_later_scan_for_pending_payable_arc_cloned)
+                    .capture_msg_and_let_it_fly_on();
+                subject.scan_schedulers.update_scheduler(
+                    ScanType::PendingPayables,
+                    Some(Box::new(notify_later_half_mock)),
+                    None,
+                );
+                subject
+            });
+        let mut peer_actors = peer_actors_builder().build();
+        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+        peer_actors.accountant = accountant_subs.clone();
+        let blockchain_bridge_addr = blockchain_bridge.start();
+        let blockchain_bridge_subs = BlockchainBridge::make_subs_from(&blockchain_bridge_addr);
+        peer_actors.blockchain_bridge = blockchain_bridge_subs.clone();
+        send_bind_message!(accountant_subs, peer_actors);
+        send_bind_message!(blockchain_bridge_subs, peer_actors);
+
+        send_start_message!(accountant_subs);
+
+        assert_eq!(system.run(), 0);
+        let mut mark_pending_payable_params = mark_pending_payable_params_arc.lock().unwrap();
+        let mut one_set_of_mark_pending_payable_params = mark_pending_payable_params.remove(0);
+        assert!(mark_pending_payable_params.is_empty());
+        let first_payable = one_set_of_mark_pending_payable_params.remove(0);
+        assert_eq!(first_payable.0, wallet_account_1);
+        assert_eq!(first_payable.1, rowid_for_account_1);
+        let second_payable = one_set_of_mark_pending_payable_params.remove(0);
+        assert!(
+            one_set_of_mark_pending_payable_params.is_empty(),
+            "{:?}",
+            one_set_of_mark_pending_payable_params
+        );
+        assert_eq!(second_payable.0, wallet_account_2);
+        assert_eq!(second_payable.1, rowid_for_account_2);
+        let return_all_errorless_fingerprints_params =
+            return_all_errorless_fingerprints_params_arc.lock().unwrap();
+        // it varies with machines and sometimes we manage more cycles than necessary
+        assert!(return_all_errorless_fingerprints_params.len() >= 5);
+        let non_pending_payables_params = non_pending_payables_params_arc.lock().unwrap();
+        assert_eq!(*non_pending_payables_params, vec![()]); // because we disabled further scanning for payables
+        let update_fingerprints_params = update_fingerprint_params_arc.lock().unwrap();
         assert_eq!(
-            *notify_later_receivable_params,
+            *update_fingerprints_params,
+            vec![
+                vec![rowid_for_account_1, rowid_for_account_2],
+                vec![rowid_for_account_1, rowid_for_account_2],
+                vec![rowid_for_account_2],
+            ]
+        );
+        let mark_failure_params = mark_failure_params_arc.lock().unwrap();
+        assert_eq!(*mark_failure_params, vec![vec![rowid_for_account_1]]);
+        let delete_record_params = delete_record_params_arc.lock().unwrap();
+        assert_eq!(*delete_record_params, vec![vec![rowid_for_account_2]]);
+        let transaction_confirmed_params = transactions_confirmed_params_arc.lock().unwrap();
+        assert_eq!(
+            *transaction_confirmed_params,
+            vec![vec![fingerprint_2_fourth_round.clone()]]
+        );
+        let expected_scan_pending_payable_msg_and_interval = (
+            ScanForPendingPayables {
+                response_skeleton_opt: None,
+            },
+            Duration::from_millis(pending_payable_scan_interval),
+        );
+        let mut notify_later_check_for_confirmation =
+            notify_later_scan_for_pending_payable_params_arc
+                .lock()
+                .unwrap();
+        // it varies with machines and sometimes we manage more cycles than necessary
+        let vector_of_first_five_cycles = notify_later_check_for_confirmation
+            .drain(0..=4)
+            .collect_vec();
+        assert_eq!(
+            vector_of_first_five_cycles,
             vec![
+                expected_scan_pending_payable_msg_and_interval.clone(),
+                expected_scan_pending_payable_msg_and_interval.clone(),
+                expected_scan_pending_payable_msg_and_interval.clone(),
+                expected_scan_pending_payable_msg_and_interval.clone(),
+                expected_scan_pending_payable_msg_and_interval,
+            ]
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(
+            "WARN: Accountant: Broken transactions 0xe66814b2812a80d619813f51aa999c0df84eb79d10f\
+            4923b2b7667b30d6b33d3 marked as an error. You should take over the care of those to make sure \
+                your debts are going to be settled properly. At the moment, there is no automated process \
+                fixing that without your assistance");
+        log_handler.exists_log_matching("INFO: Accountant: Transaction 0x0288ef000581b3bca8a2017eac9\
+        aea696366f8f1b7437f18d1aad57bccb7032c has been added to the blockchain; detected locally at \
+            attempt 4 at \\d{2,}ms after its sending");
+        log_handler.exists_log_containing(
+            "INFO: Accountant: Transactions 0x0288ef000581b3bca8a2017eac9aea696366f8f1b7437f18d1aad5\
+            7bccb7032c completed their confirmation process succeeding",
+        );
+    }
+
+    #[test]
+    fn accountant_receives_reported_transaction_receipts_and_processes_them_all() {
+        let transactions_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::default()
+            .transactions_confirmed_params(&transactions_confirmed_params_arc)
+            .transactions_confirmed_result(Ok(()));
+        let pending_payable_dao =
+            PendingPayableDaoMock::default().delete_fingerprints_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForPendingPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .build();
+        let subject_addr = subject.start();
+        let transaction_hash_1 = make_tx_hash(4545);
+        let transaction_receipt_1 = TxReceipt {
+            transaction_hash: transaction_hash_1,
+            status: TxStatus::Succeeded(TransactionBlock {
+                block_hash: Default::default(),
+                block_number: U64::from(100),
+            }),
+        };
+        let fingerprint_1 = PendingPayableFingerprint {
+            rowid: 5,
+            timestamp: from_time_t(200_000_000),
+            hash: transaction_hash_1,
+            attempt: 2,
+            amount: 444,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let transaction_hash_2 = make_tx_hash(3333333);
+        let transaction_receipt_2 = TxReceipt {
+            transaction_hash: transaction_hash_2,
+            status: TxStatus::Succeeded(TransactionBlock {
+                block_hash: Default::default(),
+                block_number: U64::from(200),
+            }),
+        };
+        let fingerprint_2 = PendingPayableFingerprint {
+            rowid: 10,
+            timestamp: from_time_t(199_780_000),
+            hash: Default::default(),
+            attempt: 15,
+            amount: 1212,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let msg = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![
                 (
-                    ScanForReceivables {
-                        response_skeleton_opt: None
-                    },
-                    Duration::from_millis(99)
+                    TransactionReceiptResult::RpcResponse(transaction_receipt_1),
+                    fingerprint_1.clone(),
                 ),
                 (
-                    ScanForReceivables {
-                        response_skeleton_opt: None
-                    },
-                    Duration::from_millis(99)
+                    TransactionReceiptResult::RpcResponse(transaction_receipt_2),
+                    fingerprint_2.clone(),
                 ),
-            ]
-        )
+            ],
+            response_skeleton_opt: None,
+        };
+
+        subject_addr.try_send(msg).unwrap();
+
+        let system = System::new("processing reported receipts");
+        System::current().stop();
+        system.run();
+        let transactions_confirmed_params = transactions_confirmed_params_arc.lock().unwrap();
+        assert_eq!(
+            *transactions_confirmed_params,
+            vec![vec![fingerprint_1, fingerprint_2]]
+        );
     }
 
     #[test]
-    fn periodical_scanning_for_pending_payable_works() {
-        init_test_logging();
-        let test_name = "periodical_scanning_for_pending_payable_works";
-        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
-        let notify_later_pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
-        let system = System::new(test_name);
-        SystemKillerActor::new(Duration::from_secs(10)).start(); // a safety net for GitHub Actions
-        let consuming_wallet = make_paying_wallet(b"consuming");
-        let pending_payable_scanner = ScannerMock::new()
-            .begin_scan_params(&begin_scan_params_arc)
-            .begin_scan_result(Err(BeginScanError::NothingToProcess))
-            .begin_scan_result(Ok(RequestTransactionReceipts {
-                pending_payable: vec![],
-                response_skeleton_opt: None,
-            }))
-            .stop_the_system_after_last_msg();
+    fn accountant_asks_for_abandoned_transactions_to_be_voided() {
+        let release_failed_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let mark_abandoned_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .release_failed_payables_params(&release_failed_payables_params_arc)
+            .release_failed_payables_result(Ok(vec![make_payable_account(1)]));
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .mark_abandoned_params(&mark_abandoned_params_arc)
+            .mark_abandoned_result(Ok(()));
         let mut config = make_bc_with_defaults();
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_secs(100),
-            receivable_scan_interval: Duration::from_secs(100),
-            pending_payable_scan_interval: Duration::from_millis(98),
-        });
+        config.when_pending_too_long_sec = 60;
         let mut subject = AccountantBuilder::default()
-            .consuming_wallet(consuming_wallet.clone())
             .bootstrapper_config(config)
-            .logger(Logger::new(test_name))
+            .payable_daos(vec![ForPendingPayableScanner(payable_dao)])
+            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
             .build();
-        subject.scanners.payable = Box::new(NullScanner::new()); //skipping
-        subject.scanners.pending_payable = Box::new(pending_payable_scanner);
-        subject.scanners.receivable = Box::new(NullScanner::new()); //skipping
-        subject.scan_schedulers.update_scheduler(
-            ScanType::PendingPayables,
-            Some(Box::new(
-                NotifyLaterHandleMock::default()
-                    .notify_later_params(&notify_later_pending_payable_params_arc)
-                    .capture_msg_and_let_it_fly_on(),
-            )),
-            None,
-        );
-        let subject_addr: Addr<Accountant> = subject.start();
-        let subject_subs = Accountant::make_subs_from(&subject_addr);
-        let peer_actors = peer_actors_builder().build();
-        send_bind_message!(subject_subs, peer_actors);
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        subject.void_abandoned_transaction_sub_opt = Some(blockchain_bridge.start().recipient());
+        let subject_addr = subject.start();
+        let hash = make_tx_hash(0x1234);
+        let basis = ReplacementBasis {
+            nonce: 8,
+            gas_price_wei: 2_000_000_000,
+        };
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 7,
+            timestamp: SystemTime::now().sub(Duration::from_secs(120)),
+            hash,
+            attempt: 30,
+            amount: 444,
+            process_error: None,
+            replacement_basis_opt: Some(basis),
+        };
+        let msg = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![(
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    transaction_hash: hash,
+                    status: TxStatus::Pending,
+                }),
+                fingerprint,
+            )],
+            response_skeleton_opt: None,
+        };
 
-        send_start_message!(subject_subs);
+        subject_addr.try_send(msg).unwrap();
 
-        let time_before = SystemTime::now();
+        let system = System::new("accountant_asks_for_abandoned_transactions_to_be_voided");
+        System::current().stop();
         system.run();
-        let time_after = SystemTime::now();
-        let notify_later_pending_payable_params =
-            notify_later_pending_payable_params_arc.lock().unwrap();
-        TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: {test_name}: There was nothing to process during PendingPayables scan."
-        ));
-        let mut begin_scan_params = begin_scan_params_arc.lock().unwrap();
-        let (
-            first_attempt_wallet,
-            first_attempt_timestamp,
-            first_attempt_response_skeleton_opt,
-            first_attempt_logger,
-        ) = begin_scan_params.remove(0);
-        let (
-            second_attempt_wallet,
-            second_attempt_timestamp,
-            second_attempt_response_skeleton_opt,
-            second_attempt_logger,
-        ) = begin_scan_params.remove(0);
-        assert_eq!(first_attempt_wallet, second_attempt_wallet);
-        assert_eq!(second_attempt_wallet, consuming_wallet);
-        assert!(time_before <= first_attempt_timestamp);
-        assert!(first_attempt_timestamp <= second_attempt_timestamp);
-        assert!(second_attempt_timestamp <= time_after);
-        assert_eq!(first_attempt_response_skeleton_opt, None);
-        assert_eq!(second_attempt_response_skeleton_opt, None);
-        debug!(first_attempt_logger, "first attempt");
-        debug!(second_attempt_logger, "second attempt");
-        let tlh = TestLogHandler::new();
-        tlh.exists_log_containing(&format!("DEBUG: {test_name}: first attempt"));
-        tlh.exists_log_containing(&format!("DEBUG: {test_name}: second attempt"));
         assert_eq!(
-            *notify_later_pending_payable_params,
-            vec![
-                (
-                    ScanForPendingPayables {
-                        response_skeleton_opt: None
-                    },
-                    Duration::from_millis(98)
-                ),
-                (
-                    ScanForPendingPayables {
-                        response_skeleton_opt: None
-                    },
-                    Duration::from_millis(98)
-                ),
-            ]
-        )
+            *release_failed_payables_params_arc.lock().unwrap(),
+            vec![vec![7]]
+        );
+        assert_eq!(
+            *mark_abandoned_params_arc.lock().unwrap(),
+            vec![(vec![7], "pending for longer than 60sec".to_string())]
+        );
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<VoidAbandonedTransaction>(0),
+            &VoidAbandonedTransaction {
+                hash,
+                basis,
+                gas_price_bump_percent: VOID_GAS_PRICE_BUMP_PERCENT,
+            }
+        );
+        assert_eq!(blockchain_bridge_recording.len(), 1);
     }
 
     #[test]
-    fn periodical_scanning_for_payable_works() {
+    fn accountant_outbids_stuck_transactions_of_a_batch_in_nonce_order() {
         init_test_logging();
-        let test_name = "periodical_scanning_for_payable_works";
-        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
-        let notify_later_payables_params_arc = Arc::new(Mutex::new(vec![]));
-        let system = System::new(test_name);
-        SystemKillerActor::new(Duration::from_secs(10)).start(); // a safety net for GitHub Actions
-        let consuming_wallet = make_paying_wallet(b"consuming");
-        let payable_scanner = ScannerMock::new()
-            .begin_scan_params(&begin_scan_params_arc)
-            .begin_scan_result(Err(BeginScanError::NothingToProcess))
-            .begin_scan_result(Ok(QualifiedPayablesMessage {
-                protected_qualified_payables: protect_payables_in_test(vec![make_payable_account(
-                    123,
-                )]),
-                consuming_wallet: consuming_wallet.clone(),
-                response_skeleton_opt: None,
-            }))
-            .stop_the_system_after_last_msg();
-        let mut config = bc_from_earning_wallet(make_wallet("hi"));
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(97),
-            receivable_scan_interval: Duration::from_secs(100), // We'll never run this scanner
-            pending_payable_scan_interval: Duration::from_secs(100), // We'll never run this scanner
+        let test_name = "accountant_outbids_stuck_transactions_of_a_batch_in_nonce_order";
+        let creditors_of_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .creditors_of_pending_payables_params(&creditors_of_pending_payables_params_arc)
+            .creditors_of_pending_payables_result(HashMap::from([
+                (4, make_wallet("creditor_4")),
+                (5, make_wallet("creditor_5")),
+            ]));
+        let mut config = make_bc_with_defaults();
+        config.gas_bump_ladder_opt = Some(GasBumpLadder {
+            step_percent: 20,
+            max_steps: 3,
+            interval_sec: 100,
         });
         let mut subject = AccountantBuilder::default()
             .bootstrapper_config(config)
-            .consuming_wallet(consuming_wallet.clone())
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
             .logger(Logger::new(test_name))
             .build();
-        subject.scanners.payable = Box::new(payable_scanner);
-        subject.scanners.pending_payable = Box::new(NullScanner::new()); //skipping
-        subject.scanners.receivable = Box::new(NullScanner::new()); //skipping
-        subject.scan_schedulers.update_scheduler(
-            ScanType::Payables,
-            Some(Box::new(
-                NotifyLaterHandleMock::default()
-                    .notify_later_params(&notify_later_payables_params_arc)
-                    .capture_msg_and_let_it_fly_on(),
-            )),
-            None,
-        );
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        subject.replace_pending_payable_sub_opt = Some(blockchain_bridge.start().recipient());
         let subject_addr = subject.start();
-        let subject_subs = Accountant::make_subs_from(&subject_addr);
-        let peer_actors = peer_actors_builder().build();
-        send_bind_message!(subject_subs, peer_actors);
-
-        send_start_message!(subject_subs);
-
-        let time_before = SystemTime::now();
-        system.run();
-        let time_after = SystemTime::now();
-        //the second attempt is the one where the queue is empty and System::current.stop() ends the cycle
-        let notify_later_payables_params = notify_later_payables_params_arc.lock().unwrap();
-        TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: {test_name}: There was nothing to process during Payables scan."
-        ));
-        let mut begin_scan_params = begin_scan_params_arc.lock().unwrap();
-        let (
-            first_attempt_wallet,
-            first_attempt_timestamp,
-            first_attempt_response_skeleton_opt,
-            first_attempt_logger,
-        ) = begin_scan_params.remove(0);
-        let (
-            second_attempt_wallet,
-            second_attempt_timestamp,
-            second_attempt_response_skeleton_opt,
-            second_attempt_logger,
-        ) = begin_scan_params.remove(0);
-        assert_eq!(first_attempt_wallet, second_attempt_wallet);
-        assert_eq!(second_attempt_wallet, consuming_wallet);
-        assert!(time_before <= first_attempt_timestamp);
-        assert!(first_attempt_timestamp <= second_attempt_timestamp);
-        assert!(second_attempt_timestamp <= time_after);
-        assert_eq!(first_attempt_response_skeleton_opt, None);
-        assert_eq!(second_attempt_response_skeleton_opt, None);
-        debug!(first_attempt_logger, "first attempt");
-        debug!(second_attempt_logger, "second attempt");
-        let tlh = TestLogHandler::new();
-        tlh.exists_log_containing(&format!("DEBUG: {test_name}: first attempt"));
-        tlh.exists_log_containing(&format!("DEBUG: {test_name}: second attempt"));
-        assert_eq!(
-            *notify_later_payables_params,
-            vec![
-                (
-                    ScanForPayables {
-                        response_skeleton_opt: None
-                    },
-                    Duration::from_millis(97)
-                ),
-                (
-                    ScanForPayables {
-                        response_skeleton_opt: None
-                    },
-                    Duration::from_millis(97)
-                ),
-            ]
-        )
-    }
-
-    #[test]
-    fn payable_scan_is_not_initiated_if_consuming_wallet_is_not_found() {
-        init_test_logging();
-        let test_name = "payable_scan_is_not_initiated_if_consuming_wallet_is_not_found";
-        let mut subject = AccountantBuilder::default().build();
-        subject.consuming_wallet_opt = None;
-        subject.logger = Logger::new(test_name);
+        let batch_timestamp = SystemTime::now().sub(Duration::from_secs(150));
+        let make_fingerprint = |rowid: u64, nonce: u64| PendingPayableFingerprint {
+            rowid,
+            timestamp: batch_timestamp,
+            hash: make_tx_hash(rowid as u32),
+            attempt: 3,
+            amount: rowid as u128 * 1_000,
+            process_error: None,
+            replacement_basis_opt: Some(ReplacementBasis {
+                nonce,
+                gas_price_wei: 2_000_000_000,
+            }),
+        };
+        let fingerprint_4 = make_fingerprint(4, 11);
+        let fingerprint_5 = make_fingerprint(5, 10);
+        let fingerprint_6 = make_fingerprint(6, 12);
+        let pending = |fingerprint: &PendingPayableFingerprint| {
+            (
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    transaction_hash: fingerprint.hash,
+                    status: TxStatus::Pending,
+                }),
+                fingerprint.clone(),
+            )
+        };
+        let msg = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![
+                pending(&fingerprint_4),
+                pending(&fingerprint_5),
+                pending(&fingerprint_6),
+            ],
+            response_skeleton_opt: None,
+        };
 
-        subject.handle_request_of_scan_for_payable(None);
+        subject_addr.try_send(msg).unwrap();
 
-        let has_scan_started = subject.scanners.payable.scan_started_at().is_some();
-        assert_eq!(has_scan_started, false);
+        let system = System::new(test_name);
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            *creditors_of_pending_payables_params_arc.lock().unwrap(),
+            vec![vec![5, 4, 6]]
+        );
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        let expected_replacement =
+            |fingerprint: &PendingPayableFingerprint, wallet: Wallet| ReplacePendingPayable {
+                fingerprint: fingerprint.clone(),
+                accounts: vec![PayableAccount {
+                    wallet,
+                    balance_wei: fingerprint.amount,
+                    last_paid_timestamp: batch_timestamp,
+                    pending_payable_opt: None,
+                }],
+                gas_price_bump_percent: 20,
+                response_skeleton_opt: None,
+            };
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<ReplacePendingPayable>(0),
+            &expected_replacement(&fingerprint_5, make_wallet("creditor_5"))
+        );
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<ReplacePendingPayable>(1),
+            &expected_replacement(&fingerprint_4, make_wallet("creditor_4"))
+        );
+        assert_eq!(blockchain_bridge_recording.len(), 2);
         TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: {test_name}: Cannot initiate Payables scan because no consuming wallet was found."
+            "WARN: {test_name}: Transaction {:?} is stuck, but the creditor it pays is unknown; \
+             it will not be outbid",
+            fingerprint_6.hash
         ));
     }
 
     #[test]
-    fn pending_payable_scan_is_not_initiated_if_consuming_wallet_is_not_found() {
+    fn idle_node_skips_scheduled_payable_scans_but_not_demanded_ones() {
         init_test_logging();
-        let test_name = "pending_payable_scan_is_not_initiated_if_consuming_wallet_is_not_found";
-        let mut subject = AccountantBuilder::default().build();
-        subject.consuming_wallet_opt = None;
-        subject.logger = Logger::new(test_name);
+        let test_name = "idle_node_skips_scheduled_payable_scans_but_not_demanded_ones";
+        let deadline_lookup_params_arc = Arc::new(Mutex::new(vec![]));
+        let scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let body_payable_dao = PayableDaoMock::new()
+            .non_pending_payables_params(&deadline_lookup_params_arc)
+            .non_pending_payables_result(vec![]);
+        let scanner_payable_dao = PayableDaoMock::new()
+            .non_pending_payables_params(&scan_params_arc)
+            .non_pending_payables_result(vec![]);
+        let freeze = IdleSpendingFreeze {
+            idle_sec: 3_600,
+            deadline_margin_sec: 600,
+        };
+        let mut config = bc_from_earning_wallet(make_wallet("earning"));
+        config.idle_spending_freeze_opt = Some(freeze);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .payable_daos(vec![
+                ForAccountantBody(body_payable_dao),
+                ForPayableScanner(scanner_payable_dao),
+            ])
+            .logger(Logger::new(test_name))
+            .build();
+        subject.idle_spending_gate = IdleSpendingGate::new(
+            Some(freeze),
+            SystemTime::now().sub(Duration::from_secs(3_600)),
+        );
 
-        subject.handle_request_of_scan_for_pending_payable(None);
+        subject.handle_request_of_scan_for_payable(None);
+        subject.handle_request_of_scan_for_payable(Some(ResponseSkeleton {
+            client_id: 1234,
+            context_id: 4321,
+        }));
 
-        let has_scan_started = subject.scanners.pending_payable.scan_started_at().is_some();
-        assert_eq!(has_scan_started, false);
+        assert_eq!(*deadline_lookup_params_arc.lock().unwrap(), vec![()]);
+        assert_eq!(*scan_params_arc.lock().unwrap(), vec![()]);
         TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: {test_name}: Cannot initiate PendingPayables scan because no consuming wallet was found."
+            "DEBUG: {test_name}: Payable scan skipped; the Node is idle"
         ));
     }
 
     #[test]
-    fn start_message_triggers_no_scans_in_suppress_mode() {
+    fn accountant_handles_inserting_new_fingerprints() {
         init_test_logging();
-        let test_name = "start_message_triggers_no_scans_in_suppress_mode";
-        let system = System::new(test_name);
-        let mut config = bc_from_earning_wallet(make_wallet("hi"));
-        config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_millis(100),
-            receivable_scan_interval: Duration::from_millis(100),
-            pending_payable_scan_interval: Duration::from_millis(100),
-        });
-        config.suppress_initial_scans = true;
-        let peer_actors = peer_actors_builder().build();
+        let insert_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .insert_fingerprints_params(&insert_fingerprint_params_arc)
+            .insert_fingerprints_result(Ok(()));
         let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .logger(Logger::new(test_name))
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
             .build();
-        let subject_addr = subject.start();
-        let subject_subs = Accountant::make_subs_from(&subject_addr);
-        send_bind_message!(subject_subs, peer_actors);
+        let accountant_addr = subject.start();
+        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+        let timestamp = SystemTime::now();
+        let hash_1 = make_tx_hash(0x6c81c);
+        let amount_1 = 12345;
+        let hash_2 = make_tx_hash(0x1b207);
+        let amount_2 = 87654;
+        let hash_and_amount_1 = HashAndAmount {
+            hash: hash_1,
+            amount: amount_1,
+        };
+        let hash_and_amount_2 = HashAndAmount {
+            hash: hash_2,
+            amount: amount_2,
+        };
+        let init_params = vec![hash_and_amount_1, hash_and_amount_2];
+        let init_fingerprints_msg = PendingPayableFingerprintSeeds {
+            batch_wide_timestamp: timestamp,
+            hashes_and_balances: init_params.clone(),
+            batch_wide_gas_price_wei: 1_500_000_000,
+            first_nonce: 44,
+            creditor_shares: vec![],
+        };
 
-        send_start_message!(subject_subs);
+        let _ = accountant_subs
+            .init_pending_payable_fingerprints
+            .try_send(init_fingerprints_msg)
+            .unwrap();
 
+        let system = System::new("ordering payment fingerprint test");
         System::current().stop();
         assert_eq!(system.run(), 0);
-        // no panics because of recalcitrant DAOs; therefore DAOs were not called; therefore test passes
+        let insert_fingerprint_params = insert_fingerprint_params_arc.lock().unwrap();
+        assert_eq!(
+            *insert_fingerprint_params,
+            vec![(
+                vec![hash_and_amount_1, hash_and_amount_2],
+                timestamp,
+                ReplacementBasis {
+                    nonce: 44,
+                    gas_price_wei: 1_500_000_000
+                }
+            )]
+        );
         TestLogHandler::new().exists_log_containing(
-            &format!("{test_name}: Started with --scans off; declining to begin database and blockchain scans"),
+            "DEBUG: Accountant: Saved new pending payable fingerprints for: \
+             0x000000000000000000000000000000000000000000000000000000000006c81c, 0x000000000000000000000000000000000000000000000000000000000001b207",
         );
     }
 
     #[test]
-    fn scan_for_payables_message_does_not_trigger_payment_for_balances_below_the_curve() {
+    fn payable_fingerprint_insertion_clearly_failed_and_we_log_it_at_least() {
+        //despite it doesn't end so here this event would be a cause of a later panic
         init_test_logging();
-        let consuming_wallet = make_paying_wallet(b"consuming wallet");
-        let payment_thresholds = PaymentThresholds {
-            threshold_interval_sec: 2_592_000,
-            debt_threshold_gwei: 1_000_000_000,
-            payment_grace_period_sec: 86_400,
-            maturity_threshold_sec: 86_400,
-            permanent_debt_allowed_gwei: 10_000_000,
-            unban_below_gwei: 10_000_000,
+        let insert_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .insert_fingerprints_params(&insert_fingerprint_params_arc)
+            .insert_fingerprints_result(Err(PendingPayableDaoError::InsertionFailed(
+                "Crashed".to_string(),
+            )));
+        let amount = 2345;
+        let transaction_hash = make_tx_hash(0x1c8);
+        let hash_and_amount = HashAndAmount {
+            hash: transaction_hash,
+            amount,
         };
-        let config = bc_from_earning_wallet(make_wallet("mine"));
-        let now = to_time_t(SystemTime::now());
-        let accounts = vec![
-            // below minimum balance, to the right of time intersection (inside buffer zone)
-            PayableAccount {
-                wallet: make_wallet("wallet0"),
-                balance_wei: gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei - 1),
-                last_paid_timestamp: from_time_t(
-                    now - checked_conversion::<u64, i64>(
-                        payment_thresholds.threshold_interval_sec + 10,
-                    ),
-                ),
-                pending_payable_opt: None,
-            },
-            // above balance intersection, to the left of minimum time (outside buffer zone)
-            PayableAccount {
-                wallet: make_wallet("wallet1"),
-                balance_wei: gwei_to_wei(payment_thresholds.debt_threshold_gwei + 1),
-                last_paid_timestamp: from_time_t(
-                    now - checked_conversion::<u64, i64>(
-                        payment_thresholds.maturity_threshold_sec - 10,
-                    ),
-                ),
-                pending_payable_opt: None,
-            },
-            // above minimum balance, to the right of minimum time (not in buffer zone, below the curve)
-            PayableAccount {
-                wallet: make_wallet("wallet2"),
-                balance_wei: gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei + 55),
-                last_paid_timestamp: from_time_t(
-                    now - checked_conversion::<u64, i64>(
-                        payment_thresholds.maturity_threshold_sec + 15,
-                    ),
-                ),
-                pending_payable_opt: None,
-            },
-        ];
-        let payable_dao = PayableDaoMock::new()
-            .non_pending_payables_result(accounts.clone())
-            .non_pending_payables_result(vec![]);
-        let (blockchain_bridge, _, blockchain_bridge_recordings_arc) = make_recorder();
-        let system = System::new(
-            "scan_for_payable_message_does_not_trigger_payment_for_balances_below_the_curve",
-        );
-        let blockchain_bridge_addr: Addr<Recorder> = blockchain_bridge.start();
-        let outbound_payments_instructions_sub =
-            blockchain_bridge_addr.recipient::<OutboundPaymentsInstructions>();
-        let mut subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .build();
-        subject.outbound_payments_instructions_sub_opt = Some(outbound_payments_instructions_sub);
-
-        let _result = subject.scanners.payable.begin_scan(
-            consuming_wallet,
-            SystemTime::now(),
-            None,
-            &subject.logger,
-        );
-
-        System::current().stop();
-        system.run();
-        let blockchain_bridge_recordings = blockchain_bridge_recordings_arc.lock().unwrap();
-        assert_eq!(blockchain_bridge_recordings.len(), 0);
-    }
-
-    #[test]
-    fn scan_for_payable_message_triggers_payment_for_balances_over_the_curve() {
-        init_test_logging();
-        let mut config = bc_from_earning_wallet(make_wallet("mine"));
-        let consuming_wallet = make_paying_wallet(b"consuming");
-        config.scan_intervals_opt = Some(ScanIntervals {
-            pending_payable_scan_interval: Duration::from_secs(50_000),
-            payable_scan_interval: Duration::from_secs(50_000),
-            receivable_scan_interval: Duration::from_secs(50_000),
-        });
-        let now = to_time_t(SystemTime::now());
-        let qualified_payables = vec![
-            // slightly above minimum balance, to the right of the curve (time intersection)
-            PayableAccount {
-                wallet: make_wallet("wallet0"),
-                balance_wei: gwei_to_wei(
-                    DEFAULT_PAYMENT_THRESHOLDS.permanent_debt_allowed_gwei + 1,
-                ),
-                last_paid_timestamp: from_time_t(
-                    now - checked_conversion::<u64, i64>(
-                        DEFAULT_PAYMENT_THRESHOLDS.threshold_interval_sec
-                            + DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec
-                            + 10,
-                    ),
-                ),
-                pending_payable_opt: None,
-            },
-            // slightly above the curve (balance intersection), to the right of minimum time
-            PayableAccount {
-                wallet: make_wallet("wallet1"),
-                balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
-                last_paid_timestamp: from_time_t(
-                    now - checked_conversion::<u64, i64>(
-                        DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 10,
-                    ),
-                ),
-                pending_payable_opt: None,
-            },
-        ];
-        let payable_dao =
-            PayableDaoMock::default().non_pending_payables_result(qualified_payables.clone());
-        let (blockchain_bridge, _, blockchain_bridge_recordings_arc) = make_recorder();
-        let blockchain_bridge = blockchain_bridge
-            .system_stop_conditions(match_every_type_id!(QualifiedPayablesMessage));
-        let system =
-            System::new("scan_for_payable_message_triggers_payment_for_balances_over_the_curve");
-        let peer_actors = peer_actors_builder()
-            .blockchain_bridge(blockchain_bridge)
-            .build();
-        let mut subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .consuming_wallet(consuming_wallet.clone())
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
+        let subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
             .build();
-        subject.scanners.pending_payable = Box::new(NullScanner::new());
-        subject.scanners.receivable = Box::new(NullScanner::new());
-        let subject_addr = subject.start();
-        let accountant_subs = Accountant::make_subs_from(&subject_addr);
-        send_bind_message!(accountant_subs, peer_actors);
+        let timestamp = SystemTime::now();
+        let report_new_fingerprints = PendingPayableFingerprintSeeds {
+            batch_wide_timestamp: timestamp,
+            hashes_and_balances: vec![hash_and_amount],
+            batch_wide_gas_price_wei: 2_000_000_000,
+            first_nonce: 3,
+            creditor_shares: vec![],
+        };
 
-        send_start_message!(accountant_subs);
+        let _ = subject.handle_new_pending_payable_fingerprints(report_new_fingerprints);
 
-        system.run();
-        let blockchain_bridge_recordings = blockchain_bridge_recordings_arc.lock().unwrap();
-        let message = blockchain_bridge_recordings.get_record::<QualifiedPayablesMessage>(0);
+        let insert_fingerprint_params = insert_fingerprint_params_arc.lock().unwrap();
         assert_eq!(
-            message,
-            &QualifiedPayablesMessage {
-                protected_qualified_payables: protect_payables_in_test(qualified_payables),
-                consuming_wallet,
-                response_skeleton_opt: None,
-            }
+            *insert_fingerprint_params,
+            vec![(
+                vec![hash_and_amount],
+                timestamp,
+                ReplacementBasis {
+                    nonce: 3,
+                    gas_price_wei: 2_000_000_000
+                }
+            )]
         );
+        TestLogHandler::new().exists_log_containing("ERROR: Accountant: Failed to process \
+         new pending payable fingerprints due to 'InsertionFailed(\"Crashed\")', disabling the automated \
+          confirmation for all these transactions: 0x00000000000000000000000000000000000000000000000000000000000001c8");
     }
 
     #[test]
-    fn accountant_does_not_initiate_another_scan_if_one_is_already_running() {
-        init_test_logging();
-        let test_name = "accountant_does_not_initiate_another_scan_if_one_is_already_running";
-        let payable_dao = PayableDaoMock::default();
-        let (blockchain_bridge, _, blockchain_bridge_recording) = make_recorder();
-        let blockchain_bridge_addr = blockchain_bridge
-            .system_stop_conditions(match_every_type_id!(
-                QualifiedPayablesMessage,
-                QualifiedPayablesMessage
-            ))
-            .start();
-        let pps_for_blockchain_bridge_sub = blockchain_bridge_addr.clone().recipient();
-        let last_paid_timestamp = to_time_t(SystemTime::now())
-            - DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec as i64
-            - 1;
-        let payable_account = PayableAccount {
-            wallet: make_wallet("scan_for_payables"),
-            balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 1),
-            last_paid_timestamp: from_time_t(last_paid_timestamp),
-            pending_payable_opt: None,
+    fn accountant_records_shares_of_creditors_paid_by_a_single_transaction() {
+        let insert_creditor_shares_params_arc = Arc::new(Mutex::new(vec![]));
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .insert_fingerprints_result(Ok(()))
+            .insert_creditor_shares_params(&insert_creditor_shares_params_arc)
+            .insert_creditor_shares_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .build();
+        let hash = make_tx_hash(0x2d5);
+        let share_1 = CreditorShare {
+            hash,
+            wallet: make_wallet("creditor_1"),
+            amount: 1_000,
         };
-        let payable_dao = payable_dao
-            .non_pending_payables_result(vec![payable_account.clone()])
-            .non_pending_payables_result(vec![payable_account]);
-        let config = bc_from_earning_wallet(make_wallet("mine"));
-        let system = System::new(test_name);
+        let share_2 = CreditorShare {
+            hash,
+            wallet: make_wallet("creditor_2"),
+            amount: 2_345,
+        };
+        let report_new_fingerprints = PendingPayableFingerprintSeeds {
+            batch_wide_timestamp: SystemTime::now(),
+            hashes_and_balances: vec![HashAndAmount {
+                hash,
+                amount: 3_345,
+            }],
+            batch_wide_gas_price_wei: 2_000_000_000,
+            first_nonce: 3,
+            creditor_shares: vec![share_1.clone(), share_2.clone()],
+        };
+
+        let _ = subject.handle_new_pending_payable_fingerprints(report_new_fingerprints);
+
+        let insert_creditor_shares_params = insert_creditor_shares_params_arc.lock().unwrap();
+        assert_eq!(*insert_creditor_shares_params, vec![vec![share_1, share_2]]);
+    }
+
+    #[test]
+    fn accountant_logs_failure_to_record_shares_of_creditors() {
+        init_test_logging();
+        let test_name = "accountant_logs_failure_to_record_shares_of_creditors";
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .insert_fingerprints_result(Ok(()))
+            .insert_creditor_shares_result(Err(PendingPayableDaoError::InsertionFailed(
+                "Crashed".to_string(),
+            )));
         let mut subject = AccountantBuilder::default()
-            .consuming_wallet(make_paying_wallet(b"consuming"))
-            .logger(Logger::new(test_name))
-            .payable_daos(vec![ForPayableScanner(payable_dao)])
-            .bootstrapper_config(config)
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
             .build();
-        let message_before = ScanForPayables {
-            response_skeleton_opt: Some(ResponseSkeleton {
-                client_id: 111,
-                context_id: 222,
-            }),
-        };
-        let message_after = ScanForPayables {
-            response_skeleton_opt: Some(ResponseSkeleton {
-                client_id: 333,
-                context_id: 444,
-            }),
+        subject.logger = Logger::new(test_name);
+        let hash = make_tx_hash(0x2d6);
+        let creditor_wallet = make_wallet("creditor");
+        let report_new_fingerprints = PendingPayableFingerprintSeeds {
+            batch_wide_timestamp: SystemTime::now(),
+            hashes_and_balances: vec![HashAndAmount { hash, amount: 500 }],
+            batch_wide_gas_price_wei: 2_000_000_000,
+            first_nonce: 3,
+            creditor_shares: vec![CreditorShare {
+                hash,
+                wallet: creditor_wallet.clone(),
+                amount: 500,
+            }],
         };
-        subject.qualified_payables_sub_opt = Some(pps_for_blockchain_bridge_sub);
-        let addr = subject.start();
-        addr.try_send(message_before.clone()).unwrap();
 
-        addr.try_send(ScanForPayables {
-            response_skeleton_opt: None,
-        })
-        .unwrap();
+        let _ = subject.handle_new_pending_payable_fingerprints(report_new_fingerprints);
 
-        // We ignored the second ScanForPayables message because the first message meant a scan
-        // was already in progress; now let's make it look like that scan has ended so that we
-        // can prove the next message will start another one.
-        addr.try_send(AssertionsMessage {
-            assertions: Box::new(|accountant: &mut Accountant| {
-                accountant
-                    .scanners
-                    .payable
-                    .mark_as_ended(&Logger::new("irrelevant"))
-            }),
-        })
-        .unwrap();
-        addr.try_send(message_after.clone()).unwrap();
-        system.run();
-        let recording = blockchain_bridge_recording.lock().unwrap();
-        let messages_received = recording.len();
-        assert_eq!(messages_received, 2);
-        let first_message: &QualifiedPayablesMessage = recording.get_record(0);
-        assert_eq!(
-            first_message.response_skeleton_opt,
-            message_before.response_skeleton_opt
-        );
-        let second_message: &QualifiedPayablesMessage = recording.get_record(1);
-        assert_eq!(
-            second_message.response_skeleton_opt,
-            message_after.response_skeleton_opt
-        );
         TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: {}: Payables scan was already initiated",
-            test_name
+            "ERROR: {test_name}: Failed to record the shares of creditors paid by a single \
+             transaction due to 'InsertionFailed(\"Crashed\")'; once confirmed, each of them \
+             would be debited the whole transaction: {:?} to {}",
+            hash, creditor_wallet
         ));
     }
 
     #[test]
-    fn scan_for_pending_payables_finds_still_pending_payables() {
+    fn accountant_records_replacement_of_pending_payable_transaction() {
         init_test_logging();
-        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
-        let blockchain_bridge_addr = blockchain_bridge
-            .system_stop_conditions(match_every_type_id!(RequestTransactionReceipts))
-            .start();
-        let payable_fingerprint_1 = PendingPayableFingerprint {
-            rowid: 555,
-            timestamp: from_time_t(210_000_000),
-            hash: make_tx_hash(45678),
-            attempt: 1,
-            amount: 4444,
-            process_error: None,
-        };
-        let payable_fingerprint_2 = PendingPayableFingerprint {
-            rowid: 550,
-            timestamp: from_time_t(210_000_100),
-            hash: make_tx_hash(112233),
-            attempt: 2,
-            amount: 7999,
-            process_error: None,
-        };
+        let record_replacement_params_arc = Arc::new(Mutex::new(vec![]));
         let pending_payable_dao = PendingPayableDaoMock::default()
-            .return_all_errorless_fingerprints_result(vec![
-                payable_fingerprint_1.clone(),
-                payable_fingerprint_2.clone(),
-            ]);
-        let config = bc_from_earning_wallet(make_wallet("mine"));
-        let system = System::new("pending payable scan");
-        let mut subject = AccountantBuilder::default()
-            .consuming_wallet(make_paying_wallet(b"consuming"))
-            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
-            .bootstrapper_config(config)
+            .record_replacement_params(&record_replacement_params_arc)
+            .record_replacement_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
             .build();
+        let accountant_addr = subject.start();
+        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+        let hash = make_tx_hash(0x2ab);
+        let basis = ReplacementBasis {
+            nonce: 12,
+            gas_price_wei: 3_300_000_000,
+        };
 
-        subject.request_transaction_receipts_subs_opt = Some(blockchain_bridge_addr.recipient());
-        let account_addr = subject.start();
-
-        let _ = account_addr
-            .try_send(ScanForPendingPayables {
-                response_skeleton_opt: None,
+        let _ = accountant_subs
+            .report_pending_payable_replacement
+            .try_send(PendingPayableReplacement {
+                rowid: 4,
+                hash,
+                basis,
             })
             .unwrap();
 
-        system.run();
-        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
-        assert_eq!(blockchain_bridge_recording.len(), 1);
-        let received_msg = blockchain_bridge_recording.get_record::<RequestTransactionReceipts>(0);
-        assert_eq!(
-            received_msg,
-            &RequestTransactionReceipts {
-                pending_payable: vec![payable_fingerprint_1, payable_fingerprint_2],
+        let system = System::new("recording pending payable replacement test");
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+        let record_replacement_params = record_replacement_params_arc.lock().unwrap();
+        assert_eq!(*record_replacement_params, vec![(4, hash, basis)]);
+        TestLogHandler::new().exists_log_containing(
+            "DEBUG: Accountant: Pending payable fingerprint 4 now tracks transaction \
+             0x00000000000000000000000000000000000000000000000000000000000002ab with nonce 12 and \
+             gas price 3300000000 wei",
+        );
+    }
+
+    #[test]
+    fn failure_to_record_replacement_of_pending_payable_transaction_is_logged() {
+        init_test_logging();
+        let pending_payable_dao = PendingPayableDaoMock::default().record_replacement_result(Err(
+            PendingPayableDaoError::UpdateFailed("Crashed".to_string()),
+        ));
+        let subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .build();
+
+        subject.handle_pending_payable_replacement(PendingPayableReplacement {
+            rowid: 4,
+            hash: make_tx_hash(0x2ab),
+            basis: ReplacementBasis {
+                nonce: 12,
+                gas_price_wei: 3_300_000_000,
+            },
+        });
+
+        TestLogHandler::new().exists_log_containing(
+            "ERROR: Accountant: Failed to record transaction \
+             0x00000000000000000000000000000000000000000000000000000000000002ab at pending payable \
+             fingerprint 4 due to 'UpdateFailed(\"Crashed\")'; its confirmation will not be \
+             recognized",
+        );
+    }
+
+    const EXAMPLE_RESPONSE_SKELETON: ResponseSkeleton = ResponseSkeleton {
+        client_id: 1234,
+        context_id: 4321,
+    };
+
+    const EXAMPLE_ERROR_MSG: &str = "My tummy hurts";
+
+    #[test]
+    fn handling_scan_error_for_externally_triggered_payables() {
+        assert_scan_error_is_handled_properly(
+            "handling_scan_error_for_externally_triggered_payables",
+            ScanError {
+                scan_type: ScanType::Payables,
+                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            },
+        );
+    }
+
+    #[test]
+    fn handling_scan_error_for_externally_triggered_pending_payables() {
+        assert_scan_error_is_handled_properly(
+            "handling_scan_error_for_externally_triggered_pending_payables",
+            ScanError {
+                scan_type: ScanType::PendingPayables,
+                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            },
+        );
+    }
+
+    #[test]
+    fn handling_scan_error_for_externally_triggered_receivables() {
+        assert_scan_error_is_handled_properly(
+            "handling_scan_error_for_externally_triggered_receivables",
+            ScanError {
+                scan_type: ScanType::Receivables,
+                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            },
+        );
+    }
+
+    #[test]
+    fn handling_scan_error_for_internally_triggered_payables() {
+        assert_scan_error_is_handled_properly(
+            "handling_scan_error_for_internally_triggered_payables",
+            ScanError {
+                scan_type: ScanType::Payables,
+                response_skeleton_opt: None,
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            },
+        );
+    }
+
+    #[test]
+    fn handling_scan_error_for_internally_triggered_pending_payables() {
+        assert_scan_error_is_handled_properly(
+            "handling_scan_error_for_internally_triggered_pending_payables",
+            ScanError {
+                scan_type: ScanType::PendingPayables,
+                response_skeleton_opt: None,
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            },
+        );
+    }
+
+    #[test]
+    fn handling_scan_error_for_internally_triggered_receivables() {
+        assert_scan_error_is_handled_properly(
+            "handling_scan_error_for_internally_triggered_receivables",
+            ScanError {
+                scan_type: ScanType::Receivables,
                 response_skeleton_opt: None,
-            }
+                msg: EXAMPLE_ERROR_MSG.to_string(),
+                retryable: false,
+            },
         );
-        let log_handler = TestLogHandler::new();
-        log_handler.exists_log_containing("DEBUG: Accountant: Found 2 pending payables to process");
     }
 
     #[test]
-    fn report_routing_service_provided_message_is_received() {
+    fn scheduled_scan_failing_for_transient_reason_is_retried_with_growing_delay() {
         init_test_logging();
-        let now = SystemTime::now();
-        let bootstrapper_config = bc_from_earning_wallet(make_wallet("hi"));
-        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
-        let receivable_dao_mock = ReceivableDaoMock::new()
-            .more_money_receivable_parameters(&more_money_receivable_parameters_arc)
-            .more_money_receivable_result(Ok(()));
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(bootstrapper_config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
-            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+        let test_name = "scheduled_scan_failing_for_transient_reason_is_retried_with_growing_delay";
+        let notify_later_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut config = bc_from_earning_wallet(make_wallet("earning"));
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_secs(100),
+            receivable_scan_interval: Duration::from_secs(100),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .logger(Logger::new(test_name))
             .build();
-        let system = System::new("report_routing_service_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
+        subject.scan_retry_handle = Box::new(
+            NotifyLaterHandleMock::default().notify_later_params(&notify_later_params_arc),
+        );
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+        let scan_error = |scan_type| ScanError {
+            scan_type,
+            response_skeleton_opt: None,
+            msg: EXAMPLE_ERROR_MSG.to_string(),
+            retryable: true,
+        };
+
         subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
-            })
+            .try_send(scan_error(ScanType::Receivables))
             .unwrap();
-
-        let paying_wallet = make_wallet("booga");
         subject_addr
-            .try_send(ReportRoutingServiceProvidedMessage {
-                timestamp: now,
-                paying_wallet: paying_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
-            })
+            .try_send(scan_error(ScanType::Receivables))
+            .unwrap();
+        subject_addr
+            .try_send(scan_error(ScanType::PendingPayables))
+            .unwrap();
+        subject_addr
+            .try_send(scan_error(ScanType::Receivables))
             .unwrap();
 
-        System::current().stop_with_code(0);
+        System::current().stop();
         system.run();
-        let more_money_receivable_parameters = more_money_receivable_parameters_arc.lock().unwrap();
+        let notify_later_params = notify_later_params_arc.lock().unwrap();
         assert_eq!(
-            more_money_receivable_parameters[0],
-            (now, make_wallet("booga"), (1 * 42) + (1234 * 24))
+            *notify_later_params,
+            vec![
+                (
+                    RetryScan {
+                        scan_type: ScanType::Receivables
+                    },
+                    Duration::from_secs(10)
+                ),
+                (
+                    RetryScan {
+                        scan_type: ScanType::Receivables
+                    },
+                    Duration::from_secs(20)
+                ),
+                (
+                    RetryScan {
+                        scan_type: ScanType::PendingPayables
+                    },
+                    Duration::from_secs(10)
+                ),
+                (
+                    RetryScan {
+                        scan_type: ScanType::Receivables
+                    },
+                    Duration::from_secs(40)
+                ),
+            ]
         );
         TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: Accountant: Charging routing of 1234 bytes to wallet {}",
-            paying_wallet
+            "DEBUG: {test_name}: Receivables scan failed for a transient reason; retrying in 40 s"
         ));
     }
 
     #[test]
-    fn report_routing_service_provided_message_is_received_from_our_consuming_wallet() {
+    fn scan_is_not_retried_early_after_permanent_failure_or_if_regular_scan_comes_sooner() {
         init_test_logging();
-        let consuming_wallet = make_wallet("our consuming wallet");
-        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("our earning wallet"));
-        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
-        let receivable_dao_mock = ReceivableDaoMock::new()
-            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
-        let subject = AccountantBuilder::default()
+        let test_name =
+            "scan_is_not_retried_early_after_permanent_failure_or_if_regular_scan_comes_sooner";
+        let notify_later_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut config = bc_from_earning_wallet(make_wallet("earning"));
+        config.scan_intervals_opt = Some(ScanIntervals {
+            payable_scan_interval: Duration::from_secs(100),
+            receivable_scan_interval: Duration::from_secs(15),
+            pending_payable_scan_interval: Duration::from_secs(100),
+        });
+        let mut subject = AccountantBuilder::default()
             .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
-            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .logger(Logger::new(test_name))
             .build();
-        let system = System::new("report_routing_service_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
-        subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
-            })
-            .unwrap();
+        subject.scan_retry_handle = Box::new(
+            NotifyLaterHandleMock::default().notify_later_params(&notify_later_params_arc),
+        );
+        let system = System::new(test_name);
+        let subject_addr = subject.start();
+        let scan_error = |retryable| ScanError {
+            scan_type: ScanType::Receivables,
+            response_skeleton_opt: None,
+            msg: EXAMPLE_ERROR_MSG.to_string(),
+            retryable,
+        };
 
-        subject_addr
-            .try_send(ReportRoutingServiceProvidedMessage {
-                timestamp: SystemTime::now(),
-                paying_wallet: consuming_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
-            })
-            .unwrap();
+        subject_addr.try_send(scan_error(true)).unwrap();
+        subject_addr.try_send(scan_error(true)).unwrap();
+        subject_addr.try_send(scan_error(false)).unwrap();
+        subject_addr.try_send(scan_error(true)).unwrap();
 
-        System::current().stop_with_code(0);
+        System::current().stop();
         system.run();
-        assert!(more_money_receivable_parameters_arc
-            .lock()
-            .unwrap()
-            .is_empty());
-
+        let notify_later_params = notify_later_params_arc.lock().unwrap();
+        let expected_retry = (
+            RetryScan {
+                scan_type: ScanType::Receivables,
+            },
+            Duration::from_secs(10),
+        );
+        assert_eq!(
+            *notify_later_params,
+            vec![expected_retry.clone(), expected_retry]
+        );
         TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
-            consuming_wallet,
+            "DEBUG: {test_name}: Receivables scan failed 2 times in a row; leaving it to the regular schedule"
         ));
     }
 
     #[test]
-    fn report_routing_service_provided_message_is_received_from_our_earning_wallet() {
-        init_test_logging();
-        let earning_wallet = make_wallet("our earning wallet");
-        let config = bc_from_earning_wallet(earning_wallet.clone());
-        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
-        let receivable_dao_mock = ReceivableDaoMock::new()
-            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
-            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+    fn retry_scan_begins_scan_without_scheduling_another_one() {
+        let begin_scan_params_arc = Arc::new(Mutex::new(vec![]));
+        let notify_later_receivable_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_scanner = ScannerMock::new()
+            .begin_scan_params(&begin_scan_params_arc)
+            .begin_scan_result(Err(BeginScanError::NothingToProcess));
+        let earning_wallet = make_wallet("earning");
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(earning_wallet.clone()))
             .build();
-        let system = System::new("report_routing_service_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
+        subject.scanners.receivable = Box::new(receivable_scanner);
+        subject.scan_schedulers.update_scheduler(
+            ScanType::Receivables,
+            Some(Box::new(
+                NotifyLaterHandleMock::<ScanForReceivables>::default()
+                    .notify_later_params(&notify_later_receivable_params_arc),
+            )),
+            None,
+        );
+        let system = System::new("retry_scan_begins_scan_without_scheduling_another_one");
+        let subject_addr = subject.start();
+
         subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
+            .try_send(RetryScan {
+                scan_type: ScanType::Receivables,
             })
             .unwrap();
 
+        System::current().stop();
+        system.run();
+        let begin_scan_params = begin_scan_params_arc.lock().unwrap();
+        assert_eq!(begin_scan_params.len(), 1);
+        assert_eq!(begin_scan_params[0].0, earning_wallet);
+        assert_eq!(begin_scan_params[0].2, None);
+        let notify_later_receivable_params = notify_later_receivable_params_arc.lock().unwrap();
+        assert!(notify_later_receivable_params.is_empty());
+    }
+
+    #[test]
+    fn successful_scan_resets_retry_backoff() {
+        let receivable_scanner = ScannerMock::new().finish_scan_result(None);
+        let pending_payable_scanner = ScannerMock::new().finish_scan_result(None);
+        let mut subject = AccountantBuilder::default().build();
+        subject.scanners.receivable = Box::new(receivable_scanner);
+        subject.scanners.pending_payable = Box::new(pending_payable_scanner);
+        subject.consecutive_retryable_scan_failures = HashMap::from_iter([
+            (ScanType::Payables, 1),
+            (ScanType::PendingPayables, 2),
+            (ScanType::Receivables, 3),
+        ]);
+        let system = System::new("successful_scan_resets_retry_backoff");
+        let subject_addr = subject.start();
+
         subject_addr
-            .try_send(ReportRoutingServiceProvidedMessage {
+            .try_send(ReceivedPayments {
                 timestamp: SystemTime::now(),
-                paying_wallet: earning_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
+                new_start_block: BlockMarker::Value(1234),
+                transactions: vec![],
+                response_skeleton_opt: None,
+                rechecked_opt: None,
+                pending: vec![],
+            })
+            .unwrap();
+        subject_addr
+            .try_send(ReportTransactionReceipts {
+                fingerprints_with_receipts: vec![],
+                response_skeleton_opt: None,
             })
             .unwrap();
 
-        System::current().stop_with_code(0);
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(|actor: &mut Accountant| {
+                    assert_eq!(
+                        actor.consecutive_retryable_scan_failures,
+                        HashMap::from_iter([(ScanType::Payables, 1)])
+                    );
+                }),
+            })
+            .unwrap();
+        System::current().stop();
         system.run();
-        assert!(more_money_receivable_parameters_arc
-            .lock()
-            .unwrap()
-            .is_empty());
+    }
 
-        TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
-            earning_wallet,
-        ));
+    fn make_failed_fingerprint(rowid: u64, hash: H256) -> PendingPayableFingerprint {
+        PendingPayableFingerprint {
+            rowid,
+            timestamp: from_time_t(200_000_000),
+            hash,
+            attempt: 4,
+            amount: 1_234_000_000_000,
+            process_error: Some("ERROR".to_string()),
+            replacement_basis_opt: None,
+        }
     }
 
     #[test]
-    fn report_exit_service_provided_message_is_received() {
-        init_test_logging();
-        let now = SystemTime::now();
-        let config = bc_from_earning_wallet(make_wallet("hi"));
-        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
-        let receivable_dao_mock = ReceivableDaoMock::new()
-            .more_money_receivable_parameters(&more_money_receivable_parameters_arc)
-            .more_money_receivable_result(Ok(()));
+    fn retry_failed_payments_request_without_hashes_only_lists_unconfirmed_payments() {
+        let failed_fingerprint = make_failed_fingerprint(4, make_tx_hash(0x1c8));
+        let pending_fingerprint = PendingPayableFingerprint {
+            process_error: None,
+            ..make_failed_fingerprint(5, make_tx_hash(0x1c9))
+        };
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_failed_fingerprints_result(vec![failed_fingerprint.clone()])
+            .return_all_errorless_fingerprints_result(vec![pending_fingerprint.clone()]);
+        let system = System::new("test");
         let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
-            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
             .build();
-        let system = System::new("report_exit_service_provided_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
-        subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
-            })
-            .unwrap();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiRetryFailedPaymentsRequest {
+                transaction_hashes: vec![],
+                gas_price_bump_percent_opt: None,
+            }
+            .tmb(2222),
+        };
 
-        let paying_wallet = make_wallet("booga");
-        subject_addr
-            .try_send(ReportExitServiceProvidedMessage {
-                timestamp: now,
-                paying_wallet: paying_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
-            })
-            .unwrap();
+        subject_addr.try_send(ui_message).unwrap();
 
         System::current().stop();
         system.run();
-        let more_money_receivable_parameters = more_money_receivable_parameters_arc.lock().unwrap();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        let (body, context_id) = UiRetryFailedPaymentsResponse::fmb(response.body.clone()).unwrap();
+        assert_eq!(context_id, 2222);
         assert_eq!(
-            more_money_receivable_parameters[0],
-            (now, make_wallet("booga"), (1 * 42) + (1234 * 24))
+            body.payments,
+            remap_unconfirmed_payments(vec![failed_fingerprint, pending_fingerprint])
         );
-        TestLogHandler::new().exists_log_containing(&format!(
-            "DEBUG: Accountant: Charging exit service for 1234 bytes to wallet {}",
-            paying_wallet
-        ));
+        assert_eq!(body.resubmitted_hashes, Vec::<String>::new());
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
     }
 
     #[test]
-    fn report_exit_service_provided_message_is_received_from_our_consuming_wallet() {
+    fn retry_failed_payments_request_resubmits_chosen_payments_with_bumped_gas_price() {
         init_test_logging();
-        let consuming_wallet = make_wallet("my consuming wallet");
-        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("my earning wallet"));
-        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
-        let receivable_dao_mock = ReceivableDaoMock::new()
-            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
+        let test_name =
+            "retry_failed_payments_request_resubmits_chosen_payments_with_bumped_gas_price";
+        let release_failed_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let delete_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
+        let chosen_hash = make_tx_hash(0x1c8);
+        let chosen_fingerprint = make_failed_fingerprint(4, chosen_hash);
+        let other_fingerprint = make_failed_fingerprint(5, make_tx_hash(0x1c9));
+        let payable = make_payable_account(123);
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_failed_fingerprints_result(vec![other_fingerprint.clone(), chosen_fingerprint])
+            .delete_fingerprints_params(&delete_fingerprints_params_arc)
+            .delete_fingerprints_result(Ok(()))
+            .return_failed_fingerprints_result(vec![other_fingerprint.clone()])
+            .return_all_errorless_fingerprints_result(vec![]);
+        let payable_dao = PayableDaoMock::default()
+            .release_failed_payables_params(&release_failed_payables_params_arc)
+            .release_failed_payables_result(Ok(vec![payable.clone()]));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let system = System::new(test_name);
         let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
-            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+            .bootstrapper_config(bc_from_wallets(
+                consuming_wallet.clone(),
+                make_wallet("earning"),
+            ))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .logger(Logger::new(test_name))
             .build();
-        let system = System::new("report_exit_service_provided_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
-        subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
-            })
-            .unwrap();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiRetryFailedPaymentsRequest {
+                transaction_hashes: vec![format!("{:?}", chosen_hash)],
+                gas_price_bump_percent_opt: Some(15),
+            }
+            .tmb(2222),
+        };
+
+        subject_addr.try_send(ui_message).unwrap();
 
         subject_addr
-            .try_send(ReportExitServiceProvidedMessage {
-                timestamp: SystemTime::now(),
-                paying_wallet: consuming_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
+            .try_send(AssertionsMessage {
+                assertions: Box::new(|actor: &mut Accountant| {
+                    assert!(actor.scanners.payable.scan_started_at().is_some())
+                }),
             })
             .unwrap();
-
         System::current().stop();
         system.run();
-        assert!(more_money_receivable_parameters_arc
-            .lock()
-            .unwrap()
-            .is_empty());
-
+        let release_failed_payables_params = release_failed_payables_params_arc.lock().unwrap();
+        assert_eq!(*release_failed_payables_params, vec![vec![4]]);
+        let delete_fingerprints_params = delete_fingerprints_params_arc.lock().unwrap();
+        assert_eq!(*delete_fingerprints_params, vec![vec![4]]);
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<QualifiedPayablesMessage>(0),
+            &QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![payable]),
+                consuming_wallet,
+                gas_price_bump_percent_opt: Some(15),
+                scan_id: ScanId(1),
+                response_skeleton_opt: None,
+            }
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        let (body, context_id) = UiRetryFailedPaymentsResponse::fmb(response.body.clone()).unwrap();
+        assert_eq!(context_id, 2222);
+        assert_eq!(
+            body,
+            UiRetryFailedPaymentsResponse {
+                payments: remap_unconfirmed_payments(vec![other_fingerprint]),
+                resubmitted_hashes: vec![format!("{:?}", chosen_hash)],
+            }
+        );
         TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
-            consuming_wallet
+            "INFO: {test_name}: Resubmitting 1 payables of failed transactions {:?}",
+            chosen_hash
         ));
     }
 
-    #[test]
-    fn report_exit_service_provided_message_is_received_from_our_earning_wallet() {
-        init_test_logging();
-        let earning_wallet = make_wallet("my earning wallet");
-        let config = bc_from_earning_wallet(earning_wallet.clone());
-        let more_money_receivable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new().non_pending_payables_result(vec![]);
-        let receivable_dao_mock = ReceivableDaoMock::new()
-            .more_money_receivable_parameters(&more_money_receivable_parameters_arc);
-        let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
-            .receivable_daos(vec![ForAccountantBody(receivable_dao_mock)])
+    fn assert_retry_failed_payments_is_refused(
+        subject: Accountant,
+        transaction_hash: String,
+        expected_error: &str,
+    ) {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
             .build();
-        let system = System::new("report_exit_service_provided_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
-        subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
-            })
-            .unwrap();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiRetryFailedPaymentsRequest {
+                transaction_hashes: vec![transaction_hash],
+                gas_price_bump_percent_opt: None,
+            }
+            .tmb(2222),
+        };
 
-        subject_addr
-            .try_send(ReportExitServiceProvidedMessage {
-                timestamp: SystemTime::now(),
-                paying_wallet: earning_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
-            })
-            .unwrap();
+        subject_addr.try_send(ui_message).unwrap();
 
         System::current().stop();
         system.run();
-        assert!(more_money_receivable_parameters_arc
-            .lock()
-            .unwrap()
-            .is_empty());
-
-        TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a receivable against our wallet {} for service we provided",
-            earning_wallet,
-        ));
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: MessageBody {
+                    opcode: "retryFailedPayments".to_string(),
+                    path: MessagePath::Conversation(2222),
+                    payload: Err((PAYMENT_RETRY_ERROR, expected_error.to_string())),
+                }
+            }
+        );
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
     }
 
     #[test]
-    fn report_services_consumed_message_is_received() {
-        init_test_logging();
-        let config = make_bc_with_defaults();
-        let more_money_payable_params_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new()
-            .more_money_payable_params(more_money_payable_params_arc.clone())
-            .more_money_payable_result(Ok(()))
-            .more_money_payable_result(Ok(()))
-            .more_money_payable_result(Ok(()));
-        let mut subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+    fn retry_failed_payments_request_refuses_hash_of_no_failed_payment() {
+        let release_failed_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let pending_payable_dao =
+            PendingPayableDaoMock::default().return_failed_fingerprints_result(vec![
+                make_failed_fingerprint(4, make_tx_hash(0x1c8)),
+            ]);
+        let payable_dao = PayableDaoMock::default()
+            .release_failed_payables_params(&release_failed_payables_params_arc);
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
             .build();
-        subject.message_id_generator = Box::new(MessageIdGeneratorMock::default().id_result(123));
-        let system = System::new("report_services_consumed_message_is_received");
-        let subject_addr: Addr<Accountant> = subject.start();
-        subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
-            })
-            .unwrap();
-        let earning_wallet_exit = make_wallet("exit");
-        let earning_wallet_routing_1 = make_wallet("routing 1");
-        let earning_wallet_routing_2 = make_wallet("routing 2");
-        let timestamp = SystemTime::now();
-
-        subject_addr
-            .try_send(ReportServicesConsumedMessage {
-                timestamp,
-                exit: ExitServiceConsumed {
-                    earning_wallet: earning_wallet_exit.clone(),
-                    payload_size: 1200,
-                    service_rate: 120,
-                    byte_rate: 30,
-                },
-                routing_payload_size: 3456,
-                routing: vec![
-                    RoutingServiceConsumed {
-                        earning_wallet: earning_wallet_routing_1.clone(),
-                        service_rate: 42,
-                        byte_rate: 24,
-                    },
-                    RoutingServiceConsumed {
-                        earning_wallet: earning_wallet_routing_2.clone(),
-                        service_rate: 52,
-                        byte_rate: 33,
-                    },
-                ],
-            })
-            .unwrap();
+        let unknown_hash = make_tx_hash(0x1c9);
 
-        System::current().stop();
-        system.run();
-        let more_money_payable_params = more_money_payable_params_arc.lock().unwrap();
-        assert_eq!(
-            more_money_payable_params
-                .iter()
-                .map(|(timestamp, wallet, amount)| (timestamp, wallet, amount))
-                .collect::<Vec<_>>(),
-            vec![
-                (&timestamp, &earning_wallet_exit, &((1 * 120) + (1200 * 30))),
-                (
-                    &timestamp,
-                    &earning_wallet_routing_1,
-                    &((1 * 42) + (3456 * 24))
-                ),
-                (
-                    &timestamp,
-                    &earning_wallet_routing_2,
-                    &((1 * 52) + (3456 * 33))
-                )
-            ]
+        assert_retry_failed_payments_is_refused(
+            subject,
+            format!("{:?}", unknown_hash),
+            &format!("No failed payment with transaction hash {:?}", unknown_hash),
         );
-        let test_log_handler = TestLogHandler::new();
 
-        test_log_handler.exists_log_containing(&format!(
-            "DEBUG: Accountant: MsgId 123: Accruing debt to {} for consuming 1200 exited bytes",
-            earning_wallet_exit
-        ));
-        test_log_handler.exists_log_containing(&format!(
-            "DEBUG: Accountant: MsgId 123: Accruing debt to {} for consuming 3456 routed bytes",
-            earning_wallet_routing_1
-        ));
-        test_log_handler.exists_log_containing(&format!(
-            "DEBUG: Accountant: MsgId 123: Accruing debt to {} for consuming 3456 routed bytes",
-            earning_wallet_routing_2
-        ));
+        let release_failed_payables_params = release_failed_payables_params_arc.lock().unwrap();
+        assert!(release_failed_payables_params.is_empty());
     }
 
-    fn assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
-        config: BootstrapperConfig,
-        message: ReportServicesConsumedMessage,
-    ) -> Arc<Mutex<Vec<(SystemTime, Wallet, u128)>>> {
-        let more_money_payable_parameters_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao_mock = PayableDaoMock::new()
-            .non_pending_payables_result(vec![])
-            .more_money_payable_result(Ok(()))
-            .more_money_payable_params(more_money_payable_parameters_arc.clone());
+    #[test]
+    fn retry_failed_payments_request_refuses_malformed_hash() {
+        let pending_payable_dao =
+            PendingPayableDaoMock::default().return_failed_fingerprints_result(vec![]);
         let subject = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .payable_daos(vec![ForAccountantBody(payable_dao_mock)])
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .build();
+
+        assert_retry_failed_payments_is_refused(
+            subject,
+            "0xbooga".to_string(),
+            "Invalid transaction hash: 0xbooga",
+        );
+    }
+
+    #[test]
+    fn retry_failed_payments_request_is_refused_while_payable_scan_is_running() {
+        let hash = make_tx_hash(0x1c8);
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .return_failed_fingerprints_result(vec![make_failed_fingerprint(4, hash)]);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_wallets(
+                make_paying_wallet(b"consuming"),
+                make_wallet("earning"),
+            ))
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+
+        assert_retry_failed_payments_is_refused(
+            subject,
+            format!("{:?}", hash),
+            "Payables are being paid right now; try again after the scan",
+        );
+    }
+
+    #[test]
+    fn write_off_payable_request_writes_off_payable_and_bans_creditor() {
+        init_test_logging();
+        let test_name = "write_off_payable_request_writes_off_payable_and_bans_creditor";
+        let write_off_params_arc = Arc::new(Mutex::new(vec![]));
+        let creditor = make_wallet("written_off_and_banned");
+        let payable_dao = PayableDaoMock::default()
+            .write_off_params(&write_off_params_arc)
+            .write_off_result(Ok(gwei_to_wei(1_234_u64)));
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
             .build();
-        let system = System::new("test");
-        let subject_addr: Addr<Accountant> = subject.start();
+        subject.logger = Logger::new(test_name);
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let system = System::new(test_name);
+        let before = SystemTime::now();
+
         subject_addr
-            .try_send(BindMessage {
-                peer_actors: peer_actors_builder().build(),
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiWriteOffPayableRequest {
+                    creditor_wallet: creditor.to_string(),
+                    reason: "Vanished".to_string(),
+                    ban_creditor: true,
+                }
+                .tmb(2222),
             })
             .unwrap();
 
-        subject_addr.try_send(message).unwrap();
-
         System::current().stop();
         system.run();
-        more_money_payable_parameters_arc
+        let after = SystemTime::now();
+        let mut write_off_params = write_off_params_arc.lock().unwrap();
+        let (wallet, reason, ban, timestamp) = write_off_params.remove(0);
+        assert_eq!(wallet, creditor);
+        assert_eq!(reason, "Vanished".to_string());
+        assert_eq!(ban, true);
+        assert!(before <= timestamp && timestamp <= after);
+        assert!(write_off_params.is_empty());
+        assert!(BAN_CACHE.is_banned(&creditor));
+        BAN_CACHE.remove(&creditor);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiWriteOffPayableResponse {
+                    written_off_gwei: 1_234
+                }
+                .tmb(2222),
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Wrote off the payable of 1234000000000 wei owed to {} and \
+             banned the creditor; reason: Vanished",
+            creditor
+        ));
     }
 
-    #[test]
-    fn routing_service_consumed_is_reported_for_our_consuming_wallet() {
-        init_test_logging();
-        let consuming_wallet = make_wallet("the consuming wallet");
-        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("the earning wallet"));
-        let foreign_wallet = make_wallet("exit wallet");
-        let timestamp = SystemTime::now();
-        let report_message = ReportServicesConsumedMessage {
-            timestamp,
-            exit: ExitServiceConsumed {
-                earning_wallet: foreign_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 45,
-                byte_rate: 10,
-            },
-            routing_payload_size: 3333,
-            routing: vec![RoutingServiceConsumed {
-                earning_wallet: consuming_wallet.clone(),
-                service_rate: 42,
-                byte_rate: 6,
-            }],
+    fn assert_write_off_payable_is_refused(
+        subject: Accountant,
+        creditor_wallet: String,
+        expected_error: &str,
+    ) {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiWriteOffPayableRequest {
+                creditor_wallet,
+                reason: "Whatever".to_string(),
+                ban_creditor: true,
+            }
+            .tmb(2222),
         };
 
-        let more_money_payable_params_arc =
-            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
-                config,
-                report_message,
-            );
+        subject_addr.try_send(ui_message).unwrap();
 
-        let more_money_payable_params = more_money_payable_params_arc.lock().unwrap();
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
         assert_eq!(
-            *more_money_payable_params,
-            //except processing the exit service there was no change in payables
-            vec![(timestamp, foreign_wallet, (45 + 10 * 1234))]
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: MessageBody {
+                    opcode: "writeOffPayable".to_string(),
+                    path: MessagePath::Conversation(2222),
+                    payload: Err((PAYABLE_WRITE_OFF_ERROR, expected_error.to_string())),
+                }
+            }
         );
-        TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
-            consuming_wallet
-        ));
     }
 
     #[test]
-    fn routing_service_consumed_is_reported_for_our_earning_wallet() {
-        init_test_logging();
-        let earning_wallet =
-            make_wallet("routing_service_consumed_is_reported_for_our_earning_wallet");
-        let foreign_wallet = make_wallet("exit wallet");
-        let config = bc_from_earning_wallet(earning_wallet.clone());
-        let timestamp = SystemTime::now();
-        let report_message = ReportServicesConsumedMessage {
-            timestamp,
-            exit: ExitServiceConsumed {
-                earning_wallet: foreign_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 45,
-                byte_rate: 10,
-            },
-            routing_payload_size: 3333,
-            routing: vec![RoutingServiceConsumed {
-                earning_wallet: earning_wallet.clone(),
-                service_rate: 42,
-                byte_rate: 6,
-            }],
-        };
+    fn write_off_payable_request_refuses_malformed_wallet() {
+        let subject = AccountantBuilder::default().build();
 
-        let more_money_payable_params_arc =
-            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
-                config,
-                report_message,
-            );
+        assert_write_off_payable_is_refused(
+            subject,
+            "0xbooga".to_string(),
+            "Invalid creditor wallet: 0xbooga",
+        );
+    }
 
-        let more_money_payable_params = more_money_payable_params_arc.lock().unwrap();
-        assert_eq!(
-            *more_money_payable_params,
-            //except processing the exit service there was no change in payables
-            vec![(timestamp, foreign_wallet, (45 + 10 * 1234))]
+    #[test]
+    fn write_off_payable_request_is_refused_while_payable_scan_is_running() {
+        let write_off_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::default().write_off_params(&write_off_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+
+        assert_write_off_payable_is_refused(
+            subject,
+            make_wallet("creditor").to_string(),
+            "Payables are being paid right now; try again after the scan",
         );
-        TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
-            earning_wallet
-        ));
+
+        assert!(write_off_params_arc.lock().unwrap().is_empty());
     }
 
     #[test]
-    fn exit_service_consumed_is_reported_for_our_consuming_wallet() {
-        init_test_logging();
-        let consuming_wallet =
-            make_wallet("exit_service_consumed_is_reported_for_our_consuming_wallet");
-        let config = bc_from_wallets(consuming_wallet.clone(), make_wallet("own earning wallet"));
-        let report_message = ReportServicesConsumedMessage {
-            timestamp: SystemTime::now(),
-            exit: ExitServiceConsumed {
-                earning_wallet: consuming_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
-            },
-            routing_payload_size: 3333,
-            routing: vec![],
-        };
+    fn write_off_payable_request_is_refused_if_payment_is_pending() {
+        let creditor = make_wallet("creditor");
+        let payable_dao =
+            PayableDaoMock::default().write_off_result(Err(PayableDaoError::PaymentPending(45)));
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
 
-        let more_money_payable_params_arc =
-            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
-                config,
-                report_message,
-            );
+        assert_write_off_payable_is_refused(
+            subject,
+            creditor.to_string(),
+            &format!(
+                "The payable owed to {} is being paid; try again once the payment is confirmed \
+                 or failed",
+                creditor
+            ),
+        );
 
-        assert!(more_money_payable_params_arc.lock().unwrap().is_empty());
-        TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
-            consuming_wallet
-        ));
+        assert!(!BAN_CACHE.is_banned(&creditor));
     }
 
     #[test]
-    fn exit_service_consumed_is_reported_for_our_earning_wallet() {
-        init_test_logging();
-        let earning_wallet = make_wallet("own earning wallet");
-        let config = bc_from_earning_wallet(earning_wallet.clone());
-        let report_message = ReportServicesConsumedMessage {
-            timestamp: SystemTime::now(),
-            exit: ExitServiceConsumed {
-                earning_wallet: earning_wallet.clone(),
-                payload_size: 1234,
-                service_rate: 42,
-                byte_rate: 24,
-            },
-            routing_payload_size: 3333,
-            routing: vec![],
+    fn write_off_payable_request_is_refused_if_nothing_is_owed() {
+        let creditor = make_wallet("creditor");
+        let payable_dao =
+            PayableDaoMock::default().write_off_result(Err(PayableDaoError::NoSuchPayable));
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+
+        assert_write_off_payable_is_refused(
+            subject,
+            creditor.to_string(),
+            &format!("No payable is owed to {}", creditor),
+        );
+    }
+
+    fn make_accounts_snapshot() -> AccountsSnapshot {
+        let pending_payable_fingerprint = PendingPayableFingerprint {
+            rowid: 0,
+            ..make_pending_payable_fingerprint()
         };
+        AccountsSnapshot {
+            chain_name: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            payables: vec![PayableAccount {
+                wallet: make_wallet("creditor"),
+                balance_wei: 12_345,
+                last_paid_timestamp: from_time_t(1_650_000_000),
+                pending_payable_opt: Some(PendingPayableId::new(
+                    0,
+                    pending_payable_fingerprint.hash,
+                )),
+            }],
+            receivables: vec![
+                ReceivableAccount {
+                    wallet: make_wallet("debtor_1"),
+                    balance_wei: 67_890,
+                    last_received_timestamp: from_time_t(1_655_000_000),
+                },
+                ReceivableAccount {
+                    wallet: make_wallet("debtor_2"),
+                    balance_wei: -1_000,
+                    last_received_timestamp: from_time_t(1_656_000_000),
+                },
+            ],
+            fingerprints: vec![pending_payable_fingerprint],
+        }
+    }
 
-        let more_money_payable_params_arc =
-            assert_that_we_do_not_charge_our_own_wallet_for_consumed_services(
-                config,
-                report_message,
-            );
+    fn send_accounts_transfer_request(subject: Accountant, body: MessageBody) -> NodeToUiMessage {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
 
-        assert!(more_money_payable_params_arc.lock().unwrap().is_empty());
-        TestLogHandler::new().exists_log_containing(&format!(
-            "WARN: Accountant: Declining to record a payable against our wallet {} for service we provided",
-            earning_wallet
-        ));
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        ui_gateway_recording
+            .get_record::<NodeToUiMessage>(0)
+            .clone()
     }
 
     #[test]
-    #[should_panic(
-        expected = "Recording services provided for 0x000000000000000000000000000000626f6f6761 \
-    but has hit fatal database error: RusqliteError(\"we cannot help ourselves; this is baaad\")"
-    )]
-    fn record_service_provided_panics_on_fatal_errors() {
+    fn export_accounts_request_writes_the_accounts_into_the_file() {
         init_test_logging();
-        let wallet = make_wallet("booga");
-        let receivable_dao = ReceivableDaoMock::new().more_money_receivable_result(Err(
-            ReceivableDaoError::RusqliteError(
-                "we cannot help ourselves; this is baaad".to_string(),
-            ),
-        ));
-        let subject = AccountantBuilder::default()
-            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+        let test_name = "export_accounts_request_writes_the_accounts_into_the_file";
+        let home_dir = ensure_node_home_directory_exists("accountant", test_name);
+        let path = home_dir.join("accounts.json");
+        let accounts_transfer_dao =
+            AccountsTransferDaoMock::default().export_result(make_accounts_snapshot());
+        let mut subject = AccountantBuilder::default()
+            .accounts_transfer_dao(accounts_transfer_dao)
             .build();
+        subject.logger = Logger::new(test_name);
 
-        let _ = subject.record_service_provided(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+        let result = send_accounts_transfer_request(
+            subject,
+            UiExportAccountsRequest {
+                path: path.to_string_lossy().to_string(),
+            }
+            .tmb(2222),
+        );
+
+        assert_eq!(
+            result,
+            NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiExportAccountsResponse {
+                    payables: 1,
+                    receivables: 2,
+                    pending_payables: 1,
+                }
+                .tmb(2222),
+            }
+        );
+        assert_eq!(read_accounts_file(&path), Ok(make_accounts_snapshot()));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Exported 1 payables, 2 receivables and 1 pending payables to {}",
+            path.display()
+        ));
     }
 
     #[test]
-    fn record_service_provided_handles_overflow() {
-        init_test_logging();
-        let wallet = make_wallet("booga");
-        let receivable_dao = ReceivableDaoMock::new()
-            .more_money_receivable_result(Err(ReceivableDaoError::SignConversion(1234)));
+    fn export_accounts_request_reports_failure_to_write_the_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "accountant",
+            "export_accounts_request_reports_failure_to_write_the_file",
+        );
+        let path = home_dir.join("accounts.json");
+        std::fs::write(&path, "precious").unwrap();
+        let accounts_transfer_dao =
+            AccountsTransferDaoMock::default().export_result(make_accounts_snapshot());
         let subject = AccountantBuilder::default()
-            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .accounts_transfer_dao(accounts_transfer_dao)
             .build();
 
-        subject.record_service_provided(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+        let result = send_accounts_transfer_request(
+            subject,
+            UiExportAccountsRequest {
+                path: path.to_string_lossy().to_string(),
+            }
+            .tmb(2222),
+        );
 
-        TestLogHandler::new().exists_log_containing(&format!(
-            "ERROR: Accountant: Overflow error recording service provided for {}: service rate {}, byte rate 1, payload size 2. Skipping",
-            wallet,
-            i64::MAX as u64
-        ));
+        assert_eq!(result.body.opcode, "exportAccounts".to_string());
+        assert_eq!(result.body.path, MessagePath::Conversation(2222));
+        let (code, message) = result.body.payload.unwrap_err();
+        assert_eq!(code, ACCOUNTS_EXPORT_ERROR);
+        assert!(
+            message.starts_with(&format!("Can't create {}: ", path.display())),
+            "{}",
+            message
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "precious")
     }
 
     #[test]
-    fn record_service_consumed_handles_overflow() {
+    fn import_accounts_request_imports_the_accounts_from_the_file() {
         init_test_logging();
-        let wallet = make_wallet("booga");
-        let payable_dao = PayableDaoMock::new()
-            .more_money_payable_result(Err(PayableDaoError::SignConversion(1234)));
-        let subject = AccountantBuilder::default()
-            .payable_daos(vec![ForAccountantBody(payable_dao)])
+        let test_name = "import_accounts_request_imports_the_accounts_from_the_file";
+        let home_dir = ensure_node_home_directory_exists("accountant", test_name);
+        let path = home_dir.join("accounts.json");
+        write_accounts_file(&path, &make_accounts_snapshot()).unwrap();
+        let import_params_arc = Arc::new(Mutex::new(vec![]));
+        let accounts_transfer_dao = AccountsTransferDaoMock::default()
+            .import_params(&import_params_arc)
+            .import_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .accounts_transfer_dao(accounts_transfer_dao)
             .build();
-        let service_rate = i64::MAX as u64;
+        subject.logger = Logger::new(test_name);
 
-        subject.record_service_consumed(service_rate, 1, SystemTime::now(), 2, &wallet);
+        let result = send_accounts_transfer_request(
+            subject,
+            UiImportAccountsRequest {
+                path: path.to_string_lossy().to_string(),
+            }
+            .tmb(2222),
+        );
 
+        assert_eq!(
+            result,
+            NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiImportAccountsResponse {
+                    payables: 1,
+                    receivables: 2,
+                    pending_payables: 1,
+                }
+                .tmb(2222),
+            }
+        );
+        assert_eq!(
+            *import_params_arc.lock().unwrap(),
+            vec![make_accounts_snapshot()]
+        );
         TestLogHandler::new().exists_log_containing(&format!(
-            "ERROR: Accountant: Overflow error recording consumed services from {}: total charge {}, service rate {}, byte rate 1, payload size 2. Skipping",
-            wallet,
-            i64::MAX as u64 + 1 * 2,
-            i64::MAX as u64
+            "INFO: {test_name}: Imported 1 payables, 2 receivables and 1 pending payables from {}",
+            path.display()
         ));
     }
 
-    #[test]
-    #[should_panic(
-        expected = "Recording services consumed from 0x000000000000000000000000000000626f6f6761 but \
-     has hit fatal database error: RusqliteError(\"we cannot help ourselves; this is baaad\")"
-    )]
-    fn record_service_consumed_panics_on_fatal_errors() {
-        init_test_logging();
-        let wallet = make_wallet("booga");
-        let payable_dao = PayableDaoMock::new().more_money_payable_result(Err(
-            PayableDaoError::RusqliteError("we cannot help ourselves; this is baaad".to_string()),
-        ));
-        let subject = AccountantBuilder::default()
-            .payable_daos(vec![ForAccountantBody(payable_dao)])
+    fn assert_import_accounts_is_refused(
+        test_name: &str,
+        subject_modifier: fn(&mut Accountant),
+        import_result_opt: Option<Result<(), AccountsTransferDaoError>>,
+        expected_error: &str,
+    ) {
+        let home_dir = ensure_node_home_directory_exists("accountant", test_name);
+        let path = home_dir.join("accounts.json");
+        write_accounts_file(&path, &make_accounts_snapshot()).unwrap();
+        let import_params_arc = Arc::new(Mutex::new(vec![]));
+        let expected_imports = if import_result_opt.is_some() { 1 } else { 0 };
+        let mut accounts_transfer_dao =
+            AccountsTransferDaoMock::default().import_params(&import_params_arc);
+        if let Some(import_result) = import_result_opt {
+            accounts_transfer_dao = accounts_transfer_dao.import_result(import_result)
+        }
+        let mut subject = AccountantBuilder::default()
+            .accounts_transfer_dao(accounts_transfer_dao)
             .build();
+        subject_modifier(&mut subject);
 
-        let _ = subject.record_service_consumed(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+        let result = send_accounts_transfer_request(
+            subject,
+            UiImportAccountsRequest {
+                path: path.to_string_lossy().to_string(),
+            }
+            .tmb(2222),
+        );
+
+        assert_eq!(
+            result,
+            NodeToUiMessage {
+                target: ClientId(1234),
+                body: MessageBody {
+                    opcode: "importAccounts".to_string(),
+                    path: MessagePath::Conversation(2222),
+                    payload: Err((ACCOUNTS_IMPORT_ERROR, expected_error.to_string())),
+                }
+            }
+        );
+        assert_eq!(import_params_arc.lock().unwrap().len(), expected_imports);
     }
 
     #[test]
-    #[should_panic(
-        expected = "panic message (processed with: node_lib::sub_lib::utils::crash_request_analyzer)"
-    )]
-    fn accountant_can_be_crashed_properly_but_not_improperly() {
-        let mut config = make_bc_with_defaults();
-        config.crash_point = CrashPoint::Message;
-        let accountant = AccountantBuilder::default()
-            .bootstrapper_config(config)
-            .build();
+    fn import_accounts_request_reports_conflicting_records() {
+        let creditor = make_wallet("creditor");
+
+        assert_import_accounts_is_refused(
+            "import_accounts_request_reports_conflicting_records",
+            |_| (),
+            Some(Err(AccountsTransferDaoError::Conflicts(vec![
+                format!("payable of {}", creditor),
+                format!("pending payable {:?}", make_tx_hash(456)),
+            ]))),
+            &format!(
+                "Nothing imported; these records would overwrite existing ones: payable of {}, \
+                 pending payable {:?}",
+                creditor,
+                make_tx_hash(456)
+            ),
+        );
+    }
 
-        prove_that_crash_request_handler_is_hooked_up(accountant, CRASH_KEY);
+    #[test]
+    fn import_accounts_request_reports_accounts_of_another_chain() {
+        assert_import_accounts_is_refused(
+            "import_accounts_request_reports_accounts_of_another_chain",
+            |_| (),
+            Some(Err(AccountsTransferDaoError::ChainMismatch(
+                "eth-mainnet".to_string(),
+            ))),
+            "Nothing imported; the accounts are on eth-mainnet, not on the chain of this Node",
+        );
     }
 
     #[test]
-    fn pending_transaction_is_registered_and_monitored_until_it_gets_confirmed_or_canceled() {
-        init_test_logging();
-        let port = find_free_port();
-        let pending_tx_hash_1 =
-            H256::from_str("e66814b2812a80d619813f51aa999c0df84eb79d10f4923b2b7667b30d6b33d3")
-                .unwrap();
-        let pending_tx_hash_2 =
-            H256::from_str("0288ef000581b3bca8a2017eac9aea696366f8f1b7437f18d1aad57bccb7032c")
-                .unwrap();
-        let _blockchain_client_server = MBCSBuilder::new(port)
-            // Blockchain Agent Gas Price
-            .ok_response("0x3B9ACA00".to_string(), 0) // 1000000000
-            // Blockchain Agent transaction fee balance
-            .ok_response("0xFFF0".to_string(), 0) // 65520
-            // Blockchain Agent masq balance
-            .ok_response(
-                "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(),
-                0,
-            )
-            // Submit payments to blockchain
-            .ok_response("0xFFF0".to_string(), 1)
-            .begin_batch()
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_1)
-                    .build(),
-            )
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_2)
-                    .build(),
-            )
-            .end_batch()
-            // Round 1 - handle_request_transaction_receipts
-            .begin_batch()
-            .raw_response(r#"{ "jsonrpc": "2.0", "id": 1, "result": null }"#.to_string()) // Null response
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_2)
-                    .build(),
-            )
-            .end_batch()
-            // Round 2 - handle_request_transaction_receipts
-            .begin_batch()
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_1)
-                    .build(),
-            )
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_2)
-                    .build(),
-            )
-            .end_batch()
-            // Round 3 - handle_request_transaction_receipts
-            .begin_batch()
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_1)
-                    .status(U64::from(0))
-                    .build(),
-            )
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_2)
-                    .build(),
-            )
-            .end_batch()
-            // Round 4 - handle_request_transaction_receipts
-            .begin_batch()
-            .raw_response(
-                ReceiptResponseBuilder::default()
-                    .transaction_hash(pending_tx_hash_2)
-                    .status(U64::from(1))
-                    .block_number(U64::from(1234))
-                    .block_hash(Default::default())
-                    .build(),
-            )
-            .end_batch()
-            .start();
-        let non_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
-        let mark_pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
-        let return_all_errorless_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
-        let update_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
-        let mark_failure_params_arc = Arc::new(Mutex::new(vec![]));
-        let transactions_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
-        let delete_record_params_arc = Arc::new(Mutex::new(vec![]));
-        let notify_later_scan_for_pending_payable_params_arc = Arc::new(Mutex::new(vec![]));
-        let notify_later_scan_for_pending_payable_arc_cloned =
-            notify_later_scan_for_pending_payable_params_arc.clone(); // because it moves into a closure
-        let rowid_for_account_1 = 3;
-        let rowid_for_account_2 = 5;
-        let now = SystemTime::now();
-        let past_payable_timestamp_1 = now.sub(Duration::from_secs(
-            (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 555) as u64,
-        ));
-        let past_payable_timestamp_2 = now.sub(Duration::from_secs(
-            (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 50) as u64,
-        ));
-        let this_payable_timestamp_1 = now;
-        let this_payable_timestamp_2 = now.add(Duration::from_millis(50));
-        let payable_account_balance_1 =
-            gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 10);
-        let payable_account_balance_2 =
-            gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 666);
-        let wallet_account_1 = make_wallet("creditor1");
-        let wallet_account_2 = make_wallet("creditor2");
-        let blockchain_interface = make_blockchain_interface_web3(port);
-        let consuming_wallet = make_paying_wallet(b"wallet");
-        let system = System::new("pending_transaction");
-        let persistent_config_id_stamp = ArbitraryIdStamp::new();
-        let persistent_config = PersistentConfigurationMock::default()
-            .set_arbitrary_id_stamp(persistent_config_id_stamp);
-        let blockchain_bridge = BlockchainBridge::new(
-            Box::new(blockchain_interface),
-            Arc::new(Mutex::new(persistent_config)),
-            false,
+    fn import_accounts_request_is_refused_while_pending_payable_scan_is_running() {
+        assert_import_accounts_is_refused(
+            "import_accounts_request_is_refused_while_pending_payable_scan_is_running",
+            |subject| {
+                subject
+                    .scanners
+                    .pending_payable
+                    .mark_as_started(SystemTime::now())
+            },
+            None,
+            "Payables are being scanned right now; try again after the scan",
         );
-        let account_1 = PayableAccount {
-            wallet: wallet_account_1.clone(),
-            balance_wei: payable_account_balance_1,
-            last_paid_timestamp: past_payable_timestamp_1,
-            pending_payable_opt: None,
-        };
-        let account_2 = PayableAccount {
-            wallet: wallet_account_2.clone(),
-            balance_wei: payable_account_balance_2,
-            last_paid_timestamp: past_payable_timestamp_2,
-            pending_payable_opt: None,
-        };
-        let pending_payable_scan_interval = 1000; // should be slightly less than 1/5 of the time until shutting the system
-        let payable_dao_for_payable_scanner = PayableDaoMock::new()
-            .non_pending_payables_params(&non_pending_payables_params_arc)
-            .non_pending_payables_result(vec![account_1, account_2])
-            .mark_pending_payables_rowids_params(&mark_pending_payable_params_arc)
-            .mark_pending_payables_rowids_result(Ok(()));
-        let payable_dao_for_pending_payable_scanner = PayableDaoMock::new()
-            .transactions_confirmed_params(&transactions_confirmed_params_arc)
-            .transactions_confirmed_result(Ok(()));
-        let mut bootstrapper_config = bc_from_earning_wallet(make_wallet("some_wallet_address"));
-        bootstrapper_config.scan_intervals_opt = Some(ScanIntervals {
-            payable_scan_interval: Duration::from_secs(1_000_000), // we don't care about this scan
-            receivable_scan_interval: Duration::from_secs(1_000_000), // we don't care about this scan
-            pending_payable_scan_interval: Duration::from_millis(pending_payable_scan_interval),
-        });
-        let fingerprint_1_first_round = PendingPayableFingerprint {
-            rowid: rowid_for_account_1,
-            timestamp: this_payable_timestamp_1,
-            hash: pending_tx_hash_1,
-            attempt: 1,
-            amount: payable_account_balance_1,
-            process_error: None,
-        };
-        let fingerprint_2_first_round = PendingPayableFingerprint {
-            rowid: rowid_for_account_2,
-            timestamp: this_payable_timestamp_2,
-            hash: pending_tx_hash_2,
-            attempt: 1,
-            amount: payable_account_balance_2,
-            process_error: None,
-        };
-        let fingerprint_1_second_round = PendingPayableFingerprint {
-            attempt: 2,
-            ..fingerprint_1_first_round.clone()
+    }
+
+    fn request_account_detail(subject: Accountant, wallet: String) -> MessageBody {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiAccountDetailRequest { wallet }.tmb(2222),
         };
-        let fingerprint_2_second_round = PendingPayableFingerprint {
+
+        subject_addr.try_send(ui_message).unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        response.body.clone()
+    }
+
+    #[test]
+    fn account_detail_request_assembles_everything_known_about_the_wallet() {
+        let payable_account_status_params_arc = Arc::new(Mutex::new(vec![]));
+        let write_offs_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_account_status_params_arc = Arc::new(Mutex::new(vec![]));
+        let received_payments_from_params_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("counterpart");
+        let now = SystemTime::now();
+        let ten_minutes_ago = now.checked_sub(Duration::from_secs(600)).unwrap();
+        let pending_fingerprint = PendingPayableFingerprint {
+            rowid: 45,
+            timestamp: ten_minutes_ago,
+            hash: make_tx_hash(789),
             attempt: 2,
-            ..fingerprint_2_first_round.clone()
-        };
-        let fingerprint_1_third_round = PendingPayableFingerprint {
-            attempt: 3,
-            ..fingerprint_1_first_round.clone()
-        };
-        let fingerprint_2_third_round = PendingPayableFingerprint {
-            attempt: 3,
-            ..fingerprint_2_first_round.clone()
-        };
-        let fingerprint_2_fourth_round = PendingPayableFingerprint {
-            attempt: 4,
-            ..fingerprint_2_first_round.clone()
+            amount: gwei_to_wei(3_000_u64),
+            process_error: None,
+            replacement_basis_opt: None,
         };
-        let pending_payable_dao_for_payable_scanner = PendingPayableDaoMock::default()
-            .fingerprints_rowids_result(TransactionHashes {
-                rowid_results: vec![
-                    (rowid_for_account_1, pending_tx_hash_1),
-                    (rowid_for_account_2, pending_tx_hash_2),
-                ],
-                no_rowid_results: vec![],
-            })
-            .fingerprints_rowids_result(TransactionHashes {
-                rowid_results: vec![
-                    (rowid_for_account_1, pending_tx_hash_1),
-                    (rowid_for_account_2, pending_tx_hash_2),
-                ],
-                no_rowid_results: vec![],
-            });
-        let mut pending_payable_dao_for_pending_payable_scanner = PendingPayableDaoMock::new()
-            .return_all_errorless_fingerprints_params(&return_all_errorless_fingerprints_params_arc)
-            .return_all_errorless_fingerprints_result(vec![])
-            .return_all_errorless_fingerprints_result(vec![
-                fingerprint_1_first_round,
-                fingerprint_2_first_round,
-            ])
-            .return_all_errorless_fingerprints_result(vec![
-                fingerprint_1_second_round,
-                fingerprint_2_second_round,
-            ])
-            .return_all_errorless_fingerprints_result(vec![
-                fingerprint_1_third_round,
-                fingerprint_2_third_round,
-            ])
-            .return_all_errorless_fingerprints_result(vec![fingerprint_2_fourth_round.clone()])
-            .fingerprints_rowids_result(TransactionHashes {
-                rowid_results: vec![
-                    (rowid_for_account_1, pending_tx_hash_1),
-                    (rowid_for_account_2, pending_tx_hash_2),
-                ],
-                no_rowid_results: vec![],
-            })
-            .increment_scan_attempts_params(&update_fingerprint_params_arc)
-            .increment_scan_attempts_result(Ok(()))
-            .increment_scan_attempts_result(Ok(()))
-            .increment_scan_attempts_result(Ok(()))
-            .mark_failures_params(&mark_failure_params_arc)
-            // we don't have a better solution yet, so we mark this down
-            .mark_failures_result(Ok(()))
-            .delete_fingerprints_params(&delete_record_params_arc)
-            // this is used during confirmation of the successful one
-            .delete_fingerprints_result(Ok(()));
-        pending_payable_dao_for_pending_payable_scanner
-            .have_return_all_errorless_fingerprints_shut_down_the_system = true;
-        let pending_payable_dao_for_accountant =
-            PendingPayableDaoMock::new().insert_fingerprints_result(Ok(()));
-        let accountant_addr = Arbiter::builder()
-            .stop_system_on_panic(true)
-            .start(move |_| {
-                let mut subject = AccountantBuilder::default()
-                    .consuming_wallet(consuming_wallet)
-                    .bootstrapper_config(bootstrapper_config)
-                    .payable_daos(vec![
-                        ForPayableScanner(payable_dao_for_payable_scanner),
-                        ForPendingPayableScanner(payable_dao_for_pending_payable_scanner),
-                    ])
-                    .pending_payable_daos(vec![
-                        ForAccountantBody(pending_payable_dao_for_accountant),
-                        ForPayableScanner(pending_payable_dao_for_payable_scanner),
-                        ForPendingPayableScanner(pending_payable_dao_for_pending_payable_scanner),
-                    ])
-                    .build();
-                subject.scanners.receivable = Box::new(NullScanner::new());
-                let notify_later_half_mock = NotifyLaterHandleMock::default()
-                    .notify_later_params(&notify_later_scan_for_pending_payable_arc_cloned)
-                    .capture_msg_and_let_it_fly_on();
-                subject.scan_schedulers.update_scheduler(
-                    ScanType::PendingPayables,
-                    Some(Box::new(notify_later_half_mock)),
-                    None,
-                );
-                subject
-            });
-        let mut peer_actors = peer_actors_builder().build();
-        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
-        peer_actors.accountant = accountant_subs.clone();
-        let blockchain_bridge_addr = blockchain_bridge.start();
-        let blockchain_bridge_subs = BlockchainBridge::make_subs_from(&blockchain_bridge_addr);
-        peer_actors.blockchain_bridge = blockchain_bridge_subs.clone();
-        send_bind_message!(accountant_subs, peer_actors);
-        send_bind_message!(blockchain_bridge_subs, peer_actors);
+        let payable_dao = PayableDaoMock::new()
+            .account_status_params(&payable_account_status_params_arc)
+            .account_status_result(Some(PayableAccount {
+                wallet: wallet.clone(),
+                balance_wei: gwei_to_wei(3_000_u64),
+                last_paid_timestamp: ten_minutes_ago,
+                pending_payable_opt: Some(PendingPayableId::new(45, H256::zero())),
+            }))
+            .write_offs_params(&write_offs_params_arc)
+            .write_offs_result(vec![PayableWriteOff {
+                wallet: wallet.clone(),
+                balance_wei: gwei_to_wei(1_500_u64),
+                reason: "Settled off-chain".to_string(),
+                banned: true,
+                timestamp: ten_minutes_ago,
+            }]);
+        let receivable_dao = ReceivableDaoMock::new()
+            .account_status_params(&receivable_account_status_params_arc)
+            .account_status_result(Some(ReceivableAccount {
+                wallet: wallet.clone(),
+                balance_wei: -gwei_to_wei::<i128, _>(250_u64),
+                last_received_timestamp: ten_minutes_ago,
+            }))
+            .received_payments_from_params(&received_payments_from_params_arc)
+            .received_payments_from_result(vec![BlockchainTransaction {
+                block_number: 1_234,
+                from: wallet.clone(),
+                wei_amount: gwei_to_wei(4_000_u64),
+            }]);
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .return_failed_fingerprints_result(vec![make_pending_payable_fingerprint()])
+            .return_all_errorless_fingerprints_result(vec![pending_fingerprint]);
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .build();
+        BAN_CACHE.insert(wallet.clone());
 
-        send_start_message!(accountant_subs);
+        let result = request_account_detail(subject, wallet.to_string());
 
-        assert_eq!(system.run(), 0);
-        let mut mark_pending_payable_params = mark_pending_payable_params_arc.lock().unwrap();
-        let mut one_set_of_mark_pending_payable_params = mark_pending_payable_params.remove(0);
-        assert!(mark_pending_payable_params.is_empty());
-        let first_payable = one_set_of_mark_pending_payable_params.remove(0);
-        assert_eq!(first_payable.0, wallet_account_1);
-        assert_eq!(first_payable.1, rowid_for_account_1);
-        let second_payable = one_set_of_mark_pending_payable_params.remove(0);
-        assert!(
-            one_set_of_mark_pending_payable_params.is_empty(),
-            "{:?}",
-            one_set_of_mark_pending_payable_params
+        BAN_CACHE.remove(&wallet);
+        let (body, context_id) = UiAccountDetailResponse::fmb(result).unwrap();
+        assert_eq!(context_id, 2222);
+        let ages = [
+            body.payable_opt.as_ref().unwrap().last_paid_age_s,
+            body.payable_opt
+                .as_ref()
+                .unwrap()
+                .pending_payment_opt
+                .as_ref()
+                .unwrap()
+                .age_s,
+            body.receivable_opt.as_ref().unwrap().last_received_age_s,
+            body.write_offs[0].age_s,
+        ];
+        ages.iter()
+            .for_each(|age_s| assert!(*age_s >= 600 && *age_s <= 601));
+        assert_eq!(
+            body,
+            UiAccountDetailResponse {
+                wallet: wallet.to_string(),
+                banned: true,
+                payable_opt: Some(UiPayableDetail {
+                    balance_gwei: 3_000,
+                    last_paid_age_s: ages[0],
+                    pending_payment_opt: Some(UiUnconfirmedPayment {
+                        transaction_hash: format!("{:?}", make_tx_hash(789)),
+                        amount_gwei: 3_000,
+                        age_s: ages[1],
+                        attempt: 2,
+                        failure_opt: None,
+                    }),
+                }),
+                receivable_opt: Some(UiReceivableDetail {
+                    balance_gwei: -250,
+                    last_received_age_s: ages[2],
+                }),
+                received_payments: vec![UiReceivedPayment {
+                    block_number: 1_234,
+                    amount_gwei: 4_000,
+                }],
+                write_offs: vec![UiPayableWriteOff {
+                    amount_gwei: 1_500,
+                    reason: "Settled off-chain".to_string(),
+                    banned: true,
+                    age_s: ages[3],
+                }],
+            }
         );
-        assert_eq!(second_payable.0, wallet_account_2);
-        assert_eq!(second_payable.1, rowid_for_account_2);
-        let return_all_errorless_fingerprints_params =
-            return_all_errorless_fingerprints_params_arc.lock().unwrap();
-        // it varies with machines and sometimes we manage more cycles than necessary
-        assert!(return_all_errorless_fingerprints_params.len() >= 5);
-        let non_pending_payables_params = non_pending_payables_params_arc.lock().unwrap();
-        assert_eq!(*non_pending_payables_params, vec![()]); // because we disabled further scanning for payables
-        let update_fingerprints_params = update_fingerprint_params_arc.lock().unwrap();
         assert_eq!(
-            *update_fingerprints_params,
-            vec![
-                vec![rowid_for_account_1, rowid_for_account_2],
-                vec![rowid_for_account_1, rowid_for_account_2],
-                vec![rowid_for_account_2],
-            ]
+            *payable_account_status_params_arc.lock().unwrap(),
+            vec![wallet.clone()]
         );
-        let mark_failure_params = mark_failure_params_arc.lock().unwrap();
-        assert_eq!(*mark_failure_params, vec![vec![rowid_for_account_1]]);
-        let delete_record_params = delete_record_params_arc.lock().unwrap();
-        assert_eq!(*delete_record_params, vec![vec![rowid_for_account_2]]);
-        let transaction_confirmed_params = transactions_confirmed_params_arc.lock().unwrap();
+        assert_eq!(*write_offs_params_arc.lock().unwrap(), vec![wallet.clone()]);
         assert_eq!(
-            *transaction_confirmed_params,
-            vec![vec![fingerprint_2_fourth_round.clone()]]
+            *receivable_account_status_params_arc.lock().unwrap(),
+            vec![wallet.clone()]
         );
-        let expected_scan_pending_payable_msg_and_interval = (
-            ScanForPendingPayables {
-                response_skeleton_opt: None,
+        assert_eq!(
+            *received_payments_from_params_arc.lock().unwrap(),
+            vec![wallet]
+        );
+    }
+
+    #[test]
+    fn account_detail_request_for_a_stranger_comes_back_empty() {
+        let wallet = make_wallet("stranger");
+        let payable_dao = PayableDaoMock::new()
+            .account_status_result(None)
+            .write_offs_result(vec![]);
+        let receivable_dao = ReceivableDaoMock::new()
+            .account_status_result(None)
+            .received_payments_from_result(vec![]);
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+
+        let result = request_account_detail(subject, wallet.to_string());
+
+        assert_eq!(
+            result,
+            UiAccountDetailResponse {
+                wallet: wallet.to_string(),
+                banned: false,
+                payable_opt: None,
+                receivable_opt: None,
+                received_payments: vec![],
+                write_offs: vec![],
+            }
+            .tmb(2222)
+        );
+    }
+
+    #[test]
+    fn account_detail_request_refuses_malformed_wallet() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = request_account_detail(subject, "0xbooga".to_string());
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "accountDetail".to_string(),
+                path: MessagePath::Conversation(2222),
+                payload: Err((ACCOUNT_DETAIL_ERROR, "Invalid wallet: 0xbooga".to_string())),
+            }
+        );
+    }
+
+    #[test]
+    fn account_detail_request_refuses_wallet_with_a_wrong_checksum() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = request_account_detail(
+            subject,
+            "0xAbCdEf0123456789012345678901234567890123".to_string(),
+        );
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "accountDetail".to_string(),
+                path: MessagePath::Conversation(2222),
+                payload: Err((
+                    ACCOUNT_DETAIL_ERROR,
+                    "Invalid wallet: Address 0xAbCdEf0123456789012345678901234567890123 fails \
+                     its EIP-55 checksum; check it for typos, or give it all in lower case"
+                        .to_string()
+                )),
+            }
+        );
+    }
+
+    fn request_why_not_paid(subject: Accountant, wallet: String) -> MessageBody {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let ui_message = NodeFromUiMessage {
+            client_id: 1234,
+            body: UiWhyNotPaidRequest { wallet }.tmb(2222),
+        };
+
+        subject_addr.try_send(ui_message).unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        response.body.clone()
+    }
+
+    #[test]
+    fn why_not_paid_request_tells_what_the_latest_scan_decided_about_the_debt() {
+        let account_status_params_arc = Arc::new(Mutex::new(vec![]));
+        let now = SystemTime::now();
+        let payable = PayableAccount {
+            wallet: make_wallet("creditor"),
+            balance_wei: gwei_to_wei(2_000_000_000_u64),
+            last_paid_timestamp: now.checked_sub(Duration::from_secs(100_000)).unwrap(),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .account_status_params(&account_status_params_arc)
+            .account_status_result(Some(payable.clone()));
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        subject.scanners.payable = Box::new(
+            PayableScannerBuilder::new()
+                .payable_dao(PayableDaoMock::new().creditor_thresholds_result(HashMap::new()))
+                .payment_thresholds(PaymentThresholds::default())
+                .build(),
+        );
+        subject.payment_decisions.start(
+            vec![payable.clone()],
+            ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(30_000_000_000_u64),
+                masq_token_balance_in_minor_units: U256::from(1_500_000_000_000_000_000_u128),
             },
-            Duration::from_millis(pending_payable_scan_interval),
+            now.checked_sub(Duration::from_secs(600)).unwrap(),
         );
-        let mut notify_later_check_for_confirmation =
-            notify_later_scan_for_pending_payable_params_arc
-                .lock()
-                .unwrap();
-        // it varies with machines and sometimes we manage more cycles than necessary
-        let vector_of_first_five_cycles = notify_later_check_for_confirmation
-            .drain(0..=4)
-            .collect_vec();
+        subject.payment_decisions.narrow(
+            &[PayableAccount {
+                balance_wei: gwei_to_wei(1_500_000_000_u64),
+                ..payable.clone()
+            }],
+            SKIPPED_BY_ADJUSTMENT,
+        );
+        subject
+            .payment_decisions
+            .narrow(&[], SKIPPED_BY_GAS_PRICE_CEILING);
+
+        let result = request_why_not_paid(subject, payable.wallet.to_string());
+
+        let (mut response, context_id) = UiWhyNotPaidResponse::fmb(result).unwrap();
+        let latest_scan = response.latest_scan_opt.as_mut().unwrap();
+        assert!(latest_scan.age_s >= 600 && latest_scan.age_s < 660);
+        latest_scan.age_s = 600;
+        assert_eq!(context_id, 2222);
         assert_eq!(
-            vector_of_first_five_cycles,
-            vec![
-                expected_scan_pending_payable_msg_and_interval.clone(),
-                expected_scan_pending_payable_msg_and_interval.clone(),
-                expected_scan_pending_payable_msg_and_interval.clone(),
-                expected_scan_pending_payable_msg_and_interval.clone(),
-                expected_scan_pending_payable_msg_and_interval,
-            ]
+            response,
+            UiWhyNotPaidResponse {
+                wallet: payable.wallet.to_string(),
+                owed_gwei: 2_000_000_000,
+                below_threshold: false,
+                latest_scan_opt: Some(UiPaymentDecisionChain {
+                    age_s: 600,
+                    owed_gwei: 2_000_000_000,
+                    service_fee_balance_gwei: 1_500_000_000,
+                    transaction_fee_balance_gwei: 30,
+                    decisions: vec![
+                        UiPaymentDecision {
+                            decision: "qualified".to_string(),
+                            payable_gwei: 2_000_000_000,
+                            detail: "".to_string(),
+                        },
+                        UiPaymentDecision {
+                            decision: "adjusted".to_string(),
+                            payable_gwei: 1_500_000_000,
+                            detail: "".to_string(),
+                        },
+                        UiPaymentDecision {
+                            decision: "heldBack".to_string(),
+                            payable_gwei: 0,
+                            detail: SKIPPED_BY_GAS_PRICE_CEILING.to_string(),
+                        },
+                    ],
+                }),
+            }
         );
-        let log_handler = TestLogHandler::new();
-        log_handler.exists_log_containing(
-            "WARN: Accountant: Broken transactions 0xe66814b2812a80d619813f51aa999c0df84eb79d10f\
-            4923b2b7667b30d6b33d3 marked as an error. You should take over the care of those to make sure \
-                your debts are going to be settled properly. At the moment, there is no automated process \
-                fixing that without your assistance");
-        log_handler.exists_log_matching("INFO: Accountant: Transaction 0x0288ef000581b3bca8a2017eac9\
-        aea696366f8f1b7437f18d1aad57bccb7032c has been added to the blockchain; detected locally at \
-            attempt 4 at \\d{2,}ms after its sending");
-        log_handler.exists_log_containing(
-            "INFO: Accountant: Transactions 0x0288ef000581b3bca8a2017eac9aea696366f8f1b7437f18d1aad5\
-            7bccb7032c completed their confirmation process succeeding",
+        let account_status_params = account_status_params_arc.lock().unwrap();
+        assert_eq!(*account_status_params, vec![payable.wallet]);
+    }
+
+    #[test]
+    fn why_not_paid_request_tells_a_young_debt_is_below_the_threshold() {
+        let payable = make_payable_account(111);
+        let payable_dao = PayableDaoMock::new().account_status_result(Some(payable.clone()));
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        subject.scanners.payable = Box::new(
+            PayableScannerBuilder::new()
+                .payable_dao(PayableDaoMock::new().creditor_thresholds_result(HashMap::new()))
+                .payment_thresholds(PaymentThresholds::default())
+                .build(),
+        );
+
+        let result = request_why_not_paid(subject, payable.wallet.to_string());
+
+        assert_eq!(
+            result,
+            UiWhyNotPaidResponse {
+                wallet: payable.wallet.to_string(),
+                owed_gwei: 111,
+                below_threshold: true,
+                latest_scan_opt: None,
+            }
+            .tmb(2222)
         );
     }
 
     #[test]
-    fn accountant_receives_reported_transaction_receipts_and_processes_them_all() {
-        let transactions_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
-        let payable_dao = PayableDaoMock::default()
-            .transactions_confirmed_params(&transactions_confirmed_params_arc)
-            .transactions_confirmed_result(Ok(()));
-        let pending_payable_dao =
-            PendingPayableDaoMock::default().delete_fingerprints_result(Ok(()));
+    fn why_not_paid_request_for_a_stranger_comes_back_empty() {
+        let wallet = make_wallet("stranger");
+        let payable_dao = PayableDaoMock::new().account_status_result(None);
         let subject = AccountantBuilder::default()
-            .payable_daos(vec![ForPendingPayableScanner(payable_dao)])
-            .pending_payable_daos(vec![ForPendingPayableScanner(pending_payable_dao)])
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
             .build();
+
+        let result = request_why_not_paid(subject, wallet.to_string());
+
+        assert_eq!(
+            result,
+            UiWhyNotPaidResponse {
+                wallet: wallet.to_string(),
+                owed_gwei: 0,
+                below_threshold: false,
+                latest_scan_opt: None,
+            }
+            .tmb(2222)
+        );
+    }
+
+    #[test]
+    fn why_not_paid_request_refuses_malformed_wallet() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = request_why_not_paid(subject, "0xbooga".to_string());
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "whyNotPaid".to_string(),
+                path: MessagePath::Conversation(2222),
+                payload: Err((WHY_NOT_PAID_ERROR, "Invalid wallet: 0xbooga".to_string())),
+            }
+        );
+    }
+
+    fn converse_about_consistency(
+        mut subject: Accountant,
+        prepare: fn(&mut Accountant),
+        requests: Vec<MessageBody>,
+    ) -> Vec<MessageBody> {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        prepare(&mut subject);
         let subject_addr = subject.start();
-        let transaction_hash_1 = make_tx_hash(4545);
-        let transaction_receipt_1 = TxReceipt {
-            transaction_hash: transaction_hash_1,
-            status: TxStatus::Succeeded(TransactionBlock {
-                block_hash: Default::default(),
-                block_number: U64::from(100),
-            }),
-        };
-        let fingerprint_1 = PendingPayableFingerprint {
-            rowid: 5,
-            timestamp: from_time_t(200_000_000),
-            hash: transaction_hash_1,
-            attempt: 2,
-            amount: 444,
-            process_error: None,
-        };
-        let transaction_hash_2 = make_tx_hash(3333333);
-        let transaction_receipt_2 = TxReceipt {
-            transaction_hash: transaction_hash_2,
-            status: TxStatus::Succeeded(TransactionBlock {
-                block_hash: Default::default(),
-                block_number: U64::from(200),
-            }),
-        };
-        let fingerprint_2 = PendingPayableFingerprint {
-            rowid: 10,
-            timestamp: from_time_t(199_780_000),
-            hash: Default::default(),
-            attempt: 15,
-            amount: 1212,
-            process_error: None,
-        };
-        let msg = ReportTransactionReceipts {
-            fingerprints_with_receipts: vec![
-                (
-                    TransactionReceiptResult::RpcResponse(transaction_receipt_1),
-                    fingerprint_1.clone(),
-                ),
-                (
-                    TransactionReceiptResult::RpcResponse(transaction_receipt_2),
-                    fingerprint_2.clone(),
-                ),
-            ],
-            response_skeleton_opt: None,
-        };
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let request_count = requests.len();
 
-        subject_addr.try_send(msg).unwrap();
+        requests.into_iter().for_each(|body| {
+            subject_addr
+                .try_send(NodeFromUiMessage {
+                    client_id: 1234,
+                    body,
+                })
+                .unwrap()
+        });
 
-        let system = System::new("processing reported receipts");
         System::current().stop();
         system.run();
-        let transactions_confirmed_params = transactions_confirmed_params_arc.lock().unwrap();
-        assert_eq!(
-            *transactions_confirmed_params,
-            vec![vec![fingerprint_1, fingerprint_2]]
-        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        (0..request_count)
+            .map(|idx| {
+                let response = ui_gateway_recording.get_record::<NodeToUiMessage>(idx);
+                assert_eq!(response.target, ClientId(1234));
+                response.body.clone()
+            })
+            .collect()
+    }
+
+    fn make_orphan_fingerprint() -> Anomaly {
+        Anomaly::OrphanFingerprint {
+            rowid: 3,
+            hash: make_tx_hash(333),
+            amount_wei: 4_000_000,
+            failed: true,
+        }
     }
 
     #[test]
-    fn accountant_handles_inserting_new_fingerprints() {
-        init_test_logging();
-        let insert_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
-        let pending_payable_dao = PendingPayableDaoMock::default()
-            .insert_fingerprints_params(&insert_fingerprint_params_arc)
-            .insert_fingerprints_result(Ok(()));
+    fn disputes_request_lists_the_recorded_disputes() {
+        let dispute = InvoiceDispute {
+            creditor_wallet: make_wallet("creditor"),
+            period_start: from_time_t(1_000_000),
+            period_end: from_time_t(1_003_600),
+            invoiced_services: 3,
+            booked_services: 2,
+            invoiced_wei: 7_000_000_000,
+            booked_wei: 5_000_000_000,
+            received_at: from_time_t(1_003_660),
+        };
+        let payable_dao = PayableDaoMock::new().invoice_disputes_result(vec![dispute.clone()]);
         let subject = AccountantBuilder::default()
-            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
             .build();
-        let accountant_addr = subject.start();
-        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
-        let timestamp = SystemTime::now();
-        let hash_1 = make_tx_hash(0x6c81c);
-        let amount_1 = 12345;
-        let hash_2 = make_tx_hash(0x1b207);
-        let amount_2 = 87654;
-        let hash_and_amount_1 = HashAndAmount {
-            hash: hash_1,
-            amount: amount_1,
-        };
-        let hash_and_amount_2 = HashAndAmount {
-            hash: hash_2,
-            amount: amount_2,
-        };
-        let init_params = vec![hash_and_amount_1, hash_and_amount_2];
-        let init_fingerprints_msg = PendingPayableFingerprintSeeds {
-            batch_wide_timestamp: timestamp,
-            hashes_and_balances: init_params.clone(),
-        };
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("disputes_request_lists_the_recorded_disputes");
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
 
-        let _ = accountant_subs
-            .init_pending_payable_fingerprints
-            .try_send(init_fingerprints_msg)
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiDisputesRequest {}.tmb(4321),
+            })
             .unwrap();
 
-        let system = System::new("ordering payment fingerprint test");
         System::current().stop();
-        assert_eq!(system.run(), 0);
-        let insert_fingerprint_params = insert_fingerprint_params_arc.lock().unwrap();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
         assert_eq!(
-            *insert_fingerprint_params,
-            vec![(vec![hash_and_amount_1, hash_and_amount_2], timestamp)]
-        );
-        TestLogHandler::new().exists_log_containing(
-            "DEBUG: Accountant: Saved new pending payable fingerprints for: \
-             0x000000000000000000000000000000000000000000000000000000000006c81c, 0x000000000000000000000000000000000000000000000000000000000001b207",
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiDisputesResponse {
+                    disputes: remap_invoice_disputes(vec![dispute]),
+                }
+                .tmb(4321),
+            }
         );
     }
 
     #[test]
-    fn payable_fingerprint_insertion_clearly_failed_and_we_log_it_at_least() {
-        //despite it doesn't end so here this event would be a cause of a later panic
+    fn check_consistency_request_lists_anomalies_and_repair_applies_them() {
         init_test_logging();
-        let insert_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
-        let pending_payable_dao = PendingPayableDaoMock::default()
-            .insert_fingerprints_params(&insert_fingerprint_params_arc)
-            .insert_fingerprints_result(Err(PendingPayableDaoError::InsertionFailed(
-                "Crashed".to_string(),
-            )));
-        let amount = 2345;
-        let transaction_hash = make_tx_hash(0x1c8);
-        let hash_and_amount = HashAndAmount {
-            hash: transaction_hash,
-            amount,
-        };
+        let test_name = "check_consistency_request_lists_anomalies_and_repair_applies_them";
+        let repair_params_arc = Arc::new(Mutex::new(vec![]));
+        let anomalies = vec![
+            make_orphan_fingerprint(),
+            Anomaly::DanglingPayable {
+                wallet: make_wallet("creditor"),
+                balance_wei: 1_000_000,
+                pending_payable_rowid: 7,
+            },
+        ];
+        let consistency_dao = ConsistencyDaoMock::new()
+            .anomalies_result(anomalies.clone())
+            .repair_params(&repair_params_arc)
+            .repair_result(Ok(()));
         let subject = AccountantBuilder::default()
-            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .consistency_dao(consistency_dao)
+            .logger(Logger::new(test_name))
             .build();
-        let timestamp = SystemTime::now();
-        let report_new_fingerprints = PendingPayableFingerprintSeeds {
-            batch_wide_timestamp: timestamp,
-            hashes_and_balances: vec![hash_and_amount],
-        };
 
-        let _ = subject.handle_new_pending_payable_fingerprints(report_new_fingerprints);
+        let result = converse_about_consistency(
+            subject,
+            |_| (),
+            vec![
+                UiCheckConsistencyRequest {}.tmb(2222),
+                UiRepairConsistencyRequest { plan_id: 1 }.tmb(3333),
+            ],
+        );
 
-        let insert_fingerprint_params = insert_fingerprint_params_arc.lock().unwrap();
         assert_eq!(
-            *insert_fingerprint_params,
-            vec![(vec![hash_and_amount], timestamp)]
+            result,
+            vec![
+                UiCheckConsistencyResponse {
+                    plan_id_opt: Some(1),
+                    anomalies: anomalies
+                        .iter()
+                        .map(|anomaly| UiAnomaly {
+                            description: anomaly.to_string(),
+                            repair: anomaly.repair().to_string(),
+                        })
+                        .collect(),
+                }
+                .tmb(2222),
+                UiRepairConsistencyResponse { repaired: 2 }.tmb(3333)
+            ]
         );
-        TestLogHandler::new().exists_log_containing("ERROR: Accountant: Failed to process \
-         new pending payable fingerprints due to 'InsertionFailed(\"Crashed\")', disabling the automated \
-          confirmation for all these transactions: 0x00000000000000000000000000000000000000000000000000000000000001c8");
+        assert_eq!(*repair_params_arc.lock().unwrap(), vec![anomalies.clone()]);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Repaired inconsistent accounts: {}; repair: Forget the transaction",
+            anomalies[0]
+        ));
     }
 
-    const EXAMPLE_RESPONSE_SKELETON: ResponseSkeleton = ResponseSkeleton {
-        client_id: 1234,
-        context_id: 4321,
-    };
+    #[test]
+    fn check_consistency_request_with_consistent_accounts_proposes_no_plan() {
+        let subject = AccountantBuilder::default().build();
 
-    const EXAMPLE_ERROR_MSG: &str = "My tummy hurts";
+        let result = converse_about_consistency(
+            subject,
+            |_| (),
+            vec![UiCheckConsistencyRequest {}.tmb(2222)],
+        );
 
-    #[test]
-    fn handling_scan_error_for_externally_triggered_payables() {
-        assert_scan_error_is_handled_properly(
-            "handling_scan_error_for_externally_triggered_payables",
-            ScanError {
-                scan_type: ScanType::Payables,
-                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
-                msg: EXAMPLE_ERROR_MSG.to_string(),
-            },
+        assert_eq!(
+            result,
+            vec![UiCheckConsistencyResponse {
+                plan_id_opt: None,
+                anomalies: vec![],
+            }
+            .tmb(2222)]
         );
     }
 
     #[test]
-    fn handling_scan_error_for_externally_triggered_pending_payables() {
-        assert_scan_error_is_handled_properly(
-            "handling_scan_error_for_externally_triggered_pending_payables",
-            ScanError {
-                scan_type: ScanType::PendingPayables,
-                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
-                msg: EXAMPLE_ERROR_MSG.to_string(),
-            },
+    fn repair_consistency_request_refuses_plan_other_than_the_latest() {
+        let repair_params_arc = Arc::new(Mutex::new(vec![]));
+        let consistency_dao = ConsistencyDaoMock::new()
+            .anomalies_result(vec![make_orphan_fingerprint()])
+            .anomalies_result(vec![make_orphan_fingerprint()])
+            .repair_params(&repair_params_arc);
+        let subject = AccountantBuilder::default()
+            .consistency_dao(consistency_dao)
+            .build();
+
+        let result = converse_about_consistency(
+            subject,
+            |_| (),
+            vec![
+                UiCheckConsistencyRequest {}.tmb(2222),
+                UiCheckConsistencyRequest {}.tmb(2223),
+                UiRepairConsistencyRequest { plan_id: 1 }.tmb(3333),
+            ],
         );
-    }
 
-    #[test]
-    fn handling_scan_error_for_externally_triggered_receivables() {
-        assert_scan_error_is_handled_properly(
-            "handling_scan_error_for_externally_triggered_receivables",
-            ScanError {
-                scan_type: ScanType::Receivables,
-                response_skeleton_opt: Some(EXAMPLE_RESPONSE_SKELETON),
-                msg: EXAMPLE_ERROR_MSG.to_string(),
-            },
+        assert_eq!(
+            result[2],
+            MessageBody {
+                opcode: "repairConsistency".to_string(),
+                path: MessagePath::Conversation(3333),
+                payload: Err((
+                    CONSISTENCY_REPAIR_ERROR,
+                    "Repair plan 1 is outdated; the latest one is 2".to_string()
+                )),
+            }
         );
+        assert!(repair_params_arc.lock().unwrap().is_empty());
     }
 
     #[test]
-    fn handling_scan_error_for_internally_triggered_payables() {
-        assert_scan_error_is_handled_properly(
-            "handling_scan_error_for_internally_triggered_payables",
-            ScanError {
-                scan_type: ScanType::Payables,
-                response_skeleton_opt: None,
-                msg: EXAMPLE_ERROR_MSG.to_string(),
-            },
+    fn repair_consistency_request_reports_anomaly_gone_since_the_check() {
+        let consistency_dao = ConsistencyDaoMock::new()
+            .anomalies_result(vec![make_orphan_fingerprint()])
+            .repair_result(Err(ConsistencyDaoError::AnomalyGone(
+                "Transaction 0x123 is awaited by no payable".to_string(),
+            )));
+        let subject = AccountantBuilder::default()
+            .consistency_dao(consistency_dao)
+            .build();
+
+        let result = converse_about_consistency(
+            subject,
+            |_| (),
+            vec![
+                UiCheckConsistencyRequest {}.tmb(2222),
+                UiRepairConsistencyRequest { plan_id: 1 }.tmb(3333),
+            ],
+        );
+
+        assert_eq!(
+            result[1],
+            MessageBody {
+                opcode: "repairConsistency".to_string(),
+                path: MessagePath::Conversation(3333),
+                payload: Err((
+                    CONSISTENCY_REPAIR_ERROR,
+                    "Nothing repaired; the accounts have changed since the check: Transaction \
+                     0x123 is awaited by no payable; check the consistency again"
+                        .to_string()
+                )),
+            }
         );
     }
 
     #[test]
-    fn handling_scan_error_for_internally_triggered_pending_payables() {
-        assert_scan_error_is_handled_properly(
-            "handling_scan_error_for_internally_triggered_pending_payables",
-            ScanError {
-                scan_type: ScanType::PendingPayables,
-                response_skeleton_opt: None,
-                msg: EXAMPLE_ERROR_MSG.to_string(),
-            },
+    fn consistency_requests_are_refused_while_payable_scan_is_running() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = converse_about_consistency(
+            subject,
+            |subject| subject.scanners.payable.mark_as_started(SystemTime::now()),
+            vec![
+                UiCheckConsistencyRequest {}.tmb(2222),
+                UiRepairConsistencyRequest { plan_id: 1 }.tmb(3333),
+            ],
+        );
+
+        let refusal = |opcode: &str, context_id: u64| MessageBody {
+            opcode: opcode.to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((
+                CONSISTENCY_REPAIR_ERROR,
+                "Payables are being scanned right now; try again after the scan".to_string(),
+            )),
+        };
+        assert_eq!(
+            result,
+            vec![
+                refusal("checkConsistency", 2222),
+                refusal("repairConsistency", 3333)
+            ]
         );
     }
 
     #[test]
-    fn handling_scan_error_for_internally_triggered_receivables() {
-        assert_scan_error_is_handled_properly(
-            "handling_scan_error_for_internally_triggered_receivables",
-            ScanError {
-                scan_type: ScanType::Receivables,
-                response_skeleton_opt: None,
-                msg: EXAMPLE_ERROR_MSG.to_string(),
-            },
-        );
+    fn services_consumed_from_banned_wallet_are_not_recorded() {
+        let more_money_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::default()
+            .more_money_payable_params(more_money_payable_params_arc.clone());
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .build();
+        let banned_creditor = make_wallet("banned_creditor_providing_service");
+        BAN_CACHE.insert(banned_creditor.clone());
+
+        subject.record_service_consumed(1_000, 10, SystemTime::now(), 2_000, &banned_creditor);
+
+        BAN_CACHE.remove(&banned_creditor);
+        assert!(more_money_payable_params_arc.lock().unwrap().is_empty());
     }
 
     #[test]
@@ -4102,11 +11178,16 @@ mod tests {
 
     #[test]
     fn financials_request_produces_financials_response() {
-        let payable_dao = PayableDaoMock::new().total_result(264_567_894_578);
-        let receivable_dao = ReceivableDaoMock::new().total_result(987_654_328_996);
+        let payable_dao = PayableDaoMock::new()
+            .total_result(264_567_894_578)
+            .aging_result(DebtAging::default());
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result(987_654_328_996)
+            .aging_result(DebtAging::default());
         let system = System::new("test");
         let subject = AccountantBuilder::default()
             .bootstrapper_config(make_bc_with_defaults())
+            .consuming_wallet(make_paying_wallet(b"consuming"))
             .payable_daos(vec![ForAccountantBody(payable_dao)])
             .receivable_daos(vec![ForAccountantBody(receivable_dao)])
             .build();
@@ -4141,18 +11222,169 @@ mod tests {
                     total_paid_payable_gwei: 0,
                     total_unpaid_receivable_gwei: 987,
                     total_paid_receivable_gwei: 0,
+                    approximate_fiat_opt: None,
+                    observe_only_opt: None,
+                    aging: UiDebtAging::default(),
                 }),
                 query_results_opt: None,
             }
         )
     }
 
+    #[test]
+    fn finance_handshake_settles_on_the_latest_version_both_sides_know() {
+        let system = System::new("test");
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiFinanceHandshakeRequest { max_version: 7 }.tmb(1111),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 4321,
+                body: UiFinanceHandshakeRequest { max_version: 1 }.tmb(2222),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let newer_ui_response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(newer_ui_response.target, ClientId(1234));
+        assert_eq!(
+            FinanceEnvelope::version_of(&newer_ui_response.body),
+            Some(FINANCE_PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            UiFinanceHandshakeResponse::fmb(newer_ui_response.body.clone()).unwrap(),
+            (
+                UiFinanceHandshakeResponse {
+                    version: FINANCE_PROTOCOL_VERSION,
+                    capabilities: finance_capabilities(FINANCE_PROTOCOL_VERSION),
+                },
+                1111
+            )
+        );
+        let older_ui_response = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(older_ui_response.target, ClientId(4321));
+        assert_eq!(
+            UiFinanceHandshakeResponse::fmb(older_ui_response.body.clone()).unwrap(),
+            (
+                UiFinanceHandshakeResponse {
+                    version: 1,
+                    capabilities: vec!["financials".to_string(), "scan".to_string()],
+                },
+                2222
+            )
+        );
+    }
+
+    #[test]
+    fn financials_response_follows_the_finance_version_of_the_client() {
+        let payable_dao = PayableDaoMock::new()
+            .total_result(264_567_894_578)
+            .total_result(264_567_894_578)
+            .total_result(264_567_894_578)
+            .aging_result(DebtAging::default())
+            .aging_result(DebtAging::default())
+            .aging_result(DebtAging::default());
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result(987_654_328_996)
+            .total_result(987_654_328_996)
+            .total_result(987_654_328_996)
+            .aging_result(DebtAging::default())
+            .aging_result(DebtAging::default())
+            .aging_result(DebtAging::default());
+        let system = System::new("test");
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let request = || UiFinancialsRequest {
+            stats_required: true,
+            top_records_opt: None,
+            custom_queries_opt: None,
+        };
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiFinanceHandshakeRequest { max_version: 1 }.tmb(1111),
+            })
+            .unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: request().tmb(2222),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: FinanceEnvelope::stamp(request().tmb(3333), 2),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 5678,
+                body: request().tmb(4444),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let stats_json_of = |idx: usize| {
+            let body = &ui_gateway_recording.get_record::<NodeToUiMessage>(idx).body;
+            let payload: serde_json::Value =
+                serde_json::from_str(body.payload.as_ref().unwrap()).unwrap();
+            (
+                FinanceEnvelope::version_of(body),
+                payload["statsOpt"].clone(),
+            )
+        };
+        let (handshaken_version, handshaken_stats) = stats_json_of(1);
+        assert_eq!(handshaken_version, Some(1));
+        assert_eq!(
+            handshaken_stats.get("totalPaidPayableGwei"),
+            Some(&serde_json::json!(0))
+        );
+        assert_eq!(handshaken_stats.get("aging"), None);
+        assert_eq!(handshaken_stats.get("observeOnlyOpt"), None);
+        let (stated_version, stated_stats) = stats_json_of(2);
+        assert_eq!(stated_version, Some(2));
+        assert!(stated_stats.get("aging").is_some());
+        let (unknown_client_version, unknown_client_stats) = stats_json_of(3);
+        assert_eq!(unknown_client_version, Some(FINANCE_PROTOCOL_VERSION));
+        assert!(unknown_client_stats.get("aging").is_some());
+    }
+
     #[test]
     fn compute_financials_processes_defaulted_request() {
-        let payable_dao = PayableDaoMock::new().total_result(u64::MAX as u128 + 123456);
-        let receivable_dao = ReceivableDaoMock::new().total_result((i64::MAX as i128) * 3);
+        let payable_dao = PayableDaoMock::new()
+            .total_result(u64::MAX as u128 + 123456)
+            .aging_result(DebtAging::default());
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result((i64::MAX as i128) * 3)
+            .aging_result(DebtAging::default());
         let subject = AccountantBuilder::default()
             .bootstrapper_config(bc_from_earning_wallet(make_wallet("some_wallet_address")))
+            .consuming_wallet(make_paying_wallet(b"consuming"))
             .payable_daos(vec![ForAccountantBody(payable_dao)])
             .receivable_daos(vec![ForAccountantBody(receivable_dao)])
             .build();
@@ -4180,7 +11412,143 @@ mod tests {
                     total_unpaid_and_pending_payable_gwei: 18446744073,
                     total_paid_payable_gwei: 172345602,
                     total_unpaid_receivable_gwei: 27670116110,
-                    total_paid_receivable_gwei: 4455656989
+                    total_paid_receivable_gwei: 4455656989,
+                    approximate_fiat_opt: None,
+                    observe_only_opt: None,
+                    aging: UiDebtAging::default(),
+                }),
+                query_results_opt: None
+            }
+            .tmb(context_id)
+        )
+    }
+
+    #[test]
+    fn compute_financials_sorts_unpaid_balances_by_age() {
+        let payable_aging_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_aging_params_arc = Arc::new(Mutex::new(vec![]));
+        let bucket = |accounts: u64, balance_wei: u128| AgingBucket {
+            accounts,
+            balance_wei,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .total_result(7_000_000_000)
+            .aging_params(&payable_aging_params_arc)
+            .aging_result(DebtAging {
+                buckets: [
+                    bucket(1, 1_000_000_000),
+                    bucket(0, 0),
+                    bucket(2, 6_000_000_000),
+                    bucket(0, 0),
+                ],
+            });
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result(5_000_000_000)
+            .aging_params(&receivable_aging_params_arc)
+            .aging_result(DebtAging {
+                buckets: [
+                    bucket(0, 0),
+                    bucket(1, 2_000_000_000),
+                    bucket(0, 0),
+                    bucket(1, 3_000_000_000),
+                ],
+            });
+        let subject = AccountantBuilder::default()
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        let request = UiFinancialsRequest {
+            stats_required: true,
+            top_records_opt: None,
+            custom_queries_opt: None,
+        };
+        let before = SystemTime::now();
+
+        let result = subject.compute_financials(&request, 1234);
+
+        let after = SystemTime::now();
+        let (response, _) = UiFinancialsResponse::fmb(result).unwrap();
+        let ui_bucket = |accounts: u64, balance_gwei: u64| UiAgingBucket {
+            accounts,
+            balance_gwei,
+        };
+        assert_eq!(
+            response.stats_opt.unwrap().aging,
+            UiDebtAging {
+                payable: UiAgingBuckets {
+                    up_to_one_day: ui_bucket(1, 1),
+                    one_to_three_days: ui_bucket(0, 0),
+                    three_to_seven_days: ui_bucket(2, 6),
+                    over_seven_days: ui_bucket(0, 0),
+                },
+                receivable: UiAgingBuckets {
+                    up_to_one_day: ui_bucket(0, 0),
+                    one_to_three_days: ui_bucket(1, 2),
+                    three_to_seven_days: ui_bucket(0, 0),
+                    over_seven_days: ui_bucket(1, 3),
+                },
+            }
+        );
+        let payable_aging_params = payable_aging_params_arc.lock().unwrap();
+        let receivable_aging_params = receivable_aging_params_arc.lock().unwrap();
+        assert!(before <= payable_aging_params[0] && payable_aging_params[0] <= after);
+        assert_eq!(*receivable_aging_params, *payable_aging_params);
+    }
+
+    #[test]
+    fn compute_financials_adds_approximate_fiat_values_if_price_feed_has_quote() {
+        let payable_dao = PayableDaoMock::new()
+            .total_result(3_000_000_000_000_000_000)
+            .aging_result(DebtAging::default());
+        let receivable_dao = ReceivableDaoMock::new()
+            .total_result(-600_000_000_000_000_000)
+            .aging_result(DebtAging::default());
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("some_wallet_address")))
+            .consuming_wallet(make_paying_wallet(b"consuming"))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+        subject.price_feed = Box::new(PriceFeedMock::default().quote_opt_result(Some(FiatQuote {
+            currency: "USD".to_string(),
+            transaction_fee_unit_price: 2_000.0,
+            service_fee_unit_price: 0.25,
+        })));
+        subject
+            .financial_statistics
+            .borrow_mut()
+            .total_paid_payable_wei = 1_000_000_000_000_000_000;
+        subject
+            .financial_statistics
+            .borrow_mut()
+            .total_paid_receivable_wei = 40_000_000_000_000_000_000;
+        let context_id = 1234;
+        let request = UiFinancialsRequest {
+            stats_required: true,
+            top_records_opt: None,
+            custom_queries_opt: None,
+        };
+
+        let result = subject.compute_financials(&request, context_id);
+
+        assert_eq!(
+            result,
+            UiFinancialsResponse {
+                stats_opt: Some(UiFinancialStatistics {
+                    total_unpaid_and_pending_payable_gwei: 3_000_000_000,
+                    total_paid_payable_gwei: 1_000_000_000,
+                    total_unpaid_receivable_gwei: -600_000_000,
+                    total_paid_receivable_gwei: 40_000_000_000,
+                    approximate_fiat_opt: Some(UiApproximateFiatTotals {
+                        currency: "USD".to_string(),
+                        total_unpaid_and_pending_payable: "0.75".to_string(),
+                        total_paid_payable: "0.25".to_string(),
+                        total_unpaid_receivable: "-0.15".to_string(),
+                        total_paid_receivable: "10.00".to_string(),
+                    }),
+                    observe_only_opt: None,
+                    aging: UiDebtAging::default(),
                 }),
                 query_results_opt: None
             }
@@ -4260,7 +11628,8 @@ mod tests {
                         wallet: make_wallet("abcd123").to_string(),
                         age_s: extracted_payable_ages[0],
                         balance_gwei: 58,
-                        pending_payable_hash_opt: None
+                        pending_payable_hash_opt: None,
+                        pending_payment_opt: None
                     },]),
                     receivable_opt: Some(vec![UiReceivableAccount {
                         wallet: make_wallet("efe4848").to_string(),
@@ -4298,6 +11667,81 @@ mod tests {
         )
     }
 
+    #[test]
+    fn compute_financials_reports_the_pending_payment_of_a_payable_account() {
+        let now = SystemTime::now();
+        let stuck_tx_hash = make_tx_hash(0x2b3);
+        let payable_accounts_retrieved = vec![
+            PayableAccount {
+                wallet: make_wallet("abcd123"),
+                balance_wei: 58_568_686_005,
+                last_paid_timestamp: now.sub(Duration::from_secs(5000)),
+                pending_payable_opt: Some(PendingPayableId::new(4, stuck_tx_hash)),
+            },
+            PayableAccount {
+                wallet: make_wallet("efef456"),
+                balance_wei: 7_000_000_000,
+                last_paid_timestamp: now.sub(Duration::from_secs(300)),
+                pending_payable_opt: None,
+            },
+        ];
+        let payable_dao =
+            PayableDaoMock::new().custom_query_result(Some(payable_accounts_retrieved));
+        let failed_fingerprint = PendingPayableFingerprint {
+            rowid: 4,
+            timestamp: now.sub(Duration::from_secs(1200)),
+            hash: stuck_tx_hash,
+            attempt: 6,
+            amount: 58_568_686_005,
+            process_error: Some("ERROR".to_string()),
+            replacement_basis_opt: None,
+        };
+        let unrelated_fingerprint = PendingPayableFingerprint {
+            rowid: 9,
+            timestamp: now,
+            hash: make_tx_hash(0x111),
+            attempt: 1,
+            amount: 1_000_000_000,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .return_failed_fingerprints_result(vec![failed_fingerprint])
+            .return_all_errorless_fingerprints_result(vec![unrelated_fingerprint]);
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .pending_payable_daos(vec![ForAccountantBody(pending_payable_dao)])
+            .build();
+        let request = UiFinancialsRequest {
+            stats_required: false,
+            top_records_opt: None,
+            custom_queries_opt: Some(CustomQueries {
+                payable_opt: Some(RangeQuery {
+                    min_age_s: 0,
+                    max_age_s: 10_000,
+                    min_amount_gwei: 1,
+                    max_amount_gwei: 100,
+                }),
+                receivable_opt: None,
+            }),
+        };
+
+        let result = subject.compute_financials(&request, 2222);
+
+        let (response, _) = UiFinancialsResponse::fmb(result).unwrap();
+        let payable = response.query_results_opt.unwrap().payable_opt.unwrap();
+        let pending_payment = payable[0].pending_payment_opt.as_ref().unwrap();
+        assert_eq!(
+            pending_payment.transaction_hash,
+            format!("{:?}", stuck_tx_hash)
+        );
+        assert_eq!(pending_payment.amount_gwei, 58);
+        assert!(pending_payment.age_s >= 1200 && pending_payment.age_s <= 1201);
+        assert_eq!(pending_payment.attempt, 6);
+        assert_eq!(pending_payment.failure_opt, Some("ERROR".to_string()));
+        assert_eq!(payable[1].pending_payment_opt, None);
+    }
+
     #[test]
     fn compute_financials_processes_request_with_top_records_only_and_age_ordering() {
         let payable_custom_query_params_arc = Arc::new(Mutex::new(vec![]));
@@ -4426,7 +11870,8 @@ mod tests {
                         wallet: make_wallet("abcd123").to_string(),
                         age_s: extracted_payable_ages[0],
                         balance_gwei: 5,
-                        pending_payable_hash_opt: None
+                        pending_payable_hash_opt: None,
+                        pending_payment_opt: None
                     },]),
                     receivable_opt: Some(vec![
                         UiReceivableAccount {
@@ -0,0 +1,273 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::pending_payable_dao::ABANDONED_MARK;
+use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+use serde_derive::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use web3::types::H256;
+
+const REPORT_FILE_PREFIX: &str = "nonce_anomalies_";
+const REPORT_FILE_EXTENSION: &str = "json";
+
+// How the fingerprints left in the database compare with the nonce the chain has confirmed for the
+// consuming wallet. Every nonce below the confirmed one belongs to a mined transaction, so its
+// fingerprint deserves a receipt lookup whatever happened to it before the Node went down.
+// Fingerprints recorded by older versions of the Node carry no nonce and are left out
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NonceReconciliation {
+    // Pending fingerprints whose transactions have been mined already
+    pub due_for_receipts: Vec<PendingPayableFingerprint>,
+    // Fingerprints marked failed, though their nonces have been consumed by no other transaction
+    // the Node knows of
+    pub reopened: Vec<PendingPayableFingerprint>,
+    // Nonces the chain has consumed from the lowest one still open up to the confirmed one
+    // without the Node holding any fingerprint for them
+    pub unrecorded_nonces: Vec<u64>,
+}
+
+impl NonceReconciliation {
+    pub fn new(
+        confirmed_nonce: u64,
+        errorless: &[PendingPayableFingerprint],
+        failed: &[PendingPayableFingerprint],
+    ) -> Self {
+        let held_nonces = errorless
+            .iter()
+            .chain(failed.iter())
+            .filter_map(nonce_opt)
+            .collect::<Vec<u64>>();
+        let mined = |fingerprint: &&PendingPayableFingerprint| {
+            nonce_opt(fingerprint)
+                .map(|nonce| nonce < confirmed_nonce)
+                .unwrap_or(false)
+        };
+        let held_once = |fingerprint: &&PendingPayableFingerprint| {
+            let nonce = nonce_opt(fingerprint);
+            held_nonces
+                .iter()
+                .filter(|held| Some(**held) == nonce)
+                .count()
+                == 1
+        };
+        let due_for_receipts = errorless.iter().filter(mined).cloned().collect();
+        let reopened = failed
+            .iter()
+            .filter(|fingerprint| !is_abandoned(fingerprint))
+            .filter(mined)
+            .filter(held_once)
+            .cloned()
+            .collect();
+        let lowest_open_nonce_opt = errorless
+            .iter()
+            .chain(
+                failed
+                    .iter()
+                    .filter(|fingerprint| !is_abandoned(fingerprint)),
+            )
+            .filter_map(nonce_opt)
+            .min();
+        let held_nonces = held_nonces.into_iter().collect::<HashSet<u64>>();
+        let unrecorded_nonces = match lowest_open_nonce_opt {
+            Some(lowest) => (lowest..confirmed_nonce)
+                .filter(|nonce| !held_nonces.contains(nonce))
+                .collect(),
+            None => vec![],
+        };
+        Self {
+            due_for_receipts,
+            reopened,
+            unrecorded_nonces,
+        }
+    }
+}
+
+fn nonce_opt(fingerprint: &PendingPayableFingerprint) -> Option<u64> {
+    fingerprint
+        .replacement_basis_opt
+        .as_ref()
+        .map(|basis| basis.nonce)
+}
+
+fn is_abandoned(fingerprint: &PendingPayableFingerprint) -> bool {
+    fingerprint
+        .process_error
+        .as_ref()
+        .map(|error| error.starts_with(ABANDONED_MARK))
+        .unwrap_or(false)
+}
+
+// Left in the data directory for the operator, since the chain holds transactions of the
+// consuming wallet that the Node cannot account for
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct NonceAnomalyReport {
+    pub wallet: String,
+    #[serde(rename = "confirmedNonce")]
+    pub confirmed_nonce: u64,
+    #[serde(rename = "unrecordedNonces")]
+    pub unrecorded_nonces: Vec<u64>,
+    #[serde(rename = "reopenedTransactions")]
+    pub reopened_transactions: Vec<H256>,
+}
+
+pub fn write_nonce_anomaly_report(
+    data_directory: &Path,
+    report: &NonceAnomalyReport,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(data_directory)
+        .map_err(|e| format!("Can't create {}: {}", data_directory.display(), e))?;
+    let serialized = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Can't serialize the report: {}", e))?;
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock went back before the epoch")
+        .as_millis();
+    let path = data_directory.join(format!(
+        "{}{:020}.{}",
+        REPORT_FILE_PREFIX, millis, REPORT_FILE_EXTENSION
+    ));
+    fs::write(&path, serialized).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::test_utils::make_pending_payable_fingerprint;
+    use crate::blockchain::blockchain_bridge::ReplacementBasis;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    fn make_fingerprint(
+        rowid: u64,
+        nonce_opt: Option<u64>,
+        error_opt: Option<&str>,
+    ) -> PendingPayableFingerprint {
+        PendingPayableFingerprint {
+            rowid,
+            hash: make_tx_hash(rowid as u32),
+            process_error: error_opt.map(|error| error.to_string()),
+            replacement_basis_opt: nonce_opt.map(|nonce| ReplacementBasis {
+                nonce,
+                gas_price_wei: 50_000_000_000,
+            }),
+            ..make_pending_payable_fingerprint()
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(REPORT_FILE_PREFIX, "nonce_anomalies_");
+        assert_eq!(REPORT_FILE_EXTENSION, "json");
+    }
+
+    #[test]
+    fn fingerprints_below_the_confirmed_nonce_are_due_for_receipts() {
+        let below = make_fingerprint(1, Some(7), None);
+        let at = make_fingerprint(2, Some(8), None);
+        let above = make_fingerprint(3, Some(9), None);
+        let without_nonce = make_fingerprint(4, None, None);
+
+        let result = NonceReconciliation::new(8, &[below.clone(), at, above, without_nonce], &[]);
+
+        assert_eq!(
+            result,
+            NonceReconciliation {
+                due_for_receipts: vec![below],
+                reopened: vec![],
+                unrecorded_nonces: vec![],
+            }
+        )
+    }
+
+    #[test]
+    fn failures_below_the_confirmed_nonce_are_reopened_unless_abandoned_or_superseded() {
+        let reopened = make_fingerprint(1, Some(4), Some("ERROR"));
+        let superseded = make_fingerprint(2, Some(5), Some("ERROR"));
+        let successor = make_fingerprint(3, Some(5), None);
+        let abandoned = make_fingerprint(4, Some(6), Some("ABANDONED: pending for too long"));
+        let not_mined_yet = make_fingerprint(5, Some(7), Some("ERROR"));
+
+        let result = NonceReconciliation::new(
+            7,
+            &[successor.clone()],
+            &[reopened.clone(), superseded, abandoned, not_mined_yet],
+        );
+
+        assert_eq!(
+            result,
+            NonceReconciliation {
+                due_for_receipts: vec![successor],
+                reopened: vec![reopened],
+                unrecorded_nonces: vec![],
+            }
+        )
+    }
+
+    #[test]
+    fn nonces_consumed_without_a_fingerprint_are_flagged_from_the_lowest_open_one() {
+        let lowest_open = make_fingerprint(1, Some(10), None);
+        let abandoned_below = make_fingerprint(2, Some(3), Some("ABANDONED: replaced"));
+        let abandoned_within = make_fingerprint(3, Some(12), Some("ABANDONED: replaced"));
+        let failed_within = make_fingerprint(4, Some(14), Some("ERROR"));
+
+        let result = NonceReconciliation::new(
+            16,
+            &[lowest_open.clone()],
+            &[abandoned_below, abandoned_within, failed_within.clone()],
+        );
+
+        assert_eq!(
+            result,
+            NonceReconciliation {
+                due_for_receipts: vec![lowest_open],
+                reopened: vec![failed_within],
+                unrecorded_nonces: vec![11, 13, 15],
+            }
+        )
+    }
+
+    #[test]
+    fn nothing_is_flagged_without_open_fingerprints() {
+        let abandoned = make_fingerprint(1, Some(3), Some("ABANDONED: replaced"));
+
+        let result = NonceReconciliation::new(10, &[], &[abandoned]);
+
+        assert_eq!(result, NonceReconciliation::default())
+    }
+
+    #[test]
+    fn write_nonce_anomaly_report_leaves_the_report_in_the_data_directory() {
+        let data_directory = ensure_node_home_directory_exists(
+            "nonce_reconciliation",
+            "write_nonce_anomaly_report_leaves_the_report_in_the_data_directory",
+        );
+        let report = NonceAnomalyReport {
+            wallet: "0x000000000000000000000000000000626f6f6761".to_string(),
+            confirmed_nonce: 16,
+            unrecorded_nonces: vec![11, 13],
+            reopened_transactions: vec![make_tx_hash(4)],
+        };
+
+        let result = write_nonce_anomaly_report(&data_directory, &report);
+
+        let path = result.unwrap();
+        assert_eq!(path.parent(), Some(data_directory.as_path()));
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("nonce_anomalies_"), "{}", name);
+        assert!(name.ends_with(".json"), "{}", name);
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            written,
+            serde_json::json!({
+                "wallet": "0x000000000000000000000000000000626f6f6761",
+                "confirmedNonce": 16,
+                "unrecordedNonces": [11, 13],
+                "reopenedTransactions": [format!("{:?}", make_tx_hash(4))]
+            })
+        )
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+// Drives the Accountant and the BlockchainBridge together through a whole payable cycle against
+// a mock blockchain server scripted to behave like a real node would
+#![cfg(test)]
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::db_access_objects::pending_payable_dao::TransactionHashes;
+use crate::accountant::gwei_to_wei;
+use crate::accountant::payment_adjuster::Adjustment;
+use crate::accountant::test_utils::DaoWithDestination::{
+    ForAccountantBody, ForPendingPayableScanner,
+};
+use crate::accountant::test_utils::{
+    bc_from_earning_wallet, AccountantBuilder, NullScanner, PayableDaoMock, PayableScannerBuilder,
+    PaymentAdjusterMock, PendingPayableDaoMock,
+};
+use crate::accountant::Accountant;
+use crate::blockchain::blockchain_bridge::{BlockchainBridge, PendingPayableFingerprint};
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::utils::sign_transaction;
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::{
+    HashAndAmount, REQUESTS_IN_PARALLEL,
+};
+use crate::blockchain::rpc_usage::MeteredHttp;
+use crate::blockchain::test_utils::{
+    blockchain_service_url, make_blockchain_bridge_config_for_port,
+};
+use crate::sub_lib::accountant::{ScanIntervals, DEFAULT_PAYMENT_THRESHOLDS};
+use crate::sub_lib::wallet::Wallet;
+use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
+use crate::test_utils::recorder::peer_actors_builder;
+use crate::test_utils::{make_paying_wallet, make_wallet};
+use actix::{Arbiter, System};
+use masq_lib::blockchains::chains::Chain;
+use masq_lib::logger::Logger;
+use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
+use masq_lib::utils::find_free_port;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::ops::Sub;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use web3::transports::{Batch, Http};
+use web3::types::{TransactionReceipt, H256, U256, U64};
+use web3::Web3;
+
+const GAS_PRICE_WEI: u128 = 1_000_000_000;
+const PENDING_NONCE: u64 = 32;
+
+#[test]
+fn payables_exceeding_the_masq_balance_are_adjusted_paid_and_confirmed() {
+    let port = find_free_port();
+    let chain = Chain::PolyMainnet;
+    let consuming_wallet = make_paying_wallet(b"consuming");
+    let now = SystemTime::now();
+    let account_1 = PayableAccount {
+        wallet: make_wallet("creditor1"),
+        balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 10),
+        last_paid_timestamp: now.sub(Duration::from_secs(
+            (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 555) as u64,
+        )),
+        pending_payable_opt: None,
+    };
+    let account_2 = PayableAccount {
+        wallet: make_wallet("creditor2"),
+        balance_wei: gwei_to_wei(DEFAULT_PAYMENT_THRESHOLDS.debt_threshold_gwei + 666),
+        last_paid_timestamp: now.sub(Duration::from_secs(
+            (DEFAULT_PAYMENT_THRESHOLDS.maturity_threshold_sec + 50) as u64,
+        )),
+        pending_payable_opt: None,
+    };
+    let masq_balance_wei = account_1.balance_wei + account_2.balance_wei / 2;
+    let adjusted_account_2 = PayableAccount {
+        balance_wei: masq_balance_wei - account_1.balance_wei,
+        ..account_2.clone()
+    };
+    let adjusted_accounts = vec![account_1.clone(), adjusted_account_2.clone()];
+    let tx_hashes = expected_transaction_hashes(port, chain, &consuming_wallet, &adjusted_accounts);
+    let blockchain_client_server = MBCSBuilder::new(port)
+        .serving_chain(chain)
+        .scripted_result("eth_gasPrice", format!("0x{:x}", GAS_PRICE_WEI))
+        .scripted_result("eth_getBalance", "0xDE0B6B3A7640000") // 1 ETH
+        .scripted_result("eth_call", format!("0x{:064x}", masq_balance_wei))
+        .scripted_result(
+            "eth_getBlockByNumber",
+            json!({"baseFeePerGas": "0x1DCD6500", "gasLimit": "0x1C9C380"}),
+        )
+        .scripted_result("eth_getTransactionCount", format!("0x{:x}", PENDING_NONCE))
+        .scripted_result("eth_sendRawTransaction", tx_hashes[0])
+        .scripted_result("eth_sendRawTransaction", tx_hashes[1])
+        .scripted_result("eth_getTransactionReceipt", confirmed_receipt(tx_hashes[0]))
+        .scripted_result("eth_getTransactionReceipt", confirmed_receipt(tx_hashes[1]))
+        .start();
+    let (rowid_1, rowid_2) = (3, 5);
+    let fingerprints = adjusted_accounts
+        .iter()
+        .zip(tx_hashes.iter())
+        .zip(vec![rowid_1, rowid_2])
+        .map(|((account, hash), rowid)| PendingPayableFingerprint {
+            rowid,
+            timestamp: now,
+            hash: *hash,
+            attempt: 1,
+            amount: account.balance_wei,
+            process_error: None,
+            replacement_basis_opt: None,
+        })
+        .collect::<Vec<_>>();
+    let rowids_of_sent_payments = TransactionHashes {
+        rowid_results: vec![(rowid_1, tx_hashes[0]), (rowid_2, tx_hashes[1])],
+        no_rowid_results: vec![],
+    };
+    let adjust_payments_params_arc = Arc::new(Mutex::new(vec![]));
+    let insert_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
+    let mark_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
+    let transactions_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
+    let delete_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
+    let payable_scanner = PayableScannerBuilder::new()
+        .payable_dao(
+            PayableDaoMock::new()
+                .non_pending_payables_result(vec![account_1.clone(), account_2])
+                .creditor_thresholds_result(Default::default())
+                .mark_pending_payables_rowids_params(&mark_pending_payables_params_arc)
+                .mark_pending_payables_rowids_result(Ok(())),
+        )
+        .pending_payable_dao(
+            PendingPayableDaoMock::new()
+                .recipients_of_errorless_fingerprints_result(HashSet::new())
+                .fingerprints_rowids_result(rowids_of_sent_payments),
+        )
+        .payment_adjuster(
+            PaymentAdjusterMock::default()
+                .is_adjustment_required_result(Ok(Some(Adjustment::MasqToken)))
+                .adjust_payments_params(&adjust_payments_params_arc)
+                .adjusted_accounts_result(adjusted_accounts.clone()),
+        )
+        .build();
+    let payable_dao_for_pending_payable_scanner = PayableDaoMock::new()
+        .transactions_confirmed_params(&transactions_confirmed_params_arc)
+        .transactions_confirmed_result(Ok(()));
+    let mut pending_payable_dao_for_pending_payable_scanner = PendingPayableDaoMock::new()
+        // The first scan comes along with the StartMessage, before any payment is made
+        .return_all_errorless_fingerprints_result(vec![])
+        .return_all_errorless_fingerprints_result(fingerprints.clone())
+        .delete_fingerprints_params(&delete_fingerprints_params_arc)
+        .delete_fingerprints_result(Ok(()));
+    pending_payable_dao_for_pending_payable_scanner
+        .have_return_all_errorless_fingerprints_shut_down_the_system = true;
+    let pending_payable_dao_for_accountant = PendingPayableDaoMock::new()
+        .insert_fingerprints_params(&insert_fingerprints_params_arc)
+        .insert_fingerprints_result(Ok(()));
+    let mut bootstrapper_config = bc_from_earning_wallet(make_wallet("earning"));
+    bootstrapper_config.scan_intervals_opt = Some(ScanIntervals {
+        payable_scan_interval: Duration::from_secs(1_000_000),
+        receivable_scan_interval: Duration::from_secs(1_000_000),
+        // Long enough for the payments to be sent before the next look at the pending ones
+        pending_payable_scan_interval: Duration::from_millis(1_000),
+    });
+    let blockchain_bridge_config = make_blockchain_bridge_config_for_port(port, chain);
+    let system = System::new("payable_pipeline");
+    let consuming_wallet_for_accountant = consuming_wallet.clone();
+    let accountant_addr = Arbiter::builder()
+        .stop_system_on_panic(true)
+        .start(move |_| {
+            let mut subject = AccountantBuilder::default()
+                .consuming_wallet(consuming_wallet_for_accountant)
+                .bootstrapper_config(bootstrapper_config)
+                .payable_daos(vec![ForPendingPayableScanner(
+                    payable_dao_for_pending_payable_scanner,
+                )])
+                .pending_payable_daos(vec![
+                    ForAccountantBody(pending_payable_dao_for_accountant),
+                    ForPendingPayableScanner(pending_payable_dao_for_pending_payable_scanner),
+                ])
+                .build();
+            subject.scanners.payable = Box::new(payable_scanner);
+            subject.scanners.receivable = Box::new(NullScanner::new());
+            subject
+        });
+    let blockchain_interface = BlockchainBridge::initialize_blockchain_interface(
+        blockchain_bridge_config.blockchain_service_url_opt,
+        blockchain_bridge_config.chain,
+        blockchain_bridge_config.payment_forwarders,
+        blockchain_bridge_config.receivable_confirmation_blocks,
+        blockchain_bridge_config.receivable_scan_attempts,
+        Duration::from_secs(blockchain_bridge_config.blockchain_request_timeout_secs),
+        None,
+        false,
+        blockchain_bridge_config.max_batch_size,
+        None,
+        Logger::new("payable_pipeline"),
+    );
+    let blockchain_bridge = BlockchainBridge::new(
+        blockchain_interface,
+        Arc::new(Mutex::new(PersistentConfigurationMock::default())),
+        false,
+        Some(consuming_wallet),
+        None,
+        None,
+    );
+    let mut peer_actors = peer_actors_builder().build();
+    let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+    peer_actors.accountant = accountant_subs.clone();
+    let blockchain_bridge_addr = blockchain_bridge.start();
+    let blockchain_bridge_subs = BlockchainBridge::make_subs_from(&blockchain_bridge_addr);
+    peer_actors.blockchain_bridge = blockchain_bridge_subs.clone();
+    send_bind_message!(accountant_subs, peer_actors);
+    send_bind_message!(blockchain_bridge_subs, peer_actors);
+
+    send_start_message!(accountant_subs);
+
+    assert_eq!(system.run(), 0);
+    let adjust_payments_params = adjust_payments_params_arc.lock().unwrap();
+    assert_eq!(adjust_payments_params.len(), 1);
+    assert_eq!(
+        adjust_payments_params[0].0.adjustment,
+        Adjustment::MasqToken
+    );
+    let insert_fingerprints_params = insert_fingerprints_params_arc.lock().unwrap();
+    assert_eq!(
+        insert_fingerprints_params[0].0,
+        vec![
+            HashAndAmount {
+                hash: tx_hashes[0],
+                amount: account_1.balance_wei
+            },
+            HashAndAmount {
+                hash: tx_hashes[1],
+                amount: adjusted_account_2.balance_wei
+            }
+        ]
+    );
+    let mark_pending_payables_params = mark_pending_payables_params_arc.lock().unwrap();
+    assert_eq!(
+        *mark_pending_payables_params,
+        vec![vec![
+            (account_1.wallet, rowid_1),
+            (adjusted_account_2.wallet, rowid_2)
+        ]]
+    );
+    let transactions_confirmed_params = transactions_confirmed_params_arc.lock().unwrap();
+    assert_eq!(*transactions_confirmed_params, vec![fingerprints]);
+    let delete_fingerprints_params = delete_fingerprints_params_arc.lock().unwrap();
+    assert_eq!(*delete_fingerprints_params, vec![vec![rowid_1, rowid_2]]);
+    let mut requested_methods = blockchain_client_server
+        .requests()
+        .iter()
+        .flat_map(|request| requested_methods(request))
+        .collect::<Vec<String>>();
+    // Whether the agent asks for the fee history depends on the probe of the blockchain service
+    // being done by then
+    requested_methods.retain(|method| method != "eth_feeHistory");
+    requested_methods.sort();
+    assert_eq!(
+        requested_methods,
+        vec![
+            // The token's name, symbol and decimals asked for once bound, then the MASQ balance
+            "eth_call",
+            "eth_call",
+            "eth_call",
+            "eth_call",
+            // The chain id asked for once bound, then once more in a batch, to probe for batches
+            "eth_chainId",
+            "eth_chainId",
+            "eth_gasPrice",
+            "eth_getBalance",
+            "eth_getBlockByNumber",
+            "eth_getTransactionCount",
+            "eth_getTransactionReceipt",
+            "eth_getTransactionReceipt",
+            "eth_sendRawTransaction",
+            "eth_sendRawTransaction",
+        ]
+    );
+}
+
+// The hashes come from signing the transactions, which is done locally, exactly the way
+// the BlockchainBridge is going to do it
+fn expected_transaction_hashes(
+    port: u16,
+    chain: Chain,
+    consuming_wallet: &Wallet,
+    accounts: &[PayableAccount],
+) -> Vec<H256> {
+    let (_event_loop_handle, transport) =
+        Http::with_max_parallel(&blockchain_service_url(port), REQUESTS_IN_PARALLEL).unwrap();
+    let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
+    accounts
+        .iter()
+        .enumerate()
+        .map(|(idx, account)| {
+            sign_transaction(
+                chain,
+                &web3_batch,
+                account.wallet.clone(),
+                consuming_wallet.clone(),
+                account.balance_wei,
+                U256::from(PENDING_NONCE + idx as u64),
+                GAS_PRICE_WEI,
+                None,
+            )
+            .transaction_hash
+        })
+        .collect()
+}
+
+fn confirmed_receipt(transaction_hash: H256) -> TransactionReceipt {
+    TransactionReceipt {
+        transaction_hash,
+        block_hash: Some(H256::from_low_u64_be(0x4321)),
+        block_number: Some(U64::from(1234)),
+        status: Some(U64::from(1)),
+        ..TransactionReceipt::default()
+    }
+}
+
+fn requested_methods(request: &str) -> Vec<String> {
+    let body = &request[request.find("\r\n\r\n").unwrap() + 4..];
+    let single_requests = match serde_json::from_str::<Value>(body).unwrap() {
+        Value::Array(batch) => batch,
+        single => vec![single],
+    };
+    single_requests
+        .iter()
+        .map(|single| single["method"].as_str().unwrap().to_string())
+        .collect()
+}
@@ -0,0 +1,327 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::{ResponseSkeleton, SentPayables};
+use crate::blockchain::blockchain_interface::data_structures::ProcessedPayableFallible;
+use masq_lib::constants::WEIS_IN_GWEI;
+use masq_lib::messages::{
+    UiAdjustedPayable, UiPaidPayable, UiSkippedPayable, UiTriggerPayableScanResponse,
+};
+
+pub const SKIPPED_BY_ADJUSTMENT: &str = "Dropped by the payment adjustment";
+pub const SKIPPED_BY_PAYMENT_WINDOW: &str = "Deferred until the payment window opens";
+pub const SKIPPED_BY_GAS_PRICE_CEILING: &str = "Deferred while the gas price is above the ceiling";
+pub const SKIPPED_BY_SPEND_RATE_LIMIT: &str = "Deferred by the spend rate limit";
+pub const SKIPPED_BY_ADJUSTMENT_INVARIANTS: &str = "Held back by a broken adjustment invariant";
+
+// Follows the debts of a scan demanded by an operator through every stage that may hold some
+// of them back, so that the operator can be told what became of each of them
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PayableScanReport {
+    pub response_skeleton: ResponseSkeleton,
+    qualified: usize,
+    remaining: Vec<PayableAccount>,
+    adjusted: Vec<UiAdjustedPayable>,
+    skipped: Vec<UiSkippedPayable>,
+}
+
+impl PayableScanReport {
+    pub fn new(response_skeleton: ResponseSkeleton) -> Self {
+        Self {
+            response_skeleton,
+            qualified: 0,
+            remaining: vec![],
+            adjusted: vec![],
+            skipped: vec![],
+        }
+    }
+
+    pub fn start(&mut self, qualified_payables: Vec<PayableAccount>) {
+        self.qualified = qualified_payables.len();
+        self.remaining = qualified_payables
+    }
+
+    // Debts missing from those left were held back for the reason given; those left with a lower
+    // balance were adjusted
+    pub fn narrow(&mut self, left: &[PayableAccount], reason: &str) {
+        for payable in &self.remaining {
+            match left.iter().find(|kept| kept.wallet == payable.wallet) {
+                None => self.skipped.push(UiSkippedPayable {
+                    wallet: payable.wallet.to_string(),
+                    owed_gwei: to_gwei(payable.balance_wei),
+                    reason: reason.to_string(),
+                }),
+                Some(kept) if kept.balance_wei < payable.balance_wei => {
+                    self.adjusted.push(UiAdjustedPayable {
+                        wallet: payable.wallet.to_string(),
+                        owed_gwei: to_gwei(payable.balance_wei),
+                        adjusted_gwei: to_gwei(kept.balance_wei),
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+        self.remaining = left.to_vec()
+    }
+
+    pub fn nothing_paid(self) -> UiTriggerPayableScanResponse {
+        self.conclude(vec![], vec![])
+    }
+
+    pub fn finish(self, msg: &SentPayables) -> UiTriggerPayableScanResponse {
+        let owed_gwei = |wallet| {
+            self.remaining
+                .iter()
+                .find(|payable| &payable.wallet == wallet)
+                .map(|payable| to_gwei(payable.balance_wei))
+                .unwrap_or_default()
+        };
+        let mut paid = vec![];
+        let mut failed = vec![];
+        match &msg.payment_procedure_result {
+            Ok(results) => results.iter().for_each(|result| match result {
+                ProcessedPayableFallible::Correct(payable) => paid.push(UiPaidPayable {
+                    wallet: payable.recipient_wallet.to_string(),
+                    amount_gwei: owed_gwei(&payable.recipient_wallet),
+                    transaction_hash: format!("{:?}", payable.hash),
+                }),
+                ProcessedPayableFallible::Failed(failure) => failed.push(UiSkippedPayable {
+                    wallet: failure.recipient_wallet.to_string(),
+                    owed_gwei: owed_gwei(&failure.recipient_wallet),
+                    reason: format!("Transaction failed: {}", failure.rpc_error),
+                }),
+            }),
+            Err(e) => failed.extend(self.remaining.iter().map(|payable| UiSkippedPayable {
+                wallet: payable.wallet.to_string(),
+                owed_gwei: to_gwei(payable.balance_wei),
+                reason: format!("Payments failed: {}", e),
+            })),
+        }
+        self.conclude(paid, failed)
+    }
+
+    fn conclude(
+        mut self,
+        paid: Vec<UiPaidPayable>,
+        failed: Vec<UiSkippedPayable>,
+    ) -> UiTriggerPayableScanResponse {
+        self.skipped.extend(failed);
+        UiTriggerPayableScanResponse {
+            qualified: self.qualified,
+            paid,
+            adjusted: self.adjusted,
+            skipped: self.skipped,
+        }
+    }
+}
+
+fn to_gwei(wei: u128) -> u64 {
+    u64::try_from(wei / WEIS_IN_GWEI as u128).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::db_access_objects::pending_payable_dao::PendingPayable;
+    use crate::accountant::payable_scan_report::{
+        PayableScanReport, SKIPPED_BY_ADJUSTMENT, SKIPPED_BY_ADJUSTMENT_INVARIANTS,
+        SKIPPED_BY_GAS_PRICE_CEILING, SKIPPED_BY_PAYMENT_WINDOW, SKIPPED_BY_SPEND_RATE_LIMIT,
+    };
+    use crate::accountant::{gwei_to_wei, ResponseSkeleton, SentPayables};
+    use crate::blockchain::blockchain_interface::data_structures::errors::PayableTransactionError;
+    use crate::blockchain::blockchain_interface::data_structures::{
+        ProcessedPayableFallible, RpcPayableFailure,
+    };
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::test_utils::make_wallet;
+    use masq_lib::messages::{
+        UiAdjustedPayable, UiPaidPayable, UiSkippedPayable, UiTriggerPayableScanResponse,
+    };
+    use std::time::SystemTime;
+    use web3::Error;
+
+    fn make_payable(name: &str, balance_gwei: u64) -> PayableAccount {
+        PayableAccount {
+            wallet: make_wallet(name),
+            balance_wei: gwei_to_wei(balance_gwei),
+            last_paid_timestamp: SystemTime::now(),
+            pending_payable_opt: None,
+        }
+    }
+
+    fn make_subject() -> PayableScanReport {
+        PayableScanReport::new(ResponseSkeleton {
+            client_id: 12,
+            context_id: 34,
+        })
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(SKIPPED_BY_ADJUSTMENT, "Dropped by the payment adjustment");
+        assert_eq!(
+            SKIPPED_BY_PAYMENT_WINDOW,
+            "Deferred until the payment window opens"
+        );
+        assert_eq!(
+            SKIPPED_BY_GAS_PRICE_CEILING,
+            "Deferred while the gas price is above the ceiling"
+        );
+        assert_eq!(
+            SKIPPED_BY_SPEND_RATE_LIMIT,
+            "Deferred by the spend rate limit"
+        );
+        assert_eq!(
+            SKIPPED_BY_ADJUSTMENT_INVARIANTS,
+            "Held back by a broken adjustment invariant"
+        );
+    }
+
+    #[test]
+    fn report_follows_the_debts_through_the_stages() {
+        let mut subject = make_subject();
+        let dropped = make_payable("dropped", 1_000);
+        let adjusted = make_payable("adjusted", 2_000);
+        let deferred = make_payable("deferred", 3_000);
+        let paid = make_payable("paid", 4_000);
+        let failed = make_payable("failed", 5_000);
+        subject.start(vec![
+            dropped.clone(),
+            adjusted.clone(),
+            deferred.clone(),
+            paid.clone(),
+            failed.clone(),
+        ]);
+        let adjusted_down = PayableAccount {
+            balance_wei: gwei_to_wei(1_500_u64),
+            ..adjusted.clone()
+        };
+        let hash = make_tx_hash(123);
+        let sent_payables = SentPayables {
+            payment_procedure_result: Ok(vec![
+                ProcessedPayableFallible::Correct(PendingPayable {
+                    recipient_wallet: adjusted.wallet.clone(),
+                    hash,
+                }),
+                ProcessedPayableFallible::Correct(PendingPayable {
+                    recipient_wallet: paid.wallet.clone(),
+                    hash,
+                }),
+                ProcessedPayableFallible::Failed(RpcPayableFailure {
+                    rpc_error: Error::Unreachable,
+                    recipient_wallet: failed.wallet.clone(),
+                    hash,
+                }),
+            ]),
+            response_skeleton_opt: None,
+        };
+
+        subject.narrow(
+            &[
+                adjusted_down.clone(),
+                deferred.clone(),
+                paid.clone(),
+                failed.clone(),
+            ],
+            SKIPPED_BY_ADJUSTMENT,
+        );
+        subject.narrow(&[adjusted_down, paid, failed], SKIPPED_BY_PAYMENT_WINDOW);
+        let result = subject.finish(&sent_payables);
+
+        assert_eq!(
+            result,
+            UiTriggerPayableScanResponse {
+                qualified: 5,
+                paid: vec![
+                    UiPaidPayable {
+                        wallet: adjusted.wallet.to_string(),
+                        amount_gwei: 1_500,
+                        transaction_hash: format!("{:?}", hash),
+                    },
+                    UiPaidPayable {
+                        wallet: make_wallet("paid").to_string(),
+                        amount_gwei: 4_000,
+                        transaction_hash: format!("{:?}", hash),
+                    }
+                ],
+                adjusted: vec![UiAdjustedPayable {
+                    wallet: adjusted.wallet.to_string(),
+                    owed_gwei: 2_000,
+                    adjusted_gwei: 1_500,
+                }],
+                skipped: vec![
+                    UiSkippedPayable {
+                        wallet: dropped.wallet.to_string(),
+                        owed_gwei: 1_000,
+                        reason: SKIPPED_BY_ADJUSTMENT.to_string(),
+                    },
+                    UiSkippedPayable {
+                        wallet: deferred.wallet.to_string(),
+                        owed_gwei: 3_000,
+                        reason: SKIPPED_BY_PAYMENT_WINDOW.to_string(),
+                    },
+                    UiSkippedPayable {
+                        wallet: make_wallet("failed").to_string(),
+                        owed_gwei: 5_000,
+                        reason: format!("Transaction failed: {}", Error::Unreachable),
+                    }
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn report_counts_every_remaining_debt_as_skipped_if_the_payments_failed_altogether() {
+        let mut subject = make_subject();
+        let first = make_payable("first", 1_000);
+        let second = make_payable("second", 2_000);
+        subject.start(vec![first.clone(), second.clone()]);
+        let error = PayableTransactionError::Signing("Bad key".to_string());
+        let sent_payables = SentPayables {
+            payment_procedure_result: Err(error.clone()),
+            response_skeleton_opt: None,
+        };
+
+        let result = subject.finish(&sent_payables);
+
+        let skipped = |payable: &PayableAccount, owed_gwei| UiSkippedPayable {
+            wallet: payable.wallet.to_string(),
+            owed_gwei,
+            reason: format!("Payments failed: {}", error),
+        };
+        assert_eq!(
+            result,
+            UiTriggerPayableScanResponse {
+                qualified: 2,
+                paid: vec![],
+                adjusted: vec![],
+                skipped: vec![skipped(&first, 1_000), skipped(&second, 2_000)],
+            }
+        );
+    }
+
+    #[test]
+    fn report_of_a_scan_that_paid_nothing_tells_why() {
+        let mut subject = make_subject();
+        let payable = make_payable("creditor", 1_000);
+        subject.start(vec![payable.clone()]);
+        subject.narrow(&[payable.clone()], SKIPPED_BY_PAYMENT_WINDOW);
+        subject.narrow(&[], SKIPPED_BY_SPEND_RATE_LIMIT);
+
+        let result = subject.nothing_paid();
+
+        assert_eq!(
+            result,
+            UiTriggerPayableScanResponse {
+                qualified: 1,
+                paid: vec![],
+                adjusted: vec![],
+                skipped: vec![UiSkippedPayable {
+                    wallet: payable.wallet.to_string(),
+                    owed_gwei: 1_000,
+                    reason: SKIPPED_BY_SPEND_RATE_LIMIT.to_string(),
+                }],
+            }
+        );
+    }
+}
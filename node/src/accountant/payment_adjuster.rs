@@ -23,6 +23,9 @@ pub trait PaymentAdjuster {
     as_any_ref_in_trait!();
 }
 
+// Deliberately stateless. Whatever an adjustment needs to know (balances, the current time, the
+// accounts in play) is to be built inside adjust_payments() and handed down the call chain as
+// an argument, rather than kept in a field that could be read before it's been initialized.
 pub struct PaymentAdjusterReal {}
 
 impl PaymentAdjuster for PaymentAdjusterReal {
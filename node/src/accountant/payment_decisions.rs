@@ -0,0 +1,460 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::SentPayables;
+use crate::blockchain::blockchain_interface::data_structures::ProcessedPayableFallible;
+use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
+use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wei::Wei;
+use masq_lib::constants::WEIS_IN_GWEI;
+use masq_lib::messages::{UiPaymentDecision, UiPaymentDecisionChain};
+use std::collections::HashMap;
+use std::time::SystemTime;
+use web3::types::H256;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PaymentDecision {
+    Qualified,
+    Adjusted { payable_wei: u128 },
+    HeldBack { reason: String },
+    Sent { transaction_hash: H256 },
+    Failed { reason: String },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PaymentDecisionChain {
+    pub timestamp: SystemTime,
+    pub owed_wei: u128,
+    pub service_fee_balance_wei: u128,
+    pub transaction_fee_balance_wei: u128,
+    pub decisions: Vec<PaymentDecision>,
+}
+
+impl PaymentDecisionChain {
+    pub fn to_ui(&self, now: SystemTime) -> UiPaymentDecisionChain {
+        let mut left_wei = self.owed_wei;
+        let decisions = self
+            .decisions
+            .iter()
+            .map(|decision| {
+                let (name, detail) = match decision {
+                    PaymentDecision::Qualified => ("qualified", String::new()),
+                    PaymentDecision::Adjusted { payable_wei } => {
+                        left_wei = *payable_wei;
+                        ("adjusted", String::new())
+                    }
+                    PaymentDecision::HeldBack { reason } => {
+                        left_wei = 0;
+                        ("heldBack", reason.clone())
+                    }
+                    PaymentDecision::Sent { transaction_hash } => {
+                        ("sent", format!("{:?}", transaction_hash))
+                    }
+                    PaymentDecision::Failed { reason } => ("failed", reason.clone()),
+                };
+                UiPaymentDecision {
+                    decision: name.to_string(),
+                    payable_gwei: to_gwei(left_wei),
+                    detail,
+                }
+            })
+            .collect();
+        UiPaymentDecisionChain {
+            age_s: now
+                .duration_since(self.timestamp)
+                .unwrap_or_default()
+                .as_secs(),
+            owed_gwei: to_gwei(self.owed_wei),
+            service_fee_balance_gwei: to_gwei(self.service_fee_balance_wei),
+            transaction_fee_balance_gwei: to_gwei(self.transaction_fee_balance_wei),
+            decisions,
+        }
+    }
+}
+
+// What the payable scans decided about each creditor, so that the operator can be told why a debt
+// wasn't paid without digging through the logs. Only the latest scan considering a creditor is
+// kept, and only in memory
+#[derive(Default)]
+pub struct PaymentDecisions {
+    chains: HashMap<Wallet, PaymentDecisionChain>,
+    // Debts of the scan in progress still on their way to be paid
+    remaining: Vec<PayableAccount>,
+}
+
+impl PaymentDecisions {
+    pub fn start(
+        &mut self,
+        qualified_payables: Vec<PayableAccount>,
+        wallet_balances: ConsumingWalletBalances,
+        now: SystemTime,
+    ) {
+        let service_fee_balance_wei =
+            Wei::saturating_from_u256(wallet_balances.masq_token_balance_in_minor_units).as_u128();
+        let transaction_fee_balance_wei =
+            Wei::saturating_from_u256(wallet_balances.transaction_fee_balance_in_minor_units)
+                .as_u128();
+        qualified_payables.iter().for_each(|payable| {
+            self.chains.insert(
+                payable.wallet.clone(),
+                PaymentDecisionChain {
+                    timestamp: now,
+                    owed_wei: payable.balance_wei,
+                    service_fee_balance_wei,
+                    transaction_fee_balance_wei,
+                    decisions: vec![PaymentDecision::Qualified],
+                },
+            );
+        });
+        self.remaining = qualified_payables
+    }
+
+    // Debts missing from those left were held back for the reason given; those left with a lower
+    // balance were adjusted
+    pub fn narrow(&mut self, left: &[PayableAccount], reason: &str) {
+        let chains = &mut self.chains;
+        self.remaining.iter().for_each(|payable| {
+            let decision_opt = match left.iter().find(|kept| kept.wallet == payable.wallet) {
+                None => Some(PaymentDecision::HeldBack {
+                    reason: reason.to_string(),
+                }),
+                Some(kept) if kept.balance_wei < payable.balance_wei => {
+                    Some(PaymentDecision::Adjusted {
+                        payable_wei: kept.balance_wei,
+                    })
+                }
+                Some(_) => None,
+            };
+            if let (Some(decision), Some(chain)) = (decision_opt, chains.get_mut(&payable.wallet)) {
+                chain.decisions.push(decision)
+            }
+        });
+        self.remaining = left.to_vec()
+    }
+
+    pub fn finish(&mut self, msg: &SentPayables) {
+        match &msg.payment_procedure_result {
+            Ok(results) => results.iter().for_each(|result| match result {
+                ProcessedPayableFallible::Correct(payable) => self.decide(
+                    &payable.recipient_wallet,
+                    PaymentDecision::Sent {
+                        transaction_hash: payable.hash,
+                    },
+                ),
+                ProcessedPayableFallible::Failed(failure) => self.decide(
+                    &failure.recipient_wallet,
+                    PaymentDecision::Failed {
+                        reason: format!("Transaction failed: {}", failure.rpc_error),
+                    },
+                ),
+            }),
+            Err(e) => self.fail_remaining(&format!("Payments failed: {}", e)),
+        }
+        self.remaining = vec![]
+    }
+
+    pub fn fail_remaining(&mut self, reason: &str) {
+        std::mem::take(&mut self.remaining)
+            .iter()
+            .for_each(|payable| {
+                self.decide(
+                    &payable.wallet,
+                    PaymentDecision::Failed {
+                        reason: reason.to_string(),
+                    },
+                )
+            })
+    }
+
+    pub fn chain_opt(&self, wallet: &Wallet) -> Option<&PaymentDecisionChain> {
+        self.chains.get(wallet)
+    }
+
+    fn decide(&mut self, wallet: &Wallet, decision: PaymentDecision) {
+        if let Some(chain) = self.chains.get_mut(wallet) {
+            chain.decisions.push(decision)
+        }
+    }
+}
+
+fn to_gwei(wei: u128) -> u64 {
+    u64::try_from(wei / WEIS_IN_GWEI as u128).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::db_access_objects::pending_payable_dao::PendingPayable;
+    use crate::accountant::payable_scan_report::SKIPPED_BY_PAYMENT_WINDOW;
+    use crate::accountant::test_utils::make_payable_account;
+    use crate::blockchain::blockchain_interface::data_structures::errors::PayableTransactionError;
+    use crate::blockchain::blockchain_interface::data_structures::RpcPayableFailure;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use std::time::Duration;
+    use web3::types::U256;
+
+    fn wallet_balances() -> ConsumingWalletBalances {
+        ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: U256::from(7_000_000_000_u64),
+            masq_token_balance_in_minor_units: U256::from(9_000_000_000_u64),
+        }
+    }
+
+    fn with_balance(payable: &PayableAccount, balance_wei: u128) -> PayableAccount {
+        PayableAccount {
+            balance_wei,
+            ..payable.clone()
+        }
+    }
+
+    #[test]
+    fn decisions_of_a_scan_follow_each_debt_to_its_end() {
+        let now = SystemTime::now();
+        let paid = make_payable_account(111);
+        let adjusted = make_payable_account(222);
+        let held_back = make_payable_account(333);
+        let failed = make_payable_account(444);
+        let mut subject = PaymentDecisions::default();
+
+        subject.start(
+            vec![
+                paid.clone(),
+                adjusted.clone(),
+                held_back.clone(),
+                failed.clone(),
+            ],
+            wallet_balances(),
+            now,
+        );
+        subject.narrow(
+            &[
+                paid.clone(),
+                with_balance(&adjusted, 1_000_000_000),
+                failed.clone(),
+            ],
+            "Dropped by the payment adjustment",
+        );
+        subject.narrow(
+            &[
+                paid.clone(),
+                with_balance(&adjusted, 1_000_000_000),
+                failed.clone(),
+            ],
+            SKIPPED_BY_PAYMENT_WINDOW,
+        );
+        subject.finish(&SentPayables {
+            payment_procedure_result: Ok(vec![
+                ProcessedPayableFallible::Correct(PendingPayable {
+                    recipient_wallet: paid.wallet.clone(),
+                    hash: make_tx_hash(1),
+                }),
+                ProcessedPayableFallible::Correct(PendingPayable {
+                    recipient_wallet: adjusted.wallet.clone(),
+                    hash: make_tx_hash(2),
+                }),
+                ProcessedPayableFallible::Failed(RpcPayableFailure {
+                    rpc_error: web3::Error::Unreachable,
+                    recipient_wallet: failed.wallet.clone(),
+                    hash: make_tx_hash(3),
+                }),
+            ]),
+            response_skeleton_opt: None,
+        });
+
+        let decisions_of = |payable: &PayableAccount| {
+            subject
+                .chain_opt(&payable.wallet)
+                .unwrap()
+                .decisions
+                .clone()
+        };
+        assert_eq!(
+            decisions_of(&paid),
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::Sent {
+                    transaction_hash: make_tx_hash(1)
+                }
+            ]
+        );
+        assert_eq!(
+            decisions_of(&adjusted),
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::Adjusted {
+                    payable_wei: 1_000_000_000
+                },
+                PaymentDecision::Sent {
+                    transaction_hash: make_tx_hash(2)
+                }
+            ]
+        );
+        assert_eq!(
+            decisions_of(&held_back),
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::HeldBack {
+                    reason: "Dropped by the payment adjustment".to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            decisions_of(&failed),
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::Failed {
+                    reason: format!("Transaction failed: {}", web3::Error::Unreachable)
+                }
+            ]
+        );
+        let chain = subject.chain_opt(&paid.wallet).unwrap();
+        assert_eq!(chain.timestamp, now);
+        assert_eq!(chain.owed_wei, paid.balance_wei);
+        assert_eq!(chain.service_fee_balance_wei, 9_000_000_000);
+        assert_eq!(chain.transaction_fee_balance_wei, 7_000_000_000);
+    }
+
+    #[test]
+    fn failed_payments_or_scan_fail_the_remaining_debts_only() {
+        let now = SystemTime::now();
+        let held_back = make_payable_account(111);
+        let remaining = make_payable_account(222);
+        let mut subject = PaymentDecisions::default();
+        subject.start(
+            vec![held_back.clone(), remaining.clone()],
+            wallet_balances(),
+            now,
+        );
+        subject.narrow(&[remaining.clone()], SKIPPED_BY_PAYMENT_WINDOW);
+
+        subject.finish(&SentPayables {
+            payment_procedure_result: Err(PayableTransactionError::MissingConsumingWallet),
+            response_skeleton_opt: None,
+        });
+        subject.fail_remaining("Nothing left");
+
+        assert_eq!(
+            subject.chain_opt(&held_back.wallet).unwrap().decisions,
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::HeldBack {
+                    reason: SKIPPED_BY_PAYMENT_WINDOW.to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            subject.chain_opt(&remaining.wallet).unwrap().decisions,
+            vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::Failed {
+                    reason: format!(
+                        "Payments failed: {}",
+                        PayableTransactionError::MissingConsumingWallet
+                    )
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn a_new_scan_replaces_the_chain_of_a_creditor_it_considers() {
+        let then = SystemTime::now() - Duration::from_secs(3_600);
+        let now = SystemTime::now();
+        let renewed = make_payable_account(111);
+        let untouched = make_payable_account(222);
+        let mut subject = PaymentDecisions::default();
+        subject.start(
+            vec![renewed.clone(), untouched.clone()],
+            wallet_balances(),
+            then,
+        );
+        subject.narrow(&[], SKIPPED_BY_PAYMENT_WINDOW);
+
+        subject.start(vec![renewed.clone()], wallet_balances(), now);
+
+        let renewed_chain = subject.chain_opt(&renewed.wallet).unwrap();
+        assert_eq!(renewed_chain.timestamp, now);
+        assert_eq!(renewed_chain.decisions, vec![PaymentDecision::Qualified]);
+        let untouched_chain = subject.chain_opt(&untouched.wallet).unwrap();
+        assert_eq!(untouched_chain.timestamp, then);
+        assert_eq!(untouched_chain.decisions.len(), 2);
+        assert_eq!(subject.chain_opt(&make_payable_account(333).wallet), None);
+    }
+
+    #[test]
+    fn chain_for_the_ui_tracks_what_was_left_to_pay_after_each_decision() {
+        let now = SystemTime::now();
+        let subject = PaymentDecisionChain {
+            timestamp: now - Duration::from_secs(90),
+            owed_wei: 5_000_000_000_000,
+            service_fee_balance_wei: 9_000_000_000,
+            transaction_fee_balance_wei: 7_000_000_000,
+            decisions: vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::Adjusted {
+                    payable_wei: 3_000_000_000_000,
+                },
+                PaymentDecision::Sent {
+                    transaction_hash: make_tx_hash(5),
+                },
+            ],
+        };
+
+        let result = subject.to_ui(now);
+
+        assert_eq!(
+            result,
+            UiPaymentDecisionChain {
+                age_s: 90,
+                owed_gwei: 5_000,
+                service_fee_balance_gwei: 9,
+                transaction_fee_balance_gwei: 7,
+                decisions: vec![
+                    UiPaymentDecision {
+                        decision: "qualified".to_string(),
+                        payable_gwei: 5_000,
+                        detail: "".to_string(),
+                    },
+                    UiPaymentDecision {
+                        decision: "adjusted".to_string(),
+                        payable_gwei: 3_000,
+                        detail: "".to_string(),
+                    },
+                    UiPaymentDecision {
+                        decision: "sent".to_string(),
+                        payable_gwei: 3_000,
+                        detail: format!("{:?}", make_tx_hash(5)),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn debt_held_back_leaves_nothing_to_pay_in_the_chain_for_the_ui() {
+        let now = SystemTime::now();
+        let subject = PaymentDecisionChain {
+            timestamp: now,
+            owed_wei: 5_000_000_000_000,
+            service_fee_balance_wei: 0,
+            transaction_fee_balance_wei: 0,
+            decisions: vec![
+                PaymentDecision::Qualified,
+                PaymentDecision::HeldBack {
+                    reason: SKIPPED_BY_PAYMENT_WINDOW.to_string(),
+                },
+            ],
+        };
+
+        let result = subject.to_ui(now);
+
+        assert_eq!(
+            result.decisions[1],
+            UiPaymentDecision {
+                decision: "heldBack".to_string(),
+                payable_gwei: 0,
+                detail: SKIPPED_BY_PAYMENT_WINDOW.to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::ReceivedPayments;
+use crate::blockchain::blockchain_bridge::BlockMarker;
+use crate::sub_lib::accountant::PaymentNotice_0v1;
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::logger::Logger;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+use thousands::Separable;
+use web3::types::{H256, U256};
+
+// When there is no room for another notice, the one accepted earliest gives way
+pub const MAX_AWAITED_PAYMENT_NOTICES: usize = 100;
+// Notices arriving sooner than this after the one that started a receivable scan are left to the
+// periodic scans
+pub const MIN_INTERVAL_BETWEEN_NOTICED_SCANS: Duration = Duration::from_secs(60);
+
+// Notices of payments that debtors claim to have made to us, kept until a receivable scan has
+// covered the blocks the payments are said to be in
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct PaymentNotices {
+    // In the order of acceptance
+    awaited: VecDeque<PaymentNotice_0v1>,
+    // The block the next receivable scan starts from, as far as the scans so far have told
+    start_block_opt: Option<u64>,
+    last_noticed_scan_opt: Option<SystemTime>,
+}
+
+impl PaymentNotices {
+    // Returns true if the notice is new and worth a receivable scan. A notice of a payment in a
+    // block the receivable scans have already covered comes too late to be reconciled.
+    pub fn accept(
+        &mut self,
+        notice: PaymentNotice_0v1,
+        earning_wallet: &Wallet,
+        logger: &Logger,
+    ) -> bool {
+        if notice.creditor_wallet.address() != earning_wallet.address() {
+            warning!(
+                logger,
+                "Ignoring notice of payment {:?} addressed to {}, which is not our earning wallet",
+                notice.tx_hash,
+                notice.creditor_wallet
+            );
+            return false;
+        }
+        if !notice.is_signed_by_payer() {
+            warning!(
+                logger,
+                "Ignoring notice of payment {:?} whose signature does not prove it comes from {}",
+                notice.tx_hash,
+                notice.payer.wallet
+            );
+            return false;
+        }
+        if let Some(start_block) = self.start_block_opt {
+            if notice.block_number < start_block {
+                debug!(
+                    logger,
+                    "Notice of payment {:?} from {} arrived after block {} had been scanned",
+                    notice.tx_hash,
+                    notice.payer.wallet,
+                    notice.block_number
+                );
+                return false;
+            }
+        }
+        if self
+            .awaited
+            .iter()
+            .any(|awaited| awaited.tx_hash == notice.tx_hash)
+        {
+            return false;
+        }
+        if self.awaited.len() >= MAX_AWAITED_PAYMENT_NOTICES {
+            if let Some(evicted) = self.awaited.pop_front() {
+                warning!(
+                    logger,
+                    "Too many notices of payments awaited; giving up on payment {:?} from {}",
+                    evicted.tx_hash,
+                    evicted.payer.wallet
+                );
+            }
+        }
+        info!(
+            logger,
+            "{} announced payment {:?} of {} wei in block {}; awaiting it in the receivable scan",
+            notice.payer.wallet,
+            notice.tx_hash,
+            notice.amount_wei.separate_with_commas(),
+            notice.block_number
+        );
+        self.awaited.push_back(notice);
+        true
+    }
+
+    // Notices arriving one after another share the receivable scan started for the first of them
+    pub fn is_scan_due(&mut self, now: SystemTime) -> bool {
+        if let Some(last_scan) = self.last_noticed_scan_opt {
+            let too_early = now
+                .duration_since(last_scan)
+                .map(|elapsed| elapsed < MIN_INTERVAL_BETWEEN_NOTICED_SCANS)
+                .unwrap_or(false);
+            if too_early {
+                return false;
+            }
+        }
+        self.last_noticed_scan_opt = Some(now);
+        true
+    }
+
+    // A notice is settled by a received transaction from the payer of the same amount in the same
+    // block; one whose block the scan has covered without finding it is dropped
+    pub fn reconcile(&mut self, msg: &ReceivedPayments, logger: &Logger) {
+        if let BlockMarker::Value(block_number) = msg.new_start_block {
+            self.start_block_opt = Some(block_number);
+        }
+        let start_block_opt = self.start_block_opt;
+        self.awaited.retain(|notice| {
+            let payer_address = notice.payer.wallet.address();
+            let received = msg.transactions.iter().any(|transaction| {
+                transaction.from.address() == payer_address
+                    && U256::from(transaction.wei_amount) == notice.amount_wei
+                    && transaction.block_number == notice.block_number
+            });
+            if received {
+                info!(
+                    logger,
+                    "Payment {:?} of {} wei from {} announced by its payer has been received",
+                    notice.tx_hash,
+                    notice.amount_wei.separate_with_commas(),
+                    notice.payer.wallet
+                );
+                return false;
+            }
+            match start_block_opt {
+                Some(block_number) if block_number > notice.block_number => {
+                    warning!(
+                        logger,
+                        "Payment {:?} of {} wei from {} announced by its payer was not found in block {}; dropping the notice",
+                        notice.tx_hash,
+                        notice.amount_wei.separate_with_commas(),
+                        notice.payer.wallet,
+                        notice.block_number
+                    );
+                    false
+                }
+                _ => true,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::blockchain_interface::data_structures::BlockchainTransaction;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::test_utils::{make_paying_wallet, make_wallet};
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::SystemTime;
+
+    fn make_notice(tx_hash_base: u32, amount_wei: u128, block_number: u64) -> PaymentNotice_0v1 {
+        PaymentNotice_0v1::new(
+            &make_paying_wallet(b"debtor"),
+            &make_wallet("earning"),
+            make_tx_hash(tx_hash_base),
+            amount_wei,
+            block_number,
+        )
+        .unwrap()
+    }
+
+    fn make_received_payments(
+        new_start_block: BlockMarker,
+        transactions: Vec<BlockchainTransaction>,
+    ) -> ReceivedPayments {
+        ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block,
+            transactions,
+            rechecked_opt: None,
+            pending: vec![],
+            response_skeleton_opt: None,
+        }
+    }
+
+    #[test]
+    fn accept_keeps_authentic_notices_addressed_to_us() {
+        init_test_logging();
+        let test_name = "accept_keeps_authentic_notices_addressed_to_us";
+        let logger = Logger::new(test_name);
+        let mut subject = PaymentNotices::default();
+        let notice = make_notice(123, 4_567_000_000, 1_000);
+
+        subject.start_block_opt = Some(1_000);
+
+        let first = subject.accept(notice.clone(), &make_wallet("earning"), &logger);
+        let second = subject.accept(notice, &make_wallet("earning"), &logger);
+
+        assert_eq!(first, true);
+        assert_eq!(second, false);
+        assert_eq!(subject.awaited.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: {} announced payment {:?} of 4,567,000,000 wei in block 1000; \
+            awaiting it in the receivable scan",
+            make_paying_wallet(b"debtor").as_address_wallet(),
+            make_tx_hash(123)
+        ));
+    }
+
+    #[test]
+    fn accept_ignores_notices_addressed_elsewhere_forged_or_late() {
+        init_test_logging();
+        let test_name = "accept_ignores_notices_addressed_elsewhere_forged_or_late";
+        let logger = Logger::new(test_name);
+        let mut subject = PaymentNotices::default();
+        let forged = PaymentNotice_0v1 {
+            amount_wei: U256::from(5_000_000_000u64),
+            ..make_notice(456, 4_567_000_000, 1_000)
+        };
+        subject.start_block_opt = Some(1_000);
+
+        let elsewhere = subject.accept(
+            make_notice(123, 4_567_000_000, 1_000),
+            &make_wallet("another earning"),
+            &logger,
+        );
+        let forged = subject.accept(forged, &make_wallet("earning"), &logger);
+        let late = subject.accept(
+            make_notice(789, 4_567_000_000, 999),
+            &make_wallet("earning"),
+            &logger,
+        );
+
+        assert_eq!((elsewhere, forged, late), (false, false, false));
+        assert_eq!(subject.awaited.len(), 0);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring notice of payment {:?} addressed to {}, which is not our \
+            earning wallet",
+            make_tx_hash(123),
+            make_wallet("earning")
+        ));
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring notice of payment {:?} whose signature does not prove it \
+            comes from {}",
+            make_tx_hash(456),
+            make_paying_wallet(b"debtor").as_address_wallet()
+        ));
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Notice of payment {:?} from {} arrived after block 999 had been \
+            scanned",
+            make_tx_hash(789),
+            make_paying_wallet(b"debtor").as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn accept_gives_up_on_the_earliest_notice_when_there_are_too_many() {
+        init_test_logging();
+        let test_name = "accept_gives_up_on_the_earliest_notice_when_there_are_too_many";
+        let logger = Logger::new(test_name);
+        let mut subject = PaymentNotices::default();
+        (0..MAX_AWAITED_PAYMENT_NOTICES as u32).for_each(|idx| {
+            subject.accept(
+                make_notice(idx, 1_000_000, 1_000),
+                &make_wallet("earning"),
+                &logger,
+            );
+        });
+        let newest = make_notice(1_000, 1_000_000, 1_000);
+
+        let result = subject.accept(newest.clone(), &make_wallet("earning"), &logger);
+
+        assert_eq!(result, true);
+        assert_eq!(subject.awaited.len(), MAX_AWAITED_PAYMENT_NOTICES);
+        assert_eq!(subject.awaited.front().unwrap().tx_hash, make_tx_hash(1));
+        assert_eq!(subject.awaited.back(), Some(&newest));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Too many notices of payments awaited; giving up on payment {:?} \
+            from {}",
+            make_tx_hash(0),
+            make_paying_wallet(b"debtor").as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn is_scan_due_lets_notices_arriving_together_share_one_scan() {
+        let mut subject = PaymentNotices::default();
+        let first_notice_at = SystemTime::now();
+
+        let first = subject.is_scan_due(first_notice_at);
+        let soon_after = subject.is_scan_due(first_notice_at + Duration::from_secs(5));
+        let just_before_the_interval = subject.is_scan_due(
+            first_notice_at + MIN_INTERVAL_BETWEEN_NOTICED_SCANS - Duration::from_millis(1),
+        );
+        let after_the_interval =
+            subject.is_scan_due(first_notice_at + MIN_INTERVAL_BETWEEN_NOTICED_SCANS);
+        let soon_after_that = subject.is_scan_due(
+            first_notice_at + MIN_INTERVAL_BETWEEN_NOTICED_SCANS + Duration::from_secs(5),
+        );
+
+        assert_eq!(
+            (
+                first,
+                soon_after,
+                just_before_the_interval,
+                after_the_interval,
+                soon_after_that
+            ),
+            (true, false, false, true, false)
+        );
+    }
+
+    #[test]
+    fn reconcile_settles_received_payments_drops_missing_ones_and_keeps_the_rest() {
+        init_test_logging();
+        let test_name = "reconcile_settles_received_payments_drops_missing_ones_and_keeps_the_rest";
+        let logger = Logger::new(test_name);
+        let payer = make_paying_wallet(b"debtor").as_address_wallet();
+        let mut subject = PaymentNotices::default();
+        let received = make_notice(111, 1_000_000, 1_000);
+        let missing = make_notice(222, 2_000_000, 1_001);
+        let not_yet_scanned = make_notice(333, 3_000_000, 1_002);
+        vec![received, missing, not_yet_scanned.clone()]
+            .into_iter()
+            .for_each(|notice| {
+                subject.accept(notice, &make_wallet("earning"), &logger);
+            });
+        let msg = make_received_payments(
+            BlockMarker::Value(1_002),
+            vec![
+                BlockchainTransaction {
+                    block_number: 1_000,
+                    from: payer.clone(),
+                    wei_amount: 1_000_000,
+                },
+                BlockchainTransaction {
+                    block_number: 1_001,
+                    from: payer.clone(),
+                    wei_amount: 1_999_999,
+                },
+            ],
+        );
+
+        subject.reconcile(&msg, &logger);
+
+        assert_eq!(
+            subject,
+            PaymentNotices {
+                awaited: VecDeque::from(vec![not_yet_scanned]),
+                start_block_opt: Some(1_002),
+                last_noticed_scan_opt: None,
+            }
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "INFO: {test_name}: Payment {:?} of 1,000,000 wei from {} announced by its payer has \
+            been received",
+            make_tx_hash(111),
+            payer
+        ));
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Payment {:?} of 2,000,000 wei from {} announced by its payer was \
+            not found in block 1001; dropping the notice",
+            make_tx_hash(222),
+            payer
+        ));
+    }
+
+    #[test]
+    fn reconcile_keeps_notices_while_no_scan_has_reached_their_block() {
+        let logger = Logger::new("test");
+        let mut subject = PaymentNotices::default();
+        subject.accept(
+            make_notice(111, 1_000_000, 1_000),
+            &make_wallet("earning"),
+            &logger,
+        );
+
+        subject.reconcile(
+            &make_received_payments(BlockMarker::Uninitialized, vec![]),
+            &logger,
+        );
+        subject.reconcile(
+            &make_received_payments(BlockMarker::Value(1_000), vec![]),
+            &logger,
+        );
+        subject.reconcile(
+            &make_received_payments(BlockMarker::Uninitialized, vec![]),
+            &logger,
+        );
+
+        assert_eq!(subject.awaited.len(), 1);
+        assert_eq!(subject.start_block_opt, Some(1_000));
+    }
+}
@@ -0,0 +1,446 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use masq_lib::logger::Logger;
+use serde_derive::Deserialize;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub const PRICE_FEED_REFRESH_INTERVAL: Duration = Duration::from_secs(600);
+pub const PRICE_FEED_MAX_QUOTE_AGE: Duration = Duration::from_secs(3_600);
+pub const PRICE_FEED_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const WEIS_IN_WHOLE_UNIT: f64 = 1_000_000_000_000_000_000.0;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FiatQuote {
+    pub currency: String,
+    pub transaction_fee_unit_price: f64,
+    pub service_fee_unit_price: f64,
+}
+
+impl FiatQuote {
+    pub fn transaction_fee_in_fiat(&self, wei: u128) -> f64 {
+        wei as f64 / WEIS_IN_WHOLE_UNIT * self.transaction_fee_unit_price
+    }
+
+    pub fn service_fee_in_fiat(&self, wei: i128) -> f64 {
+        wei as f64 / WEIS_IN_WHOLE_UNIT * self.service_fee_unit_price
+    }
+}
+
+pub fn format_fiat(amount: f64) -> String {
+    format!("{:.2}", amount)
+}
+
+// Supplies exchange rates for showing fees and balances in a fiat currency. The values are
+// meant for the user's orientation only; nothing in the Node's accounting may depend on them.
+pub trait PriceFeed {
+    // Never blocks; yields None until a fresh enough quote is at hand
+    fn quote_opt(&self) -> Option<FiatQuote>;
+}
+
+pub fn make_price_feed(url_opt: Option<String>) -> Box<dyn PriceFeed> {
+    match url_opt {
+        None => Box::new(PriceFeedNull {}),
+        Some(url) => Box::new(PriceFeedReal::new(Arc::new(HttpPriceSource::new(url)))),
+    }
+}
+
+// The default: without a price feed configured, the Node contacts no third party at all
+pub struct PriceFeedNull {}
+
+impl PriceFeed for PriceFeedNull {
+    fn quote_opt(&self) -> Option<FiatQuote> {
+        None
+    }
+}
+
+pub trait PriceSource: Send + Sync {
+    fn fetch(&self) -> Result<FiatQuote, String>;
+}
+
+#[derive(Default)]
+struct PriceCache {
+    quote_opt: Option<(FiatQuote, SystemTime)>,
+    last_attempt_opt: Option<SystemTime>,
+}
+
+// Serves the cached quote right away and, if it has grown old, asks the source for a new one
+// in a background thread, so that a slow or unreachable source can never stall the Accountant
+pub struct PriceFeedReal {
+    source: Arc<dyn PriceSource>,
+    cache: Arc<Mutex<PriceCache>>,
+    logger: Logger,
+}
+
+impl PriceFeed for PriceFeedReal {
+    fn quote_opt(&self) -> Option<FiatQuote> {
+        let now = SystemTime::now();
+        let mut cache = self.cache.lock().expect("Price cache poisoned");
+        if Self::is_due(cache.last_attempt_opt, now, PRICE_FEED_REFRESH_INTERVAL) {
+            cache.last_attempt_opt = Some(now);
+            self.refresh_in_background()
+        }
+        match &cache.quote_opt {
+            Some((quote, timestamp))
+                if !Self::is_due(Some(*timestamp), now, PRICE_FEED_MAX_QUOTE_AGE) =>
+            {
+                Some(quote.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl PriceFeedReal {
+    pub fn new(source: Arc<dyn PriceSource>) -> Self {
+        Self {
+            source,
+            cache: Arc::new(Mutex::new(PriceCache::default())),
+            logger: Logger::new("PriceFeed"),
+        }
+    }
+
+    fn is_due(timestamp_opt: Option<SystemTime>, now: SystemTime, interval: Duration) -> bool {
+        match timestamp_opt {
+            None => true,
+            Some(timestamp) => match now.duration_since(timestamp) {
+                Ok(elapsed) => elapsed >= interval,
+                Err(_) => false,
+            },
+        }
+    }
+
+    fn refresh_in_background(&self) {
+        let source = Arc::clone(&self.source);
+        let cache = Arc::clone(&self.cache);
+        let logger = self.logger.clone();
+        thread::spawn(move || match source.fetch() {
+            Ok(quote) => {
+                debug!(logger, "Received fresh quote from price feed: {:?}", quote);
+                cache.lock().expect("Price cache poisoned").quote_opt =
+                    Some((quote, SystemTime::now()))
+            }
+            Err(e) => warning!(
+                logger,
+                "Price feed could not be read: {}; fiat values stay unavailable until it can",
+                e
+            ),
+        });
+    }
+}
+
+// The URL is expected to answer a plain GET with a JSON object like
+// {"currency": "USD", "transactionFeeUnitPrice": 2345.67, "serviceFeeUnitPrice": 0.1234},
+// stating the price of a whole unit of the chain's native currency and of a whole MASQ token
+pub struct HttpPriceSource {
+    url: String,
+}
+
+impl PriceSource for HttpPriceSource {
+    fn fetch(&self) -> Result<FiatQuote, String> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(PRICE_FEED_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("client setup failed: {}", e))?;
+        let body = client
+            .get(&self.url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map_err(|e| format!("request to {} failed: {}", self.url, e))?;
+        parse_quote(&body)
+    }
+}
+
+impl HttpPriceSource {
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+}
+
+#[derive(Deserialize)]
+struct QuoteBody {
+    currency: String,
+    #[serde(rename = "transactionFeeUnitPrice")]
+    transaction_fee_unit_price: f64,
+    #[serde(rename = "serviceFeeUnitPrice")]
+    service_fee_unit_price: f64,
+}
+
+fn parse_quote(body: &str) -> Result<FiatQuote, String> {
+    let quote_body: QuoteBody =
+        serde_json::from_str(body).map_err(|e| format!("unexpected response '{}': {}", body, e))?;
+    let valid_price = |price: f64| price.is_finite() && price >= 0.0;
+    if quote_body.currency.is_empty()
+        || !valid_price(quote_body.transaction_fee_unit_price)
+        || !valid_price(quote_body.service_fee_unit_price)
+    {
+        return Err(format!("implausible quote '{}'", body));
+    }
+    Ok(FiatQuote {
+        currency: quote_body.currency,
+        transaction_fee_unit_price: quote_body.transaction_fee_unit_price,
+        service_fee_unit_price: quote_body.service_fee_unit_price,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::utils::localhost;
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpListener};
+    use std::time::Instant;
+
+    #[derive(Default)]
+    struct PriceSourceMock {
+        fetch_params: Arc<Mutex<Vec<()>>>,
+        fetch_results: Mutex<Vec<Result<FiatQuote, String>>>,
+    }
+
+    impl PriceSource for PriceSourceMock {
+        fn fetch(&self) -> Result<FiatQuote, String> {
+            self.fetch_params.lock().unwrap().push(());
+            self.fetch_results.lock().unwrap().remove(0)
+        }
+    }
+
+    impl PriceSourceMock {
+        fn fetch_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+            self.fetch_params = params.clone();
+            self
+        }
+
+        fn fetch_result(self, result: Result<FiatQuote, String>) -> Self {
+            self.fetch_results.lock().unwrap().push(result);
+            self
+        }
+    }
+
+    fn make_quote() -> FiatQuote {
+        FiatQuote {
+            currency: "USD".to_string(),
+            transaction_fee_unit_price: 2_000.0,
+            service_fee_unit_price: 0.25,
+        }
+    }
+
+    fn wait_for_fetches(params: &Arc<Mutex<Vec<()>>>, count: usize) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while params.lock().unwrap().len() < count {
+            assert!(Instant::now() < deadline, "Price source not asked in time");
+            thread::sleep(Duration::from_millis(10))
+        }
+        thread::sleep(Duration::from_millis(100))
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(PRICE_FEED_REFRESH_INTERVAL, Duration::from_secs(600));
+        assert_eq!(PRICE_FEED_MAX_QUOTE_AGE, Duration::from_secs(3_600));
+        assert_eq!(PRICE_FEED_REQUEST_TIMEOUT, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn make_price_feed_stays_silent_without_url() {
+        let subject = make_price_feed(None);
+
+        assert_eq!(subject.quote_opt(), None);
+    }
+
+    #[test]
+    fn quote_converts_wei_into_fiat() {
+        let quote = make_quote();
+
+        assert_eq!(
+            format_fiat(quote.transaction_fee_in_fiat(3_000_000_000_000_000)),
+            "6.00"
+        );
+        assert_eq!(
+            format_fiat(quote.service_fee_in_fiat(-40_000_000_000_000_000_000)),
+            "-10.00"
+        );
+        assert_eq!(
+            format_fiat(quote.service_fee_in_fiat(123_456_789_000_000_000)),
+            "0.03"
+        );
+    }
+
+    #[test]
+    fn price_feed_serves_quote_after_fetching_it_in_background() {
+        let fetch_params_arc = Arc::new(Mutex::new(vec![]));
+        let source = PriceSourceMock::default()
+            .fetch_params(&fetch_params_arc)
+            .fetch_result(Ok(make_quote()));
+        let subject = PriceFeedReal::new(Arc::new(source));
+
+        let first_result = subject.quote_opt();
+        wait_for_fetches(&fetch_params_arc, 1);
+        let second_result = subject.quote_opt();
+
+        assert_eq!(first_result, None);
+        assert_eq!(second_result, Some(make_quote()));
+        assert_eq!(fetch_params_arc.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn price_feed_asks_again_once_refresh_interval_elapsed_and_keeps_serving_meanwhile() {
+        let fetch_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut newer_quote = make_quote();
+        newer_quote.transaction_fee_unit_price = 2_100.0;
+        let source = PriceSourceMock::default()
+            .fetch_params(&fetch_params_arc)
+            .fetch_result(Ok(newer_quote.clone()));
+        let subject = PriceFeedReal::new(Arc::new(source));
+        let a_while_ago = SystemTime::now()
+            .checked_sub(PRICE_FEED_REFRESH_INTERVAL)
+            .unwrap();
+        {
+            let mut cache = subject.cache.lock().unwrap();
+            cache.quote_opt = Some((make_quote(), a_while_ago));
+            cache.last_attempt_opt = Some(a_while_ago);
+        }
+
+        let first_result = subject.quote_opt();
+        wait_for_fetches(&fetch_params_arc, 1);
+        let second_result = subject.quote_opt();
+
+        assert_eq!(first_result, Some(make_quote()));
+        assert_eq!(second_result, Some(newer_quote));
+        assert_eq!(fetch_params_arc.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn price_feed_withholds_quote_too_old_to_be_trusted() {
+        let fetch_params_arc = Arc::new(Mutex::new(vec![]));
+        let source = PriceSourceMock::default().fetch_params(&fetch_params_arc);
+        let subject = PriceFeedReal::new(Arc::new(source));
+        let now = SystemTime::now();
+        {
+            let mut cache = subject.cache.lock().unwrap();
+            cache.quote_opt = Some((
+                make_quote(),
+                now.checked_sub(PRICE_FEED_MAX_QUOTE_AGE).unwrap(),
+            ));
+            cache.last_attempt_opt = Some(now);
+        }
+
+        let result = subject.quote_opt();
+
+        assert_eq!(result, None);
+        assert_eq!(fetch_params_arc.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn price_feed_logs_failed_fetch_and_waits_for_next_interval_before_retrying() {
+        init_test_logging();
+        let fetch_params_arc = Arc::new(Mutex::new(vec![]));
+        let source = PriceSourceMock::default()
+            .fetch_params(&fetch_params_arc)
+            .fetch_result(Err("booga".to_string()));
+        let subject = PriceFeedReal::new(Arc::new(source));
+
+        let first_result = subject.quote_opt();
+        wait_for_fetches(&fetch_params_arc, 1);
+        let second_result = subject.quote_opt();
+
+        assert_eq!(first_result, None);
+        assert_eq!(second_result, None);
+        assert_eq!(fetch_params_arc.lock().unwrap().len(), 1);
+        TestLogHandler::new().exists_log_containing(
+            "WARN: PriceFeed: Price feed could not be read: booga; fiat values stay unavailable \
+            until it can",
+        );
+    }
+
+    #[test]
+    fn parse_quote_accepts_well_formed_body() {
+        let result = parse_quote(
+            r#"{"currency": "USD", "transactionFeeUnitPrice": 2000, "serviceFeeUnitPrice": 0.25}"#,
+        );
+
+        assert_eq!(result, Ok(make_quote()));
+    }
+
+    #[test]
+    fn parse_quote_rejects_malformed_body() {
+        let result = parse_quote(r#"{"currency": "USD", "price": 2000}"#);
+
+        let err = result.unwrap_err();
+        assert!(
+            err.starts_with(r#"unexpected response '{"currency": "USD", "price": 2000}': "#),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn parse_quote_rejects_negative_price_and_missing_currency() {
+        let negative_price =
+            r#"{"currency": "USD", "transactionFeeUnitPrice": -1, "serviceFeeUnitPrice": 0.25}"#;
+        let no_currency =
+            r#"{"currency": "", "transactionFeeUnitPrice": 1, "serviceFeeUnitPrice": 0.25}"#;
+
+        assert_eq!(
+            parse_quote(negative_price),
+            Err(format!("implausible quote '{}'", negative_price))
+        );
+        assert_eq!(
+            parse_quote(no_currency),
+            Err(format!("implausible quote '{}'", no_currency))
+        );
+    }
+
+    #[test]
+    fn http_price_source_reads_quote_from_url() {
+        let listener = TcpListener::bind(SocketAddr::new(localhost(), 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server_handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let len = stream.read(&mut buf).unwrap();
+            let body =
+                r#"{"currency":"USD","transactionFeeUnitPrice":2000.0,"serviceFeeUnitPrice":0.25}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8_lossy(&buf[..len]).to_string()
+        });
+        let subject = HttpPriceSource::new(format!("http://127.0.0.1:{}/quote", port));
+
+        let result = subject.fetch();
+
+        assert_eq!(result, Ok(make_quote()));
+        let request = server_handle.join().unwrap();
+        assert!(
+            request.starts_with("GET /quote HTTP/1.1\r\n"),
+            "{}",
+            request
+        );
+    }
+
+    #[test]
+    fn http_price_source_reports_unreachable_url() {
+        let port = {
+            let listener = TcpListener::bind(SocketAddr::new(localhost(), 0)).unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let url = format!("http://127.0.0.1:{}/quote", port);
+        let subject = HttpPriceSource::new(url.clone());
+
+        let result = subject.fetch();
+
+        let err = result.unwrap_err();
+        assert!(
+            err.starts_with(&format!("request to {} failed: ", url)),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::consistency_dao::Anomaly;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RepairPlan {
+    pub id: u64,
+    pub anomalies: Vec<Anomaly>,
+}
+
+// The repairs last proposed to the operator, kept until confirmed. Plans are numbered so that a
+// confirmation never applies a plan other than the one the operator has seen
+#[derive(Default)]
+pub struct RepairPlans {
+    proposed: u64,
+    pending_opt: Option<RepairPlan>,
+}
+
+impl RepairPlans {
+    // With no anomalies, there's nothing to confirm, and an older plan is outdated
+    pub fn propose(&mut self, anomalies: Vec<Anomaly>) -> Option<RepairPlan> {
+        if anomalies.is_empty() {
+            self.pending_opt = None;
+            return None;
+        }
+        self.proposed += 1;
+        let plan = RepairPlan {
+            id: self.proposed,
+            anomalies,
+        };
+        self.pending_opt = Some(plan.clone());
+        Some(plan)
+    }
+
+    pub fn take(&mut self, plan_id: u64) -> Result<RepairPlan, String> {
+        match self.pending_opt.take() {
+            Some(plan) if plan.id == plan_id => Ok(plan),
+            Some(plan) => {
+                let error = format!(
+                    "Repair plan {} is outdated; the latest one is {}",
+                    plan_id, plan.id
+                );
+                self.pending_opt = Some(plan);
+                Err(error)
+            }
+            None => Err(format!(
+                "Repair plan {} is not waiting for confirmation; check the consistency again",
+                plan_id
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::consistency_dao::Anomaly;
+    use crate::accountant::repair_plan::{RepairPlan, RepairPlans};
+    use crate::test_utils::make_wallet;
+
+    fn make_anomaly(n: u64) -> Anomaly {
+        Anomaly::DanglingPayable {
+            wallet: make_wallet(&format!("creditor_{}", n)),
+            balance_wei: 1_000,
+            pending_payable_rowid: n,
+        }
+    }
+
+    #[test]
+    fn proposed_plans_are_numbered_and_taken_by_their_number() {
+        let mut subject = RepairPlans::default();
+
+        let first = subject.propose(vec![make_anomaly(1)]);
+        let second = subject.propose(vec![make_anomaly(2)]);
+        let outdated = subject.take(1);
+        let taken = subject.take(2);
+        let taken_again = subject.take(2);
+
+        assert_eq!(
+            first,
+            Some(RepairPlan {
+                id: 1,
+                anomalies: vec![make_anomaly(1)]
+            })
+        );
+        let expected_second = RepairPlan {
+            id: 2,
+            anomalies: vec![make_anomaly(2)],
+        };
+        assert_eq!(second, Some(expected_second.clone()));
+        assert_eq!(
+            outdated,
+            Err("Repair plan 1 is outdated; the latest one is 2".to_string())
+        );
+        assert_eq!(taken, Ok(expected_second));
+        assert_eq!(
+            taken_again,
+            Err(
+                "Repair plan 2 is not waiting for confirmation; check the consistency again"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn finding_no_anomalies_withdraws_the_pending_plan() {
+        let mut subject = RepairPlans::default();
+        subject.propose(vec![make_anomaly(1)]);
+
+        let result = subject.propose(vec![]);
+
+        assert_eq!(result, None);
+        assert_eq!(
+            subject.take(1),
+            Err(
+                "Repair plan 1 is not waiting for confirmation; check the consistency again"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            subject.propose(vec![make_anomaly(2)]).map(|plan| plan.id),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::logger::Logger;
+use serde_json::json;
+use std::collections::HashMap;
+
+// Double-checks what the payment adjuster made of the qualified payables before any of it reaches
+// the blockchain. A broken invariant means a bug in the adjuster, so the whole batch is dropped:
+// its debts stay unpaid in the database for a later scan instead of being paid wrongly. On by
+// default in debug builds; --adjustment-invariants switches it either way.
+pub struct AdjustmentInvariants {
+    pub enabled: bool,
+}
+
+impl AdjustmentInvariants {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    pub fn enforce(
+        &self,
+        qualified_payables: &[PayableAccount],
+        service_fee_balance_wei: u128,
+        instructions: OutboundPaymentsInstructions,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions {
+        if !self.enabled {
+            return instructions;
+        }
+        let violations = find_violations(
+            qualified_payables,
+            service_fee_balance_wei,
+            &instructions.affordable_accounts,
+        );
+        if violations.is_empty() {
+            return instructions;
+        }
+        error!(
+            logger,
+            "Payment adjustment broke its invariants; nothing is paid in this scan. Diagnostics: {}",
+            diagnostics(
+                &violations,
+                qualified_payables,
+                service_fee_balance_wei,
+                &instructions.affordable_accounts
+            )
+        );
+        OutboundPaymentsInstructions {
+            affordable_accounts: vec![],
+            ..instructions
+        }
+    }
+}
+
+fn find_violations(
+    qualified_payables: &[PayableAccount],
+    service_fee_balance_wei: u128,
+    adjusted_accounts: &[PayableAccount],
+) -> Vec<String> {
+    let mut violations = vec![];
+    let total_allocated_wei = adjusted_accounts.iter().fold(0_u128, |sum, account| {
+        sum.saturating_add(account.balance_wei)
+    });
+    if total_allocated_wei > service_fee_balance_wei {
+        violations.push(format!(
+            "{} wei allocated in total exceeds the service fee balance of {} wei",
+            total_allocated_wei, service_fee_balance_wei
+        ))
+    }
+    let original_balances: HashMap<&Wallet, u128> = qualified_payables
+        .iter()
+        .map(|account| (&account.wallet, account.balance_wei))
+        .collect();
+    adjusted_accounts
+        .iter()
+        .for_each(|account| match original_balances.get(&account.wallet) {
+            None => violations.push(format!(
+                "{} is paid without having been among the qualified payables",
+                account.wallet
+            )),
+            Some(original_balance_wei) if account.balance_wei > *original_balance_wei => violations
+                .push(format!(
+                    "{} is allocated {} wei, more than its balance of {} wei",
+                    account.wallet, account.balance_wei, original_balance_wei
+                )),
+            Some(_) => (),
+        });
+    violations
+}
+
+// Amounts go as strings; JSON numbers can't be relied on to hold a u128
+fn diagnostics(
+    violations: &[String],
+    qualified_payables: &[PayableAccount],
+    service_fee_balance_wei: u128,
+    adjusted_accounts: &[PayableAccount],
+) -> String {
+    let accounts_json = |accounts: &[PayableAccount]| {
+        accounts
+            .iter()
+            .map(|account| {
+                json!({
+                    "wallet": account.wallet.to_string(),
+                    "balanceWei": account.balance_wei.to_string(),
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    json!({
+        "violations": violations,
+        "serviceFeeBalanceWei": service_fee_balance_wei.to_string(),
+        "qualifiedPayables": accounts_json(qualified_payables),
+        "adjustedAccounts": accounts_json(adjusted_accounts),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::UNIX_EPOCH;
+
+    fn make_account(name: &str, balance_wei: u128) -> PayableAccount {
+        PayableAccount {
+            wallet: make_wallet(name),
+            balance_wei,
+            last_paid_timestamp: UNIX_EPOCH,
+            pending_payable_opt: None,
+        }
+    }
+
+    fn make_instructions(adjusted_accounts: Vec<PayableAccount>) -> OutboundPaymentsInstructions {
+        OutboundPaymentsInstructions::new(
+            adjusted_accounts,
+            Box::new(BlockchainAgentMock::default()),
+            None,
+        )
+    }
+
+    fn qualified_payables() -> Vec<PayableAccount> {
+        vec![make_account("first", 1_000), make_account("second", 2_000)]
+    }
+
+    #[test]
+    fn sound_adjustment_passes_untouched() {
+        let subject = AdjustmentInvariants::new(true);
+        let adjusted_accounts = vec![make_account("first", 1_000), make_account("second", 500)];
+
+        let result = subject.enforce(
+            &qualified_payables(),
+            1_500,
+            make_instructions(adjusted_accounts.clone()),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result.affordable_accounts, adjusted_accounts);
+    }
+
+    #[test]
+    fn broken_adjustment_is_dropped_whole_with_diagnostics() {
+        init_test_logging();
+        let test_name = "broken_adjustment_is_dropped_whole_with_diagnostics";
+        let subject = AdjustmentInvariants::new(true);
+        let adjusted_accounts = vec![
+            make_account("first", 1_001),
+            make_account("second", 500),
+            make_account("stranger", 1),
+        ];
+
+        let result = subject.enforce(
+            &qualified_payables(),
+            1_000,
+            make_instructions(adjusted_accounts),
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(result.affordable_accounts, vec![]);
+        let expected_diagnostics = json!({
+            "violations": [
+                "1502 wei allocated in total exceeds the service fee balance of 1000 wei",
+                format!(
+                    "{} is allocated 1001 wei, more than its balance of 1000 wei",
+                    make_wallet("first")
+                ),
+                format!(
+                    "{} is paid without having been among the qualified payables",
+                    make_wallet("stranger")
+                ),
+            ],
+            "serviceFeeBalanceWei": "1000",
+            "qualifiedPayables": [
+                {"wallet": make_wallet("first").to_string(), "balanceWei": "1000"},
+                {"wallet": make_wallet("second").to_string(), "balanceWei": "2000"},
+            ],
+            "adjustedAccounts": [
+                {"wallet": make_wallet("first").to_string(), "balanceWei": "1001"},
+                {"wallet": make_wallet("second").to_string(), "balanceWei": "500"},
+                {"wallet": make_wallet("stranger").to_string(), "balanceWei": "1"},
+            ],
+        });
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: {test_name}: Payment adjustment broke its invariants; nothing is paid in this \
+             scan. Diagnostics: {}",
+            expected_diagnostics
+        ));
+    }
+
+    #[test]
+    fn disabled_invariants_let_anything_through() {
+        let subject = AdjustmentInvariants::new(false);
+        let adjusted_accounts = vec![make_account("first", 5_000)];
+
+        let result = subject.enforce(
+            &qualified_payables(),
+            0,
+            make_instructions(adjusted_accounts.clone()),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result.affordable_accounts, adjusted_accounts);
+    }
+}
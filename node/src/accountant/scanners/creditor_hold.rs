@@ -0,0 +1,419 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::sub_lib::accountant::{
+    ReportCreditorReachabilityMessage, ReportExitFailoverMessage, UnreachableCreditorHold,
+};
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::logger::Logger;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+use thousands::Separable;
+
+// What the Neighborhood has reported about creditors' Nodes leaving it and coming back, and about
+// the creditors in the exit country it has newly selected. A creditor it has never reported on is
+// taken for reachable.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct CreditorReachability {
+    unreachable_since: HashMap<Wallet, SystemTime>,
+    exit_creditors: HashSet<Wallet>,
+}
+
+impl CreditorReachability {
+    pub fn update(&mut self, msg: &ReportCreditorReachabilityMessage) {
+        msg.reachable.iter().for_each(|wallet| {
+            self.unreachable_since.remove(wallet);
+        });
+        msg.unreachable.iter().for_each(|wallet| {
+            self.unreachable_since
+                .entry(wallet.clone())
+                .or_insert(msg.timestamp);
+        });
+    }
+
+    pub fn unreachable_since(&self, wallet: &Wallet) -> Option<SystemTime> {
+        self.unreachable_since.get(wallet).copied()
+    }
+
+    // The creditors in a newly selected exit country replace those in the one before it
+    pub fn favor_exit_creditors(&mut self, msg: &ReportExitFailoverMessage) {
+        self.exit_creditors = msg.creditors.iter().cloned().collect();
+    }
+
+    // They are favored in the next payment adjustment only
+    pub fn take_exit_creditors(&mut self) -> HashSet<Wallet> {
+        mem::take(&mut self.exit_creditors)
+    }
+}
+
+// Keeps qualified debts to creditors that have been unreachable for too long out of the payable
+// scan, so that they take no part in payment batching or adjustment
+pub struct CreditorHold {
+    pub hold_opt: Option<UnreachableCreditorHold>,
+    reachability: Rc<RefCell<CreditorReachability>>,
+    held: HashSet<Wallet>,
+}
+
+impl CreditorHold {
+    pub fn new(
+        hold_opt: Option<UnreachableCreditorHold>,
+        reachability: Rc<RefCell<CreditorReachability>>,
+    ) -> Self {
+        Self {
+            hold_opt,
+            reachability,
+            held: HashSet::new(),
+        }
+    }
+
+    pub fn release_payables(
+        &mut self,
+        qualified_payables: Vec<PayableAccount>,
+        deadline_of: &dyn Fn(&PayableAccount) -> SystemTime,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> Vec<PayableAccount> {
+        let hold = match self.hold_opt {
+            Some(hold) => hold,
+            None => return qualified_payables,
+        };
+        let secs_between = |earlier: SystemTime, later: SystemTime| {
+            later
+                .duration_since(earlier)
+                .unwrap_or(Duration::from_secs(0))
+                .as_secs()
+        };
+        let reachability = self.reachability.borrow();
+        let mut still_held = HashSet::new();
+        let released = qualified_payables
+            .into_iter()
+            .filter(|payable| {
+                let wallet = &payable.wallet;
+                let absent_sec_opt = reachability
+                    .unreachable_since(wallet)
+                    .map(|since| secs_between(since, now));
+                match absent_sec_opt {
+                    Some(absent_sec) if absent_sec >= hold.absence_sec => {
+                        let sec_to_deadline = secs_between(now, deadline_of(payable));
+                        if sec_to_deadline > hold.deadline_margin_sec {
+                            if !self.held.contains(wallet) {
+                                info!(
+                                    logger,
+                                    "Holding the debt of {} wei to {}, whose Node has been gone \
+                                     from the Neighborhood for {} sec",
+                                    payable.balance_wei.separate_with_commas(),
+                                    wallet,
+                                    absent_sec
+                                );
+                            }
+                            still_held.insert(wallet.clone());
+                            false
+                        } else {
+                            warning!(
+                                logger,
+                                "Releasing the debt to {} although its Node is still gone from \
+                                 the Neighborhood; its payment deadline is only {} sec away",
+                                wallet,
+                                sec_to_deadline
+                            );
+                            true
+                        }
+                    }
+                    _ => {
+                        if self.held.contains(wallet) {
+                            info!(
+                                logger,
+                                "Releasing the debt to {}, whose Node is back in the Neighborhood",
+                                wallet
+                            );
+                        }
+                        true
+                    }
+                }
+            })
+            .collect();
+        drop(reachability);
+        self.held = still_held;
+        released
+    }
+
+    pub fn take_exit_creditors(&self) -> HashSet<Wallet> {
+        self.reachability.borrow_mut().take_exit_creditors()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::gwei_to_wei;
+    use crate::accountant::scanners::creditor_hold::{CreditorHold, CreditorReachability};
+    use crate::sub_lib::accountant::{
+        ReportCreditorReachabilityMessage, ReportExitFailoverMessage, UnreachableCreditorHold,
+    };
+    use crate::test_utils::make_wallet;
+    use masq_lib::logger::Logger;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+    use std::rc::Rc;
+    use std::time::{Duration, SystemTime};
+
+    const ABSENCE_SEC: u64 = 3_600;
+    const DEADLINE_MARGIN_SEC: u64 = 600;
+
+    fn make_payable(name: &str, balance_gwei: u64) -> PayableAccount {
+        PayableAccount {
+            wallet: make_wallet(name),
+            balance_wei: gwei_to_wei(balance_gwei),
+            last_paid_timestamp: SystemTime::now(),
+            pending_payable_opt: None,
+        }
+    }
+
+    fn make_hold(reachability: &Rc<RefCell<CreditorReachability>>) -> CreditorHold {
+        CreditorHold::new(
+            Some(UnreachableCreditorHold {
+                absence_sec: ABSENCE_SEC,
+                deadline_margin_sec: DEADLINE_MARGIN_SEC,
+            }),
+            Rc::clone(reachability),
+        )
+    }
+
+    fn report(
+        reachability: &Rc<RefCell<CreditorReachability>>,
+        timestamp: SystemTime,
+        reachable: Vec<&str>,
+        unreachable: Vec<&str>,
+    ) {
+        reachability
+            .borrow_mut()
+            .update(&ReportCreditorReachabilityMessage {
+                timestamp,
+                reachable: reachable.into_iter().map(make_wallet).collect(),
+                unreachable: unreachable.into_iter().map(make_wallet).collect(),
+            })
+    }
+
+    #[test]
+    fn reachability_remembers_when_each_creditor_became_unreachable() {
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(100);
+        let reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+
+        report(&reachability, now, vec![], vec!["gone", "back"]);
+        report(
+            &reachability,
+            later,
+            vec!["back"],
+            vec!["gone", "newly gone"],
+        );
+
+        let subject = reachability.borrow();
+        assert_eq!(subject.unreachable_since(&make_wallet("gone")), Some(now));
+        assert_eq!(
+            subject.unreachable_since(&make_wallet("newly gone")),
+            Some(later)
+        );
+        assert_eq!(subject.unreachable_since(&make_wallet("back")), None);
+        assert_eq!(subject.unreachable_since(&make_wallet("unknown")), None);
+    }
+
+    #[test]
+    fn exit_creditors_of_the_latest_failover_are_taken_once() {
+        let reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        let failover = |country: &str, creditors: Vec<&str>| ReportExitFailoverMessage {
+            timestamp: SystemTime::now(),
+            exit_country: country.to_string(),
+            creditors: creditors.into_iter().map(make_wallet).collect(),
+        };
+        reachability
+            .borrow_mut()
+            .favor_exit_creditors(&failover("CZ", vec!["czech"]));
+        reachability
+            .borrow_mut()
+            .favor_exit_creditors(&failover("SK", vec!["slovak 1", "slovak 2"]));
+        let subject = CreditorHold::new(None, Rc::clone(&reachability));
+
+        let first_take = subject.take_exit_creditors();
+        let second_take = subject.take_exit_creditors();
+
+        assert_eq!(
+            first_take,
+            vec![make_wallet("slovak 1"), make_wallet("slovak 2")]
+                .into_iter()
+                .collect::<HashSet<_>>()
+        );
+        assert_eq!(second_take, HashSet::new());
+    }
+
+    #[test]
+    fn without_hold_every_qualified_payable_is_released() {
+        let now = SystemTime::now();
+        let reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        report(
+            &reachability,
+            now - Duration::from_secs(ABSENCE_SEC * 10),
+            vec![],
+            vec!["gone"],
+        );
+        let mut subject = CreditorHold::new(None, Rc::clone(&reachability));
+        let payables = vec![make_payable("gone", 1_000), make_payable("here", 1_000)];
+
+        let result = subject.release_payables(
+            payables.clone(),
+            &|_| now + Duration::from_secs(86_400),
+            now,
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result, payables);
+    }
+
+    #[test]
+    fn debts_to_creditors_gone_for_the_absence_period_are_held() {
+        init_test_logging();
+        let test_name = "debts_to_creditors_gone_for_the_absence_period_are_held";
+        let logger = Logger::new(test_name);
+        let now = SystemTime::now();
+        let reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        report(
+            &reachability,
+            now - Duration::from_secs(ABSENCE_SEC),
+            vec![],
+            vec!["long gone"],
+        );
+        report(
+            &reachability,
+            now - Duration::from_secs(ABSENCE_SEC - 1),
+            vec![],
+            vec!["recently gone"],
+        );
+        let mut subject = make_hold(&reachability);
+        let long_gone = make_payable("long gone", 1_000);
+        let recently_gone = make_payable("recently gone", 2_000);
+        let here = make_payable("here", 3_000);
+        let far_deadline = |_: &PayableAccount| now + Duration::from_secs(86_400);
+
+        let first_result = subject.release_payables(
+            vec![long_gone.clone(), recently_gone.clone(), here.clone()],
+            &far_deadline,
+            now,
+            &logger,
+        );
+        let second_result = subject.release_payables(
+            vec![long_gone.clone(), recently_gone.clone(), here.clone()],
+            &far_deadline,
+            now + Duration::from_secs(1),
+            &logger,
+        );
+
+        assert_eq!(first_result, vec![recently_gone.clone(), here.clone()]);
+        assert_eq!(second_result, vec![here]);
+        let log_handler = TestLogHandler::new();
+        log_handler.assert_logs_contain_in_order(vec![
+            &format!(
+                "INFO: {test_name}: Holding the debt of 1,000,000,000,000 wei to {}, whose Node \
+                 has been gone from the Neighborhood for 3600 sec",
+                long_gone.wallet
+            ),
+            &format!(
+                "INFO: {test_name}: Holding the debt of 2,000,000,000,000 wei to {}, whose Node \
+                 has been gone from the Neighborhood for 3600 sec",
+                recently_gone.wallet
+            ),
+        ]);
+        assert_eq!(
+            subject.held,
+            HashSet::from([long_gone.wallet.clone(), recently_gone.wallet.clone()])
+        );
+    }
+
+    #[test]
+    fn held_debt_is_released_when_its_creditor_returns() {
+        init_test_logging();
+        let test_name = "held_debt_is_released_when_its_creditor_returns";
+        let logger = Logger::new(test_name);
+        let now = SystemTime::now();
+        let reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        report(
+            &reachability,
+            now - Duration::from_secs(ABSENCE_SEC * 2),
+            vec![],
+            vec!["wanderer"],
+        );
+        let mut subject = make_hold(&reachability);
+        let wanderer = make_payable("wanderer", 1_000);
+        let far_deadline = |_: &PayableAccount| now + Duration::from_secs(86_400);
+        let held_result =
+            subject.release_payables(vec![wanderer.clone()], &far_deadline, now, &logger);
+        report(&reachability, now, vec!["wanderer"], vec![]);
+
+        let released_result =
+            subject.release_payables(vec![wanderer.clone()], &far_deadline, now, &logger);
+
+        assert_eq!(held_result, vec![]);
+        assert_eq!(released_result, vec![wanderer.clone()]);
+        assert!(subject.held.is_empty());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Releasing the debt to {}, whose Node is back in the Neighborhood",
+            wanderer.wallet
+        ));
+    }
+
+    #[test]
+    fn held_debt_is_released_when_its_payment_deadline_nears() {
+        init_test_logging();
+        let test_name = "held_debt_is_released_when_its_payment_deadline_nears";
+        let logger = Logger::new(test_name);
+        let now = SystemTime::now();
+        let reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        report(
+            &reachability,
+            now - Duration::from_secs(ABSENCE_SEC * 2),
+            vec![],
+            vec!["gone"],
+        );
+        let mut subject = make_hold(&reachability);
+        let gone = make_payable("gone", 1_000);
+        let deadline = now + Duration::from_secs(DEADLINE_MARGIN_SEC + 10);
+
+        let result_before = subject.release_payables(
+            vec![gone.clone()],
+            &|_| deadline,
+            now + Duration::from_secs(9),
+            &logger,
+        );
+        let result_at = subject.release_payables(
+            vec![gone.clone()],
+            &|_| deadline,
+            now + Duration::from_secs(10),
+            &logger,
+        );
+        let result_past = subject.release_payables(
+            vec![gone.clone()],
+            &|_| deadline,
+            deadline + Duration::from_secs(1),
+            &logger,
+        );
+
+        assert_eq!(result_before, vec![]);
+        assert_eq!(result_at, vec![gone.clone()]);
+        assert_eq!(result_past, vec![gone.clone()]);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            &format!(
+                "WARN: {test_name}: Releasing the debt to {} although its Node is still gone \
+                 from the Neighborhood; its payment deadline is only 600 sec away",
+                gone.wallet
+            ),
+            &format!(
+                "WARN: {test_name}: Releasing the debt to {} although its Node is still gone \
+                 from the Neighborhood; its payment deadline is only 0 sec away",
+                gone.wallet
+            ),
+        ]);
+    }
+}
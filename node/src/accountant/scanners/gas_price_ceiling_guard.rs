@@ -0,0 +1,205 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::scanners::payment_urgency::PaymentUrgency;
+use crate::sub_lib::accountant::GasPriceCeiling;
+use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+use crate::sub_lib::wei::Wei;
+use masq_lib::logger::Logger;
+use std::time::SystemTime;
+use thousands::Separable;
+
+// While the gas price agreed for the batch is above the ceiling, only the debts close to their
+// deadlines are paid; the rest stay unpaid in the database until a later scan, hopefully with
+// cheaper gas, picks them up again.
+pub struct GasPriceCeilingGuard {
+    pub ceiling_opt: Option<GasPriceCeiling>,
+}
+
+impl GasPriceCeilingGuard {
+    pub fn new(ceiling_opt: Option<GasPriceCeiling>) -> Self {
+        Self { ceiling_opt }
+    }
+
+    pub fn defer_non_urgent(
+        &self,
+        instructions: OutboundPaymentsInstructions,
+        urgency: &PaymentUrgency,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions {
+        let ceiling = match self.ceiling_opt {
+            Some(ceiling) => ceiling,
+            None => return instructions,
+        };
+        let gas_price = instructions.agent.agreed_fee_per_computation_unit();
+        let ceiling_price = Wei::from_gwei(ceiling.ceiling_gwei);
+        if gas_price <= ceiling_price {
+            return instructions;
+        }
+        let (urgent, deferred) = urgency.classify(
+            instructions.affordable_accounts,
+            ceiling.deadline_margin_sec,
+            now,
+        );
+        if !deferred.is_empty() {
+            warning!(
+                logger,
+                "The gas price of {} is above the ceiling of {}; {} payments not due within {} \
+                 sec deferred to later scans: {}",
+                gas_price,
+                ceiling_price,
+                deferred.len(),
+                ceiling.deadline_margin_sec,
+                Self::list(&deferred)
+            )
+        }
+        let released: Vec<PayableAccount> =
+            urgent.into_iter().map(|(account, _)| account).collect();
+        if !released.is_empty() {
+            info!(
+                logger,
+                "Paying {} payments close to their deadlines despite the gas price of {}: {}",
+                released.len(),
+                gas_price,
+                Self::list(&released)
+            )
+        }
+        OutboundPaymentsInstructions {
+            affordable_accounts: released,
+            ..instructions
+        }
+    }
+
+    fn list(accounts: &[PayableAccount]) -> String {
+        accounts
+            .iter()
+            .map(|account| {
+                format!(
+                    "{} ({} wei)",
+                    account.wallet,
+                    account.balance_wei.separate_with_commas()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::scanners::gas_price_ceiling_guard::GasPriceCeilingGuard;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::accountant::scanners::payment_urgency::PaymentUrgency;
+    use crate::sub_lib::accountant::GasPriceCeiling;
+    use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+    use crate::sub_lib::wei::Wei;
+    use crate::test_utils::make_wallet;
+    use masq_lib::logger::Logger;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    const NOW: u64 = 1_000_000;
+
+    fn at(secs_since_epoch: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs_since_epoch)
+    }
+
+    fn make_payables() -> Vec<PayableAccount> {
+        ["urgent", "patient", "relaxed"]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, name)| PayableAccount {
+                wallet: make_wallet(name),
+                balance_wei: 1_000_000 * (idx as u128 + 1),
+                last_paid_timestamp: at(0),
+                pending_payable_opt: None,
+            })
+            .collect()
+    }
+
+    fn make_instructions(gas_price_gwei: u64) -> OutboundPaymentsInstructions {
+        let agent = BlockchainAgentMock::default()
+            .agreed_fee_per_computation_unit_result(Wei::from_gwei(gas_price_gwei));
+        OutboundPaymentsInstructions::new(make_payables(), Box::new(agent), None)
+    }
+
+    fn make_urgency() -> PaymentUrgency {
+        let mut urgency = PaymentUrgency::default();
+        let deadline_of = |payable: &PayableAccount| {
+            if payable.wallet == make_wallet("urgent") {
+                at(NOW + 600)
+            } else {
+                at(NOW + 86_400)
+            }
+        };
+        urgency.remember_deadlines(&make_payables(), &deadline_of);
+        urgency
+    }
+
+    fn make_ceiling() -> GasPriceCeiling {
+        GasPriceCeiling {
+            ceiling_gwei: 20,
+            deadline_margin_sec: 3_600,
+        }
+    }
+
+    #[test]
+    fn without_a_ceiling_everything_is_paid() {
+        let subject = GasPriceCeilingGuard::new(None);
+
+        let result = subject.defer_non_urgent(
+            make_instructions(1_000),
+            &make_urgency(),
+            at(NOW),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result.affordable_accounts, make_payables());
+    }
+
+    #[test]
+    fn everything_is_paid_at_a_gas_price_up_to_the_ceiling() {
+        let subject = GasPriceCeilingGuard::new(Some(make_ceiling()));
+
+        let result = subject.defer_non_urgent(
+            make_instructions(20),
+            &make_urgency(),
+            at(NOW),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result.affordable_accounts, make_payables());
+    }
+
+    #[test]
+    fn above_the_ceiling_only_urgent_debts_are_paid_and_the_deferred_ones_are_listed() {
+        init_test_logging();
+        let test_name =
+            "above_the_ceiling_only_urgent_debts_are_paid_and_the_deferred_ones_are_listed";
+        let subject = GasPriceCeilingGuard::new(Some(make_ceiling()));
+
+        let result = subject.defer_non_urgent(
+            make_instructions(21),
+            &make_urgency(),
+            at(NOW),
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(result.affordable_accounts, vec![make_payables()[0].clone()]);
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: The gas price of 21,000,000,000 wei is above the ceiling of \
+             20,000,000,000 wei; 2 payments not due within 3600 sec deferred to later scans: \
+             {} (2,000,000 wei), {} (3,000,000 wei)",
+            make_wallet("patient"),
+            make_wallet("relaxed")
+        ));
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Paying 1 payments close to their deadlines despite the gas price \
+             of 21,000,000,000 wei: {} (1,000,000 wei)",
+            make_wallet("urgent")
+        ));
+    }
+}
@@ -4,6 +4,7 @@ use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockch
 
 use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
 use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wei::Wei;
 use ethereum_types::U256;
 use masq_lib::blockchains::chains::Chain;
 use masq_lib::logger::Logger;
@@ -16,9 +17,9 @@ pub struct BlockchainAgentNull {
 }
 
 impl BlockchainAgent for BlockchainAgentNull {
-    fn estimated_transaction_fee_total(&self, _number_of_transactions: usize) -> u128 {
+    fn estimated_transaction_fee_total(&self, _number_of_transactions: usize) -> Wei {
         self.log_function_call("estimated_transaction_fee_total()");
-        0
+        Wei::ZERO
     }
 
     fn consuming_wallet_balances(&self) -> ConsumingWalletBalances {
@@ -29,9 +30,23 @@ impl BlockchainAgent for BlockchainAgentNull {
         }
     }
 
-    fn agreed_fee_per_computation_unit(&self) -> u128 {
+    fn agreed_fee_per_computation_unit(&self) -> Wei {
         self.log_function_call("agreed_fee_per_computation_unit()");
-        0
+        Wei::ZERO
+    }
+
+    fn bump_fee_per_computation_unit(&mut self, _percent: u8) {
+        self.log_function_call("bump_fee_per_computation_unit()");
+    }
+
+    fn base_fee_per_computation_unit_opt(&self) -> Option<Wei> {
+        self.log_function_call("base_fee_per_computation_unit_opt()");
+        None
+    }
+
+    fn block_computation_limit_opt(&self) -> Option<u128> {
+        self.log_function_call("block_computation_limit_opt()");
+        None
     }
 
     fn consuming_wallet(&self) -> &Wallet {
@@ -82,6 +97,7 @@ mod tests {
 
     use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
     use crate::sub_lib::wallet::Wallet;
+    use crate::sub_lib::wei::Wei;
 
     use masq_lib::logger::Logger;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
@@ -131,7 +147,7 @@ mod tests {
 
         let result = subject.estimated_transaction_fee_total(4);
 
-        assert_eq!(result, 0);
+        assert_eq!(result, Wei::ZERO);
         assert_error_log(test_name, "estimated_transaction_fee_total");
     }
 
@@ -163,10 +179,48 @@ mod tests {
 
         let result = subject.agreed_fee_per_computation_unit();
 
-        assert_eq!(result, 0);
+        assert_eq!(result, Wei::ZERO);
         assert_error_log(test_name, "agreed_fee_per_computation_unit")
     }
 
+    #[test]
+    fn null_agent_bump_fee_per_computation_unit() {
+        init_test_logging();
+        let test_name = "null_agent_bump_fee_per_computation_unit";
+        let mut subject = BlockchainAgentNull::new();
+        subject.logger = Logger::new(test_name);
+
+        subject.bump_fee_per_computation_unit(10);
+
+        assert_error_log(test_name, "bump_fee_per_computation_unit")
+    }
+
+    #[test]
+    fn null_agent_base_fee_per_computation_unit_opt() {
+        init_test_logging();
+        let test_name = "null_agent_base_fee_per_computation_unit_opt";
+        let mut subject = BlockchainAgentNull::new();
+        subject.logger = Logger::new(test_name);
+
+        let result = subject.base_fee_per_computation_unit_opt();
+
+        assert_eq!(result, None);
+        assert_error_log(test_name, "base_fee_per_computation_unit_opt")
+    }
+
+    #[test]
+    fn null_agent_block_computation_limit_opt() {
+        init_test_logging();
+        let test_name = "null_agent_block_computation_limit_opt";
+        let mut subject = BlockchainAgentNull::new();
+        subject.logger = Logger::new(test_name);
+
+        let result = subject.block_computation_limit_opt();
+
+        assert_eq!(result, None);
+        assert_error_log(test_name, "block_computation_limit_opt")
+    }
+
     #[test]
     fn null_agent_consuming_wallet() {
         init_test_logging();
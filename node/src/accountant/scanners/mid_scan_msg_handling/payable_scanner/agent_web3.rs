@@ -1,33 +1,66 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::LatestBlockInfo;
 use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
 use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wei::Wei;
 use masq_lib::blockchains::chains::Chain;
+use web3::types::Address;
 
 #[derive(Debug, Clone)]
 pub struct BlockchainAgentWeb3 {
-    gas_price_wei: u128,
+    gas_price: Wei,
     gas_limit_const_part: u128,
     maximum_added_gas_margin: u128,
     consuming_wallet: Wallet,
     consuming_wallet_balances: ConsumingWalletBalances,
+    payment_router_opt: Option<Address>,
+    latest_block_info_opt: Option<LatestBlockInfo>,
     chain: Chain,
 }
 
 impl BlockchainAgent for BlockchainAgentWeb3 {
-    fn estimated_transaction_fee_total(&self, number_of_transactions: usize) -> u128 {
-        let gas_price = self.gas_price_wei;
-        let max_gas_limit = self.maximum_added_gas_margin + self.gas_limit_const_part;
-        number_of_transactions as u128 * gas_price * max_gas_limit
+    fn estimated_transaction_fee_total(&self, number_of_transactions: usize) -> Wei {
+        let gas_price = self.gas_price;
+        match self.payment_router_opt {
+            Some(_) if number_of_transactions > 1 => {
+                // The router pays all the creditors within a single transaction
+                let per_recipient =
+                    ROUTER_GAS_LIMIT_PER_RECIPIENT + ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_PER_RECIPIENT;
+                let max_gas_limit = self.gas_limit_const_part
+                    + ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_FIXED
+                    + number_of_transactions as u128 * per_recipient;
+                gas_price.saturating_mul(max_gas_limit)
+            }
+            _ => {
+                let max_gas_limit = self.maximum_added_gas_margin + self.gas_limit_const_part;
+                gas_price.saturating_mul(number_of_transactions as u128 * max_gas_limit)
+            }
+        }
     }
 
     fn consuming_wallet_balances(&self) -> ConsumingWalletBalances {
         self.consuming_wallet_balances
     }
 
-    fn agreed_fee_per_computation_unit(&self) -> u128 {
-        self.gas_price_wei
+    fn agreed_fee_per_computation_unit(&self) -> Wei {
+        self.gas_price
+    }
+
+    fn bump_fee_per_computation_unit(&mut self, percent: u8) {
+        self.gas_price = Wei::new(self.gas_price.as_u128() * (100 + percent as u128) / 100)
+    }
+
+    fn base_fee_per_computation_unit_opt(&self) -> Option<Wei> {
+        self.latest_block_info_opt
+            .and_then(|block| block.base_fee_per_gas_opt)
+            .map(Wei::saturating_from_u256)
+    }
+
+    fn block_computation_limit_opt(&self) -> Option<u128> {
+        self.latest_block_info_opt
+            .map(|block| block.gas_limit.as_u128())
     }
 
     fn consuming_wallet(&self) -> &Wallet {
@@ -43,20 +76,35 @@ impl BlockchainAgent for BlockchainAgentWeb3 {
 // each non-zero byte costs 64 units of gas
 pub const WEB3_MAXIMAL_GAS_LIMIT_MARGIN: u128 = 3328;
 
+// Execution of one token transfer relayed by a disperse-style router (a transferFrom that may
+// fill up an empty balance slot)
+pub const ROUTER_GAS_LIMIT_PER_RECIPIENT: u128 = 35_000;
+
+// 68 * 64 ... each recipient adds its address and amount into the data, 32 bytes apiece;
+// in the worst case every non-zero byte costs 68 units of gas
+pub const ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_PER_RECIPIENT: u128 = 4352;
+
+// 68 * (4 + 3 * 32 + 2 * 32) ... the method id, the token and the two array offsets,
+// and the two array lengths
+pub const ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_FIXED: u128 = 11_152;
+
 impl BlockchainAgentWeb3 {
     pub fn new(
-        gas_price_wei: u128,
+        gas_price: Wei,
         gas_limit_const_part: u128,
         consuming_wallet: Wallet,
         consuming_wallet_balances: ConsumingWalletBalances,
+        latest_block_info_opt: Option<LatestBlockInfo>,
         chain: Chain,
     ) -> Self {
         Self {
-            gas_price_wei,
+            gas_price,
             gas_limit_const_part,
             consuming_wallet,
             maximum_added_gas_margin: WEB3_MAXIMAL_GAS_LIMIT_MARGIN,
             consuming_wallet_balances,
+            payment_router_opt: chain.rec().payment_router_opt,
+            latest_block_info_opt,
             chain,
         }
     }
@@ -65,22 +113,28 @@ impl BlockchainAgentWeb3 {
 #[cfg(test)]
 mod tests {
     use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::agent_web3::{
-        BlockchainAgentWeb3, WEB3_MAXIMAL_GAS_LIMIT_MARGIN,
+        BlockchainAgentWeb3, ROUTER_GAS_LIMIT_PER_RECIPIENT, ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_FIXED,
+        ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_PER_RECIPIENT, WEB3_MAXIMAL_GAS_LIMIT_MARGIN,
     };
     use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::LatestBlockInfo;
     use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
+    use crate::sub_lib::wei::Wei;
     use crate::test_utils::make_wallet;
     use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use web3::types::U256;
 
     #[test]
     fn constants_are_correct() {
-        assert_eq!(WEB3_MAXIMAL_GAS_LIMIT_MARGIN, 3_328)
+        assert_eq!(WEB3_MAXIMAL_GAS_LIMIT_MARGIN, 3_328);
+        assert_eq!(ROUTER_GAS_LIMIT_PER_RECIPIENT, 35_000);
+        assert_eq!(ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_PER_RECIPIENT, 4_352);
+        assert_eq!(ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_FIXED, 11_152)
     }
 
     #[test]
     fn blockchain_agent_can_return_non_computed_input_values() {
-        let gas_price_gwei = 123;
+        let gas_price = Wei::new(123);
         let gas_limit_const_part = 44_000;
         let consuming_wallet = make_wallet("abcde");
         let consuming_wallet_balances = ConsumingWalletBalances {
@@ -89,20 +143,99 @@ mod tests {
         };
 
         let subject = BlockchainAgentWeb3::new(
-            gas_price_gwei,
+            gas_price,
             gas_limit_const_part,
             consuming_wallet.clone(),
             consuming_wallet_balances,
+            None,
             TEST_DEFAULT_CHAIN,
         );
 
-        assert_eq!(subject.agreed_fee_per_computation_unit(), gas_price_gwei);
+        assert_eq!(subject.agreed_fee_per_computation_unit(), gas_price);
         assert_eq!(subject.consuming_wallet(), &consuming_wallet);
         assert_eq!(
             subject.consuming_wallet_balances(),
             consuming_wallet_balances
         );
         assert_eq!(subject.get_chain(), TEST_DEFAULT_CHAIN);
+        assert_eq!(subject.base_fee_per_computation_unit_opt(), None);
+        assert_eq!(subject.block_computation_limit_opt(), None);
+    }
+
+    #[test]
+    fn blockchain_agent_reveals_the_latest_block_info() {
+        let consuming_wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: Default::default(),
+            masq_token_balance_in_minor_units: Default::default(),
+        };
+
+        let subject = BlockchainAgentWeb3::new(
+            Wei::new(2_000_000_000),
+            77_777,
+            make_wallet("efg"),
+            consuming_wallet_balances,
+            Some(LatestBlockInfo {
+                base_fee_per_gas_opt: Some(U256::from(1_500_000_000)),
+                gas_limit: U256::from(30_000_000),
+            }),
+            TEST_DEFAULT_CHAIN,
+        );
+
+        assert_eq!(
+            subject.base_fee_per_computation_unit_opt(),
+            Some(Wei::new(1_500_000_000))
+        );
+        assert_eq!(subject.block_computation_limit_opt(), Some(30_000_000));
+    }
+
+    #[test]
+    fn blockchain_agent_knows_the_block_limit_even_without_base_fee() {
+        let consuming_wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: Default::default(),
+            masq_token_balance_in_minor_units: Default::default(),
+        };
+
+        let subject = BlockchainAgentWeb3::new(
+            Wei::new(2_000_000_000),
+            77_777,
+            make_wallet("efg"),
+            consuming_wallet_balances,
+            Some(LatestBlockInfo {
+                base_fee_per_gas_opt: None,
+                gas_limit: U256::from(8_000_000),
+            }),
+            TEST_DEFAULT_CHAIN,
+        );
+
+        assert_eq!(subject.base_fee_per_computation_unit_opt(), None);
+        assert_eq!(subject.block_computation_limit_opt(), Some(8_000_000));
+    }
+
+    #[test]
+    fn gas_price_can_be_bumped() {
+        let consuming_wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: Default::default(),
+            masq_token_balance_in_minor_units: Default::default(),
+        };
+        let mut agent = BlockchainAgentWeb3::new(
+            Wei::new(1_000_000_001),
+            77_777,
+            make_wallet("efg"),
+            consuming_wallet_balances,
+            None,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        agent.bump_fee_per_computation_unit(25);
+
+        assert_eq!(
+            agent.agreed_fee_per_computation_unit(),
+            Wei::new(1_250_000_001)
+        );
+        assert_eq!(
+            agent.estimated_transaction_fee_total(1),
+            Wei::new((77_777 + WEB3_MAXIMAL_GAS_LIMIT_MARGIN) * 1_250_000_001)
+        );
     }
 
     #[test]
@@ -113,10 +246,11 @@ mod tests {
             masq_token_balance_in_minor_units: Default::default(),
         };
         let agent = BlockchainAgentWeb3::new(
-            444,
+            Wei::new(444),
             77_777,
             consuming_wallet,
             consuming_wallet_balances,
+            None,
             TEST_DEFAULT_CHAIN,
         );
 
@@ -129,7 +263,43 @@ mod tests {
         );
         assert_eq!(
             result,
-            (3 * (77_777 + WEB3_MAXIMAL_GAS_LIMIT_MARGIN)) as u128 * 444
+            Wei::new((3 * (77_777 + WEB3_MAXIMAL_GAS_LIMIT_MARGIN)) as u128 * 444)
         );
     }
+
+    #[test]
+    fn estimated_transaction_fee_counts_with_single_transaction_if_payment_router_is_deployed() {
+        let consuming_wallet = make_wallet("efg");
+        let consuming_wallet_balances = ConsumingWalletBalances {
+            transaction_fee_balance_in_minor_units: Default::default(),
+            masq_token_balance_in_minor_units: Default::default(),
+        };
+        let mut agent = BlockchainAgentWeb3::new(
+            Wei::new(444),
+            77_777,
+            consuming_wallet,
+            consuming_wallet_balances,
+            None,
+            TEST_DEFAULT_CHAIN,
+        );
+        agent.payment_router_opt = Some(make_wallet("router").address());
+
+        let result_for_one = agent.estimated_transaction_fee_total(1);
+        let result_for_three = agent.estimated_transaction_fee_total(3);
+
+        assert_eq!(
+            result_for_one,
+            Wei::new((77_777 + WEB3_MAXIMAL_GAS_LIMIT_MARGIN) * 444)
+        );
+        assert_eq!(
+            result_for_three,
+            Wei::new(
+                (77_777
+                    + ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_FIXED
+                    + 3 * (ROUTER_GAS_LIMIT_PER_RECIPIENT
+                        + ROUTER_MAXIMAL_GAS_LIMIT_MARGIN_PER_RECIPIENT))
+                    * 444
+            )
+        )
+    }
 }
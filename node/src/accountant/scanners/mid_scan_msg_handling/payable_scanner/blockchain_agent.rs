@@ -3,7 +3,9 @@
 use crate::arbitrary_id_stamp_in_trait;
 use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
 use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wei::Wei;
 use masq_lib::blockchains::chains::Chain;
+use std::fmt::{Display, Formatter};
 
 // Table of chains by
 //
@@ -22,9 +24,14 @@ use masq_lib::blockchains::chains::Chain;
 //* defaulted limit
 
 pub trait BlockchainAgent: Send {
-    fn estimated_transaction_fee_total(&self, number_of_transactions: usize) -> u128;
+    fn estimated_transaction_fee_total(&self, number_of_transactions: usize) -> Wei;
     fn consuming_wallet_balances(&self) -> ConsumingWalletBalances;
-    fn agreed_fee_per_computation_unit(&self) -> u128;
+    fn agreed_fee_per_computation_unit(&self) -> Wei;
+    // Outbids the price agreed with the network, e.g. for payments resubmitted after a failure
+    fn bump_fee_per_computation_unit(&mut self, percent: u8);
+    // The floor the network sets for the fee by its latest block, if the chain has any
+    fn base_fee_per_computation_unit_opt(&self) -> Option<Wei>;
+    fn block_computation_limit_opt(&self) -> Option<u128>;
     fn consuming_wallet(&self) -> &Wallet;
 
     fn get_chain(&self) -> Chain;
@@ -36,3 +43,163 @@ pub trait BlockchainAgent: Send {
     as_any_ref_in_trait!();
     arbitrary_id_stamp_in_trait!();
 }
+
+// Tells a transaction fee balance that falls short only because the agreed price has run above
+// the base fee of the network from one that couldn't pay even at the base fee
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TransactionFeeShortage {
+    GasPriceSpike {
+        base_fee: Wei,
+        fee_total_at_base_fee: Wei,
+    },
+    InsufficientBalance {
+        base_fee: Wei,
+        fee_total_at_base_fee: Wei,
+    },
+    NoBaseFee,
+}
+
+impl TransactionFeeShortage {
+    pub fn diagnose(agent: &dyn BlockchainAgent, balance: Wei, fee_total: Wei) -> Self {
+        let base_fee = match agent.base_fee_per_computation_unit_opt() {
+            Some(base_fee) => base_fee,
+            None => return Self::NoBaseFee,
+        };
+        let agreed_fee = agent.agreed_fee_per_computation_unit();
+        if agreed_fee == Wei::ZERO {
+            return Self::NoBaseFee;
+        }
+        // The fee total grows linearly with the price per computation unit
+        let fee_total_at_base_fee = fee_total
+            .checked_mul(base_fee.as_u128())
+            .and_then(|product| product.checked_div(agreed_fee.as_u128()))
+            .unwrap_or_else(|| {
+                (fee_total.as_u128() / agreed_fee.as_u128())
+                    .checked_mul(base_fee.as_u128())
+                    .map_or(Wei::MAX, Wei::new)
+            });
+        if fee_total_at_base_fee <= balance {
+            Self::GasPriceSpike {
+                base_fee,
+                fee_total_at_base_fee,
+            }
+        } else {
+            Self::InsufficientBalance {
+                base_fee,
+                fee_total_at_base_fee,
+            }
+        }
+    }
+}
+
+impl Display for TransactionFeeShortage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GasPriceSpike {
+                base_fee,
+                fee_total_at_base_fee,
+            } => write!(
+                f,
+                "the gas price is spiking; at the base fee of {} it would cost {}, \
+                which the balance covers",
+                base_fee, fee_total_at_base_fee
+            ),
+            Self::InsufficientBalance {
+                base_fee,
+                fee_total_at_base_fee,
+            } => write!(
+                f,
+                "the balance is short even at the base fee of {}, which would make it {}",
+                base_fee, fee_total_at_base_fee
+            ),
+            Self::NoBaseFee => write!(f, "the network publishes no base fee to compare with"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::TransactionFeeShortage;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::sub_lib::wei::Wei;
+
+    #[test]
+    fn shortage_caused_by_a_surcharge_over_the_base_fee_is_a_gas_price_spike() {
+        let agent = BlockchainAgentMock::default()
+            .base_fee_per_computation_unit_result(Some(Wei::new(1_000_000_000)))
+            .agreed_fee_per_computation_unit_result(Wei::new(4_000_000_000));
+
+        let result = TransactionFeeShortage::diagnose(
+            &agent,
+            Wei::new(30_000_000_000),
+            Wei::new(100_000_000_000),
+        );
+
+        assert_eq!(
+            result,
+            TransactionFeeShortage::GasPriceSpike {
+                base_fee: Wei::new(1_000_000_000),
+                fee_total_at_base_fee: Wei::new(25_000_000_000)
+            }
+        );
+        assert_eq!(
+            result.to_string(),
+            "the gas price is spiking; at the base fee of 1,000,000,000 wei it would cost \
+            25,000,000,000 wei, which the balance covers"
+        )
+    }
+
+    #[test]
+    fn shortage_persisting_at_the_base_fee_is_an_insufficient_balance() {
+        let agent = BlockchainAgentMock::default()
+            .base_fee_per_computation_unit_result(Some(Wei::new(1_000_000_000)))
+            .agreed_fee_per_computation_unit_result(Wei::new(4_000_000_000));
+
+        let result = TransactionFeeShortage::diagnose(
+            &agent,
+            Wei::new(20_000_000_000),
+            Wei::new(100_000_000_000),
+        );
+
+        assert_eq!(
+            result,
+            TransactionFeeShortage::InsufficientBalance {
+                base_fee: Wei::new(1_000_000_000),
+                fee_total_at_base_fee: Wei::new(25_000_000_000)
+            }
+        );
+        assert_eq!(
+            result.to_string(),
+            "the balance is short even at the base fee of 1,000,000,000 wei, which would make \
+            it 25,000,000,000 wei"
+        )
+    }
+
+    #[test]
+    fn shortage_cannot_be_told_apart_without_base_fee() {
+        let agent = BlockchainAgentMock::default();
+
+        let result =
+            TransactionFeeShortage::diagnose(&agent, Wei::new(1), Wei::new(100_000_000_000));
+
+        assert_eq!(result, TransactionFeeShortage::NoBaseFee);
+        assert_eq!(
+            result.to_string(),
+            "the network publishes no base fee to compare with"
+        )
+    }
+
+    #[test]
+    fn shortage_diagnosis_survives_huge_fees() {
+        let agent = BlockchainAgentMock::default()
+            .base_fee_per_computation_unit_result(Some(Wei::new(u128::MAX / 4)))
+            .agreed_fee_per_computation_unit_result(Wei::new(u128::MAX / 2));
+
+        let result = TransactionFeeShortage::diagnose(&agent, Wei::MAX, Wei::new(u128::MAX / 2));
+
+        match result {
+            TransactionFeeShortage::GasPriceSpike { .. } => (),
+            x => panic!("we expected a gas price spike but got {:?}", x),
+        }
+    }
+}
@@ -6,13 +6,20 @@ pub mod blockchain_agent;
 pub mod msgs;
 pub mod test_utils;
 
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
 use crate::accountant::payment_adjuster::Adjustment;
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::BlockchainAgentWithContextMessage;
+use crate::accountant::scanners::spend_rate_guard::SpendRateDeferral;
 use crate::accountant::scanners::Scanner;
 use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+use crate::sub_lib::wallet::Wallet;
 use actix::Message;
 use itertools::Either;
 use masq_lib::logger::Logger;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::SystemTime;
 
 pub trait MultistagePayableScanner<BeginMessage, EndMessage>:
     Scanner<BeginMessage, EndMessage> + SolvencySensitivePaymentInstructor
@@ -20,11 +27,56 @@ where
     BeginMessage: Message,
     EndMessage: Message,
 {
+    fn set_mode(&mut self, mode: PayableScanMode);
+    // Known only in the observe-only mode, and only after a scan in it has finished
+    fn observed_payables_opt(&self) -> Option<ObservedPayables>;
+    fn limit_spend_rate(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> (OutboundPaymentsInstructions, Option<SpendRateDeferral>);
+    fn confine_to_payment_window(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions;
+    fn defer_while_gas_is_expensive(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions;
+    // Checks what is finally to be paid, after all the stages that narrow the instructions down
+    fn enforce_adjustment_invariants(
+        &self,
+        qualified_payables: &[PayableAccount],
+        service_fee_balance_wei: u128,
+        instructions: OutboundPaymentsInstructions,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions;
+    // Whether the debt is still too young or too small to qualify for a payment
+    fn is_below_threshold(&self, payable: &PayableAccount, now: SystemTime) -> bool;
+}
+
+// Without a consuming wallet nothing can be signed; a payable scan then stops after qualifying the
+// payables and only notes what it would have paid, leaving the BlockchainBridge out of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayableScanMode {
+    Paying,
+    ObserveOnly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObservedPayables {
+    pub accounts: usize,
+    pub total_wei: u128,
 }
 
 pub trait SolvencySensitivePaymentInstructor {
     fn try_skipping_payment_adjustment(
-        &self,
+        &mut self,
         msg: BlockchainAgentWithContextMessage,
         logger: &Logger,
     ) -> Result<Either<OutboundPaymentsInstructions, PreparedAdjustment>, String>;
@@ -39,6 +91,9 @@ pub trait SolvencySensitivePaymentInstructor {
 pub struct PreparedAdjustment {
     pub original_setup_msg: BlockchainAgentWithContextMessage,
     pub adjustment: Adjustment,
+    // Creditors in the exit country the Neighborhood has newly failed over to; their debts are to
+    // be cut the least
+    pub favored_creditors: HashSet<Wallet>,
 }
 
 impl PreparedAdjustment {
@@ -49,20 +104,79 @@ impl PreparedAdjustment {
         Self {
             original_setup_msg,
             adjustment,
+            favored_creditors: HashSet::new(),
+        }
+    }
+}
+
+// The agent holds no nonce; that one is fetched by the BlockchainBridge just before sending
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct BlockchainAgentSnapshot {
+    pub consuming_wallet_address: String,
+    pub transaction_fee_balance_wei: u128,
+    pub service_fee_balance_wei: u128,
+    pub agreed_fee_per_computation_unit: u128,
+    pub chain_identifier: String,
+}
+
+impl BlockchainAgentSnapshot {
+    pub fn new(agent: &dyn BlockchainAgent) -> Self {
+        let balances = agent.consuming_wallet_balances();
+        Self {
+            consuming_wallet_address: agent.consuming_wallet().to_string(),
+            transaction_fee_balance_wei: balances.transaction_fee_balance_in_minor_units.as_u128(),
+            service_fee_balance_wei: balances.masq_token_balance_in_minor_units.as_u128(),
+            agreed_fee_per_computation_unit: agent.agreed_fee_per_computation_unit().as_u128(),
+            chain_identifier: agent.get_chain().rec().literal_identifier.to_string(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::PreparedAdjustment;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{
+        BlockchainAgentSnapshot, PreparedAdjustment,
+    };
+    use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
+    use crate::sub_lib::wei::Wei;
+    use crate::test_utils::make_wallet;
+    use masq_lib::blockchains::chains::Chain;
+    use web3::types::U256;
 
     impl Clone for PreparedAdjustment {
         fn clone(&self) -> Self {
             Self {
                 original_setup_msg: self.original_setup_msg.clone(),
                 adjustment: self.adjustment.clone(),
+                favored_creditors: self.favored_creditors.clone(),
             }
         }
     }
+
+    #[test]
+    fn blockchain_agent_snapshot_takes_what_the_agent_knows() {
+        let consuming_wallet = make_wallet("consuming");
+        let agent = BlockchainAgentMock::default()
+            .consuming_wallet_result(consuming_wallet.clone())
+            .consuming_wallet_balances_result(ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(4_444_444_u64),
+                masq_token_balance_in_minor_units: U256::from(123_456_789_u64),
+            })
+            .agreed_fee_per_computation_unit_result(Wei::new(50_000_000_000))
+            .get_chain_result(Chain::PolyAmoy);
+
+        let result = BlockchainAgentSnapshot::new(&agent);
+
+        assert_eq!(
+            result,
+            BlockchainAgentSnapshot {
+                consuming_wallet_address: consuming_wallet.to_string(),
+                transaction_fee_balance_wei: 4_444_444,
+                service_fee_balance_wei: 123_456_789,
+                agreed_fee_per_computation_unit: 50_000_000_000,
+                chain_identifier: "polygon-amoy".to_string(),
+            }
+        )
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
 use crate::accountant::{ResponseSkeleton, SkeletonOptHolder};
+use crate::sub_lib::accountant::ScanId;
 use crate::sub_lib::wallet::Wallet;
 use actix::Message;
 use masq_lib::type_obfuscation::Obfuscated;
@@ -11,6 +12,9 @@ use std::fmt::Debug;
 pub struct QualifiedPayablesMessage {
     pub protected_qualified_payables: Obfuscated,
     pub consuming_wallet: Wallet,
+    // Set when resubmitting failed payments, which must outbid the original transactions
+    pub gas_price_bump_percent_opt: Option<u8>,
+    pub scan_id: ScanId,
     pub response_skeleton_opt: Option<ResponseSkeleton>,
 }
 
@@ -18,11 +22,14 @@ impl QualifiedPayablesMessage {
     pub(in crate::accountant) fn new(
         protected_qualified_payables: Obfuscated,
         consuming_wallet: Wallet,
+        gas_price_bump_percent_opt: Option<u8>,
         response_skeleton_opt: Option<ResponseSkeleton>,
     ) -> Self {
         Self {
             protected_qualified_payables,
             consuming_wallet,
+            gas_price_bump_percent_opt,
+            scan_id: ScanId::default(),
             response_skeleton_opt,
         }
     }
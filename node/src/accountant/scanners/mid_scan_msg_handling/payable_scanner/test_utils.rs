@@ -5,14 +5,21 @@
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
 use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
 use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wei::Wei;
 use crate::test_utils::unshared_test_utils::arbitrary_id_stamp::ArbitraryIdStamp;
 use crate::{arbitrary_id_stamp_in_trait_impl, set_arbitrary_id_stamp_in_mock_impl};
 use masq_lib::blockchains::chains::Chain;
 use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
 
 pub struct BlockchainAgentMock {
+    estimated_transaction_fee_total_params: Arc<Mutex<Vec<usize>>>,
+    estimated_transaction_fee_total_results: RefCell<Vec<Wei>>,
     consuming_wallet_balances_results: RefCell<Vec<ConsumingWalletBalances>>,
-    agreed_fee_per_computation_unit_results: RefCell<Vec<u128>>,
+    agreed_fee_per_computation_unit_results: RefCell<Vec<Wei>>,
+    bump_fee_per_computation_unit_params: Arc<Mutex<Vec<u8>>>,
+    base_fee_per_computation_unit_result_opt: Option<Wei>,
+    block_computation_limit_result_opt: Option<u128>,
     consuming_wallet_result_opt: Option<Wallet>,
     arbitrary_id_stamp_opt: Option<ArbitraryIdStamp>,
     get_chain_result_opt: Option<Chain>,
@@ -21,8 +28,13 @@ pub struct BlockchainAgentMock {
 impl Default for BlockchainAgentMock {
     fn default() -> Self {
         BlockchainAgentMock {
+            estimated_transaction_fee_total_params: Arc::new(Mutex::new(vec![])),
+            estimated_transaction_fee_total_results: RefCell::new(vec![]),
             consuming_wallet_balances_results: RefCell::new(vec![]),
             agreed_fee_per_computation_unit_results: RefCell::new(vec![]),
+            bump_fee_per_computation_unit_params: Arc::new(Mutex::new(vec![])),
+            base_fee_per_computation_unit_result_opt: None,
+            block_computation_limit_result_opt: None,
             consuming_wallet_result_opt: None,
             arbitrary_id_stamp_opt: None,
             get_chain_result_opt: None,
@@ -31,20 +43,43 @@ impl Default for BlockchainAgentMock {
 }
 
 impl BlockchainAgent for BlockchainAgentMock {
-    fn estimated_transaction_fee_total(&self, _number_of_transactions: usize) -> u128 {
-        todo!("to be implemented by GH-711")
+    fn estimated_transaction_fee_total(&self, number_of_transactions: usize) -> Wei {
+        self.estimated_transaction_fee_total_params
+            .lock()
+            .unwrap()
+            .push(number_of_transactions);
+        self.estimated_transaction_fee_total_results
+            .borrow_mut()
+            .remove(0)
     }
 
     fn consuming_wallet_balances(&self) -> ConsumingWalletBalances {
-        todo!("to be implemented by GH-711")
+        self.consuming_wallet_balances_results
+            .borrow_mut()
+            .remove(0)
     }
 
-    fn agreed_fee_per_computation_unit(&self) -> u128 {
+    fn agreed_fee_per_computation_unit(&self) -> Wei {
         self.agreed_fee_per_computation_unit_results
             .borrow_mut()
             .remove(0)
     }
 
+    fn bump_fee_per_computation_unit(&mut self, percent: u8) {
+        self.bump_fee_per_computation_unit_params
+            .lock()
+            .unwrap()
+            .push(percent)
+    }
+
+    fn base_fee_per_computation_unit_opt(&self) -> Option<Wei> {
+        self.base_fee_per_computation_unit_result_opt
+    }
+
+    fn block_computation_limit_opt(&self) -> Option<u128> {
+        self.block_computation_limit_result_opt
+    }
+
     fn consuming_wallet(&self) -> &Wallet {
         self.consuming_wallet_result_opt.as_ref().unwrap()
     }
@@ -61,6 +96,21 @@ impl BlockchainAgent for BlockchainAgentMock {
 }
 
 impl BlockchainAgentMock {
+    pub fn estimated_transaction_fee_total_params(
+        mut self,
+        params: &Arc<Mutex<Vec<usize>>>,
+    ) -> Self {
+        self.estimated_transaction_fee_total_params = params.clone();
+        self
+    }
+
+    pub fn estimated_transaction_fee_total_result(self, result: Wei) -> Self {
+        self.estimated_transaction_fee_total_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn consuming_wallet_balances_result(self, result: ConsumingWalletBalances) -> Self {
         self.consuming_wallet_balances_results
             .borrow_mut()
@@ -68,13 +118,28 @@ impl BlockchainAgentMock {
         self
     }
 
-    pub fn agreed_fee_per_computation_unit_result(self, result: u128) -> Self {
+    pub fn agreed_fee_per_computation_unit_result(self, result: Wei) -> Self {
         self.agreed_fee_per_computation_unit_results
             .borrow_mut()
             .push(result);
         self
     }
 
+    pub fn bump_fee_per_computation_unit_params(mut self, params: &Arc<Mutex<Vec<u8>>>) -> Self {
+        self.bump_fee_per_computation_unit_params = params.clone();
+        self
+    }
+
+    pub fn base_fee_per_computation_unit_result(mut self, result: Option<Wei>) -> Self {
+        self.base_fee_per_computation_unit_result_opt = result;
+        self
+    }
+
+    pub fn block_computation_limit_result(mut self, result: Option<u128>) -> Self {
+        self.block_computation_limit_result_opt = result;
+        self
+    }
+
     pub fn consuming_wallet_result(mut self, consuming_wallet_result: Wallet) -> Self {
         self.consuming_wallet_result_opt = Some(consuming_wallet_result);
         self
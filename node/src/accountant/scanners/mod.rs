@@ -1,13 +1,28 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod adjustment_invariants;
+pub mod creditor_hold;
+pub mod gas_price_ceiling_guard;
 pub mod mid_scan_msg_handling;
+pub mod payment_batcher;
+pub mod payment_urgency;
+pub mod payment_window_guard;
+pub mod scan_queue;
 pub mod scanners_utils;
+pub mod spend_rate_guard;
 pub mod test_utils;
 
 use crate::accountant::db_access_objects::payable_dao::{PayableAccount, PayableDao};
 use crate::accountant::db_access_objects::pending_payable_dao::{PendingPayable, PendingPayableDao};
 use crate::accountant::db_access_objects::receivable_dao::ReceivableDao;
 use crate::accountant::payment_adjuster::{PaymentAdjuster, PaymentAdjusterReal};
+use crate::accountant::scanners::adjustment_invariants::AdjustmentInvariants;
+use crate::accountant::scanners::creditor_hold::{CreditorHold, CreditorReachability};
+use crate::accountant::scanners::gas_price_ceiling_guard::GasPriceCeilingGuard;
+use crate::accountant::scanners::payment_batcher::PaymentBatcher;
+use crate::accountant::scanners::payment_urgency::{payment_deadline, PaymentUrgency};
+use crate::accountant::scanners::payment_window_guard::PaymentWindowGuard;
+use crate::accountant::scanners::spend_rate_guard::{SpendRateDeferral, SpendRateGuard};
 use crate::accountant::scanners::scanners_utils::payable_scanner_utils::PayableTransactingErrorEnum::{
     LocallyCausedError, RemotelyCausedErrors,
 };
@@ -17,7 +32,7 @@ use crate::accountant::scanners::scanners_utils::payable_scanner_utils::{
     separate_errors, separate_rowids_and_hashes, PayableThresholdsGauge,
     PayableThresholdsGaugeReal, PayableTransactingErrorEnum, PendingPayableMetadata,
 };
-use crate::accountant::scanners::scanners_utils::pending_payable_scanner_utils::{handle_none_receipt, handle_status_with_failure, handle_status_with_success, PendingPayableScanReport};
+use crate::accountant::scanners::scanners_utils::pending_payable_scanner_utils::{handle_none_receipt, handle_none_status, handle_status_with_failure, handle_status_with_success, PendingPayableScanReport};
 use crate::accountant::scanners::scanners_utils::receivable_scanner_utils::balance_and_age;
 use crate::accountant::PendingPayableId;
 use crate::accountant::{
@@ -28,7 +43,8 @@ use crate::accountant::{
 use crate::accountant::db_access_objects::banned_dao::BannedDao;
 use crate::blockchain::blockchain_bridge::{BlockMarker, PendingPayableFingerprint, RetrieveTransactions};
 use crate::sub_lib::accountant::{
-    DaoFactories, FinancialStatistics, PaymentThresholds, ScanIntervals,
+    DaoFactories, FinancialStatistics, GasPriceCeiling, PaymentBatching, PaymentThresholds,
+    PaymentWindow, ScanIntervals, SpendRateLimits, UnreachableCreditorHold,
 };
 use crate::sub_lib::blockchain_bridge::{
     OutboundPaymentsInstructions,
@@ -46,34 +62,55 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::time::{Duration, SystemTime};
+use thousands::Separable;
 use time::format_description::parse;
 use time::OffsetDateTime;
 use web3::types::H256;
 use masq_lib::type_obfuscation::Obfuscated;
-use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{PreparedAdjustment, MultistagePayableScanner, SolvencySensitivePaymentInstructor};
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{PreparedAdjustment, MultistagePayableScanner, ObservedPayables, PayableScanMode, SolvencySensitivePaymentInstructor};
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::{BlockchainAgentWithContextMessage, QualifiedPayablesMessage};
+use crate::accountant::scanners::scan_queue::{ScanQueue, ScanState};
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionReceiptResult, TxStatus};
 use crate::blockchain::blockchain_interface::data_structures::errors::PayableTransactionError;
+use crate::blockchain::blockchain_interface::data_structures::{BlockchainTransaction, RecheckedTransactions};
 use crate::db_config::persistent_configuration::{PersistentConfiguration, PersistentConfigurationReal};
 
 pub struct Scanners {
     pub payable: Box<dyn MultistagePayableScanner<QualifiedPayablesMessage, SentPayables>>,
     pub pending_payable: Box<dyn Scanner<RequestTransactionReceipts, ReportTransactionReceipts>>,
     pub receivable: Box<dyn Scanner<RetrieveTransactions, ReceivedPayments>>,
+    pub queue: ScanQueue,
 }
 
 impl Scanners {
     pub fn new(
         dao_factories: DaoFactories,
-        payment_thresholds: Rc<PaymentThresholds>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
         when_pending_too_long_sec: u64,
         financial_statistics: Rc<RefCell<FinancialStatistics>>,
+        smart_account_owners: HashMap<Wallet, Wallet>,
+        earning_wallet_rotation: Vec<Wallet>,
+        payment_batching_opt: Option<PaymentBatching>,
+        unreachable_creditor_hold_opt: Option<UnreachableCreditorHold>,
+        creditor_reachability: Rc<RefCell<CreditorReachability>>,
+        spend_rate_limits_opt: Option<SpendRateLimits>,
+        payment_window_opt: Option<PaymentWindow>,
+        gas_price_ceiling_opt: Option<GasPriceCeiling>,
+        check_adjustment_invariants: bool,
+        abandoned_transactions: Rc<RefCell<Vec<PendingPayableFingerprint>>>,
     ) -> Self {
         let payable = Box::new(PayableScanner::new(
             dao_factories.payable_dao_factory.make(),
             dao_factories.pending_payable_dao_factory.make(),
             Rc::clone(&payment_thresholds),
             Box::new(PaymentAdjusterReal::new()),
+            payment_batching_opt,
+            unreachable_creditor_hold_opt,
+            creditor_reachability,
+            spend_rate_limits_opt,
+            payment_window_opt,
+            gas_price_ceiling_opt,
+            check_adjustment_invariants,
         ));
 
         let pending_payable = Box::new(PendingPayableScanner::new(
@@ -82,6 +119,7 @@ impl Scanners {
             Rc::clone(&payment_thresholds),
             when_pending_too_long_sec,
             Rc::clone(&financial_statistics),
+            abandoned_transactions,
         ));
 
         let persistent_configuration =
@@ -92,12 +130,43 @@ impl Scanners {
             Box::new(persistent_configuration),
             Rc::clone(&payment_thresholds),
             financial_statistics,
+            smart_account_owners,
+            earning_wallet_rotation,
         ));
 
         Scanners {
             payable,
             pending_payable,
             receivable,
+            queue: ScanQueue::default(),
+        }
+    }
+
+    pub fn scan_state(&self, scan_type: ScanType) -> ScanState {
+        self.queue.state(scan_type, self.scan_started_at(scan_type))
+    }
+
+    // The queued scans whose scanners have become idle are handed out to begin
+    pub fn take_queued_scans_ready_to_begin(&mut self) -> Vec<ScanType> {
+        let idle_scan_types = vec![
+            ScanType::Payables,
+            ScanType::PendingPayables,
+            ScanType::Receivables,
+        ]
+        .into_iter()
+        .filter(|scan_type| self.scan_started_at(*scan_type).is_none())
+        .collect::<Vec<ScanType>>();
+        idle_scan_types
+            .into_iter()
+            .filter(|scan_type| self.queue.dequeue(*scan_type))
+            .collect()
+    }
+
+    fn scan_started_at(&self, scan_type: ScanType) -> Option<SystemTime> {
+        match scan_type {
+            ScanType::Payables => self.payable.scan_started_at(),
+            ScanType::PendingPayables => self.pending_payable.scan_started_at(),
+            ScanType::Receivables => self.receivable.scan_started_at(),
         }
     }
 }
@@ -125,11 +194,11 @@ where
 
 pub struct ScannerCommon {
     initiated_at_opt: Option<SystemTime>,
-    pub payment_thresholds: Rc<PaymentThresholds>,
+    pub payment_thresholds: Rc<RefCell<PaymentThresholds>>,
 }
 
 impl ScannerCommon {
-    fn new(payment_thresholds: Rc<PaymentThresholds>) -> Self {
+    fn new(payment_thresholds: Rc<RefCell<PaymentThresholds>>) -> Self {
         Self {
             initiated_at_opt: None,
             payment_thresholds,
@@ -185,6 +254,15 @@ pub struct PayableScanner {
     pub pending_payable_dao: Box<dyn PendingPayableDao>,
     pub payable_threshold_gauge: Box<dyn PayableThresholdsGauge>,
     pub payment_adjuster: Box<dyn PaymentAdjuster>,
+    pub adjustment_invariants: AdjustmentInvariants,
+    pub creditor_hold: CreditorHold,
+    pub payment_batcher: PaymentBatcher,
+    pub spend_rate_guard: SpendRateGuard,
+    pub payment_urgency: PaymentUrgency,
+    pub payment_window_guard: PaymentWindowGuard,
+    pub gas_price_ceiling_guard: GasPriceCeilingGuard,
+    pub mode: PayableScanMode,
+    pub observed_payables_opt: Option<ObservedPayables>,
 }
 
 impl Scanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
@@ -201,6 +279,11 @@ impl Scanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
         self.mark_as_started(timestamp);
         info!(logger, "Scanning for payables");
         let all_non_pending_payables = self.payable_dao.non_pending_payables();
+        let creditor_thresholds = if all_non_pending_payables.is_empty() {
+            HashMap::new()
+        } else {
+            self.payable_dao.creditor_thresholds()
+        };
 
         debug!(
             logger,
@@ -208,9 +291,44 @@ impl Scanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
             investigate_debt_extremes(timestamp, &all_non_pending_payables)
         );
 
-        let qualified_payables =
-            self.sniff_out_alarming_payables_and_maybe_log_them(all_non_pending_payables, logger);
+        let qualified_payables = self.sniff_out_alarming_payables_and_maybe_log_them(
+            all_non_pending_payables,
+            &creditor_thresholds,
+            logger,
+        );
+        let our_payment_thresholds = *self.common.payment_thresholds.borrow();
+        let thresholds_of = |wallet: &Wallet| {
+            creditor_thresholds
+                .get(wallet)
+                .copied()
+                .unwrap_or(our_payment_thresholds)
+        };
+        let deadline_of =
+            |payable: &PayableAccount| payment_deadline(payable, &thresholds_of(&payable.wallet));
+        let qualified_payables = self.creditor_hold.release_payables(
+            qualified_payables,
+            &deadline_of,
+            timestamp,
+            logger,
+        );
+        let grace_period_sec_of = |wallet: &Wallet| thresholds_of(wallet).payment_grace_period_sec;
+        let qualified_payables = self.payment_batcher.release_payables(
+            qualified_payables,
+            &grace_period_sec_of,
+            timestamp,
+            logger,
+        );
+        self.payment_urgency
+            .remember_deadlines(&qualified_payables, &deadline_of);
+
+        if self.mode == PayableScanMode::ObserveOnly {
+            self.observe_payables(&qualified_payables, logger);
+            self.mark_as_ended(logger);
+            return Err(BeginScanError::ObserveOnly);
+        }
 
+        let qualified_payables =
+            self.exclude_creditors_awaiting_confirmation(qualified_payables, logger);
         match qualified_payables.is_empty() {
             true => {
                 self.mark_as_ended(logger);
@@ -226,6 +344,7 @@ impl Scanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
                 let outgoing_msg = QualifiedPayablesMessage::new(
                     protected_payables,
                     consuming_wallet,
+                    None,
                     response_skeleton_opt,
                 );
                 Ok(outgoing_msg)
@@ -251,7 +370,7 @@ impl Scanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
             .response_skeleton_opt
             .map(|response_skeleton| NodeToUiMessage {
                 target: MessageTarget::ClientId(response_skeleton.client_id),
-                body: UiScanResponse {}.tmb(response_skeleton.context_id),
+                body: UiScanResponse { queued: false }.tmb(response_skeleton.context_id),
             })
     }
 
@@ -262,7 +381,7 @@ impl Scanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
 
 impl SolvencySensitivePaymentInstructor for PayableScanner {
     fn try_skipping_payment_adjustment(
-        &self,
+        &mut self,
         msg: BlockchainAgentWithContextMessage,
         logger: &Logger,
     ) -> Result<Either<OutboundPaymentsInstructions, PreparedAdjustment>, String> {
@@ -279,7 +398,19 @@ impl SolvencySensitivePaymentInstructor for PayableScanner {
                     msg.response_skeleton_opt,
                 )))
             }
-            Ok(Some(adjustment)) => Ok(Either::Right(PreparedAdjustment::new(msg, adjustment))),
+            Ok(Some(adjustment)) => {
+                let mut setup = PreparedAdjustment::new(msg, adjustment);
+                setup.favored_creditors = self.creditor_hold.take_exit_creditors();
+                if !setup.favored_creditors.is_empty() {
+                    info!(
+                        logger,
+                        "Favoring {} creditors in the newly selected exit country in this payment \
+                         adjustment",
+                        setup.favored_creditors.len()
+                    );
+                }
+                Ok(Either::Right(setup))
+            }
             Err(_e) => todo!("be implemented with GH-711"),
         }
     }
@@ -294,14 +425,86 @@ impl SolvencySensitivePaymentInstructor for PayableScanner {
     }
 }
 
-impl MultistagePayableScanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {}
+impl MultistagePayableScanner<QualifiedPayablesMessage, SentPayables> for PayableScanner {
+    fn set_mode(&mut self, mode: PayableScanMode) {
+        if mode == PayableScanMode::Paying {
+            self.observed_payables_opt = None
+        }
+        self.mode = mode
+    }
+
+    fn observed_payables_opt(&self) -> Option<ObservedPayables> {
+        self.observed_payables_opt
+    }
+
+    fn limit_spend_rate(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> (OutboundPaymentsInstructions, Option<SpendRateDeferral>) {
+        self.spend_rate_guard.limit(instructions, now, logger)
+    }
+
+    fn confine_to_payment_window(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions {
+        self.payment_window_guard
+            .confine(instructions, &self.payment_urgency, now, logger)
+    }
+
+    fn defer_while_gas_is_expensive(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions {
+        self.gas_price_ceiling_guard.defer_non_urgent(
+            instructions,
+            &self.payment_urgency,
+            now,
+            logger,
+        )
+    }
+
+    fn enforce_adjustment_invariants(
+        &self,
+        qualified_payables: &[PayableAccount],
+        service_fee_balance_wei: u128,
+        instructions: OutboundPaymentsInstructions,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions {
+        self.adjustment_invariants.enforce(
+            qualified_payables,
+            service_fee_balance_wei,
+            instructions,
+            logger,
+        )
+    }
+
+    fn is_below_threshold(&self, payable: &PayableAccount, now: SystemTime) -> bool {
+        let creditor_thresholds = self.payable_dao.creditor_thresholds();
+        self.payable_exceeded_threshold(payable, &creditor_thresholds, now)
+            .is_none()
+    }
+}
 
 impl PayableScanner {
     pub fn new(
         payable_dao: Box<dyn PayableDao>,
         pending_payable_dao: Box<dyn PendingPayableDao>,
-        payment_thresholds: Rc<PaymentThresholds>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
         payment_adjuster: Box<dyn PaymentAdjuster>,
+        payment_batching_opt: Option<PaymentBatching>,
+        unreachable_creditor_hold_opt: Option<UnreachableCreditorHold>,
+        creditor_reachability: Rc<RefCell<CreditorReachability>>,
+        spend_rate_limits_opt: Option<SpendRateLimits>,
+        payment_window_opt: Option<PaymentWindow>,
+        gas_price_ceiling_opt: Option<GasPriceCeiling>,
+        check_adjustment_invariants: bool,
     ) -> Self {
         Self {
             common: ScannerCommon::new(payment_thresholds),
@@ -309,12 +512,69 @@ impl PayableScanner {
             pending_payable_dao,
             payable_threshold_gauge: Box::new(PayableThresholdsGaugeReal::default()),
             payment_adjuster,
+            adjustment_invariants: AdjustmentInvariants::new(check_adjustment_invariants),
+            creditor_hold: CreditorHold::new(unreachable_creditor_hold_opt, creditor_reachability),
+            payment_batcher: PaymentBatcher::new(payment_batching_opt),
+            spend_rate_guard: SpendRateGuard::new(spend_rate_limits_opt),
+            payment_urgency: PaymentUrgency::default(),
+            payment_window_guard: PaymentWindowGuard::new(payment_window_opt),
+            gas_price_ceiling_guard: GasPriceCeilingGuard::new(gas_price_ceiling_opt),
+            mode: PayableScanMode::Paying,
+            observed_payables_opt: None,
+        }
+    }
+
+    fn observe_payables(&mut self, qualified_payables: &[PayableAccount], logger: &Logger) {
+        let observed = ObservedPayables {
+            accounts: qualified_payables.len(),
+            total_wei: qualified_payables
+                .iter()
+                .map(|account| account.balance_wei)
+                .sum(),
+        };
+        info!(
+            logger,
+            "Observe-only mode, no consuming wallet to pay from: {} qualified debts totaling {} wei \
+             would have been paid",
+            observed.accounts,
+            observed.total_wei.separate_with_commas()
+        );
+        self.observed_payables_opt = Some(observed)
+    }
+
+    // However slow the blockchain service is to confirm a payment, its creditor doesn't get another
+    // one overlapping it
+    fn exclude_creditors_awaiting_confirmation(
+        &self,
+        qualified_payables: Vec<PayableAccount>,
+        logger: &Logger,
+    ) -> Vec<PayableAccount> {
+        if qualified_payables.is_empty() {
+            return qualified_payables;
+        }
+        let awaiting_creditors = self
+            .pending_payable_dao
+            .recipients_of_errorless_fingerprints();
+        if awaiting_creditors.is_empty() {
+            return qualified_payables;
+        }
+        let (excluded, remaining): (Vec<PayableAccount>, Vec<PayableAccount>) = qualified_payables
+            .into_iter()
+            .partition(|payable| awaiting_creditors.contains(&payable.wallet));
+        if !excluded.is_empty() {
+            warning!(
+                logger,
+                "Excluded payables of {} awaiting confirmation of an earlier payment",
+                comma_joined_stringifiable(&excluded, |payable| payable.wallet.to_string())
+            );
         }
+        remaining
     }
 
     fn sniff_out_alarming_payables_and_maybe_log_them(
         &self,
         non_pending_payables: Vec<PayableAccount>,
+        creditor_thresholds: &HashMap<Wallet, PaymentThresholds>,
         logger: &Logger,
     ) -> Vec<PayableAccount> {
         fn pass_payables_and_drop_points(
@@ -326,7 +586,7 @@ impl PayableScanner {
 
         let qualified_payables_and_points_uncollected =
             non_pending_payables.into_iter().flat_map(|account| {
-                self.payable_exceeded_threshold(&account, SystemTime::now())
+                self.payable_exceeded_threshold(&account, creditor_thresholds, SystemTime::now())
                     .map(|threshold_point| (account, threshold_point))
             });
         match logger.debug_enabled() {
@@ -340,33 +600,39 @@ impl PayableScanner {
         }
     }
 
+    // A creditor who advertised its own payment thresholds gets paid by those rather than by ours
     fn payable_exceeded_threshold(
         &self,
         payable: &PayableAccount,
+        creditor_thresholds: &HashMap<Wallet, PaymentThresholds>,
         now: SystemTime,
     ) -> Option<u128> {
+        let our_payment_thresholds = self.common.payment_thresholds.borrow();
+        let payment_thresholds = creditor_thresholds
+            .get(&payable.wallet)
+            .unwrap_or(&*our_payment_thresholds);
         let debt_age = now
             .duration_since(payable.last_paid_timestamp)
             .expect("Internal error")
             .as_secs();
 
-        if self.payable_threshold_gauge.is_innocent_age(
-            debt_age,
-            self.common.payment_thresholds.maturity_threshold_sec,
-        ) {
+        if self
+            .payable_threshold_gauge
+            .is_innocent_age(debt_age, payment_thresholds.maturity_threshold_sec)
+        {
             return None;
         }
 
         if self.payable_threshold_gauge.is_innocent_balance(
             payable.balance_wei,
-            gwei_to_wei(self.common.payment_thresholds.permanent_debt_allowed_gwei),
+            gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei),
         ) {
             return None;
         }
 
         let threshold = self
             .payable_threshold_gauge
-            .calculate_payout_threshold_in_gwei(&self.common.payment_thresholds, debt_age);
+            .calculate_payout_threshold_in_gwei(payment_thresholds, debt_age);
         if payable.balance_wei > threshold {
             Some(threshold)
         } else {
@@ -488,7 +754,7 @@ impl PayableScanner {
     }
 
     fn handle_sent_payable_errors(
-        &self,
+        &mut self,
         err_opt: Option<PayableTransactingErrorEnum>,
         logger: &Logger,
     ) {
@@ -509,7 +775,7 @@ impl PayableScanner {
     }
 
     fn discard_failed_transactions_with_possible_fingerprints(
-        &self,
+        &mut self,
         hashes_of_failed: Vec<H256>,
         logger: &Logger,
     ) {
@@ -562,6 +828,8 @@ pub struct PendingPayableScanner {
     pub pending_payable_dao: Box<dyn PendingPayableDao>,
     pub when_pending_too_long_sec: u64,
     pub financial_statistics: Rc<RefCell<FinancialStatistics>>,
+    // Abandoned transactions whose nonces are known, for the Accountant to have them voided
+    pub abandoned_transactions: Rc<RefCell<Vec<PendingPayableFingerprint>>>,
 }
 
 impl Scanner<RequestTransactionReceipts, ReportTransactionReceipts> for PendingPayableScanner {
@@ -620,7 +888,7 @@ impl Scanner<RequestTransactionReceipts, ReportTransactionReceipts> for PendingP
         self.mark_as_ended(logger);
         response_skeleton_opt.map(|response_skeleton| NodeToUiMessage {
             target: MessageTarget::ClientId(response_skeleton.client_id),
-            body: UiScanResponse {}.tmb(response_skeleton.context_id),
+            body: UiScanResponse { queued: false }.tmb(response_skeleton.context_id),
         })
     }
 
@@ -633,9 +901,10 @@ impl PendingPayableScanner {
     pub fn new(
         payable_dao: Box<dyn PayableDao>,
         pending_payable_dao: Box<dyn PendingPayableDao>,
-        payment_thresholds: Rc<PaymentThresholds>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
         when_pending_too_long_sec: u64,
         financial_statistics: Rc<RefCell<FinancialStatistics>>,
+        abandoned_transactions: Rc<RefCell<Vec<PendingPayableFingerprint>>>,
     ) -> Self {
         Self {
             common: ScannerCommon::new(payment_thresholds),
@@ -643,6 +912,7 @@ impl PendingPayableScanner {
             pending_payable_dao,
             when_pending_too_long_sec,
             financial_statistics,
+            abandoned_transactions,
         }
     }
 
@@ -656,10 +926,10 @@ impl PendingPayableScanner {
             scan_report,
             |scan_report_so_far, (receipt_result, fingerprint)| match receipt_result {
                 TransactionReceiptResult::RpcResponse(tx_receipt) => match tx_receipt.status {
-                    TxStatus::Pending => handle_none_receipt(
+                    TxStatus::Pending => handle_none_status(
                         scan_report_so_far,
                         fingerprint,
-                        "none was given",
+                        self.when_pending_too_long_sec,
                         logger,
                     ),
                     TxStatus::Failed => {
@@ -686,6 +956,7 @@ impl PendingPayableScanner {
     ) {
         self.confirm_transactions(scan_report.confirmed, logger);
         self.cancel_failed_transactions(scan_report.failures, logger);
+        self.abandon_transactions(scan_report.abandoned, logger);
         self.update_remaining_fingerprints(scan_report.still_pending, logger)
     }
 
@@ -707,7 +978,7 @@ impl PendingPayableScanner {
         }
     }
 
-    fn cancel_failed_transactions(&self, ids: Vec<PendingPayableId>, logger: &Logger) {
+    fn cancel_failed_transactions(&mut self, ids: Vec<PendingPayableId>, logger: &Logger) {
         if !ids.is_empty() {
             //TODO this function is imperfect. It waits for GH-663
             let rowids = PendingPayableId::rowids(&ids);
@@ -729,6 +1000,57 @@ impl PendingPayableScanner {
         }
     }
 
+    // The payables are released first so that the next payable scan pays them again; the
+    // transactions can still be mined until their nonces are spent by something else
+    fn abandon_transactions(
+        &mut self,
+        fingerprints: Vec<PendingPayableFingerprint>,
+        logger: &Logger,
+    ) {
+        if fingerprints.is_empty() {
+            return;
+        }
+        let serialized_hashes =
+            comma_joined_stringifiable(&fingerprints, |fgp| format!("{:?}", fgp.hash));
+        let rowids = fingerprints
+            .iter()
+            .map(|fingerprint| fingerprint.rowid)
+            .collect::<Vec<u64>>();
+        if let Err(e) = self.payable_dao.release_failed_payables(&rowids) {
+            panic!(
+                "Unable to release the payables of abandoned transactions {} due to {:?}",
+                serialized_hashes, e
+            )
+        }
+        let reason = format!(
+            "pending for longer than {}sec",
+            self.when_pending_too_long_sec
+        );
+        if let Err(e) = self.pending_payable_dao.mark_abandoned(&rowids, &reason) {
+            panic!(
+                "Unable to mark transactions {} abandoned due to {:?}; database unreliable",
+                serialized_hashes, e
+            )
+        }
+        warning!(
+            logger,
+            "Transactions {} abandoned; their debts are left for the next payable scan to pay",
+            serialized_hashes
+        );
+        let (voidable, unvoidable): (Vec<_>, Vec<_>) = fingerprints
+            .into_iter()
+            .partition(|fingerprint| fingerprint.replacement_basis_opt.is_some());
+        if !unvoidable.is_empty() {
+            warning!(
+                logger,
+                "Abandoned transactions {} were recorded without their nonces and cannot be \
+                 voided; should any of them still be mined, the debt it pays will be paid twice",
+                comma_joined_stringifiable(&unvoidable, |fgp| format!("{:?}", fgp.hash))
+            )
+        }
+        self.abandoned_transactions.borrow_mut().extend(voidable)
+    }
+
     fn confirm_transactions(
         &mut self,
         fingerprints: Vec<PendingPayableFingerprint>,
@@ -789,6 +1111,12 @@ pub struct ReceivableScanner {
     pub banned_dao: Box<dyn BannedDao>,
     pub persistent_configuration: Box<dyn PersistentConfiguration>,
     pub financial_statistics: Rc<RefCell<FinancialStatistics>>,
+    // ERC-4337 smart accounts mapped to the wallets of the debtors owning them
+    pub smart_account_owners: HashMap<Wallet, Wallet>,
+    // Earlier earning wallets of the rotation can still be paid, so they are scanned together
+    pub earning_wallet_rotation: Vec<Wallet>,
+    // Payments known to be on their way but not confirmed yet, hence not credited
+    pub pending_payments: Vec<BlockchainTransaction>,
 }
 
 impl Scanner<RetrieveTransactions, ReceivedPayments> for ReceivableScanner {
@@ -803,11 +1131,16 @@ impl Scanner<RetrieveTransactions, ReceivedPayments> for ReceivableScanner {
             return Err(BeginScanError::ScanAlreadyRunning(timestamp));
         }
         self.mark_as_started(timestamp);
-        info!(logger, "Scanning for receivables to {}", earning_wallet);
+        let recipients = self.recipients(earning_wallet);
+        info!(
+            logger,
+            "Scanning for receivables to {}",
+            recipients.iter().join(", ")
+        );
         self.scan_for_delinquencies(timestamp, logger);
 
         Ok(RetrieveTransactions {
-            recipient: earning_wallet,
+            recipients,
             response_skeleton_opt,
         })
     }
@@ -818,7 +1151,7 @@ impl Scanner<RetrieveTransactions, ReceivedPayments> for ReceivableScanner {
         msg.response_skeleton_opt
             .map(|response_skeleton| NodeToUiMessage {
                 target: MessageTarget::ClientId(response_skeleton.client_id),
-                body: UiScanResponse {}.tmb(response_skeleton.context_id),
+                body: UiScanResponse { queued: false }.tmb(response_skeleton.context_id),
             })
     }
 
@@ -833,8 +1166,10 @@ impl ReceivableScanner {
         receivable_dao: Box<dyn ReceivableDao>,
         banned_dao: Box<dyn BannedDao>,
         persistent_configuration: Box<dyn PersistentConfiguration>,
-        payment_thresholds: Rc<PaymentThresholds>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
         financial_statistics: Rc<RefCell<FinancialStatistics>>,
+        smart_account_owners: HashMap<Wallet, Wallet>,
+        earning_wallet_rotation: Vec<Wallet>,
     ) -> Self {
         Self {
             common: ScannerCommon::new(payment_thresholds),
@@ -842,14 +1177,62 @@ impl ReceivableScanner {
             banned_dao,
             persistent_configuration,
             financial_statistics,
+            smart_account_owners,
+            earning_wallet_rotation,
+            pending_payments: vec![],
         }
     }
 
+    // A rotation no longer including the earning wallet must have been replaced by new wallets
+    fn recipients(&self, earning_wallet: Wallet) -> Vec<Wallet> {
+        if self
+            .earning_wallet_rotation
+            .iter()
+            .any(|wallet| wallet.address() == earning_wallet.address())
+        {
+            self.earning_wallet_rotation.clone()
+        } else {
+            vec![earning_wallet]
+        }
+    }
+
+    fn credit_smart_account_owners(
+        &self,
+        transactions: &[BlockchainTransaction],
+        logger: &Logger,
+    ) -> Vec<BlockchainTransaction> {
+        transactions
+            .iter()
+            .map(
+                |transaction| match self.smart_account_owners.get(&transaction.from) {
+                    Some(owner) => {
+                        debug!(
+                            logger,
+                            "Payment of {} wei from smart account {} credited to its owner {}",
+                            transaction.wei_amount.separate_with_commas(),
+                            transaction.from,
+                            owner
+                        );
+                        BlockchainTransaction {
+                            from: owner.clone(),
+                            ..transaction.clone()
+                        }
+                    }
+                    None => transaction.clone(),
+                },
+            )
+            .collect()
+    }
+
     fn handle_new_received_payments(
         &mut self,
         received_payments_msg: &ReceivedPayments,
         logger: &Logger,
     ) {
+        if let Some(rechecked) = &received_payments_msg.rechecked_opt {
+            self.reconcile_rechecked_payments(rechecked, logger);
+        }
+        self.track_pending_payments(received_payments_msg, logger);
         if received_payments_msg.transactions.is_empty() {
             info!(
                 logger,
@@ -869,10 +1252,12 @@ impl ReceivableScanner {
                 }
             }
         } else {
-            let mut txn = self.receivable_dao.as_mut().more_money_received(
-                received_payments_msg.timestamp,
-                &received_payments_msg.transactions,
-            );
+            let transactions =
+                self.credit_smart_account_owners(&received_payments_msg.transactions, logger);
+            let mut txn = self
+                .receivable_dao
+                .as_mut()
+                .more_money_received(received_payments_msg.timestamp, &transactions);
             let new_start_block = received_payments_msg.new_start_block;
             if let BlockMarker::Value(start_block_number) = new_start_block {
                 match self
@@ -905,6 +1290,119 @@ impl ReceivableScanner {
         }
     }
 
+    // A pending payment is confirmed once it turns up among the credited transactions, and gone for
+    // good if the blocks it was in have been scanned as confirmed without it. Those in blocks not
+    // scanned that far yet stay pending, alongside whatever pending payments this scan has found
+    fn track_pending_payments(&mut self, msg: &ReceivedPayments, logger: &Logger) {
+        msg.pending
+            .iter()
+            .filter(|payment| !self.pending_payments.contains(payment))
+            .for_each(|payment| {
+                debug!(
+                    logger,
+                    "Payment of {} wei from {} in block {} is pending confirmation",
+                    payment.wei_amount.separate_with_commas(),
+                    payment.from,
+                    payment.block_number
+                )
+            });
+        let mut still_pending = msg.pending.clone();
+        std::mem::take(&mut self.pending_payments)
+            .into_iter()
+            .for_each(|payment| {
+                if msg.transactions.contains(&payment) {
+                    info!(
+                        logger,
+                        "Pending payment of {} wei from {} in block {} has been confirmed",
+                        payment.wei_amount.separate_with_commas(),
+                        payment.from,
+                        payment.block_number
+                    )
+                } else if matches!(
+                    msg.new_start_block,
+                    BlockMarker::Value(new_start_block) if payment.block_number < new_start_block
+                ) {
+                    warning!(
+                        logger,
+                        "Pending payment of {} wei from {} in block {} vanished before it was confirmed",
+                        payment.wei_amount.separate_with_commas(),
+                        payment.from,
+                        payment.block_number
+                    )
+                } else if !still_pending.contains(&payment) {
+                    still_pending.push(payment)
+                }
+            });
+        self.financial_statistics
+            .borrow_mut()
+            .total_pending_receivable_wei =
+            still_pending.iter().map(|payment| payment.wei_amount).sum();
+        self.pending_payments = still_pending;
+    }
+
+    // Whatever was credited from the rechecked blocks but is no longer found there has been
+    // undone by a chain reorganization, and so must be our crediting of it
+    fn reconcile_rechecked_payments(&mut self, rechecked: &RecheckedTransactions, logger: &Logger) {
+        let credited_payments = self
+            .receivable_dao
+            .received_payments_between(rechecked.from_block, rechecked.to_block);
+        let mut present_payments =
+            self.credit_smart_account_owners(&rechecked.transactions, logger);
+        let vanished_payments: Vec<BlockchainTransaction> = credited_payments
+            .into_iter()
+            .filter(|credited| {
+                match present_payments
+                    .iter()
+                    .position(|present| present == credited)
+                {
+                    Some(idx) => {
+                        present_payments.remove(idx);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect();
+        if !vanished_payments.is_empty() {
+            vanished_payments.iter().for_each(|payment| {
+                warning!(
+                    logger,
+                    "Payment of {} wei from {} in block {} vanished in a chain reorganization; \
+                     its credit is reversed",
+                    payment.wei_amount.separate_with_commas(),
+                    payment.from,
+                    payment.block_number
+                )
+            });
+            if let Err(e) = self
+                .receivable_dao
+                .reverse_received_payments(&vanished_payments)
+            {
+                panic!(
+                    "Reversal of payments lost in a chain reorganization failed: {:?}",
+                    e
+                )
+            }
+            let total_vanished = vanished_payments
+                .iter()
+                .fold(0, |so_far, payment| so_far + payment.wei_amount);
+            let mut financial_statistics = self.financial_statistics.borrow_mut();
+            financial_statistics.total_paid_receivable_wei = financial_statistics
+                .total_paid_receivable_wei
+                .saturating_sub(total_vanished);
+        }
+        if let Err(e) = self
+            .receivable_dao
+            .forget_received_payments_before(rechecked.from_block)
+        {
+            warning!(
+                logger,
+                "Couldn't drop records of received payments out of reach of reorganizations: {:?}",
+                e
+            )
+        }
+    }
+
     pub fn scan_for_delinquencies(&self, timestamp: SystemTime, logger: &Logger) {
         info!(logger, "Scanning for delinquencies");
         self.find_and_ban_delinquents(timestamp, logger);
@@ -913,7 +1411,7 @@ impl ReceivableScanner {
 
     fn find_and_ban_delinquents(&self, timestamp: SystemTime, logger: &Logger) {
         self.receivable_dao
-            .new_delinquencies(timestamp, self.common.payment_thresholds.as_ref())
+            .new_delinquencies(timestamp, &self.common.payment_thresholds.borrow())
             .into_iter()
             .for_each(|account| {
                 self.banned_dao.ban(&account.wallet);
@@ -930,7 +1428,7 @@ impl ReceivableScanner {
 
     fn find_and_unban_reformed_nodes(&self, timestamp: SystemTime, logger: &Logger) {
         self.receivable_dao
-            .paid_delinquencies(self.common.payment_thresholds.as_ref())
+            .paid_delinquencies(&self.common.payment_thresholds.borrow())
             .into_iter()
             .for_each(|account| {
                 self.banned_dao.unban(&account.wallet);
@@ -950,6 +1448,8 @@ impl ReceivableScanner {
 pub enum BeginScanError {
     NothingToProcess,
     NoConsumingWalletFound,
+    // Not a failure: the scan ran in the observe-only mode and noted what it would have paid
+    ObserveOnly,
     ScanAlreadyRunning(SystemTime),
     CalledFromNullScanner, // Exclusive for tests
 }
@@ -976,6 +1476,7 @@ impl BeginScanError {
                 "Cannot initiate {:?} scan because no consuming wallet was found.",
                 scan_type
             )),
+            BeginScanError::ObserveOnly => None,
             BeginScanError::CalledFromNullScanner => match cfg!(test) {
                 true => None,
                 false => panic!("Null Scanner shouldn't be running inside production code."),
@@ -1064,12 +1565,25 @@ impl<T: Default + 'static> ScanScheduler for PeriodicalScanScheduler<T> {
 }
 #[cfg(test)]
 mod tests {
-    use crate::accountant::db_access_objects::payable_dao::{PayableAccount, PayableDaoError};
+    use crate::accountant::db_access_objects::payable_dao::{
+        PayableAccount, PayableDao, PayableDaoError, PayableDaoReal,
+    };
+    use crate::accountant::db_access_objects::receivable_dao::ReceivableDaoError;
     use crate::accountant::db_access_objects::pending_payable_dao::{
-        PendingPayable, PendingPayableDaoError, TransactionHashes,
+        PendingPayable, PendingPayableDao, PendingPayableDaoError, PendingPayableDaoReal,
+        TransactionHashes,
     };
     use crate::accountant::db_access_objects::utils::{from_time_t, to_time_t};
-    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::QualifiedPayablesMessage;
+    use crate::accountant::payment_adjuster::Adjustment;
+    use crate::accountant::scanners::creditor_hold::CreditorReachability;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::{
+        BlockchainAgentWithContextMessage, QualifiedPayablesMessage,
+    };
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{
+        MultistagePayableScanner, ObservedPayables, PayableScanMode,
+        SolvencySensitivePaymentInstructor,
+    };
     use crate::accountant::scanners::scanners_utils::payable_scanner_utils::PendingPayableMetadata;
     use crate::accountant::scanners::scanners_utils::pending_payable_scanner_utils::{handle_none_status, handle_status_with_failure, PendingPayableScanReport};
     use crate::accountant::scanners::test_utils::protect_payables_in_test;
@@ -1079,26 +1593,34 @@ mod tests {
     };
     use crate::accountant::test_utils::{
         make_custom_payment_thresholds, make_payable_account, make_payables,
-        make_pending_payable_fingerprint, make_receivable_account, BannedDaoFactoryMock,
-        BannedDaoMock, ConfigDaoFactoryMock, PayableDaoFactoryMock, PayableDaoMock,
-        PayableScannerBuilder, PayableThresholdsGaugeMock, PendingPayableDaoFactoryMock,
-        PendingPayableDaoMock, PendingPayableScannerBuilder, ReceivableDaoFactoryMock,
-        ReceivableDaoMock, ReceivableScannerBuilder,
+        make_pending_payable_fingerprint, make_receivable_account, AccountsTransferDaoFactoryMock,
+        BannedDaoFactoryMock,
+        BannedDaoMock, ConfigDaoFactoryMock, ConsistencyDaoFactoryMock,
+        OutboundPaymentDaoFactoryMock, PayableDaoFactoryMock,
+        PayableDaoMock,
+        PayableScannerBuilder, PayableThresholdsGaugeMock, PaymentAdjusterMock,
+        PendingPayableDaoFactoryMock, PendingPayableDaoMock, PendingPayableScannerBuilder,
+        ReceivableDaoFactoryMock, ReceivableDaoMock, ReceivableScannerBuilder,
     };
     use crate::accountant::{gwei_to_wei, PendingPayableId, ReceivedPayments, ReportTransactionReceipts, RequestTransactionReceipts, SentPayables, DEFAULT_PENDING_TOO_LONG_SEC};
-    use crate::blockchain::blockchain_bridge::{BlockMarker, PendingPayableFingerprint, RetrieveTransactions};
+    use crate::blockchain::blockchain_bridge::{BlockMarker, PendingPayableFingerprint, ReplacementBasis, RetrieveTransactions};
     use crate::blockchain::blockchain_interface::data_structures::errors::PayableTransactionError;
     use crate::blockchain::blockchain_interface::data_structures::{
-        BlockchainTransaction, ProcessedPayableFallible, RpcPayableFailure,
+        BlockchainTransaction, ProcessedPayableFallible, RecheckedTransactions, RpcPayableFailure,
     };
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
     use crate::blockchain::test_utils::make_tx_hash;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal,
+    };
     use crate::database::rusqlite_wrappers::TransactionSafeWrapper;
     use crate::database::test_utils::transaction_wrapper_mock::TransactionInnerWrapperMockBuilder;
     use crate::db_config::mocks::ConfigDaoMock;
     use crate::db_config::persistent_configuration::{PersistentConfigError};
     use crate::sub_lib::accountant::{
-        DaoFactories, FinancialStatistics, PaymentThresholds, ScanIntervals,
-        DEFAULT_PAYMENT_THRESHOLDS,
+        DaoFactories, FinancialStatistics, GasPriceCeiling, PaymentBatching, PaymentThresholds,
+        PaymentWindow, ScanId, ReportCreditorReachabilityMessage, ReportExitFailoverMessage,
+        ScanIntervals, SpendRateLimits, UnreachableCreditorHold, DEFAULT_PAYMENT_THRESHOLDS,
     };
     use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
     use crate::test_utils::unshared_test_utils::arbitrary_id_stamp::ArbitraryIdStamp;
@@ -1108,16 +1630,18 @@ mod tests {
     use masq_lib::logger::Logger;
     use masq_lib::messages::ScanType;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
     use regex::Regex;
-    use rusqlite::{ffi, ErrorCode};
+    use rusqlite::{ffi, ErrorCode, ToSql};
     use std::cell::RefCell;
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::ops::Sub;
     use std::panic::{catch_unwind, AssertUnwindSafe};
     use std::rc::Rc;
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, SystemTime};
     use web3::types::{TransactionReceipt, H256};
+    use thousands::Separable;
     use web3::Error;
     use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionBlock, TransactionReceiptResult, TxReceipt, TxStatus};
 
@@ -1141,10 +1665,41 @@ mod tests {
         let financial_statistics = FinancialStatistics {
             total_paid_payable_wei: 1,
             total_paid_receivable_wei: 2,
+            total_pending_receivable_wei: 3,
         };
         let payment_thresholds = make_custom_payment_thresholds();
-        let payment_thresholds_rc = Rc::new(payment_thresholds);
+        let payment_thresholds_rc = Rc::new(RefCell::new(payment_thresholds));
         let initial_rc_count = Rc::strong_count(&payment_thresholds_rc);
+        let smart_account_owners =
+            HashMap::from([(make_wallet("smart account"), make_wallet("owner"))]);
+        let earning_wallet_rotation = vec![make_wallet("earning"), make_wallet("next earning")];
+        let payment_batching = PaymentBatching {
+            min_economic_size_gwei: 1_000,
+            batch_target_gwei: 5_000,
+            max_delay_sec: 60,
+        };
+        let unreachable_creditor_hold = UnreachableCreditorHold {
+            absence_sec: 3_600,
+            deadline_margin_sec: 600,
+        };
+        let creditor_reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        let spend_rate_limits = SpendRateLimits {
+            service_fee_per_hour_gwei: 1_000,
+            service_fee_per_day_gwei: 10_000,
+            transaction_fee_per_hour_gwei: 50,
+            transaction_fee_per_day_gwei: 300,
+        };
+        let payment_window = PaymentWindow {
+            start_hour: 2,
+            end_hour: 6,
+            low_gas_price_gwei: 1,
+            deadline_margin_sec: 3_600,
+        };
+        let gas_price_ceiling = GasPriceCeiling {
+            ceiling_gwei: 30,
+            deadline_margin_sec: 7_200,
+        };
+        let abandoned_transactions = Rc::new(RefCell::new(vec![]));
 
         let mut scanners = Scanners::new(
             DaoFactories {
@@ -1153,10 +1708,23 @@ mod tests {
                 receivable_dao_factory: Box::new(receivable_dao_factory),
                 banned_dao_factory: Box::new(banned_dao_factory),
                 config_dao_factory: Box::new(config_dao_factory),
+                accounts_transfer_dao_factory: Box::new(AccountsTransferDaoFactoryMock::new()),
+                consistency_dao_factory: Box::new(ConsistencyDaoFactoryMock::new()),
+                outbound_payment_dao_factory: Box::new(OutboundPaymentDaoFactoryMock::new()),
             },
             Rc::clone(&payment_thresholds_rc),
             when_pending_too_long_sec,
             Rc::new(RefCell::new(financial_statistics.clone())),
+            smart_account_owners.clone(),
+            earning_wallet_rotation.clone(),
+            Some(payment_batching),
+            Some(unreachable_creditor_hold),
+            Rc::clone(&creditor_reachability),
+            Some(spend_rate_limits),
+            Some(payment_window),
+            Some(gas_price_ceiling),
+            true,
+            Rc::clone(&abandoned_transactions),
         );
 
         let payable_scanner = scanners
@@ -1175,10 +1743,32 @@ mod tests {
             .downcast_mut::<ReceivableScanner>()
             .unwrap();
         assert_eq!(
-            payable_scanner.common.payment_thresholds.as_ref(),
-            &payment_thresholds
+            *payable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
         );
         assert_eq!(payable_scanner.common.initiated_at_opt.is_some(), false);
+        assert_eq!(
+            payable_scanner.payment_batcher.batching_opt,
+            Some(payment_batching)
+        );
+        assert_eq!(
+            payable_scanner.creditor_hold.hold_opt,
+            Some(unreachable_creditor_hold)
+        );
+        assert_eq!(Rc::strong_count(&creditor_reachability), 2);
+        assert_eq!(
+            payable_scanner.spend_rate_guard.limits_opt,
+            Some(spend_rate_limits)
+        );
+        assert_eq!(
+            payable_scanner.payment_window_guard.window_opt,
+            Some(payment_window)
+        );
+        assert_eq!(
+            payable_scanner.gas_price_ceiling_guard.ceiling_opt,
+            Some(gas_price_ceiling)
+        );
+        assert_eq!(payable_scanner.adjustment_invariants.enabled, true);
         assert_eq!(
             pending_payable_scanner.when_pending_too_long_sec,
             when_pending_too_long_sec
@@ -1187,9 +1777,10 @@ mod tests {
             *pending_payable_scanner.financial_statistics.borrow(),
             financial_statistics
         );
+        assert_eq!(Rc::strong_count(&abandoned_transactions), 2);
         assert_eq!(
-            pending_payable_scanner.common.payment_thresholds.as_ref(),
-            &payment_thresholds
+            *pending_payable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
         );
         assert_eq!(
             pending_payable_scanner.common.initiated_at_opt.is_some(),
@@ -1200,10 +1791,18 @@ mod tests {
             financial_statistics
         );
         assert_eq!(
-            receivable_scanner.common.payment_thresholds.as_ref(),
-            &payment_thresholds
+            *receivable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
         );
         assert_eq!(receivable_scanner.common.initiated_at_opt.is_some(), false);
+        assert_eq!(
+            receivable_scanner.smart_account_owners,
+            smart_account_owners
+        );
+        assert_eq!(
+            receivable_scanner.earning_wallet_rotation,
+            earning_wallet_rotation
+        );
         receivable_scanner
             .persistent_configuration
             .set_start_block(Some(136890))
@@ -1239,10 +1838,15 @@ mod tests {
         let now = SystemTime::now();
         let (qualified_payable_accounts, _, all_non_pending_payables) =
             make_payables(now, &PaymentThresholds::default());
-        let payable_dao =
-            PayableDaoMock::new().non_pending_payables_result(all_non_pending_payables);
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
         let mut subject = PayableScannerBuilder::new()
             .payable_dao(payable_dao)
+            .pending_payable_dao(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )
             .build();
 
         let result =
@@ -1257,6 +1861,8 @@ mod tests {
                     qualified_payable_accounts.clone()
                 ),
                 consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId::default(),
                 response_skeleton_opt: None,
             })
         );
@@ -1270,68 +1876,542 @@ mod tests {
     }
 
     #[test]
-    fn payable_scanner_throws_error_when_a_scan_is_already_running() {
+    fn payable_scanner_qualifies_payables_by_thresholds_their_creditors_advertised() {
         let consuming_wallet = make_paying_wallet(b"consuming wallet");
         let now = SystemTime::now();
-        let (_, _, all_non_pending_payables) = make_payables(now, &PaymentThresholds::default());
-        let payable_dao =
-            PayableDaoMock::new().non_pending_payables_result(all_non_pending_payables);
+        let payable = PayableAccount {
+            wallet: make_wallet("creditor"),
+            balance_wei: gwei_to_wei(20_000_u64),
+            last_paid_timestamp: now.checked_sub(Duration::from_secs(1_000)).unwrap(),
+            pending_payable_opt: None,
+        };
+        let creditor_payment_thresholds = PaymentThresholds {
+            maturity_threshold_sec: 100,
+            payment_grace_period_sec: 100,
+            permanent_debt_allowed_gwei: 1_000,
+            debt_threshold_gwei: 10_000,
+            threshold_interval_sec: 1_000,
+            unban_below_gwei: 1_000,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(vec![payable.clone()])
+            .creditor_thresholds_result(HashMap::from_iter(vec![(
+                payable.wallet.clone(),
+                creditor_payment_thresholds,
+            )]));
         let mut subject = PayableScannerBuilder::new()
             .payable_dao(payable_dao)
+            .pending_payable_dao(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )
             .build();
-        let _result = subject.begin_scan(consuming_wallet.clone(), now, None, &Logger::new("test"));
 
-        let run_again_result = subject.begin_scan(
-            consuming_wallet,
-            SystemTime::now(),
-            None,
-            &Logger::new("test"),
-        );
+        let result = subject.begin_scan(consuming_wallet.clone(), now, None, &Logger::new("test"));
 
-        let is_scan_running = subject.scan_started_at().is_some();
-        assert_eq!(is_scan_running, true);
         assert_eq!(
-            run_again_result,
-            Err(BeginScanError::ScanAlreadyRunning(now))
+            result,
+            Ok(QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![payable]),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId::default(),
+                response_skeleton_opt: None,
+            })
         );
     }
 
     #[test]
-    fn payable_scanner_throws_error_in_case_no_qualified_payable_is_found() {
+    fn payable_scanner_excludes_qualified_payables_of_creditors_awaiting_confirmation() {
+        init_test_logging();
+        let test_name =
+            "payable_scanner_excludes_qualified_payables_of_creditors_awaiting_confirmation";
         let consuming_wallet = make_paying_wallet(b"consuming wallet");
         let now = SystemTime::now();
-        let (_, unqualified_payable_accounts, _) =
+        let (qualified_payable_accounts, _, all_non_pending_payables) =
             make_payables(now, &PaymentThresholds::default());
-        let payable_dao =
-            PayableDaoMock::new().non_pending_payables_result(unqualified_payable_accounts);
+        let awaiting_creditor = qualified_payable_accounts[0].wallet.clone();
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .recipients_of_errorless_fingerprints_result(HashSet::from([
+                awaiting_creditor.clone(),
+                make_wallet("unqualified creditor"),
+            ]));
         let mut subject = PayableScannerBuilder::new()
             .payable_dao(payable_dao)
+            .pending_payable_dao(pending_payable_dao)
             .build();
 
-        let result = subject.begin_scan(consuming_wallet, now, None, &Logger::new("test"));
+        let result =
+            subject.begin_scan(consuming_wallet.clone(), now, None, &Logger::new(test_name));
 
-        let is_scan_running = subject.scan_started_at().is_some();
-        assert_eq!(is_scan_running, false);
-        assert_eq!(result, Err(BeginScanError::NothingToProcess));
+        assert_eq!(
+            result,
+            Ok(QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(
+                    qualified_payable_accounts[1..].to_vec()
+                ),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId::default(),
+                response_skeleton_opt: None,
+            })
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Excluded payables of {awaiting_creditor} awaiting confirmation of \
+             an earlier payment"
+        ));
     }
 
     #[test]
-    fn payable_scanner_handles_sent_payable_message() {
-        init_test_logging();
-        let test_name = "payable_scanner_handles_sent_payable_message";
-        let fingerprints_rowids_params_arc = Arc::new(Mutex::new(vec![]));
-        let mark_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
-        let delete_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
-        let correct_payable_hash_1 = make_tx_hash(0x6f);
-        let correct_payable_rowid_1 = 125;
-        let correct_payable_wallet_1 = make_wallet("tralala");
-        let correct_pending_payable_1 =
-            PendingPayable::new(correct_payable_wallet_1.clone(), correct_payable_hash_1);
-        let failure_payable_hash_2 = make_tx_hash(0xde);
-        let failure_payable_rowid_2 = 126;
-        let failure_payable_wallet_2 = make_wallet("hihihi");
-        let failure_payable_2 = RpcPayableFailure {
-            rpc_error: Error::InvalidResponse(
+    fn payable_scanner_excludes_creditors_of_fingerprints_their_payables_no_longer_point_to() {
+        let test_name =
+            "payable_scanner_excludes_creditors_of_fingerprints_their_payables_no_longer_point_to";
+        let home_dir = ensure_node_home_directory_exists("payable_scanner", test_name);
+        let conn = || {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap()
+        };
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let now = SystemTime::now();
+        let (qualified_payable_accounts, _, _) = make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoReal::new(conn());
+        qualified_payable_accounts.iter().for_each(|account| {
+            payable_dao
+                .more_money_payable(
+                    account.last_paid_timestamp,
+                    &account.wallet,
+                    account.balance_wei,
+                )
+                .unwrap()
+        });
+        let pending_payable_dao = PendingPayableDaoReal::new(conn());
+        let hash = make_tx_hash(0x1234);
+        pending_payable_dao
+            .insert_new_fingerprints(
+                &[HashAndAmount {
+                    hash,
+                    amount: 1_000,
+                }],
+                now,
+                ReplacementBasis {
+                    nonce: 1,
+                    gas_price_wei: 1_000_000_000,
+                },
+            )
+            .unwrap();
+        // The payment is out there, but its payable isn't marked as awaiting it
+        let params: &[&dyn ToSql] = &[
+            &qualified_payable_accounts[0].wallet,
+            &format!("{:?}", hash),
+        ];
+        conn()
+            .prepare(
+                "insert into outbound_payment (instructions_rowid, wallet_address, amount_high_b, \
+                 amount_low_b, transaction_hash, status) values (1, ?, 0, 1000, ?, 'submitted')",
+            )
+            .unwrap()
+            .execute(params)
+            .unwrap();
+        let mut subject = PayableScannerBuilder::new().build();
+        subject.payable_dao = Box::new(payable_dao);
+        subject.pending_payable_dao = Box::new(pending_payable_dao);
+
+        let result =
+            subject.begin_scan(consuming_wallet.clone(), now, None, &Logger::new(test_name));
+
+        assert_eq!(
+            result,
+            Ok(QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(
+                    qualified_payable_accounts[1..].to_vec()
+                ),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId::default(),
+                response_skeleton_opt: None,
+            })
+        );
+    }
+
+    #[test]
+    fn payable_scanner_ends_the_scan_if_all_qualified_creditors_await_confirmation() {
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let now = SystemTime::now();
+        let (qualified_payable_accounts, _, all_non_pending_payables) =
+            make_payables(now, &PaymentThresholds::default());
+        let awaiting_creditors = qualified_payable_accounts
+            .iter()
+            .map(|payable| payable.wallet.clone())
+            .collect::<HashSet<_>>();
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .recipients_of_errorless_fingerprints_result(awaiting_creditors);
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(pending_payable_dao)
+            .build();
+
+        let result = subject.begin_scan(consuming_wallet, now, None, &Logger::new("test"));
+
+        let is_scan_running = subject.scan_started_at().is_some();
+        assert_eq!(is_scan_running, false);
+        assert_eq!(result, Err(BeginScanError::NothingToProcess));
+    }
+
+    #[test]
+    fn payable_scanner_throws_error_when_a_scan_is_already_running() {
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let now = SystemTime::now();
+        let (_, _, all_non_pending_payables) = make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )
+            .build();
+        let _result = subject.begin_scan(consuming_wallet.clone(), now, None, &Logger::new("test"));
+
+        let run_again_result = subject.begin_scan(
+            consuming_wallet,
+            SystemTime::now(),
+            None,
+            &Logger::new("test"),
+        );
+
+        let is_scan_running = subject.scan_started_at().is_some();
+        assert_eq!(is_scan_running, true);
+        assert_eq!(
+            run_again_result,
+            Err(BeginScanError::ScanAlreadyRunning(now))
+        );
+    }
+
+    #[test]
+    fn payable_scanner_throws_error_in_case_no_qualified_payable_is_found() {
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let now = SystemTime::now();
+        let (_, unqualified_payable_accounts, _) =
+            make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(unqualified_payable_accounts)
+            .creditor_thresholds_result(HashMap::new());
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .build();
+
+        let result = subject.begin_scan(consuming_wallet, now, None, &Logger::new("test"));
+
+        let is_scan_running = subject.scan_started_at().is_some();
+        assert_eq!(is_scan_running, false);
+        assert_eq!(result, Err(BeginScanError::NothingToProcess));
+    }
+
+    #[test]
+    fn payable_scanner_in_observe_only_mode_notes_qualified_payables_and_ends_the_scan() {
+        init_test_logging();
+        let test_name =
+            "payable_scanner_in_observe_only_mode_notes_qualified_payables_and_ends_the_scan";
+        let now = SystemTime::now();
+        let (qualified_payable_accounts, _, all_non_pending_payables) =
+            make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .build();
+        subject.set_mode(PayableScanMode::ObserveOnly);
+
+        let result = subject.begin_scan(make_wallet("earning"), now, None, &Logger::new(test_name));
+
+        let is_scan_running = subject.scan_started_at().is_some();
+        assert_eq!(is_scan_running, false);
+        assert_eq!(result, Err(BeginScanError::ObserveOnly));
+        assert_eq!(
+            subject.observed_payables_opt(),
+            Some(ObservedPayables {
+                accounts: 2,
+                total_wei: qualified_payable_accounts
+                    .iter()
+                    .map(|account| account.balance_wei)
+                    .sum()
+            })
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Observe-only mode, no consuming wallet to pay from: 2 qualified \
+             debts totaling"
+        ));
+    }
+
+    #[test]
+    fn payable_scanner_in_observe_only_mode_notes_also_that_nothing_qualifies() {
+        let now = SystemTime::now();
+        let (_, unqualified_payable_accounts, _) =
+            make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(unqualified_payable_accounts)
+            .creditor_thresholds_result(HashMap::new());
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .build();
+        subject.set_mode(PayableScanMode::ObserveOnly);
+
+        let result = subject.begin_scan(make_wallet("earning"), now, None, &Logger::new("test"));
+
+        assert_eq!(result, Err(BeginScanError::ObserveOnly));
+        assert_eq!(
+            subject.observed_payables_opt(),
+            Some(ObservedPayables {
+                accounts: 0,
+                total_wei: 0
+            })
+        );
+    }
+
+    #[test]
+    fn payable_scanner_forgets_its_observation_when_switched_to_paying() {
+        let mut subject = PayableScannerBuilder::new().build();
+        subject.set_mode(PayableScanMode::ObserveOnly);
+        subject.observed_payables_opt = Some(ObservedPayables {
+            accounts: 4,
+            total_wei: 123_456,
+        });
+
+        subject.set_mode(PayableScanMode::ObserveOnly);
+        let after_observe_only = subject.observed_payables_opt();
+        subject.set_mode(PayableScanMode::Paying);
+
+        assert_eq!(
+            after_observe_only,
+            Some(ObservedPayables {
+                accounts: 4,
+                total_wei: 123_456
+            })
+        );
+        assert_eq!(subject.observed_payables_opt(), None);
+        assert_eq!(subject.mode, PayableScanMode::Paying);
+    }
+
+    #[test]
+    fn payable_scanner_holds_back_qualified_debts_too_small_to_be_paid_alone() {
+        init_test_logging();
+        let test_name = "payable_scanner_holds_back_qualified_debts_too_small_to_be_paid_alone";
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let now = SystemTime::now();
+        let (qualified_payable_accounts, _, all_non_pending_payables) =
+            make_payables(now, &PaymentThresholds::default());
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::new());
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .payment_batching(PaymentBatching {
+                min_economic_size_gwei: u64::MAX,
+                batch_target_gwei: u64::MAX,
+                max_delay_sec: 3_600,
+            })
+            .build();
+
+        let result = subject.begin_scan(consuming_wallet, now, None, &Logger::new(test_name));
+
+        let is_scan_running = subject.scan_started_at().is_some();
+        assert_eq!(is_scan_running, false);
+        assert_eq!(result, Err(BeginScanError::NothingToProcess));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Holding back {} debts below",
+            qualified_payable_accounts.len()
+        ));
+    }
+
+    #[test]
+    fn payable_scanner_holds_debts_to_long_unreachable_creditors_until_their_deadline_nears() {
+        init_test_logging();
+        let test_name =
+            "payable_scanner_holds_debts_to_long_unreachable_creditors_until_their_deadline_nears";
+        let consuming_wallet = make_paying_wallet(b"consuming wallet");
+        let now = SystemTime::now();
+        let (qualified_payable_accounts, _, all_non_pending_payables) =
+            make_payables(now, &PaymentThresholds::default());
+        let far_from_deadline = qualified_payable_accounts[0].clone();
+        let near_deadline = qualified_payable_accounts[1].clone();
+        let impatient_thresholds = PaymentThresholds {
+            payment_grace_period_sec: 650,
+            ..PaymentThresholds::default()
+        };
+        let payable_dao = PayableDaoMock::new()
+            .non_pending_payables_result(all_non_pending_payables)
+            .creditor_thresholds_result(HashMap::from([(
+                near_deadline.wallet.clone(),
+                impatient_thresholds,
+            )]));
+        let creditor_reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        creditor_reachability
+            .borrow_mut()
+            .update(&ReportCreditorReachabilityMessage {
+                timestamp: now.sub(Duration::from_secs(3_600)),
+                reachable: vec![],
+                unreachable: vec![
+                    far_from_deadline.wallet.clone(),
+                    near_deadline.wallet.clone(),
+                ],
+            });
+        let mut subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(
+                PendingPayableDaoMock::new()
+                    .recipients_of_errorless_fingerprints_result(HashSet::new()),
+            )
+            .unreachable_creditor_hold(
+                UnreachableCreditorHold {
+                    absence_sec: 3_600,
+                    deadline_margin_sec: 600,
+                },
+                &creditor_reachability,
+            )
+            .build();
+
+        let result =
+            subject.begin_scan(consuming_wallet.clone(), now, None, &Logger::new(test_name));
+
+        assert_eq!(
+            result,
+            Ok(QualifiedPayablesMessage {
+                protected_qualified_payables: protect_payables_in_test(vec![near_deadline.clone()]),
+                consuming_wallet,
+                gas_price_bump_percent_opt: None,
+                scan_id: ScanId::default(),
+                response_skeleton_opt: None,
+            })
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Holding the debt of {} wei to {}, whose Node has been gone from \
+             the Neighborhood for 3600 sec",
+            far_from_deadline.balance_wei.separate_with_commas(),
+            far_from_deadline.wallet
+        ));
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Releasing the debt to {} although its Node is still gone from the \
+             Neighborhood",
+            near_deadline.wallet
+        ));
+    }
+
+    #[test]
+    fn payable_scanner_holds_back_final_instructions_breaking_the_adjustment_invariants() {
+        init_test_logging();
+        let test_name =
+            "payable_scanner_holds_back_final_instructions_breaking_the_adjustment_invariants";
+        let account = make_payable_account(123);
+        let overpaid_account = PayableAccount {
+            balance_wei: account.balance_wei + 1,
+            ..account.clone()
+        };
+        let instructions = OutboundPaymentsInstructions::new(
+            vec![overpaid_account],
+            Box::new(BlockchainAgentMock::default()),
+            None,
+        );
+        let subject = PayableScannerBuilder::new()
+            .check_adjustment_invariants(true)
+            .build();
+
+        let result = subject.enforce_adjustment_invariants(
+            &[account],
+            u128::MAX,
+            instructions,
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(result.affordable_accounts, vec![]);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: {test_name}: Payment adjustment broke its invariants; nothing is paid in this \
+             scan. Diagnostics: "
+        ));
+    }
+
+    #[test]
+    fn payable_scanner_favors_creditors_in_the_new_exit_country_in_the_next_adjustment() {
+        init_test_logging();
+        let test_name =
+            "payable_scanner_favors_creditors_in_the_new_exit_country_in_the_next_adjustment";
+        let make_msg = || {
+            BlockchainAgentWithContextMessage::new(
+                protect_payables_in_test(vec![make_payable_account(123)]),
+                Box::new(BlockchainAgentMock::default()),
+                None,
+            )
+        };
+        let payment_adjuster = PaymentAdjusterMock::default()
+            .is_adjustment_required_result(Ok(None))
+            .is_adjustment_required_result(Ok(Some(Adjustment::MasqToken)))
+            .is_adjustment_required_result(Ok(Some(Adjustment::MasqToken)));
+        let creditor_reachability = Rc::new(RefCell::new(CreditorReachability::default()));
+        creditor_reachability
+            .borrow_mut()
+            .favor_exit_creditors(&ReportExitFailoverMessage {
+                timestamp: SystemTime::now(),
+                exit_country: "CZ".to_string(),
+                creditors: vec![make_wallet("czech 1"), make_wallet("czech 2")],
+            });
+        let mut subject = PayableScannerBuilder::new()
+            .payment_adjuster(payment_adjuster)
+            .creditor_reachability(&creditor_reachability)
+            .build();
+        let logger = Logger::new(test_name);
+
+        let unadjusted = subject.try_skipping_payment_adjustment(make_msg(), &logger);
+        let first_adjustment = subject.try_skipping_payment_adjustment(make_msg(), &logger);
+        let second_adjustment = subject.try_skipping_payment_adjustment(make_msg(), &logger);
+
+        assert!(unadjusted.unwrap().is_left());
+        assert_eq!(
+            first_adjustment.unwrap().right().unwrap().favored_creditors,
+            vec![make_wallet("czech 1"), make_wallet("czech 2")]
+                .into_iter()
+                .collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            second_adjustment
+                .unwrap()
+                .right()
+                .unwrap()
+                .favored_creditors,
+            HashSet::new()
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Favoring 2 creditors in the newly selected exit country in this \
+             payment adjustment"
+        ));
+    }
+
+    #[test]
+    fn payable_scanner_handles_sent_payable_message() {
+        init_test_logging();
+        let test_name = "payable_scanner_handles_sent_payable_message";
+        let fingerprints_rowids_params_arc = Arc::new(Mutex::new(vec![]));
+        let mark_pending_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let delete_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
+        let correct_payable_hash_1 = make_tx_hash(0x6f);
+        let correct_payable_rowid_1 = 125;
+        let correct_payable_wallet_1 = make_wallet("tralala");
+        let correct_pending_payable_1 =
+            PendingPayable::new(correct_payable_wallet_1.clone(), correct_payable_hash_1);
+        let failure_payable_hash_2 = make_tx_hash(0xde);
+        let failure_payable_rowid_2 = 126;
+        let failure_payable_wallet_2 = make_wallet("hihihi");
+        let failure_payable_2 = RpcPayableFailure {
+            rpc_error: Error::InvalidResponse(
                 "Learn how to write before you send your garbage!".to_string(),
             ),
             recipient_wallet: failure_payable_wallet_2,
@@ -1980,7 +3060,7 @@ mod tests {
         let mut payable = make_payable_account(111);
         payable.last_paid_timestamp = last_paid_timestamp;
 
-        let result = subject.payable_exceeded_threshold(&payable, now);
+        let result = subject.payable_exceeded_threshold(&payable, &HashMap::new(), now);
 
         assert_eq!(result, None);
         let mut is_innocent_age_params = is_innocent_age_params_arc.lock().unwrap();
@@ -2012,7 +3092,7 @@ mod tests {
         payable.last_paid_timestamp = last_paid_timestamp;
         payable.balance_wei = 123456;
 
-        let result = subject.payable_exceeded_threshold(&payable, now);
+        let result = subject.payable_exceeded_threshold(&payable, &HashMap::new(), now);
 
         assert_eq!(result, None);
         let mut is_innocent_age_params = is_innocent_age_params_arc.lock().unwrap();
@@ -2069,7 +3149,7 @@ mod tests {
             .build();
         subject.payable_threshold_gauge = Box::new(payable_thresholds_gauge);
 
-        let result = subject.payable_exceeded_threshold(&payable_account, now);
+        let result = subject.payable_exceeded_threshold(&payable_account, &HashMap::new(), now);
 
         assert_eq!(result, Some(4567898));
         let mut is_innocent_age_params = is_innocent_age_params_arc.lock().unwrap();
@@ -2098,10 +3178,87 @@ mod tests {
     }
 
     #[test]
-    fn payable_with_debt_under_the_slope_is_marked_unqualified() {
-        init_test_logging();
+    fn thresholds_advertised_by_creditor_take_precedence_over_ours() {
         let now = SystemTime::now();
-        let payment_thresholds = PaymentThresholds::default();
+        let creditor_payment_thresholds = PaymentThresholds {
+            maturity_threshold_sec: 100,
+            payment_grace_period_sec: 100,
+            permanent_debt_allowed_gwei: 1_000,
+            debt_threshold_gwei: 10_000,
+            threshold_interval_sec: 1_000,
+            unban_below_gwei: 1_000,
+        };
+        let payable = PayableAccount {
+            wallet: make_wallet("creditor"),
+            balance_wei: gwei_to_wei(20_000_u64),
+            last_paid_timestamp: now.checked_sub(Duration::from_secs(1_000)).unwrap(),
+            pending_payable_opt: None,
+        };
+        let subject = PayableScannerBuilder::new()
+            .payment_thresholds(PaymentThresholds::default())
+            .build();
+        let creditor_thresholds =
+            HashMap::from_iter(vec![(payable.wallet.clone(), creditor_payment_thresholds)]);
+        let strangers_thresholds =
+            HashMap::from_iter(vec![(make_wallet("stranger"), creditor_payment_thresholds)]);
+
+        let by_our_thresholds = subject.payable_exceeded_threshold(&payable, &HashMap::new(), now);
+        let by_strangers_thresholds =
+            subject.payable_exceeded_threshold(&payable, &strangers_thresholds, now);
+        let by_creditors_thresholds =
+            subject.payable_exceeded_threshold(&payable, &creditor_thresholds, now);
+
+        assert_eq!(by_our_thresholds, None);
+        assert_eq!(by_strangers_thresholds, None);
+        assert_eq!(
+            by_creditors_thresholds,
+            Some(
+                PayableThresholdsGaugeReal::default()
+                    .calculate_payout_threshold_in_gwei(&creditor_payment_thresholds, 1_000)
+            )
+        );
+    }
+
+    #[test]
+    fn is_below_threshold_judges_by_thresholds_advertised_by_creditor() {
+        let now = SystemTime::now();
+        let creditor_payment_thresholds = PaymentThresholds {
+            maturity_threshold_sec: 100,
+            payment_grace_period_sec: 100,
+            permanent_debt_allowed_gwei: 1_000,
+            debt_threshold_gwei: 10_000,
+            threshold_interval_sec: 1_000,
+            unban_below_gwei: 1_000,
+        };
+        let payable = PayableAccount {
+            wallet: make_wallet("creditor"),
+            balance_wei: gwei_to_wei(20_000_u64),
+            last_paid_timestamp: now.checked_sub(Duration::from_secs(1_000)).unwrap(),
+            pending_payable_opt: None,
+        };
+        let payable_dao = PayableDaoMock::new()
+            .creditor_thresholds_result(HashMap::new())
+            .creditor_thresholds_result(HashMap::from_iter(vec![(
+                payable.wallet.clone(),
+                creditor_payment_thresholds,
+            )]));
+        let subject = PayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .payment_thresholds(PaymentThresholds::default())
+            .build();
+
+        let by_our_thresholds = subject.is_below_threshold(&payable, now);
+        let by_creditors_thresholds = subject.is_below_threshold(&payable, now);
+
+        assert!(by_our_thresholds);
+        assert!(!by_creditors_thresholds);
+    }
+
+    #[test]
+    fn payable_with_debt_under_the_slope_is_marked_unqualified() {
+        init_test_logging();
+        let now = SystemTime::now();
+        let payment_thresholds = PaymentThresholds::default();
         let debt = gwei_to_wei(payment_thresholds.permanent_debt_allowed_gwei + 1);
         let time = to_time_t(now) - payment_thresholds.maturity_threshold_sec as i64 - 1;
         let unqualified_payable_account = vec![PayableAccount {
@@ -2117,8 +3274,11 @@ mod tests {
             "payable_with_debt_above_the_slope_is_qualified_and_the_threshold_value_is_returned";
         let logger = Logger::new(test_name);
 
-        let result = subject
-            .sniff_out_alarming_payables_and_maybe_log_them(unqualified_payable_account, &logger);
+        let result = subject.sniff_out_alarming_payables_and_maybe_log_them(
+            unqualified_payable_account,
+            &HashMap::new(),
+            &logger,
+        );
 
         assert_eq!(result, vec![]);
         TestLogHandler::new()
@@ -2147,6 +3307,7 @@ mod tests {
 
         let result = subject.sniff_out_alarming_payables_and_maybe_log_them(
             vec![qualified_payable.clone()],
+            &HashMap::new(),
             &logger,
         );
 
@@ -2178,8 +3339,11 @@ mod tests {
             .build();
         let logger = Logger::new(test_name);
 
-        let result = subject
-            .sniff_out_alarming_payables_and_maybe_log_them(unqualified_payable_account, &logger);
+        let result = subject.sniff_out_alarming_payables_and_maybe_log_them(
+            unqualified_payable_account,
+            &HashMap::new(),
+            &logger,
+        );
 
         assert_eq!(result, vec![]);
         TestLogHandler::new()
@@ -2199,6 +3363,7 @@ mod tests {
             attempt: 1,
             amount: 4444,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let payable_fingerprint_2 = PendingPayableFingerprint {
             rowid: 550,
@@ -2207,6 +3372,7 @@ mod tests {
             attempt: 1,
             amount: 7999,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let fingerprints = vec![payable_fingerprint_1, payable_fingerprint_2];
         let pending_payable_dao = PendingPayableDaoMock::new()
@@ -2252,6 +3418,7 @@ mod tests {
                 attempt: 1,
                 amount: 1_000_000,
                 process_error: None,
+                replacement_basis_opt: None,
             }]);
         let mut subject = PendingPayableScannerBuilder::new()
             .pending_payable_dao(pending_payable_dao)
@@ -2300,6 +3467,7 @@ mod tests {
             attempt: 1,
             amount: 123,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let logger = Logger::new(test_name);
         let scan_report = PendingPayableScanReport::default();
@@ -2354,20 +3522,22 @@ mod tests {
         );
 
         let elapsed_after = elapsed_since_secs_back(DEFAULT_PENDING_TOO_LONG_SEC + 1);
+        assert_eq!(result.still_pending, vec![]);
+        assert_eq!(result.failures, vec![]);
+        assert_eq!(result.confirmed, vec![]);
         assert_eq!(
-            result,
-            PendingPayableScanReport {
-                still_pending: vec![],
-                failures: vec![PendingPayableId::new(rowid, hash)],
-                confirmed: vec![]
-            }
+            result
+                .abandoned
+                .iter()
+                .map(|fingerprint| (fingerprint.rowid, fingerprint.hash))
+                .collect::<Vec<_>>(),
+            vec![(rowid, hash)]
         );
         let capture_regex = "(\\d+){2}sec";
         assert_log_msg_and_elapsed_time_in_log_makes_sense(&format!(
-            "ERROR: {}: Pending transaction 0x00000000000000000000000000000000000000\
+            "WARN: {}: Pending transaction 0x00000000000000000000000000000000000000\
             00000000000000000000000237 has exceeded the maximum pending time \\({}sec\\) with the age \
-            \\d+sec and the confirmation process is going to be aborted now at the final attempt 1; manual \
-            resolution is required from the user to complete the transaction"
+            \\d+sec and is going to be abandoned at the final attempt 1"
             , test_name, DEFAULT_PENDING_TOO_LONG_SEC, ), elapsed_after, capture_regex)
     }
 
@@ -2392,7 +3562,8 @@ mod tests {
             PendingPayableScanReport {
                 still_pending: vec![PendingPayableId::new(rowid, hash)],
                 failures: vec![],
-                confirmed: vec![]
+                confirmed: vec![],
+                abandoned: vec![]
             }
         );
         let capture_regex = r#"\s(\d+)ms"#;
@@ -2422,7 +3593,8 @@ mod tests {
             PendingPayableScanReport {
                 still_pending: vec![PendingPayableId::new(rowid, hash)],
                 failures: vec![],
-                confirmed: vec![]
+                confirmed: vec![],
+                abandoned: vec![]
             }
         );
         let capture_regex = r#"\s(\d+)ms"#;
@@ -2446,6 +3618,7 @@ mod tests {
             attempt: 5,
             amount: 2222,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let logger = Logger::new(test_name);
         let scan_report = PendingPayableScanReport::default();
@@ -2457,7 +3630,8 @@ mod tests {
             PendingPayableScanReport {
                 still_pending: vec![],
                 failures: vec![PendingPayableId::new(777777, hash,)],
-                confirmed: vec![]
+                confirmed: vec![],
+                abandoned: vec![]
             }
         );
         TestLogHandler::new().exists_log_matching(&format!(
@@ -2481,6 +3655,7 @@ mod tests {
             attempt: 3,
             amount: 111,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let msg = ReportTransactionReceipts {
             fingerprints_with_receipts: vec![(
@@ -2500,14 +3675,153 @@ mod tests {
             PendingPayableScanReport {
                 still_pending: vec![PendingPayableId::new(rowid, hash)],
                 failures: vec![],
-                confirmed: vec![]
+                confirmed: vec![],
+                abandoned: vec![]
             }
         );
         TestLogHandler::new().exists_log_matching(&format!(
-            "DEBUG: {test_name}: Interpreting a receipt for transaction \
+            "INFO: {test_name}: Pending transaction \
             0x0000000000000000000000000000000000000000000000000000000000000913 \
-            but none was given; attempt 3, 100\\d\\dms since sending"
+            couldn't be confirmed at attempt 3 at 100\\d\\dms after its sending"
+        ));
+    }
+
+    #[test]
+    fn handle_pending_txs_with_receipts_abandons_transactions_pending_for_too_long() {
+        let subject = PendingPayableScannerBuilder::new()
+            .when_pending_too_long_sec(5)
+            .build();
+        let fingerprint = PendingPayableFingerprint {
+            rowid: 455,
+            timestamp: SystemTime::now().sub(Duration::from_secs(10)),
+            hash: make_tx_hash(0x913),
+            attempt: 3,
+            amount: 111,
+            process_error: None,
+            replacement_basis_opt: None,
+        };
+        let msg = ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![(
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    transaction_hash: fingerprint.hash,
+                    status: TxStatus::Pending,
+                }),
+                fingerprint.clone(),
+            )],
+            response_skeleton_opt: None,
+        };
+
+        let result = subject.handle_receipts_for_pending_transactions(
+            msg,
+            &Logger::new(
+                "handle_pending_txs_with_receipts_abandons_transactions_pending_for_too_long",
+            ),
+        );
+
+        assert_eq!(
+            result,
+            PendingPayableScanReport {
+                still_pending: vec![],
+                failures: vec![],
+                confirmed: vec![],
+                abandoned: vec![fingerprint]
+            }
+        );
+    }
+
+    #[test]
+    fn abandon_transactions_releases_the_payables_and_passes_on_the_voidable_transactions() {
+        init_test_logging();
+        let test_name =
+            "abandon_transactions_releases_the_payables_and_passes_on_the_voidable_transactions";
+        let release_failed_payables_params_arc = Arc::new(Mutex::new(vec![]));
+        let mark_abandoned_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .release_failed_payables_params(&release_failed_payables_params_arc)
+            .release_failed_payables_result(Ok(vec![]));
+        let pending_payable_dao = PendingPayableDaoMock::new()
+            .mark_abandoned_params(&mark_abandoned_params_arc)
+            .mark_abandoned_result(Ok(()));
+        let abandoned_transactions = Rc::new(RefCell::new(vec![]));
+        let mut subject = PendingPayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(pending_payable_dao)
+            .when_pending_too_long_sec(3_600)
+            .abandoned_transactions(&abandoned_transactions)
+            .build();
+        let voidable = PendingPayableFingerprint {
+            replacement_basis_opt: Some(ReplacementBasis {
+                nonce: 12,
+                gas_price_wei: 1_000_000_000,
+            }),
+            ..make_pending_payable_fingerprint()
+        };
+        let unvoidable = PendingPayableFingerprint {
+            rowid: 44,
+            hash: make_tx_hash(0x44),
+            replacement_basis_opt: None,
+            ..make_pending_payable_fingerprint()
+        };
+
+        subject.abandon_transactions(
+            vec![voidable.clone(), unvoidable.clone()],
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(
+            *release_failed_payables_params_arc.lock().unwrap(),
+            vec![vec![voidable.rowid, 44]]
+        );
+        assert_eq!(
+            *mark_abandoned_params_arc.lock().unwrap(),
+            vec![(
+                vec![voidable.rowid, 44],
+                "pending for longer than 3600sec".to_string()
+            )]
+        );
+        assert_eq!(*abandoned_transactions.borrow(), vec![voidable.clone()]);
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Transactions {:?}, {:?} abandoned; their debts are left for the \
+             next payable scan to pay",
+            voidable.hash, unvoidable.hash
+        ));
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Abandoned transactions {:?} were recorded without their nonces \
+             and cannot be voided",
+            unvoidable.hash
+        ));
+    }
+
+    #[test]
+    fn abandon_transactions_does_nothing_without_any() {
+        let mut subject = PendingPayableScannerBuilder::new().build();
+
+        subject.abandon_transactions(vec![], &Logger::new("test"));
+
+        assert_eq!(*subject.abandoned_transactions.borrow(), vec![]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Unable to mark transactions 0x000000000000000000000000000000000000000000000000000000000000007b \
+        abandoned due to ErrorMarkFailed(\"no no no\"); database unreliable"
+    )]
+    fn abandon_transactions_panics_when_marking_fails() {
+        let payable_dao = PayableDaoMock::new().release_failed_payables_result(Ok(vec![]));
+        let pending_payable_dao = PendingPayableDaoMock::new().mark_abandoned_result(Err(
+            PendingPayableDaoError::ErrorMarkFailed("no no no".to_string()),
         ));
+        let mut subject = PendingPayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(pending_payable_dao)
+            .build();
+        let fingerprint = PendingPayableFingerprint {
+            hash: make_tx_hash(0x7b),
+            ..make_pending_payable_fingerprint()
+        };
+
+        subject.abandon_transactions(vec![fingerprint], &Logger::new("test"));
     }
 
     #[test]
@@ -2578,7 +3892,7 @@ mod tests {
         let pending_payable_dao = PendingPayableDaoMock::default()
             .mark_failures_params(&mark_failures_params_arc)
             .mark_failures_result(Ok(()));
-        let subject = PendingPayableScannerBuilder::new()
+        let mut subject = PendingPayableScannerBuilder::new()
             .pending_payable_dao(pending_payable_dao)
             .build();
         let id_1 = PendingPayableId::new(2, make_tx_hash(0x7b));
@@ -2607,7 +3921,7 @@ mod tests {
         let pending_payable_dao = PendingPayableDaoMock::default().mark_failures_result(Err(
             PendingPayableDaoError::UpdateFailed("no no no".to_string()),
         ));
-        let subject = PendingPayableScannerBuilder::new()
+        let mut subject = PendingPayableScannerBuilder::new()
             .pending_payable_dao(pending_payable_dao)
             .build();
         let transaction_id_1 = PendingPayableId::new(2, make_tx_hash(333));
@@ -2619,7 +3933,7 @@ mod tests {
 
     #[test]
     fn cancel_failed_transactions_does_nothing_if_no_tx_failures_detected() {
-        let subject = PendingPayableScannerBuilder::new().build();
+        let mut subject = PendingPayableScannerBuilder::new().build();
 
         subject.cancel_failed_transactions(vec![], &Logger::new("test"))
 
@@ -2687,6 +4001,7 @@ mod tests {
             attempt: 1,
             amount: 4567,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let pending_payable_fingerprint_2 = PendingPayableFingerprint {
             rowid: rowid_2,
@@ -2695,6 +4010,7 @@ mod tests {
             attempt: 1,
             amount: 5555,
             process_error: None,
+            replacement_basis_opt: None,
         };
 
         subject.confirm_transactions(
@@ -2764,6 +4080,7 @@ mod tests {
             attempt: 1,
             amount: 5478,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let fingerprint_2 = PendingPayableFingerprint {
             rowid: 6,
@@ -2772,6 +4089,7 @@ mod tests {
             attempt: 1,
             amount: 6543,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let payable_dao = PayableDaoMock::default().transactions_confirmed_result(Ok(()));
         let pending_payable_dao =
@@ -2821,6 +4139,7 @@ mod tests {
             attempt: 2,
             amount: 444,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let transaction_hash_2 = make_tx_hash(1234);
         let transaction_receipt_2 = TxReceipt {
@@ -2837,6 +4156,7 @@ mod tests {
             attempt: 15,
             amount: 1212,
             process_error: None,
+            replacement_basis_opt: None,
         };
         let msg = ReportTransactionReceipts {
             fingerprints_with_receipts: vec![
@@ -2922,7 +4242,7 @@ mod tests {
         assert_eq!(
             result,
             Ok(RetrieveTransactions {
-                recipient: earning_wallet.clone(),
+                recipients: vec![earning_wallet.clone()],
                 response_skeleton_opt: None
             })
         );
@@ -2931,6 +4251,71 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn receivable_scanner_scans_for_payments_to_the_whole_earning_wallet_rotation() {
+        init_test_logging();
+        let test_name =
+            "receivable_scanner_scans_for_payments_to_the_whole_earning_wallet_rotation";
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let rotation = vec![
+            make_wallet("earning 0"),
+            make_wallet("earning 1"),
+            make_wallet("earning 2"),
+        ];
+        let mut receivable_scanner = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .earning_wallet_rotation(rotation.clone())
+            .build();
+
+        let result = receivable_scanner.begin_scan(
+            rotation[1].clone(),
+            SystemTime::now(),
+            None,
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(
+            result,
+            Ok(RetrieveTransactions {
+                recipients: rotation.clone(),
+                response_skeleton_opt: None
+            })
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Scanning for receivables to {}, {}, {}",
+            rotation[0], rotation[1], rotation[2]
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_ignores_rotation_the_earning_wallet_has_left() {
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let earning_wallet = make_wallet("new earning");
+        let mut receivable_scanner = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .earning_wallet_rotation(vec![make_wallet("earning 0"), make_wallet("earning 1")])
+            .build();
+
+        let result = receivable_scanner.begin_scan(
+            earning_wallet.clone(),
+            SystemTime::now(),
+            None,
+            &Logger::new("test"),
+        );
+
+        assert_eq!(
+            result,
+            Ok(RetrieveTransactions {
+                recipients: vec![earning_wallet],
+                response_skeleton_opt: None
+            })
+        );
+    }
+
     #[test]
     fn receivable_scanner_throws_error_in_case_scan_is_already_running() {
         let now = SystemTime::now();
@@ -2991,7 +4376,7 @@ mod tests {
         assert_eq!(
             result,
             Ok(RetrieveTransactions {
-                recipient: earning_wallet,
+                recipients: vec![earning_wallet],
                 response_skeleton_opt: None
             })
         );
@@ -3048,6 +4433,8 @@ mod tests {
             new_start_block,
             response_skeleton_opt: None,
             transactions: vec![],
+            rechecked_opt: None,
+            pending: vec![],
         };
 
         let message_opt = subject.finish_scan(msg, &Logger::new(test_name));
@@ -3083,6 +4470,8 @@ mod tests {
             new_start_block,
             response_skeleton_opt: None,
             transactions: vec![],
+            rechecked_opt: None,
+            pending: vec![],
         };
 
         // Not necessary, rather for preciseness
@@ -3136,6 +4525,8 @@ mod tests {
             new_start_block: BlockMarker::Value(7890123),
             response_skeleton_opt: None,
             transactions: receivables.clone(),
+            rechecked_opt: None,
+            pending: vec![],
         };
         subject.mark_as_started(SystemTime::now());
 
@@ -3162,6 +4553,394 @@ mod tests {
         );
     }
 
+    #[test]
+    fn receivable_scanner_keeps_pending_payments_out_of_the_paid_receivable() {
+        init_test_logging();
+        let test_name = "receivable_scanner_keeps_pending_payments_out_of_the_paid_receivable";
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Ok(None))
+            .set_start_block_result(Ok(()));
+        let mut subject = ReceivableScannerBuilder::new()
+            .persistent_configuration(persistent_config)
+            .build();
+        let pending_payment_1 = BlockchainTransaction {
+            block_number: 105,
+            from: make_wallet("payer 1"),
+            wei_amount: 1_234_567,
+        };
+        let pending_payment_2 = BlockchainTransaction {
+            block_number: 107,
+            from: make_wallet("payer 2"),
+            wei_amount: 1_000,
+        };
+        let msg = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block: BlockMarker::Value(101),
+            response_skeleton_opt: None,
+            transactions: vec![],
+            rechecked_opt: None,
+            pending: vec![pending_payment_1.clone(), pending_payment_2.clone()],
+        };
+
+        subject.finish_scan(msg, &Logger::new(test_name));
+
+        assert_eq!(
+            subject.pending_payments,
+            vec![pending_payment_1, pending_payment_2]
+        );
+        let financial_statistics = subject.financial_statistics.borrow();
+        assert_eq!(financial_statistics.total_paid_receivable_wei, 0);
+        assert_eq!(
+            financial_statistics.total_pending_receivable_wei,
+            1_234_567 + 1_000
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Payment of 1,234,567 wei from {} in block 105 is pending confirmation",
+            make_wallet("payer 1")
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_confirms_pending_payments_and_drops_those_that_vanished() {
+        init_test_logging();
+        let test_name =
+            "receivable_scanner_confirms_pending_payments_and_drops_those_that_vanished";
+        let more_money_received_params_arc = Arc::new(Mutex::new(vec![]));
+        let txn_inner_builder = TransactionInnerWrapperMockBuilder::default()
+            .commit_result(Ok(()))
+            .set_arbitrary_id_stamp(ArbitraryIdStamp::new());
+        let transaction = TransactionSafeWrapper::new_with_builder(txn_inner_builder);
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Ok(None))
+            .set_start_block_from_txn_result(Ok(()));
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_received_params(&more_money_received_params_arc)
+            .more_money_received_result(transaction);
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .persistent_configuration(persistent_config)
+            .build();
+        let confirmed_payment = BlockchainTransaction {
+            block_number: 95,
+            from: make_wallet("confirmed payer"),
+            wei_amount: 50_000,
+        };
+        let vanished_payment = BlockchainTransaction {
+            block_number: 97,
+            from: make_wallet("vanished payer"),
+            wei_amount: 60_000,
+        };
+        let still_pending_payment = BlockchainTransaction {
+            block_number: 105,
+            from: make_wallet("patient payer"),
+            wei_amount: 70_000,
+        };
+        let newly_pending_payment = BlockchainTransaction {
+            block_number: 103,
+            from: make_wallet("new payer"),
+            wei_amount: 80_000,
+        };
+        subject.pending_payments = vec![
+            confirmed_payment.clone(),
+            vanished_payment.clone(),
+            still_pending_payment.clone(),
+        ];
+        let msg = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block: BlockMarker::Value(101),
+            response_skeleton_opt: None,
+            transactions: vec![confirmed_payment.clone()],
+            rechecked_opt: None,
+            pending: vec![newly_pending_payment.clone()],
+        };
+
+        subject.finish_scan(msg, &Logger::new(test_name));
+
+        assert_eq!(
+            subject.pending_payments,
+            vec![newly_pending_payment, still_pending_payment]
+        );
+        let financial_statistics = subject.financial_statistics.borrow();
+        assert_eq!(financial_statistics.total_paid_receivable_wei, 50_000);
+        assert_eq!(
+            financial_statistics.total_pending_receivable_wei,
+            80_000 + 70_000
+        );
+        let more_money_received_params = more_money_received_params_arc.lock().unwrap();
+        assert_eq!(more_money_received_params[0].1, vec![confirmed_payment]);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "INFO: {test_name}: Pending payment of 50,000 wei from {} in block 95 has been confirmed",
+            make_wallet("confirmed payer")
+        ));
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Pending payment of 60,000 wei from {} in block 97 vanished before \
+             it was confirmed",
+            make_wallet("vanished payer")
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_reverses_credited_payments_that_vanished_in_a_reorg() {
+        init_test_logging();
+        let test_name = "receivable_scanner_reverses_credited_payments_that_vanished_in_a_reorg";
+        let received_payments_between_params_arc = Arc::new(Mutex::new(vec![]));
+        let reverse_received_payments_params_arc = Arc::new(Mutex::new(vec![]));
+        let forget_received_payments_before_params_arc = Arc::new(Mutex::new(vec![]));
+        let surviving_payment = BlockchainTransaction {
+            block_number: 4_321,
+            from: make_wallet("surviving payer"),
+            wei_amount: 123_456,
+        };
+        let reorged_payment = BlockchainTransaction {
+            block_number: 4_319,
+            from: make_wallet("reorged payer"),
+            wei_amount: 1_000_000,
+        };
+        let twice_paid_payment = BlockchainTransaction {
+            block_number: 4_322,
+            from: make_wallet("twice paying payer"),
+            wei_amount: 5_000,
+        };
+        let receivable_dao = ReceivableDaoMock::new()
+            .received_payments_between_params(&received_payments_between_params_arc)
+            .received_payments_between_result(vec![
+                surviving_payment.clone(),
+                reorged_payment.clone(),
+                twice_paid_payment.clone(),
+                twice_paid_payment.clone(),
+            ])
+            .reverse_received_payments_params(&reverse_received_payments_params_arc)
+            .reverse_received_payments_result(Ok(()))
+            .forget_received_payments_before_params(&forget_received_payments_before_params_arc)
+            .forget_received_payments_before_result(Ok(()));
+        let persistent_config = PersistentConfigurationMock::new().set_start_block_result(Ok(()));
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .persistent_configuration(persistent_config)
+            .build();
+        subject
+            .financial_statistics
+            .borrow_mut()
+            .total_paid_receivable_wei = 2_000_000;
+        let msg = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block: BlockMarker::Value(4_330),
+            response_skeleton_opt: None,
+            transactions: vec![],
+            rechecked_opt: Some(RecheckedTransactions {
+                from_block: 4_318,
+                to_block: 4_329,
+                transactions: vec![twice_paid_payment.clone(), surviving_payment],
+            }),
+            pending: vec![],
+        };
+        subject.mark_as_started(SystemTime::now());
+
+        let _ = subject.finish_scan(msg, &Logger::new(test_name));
+
+        let received_payments_between_params = received_payments_between_params_arc.lock().unwrap();
+        assert_eq!(*received_payments_between_params, vec![(4_318, 4_329)]);
+        let reverse_received_payments_params = reverse_received_payments_params_arc.lock().unwrap();
+        assert_eq!(
+            *reverse_received_payments_params,
+            vec![vec![reorged_payment, twice_paid_payment]]
+        );
+        let forget_received_payments_before_params =
+            forget_received_payments_before_params_arc.lock().unwrap();
+        assert_eq!(*forget_received_payments_before_params, vec![4_318]);
+        assert_eq!(
+            subject
+                .financial_statistics
+                .borrow()
+                .total_paid_receivable_wei,
+            2_000_000 - 1_000_000 - 5_000
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Payment of 1,000,000 wei from {} in block 4319 vanished in a chain \
+             reorganization; its credit is reversed",
+            make_wallet("reorged payer")
+        ));
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Payment of 5,000 wei from {} in block 4322 vanished in a chain \
+             reorganization; its credit is reversed",
+            make_wallet("twice paying payer")
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_reverses_nothing_if_all_rechecked_payments_are_still_there() {
+        let forget_received_payments_before_params_arc = Arc::new(Mutex::new(vec![]));
+        let payment = BlockchainTransaction {
+            block_number: 100,
+            from: make_wallet("payer"),
+            wei_amount: 123_456,
+        };
+        let receivable_dao = ReceivableDaoMock::new()
+            .received_payments_between_result(vec![payment.clone()])
+            .forget_received_payments_before_params(&forget_received_payments_before_params_arc)
+            .forget_received_payments_before_result(Ok(()));
+        let persistent_config = PersistentConfigurationMock::new().set_start_block_result(Ok(()));
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .persistent_configuration(persistent_config)
+            .build();
+        subject
+            .financial_statistics
+            .borrow_mut()
+            .total_paid_receivable_wei = 123_456;
+        let msg = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block: BlockMarker::Value(112),
+            response_skeleton_opt: None,
+            transactions: vec![],
+            rechecked_opt: Some(RecheckedTransactions {
+                from_block: 100,
+                to_block: 111,
+                transactions: vec![payment],
+            }),
+            pending: vec![],
+        };
+        subject.mark_as_started(SystemTime::now());
+
+        let _ = subject.finish_scan(msg, &Logger::new("test"));
+
+        let forget_received_payments_before_params =
+            forget_received_payments_before_params_arc.lock().unwrap();
+        assert_eq!(*forget_received_payments_before_params, vec![100]);
+        assert_eq!(
+            subject
+                .financial_statistics
+                .borrow()
+                .total_paid_receivable_wei,
+            123_456
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Reversal of payments lost in a chain reorganization failed: RusqliteError(\"booga\")"
+    )]
+    fn receivable_scanner_panics_if_reversal_of_vanished_payments_fails() {
+        let receivable_dao = ReceivableDaoMock::new()
+            .received_payments_between_result(vec![BlockchainTransaction {
+                block_number: 100,
+                from: make_wallet("payer"),
+                wei_amount: 123_456,
+            }])
+            .reverse_received_payments_result(Err(ReceivableDaoError::RusqliteError(
+                "booga".to_string(),
+            )));
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .build();
+        let msg = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            new_start_block: BlockMarker::Value(112),
+            response_skeleton_opt: None,
+            transactions: vec![],
+            rechecked_opt: Some(RecheckedTransactions {
+                from_block: 100,
+                to_block: 111,
+                transactions: vec![],
+            }),
+            pending: vec![],
+        };
+
+        let _ = subject.finish_scan(msg, &Logger::new("test"));
+    }
+
+    #[test]
+    fn receivable_scanner_credits_payments_from_smart_accounts_to_their_owners() {
+        init_test_logging();
+        let test_name = "receivable_scanner_credits_payments_from_smart_accounts_to_their_owners";
+        let now = SystemTime::now();
+        let more_money_received_params_arc = Arc::new(Mutex::new(vec![]));
+        let txn_inner_builder = TransactionInnerWrapperMockBuilder::default().commit_result(Ok(()));
+        let transaction = TransactionSafeWrapper::new_with_builder(txn_inner_builder);
+        let persistent_config =
+            PersistentConfigurationMock::new().set_start_block_from_txn_result(Ok(()));
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_received_params(&more_money_received_params_arc)
+            .more_money_received_result(transaction);
+        let smart_account = make_wallet("smart account");
+        let owner = make_wallet("owner");
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .persistent_configuration(persistent_config)
+            .smart_account_owners(HashMap::from([(smart_account.clone(), owner.clone())]))
+            .build();
+        let plain_payment = BlockchainTransaction {
+            block_number: 4578910,
+            from: make_wallet("plain wallet"),
+            wei_amount: 45_780,
+        };
+        // Two UserOperations of the same smart account bundled into one transaction
+        let bundled_payment_1 = BlockchainTransaction {
+            block_number: 4578911,
+            from: smart_account.clone(),
+            wei_amount: 3_333_345,
+        };
+        let bundled_payment_2 = BlockchainTransaction {
+            block_number: 4578911,
+            from: smart_account.clone(),
+            wei_amount: 1_000,
+        };
+        let msg = ReceivedPayments {
+            timestamp: now,
+            new_start_block: BlockMarker::Value(4578912),
+            response_skeleton_opt: None,
+            transactions: vec![
+                plain_payment.clone(),
+                bundled_payment_1.clone(),
+                bundled_payment_2.clone(),
+            ],
+            rechecked_opt: None,
+            pending: vec![],
+        };
+        subject.mark_as_started(SystemTime::now());
+
+        subject.finish_scan(msg, &Logger::new(test_name));
+
+        let more_money_received_params = more_money_received_params_arc.lock().unwrap();
+        assert_eq!(
+            *more_money_received_params,
+            vec![(
+                now,
+                vec![
+                    plain_payment,
+                    BlockchainTransaction {
+                        from: owner.clone(),
+                        ..bundled_payment_1
+                    },
+                    BlockchainTransaction {
+                        from: owner.clone(),
+                        ..bundled_payment_2
+                    },
+                ]
+            )]
+        );
+        assert_eq!(
+            subject
+                .financial_statistics
+                .borrow()
+                .total_paid_receivable_wei,
+            45_780 + 3_333_345 + 1_000
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Payment of 3,333,345 wei from smart account {} credited to its \
+             owner {}",
+            smart_account, owner
+        ));
+        log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Payment of 1,000 wei from smart account {} credited to its \
+             owner {}",
+            smart_account, owner
+        ));
+    }
+
     #[test]
     #[should_panic(
         expected = "entered unreachable code: Failed to get start_block while transactions were present"
@@ -3185,6 +4964,8 @@ mod tests {
             new_start_block: BlockMarker::Uninitialized,
             response_skeleton_opt: None,
             transactions: receivables,
+            rechecked_opt: None,
+            pending: vec![],
         };
         subject.mark_as_started(SystemTime::now());
 
@@ -3220,6 +5001,8 @@ mod tests {
             new_start_block: BlockMarker::Value(7890123),
             response_skeleton_opt: None,
             transactions: receivables,
+            rechecked_opt: None,
+            pending: vec![],
         };
         // Not necessary, rather for preciseness
         subject.mark_as_started(SystemTime::now());
@@ -3264,6 +5047,8 @@ mod tests {
             new_start_block: BlockMarker::Value(0),
             response_skeleton_opt: None,
             transactions: receivables,
+            rechecked_opt: None,
+            pending: vec![],
         };
         // Not necessary, rather for preciseness
         subject.mark_as_started(SystemTime::now());
@@ -3276,7 +5061,8 @@ mod tests {
         init_test_logging();
         let test_name = "signal_scanner_completion_and_log_if_timestamp_is_correct";
         let logger = Logger::new(test_name);
-        let mut subject = ScannerCommon::new(Rc::new(make_custom_payment_thresholds()));
+        let mut subject =
+            ScannerCommon::new(Rc::new(RefCell::new(make_custom_payment_thresholds())));
         let start = from_time_t(1_000_000_000);
         let end = start.checked_add(Duration::from_millis(145)).unwrap();
         subject.initiated_at_opt = Some(start);
@@ -3293,7 +5079,8 @@ mod tests {
         init_test_logging();
         let test_name = "signal_scanner_completion_and_log_if_timestamp_is_not_found";
         let logger = Logger::new(test_name);
-        let mut subject = ScannerCommon::new(Rc::new(make_custom_payment_thresholds()));
+        let mut subject =
+            ScannerCommon::new(Rc::new(RefCell::new(make_custom_payment_thresholds())));
         subject.initiated_at_opt = None;
 
         subject.signal_scanner_completion(ScanType::Receivables, SystemTime::now(), &logger);
@@ -0,0 +1,300 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::gwei_to_wei;
+use crate::sub_lib::accountant::PaymentBatching;
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::logger::Logger;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use thousands::Separable;
+
+// Holds back qualified debts too small to be worth a transaction of their own, until enough of
+// them accumulate or one of them can't wait any longer. A debt never waits longer than half of
+// the payment grace period of its creditor, which leaves the other half for the payment to get
+// confirmed before the creditor could find us delinquent.
+pub struct PaymentBatcher {
+    pub batching_opt: Option<PaymentBatching>,
+    held_back_since: HashMap<Wallet, SystemTime>,
+}
+
+impl PaymentBatcher {
+    pub fn new(batching_opt: Option<PaymentBatching>) -> Self {
+        Self {
+            batching_opt,
+            held_back_since: HashMap::new(),
+        }
+    }
+
+    pub fn release_payables(
+        &mut self,
+        qualified_payables: Vec<PayableAccount>,
+        grace_period_sec_of: &dyn Fn(&Wallet) -> u64,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> Vec<PayableAccount> {
+        let batching = match self.batching_opt {
+            Some(batching) => batching,
+            None => return qualified_payables,
+        };
+        let min_economic_size_wei: u128 = gwei_to_wei(batching.min_economic_size_gwei);
+        let is_small = |payable: &PayableAccount| payable.balance_wei < min_economic_size_wei;
+        let small_payables = qualified_payables
+            .iter()
+            .filter(|payable| is_small(payable))
+            .collect::<Vec<&PayableAccount>>();
+        self.held_back_since
+            .retain(|wallet, _| small_payables.iter().any(|p| &p.wallet == wallet));
+        if small_payables.is_empty() {
+            return qualified_payables;
+        }
+        small_payables.iter().for_each(|payable| {
+            self.held_back_since
+                .entry(payable.wallet.clone())
+                .or_insert(now);
+        });
+
+        let total_wei: u128 = small_payables.iter().map(|p| p.balance_wei).sum();
+        let batch_target_wei: u128 = gwei_to_wei(batching.batch_target_gwei);
+        let overdue_count = small_payables
+            .iter()
+            .filter(|payable| {
+                let waited_sec = now
+                    .duration_since(self.held_back_since[&payable.wallet])
+                    .unwrap_or(Duration::from_secs(0))
+                    .as_secs();
+                waited_sec >= batching.max_delay_sec
+                    || waited_sec >= grace_period_sec_of(&payable.wallet) / 2
+            })
+            .count();
+
+        if total_wei >= batch_target_wei || overdue_count > 0 {
+            info!(
+                logger,
+                "Releasing {} small debts totaling {} wei for payment ({} of them can't wait longer)",
+                small_payables.len(),
+                total_wei.separate_with_commas(),
+                overdue_count
+            );
+            self.held_back_since.clear();
+            qualified_payables
+        } else {
+            info!(
+                logger,
+                "Holding back {} debts below {} gwei; together they total {} wei out of the {} gwei \
+                 batch target",
+                small_payables.len(),
+                batching.min_economic_size_gwei.separate_with_commas(),
+                total_wei.separate_with_commas(),
+                batching.batch_target_gwei.separate_with_commas()
+            );
+            qualified_payables
+                .into_iter()
+                .filter(|payable| !is_small(payable))
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::gwei_to_wei;
+    use crate::accountant::scanners::payment_batcher::PaymentBatcher;
+    use crate::sub_lib::accountant::PaymentBatching;
+    use crate::sub_lib::wallet::Wallet;
+    use crate::test_utils::make_wallet;
+    use masq_lib::logger::Logger;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::{Duration, SystemTime};
+
+    const GRACE_PERIOD_SEC: u64 = 1200;
+
+    fn make_batcher() -> PaymentBatcher {
+        PaymentBatcher::new(Some(PaymentBatching {
+            min_economic_size_gwei: 1_000,
+            batch_target_gwei: 2_500,
+            max_delay_sec: 300,
+        }))
+    }
+
+    fn make_payable(name: &str, balance_gwei: u64) -> PayableAccount {
+        PayableAccount {
+            wallet: make_wallet(name),
+            balance_wei: gwei_to_wei(balance_gwei),
+            last_paid_timestamp: SystemTime::now(),
+            pending_payable_opt: None,
+        }
+    }
+
+    fn our_grace_period(_wallet: &Wallet) -> u64 {
+        GRACE_PERIOD_SEC
+    }
+
+    #[test]
+    fn without_batching_every_qualified_payable_is_released() {
+        let mut subject = PaymentBatcher::new(None);
+        let payables = vec![make_payable("small", 1), make_payable("large", 1_000_000)];
+
+        let result = subject.release_payables(
+            payables.clone(),
+            &our_grace_period,
+            SystemTime::now(),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result, payables);
+        assert!(subject.held_back_since.is_empty());
+    }
+
+    #[test]
+    fn small_payables_are_held_back_until_the_batch_target_is_reached() {
+        init_test_logging();
+        let test_name = "small_payables_are_held_back_until_the_batch_target_is_reached";
+        let logger = Logger::new(test_name);
+        let mut subject = make_batcher();
+        let now = SystemTime::now();
+        let small_1 = make_payable("small 1", 900);
+        let small_2 = make_payable("small 2", 800);
+        let large = make_payable("large", 1_000);
+
+        let first_result = subject.release_payables(
+            vec![small_1.clone(), large.clone()],
+            &our_grace_period,
+            now,
+            &logger,
+        );
+        let second_result = subject.release_payables(
+            vec![small_1.clone(), small_2.clone()],
+            &our_grace_period,
+            now + Duration::from_secs(60),
+            &logger,
+        );
+        let third_result = subject.release_payables(
+            vec![
+                small_1.clone(),
+                small_2.clone(),
+                make_payable("small 3", 900),
+            ],
+            &our_grace_period,
+            now + Duration::from_secs(120),
+            &logger,
+        );
+
+        assert_eq!(first_result, vec![large]);
+        assert_eq!(second_result, vec![]);
+        assert_eq!(third_result.len(), 3);
+        assert!(subject.held_back_since.is_empty());
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            &format!(
+                "INFO: {test_name}: Holding back 1 debts below 1,000 gwei; together they total \
+                 900,000,000,000 wei out of the 2,500 gwei batch target"
+            ),
+            &format!(
+                "INFO: {test_name}: Holding back 2 debts below 1,000 gwei; together they total \
+                 1,700,000,000,000 wei out of the 2,500 gwei batch target"
+            ),
+            &format!(
+                "INFO: {test_name}: Releasing 3 small debts totaling 2,600,000,000,000 wei for \
+                 payment (0 of them can't wait longer)"
+            ),
+        ]);
+    }
+
+    #[test]
+    fn small_payables_are_released_together_once_one_of_them_waited_the_max_delay() {
+        let mut subject = make_batcher();
+        let now = SystemTime::now();
+        let old_small = make_payable("old small", 100);
+        let new_small = make_payable("new small", 100);
+        let logger = Logger::new("test");
+        subject.release_payables(vec![old_small.clone()], &our_grace_period, now, &logger);
+
+        let result_before = subject.release_payables(
+            vec![old_small.clone(), new_small.clone()],
+            &our_grace_period,
+            now + Duration::from_secs(299),
+            &logger,
+        );
+        let result_after = subject.release_payables(
+            vec![old_small.clone(), new_small.clone()],
+            &our_grace_period,
+            now + Duration::from_secs(300),
+            &logger,
+        );
+
+        assert_eq!(result_before, vec![]);
+        assert_eq!(result_after, vec![old_small, new_small]);
+        assert!(subject.held_back_since.is_empty());
+    }
+
+    #[test]
+    fn small_payables_never_wait_beyond_half_of_the_grace_period_of_their_creditor() {
+        let mut subject = PaymentBatcher::new(Some(PaymentBatching {
+            min_economic_size_gwei: 1_000,
+            batch_target_gwei: 5_000,
+            max_delay_sec: 86_400,
+        }));
+        let now = SystemTime::now();
+        let impatient_creditor = make_payable("impatient", 100);
+        let grace_period_sec_of = |wallet: &Wallet| {
+            if wallet == &make_wallet("impatient") {
+                60
+            } else {
+                GRACE_PERIOD_SEC
+            }
+        };
+        let logger = Logger::new("test");
+        subject.release_payables(
+            vec![impatient_creditor.clone()],
+            &grace_period_sec_of,
+            now,
+            &logger,
+        );
+
+        let result_before = subject.release_payables(
+            vec![impatient_creditor.clone()],
+            &grace_period_sec_of,
+            now + Duration::from_secs(29),
+            &logger,
+        );
+        let result_after = subject.release_payables(
+            vec![impatient_creditor.clone()],
+            &grace_period_sec_of,
+            now + Duration::from_secs(30),
+            &logger,
+        );
+
+        assert_eq!(result_before, vec![]);
+        assert_eq!(result_after, vec![impatient_creditor]);
+    }
+
+    #[test]
+    fn payables_that_stop_qualifying_are_forgotten() {
+        let mut subject = make_batcher();
+        let now = SystemTime::now();
+        let small_1 = make_payable("small 1", 100);
+        let small_2 = make_payable("small 2", 100);
+        let logger = Logger::new("test");
+        subject.release_payables(
+            vec![small_1.clone(), small_2.clone()],
+            &our_grace_period,
+            now,
+            &logger,
+        );
+
+        let result = subject.release_payables(
+            vec![small_2.clone()],
+            &our_grace_period,
+            now + Duration::from_secs(10),
+            &logger,
+        );
+
+        assert_eq!(result, vec![]);
+        assert_eq!(
+            subject.held_back_since.keys().collect::<Vec<&Wallet>>(),
+            vec![&small_2.wallet]
+        );
+        assert_eq!(subject.held_back_since[&small_2.wallet], now);
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::sub_lib::accountant::PaymentThresholds;
+use crate::sub_lib::wallet::Wallet;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+// When the creditor could ban us for the debt, by the thresholds it is paid by
+pub fn payment_deadline(payable: &PayableAccount, thresholds: &PaymentThresholds) -> SystemTime {
+    payable.last_paid_timestamp
+        + Duration::from_secs(
+            thresholds.maturity_threshold_sec + thresholds.payment_grace_period_sec,
+        )
+}
+
+// How close the debts of the current payable scan are to getting us banned by their creditors.
+// The deadlines are known only while the debts are being qualified, long before the payments are
+// submitted, so they're kept here until then. A debt of an unknown deadline is never urgent.
+#[derive(Default)]
+pub struct PaymentUrgency {
+    deadlines: HashMap<Wallet, SystemTime>,
+}
+
+impl PaymentUrgency {
+    pub fn remember_deadlines(
+        &mut self,
+        qualified_payables: &[PayableAccount],
+        deadline_of: &dyn Fn(&PayableAccount) -> SystemTime,
+    ) {
+        self.deadlines = qualified_payables
+            .iter()
+            .map(|payable| (payable.wallet.clone(), deadline_of(payable)))
+            .collect()
+    }
+
+    pub fn sec_to_deadline_opt(&self, wallet: &Wallet, now: SystemTime) -> Option<u64> {
+        self.deadlines.get(wallet).map(|deadline| {
+            deadline
+                .duration_since(now)
+                .unwrap_or(Duration::from_secs(0))
+                .as_secs()
+        })
+    }
+
+    // Urgent are the debts within the margin of their deadlines; they come with the seconds left
+    pub fn classify(
+        &self,
+        accounts: Vec<PayableAccount>,
+        deadline_margin_sec: u64,
+        now: SystemTime,
+    ) -> (Vec<(PayableAccount, u64)>, Vec<PayableAccount>) {
+        let mut urgent = vec![];
+        let mut non_urgent = vec![];
+        accounts.into_iter().for_each(|account| {
+            match self.sec_to_deadline_opt(&account.wallet, now) {
+                Some(sec_to_deadline) if sec_to_deadline <= deadline_margin_sec => {
+                    urgent.push((account, sec_to_deadline))
+                }
+                _ => non_urgent.push(account),
+            }
+        });
+        (urgent, non_urgent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::scanners::payment_urgency::{payment_deadline, PaymentUrgency};
+    use crate::sub_lib::accountant::PaymentThresholds;
+    use crate::test_utils::make_wallet;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn make_payable(name: &str) -> PayableAccount {
+        PayableAccount {
+            wallet: make_wallet(name),
+            balance_wei: 1_000,
+            last_paid_timestamp: UNIX_EPOCH,
+            pending_payable_opt: None,
+        }
+    }
+
+    #[test]
+    fn debts_are_classified_by_the_time_left_to_their_deadlines() {
+        let now = SystemTime::now();
+        let mut subject = PaymentUrgency::default();
+        let deadline_of = |payable: &PayableAccount| {
+            if payable.wallet == make_wallet("urgent") {
+                now + Duration::from_secs(600)
+            } else if payable.wallet == make_wallet("overdue") {
+                now - Duration::from_secs(600)
+            } else {
+                now + Duration::from_secs(601)
+            }
+        };
+        let accounts = vec![
+            make_payable("urgent"),
+            make_payable("patient"),
+            make_payable("overdue"),
+        ];
+        subject.remember_deadlines(&accounts, &deadline_of);
+        let accounts = vec![
+            make_payable("urgent"),
+            make_payable("patient"),
+            make_payable("overdue"),
+            make_payable("unknown"),
+        ];
+
+        let (urgent, non_urgent) = subject.classify(accounts, 600, now);
+
+        assert_eq!(
+            urgent,
+            vec![(make_payable("urgent"), 600), (make_payable("overdue"), 0)]
+        );
+        assert_eq!(
+            non_urgent,
+            vec![make_payable("patient"), make_payable("unknown")]
+        );
+    }
+
+    #[test]
+    fn remembered_deadlines_replace_those_of_the_previous_scan() {
+        let now = SystemTime::now();
+        let mut subject = PaymentUrgency::default();
+        subject.remember_deadlines(&[make_payable("first")], &|_| now);
+
+        subject.remember_deadlines(&[make_payable("second")], &|_| now);
+
+        assert_eq!(
+            subject.sec_to_deadline_opt(&make_wallet("first"), now),
+            None
+        );
+        assert_eq!(
+            subject.sec_to_deadline_opt(&make_wallet("second"), now),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn payment_deadline_follows_maturity_and_grace_period() {
+        let thresholds = PaymentThresholds {
+            maturity_threshold_sec: 1_000,
+            payment_grace_period_sec: 234,
+            ..PaymentThresholds::default()
+        };
+
+        let result = payment_deadline(&make_payable("creditor"), &thresholds);
+
+        assert_eq!(result, UNIX_EPOCH + Duration::from_secs(1_234));
+    }
+}
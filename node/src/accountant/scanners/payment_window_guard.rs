@@ -0,0 +1,251 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::scanners::payment_urgency::PaymentUrgency;
+use crate::sub_lib::accountant::PaymentWindow;
+use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+use crate::sub_lib::wei::Wei;
+use masq_lib::logger::Logger;
+use std::time::SystemTime;
+use thousands::Separable;
+use time::OffsetDateTime;
+
+// Keeps payable batches from being submitted outside the payment window. The scan qualifies and
+// analyzes the debts as usual; only at the submission are those that can wait dropped, so that
+// they stay unpaid in the database and come up again in a later scan. A low gas price opens the
+// window for the whole batch, a deadline close at hand only for the debt it concerns.
+pub struct PaymentWindowGuard {
+    pub window_opt: Option<PaymentWindow>,
+}
+
+impl PaymentWindowGuard {
+    pub fn new(window_opt: Option<PaymentWindow>) -> Self {
+        Self { window_opt }
+    }
+
+    pub fn confine(
+        &self,
+        instructions: OutboundPaymentsInstructions,
+        urgency: &PaymentUrgency,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> OutboundPaymentsInstructions {
+        let window = match self.window_opt {
+            Some(window) => window,
+            None => return instructions,
+        };
+        if window.contains_hour(OffsetDateTime::from(now).hour()) {
+            return instructions;
+        }
+        let gas_price = instructions.agent.agreed_fee_per_computation_unit();
+        if window.low_gas_price_gwei > 0 && gas_price <= Wei::from_gwei(window.low_gas_price_gwei) {
+            info!(
+                logger,
+                "Paying outside the payment window because the gas price of {} is low", gas_price
+            );
+            return instructions;
+        }
+        let (urgent, deferred) = urgency.classify(
+            instructions.affordable_accounts,
+            window.deadline_margin_sec,
+            now,
+        );
+        let released = urgent
+            .into_iter()
+            .map(|(account, sec_to_deadline)| {
+                warning!(
+                    logger,
+                    "Paying {} outside the payment window; its payment deadline is only {} sec \
+                     away",
+                    account.wallet,
+                    sec_to_deadline
+                );
+                account
+            })
+            .collect();
+        if !deferred.is_empty() {
+            info!(
+                logger,
+                "Outside the payment window {:02}:00-{:02}:00 UTC; {} payments of {} wei in total \
+                 deferred to later scans",
+                window.start_hour,
+                window.end_hour,
+                deferred.len(),
+                deferred
+                    .iter()
+                    .map(|account| account.balance_wei)
+                    .sum::<u128>()
+                    .separate_with_commas()
+            )
+        }
+        OutboundPaymentsInstructions {
+            affordable_accounts: released,
+            ..instructions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::accountant::scanners::payment_urgency::PaymentUrgency;
+    use crate::accountant::scanners::payment_window_guard::PaymentWindowGuard;
+    use crate::sub_lib::accountant::PaymentWindow;
+    use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+    use crate::sub_lib::wei::Wei;
+    use crate::test_utils::make_wallet;
+    use masq_lib::logger::Logger;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    // Both in the UTC day of 1970-01-02
+    const THREE_AM: u64 = 86_400 + 3 * 3_600;
+    const NOON: u64 = 86_400 + 12 * 3_600;
+
+    fn at(secs_since_epoch: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs_since_epoch)
+    }
+
+    fn make_window(low_gas_price_gwei: u64) -> PaymentWindow {
+        PaymentWindow {
+            start_hour: 2,
+            end_hour: 6,
+            low_gas_price_gwei,
+            deadline_margin_sec: 3_600,
+        }
+    }
+
+    fn make_payables() -> Vec<PayableAccount> {
+        ["urgent", "patient"]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, name)| PayableAccount {
+                wallet: make_wallet(name),
+                balance_wei: 1_000_000 * (idx as u128 + 1),
+                last_paid_timestamp: at(0),
+                pending_payable_opt: None,
+            })
+            .collect()
+    }
+
+    fn make_instructions(gas_price_gwei: u64) -> OutboundPaymentsInstructions {
+        let agent = BlockchainAgentMock::default()
+            .agreed_fee_per_computation_unit_result(Wei::from_gwei(gas_price_gwei));
+        OutboundPaymentsInstructions::new(make_payables(), Box::new(agent), None)
+    }
+
+    fn make_urgency() -> PaymentUrgency {
+        let mut urgency = PaymentUrgency::default();
+        let deadline_of = |payable: &PayableAccount| {
+            if payable.wallet == make_wallet("urgent") {
+                at(NOON + 1_800)
+            } else {
+                at(NOON + 86_400)
+            }
+        };
+        urgency.remember_deadlines(&make_payables(), &deadline_of);
+        urgency
+    }
+
+    fn paid_wallets(instructions: &OutboundPaymentsInstructions) -> Vec<String> {
+        instructions
+            .affordable_accounts
+            .iter()
+            .map(|account| account.wallet.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn without_a_window_everything_is_paid_at_any_time() {
+        let subject = PaymentWindowGuard::new(None);
+
+        let result = subject.confine(
+            make_instructions(100),
+            &make_urgency(),
+            at(NOON),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result.affordable_accounts, make_payables());
+    }
+
+    #[test]
+    fn within_the_window_everything_is_paid() {
+        let subject = PaymentWindowGuard::new(Some(make_window(0)));
+
+        let result = subject.confine(
+            make_instructions(100),
+            &make_urgency(),
+            at(THREE_AM),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(result.affordable_accounts, make_payables());
+    }
+
+    #[test]
+    fn outside_the_window_only_debts_close_to_their_deadlines_are_paid() {
+        init_test_logging();
+        let test_name = "outside_the_window_only_debts_close_to_their_deadlines_are_paid";
+        let subject = PaymentWindowGuard::new(Some(make_window(0)));
+
+        let result = subject.confine(
+            make_instructions(100),
+            &make_urgency(),
+            at(NOON),
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(
+            paid_wallets(&result),
+            vec![make_wallet("urgent").to_string()]
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Paying {} outside the payment window; its payment deadline is \
+             only 1800 sec away",
+            make_wallet("urgent")
+        ));
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Outside the payment window 02:00-06:00 UTC; 1 payments of \
+             2,000,000 wei in total deferred to later scans"
+        ));
+    }
+
+    #[test]
+    fn outside_the_window_a_low_gas_price_lets_everything_be_paid() {
+        init_test_logging();
+        let test_name = "outside_the_window_a_low_gas_price_lets_everything_be_paid";
+        let subject = PaymentWindowGuard::new(Some(make_window(2)));
+
+        let result = subject.confine(
+            make_instructions(2),
+            &make_urgency(),
+            at(NOON),
+            &Logger::new(test_name),
+        );
+
+        assert_eq!(result.affordable_accounts, make_payables());
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Paying outside the payment window because the gas price of \
+             2,000,000,000 wei is low"
+        ));
+    }
+
+    #[test]
+    fn outside_the_window_a_gas_price_above_the_low_one_does_not_help() {
+        let subject = PaymentWindowGuard::new(Some(make_window(2)));
+
+        let result = subject.confine(
+            make_instructions(3),
+            &make_urgency(),
+            at(NOON),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(
+            paid_wallets(&result),
+            vec![make_wallet("urgent").to_string()]
+        );
+    }
+}
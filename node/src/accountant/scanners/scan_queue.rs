@@ -0,0 +1,83 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use masq_lib::messages::ScanType;
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+// Where a scanner stands with the triggers of its scan
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScanState {
+    Idle,
+    Running(SystemTime),
+    // Another scan is to begin once the running one has ended
+    Queued(SystemTime),
+}
+
+// Scans of one type never run side by side. A trigger arriving while a scan runs has another one
+// queued to follow it, and the triggers arriving before that one has begun are coalesced into it
+#[derive(Default, Debug)]
+pub struct ScanQueue {
+    queued: HashSet<ScanType>,
+}
+
+impl ScanQueue {
+    pub fn state(&self, scan_type: ScanType, started_at_opt: Option<SystemTime>) -> ScanState {
+        match started_at_opt {
+            None => ScanState::Idle,
+            Some(started_at) if self.queued.contains(&scan_type) => ScanState::Queued(started_at),
+            Some(started_at) => ScanState::Running(started_at),
+        }
+    }
+
+    // Tells whether a scan was queued, rather than the trigger coalesced into one queued before
+    pub fn enqueue(&mut self, scan_type: ScanType) -> bool {
+        self.queued.insert(scan_type)
+    }
+
+    pub fn dequeue(&mut self, scan_type: ScanType) -> bool {
+        self.queued.remove(&scan_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::scanners::scan_queue::{ScanQueue, ScanState};
+    use masq_lib::messages::ScanType;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn scan_is_idle_until_started_and_running_until_another_is_queued() {
+        let started_at = SystemTime::now() - Duration::from_secs(5);
+        let mut subject = ScanQueue::default();
+
+        let idle = subject.state(ScanType::Payables, None);
+        let running = subject.state(ScanType::Payables, Some(started_at));
+        let queued_now = subject.enqueue(ScanType::Payables);
+        let queued = subject.state(ScanType::Payables, Some(started_at));
+        let other_type = subject.state(ScanType::Receivables, Some(started_at));
+
+        assert_eq!(idle, ScanState::Idle);
+        assert_eq!(running, ScanState::Running(started_at));
+        assert_eq!(queued_now, true);
+        assert_eq!(queued, ScanState::Queued(started_at));
+        assert_eq!(other_type, ScanState::Running(started_at));
+    }
+
+    #[test]
+    fn triggers_arriving_while_a_scan_is_queued_are_coalesced_into_it() {
+        let mut subject = ScanQueue::default();
+        subject.enqueue(ScanType::PendingPayables);
+
+        let queued_again = subject.enqueue(ScanType::PendingPayables);
+        let first_dequeue = subject.dequeue(ScanType::PendingPayables);
+        let second_dequeue = subject.dequeue(ScanType::PendingPayables);
+
+        assert_eq!(queued_again, false);
+        assert_eq!(first_dequeue, true);
+        assert_eq!(second_dequeue, false);
+        assert_eq!(
+            subject.state(ScanType::PendingPayables, Some(SystemTime::UNIX_EPOCH)),
+            ScanState::Running(SystemTime::UNIX_EPOCH)
+        );
+    }
+}
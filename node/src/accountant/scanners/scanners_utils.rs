@@ -319,6 +319,8 @@ pub mod pending_payable_scanner_utils {
         pub still_pending: Vec<PendingPayableId>,
         pub failures: Vec<PendingPayableId>,
         pub confirmed: Vec<PendingPayableFingerprint>,
+        // Pending for too long to be waited for anymore
+        pub abandoned: Vec<PendingPayableFingerprint>,
     }
 
     pub fn elapsed_in_ms(timestamp: SystemTime) -> u128 {
@@ -348,18 +350,16 @@ pub mod pending_payable_scanner_utils {
             .expect("we should be older now");
         let elapsed = elapsed.as_secs();
         if elapsed > max_pending_interval {
-            error!(
+            warning!(
                 logger,
                 "Pending transaction {:?} has exceeded the maximum pending time \
-                ({}sec) with the age {}sec and the confirmation process is going to be aborted now \
-                at the final attempt {}; manual resolution is required from the \
-                user to complete the transaction.",
+                ({}sec) with the age {}sec and is going to be abandoned at the final attempt {}",
                 fingerprint.hash,
                 max_pending_interval,
                 elapsed,
                 fingerprint.attempt
             );
-            scan_report.failures.push(fingerprint.into())
+            scan_report.abandoned.push(fingerprint)
         } else {
             scan_report.still_pending.push(fingerprint.into())
         }
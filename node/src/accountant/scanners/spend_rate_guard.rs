@@ -0,0 +1,355 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+use crate::accountant::gwei_to_wei;
+use crate::sub_lib::accountant::SpendRateLimits;
+use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+use masq_lib::logger::Logger;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+const HOUR: Duration = Duration::from_secs(3_600);
+const DAY: Duration = Duration::from_secs(86_400);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SpendRateDeferral {
+    pub deferred_payments: usize,
+    pub deferred_wei: u128,
+    pub service_fee_allowance_wei: u128,
+    pub transaction_fee_allowance_wei: u128,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Spending {
+    timestamp: SystemTime,
+    service_fee_wei: u128,
+    transaction_fee_wei: u128,
+}
+
+// Last stop of the payable scan before the payments go to the BlockchainBridge. Those that would
+// take the spending of the past hour or day over a limit are dropped; their debts stay unpaid in
+// the database, and so come up again in the next scan. Spending is counted when the payments are
+// handed over, even if some of them fail later, and isn't remembered across restarts.
+pub struct SpendRateGuard {
+    pub limits_opt: Option<SpendRateLimits>,
+    spendings: VecDeque<Spending>,
+}
+
+impl SpendRateGuard {
+    pub fn new(limits_opt: Option<SpendRateLimits>) -> Self {
+        Self {
+            limits_opt,
+            spendings: VecDeque::new(),
+        }
+    }
+
+    pub fn limit(
+        &mut self,
+        instructions: OutboundPaymentsInstructions,
+        now: SystemTime,
+        logger: &Logger,
+    ) -> (OutboundPaymentsInstructions, Option<SpendRateDeferral>) {
+        let limits = match self.limits_opt {
+            Some(limits) => limits,
+            None => return (instructions, None),
+        };
+        self.forget_spendings_older_than_a_day(now);
+        let service_fee_allowance_wei = self.allowance(
+            limits.service_fee_per_hour_gwei,
+            limits.service_fee_per_day_gwei,
+            |spending| spending.service_fee_wei,
+            now,
+        );
+        let transaction_fee_allowance_wei = self.allowance(
+            limits.transaction_fee_per_hour_gwei,
+            limits.transaction_fee_per_day_gwei,
+            |spending| spending.transaction_fee_wei,
+            now,
+        );
+
+        let mut released: Vec<PayableAccount> = vec![];
+        let mut deferred: Vec<PayableAccount> = vec![];
+        let mut service_fee_wei: u128 = 0;
+        instructions
+            .affordable_accounts
+            .into_iter()
+            .for_each(|account| {
+                let fits = service_fee_wei.saturating_add(account.balance_wei)
+                    <= service_fee_allowance_wei
+                    && instructions
+                        .agent
+                        .estimated_transaction_fee_total(released.len() + 1)
+                        .as_u128()
+                        <= transaction_fee_allowance_wei;
+                if fits {
+                    service_fee_wei += account.balance_wei;
+                    released.push(account)
+                } else {
+                    deferred.push(account)
+                }
+            });
+        if !released.is_empty() {
+            self.spendings.push_back(Spending {
+                timestamp: now,
+                service_fee_wei,
+                transaction_fee_wei: instructions
+                    .agent
+                    .estimated_transaction_fee_total(released.len())
+                    .as_u128(),
+            })
+        }
+        let deferral_opt = (!deferred.is_empty()).then(|| SpendRateDeferral {
+            deferred_payments: deferred.len(),
+            deferred_wei: deferred.iter().map(|account| account.balance_wei).sum(),
+            service_fee_allowance_wei,
+            transaction_fee_allowance_wei,
+        });
+        if let Some(deferral) = deferral_opt {
+            warning!(
+                logger,
+                "Spend rate limits reached; payments deferred to later scans: \
+                 deferred_payments={} deferred_wei={} service_fee_allowance_wei={} \
+                 transaction_fee_allowance_wei={}",
+                deferral.deferred_payments,
+                deferral.deferred_wei,
+                deferral.service_fee_allowance_wei,
+                deferral.transaction_fee_allowance_wei
+            )
+        }
+        (
+            OutboundPaymentsInstructions {
+                affordable_accounts: released,
+                ..instructions
+            },
+            deferral_opt,
+        )
+    }
+
+    fn allowance(
+        &self,
+        per_hour_gwei: u64,
+        per_day_gwei: u64,
+        fee_of: fn(&Spending) -> u128,
+        now: SystemTime,
+    ) -> u128 {
+        let spent_within = |period: Duration| -> u128 {
+            self.spendings
+                .iter()
+                .filter(|spending| {
+                    now.duration_since(spending.timestamp)
+                        .map(|age| age < period)
+                        .unwrap_or(true)
+                })
+                .map(fee_of)
+                .sum()
+        };
+        let hourly_allowance_wei =
+            gwei_to_wei::<u128, u64>(per_hour_gwei).saturating_sub(spent_within(HOUR));
+        let daily_allowance_wei =
+            gwei_to_wei::<u128, u64>(per_day_gwei).saturating_sub(spent_within(DAY));
+        hourly_allowance_wei.min(daily_allowance_wei)
+    }
+
+    fn forget_spendings_older_than_a_day(&mut self, now: SystemTime) {
+        self.spendings.retain(|spending| {
+            now.duration_since(spending.timestamp)
+                .map(|age| age < DAY)
+                .unwrap_or(true)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accountant::db_access_objects::payable_dao::PayableAccount;
+    use crate::accountant::gwei_to_wei;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::agent_web3::{
+        BlockchainAgentWeb3, WEB3_MAXIMAL_GAS_LIMIT_MARGIN,
+    };
+    use crate::accountant::scanners::spend_rate_guard::{SpendRateDeferral, SpendRateGuard};
+    use crate::sub_lib::accountant::SpendRateLimits;
+    use crate::sub_lib::blockchain_bridge::{
+        ConsumingWalletBalances, OutboundPaymentsInstructions,
+    };
+    use crate::sub_lib::wei::Wei;
+    use crate::test_utils::make_wallet;
+    use masq_lib::blockchains::chains::Chain;
+    use masq_lib::logger::Logger;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::{Duration, SystemTime};
+    use web3::types::U256;
+
+    // Each transaction costs 100,000 gwei in gas
+    const TRANSACTION_FEE_GWEI: u64 = 100_000;
+
+    fn make_instructions(balances_gwei: &[u64]) -> OutboundPaymentsInstructions {
+        let agent = BlockchainAgentWeb3::new(
+            Wei::from_gwei(1),
+            100_000 - WEB3_MAXIMAL_GAS_LIMIT_MARGIN,
+            make_wallet("consuming"),
+            ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(u128::MAX),
+                masq_token_balance_in_minor_units: U256::from(u128::MAX),
+            },
+            None,
+            Chain::BaseSepolia,
+        );
+        let accounts = balances_gwei
+            .iter()
+            .enumerate()
+            .map(|(idx, balance_gwei)| PayableAccount {
+                wallet: make_wallet(&format!("creditor {}", idx)),
+                balance_wei: gwei_to_wei(*balance_gwei),
+                last_paid_timestamp: SystemTime::now(),
+                pending_payable_opt: None,
+            })
+            .collect();
+        OutboundPaymentsInstructions::new(accounts, Box::new(agent), None)
+    }
+
+    fn released_balances_gwei(instructions: &OutboundPaymentsInstructions) -> Vec<u64> {
+        instructions
+            .affordable_accounts
+            .iter()
+            .map(|account| (account.balance_wei / gwei_to_wei::<u128, u64>(1)) as u64)
+            .collect()
+    }
+
+    fn make_limits(service_fee_gwei: u64, transaction_fee_gwei: u64) -> SpendRateLimits {
+        SpendRateLimits {
+            service_fee_per_hour_gwei: service_fee_gwei,
+            service_fee_per_day_gwei: service_fee_gwei * 10,
+            transaction_fee_per_hour_gwei: transaction_fee_gwei,
+            transaction_fee_per_day_gwei: transaction_fee_gwei * 10,
+        }
+    }
+
+    #[test]
+    fn without_limits_every_payment_is_released() {
+        let mut subject = SpendRateGuard::new(None);
+        let instructions = make_instructions(&[5_000, u64::MAX]);
+
+        let (result, deferral_opt) =
+            subject.limit(instructions, SystemTime::now(), &Logger::new("test"));
+
+        assert_eq!(released_balances_gwei(&result), vec![5_000, u64::MAX]);
+        assert_eq!(deferral_opt, None);
+        assert!(subject.spendings.is_empty());
+    }
+
+    #[test]
+    fn payments_within_the_limits_are_all_released() {
+        let mut subject = SpendRateGuard::new(Some(make_limits(10_000, 3 * TRANSACTION_FEE_GWEI)));
+        let instructions = make_instructions(&[4_000, 6_000, 0]);
+
+        let (result, deferral_opt) =
+            subject.limit(instructions, SystemTime::now(), &Logger::new("test"));
+
+        assert_eq!(released_balances_gwei(&result), vec![4_000, 6_000, 0]);
+        assert_eq!(deferral_opt, None);
+    }
+
+    #[test]
+    fn payments_over_the_service_fee_limit_are_deferred() {
+        init_test_logging();
+        let test_name = "payments_over_the_service_fee_limit_are_deferred";
+        let mut subject = SpendRateGuard::new(Some(make_limits(10_000, 10 * TRANSACTION_FEE_GWEI)));
+        let instructions = make_instructions(&[4_000, 7_000, 5_000, 2_000]);
+
+        let (result, deferral_opt) =
+            subject.limit(instructions, SystemTime::now(), &Logger::new(test_name));
+
+        assert_eq!(released_balances_gwei(&result), vec![4_000, 5_000]);
+        assert_eq!(
+            deferral_opt,
+            Some(SpendRateDeferral {
+                deferred_payments: 2,
+                deferred_wei: gwei_to_wei(9_000_u64),
+                service_fee_allowance_wei: gwei_to_wei(10_000_u64),
+                transaction_fee_allowance_wei: gwei_to_wei(10 * TRANSACTION_FEE_GWEI),
+            })
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Spend rate limits reached; payments deferred to later scans: \
+             deferred_payments=2 deferred_wei=9000000000000 service_fee_allowance_wei=10000000000000 \
+             transaction_fee_allowance_wei=1000000000000000"
+        ));
+    }
+
+    #[test]
+    fn payments_over_the_transaction_fee_limit_are_deferred() {
+        let mut subject = SpendRateGuard::new(Some(make_limits(
+            u64::MAX / 10,
+            2 * TRANSACTION_FEE_GWEI + 1,
+        )));
+        let instructions = make_instructions(&[1_000, 2_000, 3_000]);
+
+        let (result, deferral_opt) =
+            subject.limit(instructions, SystemTime::now(), &Logger::new("test"));
+
+        assert_eq!(released_balances_gwei(&result), vec![1_000, 2_000]);
+        assert_eq!(
+            deferral_opt.map(|deferral| (deferral.deferred_payments, deferral.deferred_wei)),
+            Some((1, gwei_to_wei(3_000_u64)))
+        );
+    }
+
+    #[test]
+    fn spending_of_the_past_hour_and_day_counts_against_the_limits() {
+        let mut subject = SpendRateGuard::new(Some(SpendRateLimits {
+            service_fee_per_hour_gwei: 10_000,
+            service_fee_per_day_gwei: 25_000,
+            transaction_fee_per_hour_gwei: 10 * TRANSACTION_FEE_GWEI,
+            transaction_fee_per_day_gwei: 10 * TRANSACTION_FEE_GWEI,
+        }));
+        let now = SystemTime::now();
+        let logger = Logger::new("test");
+        let _ = subject.limit(
+            make_instructions(&[9_000]),
+            now - Duration::from_secs(86_400),
+            &logger,
+        );
+        let _ = subject.limit(
+            make_instructions(&[10_000]),
+            now - Duration::from_secs(7_200),
+            &logger,
+        );
+        let _ = subject.limit(
+            make_instructions(&[6_000]),
+            now - Duration::from_secs(1_800),
+            &logger,
+        );
+
+        let (result, deferral_opt) =
+            subject.limit(make_instructions(&[5_000, 4_000]), now, &logger);
+
+        // 4,000 gwei left for the hour, 9,000 for the day; the oldest spending is forgotten
+        assert_eq!(released_balances_gwei(&result), vec![4_000]);
+        assert_eq!(
+            deferral_opt,
+            Some(SpendRateDeferral {
+                deferred_payments: 1,
+                deferred_wei: gwei_to_wei(5_000_u64),
+                service_fee_allowance_wei: gwei_to_wei(4_000_u64),
+                transaction_fee_allowance_wei: gwei_to_wei(8 * TRANSACTION_FEE_GWEI),
+            })
+        );
+        assert_eq!(subject.spendings.len(), 3);
+    }
+
+    #[test]
+    fn nothing_is_recorded_when_every_payment_is_deferred() {
+        let mut subject = SpendRateGuard::new(Some(make_limits(1_000, 10 * TRANSACTION_FEE_GWEI)));
+        let instructions = make_instructions(&[5_000]);
+
+        let (result, deferral_opt) =
+            subject.limit(instructions, SystemTime::now(), &Logger::new("test"));
+
+        assert!(result.affordable_accounts.is_empty());
+        assert_eq!(
+            deferral_opt.map(|deferral| deferral.deferred_payments),
+            Some(1)
+        );
+        assert!(subject.spendings.is_empty());
+    }
+}
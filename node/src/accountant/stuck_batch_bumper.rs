@@ -0,0 +1,303 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::ReportTransactionReceipts;
+use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionReceiptResult, TxStatus};
+use crate::sub_lib::accountant::GasBumpLadder;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::time::SystemTime;
+
+// Walks the transactions of a batch stuck in the mempool up the gas bump ladder, all of them one
+// rung at a time and in nonce order, so that no transaction of the batch outbids one with a lower
+// nonce it has to wait for. A batch is known by the timestamp its transactions share. Once any
+// of its transactions gets a receipt, the blockchain is moving again and the batch climbs no more.
+pub struct StuckBatchBumper {
+    ladder_opt: Option<GasBumpLadder>,
+    when_pending_too_long_sec: u64,
+    rungs_climbed: HashMap<SystemTime, u8>,
+    off_the_ladder: HashSet<SystemTime>,
+}
+
+impl StuckBatchBumper {
+    pub fn new(ladder_opt: Option<GasBumpLadder>, when_pending_too_long_sec: u64) -> Self {
+        Self {
+            ladder_opt,
+            when_pending_too_long_sec,
+            rungs_climbed: HashMap::new(),
+            off_the_ladder: HashSet::new(),
+        }
+    }
+
+    pub fn step_percent(&self) -> Option<u8> {
+        self.ladder_opt.map(|ladder| ladder.step_percent)
+    }
+
+    // Yields the transactions due to be outbid by one more step, ordered by their nonces
+    pub fn climb(
+        &mut self,
+        msg: &ReportTransactionReceipts,
+        now: SystemTime,
+    ) -> Vec<PendingPayableFingerprint> {
+        let ladder = match self.ladder_opt {
+            Some(ladder) => ladder,
+            None => return vec![],
+        };
+        let batches = msg
+            .fingerprints_with_receipts
+            .iter()
+            .into_group_map_by(|(_, fingerprint)| fingerprint.timestamp);
+        let mut due = batches
+            .iter()
+            .flat_map(|(timestamp, members)| self.climb_batch(ladder, *timestamp, members, now))
+            .collect_vec();
+        // Batches that are gone from the report have been settled one way or another
+        self.rungs_climbed
+            .retain(|timestamp, _| batches.contains_key(timestamp));
+        self.off_the_ladder
+            .retain(|timestamp| batches.contains_key(timestamp));
+        due.sort_by_key(|fingerprint| {
+            fingerprint
+                .replacement_basis_opt
+                .map(|basis| basis.nonce)
+                .unwrap_or_default()
+        });
+        due
+    }
+
+    fn climb_batch(
+        &mut self,
+        ladder: GasBumpLadder,
+        timestamp: SystemTime,
+        members: &[&(TransactionReceiptResult, PendingPayableFingerprint)],
+        now: SystemTime,
+    ) -> Vec<PendingPayableFingerprint> {
+        if self.off_the_ladder.contains(&timestamp) {
+            return vec![];
+        }
+        if members.iter().any(|(receipt, _)| has_landed(receipt)) {
+            self.rungs_climbed.remove(&timestamp);
+            self.off_the_ladder.insert(timestamp);
+            return vec![];
+        }
+        let elapsed_sec = match now.duration_since(timestamp) {
+            Ok(elapsed) => elapsed.as_secs(),
+            Err(_) => return vec![],
+        };
+        // Past this age the batch is abandoned rather than outbid
+        if elapsed_sec > self.when_pending_too_long_sec {
+            return vec![];
+        }
+        let rungs_due = (elapsed_sec / ladder.interval_sec).min(ladder.max_steps as u64) as u8;
+        let rungs_climbed = self.rungs_climbed.entry(timestamp).or_insert(0);
+        if rungs_due <= *rungs_climbed {
+            return vec![];
+        }
+        let stuck = members
+            .iter()
+            .filter(|(receipt, fingerprint)| {
+                is_pending(receipt) && fingerprint.replacement_basis_opt.is_some()
+            })
+            .map(|(_, fingerprint)| fingerprint.clone())
+            .collect_vec();
+        if !stuck.is_empty() {
+            *rungs_climbed += 1
+        }
+        stuck
+    }
+}
+
+fn has_landed(receipt: &TransactionReceiptResult) -> bool {
+    matches!(
+        receipt,
+        TransactionReceiptResult::RpcResponse(tx_receipt)
+            if tx_receipt.status != TxStatus::Pending
+    )
+}
+
+fn is_pending(receipt: &TransactionReceiptResult) -> bool {
+    matches!(
+        receipt,
+        TransactionReceiptResult::RpcResponse(tx_receipt) if tx_receipt.status == TxStatus::Pending
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::test_utils::make_pending_payable_fingerprint;
+    use crate::blockchain::blockchain_bridge::ReplacementBasis;
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionBlock, TxReceipt};
+    use crate::blockchain::test_utils::make_tx_hash;
+    use std::time::Duration;
+    use web3::types::U64;
+
+    const LADDER: GasBumpLadder = GasBumpLadder {
+        step_percent: 15,
+        max_steps: 2,
+        interval_sec: 600,
+    };
+
+    fn make_fingerprint(timestamp: SystemTime, nonce: u64) -> PendingPayableFingerprint {
+        PendingPayableFingerprint {
+            rowid: nonce + 1,
+            timestamp,
+            hash: make_tx_hash(nonce as u32 + 100),
+            replacement_basis_opt: Some(ReplacementBasis {
+                nonce,
+                gas_price_wei: 1_000_000_000,
+            }),
+            ..make_pending_payable_fingerprint()
+        }
+    }
+
+    fn with_status(
+        status: TxStatus,
+        fingerprint: &PendingPayableFingerprint,
+    ) -> (TransactionReceiptResult, PendingPayableFingerprint) {
+        (
+            TransactionReceiptResult::RpcResponse(TxReceipt {
+                transaction_hash: fingerprint.hash,
+                status,
+            }),
+            fingerprint.clone(),
+        )
+    }
+
+    fn report(
+        fingerprints_with_receipts: Vec<(TransactionReceiptResult, PendingPayableFingerprint)>,
+    ) -> ReportTransactionReceipts {
+        ReportTransactionReceipts {
+            fingerprints_with_receipts,
+            response_skeleton_opt: None,
+        }
+    }
+
+    #[test]
+    fn without_a_ladder_nothing_climbs() {
+        let batch_timestamp = SystemTime::now() - Duration::from_secs(3_000);
+        let fingerprint = make_fingerprint(batch_timestamp, 5);
+        let mut subject = StuckBatchBumper::new(None, 21_600);
+
+        let result = subject.climb(
+            &report(vec![with_status(TxStatus::Pending, &fingerprint)]),
+            SystemTime::now(),
+        );
+
+        assert_eq!(result, vec![]);
+        assert_eq!(subject.step_percent(), None);
+    }
+
+    #[test]
+    fn stuck_batch_climbs_one_rung_per_interval_in_nonce_order_up_to_the_top() {
+        let batch_timestamp = SystemTime::now();
+        let first = make_fingerprint(batch_timestamp, 7);
+        let second = make_fingerprint(batch_timestamp, 8);
+        let third = make_fingerprint(batch_timestamp, 9);
+        let msg = report(vec![
+            with_status(TxStatus::Pending, &third),
+            with_status(TxStatus::Pending, &first),
+            with_status(TxStatus::Pending, &second),
+        ]);
+        let mut subject = StuckBatchBumper::new(Some(LADDER), 21_600);
+        let at = |sec: u64| batch_timestamp + Duration::from_secs(sec);
+
+        let before_first_interval = subject.climb(&msg, at(599));
+        let first_rung = subject.climb(&msg, at(600));
+        let same_interval_again = subject.climb(&msg, at(1_199));
+        let second_rung = subject.climb(&msg, at(1_200));
+        let above_the_top = subject.climb(&msg, at(5_000));
+
+        let whole_batch = vec![first, second, third];
+        assert_eq!(before_first_interval, vec![]);
+        assert_eq!(first_rung, whole_batch);
+        assert_eq!(same_interval_again, vec![]);
+        assert_eq!(second_rung, whole_batch);
+        assert_eq!(above_the_top, vec![]);
+        assert_eq!(subject.step_percent(), Some(15));
+    }
+
+    #[test]
+    fn a_late_scan_climbs_only_one_rung() {
+        let batch_timestamp = SystemTime::now();
+        let fingerprint = make_fingerprint(batch_timestamp, 3);
+        let msg = report(vec![with_status(TxStatus::Pending, &fingerprint)]);
+        let mut subject = StuckBatchBumper::new(Some(LADDER), 21_600);
+
+        let first = subject.climb(&msg, batch_timestamp + Duration::from_secs(1_500));
+        let second = subject.climb(&msg, batch_timestamp + Duration::from_secs(1_501));
+        let third = subject.climb(&msg, batch_timestamp + Duration::from_secs(1_502));
+
+        assert_eq!(first, vec![fingerprint.clone()]);
+        assert_eq!(second, vec![fingerprint]);
+        assert_eq!(third, vec![]);
+    }
+
+    #[test]
+    fn a_receipt_takes_the_batch_off_the_ladder_for_good() {
+        let batch_timestamp = SystemTime::now();
+        let landed = make_fingerprint(batch_timestamp, 1);
+        let stuck = make_fingerprint(batch_timestamp, 2);
+        let other_batch_timestamp = batch_timestamp - Duration::from_secs(1);
+        let other = make_fingerprint(other_batch_timestamp, 0);
+        let mut subject = StuckBatchBumper::new(Some(LADDER), 21_600);
+        let first_rung = subject.climb(
+            &report(vec![
+                with_status(TxStatus::Pending, &landed),
+                with_status(TxStatus::Pending, &stuck),
+                with_status(TxStatus::Pending, &other),
+            ]),
+            batch_timestamp + Duration::from_secs(600),
+        );
+        let succeeded = TxStatus::Succeeded(TransactionBlock {
+            block_hash: Default::default(),
+            block_number: U64::from(1_234),
+        });
+
+        let after_receipt = subject.climb(
+            &report(vec![
+                with_status(succeeded, &landed),
+                with_status(TxStatus::Pending, &stuck),
+                with_status(TxStatus::Pending, &other),
+            ]),
+            batch_timestamp + Duration::from_secs(1_200),
+        );
+        let later_still = subject.climb(
+            &report(vec![
+                with_status(TxStatus::Pending, &stuck),
+                with_status(TxStatus::Pending, &other),
+            ]),
+            batch_timestamp + Duration::from_secs(1_800),
+        );
+
+        assert_eq!(first_rung, vec![other.clone(), landed, stuck]);
+        assert_eq!(after_receipt, vec![other]);
+        assert_eq!(later_still, vec![]);
+    }
+
+    #[test]
+    fn transactions_without_nonces_failed_lookups_and_overdue_batches_stay_put() {
+        let batch_timestamp = SystemTime::now();
+        let without_nonce = PendingPayableFingerprint {
+            replacement_basis_opt: None,
+            ..make_fingerprint(batch_timestamp, 4)
+        };
+        let lookup_failed = make_fingerprint(batch_timestamp, 5);
+        let overdue = make_fingerprint(batch_timestamp - Duration::from_secs(10_000), 6);
+        let mut subject = StuckBatchBumper::new(Some(LADDER), 9_000);
+
+        let result = subject.climb(
+            &report(vec![
+                with_status(TxStatus::Pending, &without_nonce),
+                (
+                    TransactionReceiptResult::LocalError("Booga".to_string()),
+                    lookup_failed,
+                ),
+                with_status(TxStatus::Pending, &overdue),
+            ]),
+            batch_timestamp + Duration::from_secs(600),
+        );
+
+        assert_eq!(result, vec![]);
+    }
+}
@@ -2,25 +2,42 @@
 
 #![cfg(test)]
 
+use crate::accountant::db_access_objects::accounts_transfer_dao::{
+    AccountsSnapshot, AccountsTransferDao, AccountsTransferDaoError, AccountsTransferDaoFactory,
+};
 use crate::accountant::db_access_objects::banned_dao::{BannedDao, BannedDaoFactory};
+use crate::accountant::db_access_objects::consistency_dao::{
+    Anomaly, ConsistencyDao, ConsistencyDaoError, ConsistencyDaoFactory,
+};
+use crate::accountant::db_access_objects::outbound_payment_dao::{
+    OutboundPayment, OutboundPaymentDao, OutboundPaymentDaoError, OutboundPaymentDaoFactory,
+};
 use crate::accountant::db_access_objects::payable_dao::{
-    PayableAccount, PayableDao, PayableDaoError, PayableDaoFactory,
+    InvoiceDispute, PayableAccount, PayableDao, PayableDaoError, PayableDaoFactory, PayableWriteOff,
 };
 use crate::accountant::db_access_objects::pending_payable_dao::{
-    PendingPayableDao, PendingPayableDaoError, PendingPayableDaoFactory, TransactionHashes,
+    PendingPayable, PendingPayableDao, PendingPayableDaoError, PendingPayableDaoFactory,
+    TransactionHashes,
 };
 use crate::accountant::db_access_objects::receivable_dao::{
     ReceivableAccount, ReceivableDao, ReceivableDaoError, ReceivableDaoFactory,
 };
-use crate::accountant::db_access_objects::utils::{from_time_t, to_time_t, CustomQuery};
+use crate::accountant::db_access_objects::rpc_usage_dao::{
+    RpcUsage, RpcUsageDao, RpcUsageDaoError,
+};
+use crate::accountant::db_access_objects::utils::{from_time_t, to_time_t, CustomQuery, DebtAging};
 use crate::accountant::payment_adjuster::{Adjustment, AnalysisError, PaymentAdjuster};
+use crate::accountant::price_feed::{FiatQuote, PriceFeed};
+use crate::accountant::scanners::creditor_hold::CreditorReachability;
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::{
     BlockchainAgentWithContextMessage, QualifiedPayablesMessage,
 };
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::{
-    MultistagePayableScanner, PreparedAdjustment, SolvencySensitivePaymentInstructor,
+    BlockchainAgentSnapshot, MultistagePayableScanner, ObservedPayables, PayableScanMode,
+    PreparedAdjustment, SolvencySensitivePaymentInstructor,
 };
 use crate::accountant::scanners::scanners_utils::payable_scanner_utils::PayableThresholdsGauge;
+use crate::accountant::scanners::spend_rate_guard::SpendRateDeferral;
 use crate::accountant::scanners::{
     BeginScanError, PayableScanner, PendingPayableScanner, PeriodicalScanScheduler,
     ReceivableScanner, ScanSchedulers, Scanner,
@@ -28,7 +45,9 @@ use crate::accountant::scanners::{
 use crate::accountant::{
     gwei_to_wei, Accountant, ResponseSkeleton, SentPayables, DEFAULT_PENDING_TOO_LONG_SEC,
 };
-use crate::blockchain::blockchain_bridge::PendingPayableFingerprint;
+use crate::blockchain::blockchain_bridge::{
+    CreditorShare, PendingPayableFingerprint, ReplacementBasis,
+};
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::HashAndAmount;
 use crate::blockchain::blockchain_interface::data_structures::BlockchainTransaction;
 use crate::blockchain::test_utils::make_tx_hash;
@@ -37,7 +56,10 @@ use crate::database::rusqlite_wrappers::TransactionSafeWrapper;
 use crate::db_config::config_dao::{ConfigDao, ConfigDaoFactory};
 use crate::db_config::mocks::ConfigDaoMock;
 use crate::sub_lib::accountant::{DaoFactories, FinancialStatistics};
-use crate::sub_lib::accountant::{MessageIdGenerator, PaymentThresholds};
+use crate::sub_lib::accountant::{
+    GasPriceCeiling, MessageIdGenerator, PaymentBatching, PaymentThresholds, PaymentWindow,
+    SpendRateLimits, UnreachableCreditorHold,
+};
 use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
 use crate::sub_lib::utils::NotifyLaterHandle;
 use crate::sub_lib::wallet::Wallet;
@@ -53,6 +75,7 @@ use masq_lib::ui_gateway::NodeToUiMessage;
 use rusqlite::{Connection, OpenFlags, Row};
 use std::any::type_name;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::path::Path;
 use std::rc::Rc;
@@ -104,6 +127,9 @@ pub struct AccountantBuilder {
     pending_payable_dao_factory_opt: Option<PendingPayableDaoFactoryMock>,
     banned_dao_factory_opt: Option<BannedDaoFactoryMock>,
     config_dao_factory_opt: Option<ConfigDaoFactoryMock>,
+    accounts_transfer_dao_factory_opt: Option<AccountsTransferDaoFactoryMock>,
+    consistency_dao_factory_opt: Option<ConsistencyDaoFactoryMock>,
+    outbound_payment_dao_factory_opt: Option<OutboundPaymentDaoFactoryMock>,
 }
 
 impl Default for AccountantBuilder {
@@ -117,6 +143,9 @@ impl Default for AccountantBuilder {
             pending_payable_dao_factory_opt: None,
             banned_dao_factory_opt: None,
             config_dao_factory_opt: None,
+            accounts_transfer_dao_factory_opt: None,
+            consistency_dao_factory_opt: None,
+            outbound_payment_dao_factory_opt: None,
         }
     }
 }
@@ -340,6 +369,24 @@ impl AccountantBuilder {
         self
     }
 
+    pub fn accounts_transfer_dao(mut self, accounts_transfer_dao: AccountsTransferDaoMock) -> Self {
+        self.accounts_transfer_dao_factory_opt =
+            Some(AccountsTransferDaoFactoryMock::new().make_result(accounts_transfer_dao));
+        self
+    }
+
+    pub fn consistency_dao(mut self, consistency_dao: ConsistencyDaoMock) -> Self {
+        self.consistency_dao_factory_opt =
+            Some(ConsistencyDaoFactoryMock::new().make_result(consistency_dao));
+        self
+    }
+
+    pub fn outbound_payment_dao(mut self, outbound_payment_dao: OutboundPaymentDaoMock) -> Self {
+        self.outbound_payment_dao_factory_opt =
+            Some(OutboundPaymentDaoFactoryMock::new().make_result(outbound_payment_dao));
+        self
+    }
+
     pub fn build(self) -> Accountant {
         let config = self.config_opt.unwrap_or(make_bc_with_defaults());
         let payable_dao_factory = self.payable_dao_factory_opt.unwrap_or(
@@ -365,6 +412,19 @@ impl AccountantBuilder {
         let config_dao_factory = self
             .config_dao_factory_opt
             .unwrap_or(ConfigDaoFactoryMock::new().make_result(ConfigDaoMock::new()));
+        let accounts_transfer_dao_factory = self.accounts_transfer_dao_factory_opt.unwrap_or(
+            AccountsTransferDaoFactoryMock::new().make_result(AccountsTransferDaoMock::new()),
+        );
+        let consistency_dao_factory = self.consistency_dao_factory_opt.unwrap_or(
+            // Consulted on StartMessage about anomalies left by a crash
+            ConsistencyDaoFactoryMock::new()
+                .make_result(ConsistencyDaoMock::new().anomalies_result(vec![])),
+        );
+        let outbound_payment_dao_factory = self.outbound_payment_dao_factory_opt.unwrap_or(
+            // Consulted on StartMessage about payments left unfinished by a crash
+            OutboundPaymentDaoFactoryMock::new()
+                .make_result(OutboundPaymentDaoMock::new().unfinished_result(vec![])),
+        );
         let mut accountant = Accountant::new(
             config,
             DaoFactories {
@@ -373,6 +433,9 @@ impl AccountantBuilder {
                 receivable_dao_factory: Box::new(receivable_dao_factory),
                 banned_dao_factory: Box::new(banned_dao_factory),
                 config_dao_factory: Box::new(config_dao_factory),
+                accounts_transfer_dao_factory: Box::new(accounts_transfer_dao_factory),
+                consistency_dao_factory: Box::new(consistency_dao_factory),
+                outbound_payment_dao_factory: Box::new(outbound_payment_dao_factory),
             },
         );
         if let Some(logger) = self.logger_opt {
@@ -523,12 +586,390 @@ impl ConfigDaoFactoryMock {
     }
 }
 
+pub struct AccountsTransferDaoFactoryMock {
+    make_params: Arc<Mutex<Vec<()>>>,
+    make_results: RefCell<Vec<Box<dyn AccountsTransferDao>>>,
+}
+
+impl AccountsTransferDaoFactory for AccountsTransferDaoFactoryMock {
+    fn make(&self) -> Box<dyn AccountsTransferDao> {
+        if self.make_results.borrow().len() == 0 {
+            panic!("AccountsTransferDao Missing.")
+        };
+        self.make_params.lock().unwrap().push(());
+        self.make_results.borrow_mut().remove(0)
+    }
+}
+
+impl AccountsTransferDaoFactoryMock {
+    pub fn new() -> Self {
+        Self {
+            make_params: Arc::new(Mutex::new(vec![])),
+            make_results: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn make_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.make_params = params.clone();
+        self
+    }
+
+    pub fn make_result(self, result: AccountsTransferDaoMock) -> Self {
+        self.make_results.borrow_mut().push(Box::new(result));
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct AccountsTransferDaoMock {
+    export_results: RefCell<Vec<AccountsSnapshot>>,
+    import_params: Arc<Mutex<Vec<AccountsSnapshot>>>,
+    import_results: RefCell<Vec<Result<(), AccountsTransferDaoError>>>,
+}
+
+impl AccountsTransferDao for AccountsTransferDaoMock {
+    fn export(&self) -> AccountsSnapshot {
+        self.export_results.borrow_mut().remove(0)
+    }
+
+    fn import(&mut self, snapshot: &AccountsSnapshot) -> Result<(), AccountsTransferDaoError> {
+        self.import_params.lock().unwrap().push(snapshot.clone());
+        self.import_results.borrow_mut().remove(0)
+    }
+}
+
+impl AccountsTransferDaoMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn export_result(self, result: AccountsSnapshot) -> Self {
+        self.export_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn import_params(mut self, params: &Arc<Mutex<Vec<AccountsSnapshot>>>) -> Self {
+        self.import_params = params.clone();
+        self
+    }
+
+    pub fn import_result(self, result: Result<(), AccountsTransferDaoError>) -> Self {
+        self.import_results.borrow_mut().push(result);
+        self
+    }
+}
+
+pub struct ConsistencyDaoFactoryMock {
+    make_params: Arc<Mutex<Vec<()>>>,
+    make_results: RefCell<Vec<Box<dyn ConsistencyDao>>>,
+}
+
+impl ConsistencyDaoFactory for ConsistencyDaoFactoryMock {
+    fn make(&self) -> Box<dyn ConsistencyDao> {
+        if self.make_results.borrow().len() == 0 {
+            panic!("ConsistencyDao Missing.")
+        };
+        self.make_params.lock().unwrap().push(());
+        self.make_results.borrow_mut().remove(0)
+    }
+}
+
+impl ConsistencyDaoFactoryMock {
+    pub fn new() -> Self {
+        Self {
+            make_params: Arc::new(Mutex::new(vec![])),
+            make_results: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn make_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.make_params = params.clone();
+        self
+    }
+
+    pub fn make_result(self, result: ConsistencyDaoMock) -> Self {
+        self.make_results.borrow_mut().push(Box::new(result));
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct ConsistencyDaoMock {
+    anomalies_results: RefCell<Vec<Vec<Anomaly>>>,
+    repair_params: Arc<Mutex<Vec<Vec<Anomaly>>>>,
+    repair_results: RefCell<Vec<Result<(), ConsistencyDaoError>>>,
+}
+
+impl ConsistencyDao for ConsistencyDaoMock {
+    fn anomalies(&self) -> Vec<Anomaly> {
+        self.anomalies_results.borrow_mut().remove(0)
+    }
+
+    fn repair(&mut self, anomalies: &[Anomaly]) -> Result<(), ConsistencyDaoError> {
+        self.repair_params.lock().unwrap().push(anomalies.to_vec());
+        self.repair_results.borrow_mut().remove(0)
+    }
+}
+
+impl ConsistencyDaoMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn anomalies_result(self, result: Vec<Anomaly>) -> Self {
+        self.anomalies_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn repair_params(mut self, params: &Arc<Mutex<Vec<Vec<Anomaly>>>>) -> Self {
+        self.repair_params = params.clone();
+        self
+    }
+
+    pub fn repair_result(self, result: Result<(), ConsistencyDaoError>) -> Self {
+        self.repair_results.borrow_mut().push(result);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct RpcUsageDaoMock {
+    add_params: Arc<Mutex<Vec<RpcUsage>>>,
+    add_results: RefCell<Vec<Result<(), RpcUsageDaoError>>>,
+    usage_since_params: Arc<Mutex<Vec<String>>>,
+    usage_since_results: RefCell<Vec<Vec<RpcUsage>>>,
+    delete_before_params: Arc<Mutex<Vec<String>>>,
+    delete_before_results: RefCell<Vec<Result<(), RpcUsageDaoError>>>,
+}
+
+impl RpcUsageDao for RpcUsageDaoMock {
+    fn add(&self, usage: &RpcUsage) -> Result<(), RpcUsageDaoError> {
+        self.add_params.lock().unwrap().push(usage.clone());
+        self.add_results.borrow_mut().remove(0)
+    }
+
+    fn usage_since(&self, day: &str) -> Vec<RpcUsage> {
+        self.usage_since_params
+            .lock()
+            .unwrap()
+            .push(day.to_string());
+        self.usage_since_results.borrow_mut().remove(0)
+    }
+
+    fn delete_before(&self, day: &str) -> Result<(), RpcUsageDaoError> {
+        self.delete_before_params
+            .lock()
+            .unwrap()
+            .push(day.to_string());
+        self.delete_before_results.borrow_mut().remove(0)
+    }
+}
+
+impl RpcUsageDaoMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_params(mut self, params: &Arc<Mutex<Vec<RpcUsage>>>) -> Self {
+        self.add_params = params.clone();
+        self
+    }
+
+    pub fn add_result(self, result: Result<(), RpcUsageDaoError>) -> Self {
+        self.add_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn usage_since_params(mut self, params: &Arc<Mutex<Vec<String>>>) -> Self {
+        self.usage_since_params = params.clone();
+        self
+    }
+
+    pub fn usage_since_result(self, result: Vec<RpcUsage>) -> Self {
+        self.usage_since_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn delete_before_params(mut self, params: &Arc<Mutex<Vec<String>>>) -> Self {
+        self.delete_before_params = params.clone();
+        self
+    }
+
+    pub fn delete_before_result(self, result: Result<(), RpcUsageDaoError>) -> Self {
+        self.delete_before_results.borrow_mut().push(result);
+        self
+    }
+}
+
+pub struct OutboundPaymentDaoFactoryMock {
+    make_params: Arc<Mutex<Vec<()>>>,
+    make_results: RefCell<Vec<Box<dyn OutboundPaymentDao>>>,
+}
+
+impl OutboundPaymentDaoFactory for OutboundPaymentDaoFactoryMock {
+    fn make(&self) -> Box<dyn OutboundPaymentDao> {
+        if self.make_results.borrow().len() == 0 {
+            panic!("OutboundPaymentDao Missing.")
+        };
+        self.make_params.lock().unwrap().push(());
+        self.make_results.borrow_mut().remove(0)
+    }
+}
+
+impl OutboundPaymentDaoFactoryMock {
+    pub fn new() -> Self {
+        Self {
+            make_params: Arc::new(Mutex::new(vec![])),
+            make_results: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn make_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.make_params = params.clone();
+        self
+    }
+
+    pub fn make_result(self, result: OutboundPaymentDaoMock) -> Self {
+        self.make_results.borrow_mut().push(Box::new(result));
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct OutboundPaymentDaoMock {
+    enqueue_params: Arc<Mutex<Vec<(SystemTime, BlockchainAgentSnapshot, Vec<PayableAccount>)>>>,
+    enqueue_results: RefCell<Vec<Result<u64, OutboundPaymentDaoError>>>,
+    mark_submitted_params: Arc<Mutex<Vec<(u64, Vec<PendingPayable>)>>>,
+    mark_submitted_results: RefCell<Vec<Result<(), OutboundPaymentDaoError>>>,
+    mark_confirmed_params: Arc<Mutex<Vec<Vec<H256>>>>,
+    mark_confirmed_results: RefCell<Vec<Result<(), OutboundPaymentDaoError>>>,
+    unfinished_results: RefCell<Vec<Vec<OutboundPayment>>>,
+    remove_params: Arc<Mutex<Vec<Vec<u64>>>>,
+    remove_results: RefCell<Vec<Result<(), OutboundPaymentDaoError>>>,
+}
+
+impl OutboundPaymentDao for OutboundPaymentDaoMock {
+    fn enqueue(
+        &mut self,
+        timestamp: SystemTime,
+        agent_snapshot: &BlockchainAgentSnapshot,
+        accounts: &[PayableAccount],
+    ) -> Result<u64, OutboundPaymentDaoError> {
+        self.enqueue_params.lock().unwrap().push((
+            timestamp,
+            agent_snapshot.clone(),
+            accounts.to_vec(),
+        ));
+        self.enqueue_results.borrow_mut().remove(0)
+    }
+
+    fn mark_submitted(
+        &self,
+        instructions_rowid: u64,
+        sent_payments: &[PendingPayable],
+    ) -> Result<(), OutboundPaymentDaoError> {
+        self.mark_submitted_params
+            .lock()
+            .unwrap()
+            .push((instructions_rowid, sent_payments.to_vec()));
+        self.mark_submitted_results.borrow_mut().remove(0)
+    }
+
+    fn mark_confirmed(&self, hashes: &[H256]) -> Result<(), OutboundPaymentDaoError> {
+        self.mark_confirmed_params
+            .lock()
+            .unwrap()
+            .push(hashes.to_vec());
+        self.mark_confirmed_results.borrow_mut().remove(0)
+    }
+
+    fn unfinished(&self) -> Vec<OutboundPayment> {
+        self.unfinished_results.borrow_mut().remove(0)
+    }
+
+    fn remove(&self, rowids: &[u64]) -> Result<(), OutboundPaymentDaoError> {
+        self.remove_params.lock().unwrap().push(rowids.to_vec());
+        self.remove_results.borrow_mut().remove(0)
+    }
+}
+
+impl OutboundPaymentDaoMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enqueue_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(SystemTime, BlockchainAgentSnapshot, Vec<PayableAccount>)>>>,
+    ) -> Self {
+        self.enqueue_params = params.clone();
+        self
+    }
+
+    pub fn enqueue_result(self, result: Result<u64, OutboundPaymentDaoError>) -> Self {
+        self.enqueue_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn mark_submitted_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(u64, Vec<PendingPayable>)>>>,
+    ) -> Self {
+        self.mark_submitted_params = params.clone();
+        self
+    }
+
+    pub fn mark_submitted_result(self, result: Result<(), OutboundPaymentDaoError>) -> Self {
+        self.mark_submitted_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn mark_confirmed_params(mut self, params: &Arc<Mutex<Vec<Vec<H256>>>>) -> Self {
+        self.mark_confirmed_params = params.clone();
+        self
+    }
+
+    pub fn mark_confirmed_result(self, result: Result<(), OutboundPaymentDaoError>) -> Self {
+        self.mark_confirmed_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn unfinished_result(self, result: Vec<OutboundPayment>) -> Self {
+        self.unfinished_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn remove_params(mut self, params: &Arc<Mutex<Vec<Vec<u64>>>>) -> Self {
+        self.remove_params = params.clone();
+        self
+    }
+
+    pub fn remove_result(self, result: Result<(), OutboundPaymentDaoError>) -> Self {
+        self.remove_results.borrow_mut().push(result);
+        self
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct PayableDaoMock {
     more_money_payable_parameters: Arc<Mutex<Vec<(SystemTime, Wallet, u128)>>>,
     more_money_payable_results: RefCell<Vec<Result<(), PayableDaoError>>>,
     non_pending_payables_params: Arc<Mutex<Vec<()>>>,
     non_pending_payables_results: RefCell<Vec<Vec<PayableAccount>>>,
+    release_failed_payables_params: Arc<Mutex<Vec<Vec<u64>>>>,
+    release_failed_payables_results: RefCell<Vec<Result<Vec<PayableAccount>, PayableDaoError>>>,
+    creditors_of_pending_payables_params: Arc<Mutex<Vec<Vec<u64>>>>,
+    creditors_of_pending_payables_results: RefCell<Vec<HashMap<u64, Wallet>>>,
+    upsert_creditor_thresholds_params: Arc<Mutex<Vec<(Wallet, PaymentThresholds)>>>,
+    upsert_creditor_thresholds_results: RefCell<Vec<Result<(), PayableDaoError>>>,
+    creditor_thresholds_results: RefCell<Vec<HashMap<Wallet, PaymentThresholds>>>,
+    write_off_params: Arc<Mutex<Vec<(Wallet, String, bool, SystemTime)>>>,
+    write_off_results: RefCell<Vec<Result<u128, PayableDaoError>>>,
+    write_offs_params: Arc<Mutex<Vec<Wallet>>>,
+    write_offs_results: RefCell<Vec<Vec<PayableWriteOff>>>,
+    record_invoice_dispute_params: Arc<Mutex<Vec<InvoiceDispute>>>,
+    record_invoice_dispute_results: RefCell<Vec<Result<(), PayableDaoError>>>,
+    invoice_disputes_results: RefCell<Vec<Vec<InvoiceDispute>>>,
     mark_pending_payables_rowids_params: Arc<Mutex<Vec<Vec<(Wallet, u64)>>>>,
     mark_pending_payables_rowids_results: RefCell<Vec<Result<(), PayableDaoError>>>,
     transactions_confirmed_params: Arc<Mutex<Vec<Vec<PendingPayableFingerprint>>>>,
@@ -536,6 +977,10 @@ pub struct PayableDaoMock {
     custom_query_params: Arc<Mutex<Vec<CustomQuery<u64>>>>,
     custom_query_result: RefCell<Vec<Option<Vec<PayableAccount>>>>,
     total_results: RefCell<Vec<u128>>,
+    aging_params: Arc<Mutex<Vec<SystemTime>>>,
+    aging_results: RefCell<Vec<DebtAging>>,
+    account_status_params: Arc<Mutex<Vec<Wallet>>>,
+    account_status_results: RefCell<Vec<Option<PayableAccount>>>,
 }
 
 impl PayableDao for PayableDaoMock {
@@ -586,6 +1031,81 @@ impl PayableDao for PayableDaoMock {
         self.non_pending_payables_results.borrow_mut().remove(0)
     }
 
+    fn release_failed_payables(
+        &self,
+        pending_payable_rowids: &[u64],
+    ) -> Result<Vec<PayableAccount>, PayableDaoError> {
+        self.release_failed_payables_params
+            .lock()
+            .unwrap()
+            .push(pending_payable_rowids.to_vec());
+        self.release_failed_payables_results.borrow_mut().remove(0)
+    }
+
+    fn creditors_of_pending_payables(
+        &self,
+        pending_payable_rowids: &[u64],
+    ) -> HashMap<u64, Wallet> {
+        self.creditors_of_pending_payables_params
+            .lock()
+            .unwrap()
+            .push(pending_payable_rowids.to_vec());
+        self.creditors_of_pending_payables_results
+            .borrow_mut()
+            .remove(0)
+    }
+
+    fn upsert_creditor_thresholds(
+        &self,
+        creditor_wallet: &Wallet,
+        payment_thresholds: &PaymentThresholds,
+    ) -> Result<(), PayableDaoError> {
+        self.upsert_creditor_thresholds_params
+            .lock()
+            .unwrap()
+            .push((creditor_wallet.clone(), *payment_thresholds));
+        self.upsert_creditor_thresholds_results
+            .borrow_mut()
+            .remove(0)
+    }
+
+    fn creditor_thresholds(&self) -> HashMap<Wallet, PaymentThresholds> {
+        self.creditor_thresholds_results.borrow_mut().remove(0)
+    }
+
+    fn write_off(
+        &mut self,
+        wallet: &Wallet,
+        reason: &str,
+        ban: bool,
+        timestamp: SystemTime,
+    ) -> Result<u128, PayableDaoError> {
+        self.write_off_params.lock().unwrap().push((
+            wallet.clone(),
+            reason.to_string(),
+            ban,
+            timestamp,
+        ));
+        self.write_off_results.borrow_mut().remove(0)
+    }
+
+    fn write_offs(&self, wallet: &Wallet) -> Vec<PayableWriteOff> {
+        self.write_offs_params.lock().unwrap().push(wallet.clone());
+        self.write_offs_results.borrow_mut().remove(0)
+    }
+
+    fn record_invoice_dispute(&self, dispute: &InvoiceDispute) -> Result<(), PayableDaoError> {
+        self.record_invoice_dispute_params
+            .lock()
+            .unwrap()
+            .push(dispute.clone());
+        self.record_invoice_dispute_results.borrow_mut().remove(0)
+    }
+
+    fn invoice_disputes(&self) -> Vec<InvoiceDispute> {
+        self.invoice_disputes_results.borrow_mut().remove(0)
+    }
+
     fn custom_query(&self, custom_query: CustomQuery<u64>) -> Option<Vec<PayableAccount>> {
         self.custom_query_params.lock().unwrap().push(custom_query);
         self.custom_query_result.borrow_mut().remove(0)
@@ -595,9 +1115,17 @@ impl PayableDao for PayableDaoMock {
         self.total_results.borrow_mut().remove(0)
     }
 
-    fn account_status(&self, _wallet: &Wallet) -> Option<PayableAccount> {
-        //test-only trait member
-        intentionally_blank!()
+    fn aging(&self, now: SystemTime) -> DebtAging {
+        self.aging_params.lock().unwrap().push(now);
+        self.aging_results.borrow_mut().remove(0)
+    }
+
+    fn account_status(&self, wallet: &Wallet) -> Option<PayableAccount> {
+        self.account_status_params
+            .lock()
+            .unwrap()
+            .push(wallet.clone());
+        self.account_status_results.borrow_mut().remove(0)
     }
 }
 
@@ -614,18 +1142,111 @@ impl PayableDaoMock {
         self
     }
 
-    pub fn more_money_payable_result(self, result: Result<(), PayableDaoError>) -> Self {
-        self.more_money_payable_results.borrow_mut().push(result);
+    pub fn more_money_payable_result(self, result: Result<(), PayableDaoError>) -> Self {
+        self.more_money_payable_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn non_pending_payables_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.non_pending_payables_params = params.clone();
+        self
+    }
+
+    pub fn non_pending_payables_result(self, result: Vec<PayableAccount>) -> Self {
+        self.non_pending_payables_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn release_failed_payables_params(mut self, params: &Arc<Mutex<Vec<Vec<u64>>>>) -> Self {
+        self.release_failed_payables_params = params.clone();
+        self
+    }
+
+    pub fn release_failed_payables_result(
+        self,
+        result: Result<Vec<PayableAccount>, PayableDaoError>,
+    ) -> Self {
+        self.release_failed_payables_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn creditors_of_pending_payables_params(
+        mut self,
+        params: &Arc<Mutex<Vec<Vec<u64>>>>,
+    ) -> Self {
+        self.creditors_of_pending_payables_params = params.clone();
+        self
+    }
+
+    pub fn creditors_of_pending_payables_result(self, result: HashMap<u64, Wallet>) -> Self {
+        self.creditors_of_pending_payables_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn upsert_creditor_thresholds_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(Wallet, PaymentThresholds)>>>,
+    ) -> Self {
+        self.upsert_creditor_thresholds_params = params.clone();
+        self
+    }
+
+    pub fn upsert_creditor_thresholds_result(self, result: Result<(), PayableDaoError>) -> Self {
+        self.upsert_creditor_thresholds_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn creditor_thresholds_result(self, result: HashMap<Wallet, PaymentThresholds>) -> Self {
+        self.creditor_thresholds_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn write_off_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(Wallet, String, bool, SystemTime)>>>,
+    ) -> Self {
+        self.write_off_params = params.clone();
+        self
+    }
+
+    pub fn write_off_result(self, result: Result<u128, PayableDaoError>) -> Self {
+        self.write_off_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn write_offs_params(mut self, params: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.write_offs_params = params.clone();
+        self
+    }
+
+    pub fn write_offs_result(self, result: Vec<PayableWriteOff>) -> Self {
+        self.write_offs_results.borrow_mut().push(result);
         self
     }
 
-    pub fn non_pending_payables_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
-        self.non_pending_payables_params = params.clone();
+    pub fn record_invoice_dispute_params(
+        mut self,
+        params: &Arc<Mutex<Vec<InvoiceDispute>>>,
+    ) -> Self {
+        self.record_invoice_dispute_params = params.clone();
         self
     }
 
-    pub fn non_pending_payables_result(self, result: Vec<PayableAccount>) -> Self {
-        self.non_pending_payables_results.borrow_mut().push(result);
+    pub fn record_invoice_dispute_result(self, result: Result<(), PayableDaoError>) -> Self {
+        self.record_invoice_dispute_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn invoice_disputes_result(self, result: Vec<InvoiceDispute>) -> Self {
+        self.invoice_disputes_results.borrow_mut().push(result);
         self
     }
 
@@ -673,6 +1294,26 @@ impl PayableDaoMock {
         self.total_results.borrow_mut().push(result);
         self
     }
+
+    pub fn aging_params(mut self, params: &Arc<Mutex<Vec<SystemTime>>>) -> Self {
+        self.aging_params = params.clone();
+        self
+    }
+
+    pub fn aging_result(self, result: DebtAging) -> Self {
+        self.aging_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn account_status_params(mut self, params: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.account_status_params = params.clone();
+        self
+    }
+
+    pub fn account_status_result(self, result: Option<PayableAccount>) -> Self {
+        self.account_status_results.borrow_mut().push(result);
+        self
+    }
 }
 
 #[derive(Debug, Default)]
@@ -681,6 +1322,14 @@ pub struct ReceivableDaoMock {
     more_money_receivable_results: RefCell<Vec<Result<(), ReceivableDaoError>>>,
     more_money_received_parameters: Arc<Mutex<Vec<(SystemTime, Vec<BlockchainTransaction>)>>>,
     more_money_received_results: RefCell<Vec<TransactionSafeWrapper<'static>>>,
+    received_payments_between_params: Arc<Mutex<Vec<(u64, u64)>>>,
+    received_payments_between_results: RefCell<Vec<Vec<BlockchainTransaction>>>,
+    received_payments_from_params: Arc<Mutex<Vec<Wallet>>>,
+    received_payments_from_results: RefCell<Vec<Vec<BlockchainTransaction>>>,
+    reverse_received_payments_params: Arc<Mutex<Vec<Vec<BlockchainTransaction>>>>,
+    reverse_received_payments_results: RefCell<Vec<Result<(), ReceivableDaoError>>>,
+    forget_received_payments_before_params: Arc<Mutex<Vec<u64>>>,
+    forget_received_payments_before_results: RefCell<Vec<Result<(), ReceivableDaoError>>>,
     new_delinquencies_parameters: Arc<Mutex<Vec<(SystemTime, PaymentThresholds)>>>,
     new_delinquencies_results: RefCell<Vec<Vec<ReceivableAccount>>>,
     paid_delinquencies_parameters: Arc<Mutex<Vec<PaymentThresholds>>>,
@@ -688,6 +1337,10 @@ pub struct ReceivableDaoMock {
     custom_query_params: Arc<Mutex<Vec<CustomQuery<i64>>>>,
     custom_query_result: RefCell<Vec<Option<Vec<ReceivableAccount>>>>,
     total_results: RefCell<Vec<i128>>,
+    aging_params: Arc<Mutex<Vec<SystemTime>>>,
+    aging_results: RefCell<Vec<DebtAging>>,
+    account_status_params: Arc<Mutex<Vec<Wallet>>>,
+    account_status_results: RefCell<Vec<Option<ReceivableAccount>>>,
 }
 
 impl ReceivableDao for ReceivableDaoMock {
@@ -716,6 +1369,51 @@ impl ReceivableDao for ReceivableDaoMock {
         self.more_money_received_results.borrow_mut().remove(0)
     }
 
+    fn received_payments_between(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Vec<BlockchainTransaction> {
+        self.received_payments_between_params
+            .lock()
+            .unwrap()
+            .push((from_block, to_block));
+        self.received_payments_between_results
+            .borrow_mut()
+            .remove(0)
+    }
+
+    fn received_payments_from(&self, wallet: &Wallet) -> Vec<BlockchainTransaction> {
+        self.received_payments_from_params
+            .lock()
+            .unwrap()
+            .push(wallet.clone());
+        self.received_payments_from_results.borrow_mut().remove(0)
+    }
+
+    fn reverse_received_payments(
+        &mut self,
+        vanished_payments: &[BlockchainTransaction],
+    ) -> Result<(), ReceivableDaoError> {
+        self.reverse_received_payments_params
+            .lock()
+            .unwrap()
+            .push(vanished_payments.to_vec());
+        self.reverse_received_payments_results
+            .borrow_mut()
+            .remove(0)
+    }
+
+    fn forget_received_payments_before(&self, block_number: u64) -> Result<(), ReceivableDaoError> {
+        self.forget_received_payments_before_params
+            .lock()
+            .unwrap()
+            .push(block_number);
+        self.forget_received_payments_before_results
+            .borrow_mut()
+            .remove(0)
+    }
+
     fn new_delinquencies(
         &self,
         now: SystemTime,
@@ -745,9 +1443,17 @@ impl ReceivableDao for ReceivableDaoMock {
         self.total_results.borrow_mut().remove(0)
     }
 
-    fn account_status(&self, _wallet: &Wallet) -> Option<ReceivableAccount> {
-        //test-only trait member
-        intentionally_blank!()
+    fn aging(&self, now: SystemTime) -> DebtAging {
+        self.aging_params.lock().unwrap().push(now);
+        self.aging_results.borrow_mut().remove(0)
+    }
+
+    fn account_status(&self, wallet: &Wallet) -> Option<ReceivableAccount> {
+        self.account_status_params
+            .lock()
+            .unwrap()
+            .push(wallet.clone());
+        self.account_status_results.borrow_mut().remove(0)
     }
 }
 
@@ -782,6 +1488,63 @@ impl ReceivableDaoMock {
         self
     }
 
+    pub fn received_payments_between_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(u64, u64)>>>,
+    ) -> Self {
+        self.received_payments_between_params = params.clone();
+        self
+    }
+
+    pub fn received_payments_between_result(self, result: Vec<BlockchainTransaction>) -> Self {
+        self.received_payments_between_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn received_payments_from_params(mut self, params: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.received_payments_from_params = params.clone();
+        self
+    }
+
+    pub fn received_payments_from_result(self, result: Vec<BlockchainTransaction>) -> Self {
+        self.received_payments_from_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn reverse_received_payments_params(
+        mut self,
+        params: &Arc<Mutex<Vec<Vec<BlockchainTransaction>>>>,
+    ) -> Self {
+        self.reverse_received_payments_params = params.clone();
+        self
+    }
+
+    pub fn reverse_received_payments_result(self, result: Result<(), ReceivableDaoError>) -> Self {
+        self.reverse_received_payments_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn forget_received_payments_before_params(mut self, params: &Arc<Mutex<Vec<u64>>>) -> Self {
+        self.forget_received_payments_before_params = params.clone();
+        self
+    }
+
+    pub fn forget_received_payments_before_result(
+        self,
+        result: Result<(), ReceivableDaoError>,
+    ) -> Self {
+        self.forget_received_payments_before_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn new_delinquencies_parameters(
         mut self,
         parameters: &Arc<Mutex<Vec<(SystemTime, PaymentThresholds)>>>,
@@ -822,6 +1585,26 @@ impl ReceivableDaoMock {
         self.total_results.borrow_mut().push(result);
         self
     }
+
+    pub fn aging_params(mut self, params: &Arc<Mutex<Vec<SystemTime>>>) -> Self {
+        self.aging_params = params.clone();
+        self
+    }
+
+    pub fn aging_result(self, result: DebtAging) -> Self {
+        self.aging_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn account_status_params(mut self, params: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.account_status_params = params.clone();
+        self
+    }
+
+    pub fn account_status_result(self, result: Option<ReceivableAccount>) -> Self {
+        self.account_status_results.borrow_mut().push(result);
+        self
+    }
 }
 
 #[derive(Debug, Default)]
@@ -892,15 +1675,26 @@ pub struct PendingPayableDaoMock {
     fingerprints_rowids_results: RefCell<Vec<TransactionHashes>>,
     delete_fingerprints_params: Arc<Mutex<Vec<Vec<u64>>>>,
     delete_fingerprints_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
-    insert_new_fingerprints_params: Arc<Mutex<Vec<(Vec<HashAndAmount>, SystemTime)>>>,
+    insert_new_fingerprints_params:
+        Arc<Mutex<Vec<(Vec<HashAndAmount>, SystemTime, ReplacementBasis)>>>,
     insert_new_fingerprints_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
+    insert_creditor_shares_params: Arc<Mutex<Vec<Vec<CreditorShare>>>>,
+    insert_creditor_shares_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
+    record_replacement_params: Arc<Mutex<Vec<(u64, H256, ReplacementBasis)>>>,
+    record_replacement_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
     increment_scan_attempts_params: Arc<Mutex<Vec<Vec<u64>>>>,
     increment_scan_attempts_result: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
     mark_failures_params: Arc<Mutex<Vec<Vec<u64>>>>,
     mark_failures_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
+    unmark_failures_params: Arc<Mutex<Vec<Vec<u64>>>>,
+    unmark_failures_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
+    mark_abandoned_params: Arc<Mutex<Vec<(Vec<u64>, String)>>>,
+    mark_abandoned_results: RefCell<Vec<Result<(), PendingPayableDaoError>>>,
     return_all_errorless_fingerprints_params: Arc<Mutex<Vec<()>>>,
     return_all_errorless_fingerprints_results: RefCell<Vec<Vec<PendingPayableFingerprint>>>,
     pub have_return_all_errorless_fingerprints_shut_down_the_system: bool,
+    return_failed_fingerprints_results: RefCell<Vec<Vec<PendingPayableFingerprint>>>,
+    recipients_of_errorless_fingerprints_results: RefCell<Vec<HashSet<Wallet>>>,
 }
 
 impl PendingPayableDao for PendingPayableDaoMock {
@@ -931,19 +1725,57 @@ impl PendingPayableDao for PendingPayableDaoMock {
             .remove(0)
     }
 
+    fn return_failed_fingerprints(&self) -> Vec<PendingPayableFingerprint> {
+        self.return_failed_fingerprints_results
+            .borrow_mut()
+            .remove(0)
+    }
+
+    fn recipients_of_errorless_fingerprints(&self) -> HashSet<Wallet> {
+        self.recipients_of_errorless_fingerprints_results
+            .borrow_mut()
+            .remove(0)
+    }
+
     fn insert_new_fingerprints(
         &self,
         hashes_and_amounts: &[HashAndAmount],
         batch_wide_timestamp: SystemTime,
+        first_transaction_basis: ReplacementBasis,
+    ) -> Result<(), PendingPayableDaoError> {
+        self.insert_new_fingerprints_params.lock().unwrap().push((
+            hashes_and_amounts.to_vec(),
+            batch_wide_timestamp,
+            first_transaction_basis,
+        ));
+        self.insert_new_fingerprints_results.borrow_mut().remove(0)
+    }
+
+    fn insert_creditor_shares(
+        &self,
+        shares: &[CreditorShare],
     ) -> Result<(), PendingPayableDaoError> {
-        self.insert_new_fingerprints_params
+        self.insert_creditor_shares_params
             .lock()
             .unwrap()
-            .push((hashes_and_amounts.to_vec(), batch_wide_timestamp));
-        self.insert_new_fingerprints_results.borrow_mut().remove(0)
+            .push(shares.to_vec());
+        self.insert_creditor_shares_results.borrow_mut().remove(0)
     }
 
-    fn delete_fingerprints(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
+    fn record_replacement(
+        &self,
+        rowid: u64,
+        hash: H256,
+        basis: ReplacementBasis,
+    ) -> Result<(), PendingPayableDaoError> {
+        self.record_replacement_params
+            .lock()
+            .unwrap()
+            .push((rowid, hash, basis));
+        self.record_replacement_results.borrow_mut().remove(0)
+    }
+
+    fn delete_fingerprints(&mut self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
         self.delete_fingerprints_params
             .lock()
             .unwrap()
@@ -963,6 +1795,22 @@ impl PendingPayableDao for PendingPayableDaoMock {
         self.mark_failures_params.lock().unwrap().push(ids.to_vec());
         self.mark_failures_results.borrow_mut().remove(0)
     }
+
+    fn unmark_failures(&self, ids: &[u64]) -> Result<(), PendingPayableDaoError> {
+        self.unmark_failures_params
+            .lock()
+            .unwrap()
+            .push(ids.to_vec());
+        self.unmark_failures_results.borrow_mut().remove(0)
+    }
+
+    fn mark_abandoned(&self, ids: &[u64], reason: &str) -> Result<(), PendingPayableDaoError> {
+        self.mark_abandoned_params
+            .lock()
+            .unwrap()
+            .push((ids.to_vec(), reason.to_string()));
+        self.mark_abandoned_results.borrow_mut().remove(0)
+    }
 }
 
 impl PendingPayableDaoMock {
@@ -982,7 +1830,7 @@ impl PendingPayableDaoMock {
 
     pub fn insert_fingerprints_params(
         mut self,
-        params: &Arc<Mutex<Vec<(Vec<HashAndAmount>, SystemTime)>>>,
+        params: &Arc<Mutex<Vec<(Vec<HashAndAmount>, SystemTime, ReplacementBasis)>>>,
     ) -> Self {
         self.insert_new_fingerprints_params = params.clone();
         self
@@ -995,6 +1843,34 @@ impl PendingPayableDaoMock {
         self
     }
 
+    pub fn insert_creditor_shares_params(
+        mut self,
+        params: &Arc<Mutex<Vec<Vec<CreditorShare>>>>,
+    ) -> Self {
+        self.insert_creditor_shares_params = params.clone();
+        self
+    }
+
+    pub fn insert_creditor_shares_result(self, result: Result<(), PendingPayableDaoError>) -> Self {
+        self.insert_creditor_shares_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn record_replacement_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(u64, H256, ReplacementBasis)>>>,
+    ) -> Self {
+        self.record_replacement_params = params.clone();
+        self
+    }
+
+    pub fn record_replacement_result(self, result: Result<(), PendingPayableDaoError>) -> Self {
+        self.record_replacement_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn delete_fingerprints_params(mut self, params: &Arc<Mutex<Vec<Vec<u64>>>>) -> Self {
         self.delete_fingerprints_params = params.clone();
         self
@@ -1023,6 +1899,20 @@ impl PendingPayableDaoMock {
         self
     }
 
+    pub fn return_failed_fingerprints_result(self, result: Vec<PendingPayableFingerprint>) -> Self {
+        self.return_failed_fingerprints_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn recipients_of_errorless_fingerprints_result(self, result: HashSet<Wallet>) -> Self {
+        self.recipients_of_errorless_fingerprints_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn mark_failures_params(mut self, params: &Arc<Mutex<Vec<Vec<u64>>>>) -> Self {
         self.mark_failures_params = params.clone();
         self
@@ -1033,6 +1923,26 @@ impl PendingPayableDaoMock {
         self
     }
 
+    pub fn unmark_failures_params(mut self, params: &Arc<Mutex<Vec<Vec<u64>>>>) -> Self {
+        self.unmark_failures_params = params.clone();
+        self
+    }
+
+    pub fn unmark_failures_result(self, result: Result<(), PendingPayableDaoError>) -> Self {
+        self.unmark_failures_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn mark_abandoned_params(mut self, params: &Arc<Mutex<Vec<(Vec<u64>, String)>>>) -> Self {
+        self.mark_abandoned_params = params.clone();
+        self
+    }
+
+    pub fn mark_abandoned_result(self, result: Result<(), PendingPayableDaoError>) -> Self {
+        self.mark_abandoned_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn increment_scan_attempts_params(mut self, params: &Arc<Mutex<Vec<Vec<u64>>>>) -> Self {
         self.increment_scan_attempts_params = params.clone();
         self
@@ -1090,6 +2000,13 @@ pub struct PayableScannerBuilder {
     pending_payable_dao: PendingPayableDaoMock,
     payment_thresholds: PaymentThresholds,
     payment_adjuster: PaymentAdjusterMock,
+    payment_batching_opt: Option<PaymentBatching>,
+    unreachable_creditor_hold_opt: Option<UnreachableCreditorHold>,
+    creditor_reachability: Rc<RefCell<CreditorReachability>>,
+    spend_rate_limits_opt: Option<SpendRateLimits>,
+    payment_window_opt: Option<PaymentWindow>,
+    gas_price_ceiling_opt: Option<GasPriceCeiling>,
+    check_adjustment_invariants: bool,
 }
 
 impl PayableScannerBuilder {
@@ -1099,6 +2016,13 @@ impl PayableScannerBuilder {
             pending_payable_dao: PendingPayableDaoMock::new(),
             payment_thresholds: PaymentThresholds::default(),
             payment_adjuster: PaymentAdjusterMock::default(),
+            payment_batching_opt: None,
+            unreachable_creditor_hold_opt: None,
+            creditor_reachability: Rc::new(RefCell::new(CreditorReachability::default())),
+            spend_rate_limits_opt: None,
+            payment_window_opt: None,
+            gas_price_ceiling_opt: None,
+            check_adjustment_invariants: false,
         }
     }
 
@@ -1128,12 +2052,62 @@ impl PayableScannerBuilder {
         self
     }
 
+    pub fn payment_batching(mut self, payment_batching: PaymentBatching) -> Self {
+        self.payment_batching_opt = Some(payment_batching);
+        self
+    }
+
+    pub fn unreachable_creditor_hold(
+        mut self,
+        unreachable_creditor_hold: UnreachableCreditorHold,
+        creditor_reachability: &Rc<RefCell<CreditorReachability>>,
+    ) -> Self {
+        self.unreachable_creditor_hold_opt = Some(unreachable_creditor_hold);
+        self.creditor_reachability = Rc::clone(creditor_reachability);
+        self
+    }
+
+    pub fn creditor_reachability(
+        mut self,
+        creditor_reachability: &Rc<RefCell<CreditorReachability>>,
+    ) -> Self {
+        self.creditor_reachability = Rc::clone(creditor_reachability);
+        self
+    }
+
+    pub fn spend_rate_limits(mut self, spend_rate_limits: SpendRateLimits) -> Self {
+        self.spend_rate_limits_opt = Some(spend_rate_limits);
+        self
+    }
+
+    pub fn payment_window(mut self, payment_window: PaymentWindow) -> Self {
+        self.payment_window_opt = Some(payment_window);
+        self
+    }
+
+    pub fn gas_price_ceiling(mut self, gas_price_ceiling: GasPriceCeiling) -> Self {
+        self.gas_price_ceiling_opt = Some(gas_price_ceiling);
+        self
+    }
+
+    pub fn check_adjustment_invariants(mut self, check_adjustment_invariants: bool) -> Self {
+        self.check_adjustment_invariants = check_adjustment_invariants;
+        self
+    }
+
     pub fn build(self) -> PayableScanner {
         PayableScanner::new(
             Box::new(self.payable_dao),
             Box::new(self.pending_payable_dao),
-            Rc::new(self.payment_thresholds),
+            Rc::new(RefCell::new(self.payment_thresholds)),
             Box::new(self.payment_adjuster),
+            self.payment_batching_opt,
+            self.unreachable_creditor_hold_opt,
+            self.creditor_reachability,
+            self.spend_rate_limits_opt,
+            self.payment_window_opt,
+            self.gas_price_ceiling_opt,
+            self.check_adjustment_invariants,
         )
     }
 }
@@ -1144,6 +2118,7 @@ pub struct PendingPayableScannerBuilder {
     payment_thresholds: PaymentThresholds,
     when_pending_too_long_sec: u64,
     financial_statistics: FinancialStatistics,
+    abandoned_transactions: Rc<RefCell<Vec<PendingPayableFingerprint>>>,
 }
 
 impl PendingPayableScannerBuilder {
@@ -1154,6 +2129,7 @@ impl PendingPayableScannerBuilder {
             payment_thresholds: PaymentThresholds::default(),
             when_pending_too_long_sec: DEFAULT_PENDING_TOO_LONG_SEC,
             financial_statistics: FinancialStatistics::default(),
+            abandoned_transactions: Rc::new(RefCell::new(vec![])),
         }
     }
 
@@ -1172,13 +2148,22 @@ impl PendingPayableScannerBuilder {
         self
     }
 
+    pub fn abandoned_transactions(
+        mut self,
+        abandoned_transactions: &Rc<RefCell<Vec<PendingPayableFingerprint>>>,
+    ) -> Self {
+        self.abandoned_transactions = Rc::clone(abandoned_transactions);
+        self
+    }
+
     pub fn build(self) -> PendingPayableScanner {
         PendingPayableScanner::new(
             Box::new(self.payable_dao),
             Box::new(self.pending_payable_dao),
-            Rc::new(self.payment_thresholds),
+            Rc::new(RefCell::new(self.payment_thresholds)),
             self.when_pending_too_long_sec,
             Rc::new(RefCell::new(self.financial_statistics)),
+            self.abandoned_transactions,
         )
     }
 }
@@ -1189,6 +2174,8 @@ pub struct ReceivableScannerBuilder {
     persistent_configuration: PersistentConfigurationMock,
     payment_thresholds: PaymentThresholds,
     financial_statistics: FinancialStatistics,
+    smart_account_owners: HashMap<Wallet, Wallet>,
+    earning_wallet_rotation: Vec<Wallet>,
 }
 
 impl ReceivableScannerBuilder {
@@ -1199,6 +2186,8 @@ impl ReceivableScannerBuilder {
             persistent_configuration: PersistentConfigurationMock::new(),
             payment_thresholds: PaymentThresholds::default(),
             financial_statistics: FinancialStatistics::default(),
+            smart_account_owners: HashMap::new(),
+            earning_wallet_rotation: vec![],
         }
     }
 
@@ -1225,13 +2214,25 @@ impl ReceivableScannerBuilder {
         self
     }
 
+    pub fn smart_account_owners(mut self, smart_account_owners: HashMap<Wallet, Wallet>) -> Self {
+        self.smart_account_owners = smart_account_owners;
+        self
+    }
+
+    pub fn earning_wallet_rotation(mut self, earning_wallet_rotation: Vec<Wallet>) -> Self {
+        self.earning_wallet_rotation = earning_wallet_rotation;
+        self
+    }
+
     pub fn build(self) -> ReceivableScanner {
         ReceivableScanner::new(
             Box::new(self.receivable_dao),
             Box::new(self.banned_dao),
             Box::new(self.persistent_configuration),
-            Rc::new(self.payment_thresholds),
+            Rc::new(RefCell::new(self.payment_thresholds)),
             Rc::new(RefCell::new(self.financial_statistics)),
+            self.smart_account_owners,
+            self.earning_wallet_rotation,
         )
     }
 }
@@ -1255,6 +2256,7 @@ pub fn make_pending_payable_fingerprint() -> PendingPayableFingerprint {
         attempt: 1,
         amount: 12345,
         process_error: None,
+        replacement_basis_opt: None,
     }
 }
 
@@ -1437,6 +2439,24 @@ pub fn trick_rusqlite_with_read_only_conn(
     conn
 }
 
+#[derive(Default)]
+pub struct PriceFeedMock {
+    quote_opt_results: RefCell<Vec<Option<FiatQuote>>>,
+}
+
+impl PriceFeed for PriceFeedMock {
+    fn quote_opt(&self) -> Option<FiatQuote> {
+        self.quote_opt_results.borrow_mut().remove(0)
+    }
+}
+
+impl PriceFeedMock {
+    pub fn quote_opt_result(self, result: Option<FiatQuote>) -> Self {
+        self.quote_opt_results.borrow_mut().push(result);
+        self
+    }
+}
+
 #[derive(Default)]
 pub struct PaymentAdjusterMock {
     search_for_indispensable_adjustment_params:
@@ -1445,6 +2465,7 @@ pub struct PaymentAdjusterMock {
         RefCell<Vec<Result<Option<Adjustment>, AnalysisError>>>,
     adjust_payments_params: Arc<Mutex<Vec<(PreparedAdjustment, SystemTime, Logger)>>>,
     adjust_payments_results: RefCell<Vec<OutboundPaymentsInstructions>>,
+    adjusted_accounts_results: RefCell<Vec<Vec<PayableAccount>>>,
 }
 
 impl PaymentAdjuster for PaymentAdjusterMock {
@@ -1472,6 +2493,15 @@ impl PaymentAdjuster for PaymentAdjusterMock {
             .lock()
             .unwrap()
             .push((setup.clone(), now, logger.clone()));
+        if self.adjust_payments_results.borrow().is_empty() {
+            // The instructions carry on with the agent that came in, real or not
+            let affordable_accounts = self.adjusted_accounts_results.borrow_mut().remove(0);
+            return OutboundPaymentsInstructions::new(
+                affordable_accounts,
+                setup.original_setup_msg.agent,
+                setup.original_setup_msg.response_skeleton_opt,
+            );
+        }
         self.adjust_payments_results.borrow_mut().remove(0)
     }
 }
@@ -1507,15 +2537,67 @@ impl PaymentAdjusterMock {
         self.adjust_payments_results.borrow_mut().push(result);
         self
     }
+
+    pub fn adjusted_accounts_result(self, result: Vec<PayableAccount>) -> Self {
+        self.adjusted_accounts_results.borrow_mut().push(result);
+        self
+    }
 }
 
 macro_rules! formal_traits_for_payable_mid_scan_msg_handling {
     ($scanner:ty) => {
-        impl MultistagePayableScanner<QualifiedPayablesMessage, SentPayables> for $scanner {}
+        impl MultistagePayableScanner<QualifiedPayablesMessage, SentPayables> for $scanner {
+            fn set_mode(&mut self, _mode: PayableScanMode) {}
+
+            fn observed_payables_opt(&self) -> Option<ObservedPayables> {
+                None
+            }
+
+            fn limit_spend_rate(
+                &mut self,
+                _instructions: OutboundPaymentsInstructions,
+                _now: SystemTime,
+                _logger: &Logger,
+            ) -> (OutboundPaymentsInstructions, Option<SpendRateDeferral>) {
+                intentionally_blank!()
+            }
+
+            fn confine_to_payment_window(
+                &mut self,
+                _instructions: OutboundPaymentsInstructions,
+                _now: SystemTime,
+                _logger: &Logger,
+            ) -> OutboundPaymentsInstructions {
+                intentionally_blank!()
+            }
+
+            fn defer_while_gas_is_expensive(
+                &mut self,
+                _instructions: OutboundPaymentsInstructions,
+                _now: SystemTime,
+                _logger: &Logger,
+            ) -> OutboundPaymentsInstructions {
+                intentionally_blank!()
+            }
+
+            fn enforce_adjustment_invariants(
+                &self,
+                _qualified_payables: &[PayableAccount],
+                _service_fee_balance_wei: u128,
+                _instructions: OutboundPaymentsInstructions,
+                _logger: &Logger,
+            ) -> OutboundPaymentsInstructions {
+                intentionally_blank!()
+            }
+
+            fn is_below_threshold(&self, _payable: &PayableAccount, _now: SystemTime) -> bool {
+                intentionally_blank!()
+            }
+        }
 
         impl SolvencySensitivePaymentInstructor for $scanner {
             fn try_skipping_payment_adjustment(
-                &self,
+                &mut self,
                 _msg: BlockchainAgentWithContextMessage,
                 _logger: &Logger,
             ) -> Result<Either<OutboundPaymentsInstructions, PreparedAdjustment>, String> {
@@ -1667,6 +2749,11 @@ impl<BeginMessage, EndMessage> ScannerMock<BeginMessage, EndMessage> {
         self
     }
 
+    pub fn finish_scan_result(self, result: Option<NodeToUiMessage>) -> Self {
+        self.end_scan_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn stop_the_system_after_last_msg(self) -> Self {
         self.stop_system_after_last_message.replace(true);
         self
@@ -0,0 +1,307 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::checked_conversion;
+use crate::accountant::db_access_objects::payable_dao::InvoiceDispute;
+use crate::accountant::db_access_objects::utils::{from_time_t, to_time_t};
+use crate::sub_lib::accountant::UsageInvoice_0v1;
+use crate::sub_lib::wallet::Wallet;
+use std::collections::HashMap;
+use std::time::SystemTime;
+use web3::types::U256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceKind {
+    Routing,
+    Exit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UsageTally {
+    pub since: SystemTime,
+    pub routing_services: u64,
+    pub exit_services: u64,
+    pub amount_wei: u128,
+}
+
+impl UsageTally {
+    fn new(since: SystemTime) -> Self {
+        Self {
+            since,
+            routing_services: 0,
+            exit_services: 0,
+            amount_wei: 0,
+        }
+    }
+
+    fn add(&mut self, kind: ServiceKind, charge_wei: u128) {
+        match kind {
+            ServiceKind::Routing => self.routing_services = self.routing_services.saturating_add(1),
+            ServiceKind::Exit => self.exit_services = self.exit_services.saturating_add(1),
+        }
+        self.amount_wei = self.amount_wei.saturating_add(charge_wei);
+    }
+
+    fn services(&self) -> u64 {
+        self.routing_services.saturating_add(self.exit_services)
+    }
+}
+
+// Services booked per counterparty since they were last invoiced, both ways: what we've done for
+// our debtors, to invoice them with when they pay, and what our creditors have done for us, to
+// check their invoices against
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct UsageInvoices {
+    provided: HashMap<Wallet, UsageTally>,
+    consumed: HashMap<Wallet, UsageTally>,
+}
+
+impl UsageInvoices {
+    pub fn record_provided(
+        &mut self,
+        debtor_wallet: &Wallet,
+        kind: ServiceKind,
+        charge_wei: u128,
+        timestamp: SystemTime,
+    ) {
+        Self::record(
+            &mut self.provided,
+            debtor_wallet,
+            kind,
+            charge_wei,
+            timestamp,
+        )
+    }
+
+    pub fn record_consumed(
+        &mut self,
+        creditor_wallet: &Wallet,
+        kind: ServiceKind,
+        charge_wei: u128,
+        timestamp: SystemTime,
+    ) {
+        Self::record(
+            &mut self.consumed,
+            creditor_wallet,
+            kind,
+            charge_wei,
+            timestamp,
+        )
+    }
+
+    // Empties the debtor's tally into an invoice, to be signed by the Neighborhood
+    pub fn make_invoice(
+        &mut self,
+        creditor_wallet: &Wallet,
+        debtor_wallet: &Wallet,
+        now: SystemTime,
+    ) -> Option<UsageInvoice_0v1> {
+        let tally = self.provided.remove(&debtor_wallet.as_address_wallet())?;
+        Some(UsageInvoice_0v1::new(
+            creditor_wallet,
+            debtor_wallet,
+            checked_conversion::<i64, u64>(to_time_t(tally.since)),
+            checked_conversion::<i64, u64>(to_time_t(now)),
+            tally.routing_services,
+            tally.exit_services,
+            tally.amount_wei,
+        ))
+    }
+
+    // An invoice is disputed if it claims more services or more wei than we've booked for the
+    // creditor. Either way, what it claims is taken off the tally, so that services we booked
+    // after the creditor drew it up are left for its next one.
+    pub fn reconcile(
+        &mut self,
+        invoice: &UsageInvoice_0v1,
+        received_at: SystemTime,
+    ) -> Result<(), InvoiceDispute> {
+        let creditor_wallet = invoice.creditor_wallet.as_address_wallet();
+        let invoiced_services = invoice
+            .routing_services
+            .saturating_add(invoice.exit_services);
+        // An absurd claim is cut down to what the database can hold
+        let invoiced_wei = if invoice.amount_wei > U256::from(i128::MAX as u128) {
+            i128::MAX as u128
+        } else {
+            invoice.amount_wei.as_u128()
+        };
+        let (booked_services, booked_wei) = match self.consumed.get_mut(&creditor_wallet) {
+            Some(tally) => {
+                let booked = (tally.services(), tally.amount_wei);
+                tally.routing_services = tally
+                    .routing_services
+                    .saturating_sub(invoice.routing_services);
+                tally.exit_services = tally.exit_services.saturating_sub(invoice.exit_services);
+                tally.amount_wei = tally.amount_wei.saturating_sub(invoiced_wei);
+                if tally.services() == 0 && tally.amount_wei == 0 {
+                    self.consumed.remove(&creditor_wallet);
+                }
+                booked
+            }
+            None => (0, 0),
+        };
+        if invoiced_services > booked_services || invoiced_wei > booked_wei {
+            // The period is the creditor's say-so, but none of it can lie after we received it
+            let received_at_sec = to_time_t(received_at);
+            let creditor_time =
+                |sec: u64| from_time_t(i64::try_from(sec).unwrap_or(i64::MAX).min(received_at_sec));
+            Err(InvoiceDispute {
+                creditor_wallet,
+                period_start: creditor_time(invoice.period_start_sec),
+                period_end: creditor_time(invoice.period_end_sec),
+                invoiced_services,
+                booked_services,
+                invoiced_wei,
+                booked_wei,
+                received_at,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn record(
+        tallies: &mut HashMap<Wallet, UsageTally>,
+        wallet: &Wallet,
+        kind: ServiceKind,
+        charge_wei: u128,
+        timestamp: SystemTime,
+    ) {
+        tallies
+            .entry(wallet.as_address_wallet())
+            .or_insert_with(|| UsageTally::new(timestamp))
+            .add(kind, charge_wei)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{make_paying_wallet, make_wallet};
+    use std::time::Duration;
+
+    fn make_invoice(
+        routing_services: u64,
+        exit_services: u64,
+        amount_wei: u128,
+    ) -> UsageInvoice_0v1 {
+        UsageInvoice_0v1::new(
+            &make_wallet("creditor"),
+            &make_paying_wallet(b"debtor"),
+            1_000_000,
+            1_003_600,
+            routing_services,
+            exit_services,
+            amount_wei,
+        )
+    }
+
+    #[test]
+    fn make_invoice_empties_the_tally_of_services_provided_to_the_debtor() {
+        let mut subject = UsageInvoices::default();
+        let debtor_wallet = make_paying_wallet(b"debtor");
+        let since = from_time_t(1_000_000);
+        let now = from_time_t(1_003_600);
+        subject.record_provided(&debtor_wallet, ServiceKind::Routing, 1_000, since);
+        subject.record_provided(
+            &debtor_wallet.as_address_wallet(),
+            ServiceKind::Exit,
+            2_000,
+            since + Duration::from_secs(10),
+        );
+        subject.record_provided(&debtor_wallet, ServiceKind::Routing, 3_000, now);
+        subject.record_provided(&make_wallet("other debtor"), ServiceKind::Exit, 4_000, now);
+
+        let result = subject.make_invoice(&make_wallet("creditor"), &debtor_wallet, now);
+
+        assert_eq!(
+            result,
+            Some(UsageInvoice_0v1::new(
+                &make_wallet("creditor"),
+                &debtor_wallet,
+                1_000_000,
+                1_003_600,
+                2,
+                1,
+                6_000,
+            ))
+        );
+        assert_eq!(
+            subject.make_invoice(&make_wallet("creditor"), &debtor_wallet, now),
+            None
+        );
+        assert_eq!(subject.provided.len(), 1);
+    }
+
+    #[test]
+    fn reconcile_accepts_invoice_within_our_books_and_leaves_the_rest_for_the_next_one() {
+        let mut subject = UsageInvoices::default();
+        let creditor_wallet = make_wallet("creditor");
+        let timestamp = from_time_t(1_000_000);
+        subject.record_consumed(&creditor_wallet, ServiceKind::Routing, 1_000, timestamp);
+        subject.record_consumed(&creditor_wallet, ServiceKind::Routing, 1_000, timestamp);
+        subject.record_consumed(&creditor_wallet, ServiceKind::Exit, 3_000, timestamp);
+
+        let first = subject.reconcile(&make_invoice(1, 1, 4_000), timestamp);
+        let second = subject.reconcile(&make_invoice(1, 0, 1_000), timestamp);
+
+        assert_eq!(first, Ok(()));
+        assert_eq!(second, Ok(()));
+        assert_eq!(subject.consumed.len(), 0);
+    }
+
+    #[test]
+    fn reconcile_disputes_invoice_claiming_more_than_we_booked() {
+        let mut subject = UsageInvoices::default();
+        let creditor_wallet = make_wallet("creditor");
+        let timestamp = from_time_t(1_000_000);
+        let received_at = from_time_t(1_003_660);
+        subject.record_consumed(&creditor_wallet, ServiceKind::Routing, 1_000, timestamp);
+        subject.record_consumed(&creditor_wallet, ServiceKind::Exit, 3_000, timestamp);
+
+        let too_many_services = subject.reconcile(&make_invoice(2, 1, 4_000), received_at);
+        let too_much_wei = subject.reconcile(&make_invoice(0, 0, 1), received_at);
+
+        let expected_dispute = InvoiceDispute {
+            creditor_wallet: creditor_wallet.clone(),
+            period_start: from_time_t(1_000_000),
+            period_end: from_time_t(1_003_600),
+            invoiced_services: 3,
+            booked_services: 2,
+            invoiced_wei: 4_000,
+            booked_wei: 4_000,
+            received_at,
+        };
+        assert_eq!(too_many_services, Err(expected_dispute.clone()));
+        assert_eq!(
+            too_much_wei,
+            Err(InvoiceDispute {
+                invoiced_services: 0,
+                booked_services: 0,
+                invoiced_wei: 1,
+                booked_wei: 0,
+                ..expected_dispute
+            })
+        );
+        assert_eq!(subject.consumed.len(), 0);
+    }
+
+    #[test]
+    fn reconcile_cuts_absurd_claims_down_to_size() {
+        let mut subject = UsageInvoices::default();
+        let mut invoice = make_invoice(1, 0, 0);
+        invoice.amount_wei = U256::MAX;
+        invoice.period_end_sec = u64::MAX;
+
+        let result = subject.reconcile(&invoice, from_time_t(1_003_660));
+
+        match result {
+            Err(dispute) => {
+                assert_eq!(dispute.invoiced_wei, i128::MAX as u128);
+                assert_eq!(dispute.period_start, from_time_t(1_000_000));
+                assert_eq!(dispute.period_end, from_time_t(1_003_660));
+            }
+            x => panic!("we expected a dispute but got: {:?}", x),
+        }
+    }
+}
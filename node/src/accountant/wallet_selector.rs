@@ -0,0 +1,171 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
+use crate::sub_lib::accountant::ConsumingWalletSelection;
+use crate::sub_lib::wallet::Wallet;
+use std::collections::HashMap;
+use web3::types::U256;
+
+// Decides which of the unlocked consuming wallets pays for the next payable scan
+pub trait WalletSelector {
+    fn select(&mut self, candidates: &[Wallet]) -> Option<Wallet>;
+    fn record_agent(&mut self, _agent: &dyn BlockchainAgent) {}
+}
+
+pub fn make_wallet_selector(selection: ConsumingWalletSelection) -> Box<dyn WalletSelector> {
+    match selection {
+        ConsumingWalletSelection::RoundRobin => Box::new(RoundRobinWalletSelector::default()),
+        ConsumingWalletSelection::HighestBalance => {
+            Box::new(HighestBalanceWalletSelector::default())
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RoundRobinWalletSelector {
+    next_idx: usize,
+}
+
+impl WalletSelector for RoundRobinWalletSelector {
+    fn select(&mut self, candidates: &[Wallet]) -> Option<Wallet> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let idx = self.next_idx % candidates.len();
+        self.next_idx = idx + 1;
+        Some(candidates[idx].clone())
+    }
+}
+
+// Balances are only known after the BlockchainBridge has inspected a wallet, so the wallets
+// never seen yet are tried first; afterwards the one with the most MASQ wins
+#[derive(Default)]
+pub struct HighestBalanceWalletSelector {
+    masq_balances: HashMap<Wallet, U256>,
+}
+
+impl WalletSelector for HighestBalanceWalletSelector {
+    fn select(&mut self, candidates: &[Wallet]) -> Option<Wallet> {
+        candidates
+            .iter()
+            .find(|wallet| !self.masq_balances.contains_key(wallet))
+            .or_else(|| {
+                candidates.iter().rev().max_by_key(|wallet| {
+                    self.masq_balances
+                        .get(wallet)
+                        .copied()
+                        .unwrap_or_else(U256::zero)
+                })
+            })
+            .cloned()
+    }
+
+    fn record_agent(&mut self, agent: &dyn BlockchainAgent) {
+        self.masq_balances.insert(
+            agent.consuming_wallet().clone(),
+            agent
+                .consuming_wallet_balances()
+                .masq_token_balance_in_minor_units,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
+    use crate::test_utils::make_wallet;
+
+    fn agent_with_balance(wallet: &Wallet, masq_balance: u64) -> BlockchainAgentMock {
+        BlockchainAgentMock::default()
+            .consuming_wallet_result(wallet.clone())
+            .consuming_wallet_balances_result(ConsumingWalletBalances {
+                transaction_fee_balance_in_minor_units: U256::from(1_000_000),
+                masq_token_balance_in_minor_units: U256::from(masq_balance),
+            })
+    }
+
+    #[test]
+    fn round_robin_selector_cycles_through_candidates() {
+        let candidates = vec![make_wallet("abc"), make_wallet("def"), make_wallet("ghi")];
+        let mut subject = make_wallet_selector(ConsumingWalletSelection::RoundRobin);
+
+        let results = (0..5)
+            .map(|_| subject.select(&candidates).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                candidates[0].clone(),
+                candidates[1].clone(),
+                candidates[2].clone(),
+                candidates[0].clone(),
+                candidates[1].clone()
+            ]
+        )
+    }
+
+    #[test]
+    fn round_robin_selector_copes_with_shrinking_candidates() {
+        let candidates = vec![make_wallet("abc"), make_wallet("def"), make_wallet("ghi")];
+        let mut subject = RoundRobinWalletSelector::default();
+        subject.select(&candidates);
+        subject.select(&candidates);
+
+        let result = subject.select(&candidates[0..2]);
+
+        assert_eq!(result, Some(candidates[0].clone()))
+    }
+
+    #[test]
+    fn selectors_return_none_without_candidates() {
+        vec![
+            ConsumingWalletSelection::RoundRobin,
+            ConsumingWalletSelection::HighestBalance,
+        ]
+        .into_iter()
+        .for_each(|selection| {
+            let mut subject = make_wallet_selector(selection);
+
+            assert_eq!(subject.select(&[]), None)
+        })
+    }
+
+    #[test]
+    fn highest_balance_selector_tries_wallets_of_unknown_balance_first() {
+        let candidates = vec![make_wallet("abc"), make_wallet("def")];
+        let mut subject = HighestBalanceWalletSelector::default();
+        subject.record_agent(&agent_with_balance(&candidates[0], 1_000_000_000));
+
+        let result = subject.select(&candidates);
+
+        assert_eq!(result, Some(candidates[1].clone()))
+    }
+
+    #[test]
+    fn highest_balance_selector_picks_wallet_with_most_masq() {
+        let candidates = vec![make_wallet("abc"), make_wallet("def"), make_wallet("ghi")];
+        let mut subject = make_wallet_selector(ConsumingWalletSelection::HighestBalance);
+        subject.record_agent(&agent_with_balance(&candidates[0], 5_000));
+        subject.record_agent(&agent_with_balance(&candidates[1], 7_000));
+        subject.record_agent(&agent_with_balance(&candidates[2], 6_000));
+
+        let result = subject.select(&candidates);
+
+        assert_eq!(result, Some(candidates[1].clone()))
+    }
+
+    #[test]
+    fn highest_balance_selector_prefers_earlier_candidate_on_a_tie() {
+        let candidates = vec![make_wallet("abc"), make_wallet("def")];
+        let mut subject = HighestBalanceWalletSelector::default();
+        subject.record_agent(&agent_with_balance(&candidates[0], 5_000));
+        subject.record_agent(&agent_with_balance(&candidates[1], 5_000));
+
+        let result = subject.select(&candidates);
+
+        assert_eq!(result, Some(candidates[0].clone()))
+    }
+}
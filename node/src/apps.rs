@@ -37,6 +37,13 @@ pub fn app_daemon() -> App<'static, 'static> {
 
 pub fn app_node() -> App<'static, 'static> {
     shared_app(app_head().after_help(NODE_HELP_TEXT))
+        .arg(
+            Arg::with_name("check-config")
+                .long("check-config")
+                .required(false)
+                .takes_value(false)
+                .help(CHECK_CONFIG_HELP),
+        )
         .arg(data_directory_arg(DATA_DIRECTORY_HELP))
         .arg(ui_port_arg(&DAEMON_UI_PORT_HELP))
 }
@@ -68,6 +75,11 @@ lazy_static! {
 const DUMP_CONFIG_HELP: &str =
     "Dump the configuration of MASQ Node to stdout in JSON. Used chiefly by UIs.";
 
+const CHECK_CONFIG_HELP: &str =
+    "Validate the rest of the command line, the environment and the config file against the existing \
+     database without starting the Node, and print the effective values and errors to stdout in JSON. \
+     Nothing is written to the database.";
+
 const NODE_HELP_TEXT: &str = indoc!(
     r"ADDITIONAL HELP:
     If you want to start the MASQ Daemon to manage the MASQ Node and the MASQ UIs, try:
@@ -79,6 +91,11 @@ const NODE_HELP_TEXT: &str = indoc!(
 
         MASQNode --help --dump-config
 
+    If you want to see whether a configuration would be accepted, and what values it would
+    produce, without starting the Node, add --check-config to the parameters, e.g.:
+
+        MASQNode --check-config --neighborhood-mode zero-hop
+
     MASQ Node listens for connections from other Nodes using the computer's
     network interface. Configuring the internet router for port forwarding is a necessary
     step for Node users to permit network communication between Nodes.
@@ -138,6 +155,12 @@ mod tests {
             DUMP_CONFIG_HELP,
             "Dump the configuration of MASQ Node to stdout in JSON. Used chiefly by UIs."
         );
+        assert_eq!(
+            CHECK_CONFIG_HELP,
+            "Validate the rest of the command line, the environment and the config file against the \
+             existing database without starting the Node, and print the effective values and errors \
+             to stdout in JSON. Nothing is written to the database."
+        );
         assert_eq!(
             NODE_HELP_TEXT,
             indoc!(
@@ -151,6 +174,11 @@ mod tests {
             
                       MASQNode --help --dump-config
             
+                  If you want to see whether a configuration would be accepted, and what values it would
+                  produce, without starting the Node, add --check-config to the parameters, e.g.:
+            
+                      MASQNode --check-config --neighborhood-mode zero-hop
+            
                   MASQ Node listens for connections from other Nodes using the computer's
                   network interface. Configuring the internet router for port forwarding is a necessary
                   step for Node users to permit network communication between Nodes.
@@ -2,15 +2,20 @@
 
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::CONTRACT_ABI;
 use crate::blockchain::blockchain_interface::data_structures::errors::BlockchainError;
-use crate::blockchain::blockchain_interface::data_structures::errors::BlockchainError::QueryFailed;
 use crate::blockchain::blockchain_interface::lower_level_interface::LowBlockchainInt;
+use crate::blockchain::rpc_usage::MeteredHttp;
 use ethereum_types::{H256, U256, U64};
+use futures::future::{join_all, Either};
+use futures::sync::oneshot;
 use futures::Future;
-use serde_json::Value;
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use std::thread;
+use std::time::Duration;
 use web3::contract::{Contract, Options};
-use web3::transports::{Batch, Http};
-use web3::types::{Address, BlockNumber, Filter, Log, TransactionReceipt};
-use web3::{Error, Web3};
+use web3::transports::Batch;
+use web3::types::{Address, BlockNumber, Bytes, Filter, Log, TransactionId, TransactionReceipt};
+use web3::{Error, Transport, Web3};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TransactionReceiptResult {
@@ -37,6 +42,54 @@ pub struct TransactionBlock {
     pub block_number: U64,
 }
 
+// The fee market as of the latest block; the base fee is missing on chains that haven't adopted
+// EIP-1559
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+pub struct LatestBlockInfo {
+    #[serde(rename = "baseFeePerGas", default)]
+    pub base_fee_per_gas_opt: Option<U256>,
+    #[serde(rename = "gasLimit")]
+    pub gas_limit: U256,
+}
+
+// What the token contract says to a transfer tried out with eth_call, nothing being sent
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TransferSimulation {
+    Passed,
+    Refused(String),
+}
+
+// What the token contract tells about itself
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+// Which of the optional parts of the JSON-RPC API the blockchain service turned out to offer
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BlockchainCapabilities {
+    pub batch_requests: bool,
+    pub fee_history: bool,
+}
+
+// Until the blockchain service has been probed, it's assumed to be what the Node always relied on
+impl Default for BlockchainCapabilities {
+    fn default() -> Self {
+        Self {
+            batch_requests: true,
+            fee_history: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FeeHistory {
+    #[serde(rename = "baseFeePerGas", default)]
+    base_fee_per_gas: Vec<U256>,
+}
+
 impl From<TransactionReceipt> for TxReceipt {
     fn from(receipt: TransactionReceipt) -> Self {
         let status = match (receipt.status, receipt.block_hash, receipt.block_number) {
@@ -58,9 +111,10 @@ impl From<TransactionReceipt> for TxReceipt {
 }
 
 pub struct LowBlockchainIntWeb3 {
-    web3: Web3<Http>,
-    web3_batch: Web3<Batch<Http>>,
-    contract: Contract<Http>,
+    web3: Web3<MeteredHttp>,
+    web3_batch: Web3<Batch<MeteredHttp>>,
+    contract: Contract<MeteredHttp>,
+    request_timeout: Duration,
     // TODO waiting for GH-707 (note: consider to query the balances together with the id)
 }
 
@@ -69,11 +123,11 @@ impl LowBlockchainInt for LowBlockchainIntWeb3 {
         &self,
         address: Address,
     ) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
-        Box::new(
+        self.within_timeout(
             self.web3
                 .eth()
                 .balance(address, None)
-                .map_err(|e| QueryFailed(e.to_string())),
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
         )
     }
 
@@ -81,40 +135,145 @@ impl LowBlockchainInt for LowBlockchainIntWeb3 {
         &self,
         address: Address,
     ) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
-        Box::new(
+        self.within_timeout(
             self.contract
                 .query("balanceOf", address, None, Options::default(), None)
-                .map_err(|e| QueryFailed(e.to_string())),
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
+        )
+    }
+
+    fn get_token_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+    ) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
+        self.within_timeout(
+            self.contract
+                .query(
+                    "allowance",
+                    (owner, spender),
+                    None,
+                    Options::default(),
+                    None,
+                )
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
         )
     }
 
     fn get_gas_price(&self) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
-        Box::new(
+        self.within_timeout(
             self.web3
                 .eth()
                 .gas_price()
-                .map_err(|e| QueryFailed(e.to_string())),
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
         )
     }
 
     fn get_block_number(&self) -> Box<dyn Future<Item = U64, Error = BlockchainError>> {
-        Box::new(
+        self.within_timeout(
             self.web3
                 .eth()
                 .block_number()
-                .map_err(|e| QueryFailed(e.to_string())),
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
+        )
+    }
+
+    fn get_latest_block_info(
+        &self,
+    ) -> Box<dyn Future<Item = LatestBlockInfo, Error = BlockchainError>> {
+        self.within_timeout(
+            self.web3
+                .transport()
+                .execute(
+                    "eth_getBlockByNumber",
+                    vec![Value::from("latest"), Value::from(false)],
+                )
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string()))
+                .and_then(|value| {
+                    serde_json::from_value::<LatestBlockInfo>(value).map_err(|e| {
+                        BlockchainError::from_query_failure(format!("Invalid latest block: {}", e))
+                    })
+                }),
+        )
+    }
+
+    fn get_chain_id(&self) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
+        self.within_timeout(
+            self.web3
+                .transport()
+                .execute("eth_chainId", vec![])
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string()))
+                .and_then(|value| {
+                    serde_json::from_value::<U256>(value).map_err(|e| {
+                        BlockchainError::from_query_failure(format!("Invalid chain id: {}", e))
+                    })
+                }),
+        )
+    }
+
+    // The base fees reported by eth_feeHistory end with that of the block to come. A service that
+    // doesn't know the method, or a chain without base fees, leaves it unknown
+    fn get_next_base_fee(&self) -> Box<dyn Future<Item = Option<U256>, Error = BlockchainError>> {
+        self.within_timeout(
+            self.web3
+                .transport()
+                .execute(
+                    "eth_feeHistory",
+                    vec![Value::from("0x1"), Value::from("latest"), json!([])],
+                )
+                .then(|result| match result {
+                    Ok(value) => serde_json::from_value::<FeeHistory>(value)
+                        .map(|fee_history| fee_history.base_fee_per_gas.last().copied())
+                        .map_err(|e| {
+                            BlockchainError::from_query_failure(format!(
+                                "Invalid fee history: {}",
+                                e
+                            ))
+                        }),
+                    Err(Error::Rpc(_)) => Ok(None),
+                    Err(e) => Err(BlockchainError::from_query_failure(e.to_string())),
+                }),
         )
     }
 
+    // A service that can't take batches answers one with an error, or with a single response
+    fn supports_batch_requests(&self) -> Box<dyn Future<Item = bool, Error = BlockchainError>> {
+        self.web3_batch.transport().execute("eth_chainId", vec![]);
+
+        self.within_timeout(self.web3_batch.transport().submit_batch().then(
+            |result| match result {
+                Ok(responses) => Ok(responses.len() == 1 && responses[0].is_ok()),
+                Err(Error::InvalidResponse(_)) | Err(Error::Rpc(_)) => Ok(false),
+                Err(e) => Err(BlockchainError::from_query_failure(e.to_string())),
+            },
+        ))
+    }
+
     fn get_transaction_id(
         &self,
         address: Address,
     ) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
-        Box::new(
+        self.within_timeout(
             self.web3
                 .eth()
                 .transaction_count(address, Some(BlockNumber::Pending))
-                .map_err(move |e| QueryFailed(format!("{} for wallet {}", e, address))),
+                .map_err(move |e| {
+                    BlockchainError::from_query_failure(format!("{} for wallet {}", e, address))
+                }),
+        )
+    }
+
+    fn get_confirmed_transaction_count(
+        &self,
+        address: Address,
+    ) -> Box<dyn Future<Item = U256, Error = BlockchainError>> {
+        self.within_timeout(
+            self.web3
+                .eth()
+                .transaction_count(address, Some(BlockNumber::Latest))
+                .map_err(move |e| {
+                    BlockchainError::from_query_failure(format!("{} for wallet {}", e, address))
+                }),
         )
     }
 
@@ -126,11 +285,44 @@ impl LowBlockchainInt for LowBlockchainIntWeb3 {
             self.web3_batch.eth().transaction_receipt(hash);
         });
 
-        Box::new(
+        self.within_timeout(
             self.web3_batch
                 .transport()
                 .submit_batch()
-                .map_err(|e| QueryFailed(e.to_string())),
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
+        )
+    }
+
+    fn get_transaction_receipts_one_by_one(
+        &self,
+        hash_vec: Vec<H256>,
+    ) -> Box<dyn Future<Item = Vec<Result<Value, Error>>, Error = BlockchainError>> {
+        let requests = hash_vec
+            .into_iter()
+            .map(|hash| {
+                self.web3
+                    .transport()
+                    .execute("eth_getTransactionReceipt", vec![json!(hash)])
+                    .then(Ok::<_, BlockchainError>)
+            })
+            .collect::<Vec<_>>();
+
+        self.within_timeout(join_all(requests))
+    }
+
+    fn get_transactions_in_batch(
+        &self,
+        hash_vec: Vec<H256>,
+    ) -> Box<dyn Future<Item = Vec<Result<Value, Error>>, Error = BlockchainError>> {
+        hash_vec.into_iter().for_each(|hash| {
+            self.web3_batch.eth().transaction(TransactionId::Hash(hash));
+        });
+
+        self.within_timeout(
+            self.web3_batch
+                .transport()
+                .submit_batch()
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
         )
     }
 
@@ -138,25 +330,98 @@ impl LowBlockchainInt for LowBlockchainIntWeb3 {
         self.contract.address()
     }
 
+    fn get_token_name(&self) -> Box<dyn Future<Item = String, Error = BlockchainError>> {
+        self.within_timeout(
+            self.contract
+                .query("name", (), None, Options::default(), None)
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
+        )
+    }
+
+    fn get_token_symbol(&self) -> Box<dyn Future<Item = String, Error = BlockchainError>> {
+        self.within_timeout(
+            self.contract
+                .query("symbol", (), None, Options::default(), None)
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
+        )
+    }
+
+    fn get_token_decimals(&self) -> Box<dyn Future<Item = u8, Error = BlockchainError>> {
+        self.within_timeout(
+            self.contract
+                .query("decimals", (), None, Options::default(), None)
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string()))
+                .and_then(|decimals: U256| {
+                    if decimals > U256::from(u8::MAX) {
+                        Err(BlockchainError::from_query_failure(format!(
+                            "Invalid decimals: {}",
+                            decimals
+                        )))
+                    } else {
+                        Ok(decimals.low_u32() as u8)
+                    }
+                }),
+        )
+    }
+
+    // A reverted call comes back as an error of the blockchain service; errors of other kinds
+    // leave the outcome unknown
+    fn simulate_transfer(
+        &self,
+        from: Address,
+        data: Vec<u8>,
+    ) -> Box<dyn Future<Item = TransferSimulation, Error = BlockchainError>> {
+        let call = json!({
+            "from": from,
+            "to": self.contract.address(),
+            "data": Bytes(data),
+        });
+        self.within_timeout(
+            self.web3
+                .transport()
+                .execute("eth_call", vec![call, Value::from("latest")])
+                .then(|result| match result {
+                    Ok(value) => serde_json::from_value::<Bytes>(value)
+                        .map(|output| transfer_output_verdict(&output.0))
+                        .map_err(|e| {
+                            BlockchainError::from_query_failure(format!(
+                                "Invalid output of the call: {}",
+                                e
+                            ))
+                        }),
+                    Err(Error::Rpc(rpc_error))
+                        if is_revert(rpc_error.code.code(), &rpc_error.message) =>
+                    {
+                        Ok(TransferSimulation::Refused(rpc_error.message))
+                    }
+                    Err(e) => Err(BlockchainError::from_query_failure(e.to_string())),
+                }),
+        )
+    }
+
     fn get_transaction_logs(
         &self,
         filter: Filter,
     ) -> Box<dyn Future<Item = Vec<Log>, Error = BlockchainError>> {
-        Box::new(
+        self.within_timeout(
             self.web3
                 .eth()
                 .logs(filter)
-                .map_err(|e| QueryFailed(e.to_string())),
+                .map_err(|e| BlockchainError::from_query_failure(e.to_string())),
         )
     }
 
-    fn get_web3_batch(&self) -> Web3<Batch<Http>> {
+    fn get_web3_batch(&self) -> Web3<Batch<MeteredHttp>> {
         self.web3_batch.clone()
     }
 }
 
 impl LowBlockchainIntWeb3 {
-    pub fn new(transport: Http, contract_address: Address) -> Self {
+    pub fn new(
+        transport: MeteredHttp,
+        contract_address: Address,
+        request_timeout: Duration,
+    ) -> Self {
         let web3 = Web3::new(transport.clone());
         let web3_batch = Web3::new(Batch::new(transport));
         let contract = Contract::from_json(web3.eth(), contract_address, CONTRACT_ABI.as_bytes())
@@ -166,25 +431,91 @@ impl LowBlockchainIntWeb3 {
             web3,
             web3_batch,
             contract,
+            request_timeout,
         }
     }
+
+    fn within_timeout<T, F>(&self, request: F) -> Box<dyn Future<Item = T, Error = BlockchainError>>
+    where
+        T: 'static,
+        F: Future<Item = T, Error = BlockchainError> + 'static,
+    {
+        let timeout = self.request_timeout;
+        with_timeout(request, timeout, move || {
+            BlockchainError::NoResponse(timeout)
+        })
+    }
+}
+
+// Tokens that don't return a bool from transfer exist; only an explicit false is a refusal
+fn transfer_output_verdict(output: &[u8]) -> TransferSimulation {
+    if output.len() == 32 && output.iter().all(|byte| *byte == 0) {
+        TransferSimulation::Refused("The token contract returned false".to_string())
+    } else {
+        TransferSimulation::Passed
+    }
+}
+
+// Geth and its kin give the code 3 to a revert carrying a reason, others only name it
+fn is_revert(code: i64, message: &str) -> bool {
+    code == 3 || message.to_lowercase().contains("revert")
+}
+
+// Gives up on a request the blockchain service hasn't answered in time, so that a hung service
+// can't hold up a scan forever. As with the retry pause, a timer of the actor system isn't
+// available to every caller, so the deadline is kept on its own thread
+pub fn with_timeout<T, E, F, G>(
+    request: F,
+    timeout: Duration,
+    on_timeout: G,
+) -> Box<dyn Future<Item = T, Error = E>>
+where
+    T: 'static,
+    E: 'static,
+    F: Future<Item = T, Error = E> + 'static,
+    G: FnOnce() -> E + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(timeout);
+        let _ = tx.send(());
+    });
+    Box::new(request.select2(rx).then(move |result| match result {
+        Ok(Either::A((item, _))) => Ok(item),
+        Err(Either::A((e, _))) => Err(e),
+        Ok(Either::B(_)) | Err(Either::B(_)) => Err(on_timeout()),
+    }))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::blockchain::blockchain_interface::blockchain_interface_web3::TRANSACTION_LITERAL;
-    use crate::blockchain::blockchain_interface::data_structures::errors::BlockchainError::QueryFailed;
-    use crate::blockchain::blockchain_interface::{BlockchainError, BlockchainInterface};
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::LowBlockchainIntWeb3;
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::REQUESTS_IN_PARALLEL;
+    use crate::blockchain::blockchain_interface::data_structures::errors::BlockchainError::{
+        NoResponse, QueryFailed,
+    };
+    use crate::blockchain::blockchain_interface::lower_level_interface::LowBlockchainInt;
+    use crate::blockchain::blockchain_interface::{BlockchainError, BlockchainReader};
+    use crate::blockchain::rpc_usage::MeteredHttp;
     use crate::blockchain::test_utils::make_blockchain_interface_web3;
     use crate::sub_lib::wallet::Wallet;
     use crate::test_utils::make_wallet;
     use ethereum_types::{H256, U64};
     use futures::Future;
-    use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
+    use masq_lib::test_utils::mock_blockchain_client_server::{
+        abi_encoded_string, MBCSBuilder, TOKEN_DECIMALS_SELECTOR, TOKEN_NAME_SELECTOR,
+        TOKEN_SYMBOL_SELECTOR,
+    };
+    use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use masq_lib::utils::find_free_port;
+    use serde_json::Value;
+    use std::net::Ipv4Addr;
     use std::str::FromStr;
+    use std::time::{Duration, Instant};
+    use web3::transports::Http;
     use web3::types::{BlockNumber, Bytes, FilterBuilder, Log, TransactionReceipt, U256};
-    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TxReceipt, TxStatus};
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{LatestBlockInfo, TxReceipt, TxStatus};
 
     #[test]
     fn get_transaction_fee_balance_works() {
@@ -260,6 +591,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_gas_price_gives_up_on_an_unresponsive_blockchain_service() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port).unresponsive().start();
+        let (_event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = LowBlockchainIntWeb3::new(
+            MeteredHttp::from(transport),
+            TEST_DEFAULT_CHAIN.rec().contract,
+            Duration::from_millis(300),
+        );
+        let before = Instant::now();
+
+        let error = subject.get_gas_price().wait().unwrap_err();
+
+        let elapsed = before.elapsed();
+        assert_eq!(error, NoResponse(Duration::from_millis(300)));
+        assert!(
+            elapsed >= Duration::from_millis(300) && elapsed < Duration::from_secs(3),
+            "{:?}",
+            elapsed
+        );
+    }
+
     #[test]
     fn get_block_number_works() {
         let port = find_free_port();
@@ -295,6 +653,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_latest_block_info_works() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .raw_response(
+                r#"{"jsonrpc": "2.0", "result": {"number": "0x3B3", "baseFeePerGas": "0x3B9ACA00", "gasLimit": "0x1C9C380"}, "id": 1}"#
+                    .to_string(),
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_latest_block_info().wait();
+
+        assert_eq!(
+            result,
+            Ok(LatestBlockInfo {
+                base_fee_per_gas_opt: Some(U256::from(1_000_000_000)),
+                gas_limit: U256::from(30_000_000),
+            })
+        );
+    }
+
+    #[test]
+    fn get_latest_block_info_tolerates_a_chain_without_base_fee() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .raw_response(
+                r#"{"jsonrpc": "2.0", "result": {"number": "0x3B3", "gasLimit": "0x1C9C380"}, "id": 1}"#
+                    .to_string(),
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_latest_block_info().wait();
+
+        assert_eq!(
+            result,
+            Ok(LatestBlockInfo {
+                base_fee_per_gas_opt: None,
+                gas_limit: U256::from(30_000_000),
+            })
+        );
+    }
+
+    #[test]
+    fn get_latest_block_info_returns_an_error_for_unintelligible_response() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("block".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_latest_block_info().wait();
+
+        match result {
+            Err(QueryFailed(msg)) if msg.starts_with("Invalid latest block: ") => (),
+            x => panic!("we expected an invalid latest block error but got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn get_chain_id_works() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x89".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_chain_id().wait();
+
+        assert_eq!(result, Ok(137.into()));
+    }
+
+    #[test]
+    fn get_chain_id_returns_an_error_for_unintelligible_response() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("polygon".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let error = subject.lower_interface().get_chain_id().wait().unwrap_err();
+
+        match error {
+            QueryFailed(msg) if msg.starts_with("Invalid chain id: ") => (),
+            x => panic!("Expected an invalid chain id, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn get_next_base_fee_takes_the_last_base_fee_of_the_fee_history() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .raw_response(
+                r#"{"jsonrpc": "2.0", "id": 1, "result": {"oldestBlock": "0x10", "baseFeePerGas": ["0x1DCD6500", "0x218711A0"], "gasUsedRatio": [0.9]}}"#
+                    .to_string(),
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_next_base_fee().wait();
+
+        assert_eq!(result, Ok(Some(U256::from(562_500_000))));
+        let requests = blockchain_client_server.requests();
+        assert!(requests[0].contains(r#""method":"eth_feeHistory","params":["0x1","latest",[]]"#));
+    }
+
+    #[test]
+    fn get_next_base_fee_is_unknown_if_the_blockchain_service_has_no_fee_history() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .err_response(
+                -32601,
+                "the method eth_feeHistory does not exist".to_string(),
+                1,
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_next_base_fee().wait();
+
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn get_next_base_fee_returns_an_error_for_unintelligible_response() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x1DCD6500".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let error = subject
+            .lower_interface()
+            .get_next_base_fee()
+            .wait()
+            .unwrap_err();
+
+        match error {
+            QueryFailed(msg) if msg.starts_with("Invalid fee history: ") => (),
+            x => panic!("Expected an invalid fee history, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn supports_batch_requests_is_true_for_an_answered_batch() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .ok_response("0x89".to_string(), 1)
+            .end_batch()
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().supports_batch_requests().wait();
+
+        assert_eq!(result, Ok(true));
+        let requests = blockchain_client_server.requests();
+        assert!(requests[0].contains(r#"[{"jsonrpc":"2.0","method":"eth_chainId""#));
+    }
+
+    #[test]
+    fn supports_batch_requests_is_false_for_a_batch_answered_by_a_single_response() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .err_response(-32600, "batch requests are not supported".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().supports_batch_requests().wait();
+
+        assert_eq!(result, Ok(false));
+    }
+
+    #[test]
+    fn supports_batch_requests_is_false_for_a_batch_answered_by_errors() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .err_response(-32600, "batch requests are not supported".to_string(), 1)
+            .end_batch()
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().supports_batch_requests().wait();
+
+        assert_eq!(result, Ok(false));
+    }
+
+    #[test]
+    fn supports_batch_requests_returns_an_error_if_the_blockchain_service_cannot_be_reached() {
+        let subject = make_blockchain_interface_web3(find_free_port());
+
+        let result = subject.lower_interface().supports_batch_requests().wait();
+
+        match result {
+            Err(QueryFailed(_)) => (),
+            x => panic!("Expected a failed query, got {:?}", x),
+        }
+    }
+
     #[test]
     fn get_transaction_id_works() {
         let port = find_free_port();
@@ -337,6 +896,48 @@ mod tests {
         };
     }
 
+    #[test]
+    fn get_confirmed_transaction_count_works() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x1f".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let wallet = &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap();
+
+        let result = subject
+            .lower_interface()
+            .get_confirmed_transaction_count(wallet.address())
+            .wait();
+
+        assert_eq!(result, Ok(31.into()));
+    }
+
+    #[test]
+    fn get_confirmed_transaction_count_returns_an_error_for_unintelligible_response() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0xFFFQ".to_string(), 0)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject
+            .lower_interface()
+            .get_confirmed_transaction_count(
+                Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                    .unwrap()
+                    .address(),
+            )
+            .wait();
+
+        match result {
+            Err(BlockchainError::QueryFailed(msg)) if msg.contains("invalid hex character: Q") => {
+                ()
+            }
+            x => panic!("Expected complaint about hex character, but got {:?}", x),
+        };
+    }
+
     #[test]
     fn get_token_balance_can_retrieve_token_balance_of_a_wallet() {
         let port = find_free_port();
@@ -361,6 +962,40 @@ mod tests {
         assert_eq!(result, U256::from(65_535));
     }
 
+    #[test]
+    fn get_token_allowance_asks_the_token_contract_for_the_allowance_of_the_spender() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response(
+                "0x00000000000000000000000000000000000000000000000000000000000F4240".to_string(),
+                0,
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let owner = make_wallet("owner").address();
+        let spender = make_wallet("spender").address();
+
+        let result = subject
+            .lower_interface()
+            .get_token_allowance(owner, spender)
+            .wait()
+            .unwrap();
+
+        assert_eq!(result, U256::from(1_000_000));
+        let requests = blockchain_client_server.requests();
+        // allowance(address,address)
+        assert!(
+            requests[0].contains("\"method\":\"eth_call\"")
+                && requests[0].contains(&format!(
+                    "0xdd62ed3e000000000000000000000000{}000000000000000000000000{}",
+                    &format!("{:?}", owner)[2..],
+                    &format!("{:?}", spender)[2..]
+                )),
+            "{}",
+            requests[0]
+        );
+    }
+
     #[test]
     fn get_token_balance_returns_error_for_unintelligible_response_to_token_balance() {
         let port = find_free_port();
@@ -394,6 +1029,46 @@ mod tests {
         )
     }
 
+    #[test]
+    fn get_token_metadata_works() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response(abi_encoded_string("MASQ (PoS)"), 1)
+            .ok_response(abi_encoded_string("MASQ"), 2)
+            .ok_response(format!("{:#066x}", 18), 3)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let lower_interface = subject.lower_interface();
+
+        let name = lower_interface.get_token_name().wait();
+        let symbol = lower_interface.get_token_symbol().wait();
+        let decimals = lower_interface.get_token_decimals().wait();
+
+        assert_eq!(name, Ok("MASQ (PoS)".to_string()));
+        assert_eq!(symbol, Ok("MASQ".to_string()));
+        assert_eq!(decimals, Ok(18));
+        let requests = blockchain_client_server.requests();
+        assert!(requests[0].contains(&format!("\"data\":\"{}\"", TOKEN_NAME_SELECTOR)));
+        assert!(requests[1].contains(&format!("\"data\":\"{}\"", TOKEN_SYMBOL_SELECTOR)));
+        assert!(requests[2].contains(&format!("\"data\":\"{}\"", TOKEN_DECIMALS_SELECTOR)));
+    }
+
+    #[test]
+    fn get_token_decimals_returns_an_error_for_decimals_out_of_range() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response(format!("{:#066x}", 256), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.lower_interface().get_token_decimals().wait();
+
+        assert_eq!(
+            result,
+            Err(QueryFailed("Invalid decimals: 256".to_string()))
+        );
+    }
+
     #[test]
     fn get_transaction_logs_works() {
         let port = find_free_port();
@@ -545,6 +1220,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_transactions_in_batch_works() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .raw_response(
+                r#"{
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "hash": "0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                    "from": "0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182",
+                    "input": "0xa9059cbb"
+                }
+            }"#
+                .to_string(),
+            )
+            .raw_response(r#"{ "jsonrpc": "2.0", "id": 2, "result": null }"#.to_string())
+            .end_batch()
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let hash_1 =
+            H256::from_str("955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681")
+                .unwrap();
+        let hash_2 =
+            H256::from_str("955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0682")
+                .unwrap();
+
+        let result = subject
+            .lower_interface()
+            .get_transactions_in_batch(vec![hash_1, hash_2])
+            .wait()
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        let transaction = result[0].as_ref().unwrap();
+        assert_eq!(
+            transaction["from"],
+            "0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182"
+        );
+        assert_eq!(transaction["input"], "0xa9059cbb");
+        assert_eq!(result[1].as_ref().unwrap(), &Value::Null);
+    }
+
+    #[test]
+    fn get_transaction_receipts_one_by_one_sends_a_request_for_each_receipt() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .scripted_result("eth_getTransactionReceipt", Value::Null)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let hash_1 =
+            H256::from_str("955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681")
+                .unwrap();
+        let hash_2 =
+            H256::from_str("955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0682")
+                .unwrap();
+
+        let result = subject
+            .lower_interface()
+            .get_transaction_receipts_one_by_one(vec![hash_1, hash_2])
+            .wait()
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].as_ref().unwrap(), &Value::Null);
+        assert_eq!(result[1].as_ref().unwrap(), &Value::Null);
+        let requests = blockchain_client_server.requests();
+        assert_eq!(requests.len(), 2);
+        requests
+            .iter()
+            .zip([hash_1, hash_2])
+            .for_each(|(request, hash)| {
+                assert!(request.contains(&format!(
+                    r#"{{"jsonrpc":"2.0","method":"eth_getTransactionReceipt","params":["{:?}"]"#,
+                    hash
+                )))
+            });
+    }
+
     #[test]
     fn transaction_receipt_can_be_converted_to_successful_transaction() {
         let tx_receipt: TxReceipt = create_tx_receipt(
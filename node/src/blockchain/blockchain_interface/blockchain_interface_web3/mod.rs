@@ -1,30 +1,50 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 pub mod lower_level_interface_web3;
-mod utils;
+mod transfer_logs;
+pub mod utils;
 
 use std::cmp::PartialEq;
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
 use crate::blockchain::blockchain_interface::data_structures::errors::{BlockchainError, PayableTransactionError};
-use crate::blockchain::blockchain_interface::data_structures::{BlockchainTransaction, ProcessedPayableFallible};
+use crate::blockchain::blockchain_interface::data_structures::{BlockchainTransaction, ProcessedPayableFallible, RecheckedTransactions};
 use crate::blockchain::blockchain_interface::lower_level_interface::LowBlockchainInt;
 use crate::blockchain::blockchain_interface::RetrievedBlockchainTransactions;
-use crate::blockchain::blockchain_interface::{BlockchainAgentBuildError, BlockchainInterface};
+use crate::blockchain::blockchain_interface::{
+    BlockchainAgentBuildError, BlockchainInterface, BlockchainReader, BlockchainWriter,
+};
 use crate::sub_lib::wallet::Wallet;
-use futures::{Future};
+use crate::sub_lib::wei::Wei;
+use futures::future::Loop;
+use futures::sync::oneshot;
+use futures::{future, stream, Future, Stream};
+use rand::Rng;
+use serde_json::Value;
+use std::collections::HashMap;
 use indoc::indoc;
+use itertools::Itertools;
 use masq_lib::blockchains::chains::Chain;
 use masq_lib::logger::Logger;
 use std::convert::{From, TryInto};
 use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use actix::Recipient;
 use ethereum_types::U64;
 use web3::transports::{EventLoopHandle, Http};
-use web3::types::{Address, Log, H256, U256, FilterBuilder, TransactionReceipt, BlockNumber};
+use web3::types::{Address, Bytes, Log, H256, U256, FilterBuilder, TransactionReceipt, BlockNumber};
 use crate::accountant::db_access_objects::payable_dao::PayableAccount;
-use crate::blockchain::blockchain_bridge::{BlockMarker, BlockScanRange, PendingPayableFingerprintSeeds};
-use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{LowBlockchainIntWeb3, TransactionReceiptResult, TxReceipt, TxStatus};
-use crate::blockchain::blockchain_interface::blockchain_interface_web3::utils::{create_blockchain_agent_web3, send_payables_within_batch, BlockchainAgentFutureResult};
+use crate::blockchain::blockchain_bridge::{BlockMarker, BlockScanRange, PendingPayableFingerprintSeeds, PendingPayableReplacement, ReplacementBasis};
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{BlockchainCapabilities, LowBlockchainIntWeb3, TokenMetadata, TransactionReceiptResult, TxReceipt, TxStatus};
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::transfer_logs::{decode_transfer_logs, DecodedTransfer};
+use crate::blockchain::fee_history_tracker::blend_with_oracle;
+use crate::blockchain::futures_compat::{legacy, LegacyFutureExt};
+use crate::blockchain::payment_memo::PaymentMemo;
+use crate::blockchain::rpc_usage::{MeteredHttp, RpcUsageMeter};
+use crate::blockchain::receivable_scan_records::{block_number_opt, OriginalPayer, ReceivableScanRecord, ReceivableScanRecorder};
+use crate::accountant::db_access_objects::utils::now_time_t;
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::utils::{create_blockchain_agent_web3, send_payables, send_replacement, send_sweep, send_void, simulate_payments, split_into_sub_batches, sub_batch_failure, BlockchainAgentFutureResult};
 
 const CONTRACT_ABI: &str = indoc!(
     r#"[{
@@ -36,6 +56,14 @@ const CONTRACT_ABI: &str = indoc!(
     "stateMutability":"view",
     "type":"function"
     },{
+    "constant":true,
+    "inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],
+    "name":"allowance",
+    "outputs":[{"name":"","type":"uint256"}],
+    "payable":false,
+    "stateMutability":"view",
+    "type":"function"
+    },{
     "constant":false,
     "inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],
     "name":"transfer",
@@ -43,6 +71,30 @@ const CONTRACT_ABI: &str = indoc!(
     "payable":false,
     "stateMutability":"nonpayable",
     "type":"function"
+    },{
+    "constant":true,
+    "inputs":[],
+    "name":"name",
+    "outputs":[{"name":"","type":"string"}],
+    "payable":false,
+    "stateMutability":"view",
+    "type":"function"
+    },{
+    "constant":true,
+    "inputs":[],
+    "name":"symbol",
+    "outputs":[{"name":"","type":"string"}],
+    "payable":false,
+    "stateMutability":"view",
+    "type":"function"
+    },{
+    "constant":true,
+    "inputs":[],
+    "name":"decimals",
+    "outputs":[{"name":"","type":"uint8"}],
+    "payable":false,
+    "stateMutability":"view",
+    "type":"function"
     }]"#
 );
 
@@ -53,10 +105,20 @@ pub const TRANSACTION_LITERAL: H256 = H256([
 
 pub const TRANSFER_METHOD_ID: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
 
+// disperseToken(address,address[],uint256[])
+pub const DISPERSE_TOKEN_METHOD_ID: [u8; 4] = [0xc7, 0x3a, 0x2d, 0x60];
+
+// execute((address,address,uint256,uint256,uint256,bytes),bytes) of an ERC-2771 forwarder
+pub const FORWARDER_EXECUTE_METHOD_ID: [u8; 4] = [0x47, 0x15, 0x3f, 0x82];
+
 pub const REQUESTS_IN_PARALLEL: usize = 1;
 
 pub const FRESH_START_BLOCK: u64 = 0;
 
+// Pause before the first repeated attempt at a request of the received-payments scan; it doubles
+// with every further attempt, a random part of it cut off so that Nodes don't retry in lockstep
+pub const RECEIVABLE_SCAN_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
 pub const BLOCKCHAIN_SERVICE_URL_NOT_SPECIFIED: &str =
     "To avoid being delinquency-banned, you should \
 restart the Node with a value for blockchain-service-url";
@@ -65,9 +127,33 @@ pub struct BlockchainInterfaceWeb3 {
     pub logger: Logger,
     chain: Chain,
     gas_limit_const_part: u128,
+    // Contracts paying on behalf of someone else; payments coming from them are credited to
+    // the original payer instead
+    payment_forwarders: Vec<Address>,
+    // A token contract being migrated away from, still searched for received payments
+    legacy_contract_opt: Option<Address>,
+    // How many of the latest blocks are considered still exposed to a chain reorganization
+    receivable_confirmation_blocks: u64,
+    // How many times a request of the received-payments scan is tried when it fails transiently
+    receivable_scan_attempts: u16,
+    retry_base_delay: Duration,
+    // How long any single request waits for the blockchain service to answer
+    request_timeout: Duration,
+    // Tags the payments this Node makes, and has the memos of the payments it receives reported
+    payment_memo_opt: Option<PaymentMemo>,
+    // Whether every transfer of a batch is tried out with eth_call before the batch is signed
+    payment_simulation: bool,
+    // The most payments sent in one batch; longer lists are sent as consecutive sub-batches
+    max_batch_size: u16,
+    // Keeps what the blockchain service answered to the latest received-payments scans
+    receivable_scan_recorder_opt: Option<ReceivableScanRecorder>,
+    // What the token contract told about itself, once asked
+    token_metadata_arc: Arc<Mutex<Option<TokenMetadata>>>,
+    // What the blockchain service proved to offer when probed
+    capabilities_arc: Arc<Mutex<BlockchainCapabilities>>,
     // This must not be dropped for Web3 requests to be completed
     _event_loop_handle: EventLoopHandle,
-    transport: Http,
+    transport: MeteredHttp,
 }
 
 pub const GWEI: U256 = U256([1_000_000_000u64, 0, 0, 0]);
@@ -86,10 +172,25 @@ impl BlockchainInterface for BlockchainInterfaceWeb3 {
         self.chain
     }
 
+    fn as_reader(&self) -> &dyn BlockchainReader {
+        self
+    }
+
+    fn as_writer(&self) -> &dyn BlockchainWriter {
+        self
+    }
+
+    fn rpc_usage_meter(&self) -> RpcUsageMeter {
+        self.transport.meter()
+    }
+}
+
+impl BlockchainReader for BlockchainInterfaceWeb3 {
     fn lower_interface(&self) -> Box<dyn LowBlockchainInt> {
         Box::new(LowBlockchainIntWeb3::new(
             self.transport.clone(),
             self.contract_address(),
+            self.request_timeout,
         ))
     }
 
@@ -97,72 +198,237 @@ impl BlockchainInterface for BlockchainInterfaceWeb3 {
         &self,
         start_block_marker: BlockMarker,
         scan_range: BlockScanRange,
-        recipient: Address,
+        recipients: Vec<Address>,
     ) -> Box<dyn Future<Item = RetrievedBlockchainTransactions, Error = BlockchainError>> {
         let lower_level_interface = self.lower_interface();
+        let block_number_interface = self.lower_interface();
+        let logs_interface = self.lower_interface();
+        let memos_interface = self.lower_interface();
+        let reads_payment_memos = self.payment_memo_opt.is_some();
         let logger = self.logger.clone();
         let contract_address = lower_level_interface.get_contract_address();
+        let legacy_contract_opt = self.legacy_contract_opt;
         let num_chain_id = self.chain.rec().num_chain_id;
+        let payment_forwarders = self.payment_forwarders.clone();
+        let confirmation_blocks = self.receivable_confirmation_blocks;
+        let attempts = self.receivable_scan_attempts;
+        let retry_base_delay = self.retry_base_delay;
+        let recorder_opt = self.receivable_scan_recorder_opt.clone();
+        let chain_name = self.chain.rec().literal_identifier;
+        let recheck_from_block_opt =
+            Self::recheck_from_block_opt(start_block_marker, confirmation_blocks);
+        let query_start_block_marker = match recheck_from_block_opt {
+            Some(recheck_from_block) => BlockMarker::Value(recheck_from_block),
+            None => start_block_marker,
+        };
+        let block_number_future = Self::request_with_retries(
+            "block number",
+            attempts,
+            retry_base_delay,
+            &logger,
+            move || block_number_interface.get_block_number(),
+        );
         Box::new(
-            lower_level_interface.get_block_number().then(move |rpc_block_number_result| {
-                let start_block_number = match start_block_marker {
-                    BlockMarker::Uninitialized => match rpc_block_number_result {
-                        Ok(latest_block) => { BlockNumber::Number(latest_block) }
+            block_number_future.then(move |rpc_block_number_result| {
+                let latest_block_opt = rpc_block_number_result.as_ref().ok().map(|latest_block| latest_block.as_u64());
+                // Payments in the youngest blocks could still be reorganized away; they are only
+                // reported as pending, to be credited by a later scan once buried deep enough
+                let confirmed_block_number_result = rpc_block_number_result.map(|latest_block| {
+                    U64::from(latest_block.as_u64().saturating_sub(confirmation_blocks))
+                });
+                let start_block_number = match query_start_block_marker {
+                    BlockMarker::Uninitialized => match confirmed_block_number_result {
+                        Ok(confirmed_block) => { BlockNumber::Number(confirmed_block) }
                         Err(_) => { BlockNumber::Latest }
                     },
                     BlockMarker::Value(number) => BlockNumber::Number(U64::from(number)),
                 };
-                let end_block_marker = Self::calculate_end_block_marker(start_block_marker, scan_range, rpc_block_number_result, &logger);
+                let end_block_marker = Self::calculate_end_block_marker(query_start_block_marker, scan_range, confirmed_block_number_result, &logger);
                 let end_block_number = match end_block_marker {
                     BlockMarker::Uninitialized => { BlockNumber::Latest }
                     BlockMarker::Value(number) => { BlockNumber::Number(U64::from(number)) }
                 };
+                let pending_to_block_opt = Self::pending_to_block_opt(end_block_marker, latest_block_opt, confirmation_blocks);
                 debug!(
                     logger,
                     "Retrieving transactions from start block: {:?} to end block: {:?} for: {} chain_id: {} contract: {:#x}",
                     start_block_number,
                     end_block_number,
-                    recipient,
+                    recipients.iter().map(|recipient| recipient.to_string()).join(", "),
                     num_chain_id,
                     contract_address
                 );
+                if let Some(legacy_contract) = legacy_contract_opt {
+                    debug!(logger, "Retrieving transactions from the legacy contract: {:#x} as well", legacy_contract);
+                }
+                let query_end_block_number = match pending_to_block_opt {
+                    Some(pending_to_block) => {
+                        debug!(logger, "Looking for pending payments up to block: {}", pending_to_block);
+                        BlockNumber::Number(U64::from(pending_to_block))
+                    }
+                    None => end_block_number,
+                };
                 let filter = FilterBuilder::default()
-                    .address(vec![contract_address])
+                    .address(Self::receivable_contracts(contract_address, legacy_contract_opt))
                     .from_block(start_block_number)
-                    .to_block(end_block_number)
+                    .to_block(query_end_block_number)
                     .topics(
                         Some(vec![TRANSACTION_LITERAL]),
                         None,
-                        Some(vec![recipient.into()]),
+                        Some(recipients.iter().map(|recipient| (*recipient).into()).collect()),
                         None,
                     )
                     .build();
-                lower_level_interface.get_transaction_logs(filter)
+                Self::request_with_retries("transaction logs", attempts, retry_base_delay, &logger, move || logs_interface.get_transaction_logs(filter.clone()))
                     .then(move |logs_result| {
                         trace!(logger, "Transaction logs retrieval completed: {:?}", logs_result);
-                        match Self::handle_transaction_logs(logs_result, &logger) {
-                            Err(e) => Err(e),
-                            Ok(transactions) => {
-                                let new_start_block = Self::find_new_start_block(&transactions, start_block_marker, end_block_marker, &logger);
-                                Ok(RetrievedBlockchainTransactions {
-                                    new_start_block,
-                                    transactions,
-                                })
-                            }
+                        if let Ok(logs) = &logs_result {
+                            Self::report_transfer_origins(logs, contract_address, legacy_contract_opt, &logger);
                         }
+                        let forwarded_hashes = Self::hashes_of_forwarded_payments(&logs_result, &payment_forwarders);
+                        let memo_hashes = if reads_payment_memos { Self::hashes_of_received_payments(&logs_result, end_block_marker) } else { vec![] };
+                        let memos_logger = logger.clone();
+                        Self::find_original_payers(lower_level_interface.as_ref(), forwarded_hashes, logger.clone())
+                            .then(move |original_payers_result| {
+                                if let (Some(recorder), Ok(logs), Ok(original_payers)) = (&recorder_opt, &logs_result, &original_payers_result) {
+                                    let record = ReceivableScanRecord {
+                                        chain: chain_name.to_string(),
+                                        recorded_at: now_time_t(),
+                                        recipients,
+                                        start_block_opt: block_number_opt(start_block_marker),
+                                        recheck_from_block_opt,
+                                        end_block_opt: block_number_opt(end_block_marker),
+                                        logs: logs.clone(),
+                                        original_payers: original_payers
+                                            .iter()
+                                            .map(|(transaction_hash, payer)| OriginalPayer { transaction_hash: *transaction_hash, payer: *payer })
+                                            .collect(),
+                                    };
+                                    Self::record_scan(recorder, record, &logger)
+                                }
+                                match original_payers_result.and_then(|original_payers| Self::handle_transaction_logs(logs_result, &original_payers, &logger)) {
+                                    Err(e) => Err(e),
+                                    Ok(transactions) => {
+                                        let (pending, transactions) = Self::separate_pending_transactions(transactions, end_block_marker);
+                                        let (transactions, rechecked_opt) = Self::separate_rechecked_transactions(transactions, start_block_marker, end_block_marker, recheck_from_block_opt);
+                                        let new_start_block = match (Self::find_new_start_block(&transactions, start_block_marker, end_block_marker, &logger), start_block_marker) {
+                                            (BlockMarker::Value(new_start_block), BlockMarker::Value(start_block)) => BlockMarker::Value(new_start_block.max(start_block)),
+                                            (new_start_block, _) => new_start_block,
+                                        };
+                                        Ok(RetrievedBlockchainTransactions {
+                                            new_start_block,
+                                            transactions,
+                                            rechecked_opt,
+                                            pending,
+                                        })
+                                    }
+                                }
+                            })
+                            .and_then(move |retrieved| {
+                                Self::report_payment_memos(memos_interface.as_ref(), memo_hashes, memos_logger)
+                                    .map(move |_| retrieved)
+                            })
                     })
             },
             )
         )
     }
 
+    fn process_transaction_receipts(
+        &self,
+        transaction_hashes: Vec<H256>,
+    ) -> Box<dyn Future<Item = Vec<TransactionReceiptResult>, Error = BlockchainError>> {
+        let lower_interface = self.lower_interface();
+        let get_transaction_receipts = if self.capabilities().batch_requests {
+            lower_interface.get_transaction_receipt_in_batch(transaction_hashes.clone())
+        } else {
+            lower_interface.get_transaction_receipts_one_by_one(transaction_hashes.clone())
+        };
+
+        legacy(async move {
+            let batch_response = get_transaction_receipts.compat().await?;
+            Ok::<_, BlockchainError>(
+                batch_response
+                    .into_iter()
+                    .zip(transaction_hashes)
+                    .map(|(response, hash)| match response {
+                        Ok(result) => match serde_json::from_value::<TransactionReceipt>(result) {
+                            Ok(receipt) => TransactionReceiptResult::RpcResponse(receipt.into()),
+                            Err(e) => {
+                                if e.to_string().contains("invalid type: null") {
+                                    TransactionReceiptResult::RpcResponse(TxReceipt {
+                                        transaction_hash: hash,
+                                        status: TxStatus::Pending,
+                                    })
+                                } else {
+                                    TransactionReceiptResult::LocalError(e.to_string())
+                                }
+                            }
+                        },
+                        Err(e) => TransactionReceiptResult::LocalError(e.to_string()),
+                    })
+                    .collect::<Vec<TransactionReceiptResult>>(),
+            )
+        })
+    }
+
+    fn fetch_token_metadata(
+        &self,
+    ) -> Box<dyn Future<Item = TokenMetadata, Error = BlockchainError>> {
+        let lower_interface = self.lower_interface();
+        let token_metadata_arc = self.token_metadata_arc.clone();
+        Box::new(
+            lower_interface
+                .get_token_name()
+                .join3(
+                    lower_interface.get_token_symbol(),
+                    lower_interface.get_token_decimals(),
+                )
+                .map(move |(name, symbol, decimals)| {
+                    let metadata = TokenMetadata {
+                        name,
+                        symbol,
+                        decimals,
+                    };
+                    *token_metadata_arc
+                        .lock()
+                        .expect("Token metadata mutex is poisoned") = Some(metadata.clone());
+                    metadata
+                }),
+        )
+    }
+
+    fn fetch_capabilities(
+        &self,
+    ) -> Box<dyn Future<Item = BlockchainCapabilities, Error = BlockchainError>> {
+        let lower_interface = self.lower_interface();
+        let capabilities_arc = self.capabilities_arc.clone();
+        Box::new(
+            lower_interface
+                .supports_batch_requests()
+                .join(lower_interface.get_next_base_fee())
+                .map(move |(batch_requests, next_base_fee_opt)| {
+                    let capabilities = BlockchainCapabilities {
+                        batch_requests,
+                        fee_history: next_base_fee_opt.is_some(),
+                    };
+                    *capabilities_arc
+                        .lock()
+                        .expect("Capabilities mutex is poisoned") = capabilities;
+                    capabilities
+                }),
+        )
+    }
+}
+
+impl BlockchainWriter for BlockchainInterfaceWeb3 {
     fn build_blockchain_agent(
         &self,
         consuming_wallet: Wallet,
+        fee_history_estimate_opt: Option<u128>,
     ) -> Box<dyn Future<Item = Box<dyn BlockchainAgent>, Error = BlockchainAgentBuildError>> {
         let wallet_address = consuming_wallet.address();
         let gas_limit_const_part = self.gas_limit_const_part;
-        // TODO: Would it be better to wrap these 3 calls into a single batch call?
         let get_gas_price = self.lower_interface().get_gas_price();
         let get_transaction_fee_balance = self
             .lower_interface()
@@ -170,110 +436,251 @@ impl BlockchainInterface for BlockchainInterfaceWeb3 {
         let get_service_fee_balance = self
             .lower_interface()
             .get_service_fee_balance(wallet_address);
+        let get_latest_block_info = self.lower_interface().get_latest_block_info();
+        let get_next_base_fee: Box<dyn Future<Item = Option<U256>, Error = BlockchainError>> =
+            if self.capabilities().fee_history {
+                self.lower_interface().get_next_base_fee()
+            } else {
+                Box::new(future::ok(None))
+            };
         let chain = self.chain;
+        let logger = self.logger.clone();
 
-        Box::new(
-            get_gas_price
-                .map_err(BlockchainAgentBuildError::GasPrice)
-                .and_then(move |gas_price_wei| {
-                    get_transaction_fee_balance
-                        .map_err(move |e| {
-                            BlockchainAgentBuildError::TransactionFeeBalance(wallet_address, e)
-                        })
-                        .and_then(move |transaction_fee_balance| {
-                            get_service_fee_balance
-                                .map_err(move |e| {
-                                    BlockchainAgentBuildError::ServiceFeeBalance(wallet_address, e)
-                                })
-                                .and_then(move |masq_token_balance| {
-                                    let blockchain_agent_future_result =
-                                        BlockchainAgentFutureResult {
-                                            gas_price_wei,
-                                            transaction_fee_balance,
-                                            masq_token_balance,
-                                        };
-                                    Ok(create_blockchain_agent_web3(
-                                        gas_limit_const_part,
-                                        blockchain_agent_future_result,
-                                        consuming_wallet,
-                                        chain,
-                                    ))
-                                })
-                        })
-                }),
-        )
+        legacy(async move {
+            // The queries are awaited together rather than one by one. Should several of them
+            // fail, the error reported is that of the first in the order they're listed here
+            let (
+                gas_price_result,
+                transaction_fee_balance_result,
+                service_fee_balance_result,
+                latest_block_info_result,
+                next_base_fee_result,
+            ) = get_gas_price
+                .then(Ok::<_, BlockchainAgentBuildError>)
+                .join5(
+                    get_transaction_fee_balance.then(Ok),
+                    get_service_fee_balance.then(Ok),
+                    get_latest_block_info.then(Ok),
+                    get_next_base_fee.then(Ok),
+                )
+                .compat()
+                .await?;
+            let gas_price_wei = gas_price_result.map_err(BlockchainAgentBuildError::GasPrice)?;
+            let transaction_fee_balance = transaction_fee_balance_result
+                .map_err(|e| BlockchainAgentBuildError::TransactionFeeBalance(wallet_address, e))?;
+            let masq_token_balance = service_fee_balance_result
+                .map_err(|e| BlockchainAgentBuildError::ServiceFeeBalance(wallet_address, e))?;
+            // The agent can do without the block; it only sharpens its judgement of the fees
+            let latest_block_info_opt = match latest_block_info_result {
+                Ok(latest_block_info) => Some(latest_block_info),
+                Err(e) => {
+                    debug!(logger, "Couldn't fetch the latest block: {:?}", e);
+                    None
+                }
+            };
+            let base_fee_opt = latest_block_info_opt
+                .and_then(|block| block.base_fee_per_gas_opt)
+                .map(Wei::saturating_from_u256);
+            let next_base_fee_opt = match next_base_fee_result {
+                Ok(next_base_fee_opt) => next_base_fee_opt.map(Wei::saturating_from_u256),
+                Err(e) => {
+                    debug!(logger, "Couldn't fetch the fee history: {:?}", e);
+                    None
+                }
+            };
+            let blended_gas_price = Wei::new(blend_with_oracle(
+                gas_price_wei.as_u128(),
+                fee_history_estimate_opt,
+            ));
+            // A price under the base fee would leave our transactions waiting in the mempool; the
+            // base fee of the block to come, where the service tells it, may already be higher
+            let gas_price = match base_fee_opt.max(next_base_fee_opt) {
+                Some(base_fee) => blended_gas_price.max(base_fee),
+                None => blended_gas_price,
+            };
+            if let Some(base_fee) = base_fee_opt {
+                debug!(
+                    logger,
+                    "Agreed on a gas price of {}; the base fee of the latest block is {}",
+                    gas_price,
+                    base_fee
+                );
+            }
+            if let Some(next_base_fee) = next_base_fee_opt {
+                debug!(
+                    logger,
+                    "The base fee of the next block is expected to be {}", next_base_fee
+                );
+            }
+            let blockchain_agent_future_result = BlockchainAgentFutureResult {
+                gas_price,
+                transaction_fee_balance,
+                masq_token_balance,
+                latest_block_info_opt,
+            };
+            Ok::<_, BlockchainAgentBuildError>(create_blockchain_agent_web3(
+                gas_limit_const_part,
+                blockchain_agent_future_result,
+                consuming_wallet,
+                chain,
+            ))
+        })
     }
 
-    fn process_transaction_receipts(
+    fn submit_payables_in_batch(
         &self,
-        transaction_hashes: Vec<H256>,
-    ) -> Box<dyn Future<Item = Vec<TransactionReceiptResult>, Error = BlockchainError>> {
-        Box::new(
-            self.lower_interface()
-                .get_transaction_receipt_in_batch(transaction_hashes.clone())
-                .map_err(move |e| e)
-                .and_then(move |batch_response| {
-                    Ok(batch_response
-                        .into_iter()
-                        .zip(transaction_hashes)
-                        .map(|(response, hash)| match response {
-                            Ok(result) => {
-                                match serde_json::from_value::<TransactionReceipt>(result) {
-                                    Ok(receipt) => {
-                                        TransactionReceiptResult::RpcResponse(receipt.into())
-                                    }
-                                    Err(e) => {
-                                        if e.to_string().contains("invalid type: null") {
-                                            TransactionReceiptResult::RpcResponse(TxReceipt {
-                                                transaction_hash: hash,
-                                                status: TxStatus::Pending,
-                                            })
-                                        } else {
-                                            TransactionReceiptResult::LocalError(e.to_string())
-                                        }
-                                    }
-                                }
+        logger: Logger,
+        agent: Box<dyn BlockchainAgent>,
+        fingerprints_recipient: Recipient<PendingPayableFingerprintSeeds>,
+        affordable_accounts: Vec<PayableAccount>,
+    ) -> Box<dyn Future<Item = Vec<ProcessedPayableFallible>, Error = PayableTransactionError>>
+    {
+        let consuming_wallet = agent.consuming_wallet().clone();
+        let lower_interface = self.lower_interface();
+        let gas_price_wei = agent.agreed_fee_per_computation_unit().as_u128();
+        let chain = agent.get_chain();
+        let request_timeout = self.request_timeout;
+        let payment_memo_opt = self.payment_memo_opt;
+        let max_batch_size = self.max_batch_size;
+        let token_symbol = self.token_symbol();
+        let sub_batch_logger = logger.clone();
+        let simulated_accounts: Box<
+            dyn Future<Item = Vec<PayableAccount>, Error = PayableTransactionError>,
+        > = if self.payment_simulation {
+            simulate_payments(
+                logger.clone(),
+                self.lower_interface(),
+                &consuming_wallet,
+                token_symbol.clone(),
+                affordable_accounts,
+                payment_memo_opt,
+            )
+        } else {
+            Box::new(future::ok(affordable_accounts))
+        };
+
+        Box::new(simulated_accounts.and_then(move |affordable_accounts| {
+            let payment_count = affordable_accounts.len();
+            let mut sub_batches = split_into_sub_batches(affordable_accounts, max_batch_size);
+            let send_sub_batch = move |accounts: Vec<PayableAccount>| {
+                send_payables(
+                    &logger,
+                    lower_interface.as_ref(),
+                    chain,
+                    &token_symbol,
+                    request_timeout,
+                    consuming_wallet.clone(),
+                    gas_price_wei,
+                    fingerprints_recipient.clone(),
+                    accounts,
+                    payment_memo_opt,
+                )
+            };
+            if sub_batches.len() == 1 {
+                return send_sub_batch(sub_batches.remove(0));
+            }
+            info!(
+                sub_batch_logger,
+                "Sending {} payments in {} sub-batches of at most {}",
+                payment_count,
+                sub_batches.len(),
+                max_batch_size
+            );
+            // Each sub-batch asks for the pending nonce anew, so one that failed to go out
+            // leaves no gap in the nonces of those sent after it
+            Box::new(
+                stream::iter_ok::<_, PayableTransactionError>(sub_batches).fold(
+                    vec![],
+                    move |mut processed, accounts| {
+                        let logger = sub_batch_logger.clone();
+                        send_sub_batch(accounts.clone()).then(move |result| {
+                            match result {
+                                Ok(output) => processed.extend(output),
+                                Err(e) => processed.extend(sub_batch_failure(&logger, e, accounts)),
                             }
-                            Err(e) => TransactionReceiptResult::LocalError(e.to_string()),
+                            Ok::<_, PayableTransactionError>(processed)
                         })
-                        .collect::<Vec<TransactionReceiptResult>>())
-                }),
+                    },
+                ),
+            )
+        }))
+    }
+
+    fn submit_replacement(
+        &self,
+        logger: Logger,
+        consuming_wallet: Wallet,
+        rowid: u64,
+        basis: ReplacementBasis,
+        replacement_recipient: Recipient<PendingPayableReplacement>,
+        accounts: Vec<PayableAccount>,
+    ) -> Box<dyn Future<Item = H256, Error = PayableTransactionError>> {
+        let web3_batch = self.lower_interface().get_web3_batch();
+        send_replacement(
+            &logger,
+            self.chain,
+            &web3_batch,
+            self.request_timeout,
+            consuming_wallet,
+            rowid,
+            basis,
+            replacement_recipient,
+            accounts,
+            self.payment_memo_opt,
         )
     }
 
-    fn submit_payables_in_batch(
+    fn submit_sweep(
         &self,
         logger: Logger,
         agent: Box<dyn BlockchainAgent>,
-        fingerprints_recipient: Recipient<PendingPayableFingerprintSeeds>,
-        affordable_accounts: Vec<PayableAccount>,
-    ) -> Box<dyn Future<Item = Vec<ProcessedPayableFallible>, Error = PayableTransactionError>>
-    {
+        recipient: PayableAccount,
+    ) -> Box<dyn Future<Item = H256, Error = PayableTransactionError>> {
         let consuming_wallet = agent.consuming_wallet().clone();
         let web3_batch = self.lower_interface().get_web3_batch();
         let get_transaction_id = self
             .lower_interface()
             .get_transaction_id(consuming_wallet.address());
-        let gas_price_wei = agent.agreed_fee_per_computation_unit();
+        let gas_price_wei = agent.agreed_fee_per_computation_unit().as_u128();
         let chain = agent.get_chain();
+        let request_timeout = self.request_timeout;
+        let token_symbol = self.token_symbol();
 
         Box::new(
             get_transaction_id
                 .map_err(PayableTransactionError::TransactionID)
                 .and_then(move |pending_nonce| {
-                    send_payables_within_batch(
+                    send_sweep(
                         &logger,
                         chain,
+                        &token_symbol,
                         &web3_batch,
+                        request_timeout,
                         consuming_wallet,
                         gas_price_wei,
                         pending_nonce,
-                        fingerprints_recipient,
-                        affordable_accounts,
+                        recipient,
                     )
                 }),
         )
     }
+
+    fn submit_void(
+        &self,
+        logger: Logger,
+        consuming_wallet: Wallet,
+        basis: ReplacementBasis,
+    ) -> Box<dyn Future<Item = H256, Error = PayableTransactionError>> {
+        let web3_batch = self.lower_interface().get_web3_batch();
+        send_void(
+            &logger,
+            self.chain,
+            &web3_batch,
+            self.request_timeout,
+            consuming_wallet,
+            basis,
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -283,18 +690,130 @@ pub struct HashAndAmount {
 }
 
 impl BlockchainInterfaceWeb3 {
-    pub fn new(transport: Http, event_loop_handle: EventLoopHandle, chain: Chain) -> Self {
+    pub fn new(
+        transport: Http,
+        event_loop_handle: EventLoopHandle,
+        chain: Chain,
+        payment_forwarders: Vec<Address>,
+        receivable_confirmation_blocks: u64,
+        receivable_scan_attempts: u16,
+        request_timeout: Duration,
+        payment_memo_opt: Option<PaymentMemo>,
+        payment_simulation: bool,
+        max_batch_size: u16,
+        receivable_scan_recorder_opt: Option<ReceivableScanRecorder>,
+    ) -> Self {
         let gas_limit_const_part = Self::web3_gas_limit_const_part(chain);
 
         Self {
             logger: Logger::new("BlockchainInterface"),
             chain,
             gas_limit_const_part,
+            payment_forwarders,
+            legacy_contract_opt: chain.rec().legacy_contract_opt,
+            receivable_confirmation_blocks,
+            receivable_scan_attempts,
+            retry_base_delay: RECEIVABLE_SCAN_RETRY_BASE_DELAY,
+            request_timeout,
+            payment_memo_opt,
+            payment_simulation,
+            max_batch_size,
+            receivable_scan_recorder_opt,
+            token_metadata_arc: Arc::new(Mutex::new(None)),
+            capabilities_arc: Arc::new(Mutex::new(BlockchainCapabilities::default())),
             _event_loop_handle: event_loop_handle,
-            transport,
+            transport: MeteredHttp::new(transport, RpcUsageMeter::default()),
+        }
+    }
+
+    fn capabilities(&self) -> BlockchainCapabilities {
+        *self
+            .capabilities_arc
+            .lock()
+            .expect("Capabilities mutex is poisoned")
+    }
+
+    // Until the token contract has told its own, the symbol is taken from the chain record
+    fn token_symbol(&self) -> String {
+        match self
+            .token_metadata_arc
+            .lock()
+            .expect("Token metadata mutex is poisoned")
+            .as_ref()
+        {
+            Some(metadata) => metadata.symbol.clone(),
+            None => self.chain.rec().token_symbol.to_string(),
         }
     }
 
+    fn request_with_retries<T, F>(
+        request_name: &'static str,
+        attempts: u16,
+        base_delay: Duration,
+        logger: &Logger,
+        request: F,
+    ) -> Box<dyn Future<Item = T, Error = BlockchainError>>
+    where
+        T: 'static,
+        F: Fn() -> Box<dyn Future<Item = T, Error = BlockchainError>> + 'static,
+    {
+        let logger = logger.clone();
+        Box::new(future::loop_fn(1_u16, move |attempt| {
+            let logger = logger.clone();
+            request().then(
+                move |result| -> Box<dyn Future<Item = Loop<T, u16>, Error = BlockchainError>> {
+                    match result {
+                        Ok(item) => Box::new(future::ok(Loop::Break(item))),
+                        Err(e) if e.is_transient() && attempt < attempts => {
+                            let delay = Self::retry_delay(base_delay, attempt);
+                            debug!(
+                                logger,
+                                "Request for {} failed at attempt {} of {}: {}; trying again in {} ms",
+                                request_name,
+                                attempt,
+                                attempts,
+                                e,
+                                delay.as_millis()
+                            );
+                            Box::new(Self::pause(delay).map(move |_| Loop::Continue(attempt + 1)))
+                        }
+                        Err(e) => {
+                            if attempt > 1 {
+                                warning!(
+                                    logger,
+                                    "Request for {} gave up after {} of {} attempts: {}",
+                                    request_name,
+                                    attempt,
+                                    attempts,
+                                    e
+                                );
+                            }
+                            Box::new(future::err(e))
+                        }
+                    }
+                },
+            )
+        }))
+    }
+
+    fn retry_delay(base_delay: Duration, attempt: u16) -> Duration {
+        let full_delay = base_delay * 2_u32.saturating_pow(u32::from(attempt) - 1);
+        let half_delay = full_delay / 2;
+        let jitter_nanos = rand::thread_rng().gen_range(0..=half_delay.as_nanos() as u64);
+        half_delay + Duration::from_nanos(jitter_nanos)
+    }
+
+    // A timer of the actor system isn't available to every caller, so the pause runs on its own
+    // thread, leaving the caller's thread free in the meantime
+    fn pause(delay: Duration) -> impl Future<Item = (), Error = BlockchainError> {
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            thread::sleep(delay);
+            let _ = tx.send(());
+        });
+        rx.map_err(|_| BlockchainError::QueryFailed("Retry pause interrupted".to_string()))
+    }
+
     pub fn web3_gas_limit_const_part(chain: Chain) -> u128 {
         match chain {
             Chain::EthMainnet | Chain::EthRopsten | Chain::Dev => 55_000,
@@ -304,17 +823,22 @@ impl BlockchainInterfaceWeb3 {
         }
     }
 
-    fn extract_transactions_from_logs(logs: Vec<Log>) -> Vec<BlockchainTransaction> {
-        logs.iter()
-            .filter_map(|log: &Log| match log.block_number {
-                None => None,
-                Some(block_number) => {
-                    let wei_amount = U256::from(log.data.0.as_slice()).as_u128();
-                    Some(BlockchainTransaction {
-                        block_number: block_number.as_u64(),
-                        from: Wallet::from(log.topics[1]),
-                        wei_amount,
-                    })
+    fn extract_transactions_from_transfers(
+        transfers: Vec<DecodedTransfer>,
+        original_payers: &HashMap<H256, Address>,
+    ) -> Vec<BlockchainTransaction> {
+        transfers
+            .into_iter()
+            .map(|transfer| {
+                let from = transfer
+                    .transaction_hash_opt
+                    .and_then(|hash| original_payers.get(&hash))
+                    .copied()
+                    .unwrap_or(transfer.from);
+                BlockchainTransaction {
+                    block_number: transfer.block_number,
+                    from: Wallet::from(from),
+                    wei_amount: transfer.wei_amount,
                 }
             })
             .collect()
@@ -359,6 +883,75 @@ impl BlockchainInterfaceWeb3 {
         }
     }
 
+    fn recheck_from_block_opt(
+        start_block_marker: BlockMarker,
+        confirmation_blocks: u64,
+    ) -> Option<u64> {
+        match start_block_marker {
+            BlockMarker::Value(start_block) if confirmation_blocks > 0 && start_block > 0 => {
+                Some(start_block.saturating_sub(confirmation_blocks))
+            }
+            _ => None,
+        }
+    }
+
+    // A scan reaching all the way to the latest confirmed block also looks past it, to find the
+    // payments that are pending yet
+    fn pending_to_block_opt(
+        end_block_marker: BlockMarker,
+        latest_block_opt: Option<u64>,
+        confirmation_blocks: u64,
+    ) -> Option<u64> {
+        match (end_block_marker, latest_block_opt) {
+            (BlockMarker::Value(end_block), Some(latest_block))
+                if confirmation_blocks > 0
+                    && latest_block > end_block
+                    && end_block == latest_block.saturating_sub(confirmation_blocks) =>
+            {
+                Some(latest_block)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn separate_pending_transactions(
+        transactions: Vec<BlockchainTransaction>,
+        end_block_marker: BlockMarker,
+    ) -> (Vec<BlockchainTransaction>, Vec<BlockchainTransaction>) {
+        match end_block_marker {
+            BlockMarker::Value(end_block) => transactions
+                .into_iter()
+                .partition(|transaction| transaction.block_number > end_block),
+            BlockMarker::Uninitialized => (vec![], transactions),
+        }
+    }
+
+    pub(crate) fn separate_rechecked_transactions(
+        transactions: Vec<BlockchainTransaction>,
+        start_block_marker: BlockMarker,
+        end_block_marker: BlockMarker,
+        recheck_from_block_opt: Option<u64>,
+    ) -> (Vec<BlockchainTransaction>, Option<RecheckedTransactions>) {
+        match (recheck_from_block_opt, start_block_marker) {
+            (Some(from_block), BlockMarker::Value(start_block)) => {
+                let to_block = match end_block_marker {
+                    BlockMarker::Value(end_block) => end_block.min(start_block - 1),
+                    BlockMarker::Uninitialized => start_block - 1,
+                };
+                let (rechecked, new): (Vec<_>, Vec<_>) = transactions
+                    .into_iter()
+                    .partition(|transaction| transaction.block_number < start_block);
+                let rechecked_opt = (to_block >= from_block).then(|| RecheckedTransactions {
+                    from_block,
+                    to_block,
+                    transactions: rechecked,
+                });
+                (new, rechecked_opt)
+            }
+            _ => (transactions, None),
+        }
+    }
+
     fn calculate_end_block_marker(
         start_block_marker: BlockMarker,
         scan_range: BlockScanRange,
@@ -393,36 +986,262 @@ impl BlockchainInterfaceWeb3 {
         }
     }
 
+    fn hashes_of_forwarded_payments(
+        logs_result: &Result<Vec<Log>, BlockchainError>,
+        payment_forwarders: &[Address],
+    ) -> Vec<H256> {
+        match logs_result {
+            Ok(logs) => logs
+                .iter()
+                .filter(|log| {
+                    log.topics.len() >= 2
+                        && payment_forwarders.contains(&Wallet::from(log.topics[1]).address())
+                })
+                .filter_map(|log| log.transaction_hash)
+                .collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    // Pending payments have their memos reported only once confirmed
+    fn hashes_of_received_payments(
+        logs_result: &Result<Vec<Log>, BlockchainError>,
+        end_block_marker: BlockMarker,
+    ) -> Vec<H256> {
+        match logs_result {
+            Ok(logs) => logs
+                .iter()
+                .filter(|log| match (end_block_marker, log.block_number) {
+                    (BlockMarker::Value(end_block), Some(block_number)) => {
+                        block_number.as_u64() <= end_block
+                    }
+                    _ => true,
+                })
+                .filter_map(|log| log.transaction_hash)
+                .unique()
+                .collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    // The memos are only reported, so a failed batch costs nothing but the report
+    fn report_payment_memos(
+        lower_level_interface: &dyn LowBlockchainInt,
+        hashes: Vec<H256>,
+        logger: Logger,
+    ) -> Box<dyn Future<Item = (), Error = BlockchainError>> {
+        if hashes.is_empty() {
+            return Box::new(future::ok(()));
+        }
+        Box::new(
+            lower_level_interface
+                .get_transactions_in_batch(hashes.clone())
+                .then(move |result| {
+                    match result {
+                        Ok(responses) => {
+                            hashes
+                                .into_iter()
+                                .zip(responses)
+                                .for_each(|(hash, response)| {
+                                    if let Some((payer, memo)) =
+                                        response.ok().and_then(|tx| Self::payment_memo(&tx))
+                                    {
+                                        info!(
+                                            logger,
+                                            "Payment from {:?} in transaction {:?} carries the \
+                                             memo of Node {}",
+                                            payer,
+                                            hash,
+                                            memo
+                                        )
+                                    }
+                                })
+                        }
+                        Err(e) => warning!(
+                            logger,
+                            "Couldn't read the memos of the received payments: {}",
+                            e
+                        ),
+                    };
+                    Ok(())
+                }),
+        )
+    }
+
+    fn payment_memo(transaction: &Value) -> Option<(Address, PaymentMemo)> {
+        let input = serde_json::from_value::<Bytes>(transaction.get("input")?.clone()).ok()?;
+        let memo = PaymentMemo::decode(&input.0)?;
+        let payer = serde_json::from_value(transaction.get("from")?.clone()).ok()?;
+        Some((payer, memo))
+    }
+
+    // A failed batch fails the whole retrieval so that the same blocks are tried again next time,
+    // rather than crediting the forwarder with someone else's payment for good
+    fn find_original_payers(
+        lower_level_interface: &dyn LowBlockchainInt,
+        forwarded_hashes: Vec<H256>,
+        logger: Logger,
+    ) -> Box<dyn Future<Item = HashMap<H256, Address>, Error = BlockchainError>> {
+        if forwarded_hashes.is_empty() {
+            return Box::new(future::ok(HashMap::new()));
+        }
+        Box::new(
+            lower_level_interface
+                .get_transactions_in_batch(forwarded_hashes.clone())
+                .map(move |responses| {
+                    forwarded_hashes
+                        .into_iter()
+                        .zip(responses)
+                        .filter_map(|(hash, response)| {
+                            match response.ok().and_then(|tx| Self::original_payer(&tx)) {
+                                Some(original_payer) => Some((hash, original_payer)),
+                                None => {
+                                    warning!(
+                                        logger,
+                                        "Couldn't find who paid through a forwarder in \
+                                         transaction {:?}; crediting the forwarder",
+                                        hash
+                                    );
+                                    None
+                                }
+                            }
+                        })
+                        .collect()
+                }),
+        )
+    }
+
+    // ERC-2771 forwarders carry the signer of the relayed request in the call data; anything
+    // else is taken for a plain proxy called by the payer directly
+    fn original_payer(transaction: &Value) -> Option<Address> {
+        transaction
+            .get("input")
+            .and_then(|input| serde_json::from_value::<Bytes>(input.clone()).ok())
+            .and_then(|input| Self::decode_forwarded_request_signer(&input.0))
+            .or_else(|| serde_json::from_value(transaction.get("from")?.clone()).ok())
+    }
+
+    fn decode_forwarded_request_signer(input: &[u8]) -> Option<Address> {
+        if input.get(0..4)? != FORWARDER_EXECUTE_METHOD_ID {
+            return None;
+        }
+        let args = &input[4..];
+        let request_offset = U256::from(args.get(0..32)?);
+        if request_offset > U256::from(args.len()) {
+            return None;
+        }
+        let from_start = request_offset.as_usize();
+        let from_word = args.get(from_start..from_start.checked_add(32)?)?;
+        Some(Address::from_slice(&from_word[12..]))
+    }
+
     fn handle_transaction_logs(
         logs_result: Result<Vec<Log>, BlockchainError>,
+        original_payers: &HashMap<H256, Address>,
         logger: &Logger,
     ) -> Result<Vec<BlockchainTransaction>, BlockchainError> {
         let logs = logs_result?;
-        let logs_len = logs.len();
-        if logs
-            .iter()
-            .any(|log| log.topics.len() < 2 || log.data.0.len() > 32)
-        {
+        Ok(Self::extract_transactions_from_logs(
+            &logs,
+            original_payers,
+            logger,
+        ))
+    }
+
+    pub(crate) fn extract_transactions_from_logs(
+        logs: &[Log],
+        original_payers: &HashMap<H256, Address>,
+        logger: &Logger,
+    ) -> Vec<BlockchainTransaction> {
+        let decoded_logs = decode_transfer_logs(logs);
+        decoded_logs.rejected.iter().for_each(|rejected| {
             warning!(
                 logger,
-                "Invalid response from blockchain server: {:?}",
-                logs
-            );
-            Err(BlockchainError::InvalidResponse)
-        } else {
-            let transactions: Vec<BlockchainTransaction> =
-                Self::extract_transactions_from_logs(logs);
-            debug!(logger, "Retrieved transactions: {:?}", transactions);
-            if transactions.is_empty() && logs_len != transactions.len() {
-                warning!(
-                    logger,
-                    "Retrieving transactions: logs: {}, transactions: {}",
-                    logs_len,
-                    transactions.len()
-                )
+                "Ignoring a malformed transfer log entry at {}",
+                rejected
+            )
+        });
+        let transactions: Vec<BlockchainTransaction> =
+            Self::extract_transactions_from_transfers(decoded_logs.transfers, original_payers);
+        debug!(logger, "Retrieved transactions: {:?}", transactions);
+        if transactions.is_empty() && logs.len() != transactions.len() {
+            warning!(
+                logger,
+                "Retrieving transactions: logs: {}, transactions: {}",
+                logs.len(),
+                transactions.len()
+            )
+        }
+
+        transactions
+    }
+
+    fn receivable_contracts(
+        contract_address: Address,
+        legacy_contract_opt: Option<Address>,
+    ) -> Vec<Address> {
+        std::iter::once(contract_address)
+            .chain(legacy_contract_opt)
+            .collect()
+    }
+
+    // Payers may keep paying at the legacy contract for a while after a migration; the operator
+    // should be able to tell which payments did
+    fn report_transfer_origins(
+        logs: &[Log],
+        contract_address: Address,
+        legacy_contract_opt: Option<Address>,
+        logger: &Logger,
+    ) {
+        let legacy_contract = match legacy_contract_opt {
+            Some(legacy_contract) => legacy_contract,
+            None => return,
+        };
+        let origin = |address: Address| {
+            if address == contract_address {
+                "primary"
+            } else if address == legacy_contract {
+                "legacy"
+            } else {
+                "unknown"
             }
+        };
+        let describe = |value_opt: Option<String>| value_opt.unwrap_or_else(|| "?".to_string());
+        logs.iter().for_each(|log| {
+            debug!(
+                logger,
+                "Transfer in transaction {} at block {} came from the {} contract {:#x}",
+                describe(log.transaction_hash.map(|hash| format!("{:?}", hash))),
+                describe(log.block_number.map(|number| number.to_string())),
+                origin(log.address),
+                log.address
+            )
+        });
+        let from_legacy_contract = logs
+            .iter()
+            .filter(|log| log.address == legacy_contract)
+            .count();
+        if from_legacy_contract > 0 {
+            info!(
+                logger,
+                "{} of {} transfers came from the legacy contract {:#x} rather than from {:#x}",
+                from_legacy_contract,
+                logs.len(),
+                legacy_contract,
+                contract_address
+            )
+        }
+    }
 
-            Ok(transactions)
+    // A failure to record costs only the record; the scan goes on as usual
+    fn record_scan(
+        recorder: &ReceivableScanRecorder,
+        record: ReceivableScanRecord,
+        logger: &Logger,
+    ) {
+        match recorder.record(&record) {
+            Ok(path) => debug!(logger, "Received-payments scan recorded in {}", path.display()),
+            Err(e) => warning!(logger, "Failed to record the received-payments scan: {}", e),
         }
     }
 }
@@ -431,6 +1250,9 @@ impl BlockchainInterfaceWeb3 {
 mod tests {
     use super::*;
     use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::agent_web3::WEB3_MAXIMAL_GAS_LIMIT_MARGIN;
+    use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::test_utils::BlockchainAgentMock;
+    use crate::accountant::test_utils::make_payable_account;
+    use crate::blockchain::blockchain_interface::data_structures::ProcessedPayableFallible::Correct;
     use crate::blockchain::blockchain_interface::blockchain_interface_web3::{
         BlockchainInterfaceWeb3, CONTRACT_ABI, REQUESTS_IN_PARALLEL, TRANSACTION_LITERAL,
         TRANSFER_METHOD_ID,
@@ -438,28 +1260,40 @@ mod tests {
     use crate::blockchain::blockchain_interface::data_structures::errors::BlockchainError::QueryFailed;
     use crate::blockchain::blockchain_interface::data_structures::BlockchainTransaction;
     use crate::blockchain::blockchain_interface::{
-        BlockchainAgentBuildError, BlockchainError, BlockchainInterface,
-        RetrievedBlockchainTransactions,
+        BlockchainAgentBuildError, BlockchainError, BlockchainInterface, BlockchainReader,
+        BlockchainWriter, RetrievedBlockchainTransactions,
     };
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::utils::sign_transaction_data;
+    use crate::blockchain::rpc_usage::RpcUsageCount;
     use crate::blockchain::test_utils::{
         all_chains, make_blockchain_interface_web3, ReceiptResponseBuilder,
     };
     use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
+    use crate::sub_lib::cryptde::PublicKey;
     use crate::sub_lib::wallet::Wallet;
+    use crate::sub_lib::wei::Wei;
     use crate::test_utils::make_paying_wallet;
     use crate::test_utils::make_wallet;
+    use crate::test_utils::recorder::make_recorder;
+    use actix::{Actor, System};
     use ethsign_crypto::Keccak256;
     use futures::Future;
     use masq_lib::blockchains::chains::Chain;
+    use masq_lib::constants::DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
-    use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
-    use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
+    use masq_lib::test_utils::mock_blockchain_client_server::{
+        abi_encoded_string, MBCSBuilder, TOKEN_DECIMALS_SELECTOR, TOKEN_NAME_SELECTOR,
+        TOKEN_SYMBOL_SELECTOR,
+    };
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+    use crate::blockchain::receivable_scan_records::read_record;
     use masq_lib::utils::find_free_port;
+    use rustc_hex::ToHex;
     use std::net::Ipv4Addr;
     use std::str::FromStr;
     use web3::transports::Http;
     use web3::types::{H256, U256};
-    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TransactionBlock, TxReceipt, TxStatus};
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{TokenMetadata, TransactionBlock, TxReceipt, TxStatus};
 
     #[test]
     fn constants_are_correct() {
@@ -480,8 +1314,32 @@ mod tests {
             "payable":false,
             "stateMutability":"nonpayable",
             "type":"function"
-            }]"#
-        );
+            },{
+            "constant":true,
+            "inputs":[],
+            "name":"name",
+            "outputs":[{"name":"","type":"string"}],
+            "payable":false,
+            "stateMutability":"view",
+            "type":"function"
+            },{
+            "constant":true,
+            "inputs":[],
+            "name":"symbol",
+            "outputs":[{"name":"","type":"string"}],
+            "payable":false,
+            "stateMutability":"view",
+            "type":"function"
+            },{
+            "constant":true,
+            "inputs":[],
+            "name":"decimals",
+            "outputs":[{"name":"","type":"uint8"}],
+            "payable":false,
+            "stateMutability":"view",
+            "type":"function"
+            }]"#
+        );
         let transaction_literal_expected: H256 = H256 {
             0: [
                 0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37,
@@ -497,7 +1355,18 @@ mod tests {
             TRANSFER_METHOD_ID,
             "transfer(address,uint256)".keccak256()[0..4],
         );
+        assert_eq!(DISPERSE_TOKEN_METHOD_ID, [0xc7, 0x3a, 0x2d, 0x60]);
+        assert_eq!(
+            DISPERSE_TOKEN_METHOD_ID,
+            "disperseToken(address,address[],uint256[])".keccak256()[0..4],
+        );
+        assert_eq!(FORWARDER_EXECUTE_METHOD_ID, [0x47, 0x15, 0x3f, 0x82]);
+        assert_eq!(
+            FORWARDER_EXECUTE_METHOD_ID,
+            "execute((address,address,uint256,uint256,uint256,bytes),bytes)".keccak256()[0..4],
+        );
         assert_eq!(FRESH_START_BLOCK, 0);
+        assert_eq!(RECEIVABLE_SCAN_RETRY_BASE_DELAY, Duration::from_millis(500));
     }
 
     #[test]
@@ -510,6 +1379,468 @@ mod tests {
         })
     }
 
+    #[test]
+    fn blockchain_interface_web3_serves_as_both_reader_and_writer() {
+        let subject: Box<dyn BlockchainInterface> =
+            Box::new(make_blockchain_interface_web3(find_free_port()));
+
+        let reader = subject.as_reader();
+        let writer = subject.as_writer();
+
+        assert_eq!(
+            reader.lower_interface().get_contract_address(),
+            subject.get_chain().rec().contract
+        );
+        let writer_addr = writer as *const dyn BlockchainWriter as *const u8;
+        let subject_addr = subject.as_ref() as *const dyn BlockchainInterface as *const u8;
+        assert_eq!(writer_addr, subject_addr)
+    }
+
+    #[test]
+    fn fetch_token_metadata_remembers_the_symbol_for_the_logs_of_payments() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .scripted_call_result(TOKEN_NAME_SELECTOR, abi_encoded_string("Renamed MASQ"))
+            .scripted_call_result(TOKEN_SYMBOL_SELECTOR, abi_encoded_string("rMASQ"))
+            .scripted_call_result(TOKEN_DECIMALS_SELECTOR, format!("{:#066x}", 18))
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let symbol_before = subject.token_symbol();
+
+        let result = subject.fetch_token_metadata().wait();
+
+        assert_eq!(symbol_before, "MASQ".to_string());
+        assert_eq!(
+            result,
+            Ok(TokenMetadata {
+                name: "Renamed MASQ".to_string(),
+                symbol: "rMASQ".to_string(),
+                decimals: 18,
+            })
+        );
+        assert_eq!(subject.token_symbol(), "rMASQ".to_string());
+    }
+
+    #[test]
+    fn fetch_token_metadata_leaves_the_symbol_of_the_chain_record_in_place_on_failure() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .scripted_call_result(TOKEN_NAME_SELECTOR, abi_encoded_string("MASQ (PoS)"))
+            .scripted_call_result(TOKEN_SYMBOL_SELECTOR, abi_encoded_string("rMASQ"))
+            .scripted_error("eth_call", -32000, "execution reverted")
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.fetch_token_metadata().wait();
+
+        assert!(
+            matches!(result, Err(QueryFailed(ref msg)) if msg.contains("execution reverted")),
+            "{:?}",
+            result
+        );
+        assert_eq!(subject.token_symbol(), "MASQ".to_string());
+    }
+
+    #[test]
+    fn fetch_capabilities_remembers_what_the_blockchain_service_offers() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .serving_chain(Chain::PolyMainnet)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let capabilities_before = subject.capabilities();
+
+        let result = subject.fetch_capabilities().wait();
+
+        assert_eq!(capabilities_before, BlockchainCapabilities::default());
+        let expected_capabilities = BlockchainCapabilities {
+            batch_requests: true,
+            fee_history: true,
+        };
+        assert_eq!(result, Ok(expected_capabilities));
+        assert_eq!(subject.capabilities(), expected_capabilities);
+    }
+
+    #[test]
+    fn fetch_capabilities_recognizes_a_blockchain_service_without_batches_and_fee_history() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .scripted_error(
+                "eth_feeHistory",
+                -32601,
+                "the method eth_feeHistory does not exist",
+            )
+            .ok_response("0x89".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject.fetch_capabilities().wait();
+
+        let expected_capabilities = BlockchainCapabilities {
+            batch_requests: false,
+            fee_history: false,
+        };
+        assert_eq!(result, Ok(expected_capabilities));
+        assert_eq!(subject.capabilities(), expected_capabilities);
+    }
+
+    #[test]
+    fn fetch_capabilities_leaves_the_assumed_capabilities_in_place_on_failure() {
+        let subject = make_blockchain_interface_web3(find_free_port());
+
+        let result = subject.fetch_capabilities().wait();
+
+        assert!(matches!(result, Err(QueryFailed(_))), "{:?}", result);
+        assert_eq!(subject.capabilities(), BlockchainCapabilities::default());
+    }
+
+    fn make_retrying_subject(port: u16, test_name: &str, attempts: u16) -> BlockchainInterfaceWeb3 {
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let mut subject = BlockchainInterfaceWeb3::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+            vec![],
+            0,
+            attempts,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+            None,
+            false,
+            0,
+            None,
+        );
+        subject.logger = Logger::new(test_name);
+        subject.retry_base_delay = Duration::from_millis(1);
+        subject
+    }
+
+    #[test]
+    fn retrieve_transactions_retries_a_transiently_failing_block_number_request() {
+        init_test_logging();
+        let test_name = "retrieve_transactions_retries_a_transiently_failing_block_number_request";
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .err_response(-32005, "Too many requests", 1)
+            .err_response(-32005, "Request timed out", 1)
+            .ok_response("0x400", 1) // 1024
+            .raw_response(r#"{"jsonrpc":"2.0","id":2,"result":[]}"#.to_string())
+            .start();
+        let subject = make_retrying_subject(port, test_name, 3);
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::Range(1000),
+                vec![make_wallet("earning").address()],
+            )
+            .wait();
+
+        assert_eq!(
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(1024 + 1),
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
+            })
+        );
+        assert_eq!(blockchain_client_server.requests().len(), 4);
+        let test_log_handler = TestLogHandler::new();
+        test_log_handler.exists_log_containing(&format!(
+            "DEBUG: {}: Request for block number failed at attempt 1 of 3: Blockchain error: \
+            Rate limited:",
+            test_name
+        ));
+        test_log_handler.exists_log_containing(&format!(
+            "DEBUG: {}: Request for block number failed at attempt 2 of 3: Blockchain error: \
+            Timeout:",
+            test_name
+        ));
+        test_log_handler.exists_no_log_containing(&format!("WARN: {}", test_name));
+    }
+
+    #[test]
+    fn retrieve_transactions_records_the_scan_when_asked_to() {
+        let test_name = "retrieve_transactions_records_the_scan_when_asked_to";
+        let data_dir = ensure_node_home_directory_exists(
+            "blockchain_interface_web3",
+            test_name,
+        );
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x400", 1) // 1024
+            .raw_response(r#"{"jsonrpc":"2.0","id":2,"result":[]}"#.to_string())
+            .start();
+        let mut subject = make_retrying_subject(port, test_name, 1);
+        let recorder = ReceivableScanRecorder::new(&data_dir, 3);
+        subject.receivable_scan_recorder_opt = Some(recorder.clone());
+        let recipient = make_wallet("earning").address();
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::Range(1000),
+                vec![recipient],
+            )
+            .wait();
+
+        assert!(result.is_ok(), "{:?}", result);
+        let records = recorder.records().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = read_record(&records[0]).unwrap();
+        assert_eq!(record.chain, TEST_DEFAULT_CHAIN.rec().literal_identifier);
+        assert_eq!(record.recipients, vec![recipient]);
+        assert_eq!(record.start_block_opt, Some(42));
+        assert_eq!(record.recheck_from_block_opt, None);
+        assert_eq!(record.end_block_opt, Some(1024));
+        assert_eq!(record.logs, vec![]);
+        assert_eq!(record.original_payers, vec![]);
+    }
+
+    #[test]
+    fn retrieve_transactions_looks_for_payments_at_the_legacy_contract_too() {
+        init_test_logging();
+        let test_name = "retrieve_transactions_looks_for_payments_at_the_legacy_contract_too";
+        let port = find_free_port();
+        let contract = TEST_DEFAULT_CHAIN.rec().contract;
+        let legacy_contract = make_wallet("legacy contract").address();
+        let transfer_log = |contract: Address, block_number: &str, hash_byte: char| {
+            format!(
+                r#"{{
+                    "address":"{:#x}",
+                    "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                    "blockNumber":"{}",
+                    "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                    "logIndex":"0x0",
+                    "removed":false,
+                    "topics":[
+                        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                        "0x0000000000000000000000001111111111111111111111111111111111111111",
+                        "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                    ],
+                    "transactionHash":"0x{}",
+                    "transactionIndex":"0x0"
+                }}"#,
+                contract,
+                block_number,
+                hash_byte.to_string().repeat(64)
+            )
+        };
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x400", 1)
+            .raw_response(format!(
+                r#"{{"jsonrpc":"2.0","id":2,"result":[{},{}]}}"#,
+                transfer_log(contract, "0x2e", 'a'),
+                transfer_log(legacy_contract, "0x2f", 'b')
+            ))
+            .start();
+        let mut subject = make_retrying_subject(port, test_name, 1);
+        subject.legacy_contract_opt = Some(legacy_contract);
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait()
+            .unwrap();
+
+        let payer = Wallet::from_str("0x1111111111111111111111111111111111111111").unwrap();
+        assert_eq!(
+            result.transactions,
+            vec![
+                BlockchainTransaction {
+                    block_number: 46,
+                    from: payer.clone(),
+                    wei_amount: 4_503_599_627_370_496,
+                },
+                BlockchainTransaction {
+                    block_number: 47,
+                    from: payer,
+                    wei_amount: 4_503_599_627_370_496,
+                },
+            ]
+        );
+        assert_eq!(subject.contract_address(), contract);
+        let requests = blockchain_client_server.requests();
+        let filter: Value = serde_json::from_str(&requests[1]).unwrap();
+        assert_eq!(
+            filter["params"][0]["address"],
+            serde_json::json!([contract, legacy_contract])
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Retrieving transactions from the legacy contract: {:#x} as well",
+            legacy_contract
+        ));
+        log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Transfer in transaction 0x{} at block 46 came from the primary \
+             contract {:#x}",
+            "a".repeat(64),
+            contract
+        ));
+        log_handler.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Transfer in transaction 0x{} at block 47 came from the legacy \
+             contract {:#x}",
+            "b".repeat(64),
+            legacy_contract
+        ));
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: 1 of 2 transfers came from the legacy contract {:#x} rather than \
+             from {:#x}",
+            legacy_contract, contract
+        ));
+    }
+
+    #[test]
+    fn retrieve_transactions_queries_only_the_primary_contract_without_a_legacy_one() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x400", 1)
+            .raw_response(r#"{"jsonrpc":"2.0","id":2,"result":[]}"#.to_string())
+            .start();
+        let subject = make_retrying_subject(
+            port,
+            "retrieve_transactions_queries_only_the_primary_contract_without_a_legacy_one",
+            1,
+        );
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![make_wallet("earning").address()],
+            )
+            .wait();
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(subject.legacy_contract_opt, None);
+        let requests = blockchain_client_server.requests();
+        let filter: Value = serde_json::from_str(&requests[1]).unwrap();
+        assert_eq!(
+            filter["params"][0]["address"],
+            serde_json::json!(TEST_DEFAULT_CHAIN.rec().contract)
+        );
+    }
+
+    #[test]
+    fn retrieve_transactions_gives_up_on_transaction_logs_after_the_configured_attempts() {
+        init_test_logging();
+        let test_name =
+            "retrieve_transactions_gives_up_on_transaction_logs_after_the_configured_attempts";
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x400", 1)
+            .err_response(-32005, "Too many requests", 2)
+            .err_response(-32005, "Too many requests", 2)
+            .err_response(-32005, "Too many requests", 2)
+            .start();
+        let subject = make_retrying_subject(port, test_name, 3);
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::Range(1000),
+                vec![make_wallet("earning").address()],
+            )
+            .wait();
+
+        match result {
+            Err(BlockchainError::RateLimited(msg)) => {
+                assert!(msg.contains("Too many requests"), "{}", msg)
+            }
+            x => panic!("we expected a rate limiting error but got {:?}", x),
+        }
+        assert_eq!(blockchain_client_server.requests().len(), 4);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Request for transaction logs gave up after 3 of 3 attempts: Blockchain \
+            error: Rate limited:",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn retrieve_transactions_does_not_retry_a_failure_that_is_not_transient() {
+        init_test_logging();
+        let test_name = "retrieve_transactions_does_not_retry_a_failure_that_is_not_transient";
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x400", 1)
+            .err_response(-32005, "My tummy hurts", 2)
+            .start();
+        let subject = make_retrying_subject(port, test_name, 3);
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::Range(1000),
+                vec![make_wallet("earning").address()],
+            )
+            .wait();
+
+        match result {
+            Err(BlockchainError::QueryFailed(msg)) => {
+                assert!(msg.contains("My tummy hurts"), "{}", msg)
+            }
+            x => panic!("we expected a query failure but got {:?}", x),
+        }
+        assert_eq!(blockchain_client_server.requests().len(), 2);
+        TestLogHandler::new().exists_no_log_containing(&format!("WARN: {}", test_name));
+    }
+
+    #[test]
+    fn retrieve_transactions_gives_up_on_an_unresponsive_blockchain_service() {
+        let test_name = "retrieve_transactions_gives_up_on_an_unresponsive_blockchain_service";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port).unresponsive().start();
+        let mut subject = make_retrying_subject(port, test_name, 1);
+        subject.request_timeout = Duration::from_millis(300);
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::Range(1000),
+                vec![make_wallet("earning").address()],
+            )
+            .wait();
+
+        assert_eq!(
+            result,
+            Err(BlockchainError::NoResponse(Duration::from_millis(300)))
+        );
+    }
+
+    #[test]
+    fn retry_delay_grows_exponentially_with_jitter() {
+        let base_delay = Duration::from_millis(100);
+
+        (0..20).for_each(|_| {
+            [(1, 100), (2, 200), (3, 400), (4, 800)]
+                .iter()
+                .for_each(|(attempt, full_delay_ms)| {
+                    let full_delay = Duration::from_millis(*full_delay_ms);
+
+                    let result = BlockchainInterfaceWeb3::retry_delay(base_delay, *attempt);
+
+                    assert!(
+                        result >= full_delay / 2 && result <= full_delay,
+                        "attempt {}: {:?}",
+                        attempt,
+                        result
+                    );
+                })
+        })
+    }
+
     #[test]
     fn blockchain_interface_web3_retrieves_transactions_works() {
         let start_block_marker = BlockMarker::Value(42);
@@ -643,32 +1974,857 @@ mod tests {
             .retrieve_transactions(
                 start_block_marker,
                 scan_range,
-                Wallet::from_str(&to).unwrap().address(),
+                vec![Wallet::from_str(&to).unwrap().address()],
             )
             .wait()
             .unwrap();
 
         assert_eq!(
-            result,
-            RetrievedBlockchainTransactions {
-                new_start_block: expected_new_start_block,
-                transactions: vec![
-                    BlockchainTransaction {
-                        block_number: 46,
-                        from: Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182")
-                            .unwrap(),
-                        wei_amount: 4_503_599_627_370_496u128,
-                    },
-                    BlockchainTransaction {
-                        block_number: 48,
-                        from: Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
-                            .unwrap(),
-                        wei_amount: 4_503_599_627_370_496u128,
-                    },
-                ]
-            }
+            result,
+            RetrievedBlockchainTransactions {
+                new_start_block: expected_new_start_block,
+                transactions: vec![
+                    BlockchainTransaction {
+                        block_number: 46,
+                        from: Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182")
+                            .unwrap(),
+                        wei_amount: 4_503_599_627_370_496u128,
+                    },
+                    BlockchainTransaction {
+                        block_number: 48,
+                        from: Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                            .unwrap(),
+                        wei_amount: 4_503_599_627_370_496u128,
+                    },
+                ],
+                rechecked_opt: None,
+                pending: vec![],
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!("DEBUG: {test_case}: Retrieving transactions {expected_log} for: 0x3f69…72fc chain_id: 137 contract: 0xee9a352f6aac4af1a5b9f467f6a93e0ffbe9dd35"));
+    }
+
+    fn make_transfer_log_json(block_number: u64, from: &str, transaction_hash_tail: u8) -> String {
+        format!(
+            r#"{{
+                "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                "blockNumber":"{:#x}",
+                "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                "logIndex":"0x0",
+                "removed":false,
+                "topics":[
+                    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    "0x000000000000000000000000{}",
+                    "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                ],
+                "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff06{:02x}",
+                "transactionIndex":"0x0"
+            }}"#,
+            block_number,
+            from.trim_start_matches("0x"),
+            transaction_hash_tail
+        )
+    }
+
+    fn retrieve_transactions_with_confirmation_blocks(
+        test_name: &str,
+        confirmation_blocks: u64,
+        latest_block_response: &str,
+        logs: Vec<String>,
+        start_block_marker: BlockMarker,
+    ) -> RetrievedBlockchainTransactions {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response(latest_block_response, 1)
+            .raw_response(format!(
+                r#"{{"jsonrpc":"2.0","id":2,"result":[{}]}}"#,
+                logs.join(",")
+            ))
+            .start();
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let mut subject = BlockchainInterfaceWeb3::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+            vec![],
+            confirmation_blocks,
+            1,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+            None,
+            false,
+            0,
+            None,
+        );
+        subject.logger = Logger::new(test_name);
+
+        subject
+            .retrieve_transactions(
+                start_block_marker,
+                BlockScanRange::Range(1000),
+                vec![
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait()
+            .unwrap()
+    }
+
+    #[test]
+    fn blockchain_interface_web3_leaves_unconfirmed_blocks_for_later_and_rechecks_the_young_ones() {
+        init_test_logging();
+        let test_name =
+            "blockchain_interface_web3_leaves_unconfirmed_blocks_for_later_and_rechecks_the_young_ones";
+        let payer_1 = "0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182";
+        let payer_2 = "0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc";
+        let logs = vec![
+            make_transfer_log_json(40, payer_1, 1),
+            make_transfer_log_json(46, payer_2, 2),
+        ];
+
+        let result = retrieve_transactions_with_confirmation_blocks(
+            test_name,
+            10,
+            "0x7d0", // 2_000
+            logs,
+            BlockMarker::Value(42),
+        );
+
+        assert_eq!(
+            result,
+            RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(32 + 1000 + 1),
+                transactions: vec![BlockchainTransaction {
+                    block_number: 46,
+                    from: Wallet::from_str(payer_2).unwrap(),
+                    wei_amount: 4_503_599_627_370_496u128,
+                }],
+                rechecked_opt: Some(RecheckedTransactions {
+                    from_block: 32,
+                    to_block: 41,
+                    transactions: vec![BlockchainTransaction {
+                        block_number: 40,
+                        from: Wallet::from_str(payer_1).unwrap(),
+                        wei_amount: 4_503_599_627_370_496u128,
+                    }],
+                }),
+                pending: vec![],
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Retrieving transactions from start block: Number(32) to end block: Number(1032)"
+        ));
+    }
+
+    #[test]
+    fn blockchain_interface_web3_keeps_start_block_until_the_chain_grows_deep_enough() {
+        init_test_logging();
+        let test_name =
+            "blockchain_interface_web3_keeps_start_block_until_the_chain_grows_deep_enough";
+        let payer = "0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182";
+        // A reorg replaced the block 38 with one not carrying our payment anymore
+        let logs = vec![make_transfer_log_json(36, payer, 1)];
+
+        let result = retrieve_transactions_with_confirmation_blocks(
+            test_name,
+            10,
+            "0x32", // 50
+            logs,
+            BlockMarker::Value(42),
+        );
+
+        assert_eq!(
+            result,
+            RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(42),
+                transactions: vec![],
+                rechecked_opt: Some(RecheckedTransactions {
+                    from_block: 32,
+                    to_block: 40,
+                    transactions: vec![BlockchainTransaction {
+                        block_number: 36,
+                        from: Wallet::from_str(payer).unwrap(),
+                        wei_amount: 4_503_599_627_370_496u128,
+                    }],
+                }),
+                pending: vec![],
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Retrieving transactions from start block: Number(32) to end block: Number(40)"
+        ));
+    }
+
+    #[test]
+    fn blockchain_interface_web3_starts_fresh_scans_at_the_latest_confirmed_block() {
+        init_test_logging();
+        let test_name =
+            "blockchain_interface_web3_starts_fresh_scans_at_the_latest_confirmed_block";
+
+        let result = retrieve_transactions_with_confirmation_blocks(
+            test_name,
+            10,
+            "0x7d0", // 2_000
+            vec![],
+            BlockMarker::Uninitialized,
+        );
+
+        assert_eq!(
+            result,
+            RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(1_990 + 1),
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Retrieving transactions from start block: Number(1990) to end block: Number(1990)"
+        ));
+    }
+
+    #[test]
+    fn blockchain_interface_web3_reports_payments_in_unconfirmed_blocks_as_pending() {
+        init_test_logging();
+        let test_name =
+            "blockchain_interface_web3_reports_payments_in_unconfirmed_blocks_as_pending";
+        let payer_1 = "0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182";
+        let payer_2 = "0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc";
+        let logs = vec![
+            make_transfer_log_json(1_988, payer_1, 1),
+            make_transfer_log_json(1_995, payer_2, 2),
+        ];
+
+        let result = retrieve_transactions_with_confirmation_blocks(
+            test_name,
+            10,
+            "0x7d0", // 2_000
+            logs,
+            BlockMarker::Value(1_985),
+        );
+
+        assert_eq!(
+            result,
+            RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(1_990 + 1),
+                transactions: vec![BlockchainTransaction {
+                    block_number: 1_988,
+                    from: Wallet::from_str(payer_1).unwrap(),
+                    wei_amount: 4_503_599_627_370_496u128,
+                }],
+                rechecked_opt: Some(RecheckedTransactions {
+                    from_block: 1_975,
+                    to_block: 1_984,
+                    transactions: vec![],
+                }),
+                pending: vec![BlockchainTransaction {
+                    block_number: 1_995,
+                    from: Wallet::from_str(payer_2).unwrap(),
+                    wei_amount: 4_503_599_627_370_496u128,
+                }],
+            }
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Retrieving transactions from start block: Number(1975) to end block: Number(1990)"
+        ));
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Looking for pending payments up to block: 2000"
+        ));
+    }
+
+    #[test]
+    fn blockchain_interface_web3_credits_a_duplicated_log_entry_only_once() {
+        init_test_logging();
+        let test_name = "blockchain_interface_web3_credits_a_duplicated_log_entry_only_once";
+        let payer = "0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc";
+        let logs = vec![
+            make_transfer_log_json(46, payer, 1),
+            make_transfer_log_json(46, payer, 1),
+            make_transfer_log_json(47, payer, 2),
+        ];
+
+        let result = retrieve_transactions_with_confirmation_blocks(
+            test_name,
+            0,
+            "0x7d0", // 2_000
+            logs,
+            BlockMarker::Value(42),
+        );
+
+        assert_eq!(
+            result.transactions,
+            vec![
+                BlockchainTransaction {
+                    block_number: 46,
+                    from: Wallet::from_str(payer).unwrap(),
+                    wei_amount: 4_503_599_627_370_496u128,
+                },
+                BlockchainTransaction {
+                    block_number: 47,
+                    from: Wallet::from_str(payer).unwrap(),
+                    wei_amount: 4_503_599_627_370_496u128,
+                }
+            ]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring a malformed transfer log entry at transaction \
+            0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0601, log index 0: \
+            duplicate of an earlier entry"
+        ));
+    }
+
+    #[test]
+    fn recheck_from_block_opt_works() {
+        assert_eq!(
+            BlockchainInterfaceWeb3::recheck_from_block_opt(BlockMarker::Value(42), 10),
+            Some(32)
+        );
+        assert_eq!(
+            BlockchainInterfaceWeb3::recheck_from_block_opt(BlockMarker::Value(5), 10),
+            Some(0)
+        );
+        assert_eq!(
+            BlockchainInterfaceWeb3::recheck_from_block_opt(BlockMarker::Value(0), 10),
+            None
+        );
+        assert_eq!(
+            BlockchainInterfaceWeb3::recheck_from_block_opt(BlockMarker::Value(42), 0),
+            None
+        );
+        assert_eq!(
+            BlockchainInterfaceWeb3::recheck_from_block_opt(BlockMarker::Uninitialized, 10),
+            None
+        );
+    }
+
+    #[test]
+    fn pending_to_block_opt_works() {
+        type Subject = BlockchainInterfaceWeb3;
+
+        assert_eq!(
+            Subject::pending_to_block_opt(BlockMarker::Value(1_990), Some(2_000), 10),
+            Some(2_000)
+        );
+        assert_eq!(
+            Subject::pending_to_block_opt(BlockMarker::Value(1_032), Some(2_000), 10),
+            None
+        );
+        assert_eq!(
+            Subject::pending_to_block_opt(BlockMarker::Value(2_000), Some(2_000), 0),
+            None
+        );
+        assert_eq!(
+            Subject::pending_to_block_opt(BlockMarker::Value(1_990), None, 10),
+            None
+        );
+        assert_eq!(
+            Subject::pending_to_block_opt(BlockMarker::Uninitialized, Some(2_000), 10),
+            None
+        );
+        assert_eq!(
+            Subject::pending_to_block_opt(BlockMarker::Value(0), Some(5), 10),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn blockchain_interface_web3_credits_forwarded_payments_to_original_payers() {
+        init_test_logging();
+        let test_name = "blockchain_interface_web3_credits_forwarded_payments_to_original_payers";
+        let port = find_free_port();
+        let forwarder = Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182").unwrap();
+        let execute_input = concat!(
+            "0x47153f82",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "00000000000000000000000000000000000000000000000000000000000000e0",
+            "0000000000000000000000001111111111111111111111111111111111111111",
+            "000000000000000000000000cd6c588e005032dd882cd43bf53a32129be81302",
+        );
+        #[rustfmt::skip]
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x7d0", 1)
+            .raw_response(
+                r#"{
+                "jsonrpc":"2.0",
+                "id":2,
+                "result":[
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                        "blockNumber":"0x2e",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x0",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                        "transactionIndex":"0x0"
+                    },
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732b",
+                        "blockNumber":"0x2f",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x0",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0682",
+                        "transactionIndex":"0x0"
+                    },
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732c",
+                        "blockNumber":"0x30",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x0",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0683",
+                        "transactionIndex":"0x0"
+                    },
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732d",
+                        "blockNumber":"0x31",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x0",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0684",
+                        "transactionIndex":"0x0"
+                    }
+                ]
+            }"#.to_string()
+            )
+            .begin_batch()
+            .raw_response(format!(
+                r#"{{
+                "jsonrpc":"2.0",
+                "id":3,
+                "result":{{
+                    "hash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                    "from":"0x2222222222222222222222222222222222222222",
+                    "to":"0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182",
+                    "input":"{}"
+                }}
+            }}"#,
+                execute_input
+            ))
+            .raw_response(
+                r#"{
+                "jsonrpc":"2.0",
+                "id":4,
+                "result":{
+                    "hash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0682",
+                    "from":"0x3333333333333333333333333333333333333333",
+                    "to":"0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182",
+                    "input":"0xa9059cbb"
+                }
+            }"#.to_string()
+            )
+            .raw_response(r#"{"jsonrpc":"2.0","id":5,"result":null}"#.to_string())
+            .end_batch()
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.logger = Logger::new(test_name);
+        subject.payment_forwarders = vec![forwarder.address()];
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait()
+            .unwrap();
+
+        let senders = result
+            .transactions
+            .into_iter()
+            .map(|transaction| (transaction.block_number, transaction.from))
+            .collect::<Vec<(u64, Wallet)>>();
+        assert_eq!(
+            senders,
+            vec![
+                (
+                    46,
+                    Wallet::from_str("0x1111111111111111111111111111111111111111").unwrap()
+                ),
+                (
+                    47,
+                    Wallet::from_str("0x3333333333333333333333333333333333333333").unwrap()
+                ),
+                (48, forwarder),
+                (
+                    49,
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()
+                ),
+            ]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Couldn't find who paid through a forwarder in transaction \
+             0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0683; crediting the forwarder"
+        ));
+    }
+
+    fn make_payment_memo_test_logs_response(builder: MBCSBuilder) -> MBCSBuilder {
+        #[rustfmt::skip]
+        let builder = builder
+            .ok_response("0x7d0", 1)
+            .raw_response(
+                r#"{
+                "jsonrpc":"2.0",
+                "id":2,
+                "result":[
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                        "blockNumber":"0x2e",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x0",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000002222222222222222222222222222222222222222",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                        "transactionIndex":"0x0"
+                    },
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                        "blockNumber":"0x2f",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x0",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000003333333333333333333333333333333333333333",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0682",
+                        "transactionIndex":"0x0"
+                    }
+                ]
+            }"#.to_string()
+            )
+            ;
+        builder
+    }
+
+    #[test]
+    fn blockchain_interface_web3_reports_the_memos_of_received_payments() {
+        init_test_logging();
+        let test_name = "blockchain_interface_web3_reports_the_memos_of_received_payments";
+        let port = find_free_port();
+        let memo = PaymentMemo::from_public_key(&PublicKey::new(b"debtor"));
+        let tagged_input = format!(
+            "0x{}",
+            sign_transaction_data(0x10000000000000, make_wallet("creditor"), Some(memo))
+                .to_hex::<String>()
+        );
+        #[rustfmt::skip]
+        let _blockchain_client_server = make_payment_memo_test_logs_response(MBCSBuilder::new(port))
+            .begin_batch()
+            .raw_response(format!(
+                r#"{{
+                "jsonrpc":"2.0",
+                "id":3,
+                "result":{{
+                    "hash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                    "from":"0x2222222222222222222222222222222222222222",
+                    "to":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                    "input":"{}"
+                }}
+            }}"#,
+                tagged_input
+            ))
+            .raw_response(
+                r#"{
+                "jsonrpc":"2.0",
+                "id":4,
+                "result":{
+                    "hash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0682",
+                    "from":"0x3333333333333333333333333333333333333333",
+                    "to":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                    "input":"0xa9059cbb"
+                }
+            }"#.to_string()
+            )
+            .end_batch()
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.logger = Logger::new(test_name);
+        subject.payment_memo_opt = Some(PaymentMemo::from_public_key(&PublicKey::new(b"creditor")));
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait()
+            .unwrap();
+
+        assert_eq!(result.transactions.len(), 2);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "INFO: {test_name}: Payment from 0x2222222222222222222222222222222222222222 in \
+             transaction 0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681 \
+             carries the memo of Node {memo}"
+        ));
+        tlh.exists_no_log_containing(&format!(
+            "{test_name}: Payment from 0x3333333333333333333333333333333333333333"
+        ));
+    }
+
+    #[test]
+    fn blockchain_interface_web3_retrieves_payments_even_if_their_memos_cannot_be_read() {
+        init_test_logging();
+        let test_name =
+            "blockchain_interface_web3_retrieves_payments_even_if_their_memos_cannot_be_read";
+        let port = find_free_port();
+        let _blockchain_client_server =
+            make_payment_memo_test_logs_response(MBCSBuilder::new(port)).start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.logger = Logger::new(test_name);
+        subject.payment_memo_opt = Some(PaymentMemo::from_public_key(&PublicKey::new(b"creditor")));
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait()
+            .unwrap();
+
+        assert_eq!(result.transactions.len(), 2);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Couldn't read the memos of the received payments:"
+        ));
+    }
+
+    #[test]
+    fn blockchain_interface_web3_attributes_bundled_user_operation_payments_to_their_smart_accounts(
+    ) {
+        let port = find_free_port();
+        // A bundler's handleOps transaction to the ERC-4337 EntryPoint executing UserOperations
+        // of two smart accounts; each Transfer is logged as coming from the smart account itself
+        #[rustfmt::skip]
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x7d0", 1)
+            .raw_response(
+                r#"{
+                "jsonrpc":"2.0",
+                "id":2,
+                "result":[
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                        "blockNumber":"0x2e",
+                        "data":"0x0000000000000000000000000000000000000000000000000010000000000000",
+                        "logIndex":"0x3",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000004444444444444444444444444444444444444444",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                        "transactionIndex":"0x0"
+                    },
+                    {
+                        "address":"0xcd6c588e005032dd882cd43bf53a32129be81302",
+                        "blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a",
+                        "blockNumber":"0x2e",
+                        "data":"0x0000000000000000000000000000000000000000000000000020000000000000",
+                        "logIndex":"0x7",
+                        "removed":false,
+                        "topics":[
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x0000000000000000000000005555555555555555555555555555555555555555",
+                            "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+                        ],
+                        "transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681",
+                        "transactionIndex":"0x0"
+                    }
+                ]
+            }"#.to_string()
+            )
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.payment_forwarders =
+            vec![
+                Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182")
+                    .unwrap()
+                    .address(),
+            ];
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            result.transactions,
+            vec![
+                BlockchainTransaction {
+                    block_number: 46,
+                    from: Wallet::from_str("0x4444444444444444444444444444444444444444").unwrap(),
+                    wei_amount: 4_503_599_627_370_496u128,
+                },
+                BlockchainTransaction {
+                    block_number: 46,
+                    from: Wallet::from_str("0x5555555555555555555555555555555555555555").unwrap(),
+                    wei_amount: 9_007_199_254_740_992u128,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn blockchain_interface_web3_retrieve_transactions_fails_if_forwarded_payments_cannot_be_traced(
+    ) {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x400", 1)
+            .raw_response(r#"{"jsonrpc":"2.0","id":2,"result":[{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a","blockNumber":"0x2e","data":"0x0000000000000000000000000000000000000000000000000010000000000000","logIndex":"0x0","removed":false,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182","0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681","transactionIndex":"0x0"}]}"#.to_string())
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.payment_forwarders =
+            vec![
+                Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182")
+                    .unwrap()
+                    .address(),
+            ];
+
+        let result = subject
+            .retrieve_transactions(
+                BlockMarker::Value(42),
+                BlockScanRange::NoLimit,
+                vec![
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092")
+                        .unwrap()
+                        .address(),
+                ],
+            )
+            .wait();
+
+        assert_eq!(
+            result,
+            Err(QueryFailed(
+                "Transport error: Error(IncompleteMessage)".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn decode_forwarded_request_signer_reads_the_signer_of_the_relayed_request() {
+        let mut input = FORWARDER_EXECUTE_METHOD_ID.to_vec();
+        input.extend_from_slice(&abi_word(U256::from(0x40)));
+        input.extend_from_slice(&abi_word(U256::from(0xe0)));
+        input.extend_from_slice(&[0u8; 12]);
+        input.extend_from_slice(&[0x11; 20]);
+
+        let result = BlockchainInterfaceWeb3::decode_forwarded_request_signer(&input);
+
+        assert_eq!(result, Some(Address::from_slice(&[0x11; 20])));
+    }
+
+    #[test]
+    fn decode_forwarded_request_signer_rejects_unknown_or_malformed_calls() {
+        let mut wrong_selector = TRANSFER_METHOD_ID.to_vec();
+        wrong_selector.extend_from_slice(&abi_word(U256::from(0x20)));
+        wrong_selector.extend_from_slice(&[0x11; 32]);
+        let mut truncated = FORWARDER_EXECUTE_METHOD_ID.to_vec();
+        truncated.extend_from_slice(&abi_word(U256::from(0x20)));
+        truncated.extend_from_slice(&[0x11; 31]);
+        let mut offset_out_of_range = FORWARDER_EXECUTE_METHOD_ID.to_vec();
+        offset_out_of_range.extend_from_slice(&abi_word(U256::max_value()));
+        offset_out_of_range.extend_from_slice(&[0x11; 32]);
+
+        [
+            vec![],
+            FORWARDER_EXECUTE_METHOD_ID[0..3].to_vec(),
+            wrong_selector,
+            truncated,
+            offset_out_of_range,
+        ]
+        .iter()
+        .for_each(|input| {
+            assert_eq!(
+                BlockchainInterfaceWeb3::decode_forwarded_request_signer(input),
+                None,
+                "{:?}",
+                input
+            )
+        })
+    }
+
+    fn abi_word(value: U256) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        value.to_big_endian(&mut word);
+        word
+    }
+
+    #[test]
+    fn original_payer_falls_back_to_the_sender_of_the_transaction() {
+        let transaction = serde_json::json!({
+            "from": "0x3333333333333333333333333333333333333333",
+            "input": "0xa9059cbb"
+        });
+
+        let result = BlockchainInterfaceWeb3::original_payer(&transaction);
+
+        assert_eq!(result, Some(Address::from_slice(&[0x33; 20])));
+        assert_eq!(
+            BlockchainInterfaceWeb3::original_payer(&serde_json::Value::Null),
+            None
         );
-        TestLogHandler::new().exists_log_containing(&format!("DEBUG: {test_case}: Retrieving transactions {expected_log} for: 0x3f69…72fc chain_id: 137 contract: 0xee9a352f6aac4af1a5b9f467f6a93e0ffbe9dd35"));
     }
 
     #[test]
@@ -686,7 +2842,7 @@ mod tests {
             .retrieve_transactions(
                 BlockMarker::Value(42),
                 BlockScanRange::NoLimit,
-                to_wallet.address(),
+                vec![to_wallet.address()],
             )
             .wait();
 
@@ -694,7 +2850,9 @@ mod tests {
             result,
             Ok(RetrievedBlockchainTransactions {
                 new_start_block: BlockMarker::Value(1543664),
-                transactions: vec![]
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
             })
         );
     }
@@ -708,8 +2866,9 @@ mod tests {
     }
 
     #[test]
-    fn blockchain_interface_web3_retrieve_transactions_returns_an_error_if_a_response_with_too_few_topics_is_returned(
+    fn blockchain_interface_web3_retrieve_transactions_skips_a_log_with_too_few_topics_with_a_warning(
     ) {
+        init_test_logging();
         let port = find_free_port();
         let _blockchain_client_server = MBCSBuilder::new(port)
             .ok_response("0x178def", 1)
@@ -721,21 +2880,34 @@ mod tests {
             .retrieve_transactions(
                 BlockMarker::Value(42),
                 BlockScanRange::NoLimit,
-                Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
-                    .unwrap()
-                    .address(),
+                vec![
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                        .unwrap()
+                        .address(),
+                ],
             )
             .wait();
 
         assert_eq!(
-            result.expect_err("Expected an Err, got Ok"),
-            BlockchainError::InvalidResponse
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(0x178def + 1),
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
+            })
+        );
+        TestLogHandler::new().exists_log_containing(
+            "WARN: BlockchainInterface: Ignoring a malformed transfer log entry at transaction \
+            0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681, log index 0: \
+            no sender topic",
         );
     }
 
     #[test]
-    fn blockchain_interface_web3_retrieve_transactions_returns_an_error_if_a_response_with_data_that_is_too_long_is_returned(
+    fn blockchain_interface_web3_retrieve_transactions_skips_a_log_with_data_that_is_too_long_with_a_warning(
     ) {
+        init_test_logging();
         let port = find_free_port();
         let _blockchain_client_server = MBCSBuilder::new(port)
             .ok_response("0x178def", 1)
@@ -747,13 +2919,28 @@ mod tests {
             .retrieve_transactions(
                 BlockMarker::Uninitialized,
                 BlockScanRange::NoLimit,
-                Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
-                    .unwrap()
-                    .address(),
+                vec![
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                        .unwrap()
+                        .address(),
+                ],
             )
             .wait();
 
-        assert_eq!(result, Err(BlockchainError::InvalidResponse));
+        assert_eq!(
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: BlockMarker::Value(0x178def + 1),
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
+            })
+        );
+        TestLogHandler::new().exists_log_containing(
+            "WARN: BlockchainInterface: Ignoring a malformed transfer log entry at transaction \
+            0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681, log index 0: \
+            value of 33 bytes in the data",
+        );
     }
 
     #[test]
@@ -772,16 +2959,29 @@ mod tests {
         .unwrap();
 
         let end_block_nbr = BlockMarker::Value(1025u64);
-        let subject =
-            BlockchainInterfaceWeb3::new(transport, event_loop_handle, TEST_DEFAULT_CHAIN);
+        let subject = BlockchainInterfaceWeb3::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+            vec![],
+            0,
+            1,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+            None,
+            false,
+            0,
+            None,
+        );
 
         let result = subject
             .retrieve_transactions(
                 BlockMarker::Value(42),
                 BlockScanRange::Range(1000),
-                Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
-                    .unwrap()
-                    .address(),
+                vec![
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                        .unwrap()
+                        .address(),
+                ],
             )
             .wait();
 
@@ -789,7 +2989,9 @@ mod tests {
             result,
             Ok(RetrievedBlockchainTransactions {
                 new_start_block: end_block_nbr,
-                transactions: vec![]
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
             })
         );
         let test_log_handler = TestLogHandler::new();
@@ -814,9 +3016,11 @@ mod tests {
             .retrieve_transactions(
                 BlockMarker::Value(42),
                 BlockScanRange::NoLimit,
-                Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
-                    .unwrap()
-                    .address(),
+                vec![
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
+                        .unwrap()
+                        .address(),
+                ],
             )
             .wait();
 
@@ -825,9 +3029,198 @@ mod tests {
             result,
             Ok(RetrievedBlockchainTransactions {
                 new_start_block: expected_start_block,
-                transactions: vec![]
+                transactions: vec![],
+                rechecked_opt: None,
+                pending: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn submit_payables_in_batch_sends_only_the_payments_that_passed_simulation() {
+        init_test_logging();
+        let test_name = "submit_payables_in_batch_sends_only_the_payments_that_passed_simulation";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response(
+                "0x0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+                1,
+            )
+            .err_response(
+                3,
+                "execution reverted: Blacklistable: blacklisted".to_string(),
+                1,
+            )
+            .ok_response("0x01".to_string(), 1)
+            .begin_batch()
+            .ok_response("rpc_result".to_string(), 7)
+            .end_batch()
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.payment_simulation = true;
+        let consuming_wallet = make_paying_wallet(b"consuming_wallet");
+        let agent = BlockchainAgentMock::default()
+            .consuming_wallet_result(consuming_wallet)
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .get_chain_result(Chain::PolyMainnet);
+        let passing_account = make_payable_account(1);
+        let refused_account = make_payable_account(2);
+        let system = System::new(test_name);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+
+        let result = subject
+            .submit_payables_in_batch(
+                Logger::new(test_name),
+                Box::new(agent),
+                accountant.start().recipient(),
+                vec![passing_account.clone(), refused_account.clone()],
+            )
+            .wait();
+
+        System::current().stop();
+        system.run();
+        let processed_payments = result.unwrap();
+        assert_eq!(processed_payments.len(), 1);
+        match &processed_payments[0] {
+            Correct(pending_payable) => {
+                assert_eq!(pending_payable.recipient_wallet, passing_account.wallet)
+            }
+            failed => panic!("Expected the payment sent, but got {:?}", failed),
+        }
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        let fingerprint_seeds =
+            accountant_recording.get_record::<PendingPayableFingerprintSeeds>(0);
+        assert_eq!(fingerprint_seeds.hashes_and_balances.len(), 1);
+        assert_eq!(fingerprint_seeds.first_nonce, 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Leaving the payment of 2,000,000,000 wei of MASQ to {} out of the \
+             batch; the token contract would refuse it: execution reverted: Blacklistable: \
+             blacklisted",
+            refused_account.wallet
+        ));
+    }
+
+    #[test]
+    fn submit_payables_in_batch_sends_sub_batches_no_larger_than_max_batch_size() {
+        init_test_logging();
+        let test_name = "submit_payables_in_batch_sends_sub_batches_no_larger_than_max_batch_size";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x01".to_string(), 1)
+            .begin_batch()
+            .ok_response("rpc_result".to_string(), 7)
+            .ok_response("rpc_result_2".to_string(), 8)
+            .end_batch()
+            .ok_response("0x03".to_string(), 1)
+            .begin_batch()
+            .ok_response("rpc_result_3".to_string(), 7)
+            .end_batch()
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.max_batch_size = 2;
+        let agent = BlockchainAgentMock::default()
+            .consuming_wallet_result(make_paying_wallet(b"consuming_wallet"))
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .get_chain_result(Chain::PolyMainnet);
+        let accounts = vec![
+            make_payable_account(1),
+            make_payable_account(2),
+            make_payable_account(3),
+        ];
+        let system = System::new(test_name);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+
+        let result = subject
+            .submit_payables_in_batch(
+                Logger::new(test_name),
+                Box::new(agent),
+                accountant.start().recipient(),
+                accounts.clone(),
+            )
+            .wait();
+
+        System::current().stop();
+        system.run();
+        let processed_payments = result.unwrap();
+        let paid_wallets = processed_payments
+            .iter()
+            .map(|processed| match processed {
+                Correct(pending_payable) => pending_payable.recipient_wallet.clone(),
+                failed => panic!("Expected the payment sent, but got {:?}", failed),
             })
+            .collect::<Vec<Wallet>>();
+        assert_eq!(
+            paid_wallets,
+            accounts
+                .iter()
+                .map(|account| account.wallet.clone())
+                .collect::<Vec<Wallet>>()
         );
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 2);
+        let first_seeds = accountant_recording.get_record::<PendingPayableFingerprintSeeds>(0);
+        assert_eq!(first_seeds.hashes_and_balances.len(), 2);
+        assert_eq!(first_seeds.first_nonce, 1);
+        let second_seeds = accountant_recording.get_record::<PendingPayableFingerprintSeeds>(1);
+        assert_eq!(second_seeds.hashes_and_balances.len(), 1);
+        assert_eq!(second_seeds.first_nonce, 3);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Sending 3 payments in 2 sub-batches of at most 2"
+        ));
+    }
+
+    #[test]
+    fn submit_payables_in_batch_keeps_the_sub_batches_sent_when_a_later_one_fails() {
+        init_test_logging();
+        let test_name =
+            "submit_payables_in_batch_keeps_the_sub_batches_sent_when_a_later_one_fails";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x01".to_string(), 1)
+            .begin_batch()
+            .ok_response("rpc_result".to_string(), 7)
+            .end_batch()
+            .err_response(-32005, "Rate limit exceeded".to_string(), 1)
+            .start();
+        let mut subject = make_blockchain_interface_web3(port);
+        subject.max_batch_size = 1;
+        let agent = BlockchainAgentMock::default()
+            .consuming_wallet_result(make_paying_wallet(b"consuming_wallet"))
+            .agreed_fee_per_computation_unit_result(Wei::new(1))
+            .get_chain_result(Chain::PolyMainnet);
+        let sent_account = make_payable_account(1);
+        let unsent_account = make_payable_account(2);
+        let system = System::new(test_name);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+
+        let result = subject
+            .submit_payables_in_batch(
+                Logger::new(test_name),
+                Box::new(agent),
+                accountant.start().recipient(),
+                vec![sent_account.clone(), unsent_account.clone()],
+            )
+            .wait();
+
+        System::current().stop();
+        system.run();
+        let processed_payments = result.unwrap();
+        assert_eq!(processed_payments.len(), 1);
+        match &processed_payments[0] {
+            Correct(pending_payable) => {
+                assert_eq!(pending_payable.recipient_wallet, sent_account.wallet)
+            }
+            failed => panic!("Expected the payment sent, but got {:?}", failed),
+        }
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Sub-batch of 1 payments wasn't sent: Transaction id fetching failed"
+        ));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "the debts to {} are left for the next scan",
+            unsent_account.wallet
+        ));
     }
 
     #[test]
@@ -843,19 +3236,21 @@ mod tests {
                 "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(), // 65535
                 0,
             )
+            // latest_block
+            .raw_response(latest_block_response("0x1DCD6500")) // 500000000
             .start();
         let chain = Chain::PolyMainnet;
         let wallet = make_wallet("abc");
         let subject = make_blockchain_interface_web3(port);
 
         let result = subject
-            .build_blockchain_agent(wallet.clone())
+            .build_blockchain_agent(wallet.clone(), None)
             .wait()
             .unwrap();
 
         let expected_transaction_fee_balance = U256::from(65_520);
         let expected_masq_balance = U256::from(65_535);
-        let expected_gas_price_wei = 1_000_000_000;
+        let expected_gas_price_wei: u128 = 1_000_000_000;
         assert_eq!(result.consuming_wallet(), &wallet);
         assert_eq!(
             result.consuming_wallet_balances(),
@@ -866,7 +3261,7 @@ mod tests {
         );
         assert_eq!(
             result.agreed_fee_per_computation_unit(),
-            expected_gas_price_wei
+            Wei::new(expected_gas_price_wei)
         );
         let expected_fee_estimation = (3
             * (BlockchainInterfaceWeb3::web3_gas_limit_const_part(chain)
@@ -874,7 +3269,155 @@ mod tests {
             * expected_gas_price_wei) as u128;
         assert_eq!(
             result.estimated_transaction_fee_total(3),
-            expected_fee_estimation
+            Wei::new(expected_fee_estimation)
+        );
+        assert_eq!(
+            result.base_fee_per_computation_unit_opt(),
+            Some(Wei::new(500_000_000))
+        );
+        assert_eq!(result.block_computation_limit_opt(), Some(30_000_000))
+    }
+
+    fn latest_block_response(base_fee_per_gas: &str) -> String {
+        format!(
+            r#"{{"jsonrpc": "2.0", "result": {{"number": "0x3B3", "baseFeePerGas": "{}", "gasLimit": "0x1C9C380"}}, "id": 0}}"#,
+            base_fee_per_gas
+        )
+    }
+
+    #[test]
+    fn blockchain_interface_web3_raises_the_agents_gas_price_to_the_base_fee() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            // gas_price
+            .ok_response("0x3B9ACA00".to_string(), 0) // 1000000000
+            // transaction_fee_balance
+            .ok_response("0xFFF0".to_string(), 0)
+            // masq_balance
+            .ok_response(
+                "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(),
+                0,
+            )
+            // latest_block
+            .raw_response(latest_block_response("0x35A4E900")) // 900000000
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject
+            .build_blockchain_agent(make_wallet("abc"), Some(600_000_000))
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            result.agreed_fee_per_computation_unit(),
+            Wei::new(900_000_000)
+        );
+        assert_eq!(
+            result.base_fee_per_computation_unit_opt(),
+            Some(Wei::new(900_000_000))
+        )
+    }
+
+    #[test]
+    fn blockchain_interface_web3_builds_blockchain_agent_even_without_the_latest_block() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            // gas_price
+            .ok_response("0x3B9ACA00".to_string(), 0) // 1000000000
+            // transaction_fee_balance
+            .ok_response("0xFFF0".to_string(), 0)
+            // masq_balance
+            .ok_response(
+                "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(),
+                0,
+            )
+            // latest_block
+            .ok_response("block".to_string(), 0)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject
+            .build_blockchain_agent(make_wallet("abc"), None)
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            result.agreed_fee_per_computation_unit(),
+            Wei::new(1_000_000_000)
+        );
+        assert_eq!(result.base_fee_per_computation_unit_opt(), None);
+        assert_eq!(result.block_computation_limit_opt(), None)
+    }
+
+    #[test]
+    fn blockchain_interface_web3_blends_the_fee_history_estimate_into_the_agents_gas_price() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            // gas_price
+            .ok_response("0x3B9ACA00".to_string(), 0) // 1000000000
+            // transaction_fee_balance
+            .ok_response("0xFFF0".to_string(), 0)
+            // masq_balance
+            .ok_response(
+                "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(),
+                0,
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = subject
+            .build_blockchain_agent(make_wallet("abc"), Some(600_000_000))
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            result.agreed_fee_per_computation_unit(),
+            Wei::new(800_000_000)
+        )
+    }
+
+    #[test]
+    fn blockchain_interface_web3_raises_the_agents_gas_price_to_the_base_fee_of_the_next_block() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .scripted_result(
+                "eth_feeHistory",
+                serde_json::json!({
+                    "oldestBlock": "0x3B3",
+                    "baseFeePerGas": ["0x35A4E900", "0x47868C00"], // 900000000, 1200000000
+                    "gasUsedRatio": [1.0]
+                }),
+            )
+            // gas_price
+            .ok_response("0x3B9ACA00".to_string(), 0) // 1000000000
+            // transaction_fee_balance
+            .ok_response("0xFFF0".to_string(), 0)
+            // masq_balance
+            .ok_response(
+                "0x000000000000000000000000000000000000000000000000000000000000FFFF".to_string(),
+                0,
+            )
+            // latest_block
+            .raw_response(latest_block_response("0x35A4E900")) // 900000000
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        *subject.capabilities_arc.lock().unwrap() = BlockchainCapabilities {
+            batch_requests: true,
+            fee_history: true,
+        };
+
+        let result = subject
+            .build_blockchain_agent(make_wallet("abc"), None)
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            result.agreed_fee_per_computation_unit(),
+            Wei::new(1_200_000_000)
+        );
+        assert_eq!(
+            result.base_fee_per_computation_unit_opt(),
+            Some(Wei::new(900_000_000))
         )
     }
 
@@ -886,7 +3429,7 @@ mod tests {
     {
         let wallet = make_wallet("bcd");
         let subject = make_blockchain_interface_web3(port);
-        let result = subject.build_blockchain_agent(wallet.clone()).wait();
+        let result = subject.build_blockchain_agent(wallet.clone(), None).wait();
         let err = match result {
             Err(e) => e,
             _ => panic!("we expected Err() but got Ok()"),
@@ -902,7 +3445,11 @@ mod tests {
         let wallet = make_wallet("abc");
         let subject = make_blockchain_interface_web3(port);
 
-        let err = subject.build_blockchain_agent(wallet).wait().err().unwrap();
+        let err = subject
+            .build_blockchain_agent(wallet, None)
+            .wait()
+            .err()
+            .unwrap();
 
         let expected_err = BlockchainAgentBuildError::GasPrice(QueryFailed(
             "Transport error: Error(IncompleteMessage)".to_string(),
@@ -1086,6 +3633,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn process_transaction_receipts_asks_for_receipts_one_by_one_if_batches_are_not_supported() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .scripted_result("eth_getTransactionReceipt", Value::Null)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        *subject.capabilities_arc.lock().unwrap() = BlockchainCapabilities {
+            batch_requests: false,
+            fee_history: false,
+        };
+        let tx_hash_1 =
+            H256::from_str("a128f9ca1e705cc20a936a24a7fa1df73bad6e0aaf58e8e6ffcc154a7cff6e0e")
+                .unwrap();
+        let tx_hash_2 =
+            H256::from_str("a128f9ca1e705cc20a936a24a7fa1df73bad6e0aaf58e8e6ffcc154a7cff6e0f")
+                .unwrap();
+
+        let result = subject
+            .process_transaction_receipts(vec![tx_hash_1, tx_hash_2])
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    transaction_hash: tx_hash_1,
+                    status: TxStatus::Pending,
+                }),
+                TransactionReceiptResult::RpcResponse(TxReceipt {
+                    transaction_hash: tx_hash_2,
+                    status: TxStatus::Pending,
+                }),
+            ]
+        );
+        let requests = blockchain_client_server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|request| request.contains("\r\n\r\n{\"jsonrpc\"")));
+    }
+
     #[test]
     fn web3_gas_limit_const_part_returns_reasonable_values() {
         type Subject = BlockchainInterfaceWeb3;
@@ -1256,4 +3846,34 @@ mod tests {
             BlockMarker::Uninitialized
         );
     }
+
+    #[test]
+    fn rpc_usage_meter_counts_single_requests_and_each_request_of_a_batch() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x3B9ACA00".to_string(), 0)
+            .begin_batch()
+            .ok_response("0x7".to_string(), 1)
+            .ok_response("0x23".to_string(), 2)
+            .end_batch()
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let lower_interface = subject.lower_interface();
+        let web3_batch = lower_interface.get_web3_batch();
+
+        lower_interface.get_gas_price().wait().unwrap();
+        let _ = web3_batch.eth().block_number();
+        let _ = web3_batch
+            .eth()
+            .balance(make_wallet("wallet").address(), None);
+        web3_batch.transport().submit_batch().wait().unwrap();
+
+        assert_eq!(
+            subject.rpc_usage_meter().take(),
+            RpcUsageCount {
+                calls: 3,
+                weight: 50
+            }
+        );
+    }
 }
@@ -0,0 +1,321 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use web3::types::{Address, Log, H256, U256};
+
+// A standard ERC-20 Transfer event carries the sender and the recipient as indexed topics and the
+// value in the data; some tokens index the value too, leaving the data empty
+const VALUE_TOPIC_INDEX: usize = 3;
+const WORD_LEN: usize = 32;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DecodedTransfer {
+    pub block_number: u64,
+    pub transaction_hash_opt: Option<H256>,
+    pub from: Address,
+    pub wei_amount: u128,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TransferLogDefect {
+    MissingBlockNumber,
+    MissingSender,
+    MissingValue,
+    ValueTooLong(usize),
+    ValueOutOfRange(U256),
+    Duplicate,
+}
+
+impl Display for TransferLogDefect {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MissingBlockNumber => write!(f, "no block number"),
+            Self::MissingSender => write!(f, "no sender topic"),
+            Self::MissingValue => write!(f, "no value in either the data or the topics"),
+            Self::ValueTooLong(len) => write!(f, "value of {} bytes in the data", len),
+            Self::ValueOutOfRange(value) => write!(f, "value {} out of range", value),
+            Self::Duplicate => write!(f, "duplicate of an earlier entry"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RejectedTransferLog {
+    pub transaction_hash_opt: Option<H256>,
+    pub log_index_opt: Option<U256>,
+    pub defect: TransferLogDefect,
+}
+
+impl Display for RejectedTransferLog {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let describe = |value_opt: Option<String>| value_opt.unwrap_or_else(|| "?".to_string());
+        write!(
+            f,
+            "transaction {}, log index {}: {}",
+            describe(self.transaction_hash_opt.map(|hash| format!("{:?}", hash))),
+            describe(self.log_index_opt.map(|index| index.to_string())),
+            self.defect
+        )
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DecodedTransferLogs {
+    pub transfers: Vec<DecodedTransfer>,
+    pub rejected: Vec<RejectedTransferLog>,
+}
+
+// A malformed entry costs only itself; the rest of the batch is decoded as usual
+pub fn decode_transfer_logs(logs: &[Log]) -> DecodedTransferLogs {
+    let mut seen_entries: HashSet<(H256, U256)> = HashSet::new();
+    logs.iter()
+        .fold(DecodedTransferLogs::default(), |mut decoded, log| {
+            let is_duplicate = match (log.transaction_hash, log.log_index) {
+                (Some(hash), Some(index)) => !seen_entries.insert((hash, index)),
+                _ => false,
+            };
+            let result = if is_duplicate {
+                Err(TransferLogDefect::Duplicate)
+            } else {
+                decode_transfer_log(log)
+            };
+            match result {
+                Ok(transfer) => decoded.transfers.push(transfer),
+                Err(defect) => decoded.rejected.push(RejectedTransferLog {
+                    transaction_hash_opt: log.transaction_hash,
+                    log_index_opt: log.log_index,
+                    defect,
+                }),
+            }
+            decoded
+        })
+}
+
+fn decode_transfer_log(log: &Log) -> Result<DecodedTransfer, TransferLogDefect> {
+    let block_number = log
+        .block_number
+        .ok_or(TransferLogDefect::MissingBlockNumber)?
+        .as_u64();
+    let sender_topic = log.topics.get(1).ok_or(TransferLogDefect::MissingSender)?;
+    let value = decode_value(log)?;
+    if value > U256::from(u128::MAX) {
+        return Err(TransferLogDefect::ValueOutOfRange(value));
+    }
+    Ok(DecodedTransfer {
+        block_number,
+        transaction_hash_opt: log.transaction_hash,
+        from: Address::from_slice(&sender_topic.0[WORD_LEN - 20..]),
+        wei_amount: value.as_u128(),
+    })
+}
+
+fn decode_value(log: &Log) -> Result<U256, TransferLogDefect> {
+    let data = &log.data.0;
+    match (data.len(), log.topics.get(VALUE_TOPIC_INDEX)) {
+        (0, Some(value_topic)) => Ok(U256::from(&value_topic.0[..])),
+        (0, None) => Err(TransferLogDefect::MissingValue),
+        // Shorter data is taken for a big-endian number without its leading zeros
+        (len, _) if len <= WORD_LEN => Ok(U256::from(data.as_slice())),
+        (len, _) => Err(TransferLogDefect::ValueTooLong(len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use web3::types::Bytes;
+
+    fn make_log(
+        transaction_hash_opt: Option<H256>,
+        log_index_opt: Option<u64>,
+        topics: Vec<H256>,
+        data: Vec<u8>,
+    ) -> Log {
+        serde_json::from_value(json!({
+            "address": Address::from_low_u64_be(1),
+            "topics": topics,
+            "data": Bytes(data),
+            "blockHash": null,
+            "blockNumber": "0x4d2", // 1234
+            "transactionHash": transaction_hash_opt,
+            "transactionIndex": null,
+            "logIndex": log_index_opt.map(U256::from),
+            "removed": false,
+        }))
+        .unwrap()
+    }
+
+    fn sender_topic() -> H256 {
+        H256::from_low_u64_be(0xabcd)
+    }
+
+    fn recipient_topic() -> H256 {
+        H256::from_low_u64_be(0x1234)
+    }
+
+    fn value_word(value: u128) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        U256::from(value).to_big_endian(&mut word);
+        word
+    }
+
+    #[test]
+    fn decodes_the_value_from_the_data_of_a_standard_transfer() {
+        let hash = H256::from_low_u64_be(1);
+        let log = make_log(
+            Some(hash),
+            Some(0),
+            vec![H256::zero(), sender_topic(), recipient_topic()],
+            value_word(4_567).to_vec(),
+        );
+
+        let result = decode_transfer_logs(&[log]);
+
+        assert_eq!(
+            result,
+            DecodedTransferLogs {
+                transfers: vec![DecodedTransfer {
+                    block_number: 1234,
+                    transaction_hash_opt: Some(hash),
+                    from: Address::from_low_u64_be(0xabcd),
+                    wei_amount: 4_567,
+                }],
+                rejected: vec![],
+            }
+        )
+    }
+
+    #[test]
+    fn decodes_the_value_from_the_topics_of_a_transfer_with_an_indexed_value() {
+        let log = make_log(
+            None,
+            None,
+            vec![
+                H256::zero(),
+                sender_topic(),
+                recipient_topic(),
+                H256::from(value_word(98_765)),
+            ],
+            vec![],
+        );
+
+        let result = decode_transfer_logs(&[log]);
+
+        assert_eq!(result.rejected, vec![]);
+        assert_eq!(result.transfers[0].wei_amount, 98_765);
+        assert_eq!(result.transfers[0].from, Address::from_low_u64_be(0xabcd));
+    }
+
+    #[test]
+    fn decodes_a_value_stripped_of_its_leading_zeros() {
+        let log = make_log(
+            None,
+            None,
+            vec![H256::zero(), sender_topic(), recipient_topic()],
+            vec![0x01, 0x00],
+        );
+
+        let result = decode_transfer_logs(&[log]);
+
+        assert_eq!(result.transfers[0].wei_amount, 256);
+    }
+
+    #[test]
+    fn rejects_duplicated_entries_but_keeps_the_first_one() {
+        let hash = H256::from_low_u64_be(7);
+        let topics = vec![H256::zero(), sender_topic(), recipient_topic()];
+        let first = make_log(Some(hash), Some(3), topics.clone(), value_word(10).to_vec());
+        let duplicate = first.clone();
+        let same_tx_other_index = make_log(Some(hash), Some(4), topics, value_word(20).to_vec());
+
+        let result = decode_transfer_logs(&[first, duplicate, same_tx_other_index]);
+
+        assert_eq!(
+            result
+                .transfers
+                .iter()
+                .map(|transfer| transfer.wei_amount)
+                .collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+        assert_eq!(
+            result.rejected,
+            vec![RejectedTransferLog {
+                transaction_hash_opt: Some(hash),
+                log_index_opt: Some(U256::from(3)),
+                defect: TransferLogDefect::Duplicate,
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_entries_one_by_one_and_decodes_the_rest() {
+        let hash = |n| Some(H256::from_low_u64_be(n));
+        let good_topics = vec![H256::zero(), sender_topic(), recipient_topic()];
+        let mut no_block_number = make_log(
+            hash(1),
+            Some(0),
+            good_topics.clone(),
+            value_word(1).to_vec(),
+        );
+        no_block_number.block_number = None;
+        let no_sender = make_log(hash(2), Some(0), vec![H256::zero()], value_word(2).to_vec());
+        let no_value = make_log(hash(3), Some(0), good_topics.clone(), vec![]);
+        let too_long = make_log(hash(4), Some(0), good_topics.clone(), vec![0; 33]);
+        let out_of_range = make_log(hash(5), Some(0), good_topics.clone(), vec![0xff; 32]);
+        let good = make_log(hash(6), Some(0), good_topics, value_word(6).to_vec());
+
+        let result = decode_transfer_logs(&[
+            no_block_number,
+            no_sender,
+            no_value,
+            too_long,
+            out_of_range,
+            good,
+        ]);
+
+        assert_eq!(result.transfers.len(), 1);
+        assert_eq!(result.transfers[0].wei_amount, 6);
+        assert_eq!(
+            result
+                .rejected
+                .iter()
+                .map(|rejected| rejected.defect.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                TransferLogDefect::MissingBlockNumber,
+                TransferLogDefect::MissingSender,
+                TransferLogDefect::MissingValue,
+                TransferLogDefect::ValueTooLong(33),
+                TransferLogDefect::ValueOutOfRange(U256::max_value()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejected_transfer_log_displays_its_whereabouts() {
+        let subject = RejectedTransferLog {
+            transaction_hash_opt: Some(H256::from_low_u64_be(0x2a)),
+            log_index_opt: Some(U256::from(5)),
+            defect: TransferLogDefect::ValueTooLong(33),
+        };
+        let anonymous = RejectedTransferLog {
+            transaction_hash_opt: None,
+            log_index_opt: None,
+            defect: TransferLogDefect::MissingBlockNumber,
+        };
+
+        assert_eq!(
+            subject.to_string(),
+            "transaction 0x000000000000000000000000000000000000000000000000000000000000002a, \
+             log index 5: value of 33 bytes in the data"
+        );
+        assert_eq!(
+            anonymous.to_string(),
+            "transaction ?, log index ?: no block number"
+        );
+    }
+}
@@ -2,37 +2,49 @@
 
 use crate::accountant::db_access_objects::payable_dao::PayableAccount;
 use crate::accountant::db_access_objects::pending_payable_dao::PendingPayable;
-use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::agent_web3::BlockchainAgentWeb3;
+use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::agent_web3::{
+    BlockchainAgentWeb3, ROUTER_GAS_LIMIT_PER_RECIPIENT,
+};
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
-use crate::blockchain::blockchain_bridge::PendingPayableFingerprintSeeds;
+use crate::blockchain::blockchain_bridge::{
+    CreditorShare, PendingPayableFingerprintSeeds, PendingPayableReplacement, ReplacementBasis,
+};
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{with_timeout, LatestBlockInfo, TransferSimulation};
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::{
-    BlockchainInterfaceWeb3, HashAndAmount, TRANSFER_METHOD_ID,
+    BlockchainInterfaceWeb3, HashAndAmount, DISPERSE_TOKEN_METHOD_ID, TRANSFER_METHOD_ID,
 };
 use crate::blockchain::blockchain_interface::data_structures::errors::PayableTransactionError;
 use crate::blockchain::blockchain_interface::data_structures::{
     ProcessedPayableFallible, RpcPayableFailure,
 };
+use crate::blockchain::blockchain_interface::lower_level_interface::LowBlockchainInt;
+use crate::blockchain::payment_memo::PaymentMemo;
+use crate::blockchain::rpc_usage::MeteredHttp;
 use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
 use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wei::Wei;
 use actix::Recipient;
-use futures::Future;
+use futures::future::{err, ok};
+use futures::{stream, Future, Stream};
+use itertools::Itertools;
 use masq_lib::blockchains::chains::Chain;
 use masq_lib::logger::Logger;
 use secp256k1secrets::SecretKey;
 use serde_json::Value;
 use std::iter::once;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use thousands::Separable;
-use web3::transports::{Batch, Http};
-use web3::types::{Bytes, SignedTransaction, TransactionParameters, U256};
+use web3::transports::Batch;
+use web3::types::{Address, Bytes, SignedTransaction, TransactionParameters, H256, U256};
 use web3::Error as Web3Error;
 use web3::Web3;
 
 #[derive(Debug)]
 pub struct BlockchainAgentFutureResult {
-    pub gas_price_wei: U256,
+    pub gas_price: Wei,
     pub transaction_fee_balance: U256,
     pub masq_token_balance: U256,
+    pub latest_block_info_opt: Option<LatestBlockInfo>,
 }
 pub fn advance_used_nonce(current_nonce: U256) -> U256 {
     current_nonce
@@ -85,6 +97,7 @@ pub fn merged_output_data(
 
 pub fn transmission_log(
     chain: Chain,
+    token_symbol: &str,
     accounts: &[PayableAccount],
     gas_price_in_wei: u128,
 ) -> String {
@@ -102,9 +115,10 @@ pub fn transmission_log(
         \n\
         gas price:                                   {} wei\n\
         chain:                                       {}\n\
+        token:                                       {}\n\
         \n\
         [wallet address]                             [payment in wei]\n",
-        gas_price_in_wei, chain_name
+        gas_price_in_wei, chain_name, token_symbol
     ));
     let body = accounts.iter().map(|account| {
         format!(
@@ -116,15 +130,22 @@ pub fn transmission_log(
     introduction.chain(body).collect()
 }
 
-pub fn sign_transaction_data(amount: u128, recipient_wallet: Wallet) -> [u8; 68] {
+pub fn sign_transaction_data(
+    amount: u128,
+    recipient_wallet: Wallet,
+    memo_opt: Option<PaymentMemo>,
+) -> Vec<u8> {
     let mut data = [0u8; 4 + 32 + 32];
     data[0..4].copy_from_slice(&TRANSFER_METHOD_ID);
     data[16..36].copy_from_slice(&recipient_wallet.address().0[..]);
     U256::from(amount).to_big_endian(&mut data[36..68]);
-    data
+    match memo_opt {
+        Some(memo) => [&data[..], &memo.to_bytes()[..]].concat(),
+        None => data.to_vec(),
+    }
 }
 
-pub fn gas_limit(data: [u8; 68], chain: Chain) -> U256 {
+pub fn gas_limit(data: &[u8], chain: Chain) -> U256 {
     let base_gas_limit = BlockchainInterfaceWeb3::web3_gas_limit_const_part(chain);
     ethereum_types::U256::try_from(data.iter().fold(base_gas_limit, |acc, v| {
         acc + if v == &0u8 { 4 } else { 68 }
@@ -134,15 +155,16 @@ pub fn gas_limit(data: [u8; 68], chain: Chain) -> U256 {
 
 pub fn sign_transaction(
     chain: Chain,
-    web3_batch: &Web3<Batch<Http>>,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
     recipient_wallet: Wallet,
     consuming_wallet: Wallet,
     amount: u128,
     nonce: U256,
     gas_price_in_wei: u128,
+    memo_opt: Option<PaymentMemo>,
 ) -> SignedTransaction {
-    let data = sign_transaction_data(amount, recipient_wallet);
-    let gas_limit = gas_limit(data, chain);
+    let data = sign_transaction_data(amount, recipient_wallet, memo_opt);
+    let gas_limit = gas_limit(&data, chain);
     // Warning: If you set gas_price or nonce to None in transaction_parameters, sign_transaction will start making RPC calls which we don't want (Do it at your own risk).
     let transaction_parameters = TransactionParameters {
         nonce: Some(nonce),
@@ -150,7 +172,7 @@ pub fn sign_transaction(
         gas: gas_limit,
         gas_price: Some(U256::from(gas_price_in_wei)),
         value: ethereum_types::U256::zero(),
-        data: Bytes(data.to_vec()),
+        data: Bytes(data),
         chain_id: Some(chain.rec().num_chain_id),
     };
     let key = consuming_wallet
@@ -161,7 +183,7 @@ pub fn sign_transaction(
 }
 
 pub fn sign_transaction_locally(
-    web3_batch: &Web3<Batch<Http>>,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
     transaction_parameters: TransactionParameters,
     key: &SecretKey,
 ) -> SignedTransaction {
@@ -182,11 +204,12 @@ pub fn sign_transaction_locally(
 
 pub fn sign_and_append_payment(
     chain: Chain,
-    web3_batch: &Web3<Batch<Http>>,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
     recipient: &PayableAccount,
     consuming_wallet: Wallet,
     nonce: U256,
     gas_price_in_wei: u128,
+    memo_opt: Option<PaymentMemo>,
 ) -> HashAndAmount {
     let signed_tx = sign_transaction(
         chain,
@@ -196,6 +219,7 @@ pub fn sign_and_append_payment(
         recipient.balance_wei,
         nonce,
         gas_price_in_wei,
+        memo_opt,
     );
     append_signed_transaction_to_batch(web3_batch, signed_tx.raw_transaction);
 
@@ -205,26 +229,33 @@ pub fn sign_and_append_payment(
     }
 }
 
-pub fn append_signed_transaction_to_batch(web3_batch: &Web3<Batch<Http>>, raw_transaction: Bytes) {
+pub fn append_signed_transaction_to_batch(
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    raw_transaction: Bytes,
+) {
     // This function only prepares a raw transaction for a batch call and doesn't actually send it right here.
     web3_batch.eth().send_raw_transaction(raw_transaction);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn sign_and_append_multiple_payments(
     logger: &Logger,
     chain: Chain,
-    web3_batch: &Web3<Batch<Http>>,
+    token_symbol: &str,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
     consuming_wallet: Wallet,
     gas_price_in_wei: u128,
     mut pending_nonce: U256,
     accounts: &[PayableAccount],
+    memo_opt: Option<PaymentMemo>,
 ) -> Vec<HashAndAmount> {
     let mut hash_and_amount_list = vec![];
     accounts.iter().for_each(|payable| {
         debug!(
             logger,
-            "Preparing payable future of {} wei to {} with nonce {}",
+            "Preparing payable future of {} wei of {} to {} with nonce {}",
             payable.balance_wei.separate_with_commas(),
+            token_symbol,
             payable.wallet,
             pending_nonce
         );
@@ -236,6 +267,7 @@ pub fn sign_and_append_multiple_payments(
             consuming_wallet.clone(),
             pending_nonce,
             gas_price_in_wei,
+            memo_opt,
         );
 
         pending_nonce = advance_used_nonce(pending_nonce);
@@ -244,23 +276,256 @@ pub fn sign_and_append_multiple_payments(
     hash_and_amount_list
 }
 
+// Tries out every transfer with eth_call first. The ones the token contract would refuse, as when
+// the token is paused or a wallet is blacklisted, are left out of the batch so that no gas is spent
+// on them; their debts stay on record for the next payable scan. A transfer whose simulation
+// doesn't come to a verdict is sent anyway
+pub fn simulate_payments(
+    logger: Logger,
+    lower_interface: Box<dyn LowBlockchainInt>,
+    consuming_wallet: &Wallet,
+    token_symbol: String,
+    accounts: Vec<PayableAccount>,
+    memo_opt: Option<PaymentMemo>,
+) -> Box<dyn Future<Item = Vec<PayableAccount>, Error = PayableTransactionError>> {
+    let from = consuming_wallet.address();
+    Box::new(
+        stream::iter_ok::<_, PayableTransactionError>(accounts)
+            .and_then(move |account| {
+                let data =
+                    sign_transaction_data(account.balance_wei, account.wallet.clone(), memo_opt);
+                lower_interface
+                    .simulate_transfer(from, data)
+                    .then(move |verdict| Ok::<_, PayableTransactionError>((account, verdict)))
+            })
+            .collect()
+            .and_then(move |accounts_and_verdicts| {
+                let passed = accounts_and_verdicts
+                    .into_iter()
+                    .filter(|(account, verdict)| match verdict {
+                        Ok(TransferSimulation::Passed) => true,
+                        Ok(TransferSimulation::Refused(reason)) => {
+                            warning!(
+                                logger,
+                                "Leaving the payment of {} wei of {} to {} out of the batch; the \
+                                 token contract would refuse it: {}",
+                                account.balance_wei.separate_with_commas(),
+                                token_symbol,
+                                account.wallet,
+                                reason
+                            );
+                            false
+                        }
+                        Err(e) => {
+                            warning!(
+                                logger,
+                                "Sending the payment of {} wei of {} to {} unsimulated: {}",
+                                account.balance_wei.separate_with_commas(),
+                                token_symbol,
+                                account.wallet,
+                                e
+                            );
+                            true
+                        }
+                    })
+                    .map(|(account, _)| account)
+                    .collect::<Vec<PayableAccount>>();
+                if passed.is_empty() {
+                    Err(PayableTransactionError::Simulation(
+                        "The token contract would refuse every payment".to_string(),
+                    ))
+                } else {
+                    Ok(passed)
+                }
+            }),
+    )
+}
+
+// Fetches the pending nonce and sends the accounts as one batch, through the payment router
+// if the chain has one and there's more than one account to pay
+#[allow(clippy::too_many_arguments)]
+pub fn send_payables(
+    logger: &Logger,
+    lower_interface: &dyn LowBlockchainInt,
+    chain: Chain,
+    token_symbol: &str,
+    request_timeout: Duration,
+    consuming_wallet: Wallet,
+    gas_price_in_wei: u128,
+    new_fingerprints_recipient: Recipient<PendingPayableFingerprintSeeds>,
+    accounts: Vec<PayableAccount>,
+    memo_opt: Option<PaymentMemo>,
+) -> Box<dyn Future<Item = Vec<ProcessedPayableFallible>, Error = PayableTransactionError> + 'static>
+{
+    let logger = logger.clone();
+    let token_symbol = token_symbol.to_string();
+    let web3_batch = lower_interface.get_web3_batch();
+    let payment_router = payment_router_if_allowed(
+        &logger,
+        lower_interface,
+        chain.rec().payment_router_opt,
+        &consuming_wallet,
+        &accounts,
+    );
+    let pending_nonce = lower_interface
+        .get_transaction_id(consuming_wallet.address())
+        .map_err(PayableTransactionError::TransactionID);
+    Box::new(payment_router.join(pending_nonce).and_then(
+        move |(payment_router_opt, pending_nonce)| match payment_router_opt {
+            // The router makes the transfers itself, so these payments carry no memo
+            Some(router_address) => send_payables_through_router(
+                &logger,
+                chain,
+                &token_symbol,
+                router_address,
+                &web3_batch,
+                request_timeout,
+                consuming_wallet,
+                gas_price_in_wei,
+                pending_nonce,
+                new_fingerprints_recipient,
+                accounts,
+            ),
+            _ => send_payables_within_batch(
+                &logger,
+                chain,
+                &token_symbol,
+                &web3_batch,
+                request_timeout,
+                consuming_wallet,
+                gas_price_in_wei,
+                pending_nonce,
+                new_fingerprints_recipient,
+                accounts,
+                memo_opt,
+            ),
+        },
+    ))
+}
+
+// The router pays out of an allowance the consuming wallet must have granted it beforehand;
+// if that doesn't cover the whole batch, or can't be read, the payments go as separate transfers
+pub fn payment_router_if_allowed(
+    logger: &Logger,
+    lower_interface: &dyn LowBlockchainInt,
+    payment_router_opt: Option<Address>,
+    consuming_wallet: &Wallet,
+    accounts: &[PayableAccount],
+) -> Box<dyn Future<Item = Option<Address>, Error = PayableTransactionError> + 'static> {
+    let router_address = match payment_router_opt {
+        Some(router_address) if accounts.len() > 1 => router_address,
+        _ => return Box::new(ok(None)),
+    };
+    let logger = logger.clone();
+    let total_amount: u128 = accounts.iter().map(|account| account.balance_wei).sum();
+    Box::new(
+        lower_interface
+            .get_token_allowance(consuming_wallet.address(), router_address)
+            .then(move |result| {
+                match result {
+                    Ok(allowance) if allowance >= U256::from(total_amount) => {
+                        return Ok(Some(router_address))
+                    }
+                    Ok(allowance) => warning!(
+                        logger,
+                        "Allowance of {} wei for the payment router {:?} doesn't cover the {} wei \
+                         to be paid; sending the payments as separate transfers",
+                        allowance,
+                        router_address,
+                        total_amount.separate_with_commas()
+                    ),
+                    Err(e) => warning!(
+                        logger,
+                        "Cannot read the allowance for the payment router {:?}: {:?}; sending \
+                         the payments as separate transfers",
+                        router_address,
+                        e
+                    ),
+                }
+                Ok(None)
+            }),
+    )
+}
+
+// With no limit, or with fewer accounts than the limit, everything goes in a single batch
+pub fn split_into_sub_batches(
+    accounts: Vec<PayableAccount>,
+    max_batch_size: u16,
+) -> Vec<Vec<PayableAccount>> {
+    let max_batch_size = max_batch_size as usize;
+    if max_batch_size == 0 || accounts.len() <= max_batch_size {
+        vec![accounts]
+    } else {
+        accounts
+            .chunks(max_batch_size)
+            .map(|sub_batch| sub_batch.to_vec())
+            .collect()
+    }
+}
+
+// A sub-batch that failed as a whole is reported as failed payments, so that the sub-batches
+// around it still count. If it failed before anything was signed, it has no transactions whose
+// fingerprints would need to be cleaned up; its payments are then left out for the next scan.
+pub fn sub_batch_failure(
+    logger: &Logger,
+    error: PayableTransactionError,
+    accounts: Vec<PayableAccount>,
+) -> Vec<ProcessedPayableFallible> {
+    match error {
+        PayableTransactionError::Sending { msg, hashes } if !hashes.is_empty() => {
+            warning!(
+                logger,
+                "Sub-batch of {} payments failed: {}",
+                accounts.len(),
+                msg
+            );
+            // A router sub-batch has a single transaction for all its payments
+            accounts
+                .into_iter()
+                .zip(hashes.into_iter().cycle())
+                .map(|(account, hash)| {
+                    ProcessedPayableFallible::Failed(RpcPayableFailure {
+                        rpc_error: Web3Error::Transport(msg.clone()),
+                        recipient_wallet: account.wallet,
+                        hash,
+                    })
+                })
+                .collect()
+        }
+        e => {
+            warning!(
+                logger,
+                "Sub-batch of {} payments wasn't sent: {}; the debts to {} are left for the next scan",
+                accounts.len(),
+                e,
+                accounts.iter().map(|account| &account.wallet).join(", ")
+            );
+            vec![]
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn send_payables_within_batch(
     logger: &Logger,
     chain: Chain,
-    web3_batch: &Web3<Batch<Http>>,
+    token_symbol: &str,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
     consuming_wallet: Wallet,
     gas_price_in_wei: u128,
     pending_nonce: U256,
     new_fingerprints_recipient: Recipient<PendingPayableFingerprintSeeds>,
     accounts: Vec<PayableAccount>,
+    memo_opt: Option<PaymentMemo>,
 ) -> Box<dyn Future<Item = Vec<ProcessedPayableFallible>, Error = PayableTransactionError> + 'static>
 {
     debug!(
             logger,
-            "Common attributes of payables to be transacted: sender wallet: {}, contract: {:?}, chain_id: {}, gas_price: {}",
+            "Common attributes of payables to be transacted: sender wallet: {}, contract: {:?}, token: {}, chain_id: {}, gas_price: {}",
             consuming_wallet,
             chain.rec().contract,
+            token_symbol,
             chain.rec().num_chain_id,
             gas_price_in_wei
         );
@@ -268,11 +533,13 @@ pub fn send_payables_within_batch(
     let hashes_and_paid_amounts = sign_and_append_multiple_payments(
         logger,
         chain,
+        token_symbol,
         web3_batch,
         consuming_wallet,
         gas_price_in_wei,
         pending_nonce,
         &accounts,
+        memo_opt,
     );
 
     let timestamp = SystemTime::now();
@@ -284,19 +551,20 @@ pub fn send_payables_within_batch(
         .try_send(PendingPayableFingerprintSeeds {
             batch_wide_timestamp: timestamp,
             hashes_and_balances: hashes_and_paid_amounts,
+            batch_wide_gas_price_wei: gas_price_in_wei,
+            first_nonce: pending_nonce.as_u64(),
+            creditor_shares: vec![],
         })
         .expect("Accountant is dead");
 
     info!(
         logger,
         "{}",
-        transmission_log(chain, &accounts, gas_price_in_wei)
+        transmission_log(chain, token_symbol, &accounts, gas_price_in_wei)
     );
 
     Box::new(
-        web3_batch
-            .transport()
-            .submit_batch()
+        submit_batch_within(web3_batch, request_timeout)
             .map_err(|e| error_with_hashes(e, hashes_and_paid_amounts_error))
             .and_then(move |batch_response| {
                 Ok(merged_output_data(
@@ -308,6 +576,376 @@ pub fn send_payables_within_batch(
     )
 }
 
+pub fn router_transaction_data(token_contract: Address, accounts: &[PayableAccount]) -> Vec<u8> {
+    let recipients_count = accounts.len();
+    let recipients_offset = 3 * 32;
+    let amounts_offset = recipients_offset + 32 * (1 + recipients_count);
+    let head = [
+        abi_address_word(token_contract),
+        abi_uint_word(U256::from(recipients_offset)),
+        abi_uint_word(U256::from(amounts_offset)),
+        abi_uint_word(U256::from(recipients_count)),
+    ];
+    let recipients = accounts
+        .iter()
+        .map(|account| abi_address_word(account.wallet.address()));
+    let amounts_length = once(abi_uint_word(U256::from(recipients_count)));
+    let amounts = accounts
+        .iter()
+        .map(|account| abi_uint_word(U256::from(account.balance_wei)));
+    once(DISPERSE_TOKEN_METHOD_ID.to_vec())
+        .chain(
+            head.into_iter()
+                .chain(recipients)
+                .chain(amounts_length)
+                .chain(amounts)
+                .map(|word| word.to_vec()),
+        )
+        .flatten()
+        .collect()
+}
+
+fn abi_address_word(address: Address) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..32].copy_from_slice(&address.0[..]);
+    word
+}
+
+fn abi_uint_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+pub fn router_gas_limit(data: &[u8], chain: Chain, recipients_count: usize) -> U256 {
+    let base_gas_limit = BlockchainInterfaceWeb3::web3_gas_limit_const_part(chain)
+        + ROUTER_GAS_LIMIT_PER_RECIPIENT * recipients_count as u128;
+    ethereum_types::U256::try_from(data.iter().fold(base_gas_limit, |acc, v| {
+        acc + if v == &0u8 { 4 } else { 68 }
+    }))
+    .expect("Internal error")
+}
+
+pub fn sign_router_transaction(
+    chain: Chain,
+    router_address: Address,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    consuming_wallet: Wallet,
+    accounts: &[PayableAccount],
+    nonce: U256,
+    gas_price_in_wei: u128,
+) -> SignedTransaction {
+    let data = router_transaction_data(chain.rec().contract, accounts);
+    let gas_limit = router_gas_limit(&data, chain, accounts.len());
+    let transaction_parameters = TransactionParameters {
+        nonce: Some(nonce),
+        to: Some(router_address),
+        gas: gas_limit,
+        gas_price: Some(U256::from(gas_price_in_wei)),
+        value: ethereum_types::U256::zero(),
+        data: Bytes(data),
+        chain_id: Some(chain.rec().num_chain_id),
+    };
+    let key = consuming_wallet
+        .prepare_secp256k1_secret()
+        .expect("Consuming wallet doesn't contain a secret key");
+
+    sign_transaction_locally(web3_batch, transaction_parameters, &key)
+}
+
+pub fn router_output_data(
+    rpc_result: web3::transports::Result<Value>,
+    hash: H256,
+    accounts: Vec<PayableAccount>,
+) -> Vec<ProcessedPayableFallible> {
+    accounts
+        .into_iter()
+        .map(|account| match &rpc_result {
+            Ok(_rpc_result) => ProcessedPayableFallible::Correct(PendingPayable {
+                recipient_wallet: account.wallet,
+                hash,
+            }),
+            Err(rpc_error) => ProcessedPayableFallible::Failed(RpcPayableFailure {
+                rpc_error: rpc_error.clone(),
+                recipient_wallet: account.wallet,
+                hash,
+            }),
+        })
+        .collect()
+}
+
+// The router requires an allowance for the whole amount granted by the consuming wallet
+// in advance (see payment_router_if_allowed); all the payables share a single transaction
+// and therefore a single fingerprint.
+#[allow(clippy::too_many_arguments)]
+pub fn send_payables_through_router(
+    logger: &Logger,
+    chain: Chain,
+    token_symbol: &str,
+    router_address: Address,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
+    consuming_wallet: Wallet,
+    gas_price_in_wei: u128,
+    pending_nonce: U256,
+    new_fingerprints_recipient: Recipient<PendingPayableFingerprintSeeds>,
+    accounts: Vec<PayableAccount>,
+) -> Box<dyn Future<Item = Vec<ProcessedPayableFallible>, Error = PayableTransactionError> + 'static>
+{
+    debug!(
+        logger,
+        "Common attributes of payables to be transacted through router {:?}: sender wallet: {}, \
+        contract: {:?}, token: {}, chain_id: {}, gas_price: {}, nonce: {}",
+        router_address,
+        consuming_wallet,
+        chain.rec().contract,
+        token_symbol,
+        chain.rec().num_chain_id,
+        gas_price_in_wei,
+        pending_nonce
+    );
+
+    let signed_tx = sign_router_transaction(
+        chain,
+        router_address,
+        web3_batch,
+        consuming_wallet,
+        &accounts,
+        pending_nonce,
+        gas_price_in_wei,
+    );
+    append_signed_transaction_to_batch(web3_batch, signed_tx.raw_transaction);
+    let hash_and_amount = HashAndAmount {
+        hash: signed_tx.transaction_hash,
+        amount: accounts.iter().map(|account| account.balance_wei).sum(),
+    };
+    // Each creditor's debt must be lessened by its own share once the transaction is confirmed
+    let creditor_shares = accounts
+        .iter()
+        .map(|account| CreditorShare {
+            hash: hash_and_amount.hash,
+            wallet: account.wallet.clone(),
+            amount: account.balance_wei,
+        })
+        .collect();
+
+    new_fingerprints_recipient
+        .try_send(PendingPayableFingerprintSeeds {
+            batch_wide_timestamp: SystemTime::now(),
+            hashes_and_balances: vec![hash_and_amount],
+            batch_wide_gas_price_wei: gas_price_in_wei,
+            first_nonce: pending_nonce.as_u64(),
+            creditor_shares,
+        })
+        .expect("Accountant is dead");
+
+    info!(
+        logger,
+        "{}",
+        transmission_log(chain, token_symbol, &accounts, gas_price_in_wei)
+    );
+
+    Box::new(
+        submit_batch_within(web3_batch, request_timeout)
+            .map_err(move |e| error_with_hashes(e, vec![hash_and_amount]))
+            .and_then(move |mut batch_response| {
+                let rpc_result = batch_response.remove(0);
+                Ok(router_output_data(
+                    rpc_result,
+                    hash_and_amount.hash,
+                    accounts,
+                ))
+            }),
+    )
+}
+
+// The replacement is signed the same way as the transaction it outbids and takes over its
+// fingerprint; the Accountant learns the new hash before anything leaves, as with new payments
+#[allow(clippy::too_many_arguments)]
+pub fn send_replacement(
+    logger: &Logger,
+    chain: Chain,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
+    consuming_wallet: Wallet,
+    rowid: u64,
+    basis: ReplacementBasis,
+    replacement_recipient: Recipient<PendingPayableReplacement>,
+    accounts: Vec<PayableAccount>,
+    memo_opt: Option<PaymentMemo>,
+) -> Box<dyn Future<Item = H256, Error = PayableTransactionError> + 'static> {
+    let nonce = U256::from(basis.nonce);
+    let hash = match (chain.rec().payment_router_opt, accounts.as_slice()) {
+        (_, [account]) => {
+            sign_and_append_payment(
+                chain,
+                web3_batch,
+                account,
+                consuming_wallet,
+                nonce,
+                basis.gas_price_wei,
+                memo_opt,
+            )
+            .hash
+        }
+        (Some(router_address), [_, _, ..]) => {
+            let signed_tx = sign_router_transaction(
+                chain,
+                router_address,
+                web3_batch,
+                consuming_wallet,
+                &accounts,
+                nonce,
+                basis.gas_price_wei,
+            );
+            append_signed_transaction_to_batch(web3_batch, signed_tx.raw_transaction);
+            signed_tx.transaction_hash
+        }
+        _ => {
+            return Box::new(err(PayableTransactionError::Signing(format!(
+                "A single transaction paying {} accounts cannot be built on {}",
+                accounts.len(),
+                chain.rec().literal_identifier
+            ))))
+        }
+    };
+
+    replacement_recipient
+        .try_send(PendingPayableReplacement { rowid, hash, basis })
+        .expect("Accountant is dead");
+
+    info!(
+        logger,
+        "Replacing the transaction of fingerprint {} by {:?} with nonce {} and gas price {} wei",
+        rowid,
+        hash,
+        basis.nonce,
+        basis.gas_price_wei
+    );
+
+    submit_single_transaction(web3_batch, request_timeout, hash)
+}
+
+// Empties the service fee balance into the recipient's wallet. No fingerprint is made: the
+// transaction pays no debt, so the Accountant has nothing to confirm, nor does it carry a memo
+#[allow(clippy::too_many_arguments)]
+pub fn send_sweep(
+    logger: &Logger,
+    chain: Chain,
+    token_symbol: &str,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
+    consuming_wallet: Wallet,
+    gas_price_in_wei: u128,
+    nonce: U256,
+    recipient: PayableAccount,
+) -> Box<dyn Future<Item = H256, Error = PayableTransactionError> + 'static> {
+    let hash = sign_and_append_payment(
+        chain,
+        web3_batch,
+        &recipient,
+        consuming_wallet,
+        nonce,
+        gas_price_in_wei,
+        None,
+    )
+    .hash;
+
+    info!(
+        logger,
+        "Sweeping {} wei of {} to {} by {:?} with nonce {} and gas price {} wei",
+        recipient.balance_wei.separate_with_commas(),
+        token_symbol,
+        recipient.wallet,
+        hash,
+        nonce,
+        gas_price_in_wei
+    );
+
+    submit_single_transaction(web3_batch, request_timeout, hash)
+}
+
+// The gas of a plain transfer of the native currency, which carries no data
+pub const VOID_TRANSACTION_GAS_LIMIT: u64 = 21_000;
+
+// Spends the nonce of an abandoned transaction by a transfer of nothing to the consuming wallet
+// itself, so that the abandoned one can never be mined anymore. No fingerprint is made; the debts
+// the abandoned transaction was to pay are already back with the payable scanner
+pub fn send_void(
+    logger: &Logger,
+    chain: Chain,
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
+    consuming_wallet: Wallet,
+    basis: ReplacementBasis,
+) -> Box<dyn Future<Item = H256, Error = PayableTransactionError> + 'static> {
+    let transaction_parameters = TransactionParameters {
+        nonce: Some(U256::from(basis.nonce)),
+        to: Some(consuming_wallet.address()),
+        gas: U256::from(VOID_TRANSACTION_GAS_LIMIT),
+        gas_price: Some(U256::from(basis.gas_price_wei)),
+        value: U256::zero(),
+        data: Bytes(vec![]),
+        chain_id: Some(chain.rec().num_chain_id),
+    };
+    let key = consuming_wallet
+        .prepare_secp256k1_secret()
+        .expect("Consuming wallet doesn't contain a secret key");
+    let signed_tx = sign_transaction_locally(web3_batch, transaction_parameters, &key);
+    append_signed_transaction_to_batch(web3_batch, signed_tx.raw_transaction);
+    let hash = signed_tx.transaction_hash;
+
+    info!(
+        logger,
+        "Voiding nonce {} by {:?}, a transfer of nothing to {} with gas price {} wei",
+        basis.nonce,
+        hash,
+        consuming_wallet,
+        basis.gas_price_wei
+    );
+
+    submit_single_transaction(web3_batch, request_timeout, hash)
+}
+
+fn submit_single_transaction(
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
+    hash: H256,
+) -> Box<dyn Future<Item = H256, Error = PayableTransactionError> + 'static> {
+    Box::new(
+        submit_batch_within(web3_batch, request_timeout)
+            .map_err(move |e| PayableTransactionError::Sending {
+                msg: e.to_string(),
+                hashes: vec![hash],
+            })
+            .and_then(move |mut batch_response| match batch_response.remove(0) {
+                Ok(_) => Ok(hash),
+                Err(rpc_error) => Err(PayableTransactionError::Sending {
+                    msg: rpc_error.to_string(),
+                    hashes: vec![hash],
+                }),
+            }),
+    )
+}
+
+// The transactions might have reached the blockchain service even though it didn't answer in time,
+// so their hashes stay with the error for the pending payable scan to look them up later
+fn submit_batch_within(
+    web3_batch: &Web3<Batch<MeteredHttp>>,
+    request_timeout: Duration,
+) -> Box<dyn Future<Item = Vec<web3::transports::Result<Value>>, Error = Web3Error>> {
+    with_timeout(
+        web3_batch.transport().submit_batch(),
+        request_timeout,
+        move || {
+            Web3Error::Transport(format!(
+                "No response within {} ms",
+                request_timeout.as_millis()
+            ))
+        },
+    )
+}
+
 pub fn create_blockchain_agent_web3(
     gas_limit_const_part: u128,
     blockchain_agent_future_result: BlockchainAgentFutureResult,
@@ -315,7 +953,7 @@ pub fn create_blockchain_agent_web3(
     chain: Chain,
 ) -> Box<dyn BlockchainAgent> {
     Box::new(BlockchainAgentWeb3::new(
-        blockchain_agent_future_result.gas_price_wei.as_u128(),
+        blockchain_agent_future_result.gas_price,
         gas_limit_const_part,
         wallet,
         ConsumingWalletBalances {
@@ -323,6 +961,7 @@ pub fn create_blockchain_agent_web3(
                 .transaction_fee_balance,
             masq_token_balance_in_minor_units: blockchain_agent_future_result.masq_token_balance,
         },
+        blockchain_agent_future_result.latest_block_info_opt,
         chain,
     ))
 }
@@ -344,9 +983,11 @@ mod tests {
     use crate::blockchain::blockchain_interface::data_structures::ProcessedPayableFallible::{
         Correct, Failed,
     };
+    use crate::blockchain::payment_memo::PAYMENT_MEMO_LEN;
     use crate::blockchain::test_utils::{
-        make_tx_hash, transport_error_code, transport_error_message,
+        make_blockchain_interface_web3, make_tx_hash, transport_error_code, transport_error_message,
     };
+    use crate::sub_lib::cryptde::PublicKey;
     use crate::sub_lib::wallet::Wallet;
     use crate::test_utils::make_paying_wallet;
     use crate::test_utils::make_wallet;
@@ -357,16 +998,20 @@ mod tests {
     use ethereum_types::H256;
     use jsonrpc_core::ErrorCode::ServerError;
     use jsonrpc_core::{Error, ErrorCode};
-    use masq_lib::constants::{DEFAULT_CHAIN, DEFAULT_GAS_PRICE};
+    use masq_lib::constants::{
+        DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS, DEFAULT_CHAIN, DEFAULT_GAS_PRICE,
+    };
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
     use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
     use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use masq_lib::utils::find_free_port;
+    use rustc_hex::ToHex;
     use serde_json::Value;
     use std::net::Ipv4Addr;
     use std::str::FromStr;
-    use std::time::SystemTime;
+    use std::time::{Duration, SystemTime};
     use web3::api::Namespace;
+    use web3::transports::Http;
     use web3::Error::Rpc;
 
     #[test]
@@ -390,7 +1035,7 @@ mod tests {
         let gas_price_in_gwei = DEFAULT_GAS_PRICE;
         let consuming_wallet = make_paying_wallet(b"paying_wallet");
         let account = make_payable_account(1);
-        let web3_batch = Web3::new(Batch::new(transport));
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
 
         let result = sign_and_append_payment(
             chain,
@@ -399,6 +1044,7 @@ mod tests {
             consuming_wallet,
             pending_nonce.into(),
             gwei_to_wei(gas_price_in_gwei),
+            None,
         );
 
         let mut batch_result = web3_batch.eth().transport().submit_batch().wait().unwrap();
@@ -429,7 +1075,7 @@ mod tests {
             REQUESTS_IN_PARALLEL,
         )
         .unwrap();
-        let web3_batch = Web3::new(Batch::new(transport));
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
         let chain = DEFAULT_CHAIN;
         let gas_price_in_gwei = DEFAULT_GAS_PRICE;
         let pending_nonce = 1;
@@ -441,11 +1087,13 @@ mod tests {
         let result = sign_and_append_multiple_payments(
             &logger,
             chain,
+            "MASQ",
             &web3_batch,
             consuming_wallet,
             gwei_to_wei(gas_price_in_gwei),
             pending_nonce.into(),
             &accounts,
+            None,
         );
 
         assert_eq!(
@@ -469,6 +1117,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn split_into_sub_batches_leaves_accounts_together_without_limit_or_within_it() {
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+
+        let without_limit = split_into_sub_batches(accounts.clone(), 0);
+        let within_limit = split_into_sub_batches(accounts.clone(), 2);
+        let nothing = split_into_sub_batches(vec![], 2);
+
+        assert_eq!(without_limit, vec![accounts.clone()]);
+        assert_eq!(within_limit, vec![accounts]);
+        assert_eq!(nothing, vec![vec![]]);
+    }
+
+    #[test]
+    fn split_into_sub_batches_splits_accounts_over_the_limit_in_order() {
+        let accounts = (1..=5).map(make_payable_account).collect::<Vec<_>>();
+
+        let result = split_into_sub_batches(accounts.clone(), 2);
+
+        assert_eq!(
+            result,
+            vec![
+                accounts[0..2].to_vec(),
+                accounts[2..4].to_vec(),
+                accounts[4..5].to_vec()
+            ]
+        );
+    }
+
+    #[test]
+    fn sub_batch_failure_reports_each_payment_of_a_sub_batch_that_failed_to_be_sent() {
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+        let hash_1 = make_tx_hash(123);
+        let hash_2 = make_tx_hash(456);
+        let error = Sending {
+            msg: "Transport error: connection refused".to_string(),
+            hashes: vec![hash_1, hash_2],
+        };
+
+        let result = sub_batch_failure(&Logger::new("test"), error, accounts.clone());
+
+        let rpc_error = Web3Error::Transport("Transport error: connection refused".to_string());
+        assert_eq!(
+            result,
+            vec![
+                Failed(RpcPayableFailure {
+                    rpc_error: rpc_error.clone(),
+                    recipient_wallet: accounts[0].wallet.clone(),
+                    hash: hash_1,
+                }),
+                Failed(RpcPayableFailure {
+                    rpc_error,
+                    recipient_wallet: accounts[1].wallet.clone(),
+                    hash: hash_2,
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn sub_batch_failure_gives_all_payments_of_a_router_sub_batch_its_single_hash() {
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+        let hash = make_tx_hash(789);
+        let error = Sending {
+            msg: "Request timed out".to_string(),
+            hashes: vec![hash],
+        };
+
+        let result = sub_batch_failure(&Logger::new("test"), error, accounts);
+
+        assert_eq!(result.len(), 2);
+        result.into_iter().for_each(|processed| match processed {
+            Failed(failure) => assert_eq!(failure.hash, hash),
+            correct => panic!("Expected a failed payment, but got {:?}", correct),
+        })
+    }
+
+    #[test]
+    fn sub_batch_failure_leaves_out_payments_of_a_sub_batch_that_was_never_signed() {
+        init_test_logging();
+        let test_name =
+            "sub_batch_failure_leaves_out_payments_of_a_sub_batch_that_was_never_signed";
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+        let error = PayableTransactionError::Signing("No key".to_string());
+
+        let result = sub_batch_failure(&Logger::new(test_name), error, accounts.clone());
+
+        assert_eq!(result, vec![]);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Sub-batch of 2 payments wasn't sent: Signing phase: \"No key\"; \
+             the debts to {}, {} are left for the next scan",
+            accounts[0].wallet, accounts[1].wallet
+        ));
+    }
+
     #[test]
     fn transmission_log_just_works() {
         init_test_logging();
@@ -498,7 +1241,7 @@ mod tests {
         info!(
             logger,
             "{}",
-            transmission_log(TEST_DEFAULT_CHAIN, &accounts_to_process, gas_price)
+            transmission_log(TEST_DEFAULT_CHAIN, "tMASQ", &accounts_to_process, gas_price)
         );
 
         let log_handler = TestLogHandler::new();
@@ -508,6 +1251,7 @@ mod tests {
         \n\
         gas price:                                   120 wei\n\
         chain:                                       sepolia\n\
+        token:                                       tMASQ\n\
         \n\
         [wallet address]                             [payment in wei]\n\
         0x0000000000000000000000000000000077313233   900,000,000,000,000,000\n\
@@ -587,7 +1331,7 @@ mod tests {
         .unwrap();
         let gas_price = 1_000_000_000;
         let pending_nonce: U256 = 1.into();
-        let web3_batch = Web3::new(Batch::new(transport));
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
         let (accountant, _, accountant_recording) = make_recorder();
         let logger = Logger::new(test_name);
         let chain = DEFAULT_CHAIN;
@@ -599,12 +1343,15 @@ mod tests {
         let result = send_payables_within_batch(
             &logger,
             chain,
+            "MASQ",
             &web3_batch,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
             consuming_wallet.clone(),
             gas_price,
             pending_nonce,
             new_fingerprints_recipient,
             accounts.clone(),
+            None,
         )
         .wait();
 
@@ -617,9 +1364,11 @@ mod tests {
         assert_eq!(accountant_recording_result.len(), 1);
         assert!(timestamp_before <= ppfs_message.batch_wide_timestamp);
         assert!(timestamp_after >= ppfs_message.batch_wide_timestamp);
+        assert_eq!(ppfs_message.batch_wide_gas_price_wei, gas_price);
+        assert_eq!(ppfs_message.first_nonce, 1);
         let tlh = TestLogHandler::new();
         tlh.exists_log_containing(
-            &format!("DEBUG: {test_name}: Common attributes of payables to be transacted: sender wallet: {}, contract: {:?}, chain_id: {}, gas_price: {}",
+            &format!("DEBUG: {test_name}: Common attributes of payables to be transacted: sender wallet: {}, contract: {:?}, token: MASQ, chain_id: {}, gas_price: {}",
                      consuming_wallet,
                      chain.rec().contract,
                      chain.rec().num_chain_id,
@@ -628,11 +1377,100 @@ mod tests {
         );
         tlh.exists_log_containing(&format!(
             "INFO: {test_name}: {}",
-            transmission_log(chain, &accounts, gas_price)
+            transmission_log(chain, "MASQ", &accounts, gas_price)
         ));
         assert_eq!(result, expected_result);
     }
 
+    const TRANSFER_RETURNED_TRUE: &str =
+        "0x0000000000000000000000000000000000000000000000000000000000000001";
+    const TRANSFER_RETURNED_FALSE: &str =
+        "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fn simulate_payments_leaves_out_the_payments_the_token_contract_would_refuse() {
+        init_test_logging();
+        let test_name = "simulate_payments_leaves_out_the_payments_the_token_contract_would_refuse";
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response(TRANSFER_RETURNED_TRUE.to_string(), 1)
+            .err_response(3, "execution reverted: Pausable: paused".to_string(), 1)
+            .ok_response(TRANSFER_RETURNED_FALSE.to_string(), 1)
+            .err_response(-32005, "Rate limit exceeded".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let consuming_wallet = make_paying_wallet(b"consuming_wallet");
+        let accounts = (1..=4).map(make_payable_account).collect::<Vec<_>>();
+
+        let result = simulate_payments(
+            Logger::new(test_name),
+            subject.lower_interface(),
+            &consuming_wallet,
+            "MASQ".to_string(),
+            accounts.clone(),
+            None,
+        )
+        .wait();
+
+        assert_eq!(result, Ok(vec![accounts[0].clone(), accounts[3].clone()]));
+        let requests = blockchain_client_server.requests();
+        assert_eq!(requests.len(), 4);
+        let expected_data = format!(
+            "0x{}",
+            sign_transaction_data(accounts[0].balance_wei, accounts[0].wallet.clone(), None)
+                .to_hex::<String>()
+        );
+        assert!(
+            requests[0].contains("\"method\":\"eth_call\"")
+                && requests[0].contains(&format!("{:?}", consuming_wallet.address()))
+                && requests[0].contains(&expected_data),
+            "{}",
+            requests[0]
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Leaving the payment of 2,000,000,000 wei of MASQ to {} out of the \
+             batch; the token contract would refuse it: execution reverted: Pausable: paused",
+            accounts[1].wallet
+        ));
+        log_handler.exists_log_containing(&format!(
+            "WARN: {test_name}: Leaving the payment of 3,000,000,000 wei of MASQ to {} out of the \
+             batch; the token contract would refuse it: The token contract returned false",
+            accounts[2].wallet
+        ));
+        log_handler.exists_log_matching(&format!(
+            "WARN: {test_name}: Sending the payment of 4,000,000,000 wei of MASQ to {} unsimulated: \
+             .*Rate limit exceeded",
+            accounts[3].wallet
+        ));
+    }
+
+    #[test]
+    fn simulate_payments_fails_if_the_token_contract_would_refuse_every_payment() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .err_response(-32000, "execution reverted".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+
+        let result = simulate_payments(
+            Logger::new("test"),
+            subject.lower_interface(),
+            &make_paying_wallet(b"consuming_wallet"),
+            "MASQ".to_string(),
+            vec![make_payable_account(1)],
+            None,
+        )
+        .wait();
+
+        assert_eq!(
+            result,
+            Err(PayableTransactionError::Simulation(
+                "The token contract would refuse every payment".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn send_payables_within_batch_works() {
         let accounts = vec![make_payable_account(1), make_payable_account(2)];
@@ -806,15 +1644,30 @@ mod tests {
 
         sign_transaction(
             Chain::PolyAmoy,
-            &Web3::new(Batch::new(transport)),
+            &Web3::new(Batch::new(MeteredHttp::from(transport))),
             recipient_wallet,
             consuming_wallet,
             444444,
             nonce,
             gas_price,
+            None,
         );
     }
 
+    #[test]
+    fn sign_transaction_data_appends_the_payment_memo() {
+        let memo = PaymentMemo::from_public_key(&PublicKey::new(b"debtor"));
+        let recipient_wallet = make_wallet("recipient_wallet");
+        let untagged = sign_transaction_data(1_234, recipient_wallet.clone(), None);
+
+        let result = sign_transaction_data(1_234, recipient_wallet, Some(memo));
+
+        assert_eq!(result.len(), 68 + PAYMENT_MEMO_LEN);
+        assert_eq!(&result[..68], &untagged[..]);
+        assert_eq!(&result[68..], &memo.to_bytes()[..]);
+        assert!(gas_limit(&result, DEFAULT_CHAIN) > gas_limit(&untagged, DEFAULT_CHAIN));
+    }
+
     #[test]
     fn sign_transaction_just_works() {
         let port = find_free_port();
@@ -831,24 +1684,25 @@ mod tests {
         let recipient_wallet = make_wallet("recipient_wallet");
         let consuming_wallet = make_paying_wallet(b"consuming_wallet");
         let consuming_wallet_secret_key = consuming_wallet.prepare_secp256k1_secret().unwrap();
-        let data = sign_transaction_data(amount, recipient_wallet.clone());
+        let data = sign_transaction_data(amount, recipient_wallet.clone(), None);
         let tx_parameters = TransactionParameters {
             nonce: Some(nonce),
             to: Some(chain.rec().contract),
-            gas: gas_limit(data, chain),
+            gas: gas_limit(&data, chain),
             gas_price: Some(U256::from(gas_price_in_wei)),
             value: U256::zero(),
-            data: Bytes(data.to_vec()),
+            data: Bytes(data),
             chain_id: Some(chain.rec().num_chain_id),
         };
         let result = sign_transaction(
             chain,
-            &Web3::new(Batch::new(transport)),
+            &Web3::new(Batch::new(MeteredHttp::from(transport))),
             recipient_wallet,
             consuming_wallet,
             amount,
             nonce,
             gas_price_in_wei,
+            None,
         );
 
         let expected_tx_result = web3
@@ -875,7 +1729,7 @@ mod tests {
         let gas_price = U256::from(5);
         let recipient_wallet = make_wallet("recipient_wallet");
         let consuming_wallet = make_paying_wallet(b"consuming_wallet");
-        let data = sign_transaction_data(amount, recipient_wallet);
+        let data = sign_transaction_data(amount, recipient_wallet, None);
         // sign_transaction makes a blockchain call because nonce is set to None
         let transaction_parameters = TransactionParameters {
             nonce: None,
@@ -883,7 +1737,7 @@ mod tests {
             gas: gas_limit,
             gas_price: Some(gas_price),
             value: U256::zero(),
-            data: Bytes(data.to_vec()),
+            data: Bytes(data),
             chain_id: Some(chain.rec().num_chain_id),
         };
         let key = consuming_wallet
@@ -891,7 +1745,7 @@ mod tests {
             .expect("Consuming wallet doesn't contain a secret key");
 
         let _result = sign_transaction_locally(
-            &Web3::new(Batch::new(transport)),
+            &Web3::new(Batch::new(MeteredHttp::from(transport))),
             transaction_parameters,
             &key,
         );
@@ -1027,12 +1881,13 @@ mod tests {
 
         let signed_transaction = sign_transaction(
             chain,
-            &Web3::new(Batch::new(transport)),
+            &Web3::new(Batch::new(MeteredHttp::from(transport))),
             payable_account.wallet,
             consuming_wallet,
             payable_account.balance_wei,
             nonce_correct_type,
             gwei_to_wei(gas_price_in_gwei),
+            None,
         );
 
         let byte_set_to_compare = signed_transaction.raw_transaction.0;
@@ -1042,9 +1897,9 @@ mod tests {
     fn test_gas_limit_is_between_limits(chain: Chain) {
         let not_under_this_value = BlockchainInterfaceWeb3::web3_gas_limit_const_part(chain);
         let not_above_this_value = not_under_this_value + WEB3_MAXIMAL_GAS_LIMIT_MARGIN;
-        let data = sign_transaction_data(1_000_000_000, make_wallet("wallet1"));
+        let data = sign_transaction_data(1_000_000_000, make_wallet("wallet1"), None);
 
-        let gas_limit = gas_limit(data, chain);
+        let gas_limit = gas_limit(&data, chain);
 
         assert!(
             gas_limit >= U256::from(not_under_this_value),
@@ -1059,4 +1914,656 @@ mod tests {
             not_above_this_value
         );
     }
+
+    #[test]
+    fn router_transaction_data_is_abi_encoded() {
+        let token_contract = make_wallet("token").address();
+        let account_1 = make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+            make_wallet("abc"),
+            5,
+            None,
+        );
+        let account_2 = make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+            make_wallet("def"),
+            256,
+            None,
+        );
+
+        let result = router_transaction_data(token_contract, &[account_1, account_2]);
+
+        let expected_words = [
+            "000000000000000000000000000000000000000000000000000000746f6b656e",
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "00000000000000000000000000000000000000000000000000000000000000c0",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000616263",
+            "0000000000000000000000000000000000000000000000000000000000646566",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000005",
+            "0000000000000000000000000000000000000000000000000000000000000100",
+        ];
+        let expected = DISPERSE_TOKEN_METHOD_ID
+            .iter()
+            .cloned()
+            .chain(
+                expected_words
+                    .iter()
+                    .flat_map(|word| decode_hex(word).unwrap()),
+            )
+            .collect::<Vec<u8>>();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn router_gas_limit_grows_with_each_recipient() {
+        let chain = TEST_DEFAULT_CHAIN;
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+        let data = router_transaction_data(chain.rec().contract, &accounts);
+        let base = BlockchainInterfaceWeb3::web3_gas_limit_const_part(chain);
+        let data_fee = data
+            .iter()
+            .map(|byte| if byte == &0u8 { 4 } else { 68 })
+            .sum::<u128>();
+
+        let result = router_gas_limit(&data, chain, accounts.len());
+
+        assert_eq!(
+            result,
+            U256::from(base + 2 * ROUTER_GAS_LIMIT_PER_RECIPIENT + data_fee)
+        )
+    }
+
+    #[test]
+    fn send_payables_through_router_reports_the_share_of_each_creditor() {
+        let test_name = "send_payables_through_router_reports_the_share_of_each_creditor";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .ok_response("irrelevant_ok_rpc_response".to_string(), 7)
+            .end_batch()
+            .start();
+        let (_event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let new_fingerprints_recipient = accountant.start().recipient();
+        let system = System::new(test_name);
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+
+        let result = send_payables_through_router(
+            &Logger::new(test_name),
+            DEFAULT_CHAIN,
+            "MASQ",
+            make_wallet("router").address(),
+            &web3_batch,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+            make_paying_wallet(b"consuming_wallet"),
+            1_000_000_000,
+            1.into(),
+            new_fingerprints_recipient,
+            accounts.clone(),
+        )
+        .wait();
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        let seeds = accountant_recording.get_record::<PendingPayableFingerprintSeeds>(0);
+        let hash = seeds.hashes_and_balances[0].hash;
+        assert_eq!(
+            seeds.hashes_and_balances,
+            vec![HashAndAmount {
+                hash,
+                amount: accounts[0].balance_wei + accounts[1].balance_wei
+            }]
+        );
+        assert_eq!(
+            seeds.creditor_shares,
+            vec![
+                CreditorShare {
+                    hash,
+                    wallet: accounts[0].wallet.clone(),
+                    amount: accounts[0].balance_wei
+                },
+                CreditorShare {
+                    hash,
+                    wallet: accounts[1].wallet.clone(),
+                    amount: accounts[1].balance_wei
+                }
+            ]
+        );
+        assert_eq!(
+            result,
+            Ok(vec![
+                Correct(PendingPayable {
+                    recipient_wallet: accounts[0].wallet.clone(),
+                    hash
+                }),
+                Correct(PendingPayable {
+                    recipient_wallet: accounts[1].wallet.clone(),
+                    hash
+                })
+            ])
+        )
+    }
+
+    #[test]
+    fn payment_router_if_allowed_takes_the_router_if_the_allowance_covers_all_payments() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            // 3,000,000,000
+            .ok_response(
+                "0x00000000000000000000000000000000000000000000000000000000B2D05E00".to_string(),
+                1,
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let router_address = make_wallet("router").address();
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+
+        let result = payment_router_if_allowed(
+            &Logger::new("test"),
+            subject.lower_interface().as_ref(),
+            Some(router_address),
+            &make_paying_wallet(b"consuming_wallet"),
+            &accounts,
+        )
+        .wait();
+
+        assert_eq!(result, Ok(Some(router_address)));
+        let requests = blockchain_client_server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(
+            requests[0].contains("\"method\":\"eth_call\"")
+                && requests[0].contains(&format!("{:?}", router_address)[2..]),
+            "{}",
+            requests[0]
+        );
+    }
+
+    #[test]
+    fn payment_router_if_allowed_refuses_the_router_if_the_allowance_is_too_low() {
+        init_test_logging();
+        let test_name = "payment_router_if_allowed_refuses_the_router_if_the_allowance_is_too_low";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            // 2,999,999,999
+            .ok_response(
+                "0x00000000000000000000000000000000000000000000000000000000B2D05DFF".to_string(),
+                1,
+            )
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let router_address = make_wallet("router").address();
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+
+        let result = payment_router_if_allowed(
+            &Logger::new(test_name),
+            subject.lower_interface().as_ref(),
+            Some(router_address),
+            &make_paying_wallet(b"consuming_wallet"),
+            &accounts,
+        )
+        .wait();
+
+        assert_eq!(result, Ok(None));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Allowance of 2999999999 wei for the payment router {:?} doesn't \
+             cover the 3,000,000,000 wei to be paid; sending the payments as separate transfers",
+            router_address
+        ));
+    }
+
+    #[test]
+    fn payment_router_if_allowed_refuses_the_router_if_the_allowance_cannot_be_read() {
+        init_test_logging();
+        let test_name =
+            "payment_router_if_allowed_refuses_the_router_if_the_allowance_cannot_be_read";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .err_response(-32005, "Rate limit exceeded".to_string(), 1)
+            .start();
+        let subject = make_blockchain_interface_web3(port);
+        let router_address = make_wallet("router").address();
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+
+        let result = payment_router_if_allowed(
+            &Logger::new(test_name),
+            subject.lower_interface().as_ref(),
+            Some(router_address),
+            &make_paying_wallet(b"consuming_wallet"),
+            &accounts,
+        )
+        .wait();
+
+        assert_eq!(result, Ok(None));
+        TestLogHandler::new().exists_log_matching(&format!(
+            "WARN: {test_name}: Cannot read the allowance for the payment router {:?}: \
+             .*Rate limit exceeded.*; sending the payments as separate transfers",
+            router_address
+        ));
+    }
+
+    #[test]
+    fn payment_router_if_allowed_does_not_ask_about_a_single_payment_or_a_missing_router() {
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port).start();
+        let subject = make_blockchain_interface_web3(port);
+        let consuming_wallet = make_paying_wallet(b"consuming_wallet");
+
+        let single_payment_result = payment_router_if_allowed(
+            &Logger::new("test"),
+            subject.lower_interface().as_ref(),
+            Some(make_wallet("router").address()),
+            &consuming_wallet,
+            &[make_payable_account(1)],
+        )
+        .wait();
+        let missing_router_result = payment_router_if_allowed(
+            &Logger::new("test"),
+            subject.lower_interface().as_ref(),
+            None,
+            &consuming_wallet,
+            &[make_payable_account(1), make_payable_account(2)],
+        )
+        .wait();
+
+        assert_eq!(single_payment_result, Ok(None));
+        assert_eq!(missing_router_result, Ok(None));
+        assert!(blockchain_client_server.requests().is_empty());
+    }
+
+    #[test]
+    fn router_output_data_shares_the_outcome_among_all_accounts() {
+        let accounts = vec![make_payable_account(1), make_payable_account(2)];
+        let hash = make_tx_hash(0x1b3);
+        let rpc_error = Rpc(Error {
+            code: ServerError(-32000),
+            message: "insufficient allowance".to_string(),
+            data: None,
+        });
+
+        let ok_result = router_output_data(
+            Ok(Value::String("irrelevant".to_string())),
+            hash,
+            accounts.clone(),
+        );
+        let failed_result = router_output_data(Err(rpc_error.clone()), hash, accounts.clone());
+
+        assert_eq!(
+            ok_result,
+            vec![
+                Correct(PendingPayable {
+                    recipient_wallet: accounts[0].wallet.clone(),
+                    hash
+                }),
+                Correct(PendingPayable {
+                    recipient_wallet: accounts[1].wallet.clone(),
+                    hash
+                })
+            ]
+        );
+        assert_eq!(
+            failed_result,
+            vec![
+                Failed(RpcPayableFailure {
+                    rpc_error: rpc_error.clone(),
+                    recipient_wallet: accounts[0].wallet.clone(),
+                    hash
+                }),
+                Failed(RpcPayableFailure {
+                    rpc_error,
+                    recipient_wallet: accounts[1].wallet.clone(),
+                    hash
+                })
+            ]
+        )
+    }
+
+    fn execute_send_replacement_test(
+        test_name: &str,
+        accounts: Vec<PayableAccount>,
+        port: u16,
+    ) -> (
+        Result<H256, PayableTransactionError>,
+        Vec<PendingPayableReplacement>,
+    ) {
+        let (_event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
+        let (accountant, _, accountant_recording) = make_recorder();
+        let replacement_recipient = accountant.start().recipient();
+        let system = System::new(test_name);
+        let basis = ReplacementBasis {
+            nonce: 1,
+            gas_price_wei: 1_000_000_000,
+        };
+
+        let result = send_replacement(
+            &Logger::new(test_name),
+            DEFAULT_CHAIN,
+            &web3_batch,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+            make_paying_wallet(b"consuming_wallet"),
+            7,
+            basis,
+            replacement_recipient,
+            accounts,
+            None,
+        )
+        .wait();
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording.lock().unwrap();
+        let replacements = (0..accountant_recording.len())
+            .map(|idx| {
+                accountant_recording
+                    .get_record::<PendingPayableReplacement>(idx)
+                    .clone()
+            })
+            .collect();
+        (result, replacements)
+    }
+
+    #[test]
+    fn send_replacement_reuses_the_nonce_and_records_the_new_hash_before_submitting() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .ok_response("irrelevant_ok_rpc_response".to_string(), 7)
+            .end_batch()
+            .start();
+        let expected_hash =
+            H256::from_str("35f42b260f090a559e8b456718d9c91a9da0f234ed0a129b9d5c4813b6615af4")
+                .unwrap();
+
+        let (result, replacements) = execute_send_replacement_test(
+            "send_replacement_reuses_the_nonce_and_records_the_new_hash_before_submitting",
+            vec![make_payable_account(1)],
+            port,
+        );
+
+        assert_eq!(result, Ok(expected_hash));
+        assert_eq!(
+            replacements,
+            vec![PendingPayableReplacement {
+                rowid: 7,
+                hash: expected_hash,
+                basis: ReplacementBasis {
+                    nonce: 1,
+                    gas_price_wei: 1_000_000_000
+                }
+            }]
+        )
+    }
+
+    #[test]
+    fn send_replacement_reports_the_rpc_error_of_the_replacement() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .err_response(-32000, "replacement transaction underpriced".to_string(), 7)
+            .end_batch()
+            .start();
+        let expected_hash =
+            H256::from_str("35f42b260f090a559e8b456718d9c91a9da0f234ed0a129b9d5c4813b6615af4")
+                .unwrap();
+        let rpc_error = Rpc(Error {
+            code: ServerError(-32000),
+            message: "replacement transaction underpriced".to_string(),
+            data: None,
+        });
+
+        let (result, replacements) = execute_send_replacement_test(
+            "send_replacement_reports_the_rpc_error_of_the_replacement",
+            vec![make_payable_account(1)],
+            port,
+        );
+
+        assert_eq!(
+            result,
+            Err(Sending {
+                msg: rpc_error.to_string(),
+                hashes: vec![expected_hash]
+            })
+        );
+        assert_eq!(replacements.len(), 1)
+    }
+
+    #[test]
+    fn send_replacement_refuses_several_accounts_on_a_chain_without_a_router() {
+        let port = find_free_port();
+
+        let (result, replacements) = execute_send_replacement_test(
+            "send_replacement_refuses_several_accounts_on_a_chain_without_a_router",
+            vec![make_payable_account(1), make_payable_account(2)],
+            port,
+        );
+
+        assert_eq!(
+            result,
+            Err(PayableTransactionError::Signing(
+                "A single transaction paying 2 accounts cannot be built on polygon-mainnet"
+                    .to_string()
+            ))
+        );
+        assert_eq!(replacements, vec![])
+    }
+
+    fn execute_send_sweep_test(
+        test_name: &str,
+        port: u16,
+        request_timeout: Duration,
+    ) -> Result<H256, PayableTransactionError> {
+        let (_event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport)));
+
+        send_sweep(
+            &Logger::new(test_name),
+            DEFAULT_CHAIN,
+            "MASQ",
+            &web3_batch,
+            request_timeout,
+            make_paying_wallet(b"consuming_wallet"),
+            1_000_000_000,
+            U256::from(1),
+            make_payable_account(1),
+        )
+        .wait()
+    }
+
+    #[test]
+    fn send_sweep_signs_a_single_transfer_and_submits_it() {
+        init_test_logging();
+        let test_name = "send_sweep_signs_a_single_transfer_and_submits_it";
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .ok_response("irrelevant_ok_rpc_response".to_string(), 7)
+            .end_batch()
+            .start();
+        let expected_hash =
+            H256::from_str("35f42b260f090a559e8b456718d9c91a9da0f234ed0a129b9d5c4813b6615af4")
+                .unwrap();
+
+        let result = execute_send_sweep_test(
+            test_name,
+            port,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+        );
+
+        assert_eq!(result, Ok(expected_hash));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Sweeping 1,000,000,000 wei of MASQ to {} by {:?} with nonce 1 and gas price 1000000000 wei",
+            make_payable_account(1).wallet,
+            expected_hash
+        ));
+    }
+
+    #[test]
+    fn send_sweep_reports_the_rpc_error() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .err_response(-32000, "insufficient funds for gas".to_string(), 7)
+            .end_batch()
+            .start();
+        let expected_hash =
+            H256::from_str("35f42b260f090a559e8b456718d9c91a9da0f234ed0a129b9d5c4813b6615af4")
+                .unwrap();
+        let rpc_error = Rpc(Error {
+            code: ServerError(-32000),
+            message: "insufficient funds for gas".to_string(),
+            data: None,
+        });
+
+        let result = execute_send_sweep_test(
+            "send_sweep_reports_the_rpc_error",
+            port,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+        );
+
+        assert_eq!(
+            result,
+            Err(Sending {
+                msg: rpc_error.to_string(),
+                hashes: vec![expected_hash]
+            })
+        )
+    }
+
+    #[test]
+    fn send_sweep_gives_up_on_an_unresponsive_blockchain_service_but_keeps_the_hash() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port).unresponsive().start();
+        let expected_hash =
+            H256::from_str("35f42b260f090a559e8b456718d9c91a9da0f234ed0a129b9d5c4813b6615af4")
+                .unwrap();
+
+        let result = execute_send_sweep_test(
+            "send_sweep_gives_up_on_an_unresponsive_blockchain_service_but_keeps_the_hash",
+            port,
+            Duration::from_millis(300),
+        );
+
+        assert_eq!(
+            result,
+            Err(Sending {
+                msg: "Transport error: No response within 300 ms".to_string(),
+                hashes: vec![expected_hash]
+            })
+        )
+    }
+
+    fn execute_send_void_test(
+        test_name: &str,
+        port: u16,
+    ) -> (Result<H256, PayableTransactionError>, SignedTransaction) {
+        let (_event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let web3_batch = Web3::new(Batch::new(MeteredHttp::from(transport.clone())));
+        let consuming_wallet = make_paying_wallet(b"consuming_wallet");
+        let expected_signed_tx = sign_transaction_locally(
+            &Web3::new(Batch::new(MeteredHttp::from(transport))),
+            TransactionParameters {
+                nonce: Some(U256::from(4)),
+                to: Some(consuming_wallet.address()),
+                gas: U256::from(21_000),
+                gas_price: Some(U256::from(1_150_000_000)),
+                value: U256::zero(),
+                data: Bytes(vec![]),
+                chain_id: Some(DEFAULT_CHAIN.rec().num_chain_id),
+            },
+            &consuming_wallet.prepare_secp256k1_secret().unwrap(),
+        );
+
+        let result = send_void(
+            &Logger::new(test_name),
+            DEFAULT_CHAIN,
+            &web3_batch,
+            Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+            consuming_wallet,
+            ReplacementBasis {
+                nonce: 4,
+                gas_price_wei: 1_150_000_000,
+            },
+        )
+        .wait();
+
+        (result, expected_signed_tx)
+    }
+
+    #[test]
+    fn send_void_signs_a_transfer_of_nothing_to_the_consuming_wallet_with_the_same_nonce() {
+        init_test_logging();
+        let test_name =
+            "send_void_signs_a_transfer_of_nothing_to_the_consuming_wallet_with_the_same_nonce";
+        let port = find_free_port();
+        let blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .ok_response("irrelevant_ok_rpc_response".to_string(), 7)
+            .end_batch()
+            .start();
+
+        let (result, expected_signed_tx) = execute_send_void_test(test_name, port);
+
+        assert_eq!(result, Ok(expected_signed_tx.transaction_hash));
+        let requests = blockchain_client_server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(
+            requests[0].contains("\"method\":\"eth_sendRawTransaction\"")
+                && requests[0].contains(&format!(
+                    "0x{}",
+                    expected_signed_tx.raw_transaction.0.to_hex::<String>()
+                )),
+            "{}",
+            requests[0]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Voiding nonce 4 by {:?}, a transfer of nothing to {} with gas \
+             price 1150000000 wei",
+            expected_signed_tx.transaction_hash,
+            make_paying_wallet(b"consuming_wallet")
+        ));
+    }
+
+    #[test]
+    fn send_void_reports_the_rpc_error() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .begin_batch()
+            .err_response(-32000, "nonce too low".to_string(), 7)
+            .end_batch()
+            .start();
+        let rpc_error = Rpc(Error {
+            code: ServerError(-32000),
+            message: "nonce too low".to_string(),
+            data: None,
+        });
+
+        let (result, expected_signed_tx) =
+            execute_send_void_test("send_void_reports_the_rpc_error", port);
+
+        assert_eq!(
+            result,
+            Err(Sending {
+                msg: rpc_error.to_string(),
+                hashes: vec![expected_signed_tx.transaction_hash]
+            })
+        )
+    }
 }
@@ -4,27 +4,119 @@ use crate::accountant::comma_joined_stringifiable;
 use itertools::Either;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 use variant_count::VariantCount;
 use web3::types::{Address, H256};
 
 const BLOCKCHAIN_SERVICE_URL_NOT_SPECIFIED: &str = "Uninitialized blockchain interface. To avoid \
 being delinquency-banned, you should restart the Node with a value for blockchain-service-url";
 
+// Fragments of error messages by which the common providers tell what went wrong with a query;
+// they're matched against the lowercased message, the range ones first as some providers report
+// too wide a range with the same code they use for rate limiting
+const RANGE_TOO_WIDE_FRAGMENTS: [&str; 6] = [
+    "block range",
+    "range of blocks",
+    "blocks at once",
+    "query returned more than",
+    "response size exceeded",
+    "expected batch, got single",
+];
+const RATE_LIMITED_FRAGMENTS: [&str; 6] = [
+    "rate limit",
+    "too many requests",
+    "request limit",
+    "limit exceeded",
+    "exceeded the quota",
+    "capacity exceeded",
+];
+const TIMEOUT_FRAGMENTS: [&str; 3] = ["timed out", "timeout", "deadline exceeded"];
+const NODE_SYNCING_FRAGMENTS: [&str; 3] = ["syncing", "header not found", "not yet synced"];
+
 #[derive(Clone, Debug, PartialEq, Eq, VariantCount)]
 pub enum BlockchainError {
     InvalidUrl,
     InvalidAddress,
     InvalidResponse,
+    RateLimited(String),
+    Timeout(String),
+    NodeSyncing(String),
+    RangeTooWide(String),
+    // The blockchain service didn't answer at all within the time given to a request
+    NoResponse(Duration),
+    // Any failed query not recognized as one of the categories above
     QueryFailed(String),
     UninitializedBlockchainInterface,
 }
 
+impl BlockchainError {
+    pub fn from_query_failure(msg: String) -> Self {
+        let lowercase_msg = msg.to_lowercase();
+        let contains_any = |fragments: &[&str]| {
+            fragments
+                .iter()
+                .any(|fragment| lowercase_msg.contains(fragment))
+        };
+        if contains_any(&RANGE_TOO_WIDE_FRAGMENTS) {
+            Self::RangeTooWide(msg)
+        } else if contains_any(&RATE_LIMITED_FRAGMENTS) {
+            Self::RateLimited(msg)
+        } else if contains_any(&TIMEOUT_FRAGMENTS) {
+            Self::Timeout(msg)
+        } else if contains_any(&NODE_SYNCING_FRAGMENTS) {
+            Self::NodeSyncing(msg)
+        } else {
+            Self::QueryFailed(msg)
+        }
+    }
+
+    // Whether the same query has a chance to succeed if repeated a bit later, either as it is or,
+    // for a too wide range, once the range is narrowed down
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited(_)
+            | Self::Timeout(_)
+            | Self::NodeSyncing(_)
+            | Self::RangeTooWide(_)
+            | Self::NoResponse(_) => true,
+            Self::InvalidUrl
+            | Self::InvalidAddress
+            | Self::InvalidResponse
+            | Self::QueryFailed(_)
+            | Self::UninitializedBlockchainInterface => false,
+        }
+    }
+
+    // Whether the very same query is likely to pass if just repeated after a short pause
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RateLimited(_)
+            | Self::Timeout(_)
+            | Self::NodeSyncing(_)
+            | Self::NoResponse(_) => true,
+            Self::InvalidUrl
+            | Self::InvalidAddress
+            | Self::InvalidResponse
+            | Self::RangeTooWide(_)
+            | Self::QueryFailed(_)
+            | Self::UninitializedBlockchainInterface => false,
+        }
+    }
+}
+
 impl Display for BlockchainError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let err_spec = match self {
             Self::InvalidUrl => Either::Left("Invalid url"),
             Self::InvalidAddress => Either::Left("Invalid address"),
             Self::InvalidResponse => Either::Left("Invalid response"),
+            Self::RateLimited(msg) => Either::Right(format!("Rate limited: {}", msg)),
+            Self::Timeout(msg) => Either::Right(format!("Timeout: {}", msg)),
+            Self::NodeSyncing(msg) => Either::Right(format!("Node syncing: {}", msg)),
+            Self::RangeTooWide(msg) => Either::Right(format!("Block range too wide: {}", msg)),
+            Self::NoResponse(timeout) => {
+                Either::Right(format!("No response within {} ms", timeout.as_millis()))
+            }
             Self::QueryFailed(msg) => Either::Right(format!("Query failed: {}", msg)),
             Self::UninitializedBlockchainInterface => {
                 Either::Left(BLOCKCHAIN_SERVICE_URL_NOT_SPECIFIED)
@@ -40,6 +132,7 @@ pub enum PayableTransactionError {
     GasPriceQueryFailed(BlockchainError),
     TransactionID(BlockchainError),
     UnusableWallet(String),
+    Simulation(String),
     Signing(String),
     Sending { msg: String, hashes: Vec<H256> },
     UninitializedBlockchainInterface,
@@ -62,6 +155,7 @@ impl Display for PayableTransactionError {
                 "Unusable wallet for signing payable transactions: \"{}\"",
                 msg
             ),
+            Self::Simulation(msg) => write!(f, "Simulation phase: \"{}\"", msg),
             Self::Signing(msg) => write!(f, "Signing phase: \"{}\"", msg),
             Self::Sending { msg, hashes } => write!(
                 f,
@@ -123,6 +217,7 @@ mod tests {
     use crate::blockchain::test_utils::make_tx_hash;
     use crate::test_utils::make_wallet;
     use masq_lib::utils::{slice_of_strs_to_vec_of_strings, to_string};
+    use std::time::Duration;
 
     #[test]
     fn constants_have_correct_values() {
@@ -139,6 +234,11 @@ mod tests {
             BlockchainError::InvalidUrl,
             BlockchainError::InvalidAddress,
             BlockchainError::InvalidResponse,
+            BlockchainError::RateLimited("Slow down".to_string()),
+            BlockchainError::Timeout("Still waiting".to_string()),
+            BlockchainError::NodeSyncing("Catching up".to_string()),
+            BlockchainError::RangeTooWide("Too many blocks".to_string()),
+            BlockchainError::NoResponse(Duration::from_secs(5)),
             BlockchainError::QueryFailed(
                 "Don't query so often, it gives me a headache".to_string(),
             ),
@@ -158,12 +258,128 @@ mod tests {
                 "Blockchain error: Invalid url",
                 "Blockchain error: Invalid address",
                 "Blockchain error: Invalid response",
+                "Blockchain error: Rate limited: Slow down",
+                "Blockchain error: Timeout: Still waiting",
+                "Blockchain error: Node syncing: Catching up",
+                "Blockchain error: Block range too wide: Too many blocks",
+                "Blockchain error: No response within 5000 ms",
                 "Blockchain error: Query failed: Don't query so often, it gives me a headache",
                 &format!("Blockchain error: {}", BLOCKCHAIN_SERVICE_URL_NOT_SPECIFIED)
             ])
         );
     }
 
+    #[test]
+    fn blockchain_error_categorizes_provider_error_messages() {
+        vec![
+            (
+                "RPC error: Error { code: ServerError(-32005), message: \"eth_getLogs block range too large, range: 33636, max: 3500\", data: None }",
+                BlockchainError::RangeTooWide as fn(String) -> BlockchainError,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32005), message: \"Blockheight too far in the past. Check params passed to eth_getLogs or eth_call requests.Range of blocks allowed for your plan: 1000\", data: None }",
+                BlockchainError::RangeTooWide,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32064), message: \"You cannot query logs for more than 100000 blocks at once.\", data: None }",
+                BlockchainError::RangeTooWide,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32005), message: \"query returned more than 10000 results\", data: None }",
+                BlockchainError::RangeTooWide,
+            ),
+            (
+                "Got invalid response: Expected batch, got single.",
+                BlockchainError::RangeTooWide,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32005), message: \"daily request count exceeded, request rate limited\", data: None }",
+                BlockchainError::RateLimited,
+            ),
+            (
+                "Transport error: Unexpected response status code: 429 Too Many Requests",
+                BlockchainError::RateLimited,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32005), message: \"limit exceeded\", data: None }",
+                BlockchainError::RateLimited,
+            ),
+            (
+                "Transport error: Error(Io(Custom { kind: TimedOut, error: \"operation timed out\" }))",
+                BlockchainError::Timeout,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32000), message: \"header not found\", data: None }",
+                BlockchainError::NodeSyncing,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32000), message: \"node is syncing\", data: None }",
+                BlockchainError::NodeSyncing,
+            ),
+            (
+                "Transport error: Error(IncompleteMessage)",
+                BlockchainError::QueryFailed,
+            ),
+            (
+                "RPC error: Error { code: ServerError(-32601), message: \"Method not found\", data: None }",
+                BlockchainError::QueryFailed,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(msg, expected_category)| {
+            assert_eq!(
+                BlockchainError::from_query_failure(msg.to_string()),
+                expected_category(msg.to_string()),
+                "{}",
+                msg
+            )
+        })
+    }
+
+    #[test]
+    fn blockchain_error_knows_which_failures_are_worth_retrying() {
+        let msg = || "booga".to_string();
+
+        assert_eq!(BlockchainError::RateLimited(msg()).is_retryable(), true);
+        assert_eq!(BlockchainError::Timeout(msg()).is_retryable(), true);
+        assert_eq!(BlockchainError::NodeSyncing(msg()).is_retryable(), true);
+        assert_eq!(BlockchainError::RangeTooWide(msg()).is_retryable(), true);
+        assert_eq!(
+            BlockchainError::NoResponse(Duration::from_secs(5)).is_retryable(),
+            true
+        );
+        assert_eq!(BlockchainError::QueryFailed(msg()).is_retryable(), false);
+        assert_eq!(BlockchainError::InvalidResponse.is_retryable(), false);
+        assert_eq!(BlockchainError::InvalidUrl.is_retryable(), false);
+        assert_eq!(BlockchainError::InvalidAddress.is_retryable(), false);
+        assert_eq!(
+            BlockchainError::UninitializedBlockchainInterface.is_retryable(),
+            false
+        );
+    }
+
+    #[test]
+    fn blockchain_error_knows_whether_it_is_transient() {
+        let msg = || "booga".to_string();
+
+        assert_eq!(BlockchainError::RateLimited(msg()).is_transient(), true);
+        assert_eq!(BlockchainError::Timeout(msg()).is_transient(), true);
+        assert_eq!(BlockchainError::NodeSyncing(msg()).is_transient(), true);
+        assert_eq!(BlockchainError::RangeTooWide(msg()).is_transient(), false);
+        assert_eq!(
+            BlockchainError::NoResponse(Duration::from_secs(5)).is_transient(),
+            true
+        );
+        assert_eq!(BlockchainError::QueryFailed(msg()).is_transient(), false);
+        assert_eq!(BlockchainError::InvalidResponse.is_transient(), false);
+        assert_eq!(BlockchainError::InvalidUrl.is_transient(), false);
+        assert_eq!(BlockchainError::InvalidAddress.is_transient(), false);
+        assert_eq!(
+            BlockchainError::UninitializedBlockchainInterface.is_transient(),
+            false
+        );
+    }
+
     #[test]
     fn payable_payment_error_implements_display() {
         let original_errors = [
@@ -175,6 +391,9 @@ mod tests {
             PayableTransactionError::UnusableWallet(
                 "This is a LEATHER wallet, not LEDGER wallet, stupid.".to_string(),
             ),
+            PayableTransactionError::Simulation(
+                "The token contract would refuse every payment".to_string(),
+            ),
             PayableTransactionError::Signing(
                 "You cannot sign with just three crosses here, clever boy".to_string(),
             ),
@@ -200,6 +419,7 @@ mod tests {
                 "Transaction id fetching failed: Blockchain error: Invalid response",
                 "Unusable wallet for signing payable transactions: \"This is a LEATHER wallet, not \
                 LEDGER wallet, stupid.\"",
+                "Simulation phase: \"The token contract would refuse every payment\"",
                 "Signing phase: \"You cannot sign with just three crosses here, clever boy\"",
                 "Sending phase: \"Sending to cosmos belongs elsewhere\". Signed and hashed \
                 transactions: 0x000000000000000000000000000000000000000000000000000000000000006f, \
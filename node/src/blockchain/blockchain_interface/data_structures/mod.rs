@@ -31,6 +31,19 @@ impl fmt::Display for BlockchainTransaction {
 pub struct RetrievedBlockchainTransactions {
     pub new_start_block: BlockMarker,
     pub transactions: Vec<BlockchainTransaction>,
+    pub rechecked_opt: Option<RecheckedTransactions>,
+    // Payments found in blocks still too young to be trusted; they are to be reported once more,
+    // among the confirmed transactions, when the chain has grown deep enough over them
+    pub pending: Vec<BlockchainTransaction>,
+}
+
+// Payments found once again in blocks scanned before but still young enough to be undone by
+// a chain reorganization; whatever was credited from these blocks and is missing here vanished
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecheckedTransactions {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub transactions: Vec<BlockchainTransaction>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
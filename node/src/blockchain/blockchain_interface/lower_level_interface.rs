@@ -1,10 +1,12 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{LatestBlockInfo, TransferSimulation};
 use crate::blockchain::blockchain_interface::data_structures::errors::BlockchainError;
+use crate::blockchain::rpc_usage::MeteredHttp;
 use ethereum_types::{H256, U64};
 use futures::Future;
 use serde_json::Value;
-use web3::transports::{Batch, Http};
+use web3::transports::Batch;
 use web3::types::{Address, Filter, Log, U256};
 use web3::{Error, Web3};
 
@@ -22,26 +24,71 @@ pub trait LowBlockchainInt {
         address: Address,
     ) -> Box<dyn Future<Item = U256, Error = BlockchainError>>;
 
+    // What the owner lets the spender transfer from its balance of the token
+    fn get_token_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+    ) -> Box<dyn Future<Item = U256, Error = BlockchainError>>;
+
     fn get_gas_price(&self) -> Box<dyn Future<Item = U256, Error = BlockchainError>>;
 
     fn get_block_number(&self) -> Box<dyn Future<Item = U64, Error = BlockchainError>>;
 
+    fn get_latest_block_info(
+        &self,
+    ) -> Box<dyn Future<Item = LatestBlockInfo, Error = BlockchainError>>;
+
+    fn get_chain_id(&self) -> Box<dyn Future<Item = U256, Error = BlockchainError>>;
+
+    fn get_next_base_fee(&self) -> Box<dyn Future<Item = Option<U256>, Error = BlockchainError>>;
+
+    fn supports_batch_requests(&self) -> Box<dyn Future<Item = bool, Error = BlockchainError>>;
+
     fn get_transaction_id(
         &self,
         address: Address,
     ) -> Box<dyn Future<Item = U256, Error = BlockchainError>>;
 
+    // Counts only the transactions already mined, unlike the pending count of get_transaction_id
+    fn get_confirmed_transaction_count(
+        &self,
+        address: Address,
+    ) -> Box<dyn Future<Item = U256, Error = BlockchainError>>;
+
     fn get_transaction_receipt_in_batch(
         &self,
         hash_vec: Vec<H256>,
     ) -> Box<dyn Future<Item = Vec<Result<Value, Error>>, Error = BlockchainError>>;
 
+    fn get_transaction_receipts_one_by_one(
+        &self,
+        hash_vec: Vec<H256>,
+    ) -> Box<dyn Future<Item = Vec<Result<Value, Error>>, Error = BlockchainError>>;
+
+    fn get_transactions_in_batch(
+        &self,
+        hash_vec: Vec<H256>,
+    ) -> Box<dyn Future<Item = Vec<Result<Value, Error>>, Error = BlockchainError>>;
+
     fn get_contract_address(&self) -> Address;
 
+    fn get_token_name(&self) -> Box<dyn Future<Item = String, Error = BlockchainError>>;
+
+    fn get_token_symbol(&self) -> Box<dyn Future<Item = String, Error = BlockchainError>>;
+
+    fn get_token_decimals(&self) -> Box<dyn Future<Item = u8, Error = BlockchainError>>;
+
+    fn simulate_transfer(
+        &self,
+        from: Address,
+        data: Vec<u8>,
+    ) -> Box<dyn Future<Item = TransferSimulation, Error = BlockchainError>>;
+
     fn get_transaction_logs(
         &self,
         filter: Filter,
     ) -> Box<dyn Future<Item = Vec<Log>, Error = BlockchainError>>;
 
-    fn get_web3_batch(&self) -> Web3<Batch<Http>>;
+    fn get_web3_batch(&self) -> Web3<Batch<MeteredHttp>>;
 }
@@ -10,39 +10,52 @@ use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockch
 use crate::blockchain::blockchain_interface::data_structures::errors::{BlockchainAgentBuildError, BlockchainError, PayableTransactionError};
 use crate::blockchain::blockchain_interface::data_structures::{ProcessedPayableFallible, RetrievedBlockchainTransactions};
 use crate::blockchain::blockchain_interface::lower_level_interface::LowBlockchainInt;
+use crate::blockchain::rpc_usage::RpcUsageMeter;
 use crate::sub_lib::wallet::Wallet;
 use futures::Future;
 use masq_lib::blockchains::chains::Chain;
 use web3::types::Address;
 use masq_lib::logger::Logger;
 use crate::accountant::db_access_objects::payable_dao::PayableAccount;
-use crate::blockchain::blockchain_bridge::{BlockMarker, BlockScanRange, PendingPayableFingerprintSeeds};
-use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::TransactionReceiptResult;
-
-pub trait BlockchainInterface {
-    fn contract_address(&self) -> Address;
-
-    fn get_chain(&self) -> Chain;
+use crate::blockchain::blockchain_bridge::{BlockMarker, BlockScanRange, PendingPayableFingerprintSeeds, PendingPayableReplacement, ReplacementBasis};
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::lower_level_interface_web3::{BlockchainCapabilities, TokenMetadata, TransactionReceiptResult};
 
+// Queries of the chain state: logs of incoming payments, balances and receipts
+pub trait BlockchainReader {
     fn lower_interface(&self) -> Box<dyn LowBlockchainInt>;
 
     fn retrieve_transactions(
         &self,
         start_block: BlockMarker,
         scan_range: BlockScanRange,
-        recipient: Address,
+        recipients: Vec<Address>,
     ) -> Box<dyn Future<Item = RetrievedBlockchainTransactions, Error = BlockchainError>>;
 
-    fn build_blockchain_agent(
-        &self,
-        consuming_wallet: Wallet,
-    ) -> Box<dyn Future<Item = Box<dyn BlockchainAgent>, Error = BlockchainAgentBuildError>>;
-
     fn process_transaction_receipts(
         &self,
         transaction_hashes: Vec<H256>,
     ) -> Box<dyn Future<Item = Vec<TransactionReceiptResult>, Error = BlockchainError>>;
 
+    // The answer is remembered, so that the logs of our payments can name the token
+    fn fetch_token_metadata(
+        &self,
+    ) -> Box<dyn Future<Item = TokenMetadata, Error = BlockchainError>>;
+
+    // The answer is remembered too; receipts and fee estimates are then asked for accordingly
+    fn fetch_capabilities(
+        &self,
+    ) -> Box<dyn Future<Item = BlockchainCapabilities, Error = BlockchainError>>;
+}
+
+// Preparation and submission of our own transactions
+pub trait BlockchainWriter {
+    // The estimate from the fee history, when there is one, is blended with the oracle's price
+    fn build_blockchain_agent(
+        &self,
+        consuming_wallet: Wallet,
+        fee_history_estimate_opt: Option<u128>,
+    ) -> Box<dyn Future<Item = Box<dyn BlockchainAgent>, Error = BlockchainAgentBuildError>>;
+
     fn submit_payables_in_batch(
         &self,
         logger: Logger,
@@ -51,5 +64,46 @@ pub trait BlockchainInterface {
         affordable_accounts: Vec<PayableAccount>,
     ) -> Box<dyn Future<Item = Vec<ProcessedPayableFallible>, Error = PayableTransactionError>>;
 
+    fn submit_replacement(
+        &self,
+        logger: Logger,
+        consuming_wallet: Wallet,
+        rowid: u64,
+        basis: ReplacementBasis,
+        replacement_recipient: Recipient<PendingPayableReplacement>,
+        accounts: Vec<PayableAccount>,
+    ) -> Box<dyn Future<Item = H256, Error = PayableTransactionError>>;
+
+    // The recipient's balance is the amount to be transferred
+    fn submit_sweep(
+        &self,
+        logger: Logger,
+        agent: Box<dyn BlockchainAgent>,
+        recipient: PayableAccount,
+    ) -> Box<dyn Future<Item = H256, Error = PayableTransactionError>>;
+
+    // The nonce given in the basis is spent by a transfer of nothing to the consuming wallet
+    fn submit_void(
+        &self,
+        logger: Logger,
+        consuming_wallet: Wallet,
+        basis: ReplacementBasis,
+    ) -> Box<dyn Future<Item = H256, Error = PayableTransactionError>>;
+}
+
+// The facade held by the BlockchainBridge; collaborators needing only one side should be
+// handed just that one through as_reader() or as_writer()
+pub trait BlockchainInterface: BlockchainReader + BlockchainWriter {
+    fn contract_address(&self) -> Address;
+
+    fn get_chain(&self) -> Chain;
+
+    fn as_reader(&self) -> &dyn BlockchainReader;
+
+    fn as_writer(&self) -> &dyn BlockchainWriter;
+
+    // Counts the requests sent to the blockchain service
+    fn rpc_usage_meter(&self) -> RpcUsageMeter;
+
     as_any_ref_in_trait!();
 }
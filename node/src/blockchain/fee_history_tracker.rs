@@ -0,0 +1,92 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+// Weight of the newest price in the moving average, in percent
+pub const FEE_HISTORY_SMOOTHING_PERCENT: u128 = 20;
+
+// Keeps an exponential moving average of the gas prices our confirmed transactions paid. They are
+// legacy transactions, so the effectiveGasPrice of their receipts equals the gas price they were
+// signed with, which their fingerprints remember
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeeHistoryTracker {
+    average_wei_opt: Option<u128>,
+}
+
+impl FeeHistoryTracker {
+    pub fn record(&mut self, effective_gas_price_wei: u128) {
+        self.average_wei_opt = Some(match self.average_wei_opt {
+            None => effective_gas_price_wei,
+            Some(average) => {
+                (effective_gas_price_wei * FEE_HISTORY_SMOOTHING_PERCENT
+                    + average * (100 - FEE_HISTORY_SMOOTHING_PERCENT))
+                    / 100
+            }
+        })
+    }
+
+    pub fn estimate_opt(&self) -> Option<u128> {
+        self.average_wei_opt
+    }
+}
+
+// The oracle tends to quote on the safe side; meeting the prices that did get our transactions
+// mined halfway avoids overpaying without ignoring a market that just went up
+pub fn blend_with_oracle(oracle_gas_price_wei: u128, estimate_opt: Option<u128>) -> u128 {
+    match estimate_opt {
+        Some(estimate) => (oracle_gas_price_wei + estimate) / 2,
+        None => oracle_gas_price_wei,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(FEE_HISTORY_SMOOTHING_PERCENT, 20);
+    }
+
+    #[test]
+    fn tracker_without_history_has_no_estimate() {
+        let subject = FeeHistoryTracker::default();
+
+        assert_eq!(subject.estimate_opt(), None)
+    }
+
+    #[test]
+    fn first_recorded_price_becomes_the_estimate() {
+        let mut subject = FeeHistoryTracker::default();
+
+        subject.record(1_500_000_000);
+
+        assert_eq!(subject.estimate_opt(), Some(1_500_000_000))
+    }
+
+    #[test]
+    fn later_prices_move_the_estimate_by_their_weight() {
+        let mut subject = FeeHistoryTracker::default();
+        subject.record(1_000_000_000);
+
+        subject.record(2_000_000_000);
+        let after_second = subject.estimate_opt();
+        subject.record(2_000_000_000);
+        let after_third = subject.estimate_opt();
+
+        assert_eq!(after_second, Some(1_200_000_000));
+        assert_eq!(after_third, Some(1_360_000_000))
+    }
+
+    #[test]
+    fn blend_with_oracle_takes_the_oracle_alone_without_history() {
+        let result = blend_with_oracle(3_000_000_000, None);
+
+        assert_eq!(result, 3_000_000_000)
+    }
+
+    #[test]
+    fn blend_with_oracle_meets_the_estimate_halfway() {
+        let result = blend_with_oracle(3_000_000_000, Some(2_000_000_000));
+
+        assert_eq!(result, 2_500_000_000)
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+// Lets the blockchain code be written with async/await while actix and web3 still speak futures
+// 0.1. An async block handed over by legacy() is polled from within a futures 0.1 task, which is
+// also where the futures 0.1 it awaits through compat() get polled, so their wake-ups reach the
+// task. Awaiting a compat() future anywhere else, e.g. on a std executor, isn't supported
+
+use futures::{Async, Future as Future01, Poll as Poll01};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+pub type LegacyFuture<T, E> = Box<dyn Future01<Item = T, Error = E>>;
+
+pub fn legacy<T, E, F>(future: F) -> LegacyFuture<T, E>
+where
+    F: Future<Output = Result<T, E>> + 'static,
+    T: 'static,
+    E: 'static,
+{
+    Box::new(StdAsLegacy {
+        inner: Box::pin(future),
+    })
+}
+
+pub trait LegacyFutureExt: Future01 + Sized {
+    fn compat(self) -> LegacyAsStd<Self> {
+        LegacyAsStd { inner: self }
+    }
+}
+
+impl<F: Future01> LegacyFutureExt for F {}
+
+pub struct LegacyAsStd<F> {
+    inner: F,
+}
+
+impl<F: Future01 + Unpin> Future for LegacyAsStd<F> {
+    type Output = Result<F::Item, F::Error>;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
+        match self.get_mut().inner.poll() {
+            Ok(Async::Ready(item)) => Poll::Ready(Ok(item)),
+            Ok(Async::NotReady) => Poll::Pending,
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+struct StdAsLegacy<T, E> {
+    inner: Pin<Box<dyn Future<Output = Result<T, E>>>>,
+}
+
+impl<T, E> Future01 for StdAsLegacy<T, E> {
+    type Item = T;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll01<T, E> {
+        let waker = Waker::from(Arc::new(TaskWaker(futures::task::current())));
+        match self.inner.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(Ok(item)) => Ok(Async::Ready(item)),
+            Poll::Ready(Err(e)) => Err(e),
+            Poll::Pending => Ok(Async::NotReady),
+        }
+    }
+}
+
+struct TaskWaker(futures::task::Task);
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.notify()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.notify()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix::{Arbiter, System};
+    use futures::future;
+    use futures::sync::oneshot;
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct SharedState {
+        value_opt: Option<u64>,
+        waker_opt: Option<Waker>,
+        polls: usize,
+    }
+
+    // A std future completed by another thread, which wakes the task through the std waker
+    struct ValueFromAnotherThread {
+        state: Arc<Mutex<SharedState>>,
+    }
+
+    impl Future for ValueFromAnotherThread {
+        type Output = u64;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<u64> {
+            let mut state = self.state.lock().unwrap();
+            state.polls += 1;
+            match state.value_opt.take() {
+                Some(value) => Poll::Ready(value),
+                None => {
+                    state.waker_opt = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    fn value_from_another_thread(value: u64) -> (ValueFromAnotherThread, Arc<Mutex<SharedState>>) {
+        let state = Arc::new(Mutex::new(SharedState::default()));
+        let state_inner = state.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            let waker_opt = {
+                let mut state = state_inner.lock().unwrap();
+                state.value_opt = Some(value);
+                state.waker_opt.take()
+            };
+            if let Some(waker) = waker_opt {
+                waker.wake()
+            }
+        });
+        (
+            ValueFromAnotherThread {
+                state: state.clone(),
+            },
+            state,
+        )
+    }
+
+    #[test]
+    fn legacy_runs_async_block_awaiting_legacy_futures() {
+        let subject = legacy(async {
+            let first = future::ok::<u64, String>(6).compat().await?;
+            let second = future::ok::<u64, String>(7).compat().await?;
+            Ok::<_, String>(first * second)
+        });
+
+        let result = subject.wait();
+
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn legacy_stops_at_the_first_error() {
+        let subject = legacy(async {
+            let first = future::err::<u64, String>("booga".to_string())
+                .compat()
+                .await?;
+            let second = future::ok::<u64, String>(7).compat().await?;
+            Ok::<_, String>(first * second)
+        });
+
+        let result = subject.wait();
+
+        assert_eq!(result, Err("booga".to_string()));
+    }
+
+    #[test]
+    fn legacy_resumes_when_awaited_future_gets_ready_later() {
+        let (tx, rx) = oneshot::channel::<u64>();
+        let subject = legacy(async move {
+            let value = rx.compat().await.map_err(|e| format!("{:?}", e))?;
+            Ok::<_, String>(value + 1)
+        });
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            tx.send(41).unwrap()
+        });
+
+        let result = subject.wait();
+
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn legacy_resumes_when_a_pending_std_future_is_woken_from_another_thread() {
+        let (future, state) = value_from_another_thread(41);
+        let subject = legacy(async move { Ok::<_, String>(future.await + 1) });
+
+        let result = subject.wait();
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(state.lock().unwrap().polls, 2);
+    }
+
+    #[test]
+    fn legacy_spawned_on_the_actix_event_loop_resumes_when_woken_from_another_thread() {
+        let system = System::new("test");
+        let (future, state) = value_from_another_thread(41);
+        let (tx, rx) = mpsc::channel();
+        let subject = legacy(async move { Ok::<_, ()>(future.await + 1) });
+
+        Arbiter::spawn(subject.map(move |value| {
+            tx.send(value).unwrap();
+            System::current().stop()
+        }));
+        system.run();
+
+        assert_eq!(rx.try_recv(), Ok(42));
+        assert_eq!(state.lock().unwrap().polls, 2);
+    }
+}
@@ -4,7 +4,15 @@ pub mod bip39;
 pub mod blockchain_bridge;
 pub mod blockchain_interface;
 pub mod blockchain_interface_initializer;
+pub mod dev_faucet;
+pub mod fee_history_tracker;
+pub mod futures_compat;
 pub mod payer;
+pub mod payment_memo;
+pub mod receivable_scan_records;
+pub mod rpc_usage;
+pub mod self_test;
 pub mod signature;
 #[cfg(test)]
 pub mod test_utils;
+pub mod warning_throttle;
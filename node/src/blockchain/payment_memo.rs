@@ -0,0 +1,149 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::TRANSFER_METHOD_ID;
+use crate::sub_lib::cryptde::PublicKey;
+use ethsign_crypto::Keccak256;
+use rustc_hex::ToHex;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+// The memo rides behind the arguments of an ERC-20 transfer call. The token contract reads only
+// as much of the call data as its arguments take and ignores the rest, so the memo costs a little
+// gas but changes nothing about the transfer itself
+pub const PAYMENT_MEMO_MARKER: [u8; 4] = *b"MASQ";
+pub const PAYMENT_MEMO_FINGERPRINT_LEN: usize = 8;
+pub const PAYMENT_MEMO_LEN: usize = PAYMENT_MEMO_MARKER.len() + PAYMENT_MEMO_FINGERPRINT_LEN;
+const TRANSFER_CALL_DATA_LEN: usize = 4 + 32 + 32;
+
+// Names the paying Node by a fingerprint of its public key, which stays the same when the Node
+// switches to another consuming wallet
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PaymentMemo {
+    fingerprint: [u8; PAYMENT_MEMO_FINGERPRINT_LEN],
+}
+
+impl PaymentMemo {
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        let mut fingerprint = [0u8; PAYMENT_MEMO_FINGERPRINT_LEN];
+        fingerprint
+            .copy_from_slice(&public_key.as_slice().keccak256()[..PAYMENT_MEMO_FINGERPRINT_LEN]);
+        Self { fingerprint }
+    }
+
+    pub fn fingerprint(&self) -> &[u8] {
+        &self.fingerprint
+    }
+
+    pub fn to_bytes(&self) -> [u8; PAYMENT_MEMO_LEN] {
+        let mut bytes = [0u8; PAYMENT_MEMO_LEN];
+        bytes[..PAYMENT_MEMO_MARKER.len()].copy_from_slice(&PAYMENT_MEMO_MARKER);
+        bytes[PAYMENT_MEMO_MARKER.len()..].copy_from_slice(&self.fingerprint);
+        bytes
+    }
+
+    // Anything but a plain transfer call followed by exactly one memo is taken for a payment
+    // without a memo
+    pub fn decode(call_data: &[u8]) -> Option<Self> {
+        if call_data.len() != TRANSFER_CALL_DATA_LEN + PAYMENT_MEMO_LEN
+            || call_data[0..4] != TRANSFER_METHOD_ID
+        {
+            return None;
+        }
+        let memo = &call_data[TRANSFER_CALL_DATA_LEN..];
+        if memo[..PAYMENT_MEMO_MARKER.len()] != PAYMENT_MEMO_MARKER {
+            return None;
+        }
+        let mut fingerprint = [0u8; PAYMENT_MEMO_FINGERPRINT_LEN];
+        fingerprint.copy_from_slice(&memo[PAYMENT_MEMO_MARKER.len()..]);
+        Some(Self { fingerprint })
+    }
+}
+
+impl Display for PaymentMemo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.fingerprint.to_hex::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::utils::sign_transaction_data;
+    use crate::test_utils::make_wallet;
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(PAYMENT_MEMO_MARKER, [0x4d, 0x41, 0x53, 0x51]);
+        assert_eq!(PAYMENT_MEMO_FINGERPRINT_LEN, 8);
+        assert_eq!(PAYMENT_MEMO_LEN, 12);
+    }
+
+    #[test]
+    fn payment_memo_fingerprints_the_public_key() {
+        let public_key = PublicKey::new(b"booga");
+
+        let result = PaymentMemo::from_public_key(&public_key);
+
+        assert_eq!(result.fingerprint(), &b"booga".keccak256()[..8]);
+        assert_eq!(
+            result,
+            PaymentMemo::from_public_key(&PublicKey::new(b"booga"))
+        );
+        assert_ne!(
+            result,
+            PaymentMemo::from_public_key(&PublicKey::new(b"agoob"))
+        );
+    }
+
+    #[test]
+    fn payment_memo_is_marked_in_its_bytes() {
+        let subject = PaymentMemo::from_public_key(&PublicKey::new(b"booga"));
+
+        let result = subject.to_bytes();
+
+        assert_eq!(&result[..4], b"MASQ");
+        assert_eq!(&result[4..], subject.fingerprint());
+    }
+
+    #[test]
+    fn payment_memo_is_decoded_from_tagged_transfer_call_data() {
+        let subject = PaymentMemo::from_public_key(&PublicKey::new(b"booga"));
+        let call_data = sign_transaction_data(1_234, make_wallet("creditor"), Some(subject));
+
+        let result = PaymentMemo::decode(&call_data);
+
+        assert_eq!(result, Some(subject));
+    }
+
+    #[test]
+    fn payment_memo_is_not_decoded_from_other_call_data() {
+        let memo = PaymentMemo::from_public_key(&PublicKey::new(b"booga"));
+        let tagged = sign_transaction_data(1_234, make_wallet("creditor"), Some(memo));
+        let untagged = sign_transaction_data(1_234, make_wallet("creditor"), None);
+        let mut wrong_method = tagged.clone();
+        wrong_method[0] = 0xff;
+        let mut wrong_marker = tagged.clone();
+        wrong_marker[68] = b'X';
+        let too_long = [tagged.clone(), vec![0]].concat();
+
+        [
+            untagged,
+            wrong_method,
+            wrong_marker,
+            too_long,
+            tagged[..70].to_vec(),
+            vec![],
+        ]
+        .iter()
+        .for_each(|call_data| assert_eq!(PaymentMemo::decode(call_data), None, "{:?}", call_data))
+    }
+
+    #[test]
+    fn payment_memo_displays_its_fingerprint_in_hex() {
+        let subject = PaymentMemo {
+            fingerprint: [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+        };
+
+        assert_eq!(subject.to_string(), "0123456789abcdef");
+    }
+}
@@ -0,0 +1,476 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::receivable_dao::{ReceivableDao, ReceivableDaoReal};
+use crate::accountant::db_access_objects::utils::from_time_t;
+use crate::blockchain::blockchain_bridge::BlockMarker;
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::BlockchainInterfaceWeb3;
+use crate::blockchain::blockchain_interface::data_structures::{
+    BlockchainTransaction, RecheckedTransactions,
+};
+use crate::database::db_initializer::{
+    DbInitializationConfig, DbInitializer, DbInitializerReal, ExternalData,
+};
+use masq_lib::blockchains::chains::chain_from_chain_identifier_opt;
+use masq_lib::logger::Logger;
+use masq_lib::utils::NeighborhoodModeLight;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use web3::types::{Address, Log, H256};
+
+pub const RECEIVABLE_SCAN_RECORDS_DIR: &str = "receivable_scan_records";
+const RECORD_FILE_PREFIX: &str = "receivable_scan_";
+const RECORD_FILE_EXTENSION: &str = "json";
+
+// What the blockchain service answered to one received-payments scan, together with everything
+// else the scan based its outcome on, so that the outcome can be worked out again offline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReceivableScanRecord {
+    pub chain: String,
+    #[serde(rename = "recordedAt")]
+    pub recorded_at: i64,
+    pub recipients: Vec<Address>,
+    #[serde(rename = "startBlock")]
+    pub start_block_opt: Option<u64>,
+    #[serde(rename = "recheckFromBlock")]
+    pub recheck_from_block_opt: Option<u64>,
+    #[serde(rename = "endBlock")]
+    pub end_block_opt: Option<u64>,
+    pub logs: Vec<Log>,
+    #[serde(rename = "originalPayers")]
+    pub original_payers: Vec<OriginalPayer>,
+}
+
+// The payer a forwarded payment was credited to, as looked up on the blockchain at the time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OriginalPayer {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: H256,
+    pub payer: Address,
+}
+
+impl ReceivableScanRecord {
+    pub fn original_payers(&self) -> HashMap<H256, Address> {
+        self.original_payers
+            .iter()
+            .map(|original| (original.transaction_hash, original.payer))
+            .collect()
+    }
+}
+
+pub fn block_number_opt(block_marker: BlockMarker) -> Option<u64> {
+    match block_marker {
+        BlockMarker::Value(number) => Some(number),
+        BlockMarker::Uninitialized => None,
+    }
+}
+
+fn block_marker(block_number_opt: Option<u64>) -> BlockMarker {
+    match block_number_opt {
+        Some(number) => BlockMarker::Value(number),
+        None => BlockMarker::Uninitialized,
+    }
+}
+
+// Keeps the records of the latest scans in the data directory; the oldest ones give way to
+// the new ones so that the directory doesn't grow without bounds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivableScanRecorder {
+    directory: PathBuf,
+    retained_records: usize,
+}
+
+impl ReceivableScanRecorder {
+    pub fn new(data_directory: &Path, retained_records: u64) -> Self {
+        Self {
+            directory: data_directory.join(RECEIVABLE_SCAN_RECORDS_DIR),
+            retained_records: retained_records as usize,
+        }
+    }
+
+    pub fn record(&self, record: &ReceivableScanRecord) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|e| format!("Can't create {}: {}", self.directory.display(), e))?;
+        let serialized = serde_json::to_string_pretty(record)
+            .map_err(|e| format!("Can't serialize the record: {}", e))?;
+        let path = self.vacant_path();
+        fs::write(&path, serialized)
+            .map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+        self.drop_oldest_records()?;
+        Ok(path)
+    }
+
+    pub fn records(&self) -> Result<Vec<PathBuf>, String> {
+        list_records(&self.directory)
+    }
+
+    // Named by the moment of recording, so that the order of the names is the order of the scans
+    fn vacant_path(&self) -> PathBuf {
+        let mut millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Clock went back before the epoch")
+            .as_millis();
+        loop {
+            let path = self.directory.join(format!(
+                "{}{:020}.{}",
+                RECORD_FILE_PREFIX, millis, RECORD_FILE_EXTENSION
+            ));
+            if !path.exists() {
+                return path;
+            }
+            millis += 1
+        }
+    }
+
+    fn drop_oldest_records(&self) -> Result<(), String> {
+        let records = self.records()?;
+        let excess = records.len().saturating_sub(self.retained_records);
+        records.iter().take(excess).try_for_each(|path| {
+            fs::remove_file(path).map_err(|e| format!("Can't delete {}: {}", path.display(), e))
+        })
+    }
+}
+
+pub fn list_records(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|e| format!("Can't read {}: {}", directory.display(), e))?;
+    let mut records = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            name.starts_with(RECORD_FILE_PREFIX)
+                && path.extension().and_then(|ext| ext.to_str()) == Some(RECORD_FILE_EXTENSION)
+        })
+        .collect::<Vec<PathBuf>>();
+    records.sort();
+    Ok(records)
+}
+
+pub fn read_record(path: &Path) -> Result<ReceivableScanRecord, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("{} is no scan record: {}", path.display(), e))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReplayedReceivableScan {
+    pub credited: Vec<BlockchainTransaction>,
+    pub rechecked_opt: Option<RecheckedTransactions>,
+    pub pending: Vec<BlockchainTransaction>,
+}
+
+// Works a recorded scan out again and credits what it finds to the receivables in the database
+// of the sandbox directory, created there if it isn't yet. That database is meant to be a copy
+// of the one of the Node, never the one the Node runs on
+pub fn replay_receivable_scan(
+    record_path: &Path,
+    sandbox_directory: &Path,
+    logger: &Logger,
+) -> Result<ReplayedReceivableScan, String> {
+    let record = read_record(record_path)?;
+    let chain = chain_from_chain_identifier_opt(&record.chain)
+        .ok_or_else(|| format!("Unknown chain '{}' in the record", record.chain))?;
+    let start_block_marker = block_marker(record.start_block_opt);
+    let end_block_marker = block_marker(record.end_block_opt);
+    let transactions = BlockchainInterfaceWeb3::extract_transactions_from_logs(
+        &record.logs,
+        &record.original_payers(),
+        logger,
+    );
+    let (pending, transactions) =
+        BlockchainInterfaceWeb3::separate_pending_transactions(transactions, end_block_marker);
+    let (credited, rechecked_opt) = BlockchainInterfaceWeb3::separate_rechecked_transactions(
+        transactions,
+        start_block_marker,
+        end_block_marker,
+        record.recheck_from_block_opt,
+    );
+    let conn = DbInitializerReal::default()
+        .initialize(
+            sandbox_directory,
+            DbInitializationConfig::create_or_migrate(ExternalData::new(
+                chain,
+                NeighborhoodModeLight::ZeroHop,
+                None,
+            )),
+        )
+        .map_err(|e| {
+            format!(
+                "Can't open the sandbox database in {}: {:?}",
+                sandbox_directory.display(),
+                e
+            )
+        })?;
+    let mut receivable_dao = ReceivableDaoReal::new(conn);
+    receivable_dao
+        .more_money_received(from_time_t(record.recorded_at), &credited)
+        .commit()
+        .map_err(|e| format!("Can't credit the replayed payments: {}", e))?;
+    debug!(
+        logger,
+        "Replayed {}: {} payments credited, {} pending",
+        record_path.display(),
+        credited.len(),
+        pending.len()
+    );
+    Ok(ReplayedReceivableScan {
+        credited,
+        rechecked_opt,
+        pending,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::db_access_objects::receivable_dao::ReceivableDaoFactory;
+    use crate::accountant::db_access_objects::utils::DaoFactoryReal;
+    use crate::blockchain::blockchain_interface::blockchain_interface_web3::TRANSACTION_LITERAL;
+    use crate::sub_lib::wallet::Wallet;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+    use serde_json::json;
+    use web3::types::U256;
+
+    fn make_transfer_log(block_number: u64, from: Address, wei_amount: u128, hash: u64) -> Log {
+        let mut value = [0u8; 32];
+        U256::from(wei_amount).to_big_endian(&mut value);
+        serde_json::from_value(json!({
+            "address": Address::from_low_u64_be(1),
+            "topics": [TRANSACTION_LITERAL, H256::from(from), H256::from_low_u64_be(0x1234)],
+            "data": web3::types::Bytes(value.to_vec()),
+            "blockHash": null,
+            "blockNumber": format!("{:#x}", block_number),
+            "transactionHash": H256::from_low_u64_be(hash),
+            "transactionIndex": null,
+            "logIndex": "0x0",
+            "removed": false,
+        }))
+        .unwrap()
+    }
+
+    fn make_record(logs: Vec<Log>) -> ReceivableScanRecord {
+        ReceivableScanRecord {
+            chain: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            recorded_at: 1_700_000_000,
+            recipients: vec![Address::from_low_u64_be(0x1234)],
+            start_block_opt: Some(100),
+            recheck_from_block_opt: None,
+            end_block_opt: Some(200),
+            logs,
+            original_payers: vec![],
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(RECEIVABLE_SCAN_RECORDS_DIR, "receivable_scan_records");
+        assert_eq!(RECORD_FILE_PREFIX, "receivable_scan_");
+        assert_eq!(RECORD_FILE_EXTENSION, "json");
+    }
+
+    #[test]
+    fn recorded_scan_can_be_read_back() {
+        let data_dir = ensure_node_home_directory_exists(
+            "receivable_scan_records",
+            "recorded_scan_can_be_read_back",
+        );
+        let mut record = make_record(vec![make_transfer_log(
+            150,
+            Address::from_low_u64_be(0x5555),
+            4_567,
+            1,
+        )]);
+        record.original_payers = vec![OriginalPayer {
+            transaction_hash: H256::from_low_u64_be(1),
+            payer: Address::from_low_u64_be(0x6666),
+        }];
+        let subject = ReceivableScanRecorder::new(&data_dir, 5);
+
+        let path = subject.record(&record).unwrap();
+
+        assert_eq!(
+            path.parent().unwrap(),
+            data_dir.join(RECEIVABLE_SCAN_RECORDS_DIR)
+        );
+        assert_eq!(read_record(&path), Ok(record));
+    }
+
+    #[test]
+    fn recorder_keeps_only_the_latest_records() {
+        let data_dir = ensure_node_home_directory_exists(
+            "receivable_scan_records",
+            "recorder_keeps_only_the_latest_records",
+        );
+        let subject = ReceivableScanRecorder::new(&data_dir, 2);
+        let records = (1..=4)
+            .map(|n| {
+                let mut record = make_record(vec![]);
+                record.recorded_at = n;
+                record
+            })
+            .collect::<Vec<_>>();
+
+        records.iter().for_each(|record| {
+            subject.record(record).unwrap();
+        });
+
+        let kept = subject
+            .records()
+            .unwrap()
+            .iter()
+            .map(|path| read_record(path).unwrap().recorded_at)
+            .collect::<Vec<_>>();
+        assert_eq!(kept, vec![3, 4]);
+    }
+
+    #[test]
+    fn list_records_ignores_foreign_files() {
+        let data_dir = ensure_node_home_directory_exists(
+            "receivable_scan_records",
+            "list_records_ignores_foreign_files",
+        );
+        let subject = ReceivableScanRecorder::new(&data_dir, 5);
+        let path = subject.record(&make_record(vec![])).unwrap();
+        let directory = data_dir.join(RECEIVABLE_SCAN_RECORDS_DIR);
+        fs::write(directory.join("notes.txt"), "mine").unwrap();
+        fs::write(directory.join("receivable_scan_draft.txt"), "mine").unwrap();
+
+        let result = list_records(&directory);
+
+        assert_eq!(result, Ok(vec![path]));
+    }
+
+    #[test]
+    fn read_record_complains_about_a_file_that_is_no_record() {
+        let data_dir = ensure_node_home_directory_exists(
+            "receivable_scan_records",
+            "read_record_complains_about_a_file_that_is_no_record",
+        );
+        let path = data_dir.join("receivable_scan_1.json");
+        fs::write(&path, "{\"chain\": 5}").unwrap();
+
+        let result = read_record(&path);
+
+        let message = result.unwrap_err();
+        assert!(
+            message.starts_with(&format!("{} is no scan record: ", path.display())),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn replay_credits_the_confirmed_payments_to_the_sandbox_database() {
+        let data_dir = ensure_node_home_directory_exists(
+            "receivable_scan_records",
+            "replay_credits_the_confirmed_payments_to_the_sandbox_database",
+        );
+        let sandbox_dir = data_dir.join("sandbox");
+        fs::create_dir_all(&sandbox_dir).unwrap();
+        let payer = Address::from_low_u64_be(0x5555);
+        let forwarder = Address::from_low_u64_be(0x7777);
+        let original_payer = Address::from_low_u64_be(0x6666);
+        let dao_factory = DaoFactoryReal::new(
+            &sandbox_dir,
+            DbInitializationConfig::create_or_migrate(ExternalData::new(
+                TEST_DEFAULT_CHAIN,
+                NeighborhoodModeLight::ZeroHop,
+                None,
+            )),
+        );
+        let receivable_dao = dao_factory.make();
+        [payer, original_payer].iter().for_each(|wallet| {
+            receivable_dao
+                .more_money_receivable(from_time_t(1_600_000_000), &Wallet::from(*wallet), 10_000)
+                .unwrap()
+        });
+        let mut record = make_record(vec![
+            make_transfer_log(150, payer, 4_567, 1),
+            make_transfer_log(160, forwarder, 1_000, 2),
+            make_transfer_log(210, payer, 333, 3),
+        ]);
+        record.original_payers = vec![OriginalPayer {
+            transaction_hash: H256::from_low_u64_be(2),
+            payer: original_payer,
+        }];
+        let path = ReceivableScanRecorder::new(&data_dir, 5)
+            .record(&record)
+            .unwrap();
+
+        let result = replay_receivable_scan(
+            &path,
+            &sandbox_dir,
+            &Logger::new("replay_credits_the_confirmed_payments_to_the_sandbox_database"),
+        )
+        .unwrap();
+
+        let credited = vec![
+            BlockchainTransaction {
+                block_number: 150,
+                from: Wallet::from(payer),
+                wei_amount: 4_567,
+            },
+            BlockchainTransaction {
+                block_number: 160,
+                from: Wallet::from(original_payer),
+                wei_amount: 1_000,
+            },
+        ];
+        assert_eq!(
+            result,
+            ReplayedReceivableScan {
+                credited,
+                rechecked_opt: None,
+                pending: vec![BlockchainTransaction {
+                    block_number: 210,
+                    from: Wallet::from(payer),
+                    wei_amount: 333,
+                }],
+            }
+        );
+        let payer_account = receivable_dao.account_status(&Wallet::from(payer)).unwrap();
+        assert_eq!(payer_account.balance_wei, 5_433);
+        assert_eq!(
+            payer_account.last_received_timestamp,
+            from_time_t(1_700_000_000)
+        );
+        let original_payer_account = receivable_dao
+            .account_status(&Wallet::from(original_payer))
+            .unwrap();
+        assert_eq!(original_payer_account.balance_wei, 9_000);
+        assert_eq!(
+            receivable_dao.account_status(&Wallet::from(forwarder)),
+            None
+        );
+    }
+
+    #[test]
+    fn replay_refuses_a_record_of_an_unknown_chain() {
+        let data_dir = ensure_node_home_directory_exists(
+            "receivable_scan_records",
+            "replay_refuses_a_record_of_an_unknown_chain",
+        );
+        let mut record = make_record(vec![]);
+        record.chain = "moon-mainnet".to_string();
+        let path = ReceivableScanRecorder::new(&data_dir, 5)
+            .record(&record)
+            .unwrap();
+
+        let result = replay_receivable_scan(
+            &path,
+            &data_dir.join("sandbox"),
+            &Logger::new("replay_refuses_a_record_of_an_unknown_chain"),
+        );
+
+        assert_eq!(
+            result,
+            Err("Unknown chain 'moon-mainnet' in the record".to_string())
+        );
+    }
+}
@@ -0,0 +1,485 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::rpc_usage_dao::{RpcUsage, RpcUsageDao};
+use masq_lib::logger::Logger;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use time::{Duration, OffsetDateTime};
+use web3::transports::Http;
+use web3::{rpc, BatchTransport, RequestId, Transport};
+
+// Share of the daily quota whose use is warned of before the quota itself is reached
+pub const RPC_QUOTA_WARNING_PERCENT: u64 = 80;
+// Older days are deleted so that the table cannot grow without bounds
+pub const RPC_USAGE_RETENTION_DAYS: i64 = 30;
+const DEFAULT_REQUEST_WEIGHT: u64 = 20;
+
+// Rough costs of the requests in the spirit of the compute units metered providers bill;
+// searching blocks for logs or submitting a transaction takes much more than reading a number
+pub fn request_weight(method: &str) -> u64 {
+    match method {
+        "eth_chainId" | "net_version" | "eth_blockNumber" => 10,
+        "eth_getTransactionReceipt" | "eth_getBlockByNumber" => 15,
+        "eth_gasPrice" | "eth_getBalance" | "eth_feeHistory" => 20,
+        "eth_call" | "eth_getTransactionCount" | "eth_estimateGas" => 26,
+        "eth_getLogs" => 75,
+        "eth_sendRawTransaction" => 250,
+        _ => DEFAULT_REQUEST_WEIGHT,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RpcUsageCount {
+    pub calls: u64,
+    pub weight: u64,
+}
+
+// Counts the requests sent through a transport since the count was last taken
+#[derive(Debug, Clone, Default)]
+pub struct RpcUsageMeter {
+    count_arc: Arc<Mutex<RpcUsageCount>>,
+}
+
+impl RpcUsageMeter {
+    pub fn count(&self, method: &str) {
+        let mut count = self.count_arc.lock().expect("RPC usage meter is poisoned");
+        count.calls += 1;
+        count.weight += request_weight(method);
+    }
+
+    pub fn take(&self) -> RpcUsageCount {
+        std::mem::take(&mut *self.count_arc.lock().expect("RPC usage meter is poisoned"))
+    }
+}
+
+// The HTTP transport, with every request it sends counted, each one of a batch included
+#[derive(Debug, Clone)]
+pub struct MeteredHttp {
+    http: Http,
+    meter: RpcUsageMeter,
+}
+
+impl MeteredHttp {
+    pub fn new(http: Http, meter: RpcUsageMeter) -> Self {
+        Self { http, meter }
+    }
+
+    pub fn meter(&self) -> RpcUsageMeter {
+        self.meter.clone()
+    }
+
+    fn count_call(&self, call: &rpc::Call) {
+        match call {
+            rpc::Call::MethodCall(method_call) => self.meter.count(&method_call.method),
+            rpc::Call::Notification(notification) => self.meter.count(&notification.method),
+            _ => self.meter.count(""),
+        }
+    }
+}
+
+// For uses where nobody reads the count
+impl From<Http> for MeteredHttp {
+    fn from(http: Http) -> Self {
+        Self::new(http, RpcUsageMeter::default())
+    }
+}
+
+impl Transport for MeteredHttp {
+    type Out = <Http as Transport>::Out;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        self.http.prepare(method, params)
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        self.count_call(&request);
+        self.http.send(id, request)
+    }
+}
+
+impl BatchTransport for MeteredHttp {
+    type Batch = <Http as BatchTransport>::Batch;
+
+    fn send_batch<T>(&self, requests: T) -> Self::Batch
+    where
+        T: IntoIterator<Item = (RequestId, rpc::Call)>,
+    {
+        let requests = requests.into_iter().collect::<Vec<_>>();
+        requests.iter().for_each(|(_, call)| self.count_call(call));
+        self.http.send_batch(requests)
+    }
+}
+
+pub fn day_of(timestamp: SystemTime) -> String {
+    let date = OffsetDateTime::from(timestamp).date();
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+// Books what the meter of the blockchain interface counted against the endpoint, by the day,
+// and warns as the requests of the day approach the quota of the user's plan with the provider
+pub struct RpcUsageLedger {
+    dao: Box<dyn RpcUsageDao>,
+    endpoint: String,
+    // Zero means no quota
+    daily_quota: u64,
+    // The day of the latest settlement and the share of the quota last warned of on that day
+    day_opt: Option<String>,
+    warned_percent: u64,
+}
+
+impl RpcUsageLedger {
+    pub fn new(dao: Box<dyn RpcUsageDao>, endpoint: &str, daily_quota: u64) -> Self {
+        Self {
+            dao,
+            endpoint: endpoint.to_string(),
+            daily_quota,
+            day_opt: None,
+            warned_percent: 0,
+        }
+    }
+
+    pub fn daily_quota(&self) -> u64 {
+        self.daily_quota
+    }
+
+    pub fn settle(&mut self, count: RpcUsageCount, now: SystemTime, logger: &Logger) {
+        if count.calls == 0 {
+            return;
+        }
+        let day = day_of(now);
+        if self.day_opt.as_ref() != Some(&day) {
+            self.start_day(&day, now, logger)
+        }
+        let usage = RpcUsage {
+            endpoint: self.endpoint.clone(),
+            day: day.clone(),
+            calls: count.calls,
+            weight: count.weight,
+        };
+        if let Err(e) = self.dao.add(&usage) {
+            warning!(
+                logger,
+                "Failed to book the usage of the blockchain service: {:?}",
+                e
+            );
+            return;
+        }
+        if self.daily_quota > 0 {
+            self.check_quota(&day, logger)
+        }
+    }
+
+    pub fn usage(&self, now: SystemTime) -> Vec<RpcUsage> {
+        self.dao.usage_since(&Self::oldest_retained_day(now))
+    }
+
+    fn start_day(&mut self, day: &str, now: SystemTime, logger: &Logger) {
+        self.day_opt = Some(day.to_string());
+        self.warned_percent = 0;
+        if let Err(e) = self.dao.delete_before(&Self::oldest_retained_day(now)) {
+            warning!(
+                logger,
+                "Failed to delete the old usage of the blockchain service: {:?}",
+                e
+            )
+        }
+    }
+
+    fn check_quota(&mut self, day: &str, logger: &Logger) {
+        let calls_today = self
+            .dao
+            .usage_since(day)
+            .into_iter()
+            .filter(|usage| usage.endpoint == self.endpoint && usage.day == day)
+            .map(|usage| usage.calls)
+            .sum::<u64>();
+        let percent = calls_today.saturating_mul(100) / self.daily_quota;
+        if percent >= 100 && self.warned_percent < 100 {
+            warning!(
+                logger,
+                "The Node has sent {} requests to {} today, which exhausts the daily quota of {}. \
+                 Expect the blockchain service to refuse further requests until the day is over \
+                 (UTC)",
+                calls_today,
+                self.endpoint,
+                self.daily_quota
+            );
+            self.warned_percent = 100
+        } else if percent >= RPC_QUOTA_WARNING_PERCENT
+            && self.warned_percent < RPC_QUOTA_WARNING_PERCENT
+        {
+            warning!(
+                logger,
+                "The Node has sent {} requests to {} today, {}% of the daily quota of {}",
+                calls_today,
+                self.endpoint,
+                percent,
+                self.daily_quota
+            );
+            self.warned_percent = RPC_QUOTA_WARNING_PERCENT
+        }
+    }
+
+    fn oldest_retained_day(now: SystemTime) -> String {
+        day_of(now - Duration::days(RPC_USAGE_RETENTION_DAYS - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::db_access_objects::rpc_usage_dao::RpcUsageDaoError;
+    use crate::accountant::test_utils::RpcUsageDaoMock;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use std::time::UNIX_EPOCH;
+
+    // 2024-03-01 12:00:00 UTC
+    fn noon_of_march_first() -> SystemTime {
+        UNIX_EPOCH + std::time::Duration::from_secs(1_709_294_400)
+    }
+
+    fn make_usage(day: &str, calls: u64) -> RpcUsage {
+        RpcUsage {
+            endpoint: "https://provider.com/*****".to_string(),
+            day: day.to_string(),
+            calls,
+            weight: calls * 20,
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(RPC_QUOTA_WARNING_PERCENT, 80);
+        assert_eq!(RPC_USAGE_RETENTION_DAYS, 30);
+        assert_eq!(DEFAULT_REQUEST_WEIGHT, 20);
+    }
+
+    #[test]
+    fn request_weight_tells_heavy_requests_from_light_ones() {
+        assert_eq!(request_weight("eth_blockNumber"), 10);
+        assert_eq!(request_weight("eth_getTransactionReceipt"), 15);
+        assert_eq!(request_weight("eth_getBalance"), 20);
+        assert_eq!(request_weight("eth_call"), 26);
+        assert_eq!(request_weight("eth_getLogs"), 75);
+        assert_eq!(request_weight("eth_sendRawTransaction"), 250);
+        assert_eq!(request_weight("eth_somethingNew"), DEFAULT_REQUEST_WEIGHT);
+    }
+
+    #[test]
+    fn meter_counts_until_the_count_is_taken() {
+        let subject = RpcUsageMeter::default();
+        let clone = subject.clone();
+
+        subject.count("eth_getLogs");
+        clone.count("eth_blockNumber");
+        let first = subject.take();
+        clone.count("eth_call");
+        let second = subject.take();
+        let third = clone.take();
+
+        assert_eq!(
+            first,
+            RpcUsageCount {
+                calls: 2,
+                weight: 85
+            }
+        );
+        assert_eq!(
+            second,
+            RpcUsageCount {
+                calls: 1,
+                weight: 26
+            }
+        );
+        assert_eq!(third, RpcUsageCount::default());
+    }
+
+    #[test]
+    fn day_of_is_the_utc_date() {
+        assert_eq!(day_of(noon_of_march_first()), "2024-03-01".to_string());
+        assert_eq!(
+            day_of(noon_of_march_first() - Duration::hours(12)),
+            "2024-03-01".to_string()
+        );
+        assert_eq!(
+            day_of(noon_of_march_first() - Duration::hours(13)),
+            "2024-02-29".to_string()
+        );
+    }
+
+    #[test]
+    fn settle_books_the_count_and_deletes_old_days_once_a_day() {
+        let add_params_arc = Arc::new(Mutex::new(vec![]));
+        let delete_before_params_arc = Arc::new(Mutex::new(vec![]));
+        let dao = RpcUsageDaoMock::new()
+            .add_params(&add_params_arc)
+            .add_result(Ok(()))
+            .add_result(Ok(()))
+            .add_result(Ok(()))
+            .delete_before_params(&delete_before_params_arc)
+            .delete_before_result(Ok(()))
+            .delete_before_result(Ok(()));
+        let mut subject = RpcUsageLedger::new(Box::new(dao), "https://provider.com/*****", 0);
+        let logger = Logger::new("test");
+
+        subject.settle(
+            RpcUsageCount {
+                calls: 3,
+                weight: 60,
+            },
+            noon_of_march_first(),
+            &logger,
+        );
+        subject.settle(
+            RpcUsageCount::default(),
+            noon_of_march_first() + Duration::hours(1),
+            &logger,
+        );
+        subject.settle(
+            RpcUsageCount {
+                calls: 2,
+                weight: 150,
+            },
+            noon_of_march_first() + Duration::hours(2),
+            &logger,
+        );
+        subject.settle(
+            RpcUsageCount {
+                calls: 1,
+                weight: 10,
+            },
+            noon_of_march_first() + Duration::hours(12),
+            &logger,
+        );
+
+        let add_params = add_params_arc.lock().unwrap();
+        assert_eq!(
+            *add_params,
+            vec![
+                RpcUsage {
+                    endpoint: "https://provider.com/*****".to_string(),
+                    day: "2024-03-01".to_string(),
+                    calls: 3,
+                    weight: 60
+                },
+                RpcUsage {
+                    endpoint: "https://provider.com/*****".to_string(),
+                    day: "2024-03-01".to_string(),
+                    calls: 2,
+                    weight: 150
+                },
+                RpcUsage {
+                    endpoint: "https://provider.com/*****".to_string(),
+                    day: "2024-03-02".to_string(),
+                    calls: 1,
+                    weight: 10
+                },
+            ]
+        );
+        let delete_before_params = delete_before_params_arc.lock().unwrap();
+        assert_eq!(
+            *delete_before_params,
+            vec!["2024-02-01".to_string(), "2024-02-02".to_string()]
+        );
+    }
+
+    #[test]
+    fn settle_warns_once_at_each_level_of_the_quota_a_day() {
+        init_test_logging();
+        let test_name = "settle_warns_once_at_each_level_of_the_quota_a_day";
+        let dao = RpcUsageDaoMock::new()
+            .delete_before_result(Ok(()))
+            .delete_before_result(Ok(()));
+        let dao = [79, 80, 99, 100, 120]
+            .into_iter()
+            .fold(dao, |dao, calls| {
+                dao.add_result(Ok(()))
+                    .usage_since_result(vec![make_usage("2024-03-01", calls)])
+            })
+            .add_result(Ok(()))
+            .usage_since_result(vec![make_usage("2024-03-02", 85)]);
+        let mut subject = RpcUsageLedger::new(Box::new(dao), "https://provider.com/*****", 100);
+        let logger = Logger::new(test_name);
+        let count = RpcUsageCount {
+            calls: 1,
+            weight: 20,
+        };
+
+        (0..5).for_each(|_| subject.settle(count, noon_of_march_first(), &logger));
+        subject.settle(count, noon_of_march_first() + Duration::days(1), &logger);
+
+        let tlh = TestLogHandler::new();
+        tlh.assert_logs_contain_in_order(vec![
+            &format!(
+                "WARN: {}: The Node has sent 80 requests to https://provider.com/***** today, \
+                 80% of the daily quota of 100",
+                test_name
+            ),
+            &format!(
+                "WARN: {}: The Node has sent 100 requests to https://provider.com/***** today, \
+                 which exhausts the daily quota of 100",
+                test_name
+            ),
+            &format!(
+                "WARN: {}: The Node has sent 85 requests to https://provider.com/***** today, \
+                 85% of the daily quota of 100",
+                test_name
+            ),
+        ]);
+        tlh.exists_no_log_containing(&format!("{}: The Node has sent 79 requests", test_name));
+        tlh.exists_no_log_containing(&format!("{}: The Node has sent 99 requests", test_name));
+        tlh.exists_no_log_containing(&format!("{}: The Node has sent 120 requests", test_name));
+    }
+
+    #[test]
+    fn settle_logs_failure_to_book_the_count() {
+        init_test_logging();
+        let test_name = "settle_logs_failure_to_book_the_count";
+        let dao = RpcUsageDaoMock::new()
+            .delete_before_result(Err(RpcUsageDaoError::RecordDeletion("bluh".to_string())))
+            .add_result(Err(RpcUsageDaoError::RecordingFailed("blah".to_string())));
+        let mut subject = RpcUsageLedger::new(Box::new(dao), "https://provider.com/*****", 100);
+
+        subject.settle(
+            RpcUsageCount {
+                calls: 1,
+                weight: 20,
+            },
+            noon_of_march_first(),
+            &Logger::new(test_name),
+        );
+
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            &format!(
+                "WARN: {}: Failed to delete the old usage of the blockchain service: \
+                 RecordDeletion(\"bluh\")",
+                test_name
+            ),
+            &format!(
+                "WARN: {}: Failed to book the usage of the blockchain service: \
+                 RecordingFailed(\"blah\")",
+                test_name
+            ),
+        ]);
+    }
+
+    #[test]
+    fn usage_covers_the_retained_days() {
+        let usage_since_params_arc = Arc::new(Mutex::new(vec![]));
+        let dao = RpcUsageDaoMock::new()
+            .usage_since_params(&usage_since_params_arc)
+            .usage_since_result(vec![make_usage("2024-03-01", 5)]);
+        let subject = RpcUsageLedger::new(Box::new(dao), "https://provider.com/*****", 0);
+
+        let result = subject.usage(noon_of_march_first());
+
+        assert_eq!(result, vec![make_usage("2024-03-01", 5)]);
+        let usage_since_params = usage_since_params_arc.lock().unwrap();
+        assert_eq!(*usage_since_params, vec!["2024-02-01".to_string()]);
+    }
+}
@@ -0,0 +1,296 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchain::blockchain_interface::blockchain_interface_web3::TRANSACTION_LITERAL;
+use crate::blockchain::blockchain_interface::lower_level_interface::LowBlockchainInt;
+use crate::blockchain::futures_compat::{legacy, LegacyFuture, LegacyFutureExt};
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::blockchains::chains::Chain;
+use masq_lib::messages::UiSelfTestStep;
+use std::future::Future;
+use std::time::Instant;
+use web3::types::{BlockNumber, FilterBuilder, U256, U64};
+
+// How many of the latest blocks are searched for token transfers
+pub const SELF_TEST_LOG_RANGE: u64 = 100;
+
+// A battery of queries against the blockchain service, none of which changes anything on the
+// blockchain. They run one after another, so that each one's latency is its own, and a failure
+// doesn't keep the rest from running.
+pub struct BlockchainSelfTest {
+    pub lower_interface: Box<dyn LowBlockchainInt>,
+    pub chain: Chain,
+    pub consuming_wallet: Result<Wallet, String>,
+    pub earning_wallet: Result<Wallet, String>,
+}
+
+impl BlockchainSelfTest {
+    pub fn run(self) -> LegacyFuture<Vec<UiSelfTestStep>, ()> {
+        legacy(async move {
+            let lower = self.lower_interface.as_ref();
+            Ok(vec![
+                timed("Chain id", chain_id(lower, self.chain)).await,
+                timed("Latest block", latest_block(lower)).await,
+                timed("Gas price", gas_price(lower)).await,
+                timed(
+                    "Consuming wallet balances",
+                    wallet_balances(lower, &self.consuming_wallet),
+                )
+                .await,
+                timed(
+                    "Earning wallet balances",
+                    wallet_balances(lower, &self.earning_wallet),
+                )
+                .await,
+                timed("Token transfer logs", token_transfer_logs(lower)).await,
+            ])
+        })
+    }
+}
+
+// The query isn't sent until the step is first polled, so the latency is the step's alone
+async fn timed<F>(name: &str, step: F) -> UiSelfTestStep
+where
+    F: Future<Output = Result<String, String>>,
+{
+    let started = Instant::now();
+    let result = step.await;
+    UiSelfTestStep {
+        name: name.to_string(),
+        passed: result.is_ok(),
+        latency_ms: started.elapsed().as_millis() as u64,
+        detail: result.unwrap_or_else(|e| e),
+    }
+}
+
+async fn chain_id(lower: &dyn LowBlockchainInt, chain: Chain) -> Result<String, String> {
+    let chain_id = lower
+        .get_chain_id()
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    if chain_id == U256::from(chain.rec().num_chain_id) {
+        Ok(format!("Chain id {}", chain_id))
+    } else {
+        Err(format!(
+            "The blockchain service serves chain id {}, but the Node is configured for --chain \
+            {} (chain id {})",
+            chain_id,
+            chain.rec().literal_identifier,
+            chain.rec().num_chain_id
+        ))
+    }
+}
+
+async fn latest_block(lower: &dyn LowBlockchainInt) -> Result<String, String> {
+    let block_number = lower
+        .get_block_number()
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!("Block {}", block_number))
+}
+
+async fn gas_price(lower: &dyn LowBlockchainInt) -> Result<String, String> {
+    let gas_price = lower
+        .get_gas_price()
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!("{} wei", gas_price))
+}
+
+async fn wallet_balances(
+    lower: &dyn LowBlockchainInt,
+    wallet: &Result<Wallet, String>,
+) -> Result<String, String> {
+    let address = wallet.as_ref().map_err(|e| e.clone())?.address();
+    let transaction_fee_balance = lower
+        .get_transaction_fee_balance(address)
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    let service_fee_balance = lower
+        .get_service_fee_balance(address)
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!(
+        "{:?} holds {} wei of transaction fee and {} wei of service fee",
+        address, transaction_fee_balance, service_fee_balance
+    ))
+}
+
+async fn token_transfer_logs(lower: &dyn LowBlockchainInt) -> Result<String, String> {
+    let end_block = lower
+        .get_block_number()
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    let start_block = end_block.saturating_sub(U64::from(SELF_TEST_LOG_RANGE - 1));
+    let filter = FilterBuilder::default()
+        .address(vec![lower.get_contract_address()])
+        .from_block(BlockNumber::Number(start_block))
+        .to_block(BlockNumber::Number(end_block))
+        .topics(Some(vec![TRANSACTION_LITERAL]), None, None, None)
+        .build();
+    let logs = lower
+        .get_transaction_logs(filter)
+        .compat()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!(
+        "{} token transfers in blocks {} to {}",
+        logs.len(),
+        start_block,
+        end_block
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::blockchain_interface::BlockchainReader;
+    use crate::blockchain::test_utils::make_blockchain_interface_web3;
+    use crate::test_utils::make_wallet;
+    use futures::Future as Future01;
+    use masq_lib::test_utils::mock_blockchain_client_server::MBCSBuilder;
+    use masq_lib::utils::find_free_port;
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(SELF_TEST_LOG_RANGE, 100);
+    }
+
+    fn make_subject(port: u16, consuming_wallet: Result<Wallet, String>) -> BlockchainSelfTest {
+        BlockchainSelfTest {
+            lower_interface: make_blockchain_interface_web3(port).lower_interface(),
+            chain: Chain::PolyMainnet,
+            consuming_wallet,
+            earning_wallet: Ok(make_wallet("earning")),
+        }
+    }
+
+    fn names_and_outcomes(steps: &[UiSelfTestStep]) -> Vec<(&str, bool, &str)> {
+        steps
+            .iter()
+            .map(|step| (step.name.as_str(), step.passed, step.detail.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn self_test_passes_against_a_healthy_blockchain_service() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x89".to_string(), 1)
+            .ok_response("0x1f4".to_string(), 2)
+            .ok_response("0x3b9aca00".to_string(), 3)
+            .ok_response("0x64".to_string(), 4)
+            .ok_response(format!("{:#066x}", 200), 5)
+            .ok_response("0x0".to_string(), 6)
+            .ok_response(format!("{:#066x}", 0), 7)
+            .ok_response("0x1f4".to_string(), 8)
+            .ok_response(Vec::<String>::new(), 9)
+            .start();
+        let consuming_wallet = make_wallet("consuming");
+        let subject = make_subject(port, Ok(consuming_wallet.clone()));
+
+        let result = subject.run().wait().unwrap();
+
+        let consuming_wallet_detail = format!(
+            "{:?} holds 100 wei of transaction fee and 200 wei of service fee",
+            consuming_wallet.address()
+        );
+        let earning_wallet_detail = format!(
+            "{:?} holds 0 wei of transaction fee and 0 wei of service fee",
+            make_wallet("earning").address()
+        );
+        assert_eq!(
+            names_and_outcomes(&result),
+            vec![
+                ("Chain id", true, "Chain id 137"),
+                ("Latest block", true, "Block 500"),
+                ("Gas price", true, "1000000000 wei"),
+                (
+                    "Consuming wallet balances",
+                    true,
+                    consuming_wallet_detail.as_str()
+                ),
+                (
+                    "Earning wallet balances",
+                    true,
+                    earning_wallet_detail.as_str()
+                ),
+                (
+                    "Token transfer logs",
+                    true,
+                    "0 token transfers in blocks 401 to 500"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn self_test_reports_a_chain_mismatch_and_a_locked_consuming_wallet() {
+        let port = find_free_port();
+        let _blockchain_client_server = MBCSBuilder::new(port)
+            .ok_response("0x1".to_string(), 1)
+            .ok_response("0x5".to_string(), 2)
+            .ok_response("0x1".to_string(), 3)
+            .ok_response("0x0".to_string(), 4)
+            .ok_response(format!("{:#066x}", 0), 5)
+            .ok_response("0x5".to_string(), 6)
+            .err_response(-32005, "Limit exceeded", 7)
+            .start();
+        let subject = make_subject(port, Err("Consuming wallet is locked".to_string()));
+
+        let result = subject.run().wait().unwrap();
+
+        let outcomes = names_and_outcomes(&result);
+        assert_eq!(
+            outcomes[0],
+            (
+                "Chain id",
+                false,
+                "The blockchain service serves chain id 1, but the Node is configured for \
+                --chain polygon-mainnet (chain id 137)"
+            )
+        );
+        assert_eq!(outcomes[1], ("Latest block", true, "Block 5"));
+        assert_eq!(outcomes[2], ("Gas price", true, "1 wei"));
+        assert_eq!(
+            outcomes[3],
+            (
+                "Consuming wallet balances",
+                false,
+                "Consuming wallet is locked"
+            )
+        );
+        assert_eq!(outcomes[4].0, "Earning wallet balances");
+        assert_eq!(outcomes[4].1, true);
+        assert_eq!(outcomes[5].0, "Token transfer logs");
+        assert_eq!(outcomes[5].1, false);
+        assert!(
+            outcomes[5].2.contains("Limit exceeded"),
+            "{}",
+            outcomes[5].2
+        );
+        assert_eq!(result.len(), 6);
+    }
+
+    #[test]
+    fn self_test_fails_every_step_if_the_blockchain_service_cannot_be_reached() {
+        let subject = make_subject(find_free_port(), Ok(make_wallet("consuming")));
+
+        let result = subject.run().wait().unwrap();
+
+        assert_eq!(result.len(), 6);
+        result.iter().for_each(|step| {
+            assert_eq!(step.passed, false, "{:?}", step);
+            assert!(
+                step.detail.starts_with("Blockchain error: Query failed"),
+                "{:?}",
+                step
+            )
+        });
+    }
+}
@@ -5,16 +5,19 @@
 use crate::blockchain::blockchain_interface::blockchain_interface_web3::{
     BlockchainInterfaceWeb3, REQUESTS_IN_PARALLEL,
 };
+use crate::sub_lib::blockchain_bridge::BlockchainBridgeConfig;
 use bip39::{Language, Mnemonic, Seed};
 use ethabi::Hash;
 use ethereum_types::{BigEndianHash, H160, H256, U64};
 use lazy_static::lazy_static;
 use masq_lib::blockchains::chains::Chain;
+use masq_lib::constants::DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS;
 use masq_lib::utils::to_string;
 use serde::Serialize;
 use serde_derive::Deserialize;
 use std::fmt::Debug;
 use std::net::Ipv4Addr;
+use std::time::Duration;
 use web3::transports::{EventLoopHandle, Http};
 use web3::types::{Index, Log, SignedTransaction, TransactionReceipt, H2048, U256};
 
@@ -41,13 +44,45 @@ pub fn make_meaningless_seed() -> Seed {
 
 pub fn make_blockchain_interface_web3(port: u16) -> BlockchainInterfaceWeb3 {
     let chain = Chain::PolyMainnet;
-    let (event_loop_handle, transport) = Http::with_max_parallel(
-        &format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port),
-        REQUESTS_IN_PARALLEL,
+    let (event_loop_handle, transport) =
+        Http::with_max_parallel(&blockchain_service_url(port), REQUESTS_IN_PARALLEL).unwrap();
+
+    BlockchainInterfaceWeb3::new(
+        transport,
+        event_loop_handle,
+        chain,
+        vec![],
+        0,
+        1,
+        Duration::from_secs(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS),
+        None,
+        false,
+        0,
+        None,
     )
-    .unwrap();
+}
+
+pub fn blockchain_service_url(port: u16) -> String {
+    format!("http://{}:{}", &Ipv4Addr::LOCALHOST, port)
+}
 
-    BlockchainInterfaceWeb3::new(transport, event_loop_handle, chain)
+// Points the Node at a mock blockchain server listening on the given local port
+pub fn make_blockchain_bridge_config_for_port(port: u16, chain: Chain) -> BlockchainBridgeConfig {
+    BlockchainBridgeConfig {
+        blockchain_service_url_opt: Some(blockchain_service_url(port)),
+        chain,
+        gas_price: 1,
+        payment_forwarders: vec![],
+        receivable_confirmation_blocks: 0,
+        receivable_scan_attempts: 1,
+        receivable_scan_records: 0,
+        blockchain_request_timeout_secs: DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS,
+        payment_memo: false,
+        payment_simulation: false,
+        max_batch_size: 0,
+        rpc_daily_quota: 0,
+        dev_faucet: false,
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
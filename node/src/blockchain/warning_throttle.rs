@@ -0,0 +1,158 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+// How long the same warning is kept from being logged again once it has been
+pub const WARNING_REPEAT_INTERVAL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WarningRecord {
+    last_logged: SystemTime,
+    suppressed: usize,
+}
+
+// Collapses a warning that keeps coming up with every scan, as it does with a flaky blockchain
+// service, into one line per interval, which then tells how many times it came up unlogged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarningThrottle {
+    interval: Duration,
+    records: HashMap<String, WarningRecord>,
+}
+
+impl Default for WarningThrottle {
+    fn default() -> Self {
+        Self::new(WARNING_REPEAT_INTERVAL)
+    }
+}
+
+impl WarningThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            records: HashMap::new(),
+        }
+    }
+
+    // The line to log for the warning under the key, or None if it has been logged too recently
+    pub fn admit(&mut self, key: &str, warning: &str, now: SystemTime) -> Option<String> {
+        match self.records.get_mut(key) {
+            Some(record) if Self::elapsed(record.last_logged, now) < self.interval => {
+                record.suppressed += 1;
+                None
+            }
+            Some(record) => {
+                let suppressed = record.suppressed;
+                *record = WarningRecord {
+                    last_logged: now,
+                    suppressed: 0,
+                };
+                Some(self.line(warning, suppressed))
+            }
+            None => {
+                self.records.insert(
+                    key.to_string(),
+                    WarningRecord {
+                        last_logged: now,
+                        suppressed: 0,
+                    },
+                );
+                Some(warning.to_string())
+            }
+        }
+    }
+
+    fn elapsed(since: SystemTime, now: SystemTime) -> Duration {
+        now.duration_since(since).unwrap_or(Duration::ZERO)
+    }
+
+    fn line(&self, warning: &str, suppressed: usize) -> String {
+        if suppressed == 0 {
+            warning.to_string()
+        } else {
+            format!(
+                "{} (repeated {} more times in the last {} s)",
+                warning,
+                suppressed,
+                self.interval.as_secs()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(WARNING_REPEAT_INTERVAL, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn default_throttle_uses_the_standard_interval() {
+        let subject = WarningThrottle::default();
+
+        assert_eq!(subject, WarningThrottle::new(WARNING_REPEAT_INTERVAL))
+    }
+
+    #[test]
+    fn first_warning_is_admitted_as_it_is() {
+        let mut subject = WarningThrottle::new(Duration::from_secs(60));
+
+        let result = subject.admit("key", "Something broke", SystemTime::now());
+
+        assert_eq!(result, Some("Something broke".to_string()))
+    }
+
+    #[test]
+    fn repeated_warning_is_held_back_within_the_interval() {
+        let mut subject = WarningThrottle::new(Duration::from_secs(60));
+        let start = SystemTime::now();
+        subject.admit("key", "Something broke", start);
+
+        let result = subject.admit("key", "Something broke", start + Duration::from_secs(59));
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn repeated_warning_is_admitted_after_the_interval_with_the_count_of_held_back_ones() {
+        let mut subject = WarningThrottle::new(Duration::from_secs(60));
+        let start = SystemTime::now();
+        subject.admit("key", "Something broke", start);
+        subject.admit("key", "Something broke", start + Duration::from_secs(10));
+        subject.admit("key", "Something broke", start + Duration::from_secs(20));
+
+        let result = subject.admit("key", "Something broke", start + Duration::from_secs(60));
+        let next_result = subject.admit("key", "Something broke", start + Duration::from_secs(100));
+
+        assert_eq!(
+            result,
+            Some("Something broke (repeated 2 more times in the last 60 s)".to_string())
+        );
+        assert_eq!(next_result, None)
+    }
+
+    #[test]
+    fn warning_coming_up_again_only_after_the_interval_carries_no_count() {
+        let mut subject = WarningThrottle::new(Duration::from_secs(60));
+        let start = SystemTime::now();
+        subject.admit("key", "Something broke", start);
+
+        let result = subject.admit("key", "Something broke", start + Duration::from_secs(61));
+
+        assert_eq!(result, Some("Something broke".to_string()))
+    }
+
+    #[test]
+    fn warnings_under_different_keys_dont_hold_each_other_back() {
+        let mut subject = WarningThrottle::new(Duration::from_secs(60));
+        let now = SystemTime::now();
+        subject.admit("key 1", "Something broke", now);
+
+        let result = subject.admit("key 2", "Something broke", now);
+
+        assert_eq!(result, Some("Something broke".to_string()))
+    }
+}
@@ -25,13 +25,16 @@ use crate::privilege_drop::{IdWrapper, IdWrapperReal};
 use crate::server_initializer::LoggerInitializerWrapper;
 use crate::stream_handler_pool::StreamHandlerPoolSubs;
 use crate::sub_lib::accountant;
-use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals};
+use crate::sub_lib::accountant::{
+    ConsumingWalletSelection, GasBumpLadder, GasPriceCeiling, IdleSpendingFreeze, PaymentBatching,
+    PaymentThresholds, PaymentWindow, ScanIntervals, SpendRateLimits, UnreachableCreditorHold,
+};
 use crate::sub_lib::blockchain_bridge::BlockchainBridgeConfig;
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde_null::CryptDENull;
 use crate::sub_lib::cryptde_real::CryptDEReal;
 use crate::sub_lib::neighborhood::NodeDescriptor;
-use crate::sub_lib::neighborhood::{NeighborhoodConfig, NeighborhoodMode};
+use crate::sub_lib::neighborhood::{CountryClaimCheck, NeighborhoodConfig, NeighborhoodMode};
 use crate::sub_lib::node_addr::NodeAddr;
 use crate::sub_lib::socket_server::ConfiguredByPrivilege;
 use crate::sub_lib::ui_gateway::UiGatewayConfig;
@@ -42,9 +45,15 @@ use itertools::Itertools;
 use log::LevelFilter;
 use masq_lib::blockchains::chains::Chain;
 use masq_lib::command::StdStreams;
-use masq_lib::constants::DEFAULT_UI_PORT;
+use masq_lib::constants::{
+    DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS, DEFAULT_GAS_RUNWAY_ALERT_SCANS,
+    DEFAULT_MAX_BATCH_SIZE, DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS,
+    DEFAULT_RECEIVABLE_SCAN_ATTEMPTS, DEFAULT_RECEIVABLE_SCAN_RECORDS, DEFAULT_RPC_DAILY_QUOTA,
+    DEFAULT_TOP_UP_HORIZON_HOURS, DEFAULT_UI_PORT,
+};
 use masq_lib::crash_point::CrashPoint;
 use masq_lib::logger::Logger;
+use masq_lib::messages::ExitLocation;
 use masq_lib::multi_config::MultiConfig;
 use masq_lib::shared_schema::ConfiguratorError;
 use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
@@ -337,7 +346,19 @@ pub struct BootstrapperConfig {
     pub dns_servers: Vec<SocketAddr>,
     pub scan_intervals_opt: Option<ScanIntervals>,
     pub suppress_initial_scans: bool,
+    pub scan_pipelining: bool,
     pub when_pending_too_long_sec: u64,
+    pub gas_runway_alert_scans: u64,
+    pub top_up_horizon_hours: u64,
+    pub price_feed_url_opt: Option<String>,
+    pub payment_batching_opt: Option<PaymentBatching>,
+    pub unreachable_creditor_hold_opt: Option<UnreachableCreditorHold>,
+    pub spend_rate_limits_opt: Option<SpendRateLimits>,
+    pub payment_window_opt: Option<PaymentWindow>,
+    pub gas_price_ceiling_opt: Option<GasPriceCeiling>,
+    pub gas_bump_ladder_opt: Option<GasBumpLadder>,
+    pub idle_spending_freeze_opt: Option<IdleSpendingFreeze>,
+    pub check_adjustment_invariants: bool,
     pub crash_point: CrashPoint,
     pub clandestine_discriminator_factories: Vec<Box<dyn DiscriminatorFactory>>,
     pub ui_gateway_config: UiGatewayConfig,
@@ -355,8 +376,14 @@ pub struct BootstrapperConfig {
     pub db_password_opt: Option<String>,
     pub clandestine_port_opt: Option<u16>,
     pub consuming_wallet_opt: Option<Wallet>,
+    pub additional_consuming_wallets: Vec<Wallet>,
+    pub smart_account_owners: HashMap<Wallet, Wallet>,
+    pub consuming_wallet_selection: ConsumingWalletSelection,
     pub earning_wallet: Wallet,
+    pub earning_wallet_rotation: Vec<Wallet>,
     pub neighborhood_config: NeighborhoodConfig,
+    pub country_claim_check: CountryClaimCheck,
+    pub exit_locations: Vec<ExitLocation>,
 }
 
 impl Default for BootstrapperConfig {
@@ -373,6 +400,7 @@ impl BootstrapperConfig {
             dns_servers: vec![],
             scan_intervals_opt: None,
             suppress_initial_scans: false,
+            scan_pipelining: false,
             crash_point: CrashPoint::None,
             clandestine_discriminator_factories: vec![],
             ui_gateway_config: UiGatewayConfig {
@@ -382,6 +410,16 @@ impl BootstrapperConfig {
                 blockchain_service_url_opt: None,
                 chain: TEST_DEFAULT_CHAIN,
                 gas_price: 1,
+                payment_forwarders: vec![],
+                receivable_confirmation_blocks: DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS,
+                receivable_scan_attempts: DEFAULT_RECEIVABLE_SCAN_ATTEMPTS,
+                receivable_scan_records: DEFAULT_RECEIVABLE_SCAN_RECORDS,
+                blockchain_request_timeout_secs: DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS,
+                payment_memo: false,
+                payment_simulation: false,
+                max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+                rpc_daily_quota: DEFAULT_RPC_DAILY_QUOTA,
+                dev_faucet: false,
             },
             port_configurations: HashMap::new(),
             data_directory: PathBuf::new(),
@@ -396,12 +434,29 @@ impl BootstrapperConfig {
             db_password_opt: None,
             clandestine_port_opt: None,
             earning_wallet: accountant::DEFAULT_EARNING_WALLET.clone(),
+            earning_wallet_rotation: vec![],
             consuming_wallet_opt: None,
+            additional_consuming_wallets: vec![],
+            smart_account_owners: HashMap::new(),
+            consuming_wallet_selection: ConsumingWalletSelection::default(),
             neighborhood_config: NeighborhoodConfig {
                 mode: NeighborhoodMode::ZeroHop,
                 min_hops: DEFAULT_MIN_HOPS,
             },
+            country_claim_check: CountryClaimCheck::default(),
+            exit_locations: vec![],
             when_pending_too_long_sec: DEFAULT_PENDING_TOO_LONG_SEC,
+            gas_runway_alert_scans: DEFAULT_GAS_RUNWAY_ALERT_SCANS,
+            top_up_horizon_hours: DEFAULT_TOP_UP_HORIZON_HOURS,
+            price_feed_url_opt: None,
+            payment_batching_opt: None,
+            unreachable_creditor_hold_opt: None,
+            spend_rate_limits_opt: None,
+            payment_window_opt: None,
+            gas_price_ceiling_opt: None,
+            gas_bump_ladder_opt: None,
+            idle_spending_freeze_opt: None,
+            check_adjustment_invariants: cfg!(debug_assertions),
         }
     }
 
@@ -410,15 +465,57 @@ impl BootstrapperConfig {
         self.blockchain_bridge_config.blockchain_service_url_opt = unprivileged
             .blockchain_bridge_config
             .blockchain_service_url_opt;
+        self.blockchain_bridge_config.payment_forwarders =
+            unprivileged.blockchain_bridge_config.payment_forwarders;
+        self.blockchain_bridge_config.receivable_confirmation_blocks = unprivileged
+            .blockchain_bridge_config
+            .receivable_confirmation_blocks;
+        self.blockchain_bridge_config.receivable_scan_attempts = unprivileged
+            .blockchain_bridge_config
+            .receivable_scan_attempts;
+        self.blockchain_bridge_config.receivable_scan_records = unprivileged
+            .blockchain_bridge_config
+            .receivable_scan_records;
+        self.blockchain_bridge_config
+            .blockchain_request_timeout_secs = unprivileged
+            .blockchain_bridge_config
+            .blockchain_request_timeout_secs;
+        self.blockchain_bridge_config.payment_memo =
+            unprivileged.blockchain_bridge_config.payment_memo;
+        self.blockchain_bridge_config.payment_simulation =
+            unprivileged.blockchain_bridge_config.payment_simulation;
+        self.blockchain_bridge_config.max_batch_size =
+            unprivileged.blockchain_bridge_config.max_batch_size;
+        self.blockchain_bridge_config.rpc_daily_quota =
+            unprivileged.blockchain_bridge_config.rpc_daily_quota;
+        self.blockchain_bridge_config.dev_faucet = unprivileged.blockchain_bridge_config.dev_faucet;
         self.clandestine_port_opt = unprivileged.clandestine_port_opt;
         self.neighborhood_config = unprivileged.neighborhood_config;
+        self.country_claim_check = unprivileged.country_claim_check;
+        self.exit_locations = unprivileged.exit_locations;
         self.earning_wallet = unprivileged.earning_wallet;
+        self.earning_wallet_rotation = unprivileged.earning_wallet_rotation;
         self.consuming_wallet_opt = unprivileged.consuming_wallet_opt;
+        self.additional_consuming_wallets = unprivileged.additional_consuming_wallets;
+        self.smart_account_owners = unprivileged.smart_account_owners;
+        self.consuming_wallet_selection = unprivileged.consuming_wallet_selection;
         self.db_password_opt = unprivileged.db_password_opt;
         self.scan_intervals_opt = unprivileged.scan_intervals_opt;
         self.suppress_initial_scans = unprivileged.suppress_initial_scans;
+        self.scan_pipelining = unprivileged.scan_pipelining;
         self.payment_thresholds_opt = unprivileged.payment_thresholds_opt;
         self.when_pending_too_long_sec = unprivileged.when_pending_too_long_sec;
+        self.gas_runway_alert_scans = unprivileged.gas_runway_alert_scans;
+        self.top_up_horizon_hours = unprivileged.top_up_horizon_hours;
+        self.price_feed_url_opt = unprivileged.price_feed_url_opt;
+        self.payment_batching_opt = unprivileged.payment_batching_opt;
+        self.unreachable_creditor_hold_opt = unprivileged.unreachable_creditor_hold_opt;
+        self.spend_rate_limits_opt = unprivileged.spend_rate_limits_opt;
+        self.payment_window_opt = unprivileged.payment_window_opt;
+        self.gas_price_ceiling_opt = unprivileged.gas_price_ceiling_opt;
+        self.gas_bump_ladder_opt = unprivileged.gas_bump_ladder_opt;
+        self.idle_spending_freeze_opt = unprivileged.idle_spending_freeze_opt;
+        self.check_adjustment_invariants = unprivileged.check_adjustment_invariants;
     }
 
     pub fn exit_service_rate(&self) -> u64 {
@@ -733,12 +830,15 @@ mod tests {
     use crate::server_initializer::LoggerInitializerWrapper;
     use crate::stream_handler_pool::StreamHandlerPoolSubs;
     use crate::stream_messages::AddStreamMsg;
-    use crate::sub_lib::accountant::ScanIntervals;
+    use crate::sub_lib::accountant::{
+        ConsumingWalletSelection, GasBumpLadder, GasPriceCeiling, IdleSpendingFreeze,
+        PaymentBatching, PaymentWindow, ScanIntervals, SpendRateLimits, UnreachableCreditorHold,
+    };
     use crate::sub_lib::cryptde::PublicKey;
     use crate::sub_lib::cryptde::{CryptDE, PlainData};
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::neighborhood::{
-        NeighborhoodConfig, NeighborhoodMode, NodeDescriptor, DEFAULT_RATE_PACK,
+        CountryClaimCheck, NeighborhoodConfig, NeighborhoodMode, NodeDescriptor, DEFAULT_RATE_PACK,
     };
     use crate::sub_lib::node_addr::NodeAddr;
     use crate::sub_lib::socket_server::ConfiguredByPrivilege;
@@ -765,6 +865,7 @@ mod tests {
     use masq_lib::blockchains::chains::Chain;
     use masq_lib::logger::Logger;
     use masq_lib::logger::TEST_LOG_RECIPIENT_GUARD;
+    use masq_lib::messages::ExitLocation;
     use masq_lib::test_utils::environment_guard::ClapGuard;
     use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
     use masq_lib::test_utils::logging::{init_test_logging, TestLog, TestLogHandler};
@@ -1235,26 +1336,101 @@ mod tests {
         //values from unprivileged config
         let gas_price = 123;
         let blockchain_url_opt = Some("some.service@earth.abc".to_string());
+        let payment_forwarders = vec![make_wallet("payment forwarder").address()];
         let clandestine_port_opt = Some(44444);
         let neighborhood_config = NeighborhoodConfig {
             mode: NeighborhoodMode::OriginateOnly(vec![], rate_pack(9)),
             min_hops: MIN_HOPS_FOR_TEST,
         };
         let earning_wallet = make_wallet("earning wallet");
+        let earning_wallet_rotation = vec![earning_wallet.clone(), make_wallet("next earning")];
         let consuming_wallet_opt = Some(make_wallet("consuming wallet"));
+        let additional_consuming_wallets = vec![make_wallet("additional consuming wallet")];
+        let smart_account_owners =
+            HashMap::from([(make_wallet("smart account"), make_wallet("owner"))]);
         let db_password_opt = Some("password".to_string());
         unprivileged_config.blockchain_bridge_config.gas_price = gas_price;
         unprivileged_config
             .blockchain_bridge_config
             .blockchain_service_url_opt = blockchain_url_opt.clone();
+        unprivileged_config
+            .blockchain_bridge_config
+            .payment_forwarders = payment_forwarders.clone();
+        unprivileged_config
+            .blockchain_bridge_config
+            .receivable_confirmation_blocks = 24;
+        unprivileged_config
+            .blockchain_bridge_config
+            .receivable_scan_attempts = 7;
+        unprivileged_config
+            .blockchain_bridge_config
+            .receivable_scan_records = 9;
+        unprivileged_config
+            .blockchain_bridge_config
+            .blockchain_request_timeout_secs = 11;
+        unprivileged_config.blockchain_bridge_config.payment_memo = true;
+        unprivileged_config
+            .blockchain_bridge_config
+            .payment_simulation = true;
+        unprivileged_config.blockchain_bridge_config.max_batch_size = 7;
+        unprivileged_config.blockchain_bridge_config.rpc_daily_quota = 5_000;
+        unprivileged_config.blockchain_bridge_config.dev_faucet = true;
         unprivileged_config.clandestine_port_opt = clandestine_port_opt;
         unprivileged_config.neighborhood_config = neighborhood_config.clone();
+        unprivileged_config.country_claim_check = CountryClaimCheck::Enforce;
+        unprivileged_config.exit_locations = vec![ExitLocation {
+            country_codes: vec!["CZ".to_string()],
+            priority: 1,
+        }];
         unprivileged_config.earning_wallet = earning_wallet.clone();
+        unprivileged_config.earning_wallet_rotation = earning_wallet_rotation.clone();
         unprivileged_config.consuming_wallet_opt = consuming_wallet_opt.clone();
+        unprivileged_config.additional_consuming_wallets = additional_consuming_wallets.clone();
+        unprivileged_config.smart_account_owners = smart_account_owners.clone();
+        unprivileged_config.consuming_wallet_selection = ConsumingWalletSelection::HighestBalance;
         unprivileged_config.db_password_opt = db_password_opt.clone();
         unprivileged_config.scan_intervals_opt = Some(ScanIntervals::default());
         unprivileged_config.suppress_initial_scans = false;
+        unprivileged_config.scan_pipelining = true;
         unprivileged_config.when_pending_too_long_sec = DEFAULT_PENDING_TOO_LONG_SEC;
+        unprivileged_config.gas_runway_alert_scans = 17;
+        unprivileged_config.top_up_horizon_hours = 72;
+        unprivileged_config.price_feed_url_opt = Some("https://prices.example.com".to_string());
+        unprivileged_config.payment_batching_opt = Some(PaymentBatching {
+            min_economic_size_gwei: 1_000,
+            batch_target_gwei: 5_000,
+            max_delay_sec: 60,
+        });
+        unprivileged_config.unreachable_creditor_hold_opt = Some(UnreachableCreditorHold {
+            absence_sec: 3_600,
+            deadline_margin_sec: 600,
+        });
+        unprivileged_config.spend_rate_limits_opt = Some(SpendRateLimits {
+            service_fee_per_hour_gwei: 1_000,
+            service_fee_per_day_gwei: 10_000,
+            transaction_fee_per_hour_gwei: 50,
+            transaction_fee_per_day_gwei: 300,
+        });
+        unprivileged_config.payment_window_opt = Some(PaymentWindow {
+            start_hour: 2,
+            end_hour: 6,
+            low_gas_price_gwei: 1,
+            deadline_margin_sec: 3_600,
+        });
+        unprivileged_config.gas_price_ceiling_opt = Some(GasPriceCeiling {
+            ceiling_gwei: 30,
+            deadline_margin_sec: 7_200,
+        });
+        unprivileged_config.gas_bump_ladder_opt = Some(GasBumpLadder {
+            step_percent: 15,
+            max_steps: 3,
+            interval_sec: 600,
+        });
+        unprivileged_config.idle_spending_freeze_opt = Some(IdleSpendingFreeze {
+            idle_sec: 3_600,
+            deadline_margin_sec: 7_200,
+        });
+        unprivileged_config.check_adjustment_invariants = !cfg!(debug_assertions);
 
         privileged_config.merge_unprivileged(unprivileged_config);
 
@@ -1269,20 +1445,156 @@ mod tests {
                 .blockchain_service_url_opt,
             blockchain_url_opt
         );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .payment_forwarders,
+            payment_forwarders
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .receivable_confirmation_blocks,
+            24
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .receivable_scan_attempts,
+            7
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .receivable_scan_records,
+            9
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .blockchain_request_timeout_secs,
+            11
+        );
+        assert_eq!(
+            privileged_config.blockchain_bridge_config.payment_memo,
+            true
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .payment_simulation,
+            true
+        );
+        assert_eq!(privileged_config.blockchain_bridge_config.max_batch_size, 7);
+        assert_eq!(
+            privileged_config.blockchain_bridge_config.rpc_daily_quota,
+            5_000
+        );
+        assert_eq!(privileged_config.blockchain_bridge_config.dev_faucet, true);
         assert_eq!(privileged_config.clandestine_port_opt, clandestine_port_opt);
         assert_eq!(privileged_config.neighborhood_config, neighborhood_config);
+        assert_eq!(
+            privileged_config.country_claim_check,
+            CountryClaimCheck::Enforce
+        );
+        assert_eq!(
+            privileged_config.exit_locations,
+            vec![ExitLocation {
+                country_codes: vec!["CZ".to_string()],
+                priority: 1,
+            }]
+        );
         assert_eq!(privileged_config.earning_wallet, earning_wallet);
+        assert_eq!(
+            privileged_config.earning_wallet_rotation,
+            earning_wallet_rotation
+        );
         assert_eq!(privileged_config.consuming_wallet_opt, consuming_wallet_opt);
+        assert_eq!(
+            privileged_config.additional_consuming_wallets,
+            additional_consuming_wallets
+        );
+        assert_eq!(privileged_config.smart_account_owners, smart_account_owners);
+        assert_eq!(
+            privileged_config.consuming_wallet_selection,
+            ConsumingWalletSelection::HighestBalance
+        );
         assert_eq!(privileged_config.db_password_opt, db_password_opt);
         assert_eq!(
             privileged_config.scan_intervals_opt,
             Some(ScanIntervals::default())
         );
         assert_eq!(privileged_config.suppress_initial_scans, false);
+        assert_eq!(privileged_config.scan_pipelining, true);
         assert_eq!(
             privileged_config.when_pending_too_long_sec,
             DEFAULT_PENDING_TOO_LONG_SEC
         );
+        assert_eq!(privileged_config.gas_runway_alert_scans, 17);
+        assert_eq!(privileged_config.top_up_horizon_hours, 72);
+        assert_eq!(
+            privileged_config.price_feed_url_opt,
+            Some("https://prices.example.com".to_string())
+        );
+        assert_eq!(
+            privileged_config.payment_batching_opt,
+            Some(PaymentBatching {
+                min_economic_size_gwei: 1_000,
+                batch_target_gwei: 5_000,
+                max_delay_sec: 60,
+            })
+        );
+        assert_eq!(
+            privileged_config.unreachable_creditor_hold_opt,
+            Some(UnreachableCreditorHold {
+                absence_sec: 3_600,
+                deadline_margin_sec: 600,
+            })
+        );
+        assert_eq!(
+            privileged_config.spend_rate_limits_opt,
+            Some(SpendRateLimits {
+                service_fee_per_hour_gwei: 1_000,
+                service_fee_per_day_gwei: 10_000,
+                transaction_fee_per_hour_gwei: 50,
+                transaction_fee_per_day_gwei: 300,
+            })
+        );
+        assert_eq!(
+            privileged_config.payment_window_opt,
+            Some(PaymentWindow {
+                start_hour: 2,
+                end_hour: 6,
+                low_gas_price_gwei: 1,
+                deadline_margin_sec: 3_600,
+            })
+        );
+        assert_eq!(
+            privileged_config.gas_price_ceiling_opt,
+            Some(GasPriceCeiling {
+                ceiling_gwei: 30,
+                deadline_margin_sec: 7_200,
+            })
+        );
+        assert_eq!(
+            privileged_config.gas_bump_ladder_opt,
+            Some(GasBumpLadder {
+                step_percent: 15,
+                max_steps: 3,
+                interval_sec: 600,
+            })
+        );
+        assert_eq!(
+            privileged_config.idle_spending_freeze_opt,
+            Some(IdleSpendingFreeze {
+                idle_sec: 3_600,
+                deadline_margin_sec: 7_200,
+            })
+        );
+        assert_eq!(
+            privileged_config.check_adjustment_invariants,
+            !cfg!(debug_assertions)
+        );
         //some values from the privileged config
         assert_eq!(privileged_config.log_level, Off);
         assert_eq!(
@@ -136,8 +136,16 @@ impl DbInitializerReal {
         Self::initialize_config(conn, external_params);
         Self::create_payable_table(conn);
         Self::create_pending_payable_table(conn);
+        Self::create_pending_payable_share_table(conn);
         Self::create_receivable_table(conn);
         Self::create_banned_table(conn);
+        Self::create_creditor_thresholds_table(conn);
+        Self::create_received_payment_table(conn);
+        Self::create_payable_write_off_table(conn);
+        Self::create_invoice_dispute_table(conn);
+        Self::create_outbound_instructions_table(conn);
+        Self::create_outbound_payment_table(conn);
+        Self::create_rpc_usage_table(conn);
     }
 
     pub fn create_config_table(conn: &Connection) {
@@ -188,6 +196,13 @@ impl DbInitializerReal {
             false,
             "earning wallet address",
         );
+        Self::set_config_value(
+            conn,
+            "earning_wallet_rotation",
+            None,
+            false,
+            "earning wallet rotation",
+        );
         Self::set_config_value(
             conn,
             "neighborhood_mode",
@@ -267,7 +282,10 @@ impl DbInitializerReal {
                     amount_low_b integer not null,
                     payable_timestamp integer not null,
                     attempt integer not null,
-                    process_error text null
+                    process_error text null,
+                    nonce integer null,
+                    gas_price_wei integer null,
+                    chain text not null
             )",
             [],
         )
@@ -279,31 +297,65 @@ impl DbInitializerReal {
         .expect("Can't create transaction hash index in pending payments");
     }
 
+    pub fn create_pending_payable_share_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists pending_payable_share (
+                    rowid integer primary key,
+                    pending_payable_rowid integer not null,
+                    wallet_address text not null,
+                    amount_high_b integer not null,
+                    amount_low_b integer not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create pending_payable_share table");
+        conn.execute(
+            "create unique index if not exists pending_payable_share_rowid_wallet_idx \
+             on pending_payable_share (pending_payable_rowid, wallet_address)",
+            [],
+        )
+        .expect("Can't create fingerprint and wallet index in pending payable shares");
+    }
+
     pub fn create_payable_table(conn: &Connection) {
         conn.execute(
             "create table if not exists payable (
-                    wallet_address text primary key,
+                    wallet_address text not null,
                     balance_high_b integer not null,
                     balance_low_b integer not null,
                     last_paid_timestamp integer not null,
-                    pending_payable_rowid integer null
+                    pending_payable_rowid integer null,
+                    chain text not null
             ) strict",
             [],
         )
         .expect("Can't create payable table");
+        conn.execute(
+            "create unique index if not exists payable_wallet_address_chain_idx \
+             on payable (wallet_address, chain)",
+            [],
+        )
+        .expect("Can't create wallet address and chain index in payable");
     }
 
     pub fn create_receivable_table(conn: &Connection) {
         conn.execute(
             "create table if not exists receivable (
-                    wallet_address text primary key,
+                    wallet_address text not null,
                     balance_high_b integer not null,
                     balance_low_b integer not null,
-                    last_received_timestamp integer not null
+                    last_received_timestamp integer not null,
+                    chain text not null
             ) strict",
             [],
         )
         .expect("Can't create receivable table");
+        conn.execute(
+            "create unique index if not exists receivable_wallet_address_chain_idx \
+             on receivable (wallet_address, chain)",
+            [],
+        )
+        .expect("Can't create wallet address and chain index in receivable");
     }
 
     pub fn create_banned_table(conn: &Connection) {
@@ -314,6 +366,121 @@ impl DbInitializerReal {
         .expect("Can't create banned table");
     }
 
+    pub fn create_creditor_thresholds_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists creditor_thresholds (
+                    wallet_address text primary key,
+                    debt_threshold_gwei integer not null,
+                    maturity_threshold_sec integer not null,
+                    payment_grace_period_sec integer not null,
+                    permanent_debt_allowed_gwei integer not null,
+                    threshold_interval_sec integer not null,
+                    unban_below_gwei integer not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create creditor_thresholds table");
+    }
+
+    pub fn create_received_payment_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists received_payment (
+                    block_number integer not null,
+                    wallet_address text not null,
+                    amount_high_b integer not null,
+                    amount_low_b integer not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create received_payment table");
+    }
+
+    pub fn create_payable_write_off_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists payable_write_off (
+                    rowid integer primary key,
+                    wallet_address text not null,
+                    balance_high_b integer not null,
+                    balance_low_b integer not null,
+                    reason text not null,
+                    banned integer not null,
+                    timestamp integer not null,
+                    chain text not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create payable_write_off table");
+    }
+
+    pub fn create_invoice_dispute_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists invoice_dispute (
+                    rowid integer primary key,
+                    creditor_address text not null,
+                    period_start integer not null,
+                    period_end integer not null,
+                    invoiced_services integer not null,
+                    booked_services integer not null,
+                    invoiced_high_b integer not null,
+                    invoiced_low_b integer not null,
+                    booked_high_b integer not null,
+                    booked_low_b integer not null,
+                    received_at integer not null,
+                    chain text not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create invoice_dispute table");
+    }
+
+    pub fn create_outbound_instructions_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists outbound_instructions (
+                    rowid integer primary key,
+                    timestamp integer not null,
+                    agent_snapshot text not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create outbound_instructions table");
+    }
+
+    pub fn create_outbound_payment_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists outbound_payment (
+                    rowid integer primary key,
+                    instructions_rowid integer not null,
+                    wallet_address text not null,
+                    amount_high_b integer not null,
+                    amount_low_b integer not null,
+                    transaction_hash text null,
+                    status text not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create outbound_payment table");
+    }
+
+    pub fn create_rpc_usage_table(conn: &Connection) {
+        conn.execute(
+            "create table if not exists rpc_usage (
+                    rowid integer primary key,
+                    endpoint text not null,
+                    day text not null,
+                    calls integer not null,
+                    weight integer not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create rpc_usage table");
+        conn.execute(
+            "create unique index if not exists rpc_usage_endpoint_day_idx \
+             on rpc_usage (endpoint, day)",
+            [],
+        )
+        .expect("Can't create endpoint and day index in rpc usage");
+    }
+
     fn extra_configuration(
         conn: &Connection,
         init_config: &DbInitializationConfig,
@@ -652,7 +819,7 @@ mod tests {
     #[test]
     fn constants_have_correct_values() {
         assert_eq!(DATABASE_FILE, "node-data.db");
-        assert_eq!(CURRENT_SCHEMA_VERSION, 10);
+        assert_eq!(CURRENT_SCHEMA_VERSION, 23);
     }
 
     #[test]
@@ -692,7 +859,7 @@ mod tests {
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
 
-        let mut stmt = conn.prepare("select rowid, transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error from pending_payable").unwrap();
+        let mut stmt = conn.prepare("select rowid, transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error, nonce, gas_price_wei, chain from pending_payable").unwrap();
         let mut payable_contents = stmt.query_map([], |_| Ok(42)).unwrap();
         assert!(payable_contents.next().is_none());
         let expected_key_words: &[&[&str]] = &[
@@ -703,6 +870,9 @@ mod tests {
             &["payable_timestamp", "integer", "not", "null"],
             &["attempt", "integer", "not", "null"],
             &["process_error", "text", "null"],
+            &["nonce", "integer", "null"],
+            &["gas_price_wei", "integer", "null"],
+            &["chain", "text", "not", "null"],
         ];
         assert_create_table_stm_contains_all_parts(&*conn, "pending_payable", expected_key_words);
         let expected_key_words: &[&[&str]] = &[&["transaction_hash"]];
@@ -713,6 +883,44 @@ mod tests {
         )
     }
 
+    #[test]
+    fn db_initialize_creates_pending_payable_share_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_pending_payable_share_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select rowid, pending_payable_rowid, wallet_address, amount_high_b, amount_low_b from pending_payable_share")
+            .unwrap();
+        let mut share_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(share_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "pending_payable_share");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["pending_payable_rowid", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "pending_payable_share",
+            expected_key_words,
+        );
+        let expected_key_words: &[&[&str]] = &[&["pending_payable_rowid"], &["wallet_address"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "pending_payable_share_rowid_wallet_idx",
+            expected_key_words,
+        )
+    }
+
     #[test]
     fn db_initialize_creates_payable_table() {
         let home_dir = ensure_node_home_directory_does_not_exist(
@@ -725,19 +933,25 @@ mod tests {
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
 
-        let mut stmt = conn.prepare ("select wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid from payable").unwrap ();
+        let mut stmt = conn.prepare ("select wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid, chain from payable").unwrap ();
         let mut payable_contents = stmt.query_map([], |_| Ok(42)).unwrap();
         assert!(payable_contents.next().is_none());
         assert_table_created_as_strict(&*conn, "payable");
         let expected_key_words: &[&[&str]] = &[
-            &["wallet_address", "text", "primary", "key"],
+            &["wallet_address", "text", "not", "null"],
             &["balance_high_b", "integer", "not", "null"],
             &["balance_low_b", "integer", "not", "null"],
             &["last_paid_timestamp", "integer", "not", "null"],
             &["pending_payable_rowid", "integer", "null"],
+            &["chain", "text", "not", "null"],
         ];
         assert_create_table_stm_contains_all_parts(&*conn, "payable", expected_key_words);
-        assert_no_index_exists_for_table(conn.as_ref(), "payable")
+        let expected_key_words: &[&[&str]] = &[&["wallet_address"], &["chain"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "payable_wallet_address_chain_idx",
+            expected_key_words,
+        )
     }
 
     #[test]
@@ -753,19 +967,25 @@ mod tests {
             .unwrap();
 
         let mut stmt = conn
-            .prepare("select wallet_address, balance_high_b, balance_low_b, last_received_timestamp from receivable")
+            .prepare("select wallet_address, balance_high_b, balance_low_b, last_received_timestamp, chain from receivable")
             .unwrap();
         let mut receivable_contents = stmt.query_map([], |_| Ok(())).unwrap();
         assert!(receivable_contents.next().is_none());
         assert_table_created_as_strict(&*conn, "receivable");
         let expected_key_words: &[&[&str]] = &[
-            &["wallet_address", "text", "primary", "key"],
+            &["wallet_address", "text", "not", "null"],
             &["balance_high_b", "integer", "not", "null"],
             &["balance_low_b", "integer", "not", "null"],
             &["last_received_timestamp", "integer", "not", "null"],
+            &["chain", "text", "not", "null"],
         ];
         assert_create_table_stm_contains_all_parts(conn.as_ref(), "receivable", expected_key_words);
-        assert_no_index_exists_for_table(conn.as_ref(), "receivable")
+        let expected_key_words: &[&[&str]] = &[&["wallet_address"], &["chain"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "receivable_wallet_address_chain_idx",
+            expected_key_words,
+        )
     }
 
     #[test]
@@ -789,6 +1009,229 @@ mod tests {
         assert_no_index_exists_for_table(conn.as_ref(), "banned")
     }
 
+    #[test]
+    fn db_initialize_creates_creditor_thresholds_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_creditor_thresholds_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select wallet_address, debt_threshold_gwei, maturity_threshold_sec, payment_grace_period_sec, permanent_debt_allowed_gwei, threshold_interval_sec, unban_below_gwei from creditor_thresholds")
+            .unwrap();
+        let mut creditor_thresholds_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(creditor_thresholds_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "creditor_thresholds");
+        let expected_key_words: &[&[&str]] = &[
+            &["wallet_address", "text", "primary", "key"],
+            &["debt_threshold_gwei", "integer", "not", "null"],
+            &["maturity_threshold_sec", "integer", "not", "null"],
+            &["payment_grace_period_sec", "integer", "not", "null"],
+            &["permanent_debt_allowed_gwei", "integer", "not", "null"],
+            &["threshold_interval_sec", "integer", "not", "null"],
+            &["unban_below_gwei", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "creditor_thresholds",
+            expected_key_words,
+        );
+    }
+
+    #[test]
+    fn db_initialize_creates_received_payment_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_received_payment_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select block_number, wallet_address, amount_high_b, amount_low_b from received_payment")
+            .unwrap();
+        let mut received_payment_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(received_payment_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "received_payment");
+        let expected_key_words: &[&[&str]] = &[
+            &["block_number", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "received_payment",
+            expected_key_words,
+        );
+    }
+
+    #[test]
+    fn db_initialize_creates_payable_write_off_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_payable_write_off_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select rowid, wallet_address, balance_high_b, balance_low_b, reason, banned, timestamp, chain from payable_write_off")
+            .unwrap();
+        let mut write_off_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(write_off_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "payable_write_off");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["wallet_address", "text", "not", "null"],
+            &["balance_high_b", "integer", "not", "null"],
+            &["balance_low_b", "integer", "not", "null"],
+            &["reason", "text", "not", "null"],
+            &["banned", "integer", "not", "null"],
+            &["timestamp", "integer", "not", "null"],
+            &["chain", "text", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "payable_write_off",
+            expected_key_words,
+        );
+    }
+
+    #[test]
+    fn db_initialize_creates_invoice_dispute_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_invoice_dispute_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select rowid, creditor_address, period_start, period_end, invoiced_services, booked_services, invoiced_high_b, invoiced_low_b, booked_high_b, booked_low_b, received_at, chain from invoice_dispute")
+            .unwrap();
+        let mut dispute_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(dispute_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "invoice_dispute");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["creditor_address", "text", "not", "null"],
+            &["period_start", "integer", "not", "null"],
+            &["period_end", "integer", "not", "null"],
+            &["invoiced_services", "integer", "not", "null"],
+            &["booked_services", "integer", "not", "null"],
+            &["invoiced_high_b", "integer", "not", "null"],
+            &["invoiced_low_b", "integer", "not", "null"],
+            &["booked_high_b", "integer", "not", "null"],
+            &["booked_low_b", "integer", "not", "null"],
+            &["received_at", "integer", "not", "null"],
+            &["chain", "text", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "invoice_dispute",
+            expected_key_words,
+        );
+    }
+
+    #[test]
+    fn db_initialize_creates_outbound_payment_queue_tables() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_outbound_payment_queue_tables",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select rowid, timestamp, agent_snapshot from outbound_instructions")
+            .unwrap();
+        let mut instructions_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(instructions_contents.next().is_none());
+        let mut stmt = conn
+            .prepare("select rowid, instructions_rowid, wallet_address, amount_high_b, amount_low_b, transaction_hash, status from outbound_payment")
+            .unwrap();
+        let mut payment_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(payment_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "outbound_instructions");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["timestamp", "integer", "not", "null"],
+            &["agent_snapshot", "text", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "outbound_instructions",
+            expected_key_words,
+        );
+        assert_table_created_as_strict(&*conn, "outbound_payment");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["instructions_rowid", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+            &["transaction_hash", "text", "null"],
+            &["status", "text", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "outbound_payment",
+            expected_key_words,
+        );
+    }
+
+    #[test]
+    fn db_initialize_creates_rpc_usage_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_rpc_usage_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select rowid, endpoint, day, calls, weight from rpc_usage")
+            .unwrap();
+        let mut usage_contents = stmt.query_map([], |_| Ok(42)).unwrap();
+        assert!(usage_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "rpc_usage");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["endpoint", "text", "not", "null"],
+            &["day", "text", "not", "null"],
+            &["calls", "integer", "not", "null"],
+            &["weight", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(conn.as_ref(), "rpc_usage", expected_key_words);
+        let expected_key_words: &[&[&str]] = &[&["endpoint"], &["day"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "rpc_usage_endpoint_day_idx",
+            expected_key_words,
+        )
+    }
+
     #[test]
     #[should_panic(expected = "The database undoubtedly exists, but: unable to open database file")]
     fn double_check_the_result_of_db_migration_panics_if_cannot_reestablish_the_connection_to_the_database(
@@ -919,6 +1362,7 @@ mod tests {
         assert!(clandestine_port < 10000);
         verify(&mut config_vec, "consuming_wallet_private_key", None, true);
         verify(&mut config_vec, "earning_wallet_address", None, false);
+        verify(&mut config_vec, "earning_wallet_rotation", None, false);
         verify(&mut config_vec, EXAMPLE_ENCRYPTED, None, true);
         verify(
             &mut config_vec,
@@ -2,7 +2,20 @@
 
 use crate::database::db_initializer::ExternalData;
 use crate::database::db_migrations::migrations::migration_0_to_1::Migrate_0_to_1;
+use crate::database::db_migrations::migrations::migration_10_to_11::Migrate_10_to_11;
+use crate::database::db_migrations::migrations::migration_11_to_12::Migrate_11_to_12;
+use crate::database::db_migrations::migrations::migration_12_to_13::Migrate_12_to_13;
+use crate::database::db_migrations::migrations::migration_13_to_14::Migrate_13_to_14;
+use crate::database::db_migrations::migrations::migration_14_to_15::Migrate_14_to_15;
+use crate::database::db_migrations::migrations::migration_15_to_16::Migrate_15_to_16;
+use crate::database::db_migrations::migrations::migration_16_to_17::Migrate_16_to_17;
+use crate::database::db_migrations::migrations::migration_17_to_18::Migrate_17_to_18;
+use crate::database::db_migrations::migrations::migration_18_to_19::Migrate_18_to_19;
+use crate::database::db_migrations::migrations::migration_19_to_20::Migrate_19_to_20;
 use crate::database::db_migrations::migrations::migration_1_to_2::Migrate_1_to_2;
+use crate::database::db_migrations::migrations::migration_20_to_21::Migrate_20_to_21;
+use crate::database::db_migrations::migrations::migration_21_to_22::Migrate_21_to_22;
+use crate::database::db_migrations::migrations::migration_22_to_23::Migrate_22_to_23;
 use crate::database::db_migrations::migrations::migration_2_to_3::Migrate_2_to_3;
 use crate::database::db_migrations::migrations::migration_3_to_4::Migrate_3_to_4;
 use crate::database::db_migrations::migrations::migration_4_to_5::Migrate_4_to_5;
@@ -80,6 +93,19 @@ impl DbMigratorReal {
             &Migrate_7_to_8,
             &Migrate_8_to_9,
             &Migrate_9_to_10,
+            &Migrate_10_to_11,
+            &Migrate_11_to_12,
+            &Migrate_12_to_13,
+            &Migrate_13_to_14,
+            &Migrate_14_to_15,
+            &Migrate_15_to_16,
+            &Migrate_16_to_17,
+            &Migrate_17_to_18,
+            &Migrate_18_to_19,
+            &Migrate_19_to_20,
+            &Migrate_20_to_21,
+            &Migrate_21_to_22,
+            &Migrate_22_to_23,
         ]
     }
 
@@ -0,0 +1,86 @@
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_13_to_14;
+
+impl DatabaseMigration for Migrate_13_to_14 {
+    fn migrate<'a>(
+        &self,
+        declaration_utils: Box<dyn DBMigDeclarator + 'a>,
+    ) -> rusqlite::Result<()> {
+        let sql_statement = "create table if not exists received_payment (
+                    block_number integer not null,
+                    wallet_address text not null,
+                    amount_high_b integer not null,
+                    amount_low_b integer not null
+            ) strict";
+
+        declaration_utils.execute_upon_transaction(&[&sql_statement])
+    }
+
+    fn old_version(&self) -> usize {
+        13
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_create_table_stm_contains_all_parts, assert_table_created_as_strict,
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::fs::create_dir_all;
+
+    #[test]
+    fn migration_from_13_to_14_is_properly_set() {
+        init_test_logging();
+        let dir_path = ensure_node_home_directory_exists(
+            "db_migrations",
+            "migration_from_13_to_14_is_properly_set",
+        );
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            13,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        assert!(result.is_ok());
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            14,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        assert_table_created_as_strict(connection.as_ref(), "received_payment");
+        let expected_key_words: &[&[&str]] = &[
+            &["block_number", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            connection.as_ref(),
+            "received_payment",
+            expected_key_words,
+        );
+        let (cs_value, cs_encrypted) = retrieve_config_row(connection.as_ref(), "schema_version");
+        assert_eq!(cs_value, Some(14.to_string()));
+        assert_eq!(cs_encrypted, false);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            "DbMigrator: Database successfully migrated from version 13 to 14",
+        ]);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_14_to_15;
+
+impl DatabaseMigration for Migrate_14_to_15 {
+    fn migrate<'a>(
+        &self,
+        declaration_utils: Box<dyn DBMigDeclarator + 'a>,
+    ) -> rusqlite::Result<()> {
+        // Fingerprints recorded before this version stay without them and can't be replaced
+        let statement_1 = "alter table pending_payable add nonce integer null";
+        let statement_2 = "alter table pending_payable add gas_price_wei integer null";
+
+        declaration_utils.execute_upon_transaction(&[&statement_1, &statement_2])
+    }
+
+    fn old_version(&self) -> usize {
+        14
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::ToSql;
+    use std::fs::create_dir_all;
+
+    #[test]
+    fn migration_from_14_to_15_is_properly_set() {
+        init_test_logging();
+        let dir_path = ensure_node_home_directory_exists(
+            "db_migrations",
+            "migration_from_14_to_15_is_properly_set",
+        );
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        let connection = subject
+            .initialize_to_version(
+                &dir_path,
+                14,
+                DbInitializationConfig::create_or_migrate(make_external_data()),
+            )
+            .unwrap();
+        connection
+            .prepare(
+                "insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, \
+                 payable_timestamp, attempt, process_error) values ('0x1234', 0, 5000, 1500000000, \
+                 1, null)",
+            )
+            .unwrap()
+            .execute([])
+            .unwrap();
+        drop(connection);
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            15,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        let old_fingerprint_values = connection
+            .prepare("select nonce, gas_price_wei from pending_payable")
+            .unwrap()
+            .query_row([], |row| {
+                Ok((
+                    row.get::<usize, Option<i64>>(0).unwrap(),
+                    row.get::<usize, Option<i64>>(1).unwrap(),
+                ))
+            })
+            .unwrap();
+        assert_eq!(old_fingerprint_values, (None, None));
+        let params: &[&dyn ToSql] = &[&5_i64, &1_000_000_000_i64];
+        connection
+            .prepare(
+                "insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, \
+                 payable_timestamp, attempt, process_error, nonce, gas_price_wei) values \
+                 ('0x5678', 0, 6000, 1500000000, 1, null, ?, ?)",
+            )
+            .unwrap()
+            .execute(params)
+            .unwrap();
+        let (cs_value, cs_encrypted) = retrieve_config_row(connection.as_ref(), "schema_version");
+        assert_eq!(cs_value, Some(15.to_string()));
+        assert_eq!(cs_encrypted, false);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            "DbMigrator: Database successfully migrated from version 14 to 15",
+        ]);
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::{DBMigDeclarator, StatementObject};
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_15_to_16;
+
+impl DatabaseMigration for Migrate_15_to_16 {
+    fn migrate<'a>(
+        &self,
+        declaration_utils: Box<dyn DBMigDeclarator + 'a>,
+    ) -> rusqlite::Result<()> {
+        // Existing rows can only have been written for the chain the database was made for
+        let chain_name = declaration_utils
+            .external_parameters()
+            .chain
+            .rec()
+            .literal_identifier;
+        // A wallet can keep an account on every chain, so the chain becomes a part of the key
+        let payable_statements = rekey_with_chain(
+            "payable",
+            "balance_high_b integer not null,
+                    balance_low_b integer not null,
+                    last_paid_timestamp integer not null,
+                    pending_payable_rowid integer null,",
+            "balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid",
+            chain_name,
+        );
+        let receivable_statements = rekey_with_chain(
+            "receivable",
+            "balance_high_b integer not null,
+                    balance_low_b integer not null,
+                    last_received_timestamp integer not null,",
+            "balance_high_b, balance_low_b, last_received_timestamp",
+            chain_name,
+        );
+        let pending_payable_statement = format!(
+            "alter table pending_payable add chain text not null default '{}'",
+            chain_name
+        );
+        let statements = payable_statements
+            .into_iter()
+            .chain(receivable_statements)
+            .chain(std::iter::once(pending_payable_statement))
+            .collect::<Vec<String>>();
+
+        declaration_utils.execute_upon_transaction(
+            &statements
+                .iter()
+                .map(|statement| statement as &dyn StatementObject)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn old_version(&self) -> usize {
+        15
+    }
+}
+
+fn rekey_with_chain(
+    table: &str,
+    other_column_definitions: &str,
+    other_columns: &str,
+    chain_name: &str,
+) -> Vec<String> {
+    vec![
+        format!("alter table {table} rename to _{table}_old"),
+        format!(
+            "create table {table} (
+                    wallet_address text not null,
+                    {other_column_definitions}
+                    chain text not null
+            ) strict"
+        ),
+        format!(
+            "insert into {table} (rowid, wallet_address, {other_columns}, chain) \
+             select rowid, wallet_address, {other_columns}, '{chain_name}' from _{table}_old"
+        ),
+        format!("drop table _{table}_old"),
+        format!(
+            "create unique index {table}_wallet_address_chain_idx on {table} (wallet_address, chain)"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_index_stm_is_coupled_with_right_parameter, assert_table_created_as_strict,
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+    use std::fs::create_dir_all;
+
+    #[test]
+    fn migration_from_15_to_16_is_properly_set() {
+        init_test_logging();
+        let dir_path = ensure_node_home_directory_exists(
+            "db_migrations",
+            "migration_from_15_to_16_is_properly_set",
+        );
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        let connection = subject
+            .initialize_to_version(
+                &dir_path,
+                15,
+                DbInitializationConfig::create_or_migrate(make_external_data()),
+            )
+            .unwrap();
+        [
+            "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+             last_paid_timestamp, pending_payable_rowid) values ('0x1111', 0, 5000, 1500000000, \
+             null)",
+            "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+             last_received_timestamp) values ('0x2222', 0, 6000, 1500000000)",
+            "insert into pending_payable (transaction_hash, amount_high_b, amount_low_b, \
+             payable_timestamp, attempt, process_error, nonce, gas_price_wei) values ('0x3333', \
+             0, 7000, 1500000000, 1, null, 4, 1000000000)",
+        ]
+        .into_iter()
+        .for_each(|stm| {
+            connection.prepare(stm).unwrap().execute([]).unwrap();
+        });
+        drop(connection);
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            16,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        let chains_of_old_rows = ["payable", "receivable", "pending_payable"]
+            .into_iter()
+            .map(|table| {
+                connection
+                    .prepare(&format!("select chain from {}", table))
+                    .unwrap()
+                    .query_row([], |row| row.get::<usize, String>(0))
+                    .unwrap()
+            })
+            .collect::<Vec<String>>();
+        let expected_chain = TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string();
+        assert_eq!(chains_of_old_rows, vec![expected_chain; 3]);
+        let old_payable = connection
+            .prepare(
+                "select rowid, wallet_address, balance_low_b, last_paid_timestamp, \
+                 pending_payable_rowid from payable",
+            )
+            .unwrap()
+            .query_row([], |row| {
+                Ok((
+                    row.get::<usize, i64>(0)?,
+                    row.get::<usize, String>(1)?,
+                    row.get::<usize, i64>(2)?,
+                    row.get::<usize, i64>(3)?,
+                    row.get::<usize, Option<i64>>(4)?,
+                ))
+            })
+            .unwrap();
+        assert_eq!(
+            old_payable,
+            (1, "0x1111".to_string(), 5000, 1500000000, None)
+        );
+        ["payable", "receivable"].into_iter().for_each(|table| {
+            assert_table_created_as_strict(connection.as_ref(), table);
+            assert_index_stm_is_coupled_with_right_parameter(
+                connection.as_ref(),
+                &format!("{table}_wallet_address_chain_idx"),
+                &[&["wallet_address"], &["chain"]],
+            )
+        });
+        // The same wallets on another chain
+        [
+            "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+             last_paid_timestamp, pending_payable_rowid, chain) values ('0x1111', 0, 9000, \
+             1500000000, null, 'eth-mainnet')",
+            "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+             last_received_timestamp, chain) values ('0x2222', 0, 8000, 1500000000, \
+             'eth-mainnet')",
+        ]
+        .into_iter()
+        .for_each(|stm| {
+            connection.prepare(stm).unwrap().execute([]).unwrap();
+        });
+        let duplicate_result = connection
+            .prepare(&format!(
+                "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+                 last_received_timestamp, chain) values ('0x2222', 0, 8000, 1500000000, '{}')",
+                TEST_DEFAULT_CHAIN.rec().literal_identifier
+            ))
+            .unwrap()
+            .execute([]);
+        assert!(duplicate_result.is_err(), "{:?}", duplicate_result);
+        let (cs_value, cs_encrypted) = retrieve_config_row(connection.as_ref(), "schema_version");
+        assert_eq!(cs_value, Some(16.to_string()));
+        assert_eq!(cs_encrypted, false);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            "DbMigrator: Database successfully migrated from version 15 to 16",
+        ]);
+    }
+}
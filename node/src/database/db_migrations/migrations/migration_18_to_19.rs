@@ -0,0 +1,120 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_18_to_19;
+
+impl DatabaseMigration for Migrate_18_to_19 {
+    fn migrate<'a>(
+        &self,
+        declaration_utils: Box<dyn DBMigDeclarator + 'a>,
+    ) -> rusqlite::Result<()> {
+        // An earning wallet set up before rotation existed makes a rotation of one
+        let statement = "insert into config (name, value, encrypted) \
+             select 'earning_wallet_rotation', value, 0 from config \
+             where name = 'earning_wallet_address'";
+
+        declaration_utils.execute_upon_transaction(&[&statement])
+    }
+
+    fn old_version(&self) -> usize {
+        18
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::fs::create_dir_all;
+
+    #[test]
+    fn migration_from_18_to_19_is_properly_set() {
+        init_test_logging();
+        let dir_path = ensure_node_home_directory_exists(
+            "db_migrations",
+            "migration_from_18_to_19_is_properly_set",
+        );
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let connection = subject
+                .initialize_to_version(
+                    &dir_path,
+                    18,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            connection
+                .prepare(
+                    "update config set value = '0x0000000000000000000000000000000000e4a1e0' \
+                     where name = 'earning_wallet_address'",
+                )
+                .unwrap()
+                .execute([])
+                .unwrap();
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            19,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        let (rotation_value, rotation_encrypted) =
+            retrieve_config_row(connection.as_ref(), "earning_wallet_rotation");
+        assert_eq!(
+            rotation_value,
+            Some("0x0000000000000000000000000000000000e4a1e0".to_string())
+        );
+        assert_eq!(rotation_encrypted, false);
+        let (cs_value, cs_encrypted) = retrieve_config_row(connection.as_ref(), "schema_version");
+        assert_eq!(cs_value, Some(19.to_string()));
+        assert_eq!(cs_encrypted, false);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            "DbMigrator: Database successfully migrated from version 18 to 19",
+        ]);
+    }
+
+    #[test]
+    fn migration_from_18_to_19_leaves_rotation_empty_without_earning_wallet() {
+        let dir_path = ensure_node_home_directory_exists(
+            "db_migrations",
+            "migration_from_18_to_19_leaves_rotation_empty_without_earning_wallet",
+        );
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        subject
+            .initialize_to_version(
+                &dir_path,
+                18,
+                DbInitializationConfig::create_or_migrate(make_external_data()),
+            )
+            .unwrap();
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            19,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        let (rotation_value, rotation_encrypted) =
+            retrieve_config_row(connection.as_ref(), "earning_wallet_rotation");
+        assert_eq!(rotation_value, None);
+        assert_eq!(rotation_encrypted, false);
+    }
+}
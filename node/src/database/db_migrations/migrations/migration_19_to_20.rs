@@ -0,0 +1,104 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_19_to_20;
+
+impl DatabaseMigration for Migrate_19_to_20 {
+    fn migrate<'a>(
+        &self,
+        declaration_utils: Box<dyn DBMigDeclarator + 'a>,
+    ) -> rusqlite::Result<()> {
+        let sql_statement = "create table if not exists invoice_dispute (
+                    rowid integer primary key,
+                    creditor_address text not null,
+                    period_start integer not null,
+                    period_end integer not null,
+                    invoiced_services integer not null,
+                    booked_services integer not null,
+                    invoiced_high_b integer not null,
+                    invoiced_low_b integer not null,
+                    booked_high_b integer not null,
+                    booked_low_b integer not null,
+                    received_at integer not null,
+                    chain text not null
+            ) strict";
+
+        declaration_utils.execute_upon_transaction(&[&sql_statement])
+    }
+
+    fn old_version(&self) -> usize {
+        19
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_create_table_stm_contains_all_parts, assert_table_created_as_strict,
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::fs::create_dir_all;
+
+    #[test]
+    fn migration_from_19_to_20_is_properly_set() {
+        init_test_logging();
+        let dir_path = ensure_node_home_directory_exists(
+            "db_migrations",
+            "migration_from_19_to_20_is_properly_set",
+        );
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            19,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        assert!(result.is_ok());
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            20,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        assert_table_created_as_strict(connection.as_ref(), "invoice_dispute");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["creditor_address", "text", "not", "null"],
+            &["period_start", "integer", "not", "null"],
+            &["period_end", "integer", "not", "null"],
+            &["invoiced_services", "integer", "not", "null"],
+            &["booked_services", "integer", "not", "null"],
+            &["invoiced_high_b", "integer", "not", "null"],
+            &["invoiced_low_b", "integer", "not", "null"],
+            &["booked_high_b", "integer", "not", "null"],
+            &["booked_low_b", "integer", "not", "null"],
+            &["received_at", "integer", "not", "null"],
+            &["chain", "text", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            connection.as_ref(),
+            "invoice_dispute",
+            expected_key_words,
+        );
+        let (cs_value, cs_encrypted) = retrieve_config_row(connection.as_ref(), "schema_version");
+        assert_eq!(cs_value, Some(20.to_string()));
+        assert_eq!(cs_encrypted, false);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            "DbMigrator: Database successfully migrated from version 19 to 20",
+        ]);
+    }
+}
@@ -0,0 +1,438 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::db_access_objects::utils::VigilantRusqliteFlatten;
+use crate::accountant::db_big_integer::big_int_divider::BigIntDivider;
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::{DBMigDeclarator, StatementObject};
+use crate::database::rusqlite_wrappers::TransactionSafeWrapper;
+use rusqlite::ToSql;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_21_to_22;
+
+impl DatabaseMigration for Migrate_21_to_22 {
+    fn migrate<'a>(
+        &self,
+        declaration_utils: Box<dyn DBMigDeclarator + 'a>,
+    ) -> rusqlite::Result<()> {
+        // Addresses differing only in letter case belong to the same account; their rows are
+        // merged first so that lowering the case can't collide with a twin
+        let txn = declaration_utils.transaction();
+        Self::merge_twin_accounts(
+            txn,
+            "payable",
+            "last_paid_timestamp",
+            "pending_payable_rowid",
+        )?;
+        Self::merge_twin_accounts(txn, "receivable", "last_received_timestamp", "null")?;
+        Self::refuse_conflicting_creditor_thresholds(txn)?;
+        let keyed_tables = ["banned", "creditor_thresholds"].into_iter().map(|table| {
+            format!(
+                "delete from {table} where rowid not in \
+                 (select min(rowid) from {table} group by lower(wallet_address))"
+            )
+        });
+        let lowered_tables = [
+            ("payable", "wallet_address"),
+            ("receivable", "wallet_address"),
+            ("banned", "wallet_address"),
+            ("creditor_thresholds", "wallet_address"),
+            ("received_payment", "wallet_address"),
+            ("payable_write_off", "wallet_address"),
+            ("outbound_payment", "wallet_address"),
+            ("invoice_dispute", "creditor_address"),
+        ]
+        .into_iter()
+        .map(|(table, column)| {
+            format!(
+                "update {table} set {column} = lower({column}) where {column} != lower({column})"
+            )
+        });
+        let config = "update config set value = lower(value) \
+             where name in ('earning_wallet_address', 'earning_wallet_rotation') \
+             and value is not null"
+            .to_string();
+        let statements = keyed_tables
+            .chain(lowered_tables)
+            .chain(std::iter::once(config))
+            .collect::<Vec<String>>();
+
+        declaration_utils.execute_upon_transaction(
+            &statements
+                .iter()
+                .map(|statement| statement as &dyn StatementObject)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn old_version(&self) -> usize {
+        21
+    }
+}
+
+impl Migrate_21_to_22 {
+    // The balances add up and the later timestamp wins. Twins awaiting two different transactions
+    // can't be merged without losing track of one of them
+    fn merge_twin_accounts(
+        txn: &TransactionSafeWrapper,
+        table: &str,
+        timestamp_column: &str,
+        pending_payable_rowid_column: &str,
+    ) -> rusqlite::Result<()> {
+        let twins = txn
+            .prepare(&format!(
+                "select lower(wallet_address), chain from {table} \
+                 group by lower(wallet_address), chain having count(*) > 1"
+            ))?
+            .query_map([], |row| {
+                Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?))
+            })?
+            .vigilant_flatten()
+            .collect::<Vec<(String, String)>>();
+        for (wallet, chain) in twins {
+            let rows = txn
+                .prepare(&format!(
+                    "select rowid, balance_high_b, balance_low_b, {timestamp_column}, \
+                     {pending_payable_rowid_column} from {table} \
+                     where lower(wallet_address) = ? and chain = ? order by rowid"
+                ))?
+                .query_map([&wallet, &chain], |row| {
+                    Ok((
+                        row.get::<usize, i64>(0)?,
+                        BigIntDivider::reconstitute(row.get(1)?, row.get(2)?),
+                        row.get::<usize, i64>(3)?,
+                        row.get::<usize, Option<i64>>(4)?,
+                    ))
+                })?
+                .vigilant_flatten()
+                .collect::<Vec<(i64, i128, i64, Option<i64>)>>();
+            let balance = rows
+                .iter()
+                .try_fold(0_i128, |sum, (_, balance, _, _)| sum.checked_add(*balance))
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Migrating Database from 21 to 22: balances of {} in {} on {} overflow",
+                        wallet, table, chain
+                    )
+                });
+            let timestamp = rows.iter().map(|(_, _, timestamp, _)| *timestamp).max();
+            let mut pending_payable_rowids = rows
+                .iter()
+                .flat_map(|(_, _, _, rowid_opt)| *rowid_opt)
+                .collect::<Vec<i64>>();
+            pending_payable_rowids.sort();
+            pending_payable_rowids.dedup();
+            if pending_payable_rowids.len() > 1 {
+                panic!(
+                    "Migrating Database from 21 to 22: {} in {} on {} awaits the transactions \
+                     of fingerprints {:?} under different letter cases",
+                    wallet, table, chain, pending_payable_rowids
+                )
+            }
+            let (kept_rowid, _, _, _) = rows[0];
+            let (balance_high_b, balance_low_b) = BigIntDivider::deconstruct(balance);
+            let update_params: &[&dyn ToSql] =
+                &[&balance_high_b, &balance_low_b, &timestamp, &kept_rowid];
+            txn.execute(
+                &format!(
+                    "update {table} set balance_high_b = ?, balance_low_b = ?, \
+                     {timestamp_column} = ? where rowid = ?"
+                ),
+                update_params,
+            )?;
+            if let Some(pending_payable_rowid) = pending_payable_rowids.first() {
+                let params: &[&dyn ToSql] = &[pending_payable_rowid, &kept_rowid];
+                txn.execute(
+                    "update payable set pending_payable_rowid = ? where rowid = ?",
+                    params,
+                )?;
+            }
+            let delete_params: &[&dyn ToSql] = &[&wallet, &chain, &kept_rowid];
+            txn.execute(
+                &format!(
+                    "delete from {table} where lower(wallet_address) = ? and chain = ? \
+                     and rowid != ?"
+                ),
+                delete_params,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn refuse_conflicting_creditor_thresholds(
+        txn: &TransactionSafeWrapper,
+    ) -> rusqlite::Result<()> {
+        let conflicting = txn
+            .prepare(
+                "select wallet_address from (select distinct \
+                 lower(wallet_address) as wallet_address, debt_threshold_gwei, \
+                 maturity_threshold_sec, payment_grace_period_sec, \
+                 permanent_debt_allowed_gwei, threshold_interval_sec, unban_below_gwei \
+                 from creditor_thresholds) group by wallet_address having count(*) > 1",
+            )?
+            .query_map([], |row| row.get::<usize, String>(0))?
+            .vigilant_flatten()
+            .collect::<Vec<String>>();
+        if !conflicting.is_empty() {
+            panic!(
+                "Migrating Database from 21 to 22: creditor thresholds of {} differ between \
+                 letter cases of the address; remove all but one and start the Node again",
+                conflicting.join(", ")
+            )
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::database::rusqlite_wrappers::ConnectionWrapper;
+    use crate::test_utils::database_utils::{
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::params;
+    use std::fs::create_dir_all;
+    use std::path::PathBuf;
+
+    fn database_of_version_21(test_name: &str) -> (PathBuf, Box<dyn ConnectionWrapper>) {
+        let dir_path = ensure_node_home_directory_exists("db_migrations", test_name);
+        create_dir_all(&dir_path).unwrap();
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let connection = DbInitializerReal::default()
+            .initialize_to_version(
+                &dir_path,
+                21,
+                DbInitializationConfig::create_or_migrate(make_external_data()),
+            )
+            .unwrap();
+        (dir_path, connection)
+    }
+
+    fn insert_creditor_thresholds(conn: &dyn ConnectionWrapper, wallet: &str, debt_gwei: i64) {
+        conn.prepare(
+            "insert into creditor_thresholds (wallet_address, debt_threshold_gwei, \
+             maturity_threshold_sec, payment_grace_period_sec, permanent_debt_allowed_gwei, \
+             threshold_interval_sec, unban_below_gwei) values (?, ?, 1, 2, 3, 4, 5)",
+        )
+        .unwrap()
+        .execute(params![wallet, debt_gwei])
+        .unwrap();
+    }
+
+    #[test]
+    fn migration_from_21_to_22_is_properly_set() {
+        init_test_logging();
+        let (dir_path, connection) =
+            database_of_version_21("migration_from_21_to_22_is_properly_set");
+        let insert_payable = |wallet: &str, balance: i64, timestamp: i64, rowid: Option<i64>| {
+            connection
+                .prepare(
+                    "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+                     last_paid_timestamp, pending_payable_rowid, chain) \
+                     values (?, 0, ?, ?, ?, 'dev')",
+                )
+                .unwrap()
+                .execute(params![wallet, balance, timestamp, rowid])
+                .unwrap();
+        };
+        insert_payable("0xAbCdef0123456789012345678901234567890123", 100, 0, None);
+        insert_payable(
+            "0xB00FA567890123456789012345678901234B00FA",
+            100,
+            0,
+            Some(7),
+        );
+        insert_payable("0xb00fa567890123456789012345678901234b00fa", 250, 5, None);
+        connection
+            .prepare(
+                "insert into payable (wallet_address, balance_high_b, balance_low_b, \
+                 last_paid_timestamp, pending_payable_rowid, chain) \
+                 values (?, 0, 40, 0, null, 'polygon-mainnet')",
+            )
+            .unwrap()
+            .execute(params!["0xb00fa567890123456789012345678901234b00fa"])
+            .unwrap();
+        let insert_receivable = |wallet: &str, balance: i64, timestamp: i64| {
+            connection
+                .prepare(
+                    "insert into receivable (wallet_address, balance_high_b, balance_low_b, \
+                     last_received_timestamp, chain) values (?, 0, ?, ?, 'dev')",
+                )
+                .unwrap()
+                .execute(params![wallet, balance, timestamp])
+                .unwrap();
+        };
+        insert_receivable("0xCAFE567890123456789012345678901234CAFE00", 30, 20);
+        insert_receivable("0xcafe567890123456789012345678901234cafe00", 12, 10);
+        let insert_banned = |wallet: &str| {
+            connection
+                .prepare("insert into banned (wallet_address) values (?)")
+                .unwrap()
+                .execute(params![wallet])
+                .unwrap();
+        };
+        insert_banned("0x0123456789abcDEF0123456789abCDef01234567");
+        insert_banned("0x0123456789ABCDEF0123456789ABCDEF01234567");
+        insert_creditor_thresholds(
+            connection.as_ref(),
+            "0xB00FA567890123456789012345678901234B00FA",
+            1_000,
+        );
+        insert_creditor_thresholds(
+            connection.as_ref(),
+            "0xb00fa567890123456789012345678901234b00fa",
+            1_000,
+        );
+        connection
+            .prepare(
+                "insert into received_payment (block_number, wallet_address, amount_high_b, \
+                 amount_low_b) values (1, ?, 0, 100)",
+            )
+            .unwrap()
+            .execute(params!["0xAbCdef0123456789012345678901234567890123"])
+            .unwrap();
+        connection
+            .prepare("update config set value = ? where name = 'earning_wallet_address'")
+            .unwrap()
+            .execute(params!["0x0123456789abcDEF0123456789abCDef01234567"])
+            .unwrap();
+
+        let result = DbInitializerReal::default().initialize_to_version(
+            &dir_path,
+            22,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let connection = result.unwrap();
+        let read_column = |sql: &str| {
+            let mut stm = connection.prepare(sql).unwrap();
+            stm.query_map([], |row| row.get::<usize, String>(0))
+                .unwrap()
+                .map(|value| value.unwrap())
+                .collect::<Vec<String>>()
+        };
+        let payables = connection
+            .prepare(
+                "select wallet_address, chain, balance_low_b, last_paid_timestamp, \
+                 pending_payable_rowid from payable order by wallet_address, chain",
+            )
+            .unwrap()
+            .query_map([], |row| {
+                Ok((
+                    row.get::<usize, String>(0)?,
+                    row.get::<usize, String>(1)?,
+                    row.get::<usize, i64>(2)?,
+                    row.get::<usize, i64>(3)?,
+                    row.get::<usize, Option<i64>>(4)?,
+                ))
+            })
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            payables,
+            vec![
+                (
+                    "0xabcdef0123456789012345678901234567890123".to_string(),
+                    "dev".to_string(),
+                    100,
+                    0,
+                    None
+                ),
+                (
+                    "0xb00fa567890123456789012345678901234b00fa".to_string(),
+                    "dev".to_string(),
+                    350,
+                    5,
+                    Some(7)
+                ),
+                (
+                    "0xb00fa567890123456789012345678901234b00fa".to_string(),
+                    "polygon-mainnet".to_string(),
+                    40,
+                    0,
+                    None
+                ),
+            ]
+        );
+        let receivables = connection
+            .prepare(
+                "select wallet_address, balance_low_b, last_received_timestamp from receivable",
+            )
+            .unwrap()
+            .query_map([], |row| {
+                Ok((
+                    row.get::<usize, String>(0)?,
+                    row.get::<usize, i64>(1)?,
+                    row.get::<usize, i64>(2)?,
+                ))
+            })
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            receivables,
+            vec![(
+                "0xcafe567890123456789012345678901234cafe00".to_string(),
+                42,
+                20
+            )]
+        );
+        assert_eq!(
+            read_column("select wallet_address from banned"),
+            vec!["0x0123456789abcdef0123456789abcdef01234567".to_string()]
+        );
+        assert_eq!(
+            read_column("select wallet_address from creditor_thresholds"),
+            vec!["0xb00fa567890123456789012345678901234b00fa".to_string()]
+        );
+        assert_eq!(
+            read_column("select wallet_address from received_payment"),
+            vec!["0xabcdef0123456789012345678901234567890123".to_string()]
+        );
+        let (ew_value, ew_encrypted) =
+            retrieve_config_row(connection.as_ref(), "earning_wallet_address");
+        assert_eq!(
+            ew_value,
+            Some("0x0123456789abcdef0123456789abcdef01234567".to_string())
+        );
+        assert_eq!(ew_encrypted, false);
+        let (cs_value, cs_encrypted) = retrieve_config_row(connection.as_ref(), "schema_version");
+        assert_eq!(cs_value, Some(22.to_string()));
+        assert_eq!(cs_encrypted, false);
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            "DbMigrator: Database successfully migrated from version 21 to 22",
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Migrating Database from 21 to 22: creditor thresholds of \
+        0xb00fa567890123456789012345678901234b00fa differ between letter cases of the address")]
+    fn migration_from_21_to_22_refuses_twins_with_different_creditor_thresholds() {
+        let (dir_path, connection) = database_of_version_21(
+            "migration_from_21_to_22_refuses_twins_with_different_creditor_thresholds",
+        );
+        insert_creditor_thresholds(
+            connection.as_ref(),
+            "0xB00FA567890123456789012345678901234B00FA",
+            1_000,
+        );
+        insert_creditor_thresholds(
+            connection.as_ref(),
+            "0xb00fa567890123456789012345678901234b00fa",
+            2_000,
+        );
+
+        let _ = DbInitializerReal::default().initialize_to_version(
+            &dir_path,
+            22,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+    }
+}
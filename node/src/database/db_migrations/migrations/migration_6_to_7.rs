@@ -289,7 +289,7 @@ mod tests {
         query_rows_helper(&*conn, select_sql, |row| {
             assert_eq!(
                 row.get::<usize, Wallet>(0).unwrap(),
-                Wallet::from_str("0xD7d1b2cF58f6500c7CB22fCA42B8512d06813a03").unwrap()
+                Wallet::from_str("0xd7d1b2cf58f6500c7cb22fca42b8512d06813a03").unwrap()
             );
             assert_eq!(row.get::<usize, i64>(1).unwrap(), 6156);
             assert_eq!(row.get::<usize, i64>(2).unwrap(), 5467226021000125952);
@@ -301,7 +301,7 @@ mod tests {
         query_rows_helper(&*conn, select_sql, |row| {
             assert_eq!(
                 row.get::<usize, Wallet>(0).unwrap(),
-                Wallet::from_str("0xD2d1b2eF58f6500c7ae22fCA42B8512d06813a03").unwrap()
+                Wallet::from_str("0xd2d1b2ef58f6500c7ae22fca42b8512d06813a03").unwrap()
             );
             assert_eq!(row.get::<usize, i64>(1).unwrap(), -1);
             assert_eq!(row.get::<usize, i64>(2).unwrap(), 9223315252854775808);
@@ -1,7 +1,20 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 pub mod migration_0_to_1;
+pub mod migration_10_to_11;
+pub mod migration_11_to_12;
+pub mod migration_12_to_13;
+pub mod migration_13_to_14;
+pub mod migration_14_to_15;
+pub mod migration_15_to_16;
+pub mod migration_16_to_17;
+pub mod migration_17_to_18;
+pub mod migration_18_to_19;
+pub mod migration_19_to_20;
 pub mod migration_1_to_2;
+pub mod migration_20_to_21;
+pub mod migration_21_to_22;
+pub mod migration_22_to_23;
 pub mod migration_2_to_3;
 pub mod migration_3_to_4;
 pub mod migration_4_to_5;
@@ -125,6 +125,7 @@ impl Default for ConfigDaoNull {
             (Some(DEFAULT_MIN_HOPS.to_string()), false),
         );
         data.insert("earning_wallet_address".to_string(), (None, false));
+        data.insert("earning_wallet_rotation".to_string(), (None, false));
         data.insert(
             "schema_version".to_string(),
             (Some(format!("{}", CURRENT_SCHEMA_VERSION)), false),
@@ -282,6 +283,7 @@ mod tests {
             ("past_neighbors", None),
             ("mapping_protocol", None),
             ("earning_wallet_address", None),
+            ("earning_wallet_rotation", None),
             (
                 "schema_version",
                 Some(format!("{}", CURRENT_SCHEMA_VERSION).as_str()),
@@ -14,6 +14,7 @@ use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals};
 use crate::sub_lib::cryptde::PlainData;
 use crate::sub_lib::neighborhood::{Hops, NodeDescriptor, RatePack};
 use crate::sub_lib::wallet::Wallet;
+use itertools::Itertools;
 use masq_lib::constants::{HIGHEST_USABLE_PORT, LOWEST_USABLE_INSECURE_PORT};
 use masq_lib::shared_schema::{ConfiguratorError, ParamError};
 use masq_lib::utils::NeighborhoodModeLight;
@@ -108,6 +109,13 @@ pub trait PersistentConfiguration {
     fn earning_wallet(&self) -> Result<Option<Wallet>, PersistentConfigError>;
     // WARNING: Actors should get earning-wallet information from their startup config, not from here
     fn earning_wallet_address(&self) -> Result<Option<String>, PersistentConfigError>;
+    // Earning wallets derived from the same seed, the first being the earning wallet itself;
+    // empty unless a rotation has been set up
+    fn earning_wallet_rotation(&self) -> Result<Vec<Wallet>, PersistentConfigError>;
+    fn set_earning_wallet_rotation(
+        &mut self,
+        wallets: &[Wallet],
+    ) -> Result<(), PersistentConfigError>;
     fn gas_price(&self) -> Result<u64, PersistentConfigError>;
     fn set_gas_price(&mut self, gas_price: u64) -> Result<(), PersistentConfigError>;
     fn mapping_protocol(&self) -> Result<Option<AutomapProtocol>, PersistentConfigError>;
@@ -309,6 +317,40 @@ impl PersistentConfiguration for PersistentConfigurationReal {
         Ok(self.get("earning_wallet_address")?)
     }
 
+    fn earning_wallet_rotation(&self) -> Result<Vec<Wallet>, PersistentConfigError> {
+        match self.get("earning_wallet_rotation")? {
+            None => Ok(vec![]),
+            Some(addresses) => Ok(addresses
+                .split(',')
+                .map(|address| {
+                    Wallet::from_str(address).unwrap_or_else(|error| {
+                        panic!(
+                            "Database corrupt: invalid address '{}' in earning wallet rotation: {:?}",
+                            address, error
+                        )
+                    })
+                })
+                .collect()),
+        }
+    }
+
+    fn set_earning_wallet_rotation(
+        &mut self,
+        wallets: &[Wallet],
+    ) -> Result<(), PersistentConfigError> {
+        let begins_with_earning_wallet = match (self.earning_wallet()?, wallets.first()) {
+            (Some(earning_wallet), Some(first)) => earning_wallet.address() == first.address(),
+            _ => false,
+        };
+        if !begins_with_earning_wallet {
+            return Err(PersistentConfigError::Collision(
+                "Earning wallet rotation must begin with the earning wallet".to_string(),
+            ));
+        }
+        let addresses = wallets.iter().map(|wallet| wallet.to_string()).join(",");
+        Ok(self.dao.set("earning_wallet_rotation", Some(addresses))?)
+    }
+
     fn gas_price(&self) -> Result<u64, PersistentConfigError> {
         match decode_u64(self.get("gas_price")?) {
             Ok(val) => {
@@ -454,7 +496,7 @@ impl PersistentConfiguration for PersistentConfigurationReal {
         match earning_wallet_address_opt {
             None => (),
             Some(existing_earning_wallet_address) => {
-                if earning_wallet_address != existing_earning_wallet_address {
+                if earning_wallet_address.to_lowercase() != existing_earning_wallet_address {
                     return Err(PersistentConfigError::Collision(
                         "Earning wallet address already populated; cannot replace".to_string(),
                     ));
@@ -472,9 +514,10 @@ impl PersistentConfiguration for PersistentConfigurationReal {
             "consuming_wallet_private_key",
             Some(encrypted_consuming_wallet_private_key),
         )?;
+        // Kept in lower case, like every other address in the database
         Ok(self.dao.set(
             "earning_wallet_address",
-            Some(earning_wallet_address.to_string()),
+            Some(earning_wallet_address.to_lowercase()),
         )?)
     }
 
@@ -1194,6 +1237,140 @@ mod tests {
         assert_eq!(*get_params, vec!["earning_wallet_address".to_string()]);
     }
 
+    #[test]
+    fn earning_wallet_rotation_is_empty_if_not_set_up() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "earning_wallet_rotation",
+            None,
+            false,
+        )));
+        let subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.earning_wallet_rotation();
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[test]
+    fn earning_wallet_rotation_if_set_up() {
+        let get_params_arc = Arc::new(Mutex::new(vec![]));
+        let config_dao = ConfigDaoMock::new()
+            .get_params(&get_params_arc)
+            .get_result(Ok(ConfigDaoRecord::new(
+                "earning_wallet_rotation",
+                Some(
+                    "0x7d6dabd6b5c75291a3258c29b418f5805792a875,\
+                     0x0000000000000000000000000000000000e4a1e0",
+                ),
+                false,
+            )));
+        let subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.earning_wallet_rotation();
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                Wallet::from_str("0x7d6dabd6b5c75291a3258c29b418f5805792a875").unwrap(),
+                Wallet::from_str("0x0000000000000000000000000000000000e4a1e0").unwrap()
+            ])
+        );
+        let get_params = get_params_arc.lock().unwrap();
+        assert_eq!(*get_params, vec!["earning_wallet_rotation".to_string()]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Database corrupt: invalid address 'booga' in earning wallet rotation: InvalidAddress"
+    )]
+    fn earning_wallet_rotation_with_invalid_address() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "earning_wallet_rotation",
+            Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875,booga"),
+            false,
+        )));
+        let subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let _ = subject.earning_wallet_rotation();
+    }
+
+    #[test]
+    fn set_earning_wallet_rotation_works() {
+        let set_params_arc = Arc::new(Mutex::new(vec![]));
+        let config_dao = ConfigDaoMock::new()
+            .get_result(Ok(ConfigDaoRecord::new(
+                "earning_wallet_address",
+                Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875"),
+                false,
+            )))
+            .set_params(&set_params_arc)
+            .set_result(Ok(()));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.set_earning_wallet_rotation(&[
+            Wallet::from_str("0x7D6DABD6B5C75291A3258C29B418F5805792A875").unwrap(),
+            Wallet::from_str("0x0000000000000000000000000000000000e4a1e0").unwrap(),
+        ]);
+
+        assert_eq!(result, Ok(()));
+        let set_params = set_params_arc.lock().unwrap();
+        assert_eq!(
+            *set_params,
+            vec![(
+                "earning_wallet_rotation".to_string(),
+                Some(
+                    "0x7d6dabd6b5c75291a3258c29b418f5805792a875,\
+                     0x0000000000000000000000000000000000e4a1e0"
+                        .to_string()
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn set_earning_wallet_rotation_insists_on_beginning_with_the_earning_wallet() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "earning_wallet_address",
+            Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875"),
+            false,
+        )));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.set_earning_wallet_rotation(&[
+            Wallet::from_str("0x0000000000000000000000000000000000e4a1e0").unwrap(),
+            Wallet::from_str("0x7d6dabd6b5c75291a3258c29b418f5805792a875").unwrap(),
+        ]);
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::Collision(
+                "Earning wallet rotation must begin with the earning wallet".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn set_earning_wallet_rotation_requires_an_earning_wallet() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "earning_wallet_address",
+            None,
+            false,
+        )));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.set_earning_wallet_rotation(&[Wallet::from_str(
+            "0x0000000000000000000000000000000000e4a1e0",
+        )
+        .unwrap()]);
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::Collision(
+                "Earning wallet rotation must begin with the earning wallet".to_string()
+            ))
+        );
+    }
+
     fn make_seed_info(db_password: &str) -> (PlainData, String) {
         let mnemonic = Bip39::mnemonic(MnemonicType::Words12, Language::English);
         let mnemonic_seed = Bip39::seed(&mnemonic, "");
@@ -1303,6 +1480,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_wallet_info_stores_the_earning_wallet_address_in_lower_case() {
+        let set_params_arc = Arc::new(Mutex::new(vec![]));
+        let config_dao = Box::new(
+            ConfigDaoMock::new()
+                .get_result(Ok(ConfigDaoRecord::new(
+                    "consuming_wallet_private_key",
+                    None,
+                    true,
+                )))
+                .get_result(Ok(ConfigDaoRecord::new(
+                    "earning_wallet_address",
+                    None,
+                    false,
+                )))
+                .set_params(&set_params_arc)
+                .set_result(Ok(()))
+                .set_result(Ok(())),
+        );
+        let mut subject = PersistentConfigurationReal::new(config_dao);
+        let (consuming_wallet_private_key, _, earning_wallet_address) =
+            make_wallet_info("password");
+        let upper_case_address = format!("0x{}", earning_wallet_address[2..].to_uppercase());
+
+        let result = subject.set_wallet_info(
+            &consuming_wallet_private_key,
+            &upper_case_address,
+            "password",
+        );
+
+        assert_eq!(result, Ok(()));
+        let set_params = set_params_arc.lock().unwrap();
+        assert_eq!(
+            set_params[1],
+            (
+                "earning_wallet_address".to_string(),
+                Some(earning_wallet_address)
+            )
+        );
+    }
+
     #[test]
     fn set_wallet_info_fails_if_consuming_wallet_private_key_exists() {
         let example = "Aside from that, Mrs. Lincoln, how was the play?".as_bytes();
@@ -3,12 +3,16 @@ use super::live_cores_package::LiveCoresPackage;
 use crate::blockchain::payer::Payer;
 use crate::bootstrapper::CryptDEPair;
 use crate::neighborhood::gossip::Gossip_0v1;
-use crate::sub_lib::accountant::ReportRoutingServiceProvidedMessage;
+use crate::sub_lib::accountant::{
+    PaymentNotice_0v1, ReportRoutingServiceProvidedMessage, UsageInvoice_0v1,
+};
 use crate::sub_lib::cryptde::{decodex, encodex, CryptData, CryptdecError};
 use crate::sub_lib::dispatcher::{Component, Endpoint, InboundClientData};
 use crate::sub_lib::hop::LiveHop;
 use crate::sub_lib::hopper::{ExpiredCoresPackage, HopperSubs, MessageType};
-use crate::sub_lib::neighborhood::{GossipFailure_0v1, NeighborhoodSubs};
+use crate::sub_lib::neighborhood::{
+    ExitOptionsQuery_0v1, ExitOptionsReport_0v1, GossipFailure_0v1, NeighborhoodSubs,
+};
 use crate::sub_lib::proxy_client::{
     ClientResponsePayload_0v1, DnsResolveFailure_0v1, ProxyClientSubs,
 };
@@ -387,6 +391,92 @@ impl RoutingService {
                     ))
                     .expect("Neighborhood is dead")
             }
+            (Component::Neighborhood, MessageType::PaymentNotice(vd)) => {
+                let notice = match PaymentNotice_0v1::try_from(vd) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        error!(self.logger, "Received unmigratable PaymentNotice: {:?}", e);
+                        return;
+                    }
+                };
+                self.routing_service_subs
+                    .neighborhood_subs
+                    .payment_notice
+                    .try_send(ExpiredCoresPackage::new(
+                        expired_package.immediate_neighbor,
+                        expired_package.paying_wallet,
+                        expired_package.remaining_route,
+                        notice,
+                        expired_package.payload_len,
+                    ))
+                    .expect("Neighborhood is dead")
+            }
+            (Component::Neighborhood, MessageType::ExitOptionsQuery(vd)) => {
+                let query = match ExitOptionsQuery_0v1::try_from(vd) {
+                    Ok(q) => q,
+                    Err(e) => {
+                        error!(
+                            self.logger,
+                            "Received unmigratable ExitOptionsQuery: {:?}", e
+                        );
+                        return;
+                    }
+                };
+                self.routing_service_subs
+                    .neighborhood_subs
+                    .exit_options_query
+                    .try_send(ExpiredCoresPackage::new(
+                        expired_package.immediate_neighbor,
+                        expired_package.paying_wallet,
+                        expired_package.remaining_route,
+                        query,
+                        expired_package.payload_len,
+                    ))
+                    .expect("Neighborhood is dead")
+            }
+            (Component::Neighborhood, MessageType::ExitOptionsReport(vd)) => {
+                let report = match ExitOptionsReport_0v1::try_from(vd) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        error!(
+                            self.logger,
+                            "Received unmigratable ExitOptionsReport: {:?}", e
+                        );
+                        return;
+                    }
+                };
+                self.routing_service_subs
+                    .neighborhood_subs
+                    .exit_options_report
+                    .try_send(ExpiredCoresPackage::new(
+                        expired_package.immediate_neighbor,
+                        expired_package.paying_wallet,
+                        expired_package.remaining_route,
+                        report,
+                        expired_package.payload_len,
+                    ))
+                    .expect("Neighborhood is dead")
+            }
+            (Component::Neighborhood, MessageType::UsageInvoice(vd)) => {
+                let invoice = match UsageInvoice_0v1::try_from(vd) {
+                    Ok(i) => i,
+                    Err(e) => {
+                        error!(self.logger, "Received unmigratable UsageInvoice: {:?}", e);
+                        return;
+                    }
+                };
+                self.routing_service_subs
+                    .neighborhood_subs
+                    .usage_invoice
+                    .try_send(ExpiredCoresPackage::new(
+                        expired_package.immediate_neighbor,
+                        expired_package.paying_wallet,
+                        expired_package.remaining_route,
+                        invoice,
+                        expired_package.payload_len,
+                    ))
+                    .expect("Neighborhood is dead")
+            }
             (destination, payload) => error!(
                 self.logger,
                 "Attempt to send invalid combination {:?} to {:?}", payload, destination
@@ -501,6 +591,7 @@ impl RoutingService {
 mod tests {
     use super::*;
     use crate::accountant::db_access_objects::banned_dao::BAN_CACHE;
+    use crate::blockchain::test_utils::make_tx_hash;
     use crate::bootstrapper::Bootstrapper;
     use crate::neighborhood::gossip::{GossipBuilder, Gossip_0v1};
     use crate::node_test_utils::check_timestamp;
@@ -522,8 +613,8 @@ mod tests {
     use crate::test_utils::unshared_test_utils::{make_request_payload, make_response_payload};
     use crate::test_utils::{
         alias_cryptde, main_cryptde, make_cryptde_pair, make_meaningless_message_type,
-        make_paying_wallet, rate_pack_routing, rate_pack_routing_byte, route_from_proxy_client,
-        route_to_proxy_client, route_to_proxy_server,
+        make_paying_wallet, make_wallet, rate_pack_routing, rate_pack_routing_byte,
+        route_from_proxy_client, route_to_proxy_client, route_to_proxy_server,
     };
     use actix::System;
     use masq_lib::test_utils::environment_guard::EnvironmentGuard;
@@ -2176,4 +2267,311 @@ mod tests {
             "ERROR: RoutingService: Received unmigratable GossipFailure: MigrationNotFound(DataVersion { major: 0, minor: 0 }, DataVersion { major: 0, minor: 1 })",
         );
     }
+
+    #[test]
+    fn route_expired_package_sends_payment_notice_to_neighborhood() {
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let subject = RoutingService::new(
+            make_cryptde_pair(),
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            100,
+            200,
+            false,
+        );
+        let notice = PaymentNotice_0v1::new(
+            &make_paying_wallet(b"debtor"),
+            &make_wallet("creditor"),
+            make_tx_hash(789),
+            4_567_000_000_000,
+            1_234,
+        )
+        .unwrap();
+        let expired_package = ExpiredCoresPackage::new(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            None,
+            Route { hops: vec![] },
+            MessageType::PaymentNotice(VersionedData::new(
+                &crate::sub_lib::migrations::payment_notice::MIGRATIONS,
+                &notice,
+            )),
+            321,
+        );
+        let system = System::new("route_expired_package_sends_payment_notice_to_neighborhood");
+
+        subject.route_expired_package(Component::Neighborhood, expired_package, true);
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        let record = neighborhood_recording.get_record::<ExpiredCoresPackage<PaymentNotice_0v1>>(0);
+        assert_eq!(
+            record.immediate_neighbor,
+            SocketAddr::from_str("1.2.3.4:1234").unwrap()
+        );
+        assert_eq!(record.payload, notice);
+        assert_eq!(record.payload_len, 321);
+    }
+
+    #[test]
+    fn route_expired_package_handles_unmigratable_payment_notice() {
+        init_test_logging();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let subject = RoutingService::new(
+            make_cryptde_pair(),
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            100,
+            200,
+            false,
+        );
+        let expired_package = ExpiredCoresPackage::new(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            None,
+            Route { hops: vec![] },
+            MessageType::PaymentNotice(VersionedData::test_new(dv!(0, 0), vec![])),
+            0,
+        );
+        let system = System::new("route_expired_package_handles_unmigratable_payment_notice");
+
+        subject.route_expired_package(Component::Neighborhood, expired_package, true);
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(
+            "ERROR: RoutingService: Received unmigratable PaymentNotice: MigrationNotFound(DataVersion { major: 0, minor: 0 }, DataVersion { major: 0, minor: 1 })",
+        );
+    }
+
+    #[test]
+    fn route_expired_package_sends_usage_invoice_to_neighborhood() {
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let subject = RoutingService::new(
+            make_cryptde_pair(),
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            100,
+            200,
+            false,
+        );
+        let mut invoice = UsageInvoice_0v1::new(
+            &make_wallet("creditor"),
+            &make_wallet("debtor"),
+            1_000,
+            2_000,
+            12,
+            3,
+            4_567_000_000_000,
+        );
+        invoice.sign(main_cryptde()).unwrap();
+        let expired_package = ExpiredCoresPackage::new(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            None,
+            Route { hops: vec![] },
+            MessageType::UsageInvoice(invoice.clone().into()),
+            321,
+        );
+        let system = System::new("route_expired_package_sends_usage_invoice_to_neighborhood");
+
+        subject.route_expired_package(Component::Neighborhood, expired_package, true);
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        let record = neighborhood_recording.get_record::<ExpiredCoresPackage<UsageInvoice_0v1>>(0);
+        assert_eq!(
+            record.immediate_neighbor,
+            SocketAddr::from_str("1.2.3.4:1234").unwrap()
+        );
+        assert_eq!(record.payload, invoice);
+        assert_eq!(record.payload_len, 321);
+    }
+
+    #[test]
+    fn route_expired_package_handles_unmigratable_usage_invoice() {
+        init_test_logging();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let subject = RoutingService::new(
+            make_cryptde_pair(),
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            100,
+            200,
+            false,
+        );
+        let expired_package = ExpiredCoresPackage::new(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            None,
+            Route { hops: vec![] },
+            MessageType::UsageInvoice(VersionedData::test_new(dv!(0, 0), vec![])),
+            0,
+        );
+        let system = System::new("route_expired_package_handles_unmigratable_usage_invoice");
+
+        subject.route_expired_package(Component::Neighborhood, expired_package, true);
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(
+            "ERROR: RoutingService: Received unmigratable UsageInvoice: MigrationNotFound(DataVersion { major: 0, minor: 0 }, DataVersion { major: 0, minor: 1 })",
+        );
+    }
+
+    #[test]
+    fn route_expired_package_sends_exit_options_query_and_report_to_neighborhood() {
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let subject = RoutingService::new(
+            make_cryptde_pair(),
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            100,
+            200,
+            false,
+        );
+        let query = ExitOptionsQuery_0v1 {
+            sent_at_ms: 1_700_000_000_123,
+        };
+        let report = ExitOptionsReport_0v1 {
+            exit_country_codes: vec!["CZ".to_string()],
+            query_sent_at_ms: 1_700_000_000_123,
+        };
+        let make_package = |message_type: MessageType| {
+            ExpiredCoresPackage::new(
+                SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+                None,
+                Route { hops: vec![] },
+                message_type,
+                321,
+            )
+        };
+        let system = System::new(
+            "route_expired_package_sends_exit_options_query_and_report_to_neighborhood",
+        );
+
+        subject.route_expired_package(
+            Component::Neighborhood,
+            make_package(MessageType::ExitOptionsQuery(query.clone().into())),
+            true,
+        );
+        subject.route_expired_package(
+            Component::Neighborhood,
+            make_package(MessageType::ExitOptionsReport(report.clone().into())),
+            true,
+        );
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        let query_record =
+            neighborhood_recording.get_record::<ExpiredCoresPackage<ExitOptionsQuery_0v1>>(0);
+        assert_eq!(
+            query_record.immediate_neighbor,
+            SocketAddr::from_str("1.2.3.4:1234").unwrap()
+        );
+        assert_eq!(query_record.payload, query);
+        let report_record =
+            neighborhood_recording.get_record::<ExpiredCoresPackage<ExitOptionsReport_0v1>>(1);
+        assert_eq!(report_record.payload, report);
+        assert_eq!(report_record.payload_len, 321);
+    }
+
+    #[test]
+    fn route_expired_package_handles_unmigratable_exit_options_messages() {
+        init_test_logging();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let subject = RoutingService::new(
+            make_cryptde_pair(),
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            100,
+            200,
+            false,
+        );
+        let make_package = |message_type: MessageType| {
+            ExpiredCoresPackage::new(
+                SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+                None,
+                Route { hops: vec![] },
+                message_type,
+                0,
+            )
+        };
+        let system =
+            System::new("route_expired_package_handles_unmigratable_exit_options_messages");
+
+        subject.route_expired_package(
+            Component::Neighborhood,
+            make_package(MessageType::ExitOptionsQuery(VersionedData::test_new(
+                dv!(0, 0),
+                vec![],
+            ))),
+            true,
+        );
+        subject.route_expired_package(
+            Component::Neighborhood,
+            make_package(MessageType::ExitOptionsReport(VersionedData::test_new(
+                dv!(0, 0),
+                vec![],
+            ))),
+            true,
+        );
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(
+            "ERROR: RoutingService: Received unmigratable ExitOptionsQuery: MigrationNotFound(DataVersion { major: 0, minor: 0 }, DataVersion { major: 0, minor: 1 })",
+        );
+        tlh.exists_log_containing(
+            "ERROR: RoutingService: Received unmigratable ExitOptionsReport: MigrationNotFound(DataVersion { major: 0, minor: 0 }, DataVersion { major: 0, minor: 1 })",
+        );
+    }
 }
@@ -0,0 +1,159 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use itertools::Itertools;
+use masq_lib::messages::ExitLocation;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExitCountryChange {
+    Unchanged,
+    Selected {
+        previous_opt: Option<String>,
+        country: String,
+    },
+    // None of the preferred countries has an exit Node in the Neighborhood any more
+    Lost {
+        previous: String,
+    },
+}
+
+// The active exit country is the first of the preferred countries, in order of priority, that the
+// Neighborhood has exit Nodes in. When it becomes unavailable, the next one takes over, and once a
+// more preferred country is available again, the routes return to it.
+#[derive(Default, Debug)]
+pub struct ExitFailoverPolicy {
+    active_country_opt: Option<String>,
+}
+
+impl ExitFailoverPolicy {
+    pub fn active_country_opt(&self) -> Option<&String> {
+        self.active_country_opt.as_ref()
+    }
+
+    pub fn reevaluate(
+        &mut self,
+        preferences: &[ExitLocation],
+        available_countries: &[String],
+    ) -> ExitCountryChange {
+        let chosen_opt = preferences
+            .iter()
+            .sorted_by_key(|location| location.priority)
+            .flat_map(|location| location.country_codes.iter())
+            .find(|country| available_countries.contains(country))
+            .cloned();
+        let previous_opt = self.active_country_opt.take();
+        self.active_country_opt = chosen_opt.clone();
+        match (previous_opt, chosen_opt) {
+            (None, None) => ExitCountryChange::Unchanged,
+            (Some(previous), None) => ExitCountryChange::Lost { previous },
+            (Some(previous), Some(country)) if previous == country => ExitCountryChange::Unchanged,
+            (previous_opt, Some(country)) => ExitCountryChange::Selected {
+                previous_opt,
+                country,
+            },
+        }
+    }
+
+    // Without preferences, routes may exit anywhere, and no country is active
+    pub fn reset(&mut self) {
+        self.active_country_opt = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(country_codes: &[&str], priority: usize) -> ExitLocation {
+        ExitLocation {
+            country_codes: country_codes.iter().map(|cc| cc.to_string()).collect(),
+            priority,
+        }
+    }
+
+    fn countries(country_codes: &[&str]) -> Vec<String> {
+        country_codes.iter().map(|cc| cc.to_string()).collect()
+    }
+
+    #[test]
+    fn the_most_preferred_available_country_is_selected() {
+        let preferences = vec![location(&["SK"], 2), location(&["CZ", "PL"], 1)];
+        let mut subject = ExitFailoverPolicy::default();
+
+        let result = subject.reevaluate(&preferences, &countries(&["FR", "PL", "SK"]));
+
+        assert_eq!(
+            result,
+            ExitCountryChange::Selected {
+                previous_opt: None,
+                country: "PL".to_string()
+            }
+        );
+        assert_eq!(subject.active_country_opt(), Some(&"PL".to_string()));
+    }
+
+    #[test]
+    fn active_country_is_kept_while_it_is_available() {
+        let preferences = vec![location(&["CZ"], 1), location(&["SK"], 2)];
+        let mut subject = ExitFailoverPolicy::default();
+        subject.reevaluate(&preferences, &countries(&["CZ"]));
+
+        let result = subject.reevaluate(&preferences, &countries(&["CZ", "SK"]));
+
+        assert_eq!(result, ExitCountryChange::Unchanged);
+        assert_eq!(subject.active_country_opt(), Some(&"CZ".to_string()));
+    }
+
+    #[test]
+    fn unavailable_active_country_fails_over_to_the_next_one_and_back() {
+        let preferences = vec![location(&["CZ"], 1), location(&["SK"], 2)];
+        let mut subject = ExitFailoverPolicy::default();
+        subject.reevaluate(&preferences, &countries(&["CZ", "SK"]));
+
+        let failover = subject.reevaluate(&preferences, &countries(&["SK"]));
+        let failback = subject.reevaluate(&preferences, &countries(&["CZ", "SK"]));
+
+        assert_eq!(
+            failover,
+            ExitCountryChange::Selected {
+                previous_opt: Some("CZ".to_string()),
+                country: "SK".to_string()
+            }
+        );
+        assert_eq!(
+            failback,
+            ExitCountryChange::Selected {
+                previous_opt: Some("SK".to_string()),
+                country: "CZ".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn active_country_is_lost_when_no_preferred_country_is_available() {
+        let preferences = vec![location(&["CZ"], 1), location(&["SK"], 2)];
+        let mut subject = ExitFailoverPolicy::default();
+        subject.reevaluate(&preferences, &countries(&["SK"]));
+
+        let lost = subject.reevaluate(&preferences, &countries(&["FR"]));
+        let still_nothing = subject.reevaluate(&preferences, &countries(&["FR"]));
+
+        assert_eq!(
+            lost,
+            ExitCountryChange::Lost {
+                previous: "SK".to_string()
+            }
+        );
+        assert_eq!(still_nothing, ExitCountryChange::Unchanged);
+        assert_eq!(subject.active_country_opt(), None);
+    }
+
+    #[test]
+    fn reset_forgets_the_active_country() {
+        let mut subject = ExitFailoverPolicy::default();
+        subject.reevaluate(&[location(&["CZ"], 1)], &countries(&["CZ"]));
+
+        subject.reset();
+
+        assert_eq!(subject.active_country_opt(), None);
+    }
+}
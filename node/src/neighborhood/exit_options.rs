@@ -0,0 +1,236 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::sub_lib::cryptde::PublicKey;
+use crate::sub_lib::neighborhood::{ExitOptionsQuery_0v1, ExitOptionsReport_0v1};
+use masq_lib::messages::{UiExitCountryOption, UiExitNeighbor};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Reports older than this no longer count among the exit options
+pub const EXIT_OPTIONS_MAX_AGE: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExitOptionsRecord {
+    pub exit_country_codes: Vec<String>,
+    pub latency: Duration,
+    pub reported_at: SystemTime,
+}
+
+// The probes of exit options out to the neighbors, and the latest answer of each neighbor
+#[derive(Default)]
+pub struct ExitOptionsCache {
+    pending_probes: HashMap<PublicKey, u64>,
+    records: HashMap<PublicKey, ExitOptionsRecord>,
+}
+
+impl ExitOptionsCache {
+    pub fn make_query(&mut self, neighbor: &PublicKey, now: SystemTime) -> ExitOptionsQuery_0v1 {
+        let sent_at_ms = millis_since_epoch(now);
+        self.pending_probes.insert(neighbor.clone(), sent_at_ms);
+        ExitOptionsQuery_0v1 { sent_at_ms }
+    }
+
+    // Only the answer to the latest probe counts; the latency is measured by our own clock, so a
+    // neighbor can't claim to be more responsive than it is
+    pub fn record_report(
+        &mut self,
+        neighbor: &PublicKey,
+        report: ExitOptionsReport_0v1,
+        now: SystemTime,
+    ) -> Result<&ExitOptionsRecord, String> {
+        match self.pending_probes.get(neighbor) {
+            Some(sent_at_ms) if *sent_at_ms == report.query_sent_at_ms => {
+                self.pending_probes.remove(neighbor);
+            }
+            Some(_) => return Err("it answers a superseded probe".to_string()),
+            None => return Err("no probe was sent to it".to_string()),
+        }
+        let latency_ms = millis_since_epoch(now).saturating_sub(report.query_sent_at_ms);
+        let record = ExitOptionsRecord {
+            exit_country_codes: report.exit_country_codes,
+            latency: Duration::from_millis(latency_ms),
+            reported_at: now,
+        };
+        self.records.insert(neighbor.clone(), record);
+        Ok(self.records.get(neighbor).expect("Record disappeared"))
+    }
+
+    // Grouped by country; the countries with the most responsive neighbors come first, and so do
+    // the most responsive neighbors within each country
+    pub fn ranked_options(
+        &self,
+        neighbors: &HashSet<PublicKey>,
+        now: SystemTime,
+    ) -> Vec<UiExitCountryOption> {
+        let mut by_country: HashMap<&String, Vec<UiExitNeighbor>> = HashMap::new();
+        self.records
+            .iter()
+            .filter(|(key, record)| {
+                neighbors.contains(*key) && age(record, now) <= EXIT_OPTIONS_MAX_AGE
+            })
+            .for_each(|(key, record)| {
+                record.exit_country_codes.iter().for_each(|country_code| {
+                    by_country
+                        .entry(country_code)
+                        .or_default()
+                        .push(UiExitNeighbor {
+                            public_key: key.to_string(),
+                            latency_ms: record.latency.as_millis() as u64,
+                            age_seconds: age(record, now).as_secs(),
+                        })
+                })
+            });
+        let mut options = by_country
+            .into_iter()
+            .map(|(country_code, mut neighbors)| {
+                neighbors.sort_by(|a, b| {
+                    (a.latency_ms, &a.public_key).cmp(&(b.latency_ms, &b.public_key))
+                });
+                UiExitCountryOption {
+                    country_code: country_code.clone(),
+                    neighbors,
+                }
+            })
+            .collect::<Vec<UiExitCountryOption>>();
+        options.sort_by(|a, b| {
+            (a.neighbors[0].latency_ms, &a.country_code)
+                .cmp(&(b.neighbors[0].latency_ms, &b.country_code))
+        });
+        options
+    }
+}
+
+fn age(record: &ExitOptionsRecord, now: SystemTime) -> Duration {
+    now.duration_since(record.reported_at).unwrap_or_default()
+}
+
+fn millis_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .expect("Clock is set before the epoch")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(country_codes: &[&str], query_sent_at_ms: u64) -> ExitOptionsReport_0v1 {
+        ExitOptionsReport_0v1 {
+            exit_country_codes: country_codes.iter().map(|cc| cc.to_string()).collect(),
+            query_sent_at_ms,
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(EXIT_OPTIONS_MAX_AGE, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn report_answering_the_pending_probe_is_recorded_with_its_latency() {
+        let neighbor = PublicKey::new(b"neighbor");
+        let sent_at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+        let received_at = sent_at + Duration::from_millis(123);
+        let mut subject = ExitOptionsCache::default();
+        let query = subject.make_query(&neighbor, sent_at);
+
+        let result = subject
+            .record_report(&neighbor, report(&["CZ"], query.sent_at_ms), received_at)
+            .cloned();
+
+        assert_eq!(query.sent_at_ms, 1_700_000_000_000);
+        assert_eq!(
+            result,
+            Ok(ExitOptionsRecord {
+                exit_country_codes: vec!["CZ".to_string()],
+                latency: Duration::from_millis(123),
+                reported_at: received_at,
+            })
+        );
+        assert_eq!(subject.pending_probes.is_empty(), true);
+    }
+
+    #[test]
+    fn report_answering_no_pending_probe_is_refused() {
+        let neighbor = PublicKey::new(b"neighbor");
+        let first_sent_at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+        let second_sent_at = first_sent_at + Duration::from_millis(1_000);
+        let mut subject = ExitOptionsCache::default();
+
+        let unasked = subject
+            .record_report(&neighbor, report(&["CZ"], 1), second_sent_at)
+            .cloned();
+        let first_query = subject.make_query(&neighbor, first_sent_at);
+        subject.make_query(&neighbor, second_sent_at);
+        let superseded = subject
+            .record_report(
+                &neighbor,
+                report(&["CZ"], first_query.sent_at_ms),
+                second_sent_at,
+            )
+            .cloned();
+
+        assert_eq!(unasked, Err("no probe was sent to it".to_string()));
+        assert_eq!(superseded, Err("it answers a superseded probe".to_string()));
+        assert_eq!(subject.records.is_empty(), true);
+    }
+
+    #[test]
+    fn options_are_ranked_by_country_and_responsiveness() {
+        let fast = PublicKey::new(b"fast");
+        let slow = PublicKey::new(b"slow");
+        let middling = PublicKey::new(b"middling");
+        let stale = PublicKey::new(b"stale");
+        let gone = PublicKey::new(b"gone");
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut subject = ExitOptionsCache::default();
+        let mut record = |key: &PublicKey, codes: &[&str], latency_ms: u64, age: Duration| {
+            subject.records.insert(
+                key.clone(),
+                ExitOptionsRecord {
+                    exit_country_codes: codes.iter().map(|cc| cc.to_string()).collect(),
+                    latency: Duration::from_millis(latency_ms),
+                    reported_at: now - age,
+                },
+            );
+        };
+        record(&fast, &["DE", "FR"], 20, Duration::from_secs(5));
+        record(&slow, &["CZ", "FR"], 300, Duration::from_secs(1));
+        record(&middling, &["CZ"], 80, Duration::from_secs(0));
+        record(
+            &stale,
+            &["AU"],
+            1,
+            EXIT_OPTIONS_MAX_AGE + Duration::from_secs(1),
+        );
+        record(&gone, &["BR"], 1, Duration::from_secs(0));
+        let neighbors = vec![fast.clone(), slow.clone(), middling.clone(), stale.clone()]
+            .into_iter()
+            .collect::<HashSet<PublicKey>>();
+
+        let result = subject.ranked_options(&neighbors, now);
+
+        let neighbor = |key: &PublicKey, latency_ms: u64, age_seconds: u64| UiExitNeighbor {
+            public_key: key.to_string(),
+            latency_ms,
+            age_seconds,
+        };
+        assert_eq!(
+            result,
+            vec![
+                UiExitCountryOption {
+                    country_code: "DE".to_string(),
+                    neighbors: vec![neighbor(&fast, 20, 5)],
+                },
+                UiExitCountryOption {
+                    country_code: "FR".to_string(),
+                    neighbors: vec![neighbor(&fast, 20, 5), neighbor(&slow, 300, 1)],
+                },
+                UiExitCountryOption {
+                    country_code: "CZ".to_string(),
+                    neighbors: vec![neighbor(&middling, 80, 0), neighbor(&slow, 300, 1)],
+                },
+            ]
+        );
+    }
+}
@@ -5,12 +5,14 @@ use crate::neighborhood::gossip::{
 };
 use crate::neighborhood::neighborhood_database::{NeighborhoodDatabase, NeighborhoodDatabaseError};
 use crate::neighborhood::node_record::NodeRecord;
-use crate::neighborhood::UserExitPreferences;
+use crate::neighborhood::{UserExitPreferences, ZERO_UNDESIRABILITY};
 use crate::sub_lib::cryptde::{CryptDE, PublicKey};
 use crate::sub_lib::neighborhood::{
-    ConnectionProgressEvent, ConnectionProgressMessage, GossipFailure_0v1, NeighborhoodMetadata,
+    ConnectionProgressEvent, ConnectionProgressMessage, CountryClaimCheck, GossipFailure_0v1,
+    NeighborhoodMetadata,
 };
 use crate::sub_lib::node_addr::NodeAddr;
+use ip_country_lib::country_finder::CountryCodeFinder;
 use masq_lib::logger::Logger;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -1325,6 +1327,7 @@ pub trait GossipAcceptor: Send /* Send because lazily-written tests require it *
 pub struct GossipAcceptorReal<'a> {
     cryptde: &'a dyn CryptDE,
     gossip_handlers: Vec<Box<dyn GossipHandler>>,
+    country_claim_verifier: CountryClaimVerifier<'a>,
     logger: Logger,
 }
 
@@ -1349,13 +1352,22 @@ impl<'a> GossipAcceptor for GossipAcceptorReal<'a> {
                     "Gossip delegated to {}",
                     handler_ref.type_name()
                 );
-                handler_ref.handle(
+                let verdicts = self.country_claim_verifier.verify(&agrs);
+                let user_exit_preferences_opt =
+                    neighborhood_metadata.user_exit_preferences_opt.clone();
+                let result = handler_ref.handle(
                     self.cryptde,
                     database,
                     agrs,
                     gossip_source,
                     neighborhood_metadata,
-                )
+                );
+                self.country_claim_verifier.apply(
+                    database,
+                    verdicts,
+                    user_exit_preferences_opt.as_ref(),
+                );
+                result
             }
             Qualification::Unmatched => {
                 panic!("Nothing in gossip_handlers returned Matched or Malformed")
@@ -1366,7 +1378,11 @@ impl<'a> GossipAcceptor for GossipAcceptorReal<'a> {
 }
 
 impl<'a> GossipAcceptorReal<'a> {
-    pub fn new(cryptde: &'a dyn CryptDE) -> GossipAcceptorReal {
+    pub fn new(
+        cryptde: &'a dyn CryptDE,
+        country_claim_check: CountryClaimCheck,
+        country_finder: &'a CountryCodeFinder<'a>,
+    ) -> GossipAcceptorReal<'a> {
         let logger = Logger::new("GossipAcceptor");
         GossipAcceptorReal {
             gossip_handlers: vec![
@@ -1376,6 +1392,11 @@ impl<'a> GossipAcceptorReal<'a> {
                 Box::new(StandardGossipHandler::new(logger.clone())),
                 Box::new(RejectHandler::new()),
             ],
+            country_claim_verifier: CountryClaimVerifier::new(
+                country_claim_check,
+                country_finder,
+                logger.clone(),
+            ),
             cryptde,
             logger,
         }
@@ -1414,6 +1435,85 @@ impl<'a> GossipAcceptorReal<'a> {
     }
 }
 
+// Compares the countries Nodes claim to be in with the countries their IP addresses are in. Each
+// false claim is logged once, until the Node is found telling the truth again; with the check
+// enforced, the Node is also marked so that it isn't taken for an exit in the country it claims.
+struct CountryClaimVerifier<'a> {
+    check: CountryClaimCheck,
+    country_finder: &'a CountryCodeFinder<'a>,
+    reported_keys: RefCell<HashSet<PublicKey>>,
+    logger: Logger,
+}
+
+impl<'a> CountryClaimVerifier<'a> {
+    fn new(
+        check: CountryClaimCheck,
+        country_finder: &'a CountryCodeFinder<'a>,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            check,
+            country_finder,
+            reported_keys: RefCell::new(HashSet::new()),
+            logger,
+        }
+    }
+
+    // Keys of the Nodes whose claims could be checked, each paired with whether its claim is false
+    fn verify(&self, agrs: &[AccessibleGossipRecord]) -> Vec<(PublicKey, bool)> {
+        if self.check == CountryClaimCheck::Off {
+            return vec![];
+        }
+        agrs.iter()
+            .filter_map(|agr| {
+                let claimed_country_code = agr.inner.country_code_opt.as_ref()?;
+                let ip_addr = agr.node_addr_opt.as_ref()?.ip_addr();
+                let actual_country = self.country_finder.find_country(ip_addr)?;
+                let key = &agr.inner.public_key;
+                let refuted = !actual_country
+                    .iso3166
+                    .eq_ignore_ascii_case(claimed_country_code);
+                let mut reported_keys = self.reported_keys.borrow_mut();
+                if !refuted {
+                    reported_keys.remove(key);
+                } else if reported_keys.insert(key.clone()) {
+                    warning!(
+                        self.logger,
+                        "Node {} claims to be in {}, but its IP address {} is in {}",
+                        key,
+                        claimed_country_code,
+                        ip_addr,
+                        actual_country.iso3166
+                    );
+                }
+                Some((key.clone(), refuted))
+            })
+            .collect()
+    }
+
+    fn apply(
+        &self,
+        database: &mut NeighborhoodDatabase,
+        verdicts: Vec<(PublicKey, bool)>,
+        user_exit_preferences_opt: Option<&UserExitPreferences>,
+    ) {
+        if self.check != CountryClaimCheck::Enforce {
+            return;
+        }
+        verdicts.into_iter().for_each(|(key, refuted)| {
+            if let Some(node_record) = database.node_by_key_mut(&key) {
+                if node_record.metadata.country_claim_refuted != refuted {
+                    node_record.metadata.country_claim_refuted = refuted;
+                    node_record.metadata.country_undesirability = ZERO_UNDESIRABILITY;
+                    if let Some(user_exit_preferences) = user_exit_preferences_opt {
+                        user_exit_preferences.assign_nodes_country_undesirability(node_record)
+                    }
+                }
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1435,6 +1535,7 @@ mod tests {
     use crate::test_utils::unshared_test_utils::make_cpm_recipient;
     use crate::test_utils::{assert_contains, main_cryptde, vec_to_set};
     use actix::System;
+    use ip_country_lib::country_finder::COUNTRY_CODE_FINDER;
     use itertools::Itertools;
     use masq_lib::messages::ExitLocation;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
@@ -4217,6 +4318,168 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enforced_country_claim_check_marks_node_with_false_claim_and_logs_it() {
+        init_test_logging();
+        let test_name = "enforced_country_claim_check_marks_node_with_false_claim_and_logs_it";
+        let root_node = make_node_record(1234, true);
+        let root_node_cryptde = CryptDENull::from(&root_node.public_key(), TEST_DEFAULT_CHAIN);
+        let mut dest_db = db_from_node(&root_node);
+        let (gossip, debut_node, gossip_source) = make_false_claim_debut(2345);
+        let mut subject = GossipAcceptorReal::new(
+            &root_node_cryptde,
+            CountryClaimCheck::Enforce,
+            &COUNTRY_CODE_FINDER,
+        );
+        subject.country_claim_verifier.logger = Logger::new(test_name);
+
+        subject.handle(
+            &mut dest_db,
+            gossip.try_into().unwrap(),
+            gossip_source,
+            make_default_neighborhood_metadata(),
+        );
+
+        let debut_node_record = dest_db.node_by_key(debut_node.public_key()).unwrap();
+        assert_eq!(debut_node_record.metadata.country_claim_refuted, true);
+        assert_eq!(debut_node_record.trusted_country_code_opt(), None);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Node {} claims to be in AQ, but its IP address 2.3.4.5 is in {}",
+            test_name,
+            debut_node.public_key(),
+            actual_country_code(&debut_node)
+        ));
+    }
+
+    #[test]
+    fn logged_country_claim_check_logs_false_claim_without_marking_node() {
+        init_test_logging();
+        let test_name = "logged_country_claim_check_logs_false_claim_without_marking_node";
+        let root_node = make_node_record(1234, true);
+        let root_node_cryptde = CryptDENull::from(&root_node.public_key(), TEST_DEFAULT_CHAIN);
+        let mut dest_db = db_from_node(&root_node);
+        let (gossip, debut_node, gossip_source) = make_false_claim_debut(2345);
+        let mut subject = GossipAcceptorReal::new(
+            &root_node_cryptde,
+            CountryClaimCheck::Log,
+            &COUNTRY_CODE_FINDER,
+        );
+        subject.country_claim_verifier.logger = Logger::new(test_name);
+
+        subject.handle(
+            &mut dest_db,
+            gossip.try_into().unwrap(),
+            gossip_source,
+            make_default_neighborhood_metadata(),
+        );
+
+        let debut_node_record = dest_db.node_by_key(debut_node.public_key()).unwrap();
+        assert_eq!(debut_node_record.metadata.country_claim_refuted, false);
+        assert_eq!(
+            debut_node_record.trusted_country_code_opt(),
+            Some(&"AQ".to_string())
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Node {} claims to be in AQ",
+            test_name,
+            debut_node.public_key()
+        ));
+    }
+
+    #[test]
+    fn country_claim_check_turned_off_ignores_false_claim() {
+        init_test_logging();
+        let test_name = "country_claim_check_turned_off_ignores_false_claim";
+        let root_node = make_node_record(1234, true);
+        let root_node_cryptde = CryptDENull::from(&root_node.public_key(), TEST_DEFAULT_CHAIN);
+        let mut dest_db = db_from_node(&root_node);
+        let (gossip, debut_node, gossip_source) = make_false_claim_debut(2345);
+        let mut subject = GossipAcceptorReal::new(
+            &root_node_cryptde,
+            CountryClaimCheck::Off,
+            &COUNTRY_CODE_FINDER,
+        );
+        subject.country_claim_verifier.logger = Logger::new(test_name);
+
+        subject.handle(
+            &mut dest_db,
+            gossip.try_into().unwrap(),
+            gossip_source,
+            make_default_neighborhood_metadata(),
+        );
+
+        let debut_node_record = dest_db.node_by_key(debut_node.public_key()).unwrap();
+        assert_eq!(debut_node_record.metadata.country_claim_refuted, false);
+        TestLogHandler::new().exists_no_log_containing(&format!("WARN: {}", test_name));
+    }
+
+    #[test]
+    fn country_claim_verifier_reports_false_claim_once_until_node_tells_the_truth() {
+        let subject = CountryClaimVerifier::new(
+            CountryClaimCheck::Log,
+            &COUNTRY_CODE_FINDER,
+            Logger::new("test"),
+        );
+        let truthful_node = make_node_record(2345, true);
+        let mut lying_node = truthful_node.clone();
+        lying_node.inner.country_code_opt = Some("AQ".to_string());
+        let key = truthful_node.public_key().clone();
+
+        let first_verdicts = subject.verify(&[AccessibleGossipRecord::from(&lying_node)]);
+        let reported_after_first = subject.reported_keys.borrow().contains(&key);
+        let second_verdicts = subject.verify(&[AccessibleGossipRecord::from(&lying_node)]);
+        let reported_after_second = subject.reported_keys.borrow().contains(&key);
+        let truthful_verdicts = subject.verify(&[AccessibleGossipRecord::from(&truthful_node)]);
+        let reported_after_truth = subject.reported_keys.borrow().contains(&key);
+
+        assert_eq!(first_verdicts, vec![(key.clone(), true)]);
+        assert_eq!(reported_after_first, true);
+        assert_eq!(second_verdicts, vec![(key.clone(), true)]);
+        assert_eq!(reported_after_second, true);
+        assert_eq!(truthful_verdicts, vec![(key, false)]);
+        assert_eq!(reported_after_truth, false);
+    }
+
+    #[test]
+    fn country_claim_verifier_skips_nodes_without_ip_address_or_claim() {
+        let subject = CountryClaimVerifier::new(
+            CountryClaimCheck::Enforce,
+            &COUNTRY_CODE_FINDER,
+            Logger::new("test"),
+        );
+        let mut no_ip_node = make_node_record(2345, false);
+        no_ip_node.inner.country_code_opt = Some("AQ".to_string());
+        let mut no_claim_node = make_node_record(3456, true);
+        no_claim_node.inner.country_code_opt = None;
+
+        let result = subject.verify(&[
+            AccessibleGossipRecord::from(&no_ip_node),
+            AccessibleGossipRecord::from(&no_claim_node),
+        ]);
+
+        assert_eq!(result, vec![]);
+    }
+
+    fn make_false_claim_debut(n: u16) -> (Gossip_0v1, NodeRecord, SocketAddr) {
+        let mut debut_node = make_node_record(n, true);
+        debut_node.inner.country_code_opt = Some("AQ".to_string());
+        debut_node.resign();
+        let src_db = db_from_node(&debut_node);
+        let gossip = GossipBuilder::new(&src_db)
+            .node(debut_node.public_key(), true)
+            .build();
+        let gossip_source = debut_node.node_addr_opt().unwrap().into();
+        (gossip, debut_node, gossip_source)
+    }
+
+    fn actual_country_code(node: &NodeRecord) -> String {
+        COUNTRY_CODE_FINDER
+            .find_country(node.node_addr_opt().unwrap().ip_addr())
+            .unwrap()
+            .iso3166
+            .clone()
+    }
+
     #[test]
     fn make_debut_triple_doesnt_like_record_with_no_node_addr() {
         let root_node = make_node_record(1234, true);
@@ -4538,7 +4801,7 @@ mod tests {
     }
 
     fn make_subject(crypt_de: &dyn CryptDE) -> GossipAcceptorReal {
-        GossipAcceptorReal::new(crypt_de)
+        GossipAcceptorReal::new(crypt_de, CountryClaimCheck::Log, &COUNTRY_CODE_FINDER)
     }
 
     fn assert_node_records_eq(actual: &NodeRecord, expected: &NodeRecord, before: u32, after: u32) {
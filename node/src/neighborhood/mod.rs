@@ -1,6 +1,8 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 pub mod dot_graph;
+pub mod exit_failover;
+pub mod exit_options;
 pub mod gossip;
 pub mod gossip_acceptor;
 pub mod gossip_producer;
@@ -15,6 +17,8 @@ use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
 use crate::db_config::persistent_configuration::{
     PersistentConfigError, PersistentConfiguration, PersistentConfigurationReal,
 };
+use crate::neighborhood::exit_failover::{ExitCountryChange, ExitFailoverPolicy};
+use crate::neighborhood::exit_options::ExitOptionsCache;
 use crate::neighborhood::gossip::{AccessibleGossipRecord, DotGossipEndpoint, Gossip_0v1};
 use crate::neighborhood::gossip_acceptor::GossipAcceptanceResult;
 use crate::neighborhood::node_location::get_node_location;
@@ -22,12 +26,20 @@ use crate::neighborhood::overall_connection_status::{
     OverallConnectionStage, OverallConnectionStatus,
 };
 use crate::stream_messages::RemovedStreamType;
+use crate::sub_lib::accountant::{
+    PaymentNotice_0v1, ReportCreditorReachabilityMessage, ReportCreditorThresholdsMessage,
+    ReportExitFailoverMessage, ReportPaymentNoticeMessage, ReportUsageInvoiceMessage,
+    UsageInvoice_0v1,
+};
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::dispatcher::{Component, StreamShutdownMsg};
 use crate::sub_lib::hopper::{ExpiredCoresPackage, NoLookupIncipientCoresPackage};
 use crate::sub_lib::hopper::{IncipientCoresPackage, MessageType};
 use crate::sub_lib::neighborhood::RouteQueryResponse;
+use crate::sub_lib::neighborhood::SendPaymentNoticeMessage;
+use crate::sub_lib::neighborhood::SendUsageInvoiceMessage;
+use crate::sub_lib::neighborhood::ShutdownBarrierMessage;
 use crate::sub_lib::neighborhood::UpdateNodeRecordMetadataMessage;
 use crate::sub_lib::neighborhood::{AskAboutDebutGossipMessage, NodeDescriptor};
 use crate::sub_lib::neighborhood::{ConfigChange, RemoveNeighborMessage};
@@ -35,6 +47,7 @@ use crate::sub_lib::neighborhood::{ConfigChangeMsg, RouteQueryMessage};
 use crate::sub_lib::neighborhood::{ConnectionProgressEvent, ExpectedServices};
 use crate::sub_lib::neighborhood::{ConnectionProgressMessage, ExpectedService};
 use crate::sub_lib::neighborhood::{DispatcherNodeQueryMessage, GossipFailure_0v1};
+use crate::sub_lib::neighborhood::{ExitOptionsQuery_0v1, ExitOptionsReport_0v1};
 use crate::sub_lib::neighborhood::{Hops, NeighborhoodMetadata, NodeQueryResponseMetadata};
 use crate::sub_lib::neighborhood::{NRMetadataChange, NodeQueryMessage};
 use crate::sub_lib::neighborhood::{NeighborhoodSubs, NeighborhoodTools};
@@ -58,6 +71,7 @@ use gossip_acceptor::GossipAcceptor;
 use gossip_acceptor::GossipAcceptorReal;
 use gossip_producer::GossipProducer;
 use gossip_producer::GossipProducerReal;
+use ip_country_lib::country_finder::COUNTRY_CODE_FINDER;
 use itertools::Itertools;
 use masq_lib::blockchains::chains::Chain;
 use masq_lib::constants::{EXIT_COUNTRY_MISSING_COUNTRIES_ERROR, PAYLOAD_ZERO_SIZE};
@@ -69,17 +83,19 @@ use masq_lib::messages::{
     UiSetExitLocationRequest, UiSetExitLocationResponse,
 };
 use masq_lib::messages::{UiConnectionStatusResponse, UiShutdownRequest};
+use masq_lib::messages::{UiExitOptionsRequest, UiExitOptionsResponse};
 use masq_lib::ui_gateway::MessagePath::Conversation;
 use masq_lib::ui_gateway::{MessageBody, MessageTarget, NodeFromUiMessage, NodeToUiMessage};
 use masq_lib::utils::{exit_process, ExpectValue, NeighborhoodModeLight};
 use neighborhood_database::NeighborhoodDatabase;
 use node_record::NodeRecord;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
 use std::string::ToString;
+use std::time::SystemTime;
 
 pub const CRASH_KEY: &str = "NEIGHBORHOOD";
 pub const DEFAULT_MIN_HOPS: Hops = Hops::ThreeHops;
@@ -96,6 +112,17 @@ pub struct Neighborhood {
     hopper_opt: Option<Recipient<IncipientCoresPackage>>,
     hopper_no_lookup_opt: Option<Recipient<NoLookupIncipientCoresPackage>>,
     connected_signal_opt: Option<Recipient<StartMessage>>,
+    creditor_thresholds_sub_opt: Option<Recipient<ReportCreditorThresholdsMessage>>,
+    creditor_reachability_sub_opt: Option<Recipient<ReportCreditorReachabilityMessage>>,
+    // Earning wallets last reported reachable; None unless the Accountant holds payables to
+    // unreachable creditors
+    creditor_reachability_opt: Option<HashSet<Wallet>>,
+    exit_failover_sub_opt: Option<Recipient<ReportExitFailoverMessage>>,
+    payment_notice_sub_opt: Option<Recipient<ReportPaymentNoticeMessage>>,
+    usage_invoice_sub_opt: Option<Recipient<ReportUsageInvoiceMessage>>,
+    // Nodes that have sent us notice of payment, keyed by the wallets they paid from; that's
+    // where invoices to those wallets go
+    debtor_nodes: HashMap<Wallet, PublicKey>,
     node_to_ui_recipient_opt: Option<Recipient<NodeToUiMessage>>,
     gossip_acceptor: Box<dyn GossipAcceptor>,
     gossip_producer: Box<dyn GossipProducer>,
@@ -114,6 +141,8 @@ pub struct Neighborhood {
     logger: Logger,
     tools: NeighborhoodTools,
     user_exit_preferences: UserExitPreferences,
+    exit_options_cache: ExitOptionsCache,
+    exit_failover: ExitFailoverPolicy,
 }
 
 impl Actor for Neighborhood {
@@ -225,6 +254,78 @@ impl Handler<ExpiredCoresPackage<GossipFailure_0v1>> for Neighborhood {
     }
 }
 
+impl Handler<ExpiredCoresPackage<PaymentNotice_0v1>> for Neighborhood {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ExpiredCoresPackage<PaymentNotice_0v1>,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_payment_notice(msg.immediate_neighbor, msg.payload);
+    }
+}
+
+impl Handler<SendPaymentNoticeMessage> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: SendPaymentNoticeMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_send_payment_notice(msg.notice);
+    }
+}
+
+impl Handler<ExpiredCoresPackage<UsageInvoice_0v1>> for Neighborhood {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ExpiredCoresPackage<UsageInvoice_0v1>,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_usage_invoice(msg.immediate_neighbor, msg.payload);
+    }
+}
+
+impl Handler<SendUsageInvoiceMessage> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: SendUsageInvoiceMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_send_usage_invoice(msg.invoice);
+    }
+}
+
+impl Handler<ExpiredCoresPackage<ExitOptionsQuery_0v1>> for Neighborhood {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ExpiredCoresPackage<ExitOptionsQuery_0v1>,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_exit_options_query(msg.immediate_neighbor, msg.payload);
+    }
+}
+
+impl Handler<ExpiredCoresPackage<ExitOptionsReport_0v1>> for Neighborhood {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ExpiredCoresPackage<ExitOptionsReport_0v1>,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_exit_options_report(msg.immediate_neighbor, msg.payload);
+    }
+}
+
+impl Handler<ShutdownBarrierMessage> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: ShutdownBarrierMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_shutdown_barrier(msg.client_id);
+    }
+}
+
 impl Handler<RemoveNeighborMessage> for Neighborhood {
     type Result = ();
 
@@ -235,6 +336,7 @@ impl Handler<RemoveNeighborMessage> for Neighborhood {
             Ok(db_changed) => {
                 if db_changed {
                     self.gossip_to_neighbors();
+                    self.report_creditor_reachability();
                     info!(
                         self.logger,
                         "removed neighbor by public key: {}", public_key
@@ -358,14 +460,16 @@ impl Handler<StreamShutdownMsg> for Neighborhood {
 impl Handler<NodeFromUiMessage> for Neighborhood {
     type Result = ();
 
-    fn handle(&mut self, msg: NodeFromUiMessage, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, msg: NodeFromUiMessage, ctx: &mut Self::Context) -> Self::Result {
         let client_id = msg.client_id;
         if let Ok((message, context_id)) = UiSetExitLocationRequest::fmb(msg.body.clone()) {
             self.handle_exit_location_message(message, client_id, context_id);
         } else if let Ok((_, context_id)) = UiConnectionStatusRequest::fmb(msg.body.clone()) {
             self.handle_connection_status_message(client_id, context_id);
+        } else if let Ok((_, context_id)) = UiExitOptionsRequest::fmb(msg.body.clone()) {
+            self.handle_exit_options_request(client_id, context_id);
         } else if let Ok((body, _)) = UiShutdownRequest::fmb(msg.body.clone()) {
-            self.handle_shutdown_order(client_id, body);
+            self.handle_shutdown_order(client_id, body, ctx);
         } else {
             handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
         }
@@ -391,12 +495,17 @@ impl Neighborhood {
                 "A zero-hop MASQ Node is not decentralized and cannot have a --neighbors setting"
             )
         }
-        let neighborhood_database = NeighborhoodDatabase::new(
+        let mut neighborhood_database = NeighborhoodDatabase::new(
             cryptde.public_key(),
             neighborhood_mode.clone(),
             config.earning_wallet.clone(),
             cryptde,
         );
+        if let Some(payment_thresholds) = config.payment_thresholds_opt {
+            let root = neighborhood_database.root_mut();
+            root.set_payment_thresholds_opt(Some(payment_thresholds));
+            root.regenerate_signed_gossip(cryptde);
+        }
         let is_mainnet = config.blockchain_bridge_config.chain.is_mainnet();
         let initial_neighbors: Vec<NodeDescriptor> = neighbor_configs
             .iter()
@@ -414,14 +523,35 @@ impl Neighborhood {
             .collect_vec();
 
         let overall_connection_status = OverallConnectionStatus::new(initial_neighbors);
+        let mut user_exit_preferences = UserExitPreferences::new();
+        if !config.exit_locations.is_empty() {
+            user_exit_preferences.fallback_preference = FallbackPreference::ExitCountryWithFallback;
+            user_exit_preferences.exit_countries = config
+                .exit_locations
+                .iter()
+                .flat_map(|location| location.country_codes.iter().cloned())
+                .collect();
+            user_exit_preferences.locations_opt = Some(config.exit_locations.clone());
+        }
 
         Neighborhood {
             cryptde,
             hopper_opt: None,
             hopper_no_lookup_opt: None,
             connected_signal_opt: None,
+            creditor_thresholds_sub_opt: None,
+            creditor_reachability_sub_opt: None,
+            creditor_reachability_opt: config.unreachable_creditor_hold_opt.map(|_| HashSet::new()),
+            exit_failover_sub_opt: None,
+            payment_notice_sub_opt: None,
+            usage_invoice_sub_opt: None,
+            debtor_nodes: HashMap::new(),
             node_to_ui_recipient_opt: None,
-            gossip_acceptor: Box::new(GossipAcceptorReal::new(cryptde)),
+            gossip_acceptor: Box::new(GossipAcceptorReal::new(
+                cryptde,
+                config.country_claim_check,
+                &COUNTRY_CODE_FINDER,
+            )),
             gossip_producer: Box::new(GossipProducerReal::new()),
             neighborhood_database,
             consuming_wallet_opt: config.consuming_wallet_opt.clone(),
@@ -437,7 +567,9 @@ impl Neighborhood {
             db_password_opt: config.db_password_opt.clone(),
             logger: Logger::new("Neighborhood"),
             tools: NeighborhoodTools::default(),
-            user_exit_preferences: UserExitPreferences::new(),
+            user_exit_preferences,
+            exit_options_cache: ExitOptionsCache::default(),
+            exit_failover: ExitFailoverPolicy::default(),
         }
     }
 
@@ -454,6 +586,21 @@ impl Neighborhood {
             gossip_failure: addr
                 .clone()
                 .recipient::<ExpiredCoresPackage<GossipFailure_0v1>>(),
+            payment_notice: addr
+                .clone()
+                .recipient::<ExpiredCoresPackage<PaymentNotice_0v1>>(),
+            send_payment_notice: addr.clone().recipient::<SendPaymentNoticeMessage>(),
+            usage_invoice: addr
+                .clone()
+                .recipient::<ExpiredCoresPackage<UsageInvoice_0v1>>(),
+            send_usage_invoice: addr.clone().recipient::<SendUsageInvoiceMessage>(),
+            exit_options_query: addr
+                .clone()
+                .recipient::<ExpiredCoresPackage<ExitOptionsQuery_0v1>>(),
+            exit_options_report: addr
+                .clone()
+                .recipient::<ExpiredCoresPackage<ExitOptionsReport_0v1>>(),
+            shutdown_barrier: addr.clone().recipient::<ShutdownBarrierMessage>(),
             dispatcher_node_query: addr.clone().recipient::<DispatcherNodeQueryMessage>(),
             remove_neighbor: addr.clone().recipient::<RemoveNeighborMessage>(),
             config_change_msg_sub: addr.clone().recipient::<ConfigChangeMsg>(),
@@ -559,12 +706,7 @@ impl Neighborhood {
                 if let Some(exit_locations_by_priority) =
                     self.user_exit_preferences.locations_opt.clone()
                 {
-                    for exit_location in &exit_locations_by_priority {
-                        self.synchronize_exit_countries_and_return_missing(
-                            &exit_location.country_codes,
-                        );
-                    }
-                    self.set_country_undesirability_and_exit_countries(&exit_locations_by_priority);
+                    self.reroute_to_exit_locations(&exit_locations_by_priority);
                 }
                 self.search_for_a_new_route();
             }
@@ -572,6 +714,9 @@ impl Neighborhood {
                 info!(self.logger, "DB Password has been updated.");
                 self.db_password_opt = Some(new_password);
             }
+            ConfigChange::UpdatePaymentThresholds(_) | ConfigChange::UpdateScanIntervals(_) => {
+                trace!(self.logger, "Ignored irrelevant message: {:?}", msg);
+            }
         }
     }
 
@@ -750,10 +895,427 @@ impl Neighborhood {
         gossip_source: SocketAddr,
         cpm_recipient: Recipient<ConnectionProgressMessage>,
     ) {
+        let advertising_keys = Self::keys_advertising_payment_thresholds(&agrs);
         let neighbor_keys_before = self.neighbor_keys();
         self.handle_agrs(agrs, gossip_source, cpm_recipient);
         let neighbor_keys_after = self.neighbor_keys();
         self.handle_database_changes(neighbor_keys_before, neighbor_keys_after);
+        self.report_creditor_thresholds(advertising_keys);
+        self.report_creditor_reachability();
+        self.reevaluate_exit_country();
+    }
+
+    fn keys_advertising_payment_thresholds(agrs: &[AccessibleGossipRecord]) -> Vec<PublicKey> {
+        agrs.iter()
+            .filter(|agr| agr.inner.payment_thresholds_opt.is_some())
+            .map(|agr| agr.inner.public_key.clone())
+            .collect()
+    }
+
+    // Only what the GossipAcceptor let into the database is passed on; thresholds in Gossip
+    // it rejected never reach the Accountant
+    fn report_creditor_thresholds(&self, advertising_keys: Vec<PublicKey>) {
+        let root_key = self.neighborhood_database.root_key();
+        let creditor_thresholds = advertising_keys
+            .iter()
+            .filter(|key| *key != root_key)
+            .flat_map(|key| self.neighborhood_database.node_by_key(key))
+            .flat_map(|node| {
+                node.payment_thresholds_opt()
+                    .map(|payment_thresholds| (node.earning_wallet(), *payment_thresholds))
+            })
+            .collect_vec();
+        if creditor_thresholds.is_empty() {
+            return;
+        }
+        debug!(
+            self.logger,
+            "Reporting payment thresholds advertised by {} creditors",
+            creditor_thresholds.len()
+        );
+        self.creditor_thresholds_sub_opt
+            .as_ref()
+            .expect("Accountant is unbound")
+            .try_send(ReportCreditorThresholdsMessage {
+                creditor_thresholds,
+            })
+            .expect("Accountant is dead");
+    }
+
+    // Only changes are reported: wallets of Nodes that have come into the Neighborhood since the
+    // last report, and wallets of Nodes that have dropped out of it or been culled
+    fn report_creditor_reachability(&mut self) {
+        let previously_reachable = match self.creditor_reachability_opt.as_ref() {
+            Some(previously_reachable) => previously_reachable,
+            None => return,
+        };
+        let root_key = self.neighborhood_database.root_key();
+        let reachable_now: HashSet<Wallet> = self
+            .neighborhood_database
+            .referenced_node_keys()
+            .iter()
+            .filter(|key| *key != root_key)
+            .flat_map(|key| self.neighborhood_database.node_by_key(key))
+            .map(|node| node.earning_wallet())
+            .collect();
+        let reachable = reachable_now
+            .difference(previously_reachable)
+            .cloned()
+            .collect_vec();
+        let unreachable = previously_reachable
+            .difference(&reachable_now)
+            .cloned()
+            .collect_vec();
+        if reachable.is_empty() && unreachable.is_empty() {
+            return;
+        }
+        debug!(
+            self.logger,
+            "Reporting {} creditors newly reachable and {} newly unreachable",
+            reachable.len(),
+            unreachable.len()
+        );
+        self.creditor_reachability_sub_opt
+            .as_ref()
+            .expect("Accountant is unbound")
+            .try_send(ReportCreditorReachabilityMessage {
+                timestamp: SystemTime::now(),
+                reachable,
+                unreachable,
+            })
+            .expect("Accountant is dead");
+        self.creditor_reachability_opt = Some(reachable_now);
+    }
+
+    // When the active exit country drops out of the Neighborhood, routes move on to the next
+    // preferred country that has exit Nodes, and the Accountant learns whose debts to favor there
+    fn reevaluate_exit_country(&mut self) {
+        let exit_locations_by_priority = self.get_locations_opt();
+        if exit_locations_by_priority.is_empty() {
+            self.exit_failover.reset();
+            return;
+        }
+        let change = self.exit_failover.reevaluate(
+            &exit_locations_by_priority,
+            &self.user_exit_preferences.db_countries,
+        );
+        match change {
+            ExitCountryChange::Unchanged => (),
+            ExitCountryChange::Selected {
+                previous_opt,
+                country,
+            } => {
+                match previous_opt {
+                    Some(previous) => info!(
+                        self.logger,
+                        "Exit country {} is no longer the most preferred one available; failing over to {}",
+                        previous,
+                        country
+                    ),
+                    None => info!(self.logger, "Exit country {} is available", country),
+                }
+                self.reroute_to_exit_locations(&exit_locations_by_priority);
+                self.report_exit_failover(country);
+            }
+            ExitCountryChange::Lost { previous } => {
+                warning!(
+                    self.logger,
+                    "Exit country {} is no longer available, and neither is any other preferred one",
+                    previous
+                );
+                self.reroute_to_exit_locations(&exit_locations_by_priority);
+            }
+        }
+    }
+
+    fn reroute_to_exit_locations(&mut self, exit_locations_by_priority: &Vec<ExitLocation>) {
+        for exit_location in exit_locations_by_priority {
+            self.synchronize_exit_countries_and_return_missing(&exit_location.country_codes);
+        }
+        self.set_country_undesirability_and_exit_countries(exit_locations_by_priority);
+    }
+
+    fn report_exit_failover(&self, exit_country: String) {
+        let root_key = self.neighborhood_database.root_key();
+        let creditors = self
+            .find_exit_locations(root_key, self.min_hops as usize)
+            .into_iter()
+            .flat_map(|key| self.neighborhood_database.node_by_key(key))
+            .filter(|node| node.trusted_country_code_opt() == Some(&exit_country))
+            .map(|node| node.earning_wallet())
+            .unique()
+            .collect_vec();
+        self.exit_failover_sub_opt
+            .as_ref()
+            .expect("Accountant is unbound")
+            .try_send(ReportExitFailoverMessage {
+                timestamp: SystemTime::now(),
+                exit_country,
+                creditors,
+            })
+            .expect("Accountant is dead");
+    }
+
+    // The creditor's Node is found by its earning wallet; a notice to a creditor that is not in
+    // the Neighborhood, or whose Node is not reachable directly, is dropped
+    fn handle_send_payment_notice(&self, notice: PaymentNotice_0v1) {
+        let root_key = self.neighborhood_database.root_key();
+        let creditor_address = notice.creditor_wallet.address();
+        let target_opt = self
+            .neighborhood_database
+            .keys()
+            .into_iter()
+            .filter(|key| *key != root_key)
+            .flat_map(|key| self.neighborhood_database.node_by_key(key))
+            .find(|node| {
+                node.earning_wallet().address() == creditor_address
+                    && node.node_addr_opt().is_some()
+            });
+        let (target_key, target_node_addr) = match target_opt {
+            Some(node) => (
+                node.public_key().clone(),
+                node.node_addr_opt().expect("NodeAddr disappeared"),
+            ),
+            None => {
+                debug!(
+                    self.logger,
+                    "Dropping notice of payment {:?} to {}: no reachable Node earns into that wallet",
+                    notice.tx_hash,
+                    notice.creditor_wallet
+                );
+                return;
+            }
+        };
+        debug!(
+            self.logger,
+            "Sending notice of payment {:?} to {} at {}",
+            notice.tx_hash,
+            target_key,
+            target_node_addr
+        );
+        self.send_no_lookup_package(
+            MessageType::PaymentNotice(VersionedData::new(
+                &crate::sub_lib::migrations::payment_notice::MIGRATIONS,
+                &notice,
+            )),
+            &target_key,
+            &target_node_addr,
+        );
+    }
+
+    fn handle_payment_notice(&mut self, source: SocketAddr, notice: PaymentNotice_0v1) {
+        debug!(
+            self.logger,
+            "Received notice of payment {:?} from {}", notice.tx_hash, source
+        );
+        if let Some((debtor_key, _)) = self.neighbor_at(source) {
+            self.debtor_nodes
+                .insert(notice.payer.wallet.as_address_wallet(), debtor_key);
+        }
+        self.payment_notice_sub_opt
+            .as_ref()
+            .expect("Accountant is unbound")
+            .try_send(ReportPaymentNoticeMessage { notice })
+            .expect("Accountant is dead");
+    }
+
+    // The debtor's Node is the one that last sent us notice of a payment from the debtor wallet;
+    // an invoice to a debtor we've heard nothing from, or whose Node is no longer reachable
+    // directly, is dropped
+    fn handle_send_usage_invoice(&self, mut invoice: UsageInvoice_0v1) {
+        let target_opt = self
+            .debtor_nodes
+            .get(&invoice.debtor_wallet.as_address_wallet())
+            .and_then(|key| self.neighborhood_database.node_by_key(key))
+            .and_then(|node| {
+                node.node_addr_opt()
+                    .map(|node_addr| (node.public_key().clone(), node_addr))
+            });
+        let (target_key, target_node_addr) = match target_opt {
+            Some(target) => target,
+            None => {
+                debug!(
+                    self.logger,
+                    "Dropping invoice to {}: no reachable Node has paid from that wallet",
+                    invoice.debtor_wallet
+                );
+                return;
+            }
+        };
+        if let Err(e) = invoice.sign(self.cryptde) {
+            error!(
+                self.logger,
+                "Dropping invoice to {}: cannot sign it: {:?}", invoice.debtor_wallet, e
+            );
+            return;
+        }
+        debug!(
+            self.logger,
+            "Sending invoice to {} at {} for {} routing and {} exit services",
+            target_key,
+            target_node_addr,
+            invoice.routing_services,
+            invoice.exit_services
+        );
+        self.send_no_lookup_package(
+            MessageType::UsageInvoice(VersionedData::new(
+                &crate::sub_lib::migrations::usage_invoice::MIGRATIONS,
+                &invoice,
+            )),
+            &target_key,
+            &target_node_addr,
+        );
+    }
+
+    // Only the Node earning into the invoiced wallet may invoice us for it
+    fn handle_usage_invoice(&self, source: SocketAddr, invoice: UsageInvoice_0v1) {
+        let creditor_node_opt = self
+            .neighbor_at(source)
+            .and_then(|(key, _)| self.neighborhood_database.node_by_key(&key));
+        let creditor_node = match creditor_node_opt {
+            Some(node) => node,
+            None => {
+                warning!(
+                    self.logger,
+                    "Ignoring invoice from {}: no Node with that IP is in the database",
+                    source
+                );
+                return;
+            }
+        };
+        if creditor_node.earning_wallet().address() != invoice.creditor_wallet.address() {
+            warning!(
+                self.logger,
+                "Ignoring invoice for {} from {}: it earns into {}",
+                invoice.creditor_wallet,
+                creditor_node.public_key(),
+                creditor_node.earning_wallet()
+            );
+            return;
+        }
+        if !invoice.is_signed_by(self.cryptde, creditor_node.public_key()) {
+            warning!(
+                self.logger,
+                "Ignoring invoice for {} from {}: bad signature",
+                invoice.creditor_wallet,
+                creditor_node.public_key()
+            );
+            return;
+        }
+        self.usage_invoice_sub_opt
+            .as_ref()
+            .expect("Accountant is unbound")
+            .try_send(ReportUsageInvoiceMessage { invoice })
+            .expect("Accountant is dead");
+    }
+
+    // The exit countries we advertise are our own, if it's known, and those we can route to
+    fn handle_exit_options_query(&self, source: SocketAddr, query: ExitOptionsQuery_0v1) {
+        let (asker_key, asker_node_addr) = match self.neighbor_at(source) {
+            Some(neighbor) => neighbor,
+            None => {
+                debug!(
+                    self.logger,
+                    "Ignoring query of exit options from {}: no Node with that IP is in the database",
+                    source
+                );
+                return;
+            }
+        };
+        let mut exit_country_codes = self.user_exit_preferences.db_countries.clone();
+        if let Some(country_code) = self.neighborhood_database.root().trusted_country_code_opt() {
+            exit_country_codes.push(country_code.clone());
+        }
+        exit_country_codes.sort();
+        exit_country_codes.dedup();
+        let report = ExitOptionsReport_0v1 {
+            exit_country_codes,
+            query_sent_at_ms: query.sent_at_ms,
+        };
+        debug!(
+            self.logger,
+            "Reporting exit options {:?} to {}", report.exit_country_codes, asker_key
+        );
+        self.send_no_lookup_package(
+            MessageType::ExitOptionsReport(VersionedData::new(
+                &crate::sub_lib::migrations::exit_options_report::MIGRATIONS,
+                &report,
+            )),
+            &asker_key,
+            &asker_node_addr,
+        );
+    }
+
+    fn handle_exit_options_report(&mut self, source: SocketAddr, report: ExitOptionsReport_0v1) {
+        let reporter_key = match self.neighbor_at(source) {
+            Some((key, _)) => key,
+            None => {
+                debug!(
+                    self.logger,
+                    "Ignoring report of exit options from {}: no Node with that IP is in the database",
+                    source
+                );
+                return;
+            }
+        };
+        match self
+            .exit_options_cache
+            .record_report(&reporter_key, report, SystemTime::now())
+        {
+            Ok(record) => debug!(
+                self.logger,
+                "{} reports exit options {:?} with latency {}ms",
+                reporter_key,
+                record.exit_country_codes,
+                record.latency.as_millis()
+            ),
+            Err(e) => debug!(
+                self.logger,
+                "Ignoring report of exit options from {}: {}", reporter_key, e
+            ),
+        }
+    }
+
+    // The UI is answered from what the neighbors have reported so far, and the neighbors are
+    // probed afresh, so that a later request finds the options up to date
+    fn handle_exit_options_request(&mut self, client_id: u64, context_id: u64) {
+        let now = SystemTime::now();
+        let neighbor_keys = self.neighbor_keys();
+        let exit_countries = self.exit_options_cache.ranked_options(&neighbor_keys, now);
+        self.node_to_ui_recipient_opt
+            .as_ref()
+            .expect("UI Gateway is unbound")
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::ClientId(client_id),
+                body: UiExitOptionsResponse { exit_countries }.tmb(context_id),
+            })
+            .expect("UiGateway is dead");
+        let reachable_neighbors = neighbor_keys
+            .iter()
+            .sorted()
+            .flat_map(|key| self.neighborhood_database.node_by_key(key))
+            .flat_map(|node| {
+                node.node_addr_opt()
+                    .map(|node_addr| (node.public_key().clone(), node_addr))
+            })
+            .collect_vec();
+        for (key, node_addr) in reachable_neighbors {
+            let query = self.exit_options_cache.make_query(&key, now);
+            self.send_no_lookup_package(
+                MessageType::ExitOptionsQuery(VersionedData::new(
+                    &crate::sub_lib::migrations::exit_options_query::MIGRATIONS,
+                    &query,
+                )),
+                &key,
+                &node_addr,
+            );
+        }
+    }
+
+    fn neighbor_at(&self, source: SocketAddr) -> Option<(PublicKey, NodeAddr)> {
+        let node = self.neighborhood_database.node_by_ip(&source.ip())?;
+        let node_addr = node.node_addr_opt()?;
+        Some((node.public_key().clone(), node_addr))
     }
 
     fn neighbor_keys(&self) -> HashSet<PublicKey> {
@@ -1201,9 +1763,8 @@ impl Neighborhood {
     fn validate_country_code_when_fallback_routing(&self, last_node: &PublicKey) -> bool {
         let last_cc = match self.neighborhood_database.node_by_key(last_node) {
             Some(nr) => nr
-                .inner
-                .country_code_opt
-                .clone()
+                .trusted_country_code_opt()
+                .cloned()
                 .unwrap_or_else(|| "ZZ".to_string()),
             None => "ZZ".to_string(),
         };
@@ -1231,7 +1792,7 @@ impl Neighborhood {
             true // Zero- and single-hop routes are not subject to exit-too-close restrictions, when ExitLocation is not set, or we research neighborhood
         } else {
             if let Some(node_record) = self.neighborhood_database.node_by_key(last_node_key) {
-                if let Some(country_code) = &node_record.inner.country_code_opt {
+                if let Some(country_code) = node_record.trusted_country_code_opt() {
                     return self
                         .user_exit_preferences
                         .exit_countries
@@ -1638,6 +2199,11 @@ impl Neighborhood {
 
         if !message.show_countries {
             self.set_exit_locations_opt(&exit_locations_by_priority);
+            // The routes are set up for the user's choice right here; only what happens to the
+            // Neighborhood afterwards makes for a failover
+            let exit_locations = self.get_locations_opt();
+            self.exit_failover
+                .reevaluate(&exit_locations, &self.user_exit_preferences.db_countries);
         }
         match self.neighborhood_database.keys().len() > 1 {
             true => {
@@ -1882,7 +2448,7 @@ impl Neighborhood {
             for pub_key in exit_nodes {
                 let node_opt = self.neighborhood_database.node_by_key(pub_key);
                 if let Some(node_record) = node_opt {
-                    if let Some(cc) = &node_record.inner.country_code_opt {
+                    if let Some(cc) = node_record.trusted_country_code_opt() {
                         db_countries.push(cc.clone())
                     }
                 }
@@ -2015,7 +2581,10 @@ impl Neighborhood {
                     neighbor_key,
                     peer_addr.ip()
                 );
-                self.gossip_to_neighbors()
+                self.gossip_to_neighbors();
+                self.report_creditor_reachability();
+                self.user_exit_preferences.db_countries = self.init_db_countries();
+                self.reevaluate_exit_country();
             }
             Ok(false) => {
                 debug!(self.logger, "Received shutdown notification for {} at {}, but that Node is no neighbor - ignoring", neighbor_key, peer_addr.ip());
@@ -2023,8 +2592,28 @@ impl Neighborhood {
         };
     }
 
+    // The BlockchainBridge sees the same order and passes the barrier once every transaction it
+    // has submitted is recorded; if it never does, the Node goes down when the timeout expires
+    fn handle_shutdown_order(
+        &self,
+        client_id: u64,
+        _msg: UiShutdownRequest,
+        ctx: &mut Context<Neighborhood>,
+    ) {
+        info!(
+            self.logger,
+            "Received shutdown order from client {}: waiting for transactions in flight to be recorded",
+            client_id
+        );
+        self.tools.notify_later_shutdown_barrier.notify_later(
+            ShutdownBarrierMessage { client_id },
+            self.tools.shutdown_barrier_timeout,
+            ctx,
+        );
+    }
+
     #[allow(unreachable_code)]
-    fn handle_shutdown_order(&self, client_id: u64, _msg: UiShutdownRequest) {
+    fn handle_shutdown_barrier(&self, client_id: u64) {
         info!(
             self.logger,
             "Received shutdown order from client {}: shutting down hard", client_id
@@ -2059,7 +2648,19 @@ impl Neighborhood {
         self.hopper_opt = Some(msg.peer_actors.hopper.from_hopper_client);
         self.hopper_no_lookup_opt = Some(msg.peer_actors.hopper.from_hopper_client_no_lookup);
         self.connected_signal_opt = Some(msg.peer_actors.accountant.start);
+        self.creditor_thresholds_sub_opt =
+            Some(msg.peer_actors.accountant.report_creditor_thresholds);
+        self.creditor_reachability_sub_opt =
+            Some(msg.peer_actors.accountant.report_creditor_reachability);
+        self.exit_failover_sub_opt = Some(msg.peer_actors.accountant.report_exit_failover);
+        self.payment_notice_sub_opt = Some(msg.peer_actors.accountant.report_payment_notice);
+        self.usage_invoice_sub_opt = Some(msg.peer_actors.accountant.report_usage_invoice);
         self.node_to_ui_recipient_opt = Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
+        info!(
+            self.logger,
+            "Exit countries are determined by {}",
+            ip_country_lib::db_metadata()
+        );
     }
 }
 
@@ -2104,9 +2705,8 @@ impl UserExitPreferences {
 
     pub fn assign_nodes_country_undesirability(&self, node_record: &mut NodeRecord) {
         let country_code = node_record
-            .inner
-            .country_code_opt
-            .clone()
+            .trusted_country_code_opt()
+            .cloned()
             .unwrap_or_else(|| ZZ_COUNTRY_CODE_STRING.to_string());
         match &self.locations_opt {
             Some(exit_locations_by_priority) => {
@@ -2188,9 +2788,9 @@ mod tests {
         CountryGroups, ToMessageBody, UiConnectionChangeBroadcast, UiConnectionStage,
     };
     use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+    use masq_lib::ui_gateway::MessageBody;
     use masq_lib::ui_gateway::MessagePath::Conversation;
     use masq_lib::ui_gateway::MessageTarget;
-    use masq_lib::ui_gateway::MessageBody;
     use masq_lib::utils::running_test;
 
     use crate::db_config::persistent_configuration::PersistentConfigError;
@@ -2236,13 +2836,15 @@ mod tests {
     use crate::test_utils::{main_cryptde, make_paying_wallet};
 
     use super::*;
-    use crate::accountant::test_utils::bc_from_earning_wallet;
+    use crate::accountant::test_utils::{bc_from_earning_wallet, make_custom_payment_thresholds};
+    use crate::blockchain::test_utils::make_tx_hash;
     use crate::neighborhood::overall_connection_status::ConnectionStageErrors::{
         NoGossipResponseReceived, PassLoopFound, TcpConnectionFailed,
     };
     use crate::neighborhood::overall_connection_status::{
         ConnectionProgress, ConnectionStage, OverallConnectionStage,
     };
+    use crate::sub_lib::accountant::{PaymentThresholds, UnreachableCreditorHold};
     use crate::test_utils::unshared_test_utils::notify_handlers::NotifyLaterHandleMock;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
 
@@ -2312,6 +2914,32 @@ mod tests {
         assert_eq!(subject.db_patch_size, expected_db_patch_size);
     }
 
+    #[test]
+    fn node_with_refuted_country_claim_is_not_taken_for_an_exit_in_the_claimed_country() {
+        let mut user_exit_preferences = UserExitPreferences::new();
+        user_exit_preferences.exit_countries = vec!["CZ".to_string()];
+        user_exit_preferences.fallback_preference = FallbackPreference::ExitCountryNoFallback;
+        user_exit_preferences.locations_opt = Some(vec![ExitLocation {
+            country_codes: vec!["CZ".to_string()],
+            priority: 2,
+        }]);
+        let mut truthful_node = make_node_record_cc(3456, true, "CZ");
+        let mut refuted_node = make_node_record_cc(4567, true, "CZ");
+        refuted_node.metadata.country_claim_refuted = true;
+
+        user_exit_preferences.assign_nodes_country_undesirability(&mut truthful_node);
+        user_exit_preferences.assign_nodes_country_undesirability(&mut refuted_node);
+
+        assert_eq!(
+            truthful_node.metadata.country_undesirability,
+            COUNTRY_UNDESIRABILITY_FACTOR
+        );
+        assert_eq!(
+            refuted_node.metadata.country_undesirability,
+            UNREACHABLE_COUNTRY_PENALTY
+        );
+    }
+
     #[test]
     fn init_db_countries_works_properly() {
         let mut subject = make_standard_subject();
@@ -2339,7 +2967,6 @@ mod tests {
 
         subject
             .neighborhood_database
-
             .remove_arbitrary_half_neighbor(&root_node_key, second_neighbor.public_key());
         let emptied_db_countries = subject.init_db_countries();
 
@@ -2396,6 +3023,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bind_message_logs_the_provenance_of_the_country_data() {
+        init_test_logging();
+        let test_name = "bind_message_logs_the_provenance_of_the_country_data";
+        let mut subject =
+            neighborhood_from_nodes(&make_global_cryptde_node_record(1234, true), None);
+        subject.logger = Logger::new(test_name);
+
+        subject.handle_bind_message(BindMessage {
+            peer_actors: peer_actors_builder().build(),
+        });
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {}: Exit countries are determined by {}",
+            test_name,
+            ip_country_lib::db_metadata()
+        ));
+    }
+
     #[test]
     fn introduction_results_in_full_neighborship_in_debutant_db_and_enrich_db_countries_on_one_hop()
     {
@@ -2511,6 +3157,113 @@ mod tests {
         assert_eq!(root_node_record_ref.half_neighbor_keys().len(), 0);
     }
 
+    #[test]
+    fn node_tracks_creditor_reachability_only_if_the_accountant_holds_payables_for_it() {
+        let cryptde = main_cryptde();
+        let make_config = |unreachable_creditor_hold_opt| {
+            let mut bootstrapper_config = bc_from_nc_plus(
+                NeighborhoodConfig {
+                    mode: NeighborhoodMode::ZeroHop,
+                    min_hops: MIN_HOPS_FOR_TEST,
+                },
+                make_wallet("earning"),
+                None,
+                "node_tracks_creditor_reachability_only_if_the_accountant_holds_payables_for_it",
+            );
+            bootstrapper_config.unreachable_creditor_hold_opt = unreachable_creditor_hold_opt;
+            bootstrapper_config
+        };
+
+        let without_hold = Neighborhood::new(cryptde, &make_config(None));
+        let with_hold = Neighborhood::new(
+            cryptde,
+            &make_config(Some(UnreachableCreditorHold {
+                absence_sec: 3_600,
+                deadline_margin_sec: 600,
+            })),
+        );
+
+        assert_eq!(without_hold.creditor_reachability_opt, None);
+        assert_eq!(with_hold.creditor_reachability_opt, Some(HashSet::new()));
+    }
+
+    #[test]
+    fn node_takes_its_preferred_exit_locations_from_the_configuration() {
+        let cryptde = main_cryptde();
+        let mut bootstrapper_config = bc_from_nc_plus(
+            NeighborhoodConfig {
+                mode: NeighborhoodMode::ZeroHop,
+                min_hops: MIN_HOPS_FOR_TEST,
+            },
+            make_wallet("earning"),
+            None,
+            "node_takes_its_preferred_exit_locations_from_the_configuration",
+        );
+        let exit_locations = vec![
+            ExitLocation {
+                country_codes: vec!["CZ".to_string(), "SK".to_string()],
+                priority: 1,
+            },
+            ExitLocation {
+                country_codes: vec!["PL".to_string()],
+                priority: 2,
+            },
+        ];
+        bootstrapper_config.exit_locations = exit_locations.clone();
+
+        let subject = Neighborhood::new(cryptde, &bootstrapper_config);
+
+        assert_eq!(
+            subject.user_exit_preferences.exit_countries,
+            vec!["CZ".to_string(), "SK".to_string(), "PL".to_string()]
+        );
+        assert_eq!(
+            subject.user_exit_preferences.fallback_preference,
+            FallbackPreference::ExitCountryWithFallback
+        );
+        assert_eq!(
+            subject.user_exit_preferences.locations_opt,
+            Some(exit_locations)
+        );
+        assert_eq!(subject.exit_failover.active_country_opt(), None);
+    }
+
+    #[test]
+    fn node_advertises_its_payment_thresholds_in_its_own_node_record() {
+        let cryptde = main_cryptde();
+        let mut bootstrapper_config = bc_from_nc_plus(
+            NeighborhoodConfig {
+                mode: NeighborhoodMode::ZeroHop,
+                min_hops: MIN_HOPS_FOR_TEST,
+            },
+            make_wallet("earning"),
+            None,
+            "node_advertises_its_payment_thresholds_in_its_own_node_record",
+        );
+        bootstrapper_config.payment_thresholds_opt = Some(make_custom_payment_thresholds());
+
+        let subject = Neighborhood::new(cryptde, &bootstrapper_config);
+
+        let root_node_record_ref = subject.neighborhood_database.root();
+        assert_eq!(
+            root_node_record_ref.payment_thresholds_opt(),
+            Some(&make_custom_payment_thresholds())
+        );
+        assert!(cryptde.verify_signature(
+            root_node_record_ref.signed_gossip(),
+            root_node_record_ref.signature(),
+            cryptde.public_key()
+        ));
+        let signed_inner = NodeRecordInner_0v1::try_from(GossipNodeRecord::from(
+            AccessibleGossipRecord::from(root_node_record_ref),
+        ))
+        .unwrap();
+        assert_eq!(
+            signed_inner.payment_thresholds_opt,
+            Some(make_custom_payment_thresholds())
+        );
+    }
+
     #[test]
     fn node_with_originate_only_config_is_decentralized_with_neighbor_but_not_ip() {
         let cryptde: &dyn CryptDE = main_cryptde();
@@ -5245,176 +5998,77 @@ mod tests {
     }
 
     #[test]
-    fn neighborhood_sends_only_an_acceptance_debut_when_an_acceptance_debut_is_provided() {
-        let introduction_target_node = make_node_record(7345, true);
+    fn neighborhood_reports_payment_thresholds_of_accepted_nodes_to_accountant() {
         let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
-        let neighbor = make_node_record(1050, true);
-        let mut subject = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let mut accepted_node = make_node_record(2222, true);
+        accepted_node.set_payment_thresholds_opt(Some(make_custom_payment_thresholds()));
+        accepted_node.resign();
+        let mut rejected_node = make_node_record(3333, true);
+        rejected_node.set_payment_thresholds_opt(Some(PaymentThresholds::default()));
+        rejected_node.resign();
+        let silent_node = make_node_record(4444, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        // As if the GossipAcceptor had let only these two in
         subject
             .neighborhood_database
-            .add_node(introduction_target_node.clone())
+            .add_node(accepted_node.clone())
             .unwrap();
-
-        subject.neighborhood_database.add_arbitrary_half_neighbor(
-            subject_node.public_key(),
-            introduction_target_node.public_key(),
-        );
-        let debut = GossipBuilder::new(&subject.neighborhood_database)
-            .node(subject_node.public_key(), true)
-            .build();
-        let gossip_acceptor =
-            GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Reply(
-                debut.clone(),
-                introduction_target_node.public_key().clone(),
-                introduction_target_node.node_addr_opt().unwrap(),
-            ));
-        subject.gossip_acceptor = Box::new(gossip_acceptor);
-        let (hopper, _, hopper_recording_arc) = make_recorder();
-        let peer_actors = peer_actors_builder().hopper(hopper).build();
-        let system = System::new("");
-        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
-
-        subject.handle_gossip(
-            Gossip_0v1::new(vec![]),
-            SocketAddr::from_str("1.1.1.1:1111").unwrap(),
-            make_cpm_recipient().0,
-        );
-
-        System::current().stop();
-        system.run();
-        let hopper_recording = hopper_recording_arc.lock().unwrap();
-        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
-        assert_eq!(1, hopper_recording.len());
-        assert_eq!(introduction_target_node.public_key(), &package.public_key);
-        let gossip = match decodex::<MessageType>(
-            &CryptDENull::from(introduction_target_node.public_key(), TEST_DEFAULT_CHAIN),
-            &package.payload,
-        ) {
-            Ok(MessageType::Gossip(vd)) => Gossip_0v1::try_from(vd).unwrap(),
-            x => panic!("Wanted Gossip, found {:?}", x),
-        };
-        assert_eq!(debut, gossip);
-    }
-
-    #[test]
-    fn neighborhood_transmits_gossip_failure_properly() {
-        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
-        let neighbor = make_node_record(1111, true);
-        let public_key = PublicKey::new(&[1, 2, 3, 4]);
-        let node_addr = NodeAddr::from_str("1.2.3.4:1234").unwrap();
-        let gossip_acceptor =
-            GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Failed(
-                GossipFailure_0v1::NoSuitableNeighbors,
-                public_key.clone(),
-                node_addr.clone(),
-            ));
-        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
-        let (hopper, _, hopper_recording_arc) = make_recorder();
-        let system = System::new("neighborhood_transmits_gossip_failure_properly");
-        let peer_actors = peer_actors_builder().hopper(hopper).build();
-        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
-        subject.gossip_acceptor = Box::new(gossip_acceptor);
+        subject
+            .neighborhood_database
+            .add_node(silent_node.clone())
+            .unwrap();
+        subject.gossip_acceptor =
+            Box::new(GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Ignored));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.creditor_thresholds_sub_opt =
+            Some(peer_actors.accountant.report_creditor_thresholds);
+        let agrs = vec![&accepted_node, &rejected_node, &silent_node]
+            .into_iter()
+            .map(AccessibleGossipRecord::from)
+            .collect();
+        let system = System::new("test");
 
         subject.handle_gossip_agrs(
-            vec![],
+            agrs,
             SocketAddr::from_str("1.2.3.4:1234").unwrap(),
             make_cpm_recipient().0,
         );
 
         System::current().stop();
         system.run();
-        let hopper_recording = hopper_recording_arc.lock().unwrap();
-        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
-        assert_eq!(1, hopper_recording.len());
-        assert_eq!(package.node_addr, node_addr);
-        let payload = decodex::<MessageType>(
-            &CryptDENull::from(&public_key, TEST_DEFAULT_CHAIN),
-            &package.payload,
-        )
-        .unwrap();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
         assert_eq!(
-            payload,
-            MessageType::GossipFailure(VersionedData::new(
-                &crate::sub_lib::migrations::gossip_failure::MIGRATIONS,
-                &GossipFailure_0v1::NoSuitableNeighbors
-            ))
+            accountant_recording.get_record::<ReportCreditorThresholdsMessage>(0),
+            &ReportCreditorThresholdsMessage {
+                creditor_thresholds: vec![(
+                    accepted_node.earning_wallet(),
+                    make_custom_payment_thresholds()
+                )]
+            }
         );
-    }
-
-    struct DatabaseReplacementGossipAcceptor {
-        pub replacement_database: NeighborhoodDatabase,
-    }
-
-    impl GossipAcceptor for DatabaseReplacementGossipAcceptor {
-        fn handle(
-            &self,
-            database: &mut NeighborhoodDatabase,
-            _agrs: Vec<AccessibleGossipRecord>,
-            _gossip_source: SocketAddr,
-            _neighborhood_metadata: NeighborhoodMetadata,
-        ) -> GossipAcceptanceResult {
-            let non_root_database_keys = database
-                .keys()
-                .into_iter()
-                .filter(|k| *k != database.root().public_key())
-                .map(|k| k.clone())
-                .collect_vec();
-            non_root_database_keys
-                .into_iter()
-                .for_each(|k| database.remove_node(&k));
-            let database_root_neighbor_keys = database
-                .root()
-                .half_neighbor_keys()
-                .into_iter()
-                .map(|k| k.clone())
-                .collect_vec();
-            database_root_neighbor_keys.into_iter().for_each(|k| {
-                database.root_mut().remove_half_neighbor_key(&k);
-            });
-            self.replacement_database
-                .keys()
-                .into_iter()
-                .filter(|k| *k != self.replacement_database.root().public_key())
-                .for_each(|k| {
-                    database
-                        .add_node(self.replacement_database.node_by_key(k).unwrap().clone())
-                        .unwrap();
-                });
-            self.replacement_database.keys().into_iter().for_each(|k| {
-                let node_record = self.replacement_database.node_by_key(k).unwrap();
-                node_record.half_neighbor_keys().into_iter().for_each(|n| {
-                    database.add_arbitrary_half_neighbor(k, n);
-                });
-            });
-            GossipAcceptanceResult::Ignored
-        }
-    }
-
-    fn bind_subject(subject: &mut Neighborhood, peer_actors: PeerActors) {
-        subject.hopper_opt = Some(peer_actors.hopper.from_hopper_client);
-        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
-        subject.connected_signal_opt = Some(peer_actors.accountant.start);
+        assert_eq!(accountant_recording.len(), 1);
     }
 
     #[test]
-    fn neighborhood_does_not_start_accountant_if_no_route_can_be_made() {
+    fn neighborhood_reports_nothing_to_accountant_if_no_node_advertises_payment_thresholds() {
         let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
-        let neighbor = make_node_record(1111, true);
-        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
-        let mut replacement_database = subject.neighborhood_database.clone();
-        replacement_database.add_node(neighbor.clone()).unwrap();
-        replacement_database
-            .add_arbitrary_half_neighbor(subject_node.public_key(), neighbor.public_key());
-        subject.gossip_acceptor = Box::new(DatabaseReplacementGossipAcceptor {
-            replacement_database,
-        });
+        let node = make_node_record(2222, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(node.clone())
+            .unwrap();
+        subject.gossip_acceptor =
+            Box::new(GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Ignored));
         let (accountant, _, accountant_recording_arc) = make_recorder();
-        let system = System::new("neighborhood_does_not_start_accountant_if_no_route_can_be_made");
         let peer_actors = peer_actors_builder().accountant(accountant).build();
-        bind_subject(&mut subject, peer_actors);
+        subject.creditor_thresholds_sub_opt =
+            Some(peer_actors.accountant.report_creditor_thresholds);
+        let system = System::new("test");
 
         subject.handle_gossip_agrs(
-            vec![],
+            vec![AccessibleGossipRecord::from(&node)],
             SocketAddr::from_str("1.2.3.4:1234").unwrap(),
             make_cpm_recipient().0,
         );
@@ -5423,22 +6077,1038 @@ mod tests {
         system.run();
         let accountant_recording = accountant_recording_arc.lock().unwrap();
         assert_eq!(accountant_recording.len(), 0);
-        assert_eq!(subject.overall_connection_status.can_make_routes(), false);
     }
 
     #[test]
-    fn neighborhood_does_not_start_accountant_if_already_connected() {
+    fn neighborhood_reports_creditors_entering_and_leaving_the_neighborhood_to_accountant() {
         let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
-        let neighbor = make_node_record(1111, true);
-        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
-        let replacement_database = subject.neighborhood_database.clone();
-        subject.gossip_acceptor = Box::new(DatabaseReplacementGossipAcceptor {
-            replacement_database,
-        });
-        let (accountant, _, accountant_recording_arc) = make_recorder();
-        let system = System::new("neighborhood_does_not_start_accountant_if_already_connected");
-        let peer_actors = peer_actors_builder().accountant(accountant).build();
-        bind_subject(&mut subject, peer_actors);
+        let staying_node = make_node_record(2222, true);
+        let leaving_node = make_node_record(3333, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.creditor_reachability_opt = Some(HashSet::new());
+        {
+            let db = &mut subject.neighborhood_database;
+            db.add_node(staying_node.clone()).unwrap();
+            db.add_node(leaving_node.clone()).unwrap();
+            db.add_arbitrary_half_neighbor(subject_node.public_key(), staying_node.public_key());
+            db.add_arbitrary_half_neighbor(subject_node.public_key(), leaving_node.public_key());
+        }
+        subject.gossip_acceptor = Box::new(
+            GossipAcceptorMock::new()
+                .handle_result(GossipAcceptanceResult::Ignored)
+                .handle_result(GossipAcceptanceResult::Ignored)
+                .handle_result(GossipAcceptanceResult::Ignored),
+        );
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.creditor_reachability_sub_opt =
+            Some(peer_actors.accountant.report_creditor_reachability);
+        let gossip_source = SocketAddr::from_str("1.2.3.4:1234").unwrap();
+        let agrs = || vec![AccessibleGossipRecord::from(&staying_node)];
+        let system = System::new("test");
+        let before = SystemTime::now();
+
+        subject.handle_gossip_agrs(agrs(), gossip_source, make_cpm_recipient().0);
+        subject
+            .neighborhood_database
+            .remove_neighbor(leaving_node.public_key())
+            .unwrap();
+        subject.handle_gossip_agrs(agrs(), gossip_source, make_cpm_recipient().0);
+        subject.handle_gossip_agrs(agrs(), gossip_source, make_cpm_recipient().0);
+
+        let after = SystemTime::now();
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        let first_report = accountant_recording.get_record::<ReportCreditorReachabilityMessage>(0);
+        assert!(before <= first_report.timestamp && first_report.timestamp <= after);
+        assert_eq!(
+            first_report
+                .reachable
+                .iter()
+                .cloned()
+                .collect::<HashSet<Wallet>>(),
+            HashSet::from([staying_node.earning_wallet(), leaving_node.earning_wallet()])
+        );
+        assert_eq!(first_report.unreachable, vec![]);
+        let second_report = accountant_recording.get_record::<ReportCreditorReachabilityMessage>(1);
+        assert_eq!(second_report.reachable, vec![]);
+        assert_eq!(
+            second_report.unreachable,
+            vec![leaving_node.earning_wallet()]
+        );
+        assert_eq!(accountant_recording.len(), 2);
+        assert_eq!(
+            subject.creditor_reachability_opt,
+            Some(HashSet::from([staying_node.earning_wallet()]))
+        );
+    }
+
+    #[test]
+    fn neighborhood_reports_no_creditor_reachability_unless_it_tracks_it() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let node = make_node_record(2222, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(node.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_arbitrary_half_neighbor(subject_node.public_key(), node.public_key());
+        subject.gossip_acceptor =
+            Box::new(GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Ignored));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.creditor_reachability_sub_opt =
+            Some(peer_actors.accountant.report_creditor_reachability);
+        let system = System::new("test");
+
+        subject.handle_gossip_agrs(
+            vec![AccessibleGossipRecord::from(&node)],
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            make_cpm_recipient().0,
+        );
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 0);
+        assert_eq!(subject.creditor_reachability_opt, None);
+    }
+
+    fn make_exit_node(n: u16, country_code: &str) -> NodeRecord {
+        let mut node = make_node_record(n, true);
+        node.inner.country_code_opt = Some(country_code.to_string());
+        node
+    }
+
+    fn prefer_exit_countries(subject: &mut Neighborhood, country_codes_by_priority: &[&str]) {
+        let exit_locations = country_codes_by_priority
+            .iter()
+            .enumerate()
+            .map(|(index, country_code)| ExitLocation {
+                country_codes: vec![country_code.to_string()],
+                priority: index + 1,
+            })
+            .collect_vec();
+        subject.user_exit_preferences.fallback_preference =
+            FallbackPreference::ExitCountryNoFallback;
+        subject.user_exit_preferences.exit_countries = country_codes_by_priority
+            .iter()
+            .map(|country_code| country_code.to_string())
+            .collect();
+        subject.user_exit_preferences.locations_opt = Some(exit_locations);
+    }
+
+    #[test]
+    fn neighborhood_fails_over_to_the_next_preferred_exit_country_when_the_active_one_leaves() {
+        init_test_logging();
+        let test_name =
+            "neighborhood_fails_over_to_the_next_preferred_exit_country_when_the_active_one_leaves";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let czech_node = make_exit_node(2222, "CZ");
+        let slovak_node = make_exit_node(3333, "SK");
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.logger = Logger::new(test_name);
+        subject.min_hops = Hops::OneHop;
+        add_full_neighbors(&mut subject, vec![&czech_node, &slovak_node]);
+        prefer_exit_countries(&mut subject, &["CZ", "SK"]);
+        subject.user_exit_preferences.db_countries = subject.init_db_countries();
+        let (hopper, _, _) = make_recorder();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .hopper(hopper)
+            .accountant(accountant)
+            .build();
+        subject.hopper_opt = Some(peer_actors.hopper.from_hopper_client);
+        subject.exit_failover_sub_opt = Some(peer_actors.accountant.report_exit_failover);
+        let system = System::new(test_name);
+        let before = SystemTime::now();
+
+        subject.reevaluate_exit_country();
+        subject.remove_neighbor(
+            czech_node.public_key(),
+            &SocketAddr::new(czech_node.node_addr_opt().unwrap().ip_addr(), 1234),
+        );
+
+        let after = SystemTime::now();
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        let selection = accountant_recording.get_record::<ReportExitFailoverMessage>(0);
+        assert!(before <= selection.timestamp && selection.timestamp <= after);
+        assert_eq!(selection.exit_country, "CZ".to_string());
+        assert_eq!(selection.creditors, vec![czech_node.earning_wallet()]);
+        let failover = accountant_recording.get_record::<ReportExitFailoverMessage>(1);
+        assert_eq!(failover.exit_country, "SK".to_string());
+        assert_eq!(failover.creditors, vec![slovak_node.earning_wallet()]);
+        assert_eq!(accountant_recording.len(), 2);
+        assert_eq!(
+            subject.exit_failover.active_country_opt(),
+            Some(&"SK".to_string())
+        );
+        assert_eq!(
+            subject.user_exit_preferences.exit_countries,
+            vec!["SK".to_string()]
+        );
+        TestLogHandler::new().assert_logs_contain_in_order(vec![
+            &format!("INFO: {test_name}: Exit country CZ is available"),
+            &format!(
+                "INFO: {test_name}: Exit country CZ is no longer the most preferred one available; \
+                 failing over to SK"
+            ),
+        ]);
+    }
+
+    #[test]
+    fn neighborhood_reports_no_failover_while_the_active_exit_country_stays() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let czech_node = make_exit_node(2222, "CZ");
+        let slovak_node = make_exit_node(3333, "SK");
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.min_hops = Hops::OneHop;
+        add_full_neighbors(&mut subject, vec![&czech_node, &slovak_node]);
+        prefer_exit_countries(&mut subject, &["CZ", "SK"]);
+        subject.user_exit_preferences.db_countries = subject.init_db_countries();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.exit_failover_sub_opt = Some(peer_actors.accountant.report_exit_failover);
+        let system = System::new("test");
+
+        subject.reevaluate_exit_country();
+        subject.reevaluate_exit_country();
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 1);
+    }
+
+    #[test]
+    fn exit_country_lost_without_a_preferred_replacement_is_logged_but_not_reported() {
+        init_test_logging();
+        let test_name =
+            "exit_country_lost_without_a_preferred_replacement_is_logged_but_not_reported";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let czech_node = make_exit_node(2222, "CZ");
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.logger = Logger::new(test_name);
+        subject.min_hops = Hops::OneHop;
+        add_full_neighbors(&mut subject, vec![&czech_node]);
+        prefer_exit_countries(&mut subject, &["CZ", "SK"]);
+        let exit_locations = subject.get_locations_opt();
+        subject
+            .exit_failover
+            .reevaluate(&exit_locations, &["CZ".to_string()]);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.exit_failover_sub_opt = Some(peer_actors.accountant.report_exit_failover);
+        let system = System::new(test_name);
+
+        subject.reevaluate_exit_country();
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 0);
+        assert_eq!(subject.exit_failover.active_country_opt(), None);
+        assert_eq!(
+            subject.user_exit_preferences.exit_countries.is_empty(),
+            true
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Exit country CZ is no longer available, and neither is any other \
+             preferred one"
+        ));
+    }
+
+    #[test]
+    fn exit_location_set_by_the_user_is_not_taken_for_a_failover() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let czech_node = make_exit_node(2222, "CZ");
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.min_hops = Hops::OneHop;
+        add_full_neighbors(&mut subject, vec![&czech_node]);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let (ui_gateway, _, _) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .accountant(accountant)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject.exit_failover_sub_opt = Some(peer_actors.accountant.report_exit_failover);
+        subject.node_to_ui_recipient_opt = Some(peer_actors.ui_gateway.node_to_ui_message_sub);
+        let request = UiSetExitLocationRequest {
+            fallback_routing: false,
+            exit_locations: vec![CountryGroups {
+                country_codes: vec!["CZ".to_string()],
+                priority: 1,
+            }],
+            show_countries: false,
+        };
+        let system = System::new("test");
+
+        subject.handle_exit_location_message(request, 1234, 4321);
+        subject.reevaluate_exit_country();
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 0);
+        assert_eq!(
+            subject.exit_failover.active_country_opt(),
+            Some(&"CZ".to_string())
+        );
+    }
+
+    fn make_payment_notice(creditor_wallet: &Wallet) -> PaymentNotice_0v1 {
+        PaymentNotice_0v1::new(
+            &make_paying_wallet(b"debtor"),
+            creditor_wallet,
+            make_tx_hash(789),
+            4_567_000_000_000,
+            1_234,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn neighborhood_sends_payment_notice_to_the_node_earning_into_the_creditor_wallet() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let creditor_node = make_node_record(2222, true);
+        let other_node = make_node_record(3333, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(creditor_node.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_node(other_node.clone())
+            .unwrap();
+        let notice = make_payment_notice(&creditor_node.earning_wallet());
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        let system = System::new("test");
+
+        subject.handle_send_payment_notice(notice.clone());
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(hopper_recording.len(), 1);
+        assert_eq!(&package.public_key, creditor_node.public_key());
+        assert_eq!(package.node_addr, creditor_node.node_addr_opt().unwrap());
+        assert_eq!(
+            match decodex::<MessageType>(
+                &CryptDENull::from(creditor_node.public_key(), TEST_DEFAULT_CHAIN),
+                &package.payload,
+            ) {
+                Ok(MessageType::PaymentNotice(vd)) => PaymentNotice_0v1::try_from(vd).unwrap(),
+                x => panic!("Expected PaymentNotice, but found {:?}", x),
+            },
+            notice
+        );
+    }
+
+    #[test]
+    fn neighborhood_drops_payment_notice_to_a_creditor_it_cannot_reach() {
+        init_test_logging();
+        let test_name = "neighborhood_drops_payment_notice_to_a_creditor_it_cannot_reach";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let unreachable_node = make_node_record(2222, false);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(unreachable_node.clone())
+            .unwrap();
+        subject.logger = Logger::new(test_name);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        let system = System::new(test_name);
+
+        subject.handle_send_payment_notice(make_payment_notice(&unreachable_node.earning_wallet()));
+        subject.handle_send_payment_notice(make_payment_notice(&make_wallet("stranger")));
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 0);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Dropping notice of payment {:?} to {}: no reachable Node earns into that wallet",
+            make_tx_hash(789),
+            unreachable_node.earning_wallet()
+        ));
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Dropping notice of payment {:?} to {}: no reachable Node earns into that wallet",
+            make_tx_hash(789),
+            make_wallet("stranger")
+        ));
+    }
+
+    #[test]
+    fn neighborhood_forwards_incoming_payment_notice_to_accountant() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.payment_notice_sub_opt = Some(peer_actors.accountant.report_payment_notice);
+        let notice = make_payment_notice(&subject_node.earning_wallet());
+        let system = System::new("test");
+
+        subject.handle_payment_notice(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            notice.clone(),
+        );
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(
+            accountant_recording.get_record::<ReportPaymentNoticeMessage>(0),
+            &ReportPaymentNoticeMessage { notice }
+        );
+        assert_eq!(accountant_recording.len(), 1);
+    }
+
+    fn make_usage_invoice(creditor_wallet: &Wallet) -> UsageInvoice_0v1 {
+        UsageInvoice_0v1::new(
+            creditor_wallet,
+            &make_paying_wallet(b"debtor"),
+            1_000,
+            2_000,
+            12,
+            3,
+            4_567_000_000_000,
+        )
+    }
+
+    #[test]
+    fn neighborhood_sends_signed_usage_invoice_to_the_node_that_paid_from_the_debtor_wallet() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let debtor_node = make_node_record(2222, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(debtor_node.clone())
+            .unwrap();
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let (accountant, _, _) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .hopper(hopper)
+            .accountant(accountant)
+            .build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        subject.payment_notice_sub_opt = Some(peer_actors.accountant.report_payment_notice);
+        let debtor_ip = debtor_node.node_addr_opt().unwrap().ip_addr();
+        subject.handle_payment_notice(
+            SocketAddr::new(debtor_ip, 1234),
+            make_payment_notice(&subject_node.earning_wallet()),
+        );
+        let invoice = make_usage_invoice(&subject_node.earning_wallet());
+        let system = System::new("test");
+
+        subject.handle_send_usage_invoice(invoice.clone());
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(hopper_recording.len(), 1);
+        assert_eq!(&package.public_key, debtor_node.public_key());
+        assert_eq!(package.node_addr, debtor_node.node_addr_opt().unwrap());
+        let sent_invoice = match decodex::<MessageType>(
+            &CryptDENull::from(debtor_node.public_key(), TEST_DEFAULT_CHAIN),
+            &package.payload,
+        ) {
+            Ok(MessageType::UsageInvoice(vd)) => UsageInvoice_0v1::try_from(vd).unwrap(),
+            x => panic!("Expected UsageInvoice, but found {:?}", x),
+        };
+        assert_eq!(
+            sent_invoice,
+            UsageInvoice_0v1 {
+                signature: sent_invoice.signature.clone(),
+                ..invoice
+            }
+        );
+        assert_eq!(
+            sent_invoice.is_signed_by(main_cryptde(), subject_node.public_key()),
+            true
+        );
+    }
+
+    #[test]
+    fn neighborhood_drops_usage_invoice_to_a_debtor_that_has_not_paid_through_a_known_node() {
+        init_test_logging();
+        let test_name =
+            "neighborhood_drops_usage_invoice_to_a_debtor_that_has_not_paid_through_a_known_node";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.logger = Logger::new(test_name);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        let system = System::new(test_name);
+
+        subject.handle_send_usage_invoice(make_usage_invoice(&subject_node.earning_wallet()));
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: Dropping invoice to {}: no reachable Node has paid from that wallet",
+            make_paying_wallet(b"debtor").as_address_wallet()
+        ));
+    }
+
+    #[test]
+    fn neighborhood_forwards_usage_invoice_signed_by_the_creditor_node_to_accountant() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let creditor_node = make_node_record(2222, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(creditor_node.clone())
+            .unwrap();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.usage_invoice_sub_opt = Some(peer_actors.accountant.report_usage_invoice);
+        let mut invoice = make_usage_invoice(&creditor_node.earning_wallet());
+        invoice
+            .sign(&CryptDENull::from(
+                creditor_node.public_key(),
+                TEST_DEFAULT_CHAIN,
+            ))
+            .unwrap();
+        let creditor_ip = creditor_node.node_addr_opt().unwrap().ip_addr();
+        let system = System::new("test");
+
+        subject.handle_usage_invoice(SocketAddr::new(creditor_ip, 1234), invoice.clone());
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(
+            accountant_recording.get_record::<ReportUsageInvoiceMessage>(0),
+            &ReportUsageInvoiceMessage { invoice }
+        );
+        assert_eq!(accountant_recording.len(), 1);
+    }
+
+    #[test]
+    fn neighborhood_ignores_usage_invoice_not_from_the_creditor_node() {
+        init_test_logging();
+        let test_name = "neighborhood_ignores_usage_invoice_not_from_the_creditor_node";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let creditor_node = make_node_record(2222, true);
+        let impostor_node = make_node_record(3333, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(creditor_node.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_node(impostor_node.clone())
+            .unwrap();
+        subject.logger = Logger::new(test_name);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject.usage_invoice_sub_opt = Some(peer_actors.accountant.report_usage_invoice);
+        let creditor_cryptde = CryptDENull::from(creditor_node.public_key(), TEST_DEFAULT_CHAIN);
+        let impostor_cryptde = CryptDENull::from(impostor_node.public_key(), TEST_DEFAULT_CHAIN);
+        let mut invoice_from_stranger = make_usage_invoice(&creditor_node.earning_wallet());
+        invoice_from_stranger.sign(&creditor_cryptde).unwrap();
+        let mut invoice_for_other_wallet = make_usage_invoice(&creditor_node.earning_wallet());
+        invoice_for_other_wallet.sign(&impostor_cryptde).unwrap();
+        let mut invoice_signed_by_other = make_usage_invoice(&creditor_node.earning_wallet());
+        invoice_signed_by_other.sign(&impostor_cryptde).unwrap();
+        let creditor_ip = creditor_node.node_addr_opt().unwrap().ip_addr();
+        let impostor_ip = impostor_node.node_addr_opt().unwrap().ip_addr();
+        let system = System::new(test_name);
+
+        subject.handle_usage_invoice(
+            SocketAddr::from_str("9.9.9.9:1234").unwrap(),
+            invoice_from_stranger,
+        );
+        subject.handle_usage_invoice(SocketAddr::new(impostor_ip, 1234), invoice_for_other_wallet);
+        subject.handle_usage_invoice(SocketAddr::new(creditor_ip, 1234), invoice_signed_by_other);
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 0);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring invoice from 9.9.9.9:1234: no Node with that IP is in the database"
+        ));
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring invoice for {} from {}: it earns into {}",
+            creditor_node.earning_wallet(),
+            impostor_node.public_key(),
+            impostor_node.earning_wallet()
+        ));
+        tlh.exists_log_containing(&format!(
+            "WARN: {test_name}: Ignoring invoice for {} from {}: bad signature",
+            creditor_node.earning_wallet(),
+            creditor_node.public_key()
+        ));
+    }
+
+    #[test]
+    fn neighborhood_answers_exit_options_query_with_the_exit_countries_it_advertises() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let asker_node = make_node_record(2222, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .root_mut()
+            .inner
+            .country_code_opt = Some("FR".to_string());
+        subject
+            .neighborhood_database
+            .add_node(asker_node.clone())
+            .unwrap();
+        subject.user_exit_preferences.db_countries = vec!["FR".to_string(), "CZ".to_string()];
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        let asker_ip = asker_node.node_addr_opt().unwrap().ip_addr();
+        let system = System::new("test");
+
+        subject.handle_exit_options_query(
+            SocketAddr::new(asker_ip, 1234),
+            ExitOptionsQuery_0v1 {
+                sent_at_ms: 1_700_000_000_000,
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(hopper_recording.len(), 1);
+        assert_eq!(&package.public_key, asker_node.public_key());
+        assert_eq!(package.node_addr, asker_node.node_addr_opt().unwrap());
+        assert_eq!(
+            match decodex::<MessageType>(
+                &CryptDENull::from(asker_node.public_key(), TEST_DEFAULT_CHAIN),
+                &package.payload,
+            ) {
+                Ok(MessageType::ExitOptionsReport(vd)) =>
+                    ExitOptionsReport_0v1::try_from(vd).unwrap(),
+                x => panic!("Expected ExitOptionsReport, but found {:?}", x),
+            },
+            ExitOptionsReport_0v1 {
+                exit_country_codes: vec!["CZ".to_string(), "FR".to_string()],
+                query_sent_at_ms: 1_700_000_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn neighborhood_ignores_exit_options_messages_from_unknown_ips() {
+        init_test_logging();
+        let test_name = "neighborhood_ignores_exit_options_messages_from_unknown_ips";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.logger = Logger::new(test_name);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        let stranger = SocketAddr::from_str("1.2.3.4:1234").unwrap();
+        let system = System::new(test_name);
+
+        subject.handle_exit_options_query(stranger, ExitOptionsQuery_0v1 { sent_at_ms: 1 });
+        subject.handle_exit_options_report(
+            stranger,
+            ExitOptionsReport_0v1 {
+                exit_country_codes: vec!["CZ".to_string()],
+                query_sent_at_ms: 1,
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 0);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Ignoring query of exit options from 1.2.3.4:1234: no Node with that IP is in the database"
+        ));
+        tlh.exists_log_containing(&format!(
+            "DEBUG: {test_name}: Ignoring report of exit options from 1.2.3.4:1234: no Node with that IP is in the database"
+        ));
+    }
+
+    fn add_full_neighbors(subject: &mut Neighborhood, neighbors: Vec<&NodeRecord>) {
+        let root_key = subject.neighborhood_database.root_key().clone();
+        neighbors.into_iter().for_each(|neighbor| {
+            subject
+                .neighborhood_database
+                .add_node(neighbor.clone())
+                .unwrap();
+            subject
+                .neighborhood_database
+                .add_arbitrary_full_neighbor(&root_key, neighbor.public_key());
+        })
+    }
+
+    #[test]
+    fn exit_options_request_probes_the_neighbors_that_can_be_reached() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let reachable_node = make_node_record(2222, true);
+        let unreachable_node = make_node_record(3333, false);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        add_full_neighbors(&mut subject, vec![&reachable_node, &unreachable_node]);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .hopper(hopper)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        subject.node_to_ui_recipient_opt = Some(peer_actors.ui_gateway.node_to_ui_message_sub);
+        let system = System::new("test");
+        let before = SystemTime::now();
+
+        subject.handle_exit_options_request(1234, 4321);
+
+        let after = SystemTime::now();
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(hopper_recording.len(), 1);
+        assert_eq!(&package.public_key, reachable_node.public_key());
+        assert_eq!(package.node_addr, reachable_node.node_addr_opt().unwrap());
+        let query = match decodex::<MessageType>(
+            &CryptDENull::from(reachable_node.public_key(), TEST_DEFAULT_CHAIN),
+            &package.payload,
+        ) {
+            Ok(MessageType::ExitOptionsQuery(vd)) => ExitOptionsQuery_0v1::try_from(vd).unwrap(),
+            x => panic!("Expected ExitOptionsQuery, but found {:?}", x),
+        };
+        let millis = |time: SystemTime| {
+            time.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64
+        };
+        assert!(millis(before) <= query.sent_at_ms && query.sent_at_ms <= millis(after));
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiExitOptionsResponse {
+                    exit_countries: vec![]
+                }
+                .tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+    }
+
+    #[test]
+    fn exit_options_request_is_answered_from_the_reports_of_the_neighbors() {
+        init_test_logging();
+        let test_name = "exit_options_request_is_answered_from_the_reports_of_the_neighbors";
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor_node = make_node_record(2222, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.logger = Logger::new(test_name);
+        add_full_neighbors(&mut subject, vec![&neighbor_node]);
+        let (hopper, _, _) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .hopper(hopper)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        subject.node_to_ui_recipient_opt = Some(peer_actors.ui_gateway.node_to_ui_message_sub);
+        let query = subject
+            .exit_options_cache
+            .make_query(neighbor_node.public_key(), SystemTime::now());
+        let neighbor_ip = neighbor_node.node_addr_opt().unwrap().ip_addr();
+        let system = System::new(test_name);
+
+        subject.handle_exit_options_report(
+            SocketAddr::new(neighbor_ip, 1234),
+            ExitOptionsReport_0v1 {
+                exit_country_codes: vec!["DE".to_string()],
+                query_sent_at_ms: query.sent_at_ms,
+            },
+        );
+        subject.handle_exit_options_request(1234, 4321);
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let (response, context_id) = UiExitOptionsResponse::fmb(
+            ui_gateway_recording
+                .get_record::<NodeToUiMessage>(0)
+                .body
+                .clone(),
+        )
+        .unwrap();
+        assert_eq!(context_id, 4321);
+        assert_eq!(response.exit_countries.len(), 1);
+        let option = &response.exit_countries[0];
+        assert_eq!(option.country_code, "DE".to_string());
+        assert_eq!(option.neighbors.len(), 1);
+        let neighbor = &option.neighbors[0];
+        assert_eq!(neighbor.public_key, neighbor_node.public_key().to_string());
+        assert!(neighbor.latency_ms < 10_000, "{}", neighbor.latency_ms);
+        assert_eq!(neighbor.age_seconds, 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {test_name}: {} reports exit options [\"DE\"] with latency",
+            neighbor_node.public_key()
+        ));
+    }
+
+    #[test]
+    fn exit_options_request_is_hooked_up_to_the_ui() {
+        let system = System::new("test");
+        let subject = Neighborhood::new(
+            main_cryptde(),
+            &bc_from_nc_plus(
+                NeighborhoodConfig {
+                    mode: NeighborhoodMode::ZeroHop,
+                    min_hops: MIN_HOPS_FOR_TEST,
+                },
+                make_wallet("earning"),
+                None,
+                "exit_options_request_is_hooked_up_to_the_ui",
+            ),
+        );
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiExitOptionsRequest {}.tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiExitOptionsResponse {
+                    exit_countries: vec![]
+                }
+                .tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+    }
+
+    #[test]
+    fn neighborhood_sends_only_an_acceptance_debut_when_an_acceptance_debut_is_provided() {
+        let introduction_target_node = make_node_record(7345, true);
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1050, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        subject
+            .neighborhood_database
+            .add_node(introduction_target_node.clone())
+            .unwrap();
+
+        subject.neighborhood_database.add_arbitrary_half_neighbor(
+            subject_node.public_key(),
+            introduction_target_node.public_key(),
+        );
+        let debut = GossipBuilder::new(&subject.neighborhood_database)
+            .node(subject_node.public_key(), true)
+            .build();
+        let gossip_acceptor =
+            GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Reply(
+                debut.clone(),
+                introduction_target_node.public_key().clone(),
+                introduction_target_node.node_addr_opt().unwrap(),
+            ));
+        subject.gossip_acceptor = Box::new(gossip_acceptor);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        let system = System::new("");
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+
+        subject.handle_gossip(
+            Gossip_0v1::new(vec![]),
+            SocketAddr::from_str("1.1.1.1:1111").unwrap(),
+            make_cpm_recipient().0,
+        );
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(1, hopper_recording.len());
+        assert_eq!(introduction_target_node.public_key(), &package.public_key);
+        let gossip = match decodex::<MessageType>(
+            &CryptDENull::from(introduction_target_node.public_key(), TEST_DEFAULT_CHAIN),
+            &package.payload,
+        ) {
+            Ok(MessageType::Gossip(vd)) => Gossip_0v1::try_from(vd).unwrap(),
+            x => panic!("Wanted Gossip, found {:?}", x),
+        };
+        assert_eq!(debut, gossip);
+    }
+
+    #[test]
+    fn neighborhood_transmits_gossip_failure_properly() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let public_key = PublicKey::new(&[1, 2, 3, 4]);
+        let node_addr = NodeAddr::from_str("1.2.3.4:1234").unwrap();
+        let gossip_acceptor =
+            GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Failed(
+                GossipFailure_0v1::NoSuitableNeighbors,
+                public_key.clone(),
+                node_addr.clone(),
+            ));
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let system = System::new("neighborhood_transmits_gossip_failure_properly");
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        subject.gossip_acceptor = Box::new(gossip_acceptor);
+
+        subject.handle_gossip_agrs(
+            vec![],
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            make_cpm_recipient().0,
+        );
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(1, hopper_recording.len());
+        assert_eq!(package.node_addr, node_addr);
+        let payload = decodex::<MessageType>(
+            &CryptDENull::from(&public_key, TEST_DEFAULT_CHAIN),
+            &package.payload,
+        )
+        .unwrap();
+        assert_eq!(
+            payload,
+            MessageType::GossipFailure(VersionedData::new(
+                &crate::sub_lib::migrations::gossip_failure::MIGRATIONS,
+                &GossipFailure_0v1::NoSuitableNeighbors
+            ))
+        );
+    }
+
+    struct DatabaseReplacementGossipAcceptor {
+        pub replacement_database: NeighborhoodDatabase,
+    }
+
+    impl GossipAcceptor for DatabaseReplacementGossipAcceptor {
+        fn handle(
+            &self,
+            database: &mut NeighborhoodDatabase,
+            _agrs: Vec<AccessibleGossipRecord>,
+            _gossip_source: SocketAddr,
+            _neighborhood_metadata: NeighborhoodMetadata,
+        ) -> GossipAcceptanceResult {
+            let non_root_database_keys = database
+                .keys()
+                .into_iter()
+                .filter(|k| *k != database.root().public_key())
+                .map(|k| k.clone())
+                .collect_vec();
+            non_root_database_keys
+                .into_iter()
+                .for_each(|k| database.remove_node(&k));
+            let database_root_neighbor_keys = database
+                .root()
+                .half_neighbor_keys()
+                .into_iter()
+                .map(|k| k.clone())
+                .collect_vec();
+            database_root_neighbor_keys.into_iter().for_each(|k| {
+                database.root_mut().remove_half_neighbor_key(&k);
+            });
+            self.replacement_database
+                .keys()
+                .into_iter()
+                .filter(|k| *k != self.replacement_database.root().public_key())
+                .for_each(|k| {
+                    database
+                        .add_node(self.replacement_database.node_by_key(k).unwrap().clone())
+                        .unwrap();
+                });
+            self.replacement_database.keys().into_iter().for_each(|k| {
+                let node_record = self.replacement_database.node_by_key(k).unwrap();
+                node_record.half_neighbor_keys().into_iter().for_each(|n| {
+                    database.add_arbitrary_half_neighbor(k, n);
+                });
+            });
+            GossipAcceptanceResult::Ignored
+        }
+    }
+
+    fn bind_subject(subject: &mut Neighborhood, peer_actors: PeerActors) {
+        subject.hopper_opt = Some(peer_actors.hopper.from_hopper_client);
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        subject.connected_signal_opt = Some(peer_actors.accountant.start);
+    }
+
+    #[test]
+    fn neighborhood_does_not_start_accountant_if_no_route_can_be_made() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let mut replacement_database = subject.neighborhood_database.clone();
+        replacement_database.add_node(neighbor.clone()).unwrap();
+        replacement_database
+            .add_arbitrary_half_neighbor(subject_node.public_key(), neighbor.public_key());
+        subject.gossip_acceptor = Box::new(DatabaseReplacementGossipAcceptor {
+            replacement_database,
+        });
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let system = System::new("neighborhood_does_not_start_accountant_if_no_route_can_be_made");
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        bind_subject(&mut subject, peer_actors);
+
+        subject.handle_gossip_agrs(
+            vec![],
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            make_cpm_recipient().0,
+        );
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 0);
+        assert_eq!(subject.overall_connection_status.can_make_routes(), false);
+    }
+
+    #[test]
+    fn neighborhood_does_not_start_accountant_if_already_connected() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let replacement_database = subject.neighborhood_database.clone();
+        subject.gossip_acceptor = Box::new(DatabaseReplacementGossipAcceptor {
+            replacement_database,
+        });
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let system = System::new("neighborhood_does_not_start_accountant_if_already_connected");
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        bind_subject(&mut subject, peer_actors);
 
         subject.handle_gossip_agrs(
             vec![],
@@ -7298,7 +8968,93 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn shutdown_instruction_waits_for_the_shutdown_barrier() {
+        init_test_logging();
+        let system = System::new("test");
+        let notify_later_shutdown_barrier_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = Neighborhood::new(
+            main_cryptde(),
+            &bc_from_nc_plus(
+                NeighborhoodConfig {
+                    mode: NeighborhoodMode::ZeroHop,
+                    min_hops: MIN_HOPS_FOR_TEST,
+                },
+                make_wallet("earning"),
+                None,
+                "shutdown_instruction_waits_for_the_shutdown_barrier",
+            ),
+        );
+        subject.tools.notify_later_shutdown_barrier = Box::new(
+            NotifyLaterHandleMock::default()
+                .notify_later_params(&notify_later_shutdown_barrier_params_arc),
+        );
+        subject.tools.shutdown_barrier_timeout = Duration::from_millis(123);
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: MessageBody {
+                    opcode: "shutdown".to_string(),
+                    path: Conversation(4321),
+                    payload: Ok("{}".to_string()),
+                },
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 0);
+        let notify_later_shutdown_barrier_params =
+            notify_later_shutdown_barrier_params_arc.lock().unwrap();
+        assert_eq!(
+            *notify_later_shutdown_barrier_params,
+            vec![(
+                ShutdownBarrierMessage { client_id: 1234 },
+                Duration::from_millis(123)
+            )]
+        );
+        TestLogHandler::new().exists_log_containing(
+            "INFO: Neighborhood: Received shutdown order from client 1234: waiting for \
+            transactions in flight to be recorded",
+        );
+    }
+
     #[should_panic(expected = "0: Received shutdown order from client 1234: shutting down hard")]
+    #[test]
+    fn shutdown_barrier_shuts_down_hard() {
+        running_test();
+        init_test_logging();
+        let system = System::new("test");
+        let subject = Neighborhood::new(
+            main_cryptde(),
+            &bc_from_nc_plus(
+                NeighborhoodConfig {
+                    mode: NeighborhoodMode::ZeroHop,
+                    min_hops: MIN_HOPS_FOR_TEST,
+                },
+                make_wallet("earning"),
+                None,
+                "shutdown_barrier_shuts_down_hard",
+            ),
+        );
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ShutdownBarrierMessage { client_id: 1234 })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+    }
+
     #[test]
     fn shutdown_instruction_generates_log() {
         running_test();
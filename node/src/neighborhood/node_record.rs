@@ -5,6 +5,7 @@ use crate::neighborhood::gossip::{
 };
 use crate::neighborhood::neighborhood_database::{NeighborhoodDatabase, NeighborhoodDatabaseError};
 use crate::neighborhood::node_location::{get_node_location, NodeLocation};
+use crate::sub_lib::accountant::PaymentThresholds;
 use crate::sub_lib::cryptde::{CryptDE, CryptData, PlainData, PublicKey};
 use crate::sub_lib::neighborhood::{NodeDescriptor, RatePack};
 use crate::sub_lib::node_addr::NodeAddr;
@@ -28,6 +29,9 @@ pub struct NodeRecordInner_0v1 {
     pub routes_data: bool,
     pub version: u32,
     pub country_code_opt: Option<String>,
+    // When a Node advertises these, its debtors pay it by them instead of by their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_thresholds_opt: Option<PaymentThresholds>,
 }
 
 impl TryFrom<GossipNodeRecord> for NodeRecordInner_0v1 {
@@ -93,6 +97,7 @@ impl NodeRecord {
                 neighbors: BTreeSet::new(),
                 version: node_record_inputs.version,
                 country_code_opt: country_opt,
+                payment_thresholds_opt: None,
             },
             signed_gossip: PlainData::new(&[]),
             signature: CryptData::new(&[]),
@@ -260,6 +265,26 @@ impl NodeRecord {
         &self.inner.rate_pack
     }
 
+    pub fn payment_thresholds_opt(&self) -> Option<&PaymentThresholds> {
+        self.inner.payment_thresholds_opt.as_ref()
+    }
+
+    pub fn set_payment_thresholds_opt(
+        &mut self,
+        payment_thresholds_opt: Option<PaymentThresholds>,
+    ) {
+        self.inner.payment_thresholds_opt = payment_thresholds_opt;
+    }
+
+    // The country the Node claims to be in, unless its IP address has proven the claim false
+    pub fn trusted_country_code_opt(&self) -> Option<&String> {
+        if self.metadata.country_claim_refuted {
+            None
+        } else {
+            self.inner.country_code_opt.as_ref()
+        }
+    }
+
     pub fn update(&mut self, agr: AccessibleGossipRecord) -> Result<(), String> {
         if &agr.inner.public_key != self.public_key() {
             return Err(format!(
@@ -353,8 +378,10 @@ pub struct NodeRecordMetadata {
     // to false, we do not consider the undesirability of countries other than those selected for exit.
     // Therefore, we use a value of 0 for exit nodes in countries that are not considered for exit.
     pub country_undesirability: u32,
+    // Set when --country-claim-check is enforced and the Node's IP address is in another country
+    // than the one it claims
+    pub country_claim_refuted: bool,
     //TODO introduce scores for latency #582 and reliability #583
-    //TODO #479 introduce check for node_location_opt, to verify full neighbors country code (we know his IP, so we can verify it)
 }
 
 impl NodeRecordMetadata {
@@ -365,6 +392,7 @@ impl NodeRecordMetadata {
             unreachable_hosts: Default::default(),
             node_location_opt: None,
             country_undesirability: 0u32,
+            country_claim_refuted: false,
         }
     }
 }
@@ -372,6 +400,7 @@ impl NodeRecordMetadata {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::accountant::test_utils::make_custom_payment_thresholds;
     use crate::neighborhood::gossip::GossipBuilder;
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::neighborhood::ZERO_RATE_PACK;
@@ -397,6 +426,19 @@ mod tests {
         assert_eq!(nr_wo_location.inner.country_code_opt, None);
     }
 
+    #[test]
+    fn trusted_country_code_opt_is_the_claimed_one_unless_refuted() {
+        let mut subject = make_node_record(1234, true);
+        subject.inner.country_code_opt = Some("CZ".to_string());
+
+        let trusted_before = subject.trusted_country_code_opt().cloned();
+        subject.metadata.country_claim_refuted = true;
+        let trusted_after = subject.trusted_country_code_opt().cloned();
+
+        assert_eq!(trusted_before, Some("CZ".to_string()));
+        assert_eq!(trusted_after, None);
+    }
+
     #[test]
     fn can_create_a_node_record_from_a_reference() {
         let mut expected_node_record = make_node_record(1234, true);
@@ -947,6 +989,55 @@ mod tests {
         )
     }
 
+    #[test]
+    fn update_adopts_newly_advertised_payment_thresholds() {
+        let mut subject = make_node_record(1234, true);
+        let mut modified = subject.clone();
+        modified.set_payment_thresholds_opt(Some(make_custom_payment_thresholds()));
+        modified.resign();
+        let agr = AccessibleGossipRecord::from(&modified);
+
+        let result = subject.update(agr);
+
+        assert_eq!(Ok(()), result);
+        assert_eq!(
+            subject.payment_thresholds_opt(),
+            Some(&make_custom_payment_thresholds())
+        );
+    }
+
+    #[test]
+    fn payment_thresholds_survive_trip_through_gossip() {
+        let mut subject = make_node_record(1234, true);
+        subject.set_payment_thresholds_opt(Some(make_custom_payment_thresholds()));
+        let gnr = GossipNodeRecord::from((subject.inner.clone(), None, main_cryptde()));
+
+        let result = NodeRecordInner_0v1::try_from(gnr).unwrap();
+
+        assert_eq!(
+            result.payment_thresholds_opt,
+            Some(make_custom_payment_thresholds())
+        );
+        assert_eq!(result, subject.inner);
+    }
+
+    #[test]
+    fn gossip_of_nodes_advertising_no_payment_thresholds_does_not_mention_them() {
+        let subject = make_node_record(1234, true);
+
+        let serialized = serde_cbor::ser::to_vec(&subject.inner).unwrap();
+
+        let deserialized: serde_cbor::Value = serde_cbor::de::from_slice(&serialized).unwrap();
+        match deserialized {
+            serde_cbor::Value::Map(map) => assert!(!map.contains_key(&serde_cbor::Value::Text(
+                "payment_thresholds_opt".to_string()
+            ))),
+            x => panic!("Expected Map but got {:?}", x),
+        }
+        let round_trip: NodeRecordInner_0v1 = serde_cbor::de::from_slice(&serialized).unwrap();
+        assert_eq!(round_trip.payment_thresholds_opt, None);
+    }
+
     #[test]
     fn from_gnr_to_nri_when_gossip_is_corrupt() {
         let corrupt_gnr = GossipNodeRecord {
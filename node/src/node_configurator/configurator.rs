@@ -2,16 +2,18 @@
 
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use actix::{Actor, Context, Handler, Recipient};
 
 use masq_lib::messages::{
     FromMessageBody, ToMessageBody, UiChangePasswordRequest, UiChangePasswordResponse,
-    UiCheckPasswordRequest, UiCheckPasswordResponse, UiConfigurationRequest,
-    UiConfigurationResponse, UiGenerateSeedSpec, UiGenerateWalletsRequest,
-    UiGenerateWalletsResponse, UiNewPasswordBroadcast, UiPaymentThresholds, UiRatePack,
-    UiRecoverWalletsRequest, UiRecoverWalletsResponse, UiScanIntervals, UiSetConfigurationRequest,
-    UiSetConfigurationResponse, UiWalletAddressesRequest, UiWalletAddressesResponse,
+    UiChangeSettingsRequest, UiChangeSettingsResponse, UiCheckPasswordRequest,
+    UiCheckPasswordResponse, UiConfigurationRequest, UiConfigurationResponse, UiGenerateSeedSpec,
+    UiGenerateWalletsRequest, UiGenerateWalletsResponse, UiNewPasswordBroadcast,
+    UiPaymentThresholds, UiRatePack, UiRecoverWalletsRequest, UiRecoverWalletsResponse,
+    UiScanIntervals, UiSetConfigurationRequest, UiSetConfigurationResponse, UiUnlockWalletRequest,
+    UiUnlockWalletResponse, UiWalletAddressesRequest, UiWalletAddressesResponse,
 };
 use masq_lib::ui_gateway::MessageTarget::ClientId;
 use masq_lib::ui_gateway::{
@@ -26,16 +28,21 @@ use crate::db_config::config_dao::ConfigDaoReal;
 use crate::db_config::persistent_configuration::{
     PersistentConfigError, PersistentConfiguration, PersistentConfigurationReal,
 };
+use crate::node_configurator::unprivileged_parse_args_configuration::check_payment_thresholds;
+use crate::server_initializer::{LogLevelSetter, LogLevelSetterReal};
+use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals};
 use crate::sub_lib::neighborhood::{ConfigChange, ConfigChangeMsg, Hops, WalletPair};
 use crate::sub_lib::peer_actors::{BindMessage, ConfigChangeSubs};
 use crate::sub_lib::utils::{db_connection_launch_panic, handle_ui_crash_request};
-use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wallet::{Wallet, WalletError};
 use crate::test_utils::main_cryptde;
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use log::LevelFilter;
 use masq_lib::constants::{
     BAD_PASSWORD_ERROR, CONFIGURATOR_READ_ERROR, CONFIGURATOR_WRITE_ERROR, DERIVATION_PATH_ERROR,
-    ILLEGAL_MNEMONIC_WORD_COUNT_ERROR, MISSING_DATA, MNEMONIC_PHRASE_ERROR, NON_PARSABLE_VALUE,
-    UNKNOWN_ERROR, UNRECOGNIZED_MNEMONIC_LANGUAGE_ERROR, UNRECOGNIZED_PARAMETER,
+    ILLEGAL_MNEMONIC_WORD_COUNT_ERROR, ILLEGAL_VALUE, MAX_EARNING_WALLET_COUNT, MISSING_DATA,
+    MNEMONIC_PHRASE_ERROR, NON_PARSABLE_VALUE, UNKNOWN_ERROR, UNRECOGNIZED_MNEMONIC_LANGUAGE_ERROR,
+    UNRECOGNIZED_PARAMETER,
 };
 use masq_lib::logger::Logger;
 use masq_lib::utils::{derivation_path, to_string};
@@ -48,6 +55,7 @@ pub struct Configurator {
     persistent_config: Box<dyn PersistentConfiguration>,
     node_to_ui_sub_opt: Option<Recipient<NodeToUiMessage>>,
     config_change_subs_opt: Option<ConfigChangeSubs>,
+    log_level_setter: Box<dyn LogLevelSetter>,
     crashable: bool,
     logger: Logger,
 }
@@ -74,6 +82,8 @@ impl Handler<NodeFromUiMessage> for Configurator {
             self.call_handler(msg, |c| {
                 c.handle_change_password(body, client_id, context_id)
             });
+        } else if let Ok((body, context_id)) = UiChangeSettingsRequest::fmb(msg.body.clone()) {
+            self.call_handler(msg, |c| c.handle_change_settings(body, context_id));
         } else if let Ok((body, context_id)) = UiCheckPasswordRequest::fmb(msg.body.clone()) {
             self.call_handler(msg, |c| c.handle_check_password(body, context_id));
         } else if let Ok((body, context_id)) = UiConfigurationRequest::fmb(msg.body.clone()) {
@@ -84,6 +94,8 @@ impl Handler<NodeFromUiMessage> for Configurator {
             self.call_handler(msg, |c| c.handle_recover_wallets(body, context_id));
         } else if let Ok((body, context_id)) = UiSetConfigurationRequest::fmb(msg.body.clone()) {
             self.call_handler(msg, |c| c.handle_set_configuration(body, context_id));
+        } else if let Ok((body, context_id)) = UiUnlockWalletRequest::fmb(msg.body.clone()) {
+            self.call_handler(msg, |c| c.handle_unlock_wallet(body, context_id));
         } else if let Ok((body, context_id)) = UiWalletAddressesRequest::fmb(msg.body.clone()) {
             self.call_handler(msg, |c| c.handle_wallet_addresses(body, context_id));
         } else {
@@ -110,6 +122,7 @@ impl Configurator {
             persistent_config,
             node_to_ui_sub_opt: None,
             config_change_subs_opt: None,
+            log_level_setter: Box::new(LogLevelSetterReal),
             crashable,
             logger: Logger::new("Configurator"),
         }
@@ -231,6 +244,61 @@ impl Configurator {
         }
     }
 
+    fn handle_unlock_wallet(&mut self, msg: UiUnlockWalletRequest, context_id: u64) -> MessageBody {
+        match self.unfriendly_handle_unlock_wallet(&msg.db_password) {
+            Ok(consuming_wallet) => UiUnlockWalletResponse {
+                consuming_wallet_address: format!("{:?}", consuming_wallet.address()),
+            }
+            .tmb(context_id),
+            Err((code, e_msg)) => {
+                warning!(self.logger, "Failed to unlock consuming wallet: {}", e_msg);
+                MessageBody {
+                    opcode: msg.opcode().to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((code, e_msg)),
+                }
+            }
+        }
+    }
+
+    fn unfriendly_handle_unlock_wallet(&self, db_password: &str) -> Result<Wallet, MessageError> {
+        match self
+            .persistent_config
+            .check_password(Some(db_password.to_string()))
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                return Err((
+                    BAD_PASSWORD_ERROR,
+                    "Bad password; can't unlock consuming wallet".to_string(),
+                ))
+            }
+            Err(e) => return Err((CONFIGURATOR_READ_ERROR, format!("{:?}", e))),
+        }
+        let (consuming_wallet, earning_wallet) = match (
+            self.persistent_config.consuming_wallet(db_password),
+            self.persistent_config.earning_wallet(),
+        ) {
+            (Ok(Some(consuming_wallet)), Ok(Some(earning_wallet))) => {
+                (consuming_wallet, earning_wallet)
+            }
+            (Err(e), _) | (_, Err(e)) => return Err((CONFIGURATOR_READ_ERROR, format!("{:?}", e))),
+            _ => {
+                return Err((
+                    MISSING_DATA,
+                    "No consuming wallet is stored in the database".to_string(),
+                ))
+            }
+        };
+        self.send_config_change_msg(ConfigChangeMsg {
+            change: ConfigChange::UpdateWallets(WalletPair {
+                consuming_wallet: consuming_wallet.clone(),
+                earning_wallet,
+            }),
+        });
+        Ok(consuming_wallet)
+    }
+
     fn handle_generate_wallets(
         &mut self,
         msg: UiGenerateWalletsRequest,
@@ -317,12 +385,19 @@ impl Configurator {
         let earning_wallet = Self::generate_wallet(&seed, &earning_derivation_path)?;
         let earning_private_key =
             Self::generate_private_key(seed.as_bytes(), &earning_derivation_path)?;
+        let earning_rotation = Self::generate_earning_rotation(
+            &seed,
+            &earning_derivation_path,
+            Some(consuming_derivation_path.as_str()),
+            msg.earning_wallet_count_opt,
+        )?;
         Self::set_wallet_info(
             persistent_config,
             consuming_private_key.as_str(),
             &earning_wallet.string_address_from_keypair(),
             &msg.db_password,
         )?;
+        Self::set_earning_wallet_rotation(persistent_config, &earning_rotation)?;
         Ok(UiGenerateWalletsResponse {
             mnemonic_phrase_opt: Some(mnemonic_phrase),
             consuming_wallet_address: consuming_wallet.string_address_from_keypair(),
@@ -343,9 +418,11 @@ impl Configurator {
             "recover",
             &msg.db_password,
         )?;
-        let (consuming_wallet_private_key, earning_wallet_address) = match msg.seed_spec_opt {
+        let rotation_requested = matches!(msg.earning_wallet_count_opt, Some(count) if count != 1);
+        let (consuming_wallet_private_key, earning_wallet_address, earning_rotation) = match msg.seed_spec_opt {
             None => match (&msg.consuming_private_key_opt, msg.earning_address_opt) {
-                (Some (consuming_private_key), Some (earning_address)) => (consuming_private_key.clone(), earning_address),
+                (Some (_), Some (_)) if rotation_requested => return Err ((MISSING_DATA, "An earning wallet rotation can be derived only from seed information and an earning wallet derivation path".to_string())),
+                (Some (consuming_private_key), Some (earning_address)) => (consuming_private_key.clone(), earning_address, vec![]),
                 _ => return Err ((MISSING_DATA, "If you supply no seed information, you must supply both consuming wallet private key and earning wallet address".to_string())),
             },
             Some (seed_spec) => {
@@ -354,33 +431,49 @@ impl Configurator {
                     &seed_spec.mnemonic_phrase_language_opt.unwrap_or_else (|| "English".to_string()),
                     &seed_spec.mnemonic_phrase,
                 )?;
-                let consuming_private_key = match (msg.consuming_private_key_opt, msg.consuming_derivation_path_opt) {
-                    (Some (consuming_private_key), _) => consuming_private_key,
+                let consuming_derivation_path_opt = match &msg.consuming_private_key_opt {
+                    Some (_) => None,
+                    None => msg.consuming_derivation_path_opt.as_deref(),
+                };
+                let consuming_private_key = match (&msg.consuming_private_key_opt, consuming_derivation_path_opt) {
+                    (Some (consuming_private_key), _) => consuming_private_key.clone(),
                     (None, Some (consuming_derivation_path)) => {
-                        Self::generate_private_key(seed.as_bytes(), consuming_derivation_path.as_str())?
+                        Self::generate_private_key(seed.as_bytes(), consuming_derivation_path)?
                     },
                     _ => return Err((MISSING_DATA, "If you supply seed information, you must supply either the consuming wallet derivation path or the consuming wallet private key".to_string())),
                 };
-                let earning_address = match (msg.earning_address_opt, msg.earning_derivation_path_opt) {
-                    (Some (earning_address), _) => earning_address,
+                let (earning_address, earning_rotation) = match (msg.earning_address_opt, msg.earning_derivation_path_opt) {
+                    (Some (_), _) if rotation_requested => return Err ((MISSING_DATA, "An earning wallet rotation can be derived only from seed information and an earning wallet derivation path".to_string())),
+                    (Some (earning_address), _) => (earning_address, vec![]),
                     (None, Some (earning_derivation_path)) =>  {
                         let wallet = Self::generate_wallet(&seed, earning_derivation_path.as_str())?;
-                        wallet.string_address_from_keypair()
+                        let earning_rotation = Self::generate_earning_rotation(&seed, &earning_derivation_path, consuming_derivation_path_opt, msg.earning_wallet_count_opt)?;
+                        (wallet.string_address_from_keypair(), earning_rotation)
                     },
                     _ => return Err((MISSING_DATA, "If you supply seed information, you must supply either the earning wallet derivation path or the earning wallet address".to_string())),
                 };
-                (consuming_private_key, earning_address)
+                (consuming_private_key, earning_address, earning_rotation)
             },
         };
+        Self::check_earning_wallet_checksum(&earning_wallet_address)?;
         Self::set_wallet_info(
             persistent_config,
             consuming_wallet_private_key.as_str(),
             earning_wallet_address.as_str(),
             &msg.db_password,
         )?;
+        Self::set_earning_wallet_rotation(persistent_config, &earning_rotation)?;
         Ok(UiRecoverWalletsResponse {}.tmb(context_id))
     }
 
+    // Any other flaw of the address is left for the persistent configuration to refuse
+    fn check_earning_wallet_checksum(earning_wallet_address: &str) -> Result<(), MessageError> {
+        match Wallet::from_str(earning_wallet_address) {
+            Err(e @ WalletError::InvalidChecksum(_)) => Err((ILLEGAL_VALUE, e.to_string())),
+            _ => Ok(()),
+        }
+    }
+
     fn check_database_preconditions(
         persistent_config: &dyn PersistentConfiguration,
         operation: &str,
@@ -492,6 +585,62 @@ impl Configurator {
         }
     }
 
+    // The earning wallet followed by those at the next indexes of the last level of its
+    // derivation path; empty if no rotation is asked for
+    fn generate_earning_rotation(
+        seed: &Seed,
+        earning_derivation_path: &str,
+        consuming_derivation_path_opt: Option<&str>,
+        earning_wallet_count_opt: Option<u32>,
+    ) -> Result<Vec<Wallet>, MessageError> {
+        let count = match earning_wallet_count_opt {
+            None | Some(1) => return Ok(vec![]),
+            Some(count) if count == 0 || count > MAX_EARNING_WALLET_COUNT => {
+                return Err((
+                    ILLEGAL_VALUE,
+                    format!(
+                        "Earning wallet count must be between 1 and {}, not {}",
+                        MAX_EARNING_WALLET_COUNT, count
+                    ),
+                ))
+            }
+            Some(count) => count,
+        };
+        let bad_syntax = || {
+            (
+                DERIVATION_PATH_ERROR,
+                format!(
+                    "Bad derivation-path syntax: cannot count up from {}",
+                    earning_derivation_path
+                ),
+            )
+        };
+        let (stem, last_level) = earning_derivation_path
+            .rsplit_once('/')
+            .ok_or_else(bad_syntax)?;
+        let (index, hardening) = match last_level.strip_suffix('\'') {
+            Some(index) => (index, "'"),
+            None => (last_level, ""),
+        };
+        let first_index: u32 = index.parse().map_err(|_| bad_syntax())?;
+        (first_index..first_index + count)
+            .map(|index| format!("{}/{}{}", stem, index, hardening))
+            .map(|derivation_path| {
+                if Some(derivation_path.as_str()) == consuming_derivation_path_opt {
+                    Err((
+                        ILLEGAL_VALUE,
+                        format!(
+                            "Earning wallet rotation would include the consuming wallet at {}",
+                            derivation_path
+                        ),
+                    ))
+                } else {
+                    Self::generate_wallet(seed, &derivation_path)
+                }
+            })
+            .collect()
+    }
+
     fn generate_private_key(seed: &[u8], derivation_path: &str) -> Result<String, MessageError> {
         let binary = match ExtendedPrivKey::derive(seed, derivation_path) {
             Ok(epk) => epk.secret(),
@@ -699,6 +848,22 @@ impl Configurator {
         Ok(())
     }
 
+    fn set_earning_wallet_rotation(
+        persistent_config: &mut Box<dyn PersistentConfiguration>,
+        earning_rotation: &[Wallet],
+    ) -> Result<(), MessageError> {
+        if earning_rotation.is_empty() {
+            return Ok(());
+        }
+        if let Err(e) = persistent_config.set_earning_wallet_rotation(earning_rotation) {
+            return Err((
+                CONFIGURATOR_WRITE_ERROR,
+                format!("Earning wallet rotation could not be set: {:?}", e),
+            ));
+        }
+        Ok(())
+    }
+
     fn handle_set_configuration(
         &mut self,
         msg: UiSetConfigurationRequest,
@@ -801,6 +966,145 @@ impl Configurator {
         }
     }
 
+    fn handle_change_settings(
+        &mut self,
+        msg: UiChangeSettingsRequest,
+        context_id: u64,
+    ) -> MessageBody {
+        match self.unfriendly_handle_change_settings(msg, context_id) {
+            Ok(response) => response,
+            Err((code, msg)) => {
+                error!(
+                    self.logger,
+                    "The UiChangeSettingsRequest failed with an error {}: {}", code, msg
+                );
+                MessageBody {
+                    opcode: "changeSettings".to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((code, msg)),
+                }
+            }
+        }
+    }
+
+    fn unfriendly_handle_change_settings(
+        &mut self,
+        msg: UiChangeSettingsRequest,
+        context_id: u64,
+    ) -> Result<MessageBody, MessageError> {
+        if msg.gas_price_opt.is_none()
+            && msg.scan_intervals_opt.is_none()
+            && msg.payment_thresholds_opt.is_none()
+            && msg.log_level_opt.is_none()
+        {
+            return Err((
+                MISSING_DATA,
+                "No setting to change was supplied".to_string(),
+            ));
+        }
+        // Nothing is changed unless all the supplied values are valid
+        let scan_intervals_opt = msg
+            .scan_intervals_opt
+            .map(Self::scan_intervals_from_ui)
+            .transpose()?;
+        let payment_thresholds_opt = msg
+            .payment_thresholds_opt
+            .map(Self::payment_thresholds_from_ui)
+            .transpose()?;
+        let log_level_opt = msg
+            .log_level_opt
+            .map(|log_level| {
+                LevelFilter::from_str(&log_level).map_err(|_| {
+                    (
+                        NON_PARSABLE_VALUE,
+                        format!("log level: '{}' is not a recognized level", log_level),
+                    )
+                })
+            })
+            .transpose()?;
+
+        if let Some(gas_price) = msg.gas_price_opt {
+            if let Err(e) = self.persistent_config.set_gas_price(gas_price) {
+                return Err((CONFIGURATOR_WRITE_ERROR, format!("gas price: {:?}", e)));
+            }
+        }
+        if let Some(scan_intervals) = scan_intervals_opt {
+            if let Err(e) = self
+                .persistent_config
+                .set_scan_intervals(scan_intervals.to_string())
+            {
+                return Err((CONFIGURATOR_WRITE_ERROR, format!("scan intervals: {:?}", e)));
+            }
+            self.send_config_change_msg(ConfigChangeMsg {
+                change: ConfigChange::UpdateScanIntervals(scan_intervals),
+            });
+        }
+        if let Some(payment_thresholds) = payment_thresholds_opt {
+            if let Err(e) = self
+                .persistent_config
+                .set_payment_thresholds(payment_thresholds.to_string())
+            {
+                return Err((
+                    CONFIGURATOR_WRITE_ERROR,
+                    format!("payment thresholds: {:?}", e),
+                ));
+            }
+            self.send_config_change_msg(ConfigChangeMsg {
+                change: ConfigChange::UpdatePaymentThresholds(payment_thresholds),
+            });
+        }
+        // The log level comes from the command line only, so it lasts until the Node is restarted
+        if let Some(log_level) = log_level_opt {
+            if let Err(e) = self.log_level_setter.set_log_level(log_level) {
+                return Err((UNKNOWN_ERROR, format!("log level: {}", e)));
+            }
+            info!(self.logger, "Log level has been changed to {}", log_level);
+        }
+
+        Ok(UiChangeSettingsResponse {}.tmb(context_id))
+    }
+
+    fn scan_intervals_from_ui(
+        ui_scan_intervals: UiScanIntervals,
+    ) -> Result<ScanIntervals, MessageError> {
+        if ui_scan_intervals.pending_payable_sec == 0
+            || ui_scan_intervals.payable_sec == 0
+            || ui_scan_intervals.receivable_sec == 0
+        {
+            return Err((
+                ILLEGAL_VALUE,
+                "scan intervals: each interval must be at least 1 second".to_string(),
+            ));
+        }
+        Ok(ScanIntervals {
+            pending_payable_scan_interval: Duration::from_secs(
+                ui_scan_intervals.pending_payable_sec,
+            ),
+            payable_scan_interval: Duration::from_secs(ui_scan_intervals.payable_sec),
+            receivable_scan_interval: Duration::from_secs(ui_scan_intervals.receivable_sec),
+        })
+    }
+
+    fn payment_thresholds_from_ui(
+        ui_payment_thresholds: UiPaymentThresholds,
+    ) -> Result<PaymentThresholds, MessageError> {
+        let payment_thresholds = PaymentThresholds {
+            debt_threshold_gwei: ui_payment_thresholds.debt_threshold_gwei,
+            maturity_threshold_sec: ui_payment_thresholds.maturity_threshold_sec,
+            payment_grace_period_sec: ui_payment_thresholds.payment_grace_period_sec,
+            permanent_debt_allowed_gwei: ui_payment_thresholds.permanent_debt_allowed_gwei,
+            threshold_interval_sec: ui_payment_thresholds.threshold_interval_sec,
+            unban_below_gwei: ui_payment_thresholds.unban_below_gwei,
+        };
+        match check_payment_thresholds(&payment_thresholds) {
+            Ok(()) => Ok(payment_thresholds),
+            Err(e) => Err((
+                ILLEGAL_VALUE,
+                format!("payment thresholds: {}", e.param_errors[0].reason),
+            )),
+        }
+    }
+
     fn send_to_ui_gateway(&self, target: MessageTarget, body: MessageBody) {
         let msg = NodeToUiMessage { target, body };
         self.node_to_ui_sub_opt
@@ -889,6 +1193,7 @@ mod tests {
     use crate::db_config::persistent_configuration::{
         PersistentConfigError, PersistentConfigurationReal,
     };
+    use crate::server_initializer::test_utils::LogLevelSetterMock;
     use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
     use crate::test_utils::recorder::{make_recorder, peer_actors_builder};
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
@@ -1288,6 +1593,165 @@ mod tests {
         assert_eq!(ui_gateway_recording.len(), 1);
     }
 
+    #[test]
+    fn handle_unlock_wallet_works() {
+        let system = System::new("handle_unlock_wallet_works");
+        let check_password_params_arc = Arc::new(Mutex::new(vec![]));
+        let consuming_wallet_params_arc = Arc::new(Mutex::new(vec![]));
+        let consuming_wallet =
+            Wallet::from_str("0x1234567890123456789012345678901234567890").unwrap();
+        let earning_wallet =
+            Wallet::from_str("0x01234567890aa345678901234567890123456789").unwrap();
+        let persistent_config = PersistentConfigurationMock::new()
+            .check_password_params(&check_password_params_arc)
+            .check_password_result(Ok(true))
+            .consuming_wallet_params(&consuming_wallet_params_arc)
+            .consuming_wallet_result(Ok(Some(consuming_wallet.clone())))
+            .earning_wallet_result(Ok(Some(earning_wallet.clone())));
+        let subject = make_subject(Some(persistent_config));
+        let subject_addr = subject.start();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
+            .accountant(accountant)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiUnlockWalletRequest {
+                    db_password: "password".to_string(),
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let check_password_params = check_password_params_arc.lock().unwrap();
+        assert_eq!(*check_password_params, vec![Some("password".to_string())]);
+        let consuming_wallet_params = consuming_wallet_params_arc.lock().unwrap();
+        assert_eq!(*consuming_wallet_params, vec!["password".to_string()]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiUnlockWalletResponse {
+                    consuming_wallet_address: "0x1234567890123456789012345678901234567890"
+                        .to_string(),
+                }
+                .tmb(4321)
+            }
+        );
+        let expected_config_change_msg = ConfigChangeMsg {
+            change: ConfigChange::UpdateWallets(WalletPair {
+                consuming_wallet,
+                earning_wallet,
+            }),
+        };
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<ConfigChangeMsg>(0),
+            &expected_config_change_msg
+        );
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(
+            accountant_recording.get_record::<ConfigChangeMsg>(0),
+            &expected_config_change_msg
+        );
+    }
+
+    #[test]
+    fn handle_unlock_wallet_rejects_bad_password() {
+        init_test_logging();
+        let test_name = "handle_unlock_wallet_rejects_bad_password";
+        let persistent_config = PersistentConfigurationMock::new().check_password_result(Ok(false));
+        let mut subject = make_subject(Some(persistent_config));
+        subject.logger = Logger::new(test_name);
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject.config_change_subs_opt = Some(peer_actors.config_change_subs());
+
+        let result = subject.handle_unlock_wallet(
+            UiUnlockWalletRequest {
+                db_password: "wrong password".to_string(),
+            },
+            4321,
+        );
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "unlockWallet".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    BAD_PASSWORD_ERROR,
+                    "Bad password; can't unlock consuming wallet".to_string()
+                ))
+            }
+        );
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(blockchain_bridge_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Failed to unlock consuming wallet: Bad password; can't unlock consuming wallet"
+        ));
+    }
+
+    #[test]
+    fn handle_unlock_wallet_complains_if_no_wallet_is_stored() {
+        let persistent_config = PersistentConfigurationMock::new()
+            .check_password_result(Ok(true))
+            .consuming_wallet_result(Ok(None))
+            .earning_wallet_result(Ok(None));
+        let mut subject = make_subject(Some(persistent_config));
+
+        let result = subject.handle_unlock_wallet(
+            UiUnlockWalletRequest {
+                db_password: "password".to_string(),
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result.payload,
+            Err((
+                MISSING_DATA,
+                "No consuming wallet is stored in the database".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn handle_unlock_wallet_reports_database_error() {
+        let persistent_config = PersistentConfigurationMock::new()
+            .check_password_result(Ok(true))
+            .consuming_wallet_result(Err(PersistentConfigError::NotPresent))
+            .earning_wallet_result(Ok(None));
+        let mut subject = make_subject(Some(persistent_config));
+
+        let result = subject.handle_unlock_wallet(
+            UiUnlockWalletRequest {
+                db_password: "password".to_string(),
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result.payload,
+            Err((CONFIGURATOR_READ_ERROR, "NotPresent".to_string()))
+        );
+    }
+
     #[test]
     fn handle_wallet_addresses_works_if_consuming_wallet_private_key_error() {
         init_test_logging();
@@ -1541,6 +2005,7 @@ mod tests {
             }),
             consuming_derivation_path_opt: Some(derivation_path(0, 4)),
             earning_derivation_path_opt: Some(derivation_path(0, 5)),
+            earning_wallet_count_opt: None,
         };
 
         let result = subject.handle_generate_wallets(msg, 4321);
@@ -1592,6 +2057,7 @@ mod tests {
             seed_spec_opt: None,
             consuming_derivation_path_opt: Some("doesn't matter".to_string()),
             earning_derivation_path_opt: None,
+            earning_wallet_count_opt: None,
         };
 
         let result =
@@ -1618,6 +2084,7 @@ mod tests {
             seed_spec_opt: None,
             consuming_derivation_path_opt: None,
             earning_derivation_path_opt: Some("doesn't matter".to_string()),
+            earning_wallet_count_opt: None,
         };
 
         let result =
@@ -1652,6 +2119,7 @@ mod tests {
             }),
             consuming_derivation_path_opt: Some(derivation_path.clone()),
             earning_derivation_path_opt: None,
+            earning_wallet_count_opt: None,
         };
 
         let result =
@@ -1665,6 +2133,202 @@ mod tests {
         let _ = Mnemonic::from_phrase(&phrase_str, Language::English).unwrap();
     }
 
+    #[test]
+    fn unfriendly_handle_generate_wallets_sets_earning_wallet_rotation() {
+        let set_wallet_info_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_earning_wallet_rotation_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
+            PersistentConfigurationMock::new()
+                .check_password_result(Ok(true))
+                .set_wallet_info_params(&set_wallet_info_params_arc)
+                .set_wallet_info_result(Ok(()))
+                .set_earning_wallet_rotation_params(&set_earning_wallet_rotation_params_arc)
+                .set_earning_wallet_rotation_result(Ok(())),
+        );
+        let mut msg = make_example_generate_wallets_request();
+        msg.earning_wallet_count_opt = Some(3);
+
+        let result =
+            Configurator::unfriendly_handle_generate_wallets(msg, 1234, &mut persistent_config)
+                .unwrap();
+
+        let response = UiGenerateWalletsResponse::fmb(result).unwrap().0;
+        let mnemonic = Mnemonic::from_phrase(
+            &response.mnemonic_phrase_opt.unwrap().join(" "),
+            Language::English,
+        )
+        .unwrap();
+        let seed = Bip39::seed(&mnemonic, "booga");
+        let expected_addresses = (5..8)
+            .map(|index| {
+                Configurator::generate_wallet(&seed, &derivation_path(0, index))
+                    .unwrap()
+                    .address()
+            })
+            .collect::<Vec<_>>();
+        let set_earning_wallet_rotation_params =
+            set_earning_wallet_rotation_params_arc.lock().unwrap();
+        assert_eq!(set_earning_wallet_rotation_params.len(), 1);
+        assert_eq!(
+            set_earning_wallet_rotation_params[0]
+                .iter()
+                .map(|wallet| wallet.address())
+                .collect::<Vec<_>>(),
+            expected_addresses
+        );
+        assert_eq!(
+            response.earning_wallet_address,
+            format!("{:#x}", expected_addresses[0])
+        );
+        let set_wallet_info_params = set_wallet_info_params_arc.lock().unwrap();
+        assert_eq!(
+            set_wallet_info_params[0].1,
+            format!("{:#x}", expected_addresses[0])
+        );
+    }
+
+    #[test]
+    fn unfriendly_handle_generate_wallets_does_not_set_rotation_of_one() {
+        let set_earning_wallet_rotation_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
+            PersistentConfigurationMock::new()
+                .check_password_result(Ok(true))
+                .set_wallet_info_result(Ok(()))
+                .set_earning_wallet_rotation_params(&set_earning_wallet_rotation_params_arc),
+        );
+        let mut msg = make_example_generate_wallets_request();
+        msg.earning_wallet_count_opt = Some(1);
+
+        let result =
+            Configurator::unfriendly_handle_generate_wallets(msg, 1234, &mut persistent_config);
+
+        assert!(result.is_ok());
+        let set_earning_wallet_rotation_params =
+            set_earning_wallet_rotation_params_arc.lock().unwrap();
+        assert!(set_earning_wallet_rotation_params.is_empty());
+    }
+
+    #[test]
+    fn unfriendly_handle_generate_wallets_reports_failure_to_set_rotation() {
+        let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
+            PersistentConfigurationMock::new()
+                .check_password_result(Ok(true))
+                .set_wallet_info_result(Ok(()))
+                .set_earning_wallet_rotation_result(Err(PersistentConfigError::Collision(
+                    "booga".to_string(),
+                ))),
+        );
+        let mut msg = make_example_generate_wallets_request();
+        msg.earning_wallet_count_opt = Some(2);
+
+        let result =
+            Configurator::unfriendly_handle_generate_wallets(msg, 1234, &mut persistent_config);
+
+        assert_eq!(
+            result,
+            Err((
+                CONFIGURATOR_WRITE_ERROR,
+                "Earning wallet rotation could not be set: Collision(\"booga\")".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn generate_earning_rotation_counts_up_from_hardened_index() {
+        let (seed, _) =
+            Configurator::generate_seed_and_mnemonic_phrase(&None, "English", 12).unwrap();
+
+        let result =
+            Configurator::generate_earning_rotation(&seed, "m/44'/60'/7'", None, Some(2)).unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|wallet| wallet.address())
+                .collect::<Vec<_>>(),
+            vec![
+                Configurator::generate_wallet(&seed, "m/44'/60'/7'")
+                    .unwrap()
+                    .address(),
+                Configurator::generate_wallet(&seed, "m/44'/60'/8'")
+                    .unwrap()
+                    .address()
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_earning_rotation_rejects_counts_out_of_range() {
+        let (seed, _) =
+            Configurator::generate_seed_and_mnemonic_phrase(&None, "English", 12).unwrap();
+        let earning_path = derivation_path(0, 1);
+
+        let zero_result =
+            Configurator::generate_earning_rotation(&seed, &earning_path, None, Some(0));
+        let excess_result = Configurator::generate_earning_rotation(
+            &seed,
+            &earning_path,
+            None,
+            Some(MAX_EARNING_WALLET_COUNT + 1),
+        );
+
+        assert_eq!(
+            zero_result,
+            Err((
+                ILLEGAL_VALUE,
+                "Earning wallet count must be between 1 and 100, not 0".to_string()
+            ))
+        );
+        assert_eq!(
+            excess_result,
+            Err((
+                ILLEGAL_VALUE,
+                "Earning wallet count must be between 1 and 100, not 101".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn generate_earning_rotation_rejects_path_it_cannot_count_up_from() {
+        let (seed, _) =
+            Configurator::generate_seed_and_mnemonic_phrase(&None, "English", 12).unwrap();
+
+        let result = Configurator::generate_earning_rotation(&seed, "m/44'/60'/x", None, Some(2));
+
+        assert_eq!(
+            result,
+            Err((
+                DERIVATION_PATH_ERROR,
+                "Bad derivation-path syntax: cannot count up from m/44'/60'/x".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn generate_earning_rotation_rejects_rotation_including_consuming_wallet() {
+        let (seed, _) =
+            Configurator::generate_seed_and_mnemonic_phrase(&None, "English", 12).unwrap();
+        let consuming_path = derivation_path(0, 4);
+
+        let result = Configurator::generate_earning_rotation(
+            &seed,
+            &derivation_path(0, 3),
+            Some(consuming_path.as_str()),
+            Some(2),
+        );
+
+        assert_eq!(
+            result,
+            Err((
+                ILLEGAL_VALUE,
+                format!(
+                    "Earning wallet rotation would include the consuming wallet at {}",
+                    consuming_path
+                )
+            ))
+        );
+    }
+
     #[test]
     fn handle_recover_wallets_works_with_earning_wallet_address() {
         let check_password_params_arc = Arc::new(Mutex::new(vec![]));
@@ -1972,6 +2636,7 @@ mod tests {
             consuming_private_key_opt: Some(consuming_private_key.clone()),
             earning_derivation_path_opt: None,
             earning_address_opt: Some(earning_address.clone()),
+            earning_wallet_count_opt: None,
         };
         let set_wallet_info_params_arc = Arc::new(Mutex::new(vec![]));
         let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
@@ -2000,6 +2665,7 @@ mod tests {
             consuming_private_key_opt: None,
             earning_derivation_path_opt: None,
             earning_address_opt: Some("0x0123456789012345678901234567890123456789".to_string()),
+            earning_wallet_count_opt: None,
         };
         let mut persistent_config: Box<dyn PersistentConfiguration> =
             Box::new(configure_default_persistent_config(ZERO).check_password_result(Ok(true)));
@@ -2010,6 +2676,41 @@ mod tests {
         assert_eq! (result, Err((MISSING_DATA, "If you supply no seed information, you must supply both consuming wallet private key and earning wallet address".to_string())));
     }
 
+    #[test]
+    fn unfriendly_handle_recover_wallets_rejects_an_earning_wallet_address_with_a_wrong_checksum() {
+        let msg = UiRecoverWalletsRequest {
+            db_password: "password".to_string(),
+            seed_spec_opt: None,
+            consuming_derivation_path_opt: None,
+            consuming_private_key_opt: Some(
+                "00112233445566778899AABBCCDDEEFF00112233445566778899AABBCCDDEEFF".to_string(),
+            ),
+            earning_derivation_path_opt: None,
+            earning_address_opt: Some("0xAbCdEf0123456789012345678901234567890123".to_string()),
+            earning_wallet_count_opt: None,
+        };
+        let set_wallet_info_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
+            configure_default_persistent_config(ZERO)
+                .check_password_result(Ok(true))
+                .set_wallet_info_params(&set_wallet_info_params_arc),
+        );
+
+        let result =
+            Configurator::unfriendly_handle_recover_wallets(msg, 1234, &mut persistent_config);
+
+        assert_eq!(
+            result,
+            Err((
+                ILLEGAL_VALUE,
+                "Address 0xAbCdEf0123456789012345678901234567890123 fails its EIP-55 checksum; \
+                 check it for typos, or give it all in lower case"
+                    .to_string()
+            ))
+        );
+        assert!(set_wallet_info_params_arc.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn unfriendly_handle_recover_wallets_handles_seed_but_nothing_about_consuming_wallet() {
         let db_password = "password".to_string();
@@ -2025,6 +2726,7 @@ mod tests {
             consuming_private_key_opt: None,
             earning_derivation_path_opt: None,
             earning_address_opt: Some(earning_address),
+            earning_wallet_count_opt: None,
         };
         let mut persistent_config: Box<dyn PersistentConfiguration> =
             Box::new(configure_default_persistent_config(ZERO).check_password_result(Ok(true)));
@@ -2051,6 +2753,7 @@ mod tests {
             consuming_private_key_opt: Some(consuming_private_key),
             earning_derivation_path_opt: None,
             earning_address_opt: None,
+            earning_wallet_count_opt: None,
         };
         let mut persistent_config: Box<dyn PersistentConfiguration> =
             Box::new(configure_default_persistent_config(ZERO).check_password_result(Ok(true)));
@@ -2061,6 +2764,70 @@ mod tests {
         assert_eq! (result, Err((MISSING_DATA, "If you supply seed information, you must supply either the earning wallet derivation path or the earning wallet address".to_string())));
     }
 
+    #[test]
+    fn unfriendly_handle_recover_wallets_rejects_rotation_with_earning_address() {
+        let mut msg = make_example_recover_wallets_request_with_paths();
+        msg.earning_derivation_path_opt = None;
+        msg.earning_address_opt = Some("0x0123456789012345678901234567890123456789".to_string());
+        msg.earning_wallet_count_opt = Some(3);
+        let mut persistent_config: Box<dyn PersistentConfiguration> =
+            Box::new(configure_default_persistent_config(ZERO).check_password_result(Ok(true)));
+
+        let result =
+            Configurator::unfriendly_handle_recover_wallets(msg, 1234, &mut persistent_config);
+
+        assert_eq!(
+            result,
+            Err((
+                MISSING_DATA,
+                "An earning wallet rotation can be derived only from seed information and an \
+                 earning wallet derivation path"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn unfriendly_handle_recover_wallets_sets_earning_wallet_rotation() {
+        let set_earning_wallet_rotation_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut msg = make_example_recover_wallets_request_with_paths();
+        msg.earning_wallet_count_opt = Some(2);
+        let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
+            PersistentConfigurationMock::new()
+                .check_password_result(Ok(true))
+                .set_wallet_info_result(Ok(()))
+                .set_earning_wallet_rotation_params(&set_earning_wallet_rotation_params_arc)
+                .set_earning_wallet_rotation_result(Ok(())),
+        );
+
+        let result =
+            Configurator::unfriendly_handle_recover_wallets(msg, 1234, &mut persistent_config);
+
+        assert!(result.is_ok());
+        let seed = Configurator::make_seed(
+            &Some("ebullient".to_string()),
+            "English",
+            &make_meaningless_phrase_words(),
+        )
+        .unwrap();
+        let set_earning_wallet_rotation_params =
+            set_earning_wallet_rotation_params_arc.lock().unwrap();
+        assert_eq!(
+            set_earning_wallet_rotation_params[0]
+                .iter()
+                .map(|wallet| wallet.address())
+                .collect::<Vec<_>>(),
+            vec![
+                Configurator::generate_wallet(&seed, &derivation_path(0, 5))
+                    .unwrap()
+                    .address(),
+                Configurator::generate_wallet(&seed, &derivation_path(0, 6))
+                    .unwrap()
+                    .address()
+            ]
+        );
+    }
+
     #[test]
     fn unfriendly_handle_recover_wallets_defaults_language_to_english() {
         let db_password = "password".to_string();
@@ -2075,6 +2842,7 @@ mod tests {
             consuming_private_key_opt: None,
             earning_derivation_path_opt: None,
             earning_address_opt: Some("0x0123456789012345678901234567890123456789".to_string()),
+            earning_wallet_count_opt: None,
         };
         let mut persistent_config: Box<dyn PersistentConfiguration> = Box::new(
             configure_default_persistent_config(ZERO)
@@ -2358,6 +3126,236 @@ mod tests {
         ));
     }
 
+    fn make_change_settings_request() -> UiChangeSettingsRequest {
+        UiChangeSettingsRequest {
+            gas_price_opt: Some(77),
+            scan_intervals_opt: Some(UiScanIntervals {
+                pending_payable_sec: 150,
+                payable_sec: 250,
+                receivable_sec: 350,
+            }),
+            payment_thresholds_opt: Some(UiPaymentThresholds {
+                threshold_interval_sec: 10_000,
+                debt_threshold_gwei: 6_000_000,
+                payment_grace_period_sec: 1_200,
+                maturity_threshold_sec: 1_400,
+                permanent_debt_allowed_gwei: 500_000,
+                unban_below_gwei: 400_000,
+            }),
+            log_level_opt: Some("debug".to_string()),
+        }
+    }
+
+    #[test]
+    fn handle_change_settings_persists_values_and_notifies_subscribers() {
+        init_test_logging();
+        let test_name = "handle_change_settings_persists_values_and_notifies_subscribers";
+        let set_gas_price_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_scan_intervals_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_payment_thresholds_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_log_level_params_arc = Arc::new(Mutex::new(vec![]));
+        let persistent_config = PersistentConfigurationMock::new()
+            .set_gas_price_params(&set_gas_price_params_arc)
+            .set_gas_price_result(Ok(()))
+            .set_scan_intervals_params(&set_scan_intervals_params_arc)
+            .set_scan_intervals_result(Ok(()))
+            .set_payment_thresholds_params(&set_payment_thresholds_params_arc)
+            .set_payment_thresholds_result(Ok(()));
+        let system = System::new(test_name);
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder()
+            .accountant(accountant)
+            .neighborhood(neighborhood)
+            .build();
+        let mut subject = make_subject(Some(persistent_config));
+        subject.logger = Logger::new(test_name);
+        subject.config_change_subs_opt = Some(peer_actors.config_change_subs());
+        subject.log_level_setter = Box::new(
+            LogLevelSetterMock::default()
+                .set_log_level_params(&set_log_level_params_arc)
+                .set_log_level_result(Ok(())),
+        );
+
+        let result = subject.handle_change_settings(make_change_settings_request(), 4321);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "changeSettings".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Ok(r#"{}"#.to_string())
+            }
+        );
+        assert_eq!(*set_gas_price_params_arc.lock().unwrap(), vec![77]);
+        assert_eq!(
+            *set_scan_intervals_params_arc.lock().unwrap(),
+            vec!["150|250|350".to_string()]
+        );
+        assert_eq!(
+            *set_payment_thresholds_params_arc.lock().unwrap(),
+            vec!["6000000|1400|1200|500000|10000|400000".to_string()]
+        );
+        assert_eq!(
+            *set_log_level_params_arc.lock().unwrap(),
+            vec![LevelFilter::Debug]
+        );
+        let expected_scan_intervals_msg = ConfigChangeMsg {
+            change: ConfigChange::UpdateScanIntervals(ScanIntervals {
+                pending_payable_scan_interval: Duration::from_secs(150),
+                payable_scan_interval: Duration::from_secs(250),
+                receivable_scan_interval: Duration::from_secs(350),
+            }),
+        };
+        let expected_payment_thresholds_msg = ConfigChangeMsg {
+            change: ConfigChange::UpdatePaymentThresholds(PaymentThresholds {
+                debt_threshold_gwei: 6_000_000,
+                maturity_threshold_sec: 1_400,
+                payment_grace_period_sec: 1_200,
+                permanent_debt_allowed_gwei: 500_000,
+                threshold_interval_sec: 10_000,
+                unban_below_gwei: 400_000,
+            }),
+        };
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(
+            accountant_recording.get_record::<ConfigChangeMsg>(0),
+            &expected_scan_intervals_msg
+        );
+        assert_eq!(
+            accountant_recording.get_record::<ConfigChangeMsg>(1),
+            &expected_payment_thresholds_msg
+        );
+        assert_eq!(accountant_recording.len(), 2);
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 2);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Log level has been changed to DEBUG"
+        ));
+    }
+
+    #[test]
+    fn handle_change_settings_changes_nothing_if_any_value_is_invalid() {
+        init_test_logging();
+        let test_name = "handle_change_settings_changes_nothing_if_any_value_is_invalid";
+        // no results prepared in the mocks: any attempt to change something would panic
+        let mut subject = make_subject(None);
+        subject.logger = Logger::new(test_name);
+        let mut request = make_change_settings_request();
+        request
+            .payment_thresholds_opt
+            .as_mut()
+            .unwrap()
+            .permanent_debt_allowed_gwei = 6_000_000;
+
+        let result = subject.handle_change_settings(request, 4321);
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "changeSettings".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    ILLEGAL_VALUE,
+                    "payment thresholds: Value of DebtThresholdGwei (6000000) must be bigger \
+                     than PermanentDebtAllowedGwei (6000000)"
+                        .to_string()
+                ))
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: {test_name}: The UiChangeSettingsRequest failed with an error {}: payment \
+             thresholds: Value of DebtThresholdGwei (6000000) must be bigger than \
+             PermanentDebtAllowedGwei (6000000)",
+            ILLEGAL_VALUE
+        ));
+    }
+
+    #[test]
+    fn handle_change_settings_rejects_zero_scan_interval() {
+        let mut subject = make_subject(None);
+        let mut request = make_change_settings_request();
+        request.scan_intervals_opt.as_mut().unwrap().receivable_sec = 0;
+
+        let result = subject.handle_change_settings(request, 4321);
+
+        assert_eq!(
+            result.payload,
+            Err((
+                ILLEGAL_VALUE,
+                "scan intervals: each interval must be at least 1 second".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn handle_change_settings_rejects_unrecognized_log_level() {
+        let mut subject = make_subject(None);
+        let mut request = make_change_settings_request();
+        request.log_level_opt = Some("chatty".to_string());
+
+        let result = subject.handle_change_settings(request, 4321);
+
+        assert_eq!(
+            result.payload,
+            Err((
+                NON_PARSABLE_VALUE,
+                "log level: 'chatty' is not a recognized level".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn handle_change_settings_rejects_request_without_settings() {
+        let mut subject = make_subject(None);
+
+        let result = subject.handle_change_settings(
+            UiChangeSettingsRequest {
+                gas_price_opt: None,
+                scan_intervals_opt: None,
+                payment_thresholds_opt: None,
+                log_level_opt: None,
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result.payload,
+            Err((
+                MISSING_DATA,
+                "No setting to change was supplied".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn handle_change_settings_reports_failure_of_log_level_change() {
+        let persistent_config = PersistentConfigurationMock::new().set_gas_price_result(Ok(()));
+        let mut subject = make_subject(Some(persistent_config));
+        subject.log_level_setter = Box::new(
+            LogLevelSetterMock::default()
+                .set_log_level_result(Err("Logging subsystem has not been started".to_string())),
+        );
+        let request = UiChangeSettingsRequest {
+            gas_price_opt: Some(5),
+            scan_intervals_opt: None,
+            payment_thresholds_opt: None,
+            log_level_opt: Some("trace".to_string()),
+        };
+
+        let result = subject.handle_change_settings(request, 4321);
+
+        assert_eq!(
+            result.payload,
+            Err((
+                UNKNOWN_ERROR,
+                "log level: Logging subsystem has not been started".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn handle_set_configuration_throws_err_for_invalid_min_hops() {
         init_test_logging();
@@ -2972,6 +3970,7 @@ mod tests {
             }),
             consuming_derivation_path_opt: Some(derivation_path(0, 4)),
             earning_derivation_path_opt: Some(derivation_path(0, 5)),
+            earning_wallet_count_opt: None,
         }
     }
 
@@ -2987,6 +3986,7 @@ mod tests {
             consuming_private_key_opt: None,
             earning_derivation_path_opt: Some(derivation_path(0, 5)),
             earning_address_opt: None,
+            earning_wallet_count_opt: None,
         }
     }
 
@@ -2996,6 +3996,7 @@ mod tests {
                 persistent_config,
                 node_to_ui_sub_opt: None,
                 config_change_subs_opt: None,
+                log_level_setter: Box::new(LogLevelSetterMock::default()),
                 crashable: false,
                 logger: Logger::new("Configurator"),
             }
@@ -4,26 +4,39 @@ use crate::accountant::DEFAULT_PENDING_TOO_LONG_SEC;
 use crate::blockchain::bip32::Bip32EncryptionKeyProvider;
 use crate::bootstrapper::BootstrapperConfig;
 use crate::db_config::persistent_configuration::{PersistentConfigError, PersistentConfiguration};
-use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals, DEFAULT_EARNING_WALLET};
+use crate::sub_lib::accountant::{
+    ConsumingWalletSelection, GasBumpLadder, GasPriceCeiling, IdleSpendingFreeze, PaymentBatching,
+    PaymentThresholds, PaymentWindow, ScanIntervals, SpendRateLimits, UnreachableCreditorHold,
+    DEFAULT_EARNING_WALLET,
+};
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde_null::CryptDENull;
 use crate::sub_lib::cryptde_real::CryptDEReal;
 use crate::sub_lib::neighborhood::{
-    Hops, NeighborhoodConfig, NeighborhoodMode, NodeDescriptor, RatePack,
+    CountryClaimCheck, Hops, NeighborhoodConfig, NeighborhoodMode, NodeDescriptor, RatePack,
 };
 use crate::sub_lib::node_addr::NodeAddr;
-use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wallet::{Wallet, WalletError};
 use clap::value_t;
 use itertools::Itertools;
 use masq_lib::blockchains::chains::Chain;
-use masq_lib::constants::{DEFAULT_CHAIN, MASQ_URL_PREFIX};
+use masq_lib::constants::{
+    DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS, DEFAULT_CHAIN, DEFAULT_GAS_RUNWAY_ALERT_SCANS,
+    DEFAULT_MAX_BATCH_SIZE, DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS,
+    DEFAULT_RECEIVABLE_SCAN_ATTEMPTS, DEFAULT_RECEIVABLE_SCAN_RECORDS, DEFAULT_RPC_DAILY_QUOTA,
+    DEFAULT_TOP_UP_HORIZON_HOURS, MASQ_URL_PREFIX,
+};
 use masq_lib::logger::Logger;
+use masq_lib::messages::{CountryGroups, ExitLocation};
 use masq_lib::multi_config::MultiConfig;
 use masq_lib::shared_schema::{ConfiguratorError, ParamError};
 use masq_lib::utils::{to_string, AutomapProtocol, ExpectValue};
 use rustc_hex::FromHex;
+use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr};
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use web3::types::Address;
 
 pub trait UnprivilegedParseArgsConfiguration {
     // Only initialization that cannot be done with privilege should happen here.
@@ -56,6 +69,45 @@ pub trait UnprivilegedParseArgsConfiguration {
                     Err(pce) => return Err(pce.into_configurator_error("gas-price")),
                 }
             };
+        unprivileged_config
+            .blockchain_bridge_config
+            .payment_forwarders = get_payment_forwarders(multi_config)?;
+        unprivileged_config
+            .blockchain_bridge_config
+            .receivable_confirmation_blocks =
+            value_m!(multi_config, "receivable-confirmation-blocks", u64)
+                .unwrap_or(DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS);
+        unprivileged_config
+            .blockchain_bridge_config
+            .receivable_scan_attempts = value_m!(multi_config, "receivable-scan-attempts", u16)
+            .unwrap_or(DEFAULT_RECEIVABLE_SCAN_ATTEMPTS);
+        unprivileged_config
+            .blockchain_bridge_config
+            .receivable_scan_records = value_m!(multi_config, "receivable-scan-records", u64)
+            .unwrap_or(DEFAULT_RECEIVABLE_SCAN_RECORDS);
+        unprivileged_config
+            .blockchain_bridge_config
+            .blockchain_request_timeout_secs =
+            value_m!(multi_config, "blockchain-request-timeout", u64)
+                .unwrap_or(DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS);
+        unprivileged_config.blockchain_bridge_config.payment_memo =
+            value_m!(multi_config, "payment-memo", String).unwrap_or_else(|| "off".to_string())
+                == *"on";
+        unprivileged_config
+            .blockchain_bridge_config
+            .payment_simulation = value_m!(multi_config, "payment-simulation", String)
+            .unwrap_or_else(|| "off".to_string())
+            == *"on";
+        unprivileged_config.blockchain_bridge_config.max_batch_size =
+            value_m!(multi_config, "max-batch-size", u16).unwrap_or(DEFAULT_MAX_BATCH_SIZE);
+        unprivileged_config.blockchain_bridge_config.rpc_daily_quota =
+            value_m!(multi_config, "rpc-daily-quota", u64).unwrap_or(DEFAULT_RPC_DAILY_QUOTA);
+        unprivileged_config.blockchain_bridge_config.dev_faucet = get_dev_faucet(multi_config)?;
+        unprivileged_config.smart_account_owners = get_smart_account_owners(multi_config)?;
+        if let Some(check) = value_m!(multi_config, "country-claim-check", CountryClaimCheck) {
+            unprivileged_config.country_claim_check = check
+        }
+        unprivileged_config.exit_locations = get_exit_locations(multi_config);
         unprivileged_config.db_password_opt = value_m!(multi_config, "db-password", String);
         configure_accountant_config(multi_config, unprivileged_config, persistent_config)?;
         unprivileged_config.mapping_protocol_opt =
@@ -124,7 +176,33 @@ pub fn get_wallets(
     persistent_config: &mut dyn PersistentConfiguration,
     config: &mut BootstrapperConfig,
 ) -> Result<(), ConfiguratorError> {
-    let mc_consuming_opt = value_m!(multi_config, "consuming-private-key", String);
+    let mc_consuming_keys = value_m!(multi_config, "consuming-private-key", String)
+        .map(|keys| {
+            keys.split(',')
+                .map(|key| key.trim().to_string())
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+    let duplicate_keys = mc_consuming_keys
+        .iter()
+        .enumerate()
+        .filter(|(idx, key)| {
+            mc_consuming_keys[..*idx]
+                .iter()
+                .any(|earlier| wallet_params_are_equal(earlier, key))
+        })
+        .map(|(_, key)| key.as_str())
+        .collect::<Vec<&str>>();
+    if !duplicate_keys.is_empty() {
+        return Err(ConfiguratorError::required(
+            "consuming-private-key",
+            &format!(
+                "Each consuming private key may be supplied only once: {}",
+                duplicate_keys.join(",")
+            ),
+        ));
+    }
+    let mc_consuming_opt = mc_consuming_keys.first().cloned();
     let mc_earning_opt = value_m!(multi_config, "earning-wallet", String);
     let pc_consuming_opt = if let Some(db_password) = &config.db_password_opt {
         match persistent_config.consuming_wallet_private_key(db_password.as_str()) {
@@ -164,37 +242,68 @@ pub fn get_wallets(
             ))
         }
     };
-    let consuming_wallet_opt = consuming_opt.map(|consuming_private_key| {
-        let key_bytes = consuming_private_key
-            .from_hex::<Vec<u8>>()
-            .unwrap_or_else(|_| {
-                panic!(
-                    "Wallet corruption: bad hex value for consuming wallet private key: {}",
-                    consuming_private_key
-                )
-            });
-        let key_pair = Bip32EncryptionKeyProvider::from_raw_secret(key_bytes.as_slice())
-            .unwrap_or_else(|_| {
-                panic!(
-                    "Wallet corruption: consuming wallet private key in invalid format: {:?}",
-                    key_bytes
-                )
-            });
-        Wallet::from(key_pair)
-    });
-    let earning_wallet_opt = earning_opt.map(|earning_address| {
-        Wallet::from_str(&earning_address).unwrap_or_else(|_| {
-            panic!(
-                "Wallet corruption: bad value for earning wallet address: {}",
-                earning_address
-            )
-        })
-    });
+    let consuming_wallet_opt = consuming_opt.map(|key| wallet_from_private_key(&key));
+    let earning_wallet_opt = match earning_opt {
+        Some(earning_address) => Some(parse_wallet("earning-wallet", &earning_address)?),
+        None => None,
+    };
     config.consuming_wallet_opt = consuming_wallet_opt;
-    config.earning_wallet = earning_wallet_opt.unwrap_or_else(|| DEFAULT_EARNING_WALLET.clone());
+    config.additional_consuming_wallets = mc_consuming_keys
+        .iter()
+        .skip(1)
+        .map(|key| wallet_from_private_key(key))
+        .collect();
+    if let Some(selection) = value_m!(
+        multi_config,
+        "consuming-wallet-selection",
+        ConsumingWalletSelection
+    ) {
+        config.consuming_wallet_selection = selection
+    }
+    // A rotation is stored only alongside the earning wallet it begins with
+    config.earning_wallet_rotation = match (&earning_opt, &pc_earning_opt) {
+        (Some(_), Some(_)) => persistent_config
+            .earning_wallet_rotation()
+            .map_err(|e| e.into_configurator_error("earning-wallet"))?,
+        _ => vec![],
+    };
+    config.earning_wallet = if config.earning_wallet_rotation.is_empty() {
+        earning_wallet_opt.unwrap_or_else(|| DEFAULT_EARNING_WALLET.clone())
+    } else {
+        todays_earning_wallet(&config.earning_wallet_rotation, SystemTime::now())
+    };
     Ok(())
 }
 
+// Each day since the epoch falls to the next wallet in the rotation
+fn todays_earning_wallet(rotation: &[Wallet], now: SystemTime) -> Wallet {
+    let days = now
+        .duration_since(UNIX_EPOCH)
+        .expect("System clock is set before 1970")
+        .as_secs()
+        / 86_400;
+    rotation[(days % rotation.len() as u64) as usize].clone()
+}
+
+fn wallet_from_private_key(consuming_private_key: &str) -> Wallet {
+    let key_bytes = consuming_private_key
+        .from_hex::<Vec<u8>>()
+        .unwrap_or_else(|_| {
+            panic!(
+                "Wallet corruption: bad hex value for consuming wallet private key: {}",
+                consuming_private_key
+            )
+        });
+    let key_pair = Bip32EncryptionKeyProvider::from_raw_secret(key_bytes.as_slice())
+        .unwrap_or_else(|_| {
+            panic!(
+                "Wallet corruption: consuming wallet private key in invalid format: {:?}",
+                key_bytes
+            )
+        });
+    Wallet::from(key_pair)
+}
+
 fn wallet_params_are_equal(a: &str, b: &str) -> bool {
     a.to_uppercase() == b.to_uppercase()
 }
@@ -506,15 +615,139 @@ fn configure_accountant_config(
     )?;
     let suppress_initial_scans =
         value_m!(multi_config, "scans", String).unwrap_or_else(|| "on".to_string()) == *"off";
+    let scan_pipelining = value_m!(multi_config, "scan-pipelining", String)
+        .unwrap_or_else(|| "off".to_string())
+        == *"on";
 
     config.payment_thresholds_opt = Some(payment_thresholds);
     config.scan_intervals_opt = Some(scan_intervals);
     config.suppress_initial_scans = suppress_initial_scans;
-    config.when_pending_too_long_sec = DEFAULT_PENDING_TOO_LONG_SEC;
+    config.scan_pipelining = scan_pipelining;
+    config.when_pending_too_long_sec =
+        value_m!(multi_config, "max-pending-age", u64).unwrap_or(DEFAULT_PENDING_TOO_LONG_SEC);
+    config.gas_runway_alert_scans =
+        value_m!(multi_config, "gas-runway-alert", u64).unwrap_or(DEFAULT_GAS_RUNWAY_ALERT_SCANS);
+    config.top_up_horizon_hours =
+        value_m!(multi_config, "top-up-horizon", u64).unwrap_or(DEFAULT_TOP_UP_HORIZON_HOURS);
+    config.price_feed_url_opt = value_m!(multi_config, "price-feed-url", String);
+    config.payment_batching_opt = value_m!(multi_config, "payment-batching", String)
+        .map(|value| PaymentBatching::from_str(&value))
+        .transpose()
+        .map_err(|e| ConfiguratorError::required("payment-batching", &e))?;
+    config.unreachable_creditor_hold_opt =
+        value_m!(multi_config, "unreachable-creditor-hold", String)
+            .map(|value| UnreachableCreditorHold::from_str(&value))
+            .transpose()
+            .map_err(|e| ConfiguratorError::required("unreachable-creditor-hold", &e))?;
+    config.spend_rate_limits_opt = value_m!(multi_config, "spend-rate-limits", String)
+        .map(|value| SpendRateLimits::from_str(&value))
+        .transpose()
+        .map_err(|e| ConfiguratorError::required("spend-rate-limits", &e))?;
+    config.payment_window_opt = value_m!(multi_config, "payment-window", String)
+        .map(|value| PaymentWindow::from_str(&value))
+        .transpose()
+        .map_err(|e| ConfiguratorError::required("payment-window", &e))?;
+    config.gas_price_ceiling_opt = value_m!(multi_config, "gas-price-ceiling", String)
+        .map(|value| GasPriceCeiling::from_str(&value))
+        .transpose()
+        .map_err(|e| ConfiguratorError::required("gas-price-ceiling", &e))?;
+    config.gas_bump_ladder_opt = value_m!(multi_config, "gas-bump-ladder", String)
+        .map(|value| GasBumpLadder::from_str(&value))
+        .transpose()
+        .map_err(|e| ConfiguratorError::required("gas-bump-ladder", &e))?;
+    config.idle_spending_freeze_opt = value_m!(multi_config, "idle-spending-freeze", String)
+        .map(|value| IdleSpendingFreeze::from_str(&value))
+        .transpose()
+        .map_err(|e| ConfiguratorError::required("idle-spending-freeze", &e))?;
+    config.check_adjustment_invariants =
+        match value_m!(multi_config, "adjustment-invariants", String) {
+            Some(value) => value.eq_ignore_ascii_case("on"),
+            None => cfg!(debug_assertions),
+        };
     Ok(())
 }
 
-fn check_payment_thresholds(
+// Only a development chain has accounts unlocked to hand out test funds
+fn get_dev_faucet(multi_config: &MultiConfig) -> Result<bool, ConfiguratorError> {
+    match value_m!(multi_config, "dev-faucet", String) {
+        Some(value) if value.eq_ignore_ascii_case("on") => {
+            let chain = Chain::from(
+                value_m!(multi_config, "chain", String)
+                    .unwrap_or_else(|| DEFAULT_CHAIN.rec().literal_identifier.to_string())
+                    .as_str(),
+            );
+            if chain == Chain::Dev {
+                Ok(true)
+            } else {
+                Err(ConfiguratorError::required(
+                    "dev-faucet",
+                    &format!(
+                        "The faucet is available with --chain dev only, not {}",
+                        chain.rec().literal_identifier
+                    ),
+                ))
+            }
+        }
+        _ => Ok(false),
+    }
+}
+
+// The format of the address has been validated already, but not its checksum, if it has one
+fn parse_wallet(parameter: &str, address: &str) -> Result<Wallet, ConfiguratorError> {
+    Wallet::from_str(address).map_err(|e| match e {
+        WalletError::InvalidChecksum(_) => ConfiguratorError::required(parameter, &e.to_string()),
+        _ => panic!("Validation failed: bad {} address {}", parameter, address),
+    })
+}
+
+fn get_payment_forwarders(multi_config: &MultiConfig) -> Result<Vec<Address>, ConfiguratorError> {
+    match value_m!(multi_config, "payment-forwarders", String) {
+        Some(addresses) => addresses
+            .split(',')
+            .map(|address| parse_wallet("payment-forwarders", address).map(|w| w.address()))
+            .collect(),
+        None => Ok(vec![]),
+    }
+}
+
+fn get_smart_account_owners(
+    multi_config: &MultiConfig,
+) -> Result<HashMap<Wallet, Wallet>, ConfiguratorError> {
+    let to_wallet = |address: &str| parse_wallet("smart-account-owners", address);
+    match value_m!(multi_config, "smart-account-owners", String) {
+        Some(pairs) => pairs
+            .split(',')
+            .map(|pair| {
+                let (smart_account, owner) = pair.split_once(':').unwrap_or_else(|| {
+                    panic!("Validation failed: bad smart account ownership {}", pair)
+                });
+                Ok((to_wallet(smart_account)?, to_wallet(owner)?))
+            })
+            .collect(),
+        None => Ok(HashMap::new()),
+    }
+}
+
+// As in CZ,SK|PL: the groups of countries come in order of preference
+fn get_exit_locations(multi_config: &MultiConfig) -> Vec<ExitLocation> {
+    value_m!(multi_config, "exit-locations", String)
+        .map(|groups| {
+            groups
+                .split('|')
+                .enumerate()
+                .map(|(index, group)| {
+                    let country_groups = CountryGroups::from((group.to_string(), index));
+                    ExitLocation {
+                        country_codes: country_groups.country_codes,
+                        priority: country_groups.priority,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn check_payment_thresholds(
     payment_thresholds: &PaymentThresholds,
 ) -> Result<(), ConfiguratorError> {
     if payment_thresholds.debt_threshold_gwei <= payment_thresholds.permanent_debt_allowed_gwei {
@@ -641,7 +874,7 @@ mod tests {
         make_persistent_config_real_with_config_dao_null, make_simplified_multi_config,
         ACCOUNTANT_CONFIG_PARAMS, MAPPING_PROTOCOL, RATE_PACK, ZERO,
     };
-    use crate::test_utils::{main_cryptde, ArgsBuilder};
+    use crate::test_utils::{main_cryptde, make_wallet, ArgsBuilder};
     use masq_lib::constants::DEFAULT_GAS_PRICE;
     use masq_lib::multi_config::{CommandLineVcl, NameValueVclArg, VclArg, VirtualCommandLine};
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
@@ -2337,13 +2570,13 @@ mod tests {
         running_test();
         let args = [
             "--earning-wallet",
-            "0xb00fa567890123456789012345678901234B00FA",
+            "0xB00fA567890123456789012345678901234b00fA",
         ];
         let multi_config = make_simplified_multi_config(args);
         let mut persistent_config = make_persistent_config(
             None,
             None,
-            Some("0xB00FA567890123456789012345678901234b00fa"),
+            Some("0xB00FA567890123456789012345678901234B00FA"),
             None,
             None,
             None,
@@ -2359,6 +2592,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn earning_wallet_address_with_a_wrong_checksum_is_rejected() {
+        running_test();
+        let args = [
+            "--earning-wallet",
+            "0xB00FA567890123456789012345678901234b00fA",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config =
+            make_persistent_config(None, None, None, None, None, None, None);
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config).err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::required(
+                "earning-wallet",
+                "Address 0xB00FA567890123456789012345678901234b00fA fails its EIP-55 checksum; \
+                 check it for typos, or give it all in lower case"
+            ))
+        );
+    }
+
     #[test]
     fn consuming_wallet_private_key_different_from_database() {
         running_test();
@@ -2389,6 +2646,106 @@ mod tests {
         )
     }
 
+    #[test]
+    fn get_wallets_accepts_additional_consuming_private_keys() {
+        running_test();
+        let primary_key = "ABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCD";
+        let first_additional_key =
+            "0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF";
+        let second_additional_key =
+            "FEDCBA9876543210FEDCBA9876543210FEDCBA9876543210FEDCBA9876543210";
+        let keys = format!(
+            "{},{},{}",
+            primary_key, first_additional_key, second_additional_key
+        );
+        let args = [
+            "--consuming-private-key",
+            keys.as_str(),
+            "--consuming-wallet-selection",
+            "highest-balance",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config = make_persistent_config(
+            Some("password"),
+            Some(primary_key),
+            Some("0x0123456789012345678901234567890123456789"),
+            None,
+            None,
+            None,
+            None,
+        );
+        let mut config = BootstrapperConfig::new();
+        config.db_password_opt = Some("password".to_string());
+
+        get_wallets(&multi_config, &mut persistent_config, &mut config).unwrap();
+
+        let wallet_from = |key: &str| {
+            Wallet::from(
+                Bip32EncryptionKeyProvider::from_raw_secret(
+                    key.from_hex::<Vec<u8>>().unwrap().as_slice(),
+                )
+                .unwrap(),
+            )
+        };
+        assert_eq!(config.consuming_wallet_opt, Some(wallet_from(primary_key)));
+        assert_eq!(
+            config.additional_consuming_wallets,
+            vec![
+                wallet_from(first_additional_key),
+                wallet_from(second_additional_key)
+            ]
+        );
+        assert_eq!(
+            config.consuming_wallet_selection,
+            ConsumingWalletSelection::HighestBalance
+        );
+    }
+
+    #[test]
+    fn get_wallets_defaults_to_single_consuming_wallet_and_round_robin() {
+        running_test();
+        let consuming_private_key =
+            "ABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCD";
+        let args = ["--consuming-private-key", consuming_private_key];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config =
+            make_persistent_config(None, None, None, None, None, None, None);
+        let mut config = BootstrapperConfig::new();
+
+        get_wallets(&multi_config, &mut persistent_config, &mut config).unwrap();
+
+        assert!(config.consuming_wallet_opt.is_some());
+        assert_eq!(config.additional_consuming_wallets, vec![]);
+        assert_eq!(
+            config.consuming_wallet_selection,
+            ConsumingWalletSelection::RoundRobin
+        );
+    }
+
+    #[test]
+    fn get_wallets_rejects_duplicate_consuming_private_keys() {
+        running_test();
+        let keys = "ABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCD,\
+            0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF,\
+            abcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcd";
+        let args = ["--consuming-private-key", keys];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config =
+            make_persistent_config(None, None, None, None, None, None, None);
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config).err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "consuming-private-key",
+                "Each consuming private key may be supplied only once: \
+                 abcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcd"
+            )]))
+        )
+    }
+
     #[test]
     fn consuming_wallet_private_key_with_no_db_password_parameter() {
         running_test();
@@ -2414,6 +2771,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_wallets_takes_todays_earning_wallet_from_rotation() {
+        running_test();
+        let multi_config = make_simplified_multi_config([]);
+        let rotation = vec![
+            Wallet::from_str("0xcafedeadbeefbabefacecafedeadbeefbabeface").unwrap(),
+            Wallet::from_str("0x0123456789012345678901234567890123456789").unwrap(),
+            Wallet::from_str("0x9876543210987654321098765432109876543210").unwrap(),
+        ];
+        let mut persistent_config = PersistentConfigurationMock::new()
+            .earning_wallet_address_result(Ok(Some(
+                "0xcafedeadbeefbabefacecafedeadbeefbabeface".to_string(),
+            )))
+            .earning_wallet_rotation_result(Ok(rotation.clone()));
+        let mut config = BootstrapperConfig::new();
+
+        get_wallets(&multi_config, &mut persistent_config, &mut config).unwrap();
+
+        assert_eq!(config.earning_wallet_rotation, rotation);
+        assert_eq!(
+            config.earning_wallet,
+            todays_earning_wallet(&rotation, SystemTime::now())
+        );
+    }
+
+    #[test]
+    fn get_wallets_handles_failure_of_earning_wallet_rotation() {
+        let multi_config = make_simplified_multi_config([]);
+        let mut persistent_config = PersistentConfigurationMock::new()
+            .earning_wallet_address_result(Ok(Some(
+                "0xcafedeadbeefbabefacecafedeadbeefbabeface".to_string(),
+            )))
+            .earning_wallet_rotation_result(Err(PersistentConfigError::NotPresent));
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config);
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::NotPresent.into_configurator_error("earning-wallet"))
+        );
+    }
+
+    #[test]
+    fn todays_earning_wallet_moves_through_rotation_day_by_day() {
+        let rotation = vec![
+            make_wallet("first"),
+            make_wallet("second"),
+            make_wallet("third"),
+        ];
+        let day = Duration::from_secs(86_400);
+        let seventh_day = UNIX_EPOCH + day * 7;
+
+        let results = (0..4)
+            .map(|offset| todays_earning_wallet(&rotation, seventh_day + day * offset))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                make_wallet("second"),
+                make_wallet("third"),
+                make_wallet("first"),
+                make_wallet("second")
+            ]
+        );
+    }
+
     #[test]
     fn configure_rate_pack_command_line_absent_config_dao_null_so_all_defaults() {
         running_test();
@@ -2602,6 +3027,27 @@ mod tests {
         assert_eq!(bootstrapper_config.suppress_initial_scans, false);
     }
 
+    #[test]
+    fn unprivileged_configuration_handles_scan_pipelining_on() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--scan-pipelining", "on"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(bootstrapper_config.scan_pipelining, true);
+    }
+
     #[test]
     fn unprivileged_configuration_defaults_scans() {
         running_test();
@@ -2621,6 +3067,1269 @@ mod tests {
             .unwrap();
 
         assert_eq!(bootstrapper_config.suppress_initial_scans, false);
+        assert_eq!(bootstrapper_config.scan_pipelining, false);
+        assert_eq!(
+            bootstrapper_config.gas_runway_alert_scans,
+            DEFAULT_GAS_RUNWAY_ALERT_SCANS
+        );
+        assert_eq!(
+            bootstrapper_config.top_up_horizon_hours,
+            DEFAULT_TOP_UP_HORIZON_HOURS
+        );
+        assert_eq!(bootstrapper_config.price_feed_url_opt, None);
+        assert_eq!(bootstrapper_config.payment_batching_opt, None);
+        assert_eq!(bootstrapper_config.unreachable_creditor_hold_opt, None);
+        assert_eq!(
+            bootstrapper_config.country_claim_check,
+            CountryClaimCheck::Log
+        );
+        assert_eq!(bootstrapper_config.exit_locations, vec![]);
+        assert_eq!(bootstrapper_config.spend_rate_limits_opt, None);
+        assert_eq!(bootstrapper_config.payment_window_opt, None);
+        assert_eq!(bootstrapper_config.gas_price_ceiling_opt, None);
+        assert_eq!(bootstrapper_config.gas_bump_ladder_opt, None);
+        assert_eq!(bootstrapper_config.idle_spending_freeze_opt, None);
+        assert_eq!(
+            bootstrapper_config.check_adjustment_invariants,
+            cfg!(debug_assertions)
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_gas_runway_alert() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--gas-runway-alert", "12"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(bootstrapper_config.gas_runway_alert_scans, 12);
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_top_up_horizon() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--top-up-horizon", "168"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(bootstrapper_config.top_up_horizon_hours, 168);
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_max_pending_age() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--max-pending-age", "3600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(bootstrapper_config.when_pending_too_long_sec, 3600);
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_price_feed_url() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--price-feed-url",
+            "https://prices.example.com/masq",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.price_feed_url_opt,
+            Some("https://prices.example.com/masq".to_string())
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_payment_batching() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--payment-batching", "1000|5000|60"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.payment_batching_opt,
+            Some(PaymentBatching {
+                min_economic_size_gwei: 1000,
+                batch_target_gwei: 5000,
+                max_delay_sec: 60,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_payment_batching_with_missing_values() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--payment-batching", "1000|5000"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "payment-batching",
+                "'1000|5000' is not a payment batching; supply three nonnegative numbers like \
+                 MIN-ECONOMIC-SIZE-GWEI|BATCH-TARGET-GWEI|MAX-DELAY-SEC"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_country_claim_check() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--country-claim-check", "enforce"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.country_claim_check,
+            CountryClaimCheck::Enforce
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_exit_locations() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--exit-locations", "CZ,SK|PL"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.exit_locations,
+            vec![
+                ExitLocation {
+                    country_codes: vec!["CZ".to_string(), "SK".to_string()],
+                    priority: 1,
+                },
+                ExitLocation {
+                    country_codes: vec!["PL".to_string()],
+                    priority: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_unreachable_creditor_hold() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--unreachable-creditor-hold", "3600|600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.unreachable_creditor_hold_opt,
+            Some(UnreachableCreditorHold {
+                absence_sec: 3600,
+                deadline_margin_sec: 600,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_unreachable_creditor_hold_with_missing_values() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--unreachable-creditor-hold", "3600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "unreachable-creditor-hold",
+                "'3600' is not an unreachable-creditor hold; supply two nonnegative numbers like \
+                 ABSENCE-SEC|DEADLINE-MARGIN-SEC"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_spend_rate_limits() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--spend-rate-limits",
+            "1000|10000|50|300",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.spend_rate_limits_opt,
+            Some(SpendRateLimits {
+                service_fee_per_hour_gwei: 1000,
+                service_fee_per_day_gwei: 10000,
+                transaction_fee_per_hour_gwei: 50,
+                transaction_fee_per_day_gwei: 300,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_spend_rate_limits_with_missing_values() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--spend-rate-limits", "1000|10000|50"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "spend-rate-limits",
+                "'1000|10000|50' is not a set of spend rate limits; supply four nonnegative numbers \
+                 like SERVICE-FEE-PER-HOUR-GWEI|SERVICE-FEE-PER-DAY-GWEI|\
+                 TRANSACTION-FEE-PER-HOUR-GWEI|TRANSACTION-FEE-PER-DAY-GWEI"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_payment_window() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--payment-window", "22|4|2|3600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.payment_window_opt,
+            Some(PaymentWindow {
+                start_hour: 22,
+                end_hour: 4,
+                low_gas_price_gwei: 2,
+                deadline_margin_sec: 3600,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_payment_window_with_an_hour_out_of_the_day() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--payment-window", "22|24|2|3600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "payment-window",
+                "'22|24|2|3600' is not a payment window; supply four nonnegative numbers like \
+                 START-HOUR-UTC|END-HOUR-UTC|LOW-GAS-PRICE-GWEI|DEADLINE-MARGIN-SEC, with two \
+                 different hours below 24"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_gas_price_ceiling() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--gas-price-ceiling", "30|7200"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.gas_price_ceiling_opt,
+            Some(GasPriceCeiling {
+                ceiling_gwei: 30,
+                deadline_margin_sec: 7200,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_gas_price_ceiling_with_missing_values() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--gas-price-ceiling", "30"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "gas-price-ceiling",
+                "'30' is not a gas price ceiling; supply two nonnegative numbers like \
+                 CEILING-GWEI|DEADLINE-MARGIN-SEC"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_gas_bump_ladder() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--gas-bump-ladder", "15|3|600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.gas_bump_ladder_opt,
+            Some(GasBumpLadder {
+                step_percent: 15,
+                max_steps: 3,
+                interval_sec: 600,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_gas_bump_ladder_with_too_small_a_step() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--gas-bump-ladder", "5|3|600"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "gas-bump-ladder",
+                "'5|3|600' is not a gas bump ladder; a step under 10% would be turned down by the \
+                 blockchain service"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_idle_spending_freeze() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--idle-spending-freeze", "3600|7200"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.idle_spending_freeze_opt,
+            Some(IdleSpendingFreeze {
+                idle_sec: 3_600,
+                deadline_margin_sec: 7_200,
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_idle_spending_freeze_without_an_idle_period() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--idle-spending-freeze", "0|7200"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .err();
+
+        assert_eq!(
+            result,
+            Some(ConfiguratorError::new(vec![ParamError::new(
+                "idle-spending-freeze",
+                "'0|7200' is not an idle spending freeze; supply two nonnegative numbers like \
+                 IDLE-SEC|DEADLINE-MARGIN-SEC, the first above 0"
+            )]))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_adjustment_invariants_either_way() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let parse = |value: &str| {
+            let args = ["--ip", "1.2.3.4", "--adjustment-invariants", value];
+            let mut bootstrapper_config = BootstrapperConfig::new();
+            subject
+                .unprivileged_parse_args(
+                    &make_simplified_multi_config(args),
+                    &mut bootstrapper_config,
+                    &mut configure_default_persistent_config(
+                        ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                    ),
+                    &Logger::new("test"),
+                )
+                .unwrap();
+            bootstrapper_config.check_adjustment_invariants
+        };
+
+        let on = parse("ON");
+        let off = parse("off");
+
+        assert_eq!(on, true);
+        assert_eq!(off, false);
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_payment_forwarders() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--payment-forwarders",
+            "0x0123456789012345678901234567890123456789,0xAbCdef0123456789012345678901234567890123",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .payment_forwarders,
+            vec![
+                make_wallet_address("0x0123456789012345678901234567890123456789"),
+                make_wallet_address("0xabcdef0123456789012345678901234567890123"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_to_no_payment_forwarders() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .payment_forwarders,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_a_payment_forwarder_with_a_wrong_checksum() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--payment-forwarders",
+            "0x0123456789012345678901234567890123456789,0xAbCdEf0123456789012345678901234567890123",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject.unprivileged_parse_args(
+            &make_simplified_multi_config(args),
+            &mut bootstrapper_config,
+            &mut configure_default_persistent_config(
+                ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+            ),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "payment-forwarders",
+                "Address 0xAbCdEf0123456789012345678901234567890123 fails its EIP-55 checksum; \
+                 check it for typos, or give it all in lower case"
+            ))
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_receivable_confirmation_blocks() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--receivable-confirmation-blocks", "30"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .receivable_confirmation_blocks,
+            30
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_receivable_confirmation_blocks() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config
+            .blockchain_bridge_config
+            .receivable_confirmation_blocks = 30;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .receivable_confirmation_blocks,
+            DEFAULT_RECEIVABLE_CONFIRMATION_BLOCKS
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_receivable_scan_attempts() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--receivable-scan-attempts", "5"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .receivable_scan_attempts,
+            5
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_receivable_scan_attempts() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config
+            .blockchain_bridge_config
+            .receivable_scan_attempts = 5;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .receivable_scan_attempts,
+            DEFAULT_RECEIVABLE_SCAN_ATTEMPTS
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_receivable_scan_records() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--receivable-scan-records", "25"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .receivable_scan_records,
+            25
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_receivable_scan_records() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config
+            .blockchain_bridge_config
+            .receivable_scan_records = 25;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .receivable_scan_records,
+            DEFAULT_RECEIVABLE_SCAN_RECORDS
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_blockchain_request_timeout() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--blockchain-request-timeout", "12"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .blockchain_request_timeout_secs,
+            12
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_blockchain_request_timeout() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config
+            .blockchain_bridge_config
+            .blockchain_request_timeout_secs = 12;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .blockchain_request_timeout_secs,
+            DEFAULT_BLOCKCHAIN_REQUEST_TIMEOUT_SECS
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_payment_memo_on() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--payment-memo", "on"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.blockchain_bridge_config.payment_memo,
+            true
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_payment_memo_to_off() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config.blockchain_bridge_config.payment_memo = true;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.blockchain_bridge_config.payment_memo,
+            false
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_payment_simulation_on() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--payment-simulation", "on"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .payment_simulation,
+            true
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_payment_simulation_to_off() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config
+            .blockchain_bridge_config
+            .payment_simulation = true;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config
+                .blockchain_bridge_config
+                .payment_simulation,
+            false
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_max_batch_size() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--max-batch-size", "25"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.blockchain_bridge_config.max_batch_size,
+            25
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_max_batch_size() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config.blockchain_bridge_config.max_batch_size = 25;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.blockchain_bridge_config.max_batch_size,
+            DEFAULT_MAX_BATCH_SIZE
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_rpc_daily_quota() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4", "--rpc-daily-quota", "100000"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.blockchain_bridge_config.rpc_daily_quota,
+            100_000
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_defaults_rpc_daily_quota() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = ["--ip", "1.2.3.4"];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config.blockchain_bridge_config.rpc_daily_quota = 100_000;
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bootstrapper_config.blockchain_bridge_config.rpc_daily_quota,
+            DEFAULT_RPC_DAILY_QUOTA
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_dev_faucet() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let parse = |multi_config: MultiConfig| {
+            let mut bootstrapper_config = BootstrapperConfig::new();
+            subject
+                .unprivileged_parse_args(
+                    &multi_config,
+                    &mut bootstrapper_config,
+                    &mut configure_default_persistent_config(
+                        ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                    ),
+                    &Logger::new("test"),
+                )
+                .map(|_| bootstrapper_config.blockchain_bridge_config.dev_faucet)
+        };
+
+        let on = parse(make_simplified_multi_config([
+            "--ip",
+            "1.2.3.4",
+            "--chain",
+            "dev",
+            "--dev-faucet",
+            "ON",
+        ]));
+        let off = parse(make_simplified_multi_config([
+            "--ip",
+            "1.2.3.4",
+            "--chain",
+            "dev",
+            "--dev-faucet",
+            "off",
+        ]));
+        let absent = parse(make_simplified_multi_config([
+            "--ip", "1.2.3.4", "--chain", "dev",
+        ]));
+
+        assert_eq!(on, Ok(true));
+        assert_eq!(off, Ok(false));
+        assert_eq!(absent, Ok(false));
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_dev_faucet_off_the_dev_chain() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--chain",
+            "polygon-amoy",
+            "--dev-faucet",
+            "on",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject.unprivileged_parse_args(
+            &make_simplified_multi_config(args),
+            &mut bootstrapper_config,
+            &mut configure_default_persistent_config(
+                ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+            ),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "dev-faucet",
+                "The faucet is available with --chain dev only, not polygon-amoy"
+            ))
+        )
+    }
+
+    #[test]
+    fn unprivileged_configuration_handles_smart_account_owners() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--smart-account-owners",
+            "0x1111111111111111111111111111111111111111:0x0123456789012345678901234567890123456789,\
+             0x2222222222222222222222222222222222222222:0x0123456789012345678901234567890123456789",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        subject
+            .unprivileged_parse_args(
+                &make_simplified_multi_config(args),
+                &mut bootstrapper_config,
+                &mut configure_default_persistent_config(
+                    ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+                ),
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        let owner = Wallet::from_str("0x0123456789012345678901234567890123456789").unwrap();
+        assert_eq!(
+            bootstrapper_config.smart_account_owners,
+            HashMap::from([
+                (
+                    Wallet::from_str("0x1111111111111111111111111111111111111111").unwrap(),
+                    owner.clone()
+                ),
+                (
+                    Wallet::from_str("0x2222222222222222222222222222222222222222").unwrap(),
+                    owner
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn unprivileged_configuration_rejects_a_smart_account_owner_with_a_wrong_checksum() {
+        running_test();
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+        let args = [
+            "--ip",
+            "1.2.3.4",
+            "--smart-account-owners",
+            "0x1111111111111111111111111111111111111111:0x0123456789ABCDEF0123456789abcdef01234567",
+        ];
+        let mut bootstrapper_config = BootstrapperConfig::new();
+
+        let result = subject.unprivileged_parse_args(
+            &make_simplified_multi_config(args),
+            &mut bootstrapper_config,
+            &mut configure_default_persistent_config(
+                ACCOUNTANT_CONFIG_PARAMS | MAPPING_PROTOCOL | RATE_PACK,
+            ),
+            &Logger::new("test"),
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "smart-account-owners",
+                "Address 0x0123456789ABCDEF0123456789abcdef01234567 fails its EIP-55 checksum; \
+                 check it for typos, or give it all in lower case"
+            ))
+        );
+    }
+
+    fn make_wallet_address(address: &str) -> Address {
+        Wallet::from_str(address).unwrap().address()
     }
 
     fn make_persistent_config(
@@ -2653,6 +4362,7 @@ mod tests {
             .consuming_wallet_private_key_result(Ok(consuming_wallet_private_key_opt))
             .earning_wallet_address_result(Ok(earning_wallet_address_opt.map(to_string)))
             .earning_wallet_result(Ok(earning_wallet_opt))
+            .earning_wallet_rotation_result(Ok(vec![]))
             .gas_price_result(Ok(gas_price))
             .past_neighbors_result(past_neighbors_result)
             .mapping_protocol_result(Ok(Some(AutomapProtocol::Pcp)))
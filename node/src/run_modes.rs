@@ -4,8 +4,9 @@ use crate::apps::{app_config_dumper, app_daemon, app_node};
 use crate::privilege_drop::{PrivilegeDropper, PrivilegeDropperReal};
 use crate::run_modes::Leaving::{ExitCode, Not};
 use crate::run_modes_factories::{
-    DaemonInitializerFactory, DaemonInitializerFactoryReal, DumpConfigRunnerFactory,
-    DumpConfigRunnerFactoryReal, ServerInitializerFactory, ServerInitializerFactoryReal,
+    CheckConfigRunnerFactory, CheckConfigRunnerFactoryReal, DaemonInitializerFactory,
+    DaemonInitializerFactoryReal, DumpConfigRunnerFactory, DumpConfigRunnerFactoryReal,
+    ServerInitializerFactory, ServerInitializerFactoryReal,
 };
 use actix::System;
 use clap::Error;
@@ -18,6 +19,7 @@ use ProgramEntering::{Enter, Leave};
 #[derive(Debug, PartialEq, Eq)]
 enum Mode {
     DumpConfig,
+    CheckConfig,
     Initialization,
     Service,
 }
@@ -49,6 +51,7 @@ impl RunModes {
 
         match match mode {
             Mode::DumpConfig => self.runner.dump_config(args, streams),
+            Mode::CheckConfig => self.runner.check_config(args, streams),
             Mode::Initialization => self.runner.run_daemon(args, streams),
             Mode::Service => self.runner.run_node(args, streams),
         } {
@@ -88,7 +91,7 @@ impl RunModes {
         } {
             Mode::DumpConfig => app_config_dumper(),
             Mode::Initialization => app_daemon(),
-            Mode::Service => app_node(),
+            Mode::CheckConfig | Mode::Service => app_node(),
         }
         .get_matches_from_safe(args)
         {
@@ -195,6 +198,8 @@ impl RunModes {
     fn determine_mode_and_priv_req(&self, args: &[String]) -> (Mode, bool) {
         if args.contains(&"--dump-config".to_string()) {
             (Mode::DumpConfig, false)
+        } else if args.contains(&"--check-config".to_string()) {
+            (Mode::CheckConfig, false)
         } else if args.contains(&"--initialization".to_string()) {
             (Mode::Initialization, true)
         } else {
@@ -223,11 +228,17 @@ trait Runner {
     fn run_node(&self, args: &[String], streams: &mut StdStreams<'_>) -> Result<(), RunnerError>;
     fn dump_config(&self, args: &[String], streams: &mut StdStreams<'_>)
         -> Result<(), RunnerError>;
+    fn check_config(
+        &self,
+        args: &[String],
+        streams: &mut StdStreams<'_>,
+    ) -> Result<(), RunnerError>;
     fn run_daemon(&self, args: &[String], streams: &mut StdStreams<'_>) -> Result<(), RunnerError>;
 }
 
 struct RunnerReal {
     dump_config_runner_factory: Box<dyn DumpConfigRunnerFactory>,
+    check_config_runner_factory: Box<dyn CheckConfigRunnerFactory>,
     server_initializer_factory: Box<dyn ServerInitializerFactory>,
     daemon_initializer_factory: Box<dyn DaemonInitializerFactory>,
 }
@@ -257,6 +268,17 @@ impl Runner for RunnerReal {
             .map_err(RunnerError::Configurator)
     }
 
+    fn check_config(
+        &self,
+        args: &[String],
+        streams: &mut StdStreams<'_>,
+    ) -> Result<(), RunnerError> {
+        self.check_config_runner_factory
+            .make()
+            .go(streams, args)
+            .map_err(RunnerError::Configurator)
+    }
+
     fn run_daemon(&self, args: &[String], streams: &mut StdStreams<'_>) -> Result<(), RunnerError> {
         let mut initializer = self.daemon_initializer_factory.make(args)?;
         initializer.go(streams, args)?;
@@ -268,6 +290,7 @@ impl RunnerReal {
     pub fn new() -> Self {
         Self {
             dump_config_runner_factory: Box::new(DumpConfigRunnerFactoryReal),
+            check_config_runner_factory: Box::new(CheckConfigRunnerFactoryReal),
             server_initializer_factory: Box::new(ServerInitializerFactoryReal),
             daemon_initializer_factory: Box::new(DaemonInitializerFactoryReal::default()),
         }
@@ -284,8 +307,9 @@ impl From<ConfiguratorError> for RunnerError {
 mod tests {
     use super::*;
     use crate::run_modes_factories::mocks::{
-        DaemonInitializerFactoryMock, DaemonInitializerMock, DumpConfigRunnerFactoryMock,
-        DumpConfigRunnerMock, ServerInitializerFactoryMock, ServerInitializerMock,
+        CheckConfigRunnerFactoryMock, CheckConfigRunnerMock, DaemonInitializerFactoryMock,
+        DaemonInitializerMock, DumpConfigRunnerFactoryMock, DumpConfigRunnerMock,
+        ServerInitializerFactoryMock, ServerInitializerMock,
     };
     use crate::server_initializer::test_utils::PrivilegeDropperMock;
     use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
@@ -301,6 +325,8 @@ mod tests {
         run_node_results: RefCell<Vec<Result<(), RunnerError>>>,
         dump_config_params: Arc<Mutex<Vec<Vec<String>>>>,
         dump_config_results: RefCell<Vec<Result<(), RunnerError>>>,
+        check_config_params: Arc<Mutex<Vec<Vec<String>>>>,
+        check_config_results: RefCell<Vec<Result<(), RunnerError>>>,
         run_daemon_params: Arc<Mutex<Vec<Vec<String>>>>,
         run_daemon_results: RefCell<Vec<Result<(), RunnerError>>>,
     }
@@ -324,6 +350,15 @@ mod tests {
             self.dump_config_results.borrow_mut().remove(0)
         }
 
+        fn check_config(
+            &self,
+            args: &[String],
+            _streams: &mut StdStreams<'_>,
+        ) -> Result<(), RunnerError> {
+            self.check_config_params.lock().unwrap().push(args.to_vec());
+            self.check_config_results.borrow_mut().remove(0)
+        }
+
         fn run_daemon(
             &self,
             args: &[String],
@@ -342,6 +377,8 @@ mod tests {
                 run_node_results: RefCell::new(vec![]),
                 dump_config_params: Arc::new(Mutex::new(vec![])),
                 dump_config_results: RefCell::new(vec![]),
+                check_config_params: Arc::new(Mutex::new(vec![])),
+                check_config_results: RefCell::new(vec![]),
                 run_daemon_params: Arc::new(Mutex::new(vec![])),
                 run_daemon_results: RefCell::new(vec![]),
             }
@@ -367,6 +404,16 @@ mod tests {
             self
         }
 
+        pub fn check_config_params(mut self, params: &Arc<Mutex<Vec<Vec<String>>>>) -> Self {
+            self.check_config_params = params.clone();
+            self
+        }
+
+        pub fn check_config_result(self, result: Result<(), RunnerError>) -> Self {
+            self.check_config_results.borrow_mut().push(result);
+            self
+        }
+
         pub fn run_daemon_params(mut self, params: &Arc<Mutex<Vec<Vec<String>>>>) -> Self {
             self.run_daemon_params = params.clone();
             self
@@ -384,6 +431,26 @@ mod tests {
         check_mode(arg, Mode::DumpConfig, false);
     }
 
+    #[test]
+    fn check_config() {
+        let arg = vec!["--check-config".to_string()];
+        check_mode(arg, Mode::CheckConfig, false);
+    }
+
+    #[test]
+    fn check_config_beats_initialization_but_not_dump_config() {
+        check_mode(
+            slice_of_strs_to_vec_of_strings(&["--initialization", "--check-config"]),
+            Mode::CheckConfig,
+            false,
+        );
+        check_mode(
+            slice_of_strs_to_vec_of_strings(&["--check-config", "--dump-config"]),
+            Mode::DumpConfig,
+            false,
+        );
+    }
+
     #[test]
     fn initialization() {
         let arg = vec!["--initialization".to_string()];
@@ -659,6 +726,63 @@ parm2 - msg2\n"
         assert_eq!(*dump_config_params[0], args)
     }
 
+    #[test]
+    fn check_config_hands_in_an_error_from_check_config() {
+        let check_config_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = RunModes::new();
+        let mut runner = RunnerReal::new();
+        runner.check_config_runner_factory = Box::new(
+            CheckConfigRunnerFactoryMock::default().make_result(Box::new(
+                CheckConfigRunnerMock::default()
+                    .check_config_result(Err(ConfiguratorError::required(
+                        "gas-price",
+                        "deep-reason",
+                    )))
+                    .check_config_params(&check_config_params_arc),
+            )),
+        );
+        subject.runner = Box::new(runner);
+        let mut holder = FakeStreamHolder::new();
+        let args = slice_of_strs_to_vec_of_strings(&["program", "--check-config", "--arg"]);
+
+        let result = subject.runner.check_config(&args, &mut holder.streams());
+
+        assert_eq!(
+            result,
+            Err(RunnerError::Configurator(ConfiguratorError::required(
+                "gas-price",
+                "deep-reason"
+            )))
+        );
+        assert_eq!(&holder.stdout.get_string(), "");
+        assert_eq!(&holder.stderr.get_string(), "");
+        let check_config_params = check_config_params_arc.lock().unwrap();
+        assert_eq!(*check_config_params, vec![args])
+    }
+
+    #[test]
+    fn check_config_mode_reports_errors_and_exits_with_one() {
+        let mut subject = RunModes::new();
+        let privilege_dropper = PrivilegeDropperMock::new().expect_privilege_result(true);
+        subject.privilege_dropper = Box::new(privilege_dropper);
+        subject.runner = Box::new(RunnerMock::new().check_config_result(Err(
+            RunnerError::Configurator(ConfiguratorError::required("min-hops", "Too many")),
+        )));
+        let mut holder = FakeStreamHolder::new();
+
+        let exit_code = subject.go(
+            &slice_of_strs_to_vec_of_strings(&["program", "--check-config"]),
+            &mut holder.streams(),
+        );
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(holder.stdout.get_string(), "");
+        assert_eq!(
+            holder.stderr.get_string(),
+            "Configuration error\nmin-hops - Too many\n"
+        );
+    }
+
     #[test]
     fn daemon_and_node_modes_complain_without_privilege() {
         let mut subject = RunModes::new();
@@ -8,6 +8,7 @@ use crate::database::config_dumper::DumpConfigRunnerReal;
 use crate::node_configurator::node_configurator_initialization::{
     InitializationConfig, NodeConfiguratorInitializationReal,
 };
+use crate::node_configurator::node_configurator_standard::CheckConfigRunnerReal;
 use crate::node_configurator::{DirsWrapper, DirsWrapperReal, NodeConfigurator};
 use crate::server_initializer::{
     LoggerInitializerWrapper, LoggerInitializerWrapperReal, ServerInitializerReal,
@@ -20,6 +21,7 @@ use std::cell::RefCell;
 pub type RunModeResult = Result<(), ConfiguratorError>;
 
 pub struct DumpConfigRunnerFactoryReal;
+pub struct CheckConfigRunnerFactoryReal;
 pub struct ServerInitializerFactoryReal;
 pub struct DaemonInitializerFactoryReal {
     configurator: RefCell<Option<Box<dyn NodeConfigurator<InitializationConfig>>>>,
@@ -57,6 +59,9 @@ impl DaemonInitializerFactoryReal {
 pub trait DumpConfigRunnerFactory {
     fn make(&self) -> Box<dyn DumpConfigRunner>;
 }
+pub trait CheckConfigRunnerFactory {
+    fn make(&self) -> Box<dyn CheckConfigRunner>;
+}
 pub trait ServerInitializerFactory {
     fn make(&self) -> Box<dyn ServerInitializer<Item = (), Error = ()>>;
 }
@@ -69,6 +74,11 @@ pub trait DumpConfigRunner {
     as_any_ref_in_trait!();
 }
 
+pub trait CheckConfigRunner {
+    fn go(&self, streams: &mut StdStreams, args: &[String]) -> RunModeResult;
+    as_any_ref_in_trait!();
+}
+
 pub trait ServerInitializer: futures::Future {
     fn go(&mut self, streams: &mut StdStreams, args: &[String]) -> RunModeResult;
     as_any_ref_in_trait!();
@@ -87,6 +97,14 @@ impl DumpConfigRunnerFactory for DumpConfigRunnerFactoryReal {
     }
 }
 
+impl CheckConfigRunnerFactory for CheckConfigRunnerFactoryReal {
+    fn make(&self) -> Box<dyn CheckConfigRunner> {
+        Box::new(CheckConfigRunnerReal {
+            dirs_wrapper: Box::new(DirsWrapperReal::default()),
+        })
+    }
+}
+
 impl ServerInitializerFactory for ServerInitializerFactoryReal {
     fn make(&self) -> Box<dyn ServerInitializer<Item = (), Error = ()>> {
         Box::new(ServerInitializerReal::default())
@@ -132,13 +150,14 @@ pub struct DIClusteredParams {
 mod tests {
     use crate::database::config_dumper::DumpConfigRunnerReal;
     use crate::node_configurator::node_configurator_initialization::NodeConfiguratorInitializationReal;
+    use crate::node_configurator::node_configurator_standard::CheckConfigRunnerReal;
     use crate::run_modes_factories::mocks::{
         test_clustered_params, NodeConfiguratorInitializationMock,
     };
     use crate::run_modes_factories::{
-        DIClusteredParams, DaemonInitializerFactory, DaemonInitializerFactoryReal,
-        DumpConfigRunnerFactory, DumpConfigRunnerFactoryReal, ServerInitializerFactory,
-        ServerInitializerFactoryReal,
+        CheckConfigRunnerFactory, CheckConfigRunnerFactoryReal, DIClusteredParams,
+        DaemonInitializerFactory, DaemonInitializerFactoryReal, DumpConfigRunnerFactory,
+        DumpConfigRunnerFactoryReal, ServerInitializerFactory, ServerInitializerFactoryReal,
     };
     use crate::server_initializer::ServerInitializerReal;
     use masq_lib::shared_schema::ConfiguratorError;
@@ -157,6 +176,17 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn make_for_check_config_runner_factory_produces_a_proper_object() {
+        let subject = CheckConfigRunnerFactoryReal;
+        let result = subject.make();
+
+        let _ = result
+            .as_any()
+            .downcast_ref::<CheckConfigRunnerReal>()
+            .unwrap();
+    }
+
     #[test]
     fn make_for_server_initializer_factory_produces_a_proper_object() {
         let subject = ServerInitializerFactoryReal;
@@ -239,8 +269,9 @@ pub mod mocks {
     use crate::node_configurator::node_configurator_initialization::InitializationConfig;
     use crate::node_configurator::NodeConfigurator;
     use crate::run_modes_factories::{
-        DIClusteredParams, DaemonInitializer, DaemonInitializerFactory, DumpConfigRunner,
-        DumpConfigRunnerFactory, RunModeResult, ServerInitializer, ServerInitializerFactory,
+        CheckConfigRunner, CheckConfigRunnerFactory, DIClusteredParams, DaemonInitializer,
+        DaemonInitializerFactory, DumpConfigRunner, DumpConfigRunnerFactory, RunModeResult,
+        ServerInitializer, ServerInitializerFactory,
     };
     use crate::server_initializer::test_utils::LoggerInitializerWrapperMock;
     use crate::server_initializer::tests::{
@@ -284,6 +315,24 @@ pub mod mocks {
         }
     }
 
+    #[derive(Default)]
+    pub struct CheckConfigRunnerFactoryMock {
+        make_results: RefCell<Vec<Box<CheckConfigRunnerMock>>>,
+    }
+
+    impl CheckConfigRunnerFactoryMock {
+        pub fn make_result(self, result: Box<CheckConfigRunnerMock>) -> Self {
+            self.make_results.borrow_mut().push(result);
+            self
+        }
+    }
+
+    impl CheckConfigRunnerFactory for CheckConfigRunnerFactoryMock {
+        fn make(&self) -> Box<dyn CheckConfigRunner> {
+            self.make_results.borrow_mut().remove(0)
+        }
+    }
+
     #[derive(Default)]
     pub struct ServerInitializerFactoryMock {
         make_results: RefCell<Vec<Box<ServerInitializerMock>>>,
@@ -380,6 +429,31 @@ pub mod mocks {
         }
     }
 
+    #[derive(Default)]
+    pub struct CheckConfigRunnerMock {
+        check_config_params: Arc<Mutex<Vec<Vec<String>>>>,
+        check_config_results: RefCell<Vec<Result<(), ConfiguratorError>>>,
+    }
+
+    impl CheckConfigRunner for CheckConfigRunnerMock {
+        fn go(&self, _streams: &mut StdStreams, args: &[String]) -> Result<(), ConfiguratorError> {
+            self.check_config_params.lock().unwrap().push(args.to_vec());
+            self.check_config_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl CheckConfigRunnerMock {
+        pub fn check_config_result(self, result: Result<(), ConfiguratorError>) -> Self {
+            self.check_config_results.borrow_mut().push(result);
+            self
+        }
+
+        pub fn check_config_params(mut self, params_arc: &Arc<Mutex<Vec<Vec<String>>>>) -> Self {
+            self.check_config_params = params_arc.clone();
+            self
+        }
+    }
+
     #[derive(Default)]
     pub struct ServerInitializerMock {
         go_result: RefCell<Vec<Result<(), ConfiguratorError>>>,
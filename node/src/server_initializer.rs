@@ -10,7 +10,8 @@ use crate::sub_lib::socket_server::ConfiguredByPrivilege;
 use backtrace::Backtrace;
 use clap::value_t;
 use flexi_logger::{
-    Cleanup, Criterion, DeferredNow, Duplicate, LevelFilter, LogSpecBuilder, Logger, Naming, Record,
+    Cleanup, Criterion, DeferredNow, Duplicate, LevelFilter, LogSpecBuilder, LogSpecification,
+    Logger, Naming, ReconfigurationHandle, Record,
 };
 use futures::try_ready;
 use lazy_static::lazy_static;
@@ -124,6 +125,7 @@ impl ResultsCombiner for RunModeResult {
 
 lazy_static! {
     pub static ref LOGFILE_NAME: Mutex<PathBuf> = Mutex::new(PathBuf::from("uninitialized"));
+    static ref LOG_RECONFIGURATION_HANDLE: Mutex<Option<ReconfigurationHandle>> = Mutex::new(None);
 }
 
 pub trait LoggerInitializerWrapper {
@@ -146,28 +148,25 @@ impl LoggerInitializerWrapper for LoggerInitializerWrapperReal {
         log_level: LevelFilter,
         discriminant_opt: Option<&str>,
     ) {
-        let mut logger = Logger::with(
-            LogSpecBuilder::new()
-                .default(log_level)
-                .module("tokio", LevelFilter::Off)
-                .module("mio", LevelFilter::Off)
-                .build(),
-        )
-        .log_to_file()
-        .directory(file_path.clone())
-        .print_message()
-        .duplicate_to_stderr(Duplicate::Info)
-        .suppress_timestamp()
-        .format(format_function)
-        .rotate(
-            Criterion::Size(100_000_000),
-            Naming::Numbers,
-            Cleanup::KeepZipFiles(50),
-        );
+        let mut logger = Logger::with(log_specification(log_level))
+            .log_to_file()
+            .directory(file_path.clone())
+            .print_message()
+            .duplicate_to_stderr(Duplicate::Info)
+            .suppress_timestamp()
+            .format(format_function)
+            .rotate(
+                Criterion::Size(100_000_000),
+                Naming::Numbers,
+                Cleanup::KeepZipFiles(50),
+            );
         if let Some(discriminant) = discriminant_opt {
             logger = logger.discriminant(discriminant);
         }
-        logger.start().expect("Logging subsystem failed to start");
+        let reconfiguration_handle = logger.start().expect("Logging subsystem failed to start");
+        *(LOG_RECONFIGURATION_HANDLE
+            .lock()
+            .expect("Log reconfiguration handle is poisoned")) = Some(reconfiguration_handle);
         let privilege_dropper = PrivilegeDropperReal::new();
         let logfile_name = file_path.join(format!(
             "MASQNode_{}rCURRENT.log",
@@ -192,6 +191,36 @@ impl LoggerInitializerWrapper for LoggerInitializerWrapperReal {
     }
 }
 
+fn log_specification(log_level: LevelFilter) -> LogSpecification {
+    LogSpecBuilder::new()
+        .default(log_level)
+        .module("tokio", LevelFilter::Off)
+        .module("mio", LevelFilter::Off)
+        .build()
+}
+
+pub trait LogLevelSetter {
+    fn set_log_level(&self, log_level: LevelFilter) -> Result<(), String>;
+}
+
+pub struct LogLevelSetterReal;
+
+impl LogLevelSetter for LogLevelSetterReal {
+    fn set_log_level(&self, log_level: LevelFilter) -> Result<(), String> {
+        match LOG_RECONFIGURATION_HANDLE
+            .lock()
+            .expect("Log reconfiguration handle is poisoned")
+            .as_mut()
+        {
+            Some(handle) => {
+                handle.set_new_spec(log_specification(log_level));
+                Ok(())
+            }
+            None => Err("Logging subsystem has not been started".to_string()),
+        }
+    }
+}
+
 impl LoggerInitializerWrapperReal {
     pub fn get_logfile_name() -> PathBuf {
         let path: &Path = &(*(Self::logfile_name_guard()).clone());
@@ -275,7 +304,7 @@ fn format_function(
 pub mod test_utils {
     use crate::bootstrapper::RealUser;
     use crate::privilege_drop::PrivilegeDropper;
-    use crate::server_initializer::LoggerInitializerWrapper;
+    use crate::server_initializer::{LogLevelSetter, LoggerInitializerWrapper};
     use log::LevelFilter;
     use std::cell::RefCell;
     use std::path::{Path, PathBuf};
@@ -343,6 +372,31 @@ pub mod test_utils {
         }
     }
 
+    #[derive(Default)]
+    pub struct LogLevelSetterMock {
+        set_log_level_params: Arc<Mutex<Vec<LevelFilter>>>,
+        set_log_level_results: RefCell<Vec<Result<(), String>>>,
+    }
+
+    impl LogLevelSetter for LogLevelSetterMock {
+        fn set_log_level(&self, log_level: LevelFilter) -> Result<(), String> {
+            self.set_log_level_params.lock().unwrap().push(log_level);
+            self.set_log_level_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl LogLevelSetterMock {
+        pub fn set_log_level_params(mut self, params: &Arc<Mutex<Vec<LevelFilter>>>) -> Self {
+            self.set_log_level_params = params.clone();
+            self
+        }
+
+        pub fn set_log_level_result(self, result: Result<(), String>) -> Self {
+            self.set_log_level_results.borrow_mut().push(result);
+            self
+        }
+    }
+
     pub struct LoggerInitializerWrapperMock {
         init_parameters: Arc<Mutex<Vec<(PathBuf, RealUser, LevelFilter, Option<String>)>>>,
     }
@@ -1,5 +1,8 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::accountant::db_access_objects::accounts_transfer_dao::AccountsTransferDaoFactory;
 use crate::accountant::db_access_objects::banned_dao::BannedDaoFactory;
+use crate::accountant::db_access_objects::consistency_dao::ConsistencyDaoFactory;
+use crate::accountant::db_access_objects::outbound_payment_dao::OutboundPaymentDaoFactory;
 use crate::accountant::db_access_objects::payable_dao::PayableDaoFactory;
 use crate::accountant::db_access_objects::pending_payable_dao::PendingPayableDaoFactory;
 use crate::accountant::db_access_objects::receivable_dao::ReceivableDaoFactory;
@@ -9,19 +12,27 @@ use crate::accountant::{
     SentPayables,
 };
 use crate::actor_system_factory::SubsFactory;
-use crate::blockchain::blockchain_bridge::PendingPayableFingerprintSeeds;
+use crate::blockchain::blockchain_bridge::{
+    PendingPayableFingerprintSeeds, PendingPayableReplacement,
+};
+use crate::blockchain::payer::Payer;
 use crate::db_config::config_dao::ConfigDaoFactory;
-use crate::sub_lib::neighborhood::ConfigChangeMsg;
+use crate::sub_lib::cryptde::{CryptDE, CryptData, CryptdecError, PlainData, PublicKey};
+use crate::sub_lib::neighborhood::{ConfigChangeMsg, ShutdownBarrierMessage};
 use crate::sub_lib::peer_actors::{BindMessage, StartMessage};
-use crate::sub_lib::wallet::Wallet;
+use crate::sub_lib::wallet::{Wallet, WalletError};
 use actix::Recipient;
 use actix::{Addr, Message};
+use ethsign_crypto::Keccak256;
 use lazy_static::lazy_static;
+use masq_lib::logger::Logger;
 use masq_lib::ui_gateway::NodeFromUiMessage;
-use std::fmt::{Debug, Formatter};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::{Duration, SystemTime};
+use web3::types::{H256, U256};
 
 lazy_static! {
     pub static ref DEFAULT_EARNING_WALLET: Wallet = Wallet::from_str("0x27d9A2AC83b493f88ce9B4532EDcf74e95B9788d").expect("Internal error");
@@ -45,7 +56,7 @@ lazy_static! {
 }
 
 //please, alphabetical order
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PaymentThresholds {
     pub debt_threshold_gwei: u64,
     pub maturity_threshold_sec: u64,
@@ -75,6 +86,9 @@ pub struct DaoFactories {
     pub receivable_dao_factory: Box<dyn ReceivableDaoFactory>,
     pub banned_dao_factory: Box<dyn BannedDaoFactory>,
     pub config_dao_factory: Box<dyn ConfigDaoFactory>,
+    pub accounts_transfer_dao_factory: Box<dyn AccountsTransferDaoFactory>,
+    pub consistency_dao_factory: Box<dyn ConsistencyDaoFactory>,
+    pub outbound_payment_dao_factory: Box<dyn OutboundPaymentDaoFactory>,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -90,6 +104,383 @@ impl Default for ScanIntervals {
     }
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ConsumingWalletSelection {
+    RoundRobin,
+    HighestBalance,
+}
+
+impl Default for ConsumingWalletSelection {
+    fn default() -> Self {
+        ConsumingWalletSelection::RoundRobin
+    }
+}
+
+impl FromStr for ConsumingWalletSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "round-robin" => Ok(ConsumingWalletSelection::RoundRobin),
+            "highest-balance" => Ok(ConsumingWalletSelection::HighestBalance),
+            other => Err(format!(
+                "'{}' is not a consuming wallet selection; use round-robin or highest-balance",
+                other
+            )),
+        }
+    }
+}
+
+impl Display for ConsumingWalletSelection {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ConsumingWalletSelection::RoundRobin => write!(f, "round-robin"),
+            ConsumingWalletSelection::HighestBalance => write!(f, "highest-balance"),
+        }
+    }
+}
+
+// Debts due for payment but smaller than the minimum economic size wait until they add up to the
+// batch target, though none of them longer than the max delay
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct PaymentBatching {
+    pub min_economic_size_gwei: u64,
+    pub batch_target_gwei: u64,
+    pub max_delay_sec: u64,
+}
+
+impl FromStr for PaymentBatching {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        match values.as_deref() {
+            Ok([min_economic_size_gwei, batch_target_gwei, max_delay_sec]) => Ok(PaymentBatching {
+                min_economic_size_gwei: *min_economic_size_gwei,
+                batch_target_gwei: *batch_target_gwei,
+                max_delay_sec: *max_delay_sec,
+            }),
+            _ => Err(format!(
+                "'{}' is not a payment batching; supply three nonnegative numbers like \
+                 MIN-ECONOMIC-SIZE-GWEI|BATCH-TARGET-GWEI|MAX-DELAY-SEC",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for PaymentBatching {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}",
+            self.min_economic_size_gwei, self.batch_target_gwei, self.max_delay_sec
+        )
+    }
+}
+
+// Debts to creditors whose Nodes have been gone from the Neighborhood for the absence period are
+// held unpaid, so that scarce funds go to creditors still relaying for us; each is released as
+// soon as its Node returns, or once its payment deadline is no more than the deadline margin away
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct UnreachableCreditorHold {
+    pub absence_sec: u64,
+    pub deadline_margin_sec: u64,
+}
+
+impl FromStr for UnreachableCreditorHold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        match values.as_deref() {
+            Ok([absence_sec, deadline_margin_sec]) => Ok(UnreachableCreditorHold {
+                absence_sec: *absence_sec,
+                deadline_margin_sec: *deadline_margin_sec,
+            }),
+            _ => Err(format!(
+                "'{}' is not an unreachable-creditor hold; supply two nonnegative numbers like \
+                 ABSENCE-SEC|DEADLINE-MARGIN-SEC",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for UnreachableCreditorHold {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}|{}", self.absence_sec, self.deadline_margin_sec)
+    }
+}
+
+// Caps on what the consuming wallet may spend within any hour and any day, on the service fee
+// (MASQ paid to creditors) and on the transaction fee (gas); payments that would exceed them are
+// left for later payable scans
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SpendRateLimits {
+    pub service_fee_per_hour_gwei: u64,
+    pub service_fee_per_day_gwei: u64,
+    pub transaction_fee_per_hour_gwei: u64,
+    pub transaction_fee_per_day_gwei: u64,
+}
+
+impl FromStr for SpendRateLimits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        match values.as_deref() {
+            Ok(
+                [service_fee_per_hour_gwei, service_fee_per_day_gwei, transaction_fee_per_hour_gwei, transaction_fee_per_day_gwei],
+            ) => Ok(SpendRateLimits {
+                service_fee_per_hour_gwei: *service_fee_per_hour_gwei,
+                service_fee_per_day_gwei: *service_fee_per_day_gwei,
+                transaction_fee_per_hour_gwei: *transaction_fee_per_hour_gwei,
+                transaction_fee_per_day_gwei: *transaction_fee_per_day_gwei,
+            }),
+            _ => Err(format!(
+                "'{}' is not a set of spend rate limits; supply four nonnegative numbers like \
+                 SERVICE-FEE-PER-HOUR-GWEI|SERVICE-FEE-PER-DAY-GWEI|TRANSACTION-FEE-PER-HOUR-GWEI|\
+                 TRANSACTION-FEE-PER-DAY-GWEI",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for SpendRateLimits {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}",
+            self.service_fee_per_hour_gwei,
+            self.service_fee_per_day_gwei,
+            self.transaction_fee_per_hour_gwei,
+            self.transaction_fee_per_day_gwei
+        )
+    }
+}
+
+// Hours of the day, in UTC, within which payable batches may be submitted. A window whose start
+// is later than its end spans midnight. Outside it, payments still go out if the gas price has
+// sunk to the low-gas price (zero never counts as low) or a debt is within the deadline margin of
+// getting its creditor to ban us.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct PaymentWindow {
+    pub start_hour: u8,
+    pub end_hour: u8,
+    pub low_gas_price_gwei: u64,
+    pub deadline_margin_sec: u64,
+}
+
+impl PaymentWindow {
+    pub fn contains_hour(&self, hour: u8) -> bool {
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+impl FromStr for PaymentWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        match values.as_deref() {
+            Ok([start_hour, end_hour, low_gas_price_gwei, deadline_margin_sec])
+                if *start_hour < 24 && *end_hour < 24 && start_hour != end_hour =>
+            {
+                Ok(PaymentWindow {
+                    start_hour: *start_hour as u8,
+                    end_hour: *end_hour as u8,
+                    low_gas_price_gwei: *low_gas_price_gwei,
+                    deadline_margin_sec: *deadline_margin_sec,
+                })
+            }
+            _ => Err(format!(
+                "'{}' is not a payment window; supply four nonnegative numbers like \
+                 START-HOUR-UTC|END-HOUR-UTC|LOW-GAS-PRICE-GWEI|DEADLINE-MARGIN-SEC, with two \
+                 different hours below 24",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for PaymentWindow {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}",
+            self.start_hour, self.end_hour, self.low_gas_price_gwei, self.deadline_margin_sec
+        )
+    }
+}
+
+// A gas price above which only the debts within the deadline margin of getting their creditors
+// to ban us are paid, while the rest wait for cheaper gas
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct GasPriceCeiling {
+    pub ceiling_gwei: u64,
+    pub deadline_margin_sec: u64,
+}
+
+impl FromStr for GasPriceCeiling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        match values.as_deref() {
+            Ok([ceiling_gwei, deadline_margin_sec]) => Ok(GasPriceCeiling {
+                ceiling_gwei: *ceiling_gwei,
+                deadline_margin_sec: *deadline_margin_sec,
+            }),
+            _ => Err(format!(
+                "'{}' is not a gas price ceiling; supply two nonnegative numbers like \
+                 CEILING-GWEI|DEADLINE-MARGIN-SEC",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for GasPriceCeiling {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}|{}", self.ceiling_gwei, self.deadline_margin_sec)
+    }
+}
+
+// Nodes turn down a replacement transaction that doesn't outbid the original by at least this much
+pub const MIN_GAS_BUMP_PERCENT: u8 = 10;
+
+// Raises the gas price of the transactions of a batch stuck in the mempool a step at a time, once
+// per interval the batch has been pending for, until they're mined or the steps run out
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct GasBumpLadder {
+    pub step_percent: u8,
+    pub max_steps: u8,
+    pub interval_sec: u64,
+}
+
+impl FromStr for GasBumpLadder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        let ladder = match values.as_deref() {
+            Ok([step_percent, max_steps, interval_sec]) => {
+                match (u8::try_from(*step_percent), u8::try_from(*max_steps)) {
+                    (Ok(step_percent), Ok(max_steps)) if *interval_sec > 0 => Some(GasBumpLadder {
+                        step_percent,
+                        max_steps,
+                        interval_sec: *interval_sec,
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match ladder {
+            Some(ladder) if ladder.step_percent < MIN_GAS_BUMP_PERCENT => Err(format!(
+                "'{}' is not a gas bump ladder; a step under {}% would be turned down by the \
+                 blockchain service",
+                s, MIN_GAS_BUMP_PERCENT
+            )),
+            Some(ladder) => Ok(ladder),
+            None => Err(format!(
+                "'{}' is not a gas bump ladder; supply three numbers like \
+                 STEP-PERCENT|MAX-STEPS|INTERVAL-SEC, the first two up to 255 and the last \
+                 above 0",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for GasBumpLadder {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}",
+            self.step_percent, self.max_steps, self.interval_sec
+        )
+    }
+}
+
+// Pauses scheduled payable scans while the Node has neither routed nor consumed any traffic for the
+// idle period, as no new debts are arising then; a debt this close to its ban deadline lifts it
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct IdleSpendingFreeze {
+    pub idle_sec: u64,
+    pub deadline_margin_sec: u64,
+}
+
+impl FromStr for IdleSpendingFreeze {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| value.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        match values.as_deref() {
+            Ok([idle_sec, deadline_margin_sec]) if *idle_sec > 0 => Ok(IdleSpendingFreeze {
+                idle_sec: *idle_sec,
+                deadline_margin_sec: *deadline_margin_sec,
+            }),
+            _ => Err(format!(
+                "'{}' is not an idle spending freeze; supply two nonnegative numbers like \
+                 IDLE-SEC|DEADLINE-MARGIN-SEC, the first above 0",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for IdleSpendingFreeze {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}|{}", self.idle_sec, self.deadline_margin_sec)
+    }
+}
+
+// Correlates the log lines of one payable scan through qualification, adjustment, signing and
+// submission, even though they come from different actors. Zero stands for no scan at all.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct ScanId(pub u64);
+
+impl ScanId {
+    pub fn next(self) -> Self {
+        ScanId(self.0 + 1)
+    }
+
+    pub fn span(self, logger: &Logger) -> Logger {
+        match self.0 {
+            0 => logger.clone(),
+            id => logger.in_span(&format!("scan_id={}", id)),
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct AccountantSubs {
     pub bind: Recipient<BindMessage>,
@@ -101,9 +492,17 @@ pub struct AccountantSubs {
     pub report_payable_payments_setup: Recipient<BlockchainAgentWithContextMessage>,
     pub report_inbound_payments: Recipient<ReceivedPayments>,
     pub init_pending_payable_fingerprints: Recipient<PendingPayableFingerprintSeeds>,
+    pub report_pending_payable_replacement: Recipient<PendingPayableReplacement>,
     pub report_transaction_receipts: Recipient<ReportTransactionReceipts>,
     pub report_sent_payments: Recipient<SentPayables>,
     pub scan_errors: Recipient<ScanError>,
+    pub report_creditor_thresholds: Recipient<ReportCreditorThresholdsMessage>,
+    pub report_creditor_reachability: Recipient<ReportCreditorReachabilityMessage>,
+    pub report_exit_failover: Recipient<ReportExitFailoverMessage>,
+    pub report_payment_notice: Recipient<ReportPaymentNoticeMessage>,
+    pub report_usage_invoice: Recipient<ReportUsageInvoiceMessage>,
+    pub report_confirmed_nonce: Recipient<ReportConfirmedNonce>,
+    pub shutdown_barrier: Recipient<ShutdownBarrierMessage>,
     pub ui_message_sub: Recipient<NodeFromUiMessage>,
 }
 
@@ -148,6 +547,179 @@ pub struct ReportServicesConsumedMessage {
     pub routing: Vec<RoutingServiceConsumed>,
 }
 
+// Payment thresholds that creditors advertised about themselves in Gossip, keyed by
+// their earning wallets
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct ReportCreditorThresholdsMessage {
+    pub creditor_thresholds: Vec<(Wallet, PaymentThresholds)>,
+}
+
+// Earning wallets of creditors whose Nodes have come into or dropped out of the Neighborhood
+// since the last report
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct ReportCreditorReachabilityMessage {
+    pub timestamp: SystemTime,
+    pub reachable: Vec<Wallet>,
+    pub unreachable: Vec<Wallet>,
+}
+
+// Earning wallets of the Nodes in the exit country the Neighborhood has newly selected, whose
+// debts are to be favored when the next payments are adjusted
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct ReportExitFailoverMessage {
+    pub timestamp: SystemTime,
+    pub exit_country: String,
+    pub creditors: Vec<Wallet>,
+}
+
+// Sent by a debtor's Node to its creditor's Node once a payment has been confirmed on the
+// blockchain, so that the creditor need not wait for its next receivable scan to learn of it. The
+// payer proves it holds the paying wallet by signing the rest of the notice with it.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct PaymentNotice_0v1 {
+    pub creditor_wallet: Wallet,
+    pub tx_hash: H256,
+    pub amount_wei: U256,
+    pub block_number: u64,
+    pub payer: Payer,
+}
+
+impl PaymentNotice_0v1 {
+    pub fn new(
+        paying_wallet: &Wallet,
+        creditor_wallet: &Wallet,
+        tx_hash: H256,
+        amount_wei: u128,
+        block_number: u64,
+    ) -> Result<Self, WalletError> {
+        let creditor_wallet = creditor_wallet.as_address_wallet();
+        let amount_wei = U256::from(amount_wei);
+        let digest = Self::digest(&creditor_wallet, &tx_hash, &amount_wei, block_number);
+        let proof = paying_wallet.sign(&digest)?;
+        Ok(Self {
+            creditor_wallet,
+            tx_hash,
+            amount_wei,
+            block_number,
+            payer: Payer::new(paying_wallet, &proof),
+        })
+    }
+
+    pub fn is_signed_by_payer(&self) -> bool {
+        self.payer.owns_secret_key(&Self::digest(
+            &self.creditor_wallet,
+            &self.tx_hash,
+            &self.amount_wei,
+            self.block_number,
+        ))
+    }
+
+    fn digest(
+        creditor_wallet: &Wallet,
+        tx_hash: &H256,
+        amount_wei: &U256,
+        block_number: u64,
+    ) -> [u8; 32] {
+        let mut amount_bytes = [0u8; 32];
+        amount_wei.to_big_endian(&mut amount_bytes);
+        [
+            creditor_wallet.address().as_bytes(),
+            tx_hash.as_bytes(),
+            &amount_bytes,
+            &block_number.to_be_bytes(),
+        ]
+        .concat()
+        .keccak256()
+    }
+}
+
+// A payment notice that arrived from a debtor's Node, yet to be checked by the Accountant
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct ReportPaymentNoticeMessage {
+    pub notice: PaymentNotice_0v1,
+}
+
+// Sent by a creditor's Node to its debtor's Node along with payment it has received, stating what
+// it believes it has done for the debtor since its last invoice. The creditor signs it with its
+// Node's key, which the debtor's Neighborhood knows from Gossip.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct UsageInvoice_0v1 {
+    pub creditor_wallet: Wallet,
+    pub debtor_wallet: Wallet,
+    pub period_start_sec: u64,
+    pub period_end_sec: u64,
+    pub routing_services: u64,
+    pub exit_services: u64,
+    pub amount_wei: U256,
+    pub signature: CryptData,
+}
+
+impl UsageInvoice_0v1 {
+    // Unsigned until it passes through the creditor's Neighborhood
+    pub fn new(
+        creditor_wallet: &Wallet,
+        debtor_wallet: &Wallet,
+        period_start_sec: u64,
+        period_end_sec: u64,
+        routing_services: u64,
+        exit_services: u64,
+        amount_wei: u128,
+    ) -> Self {
+        Self {
+            creditor_wallet: creditor_wallet.as_address_wallet(),
+            debtor_wallet: debtor_wallet.as_address_wallet(),
+            period_start_sec,
+            period_end_sec,
+            routing_services,
+            exit_services,
+            amount_wei: U256::from(amount_wei),
+            signature: CryptData::new(&[]),
+        }
+    }
+
+    pub fn sign(&mut self, cryptde: &dyn CryptDE) -> Result<(), CryptdecError> {
+        self.signature = cryptde.sign(&self.digest())?;
+        Ok(())
+    }
+
+    pub fn is_signed_by(&self, cryptde: &dyn CryptDE, creditor_key: &PublicKey) -> bool {
+        cryptde.verify_signature(&self.digest(), &self.signature, creditor_key)
+    }
+
+    fn digest(&self) -> PlainData {
+        let mut amount_bytes = [0u8; 32];
+        self.amount_wei.to_big_endian(&mut amount_bytes);
+        PlainData::from(
+            [
+                self.creditor_wallet.address().as_bytes(),
+                self.debtor_wallet.address().as_bytes(),
+                &self.period_start_sec.to_be_bytes(),
+                &self.period_end_sec.to_be_bytes(),
+                &self.routing_services.to_be_bytes(),
+                &self.exit_services.to_be_bytes(),
+                &amount_bytes,
+            ]
+            .concat(),
+        )
+    }
+}
+
+// An invoice that arrived from a creditor's Node, for the Accountant to check against its books
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct ReportUsageInvoiceMessage {
+    pub invoice: UsageInvoice_0v1,
+}
+
+// How many transactions of the consuming wallet the chain has confirmed, which at startup is
+// compared against the fingerprints left in the database
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct ReportConfirmedNonce {
+    pub wallet: Wallet,
+    pub confirmed_nonce: u64,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct RoutingServiceConsumed {
     pub earning_wallet: Wallet,
@@ -167,6 +739,8 @@ pub struct ExitServiceConsumed {
 pub struct FinancialStatistics {
     pub total_paid_payable_wei: u128,
     pub total_paid_receivable_wei: u128,
+    // Payments seen in blocks still too young to be trusted; they aren't counted as paid until confirmed
+    pub total_pending_receivable_wei: u128,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -195,18 +769,29 @@ impl MessageIdGenerator for MessageIdGeneratorReal {
 mod tests {
     use crate::accountant::test_utils::AccountantBuilder;
     use crate::accountant::{checked_conversion, Accountant};
+    use crate::blockchain::test_utils::make_tx_hash;
     use crate::sub_lib::accountant::{
-        AccountantSubsFactoryReal, MessageIdGenerator, MessageIdGeneratorReal, PaymentThresholds,
-        ScanIntervals, SubsFactory, DEFAULT_EARNING_WALLET, DEFAULT_PAYMENT_THRESHOLDS,
-        DEFAULT_SCAN_INTERVALS, MSG_ID_INCREMENTER, TEMPORARY_CONSUMING_WALLET,
+        AccountantSubsFactoryReal, ConsumingWalletSelection, GasBumpLadder, GasPriceCeiling,
+        IdleSpendingFreeze, MessageIdGenerator, MessageIdGeneratorReal, PaymentBatching,
+        PaymentNotice_0v1, PaymentThresholds, PaymentWindow, ScanId, ScanIntervals,
+        SpendRateLimits, SubsFactory, UnreachableCreditorHold, UsageInvoice_0v1,
+        DEFAULT_EARNING_WALLET, DEFAULT_PAYMENT_THRESHOLDS, DEFAULT_SCAN_INTERVALS,
+        MSG_ID_INCREMENTER, TEMPORARY_CONSUMING_WALLET,
     };
-    use crate::sub_lib::wallet::Wallet;
+    use crate::sub_lib::cryptde::PublicKey;
+    use crate::sub_lib::cryptde_null::CryptDENull;
+    use crate::sub_lib::wallet::{Wallet, WalletError};
     use crate::test_utils::recorder::{make_accountant_subs_from_recorder, Recorder};
+    use crate::test_utils::{main_cryptde, make_paying_wallet, make_wallet};
     use actix::Actor;
+    use masq_lib::logger::Logger;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
+    use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use std::str::FromStr;
     use std::sync::atomic::Ordering;
     use std::sync::Mutex;
     use std::time::Duration;
+    use web3::types::U256;
 
     static MSG_ID_GENERATOR_TEST_GUARD: Mutex<()> = Mutex::new(());
 
@@ -241,7 +826,8 @@ mod tests {
         assert_eq!(
             *TEMPORARY_CONSUMING_WALLET,
             temporary_consuming_wallet_expected
-        )
+        );
+        assert_eq!(MIN_GAS_BUMP_PERCENT, 10)
     }
 
     #[test]
@@ -253,6 +839,328 @@ mod tests {
         assert_eq!(format!("{:?}", subject), "AccountantSubs");
     }
 
+    #[test]
+    fn consuming_wallet_selection_defaults_to_round_robin() {
+        assert_eq!(
+            ConsumingWalletSelection::default(),
+            ConsumingWalletSelection::RoundRobin
+        )
+    }
+
+    #[test]
+    fn consuming_wallet_selection_round_trips_through_strings() {
+        vec![
+            ConsumingWalletSelection::RoundRobin,
+            ConsumingWalletSelection::HighestBalance,
+        ]
+        .into_iter()
+        .for_each(|selection| {
+            assert_eq!(
+                ConsumingWalletSelection::from_str(&selection.to_string()),
+                Ok(selection)
+            )
+        });
+        assert_eq!(
+            ConsumingWalletSelection::from_str("Highest-Balance"),
+            Ok(ConsumingWalletSelection::HighestBalance)
+        );
+    }
+
+    #[test]
+    fn consuming_wallet_selection_rejects_unknown_value() {
+        let result = ConsumingWalletSelection::from_str("lowest-balance");
+
+        assert_eq!(
+            result,
+            Err(
+                "'lowest-balance' is not a consuming wallet selection; use round-robin or highest-balance"
+                    .to_string()
+            )
+        )
+    }
+
+    #[test]
+    fn payment_batching_round_trips_through_strings() {
+        let batching = PaymentBatching {
+            min_economic_size_gwei: 50_000_000,
+            batch_target_gwei: 400_000_000,
+            max_delay_sec: 3600,
+        };
+
+        assert_eq!(batching.to_string(), "50000000|400000000|3600".to_string());
+        assert_eq!(
+            PaymentBatching::from_str(&batching.to_string()),
+            Ok(batching)
+        );
+    }
+
+    #[test]
+    fn payment_batching_requires_exactly_three_numbers() {
+        vec!["1000|5000", "1000|5000|60|7", "1000|booga|60"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    PaymentBatching::from_str(value),
+                    Err(format!(
+                        "'{}' is not a payment batching; supply three nonnegative numbers like \
+                         MIN-ECONOMIC-SIZE-GWEI|BATCH-TARGET-GWEI|MAX-DELAY-SEC",
+                        value
+                    ))
+                )
+            });
+    }
+
+    #[test]
+    fn unreachable_creditor_hold_round_trips_through_strings() {
+        let hold = UnreachableCreditorHold {
+            absence_sec: 86_400,
+            deadline_margin_sec: 3_600,
+        };
+
+        assert_eq!(hold.to_string(), "86400|3600".to_string());
+        assert_eq!(
+            UnreachableCreditorHold::from_str(&hold.to_string()),
+            Ok(hold)
+        );
+    }
+
+    #[test]
+    fn unreachable_creditor_hold_requires_exactly_two_numbers() {
+        vec!["86400", "86400|3600|60", "86400|booga"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    UnreachableCreditorHold::from_str(value),
+                    Err(format!(
+                        "'{}' is not an unreachable-creditor hold; supply two nonnegative numbers \
+                         like ABSENCE-SEC|DEADLINE-MARGIN-SEC",
+                        value
+                    ))
+                )
+            });
+    }
+
+    #[test]
+    fn spend_rate_limits_round_trip_through_strings() {
+        let limits = SpendRateLimits {
+            service_fee_per_hour_gwei: 100_000_000,
+            service_fee_per_day_gwei: 1_000_000_000,
+            transaction_fee_per_hour_gwei: 5_000_000,
+            transaction_fee_per_day_gwei: 20_000_000,
+        };
+
+        assert_eq!(
+            limits.to_string(),
+            "100000000|1000000000|5000000|20000000".to_string()
+        );
+        assert_eq!(SpendRateLimits::from_str(&limits.to_string()), Ok(limits));
+    }
+
+    #[test]
+    fn spend_rate_limits_require_exactly_four_numbers() {
+        vec!["100|1000|5", "100|1000|5|20|7", "100|1000|booga|20"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    SpendRateLimits::from_str(value),
+                    Err(format!(
+                        "'{}' is not a set of spend rate limits; supply four nonnegative numbers \
+                         like SERVICE-FEE-PER-HOUR-GWEI|SERVICE-FEE-PER-DAY-GWEI|\
+                         TRANSACTION-FEE-PER-HOUR-GWEI|TRANSACTION-FEE-PER-DAY-GWEI",
+                        value
+                    ))
+                )
+            });
+    }
+
+    #[test]
+    fn payment_window_round_trips_through_strings() {
+        let window = PaymentWindow {
+            start_hour: 22,
+            end_hour: 4,
+            low_gas_price_gwei: 2,
+            deadline_margin_sec: 3_600,
+        };
+
+        assert_eq!(window.to_string(), "22|4|2|3600".to_string());
+        assert_eq!(PaymentWindow::from_str(&window.to_string()), Ok(window));
+    }
+
+    #[test]
+    fn payment_window_requires_four_numbers_and_two_different_hours_of_the_day() {
+        vec![
+            "2|6|0",
+            "2|6|0|600|7",
+            "2|booga|0|600",
+            "2|24|0|600",
+            "5|5|0|600",
+        ]
+        .into_iter()
+        .for_each(|value| {
+            assert_eq!(
+                PaymentWindow::from_str(value),
+                Err(format!(
+                    "'{}' is not a payment window; supply four nonnegative numbers like \
+                         START-HOUR-UTC|END-HOUR-UTC|LOW-GAS-PRICE-GWEI|DEADLINE-MARGIN-SEC, with \
+                         two different hours below 24",
+                    value
+                ))
+            )
+        });
+    }
+
+    #[test]
+    fn payment_window_contains_hours_from_its_start_up_to_its_end() {
+        let make_window = |start_hour, end_hour| PaymentWindow {
+            start_hour,
+            end_hour,
+            low_gas_price_gwei: 0,
+            deadline_margin_sec: 0,
+        };
+        let daytime = make_window(2, 6);
+        let overnight = make_window(22, 4);
+
+        assert_eq!(
+            (0..24)
+                .filter(|hour| daytime.contains_hour(*hour))
+                .collect::<Vec<u8>>(),
+            vec![2, 3, 4, 5]
+        );
+        assert_eq!(
+            (0..24)
+                .filter(|hour| overnight.contains_hour(*hour))
+                .collect::<Vec<u8>>(),
+            vec![0, 1, 2, 3, 22, 23]
+        );
+    }
+
+    #[test]
+    fn gas_price_ceiling_round_trips_through_strings() {
+        let ceiling = GasPriceCeiling {
+            ceiling_gwei: 30,
+            deadline_margin_sec: 7_200,
+        };
+
+        assert_eq!(ceiling.to_string(), "30|7200".to_string());
+        assert_eq!(GasPriceCeiling::from_str(&ceiling.to_string()), Ok(ceiling));
+    }
+
+    #[test]
+    fn gas_price_ceiling_requires_exactly_two_numbers() {
+        vec!["30", "30|7200|5", "30|booga"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    GasPriceCeiling::from_str(value),
+                    Err(format!(
+                        "'{}' is not a gas price ceiling; supply two nonnegative numbers like \
+                         CEILING-GWEI|DEADLINE-MARGIN-SEC",
+                        value
+                    ))
+                )
+            });
+    }
+
+    #[test]
+    fn gas_bump_ladder_round_trips_through_strings() {
+        let ladder = GasBumpLadder {
+            step_percent: 15,
+            max_steps: 3,
+            interval_sec: 600,
+        };
+
+        assert_eq!(ladder.to_string(), "15|3|600".to_string());
+        assert_eq!(GasBumpLadder::from_str(&ladder.to_string()), Ok(ladder));
+    }
+
+    #[test]
+    fn gas_bump_ladder_requires_three_numbers_in_range() {
+        vec![
+            "15|3",
+            "15|3|600|1",
+            "15|booga|600",
+            "256|3|600",
+            "15|256|600",
+            "15|3|0",
+        ]
+        .into_iter()
+        .for_each(|value| {
+            assert_eq!(
+                GasBumpLadder::from_str(value),
+                Err(format!(
+                    "'{}' is not a gas bump ladder; supply three numbers like \
+                         STEP-PERCENT|MAX-STEPS|INTERVAL-SEC, the first two up to 255 and the \
+                         last above 0",
+                    value
+                ))
+            )
+        });
+    }
+
+    #[test]
+    fn gas_bump_ladder_rejects_steps_too_small_to_outbid() {
+        let result = GasBumpLadder::from_str("9|3|600");
+
+        assert_eq!(
+            result,
+            Err(
+                "'9|3|600' is not a gas bump ladder; a step under 10% would be turned down by \
+                 the blockchain service"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn idle_spending_freeze_round_trips_through_strings() {
+        let freeze = IdleSpendingFreeze {
+            idle_sec: 3_600,
+            deadline_margin_sec: 7_200,
+        };
+
+        assert_eq!(freeze.to_string(), "3600|7200".to_string());
+        assert_eq!(
+            IdleSpendingFreeze::from_str(&freeze.to_string()),
+            Ok(freeze)
+        );
+    }
+
+    #[test]
+    fn idle_spending_freeze_requires_two_numbers_and_an_idle_period() {
+        vec!["3600", "3600|7200|1", "booga|7200", "3600|-1", "0|7200"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    IdleSpendingFreeze::from_str(value),
+                    Err(format!(
+                        "'{}' is not an idle spending freeze; supply two nonnegative numbers \
+                         like IDLE-SEC|DEADLINE-MARGIN-SEC, the first above 0",
+                        value
+                    ))
+                )
+            });
+    }
+
+    #[test]
+    fn scan_ids_follow_each_other() {
+        assert_eq!(ScanId::default(), ScanId(0));
+        assert_eq!(ScanId::default().next(), ScanId(1));
+        assert_eq!(ScanId(41).next(), ScanId(42));
+    }
+
+    #[test]
+    fn scan_id_spans_the_logger_unless_it_stands_for_no_scan() {
+        init_test_logging();
+        let logger = Logger::new("scan_id_spans_the_logger");
+
+        info!(ScanId(42).span(&logger), "within a scan");
+        info!(ScanId::default().span(&logger), "outside any scan");
+
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing("INFO: scan_id_spans_the_logger[scan_id=42]: within a scan");
+        tlh.exists_log_containing("INFO: scan_id_spans_the_logger: outside any scan");
+    }
+
     #[test]
     fn accountant_subs_factory_produces_proper_subs() {
         let subject = AccountantSubsFactoryReal {};
@@ -288,4 +1196,164 @@ mod tests {
 
         assert_eq!(id, 0)
     }
+
+    #[test]
+    fn payment_notice_is_signed_by_the_paying_wallet() {
+        let paying_wallet = make_paying_wallet(b"debtor");
+        let creditor_wallet = make_wallet("creditor");
+
+        let subject = PaymentNotice_0v1::new(
+            &paying_wallet,
+            &creditor_wallet,
+            make_tx_hash(789),
+            4_567_000_000_000,
+            1_234,
+        )
+        .unwrap();
+
+        assert_eq!(subject.creditor_wallet, creditor_wallet);
+        assert_eq!(subject.tx_hash, make_tx_hash(789));
+        assert_eq!(subject.amount_wei, U256::from(4_567_000_000_000u128));
+        assert_eq!(subject.block_number, 1_234);
+        assert_eq!(subject.payer.wallet, paying_wallet.as_address_wallet());
+        assert_eq!(subject.is_signed_by_payer(), true)
+    }
+
+    #[test]
+    fn payment_notice_altered_after_signing_is_not_signed_by_payer() {
+        let original = PaymentNotice_0v1::new(
+            &make_paying_wallet(b"debtor"),
+            &make_wallet("creditor"),
+            make_tx_hash(789),
+            4_567_000_000_000,
+            1_234,
+        )
+        .unwrap();
+        let altered = vec![
+            PaymentNotice_0v1 {
+                creditor_wallet: make_wallet("impostor"),
+                ..original.clone()
+            },
+            PaymentNotice_0v1 {
+                tx_hash: make_tx_hash(790),
+                ..original.clone()
+            },
+            PaymentNotice_0v1 {
+                amount_wei: U256::from(4_567_000_000_001u128),
+                ..original.clone()
+            },
+            PaymentNotice_0v1 {
+                block_number: 1_235,
+                ..original.clone()
+            },
+        ];
+
+        altered
+            .into_iter()
+            .for_each(|notice| assert_eq!(notice.is_signed_by_payer(), false, "{:?}", notice));
+    }
+
+    #[test]
+    fn payment_notice_cannot_be_signed_by_a_wallet_without_a_secret_key() {
+        let result = PaymentNotice_0v1::new(
+            &make_wallet("debtor"),
+            &make_wallet("creditor"),
+            make_tx_hash(789),
+            4_567_000_000_000,
+            1_234,
+        );
+
+        match result {
+            Err(WalletError::Signature(msg)) => {
+                assert!(
+                    msg.starts_with("Cannot sign with non-keypair wallet"),
+                    "{}",
+                    msg
+                )
+            }
+            x => panic!("we expected a signature error but got: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn usage_invoice_signed_by_the_creditor_node_is_recognized_as_such() {
+        let cryptde = main_cryptde();
+        let mut subject = UsageInvoice_0v1::new(
+            &make_paying_wallet(b"creditor"),
+            &make_wallet("debtor"),
+            1_000,
+            2_000,
+            12,
+            3,
+            4_567_000_000_000,
+        );
+
+        subject.sign(cryptde).unwrap();
+
+        assert_eq!(
+            subject.creditor_wallet,
+            make_paying_wallet(b"creditor").as_address_wallet()
+        );
+        assert_eq!(subject.debtor_wallet, make_wallet("debtor"));
+        assert_eq!(subject.amount_wei, U256::from(4_567_000_000_000u128));
+        assert_eq!(subject.is_signed_by(cryptde, cryptde.public_key()), true);
+        let other_key = PublicKey::new(b"someone else");
+        let other_cryptde = CryptDENull::from(&other_key, TEST_DEFAULT_CHAIN);
+        assert_eq!(subject.is_signed_by(cryptde, &other_key), false);
+        assert_eq!(subject.is_signed_by(&other_cryptde, &other_key), false);
+    }
+
+    #[test]
+    fn usage_invoice_altered_after_signing_is_not_signed_by_the_creditor_node() {
+        let cryptde = main_cryptde();
+        let mut original = UsageInvoice_0v1::new(
+            &make_wallet("creditor"),
+            &make_wallet("debtor"),
+            1_000,
+            2_000,
+            12,
+            3,
+            4_567_000_000_000,
+        );
+        original.sign(cryptde).unwrap();
+        let altered = vec![
+            UsageInvoice_0v1 {
+                creditor_wallet: make_wallet("impostor"),
+                ..original.clone()
+            },
+            UsageInvoice_0v1 {
+                debtor_wallet: make_wallet("bystander"),
+                ..original.clone()
+            },
+            UsageInvoice_0v1 {
+                period_start_sec: 999,
+                ..original.clone()
+            },
+            UsageInvoice_0v1 {
+                period_end_sec: 2_001,
+                ..original.clone()
+            },
+            UsageInvoice_0v1 {
+                routing_services: 13,
+                ..original.clone()
+            },
+            UsageInvoice_0v1 {
+                exit_services: 4,
+                ..original.clone()
+            },
+            UsageInvoice_0v1 {
+                amount_wei: U256::from(4_567_000_000_001u128),
+                ..original.clone()
+            },
+        ];
+
+        altered.into_iter().for_each(|invoice| {
+            assert_eq!(
+                invoice.is_signed_by(cryptde, cryptde.public_key()),
+                false,
+                "{:?}",
+                invoice
+            )
+        });
+    }
 }
@@ -4,7 +4,11 @@ use crate::accountant::db_access_objects::payable_dao::PayableAccount;
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::blockchain_agent::BlockchainAgent;
 use crate::accountant::scanners::mid_scan_msg_handling::payable_scanner::msgs::QualifiedPayablesMessage;
 use crate::accountant::{RequestTransactionReceipts, ResponseSkeleton, SkeletonOptHolder};
-use crate::blockchain::blockchain_bridge::RetrieveTransactions;
+use crate::blockchain::blockchain_bridge::{
+    PendingPayableFingerprint, ReplacementBasis, RetrieveTransactions,
+};
+use crate::sub_lib::accountant::ScanId;
+use crate::sub_lib::neighborhood::ConfigChangeMsg;
 use crate::sub_lib::peer_actors::BindMessage;
 use actix::Message;
 use actix::Recipient;
@@ -12,7 +16,7 @@ use masq_lib::blockchains::chains::Chain;
 use masq_lib::ui_gateway::NodeFromUiMessage;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
-use web3::types::U256;
+use web3::types::{Address, H256, U256};
 
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct BlockchainBridgeConfig {
@@ -21,6 +25,26 @@ pub struct BlockchainBridgeConfig {
     // TODO: totally ignored during the setup of the BlockchainBridge actor!
     // Use it in the body or delete this field
     pub gas_price: u64,
+    // Contracts (e.g. ERC-2771 forwarders) relaying payments on behalf of their actual payers
+    pub payment_forwarders: Vec<Address>,
+    // Received payments younger than this many blocks are neither credited nor yet trusted
+    pub receivable_confirmation_blocks: u64,
+    // How many times each request of a received-payments scan is tried when it fails transiently
+    pub receivable_scan_attempts: u16,
+    // How many received-payments scans have their raw logs recorded for an offline replay
+    pub receivable_scan_records: u64,
+    // How long a single request may wait for the blockchain service before it counts as failed
+    pub blockchain_request_timeout_secs: u64,
+    // Whether the payments this Node makes carry a memo naming it
+    pub payment_memo: bool,
+    // Whether each payment is tried out with eth_call before its batch is sent
+    pub payment_simulation: bool,
+    // The most payments sent in one batch; longer lists go out as several batches. 0 means no limit
+    pub max_batch_size: u16,
+    // Requests a day the plan with the provider of the blockchain service allows. 0 means no quota
+    pub rpc_daily_quota: u64,
+    // Whether the consuming wallet is funded from the unlocked accounts of a development chain
+    pub dev_faucet: bool,
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -31,6 +55,9 @@ pub struct BlockchainBridgeSubs {
     pub retrieve_transactions: Recipient<RetrieveTransactions>,
     pub ui_sub: Recipient<NodeFromUiMessage>,
     pub request_transaction_receipts: Recipient<RequestTransactionReceipts>,
+    pub replace_pending_payable: Recipient<ReplacePendingPayable>,
+    pub void_abandoned_transaction: Recipient<VoidAbandonedTransaction>,
+    pub config_change_msg_sub: Recipient<ConfigChangeMsg>,
 }
 
 impl Debug for BlockchainBridgeSubs {
@@ -43,6 +70,7 @@ impl Debug for BlockchainBridgeSubs {
 pub struct OutboundPaymentsInstructions {
     pub affordable_accounts: Vec<PayableAccount>,
     pub agent: Box<dyn BlockchainAgent>,
+    pub scan_id: ScanId,
     pub response_skeleton_opt: Option<ResponseSkeleton>,
 }
 
@@ -55,6 +83,7 @@ impl OutboundPaymentsInstructions {
         Self {
             affordable_accounts,
             agent,
+            scan_id: ScanId::default(),
             response_skeleton_opt,
         }
     }
@@ -66,6 +95,31 @@ impl SkeletonOptHolder for OutboundPaymentsInstructions {
     }
 }
 
+// Asks for the transaction of a pending payable to be outbid by one with the same nonce and a gas
+// price raised by the given percentage; the accounts are those the original transaction pays
+#[derive(Debug, Clone, PartialEq, Eq, Message)]
+pub struct ReplacePendingPayable {
+    pub fingerprint: PendingPayableFingerprint,
+    pub accounts: Vec<PayableAccount>,
+    pub gas_price_bump_percent: u8,
+    pub response_skeleton_opt: Option<ResponseSkeleton>,
+}
+
+impl SkeletonOptHolder for ReplacePendingPayable {
+    fn skeleton_opt(&self) -> Option<ResponseSkeleton> {
+        self.response_skeleton_opt
+    }
+}
+
+// Asks for the nonce of an abandoned transaction to be spent by a transfer of nothing to the
+// consuming wallet, outbidding the abandoned one by the given percentage of its gas price
+#[derive(Debug, Clone, PartialEq, Eq, Message)]
+pub struct VoidAbandonedTransaction {
+    pub hash: H256,
+    pub basis: ReplacementBasis,
+    pub gas_price_bump_percent: u8,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ConsumingWalletBalances {
     pub transaction_fee_balance_in_minor_units: U256,
@@ -110,6 +164,9 @@ mod tests {
             Box::new(blockchain_interface),
             Arc::new(Mutex::new(persistent_config)),
             false,
+            None,
+            None,
+            None,
         );
         let system = System::new("blockchain_bridge_subs_factory_produces_proper_subs");
         let addr = accountant.start();
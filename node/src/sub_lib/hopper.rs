@@ -1,12 +1,15 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 use crate::bootstrapper::CryptDEPair;
 use crate::neighborhood::gossip::Gossip_0v1;
+use crate::sub_lib::accountant::{PaymentNotice_0v1, UsageInvoice_0v1};
 use crate::sub_lib::cryptde::encodex;
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde::CryptData;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::dispatcher::InboundClientData;
-use crate::sub_lib::neighborhood::GossipFailure_0v1;
+use crate::sub_lib::neighborhood::{
+    ExitOptionsQuery_0v1, ExitOptionsReport_0v1, GossipFailure_0v1,
+};
 use crate::sub_lib::node_addr::NodeAddr;
 use crate::sub_lib::peer_actors::BindMessage;
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
@@ -72,6 +75,10 @@ pub enum MessageType {
     Gossip(VersionedData<Gossip_0v1>),
     GossipFailure(VersionedData<GossipFailure_0v1>),
     DnsResolveFailed(VersionedData<DnsResolveFailure_0v1>),
+    PaymentNotice(VersionedData<PaymentNotice_0v1>),
+    ExitOptionsQuery(VersionedData<ExitOptionsQuery_0v1>),
+    ExitOptionsReport(VersionedData<ExitOptionsReport_0v1>),
+    UsageInvoice(VersionedData<UsageInvoice_0v1>),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -81,6 +88,10 @@ pub enum MessageTypeLite {
     Gossip,
     GossipFailure,
     DnsResolveFailed,
+    PaymentNotice,
+    ExitOptionsQuery,
+    ExitOptionsReport,
+    UsageInvoice,
 }
 
 #[allow(clippy::from_over_into)]
@@ -92,6 +103,10 @@ impl Into<MessageTypeLite> for MessageType {
             MessageType::Gossip(_) => MessageTypeLite::Gossip,
             MessageType::GossipFailure(_) => MessageTypeLite::GossipFailure,
             MessageType::DnsResolveFailed(_) => MessageTypeLite::DnsResolveFailed,
+            MessageType::PaymentNotice(_) => MessageTypeLite::PaymentNotice,
+            MessageType::ExitOptionsQuery(_) => MessageTypeLite::ExitOptionsQuery,
+            MessageType::ExitOptionsReport(_) => MessageTypeLite::ExitOptionsReport,
+            MessageType::UsageInvoice(_) => MessageTypeLite::UsageInvoice,
         }
     }
 }
@@ -325,17 +340,34 @@ mod tests {
         let client_request = MessageType::ClientRequest(VersionedData::test_new(dv!(0, 0), vec![]));
         let gossip_failure = MessageType::GossipFailure(VersionedData::test_new(dv!(0, 0), vec![]));
         let gossip = MessageType::Gossip(VersionedData::test_new(dv!(0, 0), vec![]));
+        let payment_notice = MessageType::PaymentNotice(VersionedData::test_new(dv!(0, 0), vec![]));
+        let exit_options_query =
+            MessageType::ExitOptionsQuery(VersionedData::test_new(dv!(0, 0), vec![]));
+        let exit_options_report =
+            MessageType::ExitOptionsReport(VersionedData::test_new(dv!(0, 0), vec![]));
+        let usage_invoice = MessageType::UsageInvoice(VersionedData::test_new(dv!(0, 0), vec![]));
 
         let dns_resolve_failed_result: MessageTypeLite = dns_resolve_failed.into();
         let client_response_result: MessageTypeLite = client_response.into();
         let client_request_result: MessageTypeLite = client_request.into();
         let gossip_failure_result: MessageTypeLite = gossip_failure.into();
         let gossip_result: MessageTypeLite = gossip.into();
+        let payment_notice_result: MessageTypeLite = payment_notice.into();
+        let exit_options_query_result: MessageTypeLite = exit_options_query.into();
+        let exit_options_report_result: MessageTypeLite = exit_options_report.into();
+        let usage_invoice_result: MessageTypeLite = usage_invoice.into();
 
         assert_eq!(dns_resolve_failed_result, MessageTypeLite::DnsResolveFailed);
         assert_eq!(client_response_result, MessageTypeLite::ClientResponse);
         assert_eq!(client_request_result, MessageTypeLite::ClientRequest);
         assert_eq!(gossip_failure_result, MessageTypeLite::GossipFailure);
         assert_eq!(gossip_result, MessageTypeLite::Gossip);
+        assert_eq!(payment_notice_result, MessageTypeLite::PaymentNotice);
+        assert_eq!(exit_options_query_result, MessageTypeLite::ExitOptionsQuery);
+        assert_eq!(
+            exit_options_report_result,
+            MessageTypeLite::ExitOptionsReport
+        );
+        assert_eq!(usage_invoice_result, MessageTypeLite::UsageInvoice);
     }
 }
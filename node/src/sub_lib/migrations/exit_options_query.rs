@@ -0,0 +1,115 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::sub_lib::migrations::utils::value_to_type;
+use crate::sub_lib::neighborhood::ExitOptionsQuery_0v1;
+use crate::sub_lib::versioned_data::Migrations;
+use crate::sub_lib::versioned_data::{MigrationError, StepError, VersionedData};
+use lazy_static::lazy_static;
+use serde_cbor::Value;
+use std::convert::TryFrom;
+
+lazy_static! {
+    pub static ref MIGRATIONS: Migrations = {
+        let current_version = masq_lib::constants::EXIT_OPTIONS_QUERY_CURRENT_VERSION;
+        let mut migrations = Migrations::new(current_version);
+
+        migrate_value!(dv!(0, 1), ExitOptionsQuery_0v1, ExitOptionsQueryMF_0v1, {|value: serde_cbor::Value| {
+            ExitOptionsQuery_0v1::try_from (&value)
+        }});
+        migrations.add_step (masq_lib::data_version::FUTURE_VERSION, dv!(0, 1), Box::new (ExitOptionsQueryMF_0v1{}));
+
+        // add more steps here
+
+        migrations
+    };
+}
+
+impl From<ExitOptionsQuery_0v1> for VersionedData<ExitOptionsQuery_0v1> {
+    fn from(data: ExitOptionsQuery_0v1) -> Self {
+        VersionedData::new(&MIGRATIONS, &data)
+    }
+}
+
+impl TryFrom<VersionedData<ExitOptionsQuery_0v1>> for ExitOptionsQuery_0v1 {
+    type Error = MigrationError;
+
+    fn try_from(vd: VersionedData<ExitOptionsQuery_0v1>) -> Result<Self, Self::Error> {
+        vd.extract(&MIGRATIONS)
+    }
+}
+
+impl TryFrom<&Value> for ExitOptionsQuery_0v1 {
+    type Error = StepError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => {
+                let mut sent_at_ms_opt: Option<u64> = None;
+                map.keys().for_each(|k| {
+                    let v = map.get(k).expect("Disappeared");
+                    if let Value::Text(field_name) = k {
+                        if field_name.as_str() == "sent_at_ms" {
+                            sent_at_ms_opt = value_to_type::<u64>(v)
+                        }
+                    }
+                });
+                match sent_at_ms_opt {
+                    Some(sent_at_ms) => Ok(ExitOptionsQuery_0v1 { sent_at_ms }),
+                    None => unimplemented!("{:?}", vec!["sent_at_ms"]),
+                }
+            }
+            _ => Err(StepError::SemanticError(format!(
+                "Expected Value::Map; found {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::data_version::DataVersion;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[test]
+    fn can_migrate_from_the_future() {
+        #[derive(Serialize, Deserialize)]
+        struct ExampleFutureEOQ {
+            pub sent_at_ms: u64,
+            pub another_field: String,
+        }
+        let future_eoq = ExampleFutureEOQ {
+            sent_at_ms: 1_700_000_000_123,
+            another_field: "These are the times that try men's souls".to_string(),
+        };
+        let future_migrations = Migrations::new(DataVersion::new(4095, 4095));
+        let serialized =
+            serde_cbor::ser::to_vec(&VersionedData::new(&future_migrations, &future_eoq)).unwrap();
+        let future_vd =
+            serde_cbor::de::from_slice::<VersionedData<ExitOptionsQuery_0v1>>(&serialized).unwrap();
+
+        let actual_eoq = ExitOptionsQuery_0v1::try_from(future_vd).unwrap();
+
+        assert_eq!(
+            actual_eoq,
+            ExitOptionsQuery_0v1 {
+                sent_at_ms: 1_700_000_000_123
+            }
+        );
+    }
+
+    #[test]
+    fn cannot_migrate_from_value_other_than_map() {
+        let value = Value::Bool(true);
+
+        let result = ExitOptionsQuery_0v1::try_from(&value);
+
+        assert_eq!(
+            result,
+            Err(StepError::SemanticError(
+                "Expected Value::Map; found Bool(true)".to_string()
+            ))
+        )
+    }
+}
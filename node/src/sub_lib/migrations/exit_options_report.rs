@@ -0,0 +1,137 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::sub_lib::migrations::utils::value_to_type;
+use crate::sub_lib::neighborhood::ExitOptionsReport_0v1;
+use crate::sub_lib::versioned_data::Migrations;
+use crate::sub_lib::versioned_data::{MigrationError, StepError, VersionedData};
+use lazy_static::lazy_static;
+use serde_cbor::Value;
+use std::convert::TryFrom;
+
+lazy_static! {
+    pub static ref MIGRATIONS: Migrations = {
+        let current_version = masq_lib::constants::EXIT_OPTIONS_REPORT_CURRENT_VERSION;
+        let mut migrations = Migrations::new(current_version);
+
+        migrate_value!(dv!(0, 1), ExitOptionsReport_0v1, ExitOptionsReportMF_0v1, {|value: serde_cbor::Value| {
+            ExitOptionsReport_0v1::try_from (&value)
+        }});
+        migrations.add_step (masq_lib::data_version::FUTURE_VERSION, dv!(0, 1), Box::new (ExitOptionsReportMF_0v1{}));
+
+        // add more steps here
+
+        migrations
+    };
+}
+
+impl From<ExitOptionsReport_0v1> for VersionedData<ExitOptionsReport_0v1> {
+    fn from(data: ExitOptionsReport_0v1) -> Self {
+        VersionedData::new(&MIGRATIONS, &data)
+    }
+}
+
+impl TryFrom<VersionedData<ExitOptionsReport_0v1>> for ExitOptionsReport_0v1 {
+    type Error = MigrationError;
+
+    fn try_from(vd: VersionedData<ExitOptionsReport_0v1>) -> Result<Self, Self::Error> {
+        vd.extract(&MIGRATIONS)
+    }
+}
+
+impl TryFrom<&Value> for ExitOptionsReport_0v1 {
+    type Error = StepError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => {
+                let mut exit_country_codes_opt: Option<Vec<String>> = None;
+                let mut query_sent_at_ms_opt: Option<u64> = None;
+                map.keys().for_each(|k| {
+                    let v = map.get(k).expect("Disappeared");
+                    if let Value::Text(field_name) = k {
+                        match field_name.as_str() {
+                            "exit_country_codes" => {
+                                exit_country_codes_opt = value_to_type::<Vec<String>>(v)
+                            }
+                            "query_sent_at_ms" => query_sent_at_ms_opt = value_to_type::<u64>(v),
+                            _ => (),
+                        }
+                    }
+                });
+                let mut missing_fields: Vec<&str> = vec![];
+                if exit_country_codes_opt.is_none() {
+                    missing_fields.push("exit_country_codes")
+                }
+                if query_sent_at_ms_opt.is_none() {
+                    missing_fields.push("query_sent_at_ms")
+                }
+                if !missing_fields.is_empty() {
+                    unimplemented!("{:?}", missing_fields.clone())
+                }
+                Ok(ExitOptionsReport_0v1 {
+                    exit_country_codes: exit_country_codes_opt
+                        .expect("exit_country_codes disappeared"),
+                    query_sent_at_ms: query_sent_at_ms_opt.expect("query_sent_at_ms disappeared"),
+                })
+            }
+            _ => Err(StepError::SemanticError(format!(
+                "Expected Value::Map; found {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::data_version::DataVersion;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[test]
+    fn can_migrate_from_the_future() {
+        #[derive(Serialize, Deserialize)]
+        struct ExampleFutureEOR {
+            pub exit_country_codes: Vec<String>,
+            pub query_sent_at_ms: u64,
+            pub another_field: String,
+            pub yet_another_field: u64,
+        }
+        let future_eor = ExampleFutureEOR {
+            exit_country_codes: vec!["CZ".to_string(), "DE".to_string()],
+            query_sent_at_ms: 1_700_000_000_123,
+            another_field: "These are the times that try men's souls".to_string(),
+            yet_another_field: 1234567890,
+        };
+        let future_migrations = Migrations::new(DataVersion::new(4095, 4095));
+        let serialized =
+            serde_cbor::ser::to_vec(&VersionedData::new(&future_migrations, &future_eor)).unwrap();
+        let future_vd =
+            serde_cbor::de::from_slice::<VersionedData<ExitOptionsReport_0v1>>(&serialized)
+                .unwrap();
+
+        let actual_eor = ExitOptionsReport_0v1::try_from(future_vd).unwrap();
+
+        assert_eq!(
+            actual_eor,
+            ExitOptionsReport_0v1 {
+                exit_country_codes: vec!["CZ".to_string(), "DE".to_string()],
+                query_sent_at_ms: 1_700_000_000_123,
+            }
+        );
+    }
+
+    #[test]
+    fn cannot_migrate_from_value_other_than_map() {
+        let value = Value::Bool(true);
+
+        let result = ExitOptionsReport_0v1::try_from(&value);
+
+        assert_eq!(
+            result,
+            Err(StepError::SemanticError(
+                "Expected Value::Map; found Bool(true)".to_string()
+            ))
+        )
+    }
+}
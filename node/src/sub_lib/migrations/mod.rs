@@ -3,7 +3,11 @@
 pub mod client_request_payload;
 pub mod client_response_payload;
 pub mod dns_resolve_failure;
+pub mod exit_options_query;
+pub mod exit_options_report;
 pub mod gossip;
 pub mod gossip_failure;
 pub mod node_record_inner;
+pub mod payment_notice;
+pub mod usage_invoice;
 pub mod utils;
@@ -1,6 +1,7 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::neighborhood::node_record::NodeRecordInner_0v1;
+use crate::sub_lib::accountant::PaymentThresholds;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::migrations::utils::value_to_type;
 use crate::sub_lib::neighborhood::RatePack;
@@ -56,12 +57,16 @@ impl TryFrom<&Value> for NodeRecordInner_0v1 {
                 let mut routes_data_opt: Option<bool> = None;
                 let mut version_opt: Option<u32> = None;
                 let mut country_code_opt: Option<String> = None;
+                let mut payment_thresholds_opt: Option<PaymentThresholds> = None;
                 map.keys().for_each(|k| {
                     let v = map.get(k).expect("Disappeared");
                     match (k, v) {
                         (Value::Text(field_name), Value::Map(_)) => match field_name.as_str() {
                             "earning_wallet" => earning_wallet_opt = value_to_type::<Wallet>(v),
                             "rate_pack" => rate_pack_opt = value_to_type::<RatePack>(v),
+                            "payment_thresholds_opt" => {
+                                payment_thresholds_opt = value_to_type::<PaymentThresholds>(v)
+                            }
                             _ => (),
                         },
                         (Value::Text(field_name), Value::Array(field_value)) => {
@@ -140,6 +145,7 @@ impl TryFrom<&Value> for NodeRecordInner_0v1 {
                     routes_data: routes_data_opt.expect("public_key disappeared"),
                     version: version_opt.expect("public_key disappeared"),
                     country_code_opt,
+                    payment_thresholds_opt,
                 })
             }
             _ => Err(StepError::SemanticError(format!(
@@ -197,6 +203,7 @@ mod tests {
             routes_data: true,
             version: 42,
             country_code_opt: Some("AU".to_string()),
+            payment_thresholds_opt: None,
         };
         let future_nri = ExampleFutureNRI {
             public_key: expected_nri.public_key.clone(),
@@ -0,0 +1,164 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchain::payer::Payer;
+use crate::sub_lib::accountant::PaymentNotice_0v1;
+use crate::sub_lib::migrations::utils::value_to_type;
+use crate::sub_lib::versioned_data::Migrations;
+use crate::sub_lib::versioned_data::{MigrationError, StepError, VersionedData};
+use crate::sub_lib::wallet::Wallet;
+use lazy_static::lazy_static;
+use serde_cbor::Value;
+use std::convert::TryFrom;
+use web3::types::{H256, U256};
+
+lazy_static! {
+    pub static ref MIGRATIONS: Migrations = {
+        let current_version = masq_lib::constants::PAYMENT_NOTICE_CURRENT_VERSION;
+        let mut migrations = Migrations::new(current_version);
+
+        migrate_value!(dv!(0, 1), PaymentNotice_0v1, PaymentNoticeMF_0v1, {|value: serde_cbor::Value| {
+            PaymentNotice_0v1::try_from (&value)
+        }});
+        migrations.add_step (masq_lib::data_version::FUTURE_VERSION, dv!(0, 1), Box::new (PaymentNoticeMF_0v1{}));
+
+        // add more steps here
+
+        migrations
+    };
+}
+
+impl From<PaymentNotice_0v1> for VersionedData<PaymentNotice_0v1> {
+    fn from(data: PaymentNotice_0v1) -> Self {
+        VersionedData::new(&MIGRATIONS, &data)
+    }
+}
+
+impl TryFrom<VersionedData<PaymentNotice_0v1>> for PaymentNotice_0v1 {
+    type Error = MigrationError;
+
+    fn try_from(vd: VersionedData<PaymentNotice_0v1>) -> Result<Self, Self::Error> {
+        vd.extract(&MIGRATIONS)
+    }
+}
+
+impl TryFrom<&Value> for PaymentNotice_0v1 {
+    type Error = StepError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => {
+                let mut creditor_wallet_opt: Option<Wallet> = None;
+                let mut tx_hash_opt: Option<H256> = None;
+                let mut amount_wei_opt: Option<U256> = None;
+                let mut block_number_opt: Option<u64> = None;
+                let mut payer_opt: Option<Payer> = None;
+                map.keys().for_each(|k| {
+                    let v = map.get(k).expect("Disappeared");
+                    if let Value::Text(field_name) = k {
+                        match field_name.as_str() {
+                            "creditor_wallet" => creditor_wallet_opt = value_to_type::<Wallet>(v),
+                            "tx_hash" => tx_hash_opt = value_to_type::<H256>(v),
+                            "amount_wei" => amount_wei_opt = value_to_type::<U256>(v),
+                            "block_number" => block_number_opt = value_to_type::<u64>(v),
+                            "payer" => payer_opt = value_to_type::<Payer>(v),
+                            _ => (),
+                        }
+                    }
+                });
+                let mut missing_fields: Vec<&str> = vec![];
+                fn check_field<'a, T>(
+                    missing_fields: &mut Vec<&'a str>,
+                    name: &'a str,
+                    field: &Option<T>,
+                ) {
+                    if field.is_none() {
+                        missing_fields.push(name)
+                    }
+                }
+                check_field(&mut missing_fields, "creditor_wallet", &creditor_wallet_opt);
+                check_field(&mut missing_fields, "tx_hash", &tx_hash_opt);
+                check_field(&mut missing_fields, "amount_wei", &amount_wei_opt);
+                check_field(&mut missing_fields, "block_number", &block_number_opt);
+                check_field(&mut missing_fields, "payer", &payer_opt);
+                if !missing_fields.is_empty() {
+                    unimplemented!("{:?}", missing_fields.clone())
+                }
+                Ok(PaymentNotice_0v1 {
+                    creditor_wallet: creditor_wallet_opt.expect("creditor_wallet disappeared"),
+                    tx_hash: tx_hash_opt.expect("tx_hash disappeared"),
+                    amount_wei: amount_wei_opt.expect("amount_wei disappeared"),
+                    block_number: block_number_opt.expect("block_number disappeared"),
+                    payer: payer_opt.expect("payer disappeared"),
+                })
+            }
+            _ => Err(StepError::SemanticError(format!(
+                "Expected Value::Map; found {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::test_utils::{make_paying_wallet, make_wallet};
+    use masq_lib::data_version::DataVersion;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[test]
+    fn can_migrate_from_the_future() {
+        #[derive(Serialize, Deserialize)]
+        struct ExampleFuturePN {
+            pub creditor_wallet: Wallet,
+            pub tx_hash: H256,
+            pub amount_wei: U256,
+            pub block_number: u64,
+            pub payer: Payer,
+            pub another_field: String,
+            pub yet_another_field: u64,
+        }
+        let expected_pn = PaymentNotice_0v1::new(
+            &make_paying_wallet(b"debtor"),
+            &make_wallet("creditor"),
+            make_tx_hash(789),
+            4_567_000_000_000,
+            1_234,
+        )
+        .unwrap();
+        let future_pn = ExampleFuturePN {
+            creditor_wallet: expected_pn.creditor_wallet.clone(),
+            tx_hash: expected_pn.tx_hash,
+            amount_wei: expected_pn.amount_wei,
+            block_number: expected_pn.block_number,
+            payer: expected_pn.payer.clone(),
+            another_field: "These are the times that try men's souls".to_string(),
+            yet_another_field: 1234567890,
+        };
+        let future_migrations = Migrations::new(DataVersion::new(4095, 4095));
+        let serialized =
+            serde_cbor::ser::to_vec(&VersionedData::new(&future_migrations, &future_pn)).unwrap();
+        let future_vd =
+            serde_cbor::de::from_slice::<VersionedData<PaymentNotice_0v1>>(&serialized).unwrap();
+
+        let actual_pn = PaymentNotice_0v1::try_from(future_vd).unwrap();
+
+        assert_eq!(actual_pn, expected_pn);
+        assert_eq!(actual_pn.is_signed_by_payer(), true);
+    }
+
+    #[test]
+    fn cannot_migrate_from_value_other_than_map() {
+        let value = Value::Bool(true);
+
+        let result = PaymentNotice_0v1::try_from(&value);
+
+        assert_eq!(
+            result,
+            Err(StepError::SemanticError(
+                "Expected Value::Map; found Bool(true)".to_string()
+            ))
+        )
+    }
+}
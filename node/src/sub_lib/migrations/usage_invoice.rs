@@ -0,0 +1,192 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::sub_lib::accountant::UsageInvoice_0v1;
+use crate::sub_lib::cryptde::CryptData;
+use crate::sub_lib::migrations::utils::value_to_type;
+use crate::sub_lib::versioned_data::Migrations;
+use crate::sub_lib::versioned_data::{MigrationError, StepError, VersionedData};
+use crate::sub_lib::wallet::Wallet;
+use lazy_static::lazy_static;
+use serde_cbor::Value;
+use std::convert::TryFrom;
+use web3::types::U256;
+
+lazy_static! {
+    pub static ref MIGRATIONS: Migrations = {
+        let current_version = masq_lib::constants::USAGE_INVOICE_CURRENT_VERSION;
+        let mut migrations = Migrations::new(current_version);
+
+        migrate_value!(dv!(0, 1), UsageInvoice_0v1, UsageInvoiceMF_0v1, {|value: serde_cbor::Value| {
+            UsageInvoice_0v1::try_from (&value)
+        }});
+        migrations.add_step (masq_lib::data_version::FUTURE_VERSION, dv!(0, 1), Box::new (UsageInvoiceMF_0v1{}));
+
+        // add more steps here
+
+        migrations
+    };
+}
+
+impl From<UsageInvoice_0v1> for VersionedData<UsageInvoice_0v1> {
+    fn from(data: UsageInvoice_0v1) -> Self {
+        VersionedData::new(&MIGRATIONS, &data)
+    }
+}
+
+impl TryFrom<VersionedData<UsageInvoice_0v1>> for UsageInvoice_0v1 {
+    type Error = MigrationError;
+
+    fn try_from(vd: VersionedData<UsageInvoice_0v1>) -> Result<Self, Self::Error> {
+        vd.extract(&MIGRATIONS)
+    }
+}
+
+impl TryFrom<&Value> for UsageInvoice_0v1 {
+    type Error = StepError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => {
+                let mut creditor_wallet_opt: Option<Wallet> = None;
+                let mut debtor_wallet_opt: Option<Wallet> = None;
+                let mut period_start_sec_opt: Option<u64> = None;
+                let mut period_end_sec_opt: Option<u64> = None;
+                let mut routing_services_opt: Option<u64> = None;
+                let mut exit_services_opt: Option<u64> = None;
+                let mut amount_wei_opt: Option<U256> = None;
+                let mut signature_opt: Option<CryptData> = None;
+                map.keys().for_each(|k| {
+                    let v = map.get(k).expect("Disappeared");
+                    if let Value::Text(field_name) = k {
+                        match field_name.as_str() {
+                            "creditor_wallet" => creditor_wallet_opt = value_to_type::<Wallet>(v),
+                            "debtor_wallet" => debtor_wallet_opt = value_to_type::<Wallet>(v),
+                            "period_start_sec" => period_start_sec_opt = value_to_type::<u64>(v),
+                            "period_end_sec" => period_end_sec_opt = value_to_type::<u64>(v),
+                            "routing_services" => routing_services_opt = value_to_type::<u64>(v),
+                            "exit_services" => exit_services_opt = value_to_type::<u64>(v),
+                            "amount_wei" => amount_wei_opt = value_to_type::<U256>(v),
+                            "signature" => signature_opt = value_to_type::<CryptData>(v),
+                            _ => (),
+                        }
+                    }
+                });
+                let mut missing_fields: Vec<&str> = vec![];
+                fn check_field<'a, T>(
+                    missing_fields: &mut Vec<&'a str>,
+                    name: &'a str,
+                    field: &Option<T>,
+                ) {
+                    if field.is_none() {
+                        missing_fields.push(name)
+                    }
+                }
+                check_field(&mut missing_fields, "creditor_wallet", &creditor_wallet_opt);
+                check_field(&mut missing_fields, "debtor_wallet", &debtor_wallet_opt);
+                check_field(
+                    &mut missing_fields,
+                    "period_start_sec",
+                    &period_start_sec_opt,
+                );
+                check_field(&mut missing_fields, "period_end_sec", &period_end_sec_opt);
+                check_field(
+                    &mut missing_fields,
+                    "routing_services",
+                    &routing_services_opt,
+                );
+                check_field(&mut missing_fields, "exit_services", &exit_services_opt);
+                check_field(&mut missing_fields, "amount_wei", &amount_wei_opt);
+                check_field(&mut missing_fields, "signature", &signature_opt);
+                if !missing_fields.is_empty() {
+                    unimplemented!("{:?}", missing_fields.clone())
+                }
+                Ok(UsageInvoice_0v1 {
+                    creditor_wallet: creditor_wallet_opt.expect("creditor_wallet disappeared"),
+                    debtor_wallet: debtor_wallet_opt.expect("debtor_wallet disappeared"),
+                    period_start_sec: period_start_sec_opt.expect("period_start_sec disappeared"),
+                    period_end_sec: period_end_sec_opt.expect("period_end_sec disappeared"),
+                    routing_services: routing_services_opt.expect("routing_services disappeared"),
+                    exit_services: exit_services_opt.expect("exit_services disappeared"),
+                    amount_wei: amount_wei_opt.expect("amount_wei disappeared"),
+                    signature: signature_opt.expect("signature disappeared"),
+                })
+            }
+            _ => Err(StepError::SemanticError(format!(
+                "Expected Value::Map; found {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{main_cryptde, make_wallet};
+    use masq_lib::data_version::DataVersion;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[test]
+    fn can_migrate_from_the_future() {
+        #[derive(Serialize, Deserialize)]
+        struct ExampleFutureUI {
+            pub creditor_wallet: Wallet,
+            pub debtor_wallet: Wallet,
+            pub period_start_sec: u64,
+            pub period_end_sec: u64,
+            pub routing_services: u64,
+            pub exit_services: u64,
+            pub amount_wei: U256,
+            pub signature: CryptData,
+            pub another_field: String,
+            pub yet_another_field: u64,
+        }
+        let cryptde = main_cryptde();
+        let mut expected_ui = UsageInvoice_0v1::new(
+            &make_wallet("creditor"),
+            &make_wallet("debtor"),
+            1_000,
+            2_000,
+            12,
+            3,
+            4_567_000_000_000,
+        );
+        expected_ui.sign(cryptde).unwrap();
+        let future_ui = ExampleFutureUI {
+            creditor_wallet: expected_ui.creditor_wallet.clone(),
+            debtor_wallet: expected_ui.debtor_wallet.clone(),
+            period_start_sec: expected_ui.period_start_sec,
+            period_end_sec: expected_ui.period_end_sec,
+            routing_services: expected_ui.routing_services,
+            exit_services: expected_ui.exit_services,
+            amount_wei: expected_ui.amount_wei,
+            signature: expected_ui.signature.clone(),
+            another_field: "These are the times that try men's souls".to_string(),
+            yet_another_field: 1234567890,
+        };
+        let future_migrations = Migrations::new(DataVersion::new(4095, 4095));
+        let serialized =
+            serde_cbor::ser::to_vec(&VersionedData::new(&future_migrations, &future_ui)).unwrap();
+        let future_vd =
+            serde_cbor::de::from_slice::<VersionedData<UsageInvoice_0v1>>(&serialized).unwrap();
+
+        let actual_ui = UsageInvoice_0v1::try_from(future_vd).unwrap();
+
+        assert_eq!(actual_ui, expected_ui);
+        assert_eq!(actual_ui.is_signed_by(cryptde, cryptde.public_key()), true);
+    }
+
+    #[test]
+    fn cannot_migrate_from_value_other_than_map() {
+        let value = Value::Bool(true);
+
+        let result = UsageInvoice_0v1::try_from(&value);
+
+        assert_eq!(
+            result,
+            Err(StepError::SemanticError(
+                "Expected Value::Map; found Bool(true)".to_string()
+            ))
+        )
+    }
+}
@@ -4,6 +4,9 @@ use crate::neighborhood::gossip::Gossip_0v1;
 use crate::neighborhood::node_record::NodeRecord;
 use crate::neighborhood::overall_connection_status::ConnectionProgress;
 use crate::neighborhood::{Neighborhood, UserExitPreferences};
+use crate::sub_lib::accountant::{
+    PaymentNotice_0v1, PaymentThresholds, ScanIntervals, UsageInvoice_0v1,
+};
 use crate::sub_lib::cryptde::{CryptDE, PublicKey};
 use crate::sub_lib::cryptde_real::CryptDEReal;
 use crate::sub_lib::dispatcher::{Component, StreamShutdownMsg};
@@ -33,6 +36,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 const ASK_ABOUT_GOSSIP_INTERVAL: Duration = Duration::from_secs(10);
+const SHUTDOWN_BARRIER_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub const DEFAULT_RATE_PACK: RatePack = RatePack {
     routing_byte_rate: 172_300_000,
@@ -400,6 +404,46 @@ impl Display for Hops {
     }
 }
 
+// What to do about a Node claiming to be in another country than its IP address is in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CountryClaimCheck {
+    Off,
+    Log,
+    Enforce,
+}
+
+impl Default for CountryClaimCheck {
+    fn default() -> Self {
+        CountryClaimCheck::Log
+    }
+}
+
+impl FromStr for CountryClaimCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(CountryClaimCheck::Off),
+            "log" => Ok(CountryClaimCheck::Log),
+            "enforce" => Ok(CountryClaimCheck::Enforce),
+            other => Err(format!(
+                "'{}' is not a country claim check; use off, log or enforce",
+                other
+            )),
+        }
+    }
+}
+
+impl Display for CountryClaimCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CountryClaimCheck::Off => write!(f, "off"),
+            CountryClaimCheck::Log => write!(f, "log"),
+            CountryClaimCheck::Enforce => write!(f, "enforce"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NeighborhoodConfig {
     pub mode: NeighborhoodMode,
@@ -419,6 +463,13 @@ pub struct NeighborhoodSubs {
     pub update_node_record_metadata: Recipient<UpdateNodeRecordMetadataMessage>,
     pub from_hopper: Recipient<ExpiredCoresPackage<Gossip_0v1>>,
     pub gossip_failure: Recipient<ExpiredCoresPackage<GossipFailure_0v1>>,
+    pub payment_notice: Recipient<ExpiredCoresPackage<PaymentNotice_0v1>>,
+    pub send_payment_notice: Recipient<SendPaymentNoticeMessage>,
+    pub usage_invoice: Recipient<ExpiredCoresPackage<UsageInvoice_0v1>>,
+    pub send_usage_invoice: Recipient<SendUsageInvoiceMessage>,
+    pub exit_options_query: Recipient<ExpiredCoresPackage<ExitOptionsQuery_0v1>>,
+    pub exit_options_report: Recipient<ExpiredCoresPackage<ExitOptionsReport_0v1>>,
+    pub shutdown_barrier: Recipient<ShutdownBarrierMessage>,
     pub dispatcher_node_query: Recipient<DispatcherNodeQueryMessage>,
     pub remove_neighbor: Recipient<RemoveNeighborMessage>,
     pub config_change_msg_sub: Recipient<ConfigChangeMsg>,
@@ -519,6 +570,26 @@ pub struct RemoveNeighborMessage {
     pub public_key: PublicKey,
 }
 
+// Asks the Neighborhood to deliver a payment notice to the Node earning into its creditor wallet
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct SendPaymentNoticeMessage {
+    pub notice: PaymentNotice_0v1,
+}
+
+// Asks the Neighborhood to sign a usage invoice and deliver it to the Node paying from its debtor
+// wallet
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct SendUsageInvoiceMessage {
+    pub invoice: UsageInvoice_0v1,
+}
+
+// Tells the Neighborhood that every transaction submitted before a shutdown order from the
+// client has been recorded, so that the Node can go down without losing track of any of them
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct ShutdownBarrierMessage {
+    pub client_id: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ConnectionProgressEvent {
     TcpConnectionSuccessful,
@@ -567,6 +638,8 @@ pub struct WalletPair {
 pub enum ConfigChange {
     UpdateMinHops(Hops),
     UpdatePassword(String),
+    UpdatePaymentThresholds(PaymentThresholds),
+    UpdateScanIntervals(ScanIntervals),
     UpdateWallets(WalletPair),
 }
 
@@ -593,6 +666,22 @@ impl fmt::Display for GossipFailure_0v1 {
     }
 }
 
+// Asks a neighbor where it offers exit. The time of asking, by the asker's clock, comes back in
+// the report, so that the round trip can be measured.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct ExitOptionsQuery_0v1 {
+    pub sent_at_ms: u64,
+}
+
+// The countries a neighbor can exit in: its own, and those of the exit Nodes it knows of
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct ExitOptionsReport_0v1 {
+    pub exit_country_codes: Vec<String>,
+    pub query_sent_at_ms: u64,
+}
+
 // This metadata is only passed from Neighborhood to GossipHandler
 pub struct NeighborhoodMetadata {
     pub connection_progress_peers: Vec<IpAddr>,
@@ -605,6 +694,9 @@ pub struct NeighborhoodTools {
     pub notify_later_ask_about_gossip:
         Box<dyn NotifyLaterHandle<AskAboutDebutGossipMessage, Neighborhood>>,
     pub ask_about_gossip_interval: Duration,
+    pub notify_later_shutdown_barrier:
+        Box<dyn NotifyLaterHandle<ShutdownBarrierMessage, Neighborhood>>,
+    pub shutdown_barrier_timeout: Duration,
 }
 
 impl Default for NeighborhoodTools {
@@ -612,6 +704,8 @@ impl Default for NeighborhoodTools {
         Self {
             notify_later_ask_about_gossip: Box::new(NotifyLaterHandleReal::new()),
             ask_about_gossip_interval: ASK_ABOUT_GOSSIP_INTERVAL,
+            notify_later_shutdown_barrier: Box::new(NotifyLaterHandleReal::new()),
+            shutdown_barrier_timeout: SHUTDOWN_BARRIER_TIMEOUT,
         }
     }
 }
@@ -673,6 +767,13 @@ mod tests {
             update_node_record_metadata: recipient!(recorder, UpdateNodeRecordMetadataMessage),
             from_hopper: recipient!(recorder, ExpiredCoresPackage<Gossip_0v1>),
             gossip_failure: recipient!(recorder, ExpiredCoresPackage<GossipFailure_0v1>),
+            payment_notice: recipient!(recorder, ExpiredCoresPackage<PaymentNotice_0v1>),
+            send_payment_notice: recipient!(recorder, SendPaymentNoticeMessage),
+            usage_invoice: recipient!(recorder, ExpiredCoresPackage<UsageInvoice_0v1>),
+            send_usage_invoice: recipient!(recorder, SendUsageInvoiceMessage),
+            exit_options_query: recipient!(recorder, ExpiredCoresPackage<ExitOptionsQuery_0v1>),
+            exit_options_report: recipient!(recorder, ExpiredCoresPackage<ExitOptionsReport_0v1>),
+            shutdown_barrier: recipient!(recorder, ShutdownBarrierMessage),
             dispatcher_node_query: recipient!(recorder, DispatcherNodeQueryMessage),
             remove_neighbor: recipient!(recorder, RemoveNeighborMessage),
             config_change_msg_sub: recipient!(recorder, ConfigChangeMsg),
@@ -1266,6 +1367,12 @@ mod tests {
             .downcast_ref::<NotifyLaterHandleReal<AskAboutDebutGossipMessage>>()
             .unwrap();
         assert_eq!(subject.ask_about_gossip_interval, Duration::from_secs(10));
+        subject
+            .notify_later_shutdown_barrier
+            .as_any()
+            .downcast_ref::<NotifyLaterHandleReal<ShutdownBarrierMessage>>()
+            .unwrap();
+        assert_eq!(subject.shutdown_barrier_timeout, Duration::from_secs(60));
     }
 
     #[test]
@@ -1297,4 +1404,34 @@ mod tests {
         assert_eq!(Hops::FiveHops.to_string(), "5");
         assert_eq!(Hops::SixHops.to_string(), "6");
     }
+
+    #[test]
+    fn country_claim_check_defaults_to_log() {
+        assert_eq!(CountryClaimCheck::default(), CountryClaimCheck::Log)
+    }
+
+    #[test]
+    fn country_claim_check_round_trips_through_strings() {
+        vec![
+            CountryClaimCheck::Off,
+            CountryClaimCheck::Log,
+            CountryClaimCheck::Enforce,
+        ]
+        .into_iter()
+        .for_each(|check| assert_eq!(CountryClaimCheck::from_str(&check.to_string()), Ok(check)));
+        assert_eq!(
+            CountryClaimCheck::from_str("Enforce"),
+            Ok(CountryClaimCheck::Enforce)
+        );
+    }
+
+    #[test]
+    fn country_claim_check_rejects_unknown_value() {
+        let result = CountryClaimCheck::from_str("ban");
+
+        assert_eq!(
+            result,
+            Err("'ban' is not a country claim check; use off, log or enforce".to_string())
+        )
+    }
 }
@@ -5,6 +5,7 @@ use crate::sub_lib::cryptde;
 use crate::sub_lib::cryptde::PublicKey as CryptdePublicKey;
 use ethereum_types::H160;
 use ethsign::{PublicKey, Signature};
+use ethsign_crypto::Keccak256;
 use rusqlite::types::{FromSql, FromSqlError, ToSqlOutput, Value, ValueRef};
 use rusqlite::ToSql;
 use rustc_hex::ToHex;
@@ -21,6 +22,7 @@ use web3::types::{Address, H256};
 #[derive(Debug, PartialEq, Eq)]
 pub enum WalletError {
     InvalidAddress,
+    InvalidChecksum(String),
     Signature(String),
 }
 
@@ -30,6 +32,12 @@ impl Display for WalletError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             WalletError::InvalidAddress => write!(f, "Invalid address"),
+            WalletError::InvalidChecksum(address) => write!(
+                f,
+                "Address {} fails its EIP-55 checksum; check it for typos, or give it all in \
+                 lower case",
+                address
+            ),
             WalletError::Signature(msg) => write!(f, "{}", msg),
         }
     }
@@ -142,6 +150,25 @@ impl Wallet {
         format!("{:#x}", self.address())
     }
 
+    // The mixed case form of EIP-55, for people to copy: the Node itself keeps to lower case
+    pub fn checksummed_address(&self) -> String {
+        let lower_case = format!("{:x}", self.address());
+        let hash = lower_case.as_bytes().keccak256();
+        let checksummed = lower_case
+            .chars()
+            .enumerate()
+            .map(|(index, character)| {
+                let nibble = (hash[index / 2] >> (4 * (1 - index % 2))) & 0x0f;
+                if nibble >= 8 {
+                    character.to_ascii_uppercase()
+                } else {
+                    character
+                }
+            })
+            .collect::<String>();
+        format!("0x{}", checksummed)
+    }
+
     pub fn sign(&self, msg: &dyn AsRef<[u8]>) -> Result<Signature, WalletError> {
         match self.kind {
             WalletKind::SecretKey(ref key_provider) => key_provider
@@ -204,13 +231,22 @@ impl Wallet {
 impl FromStr for Wallet {
     type Err = WalletError;
 
+    // An address all in lower or all in upper case carries no checksum; one in mixed case must
+    // have it right
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match serde_json::from_value::<Address>(json!(s)) {
-            Ok(address) => Ok(Self {
+        let wallet = match serde_json::from_value::<Address>(json!(s)) {
+            Ok(address) => Self {
                 kind: WalletKind::Address(address),
-            }),
-            Err(_) => Err(WalletError::InvalidAddress),
+            },
+            Err(_) => return Err(WalletError::InvalidAddress),
+        };
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        let mixed_case = digits.chars().any(|c| c.is_ascii_lowercase())
+            && digits.chars().any(|c| c.is_ascii_uppercase());
+        if mixed_case && wallet.checksummed_address() != s {
+            return Err(WalletError::InvalidChecksum(s.to_string()));
         }
+        Ok(wallet)
     }
 }
 
@@ -262,10 +298,12 @@ impl ToSql for Wallet {
     }
 }
 
+// The Node wrote the rows itself, so there is no checksum of theirs worth checking
 impl FromSql for Wallet {
     fn column_result(value: ValueRef) -> Result<Self, FromSqlError> {
         match value.as_str() {
-            Ok(address) => Wallet::from_str(address).map_err(|e| FromSqlError::Other(Box::new(e))),
+            Ok(address) => Wallet::from_str(&address.to_lowercase())
+                .map_err(|e| FromSqlError::Other(Box::new(e))),
             Err(e) => Err(e),
         }
     }
@@ -379,7 +417,11 @@ impl<'de> serde::Deserialize<'de> for Wallet {
                             }
                             possible_address = match &self.human_readable {
                                 true => {
-                                    Some(serde::de::MapAccess::next_value::<Address>(&mut map)?)
+                                    let address =
+                                        serde::de::MapAccess::next_value::<String>(&mut map)?;
+                                    let wallet = Wallet::from_str(&address)
+                                        .map_err(<A::Error as de::Error>::custom)?;
+                                    Some(wallet.address())
                                 }
                                 false => {
                                     let bytes =
@@ -467,6 +509,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checksummed_address_follows_eip_55() {
+        let checksummed_addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+
+        let result = checksummed_addresses
+            .iter()
+            .map(|address| Wallet::from_str(&address.to_lowercase()).unwrap())
+            .map(|wallet| wallet.checksummed_address())
+            .collect::<Vec<String>>();
+
+        assert_eq!(result, checksummed_addresses)
+    }
+
+    #[test]
+    fn from_str_accepts_addresses_in_one_case_or_correctly_checksummed() {
+        let addresses = [
+            "0xd1220a0cf47c7b9be7a2e6ba89f429762e7b9adb",
+            "0xD1220A0CF47C7B9BE7A2E6BA89F429762E7B9ADB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+
+        let result = addresses
+            .iter()
+            .map(|address| Wallet::from_str(address).unwrap().to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            result,
+            vec!["0xd1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".to_string(); 3]
+        )
+    }
+
+    #[test]
+    fn from_str_rejects_an_address_with_a_wrong_checksum() {
+        let address = "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9adB";
+
+        let result = Wallet::from_str(address);
+
+        assert_eq!(
+            result,
+            Err(WalletError::InvalidChecksum(address.to_string()))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Address 0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9adB fails its EIP-55 checksum; check \
+             it for typos, or give it all in lower case"
+        )
+    }
+
+    #[test]
+    fn json_deserialization_rejects_an_address_with_a_wrong_checksum() {
+        let json = r#"{"address":"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9adB"}"#;
+
+        let result = serde_json::from_str::<Wallet>(json);
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("fails its EIP-55 checksum"), "{}", error)
+    }
+
     #[test]
     fn can_create_from_an_h256() {
         let result = Wallet::from(
@@ -665,6 +771,27 @@ mod tests {
         assert_eq!(result, vec![wallet]);
     }
 
+    #[test]
+    fn rusqlite_reads_addresses_stored_in_any_case() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(
+            "CREATE TABLE foo(wallet TEXT); \
+             INSERT INTO foo(wallet) VALUES ('0xCAFEdeadBEEFbabefacecafedeadbeefbabeface')",
+        )
+        .unwrap();
+
+        let result = db
+            .query_row("SELECT wallet FROM foo", [], |row| {
+                row.get::<usize, Wallet>(0)
+            })
+            .unwrap();
+
+        assert_eq!(
+            result,
+            Wallet::from_str("0xcafedeadbeefbabefacecafedeadbeefbabeface").unwrap()
+        )
+    }
+
     #[test]
     fn can_convert_to_keypair_if_came_from_keypair() {
         let secret_key_text = "0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc";
@@ -0,0 +1,141 @@
+// Copyright (c) 2024, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use masq_lib::constants::WEIS_IN_GWEI;
+use std::fmt::{Display, Formatter};
+use thousands::Separable;
+use web3::types::U256;
+
+// An amount in the smallest unit of a currency on an EVM chain, whether the native coin that
+// pays the transaction fees or the MASQ token. A bare number could be gwei just as well, or
+// a count of gas units; this can't
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wei(u128);
+
+impl Wei {
+    pub const ZERO: Wei = Wei(0);
+    pub const MAX: Wei = Wei(u128::MAX);
+
+    pub const fn new(amount: u128) -> Self {
+        Self(amount)
+    }
+
+    pub fn from_gwei(gwei: u64) -> Self {
+        Self(gwei as u128 * WEIS_IN_GWEI as u128)
+    }
+
+    pub fn try_from_u256(amount: U256) -> Option<Self> {
+        (amount <= U256::from(u128::MAX)).then(|| Self(amount.as_u128()))
+    }
+
+    pub fn saturating_from_u256(amount: U256) -> Self {
+        Self::try_from_u256(amount).unwrap_or(Self::MAX)
+    }
+
+    pub const fn as_u128(self) -> u128 {
+        self.0
+    }
+
+    pub fn as_u256(self) -> U256 {
+        U256::from(self.0)
+    }
+
+    // Whole gwei, the rest is cut off
+    pub fn as_gwei(self) -> u128 {
+        self.0 / WEIS_IN_GWEI as u128
+    }
+
+    pub fn checked_add(self, other: Wei) -> Option<Wei> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn saturating_add(self, other: Wei) -> Wei {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    pub fn checked_sub(self, other: Wei) -> Option<Wei> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn saturating_sub(self, other: Wei) -> Wei {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    // The factor is a plain number, such as units of gas for a price per unit
+    pub fn checked_mul(self, factor: u128) -> Option<Wei> {
+        self.0.checked_mul(factor).map(Self)
+    }
+
+    pub fn saturating_mul(self, factor: u128) -> Wei {
+        Self(self.0.saturating_mul(factor))
+    }
+
+    pub fn checked_div(self, divisor: u128) -> Option<Wei> {
+        self.0.checked_div(divisor).map(Self)
+    }
+}
+
+impl Display for Wei {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} wei", self.0.separate_with_commas())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(Wei::ZERO, Wei::new(0));
+        assert_eq!(Wei::MAX, Wei::new(u128::MAX));
+    }
+
+    #[test]
+    fn wei_converts_from_and_to_gwei() {
+        let subject = Wei::from_gwei(1_234);
+
+        assert_eq!(subject, Wei::new(1_234_000_000_000));
+        assert_eq!(Wei::new(1_234_999_999_999).as_gwei(), 1_234);
+    }
+
+    #[test]
+    fn wei_converts_from_and_to_u256() {
+        assert_eq!(
+            Wei::try_from_u256(U256::from(u128::MAX)),
+            Some(Wei::new(u128::MAX))
+        );
+        assert_eq!(Wei::try_from_u256(U256::from(u128::MAX) + 1), None);
+        assert_eq!(
+            Wei::saturating_from_u256(U256::from(u128::MAX) + 1),
+            Wei::MAX
+        );
+        assert_eq!(Wei::new(456).as_u256(), U256::from(456))
+    }
+
+    #[test]
+    fn checked_arithmetic_refuses_to_overflow() {
+        assert_eq!(Wei::new(5).checked_add(Wei::new(6)), Some(Wei::new(11)));
+        assert_eq!(Wei::MAX.checked_add(Wei::new(1)), None);
+        assert_eq!(Wei::new(6).checked_sub(Wei::new(5)), Some(Wei::new(1)));
+        assert_eq!(Wei::new(5).checked_sub(Wei::new(6)), None);
+        assert_eq!(Wei::new(5).checked_mul(6), Some(Wei::new(30)));
+        assert_eq!(Wei::MAX.checked_mul(2), None);
+        assert_eq!(Wei::new(30).checked_div(6), Some(Wei::new(5)));
+        assert_eq!(Wei::new(30).checked_div(0), None)
+    }
+
+    #[test]
+    fn saturating_arithmetic_stops_at_the_limits() {
+        assert_eq!(Wei::new(5).saturating_add(Wei::new(6)), Wei::new(11));
+        assert_eq!(Wei::MAX.saturating_add(Wei::new(1)), Wei::MAX);
+        assert_eq!(Wei::new(5).saturating_sub(Wei::new(6)), Wei::ZERO);
+        assert_eq!(Wei::new(5).saturating_mul(6), Wei::new(30));
+        assert_eq!(Wei::MAX.saturating_mul(2), Wei::MAX)
+    }
+
+    #[test]
+    fn wei_displays_with_separators_and_unit() {
+        assert_eq!(Wei::new(1_234_567_890).to_string(), "1,234,567,890 wei");
+        assert_eq!(Wei::ZERO.to_string(), "0 wei")
+    }
+}
@@ -41,6 +41,9 @@ pub struct PersistentConfigurationMock {
         RefCell<Vec<Result<Option<String>, PersistentConfigError>>>,
     earning_wallet_results: RefCell<Vec<Result<Option<Wallet>, PersistentConfigError>>>,
     earning_wallet_address_results: RefCell<Vec<Result<Option<String>, PersistentConfigError>>>,
+    earning_wallet_rotation_results: RefCell<Vec<Result<Vec<Wallet>, PersistentConfigError>>>,
+    set_earning_wallet_rotation_params: Arc<Mutex<Vec<Vec<Wallet>>>>,
+    set_earning_wallet_rotation_results: RefCell<Vec<Result<(), PersistentConfigError>>>,
     set_wallet_info_params: Arc<Mutex<Vec<(String, String, String)>>>,
     set_wallet_info_results: RefCell<Vec<Result<(), PersistentConfigError>>>,
     mapping_protocol_results: RefCell<Vec<Result<Option<AutomapProtocol>, PersistentConfigError>>>,
@@ -162,6 +165,23 @@ impl PersistentConfiguration for PersistentConfigurationMock {
         Self::result_from(&self.earning_wallet_address_results)
     }
 
+    fn earning_wallet_rotation(&self) -> Result<Vec<Wallet>, PersistentConfigError> {
+        Self::result_from(&self.earning_wallet_rotation_results)
+    }
+
+    fn set_earning_wallet_rotation(
+        &mut self,
+        wallets: &[Wallet],
+    ) -> Result<(), PersistentConfigError> {
+        self.set_earning_wallet_rotation_params
+            .lock()
+            .unwrap()
+            .push(wallets.to_vec());
+        self.set_earning_wallet_rotation_results
+            .borrow_mut()
+            .remove(0)
+    }
+
     fn gas_price(&self) -> Result<u64, PersistentConfigError> {
         Self::result_from(&self.gas_price_results)
     }
@@ -541,6 +561,34 @@ impl PersistentConfigurationMock {
         self
     }
 
+    pub fn earning_wallet_rotation_result(
+        self,
+        result: Result<Vec<Wallet>, PersistentConfigError>,
+    ) -> Self {
+        self.earning_wallet_rotation_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn set_earning_wallet_rotation_params(
+        mut self,
+        params: &Arc<Mutex<Vec<Vec<Wallet>>>>,
+    ) -> Self {
+        self.set_earning_wallet_rotation_params = params.clone();
+        self
+    }
+
+    pub fn set_earning_wallet_rotation_result(
+        self,
+        result: Result<(), PersistentConfigError>,
+    ) -> Self {
+        self.set_earning_wallet_rotation_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn start_block_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
         self.start_block_params = params.clone();
         self
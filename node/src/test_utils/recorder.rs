@@ -9,17 +9,28 @@ use crate::accountant::{
     ScanForPendingPayables, ScanForReceivables, SentPayables,
 };
 use crate::blockchain::blockchain_bridge::PendingPayableFingerprintSeeds;
+use crate::blockchain::blockchain_bridge::PendingPayableReplacement;
 use crate::blockchain::blockchain_bridge::RetrieveTransactions;
 use crate::daemon::crash_notification::CrashNotification;
 use crate::daemon::DaemonBindMessage;
 use crate::neighborhood::gossip::Gossip_0v1;
 use crate::stream_messages::{AddStreamMsg, PoolBindMessage, RemoveStreamMsg};
 use crate::sub_lib::accountant::AccountantSubs;
+use crate::sub_lib::accountant::PaymentNotice_0v1;
+use crate::sub_lib::accountant::ReportConfirmedNonce;
+use crate::sub_lib::accountant::ReportCreditorReachabilityMessage;
+use crate::sub_lib::accountant::ReportCreditorThresholdsMessage;
+use crate::sub_lib::accountant::ReportExitFailoverMessage;
 use crate::sub_lib::accountant::ReportExitServiceProvidedMessage;
+use crate::sub_lib::accountant::ReportPaymentNoticeMessage;
 use crate::sub_lib::accountant::ReportRoutingServiceProvidedMessage;
 use crate::sub_lib::accountant::ReportServicesConsumedMessage;
+use crate::sub_lib::accountant::ReportUsageInvoiceMessage;
+use crate::sub_lib::accountant::UsageInvoice_0v1;
 use crate::sub_lib::blockchain_bridge::BlockchainBridgeSubs;
 use crate::sub_lib::blockchain_bridge::OutboundPaymentsInstructions;
+use crate::sub_lib::blockchain_bridge::ReplacePendingPayable;
+use crate::sub_lib::blockchain_bridge::VoidAbandonedTransaction;
 use crate::sub_lib::dispatcher::InboundClientData;
 use crate::sub_lib::dispatcher::{DispatcherSubs, StreamShutdownMsg};
 use crate::sub_lib::hopper::IncipientCoresPackage;
@@ -27,6 +38,7 @@ use crate::sub_lib::hopper::{ExpiredCoresPackage, NoLookupIncipientCoresPackage}
 use crate::sub_lib::hopper::{HopperSubs, MessageType};
 use crate::sub_lib::neighborhood::NeighborhoodSubs;
 use crate::sub_lib::neighborhood::{ConfigChangeMsg, ConnectionProgressMessage};
+use crate::sub_lib::neighborhood::{ExitOptionsQuery_0v1, ExitOptionsReport_0v1};
 
 use crate::sub_lib::configurator::ConfiguratorSubs;
 use crate::sub_lib::neighborhood::NodeQueryResponseMetadata;
@@ -34,7 +46,10 @@ use crate::sub_lib::neighborhood::RemoveNeighborMessage;
 use crate::sub_lib::neighborhood::RouteQueryMessage;
 use crate::sub_lib::neighborhood::RouteQueryResponse;
 use crate::sub_lib::neighborhood::UpdateNodeRecordMetadataMessage;
-use crate::sub_lib::neighborhood::{DispatcherNodeQueryMessage, GossipFailure_0v1};
+use crate::sub_lib::neighborhood::{
+    DispatcherNodeQueryMessage, GossipFailure_0v1, SendPaymentNoticeMessage,
+    SendUsageInvoiceMessage, ShutdownBarrierMessage,
+};
 use crate::sub_lib::peer_actors::PeerActors;
 use crate::sub_lib::peer_actors::{BindMessage, NewPublicIp, StartMessage};
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, InboundServerData};
@@ -140,6 +155,13 @@ recorder_message_handler_t_m_p!(ExpiredCoresPackage<ClientResponsePayload_0v1>);
 recorder_message_handler_t_m_p!(ExpiredCoresPackage<DnsResolveFailure_0v1>);
 recorder_message_handler_t_m_p!(ExpiredCoresPackage<Gossip_0v1>);
 recorder_message_handler_t_m_p!(ExpiredCoresPackage<GossipFailure_0v1>);
+recorder_message_handler_t_m_p!(ExpiredCoresPackage<PaymentNotice_0v1>);
+recorder_message_handler_t_m_p!(ExpiredCoresPackage<ExitOptionsQuery_0v1>);
+recorder_message_handler_t_m_p!(ExpiredCoresPackage<ExitOptionsReport_0v1>);
+recorder_message_handler_t_m_p!(ExpiredCoresPackage<UsageInvoice_0v1>);
+recorder_message_handler_t_m_p!(SendPaymentNoticeMessage);
+recorder_message_handler_t_m_p!(SendUsageInvoiceMessage);
+recorder_message_handler_t_m_p!(ShutdownBarrierMessage);
 recorder_message_handler_t_m_p!(ExpiredCoresPackage<MessageType>);
 recorder_message_handler_t_m_p!(InboundClientData);
 recorder_message_handler_t_m_p!(InboundServerData);
@@ -150,11 +172,19 @@ recorder_message_handler_t_m_p!(NodeToUiMessage);
 recorder_message_handler_t_m_p!(NoLookupIncipientCoresPackage);
 recorder_message_handler_t_p!(OutboundPaymentsInstructions);
 recorder_message_handler_t_m_p!(PendingPayableFingerprintSeeds);
+recorder_message_handler_t_m_p!(PendingPayableReplacement);
 recorder_message_handler_t_m_p!(PoolBindMessage);
 recorder_message_handler_t_m_p!(QualifiedPayablesMessage);
 recorder_message_handler_t_m_p!(ReceivedPayments);
 recorder_message_handler_t_m_p!(RemoveNeighborMessage);
 recorder_message_handler_t_m_p!(RemoveStreamMsg);
+recorder_message_handler_t_m_p!(ReplacePendingPayable);
+recorder_message_handler_t_m_p!(ReportCreditorReachabilityMessage);
+recorder_message_handler_t_m_p!(ReportExitFailoverMessage);
+recorder_message_handler_t_m_p!(ReportPaymentNoticeMessage);
+recorder_message_handler_t_m_p!(ReportUsageInvoiceMessage);
+recorder_message_handler_t_m_p!(ReportConfirmedNonce);
+recorder_message_handler_t_m_p!(ReportCreditorThresholdsMessage);
 recorder_message_handler_t_m_p!(ReportExitServiceProvidedMessage);
 recorder_message_handler_t_m_p!(ReportRoutingServiceProvidedMessage);
 recorder_message_handler_t_m_p!(ReportServicesConsumedMessage);
@@ -170,6 +200,7 @@ recorder_message_handler_t_m_p!(StartMessage);
 recorder_message_handler_t_m_p!(StreamShutdownMsg);
 recorder_message_handler_t_m_p!(TransmitDataMsg);
 recorder_message_handler_t_m_p!(UpdateNodeRecordMetadataMessage);
+recorder_message_handler_t_m_p!(VoidAbandonedTransaction);
 
 impl<M> Handler<MessageScheduler<M>> for Recorder
 where
@@ -446,6 +477,13 @@ pub fn make_neighborhood_subs_from_recorder(addr: &Addr<Recorder>) -> Neighborho
         update_node_record_metadata: recipient!(addr, UpdateNodeRecordMetadataMessage),
         from_hopper: recipient!(addr, ExpiredCoresPackage<Gossip_0v1>),
         gossip_failure: recipient!(addr, ExpiredCoresPackage<GossipFailure_0v1>),
+        payment_notice: recipient!(addr, ExpiredCoresPackage<PaymentNotice_0v1>),
+        send_payment_notice: recipient!(addr, SendPaymentNoticeMessage),
+        usage_invoice: recipient!(addr, ExpiredCoresPackage<UsageInvoice_0v1>),
+        send_usage_invoice: recipient!(addr, SendUsageInvoiceMessage),
+        exit_options_query: recipient!(addr, ExpiredCoresPackage<ExitOptionsQuery_0v1>),
+        exit_options_report: recipient!(addr, ExpiredCoresPackage<ExitOptionsReport_0v1>),
+        shutdown_barrier: recipient!(addr, ShutdownBarrierMessage),
         dispatcher_node_query: recipient!(addr, DispatcherNodeQueryMessage),
         remove_neighbor: recipient!(addr, RemoveNeighborMessage),
         config_change_msg_sub: recipient!(addr, ConfigChangeMsg),
@@ -466,9 +504,17 @@ pub fn make_accountant_subs_from_recorder(addr: &Addr<Recorder>) -> AccountantSu
         report_payable_payments_setup: recipient!(addr, BlockchainAgentWithContextMessage),
         report_inbound_payments: recipient!(addr, ReceivedPayments),
         init_pending_payable_fingerprints: recipient!(addr, PendingPayableFingerprintSeeds),
+        report_pending_payable_replacement: recipient!(addr, PendingPayableReplacement),
         report_transaction_receipts: recipient!(addr, ReportTransactionReceipts),
         report_sent_payments: recipient!(addr, SentPayables),
         scan_errors: recipient!(addr, ScanError),
+        report_creditor_thresholds: recipient!(addr, ReportCreditorThresholdsMessage),
+        report_creditor_reachability: recipient!(addr, ReportCreditorReachabilityMessage),
+        report_exit_failover: recipient!(addr, ReportExitFailoverMessage),
+        report_payment_notice: recipient!(addr, ReportPaymentNoticeMessage),
+        report_usage_invoice: recipient!(addr, ReportUsageInvoiceMessage),
+        report_confirmed_nonce: recipient!(addr, ReportConfirmedNonce),
+        shutdown_barrier: recipient!(addr, ShutdownBarrierMessage),
         ui_message_sub: recipient!(addr, NodeFromUiMessage),
     }
 }
@@ -489,6 +535,9 @@ pub fn make_blockchain_bridge_subs_from_recorder(addr: &Addr<Recorder>) -> Block
         retrieve_transactions: recipient!(addr, RetrieveTransactions),
         ui_sub: recipient!(addr, NodeFromUiMessage),
         request_transaction_receipts: recipient!(addr, RequestTransactionReceipts),
+        replace_pending_payable: recipient!(addr, ReplacePendingPayable),
+        void_abandoned_transaction: recipient!(addr, VoidAbandonedTransaction),
+        config_change_msg_sub: recipient!(addr, ConfigChangeMsg),
     }
 }
 
@@ -229,11 +229,13 @@ mod tests {
             scan_type: ScanType::Payables,
             response_skeleton_opt: None,
             msg: "booga".to_string(),
+            retryable: false,
         };
         let good_msg = ScanError {
             scan_type: ScanType::PendingPayables,
             response_skeleton_opt: None,
             msg: "blah".to_string(),
+            retryable: false,
         };
 
         assert_eq!(